@@ -0,0 +1,978 @@
+//! Public API surface.
+//!
+//! Lists what each file exports, with signatures:
+//! - Rust `pub` items (`pub(crate)` and narrower don't count), inherent
+//!   `impl` methods and trait methods
+//! - Go identifiers starting with a capital letter
+//! - Python names without a leading underscore (dunder methods count)
+//! - JavaScript and TypeScript `export`s, and the methods of exported
+//!   classes that aren't `#private`, `private` or `protected`
+//! - Java and C# `public` types and members, and interface members
+//!
+//! With git history the surface is compared with a baseline revision:
+//! `[api] base` when set (such as the last release tag), otherwise the
+//! commit just before the `[api] since` window. Each item added, removed or
+//! given a new signature since then is reported. Removed and changed items
+//! may break callers. When the window reaches back to the first commit
+//! there is nothing to compare with, and only the surface is reported.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+
+use crate::config::ApiConfig;
+use crate::core::{
+    AnalysisContext, Analyzer as AnalyzerTrait, ContentSource, Error, FileSet, Language, Result,
+    Severity, SeverityCounts,
+};
+use crate::git::{GitRepo, PathFilter};
+use crate::parser::{ParseResult, Parser};
+
+/// Public API surface analyzer.
+#[derive(Default)]
+pub struct Analyzer;
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "api"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the public API surface and how it changed since a baseline"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let files: Vec<FileSurface> = ctx
+            .files
+            .files()
+            .par_iter()
+            .filter(|path| ctx.files.language(path).is_some_and(has_api))
+            .filter_map(|path| {
+                let parsed = ctx.parsed(path).ok()?;
+                let file = path.strip_prefix(ctx.root).unwrap_or(path);
+                Some(FileSurface {
+                    file: file.to_string_lossy().replace('\\', "/"),
+                    language: parsed.language,
+                    items: surface(&parsed),
+                })
+            })
+            .collect();
+
+        let Some(repo) = ctx.open_git()? else {
+            return Ok(Analysis::new(files, None, Vec::new()));
+        };
+        let Some(base) = baseline_rev(&repo, &ctx.config.api)? else {
+            return Ok(Analysis::new(files, None, Vec::new()));
+        };
+        let base_repo = GitRepo::open(repo.root())?.at_ref(Some(&base));
+        let sha = base_repo.rev_sha()?;
+        let before = baseline_surface(ctx, &repo, &base_repo, &files)?;
+        let changes = compare(&before, &files);
+        Ok(Analysis::new(files, Some(sha), changes))
+    }
+}
+
+/// Languages whose public items can be told from private ones.
+fn has_api(language: Language) -> bool {
+    matches!(
+        language,
+        Language::Rust
+            | Language::Go
+            | Language::Python
+            | Language::JavaScript
+            | Language::TypeScript
+            | Language::Tsx
+            | Language::Jsx
+            | Language::Java
+            | Language::CSharp
+    )
+}
+
+/// The revision to compare with: `config.base`, or the parent of the oldest
+/// commit in the `config.since` window. `None` when that commit has no
+/// parent.
+fn baseline_rev(repo: &GitRepo, config: &ApiConfig) -> Result<Option<String>> {
+    if let Some(base) = &config.base {
+        if !repo.ref_exists(base) {
+            return Err(Error::git(format!("Unknown base revision '{base}'")));
+        }
+        return Ok(Some(base.clone()));
+    }
+    let commits = repo.log(Some(&config.since), None, None)?;
+    let Some(oldest) = commits.last() else {
+        // Nothing committed in the window: compare the worktree with the
+        // analyzed revision.
+        return Ok(Some(repo.rev().unwrap_or("HEAD").to_string()));
+    };
+    let parent = format!("{}^", oldest.sha);
+    Ok(repo.ref_exists(&parent).then_some(parent))
+}
+
+/// The surface at `base_repo`'s revision, for the files in the analysis
+/// scope that are still in `current` or have been deleted since.
+fn baseline_surface(
+    ctx: &AnalysisContext<'_>,
+    repo: &GitRepo,
+    base_repo: &GitRepo,
+    current: &[FileSurface],
+) -> Result<Vec<FileSurface>> {
+    let tree = base_repo.tree_source()?;
+    let filter = PathFilter::from_context(ctx, repo.root());
+    let analyzed: BTreeSet<&str> = current.iter().map(|f| f.file.as_str()).collect();
+    let files = FileSet::from_tree_source(&tree, ctx.config)?;
+    Ok(files
+        .files()
+        .par_iter()
+        .filter_map(|path| {
+            let language = files.language(path).filter(|l| has_api(*l))?;
+            let repo_path = path.to_string_lossy().replace('\\', "/");
+            if !filter.matches(&repo_path) {
+                return None;
+            }
+            let file = filter.relative_path(&repo_path)?.to_string();
+            // A file that still exists but wasn't analyzed is out of scope
+            if !analyzed.contains(file.as_str()) && ctx.read_file(Path::new(&file)).is_ok() {
+                return None;
+            }
+            let content = tree.read(path).ok()?;
+            let parsed = Parser::new().parse(&content, language, path).ok()?;
+            Some(FileSurface {
+                file,
+                language,
+                items: surface(&parsed),
+            })
+        })
+        .collect())
+}
+
+/// Public items of a parsed file, in source order.
+pub fn surface(parsed: &ParseResult) -> Vec<ApiItem> {
+    let mut walker = Walker {
+        language: parsed.language,
+        source: &parsed.source,
+        items: Vec::new(),
+    };
+    walker.visit_children(parsed.root_node(), &Scope::default());
+    walker.items.sort_by_key(|item| item.line);
+    walker.items
+}
+
+/// What encloses a node.
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    /// Names of the enclosing types and modules.
+    path: Vec<String>,
+    /// Inside a type, so functions are methods.
+    in_type: bool,
+    /// Inside a trait or interface, whose members are public with it.
+    implicit: bool,
+    /// Under a JavaScript or TypeScript `export`.
+    exported: bool,
+}
+
+impl Scope {
+    fn enter(&self, name: &str) -> Self {
+        let mut path = self.path.clone();
+        path.push(name.to_string());
+        Self {
+            path,
+            in_type: true,
+            implicit: false,
+            exported: self.exported,
+        }
+    }
+}
+
+struct Walker<'s> {
+    language: Language,
+    source: &'s [u8],
+    items: Vec<ApiItem>,
+}
+
+impl<'s> Walker<'s> {
+    fn visit(&mut self, node: Node<'_>, scope: &Scope) {
+        match self.language {
+            Language::Rust => self.visit_rust(node, scope),
+            Language::Go => self.visit_go(node, scope),
+            Language::Python => self.visit_python(node, scope),
+            Language::Java | Language::CSharp => self.visit_java(node, scope),
+            _ => self.visit_js(node, scope),
+        }
+    }
+
+    fn visit_children(&mut self, node: Node<'_>, scope: &Scope) {
+        for child in node.named_children(&mut node.walk()) {
+            self.visit(child, scope);
+        }
+    }
+
+    fn visit_body(&mut self, node: Node<'_>, scope: &Scope) {
+        if let Some(body) = node.child_by_field_name("body") {
+            self.visit_children(body, scope);
+        }
+    }
+
+    fn visit_rust(&mut self, node: Node<'_>, scope: &Scope) {
+        let public = scope.implicit
+            || node
+                .named_children(&mut node.walk())
+                .any(|c| c.kind() == "visibility_modifier" && self.text(c) == "pub");
+        match node.kind() {
+            "function_item" | "function_signature_item" if public => {
+                self.push_function(node, scope);
+            }
+            "struct_item" | "enum_item" | "union_item" | "type_item" if public => {
+                self.push_named(node, ItemKind::Type, scope);
+            }
+            "trait_item" if public => {
+                if let Some(name) = self.push_named(node, ItemKind::Interface, scope) {
+                    let scope = Scope {
+                        implicit: true,
+                        ..scope.enter(&name)
+                    };
+                    self.visit_body(node, &scope);
+                }
+            }
+            // Trait impls add no names of their own
+            "impl_item" if node.child_by_field_name("trait").is_none() => {
+                let Some(ty) = node.child_by_field_name("type") else {
+                    return;
+                };
+                let name = self.text(ty).split('<').next().unwrap_or_default().trim();
+                self.visit_body(node, &scope.enter(name));
+            }
+            "mod_item" if public => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    let scope = Scope {
+                        in_type: false,
+                        ..scope.enter(self.text(name))
+                    };
+                    self.visit_body(node, &scope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_go(&mut self, node: Node<'_>, scope: &Scope) {
+        let exported = |name: &str| name.starts_with(|c: char| c.is_uppercase());
+        match node.kind() {
+            "function_declaration" => {
+                if self.name(node).is_some_and(|n| exported(&n)) {
+                    self.push_function(node, scope);
+                }
+            }
+            "method_declaration" => {
+                let receiver = node
+                    .child_by_field_name("receiver")
+                    .and_then(|list| list.named_child(0))
+                    .and_then(|param| param.child_by_field_name("type"))
+                    .map(|ty| {
+                        let text = self.text(ty).trim_start_matches('*');
+                        text.split('[').next().unwrap_or_default().trim()
+                    });
+                if let Some(receiver) = receiver.filter(|r| exported(r)) {
+                    if self.name(node).is_some_and(|n| exported(&n)) {
+                        self.push_function(node, &scope.enter(receiver));
+                    }
+                }
+            }
+            "type_declaration" => {
+                for spec in node.named_children(&mut node.walk()) {
+                    let Some(name) = self.name(spec).filter(|n| exported(n)) else {
+                        continue;
+                    };
+                    let ty = spec.child_by_field_name("type");
+                    let (kind, signature) = match ty.map(|t| t.kind()) {
+                        Some("struct_type") => (ItemKind::Type, format!("type {name} struct")),
+                        Some("interface_type") => {
+                            (ItemKind::Interface, format!("type {name} interface"))
+                        }
+                        _ => (
+                            ItemKind::Type,
+                            collapse(&format!("type {}", self.text(spec))),
+                        ),
+                    };
+                    self.push(spec, kind, &name, scope, signature);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_python(&mut self, node: Node<'_>, scope: &Scope) {
+        let public =
+            |name: &str| !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__"));
+        match node.kind() {
+            "function_definition" => {
+                if self.name(node).is_some_and(|n| public(&n)) {
+                    self.push_function(node, scope);
+                }
+            }
+            "class_definition" => {
+                if self.name(node).is_some_and(|n| public(&n)) {
+                    if let Some(name) = self.push_named(node, ItemKind::Type, scope) {
+                        self.visit_body(node, &scope.enter(&name));
+                    }
+                }
+            }
+            "decorated_definition" => {
+                if let Some(definition) = node.child_by_field_name("definition") {
+                    self.visit(definition, scope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_js(&mut self, node: Node<'_>, scope: &Scope) {
+        match node.kind() {
+            "export_statement" => {
+                if let Some(declaration) = node.child_by_field_name("declaration") {
+                    let scope = Scope {
+                        exported: true,
+                        ..scope.clone()
+                    };
+                    self.visit(declaration, &scope);
+                }
+            }
+            _ if !scope.exported => {}
+            "function_declaration" | "generator_function_declaration" | "function_signature"
+                if !scope.in_type =>
+            {
+                self.push_function(node, scope);
+            }
+            "class_declaration" | "abstract_class_declaration" => {
+                if let Some(name) = self.push_named(node, ItemKind::Type, scope) {
+                    self.visit_body(node, &scope.enter(&name));
+                }
+            }
+            "interface_declaration" => {
+                if let Some(name) = self.push_named(node, ItemKind::Interface, scope) {
+                    self.visit_body(node, &scope.enter(&name));
+                }
+            }
+            "type_alias_declaration" | "enum_declaration" => {
+                self.push_named(node, ItemKind::Type, scope);
+            }
+            "lexical_declaration" | "variable_declaration" => {
+                for declarator in node.named_children(&mut node.walk()) {
+                    let Some(value) = declarator.child_by_field_name("value") else {
+                        continue;
+                    };
+                    if !matches!(
+                        value.kind(),
+                        "arrow_function"
+                            | "function_expression"
+                            | "function"
+                            | "generator_function"
+                    ) {
+                        continue;
+                    }
+                    if let Some(name) = self.name(declarator) {
+                        let end = value
+                            .child_by_field_name("body")
+                            .map_or(value.end_byte(), |b| b.start_byte());
+                        let signature = self.signature_until(declarator, end);
+                        self.push(declarator, ItemKind::Function, &name, scope, signature);
+                    }
+                }
+            }
+            "method_definition" | "method_signature" | "abstract_method_signature"
+                if scope.in_type =>
+            {
+                let hidden = node.named_children(&mut node.walk()).any(|c| {
+                    c.kind() == "accessibility_modifier"
+                        && matches!(self.text(c), "private" | "protected")
+                });
+                if !hidden && self.name(node).is_some_and(|n| !n.starts_with('#')) {
+                    self.push_function(node, scope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_java(&mut self, node: Node<'_>, scope: &Scope) {
+        let public = if scope.implicit {
+            !self.has_modifier(node, "private")
+        } else {
+            self.has_modifier(node, "public")
+        };
+        match node.kind() {
+            "namespace_declaration" => self.visit_body(node, scope),
+            "file_scoped_namespace_declaration" | "enum_body_declarations" => {
+                self.visit_children(node, scope);
+            }
+            "interface_declaration" if public => {
+                if let Some(name) = self.push_named(node, ItemKind::Interface, scope) {
+                    let scope = Scope {
+                        implicit: true,
+                        ..scope.enter(&name)
+                    };
+                    self.visit_body(node, &scope);
+                }
+            }
+            "class_declaration"
+            | "struct_declaration"
+            | "enum_declaration"
+            | "record_declaration"
+            | "record_struct_declaration"
+                if public =>
+            {
+                if let Some(name) = self.push_named(node, ItemKind::Type, scope) {
+                    self.visit_body(node, &scope.enter(&name));
+                }
+            }
+            "method_declaration" | "constructor_declaration" if public => {
+                self.push_function(node, scope);
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether a Java `modifiers` or C# `modifier` child says `word`.
+    fn has_modifier(&self, node: Node<'_>, word: &str) -> bool {
+        node.children(&mut node.walk())
+            .any(|child| match child.kind() {
+                "modifiers" => self.text(child).split_whitespace().any(|w| w == word),
+                "modifier" => self.text(child) == word,
+                _ => false,
+            })
+    }
+
+    fn push_function(&mut self, node: Node<'_>, scope: &Scope) {
+        let kind = if scope.in_type {
+            ItemKind::Method
+        } else {
+            ItemKind::Function
+        };
+        self.push_named(node, kind, scope);
+    }
+
+    /// Push a declaration with a `name` field, returning the name.
+    fn push_named(&mut self, node: Node<'_>, kind: ItemKind, scope: &Scope) -> Option<String> {
+        let name = self.name(node)?;
+        let end = node
+            .child_by_field_name("body")
+            .map_or(node.end_byte(), |b| b.start_byte());
+        let signature = self.signature_until(node, end);
+        self.push(node, kind, &name, scope, signature);
+        Some(name)
+    }
+
+    fn push(&mut self, node: Node<'_>, kind: ItemKind, name: &str, scope: &Scope, sig: String) {
+        let separator = if self.language == Language::Rust {
+            "::"
+        } else {
+            "."
+        };
+        let mut path = scope.path.clone();
+        path.push(name.to_string());
+        self.items.push(ApiItem {
+            name: path.join(separator),
+            kind,
+            line: node.start_position().row as u32 + 1,
+            signature: sig,
+        });
+    }
+
+    fn name(&self, node: Node<'_>) -> Option<String> {
+        let name = self.text(node.child_by_field_name("name")?);
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    fn text(&self, node: Node<'_>) -> &'s str {
+        node.utf8_text(self.source).unwrap_or_default()
+    }
+
+    /// The declaration text from `node`'s start to `end` (where its body
+    /// begins), on one line.
+    fn signature_until(&self, node: Node<'_>, end: usize) -> String {
+        let text = String::from_utf8_lossy(&self.source[node.start_byte()..end]);
+        collapse(&text)
+            .trim_end_matches(['{', ':', ';', '=', '>', ' '])
+            .to_string()
+    }
+}
+
+/// `text` with each run of whitespace replaced by one space.
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Items added, removed or changed between two surfaces. Items are matched
+/// by file and qualified name; among overloads, those with an unchanged
+/// signature are matched first.
+fn compare(before: &[FileSurface], after: &[FileSurface]) -> Vec<ApiChange> {
+    let by_file = |surfaces: &[FileSurface]| -> BTreeMap<String, Vec<ApiItem>> {
+        surfaces
+            .iter()
+            .map(|s| (s.file.clone(), s.items.clone()))
+            .collect()
+    };
+    let mut before = by_file(before);
+    let mut after = by_file(after);
+    let files: BTreeSet<String> = before.keys().chain(after.keys()).cloned().collect();
+
+    let mut changes = Vec::new();
+    for file in files {
+        let mut old = before.remove(&file).unwrap_or_default();
+        let mut new = after.remove(&file).unwrap_or_default();
+        old.retain(|item| {
+            let unchanged = new
+                .iter()
+                .position(|n| n.name == item.name && n.signature == item.signature);
+            unchanged.map(|idx| new.remove(idx)).is_none()
+        });
+        for item in old {
+            let replacement = new.iter().position(|n| n.name == item.name);
+            let change =
+                match replacement.map(|idx| new.remove(idx)) {
+                    Some(current) => ApiChange::new(&file, ChangeKind::Changed, &current)
+                        .with_before(item.signature),
+                    None => ApiChange::new(&file, ChangeKind::Removed, &item)
+                        .with_before(item.signature),
+                };
+            changes.push(change);
+        }
+        changes.extend(
+            new.iter()
+                .map(|item| ApiChange::new(&file, ChangeKind::Added, item)),
+        );
+    }
+    changes
+}
+
+/// A public declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiItem {
+    /// Name qualified by its enclosing types and modules (`Client.send`,
+    /// `shapes::Circle::area`).
+    pub name: String,
+    pub kind: ItemKind,
+    pub line: u32,
+    /// The declaration up to its body, on one line.
+    pub signature: String,
+}
+
+/// Kind of public declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Function,
+    Method,
+    /// Struct, class, enum, union or type alias.
+    Type,
+    /// Trait or interface.
+    Interface,
+}
+
+/// The public surface of one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSurface {
+    pub file: String,
+    pub language: Language,
+    pub items: Vec<ApiItem>,
+}
+
+/// How an item differs from the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    /// Gone from the surface: removed, renamed, moved or made private.
+    Removed,
+    /// Same name, different signature.
+    Changed,
+}
+
+impl ChangeKind {
+    fn severity(self) -> Severity {
+        match self {
+            Self::Removed => Severity::High,
+            Self::Changed => Severity::Medium,
+            Self::Added => Severity::Low,
+        }
+    }
+
+    /// Whether callers of the item may break.
+    pub fn is_breaking(self) -> bool {
+        matches!(self, Self::Removed | Self::Changed)
+    }
+}
+
+/// A public item added, removed or changed since the baseline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiChange {
+    pub file: String,
+    pub name: String,
+    pub kind: ItemKind,
+    pub change: ChangeKind,
+    pub severity: Severity,
+    /// Line now, or in the baseline for a removed item.
+    pub line: u32,
+    /// Signature in the baseline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Signature now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl ApiChange {
+    fn new(file: &str, change: ChangeKind, item: &ApiItem) -> Self {
+        Self {
+            file: file.to_string(),
+            name: item.name.clone(),
+            kind: item.kind,
+            change,
+            severity: change.severity(),
+            line: item.line,
+            before: None,
+            after: (change != ChangeKind::Removed).then(|| item.signature.clone()),
+        }
+    }
+
+    fn with_before(mut self, signature: String) -> Self {
+        self.before = Some(signature);
+        self
+    }
+}
+
+/// Public API surface result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    /// Changes since the baseline, most severe first, then by location.
+    pub changes: Vec<ApiChange>,
+    /// Files with public items, in path order.
+    pub files: Vec<FileSurface>,
+    pub summary: Summary,
+}
+
+impl Analysis {
+    fn new(files: Vec<FileSurface>, baseline: Option<String>, mut changes: Vec<ApiChange>) -> Self {
+        let total_files = files.len();
+        let mut files: Vec<FileSurface> =
+            files.into_iter().filter(|f| !f.items.is_empty()).collect();
+        files.sort_by(|a, b| a.file.cmp(&b.file));
+        changes.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.file.cmp(&b.file))
+                .then(a.line.cmp(&b.line))
+        });
+
+        let mut by_kind = BTreeMap::new();
+        for item in files.iter().flat_map(|f| &f.items) {
+            *by_kind.entry(item.kind).or_default() += 1;
+        }
+        let count = |kind: ChangeKind| changes.iter().filter(|c| c.change == kind).count();
+        let summary = Summary {
+            total_files,
+            public_items: by_kind.values().sum(),
+            by_kind,
+            baseline,
+            added: count(ChangeKind::Added),
+            removed: count(ChangeKind::Removed),
+            changed: count(ChangeKind::Changed),
+            breaking: changes.iter().filter(|c| c.change.is_breaking()).count(),
+            severity_counts: changes.iter().map(|c| c.severity).collect(),
+        };
+        Self {
+            changes,
+            files,
+            summary,
+        }
+    }
+}
+
+/// Aggregate surface statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_files: usize,
+    pub public_items: usize,
+    pub by_kind: BTreeMap<ItemKind, usize>,
+    /// Commit the surface was compared with, if any.
+    pub baseline: Option<String>,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    /// Removed and changed items, which may break callers.
+    pub breaking: usize,
+    pub severity_counts: SeverityCounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{git, init_repo};
+
+    fn items(code: &str, language: Language, file: &str) -> Vec<(ItemKind, String, String)> {
+        let parsed = Parser::new()
+            .parse(code.as_bytes(), language, Path::new(file))
+            .unwrap();
+        surface(&parsed)
+            .into_iter()
+            .map(|item| (item.kind, item.name, item.signature))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_surface() {
+        let surface = items(
+            "pub fn open(path: &Path,\n    mode: u32) -> File {}\n\
+             fn private() {}\n\
+             pub(crate) fn internal() {}\n\
+             pub struct Circle { radius: f64 }\n\
+             impl Circle {\n    pub fn area(&self) -> f64 { 0.0 }\n    fn helper(&self) {}\n}\n\
+             impl Shape for Circle {\n    fn sides(&self) -> u32 { 0 }\n}\n\
+             pub trait Shape {\n    fn sides(&self) -> u32;\n}\n\
+             pub mod units {\n    pub type Meters = f64;\n}\n",
+            Language::Rust,
+            "lib.rs",
+        );
+
+        assert_eq!(
+            surface,
+            vec![
+                (
+                    ItemKind::Function,
+                    "open".into(),
+                    "pub fn open(path: &Path, mode: u32) -> File".into()
+                ),
+                (ItemKind::Type, "Circle".into(), "pub struct Circle".into()),
+                (
+                    ItemKind::Method,
+                    "Circle::area".into(),
+                    "pub fn area(&self) -> f64".into()
+                ),
+                (
+                    ItemKind::Interface,
+                    "Shape".into(),
+                    "pub trait Shape".into()
+                ),
+                (
+                    ItemKind::Method,
+                    "Shape::sides".into(),
+                    "fn sides(&self) -> u32".into()
+                ),
+                (
+                    ItemKind::Type,
+                    "units::Meters".into(),
+                    "pub type Meters = f64".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typescript_surface() {
+        let surface = items(
+            "export function get(url: string): Promise<Response> {}\n\
+             function local() {}\n\
+             export const post = async (url: string) => {};\n\
+             export class Client {\n\
+               send(message: string): void {}\n\
+               private retry(): void {}\n\
+               #hidden() {}\n\
+             }\n\
+             class Internal { run() {} }\n\
+             export interface Options { timeout: number }\n",
+            Language::TypeScript,
+            "client.ts",
+        );
+
+        let names: Vec<(ItemKind, &str)> = surface
+            .iter()
+            .map(|(kind, name, _)| (*kind, name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (ItemKind::Function, "get"),
+                (ItemKind::Function, "post"),
+                (ItemKind::Type, "Client"),
+                (ItemKind::Method, "Client.send"),
+                (ItemKind::Interface, "Options"),
+            ]
+        );
+        assert_eq!(surface[1].2, "post = async (url: string)");
+    }
+
+    #[test]
+    fn test_python_and_go_surface() {
+        let python = items(
+            "def load(path):\n    def inner(): pass\n\n\
+             def _private(): pass\n\n\
+             class Store:\n    def __init__(self, root): pass\n    def _cache(self): pass\n    \
+             @property\n    def size(self) -> int: pass\n",
+            Language::Python,
+            "store.py",
+        );
+        let names: Vec<&str> = python.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(names, ["load", "Store", "Store.__init__", "Store.size"]);
+        assert_eq!(python[3].2, "def size(self) -> int");
+
+        let go = items(
+            "package store\n\
+             type Store struct { root string }\n\
+             type reader interface { Read() }\n\
+             func New(root string) *Store { return nil }\n\
+             func helper() {}\n\
+             func (s *Store) Get(key string) ([]byte, error) { return nil, nil }\n\
+             func (s *Store) put() {}\n",
+            Language::Go,
+            "store.go",
+        );
+        let names: Vec<(ItemKind, &str)> = go
+            .iter()
+            .map(|(kind, name, _)| (*kind, name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (ItemKind::Type, "Store"),
+                (ItemKind::Function, "New"),
+                (ItemKind::Method, "Store.Get"),
+            ]
+        );
+        assert_eq!(go[0].2, "type Store struct");
+    }
+
+    #[test]
+    fn test_java_surface() {
+        let surface = items(
+            "public class Parser {\n\
+               public Parser(String input) {}\n\
+               public Node parse() { return null; }\n\
+               private void skip() {}\n\
+               public interface Visitor { void visit(Node node); }\n\
+             }\n\
+             class Hidden { public void run() {} }\n",
+            Language::Java,
+            "Parser.java",
+        );
+
+        let names: Vec<&str> = surface.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Parser",
+                "Parser.Parser",
+                "Parser.parse",
+                "Parser.Visitor",
+                "Parser.Visitor.visit"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let item = |name: &str, signature: &str, line| ApiItem {
+            name: name.to_string(),
+            kind: ItemKind::Method,
+            line,
+            signature: signature.to_string(),
+        };
+        let file = |items| FileSurface {
+            file: "Api.java".to_string(),
+            language: Language::Java,
+            items,
+        };
+        let before = [file(vec![
+            item("Api.get", "public int get(int id)", 1),
+            item("Api.get", "public int get(String name)", 2),
+            item("Api.drop", "public void drop()", 3),
+        ])];
+        let after = [file(vec![
+            item("Api.get", "public int get(String name)", 1),
+            item("Api.get", "public long get(int id)", 2),
+            item("Api.put", "public void put()", 3),
+        ])];
+
+        let analysis = Analysis::new(after.to_vec(), None, compare(&before, &after));
+        let changes: Vec<(ChangeKind, &str, Option<&str>)> = analysis
+            .changes
+            .iter()
+            .map(|c| (c.change, c.name.as_str(), c.before.as_deref()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (ChangeKind::Removed, "Api.drop", Some("public void drop()")),
+                (
+                    ChangeKind::Changed,
+                    "Api.get",
+                    Some("public int get(int id)")
+                ),
+                (ChangeKind::Added, "Api.put", None),
+            ]
+        );
+        assert_eq!(
+            analysis.changes[1].after.as_deref(),
+            Some("public long get(int id)")
+        );
+        assert_eq!(analysis.summary.breaking, 2);
+        assert_eq!(analysis.summary.public_items, 3);
+    }
+
+    #[test]
+    fn test_compares_worktree_with_base() {
+        use crate::config::Config;
+        use crate::core::FileSet;
+
+        let repo = tempfile::TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::write(
+            repo.path().join("lib.rs"),
+            "pub fn parse(input: &str) {}\npub fn reset() {}\n",
+        )
+        .unwrap();
+        std::fs::write(repo.path().join("old.rs"), "pub fn legacy() {}\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-q", "-m", "init"]);
+
+        std::fs::write(
+            repo.path().join("lib.rs"),
+            "pub fn parse(input: &str, strict: bool) {}\nfn reset() {}\npub fn format() {}\n",
+        )
+        .unwrap();
+        std::fs::remove_file(repo.path().join("old.rs")).unwrap();
+
+        let mut config = Config::default();
+        config.api.base = Some("HEAD".to_string());
+        let files = FileSet::from_path(repo.path(), &config).unwrap();
+        let ctx =
+            AnalysisContext::new(&files, &config, Some(repo.path())).with_git_path(repo.path());
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let changes: Vec<(ChangeKind, &str, &str)> = analysis
+            .changes
+            .iter()
+            .map(|c| (c.change, c.file.as_str(), c.name.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (ChangeKind::Removed, "lib.rs", "reset"),
+                (ChangeKind::Removed, "old.rs", "legacy"),
+                (ChangeKind::Changed, "lib.rs", "parse"),
+                (ChangeKind::Added, "lib.rs", "format"),
+            ]
+        );
+        assert_eq!(analysis.summary.baseline.as_deref().map(str::len), Some(40));
+        assert_eq!(analysis.summary.total_files, 1);
+    }
+}
@@ -0,0 +1,354 @@
+//! Symbol-level call graph analyzer.
+//!
+//! Builds a function/method call graph from the tree-sitter parses, using
+//! the same index as the repo map and `omen impact` (see
+//! [`repomap::build_index`]). Calls resolve to a definition in the same file,
+//! then through Rust `use` declarations, then by name within the caller's
+//! language family, so a Python `helper` is never taken for a Go one.
+//!
+//! # Metrics
+//!
+//! - **PageRank**: importance based on incoming calls
+//!   Reference: Page, Brin, Motwani, Winograd (1999) "The PageRank Citation Ranking"
+//!
+//! - **Fan-in / fan-out**: distinct callers and callees of a symbol
+//!   Reference: Henry, S., Kafura, D. (1981) "Software Structure Metrics Based
+//!   on Information Flow"
+//!
+//! - **Cycles**: direct and mutual recursion, as strongly connected
+//!   components of the call graph (Tarjan's algorithm)
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::repomap::{self, CallGraphIndex};
+use crate::core::{is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
+
+/// Call graph analyzer configuration.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// PageRank damping factor (default: 0.85).
+    pub damping: f64,
+    /// PageRank max iterations (default: 100).
+    pub max_iterations: usize,
+    /// PageRank convergence tolerance (default: 1e-6).
+    pub tolerance: f64,
+    /// Skip test files.
+    pub skip_test_files: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            max_iterations: 100,
+            tolerance: 1e-6,
+            skip_test_files: true,
+        }
+    }
+}
+
+/// Call graph analyzer.
+pub struct Analyzer {
+    config: Config,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self {
+            config: Config::default(),
+        }
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Analyze using a pre-filtered file set from an AnalysisContext.
+    pub fn analyze_with_files(
+        &self,
+        repo_path: &Path,
+        file_set: &crate::core::FileSet,
+    ) -> Result<Analysis> {
+        let files: Vec<_> = file_set
+            .iter()
+            .filter(|path| !self.config.skip_test_files || !is_test_file(path))
+            .filter(|path| file_set.language(path).is_some())
+            .map(|p| repo_path.join(p))
+            .collect();
+
+        self.analyze_files(repo_path, &files)
+    }
+
+    /// Core analysis logic operating on a list of absolute file paths.
+    fn analyze_files(&self, repo_path: &Path, files: &[PathBuf]) -> Result<Analysis> {
+        let index = repomap::build_index(repo_path, files)?;
+        let calls = distinct_calls(&index);
+
+        let pagerank = repomap::pagerank(
+            &calls,
+            self.config.damping,
+            self.config.max_iterations,
+            self.config.tolerance,
+        );
+        let cycles = cycles(&calls, &index);
+        let recursive: HashSet<&str> = cycles.iter().flatten().map(String::as_str).collect();
+
+        let mut symbols: Vec<CallSymbol> = calls
+            .node_indices()
+            .map(|node| {
+                let symbol = &index.symbols[calls[node]];
+                CallSymbol {
+                    name: symbol.name.clone(),
+                    qualified_name: symbol.qualified_name.clone(),
+                    file: symbol.file.clone(),
+                    line: symbol.line,
+                    language: symbol.language,
+                    pagerank: pagerank.get(&node).copied().unwrap_or(0.0),
+                    fan_in: calls.edges_directed(node, Direction::Incoming).count(),
+                    fan_out: calls.edges_directed(node, Direction::Outgoing).count(),
+                    recursive: recursive.contains(symbol.qualified_name.as_str()),
+                }
+            })
+            .collect();
+        symbols.sort_by(|a, b| {
+            b.pagerank
+                .total_cmp(&a.pagerank)
+                .then_with(|| a.qualified_name.cmp(&b.qualified_name))
+        });
+
+        let mut edges: Vec<CallEdge> = calls
+            .edge_references()
+            .map(|e| CallEdge {
+                from: index.symbols[calls[e.source()]].qualified_name.clone(),
+                to: index.symbols[calls[e.target()]].qualified_name.clone(),
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+        let summary = Summary {
+            total_symbols: symbols.len(),
+            total_calls: edges.len(),
+            cycle_count: cycles.len(),
+            recursive_symbols: recursive.len(),
+            max_fan_in: symbols.iter().map(|s| s.fan_in).max().unwrap_or(0),
+            max_fan_out: symbols.iter().map(|s| s.fan_out).max().unwrap_or(0),
+            avg_fan_out: if symbols.is_empty() {
+                0.0
+            } else {
+                edges.len() as f64 / symbols.len() as f64
+            },
+        };
+
+        Ok(Analysis {
+            symbols,
+            edges,
+            cycles,
+            summary,
+        })
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "callgraph"
+    }
+
+    fn description(&self) -> &'static str {
+        "Build the function call graph with PageRank, fan-in/fan-out, and recursion"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        self.analyze_with_files(ctx.root, ctx.files)
+    }
+}
+
+/// The index's call graph with repeated calls between the same two symbols
+/// merged into one edge.
+fn distinct_calls(index: &CallGraphIndex) -> DiGraph<usize, ()> {
+    let mut calls: DiGraph<usize, ()> = DiGraph::with_capacity(index.symbols.len(), 0);
+    let nodes: Vec<NodeIndex> = (0..index.symbols.len())
+        .map(|idx| calls.add_node(idx))
+        .collect();
+    for edge in index.graph.edge_references() {
+        let caller = index.graph[edge.source()];
+        let callee = index.graph[edge.target()];
+        calls.update_edge(nodes[caller], nodes[callee], ());
+    }
+    calls
+}
+
+/// Recursive call cycles: strongly connected components with more than one
+/// symbol, plus functions calling themselves. Members are sorted by
+/// qualified name, larger cycles first.
+fn cycles(calls: &DiGraph<usize, ()>, index: &CallGraphIndex) -> Vec<Vec<String>> {
+    let mut cycles: Vec<Vec<String>> = tarjan_scc(calls)
+        .into_iter()
+        .filter(|scc| scc.len() > 1 || calls.contains_edge(scc[0], scc[0]))
+        .map(|scc| {
+            let mut names: Vec<String> = scc
+                .into_iter()
+                .map(|node| index.symbols[calls[node]].qualified_name.clone())
+                .collect();
+            names.sort();
+            names
+        })
+        .collect();
+    cycles.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    cycles
+}
+
+/// Call graph analysis result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    /// Symbols by PageRank, highest first.
+    pub symbols: Vec<CallSymbol>,
+    /// Distinct caller -> callee pairs, by qualified name.
+    pub edges: Vec<CallEdge>,
+    /// Recursive call cycles, by qualified name.
+    pub cycles: Vec<Vec<String>>,
+    pub summary: Summary,
+}
+
+/// A function or method in the call graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallSymbol {
+    pub name: String,
+    pub qualified_name: String,
+    pub file: String,
+    pub line: u32,
+    pub language: Language,
+    pub pagerank: f64,
+    /// Distinct symbols calling this one.
+    pub fan_in: usize,
+    /// Distinct symbols this one calls.
+    pub fan_out: usize,
+    /// Part of a recursive call cycle.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub recursive: bool,
+}
+
+/// A call from one symbol to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Summary statistics for the call graph.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_symbols: usize,
+    pub total_calls: usize,
+    pub cycle_count: usize,
+    pub recursive_symbols: usize,
+    pub max_fan_in: usize,
+    pub max_fan_out: usize,
+    pub avg_fan_out: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn analyze(files: &[(&str, &str)]) -> Analysis {
+        let dir = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = files
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.path().join(name);
+                fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+        Analyzer::new().analyze_files(dir.path(), &paths).unwrap()
+    }
+
+    fn symbol<'a>(analysis: &'a Analysis, qualified_name: &str) -> &'a CallSymbol {
+        analysis
+            .symbols
+            .iter()
+            .find(|s| s.qualified_name == qualified_name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_and_cycles() {
+        let analysis = analyze(&[(
+            "main.rs",
+            "fn main() { parse(); run(); run(); }\n\
+             fn parse() { lex(); }\n\
+             fn lex() {}\n\
+             fn run() { step(); }\n\
+             fn step() { run(); }\n\
+             fn fact(n: u32) -> u32 { if n == 0 { 1 } else { n * fact(n - 1) } }\n",
+        )]);
+
+        let main = symbol(&analysis, "main.rs:main");
+        assert_eq!((main.fan_in, main.fan_out), (0, 2));
+        assert!(!main.recursive);
+        let run = symbol(&analysis, "main.rs:run");
+        assert_eq!((run.fan_in, run.fan_out), (2, 1));
+        assert!(run.recursive);
+        assert_eq!(run.language, Language::Rust);
+
+        assert_eq!(
+            analysis.cycles,
+            vec![
+                vec!["main.rs:run".to_string(), "main.rs:step".to_string()],
+                vec!["main.rs:fact".to_string()],
+            ]
+        );
+        assert_eq!(analysis.summary.cycle_count, 2);
+        assert_eq!(analysis.summary.recursive_symbols, 3);
+        assert_eq!(analysis.summary.total_symbols, 6);
+        // main -> parse, main -> run, parse -> lex, run -> step, step -> run, fact -> fact
+        assert_eq!(analysis.summary.total_calls, 6);
+        assert_eq!(analysis.summary.max_fan_in, 2);
+        assert!(analysis.edges.contains(&CallEdge {
+            from: "main.rs:main".to_string(),
+            to: "main.rs:run".to_string(),
+        }));
+
+        // Nothing calls main, so it ranks last
+        assert_eq!(
+            analysis.symbols.last().unwrap().qualified_name,
+            "main.rs:main"
+        );
+    }
+
+    #[test]
+    fn test_calls_stay_within_language_family() {
+        let analysis = analyze(&[
+            ("a.py", "def helper():\n    pass\n"),
+            ("b.go", "package b\n\nfunc run() {\n\thelper()\n}\n"),
+        ]);
+
+        assert!(analysis.edges.is_empty());
+        assert!(analysis.symbols.iter().all(|s| s.fan_in == 0));
+    }
+
+    #[test]
+    fn test_empty_repository() {
+        let analysis = analyze(&[]);
+
+        assert!(analysis.symbols.is_empty());
+        assert_eq!(analysis.summary.avg_fan_out, 0.0);
+    }
+}
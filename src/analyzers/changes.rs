@@ -66,8 +66,11 @@ use chrono::{DateTime, TimeZone, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result};
-use crate::git::GitRepo;
+use crate::core::{
+    is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Language, Result, SeverityCounts,
+    SourceFile,
+};
+use crate::git::{ChangeType, DiffScope, FileChange, FileHunks, GitRepo, PullRequest, ReviewState};
 
 /// Weights for change-level defect prediction features.
 ///
@@ -146,6 +149,7 @@ const HIGH_DIFFUSION_THRESHOLD: f64 = 0.7;
 pub struct Analyzer {
     days: u32,
     weights: Weights,
+    diff_rules: DiffRules,
 }
 
 impl Default for Analyzer {
@@ -159,6 +163,7 @@ impl Analyzer {
         Self {
             days: 30,
             weights: Weights::default(),
+            diff_rules: DiffRules::default(),
         }
     }
 
@@ -173,6 +178,11 @@ impl Analyzer {
         self.weights = weights;
         self
     }
+
+    pub fn with_diff_rules(mut self, rules: DiffRules) -> Self {
+        self.diff_rules = rules;
+        self
+    }
 }
 
 impl AnalyzerTrait for Analyzer {
@@ -277,6 +287,7 @@ impl AnalyzerTrait for Analyzer {
         let mut medium_risk_count = 0;
         let mut low_risk_count = 0;
         let mut bug_fix_count = 0;
+        let mut severity_counts = SeverityCounts::default();
 
         let mut commit_risks: Vec<CommitRisk> = commits
             .iter()
@@ -294,6 +305,7 @@ impl AnalyzerTrait for Analyzer {
                     RiskLevel::Medium => medium_risk_count += 1,
                     RiskLevel::Low => low_risk_count += 1,
                 }
+                severity_counts.record(risk_level.into());
 
                 build_commit_risk(
                     features,
@@ -333,6 +345,7 @@ impl AnalyzerTrait for Analyzer {
                 avg_risk_score,
                 p50_risk_score: percentile(&sorted_scores, 50),
                 p95_risk_score: percentile(&sorted_scores, 95),
+                severity_counts,
             },
             weights: self.weights.clone(),
             normalization,
@@ -426,6 +439,16 @@ pub enum RiskLevel {
     High,
 }
 
+impl From<RiskLevel> for crate::core::Severity {
+    fn from(level: RiskLevel) -> Self {
+        match level {
+            RiskLevel::High => Self::High,
+            RiskLevel::Medium => Self::Medium,
+            RiskLevel::Low => Self::Low,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Summary {
     pub total_commits: usize,
@@ -436,6 +459,8 @@ pub struct Summary {
     pub avg_risk_score: f64,
     pub p50_risk_score: f64,
     pub p95_risk_score: f64,
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1340,6 +1365,156 @@ pub struct DiffResult {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub recommendations: Vec<String>,
     pub file_risk: FileRiskSignals,
+    /// Risky patterns matched by the diff rules, for bot-driven triage.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risk_factors: Vec<DiffRiskFactor>,
+    /// Complexity of the functions the diff touches, before and after.
+    #[serde(default)]
+    pub complexity: ComplexityDelta,
+}
+
+/// How a branch diff moves function complexity.
+///
+/// Functions are compared between the merge base and HEAD; the net deltas
+/// are the PR's complexity spend, checked against `budget` when one is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComplexityDelta {
+    /// Added, removed and modified functions, largest cyclomatic change first.
+    pub functions: Vec<FunctionDelta>,
+    pub net_cyclomatic: i64,
+    pub net_cognitive: i64,
+    /// Maximum net cyclomatic increase allowed for the diff.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<u32>,
+    /// Whether `net_cyclomatic` exceeds `budget`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub over_budget: bool,
+}
+
+/// Complexity of one function at the merge base and at HEAD.
+///
+/// Added functions have zero "before" metrics; removed ones zero "after".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionDelta {
+    pub file: String,
+    pub name: String,
+    pub change: FunctionChange,
+    /// Start line at HEAD, or at the merge base for removed functions.
+    pub line: u32,
+    pub cyclomatic_before: u32,
+    pub cyclomatic_after: u32,
+    pub cyclomatic_delta: i64,
+    pub cognitive_before: u32,
+    pub cognitive_after: u32,
+    pub cognitive_delta: i64,
+}
+
+/// Whether a diff added, removed or modified a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A named risky pattern found in a branch diff.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffRiskFactor {
+    /// Stable rule name: `deletes_tests`, `adds_satd`, `touches_hotspots`,
+    /// or `bus_factor_one`.
+    pub name: String,
+    pub description: String,
+    pub files: Vec<String>,
+}
+
+/// Thresholds for the diff risk rules.
+#[derive(Debug, Clone)]
+pub struct DiffRules {
+    /// Flag the diff when it touches more than this many hotspots.
+    pub max_hotspots: usize,
+    /// Net lines removed from a test file that count as deleting tests.
+    pub test_deletion_lines: u32,
+    /// Maximum net cyclomatic complexity a branch diff may add.
+    pub complexity_budget: Option<u32>,
+}
+
+impl Default for DiffRules {
+    fn default() -> Self {
+        Self {
+            max_hotspots: 2,
+            test_deletion_lines: 20,
+            complexity_budget: None,
+        }
+    }
+}
+
+/// A pull request's forge metadata joined with the risk of its diff.
+///
+/// One document per PR, shaped for merge-queue bots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrRiskAssessment {
+    pub generated_at: DateTime<Utc>,
+    pub pull_request: PullRequest,
+    pub risk: DiffResult,
+    /// Files the forge lists for the PR that the local diff does not touch;
+    /// non-empty when the checkout is stale or diffed against another base.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files_not_in_diff: Vec<String>,
+    /// Merge-readiness notes from combining review state with risk.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recommendations: Vec<String>,
+}
+
+/// Risk of the staged or unstaged changes in a working tree.
+///
+/// Shaped for pre-commit hooks: besides the overall score, every hunk is
+/// scored on its own so a hook can point at exactly what is risky.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UncommittedResult {
+    pub generated_at: DateTime<Utc>,
+    pub scope: DiffScope,
+    pub score: f64,
+    pub level: RiskLevel,
+    pub lines_added: i32,
+    pub lines_deleted: i32,
+    pub files_modified: i32,
+    pub factors: HashMap<String, f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recommendations: Vec<String>,
+    pub file_risk: FileRiskSignals,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risk_factors: Vec<DiffRiskFactor>,
+    pub hunks: Vec<HunkRisk>,
+    pub summary: UncommittedSummary,
+}
+
+/// Risk of one hunk, scored as a single-file change of the hunk's size
+/// against the file's churn, complexity and ownership.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HunkRisk {
+    pub file: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub score: f64,
+    pub level: RiskLevel,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UncommittedSummary {
+    pub total_hunks: usize,
+    pub max_hunk_score: f64,
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
+}
+
+impl UncommittedResult {
+    /// Hunks whose risk score is above `max_score`.
+    pub fn hunks_above(&self, max_score: f64) -> impl Iterator<Item = &HunkRisk> {
+        self.hunks.iter().filter(move |h| h.score > max_score)
+    }
 }
 
 impl Analyzer {
@@ -1408,26 +1583,220 @@ impl Analyzer {
         let level = get_risk_level(score, &thresholds);
 
         // Build contributing factors
+        let factors = self.diff_factors(&features, &file_risk, &norm);
+
+        // Generate recommendations
+        let recommendations = generate_diff_recommendations(
+            lines_added,
+            lines_deleted,
+            files_modified,
+            commit_count,
+            score,
+            &factors,
+            &file_risk,
+        );
+
+        let changes = GitRepo::open(repo_path)?.diff_stats(&merge_base, "HEAD")?;
+        let satd_added = count_added_satd(repo_path, &merge_base, &changes);
+        let risk_factors =
+            detect_risk_factors(&changes, &file_profiles, &satd_added, &self.diff_rules);
+        let complexity = complexity_deltas(
+            repo_path,
+            &merge_base,
+            &changes,
+            self.diff_rules.complexity_budget,
+        );
+
+        Ok(DiffResult {
+            generated_at: Utc::now(),
+            source_branch,
+            target_branch,
+            merge_base,
+            score,
+            level,
+            lines_added,
+            lines_deleted,
+            files_modified,
+            commits: commit_count,
+            factors,
+            recommendations,
+            file_risk,
+            risk_factors,
+            complexity,
+        })
+    }
+
+    /// Analyze the checked-out head of `pr` and join it with the PR metadata.
+    ///
+    /// Diffs against `target` when given, otherwise against the PR's base
+    /// branch, preferring `origin/<base>` since CI checkouts rarely have the
+    /// local branch.
+    pub fn analyze_pull_request(
+        &self,
+        repo_path: &Path,
+        pr: PullRequest,
+        target: Option<&str>,
+    ) -> Result<PrRiskAssessment> {
+        let target = match target {
+            Some(t) => Some(t.to_string()),
+            None => pr_base_ref(repo_path, &pr.base_branch),
+        };
+        let risk = self.analyze_diff(repo_path, target.as_deref())?;
+
+        let local: HashSet<String> = get_lines_per_file(repo_path, &risk.merge_base)?
+            .into_keys()
+            .collect();
+        let mut files_not_in_diff: Vec<String> = pr
+            .files
+            .iter()
+            .filter(|f| !local.contains(*f))
+            .cloned()
+            .collect();
+        files_not_in_diff.sort();
+
+        let recommendations = generate_pr_recommendations(&pr, &risk, &files_not_in_diff);
+        Ok(PrRiskAssessment {
+            generated_at: Utc::now(),
+            pull_request: pr,
+            risk,
+            files_not_in_diff,
+            recommendations,
+        })
+    }
+
+    /// Analyze uncommitted changes: the index against HEAD for
+    /// [`DiffScope::Staged`], the working tree against the index for
+    /// [`DiffScope::Worktree`].
+    ///
+    /// File signals (complexity, churn, ownership) are read from the
+    /// working tree and history in both scopes.
+    pub fn analyze_uncommitted(
+        &self,
+        repo_path: &Path,
+        scope: DiffScope,
+    ) -> Result<UncommittedResult> {
+        let files = GitRepo::open(repo_path)?.uncommitted_changes(scope)?;
+        let changes: Vec<FileChange> = files.iter().map(|f| f.change.clone()).collect();
+
+        let lines_per_file: HashMap<String, i32> = changes
+            .iter()
+            .map(|c| {
+                let lines = c.additions as i32 + c.deletions as i32;
+                (c.path.to_string_lossy().to_string(), lines)
+            })
+            .collect();
+        let diff_files: Vec<String> = lines_per_file.keys().cloned().collect();
+        let file_churn = build_churn_for_files(repo_path, &diff_files)?;
+        let max_churn_count = file_churn
+            .values()
+            .map(|c| c.commit_count)
+            .max()
+            .unwrap_or(1);
+        let file_profiles =
+            compute_file_risk_profiles(repo_path, &diff_files, &file_churn, max_churn_count);
+        let file_risk = aggregate_file_risk(&file_profiles, &diff_files);
+
+        let norm = diff_normalization();
+        let thresholds = RiskThresholds::default();
+        let lines_added: i32 = changes.iter().map(|c| c.additions as i32).sum();
+        let lines_deleted: i32 = changes.iter().map(|c| c.deletions as i32).sum();
+        let files_modified = changes.len() as i32;
+        let features = CommitFeatures {
+            lines_added,
+            lines_deleted,
+            num_files: files_modified,
+            entropy: calculate_entropy(&lines_per_file),
+            ..Default::default()
+        };
+        let score = calculate_risk(&features, &self.weights, &norm, &file_risk);
+        let factors = self.diff_factors(&features, &file_risk, &norm);
+        let recommendations = generate_diff_recommendations(
+            lines_added,
+            lines_deleted,
+            files_modified,
+            0,
+            score,
+            &factors,
+            &file_risk,
+        );
+
+        let mut hunks = Vec::new();
+        for file in &files {
+            let path = file.change.path.to_string_lossy().to_string();
+            let file_risk = aggregate_file_risk(&file_profiles, std::slice::from_ref(&path));
+            for hunk in &file.hunks {
+                let features = CommitFeatures {
+                    lines_added: hunk.new_lines as i32,
+                    lines_deleted: hunk.old_lines as i32,
+                    num_files: 1,
+                    ..Default::default()
+                };
+                let score = calculate_risk(&features, &self.weights, &norm, &file_risk);
+                hunks.push(HunkRisk {
+                    file: path.clone(),
+                    old_start: hunk.old_start,
+                    old_lines: hunk.old_lines,
+                    new_start: hunk.new_start,
+                    new_lines: hunk.new_lines,
+                    score,
+                    level: get_risk_level(score, &thresholds),
+                });
+            }
+        }
+        let summary = UncommittedSummary {
+            total_hunks: hunks.len(),
+            max_hunk_score: hunks.iter().map(|h| h.score).fold(0.0, f64::max),
+            severity_counts: hunks.iter().map(|h| h.level).collect(),
+        };
+
+        let satd_added = count_satd_in_hunks(&files);
+        let risk_factors =
+            detect_risk_factors(&changes, &file_profiles, &satd_added, &self.diff_rules);
+
+        Ok(UncommittedResult {
+            generated_at: Utc::now(),
+            scope,
+            score,
+            level: get_risk_level(score, &thresholds),
+            lines_added,
+            lines_deleted,
+            files_modified,
+            factors,
+            recommendations,
+            file_risk,
+            risk_factors,
+            hunks,
+            summary,
+        })
+    }
+
+    /// Weighted contribution of each diff feature to the risk score.
+    fn diff_factors(
+        &self,
+        features: &CommitFeatures,
+        file_risk: &FileRiskSignals,
+        norm: &NormalizationStats,
+    ) -> HashMap<String, f64> {
         let mut factors = HashMap::new();
         factors.insert(
             "entropy".to_string(),
-            safe_normalize(entropy, norm.max_entropy) * self.weights.entropy,
+            safe_normalize(features.entropy, norm.max_entropy) * self.weights.entropy,
         );
         factors.insert(
             "lines_added".to_string(),
-            safe_normalize_int(lines_added, norm.max_lines_added) * self.weights.la,
+            safe_normalize_int(features.lines_added, norm.max_lines_added) * self.weights.la,
         );
         factors.insert(
             "lines_deleted".to_string(),
-            safe_normalize_int(lines_deleted, norm.max_lines_deleted) * self.weights.ld,
+            safe_normalize_int(features.lines_deleted, norm.max_lines_deleted) * self.weights.ld,
         );
         factors.insert(
             "num_files".to_string(),
-            safe_normalize_int(files_modified, norm.max_num_files) * self.weights.nf,
+            safe_normalize_int(features.num_files, norm.max_num_files) * self.weights.nf,
         );
         factors.insert(
             "commits".to_string(),
-            safe_normalize_int(commit_count, norm.max_unique_changes) * self.weights.nuc,
+            safe_normalize_int(features.unique_changes, norm.max_unique_changes) * self.weights.nuc,
         );
         factors.insert(
             "file_churn".to_string(),
@@ -1442,34 +1811,39 @@ impl Analyzer {
             "ownership_diffusion".to_string(),
             file_risk.ownership_diffusion * self.weights.ownership_diffusion,
         );
+        factors
+    }
+}
 
-        // Generate recommendations
-        let recommendations = generate_diff_recommendations(
-            lines_added,
-            lines_deleted,
-            files_modified,
-            commit_count,
-            score,
-            &factors,
-            &file_risk,
-        );
-
-        Ok(DiffResult {
-            generated_at: Utc::now(),
-            source_branch,
-            target_branch,
-            merge_base,
-            score,
-            level,
-            lines_added,
-            lines_deleted,
-            files_modified,
-            commits: commit_count,
-            factors,
-            recommendations,
-            file_risk,
+/// SATD markers in the added lines of each file.
+fn count_satd_in_hunks(files: &[FileHunks]) -> HashMap<String, usize> {
+    let satd = crate::analyzers::satd::Analyzer::new();
+    files
+        .iter()
+        .filter_map(|f| {
+            let language = Language::detect(&f.change.path)?;
+            let added: Vec<&str> = f
+                .hunks
+                .iter()
+                .flat_map(|h| h.added.iter().map(String::as_str))
+                .collect();
+            let file =
+                SourceFile::from_content(&f.change.path, language, added.join("\n").into_bytes());
+            let count = satd.analyze_file(&file).len();
+            (count > 0).then(|| (f.change.path.to_string_lossy().to_string(), count))
         })
+        .collect()
+}
+
+/// The local ref for a PR base branch, if one exists.
+fn pr_base_ref(repo_path: &Path, base: &str) -> Option<String> {
+    if base.is_empty() {
+        return None;
     }
+    let repo = GitRepo::open(repo_path).ok()?;
+    [format!("origin/{base}"), base.to_string()]
+        .into_iter()
+        .find(|candidate| repo.ref_exists(candidate))
 }
 
 /// Fixed thresholds for branch diff analysis.
@@ -1572,6 +1946,290 @@ fn build_churn_for_files(
     Ok(churn)
 }
 
+/// SATD markers added per file: count at HEAD minus count at the merge base.
+///
+/// Files that can't be read or parsed at either side count as zero there.
+fn count_added_satd(
+    repo_path: &Path,
+    merge_base: &str,
+    changes: &[FileChange],
+) -> HashMap<String, usize> {
+    let satd = crate::analyzers::satd::Analyzer::new();
+    let head = GitRepo::open(repo_path)
+        .ok()
+        .map(|r| r.at_ref(Some("HEAD")));
+    let base = GitRepo::open(repo_path)
+        .ok()
+        .map(|r| r.at_ref(Some(merge_base)));
+    let count_in = |repo: Option<&GitRepo>, path: &Path| -> usize {
+        let (Some(repo), Some(language)) = (repo, Language::detect(path)) else {
+            return 0;
+        };
+        repo.read_file(path)
+            .map(|content| {
+                let file = SourceFile::from_content(path, language, content);
+                satd.analyze_file(&file).len()
+            })
+            .unwrap_or(0)
+    };
+
+    changes
+        .iter()
+        .filter(|c| c.change_type != ChangeType::Deleted)
+        .filter_map(|c| {
+            let head = count_in(head.as_ref(), &c.path);
+            let base = count_in(base.as_ref(), &c.path);
+            (head > base).then(|| (c.path.to_string_lossy().to_string(), head - base))
+        })
+        .collect()
+}
+
+/// Function complexity at the merge base and at HEAD for each changed file.
+///
+/// Functions are matched by name, and by order among same-named functions
+/// in a file. Renamed files have no base path in `changes`, so their
+/// functions count as added; files that can't be read or parsed at a side
+/// have no functions there.
+fn complexity_deltas(
+    repo_path: &Path,
+    merge_base: &str,
+    changes: &[FileChange],
+    budget: Option<u32>,
+) -> ComplexityDelta {
+    use crate::analyzers::complexity;
+
+    let analyzer = complexity::Analyzer::default();
+    let head = GitRepo::open(repo_path)
+        .ok()
+        .map(|r| r.at_ref(Some("HEAD")));
+    let base = GitRepo::open(repo_path)
+        .ok()
+        .map(|r| r.at_ref(Some(merge_base)));
+    // Each function with its body text, so unchanged ones can be skipped.
+    let functions_in = |repo: Option<&GitRepo>, path: &Path| {
+        let Some(content) = repo.and_then(|r| r.read_file(path).ok()) else {
+            return Vec::new();
+        };
+        let text = String::from_utf8_lossy(&content).into_owned();
+        let lines: Vec<&str> = text.lines().collect();
+        let Ok(result) = analyzer.analyze_content(path, content) else {
+            return Vec::new();
+        };
+        result
+            .functions
+            .into_iter()
+            .map(|f| {
+                let start = (f.start_line as usize).saturating_sub(1).min(lines.len());
+                let end = (f.end_line as usize).clamp(start, lines.len());
+                let body = lines[start..end].join("\n");
+                (f, body)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut functions = Vec::new();
+    for change in changes {
+        if Language::detect(&change.path).is_none() {
+            continue;
+        }
+        let file = change.path.to_string_lossy().to_string();
+        let after = match change.change_type {
+            ChangeType::Deleted => Vec::new(),
+            _ => functions_in(head.as_ref(), &change.path),
+        };
+        let before = match change.change_type {
+            ChangeType::Added | ChangeType::Renamed => Vec::new(),
+            _ => functions_in(base.as_ref(), &change.path),
+        };
+
+        let mut unmatched: HashMap<&str, Vec<_>> = HashMap::new();
+        for (f, body) in &before {
+            unmatched
+                .entry(f.name.as_str())
+                .or_default()
+                .push((f, body));
+        }
+        for list in unmatched.values_mut() {
+            list.reverse();
+        }
+        for (f, body) in &after {
+            let old = unmatched.get_mut(f.name.as_str()).and_then(|l| l.pop());
+            if old.is_some_and(|(_, old_body)| old_body == body) {
+                continue;
+            }
+            let (change, cyclomatic_before, cognitive_before) = match old {
+                Some((o, _)) => (
+                    FunctionChange::Modified,
+                    o.metrics.cyclomatic,
+                    o.metrics.cognitive,
+                ),
+                None => (FunctionChange::Added, 0, 0),
+            };
+            functions.push(function_delta(
+                &file,
+                &f.name,
+                change,
+                f.start_line,
+                (cyclomatic_before, f.metrics.cyclomatic),
+                (cognitive_before, f.metrics.cognitive),
+            ));
+        }
+        for (o, _) in unmatched.into_values().flatten() {
+            functions.push(function_delta(
+                &file,
+                &o.name,
+                FunctionChange::Removed,
+                o.start_line,
+                (o.metrics.cyclomatic, 0),
+                (o.metrics.cognitive, 0),
+            ));
+        }
+    }
+
+    functions.sort_by(|a, b| {
+        b.cyclomatic_delta
+            .abs()
+            .cmp(&a.cyclomatic_delta.abs())
+            .then_with(|| a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+    });
+    let net_cyclomatic = functions.iter().map(|f| f.cyclomatic_delta).sum();
+    let net_cognitive = functions.iter().map(|f| f.cognitive_delta).sum();
+    ComplexityDelta {
+        functions,
+        net_cyclomatic,
+        net_cognitive,
+        budget,
+        over_budget: budget.is_some_and(|b| net_cyclomatic > i64::from(b)),
+    }
+}
+
+fn function_delta(
+    file: &str,
+    name: &str,
+    change: FunctionChange,
+    line: u32,
+    (cyclomatic_before, cyclomatic_after): (u32, u32),
+    (cognitive_before, cognitive_after): (u32, u32),
+) -> FunctionDelta {
+    FunctionDelta {
+        file: file.to_string(),
+        name: name.to_string(),
+        change,
+        line,
+        cyclomatic_before,
+        cyclomatic_after,
+        cyclomatic_delta: i64::from(cyclomatic_after) - i64::from(cyclomatic_before),
+        cognitive_before,
+        cognitive_after,
+        cognitive_delta: i64::from(cognitive_after) - i64::from(cognitive_before),
+    }
+}
+
+/// Apply the named diff rules to a branch's file changes.
+fn detect_risk_factors(
+    changes: &[FileChange],
+    profiles: &HashMap<String, FileRiskProfile>,
+    satd_added: &HashMap<String, usize>,
+    rules: &DiffRules,
+) -> Vec<DiffRiskFactor> {
+    let mut factors = Vec::new();
+    let path_of = |c: &FileChange| c.path.to_string_lossy().to_string();
+
+    let changes_production = changes.iter().any(|c| {
+        c.change_type != ChangeType::Deleted
+            && !is_test_file(&c.path)
+            && Language::detect(&c.path).is_some()
+    });
+    let mut deleted_tests: Vec<String> = changes
+        .iter()
+        .filter(|c| is_test_file(&c.path))
+        .filter(|c| {
+            c.change_type == ChangeType::Deleted
+                || c.deletions.saturating_sub(c.additions) >= rules.test_deletion_lines
+        })
+        .map(path_of)
+        .collect();
+    if changes_production && !deleted_tests.is_empty() {
+        deleted_tests.sort();
+        factors.push(DiffRiskFactor {
+            name: "deletes_tests".to_string(),
+            description: format!(
+                "Removes tests in {} file(s) while changing production code",
+                deleted_tests.len()
+            ),
+            files: deleted_tests,
+        });
+    }
+
+    if !satd_added.is_empty() {
+        let mut files: Vec<String> = satd_added.keys().cloned().collect();
+        files.sort();
+        factors.push(DiffRiskFactor {
+            name: "adds_satd".to_string(),
+            description: format!(
+                "Adds {} TODO/FIXME/HACK marker(s)",
+                satd_added.values().sum::<usize>()
+            ),
+            files,
+        });
+    }
+
+    let live: Vec<(String, &FileRiskProfile)> = changes
+        .iter()
+        .filter(|c| c.change_type != ChangeType::Deleted)
+        .filter_map(|c| {
+            let path = path_of(c);
+            profiles.get(&path).map(|p| (path, p))
+        })
+        .collect();
+
+    let mut hotspots: Vec<String> = live
+        .iter()
+        .filter(|(_, p)| {
+            p.churn_score >= HIGH_CHURN_THRESHOLD && p.avg_cyclomatic >= HIGH_COMPLEXITY_THRESHOLD
+        })
+        .map(|(path, _)| path.clone())
+        .collect();
+    if hotspots.len() > rules.max_hotspots {
+        hotspots.sort();
+        factors.push(DiffRiskFactor {
+            name: "touches_hotspots".to_string(),
+            description: format!(
+                "Touches {} hotspots (high churn and complexity), more than {}",
+                hotspots.len(),
+                rules.max_hotspots
+            ),
+            files: hotspots,
+        });
+    }
+
+    // New files are trivially single-author; only pre-existing ones count.
+    let added: HashSet<String> = changes
+        .iter()
+        .filter(|c| c.change_type == ChangeType::Added)
+        .map(path_of)
+        .collect();
+    let mut single_owner: Vec<String> = live
+        .iter()
+        .filter(|(path, p)| !added.contains(path) && p.ownership_concentration >= 1.0)
+        .map(|(path, _)| path.clone())
+        .collect();
+    if !single_owner.is_empty() {
+        single_owner.sort();
+        factors.push(DiffRiskFactor {
+            name: "bus_factor_one".to_string(),
+            description: format!(
+                "Modifies {} file(s) with a single author (bus factor 1)",
+                single_owner.len()
+            ),
+            files: single_owner,
+        });
+    }
+
+    factors
+}
+
 fn generate_diff_recommendations(
     lines_added: i32,
     lines_deleted: i32,
@@ -1648,9 +2306,41 @@ fn generate_diff_recommendations(
     recs
 }
 
+fn generate_pr_recommendations(
+    pr: &PullRequest,
+    risk: &DiffResult,
+    files_not_in_diff: &[String],
+) -> Vec<String> {
+    let mut recs = Vec::new();
+
+    if pr.draft {
+        recs.push("PR is a draft - keep it out of the merge queue".to_string());
+    }
+
+    match pr.review_state {
+        ReviewState::ChangesRequested => {
+            recs.push("Changes were requested - resolve review feedback before merging".to_string())
+        }
+        ReviewState::Pending if risk.level == RiskLevel::High => recs
+            .push("High-risk PR has no approving review - require one before merging".to_string()),
+        _ => {}
+    }
+
+    if !files_not_in_diff.is_empty() {
+        recs.push(format!(
+            "{} PR file(s) missing from the local diff - fetch the PR head before trusting this \
+             assessment",
+            files_not_in_diff.len()
+        ));
+    }
+
+    recs
+}
+
 #[cfg(test)]
 mod diff_tests {
     use super::*;
+    use crate::core::Severity;
 
     #[test]
     fn test_diff_normalization() {
@@ -1773,4 +2463,258 @@ mod diff_tests {
         let recs = generate_diff_recommendations(50, 20, 3, 2, 0.2, &factors, &file_risk);
         assert!(recs.iter().any(|r| r.contains("diffuse ownership")));
     }
+
+    fn change(path: &str, additions: u32, deletions: u32, change_type: ChangeType) -> FileChange {
+        FileChange {
+            path: path.into(),
+            additions,
+            deletions,
+            change_type,
+        }
+    }
+
+    fn profile(avg_cyclomatic: f64, churn_score: f64, concentration: f64) -> FileRiskProfile {
+        FileRiskProfile {
+            avg_cyclomatic,
+            churn_score,
+            ownership_concentration: concentration,
+        }
+    }
+
+    fn names(factors: &[DiffRiskFactor]) -> Vec<&str> {
+        factors.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_risk_factor_deletes_tests() {
+        let rules = DiffRules::default();
+        let changes = vec![
+            change("src/lib.rs", 10, 2, ChangeType::Modified),
+            change("tests/api_test.rs", 0, 40, ChangeType::Deleted),
+            change("src/foo_test.go", 1, 30, ChangeType::Modified),
+            change("src/bar_test.go", 5, 10, ChangeType::Modified),
+        ];
+        let factors = detect_risk_factors(&changes, &HashMap::new(), &HashMap::new(), &rules);
+        assert_eq!(names(&factors), vec!["deletes_tests"]);
+        assert_eq!(
+            factors[0].files,
+            vec![
+                "src/foo_test.go".to_string(),
+                "tests/api_test.rs".to_string()
+            ]
+        );
+
+        // Deleting tests alone (no production change) is not flagged.
+        let factors = detect_risk_factors(&changes[1..], &HashMap::new(), &HashMap::new(), &rules);
+        assert!(factors.is_empty());
+    }
+
+    #[test]
+    fn test_risk_factor_adds_satd() {
+        let mut satd = HashMap::new();
+        satd.insert("src/b.rs".to_string(), 2);
+        satd.insert("src/a.rs".to_string(), 1);
+        let factors = detect_risk_factors(&[], &HashMap::new(), &satd, &DiffRules::default());
+        assert_eq!(names(&factors), vec!["adds_satd"]);
+        assert!(factors[0].description.contains("3 TODO"));
+        assert_eq!(factors[0].files, vec!["src/a.rs", "src/b.rs"]);
+    }
+
+    #[test]
+    fn test_risk_factor_touches_hotspots() {
+        let changes = vec![
+            change("a.rs", 1, 1, ChangeType::Modified),
+            change("b.rs", 1, 1, ChangeType::Modified),
+            change("c.rs", 1, 1, ChangeType::Modified),
+            change("d.rs", 1, 1, ChangeType::Modified),
+        ];
+        let mut profiles = HashMap::new();
+        profiles.insert("a.rs".to_string(), profile(12.0, 0.9, 0.5));
+        profiles.insert("b.rs".to_string(), profile(15.0, 0.8, 0.5));
+        profiles.insert("c.rs".to_string(), profile(11.0, 1.0, 0.5));
+        profiles.insert("d.rs".to_string(), profile(2.0, 1.0, 0.5));
+
+        let rules = DiffRules {
+            max_hotspots: 3,
+            ..Default::default()
+        };
+        assert!(detect_risk_factors(&changes, &profiles, &HashMap::new(), &rules).is_empty());
+
+        let factors =
+            detect_risk_factors(&changes, &profiles, &HashMap::new(), &DiffRules::default());
+        assert_eq!(names(&factors), vec!["touches_hotspots"]);
+        assert_eq!(factors[0].files, vec!["a.rs", "b.rs", "c.rs"]);
+    }
+
+    #[test]
+    fn test_risk_factor_bus_factor_one_skips_new_files() {
+        let changes = vec![
+            change("old.rs", 3, 1, ChangeType::Modified),
+            change("new.rs", 50, 0, ChangeType::Added),
+            change("shared.rs", 3, 1, ChangeType::Modified),
+        ];
+        let mut profiles = HashMap::new();
+        profiles.insert("old.rs".to_string(), profile(1.0, 0.1, 1.0));
+        profiles.insert("new.rs".to_string(), profile(1.0, 0.1, 1.0));
+        profiles.insert("shared.rs".to_string(), profile(1.0, 0.1, 0.6));
+
+        let factors =
+            detect_risk_factors(&changes, &profiles, &HashMap::new(), &DiffRules::default());
+        assert_eq!(names(&factors), vec!["bus_factor_one"]);
+        assert_eq!(factors[0].files, vec!["old.rs"]);
+    }
+
+    #[test]
+    fn test_analyze_uncommitted_separates_staged_and_worktree() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "a@example.com"]);
+        git(&["config", "user.name", "Alice"]);
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "first"]);
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n// TODO: tidy\nfn b() {}\n").unwrap();
+        git(&["add", "."]);
+        std::fs::write(
+            dir.join("lib.rs"),
+            "fn a() {}\n// TODO: tidy\nfn b() {}\nfn c() {}\n",
+        )
+        .unwrap();
+
+        let analyzer = Analyzer::default();
+        let staged = analyzer
+            .analyze_uncommitted(dir, DiffScope::Staged)
+            .unwrap();
+        assert_eq!((staged.lines_added, staged.lines_deleted), (2, 0));
+        assert_eq!(staged.hunks.len(), 1);
+        assert_eq!(
+            (staged.hunks[0].new_start, staged.hunks[0].new_lines),
+            (2, 2)
+        );
+        assert_eq!(staged.hunks[0].file, "lib.rs");
+        assert_eq!(names(&staged.risk_factors), vec!["adds_satd"]);
+        assert_eq!(staged.summary.severity_counts.at_least(Severity::Low), 1);
+
+        let worktree = analyzer
+            .analyze_uncommitted(dir, DiffScope::Worktree)
+            .unwrap();
+        assert_eq!(worktree.lines_added, 1);
+        assert_eq!(worktree.hunks[0].new_start, 4);
+        assert!(worktree.risk_factors.is_empty());
+        assert_eq!(worktree.hunks_above(1.0).count(), 0);
+    }
+
+    #[test]
+    fn test_complexity_deltas_compare_functions_with_merge_base() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "a@example.com"]);
+        git(&["config", "user.name", "Alice"]);
+        std::fs::write(
+            dir.join("lib.py"),
+            "def same():\n    return 1\n\n\ndef grow(x):\n    return x\n\n\n\
+             def gone(x):\n    if x:\n        return 1\n    return 2\n",
+        )
+        .unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "base"]);
+        let base = get_merge_base(dir, "HEAD", "HEAD").unwrap();
+        std::fs::write(
+            dir.join("lib.py"),
+            "def same():\n    return 1\n\n\ndef grow(x):\n    if x > 1:\n        \
+             return 1\n    if x < 0:\n        return 2\n    return x\n\n\n\
+             def fresh(x):\n    return x\n",
+        )
+        .unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "change"]);
+
+        let changes = GitRepo::open(dir)
+            .unwrap()
+            .diff_stats(&base, "HEAD")
+            .unwrap();
+        let delta = complexity_deltas(dir, &base, &changes, Some(0));
+        let summary: Vec<(&str, FunctionChange, i64)> = delta
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.change, f.cyclomatic_delta))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("grow", FunctionChange::Modified, 2),
+                ("gone", FunctionChange::Removed, -2),
+                ("fresh", FunctionChange::Added, 1),
+            ]
+        );
+        assert_eq!(delta.functions[0].cyclomatic_before, 1);
+        assert_eq!(delta.functions[0].cyclomatic_after, 3);
+        assert_eq!(delta.net_cyclomatic, 1);
+        assert!(delta.over_budget);
+        assert!(!complexity_deltas(dir, &base, &changes, Some(1)).over_budget);
+    }
+
+    #[test]
+    fn test_pr_recommendations_join_review_and_risk() {
+        let risk = DiffResult {
+            generated_at: Utc::now(),
+            source_branch: "feature".to_string(),
+            target_branch: "origin/main".to_string(),
+            merge_base: "abc".to_string(),
+            score: 0.8,
+            level: RiskLevel::High,
+            lines_added: 500,
+            lines_deleted: 0,
+            files_modified: 2,
+            commits: 1,
+            factors: HashMap::new(),
+            recommendations: vec![],
+            file_risk: FileRiskSignals::default(),
+            risk_factors: vec![],
+            complexity: ComplexityDelta::default(),
+        };
+        let mut pr = PullRequest {
+            forge: crate::git::Forge::GitHub,
+            number: 1,
+            title: "Big change".to_string(),
+            url: String::new(),
+            author: "alice".to_string(),
+            base_branch: "main".to_string(),
+            head_branch: "feature".to_string(),
+            draft: false,
+            labels: vec![],
+            files: vec!["a.rs".to_string()],
+            review_state: ReviewState::Pending,
+            approvals: 0,
+        };
+
+        let recs = generate_pr_recommendations(&pr, &risk, &[]);
+        assert_eq!(recs.len(), 1);
+        assert!(recs[0].contains("no approving review"));
+
+        pr.review_state = ReviewState::Approved;
+        pr.draft = true;
+        let recs = generate_pr_recommendations(&pr, &risk, &["b.rs".to_string()]);
+        assert_eq!(recs.len(), 2);
+        assert!(recs[0].contains("draft"));
+        assert!(recs[1].starts_with("1 PR file(s) missing"));
+    }
 }
@@ -15,24 +15,49 @@
 //! line changes, as frequent small changes often indicate instability.
 //! The original Nagappan & Ball research uses relative churn (churn / LOC)
 //! rather than absolute weights.
+//!
+//! # Off-Hours Changes
+//!
+//! With `[churn.off_hours]` enabled, each file and each configured team
+//! also reports the share of commits made after hours or at weekends, read
+//! in the author's team's UTC offset. Areas changed mostly off-hours are
+//! often being firefought.
+//!
+//! # Warm Starts
+//!
+//! With a state directory, the window's commits and their numstat are saved
+//! next to the result. A warm-started run takes the numstat of files no
+//! commit has touched since from there, drops commits that have aged out of
+//! the window, and asks git only about the touched files.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 #[cfg(test)]
 use std::io::{BufRead, BufReader};
 
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Timelike, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 
+use crate::config::OffHoursConfig;
+use crate::core::warm_start::save_state;
 use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
-use crate::git::GitRepo;
+use crate::git::{Commit, GitRepo, PathFilter};
+
+/// Team reported for authors in no configured team.
+const NO_TEAM: &str = "(no team)";
+
+/// Most touched files a warm start asks git about; past this, reading the
+/// whole window is as quick.
+const MAX_WARM_PATHS: usize = 1000;
 
 /// Churn analyzer using git log.
 pub struct Analyzer {
     /// Number of days of history to analyze.
     days: u32,
+    /// Where to save the window's numstat for a later warm start.
+    state_dir: Option<PathBuf>,
 }
 
 impl Default for Analyzer {
@@ -44,7 +69,10 @@ impl Default for Analyzer {
 impl Analyzer {
     /// Create a new churn analyzer with default 30-day window.
     pub fn new() -> Self {
-        Self { days: 30 }
+        Self {
+            days: 30,
+            state_dir: None,
+        }
     }
 
     /// Set the number of days to analyze.
@@ -52,6 +80,67 @@ impl Analyzer {
         self.days = days;
         self
     }
+
+    /// Save the window's numstat to the data directory `dir`, for a later
+    /// run to warm-start from.
+    pub fn with_state_dir(mut self, dir: &Path) -> Self {
+        self.state_dir = Some(dir.to_path_buf());
+        self
+    }
+
+    /// The window's commits rebuilt from the previous run: its numstat for
+    /// files untouched since, without the commits that have aged out of the
+    /// window, plus a fresh log of the touched files. `None` without a
+    /// usable previous run or when too many files were touched.
+    fn warm_commits(
+        &self,
+        ctx: &AnalysisContext<'_>,
+        repo: &GitRepo,
+        since: Option<&str>,
+        filter: &PathFilter,
+    ) -> Result<Option<Vec<Commit>>> {
+        let Some(warm) = ctx.warm_start.as_ref() else {
+            return Ok(None);
+        };
+        let Some(previous) = warm.previous_state::<Vec<Commit>>("churn") else {
+            return Ok(None);
+        };
+        let touched: Vec<&Path> = warm.changed_paths().collect();
+        if touched.len() > MAX_WARM_PATHS {
+            return Ok(None);
+        }
+
+        let cutoff =
+            (self.days != u32::MAX).then(|| Utc::now().timestamp() - i64::from(self.days) * 86_400);
+        let mut commits: Vec<Commit> = previous
+            .into_iter()
+            .filter(|commit| cutoff.is_none_or(|cutoff| commit.timestamp >= cutoff))
+            .filter_map(|mut commit| {
+                commit.files.retain(|file| !warm.changed(&file.path));
+                (!commit.files.is_empty()).then_some(commit)
+            })
+            .collect();
+        if touched.is_empty() {
+            return Ok(Some(commits));
+        }
+
+        let mut by_sha: HashMap<String, usize> = commits
+            .iter()
+            .enumerate()
+            .map(|(i, commit)| (commit.sha.clone(), i))
+            .collect();
+        for commit in repo.log_with_stats_for_paths(since, &touched, filter)? {
+            match by_sha.get(&commit.sha) {
+                Some(&i) => commits[i].files.extend(commit.files),
+                None => {
+                    by_sha.insert(commit.sha.clone(), commits.len());
+                    commits.push(commit);
+                }
+            }
+        }
+        commits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(Some(commits))
+    }
 }
 
 impl AnalyzerTrait for Analyzer {
@@ -78,7 +167,9 @@ impl AnalyzerTrait for Analyzer {
             .ok_or_else(|| Error::git("Invalid repository path"))?;
 
         // Open repository with gix
-        let repo = GitRepo::open(git_path)?;
+        let repo = GitRepo::open(git_path)?
+            .at_ref(ctx.config.git.at_ref.as_deref())
+            .with_identities(&ctx.config.git);
 
         // Calculate since date (u32::MAX means "all history" -- no time limit)
         let since = if self.days == u32::MAX {
@@ -87,14 +178,26 @@ impl AnalyzerTrait for Analyzer {
             Some(format!("{} days", self.days))
         };
 
-        // Get commits with file changes using gix
-        let commits = repo.log_with_stats(since.as_deref(), None)?;
+        // Get commits with file changes, letting git skip paths outside the
+        // analysis root and glob filters.
+        let filter = PathFilter::from_context(ctx, repo.root());
+        let commits = match self.warm_commits(ctx, &repo, since.as_deref(), &filter)? {
+            Some(commits) => commits,
+            None => ctx.log_with_stats(&repo, since.as_deref(), &filter)?,
+        };
+        if let Some(dir) = &self.state_dir {
+            if let Err(e) = save_state(dir, self.name(), &commits) {
+                tracing::warn!("Failed to save churn state: {e}");
+            }
+        }
+        let work_hours = WorkHours::from_context(ctx)?;
 
         // Convert to file metrics
-        let file_metrics = commits_to_file_metrics(&commits);
+        let file_metrics = commits_to_file_metrics(&commits, work_hours.as_ref());
 
         // Build analysis from metrics
-        let analysis = build_analysis(file_metrics, repo_root, self.days);
+        let mut analysis = build_analysis(file_metrics, repo_root, self.days);
+        analysis.summary.off_hours = work_hours.map(|w| off_hours_summary(&commits, &w));
 
         tracing::info!(
             "Churn analysis completed in {:?}: {} files",
@@ -106,13 +209,18 @@ impl AnalyzerTrait for Analyzer {
     }
 }
 
-/// Convert commits to file metrics map.
-fn commits_to_file_metrics(commits: &[crate::git::Commit]) -> HashMap<String, FileMetrics> {
+/// Convert commits to file metrics map, with off-hours counts when
+/// `work_hours` is given.
+fn commits_to_file_metrics(
+    commits: &[Commit],
+    work_hours: Option<&WorkHours>,
+) -> HashMap<String, FileMetrics> {
     let mut file_metrics: HashMap<String, FileMetrics> = HashMap::new();
 
     for commit in commits {
         let author = commit.author.clone();
         let timestamp = Utc.timestamp_opt(commit.timestamp, 0).single();
+        let time = work_hours.map(|w| w.classify(&commit.author, &commit.email, commit.timestamp));
 
         for file_change in &commit.files {
             let path_str = file_change.path.to_string_lossy().to_string();
@@ -135,12 +243,18 @@ fn commits_to_file_metrics(commits: &[crate::git::Commit]) -> HashMap<String, Fi
                     churn_rate: 0.0,
                     change_frequency: 0.0,
                     days_active: 0,
+                    off_hours: None,
                 });
 
             fm.commits += 1;
             *fm.author_counts.entry(author.clone()).or_insert(0) += 1;
             fm.lines_added += file_change.additions;
             fm.lines_deleted += file_change.deletions;
+            if let Some(time) = time {
+                fm.off_hours
+                    .get_or_insert_with(Default::default)
+                    .record(time);
+            }
 
             // Update time range
             if let Some(t) = timestamp {
@@ -158,9 +272,179 @@ fn commits_to_file_metrics(commits: &[crate::git::Commit]) -> HashMap<String, Fi
         }
     }
 
+    for off_hours in file_metrics
+        .values_mut()
+        .filter_map(|fm| fm.off_hours.as_mut())
+    {
+        off_hours.finish();
+    }
     file_metrics
 }
 
+/// Off-hours shares across all commits and per configured team.
+fn off_hours_summary(commits: &[Commit], work_hours: &WorkHours) -> OffHoursSummary {
+    let mut overall = OffHours::default();
+    let mut teams: BTreeMap<&str, OffHours> = BTreeMap::new();
+    for commit in commits {
+        let time = work_hours.classify(&commit.author, &commit.email, commit.timestamp);
+        overall.record(time);
+        if work_hours.has_teams() {
+            let team = work_hours
+                .team(&commit.author, &commit.email)
+                .unwrap_or(NO_TEAM);
+            teams.entry(team).or_default().record(time);
+        }
+    }
+    overall.finish();
+    OffHoursSummary {
+        overall,
+        teams: teams
+            .into_iter()
+            .map(|(team, mut off_hours)| {
+                off_hours.finish();
+                TeamOffHours {
+                    team: team.to_string(),
+                    off_hours,
+                }
+            })
+            .collect(),
+    }
+}
+
+/// When a commit was made relative to working hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitTime {
+    WorkHours,
+    /// A weekday, outside working hours.
+    AfterHours,
+    Weekend,
+}
+
+/// Working hours from `[churn.off_hours]`, to classify commit times.
+#[derive(Debug, Clone)]
+pub struct WorkHours {
+    offset: FixedOffset,
+    start: u32,
+    end: u32,
+    weekend: Vec<Weekday>,
+    /// Team name and UTC offset by lowercased member name or email.
+    members: HashMap<String, (String, FixedOffset)>,
+}
+
+impl WorkHours {
+    /// Working hours from the context's config, if off-hours metrics are
+    /// enabled.
+    pub fn from_context(ctx: &AnalysisContext<'_>) -> Result<Option<Self>> {
+        let config = &ctx.config.churn.off_hours;
+        config
+            .enabled
+            .then(|| Self::from_config(config))
+            .transpose()
+    }
+
+    pub fn from_config(config: &OffHoursConfig) -> Result<Self> {
+        if config.workday_start >= config.workday_end || config.workday_end > 24 {
+            return Err(Error::config(format!(
+                "churn.off_hours: workday_start ({}) must be before workday_end ({}), within 0-24",
+                config.workday_start, config.workday_end
+            )));
+        }
+        let offset = parse_offset(&config.timezone)?;
+        let weekend = config
+            .weekend
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>().map_err(|_| {
+                    Error::config(format!("churn.off_hours: unknown weekend day {day:?}"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut members = HashMap::new();
+        for team in &config.teams {
+            let team_offset = match &team.timezone {
+                Some(timezone) => parse_offset(timezone)?,
+                None => offset,
+            };
+            for member in &team.members {
+                members
+                    .entry(member.to_lowercase())
+                    .or_insert_with(|| (team.name.clone(), team_offset));
+            }
+        }
+        Ok(Self {
+            offset,
+            start: config.workday_start,
+            end: config.workday_end,
+            weekend,
+            members,
+        })
+    }
+
+    /// Whether any teams are configured.
+    pub fn has_teams(&self) -> bool {
+        !self.members.is_empty()
+    }
+
+    /// The team of the author with this name or email.
+    pub fn team(&self, author: &str, email: &str) -> Option<&str> {
+        self.member(author, email).map(|(team, _)| team.as_str())
+    }
+
+    fn member(&self, author: &str, email: &str) -> Option<&(String, FixedOffset)> {
+        self.members
+            .get(&email.to_lowercase())
+            .or_else(|| self.members.get(&author.to_lowercase()))
+    }
+
+    /// Classify a commit at `timestamp` (Unix seconds) by its author's
+    /// local time.
+    pub fn classify(&self, author: &str, email: &str, timestamp: i64) -> CommitTime {
+        let offset = self
+            .member(author, email)
+            .map_or(self.offset, |&(_, offset)| offset);
+        let Some(local) = offset.timestamp_opt(timestamp, 0).single() else {
+            return CommitTime::WorkHours;
+        };
+        if self.weekend.contains(&local.weekday()) {
+            CommitTime::Weekend
+        } else if (self.start..self.end).contains(&local.hour()) {
+            CommitTime::WorkHours
+        } else {
+            CommitTime::AfterHours
+        }
+    }
+}
+
+/// Parse a UTC offset such as `+05:30`, `-0800`, `+01` or `UTC`.
+fn parse_offset(value: &str) -> Result<FixedOffset> {
+    let invalid = || {
+        Error::config(format!(
+            "churn.off_hours: invalid UTC offset {value:?}, expected e.g. \"-05:00\""
+        ))
+    };
+    let trimmed = value.trim();
+    if trimmed.eq_ignore_ascii_case("utc") || trimmed == "Z" {
+        return FixedOffset::east_opt(0).ok_or_else(invalid);
+    }
+    let sign = match trimmed.chars().next() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return Err(invalid()),
+    };
+    let digits: String = trimmed[1..].chars().filter(|&c| c != ':').collect();
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (&digits[..], "0"),
+        4 => digits.split_at(2),
+        _ => return Err(invalid()),
+    };
+    let seconds = hours.parse::<i32>().map_err(|_| invalid())? * 3600
+        + minutes.parse::<i32>().map_err(|_| invalid())? * 60;
+    FixedOffset::east_opt(sign * seconds).ok_or_else(invalid)
+}
+
 /// Parse git log --numstat output (kept for tests).
 #[cfg(test)]
 fn parse_git_log_numstat(output: &[u8]) -> Result<HashMap<String, FileMetrics>> {
@@ -224,6 +508,7 @@ fn parse_git_log_numstat(output: &[u8]) -> Result<HashMap<String, FileMetrics>>
                 churn_rate: 0.0,
                 change_frequency: 0.0,
                 days_active: 0,
+                off_hours: None,
             });
 
         fm.commits += 1;
@@ -327,6 +612,7 @@ fn build_analysis(
         hotspot_files: Vec::new(),
         stable_files: Vec::new(),
         author_contributions,
+        off_hours: None,
     };
 
     calculate_statistics(&mut summary, &files);
@@ -470,6 +756,62 @@ pub struct FileMetrics {
     pub change_frequency: f64,
     #[serde(skip_serializing_if = "is_zero_u32")]
     pub days_active: u32,
+    /// After-hours and weekend commits, when enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_hours: Option<OffHours>,
+}
+
+/// Commits made after hours or at weekends.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OffHours {
+    pub commits: u32,
+    pub after_hours_commits: u32,
+    pub weekend_commits: u32,
+    /// Share of commits made on weekdays outside working hours.
+    pub after_hours_ratio: f64,
+    /// Share of commits made at weekends.
+    pub weekend_ratio: f64,
+}
+
+impl OffHours {
+    fn record(&mut self, time: CommitTime) {
+        self.commits += 1;
+        match time {
+            CommitTime::WorkHours => {}
+            CommitTime::AfterHours => self.after_hours_commits += 1,
+            CommitTime::Weekend => self.weekend_commits += 1,
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.commits > 0 {
+            self.after_hours_ratio = self.after_hours_commits as f64 / self.commits as f64;
+            self.weekend_ratio = self.weekend_commits as f64 / self.commits as f64;
+        }
+    }
+
+    /// Share of commits made outside working hours, weekends included.
+    pub fn ratio(&self) -> f64 {
+        self.after_hours_ratio + self.weekend_ratio
+    }
+}
+
+/// Off-hours commits across the analyzed history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OffHoursSummary {
+    #[serde(flatten)]
+    pub overall: OffHours,
+    /// Per configured team, with authors in no team under `(no team)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub teams: Vec<TeamOffHours>,
+}
+
+/// Off-hours commits by one team.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamOffHours {
+    pub team: String,
+    #[serde(flatten)]
+    pub off_hours: OffHours,
 }
 
 fn is_zero_u32(v: &u32) -> bool {
@@ -497,6 +839,8 @@ pub struct Summary {
     pub hotspot_files: Vec<String>,
     pub stable_files: Vec<String>,
     pub author_contributions: HashMap<String, u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_hours: Option<OffHoursSummary>,
 }
 
 /// Full churn analysis result.
@@ -544,6 +888,7 @@ mod tests {
             churn_rate: 0.0,
             change_frequency: 0.0,
             days_active: 0,
+            off_hours: None,
         };
 
         calculate_churn_score(&mut fm, 10, 150);
@@ -606,6 +951,7 @@ mod tests {
             churn_rate: 0.0,
             change_frequency: 0.0,
             days_active: 0,
+            off_hours: None,
         }
     }
 
@@ -656,6 +1002,104 @@ mod tests {
         assert_eq!(analysis.summary.author_contributions.len(), 2);
     }
 
+    fn off_hours_config() -> OffHoursConfig {
+        OffHoursConfig {
+            enabled: true,
+            timezone: "-05:00".to_string(),
+            teams: vec![crate::config::OffHoursTeam {
+                name: "platform".to_string(),
+                members: vec!["kim@example.com".to_string()],
+                timezone: Some("+09:00".to_string()),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_work_hours_classifies_in_team_timezone() {
+        let hours = WorkHours::from_config(&off_hours_config()).unwrap();
+        // Wednesday 2024-01-10 15:00 UTC: 10:00 in New York, 00:00 in Seoul.
+        let wednesday = 1_704_898_800;
+        assert_eq!(
+            hours.classify("Ann", "ann@example.com", wednesday),
+            CommitTime::WorkHours
+        );
+        assert_eq!(
+            hours.classify("Kim", "KIM@example.com", wednesday),
+            CommitTime::AfterHours
+        );
+        // Saturday 2024-01-13 15:00 UTC.
+        let saturday = wednesday + 3 * 86_400;
+        assert_eq!(
+            hours.classify("Ann", "ann@example.com", saturday),
+            CommitTime::Weekend
+        );
+        assert_eq!(hours.team("Kim", "kim@example.com"), Some("platform"));
+        assert_eq!(hours.team("Ann", "ann@example.com"), None);
+
+        for (timezone, seconds) in [("UTC", 0), ("+0530", 19_800), ("-08", -28_800)] {
+            assert_eq!(parse_offset(timezone).unwrap().local_minus_utc(), seconds);
+        }
+        assert!(parse_offset("EST").is_err());
+        let mut bad = off_hours_config();
+        bad.workday_start = 18;
+        bad.workday_end = 9;
+        assert!(WorkHours::from_config(&bad).is_err());
+        bad = off_hours_config();
+        bad.weekend = vec!["caturday".to_string()];
+        assert!(WorkHours::from_config(&bad).is_err());
+    }
+
+    #[test]
+    fn test_off_hours_ratios_per_file_and_team() {
+        let hours = WorkHours::from_config(&off_hours_config()).unwrap();
+        let wednesday = 1_704_898_800;
+        let commit = |email: &str, timestamp: i64, paths: &[&str]| Commit {
+            sha: String::new(),
+            author: email.to_string(),
+            email: email.to_string(),
+            timestamp,
+            message: String::new(),
+            files: paths
+                .iter()
+                .map(|path| crate::git::FileChange {
+                    path: path.into(),
+                    additions: 1,
+                    deletions: 0,
+                    change_type: crate::git::ChangeType::Modified,
+                })
+                .collect(),
+        };
+        let commits = vec![
+            commit("ann@example.com", wednesday, &["a.rs", "b.rs"]),
+            commit("kim@example.com", wednesday, &["a.rs"]),
+            commit("ann@example.com", wednesday + 3 * 86_400, &["a.rs"]),
+            commit("ann@example.com", wednesday + 86_400, &["a.rs"]),
+        ];
+
+        let metrics = commits_to_file_metrics(&commits, Some(&hours));
+        let a = metrics["a.rs"].off_hours.as_ref().unwrap();
+        assert_eq!(
+            (a.commits, a.after_hours_commits, a.weekend_commits),
+            (4, 1, 1)
+        );
+        assert_eq!(a.ratio(), 0.5);
+        assert_eq!(metrics["b.rs"].off_hours.as_ref().unwrap().ratio(), 0.0);
+        assert!(commits_to_file_metrics(&commits, None)["a.rs"]
+            .off_hours
+            .is_none());
+
+        let summary = off_hours_summary(&commits, &hours);
+        assert_eq!(summary.overall.commits, 4);
+        let teams: Vec<(&str, u32)> = summary
+            .teams
+            .iter()
+            .map(|t| (t.team.as_str(), t.off_hours.commits))
+            .collect();
+        assert_eq!(teams, vec![(NO_TEAM, 3), ("platform", 1)]);
+        assert_eq!(summary.teams[1].off_hours.after_hours_ratio, 1.0);
+    }
+
     #[test]
     fn test_build_analysis_multiple_files() {
         let mut metrics = HashMap::new();
@@ -912,4 +1356,148 @@ mod tests {
         calculate_churn_score(&mut fm, 0, 0);
         assert_eq!(fm.churn_score, 0.0);
     }
+
+    #[test]
+    fn test_warm_start_reuses_numstat_of_untouched_files() {
+        use crate::config::Config;
+        use crate::core::{FileSet, WarmStart};
+        use crate::manifest::Manifest;
+        use crate::test_support::{git, init_repo};
+        use std::sync::Arc;
+
+        let repo = tempfile::TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::write(repo.path().join("a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(repo.path().join("b.rs"), "fn b() {}\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-q", "-m", "init"]);
+        let config = Config::default();
+        let files = FileSet::from_path(repo.path(), &config).unwrap();
+        let analyze = |analyzer: Analyzer, warm: Option<&Arc<WarmStart>>| {
+            let mut ctx =
+                AnalysisContext::new(&files, &config, Some(repo.path())).with_git_path(repo.path());
+            if let Some(warm) = warm {
+                ctx = ctx.with_warm_start(Arc::clone(warm));
+            }
+            analyzer.with_days(365).analyze(&ctx).unwrap()
+        };
+
+        // Save a previous run, with a recognizable count for a.rs
+        let data = tempfile::TempDir::new().unwrap();
+        analyze(Analyzer::new().with_state_dir(data.path()), None);
+        let state_path = crate::core::warm_start::state_path(data.path(), "churn");
+        let mut state: Vec<Commit> =
+            serde_json::from_slice(&std::fs::read(&state_path).unwrap()).unwrap();
+        assert_eq!(state.len(), 1);
+        for file in &mut state[0].files {
+            file.additions = 40;
+        }
+        save_state(data.path(), "churn", &state).unwrap();
+        let manifest = Manifest::build(repo.path(), &files, &config).unwrap();
+        let metadata = serde_json::json!({ "since": "1y", "manifest": manifest });
+        std::fs::write(data.path().join("metadata.json"), metadata.to_string()).unwrap();
+
+        std::fs::write(repo.path().join("b.rs"), "fn b() { a() }\n").unwrap();
+        git(repo.path(), &["commit", "-q", "-am", "change b"]);
+        let current = Manifest::build(repo.path(), &files, &config).unwrap();
+        let git_repo = GitRepo::open(repo.path()).unwrap();
+        let warm = Arc::new(WarmStart::load(data.path(), &git_repo, &current, "1y").unwrap());
+
+        let analysis = analyze(Analyzer::new(), Some(&warm));
+        let churn = |name: &str| {
+            let file = analysis.files.iter().find(|f| f.relative_path == name);
+            let file = file.unwrap();
+            (file.commits, file.lines_added)
+        };
+        assert_eq!(churn("a.rs"), (1, 40));
+        assert_eq!(churn("b.rs"), (2, 2));
+    }
+
+    #[test]
+    fn test_analyze_subdirectory_only_reports_scoped_history() {
+        use std::process::Command;
+
+        let temp = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .expect("git command failed");
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        for dir in ["app", "lib", "app/vendor"] {
+            std::fs::create_dir_all(temp.path().join(dir)).unwrap();
+            std::fs::write(temp.path().join(dir).join("mod.rs"), "fn f() {}").unwrap();
+            git(&["add", "."]);
+            git(&["commit", "-m", &format!("add {dir}")]);
+        }
+
+        let app = temp.path().join("app");
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(&app, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(&app)).with_git_path(temp.path());
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let paths: Vec<&str> = analysis
+            .files
+            .iter()
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["app/mod.rs"]);
+    }
+
+    #[test]
+    fn test_analyze_bare_repository_at_ref() {
+        use std::process::Command;
+
+        let temp = tempfile::tempdir().unwrap();
+        let work = temp.path().join("work");
+        std::fs::create_dir_all(&work).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&work)
+                .output()
+                .expect("git command failed");
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        for name in ["a.rs", "b.rs"] {
+            std::fs::write(work.join(name), "fn f() {}").unwrap();
+            git(&["add", "."]);
+            git(&["commit", "-m", &format!("add {name}")]);
+        }
+        git(&["tag", "first", "HEAD~1"]);
+        let bare = temp.path().join("mirror.git");
+        Command::new("git")
+            .args(["clone", "--bare", "--quiet"])
+            .arg(&work)
+            .arg(&bare)
+            .output()
+            .expect("git clone failed");
+
+        let mut config = crate::config::Config::default();
+        config.git.at_ref = Some("first".to_string());
+        let source = crate::git::GitRepo::open(&bare)
+            .unwrap()
+            .at_ref(Some("first"))
+            .tree_source()
+            .unwrap();
+        let file_set = crate::core::FileSet::from_tree_source(&source, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(&bare)).with_git_path(&bare);
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let paths: Vec<&str> = analysis
+            .files
+            .iter()
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["a.rs"]);
+    }
 }
@@ -21,15 +21,17 @@
 //! where methods are connected if they share instance variables. This differs from
 //! LCOM4 which also connects methods that call each other.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
 use chrono::Utc;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
-use crate::parser::Parser;
+use crate::config::GeneratedHandling;
+use crate::core::generated::{self, GeneratedKind, GeneratedSummary};
+use crate::core::{is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
+use crate::parser::{extract_imports, ParseResult};
 
 /// Default threshold for WMC above which a class is considered complex.
 /// Research suggests 20-24 is appropriate (Chidamber & Kemerer 1994 IEEE TSE).
@@ -92,7 +94,8 @@ impl Analyzer {
     /// Analyzes cohesion metrics in a repository.
     /// Uses ctx.read_file() to support both filesystem and git tree sources.
     pub fn analyze_repo(&self, ctx: &AnalysisContext<'_>) -> Result<Analysis> {
-        // Phase 1: Get files from context, filtered by OO languages
+        // Phase 1: Get files from context. Every language gets module
+        // metrics; class metrics are limited to OO languages.
         let files: Vec<_> = ctx
             .files
             .iter()
@@ -101,47 +104,78 @@ impl Analyzer {
                 if self.config.skip_test_files && is_test_file(path) {
                     return false;
                 }
-                // Only include OO languages
-                Language::detect(path).map(is_oo_language).unwrap_or(false)
+                ctx.files.language(path).is_some()
             })
             .collect();
 
-        // Phase 2: Parse files in parallel and extract classes
+        // Phase 2: Parse files in parallel and extract classes and modules
         let max_file_size = self.config.max_file_size;
-        let all_classes: Vec<ClassMetrics> = files
+        let per_file: Vec<FileMetrics> = files
             .par_iter()
             .filter_map(|path| {
-                // Read file via context (supports both filesystem and git tree)
-                let source = ctx.read_file(path).ok()?;
+                let lang = ctx.files.language(path)?;
+
+                // Parse via context (shared parse cache, filesystem or git tree)
+                let parse_result = ctx.parsed(path).ok()?;
+                let source = &parse_result.source;
 
                 // Skip if too large
                 if max_file_size > 0 && source.len() > max_file_size {
                     return None;
                 }
 
-                let lang = Language::detect(path)?;
-
-                // Parse with thread-local parser
-                let parser = Parser::new();
-                let parse_result = parser.parse(&source, lang, path).ok()?;
-                let classes =
-                    extract_classes_from_file(path, &source, parse_result.tree.as_ref(), lang);
+                let classes = if is_oo_language(lang) {
+                    extract_classes_from_file(path, source, parse_result.tree.as_ref(), lang)
+                } else {
+                    Vec::new()
+                };
+                // Modules are reported by their path in the set, like classes.
+                let module = module_metrics(&parse_result).map(|module| ModuleMetrics {
+                    path: path.to_string_lossy().to_string(),
+                    ..module
+                });
+                let generated = generated::classify(path, source, &ctx.config.generated);
 
-                Some(classes)
+                Some((classes, module, generated))
             })
-            .flatten()
             .collect();
 
-        let mut all_classes = all_classes;
+        let handling = ctx.config.generated.cohesion;
+        let weight = ctx.config.generated.weight;
+        let generated_files = per_file.iter().filter(|f| f.2.is_some()).count();
+        let total_files = per_file.len();
+
+        let mut all_classes = Vec::new();
+        let mut modules = Vec::new();
+        for (mut classes, module, kind) in per_file {
+            if let Some(kind) = kind {
+                match handling {
+                    GeneratedHandling::Exclude => continue,
+                    GeneratedHandling::Include => {
+                        classes.iter_mut().for_each(|c| c.generated = Some(kind));
+                    }
+                    GeneratedHandling::Downweight => {
+                        classes.iter_mut().for_each(|c| c.downweight(kind, weight));
+                    }
+                }
+            }
+            all_classes.extend(classes);
+            modules.extend(module);
+        }
 
-        // Build class hierarchy for DIT/NOC calculation
-        let mut hierarchy = ClassHierarchy::new();
+        // Build one project-wide class hierarchy per language for DIT/NOC:
+        // a Python `Base` and a Java `Base` are different classes.
+        let mut hierarchies: HashMap<String, ClassHierarchy> = HashMap::new();
         for cls in &all_classes {
-            hierarchy.add_class(&cls.class_name, cls.parent_class.as_deref());
+            hierarchies
+                .entry(cls.language.clone())
+                .or_default()
+                .add_class(&cls.class_name, cls.parent_class.as_deref());
         }
 
         // Update DIT and NOC for each class
         for cls in &mut all_classes {
+            let hierarchy = &hierarchies[&cls.language];
             cls.dit = hierarchy.get_dit(&cls.class_name);
             cls.noc = hierarchy.get_noc(&cls.class_name);
         }
@@ -149,14 +183,98 @@ impl Analyzer {
         // Sort by LCOM (least cohesive first)
         all_classes.sort_by(|a, b| b.lcom.cmp(&a.lcom));
 
-        let summary = calculate_summary(&all_classes);
+        // Sort modules by LCOM (least cohesive first), then path
+        modules.sort_by(|a, b| b.lcom.cmp(&a.lcom).then_with(|| a.path.cmp(&b.path)));
+
+        let mut summary = calculate_summary(&all_classes);
+        summarize_modules(&modules, &mut summary);
+        summary.generated = GeneratedSummary::new(handling, generated_files, total_files);
 
         Ok(Analysis {
             generated_at: Utc::now().to_rfc3339(),
             classes: all_classes,
+            modules,
             summary,
         })
     }
+
+    /// Generate the inheritance hierarchy in DOT format (Graphviz).
+    ///
+    /// One cluster per language, holding the classes that extend or are
+    /// extended by another. Edges point from subclass to parent; parents
+    /// declared outside the analyzed files are drawn dashed.
+    pub fn to_dot(&self, analysis: &Analysis) -> String {
+        let mut by_language: BTreeMap<&str, BTreeMap<&str, &ClassMetrics>> = BTreeMap::new();
+        for cls in &analysis.classes {
+            by_language
+                .entry(&cls.language)
+                .or_default()
+                .entry(&cls.class_name)
+                .or_insert(cls);
+        }
+
+        let mut output = String::from("digraph Inheritance {\n");
+        output.push_str("    rankdir=BT;\n");
+        output.push_str("    node [shape=box];\n");
+
+        for (i, (language, classes)) in by_language.iter().enumerate() {
+            let parents: HashSet<&str> = classes
+                .values()
+                .filter_map(|c| c.parent_class.as_deref())
+                .collect();
+            let mut nodes = String::new();
+            let mut edges = String::new();
+            for (name, cls) in classes {
+                if cls.parent_class.is_none() && !parents.contains(name) {
+                    continue;
+                }
+                nodes.push_str(&format!(
+                    "        {} [label=\"{}\\nDIT: {} NOC: {}\"];\n",
+                    dot_id(language, name),
+                    name.replace('"', "\\\""),
+                    cls.dit,
+                    cls.noc
+                ));
+                if let Some(ref parent) = cls.parent_class {
+                    edges.push_str(&format!(
+                        "        {} -> {};\n",
+                        dot_id(language, name),
+                        dot_id(language, parent)
+                    ));
+                }
+            }
+            let mut external: Vec<&str> = parents
+                .iter()
+                .filter(|p| !classes.contains_key(*p))
+                .copied()
+                .collect();
+            external.sort_unstable();
+            for parent in external {
+                nodes.push_str(&format!(
+                    "        {} [label=\"{}\", style=dashed];\n",
+                    dot_id(language, parent),
+                    parent.replace('"', "\\\"")
+                ));
+            }
+            if nodes.is_empty() {
+                continue;
+            }
+
+            output.push_str(&format!("\n    subgraph cluster_{i} {{\n"));
+            output.push_str(&format!("        label=\"{language}\";\n"));
+            output.push_str(&nodes);
+            output.push_str(&edges);
+            output.push_str("    }\n");
+        }
+
+        output.push_str("}\n");
+        output
+    }
+}
+
+/// Quoted DOT node id, unique per language.
+fn dot_id(language: &str, class_name: &str) -> String {
+    format!("\"{}:{}\"", language, class_name.replace('"', "\\\""))
 }
 
 impl AnalyzerTrait for Analyzer {
@@ -175,6 +293,13 @@ impl AnalyzerTrait for Analyzer {
     }
 }
 
+/// Classes, module metrics and generated-code classification of one file.
+type FileMetrics = (
+    Vec<ClassMetrics>,
+    Option<ModuleMetrics>,
+    Option<GeneratedKind>,
+);
+
 /// Checks if a language supports class-like structures.
 /// Includes traditional OO languages plus Rust (struct+impl) and Go (struct+methods).
 fn is_oo_language(lang: Language) -> bool {
@@ -215,12 +340,14 @@ impl ClassHierarchy {
         self.parents
             .insert(class_name.to_string(), parent_str.clone());
 
-        // Register this class as a child of its parent
+        // Register this class as a child of its parent, once even when the
+        // class is declared in several places (partial classes, reopened
+        // Ruby classes).
         if let Some(parent_name) = parent_str {
-            self.children
-                .entry(parent_name)
-                .or_default()
-                .push(class_name.to_string());
+            let siblings = self.children.entry(parent_name).or_default();
+            if !siblings.iter().any(|c| c == class_name) {
+                siblings.push(class_name.to_string());
+            }
         }
 
         // Ensure the class has an entry in children map (even if empty)
@@ -265,23 +392,6 @@ impl ClassHierarchy {
     }
 }
 
-/// Checks if a file is a test file.
-fn is_test_file(path: &Path) -> bool {
-    let path_str = path.to_string_lossy();
-    path_str.ends_with("_test.go")
-        || path_str.ends_with("_test.py")
-        || path_str.ends_with(".test.ts")
-        || path_str.ends_with(".test.js")
-        || path_str.ends_with(".spec.ts")
-        || path_str.ends_with(".spec.js")
-        || path_str.contains("/test/")
-        || path_str.contains("/tests/")
-        || path_str.contains("/__tests__/")
-        || path_str.starts_with("__tests__/")
-        || path_str.starts_with("test/")
-        || path_str.starts_with("tests/")
-}
-
 /// Extracts class metrics from a parsed file.
 fn extract_classes_from_file(
     path: &Path,
@@ -377,6 +487,7 @@ fn extract_rust_classes(path: &Path, source: &[u8], tree: &tree_sitter::Tree) ->
             fields,
             coupled_classes: all_coupled.into_iter().collect(),
             violations,
+            generated: None,
         });
     }
 
@@ -504,6 +615,7 @@ fn extract_go_classes(path: &Path, source: &[u8], tree: &tree_sitter::Tree) -> V
             fields,
             coupled_classes: all_coupled.into_iter().collect(),
             violations,
+            generated: None,
         });
     }
 
@@ -782,73 +894,82 @@ fn extract_class_metrics(
         fields,
         coupled_classes,
         violations,
+        generated: None,
     })
 }
 
-/// Finds the first child of `parent` whose `kind()` matches one of `kinds`,
-/// and returns its source text as a `String`.
-fn first_child_text_by_kind(
-    parent: &tree_sitter::Node,
-    source: &[u8],
-    kinds: &[&str],
-) -> Option<String> {
-    for i in 0..parent.child_count() as u32 {
-        let child = parent.child(i)?;
-        if kinds.contains(&child.kind()) {
-            return std::str::from_utf8(&source[child.byte_range()])
-                .ok()
-                .map(|s| s.to_string());
-        }
-    }
-    None
-}
-
 /// Extracts the parent class name from a class node (for extends/inherits).
+///
+/// The name is reduced with [`base_class_name`] so that qualified and
+/// generic references (`models.Model`, `Base<T>`) match the bare names
+/// classes are declared with in other files.
 fn extract_parent_class(node: &tree_sitter::Node, source: &[u8], lang: Language) -> Option<String> {
-    match lang {
+    let reference = match lang {
         Language::Java => {
-            // Java: class Child extends Parent { }
+            // Java: class Child extends pkg.Parent<T> { }
             let sc = node.child_by_field_name("superclass")?;
-            first_child_text_by_kind(&sc, source, &["type_identifier"])
+            first_named_child_text(&sc, source, &[])
         }
         Language::TypeScript | Language::JavaScript => {
-            // TS/JS: class Child extends Parent { }
-            // class_heritage -> extends_clause -> identifier|type_identifier
+            // TS/JS: class Child extends mod.Parent { }
+            // class_heritage -> extends_clause -> value
             let heritage = find_child_by_kind(node, "class_heritage")?;
             let clause = find_child_by_kind(&heritage, "extends_clause")?;
-            first_child_text_by_kind(&clause, source, &["identifier", "type_identifier"])
+            first_named_child_text(&clause, source, &[])
         }
         Language::Python => {
-            // Python: class Child(Parent):
+            // Python: class Child(module.Parent, metaclass=Meta):
             let args = node.child_by_field_name("superclasses")?;
-            first_child_text_by_kind(&args, source, &["identifier"])
+            first_named_child_text(&args, source, &["keyword_argument"])
         }
         Language::CSharp => {
-            // C#: class Child : Parent { }
+            // C#: class Child : Ns.Parent<T> { }
             let bases = node.child_by_field_name("bases")?;
-            first_child_text_by_kind(&bases, source, &["identifier", "type_identifier"])
+            first_named_child_text(&bases, source, &[])
         }
         Language::Cpp => {
-            // C++: class Child : public Parent { }
+            // C++: class Child : public virtual ns::Parent { }
             let clause = find_child_by_kind(node, "base_class_clause")?;
-            first_child_text_by_kind(&clause, source, &["type_identifier"])
+            first_named_child_text(&clause, source, &["access_specifier", "virtual"])
         }
         Language::Ruby => {
-            // Ruby: class Child < Parent
+            // Ruby: class Child < Module::Parent
             let sc = node.child_by_field_name("superclass")?;
-            std::str::from_utf8(&source[sc.byte_range()])
-                .ok()
-                .map(|s| s.to_string())
+            first_named_child_text(&sc, source, &[])
         }
         Language::Php => {
-            // PHP: class Child extends Parent { }
+            // PHP: class Child extends \App\Parent { }
             let base = node.child_by_field_name("base_clause")?;
-            first_child_text_by_kind(&base, source, &["name", "qualified_name"])
+            first_named_child_text(&base, source, &[])
         }
         // Rust and Go have no class inheritance
         Language::Rust | Language::Go => None,
         _ => None,
-    }
+    }?;
+    base_class_name(&reference)
+}
+
+/// Text of the first named child of `node` whose kind is not in `skip`.
+fn first_named_child_text(
+    node: &tree_sitter::Node,
+    source: &[u8],
+    skip: &[&str],
+) -> Option<String> {
+    let mut cursor = node.walk();
+    let child = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() != "comment" && !skip.contains(&c.kind()))?;
+    std::str::from_utf8(&source[child.byte_range()])
+        .ok()
+        .map(|s| s.to_string())
+}
+
+/// Reduces a base-class reference to the bare class name: `pkg.Base`,
+/// `ns::Base<T>`, `\App\Base` and `Generic[T]` keep only the last segment.
+fn base_class_name(reference: &str) -> Option<String> {
+    let end = reference.find(['<', '[', '(']).unwrap_or(reference.len());
+    let name = reference[..end].rsplit(['.', ':', '\\']).next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
 }
 
 /// Finds the first direct child of `node` with the given `kind`.
@@ -1524,6 +1645,291 @@ fn dfs(v: usize, adj: &[Vec<usize>], visited: &mut [bool]) {
     }
 }
 
+/// Minimum top-level functions for a file to get module cohesion metrics.
+pub const MODULE_MIN_FUNCTIONS: usize = 2;
+
+/// Computes module-level cohesion for a parsed file from its free functions.
+///
+/// Classes are covered by the CK metrics; this covers code organized as free
+/// functions (TypeScript, Go, Python, C). Two functions are related when one
+/// calls the other or both reference the same top-level declaration (a
+/// constant, type, or module variable). Imports are measured separately as
+/// `import_overlap` because shared utility imports would otherwise link
+/// every function.
+///
+/// Returns `None` for files with fewer than [`MODULE_MIN_FUNCTIONS`] free
+/// functions.
+pub fn module_metrics(result: &ParseResult) -> Option<ModuleMetrics> {
+    let source = &result.source[..];
+    let root = result.root_node();
+
+    let mut functions: Vec<(String, tree_sitter::Node)> = Vec::new();
+    let mut declared: HashSet<String> = HashSet::new();
+    for child in top_level_items(&root) {
+        let kind = child.kind();
+        if kind.contains("import") || kind == "use_declaration" || kind.starts_with("preproc") {
+            continue;
+        }
+        match top_level_function(&child, source) {
+            Some(func) => functions.push(func),
+            None => collect_declared_names(&child, source, 0, &mut declared),
+        }
+    }
+    if functions.len() < MODULE_MIN_FUNCTIONS {
+        return None;
+    }
+
+    let function_names: HashSet<&str> = functions.iter().map(|(n, _)| n.as_str()).collect();
+    let mut imported: HashSet<String> = HashSet::new();
+    for import in extract_imports(result) {
+        imported.extend(
+            import
+                .names
+                .into_iter()
+                .map(|n| last_segment(&n).to_string()),
+        );
+        let path = import.path.trim_matches(|c| c == '"' || c == '\'');
+        let mut segments = path.split(['/', '.', ':']).filter(|s| !s.is_empty());
+        if let Some(first) = segments.next() {
+            imported.insert(first.to_string());
+        }
+        imported.insert(last_segment(path).to_string());
+    }
+
+    // Per function: referenced top-level declarations/functions, and imports.
+    let mut links: Vec<HashSet<&str>> = Vec::with_capacity(functions.len());
+    let mut import_uses: Vec<HashSet<&str>> = Vec::with_capacity(functions.len());
+    for (name, node) in &functions {
+        let mut used = HashSet::new();
+        collect_used_identifiers(node, source, &mut used);
+        let mut linked = HashSet::new();
+        let mut imports_used = HashSet::new();
+        for ident in used {
+            if let Some(&f) = function_names.get(ident) {
+                if f != name.as_str() {
+                    linked.insert(f);
+                }
+            } else if let Some(d) = declared.get(ident) {
+                linked.insert(d.as_str());
+            } else if let Some(i) = imported.get(ident) {
+                imports_used.insert(i.as_str());
+            }
+        }
+        links.push(linked);
+        import_uses.push(imports_used);
+    }
+
+    let n = functions.len();
+    let related = |a: usize, b: usize| {
+        links[a].contains(functions[b].0.as_str())
+            || links[b].contains(functions[a].0.as_str())
+            || !links[a].is_disjoint(&links[b])
+    };
+    let mut adj: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut related_pairs = 0usize;
+    for a in 0..n {
+        for b in (a + 1)..n {
+            if related(a, b) {
+                adj[a].push(b);
+                adj[b].push(a);
+                related_pairs += 1;
+            }
+        }
+    }
+
+    let mut visited = vec![false; n];
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    for i in 0..n {
+        if !visited[i] {
+            let before: Vec<bool> = visited.clone();
+            dfs(i, &adj, &mut visited);
+            let mut group: Vec<String> = (0..n)
+                .filter(|&j| visited[j] && !before[j])
+                .map(|j| functions[j].0.clone())
+                .collect();
+            group.sort();
+            groups.push(group);
+        }
+    }
+    let lcom = groups.len() as u32;
+    if lcom <= 1 {
+        groups.clear();
+    } else {
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    }
+
+    let mut import_counts: HashMap<&str, usize> = HashMap::new();
+    for uses in &import_uses {
+        for import in uses {
+            *import_counts.entry(import).or_default() += 1;
+        }
+    }
+    let import_overlap = (!import_counts.is_empty()).then(|| {
+        import_counts
+            .values()
+            .map(|&c| c as f64 / n as f64)
+            .sum::<f64>()
+            / import_counts.len() as f64
+    });
+
+    let pairs = n * (n - 1) / 2;
+    Some(ModuleMetrics {
+        path: result.path.to_string_lossy().to_string(),
+        language: result.language.to_string(),
+        functions: n as u32,
+        declarations: declared.len() as u32,
+        lcom,
+        shared_identifier_ratio: related_pairs as f64 / pairs as f64,
+        import_overlap,
+        groups,
+    })
+}
+
+/// Top-level items, looking through export and decorator wrappers.
+fn top_level_items<'a>(root: &tree_sitter::Node<'a>) -> Vec<tree_sitter::Node<'a>> {
+    let mut items = Vec::new();
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        match child.kind() {
+            "export_statement" => {
+                if let Some(decl) = child
+                    .child_by_field_name("declaration")
+                    .or_else(|| child.child_by_field_name("value"))
+                {
+                    items.push(decl);
+                }
+            }
+            "decorated_definition" => {
+                if let Some(def) = child.child_by_field_name("definition") {
+                    items.push(def);
+                }
+            }
+            _ => items.push(child),
+        }
+    }
+    items
+}
+
+/// Returns the name and node of a free function declared by a top-level
+/// item, including `const f = () => ...` in JavaScript/TypeScript.
+fn top_level_function<'a>(
+    node: &tree_sitter::Node<'a>,
+    source: &[u8],
+) -> Option<(String, tree_sitter::Node<'a>)> {
+    match node.kind() {
+        "function_declaration"
+        | "generator_function_declaration"
+        | "function_item"
+        | "function_definition"
+        | "method" => {
+            let name = node_name_text(node, source).or_else(|| c_declarator_name(node, source))?;
+            Some((name, *node))
+        }
+        "lexical_declaration" | "variable_declaration" if node.named_child_count() == 1 => {
+            let declarator = node.named_child(0)?;
+            let value = declarator.child_by_field_name("value")?;
+            if matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function"
+            ) {
+                Some((node_name_text(&declarator, source)?, *node))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Name of a C/C++ function from its declarator chain.
+fn c_declarator_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut current = node.child_by_field_name("declarator")?;
+    loop {
+        if current.kind() == "identifier" {
+            return std::str::from_utf8(&source[current.byte_range()])
+                .ok()
+                .map(str::to_string);
+        }
+        current = current.child_by_field_name("declarator")?;
+    }
+}
+
+/// Collects names declared by a non-function top-level item (constants,
+/// variables, types, classes).
+fn collect_declared_names(
+    node: &tree_sitter::Node,
+    source: &[u8],
+    depth: usize,
+    out: &mut HashSet<String>,
+) {
+    let name_node = ["name", "left", "declarator"]
+        .iter()
+        .filter_map(|field| node.child_by_field_name(field))
+        .find(|n| is_identifier_kind(n.kind()));
+    if let Some(name_node) = name_node {
+        if let Ok(name) = std::str::from_utf8(&source[name_node.byte_range()]) {
+            out.insert(name.to_string());
+        }
+        return;
+    }
+    if depth >= 3 {
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if !child.kind().contains("body") && !child.kind().contains("block") {
+            collect_declared_names(&child, source, depth + 1, out);
+        }
+    }
+}
+
+/// Collects identifiers referenced inside a node, skipping member names
+/// (`obj.field`), which never refer to top-level declarations.
+fn collect_used_identifiers<'a>(
+    node: &tree_sitter::Node,
+    source: &'a [u8],
+    out: &mut HashSet<&'a str>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let kind = child.kind();
+        if child.child_count() == 0 {
+            if is_identifier_kind(kind) {
+                if let Ok(text) = std::str::from_utf8(&source[child.byte_range()]) {
+                    out.insert(text);
+                }
+            }
+        } else {
+            collect_used_identifiers(&child, source, out);
+        }
+    }
+}
+
+fn is_identifier_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "identifier" | "type_identifier" | "constant" | "name" | "simple_identifier"
+    )
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit(['/', '.', ':'])
+        .find(|s| !s.is_empty())
+        .unwrap_or(path)
+}
+
+/// Calculates module cohesion summary fields.
+fn summarize_modules(modules: &[ModuleMetrics], summary: &mut Summary) {
+    summary.total_modules = modules.len();
+    if modules.is_empty() {
+        return;
+    }
+    let total: u32 = modules.iter().map(|m| m.lcom).sum();
+    summary.avg_module_lcom = total as f64 / modules.len() as f64;
+    summary.max_module_lcom = modules.iter().map(|m| m.lcom).max().unwrap_or(0);
+    summary.low_cohesion_module_count = modules.iter().filter(|m| m.lcom > 1).count();
+}
+
 /// Calculates summary statistics.
 fn calculate_summary(classes: &[ClassMetrics]) -> Summary {
     if classes.is_empty() {
@@ -1577,6 +1983,7 @@ fn calculate_summary(classes: &[ClassMetrics]) -> Summary {
         max_dit,
         low_cohesion_count,
         violation_count,
+        ..Default::default()
     }
 }
 
@@ -1587,6 +1994,9 @@ pub struct Analysis {
     pub generated_at: String,
     /// Per-class metrics.
     pub classes: Vec<ClassMetrics>,
+    /// Per-file metrics for free functions, least cohesive first.
+    #[serde(default)]
+    pub modules: Vec<ModuleMetrics>,
     /// Summary statistics.
     pub summary: Summary,
 }
@@ -1650,6 +2060,47 @@ pub struct ClassMetrics {
     pub coupled_classes: Vec<String>,
     /// Metric violations.
     pub violations: Vec<String>,
+    /// Set when the class is in a generated or macro-expanded file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<GeneratedKind>,
+}
+
+impl ClassMetrics {
+    /// Scale WMC, CBO and RFC by `weight` and rebuild the violations.
+    fn downweight(&mut self, kind: GeneratedKind, weight: f64) {
+        self.generated = Some(kind);
+        self.wmc = generated::downweight(self.wmc, weight);
+        self.cbo = generated::downweight(self.cbo, weight);
+        self.rfc = generated::downweight(self.rfc, weight);
+        self.violations = build_violations(self.wmc, self.cbo, self.lcom);
+    }
+}
+
+/// Cohesion metrics for the free functions of a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleMetrics {
+    /// File path.
+    pub path: String,
+    /// Programming language.
+    pub language: String,
+    /// Number of top-level functions.
+    pub functions: u32,
+    /// Number of other top-level declarations (constants, variables, types).
+    pub declarations: u32,
+    /// Groups of functions with no calls or shared declarations between
+    /// them (connected components, as in LCOM).
+    pub lcom: u32,
+    /// Fraction of function pairs that call each other or share a
+    /// top-level declaration (0-1).
+    pub shared_identifier_ratio: f64,
+    /// Average fraction of functions using each referenced import (0-1).
+    /// `None` when no function references an import.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import_overlap: Option<f64>,
+    /// Function names per group when `lcom > 1`, largest first; each group
+    /// is a candidate for its own module.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<Vec<String>>,
 }
 
 /// Aggregate CK metrics summary.
@@ -1681,11 +2132,27 @@ pub struct Summary {
     pub low_cohesion_count: usize,
     /// Total number of violations.
     pub violation_count: usize,
+    /// Files with module cohesion metrics.
+    #[serde(default)]
+    pub total_modules: usize,
+    /// Average module LCOM.
+    #[serde(default)]
+    pub avg_module_lcom: f64,
+    /// Maximum module LCOM.
+    #[serde(default)]
+    pub max_module_lcom: u32,
+    /// Modules with LCOM > 1.
+    #[serde(default)]
+    pub low_cohesion_module_count: usize,
+    /// Generated and macro-expanded files found, and how they were handled.
+    #[serde(default)]
+    pub generated: GeneratedSummary,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::Parser;
 
     #[test]
     fn test_config_default() {
@@ -1845,6 +2312,7 @@ mod tests {
                 fields: vec![],
                 coupled_classes: vec![],
                 violations: vec![],
+                generated: None,
             },
             ClassMetrics {
                 path: "b.java".to_string(),
@@ -1866,6 +2334,7 @@ mod tests {
                 fields: vec![],
                 coupled_classes: vec![],
                 violations: vec![],
+                generated: None,
             },
         ];
 
@@ -1902,6 +2371,7 @@ mod tests {
             fields: vec!["x".to_string(), "y".to_string()],
             coupled_classes: vec!["Helper".to_string()],
             violations: vec!["LCOM 3 exceeds threshold 1".to_string()],
+            generated: None,
         };
 
         assert_eq!(metrics.class_name, "Test");
@@ -1934,6 +2404,7 @@ mod tests {
                     fields: vec![],
                     coupled_classes: vec![],
                     violations: vec![],
+                    generated: None,
                 },
                 ClassMetrics {
                     class_name: "High".to_string(),
@@ -1955,8 +2426,10 @@ mod tests {
                     fields: vec![],
                     coupled_classes: vec![],
                     violations: vec![],
+                    generated: None,
                 },
             ],
+            modules: vec![],
             summary: Summary::default(),
         };
 
@@ -2008,6 +2481,7 @@ mod tests {
             fields: vec![],
             coupled_classes: vec![],
             violations: vec![format!("WMC {} exceeds threshold {}", 25, WMC_THRESHOLD)],
+            generated: None,
         };
 
         assert!(metrics.wmc > WMC_THRESHOLD);
@@ -2556,4 +3030,271 @@ func (r *Response) IsOK() bool {
             assert!(cls.nof >= 2);
         }
     }
+
+    fn parse_module(source: &[u8], lang: Language, path: &str) -> Option<ModuleMetrics> {
+        let result = Parser::new().parse(source, lang, Path::new(path)).unwrap();
+        module_metrics(&result)
+    }
+
+    #[test]
+    fn test_module_metrics_typescript_unrelated_groups() {
+        let source = br#"
+import { readFileSync } from "fs";
+import { format } from "date-fns";
+
+const CACHE_TTL = 60;
+
+export function loadConfig(path: string) {
+    return parseConfig(readFileSync(path, "utf8"));
+}
+
+function parseConfig(text: string) {
+    return JSON.parse(text);
+}
+
+export const formatDate = (d: Date) => format(d, "yyyy-MM-dd");
+
+export function cacheKey(id: string) {
+    return `${id}:${CACHE_TTL}`;
+}
+
+export function isExpired(age: number) {
+    return age > CACHE_TTL;
+}
+"#;
+        let module = parse_module(source, Language::TypeScript, "utils.ts").unwrap();
+        assert_eq!(module.functions, 5);
+        assert_eq!(module.declarations, 1);
+        // {loadConfig, parseConfig}, {cacheKey, isExpired}, {formatDate}
+        assert_eq!(module.lcom, 3);
+        assert_eq!(
+            module.groups,
+            vec![
+                vec!["cacheKey".to_string(), "isExpired".to_string()],
+                vec!["loadConfig".to_string(), "parseConfig".to_string()],
+                vec!["formatDate".to_string()],
+            ]
+        );
+        assert!((module.shared_identifier_ratio - 0.2).abs() < 1e-9);
+        // readFileSync and format are each used by one of five functions.
+        assert!((module.import_overlap.unwrap() - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_module_metrics_go_shared_state_is_cohesive() {
+        let source = br#"
+package registry
+
+import "sync"
+
+var mu sync.Mutex
+var items = map[string]int{}
+
+func Add(name string) {
+	mu.Lock()
+	defer mu.Unlock()
+	items[name]++
+}
+
+func Count(name string) int {
+	mu.Lock()
+	defer mu.Unlock()
+	return items[name]
+}
+
+func Reset() {
+	items = map[string]int{}
+}
+
+func (r *Other) Method() {}
+"#;
+        let module = parse_module(source, Language::Go, "registry.go").unwrap();
+        assert_eq!(module.functions, 3, "methods belong to class metrics");
+        assert_eq!(module.lcom, 1);
+        assert!(module.groups.is_empty());
+        assert!((module.shared_identifier_ratio - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_module_metrics_python_calls_connect_functions() {
+        let source = br#"
+import os
+
+def main():
+    run(os.getcwd())
+
+def run(path):
+    return path
+
+def unrelated():
+    return 1
+"#;
+        let module = parse_module(source, Language::Python, "app.py").unwrap();
+        assert_eq!(module.functions, 3);
+        assert_eq!(module.lcom, 2);
+        assert_eq!(
+            module.groups[0],
+            vec!["main".to_string(), "run".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_module_metrics_requires_two_functions() {
+        let source = b"export function only() { return 1; }\n";
+        assert!(parse_module(source, Language::TypeScript, "one.ts").is_none());
+    }
+
+    #[test]
+    fn test_analyze_repo_reports_modules_for_non_oo_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("util.c"),
+            "static int limit = 3;\nint a(void) { return limit; }\nint b(void) { return limit + 1; }\nint c(void) { return 0; }\n",
+        )
+        .unwrap();
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+
+        let analysis = Analyzer::new().analyze_repo(&ctx).unwrap();
+        assert!(analysis.classes.is_empty());
+        assert_eq!(analysis.modules.len(), 1);
+        assert_eq!(analysis.modules[0].lcom, 2);
+        assert_eq!(analysis.summary.total_modules, 1);
+        assert_eq!(analysis.summary.low_cohesion_module_count, 1);
+        assert!((analysis.summary.avg_module_lcom - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_repo_handles_generated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let class = |name: &str| {
+            format!(
+                "export class {name} {{\n  a(x: number) {{ if (x > 1) {{ return 1; }} return 0; }}\n  b(x: number) {{ if (x > 2) {{ return 2; }} return 0; }}\n  c(x: number) {{ if (x > 3) {{ return 3; }} return 0; }}\n  d(x: number) {{ if (x > 4) {{ return 4; }} return 0; }}\n}}\n"
+            )
+        };
+        std::fs::write(dir.path().join("service.ts"), class("Service")).unwrap();
+        std::fs::write(dir.path().join("api.generated.ts"), class("ApiClient")).unwrap();
+
+        let mut config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+        let analysis = Analyzer::new().analyze_repo(&ctx).unwrap();
+        let find = |name: &str| {
+            analysis
+                .classes
+                .iter()
+                .find(|c| c.class_name == name)
+                .unwrap()
+                .clone()
+        };
+        let (service, client) = (find("Service"), find("ApiClient"));
+        assert_eq!(service.generated, None);
+        assert_eq!(client.generated, Some(GeneratedKind::Generated));
+        assert!(client.wmc < service.wmc);
+        assert_eq!(analysis.summary.generated.files, 1);
+
+        config.generated.cohesion = GeneratedHandling::Exclude;
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+        let analysis = Analyzer::new().analyze_repo(&ctx).unwrap();
+        assert_eq!(analysis.classes.len(), 1);
+        assert_eq!(analysis.classes[0].class_name, "Service");
+        assert!((analysis.summary.generated.ratio - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_base_class_name_strips_qualifiers_and_generics() {
+        assert_eq!(base_class_name("models.Model"), Some("Model".to_string()));
+        assert_eq!(base_class_name("ns::Base<T>"), Some("Base".to_string()));
+        assert_eq!(base_class_name("\\App\\Base"), Some("Base".to_string()));
+        assert_eq!(base_class_name("Generic[T]"), Some("Generic".to_string()));
+        assert_eq!(base_class_name("Parent"), Some("Parent".to_string()));
+        assert_eq!(base_class_name("<T>"), None);
+    }
+
+    #[test]
+    fn test_analyze_repo_builds_cross_file_hierarchy_per_language() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("base.py"), "class Base:\n    pass\n").unwrap();
+        std::fs::write(
+            dir.path().join("child.py"),
+            "import base\n\nclass Child(base.Base):\n    pass\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("grandchild.py"),
+            "from child import Child\n\nclass GrandChild(Child):\n    pass\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("Base.java"), "class Base { }\n").unwrap();
+        std::fs::write(
+            dir.path().join("Leaf.java"),
+            "class Leaf extends Base { }\n",
+        )
+        .unwrap();
+
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+        let analyzer = Analyzer::new();
+        let analysis = analyzer.analyze_repo(&ctx).unwrap();
+        let find = |language: &str, name: &str| {
+            analysis
+                .classes
+                .iter()
+                .find(|c| c.language == language && c.class_name == name)
+                .unwrap()
+        };
+
+        assert_eq!(
+            find("Python", "Child").parent_class.as_deref(),
+            Some("Base")
+        );
+        assert_eq!(find("Python", "GrandChild").dit, 2);
+        // Java's Leaf does not count as a child of Python's Base.
+        assert_eq!(find("Python", "Base").noc, 1);
+        assert_eq!(find("Java", "Base").noc, 1);
+        assert_eq!(analysis.summary.max_dit, 2);
+
+        let dot = analyzer.to_dot(&analysis);
+        assert!(dot.starts_with("digraph Inheritance {"));
+        assert!(dot.contains("label=\"Java\";"));
+        assert!(dot.contains("\"Python:GrandChild\" -> \"Python:Child\";"));
+        assert!(dot.contains("\"Java:Leaf\" -> \"Java:Base\";"));
+    }
+
+    #[test]
+    fn test_to_dot_marks_external_parents() {
+        let widget = ClassMetrics {
+            path: "widget.ts".to_string(),
+            class_name: "Widget".to_string(),
+            parent_class: Some("Component".to_string()),
+            language: "TypeScript".to_string(),
+            start_line: 1,
+            end_line: 10,
+            loc: 10,
+            wmc: 1,
+            cbo: 0,
+            rfc: 1,
+            lcom: 0,
+            dit: 1,
+            noc: 0,
+            nom: 1,
+            nof: 0,
+            methods: vec![],
+            fields: vec![],
+            coupled_classes: vec![],
+            violations: vec![],
+            generated: None,
+        };
+        let analysis = Analysis {
+            generated_at: String::new(),
+            classes: vec![widget],
+            modules: vec![],
+            summary: calculate_summary(&[]),
+        };
+
+        let dot = Analyzer::new().to_dot(&analysis);
+        assert!(dot.contains("[label=\"Component\", style=dashed];"));
+    }
 }
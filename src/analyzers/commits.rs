@@ -0,0 +1,581 @@
+//! Commit message quality analysis.
+//!
+//! Checks recent commit messages for:
+//! - Conventional-commit compliance (`type(scope)!: description`)
+//! - Subject line length and trailing periods
+//! - Issue linkage (`#123`, `PROJ-123`, issue/PR URLs)
+//! - Revert and `fixup!`/`squash!` density
+//!
+//! Like commitlint, merge commits, git-generated reverts (`Revert "..."`) and
+//! autosquash commits are counted but not held to the conventional format.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, TimeZone, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::CommitsConfig;
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
+use crate::git::CommitMessage;
+
+/// Commit message analyzer.
+#[derive(Default)]
+pub struct Analyzer {
+    days: Option<u32>,
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override `commits.days` from config.
+    pub fn with_days(mut self, days: u32) -> Self {
+        if days > 0 {
+            self.days = Some(days);
+        }
+        self
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "commits"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check commit messages for conventional-commit compliance and hygiene"
+    }
+
+    fn requires_git(&self) -> bool {
+        true
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let repo = ctx
+            .open_git()?
+            .ok_or_else(|| Error::git("Commits analyzer requires a git repository"))?;
+        let config = &ctx.config.commits;
+        let days = self.days.unwrap_or(config.days);
+
+        let messages = repo.messages(Some(&format!("{days} days")), None)?;
+        let commits: Vec<CommitQuality> = messages.iter().map(|m| assess(m, config)).collect();
+        let summary = Summary::from_commits(&commits);
+
+        Ok(Analysis {
+            generated_at: Utc::now(),
+            period_days: days,
+            commits,
+            summary,
+        })
+    }
+}
+
+/// Commit message analysis result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    pub generated_at: DateTime<Utc>,
+    pub period_days: u32,
+    pub commits: Vec<CommitQuality>,
+    pub summary: Summary,
+}
+
+impl Analysis {
+    /// Check compliance against `min_compliance` and, if `fail_on_fixups`,
+    /// that no `fixup!`/`squash!` commits remain.
+    pub fn check(&self, min_compliance: f64, fail_on_fixups: bool) -> Result<()> {
+        let mut failures = Vec::new();
+        if self.summary.compliance_rate < min_compliance {
+            failures.push(format!(
+                "conventional-commit compliance {:.0}% is below minimum {:.0}%",
+                self.summary.compliance_rate * 100.0,
+                min_compliance * 100.0
+            ));
+        }
+        if fail_on_fixups && self.summary.fixup_commits > 0 {
+            failures.push(format!(
+                "{} fixup/squash commit(s) not squashed",
+                self.summary.fixup_commits
+            ));
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::threshold_violation(
+                failures.join("; "),
+                self.summary.compliance_rate,
+            ))
+        }
+    }
+}
+
+/// Quality assessment of a single commit message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitQuality {
+    pub commit_hash: String,
+    pub author: String,
+    pub timestamp: DateTime<Utc>,
+    pub subject: String,
+    pub kind: CommitKind,
+    /// Parsed conventional-commit header, if the subject has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conventional: Option<ConventionalHeader>,
+    /// Whether the commit is held to the conventional format.
+    pub scored: bool,
+    /// Scored and uses an accepted conventional type.
+    pub compliant: bool,
+    pub subject_length: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issue_refs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<MessageProblem>,
+}
+
+/// Commit category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitKind {
+    Normal,
+    Merge,
+    Revert,
+    /// `fixup!`, `squash!` or `amend!` commits meant to be autosquashed.
+    Fixup,
+}
+
+/// Parsed `type(scope)!: description` header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConventionalHeader {
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// `!` after the type/scope or a `BREAKING CHANGE:` footer.
+    pub breaking: bool,
+}
+
+/// A problem with a commit message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageProblem {
+    /// Subject does not follow `type(scope): description`.
+    NotConventional,
+    /// Conventional header with a type outside `commits.types`.
+    UnknownType,
+    /// Subject longer than `commits.max_subject_length`.
+    SubjectTooLong,
+    /// Subject ends with a period.
+    TrailingPeriod,
+    /// Work-in-progress commit.
+    Wip,
+}
+
+impl std::fmt::Display for MessageProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::NotConventional => "not_conventional",
+            Self::UnknownType => "unknown_type",
+            Self::SubjectTooLong => "subject_too_long",
+            Self::TrailingPeriod => "trailing_period",
+            Self::Wip => "wip",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Summary statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_commits: usize,
+    pub merge_commits: usize,
+    /// Commits held to the conventional format.
+    pub scored_commits: usize,
+    pub compliant_commits: usize,
+    /// Compliant / scored (1.0 when nothing is scored).
+    pub compliance_rate: f64,
+    pub avg_subject_length: f64,
+    pub long_subjects: usize,
+    pub linked_commits: usize,
+    /// Non-merge commits referencing an issue.
+    pub issue_link_rate: f64,
+    pub revert_commits: usize,
+    /// Reverts per non-merge commit.
+    pub revert_density: f64,
+    pub fixup_commits: usize,
+    /// Fixup/squash commits per non-merge commit.
+    pub fixup_density: f64,
+    pub breaking_changes: usize,
+    pub by_type: BTreeMap<String, usize>,
+    pub by_problem: BTreeMap<String, usize>,
+}
+
+impl Summary {
+    fn from_commits(commits: &[CommitQuality]) -> Self {
+        let mut summary = Self {
+            total_commits: commits.len(),
+            ..Default::default()
+        };
+        let mut subject_chars = 0;
+        for commit in commits {
+            match commit.kind {
+                CommitKind::Merge => {
+                    summary.merge_commits += 1;
+                    continue;
+                }
+                CommitKind::Revert => summary.revert_commits += 1,
+                CommitKind::Fixup => summary.fixup_commits += 1,
+                CommitKind::Normal => {}
+            }
+            subject_chars += commit.subject_length;
+            if !commit.issue_refs.is_empty() {
+                summary.linked_commits += 1;
+            }
+            if commit.scored {
+                summary.scored_commits += 1;
+            }
+            if commit.compliant {
+                summary.compliant_commits += 1;
+            }
+            if let Some(ref header) = commit.conventional {
+                *summary
+                    .by_type
+                    .entry(header.commit_type.clone())
+                    .or_default() += 1;
+                if header.breaking {
+                    summary.breaking_changes += 1;
+                }
+            }
+            for problem in &commit.problems {
+                if *problem == MessageProblem::SubjectTooLong {
+                    summary.long_subjects += 1;
+                }
+                *summary.by_problem.entry(problem.to_string()).or_default() += 1;
+            }
+        }
+
+        let non_merge = summary.total_commits - summary.merge_commits;
+        summary.compliance_rate = if summary.scored_commits > 0 {
+            summary.compliant_commits as f64 / summary.scored_commits as f64
+        } else {
+            1.0
+        };
+        if non_merge > 0 {
+            let non_merge = non_merge as f64;
+            summary.avg_subject_length = subject_chars as f64 / non_merge;
+            summary.issue_link_rate = summary.linked_commits as f64 / non_merge;
+            summary.revert_density = summary.revert_commits as f64 / non_merge;
+            summary.fixup_density = summary.fixup_commits as f64 / non_merge;
+        }
+        summary
+    }
+}
+
+/// Assess one commit message.
+fn assess(message: &CommitMessage, config: &CommitsConfig) -> CommitQuality {
+    let subject = message.subject.as_str();
+    let conventional = parse_conventional(subject, &message.body);
+    let kind = classify(message, conventional.as_ref());
+
+    // Merges, autosquash commits, and git-generated reverts are exempt.
+    let scored = match kind {
+        CommitKind::Merge | CommitKind::Fixup => false,
+        CommitKind::Revert => conventional.is_some(),
+        CommitKind::Normal => true,
+    };
+
+    let subject_length = subject.chars().count();
+    let mut problems = Vec::new();
+    let mut compliant = false;
+    if scored {
+        match conventional {
+            Some(ref header) if is_known_type(&header.commit_type, &config.types) => {
+                compliant = true;
+            }
+            Some(_) => problems.push(MessageProblem::UnknownType),
+            None => problems.push(MessageProblem::NotConventional),
+        }
+    }
+    if kind != CommitKind::Merge {
+        if subject_length > config.max_subject_length {
+            problems.push(MessageProblem::SubjectTooLong);
+        }
+        if subject.ends_with('.') {
+            problems.push(MessageProblem::TrailingPeriod);
+        }
+        if is_wip(subject) {
+            problems.push(MessageProblem::Wip);
+        }
+    }
+
+    CommitQuality {
+        commit_hash: message.sha.clone(),
+        author: message.author.clone(),
+        timestamp: Utc
+            .timestamp_opt(message.timestamp, 0)
+            .single()
+            .unwrap_or_default(),
+        subject: subject.to_string(),
+        kind,
+        conventional,
+        scored,
+        compliant,
+        subject_length,
+        issue_refs: issue_refs(subject, &message.body),
+        problems,
+    }
+}
+
+fn classify(message: &CommitMessage, conventional: Option<&ConventionalHeader>) -> CommitKind {
+    let subject = message.subject.as_str();
+    if message.parents > 1 {
+        CommitKind::Merge
+    } else if ["fixup!", "squash!", "amend!"]
+        .iter()
+        .any(|prefix| subject.starts_with(prefix))
+    {
+        CommitKind::Fixup
+    } else if subject.starts_with("Revert \"")
+        || conventional.is_some_and(|h| h.commit_type.eq_ignore_ascii_case("revert"))
+    {
+        CommitKind::Revert
+    } else {
+        CommitKind::Normal
+    }
+}
+
+/// Parse a conventional-commit header from the subject.
+fn parse_conventional(subject: &str, body: &str) -> Option<ConventionalHeader> {
+    static HEADER: OnceLock<Regex> = OnceLock::new();
+    let header = HEADER.get_or_init(|| {
+        Regex::new(r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()\r\n]+)\))?(?P<bang>!)?: \S")
+            .expect("valid regex")
+    });
+
+    let caps = header.captures(subject)?;
+    let breaking = caps.name("bang").is_some()
+        || body.lines().any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+    Some(ConventionalHeader {
+        commit_type: caps["type"].to_lowercase(),
+        scope: caps.name("scope").map(|s| s.as_str().trim().to_string()),
+        breaking,
+    })
+}
+
+fn is_known_type(commit_type: &str, types: &[String]) -> bool {
+    types.iter().any(|t| t.eq_ignore_ascii_case(commit_type))
+}
+
+fn is_wip(subject: &str) -> bool {
+    static WIP: OnceLock<Regex> = OnceLock::new();
+    WIP.get_or_init(|| Regex::new(r"(?i)^\s*(\[wip\]|wip\b)").expect("valid regex"))
+        .is_match(subject)
+}
+
+/// Issue references in the message: `#123`, `PROJ-123`, and issue/PR URLs.
+fn issue_refs(subject: &str, body: &str) -> Vec<String> {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        vec![
+            Regex::new(r"https?://\S+/(?:issues|pull|merge_requests)/\d+").expect("valid regex"),
+            Regex::new(r"(?:^|[\s(\[])(#\d+)\b").expect("valid regex"),
+            Regex::new(r"\b([A-Z][A-Z0-9]+-\d+)\b").expect("valid regex"),
+        ]
+    });
+
+    let mut refs: Vec<String> = Vec::new();
+    for text in [subject, body] {
+        for pattern in patterns {
+            for caps in pattern.captures_iter(text) {
+                let found = caps.get(1).unwrap_or_else(|| caps.get(0).expect("match"));
+                let found = found.as_str().to_string();
+                if !refs.contains(&found) {
+                    refs.push(found);
+                }
+            }
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(subject: &str, body: &str) -> CommitMessage {
+        CommitMessage {
+            sha: "abc123".to_string(),
+            author: "Test".to_string(),
+            timestamp: 1_704_067_200,
+            subject: subject.to_string(),
+            body: body.to_string(),
+            parents: 1,
+        }
+    }
+
+    #[test]
+    fn test_parse_conventional() {
+        let header = parse_conventional("feat(cli)!: add flag", "").unwrap();
+        assert_eq!(header.commit_type, "feat");
+        assert_eq!(header.scope.as_deref(), Some("cli"));
+        assert!(header.breaking);
+
+        let header = parse_conventional("fix: handle empty", "BREAKING CHANGE: drops x").unwrap();
+        assert!(header.scope.is_none());
+        assert!(header.breaking);
+
+        assert!(parse_conventional("Add flag", "").is_none());
+        assert!(parse_conventional("feat:missing space", "").is_none());
+        assert!(parse_conventional("feat: ", "").is_none());
+    }
+
+    #[test]
+    fn test_assess_flags_problems() {
+        let config = CommitsConfig::default();
+
+        let good = assess(&message("feat(api): add endpoint", ""), &config);
+        assert!(good.scored && good.compliant);
+        assert!(good.problems.is_empty());
+
+        let bad = assess(&message("Updated the thing.", ""), &config);
+        assert!(!bad.compliant);
+        assert_eq!(
+            bad.problems,
+            vec![
+                MessageProblem::NotConventional,
+                MessageProblem::TrailingPeriod
+            ]
+        );
+
+        let unknown = assess(&message("feature: add endpoint", ""), &config);
+        assert_eq!(unknown.problems, vec![MessageProblem::UnknownType]);
+
+        let long = assess(&message(&format!("fix: {}", "x".repeat(80)), ""), &config);
+        assert!(long.compliant);
+        assert_eq!(long.problems, vec![MessageProblem::SubjectTooLong]);
+
+        let wip = assess(&message("WIP parser", ""), &config);
+        assert!(wip.problems.contains(&MessageProblem::Wip));
+    }
+
+    #[test]
+    fn test_assess_exempts_merges_reverts_and_fixups() {
+        let config = CommitsConfig::default();
+
+        let mut merge = message("Merge branch 'main' into topic", "");
+        merge.parents = 2;
+        let merge = assess(&merge, &config);
+        assert_eq!(merge.kind, CommitKind::Merge);
+        assert!(!merge.scored && merge.problems.is_empty());
+
+        let revert = assess(&message("Revert \"feat: add endpoint\"", ""), &config);
+        assert_eq!(revert.kind, CommitKind::Revert);
+        assert!(!revert.scored);
+
+        let conventional_revert = assess(&message("revert: drop endpoint", ""), &config);
+        assert_eq!(conventional_revert.kind, CommitKind::Revert);
+        assert!(conventional_revert.compliant);
+
+        let fixup = assess(&message("fixup! feat: add endpoint", ""), &config);
+        assert_eq!(fixup.kind, CommitKind::Fixup);
+        assert!(!fixup.scored);
+    }
+
+    #[test]
+    fn test_issue_refs() {
+        let refs = issue_refs(
+            "fix(auth): expire tokens (#42)",
+            "Refs PROJ-7 and https://github.com/o/r/issues/9\nSee #42",
+        );
+        assert_eq!(
+            refs,
+            vec!["#42", "https://github.com/o/r/issues/9", "PROJ-7"]
+        );
+        assert!(issue_refs("fix: utf-8 handling", "").is_empty());
+    }
+
+    #[test]
+    fn test_summary_and_check() {
+        let config = CommitsConfig::default();
+        let mut merge = message("Merge pull request #3", "");
+        merge.parents = 2;
+        let commits: Vec<CommitQuality> = [
+            message("feat: a (#1)", ""),
+            message("fix: b", ""),
+            message("fixup! fix: b", ""),
+            message("Revert \"fix: b\"", ""),
+            message("misc changes", ""),
+            merge,
+        ]
+        .iter()
+        .map(|m| assess(m, &config))
+        .collect();
+        let summary = Summary::from_commits(&commits);
+
+        assert_eq!(summary.total_commits, 6);
+        assert_eq!(summary.merge_commits, 1);
+        assert_eq!(summary.scored_commits, 3);
+        assert_eq!(summary.compliant_commits, 2);
+        assert!((summary.compliance_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summary.linked_commits, 1);
+        assert!((summary.revert_density - 0.2).abs() < 1e-9);
+        assert!((summary.fixup_density - 0.2).abs() < 1e-9);
+        assert_eq!(summary.by_type.get("feat"), Some(&1));
+        assert_eq!(summary.by_problem.get("not_conventional"), Some(&1));
+
+        let analysis = Analysis {
+            generated_at: Utc::now(),
+            period_days: 30,
+            commits,
+            summary,
+        };
+        assert!(analysis.check(0.5, false).is_ok());
+        let err = analysis.check(0.8, true).unwrap_err().to_string();
+        assert!(err.contains("below minimum 80%"));
+        assert!(err.contains("1 fixup/squash commit(s)"));
+    }
+
+    #[test]
+    fn test_analyze_repository() {
+        use std::process::Command;
+
+        let temp = tempfile::tempdir().unwrap();
+        let repo_path = temp.path();
+        for args in [
+            vec!["init"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test User"],
+            vec!["commit", "--allow-empty", "-m", "feat: initial"],
+            vec!["commit", "--allow-empty", "-m", "tweak stuff"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(repo_path)
+                .output()
+                .expect("failed to run git");
+        }
+
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(repo_path, &config).unwrap();
+        let ctx =
+            AnalysisContext::new(&file_set, &config, Some(repo_path)).with_git_path(repo_path);
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert_eq!(analysis.summary.total_commits, 2);
+        assert_eq!(analysis.summary.compliant_commits, 1);
+        assert_eq!(analysis.commits[0].subject, "tweak stuff");
+    }
+
+    #[test]
+    fn test_analyze_requires_git() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(temp.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(temp.path()));
+        assert!(Analyzer::new().analyze(&ctx).is_err());
+    }
+}
@@ -34,7 +34,13 @@ use std::time::Instant;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result, SourceFile};
+use crate::config::GeneratedHandling;
+use crate::core::generated::{self, GeneratedKind, GeneratedSummary};
+use crate::core::{
+    AnalysisContext, Analyzer as AnalyzerTrait, Language, Result, Severity, SeverityCounts,
+    SourceFile, Span,
+};
+use crate::git::LineOrigin;
 use crate::parser::queries::{
     get_decision_node_types, get_flat_node_types, get_nesting_node_types,
 };
@@ -66,6 +72,15 @@ impl Analyzer {
 
     /// Analyze complexity for a single file.
     pub fn analyze_file(&self, path: &std::path::Path) -> Result<FileResult> {
+        Ok(analyze_parse_result(&self.parse_file(path)?))
+    }
+
+    /// Analyze complexity for file content (without reading from filesystem).
+    pub fn analyze_content(&self, path: &Path, content: Vec<u8>) -> Result<FileResult> {
+        Ok(analyze_parse_result(&self.parse_content(path, content)?))
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<ParseResult> {
         // Skip files that are too large (likely minified bundles)
         if let Ok(metadata) = std::fs::metadata(path) {
             if metadata.len() > Self::MAX_FILE_SIZE {
@@ -79,12 +94,10 @@ impl Analyzer {
                 });
             }
         }
-        let result = self.parser.parse_file(path)?;
-        Ok(analyze_parse_result(&result))
+        self.parser.parse_file(path)
     }
 
-    /// Analyze complexity for file content (without reading from filesystem).
-    pub fn analyze_content(&self, path: &Path, content: Vec<u8>) -> Result<FileResult> {
+    fn parse_content(&self, path: &Path, content: Vec<u8>) -> Result<ParseResult> {
         // Skip files that are too large
         if content.len() > Self::MAX_FILE_SIZE as usize {
             return Err(crate::core::Error::Parse {
@@ -97,14 +110,16 @@ impl Analyzer {
             });
         }
 
-        let language =
-            Language::detect(path).ok_or_else(|| crate::core::Error::UnsupportedLanguage {
-                path: path.to_path_buf(),
-            })?;
+        let language = match path.extension() {
+            Some(_) => Language::detect(path),
+            None => Language::from_script(&content),
+        }
+        .ok_or_else(|| crate::core::Error::UnsupportedLanguage {
+            path: path.to_path_buf(),
+        })?;
 
         let source_file = SourceFile::from_content(path, language, content);
-        let result = self.parser.parse_source(&source_file)?;
-        Ok(analyze_parse_result(&result))
+        self.parser.parse_source(&source_file)
     }
 }
 
@@ -124,21 +139,22 @@ impl AnalyzerTrait for Analyzer {
         let total_files = ctx.files.len();
         let counter = Arc::new(AtomicUsize::new(0));
 
-        let results: Vec<FileResult> = ctx
+        let mut results: Vec<FileResult> = ctx
             .files
             .files()
             .par_iter()
             .filter_map(|path| {
-                let result = if ctx.content_source.is_some() {
-                    // Read via content source (e.g., git tree)
-                    ctx.read_file(path)
-                        .ok()
-                        .and_then(|content| self.analyze_content(path, content).ok())
-                } else {
-                    // Read from filesystem using absolute path
-                    let full_path = ctx.root.join(path);
-                    self.analyze_file(&full_path).ok()
-                };
+                // Skip files that are too large (likely minified bundles)
+                let parsed = ctx
+                    .parsed(path)
+                    .ok()
+                    .filter(|parsed| parsed.source.len() as u64 <= Self::MAX_FILE_SIZE);
+                let result = parsed.map(|parsed| {
+                    let mut file = analyze_parse_result(&parsed);
+                    file.generated =
+                        generated::classify(path, &parsed.source, &ctx.config.generated);
+                    file
+                });
 
                 // Report progress
                 let current = counter.fetch_add(1, Ordering::Relaxed) + 1;
@@ -148,7 +164,61 @@ impl AnalyzerTrait for Analyzer {
             })
             .collect();
 
-        let summary = build_summary(&results);
+        if let Some(blame) = &ctx.blame {
+            let complexity = &ctx.config.complexity;
+            results
+                .par_iter_mut()
+                .flat_map(|file| file.functions.par_iter_mut())
+                .filter(|func| {
+                    func.metrics.cyclomatic > complexity.cyclomatic_error
+                        || func.metrics.cognitive > complexity.cognitive_error
+                })
+                .for_each(|func| {
+                    func.introduced = blame.latest_origin(
+                        &ctx.root.join(&func.file),
+                        func.start_line,
+                        func.end_line,
+                    );
+                });
+        }
+
+        if let Some(changed) = &ctx.changed_lines {
+            results
+                .par_iter_mut()
+                .flat_map(|file| file.functions.par_iter_mut())
+                .for_each(|func| {
+                    func.changed = Some(changed.touches(
+                        Path::new(&func.file),
+                        func.start_line,
+                        func.end_line,
+                    ));
+                });
+        }
+
+        let handling = ctx.config.generated.complexity;
+        let generated_files = results.iter().filter(|f| f.generated.is_some()).count();
+        let total_files = results.len();
+        match handling {
+            GeneratedHandling::Include => {}
+            GeneratedHandling::Exclude => results.retain(|f| f.generated.is_none()),
+            GeneratedHandling::Downweight => {
+                let weight = ctx.config.generated.weight;
+                for file in results.iter_mut().filter(|f| f.generated.is_some()) {
+                    file.downweight(weight);
+                }
+            }
+        }
+
+        let mut summary = build_summary(
+            &results,
+            ctx.config.complexity.exclude_trivial_from_percentiles,
+        );
+        summary.generated = GeneratedSummary::new(handling, generated_files, total_files);
+        summary.severity_counts = results
+            .iter()
+            .flat_map(|file| &file.functions)
+            .filter_map(|func| function_severity(&func.metrics, &ctx.config.complexity))
+            .collect();
         let analysis = Analysis {
             files: results,
             summary,
@@ -187,6 +257,12 @@ pub struct Violation {
     pub cyclomatic: u32,
     /// Cognitive complexity.
     pub cognitive: u32,
+    /// Most recent commit touching the function, when blame is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<LineOrigin>,
+    /// Whether the function overlaps lines changed since `--base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
 }
 
 impl Analysis {
@@ -212,6 +288,8 @@ impl Analysis {
                 line: func.start_line,
                 cyclomatic: func.metrics.cyclomatic,
                 cognitive: func.metrics.cognitive,
+                introduced: func.introduced.clone(),
+                changed: func.changed,
             })
             .collect();
 
@@ -240,6 +318,28 @@ pub struct FileResult {
     pub avg_cyclomatic: f64,
     /// Average cognitive complexity.
     pub avg_cognitive: f64,
+    /// Set when the file is generated or macro-expanded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<GeneratedKind>,
+}
+
+impl FileResult {
+    /// Scale function complexities by `weight` and recompute the totals.
+    fn downweight(&mut self, weight: f64) {
+        self.total_cyclomatic = 0;
+        self.total_cognitive = 0;
+        for func in &mut self.functions {
+            func.metrics.cyclomatic = generated::downweight(func.metrics.cyclomatic, weight);
+            func.metrics.cognitive = generated::downweight(func.metrics.cognitive, weight);
+            self.total_cyclomatic += func.metrics.cyclomatic;
+            self.total_cognitive += func.metrics.cognitive;
+        }
+        if !self.functions.is_empty() {
+            let count = self.functions.len() as f64;
+            self.avg_cyclomatic = self.total_cyclomatic as f64 / count;
+            self.avg_cognitive = self.total_cognitive as f64 / count;
+        }
+    }
 }
 
 /// Per-function complexity result.
@@ -253,10 +353,33 @@ pub struct FunctionResult {
     pub start_line: u32,
     /// End line (1-indexed).
     pub end_line: u32,
+    /// Range of the whole function.
+    #[serde(default)]
+    pub span: Span,
+    /// Most recent commit touching the function; set with `[git] blame` for
+    /// functions over the error thresholds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<LineOrigin>,
+    /// Whether the function overlaps lines changed since `--base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
+    /// Set when the function only returns, assigns or forwards one thing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trivial: Option<TrivialKind>,
     /// Complexity metrics.
     pub metrics: Metrics,
 }
 
+/// Why a function counts as trivial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrivialKind {
+    /// A getter or setter: a single return or assignment with no calls.
+    Accessor,
+    /// A single call, such as an overload forwarding to another.
+    Delegation,
+}
+
 /// Complexity metrics for a function.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Metrics {
@@ -297,6 +420,36 @@ pub struct AnalysisSummary {
     pub p90_cognitive: u32,
     /// P95 cognitive complexity.
     pub p95_cognitive: u32,
+    /// Generated and macro-expanded files found, and how they were handled.
+    #[serde(default)]
+    pub generated: GeneratedSummary,
+    /// Functions over the warning (medium) or error (high) thresholds.
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
+    /// Accessors and one-call delegations among the functions.
+    #[serde(default)]
+    pub trivial_functions: usize,
+    /// Whether trivial functions were left out of the percentiles.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub trivial_excluded: bool,
+}
+
+/// Severity of a function against the configured thresholds, if over any.
+fn function_severity(
+    metrics: &Metrics,
+    thresholds: &crate::config::ComplexityConfig,
+) -> Option<Severity> {
+    if metrics.cyclomatic > thresholds.cyclomatic_error
+        || metrics.cognitive > thresholds.cognitive_error
+    {
+        Some(Severity::High)
+    } else if metrics.cyclomatic > thresholds.cyclomatic_warn
+        || metrics.cognitive > thresholds.cognitive_warn
+    {
+        Some(Severity::Medium)
+    } else {
+        None
+    }
 }
 
 /// Analyze a parsed file and extract complexity metrics.
@@ -310,10 +463,11 @@ fn analyze_parse_result(result: &ParseResult) -> FileResult {
         total_cognitive: 0,
         avg_cyclomatic: 0.0,
         avg_cognitive: 0.0,
+        generated: None,
     };
 
     for func in functions {
-        let metrics = analyze_function_complexity(&func, result);
+        let (metrics, trivial) = function_complexity(&func, result);
         file_result.total_cyclomatic += metrics.cyclomatic;
         file_result.total_cognitive += metrics.cognitive;
 
@@ -322,6 +476,10 @@ fn analyze_parse_result(result: &ParseResult) -> FileResult {
             file: result.path.to_string_lossy().to_string(),
             start_line: func.start_line,
             end_line: func.end_line,
+            span: func.span,
+            introduced: None,
+            changed: None,
+            trivial,
             metrics,
         });
     }
@@ -337,27 +495,98 @@ fn analyze_parse_result(result: &ParseResult) -> FileResult {
 
 /// Analyze complexity for a single function from its parse result.
 pub fn analyze_function_complexity(func: &parser::FunctionNode, result: &ParseResult) -> Metrics {
+    function_complexity(func, result).0
+}
+
+/// Metrics for a function, and whether it is trivial.
+fn function_complexity(
+    func: &parser::FunctionNode,
+    result: &ParseResult,
+) -> (Metrics, Option<TrivialKind>) {
     let root = result.root_node();
 
     // Find the function node in the tree
     let func_node = find_function_at_line(&root, func.start_line);
 
-    let (cyclomatic, cognitive, max_nesting) = if let Some(node) = func_node {
+    let (cyclomatic, cognitive, max_nesting, trivial) = if let Some(node) = func_node {
         let body = node.child_by_field_name("body").unwrap_or(node);
+        let cyclomatic = 1 + count_decision_points(&body, &result.source, result.language);
         (
-            1 + count_decision_points(&body, &result.source, result.language),
+            cyclomatic,
             calculate_cognitive_complexity(&body, &result.source, result.language, 0),
             calculate_max_nesting(&body, &result.source, 0),
+            (cyclomatic == 1).then(|| trivial_kind(&body)).flatten(),
         )
     } else {
-        (1, 0, 0)
+        (1, 0, 0, None)
     };
 
-    Metrics {
+    let metrics = Metrics {
         cyclomatic,
         cognitive,
         max_nesting,
         lines: func.end_line.saturating_sub(func.start_line) + 1,
+    };
+    (metrics, trivial)
+}
+
+/// Classify a function body holding a single return, assignment or call.
+/// Bodies with declarations, nested blocks or closures aren't trivial.
+fn trivial_kind(body: &tree_sitter::Node<'_>) -> Option<TrivialKind> {
+    const CONTAINER_KINDS: &[&str] = &[
+        "block",
+        "statement_block",
+        "compound_statement",
+        "body_statement",
+        "constructor_body",
+    ];
+
+    let statement = if CONTAINER_KINDS.contains(&body.kind()) {
+        let mut cursor = body.walk();
+        let mut statements = body
+            .named_children(&mut cursor)
+            .filter(|child| !child.kind().contains("comment"));
+        match (statements.next(), statements.next()) {
+            (Some(statement), None) => statement,
+            _ => return None,
+        }
+    } else {
+        // Expression bodies, as in `user => user.name`
+        *body
+    };
+    let kind = statement.kind();
+    if kind.contains("declaration") || kind.contains("definition") || kind.starts_with("let") {
+        return None;
+    }
+
+    let mut calls = false;
+    let mut cursor = statement.walk();
+    loop {
+        let kind = cursor.node().kind();
+        if CONTAINER_KINDS.contains(&kind)
+            || kind.contains("lambda")
+            || kind.contains("closure")
+            || kind.contains("function")
+        {
+            return None;
+        }
+        calls |= kind.contains("call") || kind.contains("invocation");
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        loop {
+            if cursor.goto_next_sibling() {
+                break;
+            }
+            if !cursor.goto_parent() {
+                return Some(if calls {
+                    TrivialKind::Delegation
+                } else {
+                    TrivialKind::Accessor
+                });
+            }
+        }
     }
 }
 
@@ -619,10 +848,13 @@ fn get_operator<'a>(node: &tree_sitter::Node<'a>, source: &'a [u8]) -> Option<&'
     None
 }
 
-/// Build summary statistics from file results.
-fn build_summary(results: &[FileResult]) -> AnalysisSummary {
+/// Build summary statistics from file results. With `exclude_trivial`,
+/// trivial functions still count towards the totals, averages and maxima
+/// but not the percentiles.
+fn build_summary(results: &[FileResult], exclude_trivial: bool) -> AnalysisSummary {
     let mut summary = AnalysisSummary {
         total_files: results.len(),
+        trivial_excluded: exclude_trivial,
         ..Default::default()
     };
 
@@ -635,8 +867,13 @@ fn build_summary(results: &[FileResult]) -> AnalysisSummary {
         summary.total_functions += file.functions.len();
 
         for func in &file.functions {
-            all_cyclomatic.push(func.metrics.cyclomatic);
-            all_cognitive.push(func.metrics.cognitive);
+            if func.trivial.is_some() {
+                summary.trivial_functions += 1;
+            }
+            if !(exclude_trivial && func.trivial.is_some()) {
+                all_cyclomatic.push(func.metrics.cyclomatic);
+                all_cognitive.push(func.metrics.cognitive);
+            }
             total_cyclomatic += func.metrics.cyclomatic as u64;
             total_cognitive += func.metrics.cognitive as u64;
 
@@ -701,6 +938,10 @@ mod tests {
                     file: "test.rs".to_string(),
                     start_line: 1,
                     end_line: 5,
+                    span: Span::default(),
+                    introduced: None,
+                    changed: None,
+                    trivial: None,
                     metrics: Metrics {
                         cyclomatic: 5,
                         cognitive: 3,
@@ -712,6 +953,7 @@ mod tests {
                 total_cognitive: 3,
                 avg_cyclomatic: 5.0,
                 avg_cognitive: 3.0,
+                generated: None,
             }],
             summary: AnalysisSummary::default(),
         };
@@ -731,6 +973,10 @@ mod tests {
                     file: "test.rs".to_string(),
                     start_line: 1,
                     end_line: 50,
+                    span: Span::default(),
+                    introduced: None,
+                    changed: None,
+                    trivial: None,
                     metrics: Metrics {
                         cyclomatic: 20,
                         cognitive: 5,
@@ -742,6 +988,7 @@ mod tests {
                 total_cognitive: 5,
                 avg_cyclomatic: 20.0,
                 avg_cognitive: 5.0,
+                generated: None,
             }],
             summary: AnalysisSummary::default(),
         };
@@ -765,6 +1012,10 @@ mod tests {
                     file: "test.rs".to_string(),
                     start_line: 1,
                     end_line: 30,
+                    span: Span::default(),
+                    introduced: None,
+                    changed: None,
+                    trivial: None,
                     metrics: Metrics {
                         cyclomatic: 5,
                         cognitive: 25,
@@ -776,6 +1027,7 @@ mod tests {
                 total_cognitive: 25,
                 avg_cyclomatic: 5.0,
                 avg_cognitive: 25.0,
+                generated: None,
             }],
             summary: AnalysisSummary::default(),
         };
@@ -799,6 +1051,10 @@ mod tests {
                         file: "test.rs".to_string(),
                         start_line: 1,
                         end_line: 5,
+                        span: Span::default(),
+                        introduced: None,
+                        changed: None,
+                        trivial: None,
                         metrics: Metrics {
                             cyclomatic: 3,
                             cognitive: 2,
@@ -811,6 +1067,10 @@ mod tests {
                         file: "test.rs".to_string(),
                         start_line: 10,
                         end_line: 50,
+                        span: Span::default(),
+                        introduced: None,
+                        changed: None,
+                        trivial: None,
                         metrics: Metrics {
                             cyclomatic: 20,
                             cognitive: 18,
@@ -823,6 +1083,10 @@ mod tests {
                         file: "test.rs".to_string(),
                         start_line: 60,
                         end_line: 100,
+                        span: Span::default(),
+                        introduced: None,
+                        changed: None,
+                        trivial: None,
                         metrics: Metrics {
                             cyclomatic: 10,
                             cognitive: 25,
@@ -835,6 +1099,7 @@ mod tests {
                 total_cognitive: 45,
                 avg_cyclomatic: 11.0,
                 avg_cognitive: 15.0,
+                generated: None,
             }],
             summary: AnalysisSummary::default(),
         };
@@ -845,6 +1110,26 @@ mod tests {
         assert_eq!(violations.len(), 2);
     }
 
+    #[test]
+    fn test_function_severity_against_thresholds() {
+        let thresholds = crate::config::ComplexityConfig::default();
+        let metrics = |cyclomatic, cognitive| Metrics {
+            cyclomatic,
+            cognitive,
+            ..Default::default()
+        };
+
+        assert_eq!(function_severity(&metrics(1, 1), &thresholds), None);
+        assert_eq!(
+            function_severity(&metrics(thresholds.cyclomatic_warn + 1, 1), &thresholds),
+            Some(Severity::Medium)
+        );
+        assert_eq!(
+            function_severity(&metrics(1, thresholds.cognitive_error + 1), &thresholds),
+            Some(Severity::High)
+        );
+    }
+
     #[test]
     fn test_percentile() {
         let sorted = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -866,6 +1151,51 @@ mod tests {
         assert_eq!(analyzer.name(), "complexity");
     }
 
+    #[test]
+    fn test_generated_files_are_downweighted_or_excluded() {
+        let dir = tempfile::tempdir().unwrap();
+        let branchy = "fn f(x: i32) -> i32 {\n    if x > 0 { 1 } else if x < 0 { 2 } else if x == 5 { 3 } else { 4 }\n}\n";
+        std::fs::write(dir.path().join("hand.rs"), branchy).unwrap();
+        std::fs::write(
+            dir.path().join("client.rs"),
+            format!("// @generated by protoc\n{branchy}"),
+        )
+        .unwrap();
+        let files = crate::core::FileSet::from_files(
+            dir.path().to_path_buf(),
+            vec!["hand.rs".into(), "client.rs".into()],
+        );
+
+        let mut config = crate::config::Config::default();
+        let ctx = AnalysisContext::new(&files, &config, Some(dir.path()));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+        let by_name = |name: &str| {
+            analysis
+                .files
+                .iter()
+                .find(|f| f.path.ends_with(name))
+                .unwrap()
+                .clone()
+        };
+        let (hand, client) = (by_name("hand.rs"), by_name("client.rs"));
+        assert_eq!(hand.generated, None);
+        assert_eq!(client.generated, Some(GeneratedKind::Generated));
+        assert!(client.total_cyclomatic < hand.total_cyclomatic);
+        assert_eq!(analysis.summary.generated.files, 1);
+        assert!((analysis.summary.generated.ratio - 0.5).abs() < f64::EPSILON);
+
+        config.generated.complexity = GeneratedHandling::Exclude;
+        let ctx = AnalysisContext::new(&files, &config, Some(dir.path()));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+        assert_eq!(analysis.files.len(), 1);
+        assert_eq!(analysis.summary.total_files, 1);
+        assert_eq!(analysis.summary.generated.files, 1);
+        assert_eq!(
+            analysis.summary.generated.handling,
+            GeneratedHandling::Exclude
+        );
+    }
+
     // Language-specific complexity calculation tests
 
     fn parse_and_analyze(code: &[u8], lang: Language, filename: &str) -> FileResult {
@@ -1207,6 +1537,102 @@ class Test {
         assert!(result.functions[0].metrics.cyclomatic >= 2);
     }
 
+    #[test]
+    fn test_trivial_java_accessors_and_forwarding() {
+        let code = br#"
+class User {
+    String getName() { return this.name; }
+    void setName(String name) { this.name = name; }
+    void log(String message) { log(message, 0); }
+    void log(String message, int level) {
+        String line = message;
+        if (level > 0) {
+            System.out.println(line);
+        }
+    }
+    void reset() {
+        this.name = null;
+        this.age = 0;
+    }
+}
+"#;
+        let result = parse_and_analyze(code, Language::Java, "User.java");
+        let trivial: Vec<_> = result
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.start_line, f.trivial))
+            .collect();
+        assert_eq!(
+            trivial,
+            vec![
+                ("getName", 3, Some(TrivialKind::Accessor)),
+                ("setName", 4, Some(TrivialKind::Accessor)),
+                ("log", 5, Some(TrivialKind::Delegation)),
+                ("log", 6, None),
+                ("reset", 12, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trivial_rust_and_python_bodies() {
+        let rust = parse_and_analyze(
+            b"impl Point {\n    fn x(&self) -> i32 { self.x }\n    fn empty(&self) {}\n}\n",
+            Language::Rust,
+            "test.rs",
+        );
+        assert_eq!(rust.functions[0].trivial, Some(TrivialKind::Accessor));
+        assert_eq!(rust.functions[1].trivial, None);
+
+        let python = parse_and_analyze(
+            b"def area(self):\n    return compute_area(self.w, self.h)\n",
+            Language::Python,
+            "test.py",
+        );
+        assert_eq!(python.functions[0].trivial, Some(TrivialKind::Delegation));
+    }
+
+    #[test]
+    fn test_build_summary_excludes_trivial_from_percentiles() {
+        let function = |cyclomatic, trivial| FunctionResult {
+            name: "f".to_string(),
+            file: "test.rs".to_string(),
+            start_line: 1,
+            end_line: 1,
+            span: Span::default(),
+            introduced: None,
+            changed: None,
+            trivial,
+            metrics: Metrics {
+                cyclomatic,
+                ..Default::default()
+            },
+        };
+        let mut functions = vec![function(1, Some(TrivialKind::Accessor)); 8];
+        functions.extend([function(6, None), function(9, None)]);
+        let files = vec![FileResult {
+            path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            functions,
+            total_cyclomatic: 23,
+            total_cognitive: 0,
+            avg_cyclomatic: 2.3,
+            avg_cognitive: 0.0,
+            generated: None,
+        }];
+
+        let all = build_summary(&files, false);
+        assert_eq!(all.trivial_functions, 8);
+        assert_eq!(all.p50_cyclomatic, 1);
+
+        let excluded = build_summary(&files, true);
+        assert_eq!(excluded.trivial_functions, 8);
+        assert_eq!(excluded.total_functions, 10);
+        assert_eq!(excluded.p50_cyclomatic, 9);
+        assert_eq!(excluded.max_cyclomatic, 9);
+        assert!((excluded.avg_cyclomatic - 2.3).abs() < 1e-9);
+    }
+
     #[test]
     fn test_nesting_depth_multiple_levels() {
         let code = br#"
@@ -3,6 +3,24 @@
 //! Finds unreachable/unused functions, variables, and classes using
 //! reference graph analysis.
 //!
+//! ## Reachability
+//!
+//! Definitions and references are resolved across the whole project (see
+//! [`symbols`]), and a function is live only if an entry point or
+//! module-level code reaches it. A function referenced only by other dead
+//! functions is reported too. Exported functions count as entry points,
+//! except in internal-only modules whose exports cannot leave the project:
+//! Rust `pub(crate)`/`pub(super)` items, Go packages under `internal/`, and
+//! Python modules or packages named with a leading underscore.
+//!
+//! ## Test-only Code
+//!
+//! Production functions that are referenced from test code but never from
+//! production code are reported separately in `test_only`. Test files are
+//! classified by [`crate::core::is_test_file`]; Rust functions inside
+//! `#[cfg(test)]` modules also count as test code. Entry points (including
+//! exported symbols) are not reported, matching the dead code rules.
+//!
 //! ## Limitations
 //!
 //! Currently only function definitions and usages are tracked.
@@ -10,17 +28,25 @@
 //! constants, and type aliases are not yet analyzed.
 
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::time::Instant;
 
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
-use crate::parser::{self, Parser};
+use crate::analyzers::graph::rust_modules::{self, RustFile, RustModules};
+use crate::core::{
+    is_generated_path, is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Language, Result,
+    Span,
+};
+use crate::parser;
+
+mod symbols;
+
+use symbols::{qualify, simple_name, Reference, SymbolGraph};
 
 /// Dead code analyzer.
 pub struct Analyzer {
-    parser: Parser,
     confidence_threshold: f64,
 }
 
@@ -33,7 +59,6 @@ impl Default for Analyzer {
 impl Analyzer {
     pub fn new() -> Self {
         Self {
-            parser: Parser::new(),
             confidence_threshold: 0.8,
         }
     }
@@ -42,12 +67,6 @@ impl Analyzer {
         self.confidence_threshold = threshold.clamp(0.0, 1.0);
         self
     }
-
-    /// Analyze a single file for definitions and usages.
-    fn analyze_file(&self, path: &std::path::Path) -> Result<FileDeadCode> {
-        let result = self.parser.parse_file(path)?;
-        Ok(collect_file_data(&result))
-    }
 }
 
 impl AnalyzerTrait for Analyzer {
@@ -87,91 +106,78 @@ impl AnalyzerTrait for Analyzer {
         // the definition/reference graph. Cargo results supplement this with
         // compiler-level dead code warnings when available.
         let files: Vec<_> = ctx.files.iter().collect();
-        let file_results: Vec<FileDeadCode> = files
+        let mut file_results: Vec<FileDeadCode> = files
             .par_iter()
-            .filter_map(|path| {
-                let full_path = ctx.root.join(path);
-                self.analyze_file(&full_path).ok()
-            })
+            .filter_map(|path| ctx.parsed(path).ok())
+            .map(|result| collect_file_data(&result))
             .collect();
 
-        // Phase 2: Build global symbol tables with qualified names
-        // Use file::function_name format to prevent collisions when multiple files
-        // have functions with the same name.
-        let mut all_definitions: HashMap<String, Definition> = HashMap::new();
-        let mut all_usages: HashSet<String> = HashSet::new();
-        let mut all_calls: Vec<CallReference> = Vec::new();
-        // Map from simple name to qualified names for cross-file call resolution
-        let mut name_to_qualified: HashMap<String, Vec<String>> = HashMap::new();
-
-        for fdc in &file_results {
-            for (name, def) in &fdc.definitions {
-                let qualified_name = format!("{}::{}", def.file, name);
-                all_definitions.insert(qualified_name.clone(), def.clone());
-                name_to_qualified
-                    .entry(name.clone())
-                    .or_default()
-                    .push(qualified_name);
-            }
-            all_usages.extend(fdc.usages.iter().cloned());
-            all_calls.extend(fdc.calls.iter().cloned());
-        }
-
-        // Phase 3: Build call graph edges (for reachability) using qualified names
-        let mut call_graph: HashMap<String, Vec<String>> = HashMap::new();
-        for call in &all_calls {
-            // Qualify the caller with its file path
-            let qualified_caller = format!("{}::{}", call.file, call.caller);
-
-            // For the callee, first try same-file lookup, then cross-file
-            let qualified_callees: Vec<String> = {
-                let same_file_qualified = format!("{}::{}", call.file, call.callee);
-                if all_definitions.contains_key(&same_file_qualified) {
-                    vec![same_file_qualified]
-                } else if let Some(qualified_names) = name_to_qualified.get(&call.callee) {
-                    // Cross-file call: could be any of the matching functions
-                    qualified_names.clone()
-                } else {
-                    vec![]
+        // Exported functions of internal-only modules can only be called from
+        // inside the project, so like private ones they need a reference.
+        for fdc in &mut file_results {
+            let path = Path::new(&fdc.path);
+            let relative = path.strip_prefix(ctx.root).unwrap_or(path);
+            if is_internal_module(relative, fdc.language) {
+                for (name, def) in fdc.definitions.iter_mut() {
+                    if !fdc.methods.contains(name) {
+                        def.exported = false;
+                    }
                 }
-            };
-
-            for qualified_callee in qualified_callees {
-                call_graph
-                    .entry(qualified_caller.clone())
-                    .or_default()
-                    .push(qualified_callee);
             }
         }
 
-        // Phase 4: Mark reachable from entry points (using qualified names)
-        let mut reachable: HashSet<String> = HashSet::new();
-        let mut queue: Vec<String> = Vec::new();
-
-        // Identify entry points using qualified names
-        for (qualified_name, def) in &all_definitions {
-            // Extract simple name from qualified name for entry point check
-            let simple_name = qualified_name.rsplit("::").next().unwrap_or(qualified_name);
-            if is_entry_point(simple_name, def) {
-                reachable.insert(qualified_name.clone());
-                queue.push(qualified_name.clone());
+        // Rust `use` paths pin imported names to the file defining them
+        let rust_files: Vec<(String, RustFile)> = file_results
+            .iter_mut()
+            .filter_map(|fdc| Some((fdc.path.clone(), fdc.rust.take()?)))
+            .collect();
+        if !rust_files.is_empty() {
+            let modules = RustModules::new(ctx.root, rust_files);
+            for fdc in &mut file_results {
+                fdc.imports = modules
+                    .bindings(&fdc.path)
+                    .into_iter()
+                    .filter_map(|(local, resolved)| {
+                        Some((local, qualify(&resolved.file, &resolved.symbol?)))
+                    })
+                    .collect();
             }
         }
 
-        // BFS to mark reachable (all names are qualified now)
-        while let Some(current) = queue.pop() {
-            if let Some(callees) = call_graph.get(&current) {
-                for callee in callees {
-                    if all_definitions.contains_key(callee) && !reachable.contains(callee) {
-                        reachable.insert(callee.clone());
-                        queue.push(callee.clone());
-                    }
+        // Phase 2: Resolve definitions and references across all files.
+        // Qualified names (file::function_name) prevent collisions when
+        // multiple files have functions with the same name.
+        let symbols = SymbolGraph::build(&file_results);
+        let all_definitions = &symbols.definitions;
+
+        // Phase 3: Attribute references to test or production code
+        let mut production_refs: HashSet<String> = HashSet::new();
+        let mut test_refs: HashSet<String> = HashSet::new();
+        for fdc in &file_results {
+            production_refs.extend(fdc.production_usages.iter().cloned());
+            test_refs.extend(fdc.usages.difference(&fdc.production_usages).cloned());
+
+            for call in &fdc.calls {
+                // Method calls aren't identifier usages, so attribute the
+                // callee to test or production code by the caller's context.
+                let qualified_caller = qualify(&call.file, &call.caller);
+                let caller_in_test = all_definitions
+                    .get(&qualified_caller)
+                    .is_some_and(|def| def.is_test_file);
+                if caller_in_test {
+                    test_refs.insert(call.callee.clone());
+                } else {
+                    production_refs.insert(call.callee.clone());
                 }
             }
         }
 
+        // Phase 4: Mark reachable from entry points and module-level code
+        let reachable = symbols.reachable(is_entry_point);
+
         // Phase 5: Classify dead code (using qualified names)
         let mut items = Vec::new();
+        let mut test_only = Vec::new();
         let mut by_kind: HashMap<String, usize> = HashMap::new();
 
         // Add cargo-detected dead code items (Rust files).
@@ -179,12 +185,18 @@ impl AnalyzerTrait for Analyzer {
         let mut cargo_reported: HashSet<(String, u32)> = HashSet::new();
         for cargo_item in cargo_items {
             cargo_reported.insert((cargo_item.file.clone(), cargo_item.line));
+            // rustc reports byte offsets; columns need the file contents.
+            let span = cargo_item.byte_range.and_then(|(start, end)| {
+                let content = std::fs::read(ctx.root.join(&cargo_item.file)).ok()?;
+                Some(Span::from_bytes(&content, start, end))
+            });
             let item = DeadCodeItem {
                 name: cargo_item.name,
                 kind: cargo_item.kind.clone(),
                 file: cargo_item.file,
                 line: cargo_item.line,
                 end_line: cargo_item.end_line,
+                span,
                 visibility: "unknown".to_string(),
                 confidence: 1.0, // Cargo/rustc is authoritative
                 reason: cargo_item.message,
@@ -194,15 +206,14 @@ impl AnalyzerTrait for Analyzer {
         }
 
         // Add tree-sitter detected dead code, skipping items already reported by cargo
-        for (qualified_name, def) in &all_definitions {
+        for (qualified_name, def) in all_definitions {
             // Skip generated files -- they participate in the call graph for
             // reachability but their definitions should not be reported.
-            if is_generated_file(&def.file) {
+            if is_generated_path(&def.file) {
                 continue;
             }
 
-            // Extract simple name for entry point check and usage lookup
-            let simple_name = qualified_name.rsplit("::").next().unwrap_or(qualified_name);
+            let simple_name = simple_name(qualified_name);
 
             // Skip entry points
             if is_entry_point(simple_name, def) {
@@ -214,9 +225,8 @@ impl AnalyzerTrait for Analyzer {
                 continue;
             }
 
-            // Check if unreachable (using qualified name) AND not used (simple name in usages)
             let is_unreachable = !reachable.contains(qualified_name);
-            let is_unused = !all_usages.contains(simple_name);
+            let is_unused = !symbols.is_referenced(qualified_name);
 
             if is_unreachable || is_unused {
                 let confidence = calculate_confidence(def, is_unreachable, is_unused);
@@ -228,12 +238,13 @@ impl AnalyzerTrait for Analyzer {
                         file: def.file.clone(),
                         line: def.line,
                         end_line: def.end_line,
+                        span: def.span,
                         visibility: def.visibility.clone(),
                         confidence,
                         reason: if is_unreachable && is_unused {
                             "Not reachable from entry points and no references found".to_string()
                         } else if is_unreachable {
-                            "Not reachable from entry points".to_string()
+                            "Only referenced from unreachable code".to_string()
                         } else {
                             "No references found in codebase".to_string()
                         },
@@ -242,17 +253,38 @@ impl AnalyzerTrait for Analyzer {
                     *by_kind.entry(def.kind.clone()).or_insert(0) += 1;
                     items.push(item);
                 }
+            } else if is_test_only(simple_name, def, &production_refs, &test_refs) {
+                let confidence = calculate_confidence(def, false, false);
+
+                if confidence >= self.confidence_threshold {
+                    test_only.push(DeadCodeItem {
+                        name: simple_name.to_string(),
+                        kind: def.kind.clone(),
+                        file: def.file.clone(),
+                        line: def.line,
+                        end_line: def.end_line,
+                        span: def.span,
+                        visibility: def.visibility.clone(),
+                        confidence,
+                        reason: "Only referenced from test code".to_string(),
+                    });
+                }
             }
         }
 
+        test_only.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
         let total_items = items.len();
+        let test_only_items = test_only.len();
         let analysis = Analysis {
             items,
+            test_only,
             summary: AnalysisSummary {
                 total_items,
                 by_kind,
                 total_definitions: all_definitions.len(),
                 reachable_count: reachable.len(),
+                test_only_items,
             },
         };
 
@@ -269,17 +301,12 @@ impl AnalyzerTrait for Analyzer {
 /// Collect definitions and usages from a parsed file.
 fn collect_file_data(result: &parser::ParseResult) -> FileDeadCode {
     let path_str = result.path.to_string_lossy().to_string();
-    let mut fdc = FileDeadCode {
-        path: path_str.clone(),
-        definitions: HashMap::new(),
-        usages: HashSet::new(),
-        calls: Vec::new(),
-    };
+    let is_test_file = is_test_file(&path_str);
+    let mut fdc = FileDeadCode::new(path_str, result.language, is_test_file);
+    fdc.methods = collect_method_names(result);
 
     let functions = parser::extract_functions(result);
 
-    let is_test_file = is_test_file(&fdc.path);
-
     // For Rust, extract function attributes and context from the AST
     let function_info = if result.language == Language::Rust {
         extract_rust_function_attributes(result)
@@ -312,6 +339,10 @@ fn collect_file_data(result: &parser::ParseResult) -> FileDeadCode {
         let is_in_test_context =
             is_test_file || info.map(|i| i.in_cfg_test_module).unwrap_or(false);
         let is_trait_impl = info.map(|i| i.is_trait_impl).unwrap_or(false);
+        // `pub(crate)` and friends are visible only inside the crate
+        if info.is_some_and(|i| i.restricted_visibility) {
+            exported = false;
+        }
 
         // Apply Python-specific context
         if let Some(ref py_ctx) = python_ctx {
@@ -347,6 +378,7 @@ fn collect_file_data(result: &parser::ParseResult) -> FileDeadCode {
                 file: fdc.path.clone(),
                 line: func.start_line,
                 end_line: func.end_line,
+                span: Some(func.span),
                 visibility,
                 exported,
                 is_test_file: is_in_test_context,
@@ -359,6 +391,10 @@ fn collect_file_data(result: &parser::ParseResult) -> FileDeadCode {
     // Extract usages and calls by walking the AST
     collect_usages_and_calls(result, &mut fdc);
 
+    if result.language == Language::Rust {
+        fdc.rust = Some(rust_modules::parse(result));
+    }
+
     fdc
 }
 
@@ -367,6 +403,8 @@ struct RustFunctionInfo {
     attributes: Vec<String>,
     in_cfg_test_module: bool,
     is_trait_impl: bool,
+    /// Visibility such as `pub(crate)` or `pub(super)`.
+    restricted_visibility: bool,
 }
 
 /// Python-specific context extracted from the AST.
@@ -452,12 +490,17 @@ fn collect_rust_function_info(
     let func_name = name_node.utf8_text(source).ok()?;
 
     let preceding_attrs = collect_preceding_attributes(node, source);
+    let restricted_visibility = node.children(&mut node.walk()).any(|child| {
+        child.kind() == "visibility_modifier"
+            && child.utf8_text(source).is_ok_and(|v| v.starts_with("pub("))
+    });
     Some((
         func_name.to_string(),
         RustFunctionInfo {
             attributes: preceding_attrs,
             in_cfg_test_module,
             is_trait_impl,
+            restricted_visibility,
         },
     ))
 }
@@ -884,10 +927,33 @@ fn collect_usages_and_calls(result: &parser::ParseResult, fdc: &mut FileDeadCode
         // Collect usages from identifiers (excluding definitions)
         if (kind == "identifier" || kind == "type_identifier") && !is_definition_context(&node) {
             if let Ok(name) = node.utf8_text(source) {
+                let in_test_context = fdc.is_test_file
+                    || current_function
+                        .as_ref()
+                        .and_then(|f| fdc.definitions.get(f))
+                        .is_some_and(|def| def.is_test_file);
+                if !in_test_context {
+                    fdc.production_usages.insert(name.to_string());
+                }
                 fdc.usages.insert(name.to_string());
             }
         }
 
+        // Collect references for project-wide resolution. Imports name a
+        // symbol without using it; they sit at module level, which keeps the
+        // ancestor walk off identifiers inside functions.
+        if is_reference_node(kind)
+            && !is_definition_context(&node)
+            && (current_function.is_some() || !is_in_import(&node))
+        {
+            if let Ok(name) = node.utf8_text(source) {
+                fdc.references.insert(Reference {
+                    from: current_function.clone(),
+                    name: name.to_string(),
+                });
+            }
+        }
+
         // Collect function calls
         if kind == "call_expression" || kind == "function_call" || kind == "call" {
             if let Some(callee) = extract_callee(&node, source, lang) {
@@ -910,6 +976,25 @@ fn collect_usages_and_calls(result: &parser::ParseResult, fdc: &mut FileDeadCode
             }
         }
 
+        // JSX elements (`<MyComponent />`) render a component without a call
+        // expression, so treat them as a call to the component.
+        if kind == "jsx_opening_element" || kind == "jsx_self_closing_element" {
+            if let Some(component) = jsx_component_name(&node, source) {
+                if !fdc.is_test_file {
+                    fdc.production_usages.insert(component.clone());
+                }
+                fdc.usages.insert(component.clone());
+                if let Some(ref caller) = current_function {
+                    fdc.calls.push(CallReference {
+                        caller: caller.clone(),
+                        callee: component,
+                        file: fdc.path.clone(),
+                        line: node.start_position().row as u32 + 1,
+                    });
+                }
+            }
+        }
+
         // Move to next node in pre-order traversal
         if cursor.goto_first_child() {
             continue;
@@ -948,6 +1033,95 @@ fn is_function_node(kind: &str) -> bool {
     )
 }
 
+/// Nodes that can name a function: plain identifiers, plus the member names
+/// of method calls (`x.f()` in Go, Rust and JS) and PHP/Ruby names.
+fn is_reference_node(kind: &str) -> bool {
+    matches!(
+        kind,
+        "identifier"
+            | "type_identifier"
+            | "field_identifier"
+            | "property_identifier"
+            | "shorthand_property_identifier"
+            | "name"
+            | "constant"
+    )
+}
+
+fn is_in_import(node: &tree_sitter::Node<'_>) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if matches!(
+            parent.kind(),
+            "use_declaration"
+                | "import_statement"
+                | "import_from_statement"
+                | "import_declaration"
+                | "using_directive"
+                | "namespace_use_declaration"
+        ) {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+/// Names of functions defined as methods: in a class or impl body, or with
+/// a method node kind (Go receivers, JS classes, Ruby).
+fn collect_method_names(result: &parser::ParseResult) -> HashSet<String> {
+    fn visit(
+        node: tree_sitter::Node<'_>,
+        source: &[u8],
+        in_class: bool,
+        names: &mut HashSet<String>,
+    ) {
+        let kind = node.kind();
+        let in_class = if is_function_node(kind) {
+            let is_method =
+                in_class || matches!(kind, "method_declaration" | "method_definition" | "method");
+            let name = node
+                .child_by_field_name("name")
+                .filter(|_| is_method)
+                .and_then(|n| n.utf8_text(source).ok());
+            if let Some(name) = name {
+                names.insert(name.to_string());
+            }
+            // Functions nested in a method are not methods themselves.
+            false
+        } else {
+            in_class
+                || matches!(
+                    kind,
+                    "class_definition" | "class_declaration" | "class_body" | "impl_item"
+                )
+        };
+        for child in node.children(&mut node.walk()) {
+            visit(child, source, in_class, names);
+        }
+    }
+
+    let mut names = HashSet::new();
+    visit(result.root_node(), &result.source, false, &mut names);
+    names
+}
+
+/// Whether a module's exports are visible only inside the project: Go
+/// packages under an `internal/` directory, and Python modules or packages
+/// named with a single leading underscore. `relative` is from the root.
+fn is_internal_module(relative: &Path, lang: Language) -> bool {
+    match lang {
+        Language::Go => relative
+            .parent()
+            .is_some_and(|dir| dir.iter().any(|part| part == "internal")),
+        Language::Python => relative.iter().any(|part| {
+            part.to_str()
+                .is_some_and(|p| p.starts_with('_') && !p.starts_with("__"))
+        }),
+        _ => false,
+    }
+}
+
 fn is_definition_context(node: &tree_sitter::Node<'_>) -> bool {
     if let Some(parent) = node.parent() {
         let parent_kind = parent.kind();
@@ -995,6 +1169,22 @@ fn extract_callee(node: &tree_sitter::Node<'_>, source: &[u8], _lang: Language)
     None
 }
 
+/// Component referenced by a JSX opening or self-closing element.
+///
+/// Lowercase names are intrinsic elements (`<div>`) and yield `None`. For
+/// member expressions (`<UI.Button />`) the last segment is returned.
+fn jsx_component_name(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<String> {
+    let name_node = node.child_by_field_name("name")?;
+    let name_node = match name_node.kind() {
+        "identifier" => name_node,
+        "member_expression" => name_node.child_by_field_name("property")?,
+        _ => return None,
+    };
+    let name = name_node.utf8_text(source).ok()?;
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        .then(|| name.to_string())
+}
+
 /// Scan call arguments for bare identifiers that match known function definitions.
 /// These represent function-as-value references (callbacks) and get synthetic call edges
 /// so that BFS reachability can follow them.
@@ -1080,69 +1270,6 @@ fn is_exported(name: &str, lang: Language) -> bool {
     }
 }
 
-fn is_generated_file(path: &str) -> bool {
-    let filename = std::path::Path::new(path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or(path);
-    let lower = filename.to_lowercase();
-
-    // Protobuf generated files
-    if lower.ends_with(".pb.go")
-        || lower.ends_with(".pb.cc")
-        || lower.ends_with(".pb.h")
-        || lower.ends_with(".pb2.py")
-        || lower.ends_with("_pb2.py")
-        || lower.ends_with("_pb2_grpc.py")
-    {
-        return true;
-    }
-
-    // Common generated file patterns
-    if lower.ends_with(".gen.go")
-        || lower.ends_with(".generated.go")
-        || lower.ends_with(".generated.ts")
-        || lower.ends_with(".generated.js")
-    {
-        return true;
-    }
-
-    // Go-specific generated file conventions
-    if lower.ends_with("_gen.go")
-        || lower.ends_with("_generated.go")
-        || lower == "bindata.go"
-        || lower.ends_with("wire_gen.go")
-    {
-        return true;
-    }
-
-    // Mock generated files
-    if lower.starts_with("mock_") && lower.ends_with(".go") {
-        return true;
-    }
-
-    // Kubernetes code-gen (zz_generated.*.go)
-    if lower.starts_with("zz_generated.") {
-        return true;
-    }
-
-    false
-}
-
-fn is_test_file(path: &str) -> bool {
-    path.ends_with("_test.go")
-        || path.ends_with("_test.py")
-        || path.ends_with(".test.ts")
-        || path.ends_with(".test.js")
-        || path.ends_with(".spec.ts")
-        || path.ends_with(".spec.js")
-        || path.ends_with("_spec.rb")
-        || path.ends_with("_test.rb")
-        || path.contains("/test/")
-        || path.contains("/tests/")
-        || path.contains("/__tests__/")
-}
-
 fn is_entry_point(name: &str, def: &Definition) -> bool {
     // Standard entry points
     if name == "main" || name == "init" || name == "Main" {
@@ -1203,6 +1330,18 @@ fn is_entry_point(name: &str, def: &Definition) -> bool {
     false
 }
 
+/// A production definition is test-only when test code references it and no
+/// production code does. Such symbols are candidates for moving into test
+/// helpers or deleting.
+fn is_test_only(
+    name: &str,
+    def: &Definition,
+    production_refs: &HashSet<String>,
+    test_refs: &HashSet<String>,
+) -> bool {
+    !def.is_test_file && test_refs.contains(name) && !production_refs.contains(name)
+}
+
 fn calculate_confidence(def: &Definition, is_unreachable: bool, is_unused: bool) -> f64 {
     let mut confidence: f64 = 0.9;
 
@@ -1232,9 +1371,41 @@ fn calculate_confidence(def: &Definition, is_unreachable: bool, is_unused: bool)
 // Internal types for file analysis
 struct FileDeadCode {
     path: String,
+    language: Language,
+    is_test_file: bool,
     definitions: HashMap<String, Definition>,
+    /// Functions defined as methods, which may be called through an
+    /// interface the symbol graph cannot follow.
+    methods: HashSet<String>,
     usages: HashSet<String>,
+    /// Usages outside test files and test functions.
+    production_usages: HashSet<String>,
     calls: Vec<CallReference>,
+    /// Every name referenced, for project-wide resolution.
+    references: HashSet<Reference>,
+    /// Items and `use` declarations of a Rust file, until resolved.
+    rust: Option<RustFile>,
+    /// Names brought in by Rust `use` declarations -> qualified name of the
+    /// definition they resolve to.
+    imports: HashMap<String, String>,
+}
+
+impl FileDeadCode {
+    fn new(path: String, language: Language, is_test_file: bool) -> Self {
+        Self {
+            path,
+            language,
+            is_test_file,
+            definitions: HashMap::new(),
+            methods: HashSet::new(),
+            usages: HashSet::new(),
+            production_usages: HashSet::new(),
+            calls: Vec::new(),
+            references: HashSet::new(),
+            rust: None,
+            imports: HashMap::new(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -1245,6 +1416,7 @@ struct Definition {
     file: String,
     line: u32,
     end_line: u32,
+    span: Option<Span>,
     visibility: String,
     exported: bool,
     is_test_file: bool,
@@ -1267,6 +1439,9 @@ struct CallReference {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Analysis {
     pub items: Vec<DeadCodeItem>,
+    /// Production symbols referenced only from test code.
+    #[serde(default)]
+    pub test_only: Vec<DeadCodeItem>,
     pub summary: AnalysisSummary,
 }
 
@@ -1277,6 +1452,8 @@ pub struct DeadCodeItem {
     pub file: String,
     pub line: u32,
     pub end_line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
     pub visibility: String,
     pub confidence: f64,
     pub reason: String,
@@ -1288,6 +1465,8 @@ pub struct AnalysisSummary {
     pub by_kind: HashMap<String, usize>,
     pub total_definitions: usize,
     pub reachable_count: usize,
+    #[serde(default)]
+    pub test_only_items: usize,
 }
 
 #[cfg(test)]
@@ -1317,6 +1496,7 @@ mod tests {
             file: "main.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: false,
@@ -1331,6 +1511,7 @@ mod tests {
             file: "foo_test.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "public".to_string(),
             exported: true,
             is_test_file: true,
@@ -1357,6 +1538,7 @@ mod tests {
             file: "main.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: false,
@@ -1372,6 +1554,7 @@ mod tests {
             file: "lib.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "public".to_string(),
             exported: true,
             is_test_file: false,
@@ -1396,6 +1579,7 @@ mod tests {
             file: "src/util.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: false,
@@ -1409,6 +1593,7 @@ mod tests {
             file: "src/parser.go".to_string(),
             line: 5,
             end_line: 15,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: false,
@@ -1670,6 +1855,7 @@ mod tests {
         assert_eq!(item.file, "src/lib.rs");
         assert_eq!(item.line, 5);
         assert_eq!(item.end_line, 7);
+        assert_eq!(item.byte_range, Some((0, 10)));
     }
 
     #[test]
@@ -1948,11 +2134,11 @@ def plain_function():
     fn test_max_nesting_depth_acceptable() {
         // Verify refactored code keeps nesting under 10 levels
         // This test documents the refactoring goal
-        let source = include_str!("deadcode.rs");
+        let source = include_str!("mod.rs");
         let max_nesting = measure_max_indent_depth(source);
         assert!(
             max_nesting <= 10,
-            "deadcode.rs nesting depth {max_nesting} exceeds 10"
+            "deadcode/mod.rs nesting depth {max_nesting} exceeds 10"
         );
     }
 
@@ -1977,6 +2163,7 @@ def plain_function():
             file: "main.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "public".to_string(),
             exported: true,
             is_test_file: false,
@@ -1991,6 +2178,7 @@ def plain_function():
             file: "main.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: false,
@@ -2008,6 +2196,7 @@ def plain_function():
             file: "sort_test.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "public".to_string(),
             exported: true,
             is_test_file: true,
@@ -2189,6 +2378,46 @@ func setup() {
         );
     }
 
+    #[test]
+    fn test_jsx_elements_create_component_call_edges() {
+        use std::path::Path;
+
+        let parser = crate::parser::Parser::new();
+        let content = br#"
+function Header() {
+    return <h1>Title</h1>;
+}
+
+function Badge() {
+    return <span />;
+}
+
+export function App() {
+    return (
+        <div>
+            <Header />
+            <UI.Badge count={1} />
+        </div>
+    );
+}
+"#;
+        let result = parser
+            .parse(content, Language::Tsx, Path::new("App.tsx"))
+            .unwrap();
+        let fdc = collect_file_data(&result);
+
+        let callees: Vec<&str> = fdc
+            .calls
+            .iter()
+            .filter(|c| c.caller == "App")
+            .map(|c| c.callee.as_str())
+            .collect();
+        assert!(callees.contains(&"Header"), "calls: {callees:?}");
+        assert!(callees.contains(&"Badge"), "calls: {callees:?}");
+        assert!(!callees.contains(&"div") && !callees.contains(&"h1"));
+        assert!(fdc.production_usages.contains("Badge"));
+    }
+
     #[test]
     fn test_cross_file_function_as_value_creates_call_edge() {
         // When a function identifier is passed as an argument but is NOT
@@ -2257,23 +2486,23 @@ func privateHelper() {
 
     #[test]
     fn test_generated_files_are_detected() {
-        assert!(is_generated_file("conductor_grpc.pb.go"));
-        assert!(is_generated_file("service.pb.go"));
-        assert!(is_generated_file("types.gen.go"));
-        assert!(is_generated_file("schema.generated.ts"));
-        assert!(is_generated_file("models_gen.go"));
-        assert!(is_generated_file("deep_copy_generated.go"));
-        assert!(is_generated_file("bindata.go")); // go-bindata output
-        assert!(is_generated_file("wire_gen.go")); // Wire DI
-        assert!(is_generated_file("mock_service.go")); // mockgen
-        assert!(is_generated_file("zz_generated.deepcopy.go")); // k8s code-gen
+        assert!(is_generated_path("conductor_grpc.pb.go"));
+        assert!(is_generated_path("service.pb.go"));
+        assert!(is_generated_path("types.gen.go"));
+        assert!(is_generated_path("schema.generated.ts"));
+        assert!(is_generated_path("models_gen.go"));
+        assert!(is_generated_path("deep_copy_generated.go"));
+        assert!(is_generated_path("bindata.go")); // go-bindata output
+        assert!(is_generated_path("wire_gen.go")); // Wire DI
+        assert!(is_generated_path("mock_service.go")); // mockgen
+        assert!(is_generated_path("zz_generated.deepcopy.go")); // k8s code-gen
 
         // Non-generated files
-        assert!(!is_generated_file("server.go"));
-        assert!(!is_generated_file("handler.go"));
-        assert!(!is_generated_file("main.go"));
-        assert!(!is_generated_file("generator.go")); // contains "gen" but isn't generated
-        assert!(!is_generated_file("utils.ts"));
+        assert!(!is_generated_path("server.go"));
+        assert!(!is_generated_path("handler.go"));
+        assert!(!is_generated_path("main.go"));
+        assert!(!is_generated_path("generator.go")); // contains "gen" but isn't generated
+        assert!(!is_generated_path("utils.ts"));
     }
 
     #[test]
@@ -2306,9 +2535,9 @@ func RegisterConductorServiceServer() {
             "Generated file definitions should be collected for call graph"
         );
 
-        // But is_generated_file returns true so Phase 5 will skip them
+        // But is_generated_path returns true so Phase 5 will skip them
         assert!(
-            is_generated_file("conductor_grpc.pb.go"),
+            is_generated_path("conductor_grpc.pb.go"),
             "Should detect generated file"
         );
     }
@@ -2321,6 +2550,7 @@ func RegisterConductorServiceServer() {
             file: "handler.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: false,
@@ -2342,6 +2572,7 @@ func RegisterConductorServiceServer() {
             file: "helper_test.go".to_string(),
             line: 1,
             end_line: 10,
+            span: None,
             visibility: "private".to_string(),
             exported: false,
             is_test_file: true,
@@ -2444,6 +2675,9 @@ pub struct CargoDeadCodeItem {
     pub file: String,
     pub line: u32,
     pub end_line: u32,
+    /// Byte range of the primary span reported by rustc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_range: Option<(usize, usize)>,
     pub message: String,
     pub kind: String,
 }
@@ -2538,6 +2772,11 @@ impl CargoDeadCodeAnalyzer {
         let file = span.get("file_name")?.as_str()?.to_string();
         let line = span.get("line_start")?.as_u64()? as u32;
         let end_line = span.get("line_end")?.as_u64()? as u32;
+        let byte_range = span
+            .get("byte_start")
+            .and_then(|v| v.as_u64())
+            .zip(span.get("byte_end").and_then(|v| v.as_u64()))
+            .map(|(start, end)| (start as usize, end as usize));
 
         // Determine kind from message
         let kind = Self::determine_kind(&message);
@@ -2547,6 +2786,7 @@ impl CargoDeadCodeAnalyzer {
             file,
             line,
             end_line,
+            byte_range,
             message,
             kind,
         })
@@ -2587,4 +2827,123 @@ impl CargoDeadCodeAnalyzer {
             "unknown".to_string()
         }
     }
+
+    fn analyze_dir(dir: &std::path::Path) -> Analysis {
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir));
+        Analyzer::new().analyze(&ctx).unwrap()
+    }
+
+    #[test]
+    fn test_dead_code_item_has_span() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp_dir.path().join("util.go"),
+            "package util\n\nfunc unused() {}\n",
+        )
+        .unwrap();
+
+        let analysis = analyze_dir(tmp_dir.path());
+
+        let item = analysis.items.iter().find(|i| i.name == "unused").unwrap();
+        let span = item.span.expect("tree-sitter items carry a span");
+        assert_eq!((span.start_line, span.start_column), (3, 1));
+        assert_eq!((span.end_line, span.end_column), (3, 17));
+        assert_eq!(span.end_byte - span.start_byte, 16);
+    }
+
+    #[test]
+    fn test_go_helper_used_only_by_tests_is_test_only() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp_dir.path().join("util.go"),
+            "package util\n\nfunc Run() {\n\tused()\n}\n\nfunc used() {}\n\nfunc helper() int {\n\treturn 1\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp_dir.path().join("util_test.go"),
+            "package util\n\nimport \"testing\"\n\nfunc TestHelper(t *testing.T) {\n\tif helper() != 1 {\n\t\tt.Fatal(\"bad\")\n\t}\n}\n",
+        )
+        .unwrap();
+
+        let analysis = analyze_dir(tmp_dir.path());
+
+        let test_only: Vec<&str> = analysis.test_only.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(test_only, vec!["helper"]);
+        assert_eq!(analysis.summary.test_only_items, 1);
+        assert!(analysis.items.iter().all(|i| i.name != "helper"));
+    }
+
+    #[test]
+    fn test_rust_helper_used_only_in_cfg_test_is_test_only() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp_dir.path().join("lib.rs"),
+            r#"
+pub fn run() -> i32 {
+    compute()
+}
+
+fn compute() -> i32 {
+    1
+}
+
+fn fixture() -> i32 {
+    2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_fixture() {
+        assert_eq!(fixture() + compute(), 3);
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let analysis = analyze_dir(tmp_dir.path());
+
+        let test_only: Vec<&str> = analysis.test_only.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(test_only, vec!["fixture"]);
+    }
+
+    #[test]
+    fn test_is_test_only_requires_no_production_refs() {
+        let def = Definition {
+            name: "helper".to_string(),
+            kind: "function".to_string(),
+            file: "util.go".to_string(),
+            line: 1,
+            end_line: 3,
+            span: None,
+            visibility: "private".to_string(),
+            exported: false,
+            is_test_file: false,
+            attributes: vec![],
+            is_trait_impl: false,
+        };
+        let test_refs: HashSet<String> = ["helper".to_string()].into_iter().collect();
+        let mut production_refs = HashSet::new();
+
+        assert!(is_test_only("helper", &def, &production_refs, &test_refs));
+
+        production_refs.insert("helper".to_string());
+        assert!(!is_test_only("helper", &def, &production_refs, &test_refs));
+
+        let test_def = Definition {
+            is_test_file: true,
+            ..def
+        };
+        assert!(!is_test_only(
+            "helper",
+            &test_def,
+            &HashSet::new(),
+            &test_refs
+        ));
+    }
 }
@@ -0,0 +1,288 @@
+//! Project-wide symbol resolution.
+//!
+//! Each file contributes its function definitions and the names it
+//! references, tagged with the function each reference appears in. A
+//! reference resolves to the definition in its own file when there is one,
+//! then to the definition a Rust `use` declaration imports it from, and
+//! otherwise to every definition of that name in files of the same language
+//! family, so a Python `helper` never keeps a Go `helper` alive.
+//!
+//! Code outside any function runs when its module loads, so what it
+//! references counts as reachable.
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Definition, FileDeadCode};
+use crate::core::Language;
+
+/// A name referenced in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct Reference {
+    /// Function the reference appears in; `None` for module-level code.
+    pub from: Option<String>,
+    pub name: String,
+}
+
+/// Definitions and resolved references of a whole project.
+pub(super) struct SymbolGraph {
+    /// Definitions by qualified name (`file::name`).
+    pub definitions: HashMap<String, Definition>,
+    /// Definitions each definition references.
+    edges: HashMap<String, HashSet<String>>,
+    /// Definitions referenced from module-level code.
+    module_refs: HashSet<String>,
+    /// Definitions referenced from anywhere but themselves.
+    referenced: HashSet<String>,
+}
+
+impl SymbolGraph {
+    pub fn build(files: &[FileDeadCode]) -> Self {
+        let mut definitions = HashMap::new();
+        let mut by_name: HashMap<(Language, &str), Vec<String>> = HashMap::new();
+        for fdc in files {
+            for (name, def) in &fdc.definitions {
+                let qualified = qualify(&fdc.path, name);
+                by_name
+                    .entry((fdc.language.family(), name.as_str()))
+                    .or_default()
+                    .push(qualified.clone());
+                definitions.insert(qualified, def.clone());
+            }
+        }
+
+        let mut graph = Self {
+            definitions,
+            edges: HashMap::new(),
+            module_refs: HashSet::new(),
+            referenced: HashSet::new(),
+        };
+        for fdc in files {
+            for reference in &fdc.references {
+                // References from functions the parser did not extract (such
+                // as anonymous ones) are treated like module-level code.
+                let from = reference
+                    .from
+                    .as_ref()
+                    .map(|name| qualify(&fdc.path, name))
+                    .filter(|qualified| graph.definitions.contains_key(qualified));
+
+                let same_file = qualify(&fdc.path, &reference.name);
+                let imported = fdc
+                    .imports
+                    .get(&reference.name)
+                    .filter(|qualified| graph.definitions.contains_key(*qualified));
+                let targets = if graph.definitions.contains_key(&same_file) {
+                    vec![same_file]
+                } else if let Some(imported) = imported {
+                    vec![imported.clone()]
+                } else {
+                    by_name
+                        .get(&(fdc.language.family(), reference.name.as_str()))
+                        .cloned()
+                        .unwrap_or_default()
+                };
+
+                for target in targets {
+                    graph.add_reference(from.as_ref(), target);
+                }
+            }
+        }
+        graph
+    }
+
+    fn add_reference(&mut self, from: Option<&String>, target: String) {
+        match from {
+            // Recursion does not keep a function alive.
+            Some(from) if *from == target => {}
+            Some(from) => {
+                self.referenced.insert(target.clone());
+                self.edges.entry(from.clone()).or_default().insert(target);
+            }
+            None => {
+                self.referenced.insert(target.clone());
+                self.module_refs.insert(target);
+            }
+        }
+    }
+
+    /// Whether anything other than the definition itself references it.
+    pub fn is_referenced(&self, qualified: &str) -> bool {
+        self.referenced.contains(qualified)
+    }
+
+    /// Definitions reachable from entry points and module-level code.
+    pub fn reachable(&self, is_entry_point: impl Fn(&str, &Definition) -> bool) -> HashSet<String> {
+        let mut queue: Vec<&String> = self
+            .definitions
+            .iter()
+            .filter(|(qualified, def)| is_entry_point(simple_name(qualified), def))
+            .map(|(qualified, _)| qualified)
+            .chain(&self.module_refs)
+            .collect();
+        let mut reachable: HashSet<String> = queue.iter().map(|q| (*q).clone()).collect();
+
+        while let Some(current) = queue.pop() {
+            for callee in self.edges.get(current).into_iter().flatten() {
+                if reachable.insert(callee.clone()) {
+                    queue.push(callee);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+/// Qualified name of `name` defined in `file`.
+pub(super) fn qualify(file: &str, name: &str) -> String {
+    format!("{file}::{name}")
+}
+
+/// Name part of a qualified name.
+pub(super) fn simple_name(qualified: &str) -> &str {
+    qualified.rsplit("::").next().unwrap_or(qualified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(
+        path: &str,
+        language: Language,
+        defs: &[&str],
+        refs: &[(Option<&str>, &str)],
+    ) -> FileDeadCode {
+        let mut fdc = FileDeadCode::new(path.to_string(), language, false);
+        for name in defs {
+            fdc.definitions.insert(
+                name.to_string(),
+                Definition {
+                    name: name.to_string(),
+                    kind: "function".to_string(),
+                    file: path.to_string(),
+                    line: 1,
+                    end_line: 1,
+                    span: None,
+                    visibility: "private".to_string(),
+                    exported: false,
+                    is_test_file: false,
+                    attributes: vec![],
+                    is_trait_impl: false,
+                },
+            );
+        }
+        for (from, name) in refs {
+            fdc.references.insert(Reference {
+                from: from.map(str::to_string),
+                name: name.to_string(),
+            });
+        }
+        fdc
+    }
+
+    #[test]
+    fn test_rust_imports_pick_the_definition() {
+        let mut caller = file(
+            "src/app.rs",
+            Language::Rust,
+            &["run"],
+            &[(Some("run"), "helper"), (Some("run"), "fmt")],
+        );
+        caller
+            .imports
+            .insert("helper".to_string(), "src/b.rs::helper".to_string());
+        // Resolved to something that is not a tracked function
+        caller
+            .imports
+            .insert("fmt".to_string(), "src/types.rs::fmt".to_string());
+        let files = vec![
+            caller,
+            file("src/a.rs", Language::Rust, &["helper", "fmt"], &[]),
+            file("src/b.rs", Language::Rust, &["helper"], &[]),
+        ];
+        let graph = SymbolGraph::build(&files);
+
+        assert!(graph.is_referenced("src/b.rs::helper"));
+        assert!(
+            !graph.is_referenced("src/a.rs::helper"),
+            "the use declaration names src/b.rs"
+        );
+        assert!(
+            graph.is_referenced("src/a.rs::fmt"),
+            "unknown imports fall back to name matching"
+        );
+    }
+
+    #[test]
+    fn test_resolves_same_file_first_then_same_family() {
+        let files = vec![
+            file(
+                "a.py",
+                Language::Python,
+                &["run", "helper"],
+                &[(Some("run"), "helper")],
+            ),
+            file("b.py", Language::Python, &["helper", "shared"], &[]),
+            file(
+                "c.go",
+                Language::Go,
+                &["main", "shared"],
+                &[(Some("main"), "shared")],
+            ),
+            file("d.ts", Language::TypeScript, &["view"], &[]),
+            file("e.tsx", Language::Tsx, &["App"], &[(Some("App"), "view")]),
+        ];
+        let graph = SymbolGraph::build(&files);
+
+        assert!(graph.is_referenced("a.py::helper"));
+        assert!(
+            !graph.is_referenced("b.py::helper"),
+            "shadowed by a.py's own helper"
+        );
+        assert!(graph.is_referenced("c.go::shared"));
+        assert!(
+            !graph.is_referenced("b.py::shared"),
+            "Go code cannot call Python"
+        );
+        assert!(
+            graph.is_referenced("d.ts::view"),
+            "TSX and TypeScript share a family"
+        );
+    }
+
+    #[test]
+    fn test_reachable_skips_functions_only_referenced_from_dead_code() {
+        let files = vec![file(
+            "lib.py",
+            Language::Python,
+            &[
+                "main",
+                "used",
+                "dead",
+                "only_from_dead",
+                "loop_a",
+                "loop_b",
+                "on_load",
+            ],
+            &[
+                (Some("main"), "used"),
+                (Some("dead"), "only_from_dead"),
+                (Some("loop_a"), "loop_b"),
+                (Some("loop_b"), "loop_a"),
+                (Some("dead"), "dead"),
+                (None, "on_load"),
+            ],
+        )];
+        let graph = SymbolGraph::build(&files);
+        let reachable = graph.reachable(|name, _| name == "main");
+
+        let mut names: Vec<&str> = reachable.iter().map(|q| simple_name(q)).collect();
+        names.sort();
+        assert_eq!(names, vec!["main", "on_load", "used"]);
+        assert!(graph.is_referenced("lib.py::only_from_dead"));
+        assert!(
+            !graph.is_referenced("lib.py::dead"),
+            "recursion is not a reference"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Calibration of defect predictions against historical incidents.
+//!
+//! Every distinct incident date is a replay point. Churn and ownership are
+//! rebuilt from the commits in the `churn_days` before that date, so the fix
+//! for an incident never leaks into its own prediction. Complexity,
+//! duplication and coupling come from the analyzed tree. Files with no
+//! commit before a replay point did not exist yet and are left out of its
+//! ranking.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{apply_git_metrics, Analyzer, FileMetrics};
+use crate::core::{AnalysisContext, Error, Result};
+use crate::git::Commit;
+
+/// A past bug or incident traced to a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Incident {
+    /// Repository-relative path of the file that was fixed.
+    pub path: String,
+    /// When the incident was reported.
+    pub date: DateTime<Utc>,
+}
+
+/// How one incident ranked at its replay point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentResult {
+    pub path: String,
+    pub date: DateTime<Utc>,
+    /// 1-based rank among files that existed at the replay point; `None`
+    /// when the file was not among them.
+    pub rank: Option<usize>,
+    /// Number of files ranked at the replay point.
+    pub candidates: usize,
+    pub probability: Option<f32>,
+    /// Share of candidates ranked at or below this file (1.0 = top).
+    pub percentile: Option<f64>,
+    pub in_top_k: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalibrationSummary {
+    pub total_incidents: usize,
+    /// Incidents whose file was ranked at its replay point.
+    pub matched_incidents: usize,
+    pub replays: usize,
+    /// Probability that an incident file outranks a file without an
+    /// incident at the same replay point; `None` without both kinds.
+    pub auc: Option<f64>,
+    /// Mean share of the top-k files that had an incident, per replay.
+    pub precision_at_k: f64,
+    /// Share of matched incidents whose file ranked in the top k.
+    pub recall_at_k: f64,
+    pub mean_percentile: f64,
+}
+
+/// Calibration metrics for defect predictions over history replays.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calibration {
+    pub generated_at: DateTime<Utc>,
+    pub top_k: usize,
+    pub churn_days: u32,
+    pub incidents: Vec<IncidentResult>,
+    pub summary: CalibrationSummary,
+}
+
+#[derive(Deserialize)]
+struct RawIncident {
+    #[serde(alias = "file")]
+    path: String,
+    date: String,
+}
+
+/// Load incidents from a JSON array of `{"path", "date"}` objects or from
+/// CSV lines of `path,date`. Dates are `YYYY-MM-DD` or RFC 3339.
+pub fn load_incidents(path: &Path) -> Result<Vec<Incident>> {
+    let content = std::fs::read_to_string(path)?;
+    parse_incidents(&content)
+}
+
+fn parse_incidents(content: &str) -> Result<Vec<Incident>> {
+    if content.trim_start().starts_with('[') {
+        let raw: Vec<RawIncident> = serde_json::from_str(content)?;
+        return raw
+            .into_iter()
+            .map(|r| {
+                let date = parse_date(&r.date).ok_or_else(|| invalid_date(&r.date))?;
+                Ok(Incident {
+                    path: normalize_path(&r.path),
+                    date,
+                })
+            })
+            .collect();
+    }
+
+    let mut incidents = Vec::new();
+    let mut first = true;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (path, date) = line.rsplit_once(',').ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "incident line {}: expected `path,date`, got '{line}'",
+                i + 1
+            ))
+        })?;
+        let date = date.trim().trim_matches('"');
+        match parse_date(date) {
+            Some(date) => incidents.push(Incident {
+                path: normalize_path(path.trim().trim_matches('"')),
+                date,
+            }),
+            // A header row such as `path,date`.
+            None if first => {}
+            None => return Err(invalid_date(date)),
+        }
+        first = false;
+    }
+    Ok(incidents)
+}
+
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+}
+
+fn invalid_date(date: &str) -> Error {
+    Error::InvalidArgument(format!(
+        "invalid incident date '{date}' (expected YYYY-MM-DD or RFC 3339)"
+    ))
+}
+
+fn normalize_path(path: &str) -> String {
+    path.trim_start_matches("./").to_string()
+}
+
+impl Analyzer {
+    /// Replay predictions at each incident date and score how well they
+    /// ranked the files that later had incidents.
+    pub fn calibrate(
+        &self,
+        ctx: &AnalysisContext<'_>,
+        incidents: &[Incident],
+        top_k: usize,
+    ) -> Result<Calibration> {
+        let repo = ctx
+            .open_git()?
+            .ok_or_else(|| Error::git("Defect calibration requires a git repository"))?;
+        let commits = repo.log_with_stats(None, None)?;
+
+        let complexity_data = self.compute_complexity_data(ctx);
+        let duplication_data = self.compute_duplication_data(ctx);
+        let coupling_data = self.compute_coupling_data(ctx);
+        let no_git = HashMap::new();
+        let base: Vec<FileMetrics> = ctx
+            .files
+            .iter()
+            .map(|path| {
+                let file_path = path.strip_prefix(ctx.root).unwrap_or(path);
+                self.get_file_metrics(
+                    ctx.root,
+                    &file_path.to_string_lossy(),
+                    &complexity_data,
+                    &duplication_data,
+                    &coupling_data,
+                    &no_git,
+                )
+            })
+            .collect();
+
+        Ok(self.replay(&base, &commits, incidents, top_k))
+    }
+
+    fn replay(
+        &self,
+        base: &[FileMetrics],
+        commits: &[Commit],
+        incidents: &[Incident],
+        top_k: usize,
+    ) -> Calibration {
+        let top_k = top_k.max(1);
+        let first_seen = first_seen(commits);
+        let window = i64::from(self.config.churn_days) * 86_400;
+
+        let mut by_date: BTreeMap<i64, Vec<&Incident>> = BTreeMap::new();
+        for incident in incidents {
+            by_date
+                .entry(incident.date.timestamp())
+                .or_default()
+                .push(incident);
+        }
+
+        let mut results = Vec::with_capacity(incidents.len());
+        let (mut wins, mut pairs) = (0.0f64, 0.0f64);
+        let (mut precision_sum, mut scored_replays) = (0.0f64, 0usize);
+
+        for (&at, day) in &by_date {
+            let git_metrics = window_metrics(commits, at - window, at);
+            let mut ranked: Vec<(&str, f32)> = base
+                .iter()
+                .filter(|m| {
+                    first_seen
+                        .get(Path::new(&m.file_path))
+                        .is_some_and(|&ts| ts < at)
+                })
+                .map(|m| {
+                    let mut metrics = m.clone();
+                    apply_git_metrics(&mut metrics, &git_metrics);
+                    (m.file_path.as_str(), self.calculate_probability(&metrics))
+                })
+                .collect();
+            ranked.sort_by(|a, b| {
+                b.1.partial_cmp(&a.1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.0.cmp(b.0))
+            });
+
+            let positives: HashSet<&str> = day
+                .iter()
+                .map(|i| i.path.as_str())
+                .filter(|p| ranked.iter().any(|(f, _)| f == p))
+                .collect();
+            if !positives.is_empty() {
+                for &(_, p) in ranked.iter().filter(|(f, _)| positives.contains(f)) {
+                    for &(_, n) in ranked.iter().filter(|(f, _)| !positives.contains(f)) {
+                        pairs += 1.0;
+                        if p > n {
+                            wins += 1.0;
+                        } else if p == n {
+                            wins += 0.5;
+                        }
+                    }
+                }
+                let k = top_k.min(ranked.len());
+                let hits = ranked[..k]
+                    .iter()
+                    .filter(|(f, _)| positives.contains(f))
+                    .count();
+                precision_sum += hits as f64 / k as f64;
+                scored_replays += 1;
+            }
+
+            for incident in day {
+                let pos = ranked.iter().position(|(f, _)| *f == incident.path);
+                results.push(IncidentResult {
+                    path: incident.path.clone(),
+                    date: incident.date,
+                    rank: pos.map(|p| p + 1),
+                    candidates: ranked.len(),
+                    probability: pos.map(|p| ranked[p].1),
+                    percentile: pos.map(|p| 1.0 - p as f64 / ranked.len() as f64),
+                    in_top_k: pos.is_some_and(|p| p < top_k),
+                });
+            }
+        }
+
+        let matched: Vec<&IncidentResult> = results.iter().filter(|r| r.rank.is_some()).collect();
+        let mut summary = CalibrationSummary {
+            total_incidents: results.len(),
+            matched_incidents: matched.len(),
+            replays: by_date.len(),
+            auc: (pairs > 0.0).then(|| wins / pairs),
+            ..Default::default()
+        };
+        if scored_replays > 0 {
+            summary.precision_at_k = precision_sum / scored_replays as f64;
+        }
+        if !matched.is_empty() {
+            summary.recall_at_k =
+                matched.iter().filter(|r| r.in_top_k).count() as f64 / matched.len() as f64;
+            summary.mean_percentile =
+                matched.iter().filter_map(|r| r.percentile).sum::<f64>() / matched.len() as f64;
+        }
+
+        Calibration {
+            generated_at: Utc::now(),
+            top_k,
+            churn_days: self.config.churn_days,
+            incidents: results,
+            summary,
+        }
+    }
+}
+
+/// Timestamp of the earliest commit touching each file.
+fn first_seen(commits: &[Commit]) -> HashMap<PathBuf, i64> {
+    let mut seen: HashMap<PathBuf, i64> = HashMap::new();
+    for commit in commits {
+        for file in &commit.files {
+            seen.entry(file.path.clone())
+                .and_modify(|ts| *ts = (*ts).min(commit.timestamp))
+                .or_insert(commit.timestamp);
+        }
+    }
+    seen
+}
+
+/// Per-file (commit count, contributor count) for commits in `[from, to)`.
+fn window_metrics(commits: &[Commit], from: i64, to: i64) -> HashMap<PathBuf, (usize, usize)> {
+    let mut counts: HashMap<PathBuf, (usize, HashSet<&str>)> = HashMap::new();
+    for commit in commits
+        .iter()
+        .filter(|c| c.timestamp >= from && c.timestamp < to)
+    {
+        for file in &commit.files {
+            let entry = counts.entry(file.path.clone()).or_default();
+            entry.0 += 1;
+            entry.1.insert(commit.author.as_str());
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(path, (commits, authors))| (path, (commits, authors.len())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{ChangeType, FileChange};
+
+    const DAY: i64 = 86_400;
+
+    fn commit(ts: i64, author: &str, files: &[&str]) -> Commit {
+        Commit {
+            sha: format!("{ts:040x}"),
+            author: author.to_string(),
+            email: format!("{author}@example.com"),
+            timestamp: ts,
+            message: "change".to_string(),
+            files: files
+                .iter()
+                .map(|f| FileChange {
+                    path: PathBuf::from(f),
+                    additions: 1,
+                    deletions: 0,
+                    change_type: ChangeType::Modified,
+                })
+                .collect(),
+        }
+    }
+
+    fn metrics(path: &str) -> FileMetrics {
+        FileMetrics {
+            file_path: path.to_string(),
+            lines_of_code: 100,
+            complexity: 5.0,
+            ..Default::default()
+        }
+    }
+
+    fn incident(path: &str, ts: i64) -> Incident {
+        Incident {
+            path: path.to_string(),
+            date: DateTime::from_timestamp(ts, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_parse_incidents_csv_and_json() {
+        let csv =
+            "# incidents\npath,date\n./src/a.rs,2024-03-01\n\"src/b.rs\",2024-03-02T10:00:00Z\n";
+        let parsed = parse_incidents(csv).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].path, "src/a.rs");
+        assert_eq!(parsed[0].date.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert_eq!(parsed[1].path, "src/b.rs");
+
+        let json = r#"[{"file": "src/a.rs", "date": "2024-03-01"}]"#;
+        assert_eq!(parse_incidents(json).unwrap(), vec![parsed[0].clone()]);
+
+        assert!(parse_incidents("src/a.rs,2024-03-01\nsrc/b.rs,yesterday\n").is_err());
+        assert!(parse_incidents("src/a.rs\n").is_err());
+    }
+
+    #[test]
+    fn test_replay_ranks_churned_file_first() {
+        let start = 1_700_000_000;
+        let mut commits = vec![commit(start, "a", &["hot.rs", "cold.rs", "calm.rs"])];
+        for (i, author) in ["a", "b", "c", "d", "e", "f"].iter().enumerate() {
+            commits.push(commit(start + (i as i64 + 1) * DAY, author, &["hot.rs"]));
+        }
+        let base = vec![metrics("hot.rs"), metrics("cold.rs"), metrics("calm.rs")];
+        let incidents = vec![incident("hot.rs", start + 10 * DAY)];
+
+        let calibration = Analyzer::new().replay(&base, &commits, &incidents, 1);
+        let result = &calibration.incidents[0];
+        assert_eq!(result.rank, Some(1));
+        assert_eq!(result.candidates, 3);
+        assert!(result.in_top_k);
+        assert_eq!(calibration.summary.auc, Some(1.0));
+        assert_eq!(calibration.summary.precision_at_k, 1.0);
+        assert_eq!(calibration.summary.recall_at_k, 1.0);
+    }
+
+    #[test]
+    fn test_replay_ignores_history_after_incident() {
+        let start = 1_700_000_000;
+        let mut commits = vec![commit(start, "a", &["a.rs", "b.rs"])];
+        commits.push(commit(start + DAY, "a", &["a.rs"]));
+        // Fixes for the incident land after it and must not raise b.rs.
+        for (i, author) in ["b", "c", "d", "e"].iter().enumerate() {
+            commits.push(commit(start + (i as i64 + 6) * DAY, author, &["b.rs"]));
+        }
+        let base = vec![metrics("a.rs"), metrics("b.rs")];
+        let incidents = vec![incident("b.rs", start + 5 * DAY)];
+
+        let calibration = Analyzer::new().replay(&base, &commits, &incidents, 1);
+        assert_eq!(calibration.incidents[0].rank, Some(2));
+        assert_eq!(calibration.summary.auc, Some(0.0));
+        assert_eq!(calibration.summary.recall_at_k, 0.0);
+    }
+
+    #[test]
+    fn test_replay_skips_files_not_yet_created() {
+        let start = 1_700_000_000;
+        let commits = vec![
+            commit(start, "a", &["old.rs"]),
+            commit(start + 20 * DAY, "a", &["new.rs"]),
+        ];
+        let base = vec![metrics("old.rs"), metrics("new.rs")];
+        let incidents = vec![
+            incident("new.rs", start + 10 * DAY),
+            incident("gone.rs", start + 10 * DAY),
+        ];
+
+        let calibration = Analyzer::new().replay(&base, &commits, &incidents, 5);
+        assert!(calibration.incidents.iter().all(|r| r.rank.is_none()));
+        assert!(calibration.incidents.iter().all(|r| r.candidates == 1));
+        assert_eq!(calibration.summary.matched_incidents, 0);
+        assert_eq!(calibration.summary.replays, 1);
+        assert_eq!(calibration.summary.auc, None);
+    }
+}
@@ -6,6 +6,14 @@
 //! - Duplication: Code clone ratio (from duplicates::Analyzer)
 //! - Coupling: Afferent coupling (from graph::Analyzer edge analysis)
 //! - Ownership: Contributor diffusion (Bird et al. 2011, from git history)
+//!
+//! [`Analyzer::calibrate`] replays predictions against a file of past
+//! incidents to measure how well they would have ranked the files that
+//! actually broke.
+
+mod calibration;
+
+pub use calibration::{load_incidents, Calibration, CalibrationSummary, Incident, IncidentResult};
 
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
@@ -14,7 +22,7 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::analyzers::{complexity, duplicates, graph};
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result};
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result, SeverityCounts};
 use crate::git::GitRepo;
 
 /// Risk level categories (PMAT-compatible).
@@ -38,6 +46,16 @@ impl RiskLevel {
     }
 }
 
+impl From<RiskLevel> for crate::core::Severity {
+    fn from(level: RiskLevel) -> Self {
+        match level {
+            RiskLevel::High => Self::High,
+            RiskLevel::Medium => Self::Medium,
+            RiskLevel::Low => Self::Low,
+        }
+    }
+}
+
 /// PMAT weights for defect prediction factors.
 /// Based on empirical research + ownership research (Bird et al. 2011).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -258,12 +276,7 @@ impl Analyzer {
         };
 
         // Get churn and ownership from pre-computed git metrics
-        let file_pathbuf = PathBuf::from(file_path);
-        if let Some(&(commit_count, contributor_count)) = git_metrics.get(&file_pathbuf) {
-            // Normalize churn score (max ~20 commits/month = high churn)
-            metrics.churn_score = (commit_count as f32 / 20.0).min(1.0);
-            metrics.ownership_diffusion = contributor_count as f32;
-        }
+        apply_git_metrics(&mut metrics, git_metrics);
 
         // Get file LOC for confidence calculation
         if let Ok(content) = std::fs::read_to_string(root_path.join(file_path)) {
@@ -508,6 +521,7 @@ impl AnalyzerTrait for Analyzer {
             high_risk_count: high_count,
             medium_risk_count: medium_count,
             low_risk_count: low_count,
+            severity_counts: file_scores.iter().map(|f| f.risk_level).collect(),
             ..Default::default()
         };
 
@@ -528,10 +542,19 @@ impl AnalyzerTrait for Analyzer {
     }
 }
 
+/// Set churn and ownership on `metrics` from per-file git metrics.
+fn apply_git_metrics(metrics: &mut FileMetrics, git_metrics: &HashMap<PathBuf, (usize, usize)>) {
+    let file_pathbuf = PathBuf::from(&metrics.file_path);
+    if let Some(&(commit_count, contributor_count)) = git_metrics.get(&file_pathbuf) {
+        // Normalize churn score (max ~20 commits/month = high churn)
+        metrics.churn_score = (commit_count as f32 / 20.0).min(1.0);
+        metrics.ownership_diffusion = contributor_count as f32;
+    }
+}
+
 /// Internal file metrics.
 #[derive(Debug, Clone, Default)]
 struct FileMetrics {
-    #[allow(dead_code)]
     file_path: String,
     churn_score: f32,
     complexity: f32,
@@ -579,6 +602,8 @@ pub struct Summary {
     pub avg_probability: f32,
     pub p50_probability: f32,
     pub p95_probability: f32,
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
 }
 
 // PMAT-compatible CDF percentile tables for normalization
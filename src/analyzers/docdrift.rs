@@ -0,0 +1,898 @@
+//! Public API documentation drift.
+//!
+//! Compares the parameters a doc comment describes with the function's
+//! signature, for the conventions that name them:
+//! - rustdoc `# Arguments` (or `# Parameters`) lists: `` * `name` - ... ``
+//! - JSDoc, Javadoc and PHPDoc `@param` tags
+//! - Python docstrings in Google (`Args:`), NumPy (`Parameters` with an
+//!   underline) and Sphinx (`:param name:`) style
+//!
+//! Each function yields items of three kinds:
+//! - **Renamed**: a documented name sits where the signature has a different,
+//!   undocumented parameter
+//! - **Stale**: a documented parameter the signature no longer has
+//! - **Missing**: a parameter the doc comment leaves out
+//!
+//! Doc comments that describe no parameters at all are left alone; that is
+//! a coverage question, not drift. Only public functions are checked unless
+//! `[docdrift] include_private` is set.
+
+use std::collections::{BTreeMap, HashSet};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+
+use crate::core::{
+    AnalysisContext, Analyzer as AnalyzerTrait, Language, Result, Severity, SeverityCounts,
+};
+use crate::parser::ParseResult;
+
+/// Documentation drift analyzer.
+#[derive(Default)]
+pub struct Analyzer;
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "docdrift"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find doc comments whose parameters no longer match the signature"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let include_private = ctx.config.docdrift.include_private;
+        let files: Vec<FileDrift> = ctx
+            .files
+            .files()
+            .par_iter()
+            .filter(|path| ctx.files.language(path).is_some_and(has_param_docs))
+            .filter_map(|path| {
+                let parsed = ctx.parsed(path).ok()?;
+                Some(check_parsed(
+                    &path.to_string_lossy(),
+                    &parsed,
+                    include_private,
+                ))
+            })
+            .collect();
+        Ok(Analysis::from_files(files))
+    }
+}
+
+/// Languages with a doc convention that names parameters.
+fn has_param_docs(language: Language) -> bool {
+    matches!(
+        language,
+        Language::Rust
+            | Language::Python
+            | Language::JavaScript
+            | Language::TypeScript
+            | Language::Tsx
+            | Language::Jsx
+            | Language::Java
+            | Language::Php
+    )
+}
+
+/// Drift found in one file.
+pub struct FileDrift {
+    /// Functions whose doc comment describes parameters.
+    pub documented_functions: usize,
+    pub items: Vec<DriftItem>,
+}
+
+/// Check the documented functions of a parsed file, reported under `file`.
+pub fn check_parsed(file: &str, parsed: &ParseResult, include_private: bool) -> FileDrift {
+    let mut drift = FileDrift {
+        documented_functions: 0,
+        items: Vec::new(),
+    };
+    let source: &[u8] = &parsed.source;
+    let language = parsed.language;
+    let mut stack = vec![parsed.root_node()];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+        if !function_kinds(language).contains(&node.kind()) {
+            continue;
+        }
+        let Some(name) = node
+            .child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+        else {
+            continue;
+        };
+        if !include_private && !is_public(&node, name, language, source) {
+            continue;
+        }
+        let Some(documented) =
+            doc_comment(&node, language, source).and_then(|doc| documented_params(&doc, language))
+        else {
+            continue;
+        };
+        let Some(parameters) = parameters(&node, language, source) else {
+            continue;
+        };
+
+        drift.documented_functions += 1;
+        for (kind, parameter, renamed_to) in compare(&documented, &parameters) {
+            drift.items.push(DriftItem {
+                file: file.to_string(),
+                line: node.start_position().row as u32 + 1,
+                function: name.to_string(),
+                language,
+                kind,
+                severity: kind.severity(),
+                message: kind.message(&parameter, renamed_to.as_deref()),
+                parameter,
+                renamed_to,
+                documented: documented.len(),
+                parameters: parameters.len(),
+            });
+        }
+    }
+    drift.items.sort_by_key(|item| item.line);
+    drift
+}
+
+fn function_kinds(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Rust => &["function_item", "function_signature_item"],
+        Language::Python => &["function_definition"],
+        Language::JavaScript | Language::TypeScript | Language::Tsx | Language::Jsx => &[
+            "function_declaration",
+            "generator_function_declaration",
+            "method_definition",
+        ],
+        Language::Java => &["method_declaration", "constructor_declaration"],
+        Language::Php => &["function_definition", "method_declaration"],
+        _ => &[],
+    }
+}
+
+/// Whether a function is part of its module's public API.
+fn is_public(node: &Node<'_>, name: &str, language: Language, source: &[u8]) -> bool {
+    let has_child = |node: &Node<'_>, kind: &str, text: &[&str]| {
+        node.children(&mut node.walk()).any(|child| {
+            child.kind() == kind
+                && (text.is_empty()
+                    || child
+                        .utf8_text(source)
+                        .is_ok_and(|t| text.iter().any(|word| t.contains(word))))
+        })
+    };
+    let parent_kind = |node: &Node<'_>| node.parent().map(|p| p.kind());
+    match language {
+        // Trait methods are as public as their trait
+        Language::Rust => {
+            node.kind() == "function_signature_item" || has_child(node, "visibility_modifier", &[])
+        }
+        Language::Python => {
+            !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__"))
+        }
+        Language::JavaScript | Language::TypeScript | Language::Tsx | Language::Jsx => {
+            if node.kind() != "method_definition" {
+                return parent_kind(node) == Some("export_statement");
+            }
+            let class = node.parent().and_then(|body| body.parent());
+            !name.starts_with('#')
+                && !has_child(node, "accessibility_modifier", &["private", "protected"])
+                && class.is_some_and(|class| parent_kind(&class) == Some("export_statement"))
+        }
+        Language::Java => {
+            has_child(node, "modifiers", &["public"]) || parent_kind(node) == Some("interface_body")
+        }
+        Language::Php => !has_child(node, "visibility_modifier", &["private", "protected"]),
+        _ => false,
+    }
+}
+
+/// The doc comment text of a function, with comment markers stripped.
+fn doc_comment(node: &Node<'_>, language: Language, source: &[u8]) -> Option<String> {
+    match language {
+        Language::Rust => rust_doc(node, source),
+        Language::Python => docstring(node, source),
+        _ => {
+            // `export function f` puts the comment before the export
+            let anchor = match node.parent() {
+                Some(parent) if parent.kind() == "export_statement" => parent,
+                _ => *node,
+            };
+            let comment = anchor
+                .prev_sibling()
+                .filter(|s| s.kind().contains("comment"))?;
+            let text = comment.utf8_text(source).ok()?;
+            let body = text.strip_prefix("/**")?.strip_suffix("*/")?;
+            Some(
+                body.lines()
+                    .map(|line| {
+                        let line = line.trim_start();
+                        line.strip_prefix('*')
+                            .map_or(line, |rest| rest.strip_prefix(' ').unwrap_or(rest))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        }
+    }
+}
+
+/// The `///` lines above a Rust item, skipping attributes.
+fn rust_doc(node: &Node<'_>, source: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut sibling = node.prev_sibling();
+    while let Some(current) = sibling {
+        match current.kind() {
+            "attribute_item" => {}
+            "line_comment" => {
+                let text = current.utf8_text(source).ok()?;
+                match text.strip_prefix("///") {
+                    Some(doc) if !doc.starts_with('/') => {
+                        let doc = doc.trim_end();
+                        lines.push(doc.strip_prefix(' ').unwrap_or(doc));
+                    }
+                    _ => break,
+                }
+            }
+            _ => break,
+        }
+        sibling = current.prev_sibling();
+    }
+    lines.reverse();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// A Python function's docstring without its quotes.
+fn docstring(node: &Node<'_>, source: &[u8]) -> Option<String> {
+    let statement = node.child_by_field_name("body")?.named_child(0)?;
+    if statement.kind() != "expression_statement" {
+        return None;
+    }
+    let string = statement.named_child(0).filter(|n| n.kind() == "string")?;
+    // Drop `r`, `u` and other string prefixes
+    let text = string
+        .utf8_text(source)
+        .ok()?
+        .trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let quote = ["\"\"\"", "'''", "\"", "'"]
+        .into_iter()
+        .find(|quote| text.starts_with(quote))?;
+    Some(text.strip_prefix(quote)?.strip_suffix(quote)?.to_string())
+}
+
+/// Parameter names a doc comment describes, or `None` when it describes
+/// none.
+fn documented_params(doc: &str, language: Language) -> Option<Vec<String>> {
+    let names = match language {
+        Language::Rust => rustdoc_params(doc),
+        Language::Python => sphinx_params(doc)
+            .or_else(|| google_params(doc))
+            .or_else(|| numpy_params(doc)),
+        _ => tag_params(doc),
+    }?;
+    (!names.is_empty()).then_some(names)
+}
+
+/// Bullets under a rustdoc `# Arguments` or `# Parameters` heading.
+fn rustdoc_params(doc: &str) -> Option<Vec<String>> {
+    let mut names = Vec::new();
+    let mut found = false;
+    let mut in_section = false;
+    for line in doc.lines() {
+        if let Some(heading) = line.trim().strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim().to_ascii_lowercase();
+            in_section = matches!(
+                heading.as_str(),
+                "arguments" | "args" | "parameters" | "params"
+            );
+            found |= in_section;
+            continue;
+        }
+        // Nested bullets describe the argument above them
+        if !in_section || line.starts_with("  ") {
+            continue;
+        }
+        let Some(item) = line.trim_start().strip_prefix(['*', '-']) else {
+            continue;
+        };
+        let item = item.trim_start();
+        let name = match item.strip_prefix('`') {
+            Some(quoted) => quoted.split('`').next().unwrap_or(""),
+            None => item
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or(""),
+        };
+        if !name.is_empty() {
+            names.push(name.to_string());
+        }
+    }
+    found.then_some(names)
+}
+
+/// `@param` tags, as JSDoc, Javadoc and PHPDoc write them.
+fn tag_params(doc: &str) -> Option<Vec<String>> {
+    let mut names = Vec::new();
+    let mut found = false;
+    for line in doc.lines() {
+        let line = line.trim();
+        let Some(rest) = ["@param", "@arg", "@argument"].iter().find_map(|tag| {
+            line.strip_prefix(tag)
+                .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        }) else {
+            continue;
+        };
+        found = true;
+        names.extend(tag_param_name(rest));
+    }
+    found.then_some(names)
+}
+
+/// The name in a `@param` tag: `{Type} name`, `[name=default]`,
+/// `Type $name` or `name`. Properties (`options.timeout`) and Javadoc type
+/// parameters (`<T>`) give `None`.
+fn tag_param_name(rest: &str) -> Option<String> {
+    let mut rest = rest.trim_start();
+    if rest.starts_with('{') {
+        let mut depth = 0;
+        for (idx, c) in rest.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => continue,
+            }
+            if depth == 0 {
+                rest = rest[idx + 1..].trim_start();
+                break;
+            }
+        }
+    }
+    let is_php_variable = |token: &str| token.trim_start_matches(['.', '&']).starts_with('$');
+    let mut tokens = rest.split_whitespace();
+    let first = tokens.next()?;
+    // PHPDoc puts the type first
+    let token = match tokens.next() {
+        Some(second) if !is_php_variable(first) && is_php_variable(second) => second,
+        _ => first,
+    };
+    if token.starts_with('<') {
+        return None;
+    }
+    let name = token
+        .trim_start_matches(['[', '.', '&', '$'])
+        .split(['=', ']'])
+        .next()?
+        .trim_end_matches([':', ',']);
+    (!name.is_empty() && !name.contains('.')).then(|| name.to_string())
+}
+
+/// Sphinx `:param name:` and `:param type name:` fields.
+fn sphinx_params(doc: &str) -> Option<Vec<String>> {
+    let mut names = Vec::new();
+    for line in doc.lines() {
+        let line = line.trim();
+        let Some(field) = [":param ", ":parameter ", ":arg ", ":argument "]
+            .iter()
+            .find_map(|tag| line.strip_prefix(tag))
+        else {
+            continue;
+        };
+        let declaration = field.split(':').next().unwrap_or("");
+        if let Some(name) = declaration.split_whitespace().last() {
+            names.push(name.trim_start_matches('*').to_string());
+        }
+    }
+    (!names.is_empty()).then_some(names)
+}
+
+/// Entries of a Google-style `Args:` section: `name (type): description`.
+fn google_params(doc: &str) -> Option<Vec<String>> {
+    let lines: Vec<&str> = doc.lines().collect();
+    let header = lines.iter().position(|line| {
+        matches!(
+            line.trim().to_ascii_lowercase().as_str(),
+            "args:" | "arguments:" | "parameters:" | "params:"
+        )
+    })?;
+    let header_indent = indent(lines[header]);
+    let mut entry_indent = None;
+    let mut names = Vec::new();
+    for line in &lines[header + 1..] {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_indent = indent(line);
+        if line_indent <= header_indent {
+            break;
+        }
+        if *entry_indent.get_or_insert(line_indent) != line_indent {
+            continue;
+        }
+        let name = line.trim().split(['(', ':']).next().unwrap_or("").trim();
+        if !name.is_empty() && !name.contains(char::is_whitespace) {
+            names.push(name.trim_start_matches('*').to_string());
+        }
+    }
+    Some(names)
+}
+
+/// Entries of a NumPy-style `Parameters` section: `name : type`, with
+/// `x, y : int` naming several.
+fn numpy_params(doc: &str) -> Option<Vec<String>> {
+    let lines: Vec<&str> = doc.lines().collect();
+    let is_underline = |idx: usize| {
+        lines
+            .get(idx)
+            .is_some_and(|l| !l.trim().is_empty() && l.trim().chars().all(|c| c == '-'))
+    };
+    let header = (0..lines.len()).find(|&idx| {
+        lines[idx].trim().eq_ignore_ascii_case("parameters") && is_underline(idx + 1)
+    })?;
+    let header_indent = indent(lines[header]);
+    let mut names = Vec::new();
+    for idx in header + 2..lines.len() {
+        let line = lines[idx];
+        if line.trim().is_empty() || indent(line) != header_indent {
+            continue;
+        }
+        // The next section's heading
+        if is_underline(idx + 1) {
+            break;
+        }
+        let declaration = line.split(':').next().unwrap_or("");
+        names.extend(
+            declaration
+                .split(',')
+                .map(|name| name.trim().trim_start_matches('*'))
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+        );
+    }
+    Some(names)
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// A function's parameters in order; destructured ones have no name.
+/// Receivers (`self`, `this`, `cls`) are left out.
+fn parameters(node: &Node<'_>, language: Language, source: &[u8]) -> Option<Vec<Option<String>>> {
+    let list = node.child_by_field_name("parameters")?;
+    let mut params = Vec::new();
+    for param in list.named_children(&mut list.walk()) {
+        let binding = match (language, param.kind()) {
+            (Language::Rust, "parameter") => param.child_by_field_name("pattern"),
+            (Language::Python, "default_parameter" | "typed_default_parameter") => {
+                param.child_by_field_name("name")
+            }
+            (
+                Language::Python,
+                "identifier"
+                | "typed_parameter"
+                | "list_splat_pattern"
+                | "dictionary_splat_pattern",
+            ) => Some(param),
+            (Language::Java, "formal_parameter") => param.child_by_field_name("name"),
+            (Language::Java, "spread_parameter") => param
+                .named_children(&mut param.walk())
+                .find(|child| child.kind() == "variable_declarator")
+                .and_then(|declarator| declarator.child_by_field_name("name")),
+            (Language::Php, _) => param.child_by_field_name("name"),
+            (Language::Rust | Language::Python | Language::Java, _) => continue,
+            (_, "required_parameter" | "optional_parameter") => {
+                param.child_by_field_name("pattern")
+            }
+            (_, "assignment_pattern") => param.child_by_field_name("left"),
+            (_, kind) if kind.contains("comment") => continue,
+            _ => Some(param),
+        };
+        let Some(binding) = binding else {
+            continue;
+        };
+        if binding.kind() == "this" {
+            continue;
+        }
+        let name = binding_name(&binding, source);
+        if language == Language::Python && matches!(name.as_deref(), Some("self" | "cls")) {
+            continue;
+        }
+        params.push(name);
+    }
+    Some(params)
+}
+
+/// The name a parameter binds; `None` for destructuring patterns.
+fn binding_name(node: &Node<'_>, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "identifier" | "variable_name" => node
+            .utf8_text(source)
+            .ok()
+            .map(|name| name.trim_start_matches('$').to_string()),
+        "rest_pattern" | "list_splat_pattern" | "dictionary_splat_pattern" | "typed_parameter" => {
+            binding_name(&node.named_child(0)?, source)
+        }
+        _ => None,
+    }
+}
+
+/// Drift between documented names and signature parameters, as
+/// `(kind, parameter, renamed_to)`.
+fn compare(
+    documented: &[String],
+    parameters: &[Option<String>],
+) -> Vec<(DriftKind, String, Option<String>)> {
+    let named: HashSet<&str> = parameters.iter().flatten().map(String::as_str).collect();
+    let documented_set: HashSet<&str> = documented.iter().map(String::as_str).collect();
+    // Destructured parameters are documented under any name
+    let mut unnamed = parameters.iter().filter(|p| p.is_none()).count();
+    let mut missing: Vec<&str> = parameters
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .filter(|name| !documented_set.contains(name))
+        .collect();
+
+    let mut drift = Vec::new();
+    let mut seen = HashSet::new();
+    for (position, name) in documented.iter().enumerate() {
+        if named.contains(name.as_str()) || !seen.insert(name.as_str()) {
+            continue;
+        }
+        if unnamed > 0 {
+            unnamed -= 1;
+            continue;
+        }
+        let renamed_to = parameters
+            .get(position)
+            .and_then(Option::as_deref)
+            .filter(|param| missing.contains(param));
+        match renamed_to {
+            Some(param) => {
+                missing.retain(|m| *m != param);
+                drift.push((DriftKind::Renamed, name.clone(), Some(param.to_string())));
+            }
+            None => drift.push((DriftKind::Stale, name.clone(), None)),
+        }
+    }
+    drift.extend(
+        missing
+            .into_iter()
+            .map(|name| (DriftKind::Missing, name.to_string(), None)),
+    );
+    drift
+}
+
+/// Kind of mismatch between a doc comment and its signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// Documented under a name the parameter in its place no longer has.
+    Renamed,
+    /// Documented parameter the signature doesn't have.
+    Stale,
+    /// Parameter the doc comment doesn't describe.
+    Missing,
+}
+
+impl DriftKind {
+    fn severity(self) -> Severity {
+        match self {
+            Self::Renamed | Self::Stale => Severity::Medium,
+            Self::Missing => Severity::Low,
+        }
+    }
+
+    fn message(self, parameter: &str, renamed_to: Option<&str>) -> String {
+        match self {
+            Self::Renamed => format!(
+                "documented parameter `{parameter}` is now `{}`",
+                renamed_to.unwrap_or_default()
+            ),
+            Self::Stale => format!("documented parameter `{parameter}` is not in the signature"),
+            Self::Missing => format!("parameter `{parameter}` is not documented"),
+        }
+    }
+}
+
+/// A doc comment out of step with its function's signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftItem {
+    pub file: String,
+    /// Line of the function.
+    pub line: u32,
+    pub function: String,
+    pub language: Language,
+    pub kind: DriftKind,
+    pub severity: Severity,
+    pub message: String,
+    /// The documented name, or the undocumented parameter for `missing`.
+    pub parameter: String,
+    /// The parameter now in a renamed one's place.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_to: Option<String>,
+    /// Number of parameters the doc comment describes.
+    pub documented: usize,
+    /// Number of parameters in the signature.
+    pub parameters: usize,
+}
+
+/// Documentation drift result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    /// Items ordered by severity, most severe first, then by location.
+    pub items: Vec<DriftItem>,
+    pub summary: Summary,
+}
+
+impl Analysis {
+    fn from_files(files: Vec<FileDrift>) -> Self {
+        let total_files = files.len();
+        let documented_functions = files.iter().map(|f| f.documented_functions).sum();
+        let mut items: Vec<DriftItem> = files.into_iter().flat_map(|f| f.items).collect();
+        items.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.file.cmp(&b.file))
+                .then(a.line.cmp(&b.line))
+        });
+
+        let mut by_kind = BTreeMap::new();
+        for item in &items {
+            *by_kind.entry(item.kind).or_default() += 1;
+        }
+        let drifted: HashSet<(&str, u32)> = items
+            .iter()
+            .map(|item| (item.file.as_str(), item.line))
+            .collect();
+        let summary = Summary {
+            total_files,
+            documented_functions,
+            drifted_functions: drifted.len(),
+            total_items: items.len(),
+            severity_counts: items.iter().map(|item| item.severity).collect(),
+            by_kind,
+        };
+        Self { items, summary }
+    }
+}
+
+/// Aggregate drift statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_files: usize,
+    /// Functions whose doc comment describes parameters.
+    pub documented_functions: usize,
+    pub drifted_functions: usize,
+    pub total_items: usize,
+    pub severity_counts: SeverityCounts,
+    pub by_kind: BTreeMap<DriftKind, usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use std::path::Path;
+
+    fn check(code: &str, language: Language, file: &str) -> FileDrift {
+        let parsed = Parser::new()
+            .parse(code.as_bytes(), language, Path::new(file))
+            .unwrap();
+        check_parsed(file, &parsed, false)
+    }
+
+    fn kinds(drift: &FileDrift) -> Vec<(DriftKind, &str, Option<&str>)> {
+        drift
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item.kind,
+                    item.parameter.as_str(),
+                    item.renamed_to.as_deref(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rustdoc_arguments() {
+        let drift = check(
+            "/// Resize the buffer.\n\
+             ///\n\
+             /// # Arguments\n\
+             ///\n\
+             /// * `width` - new width\n\
+             /// * `depth` - new depth\n\
+             ///   - nested detail\n\
+             #[inline]\n\
+             pub fn resize(&mut self, width: u32, height: u32, fill: u8) {}\n\
+             \n\
+             /// # Arguments\n\
+             /// * `a` - unused\n\
+             fn private(b: u32) {}\n\
+             \n\
+             /// No argument docs here.\n\
+             pub fn plain(x: u32) {}\n",
+            Language::Rust,
+            "lib.rs",
+        );
+
+        assert_eq!(drift.documented_functions, 1);
+        assert_eq!(
+            kinds(&drift),
+            vec![
+                (DriftKind::Renamed, "depth", Some("height")),
+                (DriftKind::Missing, "fill", None),
+            ]
+        );
+        assert_eq!(drift.items[0].line, 9);
+        assert_eq!(
+            (drift.items[0].documented, drift.items[0].parameters),
+            (2, 3)
+        );
+    }
+
+    #[test]
+    fn test_jsdoc_params() {
+        let drift = check(
+            "/**\n\
+              * Fetch a page.\n\
+              * @param {string} url - address\n\
+              * @param {Object} options\n\
+              * @param {number} [options.timeout=30]\n\
+              * @param {number} retries\n\
+              */\n\
+             export function fetchPage(url, { timeout }) {}\n\
+             \n\
+             export class Client {\n\
+               /** @param {string} name */\n\
+               send(message) {}\n\
+               /** @param {string} name */\n\
+               #hidden(other) {}\n\
+             }\n",
+            Language::JavaScript,
+            "client.js",
+        );
+
+        assert_eq!(drift.documented_functions, 2);
+        assert_eq!(
+            kinds(&drift),
+            vec![
+                (DriftKind::Stale, "retries", None),
+                (DriftKind::Renamed, "name", Some("message")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_docstring_styles() {
+        let drift = check(
+            "def google(self, path, *args, mode='r'):\n    \
+                 \"\"\"Open a file.\n\n    \
+                 Args:\n        \
+                     path (str): Where.\n            \
+                         Continued description.\n        \
+                     flags: Gone.\n        \
+                     *args: Extra.\n\n    \
+                 Returns:\n        \
+                     The handle.\n    \
+                 \"\"\"\n\
+             \n\
+             def numpy(x, y):\n    \
+                 \"\"\"Add.\n\n    \
+                 Parameters\n    \
+                 ----------\n    \
+                 x, y : int\n        \
+                     Operands.\n\n    \
+                 Returns\n    \
+                 -------\n    \
+                 int\n    \
+                 \"\"\"\n\
+             \n\
+             def sphinx(src, dst):\n    \
+                 \"\"\"Copy.\n\n    \
+                 :param str src: Source.\n    \
+                 :param target: Target.\n    \
+                 \"\"\"\n\
+             \n\
+             def _private(a):\n    \
+                 \"\"\":param b: nope\"\"\"\n",
+            Language::Python,
+            "io.py",
+        );
+
+        assert_eq!(drift.documented_functions, 3);
+        let mut found = kinds(&drift);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                (DriftKind::Renamed, "target", Some("dst")),
+                (DriftKind::Stale, "flags", None),
+                (DriftKind::Missing, "mode", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_javadoc_and_phpdoc() {
+        let java = check(
+            "public class Store {\n\
+               /**\n\
+                * @param <T> the value type\n\
+                * @param key the key\n\
+                * @param value the value\n\
+                */\n\
+               public <T> void put(String key, T value, long ttl) {}\n\
+             }\n",
+            Language::Java,
+            "Store.java",
+        );
+        assert_eq!(kinds(&java), vec![(DriftKind::Missing, "ttl", None)]);
+
+        let php = check(
+            "<?php\n\
+             class Repo {\n\
+                 /**\n\
+                  * @param int $id\n\
+                  * @param string ...$fields\n\
+                  */\n\
+                 public function find($id, ...$columns) {}\n\
+             }\n",
+            Language::Php,
+            "Repo.php",
+        );
+        assert_eq!(
+            kinds(&php),
+            vec![(DriftKind::Renamed, "fields", Some("columns"))]
+        );
+    }
+
+    #[test]
+    fn test_tag_param_name() {
+        assert_eq!(
+            tag_param_name(" {Map<string, {a: number}>} opts"),
+            Some("opts".into())
+        );
+        assert_eq!(
+            tag_param_name(" [name=\"x\"] optional"),
+            Some("name".into())
+        );
+        assert_eq!(tag_param_name(" {...number} ...nums"), Some("nums".into()));
+        assert_eq!(
+            tag_param_name(" array &$items by reference"),
+            Some("items".into())
+        );
+        assert_eq!(tag_param_name(" {number} options.retries"), None);
+        assert_eq!(tag_param_name(""), None);
+    }
+
+    #[test]
+    fn test_summary() {
+        let analysis = Analysis::from_files(vec![check(
+            "/**\n * @param a\n * @param b\n */\nexport function f(c) {}\n",
+            Language::TypeScript,
+            "f.ts",
+        )]);
+
+        assert_eq!(analysis.summary.total_files, 1);
+        assert_eq!(analysis.summary.documented_functions, 1);
+        assert_eq!(analysis.summary.drifted_functions, 1);
+        assert_eq!(analysis.summary.total_items, 2);
+        assert_eq!(analysis.items[0].kind, DriftKind::Renamed);
+        assert_eq!(analysis.summary.severity_counts.medium, 2);
+        assert_eq!(analysis.summary.by_kind[&DriftKind::Stale], 1);
+    }
+}
@@ -0,0 +1,712 @@
+//! Dockerfile and container image hygiene.
+//!
+//! Finds `Dockerfile`, `Containerfile`, `Dockerfile.*` and `*.dockerfile`
+//! files and reports:
+//! - Base images that float (no tag, `latest`, major-version tags) or are
+//!   pinned to a tag rather than a digest
+//! - `apt-get install` without clearing `/var/lib/apt/lists`, and `apk add`
+//!   without `--no-cache`, in the same `RUN`
+//! - Secret-looking `ENV` and `ARG` names, which persist in the image config
+//!   or its build history
+//! - Build stages with more layer-creating instructions than
+//!   `[docker] max_layers`
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::pipeline::{ExternalRef, Pinning};
+use crate::config::DockerConfig;
+use crate::core::{
+    AnalysisContext, Analyzer as AnalyzerTrait, ContentSource, Result, Severity, SeverityCounts,
+    TreeSource,
+};
+
+/// Dockerfile hygiene analyzer.
+#[derive(Default)]
+pub struct Analyzer;
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check Dockerfiles for unpinned images, package caches, secrets and layer bloat"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let config = &ctx.config.docker;
+        if let Some(repo) = ctx.open_git()? {
+            if repo.reads_from_objects() {
+                return analyze_tree(&repo.tree_source()?, config);
+            }
+        }
+
+        let mut sources = Vec::new();
+        let walker = WalkBuilder::new(ctx.root)
+            .hidden(false)
+            .filter_entry(|e| e.file_name() != ".git")
+            .build();
+        for entry in walker.flatten() {
+            let Ok(relative) = entry.path().strip_prefix(ctx.root) else {
+                continue;
+            };
+            if !is_dockerfile(relative) {
+                continue;
+            }
+            if let Ok(bytes) = ctx.read_file(relative) {
+                sources.push((
+                    relative.to_path_buf(),
+                    String::from_utf8_lossy(&bytes).into_owned(),
+                ));
+            }
+        }
+        Ok(analyze_sources(sources, config))
+    }
+}
+
+/// Check the Dockerfiles in a git tree.
+pub fn analyze_tree(tree: &TreeSource, config: &DockerConfig) -> Result<Analysis> {
+    let sources = tree
+        .list_files()?
+        .into_iter()
+        .filter(|path| is_dockerfile(path))
+        .filter_map(|path| {
+            let bytes = tree.read(&path).ok()?;
+            Some((path, String::from_utf8_lossy(&bytes).into_owned()))
+        })
+        .collect();
+    Ok(analyze_sources(sources, config))
+}
+
+/// Check already-loaded Dockerfiles given as `(relative path, contents)`.
+pub fn analyze_sources(mut sources: Vec<(PathBuf, String)>, config: &DockerConfig) -> Analysis {
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut files = Vec::new();
+    let mut findings = Vec::new();
+    for (path, content) in &sources {
+        let path = path.to_string_lossy().to_string();
+        let mut audit = FileAudit::new(&path, config);
+        for instruction in instructions(content) {
+            audit.instruction(&instruction);
+        }
+        let (file, file_findings) = audit.finish();
+        files.push(file);
+        findings.extend(file_findings);
+    }
+
+    findings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+    });
+    files.sort_by(|a, b| {
+        b.findings
+            .cmp(&a.findings)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let summary = Summary::from_results(&files, &findings);
+    Analysis {
+        generated_at: Utc::now(),
+        files,
+        findings,
+        summary,
+    }
+}
+
+/// Whether a path names a Dockerfile or Containerfile.
+pub fn is_dockerfile(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let name = name.to_ascii_lowercase();
+    // `Dockerfile.dockerignore` is BuildKit's per-Dockerfile ignore file.
+    if name.ends_with(".dockerignore") {
+        return false;
+    }
+    ["dockerfile", "containerfile"].iter().any(|base| {
+        name == *base
+            || name.starts_with(&format!("{base}."))
+            || name.ends_with(&format!(".{base}"))
+    })
+}
+
+/// Kind of hygiene problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DockerRule {
+    /// Base image without a digest.
+    UnpinnedBaseImage,
+    /// Package manager cache left behind in a layer.
+    PackageCache,
+    /// Secret-looking `ENV` or `ARG`.
+    SecretInEnv,
+    /// Build stage with more layers than `[docker] max_layers`.
+    ExcessiveLayers,
+}
+
+/// A hygiene problem at one instruction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub file: String,
+    pub line: u32,
+    pub rule: DockerRule,
+    pub severity: Severity,
+    pub message: String,
+    pub suggestion: String,
+}
+
+/// Per-file overview.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Dockerfile {
+    pub path: String,
+    pub stages: usize,
+    /// `RUN`, `COPY` and `ADD` instructions across all stages.
+    pub layers: usize,
+    /// External base images; earlier stages and `scratch` are left out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub base_images: Vec<ExternalRef>,
+    pub findings: usize,
+}
+
+/// Dockerfile hygiene result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    pub generated_at: DateTime<Utc>,
+    /// Files ordered by finding count, most first.
+    pub files: Vec<Dockerfile>,
+    /// Findings ordered by severity, most severe first.
+    pub findings: Vec<Finding>,
+    pub summary: Summary,
+}
+
+/// Aggregate Dockerfile statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_files: usize,
+    pub total_stages: usize,
+    pub total_findings: usize,
+    pub severity_counts: SeverityCounts,
+    pub by_rule: BTreeMap<DockerRule, usize>,
+}
+
+impl Summary {
+    fn from_results(files: &[Dockerfile], findings: &[Finding]) -> Self {
+        let mut by_rule = BTreeMap::new();
+        for finding in findings {
+            *by_rule.entry(finding.rule).or_default() += 1;
+        }
+        Self {
+            total_files: files.len(),
+            total_stages: files.iter().map(|f| f.stages).sum(),
+            total_findings: findings.len(),
+            severity_counts: findings.iter().map(|f| f.severity).collect(),
+            by_rule,
+        }
+    }
+}
+
+/// One logical instruction with continuation lines and heredocs joined.
+struct Instruction {
+    line: u32,
+    keyword: String,
+    args: String,
+}
+
+fn instructions(content: &str) -> Vec<Instruction> {
+    let is_skipped = |line: &str| line.is_empty() || line.starts_with('#');
+    let mut out = Vec::new();
+    let mut lines = content.lines().enumerate();
+    while let Some((i, raw)) = lines.next() {
+        let trimmed = raw.trim();
+        if is_skipped(trimmed) {
+            continue;
+        }
+        let (keyword, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        let mut args = rest.trim().to_string();
+
+        // Comment and blank lines inside a continuation are dropped.
+        while args.ends_with('\\') {
+            args.pop();
+            args.truncate(args.trim_end().len());
+            let Some((_, next)) = lines.find(|(_, l)| !is_skipped(l.trim())) else {
+                break;
+            };
+            args.push(' ');
+            args.push_str(next.trim());
+        }
+        for delimiter in heredoc_delimiters(&args) {
+            for (_, body) in lines.by_ref() {
+                if body.trim() == delimiter {
+                    break;
+                }
+                args.push('\n');
+                args.push_str(body);
+            }
+        }
+
+        out.push(Instruction {
+            line: i as u32 + 1,
+            keyword: keyword.to_ascii_uppercase(),
+            args,
+        });
+    }
+    out
+}
+
+/// Delimiters of `<<EOF`, `<<-EOF` and `<<"EOF"` heredocs, in order.
+fn heredoc_delimiters(args: &str) -> Vec<String> {
+    args.match_indices("<<")
+        .filter_map(|(i, _)| {
+            let rest = args[i + 2..].trim_start_matches('-');
+            let word: String = rest
+                .trim_start_matches(['"', '\''])
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            (!word.is_empty()).then_some(word)
+        })
+        .collect()
+}
+
+/// Name fragments that mark an `ENV` or `ARG` as holding a secret.
+const SECRET_MARKERS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "SECRET",
+    "TOKEN",
+    "API_KEY",
+    "APIKEY",
+    "PRIVATE_KEY",
+    "ACCESS_KEY",
+    "CREDENTIALS",
+];
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    // `*_FILE` and `*_PATH` point at a secret rather than hold one.
+    if name.ends_with("_FILE") || name.ends_with("_PATH") {
+        return false;
+    }
+    SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+}
+
+/// `KEY=value` pairs of `ENV`/`ARG`, or the legacy `ENV KEY value` form.
+/// `ARG NAME` without a default yields no value.
+fn variables(args: &str) -> Vec<(&str, Option<&str>)> {
+    let first = args.split_whitespace().next().unwrap_or("");
+    if !first.contains('=') && args.trim() != first {
+        let (key, value) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        return vec![(key, Some(value.trim()))];
+    }
+    args.split_whitespace()
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key, Some(value.trim_matches(['"', '\'']))),
+            None => (pair, None),
+        })
+        .collect()
+}
+
+/// Whether any command in a shell script runs `tool ... subcommand`.
+fn invokes(script: &str, tool: &str, subcommand: &str) -> bool {
+    script.split(['&', ';', '|', '\n']).any(|command| {
+        let mut words = command.split_whitespace().skip_while(|w| *w != tool);
+        words.next().is_some() && words.any(|w| w == subcommand)
+    })
+}
+
+struct Stage {
+    line: u32,
+    name: String,
+    layers: usize,
+}
+
+struct FileAudit<'a> {
+    config: &'a DockerConfig,
+    file: Dockerfile,
+    findings: Vec<Finding>,
+    /// `ARG` defaults declared before the first `FROM`.
+    global_args: HashMap<String, String>,
+    stage_names: Vec<String>,
+    stage: Option<Stage>,
+}
+
+impl<'a> FileAudit<'a> {
+    fn new(path: &str, config: &'a DockerConfig) -> Self {
+        Self {
+            config,
+            file: Dockerfile {
+                path: path.to_string(),
+                ..Default::default()
+            },
+            findings: Vec::new(),
+            global_args: HashMap::new(),
+            stage_names: Vec::new(),
+            stage: None,
+        }
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) {
+        let (line, args) = (instruction.line, instruction.args.as_str());
+        match instruction.keyword.as_str() {
+            "FROM" => self.from(line, args),
+            "RUN" => {
+                self.layer();
+                self.run(line, args);
+            }
+            "COPY" | "ADD" => self.layer(),
+            "ENV" => {
+                for (name, value) in variables(args) {
+                    self.secret(line, "ENV", name, value);
+                }
+            }
+            "ARG" => {
+                for (name, value) in variables(args) {
+                    if self.file.stages == 0 {
+                        self.global_args
+                            .insert(name.to_string(), value.unwrap_or("").to_string());
+                    }
+                    self.secret(line, "ARG", name, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn from(&mut self, line: u32, args: &str) {
+        self.finish_stage();
+        let mut words = args.split_whitespace().filter(|w| !w.starts_with("--"));
+        let Some(image) = words.next() else {
+            return;
+        };
+        let alias = match (words.next(), words.next()) {
+            (Some(kw), Some(alias)) if kw.eq_ignore_ascii_case("as") => {
+                Some(alias.to_ascii_lowercase())
+            }
+            _ => None,
+        };
+
+        self.file.stages += 1;
+        let image = self.expand(image);
+        let is_stage = self.stage_names.contains(&image.to_ascii_lowercase());
+        self.stage = Some(Stage {
+            line,
+            name: alias
+                .clone()
+                .unwrap_or_else(|| format!("#{}", self.file.stages)),
+            layers: 0,
+        });
+        self.stage_names.extend(alias);
+        // Unresolved build args cannot be classified.
+        if is_stage || image == "scratch" || image.contains('$') {
+            return;
+        }
+
+        let reference = ExternalRef::image(&image, line);
+        let problem = match (reference.pinning, reference.version.as_deref()) {
+            (Pinning::Sha, _) => None,
+            (Pinning::Floating, None) => {
+                Some((Severity::High, "has no tag, so it resolves to `latest`"))
+            }
+            (Pinning::Floating, Some("latest")) => Some((Severity::High, "uses `latest`")),
+            (Pinning::Floating, Some(_)) => Some((Severity::Medium, "uses a floating tag")),
+            (Pinning::Version, _) => {
+                Some((Severity::Low, "is pinned to a tag, which can be re-pushed"))
+            }
+        };
+        if let Some((severity, problem)) = problem {
+            self.push(
+                line,
+                DockerRule::UnpinnedBaseImage,
+                severity,
+                format!("Base image `{image}` {problem}"),
+                "Pin the base image by digest (`image:tag@sha256:...`) and let a bot bump it",
+            );
+        }
+        self.file.base_images.push(reference);
+    }
+
+    fn run(&mut self, line: u32, script: &str) {
+        // Cache mounts keep package caches out of the layer on purpose.
+        if script.contains("type=cache") {
+            return;
+        }
+        let apt = invokes(script, "apt-get", "install") || invokes(script, "apt", "install");
+        if apt && !script.contains("/var/lib/apt/lists") {
+            self.push(
+                line,
+                DockerRule::PackageCache,
+                Severity::Medium,
+                "`apt-get install` leaves `/var/lib/apt/lists` in the layer".to_string(),
+                "Add `&& rm -rf /var/lib/apt/lists/*` to the same `RUN`",
+            );
+        }
+        if invokes(script, "apk", "add")
+            && !script.contains("--no-cache")
+            && !script.contains("/var/cache/apk")
+        {
+            self.push(
+                line,
+                DockerRule::PackageCache,
+                Severity::Medium,
+                "`apk add` without `--no-cache` leaves the package index in the layer".to_string(),
+                "Use `apk add --no-cache`",
+            );
+        }
+    }
+
+    fn secret(&mut self, line: u32, keyword: &str, name: &str, value: Option<&str>) {
+        if !is_secret_name(name) {
+            return;
+        }
+        let literal = value.is_some_and(|v| !v.is_empty() && !v.starts_with('$'));
+        let (severity, message) = match keyword {
+            "ENV" if literal => (
+                Severity::Critical,
+                format!("`ENV {name}` bakes a secret value into the image"),
+            ),
+            "ENV" => (
+                Severity::High,
+                format!("`ENV {name}` keeps a secret in the image config"),
+            ),
+            _ => (
+                Severity::High,
+                format!("`ARG {name}` is recorded in the image build history"),
+            ),
+        };
+        self.push(
+            line,
+            DockerRule::SecretInEnv,
+            severity,
+            message,
+            "Pass secrets with `RUN --mount=type=secret` instead of `ENV` or `ARG`",
+        );
+    }
+
+    fn layer(&mut self) {
+        self.file.layers += 1;
+        if let Some(stage) = self.stage.as_mut() {
+            stage.layers += 1;
+        }
+    }
+
+    fn finish_stage(&mut self) {
+        let Some(stage) = self.stage.take() else {
+            return;
+        };
+        if stage.layers > self.config.max_layers {
+            self.push(
+                stage.line,
+                DockerRule::ExcessiveLayers,
+                Severity::Low,
+                format!(
+                    "Stage `{}` has {} layers (max {})",
+                    stage.name, stage.layers, self.config.max_layers
+                ),
+                "Merge related `RUN` instructions and copy with fewer `COPY` steps",
+            );
+        }
+    }
+
+    /// Substitute `$NAME` and `${NAME}` with global `ARG` defaults.
+    fn expand(&self, value: &str) -> String {
+        let mut names: Vec<&String> = self.global_args.keys().collect();
+        // Longest first so `$BASE` does not clobber `$BASE_TAG`.
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let mut out = value.to_string();
+        for name in names {
+            let default = &self.global_args[name];
+            out = out
+                .replace(&format!("${{{name}}}"), default)
+                .replace(&format!("${name}"), default);
+        }
+        out
+    }
+
+    fn push(
+        &mut self,
+        line: u32,
+        rule: DockerRule,
+        severity: Severity,
+        message: String,
+        suggestion: &str,
+    ) {
+        self.findings.push(Finding {
+            file: self.file.path.clone(),
+            line,
+            rule,
+            severity,
+            message,
+            suggestion: suggestion.to_string(),
+        });
+    }
+
+    fn finish(mut self) -> (Dockerfile, Vec<Finding>) {
+        self.finish_stage();
+        self.file.findings = self.findings.len();
+        (self.file, self.findings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit(dockerfile: &str) -> Analysis {
+        analyze_sources(
+            vec![(PathBuf::from("Dockerfile"), dockerfile.to_string())],
+            &DockerConfig::default(),
+        )
+    }
+
+    fn rules(result: &Analysis) -> Vec<(DockerRule, Severity, u32)> {
+        let mut rules: Vec<_> = result
+            .findings
+            .iter()
+            .map(|f| (f.rule, f.severity, f.line))
+            .collect();
+        rules.sort_by_key(|r| r.2);
+        rules
+    }
+
+    #[test]
+    fn test_is_dockerfile() {
+        let detect = |p: &str| is_dockerfile(Path::new(p));
+        assert!(detect("Dockerfile"));
+        assert!(detect("deploy/Dockerfile.prod"));
+        assert!(detect("api.dockerfile"));
+        assert!(detect("Containerfile"));
+        assert!(!detect(".dockerignore"));
+        assert!(!detect("Dockerfile.dockerignore"));
+        assert!(!detect("docker-compose.yml"));
+    }
+
+    #[test]
+    fn test_instructions_join_continuations_and_heredocs() {
+        let content = "FROM alpine\nRUN apk add \\\n    # comment\n    curl\nRUN <<EOF\nset -e\necho hi\nEOF\nCOPY . .\n";
+        let parsed = instructions(content);
+        let keywords: Vec<_> = parsed.iter().map(|i| i.keyword.as_str()).collect();
+        assert_eq!(keywords, vec!["FROM", "RUN", "RUN", "COPY"]);
+        assert_eq!(parsed[1].args, "apk add curl");
+        assert_eq!(parsed[2].args, "<<EOF\nset -e\necho hi");
+        assert_eq!(parsed[3].line, 9);
+    }
+
+    #[test]
+    fn test_base_image_pinning() {
+        let digest = "a".repeat(64);
+        let dockerfile = format!(
+            "ARG NODE_VERSION=20.11.1\nFROM node:${{NODE_VERSION}} AS build\nFROM build AS test\nFROM ubuntu\nFROM python:3.12-slim\nFROM nginx:latest\nFROM alpine:3.19.1@sha256:{digest}\nFROM scratch\n"
+        );
+        let result = audit(&dockerfile);
+        assert_eq!(
+            rules(&result),
+            vec![
+                (DockerRule::UnpinnedBaseImage, Severity::Low, 2),
+                (DockerRule::UnpinnedBaseImage, Severity::High, 4),
+                (DockerRule::UnpinnedBaseImage, Severity::Medium, 5),
+                (DockerRule::UnpinnedBaseImage, Severity::High, 6),
+            ]
+        );
+        let file = &result.files[0];
+        assert_eq!(file.stages, 7);
+        // Earlier stages and `scratch` are not external images.
+        assert_eq!(file.base_images.len(), 5);
+        assert_eq!(file.base_images[0].name, "node");
+    }
+
+    #[test]
+    fn test_package_cache() {
+        let result = audit(
+            "FROM debian:12.5@sha256:0000000000000000000000000000000000000000000000000000000000000000\n\
+             RUN apt-get update && apt-get -y install curl\n\
+             RUN apt-get update && apt-get install -y git && rm -rf /var/lib/apt/lists/*\n\
+             RUN apk add curl\n\
+             RUN apk add --no-cache git\n\
+             RUN --mount=type=cache,target=/var/cache/apt apt-get install -y make\n",
+        );
+        let lines: Vec<_> = rules(&result).iter().map(|r| (r.0, r.2)).collect();
+        assert_eq!(
+            lines,
+            vec![(DockerRule::PackageCache, 2), (DockerRule::PackageCache, 4)]
+        );
+    }
+
+    #[test]
+    fn test_secrets_in_env_and_arg() {
+        let result = audit(
+            "FROM alpine:3.19.1\nARG NPM_TOKEN\nENV API_KEY=abc123 LOG_LEVEL=info\nENV DB_PASSWORD $DB_PASSWORD\nENV POSTGRES_PASSWORD_FILE=/run/secrets/db\n",
+        );
+        let secrets: Vec<_> = rules(&result)
+            .into_iter()
+            .filter(|r| r.0 == DockerRule::SecretInEnv)
+            .map(|r| (r.1, r.2))
+            .collect();
+        assert_eq!(
+            secrets,
+            vec![
+                (Severity::High, 2),
+                (Severity::Critical, 3),
+                (Severity::High, 4),
+            ]
+        );
+        assert_eq!(result.findings[0].severity, Severity::Critical);
+        assert_eq!(result.summary.severity_counts.critical, 1);
+    }
+
+    #[test]
+    fn test_excessive_layers_per_stage() {
+        let mut dockerfile = String::from("FROM alpine:3.19.1 AS build\n");
+        dockerfile.push_str(&"RUN true\n".repeat(4));
+        dockerfile.push_str("FROM alpine:3.19.1\nCOPY --from=build /out /out\n");
+        let config = DockerConfig { max_layers: 3 };
+        let result = analyze_sources(vec![(PathBuf::from("Dockerfile"), dockerfile)], &config);
+
+        let layers: Vec<_> = result
+            .findings
+            .iter()
+            .filter(|f| f.rule == DockerRule::ExcessiveLayers)
+            .collect();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].line, 1);
+        assert!(layers[0].message.contains("`build` has 4 layers"));
+        assert_eq!(result.files[0].layers, 5);
+        assert_eq!(result.summary.by_rule[&DockerRule::ExcessiveLayers], 1);
+    }
+
+    #[test]
+    fn test_analyze_walks_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("deploy")).unwrap();
+        std::fs::write(root.join("Dockerfile"), "FROM node\n").unwrap();
+        std::fs::write(root.join("deploy/Dockerfile.worker"), "FROM python:3.12\n").unwrap();
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(root, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        let result = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert_eq!(result.summary.total_files, 2);
+        assert_eq!(result.findings[0].file, "Dockerfile");
+        assert_eq!(result.findings[0].severity, Severity::High);
+    }
+}
@@ -14,13 +14,52 @@
 //!
 //! Default: 200 hashes, 20 bands x 10 rows, 0.70 similarity threshold.
 //! These parameters provide good precision/recall balance for code clones.
+//! `min_tokens`, `min_lines`, and `min_similarity` can be overridden per
+//! language under `[duplicates.languages.<name>]`.
+//!
+//! # Intra-file clones
+//!
+//! Function-level fragments miss copy-paste inside a single file when the
+//! copies are blocks rather than whole functions. Each file is also scanned
+//! with a sliding window of `min_lines` significant lines; windows with
+//! identical normalized tokens at different positions are extended into
+//! maximal non-overlapping ranges and reported as Type-1 (identical text) or
+//! Type-2 (renamed identifiers/literals) clones.
+//!
+//! # Structural matching
+//!
+//! Token shingles still differ when a formatter adds trailing commas,
+//! semicolons or parentheses. With `structural` enabled, each fragment's
+//! syntax tree is also reduced to its shape - node kinds only, without
+//! identifier and literal text, punctuation, comments or layout - and
+//! fragments with equal shape hashes are reported as Type-2 clones even when
+//! their token similarity falls below the threshold. Each clone records the
+//! [`Normalization`] level it matched at.
+//!
+//! # Warm starts
+//!
+//! With a state directory, each scanned file's fragment hashes and
+//! intra-file clones are saved next to the result. A warm-started run takes
+//! them from there for files no commit has touched since, skipping their
+//! tokenizing, parsing and hashing, and only pairs and groups in full.
 
 use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result};
+use crate::config::{DuplicatesConfig, DuplicatesLanguageConfig};
+use crate::core::warm_start::save_state;
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
+use crate::parser::Parser;
+
+/// Windows repeated more often than this are boilerplate, not copy-paste.
+const MAX_WINDOW_REPEATS: usize = 16;
+
+/// Lowest similarity classified as a Type-2 clone.
+const TYPE2_SIMILARITY: f64 = 0.85;
 
 /// Clone type classification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -34,11 +73,22 @@ pub enum CloneType {
     Type3,
 }
 
+/// Normalization level at which a clone matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// Token sequences with identifiers and literals canonicalized.
+    #[default]
+    Tokens,
+    /// Syntax tree shape, ignoring names, literals, punctuation and layout.
+    AstShape,
+}
+
 impl CloneType {
     fn from_similarity(similarity: f64) -> Self {
         if similarity >= 0.95 {
             CloneType::Type1
-        } else if similarity >= 0.85 {
+        } else if similarity >= TYPE2_SIMILARITY {
             CloneType::Type2
         } else {
             CloneType::Type3
@@ -51,6 +101,14 @@ impl CloneType {
 pub struct Config {
     pub min_tokens: usize,
     pub similarity_threshold: f64,
+    /// Minimum significant (non-blank, non-comment) lines in a clone.
+    pub min_lines: usize,
+    /// Detect clones between different ranges of the same file.
+    pub intra_file: bool,
+    /// Also match fragments whose syntax trees have the same shape.
+    pub structural: bool,
+    /// Per-language overrides keyed by [`detect_language`] name.
+    pub languages: HashMap<String, DuplicatesLanguageConfig>,
     pub shingle_size: usize,
     pub num_hash_functions: usize,
     pub num_bands: usize,
@@ -66,6 +124,10 @@ impl Default for Config {
         Self {
             min_tokens: 50,
             similarity_threshold: 0.70,
+            min_lines: 6,
+            intra_file: true,
+            structural: true,
+            languages: HashMap::new(),
             shingle_size: 5,
             num_hash_functions: 200,
             num_bands: 20,
@@ -78,10 +140,60 @@ impl Default for Config {
     }
 }
 
+impl From<&DuplicatesConfig> for Config {
+    fn from(settings: &DuplicatesConfig) -> Self {
+        Self {
+            min_tokens: settings.min_tokens,
+            similarity_threshold: settings.min_similarity,
+            min_lines: settings.min_lines,
+            intra_file: settings.intra_file,
+            structural: settings.structural,
+            languages: settings
+                .languages
+                .iter()
+                .map(|(lang, thresholds)| (lang.to_lowercase(), thresholds.clone()))
+                .collect(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Config {
+    fn language(&self, lang: &str) -> Option<&DuplicatesLanguageConfig> {
+        self.languages.get(lang)
+    }
+
+    /// Minimum token count for fragments in `lang`.
+    pub fn min_tokens_for(&self, lang: &str) -> usize {
+        self.language(lang)
+            .and_then(|l| l.min_tokens)
+            .unwrap_or(self.min_tokens)
+    }
+
+    /// Minimum significant line count for fragments in `lang`.
+    pub fn min_lines_for(&self, lang: &str) -> usize {
+        self.language(lang)
+            .and_then(|l| l.min_lines)
+            .unwrap_or(self.min_lines)
+    }
+
+    /// Similarity threshold for fragments in `lang`.
+    pub fn similarity_for(&self, lang: &str) -> f64 {
+        self.language(lang)
+            .and_then(|l| l.min_similarity)
+            .unwrap_or(self.similarity_threshold)
+    }
+}
+
 /// Duplicates analyzer using MinHash with LSH.
 pub struct Analyzer {
     config: Config,
+    /// Set by the builder methods; otherwise `[duplicates]` from the
+    /// analysis context's config applies.
+    configured: bool,
     max_file_size: usize,
+    /// Where to save the fragment hashes for a later warm start.
+    state_dir: Option<PathBuf>,
 }
 
 impl Default for Analyzer {
@@ -94,22 +206,33 @@ impl Analyzer {
     pub fn new() -> Self {
         Self {
             config: Config::default(),
+            configured: false,
             max_file_size: 0, // No limit
+            state_dir: None,
         }
     }
 
     pub fn with_config(mut self, config: Config) -> Self {
         self.config = config;
+        self.configured = true;
         self
     }
 
     pub fn with_min_tokens(mut self, min_tokens: usize) -> Self {
         self.config.min_tokens = min_tokens;
+        self.configured = true;
         self
     }
 
     pub fn with_similarity_threshold(mut self, threshold: f64) -> Self {
         self.config.similarity_threshold = threshold;
+        self.configured = true;
+        self
+    }
+
+    pub fn with_min_lines(mut self, min_lines: usize) -> Self {
+        self.config.min_lines = min_lines;
+        self.configured = true;
         self
     }
 
@@ -118,6 +241,13 @@ impl Analyzer {
         self
     }
 
+    /// Save each file's fragment hashes to the data directory `dir`, for a
+    /// later run to warm-start from.
+    pub fn with_state_dir(mut self, dir: &Path) -> Self {
+        self.state_dir = Some(dir.to_path_buf());
+        self
+    }
+
     /// Extract code fragments from file content.
     fn extract_fragments(&self, path: &str, content: &[u8]) -> Vec<CodeFragment> {
         let content_str = match std::str::from_utf8(content) {
@@ -144,6 +274,10 @@ impl Analyzer {
             }
         }
 
+        if self.config.structural {
+            assign_shape_hashes(path, content, &mut fragments);
+        }
+
         fragments
     }
 
@@ -173,6 +307,12 @@ impl Analyzer {
                     brace_depth = 1;
                 } else if lang == "ruby" {
                     end_depth = 1;
+                } else if lang == "lua" {
+                    end_depth = lua_block_delta(trimmed);
+                    if end_depth <= 0 {
+                        // One-line function; too small to be a clone.
+                        in_function = false;
+                    }
                 }
                 continue;
             }
@@ -210,6 +350,10 @@ impl Analyzer {
 
             let ended = match lang {
                 "ruby" => process_ruby_line(trimmed, &mut end_depth),
+                "lua" => {
+                    end_depth += lua_block_delta(trimmed);
+                    end_depth <= 0
+                }
                 _ => {
                     brace_depth +=
                         line.matches('{').count() as i32 - line.matches('}').count() as i32;
@@ -226,7 +370,7 @@ impl Analyzer {
             }
             in_function = false;
             func_lines.clear();
-            if lang == "ruby" {
+            if lang == "ruby" || lang == "lua" {
                 end_depth = 0;
             }
         }
@@ -265,20 +409,25 @@ impl Analyzer {
         // identical token sequences, enabling proper similarity detection.
         let normalized_tokens = normalize_tokens_fresh(&tokens, &self.config);
 
-        // Check minimum token count
-        if normalized_tokens.len() < self.config.min_tokens {
+        // Check minimum token and line counts
+        if normalized_tokens.len() < self.config.min_tokens_for(lang)
+            || normalized.lines().count() < self.config.min_lines_for(lang)
+        {
             return None;
         }
 
         Some(CodeFragment {
             id: 0, // Set later
             file: path.to_string(),
+            lang: detect_language(path),
             start_line: (start_line + 1) as u32,
             end_line: (end_line + 1) as u32,
             content: normalized_tokens.join(" "),
+            token_count: normalized_tokens.len(),
             tokens: normalized_tokens,
             normalized_hash: 0, // Set later
             signature: None,    // Set later
+            shape_hash: None,   // Set by assign_shape_hashes
         })
     }
 
@@ -391,14 +540,19 @@ impl Analyzer {
                 continue;
             }
 
-            // Calculate actual similarity
+            // Calculate actual similarity against the stricter language threshold
+            let threshold = self
+                .config
+                .similarity_for(frag_a.lang)
+                .max(self.config.similarity_for(frag_b.lang));
             if let (Some(sig_a), Some(sig_b)) = (&frag_a.signature, &frag_b.signature) {
                 let similarity = sig_a.jaccard_similarity(sig_b);
-                if similarity >= self.config.similarity_threshold {
+                if similarity >= threshold {
                     pairs.push(ClonePair {
                         idx_a,
                         idx_b,
                         similarity,
+                        normalization: Normalization::Tokens,
                     });
                 }
             }
@@ -407,6 +561,51 @@ impl Analyzer {
         pairs
     }
 
+    /// Pair fragments with equal syntax tree shapes that token similarity
+    /// missed, such as copies run through different formatters.
+    fn find_shape_pairs(&self, fragments: &[CodeFragment], found: &[ClonePair]) -> Vec<ClonePair> {
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (idx, fragment) in fragments.iter().enumerate() {
+            if let Some(hash) = fragment.shape_hash {
+                buckets.entry(hash).or_default().push(idx);
+            }
+        }
+        let known: HashSet<(usize, usize)> = found.iter().map(|p| (p.idx_a, p.idx_b)).collect();
+
+        let mut pairs = Vec::new();
+        for bucket in buckets.values() {
+            // Shapes shared this widely are boilerplate, not copy-paste.
+            if bucket.len() < 2 || bucket.len() > MAX_WINDOW_REPEATS {
+                continue;
+            }
+            for (i, &idx_a) in bucket.iter().enumerate() {
+                for &idx_b in &bucket[i + 1..] {
+                    let (frag_a, frag_b) = (&fragments[idx_a], &fragments[idx_b]);
+                    if known.contains(&(idx_a, idx_b))
+                        || known.contains(&(idx_b, idx_a))
+                        || (frag_a.file == frag_b.file
+                            && frag_a.start_line <= frag_b.end_line
+                            && frag_b.start_line <= frag_a.end_line)
+                    {
+                        continue;
+                    }
+                    let similarity = match (&frag_a.signature, &frag_b.signature) {
+                        (Some(sig_a), Some(sig_b)) => sig_a.jaccard_similarity(sig_b),
+                        _ => 0.0,
+                    };
+                    // Equal shapes differ at most in names and literals.
+                    pairs.push(ClonePair {
+                        idx_a,
+                        idx_b,
+                        similarity: similarity.max(TYPE2_SIMILARITY),
+                        normalization: Normalization::AstShape,
+                    });
+                }
+            }
+        }
+        pairs
+    }
+
     /// Group clone pairs using Union-Find algorithm.
     fn group_clones(&self, fragments: &[CodeFragment], pairs: &[ClonePair]) -> Vec<CloneGroup> {
         if pairs.is_empty() {
@@ -444,14 +643,14 @@ impl Analyzer {
         }
 
         // Build similarity map
-        let mut similarity_map: HashMap<(usize, usize), f64> = HashMap::new();
+        let mut similarity_map: HashMap<(usize, usize), &ClonePair> = HashMap::new();
         for pair in pairs {
             let key = if pair.idx_a < pair.idx_b {
                 (pair.idx_a, pair.idx_b)
             } else {
                 (pair.idx_b, pair.idx_a)
             };
-            similarity_map.insert(key, pair.similarity);
+            similarity_map.insert(key, pair);
         }
 
         // Convert to CloneGroup
@@ -469,6 +668,7 @@ impl Analyzer {
             let mut total_tokens = 0;
             let mut similarity_sum = 0.0;
             let mut similarity_count = 0;
+            let mut normalization = Normalization::Tokens;
 
             for &idx in member_indices {
                 let frag = &fragments[idx];
@@ -480,9 +680,10 @@ impl Analyzer {
                     lines,
                     normalized_hash: frag.normalized_hash,
                     similarity: 1.0,
+                    changed: None,
                 });
                 total_lines += lines;
-                total_tokens += frag.tokens.len();
+                total_tokens += frag.token_count;
             }
 
             // Calculate average similarity
@@ -493,9 +694,12 @@ impl Analyzer {
                     } else {
                         (member_indices[j], member_indices[i])
                     };
-                    if let Some(&sim) = similarity_map.get(&key) {
-                        similarity_sum += sim;
+                    if let Some(pair) = similarity_map.get(&key) {
+                        similarity_sum += pair.similarity;
                         similarity_count += 1;
+                        if pair.normalization == Normalization::AstShape {
+                            normalization = Normalization::AstShape;
+                        }
                     }
                 }
             }
@@ -509,6 +713,7 @@ impl Analyzer {
             groups.push(CloneGroup {
                 id: group_id,
                 clone_type: CloneType::from_similarity(avg_similarity),
+                normalization,
                 instances,
                 total_lines,
                 total_tokens,
@@ -519,6 +724,206 @@ impl Analyzer {
         groups
     }
 
+    /// Find copy-pasted ranges within a single file.
+    ///
+    /// Hashes the normalized tokens of every window of `min_lines`
+    /// significant lines, then extends matching window pairs into maximal
+    /// ranges that do not overlap each other.
+    fn find_intra_file_clones(&self, path: &str, content: &[u8]) -> Vec<IntraFileClone> {
+        let Ok(content) = std::str::from_utf8(content) else {
+            return Vec::new();
+        };
+        let lang = detect_language(path);
+        let window = self.config.min_lines_for(lang).max(2);
+        let min_tokens = self.config.min_tokens_for(lang);
+
+        // (0-indexed line, trimmed text, raw tokens) for significant lines
+        let significant: Vec<(usize, &str, Vec<String>)> = content
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || (self.config.ignore_comments && is_comment(trimmed, lang))
+                {
+                    return None;
+                }
+                Some((i, trimmed, tokenize(trimmed)))
+            })
+            .collect();
+        if significant.len() < window * 2 {
+            return Vec::new();
+        }
+
+        let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+        for start in 0..=significant.len() - window {
+            let tokens: Vec<String> = significant[start..start + window]
+                .iter()
+                .flat_map(|(_, _, tokens)| tokens.iter().cloned())
+                .collect();
+            let normalized = normalize_tokens_fresh(&tokens, &self.config);
+            if normalized.len() < min_tokens {
+                continue;
+            }
+            by_hash
+                .entry(self.compute_normalized_hash(&normalized))
+                .or_default()
+                .push(start);
+        }
+
+        let mut matches: HashSet<(usize, usize)> = HashSet::new();
+        for starts in by_hash.values() {
+            if starts.len() < 2 || starts.len() > MAX_WINDOW_REPEATS {
+                continue;
+            }
+            for (k, &a) in starts.iter().enumerate() {
+                for &b in &starts[k + 1..] {
+                    if b >= a + window {
+                        matches.insert((a, b));
+                    }
+                }
+            }
+        }
+
+        let mut clones = Vec::new();
+        for &(a, b) in &matches {
+            // Only start runs at their first window pair
+            if a > 0 && matches.contains(&(a - 1, b - 1)) {
+                continue;
+            }
+            let mut len = 1;
+            while matches.contains(&(a + len, b + len)) && a + len + window <= b {
+                len += 1;
+            }
+            let span = len + window - 1;
+            let exact = (0..span).all(|k| significant[a + k].1 == significant[b + k].1);
+            let tokens = significant[a..a + span]
+                .iter()
+                .map(|(_, _, tokens)| tokens.len())
+                .sum();
+            let line = |idx: usize| significant[idx].0 as u32 + 1;
+            clones.push(IntraFileClone {
+                file: path.to_string(),
+                range_a: (line(a), line(a + span - 1)),
+                range_b: (line(b), line(b + span - 1)),
+                exact,
+                tokens,
+            });
+        }
+
+        clones.sort_by_key(|c| (c.range_a, c.range_b));
+        clones
+    }
+
+    /// Group intra-file clone pairs that share a range, skipping pairs
+    /// already covered by a function-level clone group.
+    fn group_intra_file_clones(
+        &self,
+        clones: &[IntraFileClone],
+        existing: &[CloneGroup],
+        first_id: u64,
+    ) -> Vec<CloneGroup> {
+        let covered = |file: &str, (start, end): (u32, u32), group: &CloneGroup| {
+            group
+                .instances
+                .iter()
+                .any(|i| i.file == file && i.start_line <= start && end <= i.end_line)
+        };
+        let clones: Vec<&IntraFileClone> = clones
+            .iter()
+            .filter(|c| {
+                !existing
+                    .iter()
+                    .any(|g| covered(&c.file, c.range_a, g) && covered(&c.file, c.range_b, g))
+            })
+            .collect();
+
+        // Union ranges that appear in the same pair (three copies -> one group)
+        let mut range_ids: HashMap<(&str, (u32, u32)), usize> = HashMap::new();
+        let mut ranges: Vec<(&str, (u32, u32), usize)> = Vec::new();
+        let mut edges = Vec::with_capacity(clones.len());
+        for c in &clones {
+            let mut ids = [0usize; 2];
+            for (id, range) in ids.iter_mut().zip([c.range_a, c.range_b]) {
+                *id = *range_ids
+                    .entry((c.file.as_str(), range))
+                    .or_insert_with(|| {
+                        ranges.push((c.file.as_str(), range, c.tokens));
+                        ranges.len() - 1
+                    });
+            }
+            edges.push((ids[0], ids[1], c.exact));
+        }
+        let mut parent: Vec<usize> = (0..ranges.len()).collect();
+
+        fn find(parent: &mut [usize], x: usize) -> usize {
+            if parent[x] != x {
+                parent[x] = find(parent, parent[x]);
+            }
+            parent[x]
+        }
+        for &(a, b, _) in &edges {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            if ra != rb {
+                parent[ra] = rb;
+            }
+        }
+
+        let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..ranges.len() {
+            let root = find(&mut parent, i);
+            members.entry(root).or_default().push(i);
+        }
+        let mut inexact: HashSet<usize> = HashSet::new();
+        for &(a, _, exact) in &edges {
+            if !exact {
+                inexact.insert(find(&mut parent, a));
+            }
+        }
+
+        let mut roots: Vec<usize> = members.keys().copied().collect();
+        roots.sort_by_key(|&root| {
+            let (file, range, _) = ranges[members[&root][0]];
+            (file, range)
+        });
+
+        roots
+            .into_iter()
+            .enumerate()
+            .map(|(n, root)| {
+                let mut idxs = members[&root].clone();
+                idxs.sort_by_key(|&i| ranges[i].1);
+                let instances: Vec<CloneInstance> = idxs
+                    .iter()
+                    .map(|&i| {
+                        let (file, (start, end), _) = ranges[i];
+                        CloneInstance {
+                            file: file.to_string(),
+                            start_line: start,
+                            end_line: end,
+                            lines: (end - start + 1) as usize,
+                            normalized_hash: 0,
+                            similarity: 1.0,
+                            changed: None,
+                        }
+                    })
+                    .collect();
+                CloneGroup {
+                    id: first_id + n as u64,
+                    clone_type: if inexact.contains(&root) {
+                        CloneType::Type2
+                    } else {
+                        CloneType::Type1
+                    },
+                    normalization: Normalization::Tokens,
+                    total_lines: instances.iter().map(|i| i.lines).sum(),
+                    total_tokens: idxs.iter().map(|&i| ranges[i].2).sum(),
+                    instances,
+                    average_similarity: 1.0,
+                }
+            })
+            .collect()
+    }
+
     /// Compute duplication hotspots.
     fn compute_hotspots(&self, groups: &[CloneGroup]) -> Vec<Hotspot> {
         let mut file_stats: HashMap<String, (usize, HashSet<u64>)> = HashMap::new();
@@ -570,30 +975,66 @@ impl AnalyzerTrait for Analyzer {
     }
 
     fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
-        // Extract fragments from all files in parallel
+        if !self.configured {
+            return Analyzer {
+                config: Config::from(&ctx.config.duplicates),
+                configured: true,
+                max_file_size: self.max_file_size,
+                state_dir: self.state_dir.clone(),
+            }
+            .analyze(ctx);
+        }
+
+        // Fragments of files untouched since a previous run, keyed by path
+        let previous: HashMap<String, FileFragments> = ctx
+            .warm_start
+            .as_ref()
+            .and_then(|warm| warm.previous_state::<FragmentState>(self.name()))
+            .map(|state| state.files)
+            .unwrap_or_default();
+        let reusable = |path: &Path, path_str: &str| {
+            let warm = ctx.warm_start.as_ref()?;
+            if warm.changed(path) {
+                return None;
+            }
+            let saved = previous.get(path_str)?;
+            Some((saved.restore(path_str), saved.intra.clone()))
+        };
+
+        // Extract fragments (and intra-file clones) from all files in parallel
         let max_file_size = self.max_file_size;
-        let files_scanned = std::sync::atomic::AtomicUsize::new(0);
-        let mut all_fragments: Vec<CodeFragment> = ctx
+        let per_file: Vec<(String, Vec<CodeFragment>, Vec<IntraFileClone>)> = ctx
             .files
             .files()
             .par_iter()
             .filter_map(|path| {
+                let path_str = path.to_string_lossy().into_owned();
+                if let Some((fragments, intra)) = reusable(path, &path_str) {
+                    return Some((path_str, fragments, intra));
+                }
                 let content = ctx.read_file(path).ok()?;
                 if max_file_size > 0 && content.len() > max_file_size {
                     return None;
                 }
-                files_scanned.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                let path_str = path.to_string_lossy();
                 let fragments = self.extract_fragments(&path_str, &content);
-                if fragments.is_empty() {
-                    None
+                let intra = if self.config.intra_file {
+                    self.find_intra_file_clones(&path_str, &content)
                 } else {
-                    Some(fragments)
-                }
+                    Vec::new()
+                };
+                Some((path_str, fragments, intra))
             })
-            .flatten()
             .collect();
-        let files_scanned = files_scanned.into_inner();
+        let files_scanned = per_file.len();
+        let mut scanned: Vec<String> = Vec::with_capacity(files_scanned);
+        let mut all_fragments: Vec<CodeFragment> = Vec::new();
+        let mut intra_file_clones: Vec<IntraFileClone> = Vec::new();
+        for (path, fragments, intra) in per_file {
+            scanned.push(path);
+            all_fragments.extend(fragments);
+            intra_file_clones.extend(intra);
+        }
+        intra_file_clones.sort_by(|a, b| a.file.cmp(&b.file).then(a.range_a.cmp(&b.range_a)));
 
         // Sort by file path then line number for deterministic output
         all_fragments.sort_by(|a, b| a.file.cmp(&b.file).then(a.start_line.cmp(&b.start_line)));
@@ -605,17 +1046,41 @@ impl AnalyzerTrait for Analyzer {
             total_lines += (fragment.end_line - fragment.start_line + 1) as usize;
         }
 
-        // Compute MinHash signatures in parallel
-        all_fragments.par_iter_mut().for_each(|frag| {
-            frag.signature = Some(self.compute_minhash(&frag.tokens));
-            frag.normalized_hash = self.compute_normalized_hash(&frag.tokens);
-        });
+        // Compute MinHash signatures in parallel; restored fragments have theirs
+        all_fragments
+            .par_iter_mut()
+            .filter(|frag| frag.signature.is_none())
+            .for_each(|frag| {
+                frag.signature = Some(self.compute_minhash(&frag.tokens));
+                frag.normalized_hash = self.compute_normalized_hash(&frag.tokens);
+            });
 
-        // Find clone pairs using LSH
-        let clone_pairs = self.find_clone_pairs_lsh(&all_fragments);
+        if let Some(dir) = &self.state_dir {
+            let state = FragmentState::new(&scanned, &all_fragments, &intra_file_clones);
+            if let Err(e) = save_state(dir, self.name(), &state) {
+                tracing::warn!("Failed to save duplicates state: {e}");
+            }
+        }
+
+        // Find clone pairs using LSH, then by syntax tree shape
+        let mut clone_pairs = self.find_clone_pairs_lsh(&all_fragments);
+        if self.config.structural {
+            let shape_pairs = self.find_shape_pairs(&all_fragments, &clone_pairs);
+            clone_pairs.extend(shape_pairs);
+        }
 
         // Group clones using Union-Find
-        let groups = self.group_clones(&all_fragments, &clone_pairs);
+        let mut groups = self.group_clones(&all_fragments, &clone_pairs);
+        let intra_groups =
+            self.group_intra_file_clones(&intra_file_clones, &groups, groups.len() as u64 + 1);
+        groups.extend(intra_groups);
+
+        if let Some(changed) = &ctx.changed_lines {
+            for inst in groups.iter_mut().flat_map(|g| g.instances.iter_mut()) {
+                inst.changed =
+                    Some(changed.touches(Path::new(&inst.file), inst.start_line, inst.end_line));
+            }
+        }
 
         // Build summary
         let mut summary = AnalysisSummary {
@@ -646,6 +1111,7 @@ impl AnalyzerTrait for Analyzer {
 
                     let clone = Clone {
                         clone_type: group.clone_type,
+                        normalization: group.normalization,
                         similarity: group.average_similarity,
                         file_a: inst_a.file.clone(),
                         file_b: inst_b.file.clone(),
@@ -656,6 +1122,7 @@ impl AnalyzerTrait for Analyzer {
                         lines_a: inst_a.lines,
                         lines_b: inst_b.lines,
                         group_id: group.id,
+                        changed: inst_a.changed.zip(inst_b.changed).map(|(a, b)| a || b),
                     };
 
                     summary.add_clone_stats(&clone);
@@ -689,7 +1156,7 @@ impl AnalyzerTrait for Analyzer {
             groups,
             summary,
             total_files_scanned: files_scanned,
-            min_lines: self.config.min_tokens / 8,
+            min_lines: self.config.min_lines,
             threshold: self.config.similarity_threshold,
         })
     }
@@ -699,13 +1166,107 @@ impl AnalyzerTrait for Analyzer {
 struct CodeFragment {
     id: u64,
     file: String,
+    lang: &'static str,
     start_line: u32,
     end_line: u32,
     #[allow(dead_code)]
     content: String,
+    /// Normalized tokens; empty for fragments restored from a warm start.
     tokens: Vec<String>,
+    token_count: usize,
     normalized_hash: u64,
     signature: Option<MinHashSignature>,
+    /// Hash of the syntax tree shape; `None` when the file did not parse.
+    shape_hash: Option<u64>,
+}
+
+/// A pair of matching ranges (1-indexed, inclusive) within one file.
+#[derive(Clone, Serialize, Deserialize)]
+struct IntraFileClone {
+    file: String,
+    range_a: (u32, u32),
+    range_b: (u32, u32),
+    /// Whether the trimmed source text is identical (Type-1).
+    exact: bool,
+    tokens: usize,
+}
+
+/// Fragment hashes and intra-file clones of each scanned file, saved for a
+/// later warm start.
+#[derive(Default, Serialize, Deserialize)]
+struct FragmentState {
+    files: HashMap<String, FileFragments>,
+}
+
+impl FragmentState {
+    fn new(scanned: &[String], fragments: &[CodeFragment], intra: &[IntraFileClone]) -> Self {
+        let mut files: HashMap<String, FileFragments> = scanned
+            .iter()
+            .map(|path| (path.clone(), FileFragments::default()))
+            .collect();
+        for frag in fragments {
+            if let Some(file) = files.get_mut(&frag.file) {
+                file.fragments.push(FragmentHashes {
+                    start_line: frag.start_line,
+                    end_line: frag.end_line,
+                    tokens: frag.token_count,
+                    normalized_hash: frag.normalized_hash,
+                    signature: frag
+                        .signature
+                        .as_ref()
+                        .map(|sig| sig.values.clone())
+                        .unwrap_or_default(),
+                    shape_hash: frag.shape_hash,
+                });
+            }
+        }
+        for clone in intra {
+            if let Some(file) = files.get_mut(&clone.file) {
+                file.intra.push(clone.clone());
+            }
+        }
+        Self { files }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct FileFragments {
+    fragments: Vec<FragmentHashes>,
+    intra: Vec<IntraFileClone>,
+}
+
+impl FileFragments {
+    /// The saved fragments of `path`, ready for pairing.
+    fn restore(&self, path: &str) -> Vec<CodeFragment> {
+        self.fragments
+            .iter()
+            .map(|saved| CodeFragment {
+                id: 0, // Set later
+                file: path.to_string(),
+                lang: detect_language(path),
+                start_line: saved.start_line,
+                end_line: saved.end_line,
+                content: String::new(),
+                tokens: Vec::new(),
+                token_count: saved.tokens,
+                normalized_hash: saved.normalized_hash,
+                signature: Some(MinHashSignature {
+                    values: saved.signature.clone(),
+                }),
+                shape_hash: saved.shape_hash,
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FragmentHashes {
+    start_line: u32,
+    end_line: u32,
+    tokens: usize,
+    normalized_hash: u64,
+    signature: Vec<u64>,
+    shape_hash: Option<u64>,
 }
 
 /// Internal clone pair representation.
@@ -713,6 +1274,7 @@ struct ClonePair {
     idx_a: usize,
     idx_b: usize,
     similarity: f64,
+    normalization: Normalization,
 }
 
 /// MinHash signature for similarity estimation.
@@ -754,6 +1316,8 @@ pub struct Analysis {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Clone {
     pub clone_type: CloneType,
+    #[serde(default)]
+    pub normalization: Normalization,
     pub similarity: f64,
     pub file_a: String,
     pub file_b: String,
@@ -765,6 +1329,9 @@ pub struct Clone {
     pub lines_b: usize,
     #[serde(skip_serializing_if = "is_zero_u64")]
     pub group_id: u64,
+    /// Whether either side overlaps lines changed since `--base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
 }
 
 fn is_zero_u64(v: &u64) -> bool {
@@ -775,6 +1342,8 @@ fn is_zero_u64(v: &u64) -> bool {
 pub struct CloneGroup {
     pub id: u64,
     pub clone_type: CloneType,
+    #[serde(default)]
+    pub normalization: Normalization,
     pub instances: Vec<CloneInstance>,
     pub total_lines: usize,
     pub total_tokens: usize,
@@ -789,6 +1358,9 @@ pub struct CloneInstance {
     pub lines: usize,
     pub normalized_hash: u64,
     pub similarity: f64,
+    /// Whether the instance overlaps lines changed since `--base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -801,6 +1373,9 @@ pub struct AnalysisSummary {
     pub duplicated_lines: usize,
     pub total_lines: usize,
     pub duplication_ratio: f64,
+    /// Clones whose two instances are in the same file.
+    #[serde(default)]
+    pub intra_file_clones: usize,
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub file_occurrences: HashMap<String, usize>,
     pub avg_similarity: f64,
@@ -824,6 +1399,8 @@ impl AnalysisSummary {
                 .file_occurrences
                 .entry(clone.file_b.clone())
                 .or_default() += 1;
+        } else {
+            self.intra_file_clones += 1;
         }
 
         match clone.clone_type {
@@ -871,6 +1448,10 @@ fn detect_language(path: &str) -> &'static str {
         "ruby"
     } else if path_lower.ends_with(".php") {
         "php"
+    } else if path_lower.ends_with(".lua") {
+        "lua"
+    } else if path_lower.ends_with(".zig") {
+        "zig"
     } else {
         "unknown"
     }
@@ -900,6 +1481,13 @@ fn is_function_start(line: &str, lang: &str) -> bool {
                 && line.contains('(')
         }
         "php" => line.contains("function ") && line.contains('('),
+        "lua" => {
+            (line.starts_with("function ")
+                || line.starts_with("local function ")
+                || line.contains("= function"))
+                && line.contains('(')
+        }
+        "zig" => line.contains("fn ") && line.contains('('),
         _ => false,
     }
 }
@@ -934,6 +1522,21 @@ fn process_ruby_line(trimmed: &str, end_depth: &mut i32) -> bool {
     *end_depth <= 0
 }
 
+/// Net change in Lua block nesting contributed by a line.
+///
+/// `function`, `if`, `do` and `repeat` open a block; `end` and `until` close one.
+/// `for`/`while` are not counted because their body is opened by `do`.
+fn lua_block_delta(trimmed: &str) -> i32 {
+    let code = trimmed.split("--").next().unwrap_or("");
+    code.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(|word| match word {
+            "function" | "if" | "do" | "repeat" => 1,
+            "end" | "until" => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
 /// Check if a line is a comment, using language-specific comment prefixes.
 ///
 /// The `#` character is only a comment prefix in languages that use it
@@ -952,6 +1555,10 @@ fn is_comment(line: &str, lang: &str) -> bool {
         return matches!(lang, "python" | "ruby" | "bash" | "php");
     }
 
+    if line.starts_with("--") {
+        return lang == "lua";
+    }
+
     false
 }
 
@@ -1343,6 +1950,65 @@ fn hash_band(values: &[u64], seed: u64) -> u64 {
     h
 }
 
+/// Hash the syntax tree shape of each fragment's line range.
+fn assign_shape_hashes(path: &str, content: &[u8], fragments: &mut [CodeFragment]) {
+    let path = Path::new(path);
+    let Some(lang) = Language::detect(path) else {
+        return;
+    };
+    let Ok(result) = Parser::new().parse(content, lang, path) else {
+        return;
+    };
+    for fragment in fragments {
+        let rows = (fragment.start_line - 1) as usize..=(fragment.end_line - 1) as usize;
+        let mut shape = Vec::new();
+        collect_shape(result.root_node(), &rows, &mut shape);
+        if shape.is_empty() {
+            continue;
+        }
+        let mut bytes = format!("{lang:?}").into_bytes();
+        bytes.extend(shape.iter().flat_map(|kind| kind.to_le_bytes()));
+        fragment.shape_hash = Some(xxhash_rust::xxh3::xxh3_64(&bytes));
+    }
+}
+
+/// Append the shapes of the largest subtrees of `node` lying within `rows`.
+fn collect_shape(node: tree_sitter::Node<'_>, rows: &RangeInclusive<usize>, shape: &mut Vec<u16>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let (start, end) = (child.start_position().row, child.end_position().row);
+        if end < *rows.start() || start > *rows.end() {
+            continue;
+        }
+        if rows.contains(&start) && rows.contains(&end) {
+            push_shape(child, shape);
+        } else {
+            collect_shape(child, rows, shape);
+        }
+    }
+}
+
+/// Pre-order node kinds of a subtree, bracketed to keep the nesting.
+/// Comments, punctuation and the contents of literals are left out; node
+/// text never enters, so renamed identifiers keep the same shape.
+fn push_shape(node: tree_sitter::Node<'_>, shape: &mut Vec<u16>) {
+    if node.is_extra()
+        || (!node.is_named()
+            && matches!(node.kind(), "," | ";" | "(" | ")" | "{" | "}" | "[" | "]"))
+    {
+        return;
+    }
+    shape.push(node.kind_id());
+    let kind = node.kind();
+    if !(kind.contains("string") || kind.contains("literal")) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            push_shape(child, shape);
+        }
+    }
+    shape.push(u16::MAX);
+}
+
 /// Calculate percentile from sorted values.
 fn percentile(sorted: &[f64], p: f64) -> f64 {
     if sorted.is_empty() {
@@ -1378,6 +2044,179 @@ mod tests {
         assert!((analyzer.config.similarity_threshold - 0.8).abs() < 0.001);
     }
 
+    #[test]
+    fn test_config_from_settings_with_language_overrides() {
+        let mut settings = DuplicatesConfig {
+            min_tokens: 60,
+            min_similarity: 0.8,
+            min_lines: 8,
+            ..Default::default()
+        };
+        settings.languages.insert(
+            "Python".to_string(),
+            DuplicatesLanguageConfig {
+                min_tokens: Some(30),
+                min_similarity: None,
+                min_lines: Some(4),
+            },
+        );
+
+        let config = Config::from(&settings);
+        assert_eq!(config.min_tokens_for("python"), 30);
+        assert_eq!(config.min_lines_for("python"), 4);
+        assert!((config.similarity_for("python") - 0.8).abs() < 0.001);
+        assert_eq!(config.min_tokens_for("go"), 60);
+        assert_eq!(config.min_lines_for("go"), 8);
+        assert_eq!(config.num_hash_functions, 200);
+    }
+
+    const INTRA_FILE_PY: &str = r#"def load_users(db):
+    rows = db.query("users")
+    result = []
+    for row in rows:
+        if row.active:
+            result.append(row.name.strip().lower())
+    return result
+
+def load_orders(db, limit):
+    log("loading orders", limit)
+    if limit <= 0:
+        raise ValueError(limit)
+    rows = db.query("orders")
+    result = []
+    for row in rows:
+        if row.active:
+            result.append(row.name.strip().lower())
+    return result
+"#;
+
+    #[test]
+    fn test_find_intra_file_clones_extends_to_maximal_ranges() {
+        let analyzer = Analyzer::new().with_min_tokens(20).with_min_lines(5);
+        let clones = analyzer.find_intra_file_clones("loaders.py", INTRA_FILE_PY.as_bytes());
+
+        assert_eq!(clones.len(), 1);
+        assert_eq!(clones[0].range_a, (2, 7));
+        assert_eq!(clones[0].range_b, (13, 18));
+        // The query literals differ, so this is a Type-2 clone.
+        assert!(!clones[0].exact);
+    }
+
+    #[test]
+    fn test_find_intra_file_clones_exact_copy() {
+        let block = "    total = 0\n    for item in items:\n        total += item.price * item.qty\n    tax = total * rate\n    return total + tax\n";
+        let content =
+            format!("def a(items, rate):\n{block}\ndef b(items, rate):\n    check(items)\n{block}");
+        let analyzer = Analyzer::new().with_min_tokens(15).with_min_lines(4);
+        let clones = analyzer.find_intra_file_clones("cart.py", content.as_bytes());
+
+        assert_eq!(clones.len(), 1);
+        assert_eq!(clones[0].range_a, (2, 6));
+        assert_eq!(clones[0].range_b, (10, 14));
+        assert!(clones[0].exact);
+    }
+
+    #[test]
+    fn test_find_intra_file_clones_ignores_short_windows() {
+        let analyzer = Analyzer::new().with_min_tokens(200).with_min_lines(5);
+        let clones = analyzer.find_intra_file_clones("loaders.py", INTRA_FILE_PY.as_bytes());
+        assert!(clones.is_empty());
+    }
+
+    #[test]
+    fn test_warm_start_reuses_fragments_of_untouched_files() {
+        use crate::core::warm_start::state_path;
+        use crate::core::WarmStart;
+        use crate::git::GitRepo;
+        use crate::manifest::Manifest;
+        use crate::test_support::{git, init_repo};
+        use std::sync::Arc;
+
+        let repo = TempDir::new().unwrap();
+        init_repo(repo.path());
+        let loader = INTRA_FILE_PY.split("\n\n").next().unwrap();
+        for name in ["a.py", "b.py", "c.py"] {
+            fs::write(repo.path().join(name), format!("{loader}\n")).unwrap();
+        }
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-q", "-m", "init"]);
+        let mut config = CoreConfig::default();
+        config.duplicates.min_tokens = 20;
+        config.duplicates.min_lines = 5;
+        config.duplicates.intra_file = false;
+        let file_set = FileSet::from_path(repo.path(), &config).unwrap();
+        let files_in_groups = |analysis: &Analysis| {
+            let mut files: Vec<String> = analysis
+                .groups
+                .iter()
+                .flat_map(|g| g.instances.iter().map(|i| i.file.clone()))
+                .collect();
+            files.sort();
+            files
+        };
+
+        // Save a previous run, then forget a.py's fragments to tell reuse apart
+        let data = TempDir::new().unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(repo.path()));
+        let cold = Analyzer::new()
+            .with_state_dir(data.path())
+            .analyze(&ctx)
+            .unwrap();
+        assert_eq!(files_in_groups(&cold), vec!["a.py", "b.py", "c.py"]);
+        let mut state: FragmentState =
+            serde_json::from_slice(&fs::read(state_path(data.path(), "duplicates")).unwrap())
+                .unwrap();
+        assert_eq!(state.files.len(), 3);
+        state.files.get_mut("a.py").unwrap().fragments.clear();
+        save_state(data.path(), "duplicates", &state).unwrap();
+        let manifest = Manifest::build(repo.path(), &file_set, &config).unwrap();
+        let metadata = serde_json::json!({ "since": "1y", "manifest": manifest });
+        fs::write(data.path().join("metadata.json"), metadata.to_string()).unwrap();
+
+        fs::write(repo.path().join("c.py"), format!("{loader}\n\n# copied\n")).unwrap();
+        git(repo.path(), &["commit", "-q", "-am", "touch c"]);
+        let current = Manifest::build(repo.path(), &file_set, &config).unwrap();
+        let git_repo = GitRepo::open(repo.path()).unwrap();
+        let warm = WarmStart::load(data.path(), &git_repo, &current, "1y").unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(repo.path()))
+            .with_warm_start(Arc::new(warm));
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+        assert_eq!(files_in_groups(&analysis), vec!["b.py", "c.py"]);
+        assert_eq!(analysis.total_files_scanned, 3);
+    }
+
+    #[test]
+    fn test_analyze_reports_intra_file_clones_from_context_config() {
+        let tmp_dir = TempDir::new().unwrap();
+        fs::write(tmp_dir.path().join("loaders.py"), INTRA_FILE_PY).unwrap();
+
+        let mut config = CoreConfig::default();
+        config.duplicates.min_tokens = 20;
+        config.duplicates.min_lines = 5;
+        config.duplicates.min_similarity = 0.99;
+        let file_set = FileSet::from_path(tmp_dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(tmp_dir.path()));
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+        assert_eq!(analysis.min_lines, 5);
+        assert!((analysis.threshold - 0.99).abs() < 0.001);
+        assert_eq!(analysis.summary.intra_file_clones, 1);
+        let group = &analysis.groups[0];
+        assert_eq!(group.clone_type, CloneType::Type2);
+        let ranges: Vec<(u32, u32)> = group
+            .instances
+            .iter()
+            .map(|i| (i.start_line, i.end_line))
+            .collect();
+        assert_eq!(ranges, vec![(2, 7), (13, 18)]);
+
+        config.duplicates.intra_file = false;
+        let ctx = AnalysisContext::new(&file_set, &config, Some(tmp_dir.path()));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+        assert_eq!(analysis.summary.intra_file_clones, 0);
+    }
+
     #[test]
     fn test_clone_type_from_similarity() {
         assert_eq!(CloneType::from_similarity(0.99), CloneType::Type1);
@@ -1441,6 +2280,8 @@ mod tests {
         assert_eq!(detect_language("app.ts"), "typescript");
         assert_eq!(detect_language("app.js"), "javascript");
         assert_eq!(detect_language("app.rb"), "ruby");
+        assert_eq!(detect_language("init.lua"), "lua");
+        assert_eq!(detect_language("build.zig"), "zig");
         assert_eq!(detect_language("unknown.xyz"), "unknown");
     }
 
@@ -1561,6 +2402,40 @@ mod tests {
         assert!(!is_function_start("class MyClass", "ruby"));
     }
 
+    #[test]
+    fn test_is_function_start_lua_and_zig() {
+        assert!(is_function_start("function M.load(path)", "lua"));
+        assert!(is_function_start("local function helper(x)", "lua"));
+        assert!(is_function_start("M.run = function(opts)", "lua"));
+        assert!(!is_function_start("local x = 1", "lua"));
+        assert!(is_function_start("pub fn main() void {", "zig"));
+        assert!(!is_function_start("const std = @import(\"std\");", "zig"));
+    }
+
+    #[test]
+    fn test_lua_fragment_ends_at_matching_end() {
+        let analyzer = Analyzer::new().with_min_tokens(5);
+
+        let code = r#"local function load(path)
+  local f = io.open(path, "r")
+  if not f then
+    return nil
+  end
+  for line in f:lines() do
+    print(line)
+  end
+  f:close()
+end
+
+local x = 1
+"#;
+        let fragments = analyzer.extract_fragments("init.lua", code.as_bytes());
+
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].start_line, 1);
+        assert_eq!(fragments[0].end_line, 10);
+    }
+
     /// Test Ruby fragment extraction works correctly.
     #[test]
     fn test_ruby_fragment_extraction() {
@@ -1761,6 +2636,61 @@ func duplicate() int {
     }
 
     /// Test from Go: no clones should be found for different code
+    #[test]
+    fn test_analyze_matches_reformatted_copies_by_ast_shape() {
+        let tmp_dir = TempDir::new().unwrap();
+        fs::write(
+            tmp_dir.path().join("a.js"),
+            r#"function total(items) {
+  let sum = 0;
+  for (const item of items) {
+    if (item.price > 10) { sum += item.price * 2; }
+  }
+  return sum;
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            tmp_dir.path().join("b.js"),
+            // Prettier-style: no semicolons, block split over lines, renamed
+            r#"function computeTotal(entries) {
+  let acc = 0
+  for (const entry of entries) {
+    if (entry.cost > 99) {
+      acc += entry.cost * 3
+    }
+  }
+  return acc
+}
+"#,
+        )
+        .unwrap();
+
+        let config = CoreConfig::default();
+        let file_set = FileSet::from_path(tmp_dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(tmp_dir.path()));
+        let settings = Config {
+            min_tokens: 10,
+            similarity_threshold: 0.99,
+            intra_file: false,
+            ..Config::default()
+        };
+
+        let token_only = Analyzer::new().with_config(Config {
+            structural: false,
+            ..settings.clone()
+        });
+        assert!(token_only.analyze(&ctx).unwrap().clones.is_empty());
+
+        let analysis = Analyzer::new().with_config(settings).analyze(&ctx).unwrap();
+        assert_eq!(analysis.clones.len(), 1);
+        let clone = &analysis.clones[0];
+        assert_eq!(clone.normalization, Normalization::AstShape);
+        assert_eq!(clone.clone_type, CloneType::Type2);
+        assert_ne!(clone.file_a, clone.file_b);
+    }
+
     #[test]
     fn test_analyze_no_clones() {
         let tmp_dir = TempDir::new().unwrap();
@@ -1823,6 +2753,7 @@ func funcB() string {
 
         let clone = Clone {
             clone_type: CloneType::Type1,
+            normalization: Normalization::Tokens,
             similarity: 0.95,
             file_a: "a.go".to_string(),
             file_b: "b.go".to_string(),
@@ -1833,6 +2764,7 @@ func funcB() string {
             lines_a: 10,
             lines_b: 10,
             group_id: 1,
+            changed: None,
         };
 
         summary.add_clone_stats(&clone);
@@ -1906,6 +2838,10 @@ func funcB() string {
         assert!(is_comment("# comment", "bash"));
         assert!(is_comment("# comment", "php"));
 
+        // Double-dash is a comment only in Lua
+        assert!(is_comment("-- comment", "lua"));
+        assert!(!is_comment("-- not a comment", "c"));
+
         // Hash is NOT a comment in C-family or Rust
         assert!(!is_comment("#include <stdio.h>", "c"));
         assert!(!is_comment("#include <vector>", "cpp"));
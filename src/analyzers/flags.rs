@@ -3,8 +3,13 @@
 //! Detects feature flags from common providers and assesses staleness based on
 //! git history. Supports LaunchDarkly, Flipper, Split, generic patterns, and
 //! custom providers defined via tree-sitter queries in the configuration.
+//!
+//! Several providers can be detected in one run. Besides the providers listed
+//! in `[feature_flags] providers`, built-in providers whose SDK appears as a
+//! dependency in the project's manifests are enabled automatically
+//! (`auto_detect`), and the summary breaks flags down per provider.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -14,9 +19,11 @@ use serde::{Deserialize, Serialize};
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Query, QueryCursor};
 
+use crate::analyzers::mutation::Workspace;
+use crate::analyzers::prioritize::{prioritize, Prioritization, Prioritized};
 use crate::config::CustomProvider;
 use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
-use crate::parser::{get_tree_sitter_language, Parser};
+use crate::parser::get_tree_sitter_language;
 
 /// Feature flags analyzer configuration.
 #[derive(Debug, Clone)]
@@ -115,19 +122,107 @@ impl AnalyzerTrait for Analyzer {
             self.config.expected_ttl_days
         };
 
-        let providers = if !ctx.config.feature_flags.providers.is_empty() {
-            &ctx.config.feature_flags.providers
+        let mut providers = if !ctx.config.feature_flags.providers.is_empty() {
+            ctx.config.feature_flags.providers.clone()
+        } else {
+            self.config.providers.clone()
+        };
+        let detected = if ctx.config.feature_flags.auto_detect {
+            detect_providers(ctx.root)
         } else {
-            &self.config.providers
+            Vec::new()
         };
+        for provider in &detected {
+            if !providers.contains(provider) {
+                providers.push(provider.clone());
+            }
+        }
 
-        self.analyze_with_config(
+        let mut analysis = self.analyze_with_config(
             ctx,
             expected_ttl_days,
-            providers,
+            &providers,
             &ctx.config.feature_flags.custom_providers,
-        )
+        )?;
+        analysis.summary.detected_providers = detected;
+        Ok(analysis)
+    }
+}
+
+/// Dependency name prefixes of each built-in provider's SDKs, matched
+/// case-insensitively.
+const PROVIDER_PACKAGES: &[(&str, &str)] = &[
+    ("launchdarkly-", "launchdarkly"),
+    ("@launchdarkly/", "launchdarkly"),
+    ("github.com/launchdarkly/", "launchdarkly"),
+    ("@splitsoftware/", "split"),
+    ("github.com/splitio/", "split"),
+    ("unleash-", "unleash"),
+    ("@unleash/", "unleash"),
+    ("unleashclient", "unleash"),
+    ("github.com/unleash/", "unleash"),
+    ("flipper", "flipper"),
+];
+
+/// The built-in provider whose SDK `dependency` is, if any.
+fn provider_for_dependency(dependency: &str) -> Option<&'static str> {
+    let dependency = dependency.to_lowercase();
+    PROVIDER_PACKAGES
+        .iter()
+        .find(|(prefix, _)| dependency.starts_with(prefix))
+        .map(|(_, provider)| *provider)
+}
+
+/// Built-in providers whose SDK is a dependency of a package under `root`
+/// (`package.json`, `Cargo.toml`, `go.mod`), or of the `Gemfile` or
+/// `requirements.txt` at `root`. Sorted by name.
+fn detect_providers(root: &Path) -> Vec<String> {
+    let workspace = Workspace::discover(root);
+    let mut dependencies: Vec<String> = workspace
+        .packages()
+        .iter()
+        .flat_map(|package| package.dependencies.iter().cloned())
+        .collect();
+    if let Ok(gemfile) = std::fs::read_to_string(root.join("Gemfile")) {
+        dependencies.extend(gemfile_dependencies(&gemfile));
     }
+    if let Ok(requirements) = std::fs::read_to_string(root.join("requirements.txt")) {
+        dependencies.extend(requirements_dependencies(&requirements));
+    }
+
+    let providers: BTreeSet<&str> = dependencies
+        .iter()
+        .filter_map(|dependency| provider_for_dependency(dependency))
+        .collect();
+    providers.into_iter().map(str::to_string).collect()
+}
+
+/// Gem names from `gem "name"` lines of a Gemfile.
+fn gemfile_dependencies(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("gem "))
+        .filter_map(|rest| {
+            let rest = rest.trim_start();
+            let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+            rest[1..].split(quote).next().map(str::to_string)
+        })
+        .collect()
+}
+
+/// Package names from a pip requirements file, without version specifiers.
+fn requirements_dependencies(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .filter_map(|line| {
+            line.split(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .next()
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+        })
+        .collect()
 }
 
 /// Built-in provider definition using tree-sitter queries.
@@ -339,6 +434,8 @@ fn parse_language_name(name: &str) -> Option<Language> {
         "ruby" | "rb" => Some(Language::Ruby),
         "php" => Some(Language::Php),
         "bash" | "sh" | "shell" => Some(Language::Bash),
+        "lua" => Some(Language::Lua),
+        "zig" => Some(Language::Zig),
         _ => None,
     }
 }
@@ -411,18 +508,17 @@ impl Analyzer {
             .flat_map(|path| {
                 let mut file_refs = Vec::new();
 
-                let language = match Language::detect(path) {
+                let language = match ctx.files.language(path) {
                     Some(lang) => lang,
                     None => return file_refs,
                 };
 
-                // Read file via context (supports both filesystem and git tree)
-                let content = match ctx.read_file(path) {
-                    Ok(bytes) => match String::from_utf8(bytes) {
-                        Ok(s) => s,
-                        Err(_) => return file_refs,
-                    },
-                    Err(_) => return file_refs,
+                // Parse via context (shared parse cache, filesystem or git tree)
+                let Ok(parse_result) = ctx.parsed(path) else {
+                    return file_refs;
+                };
+                let Ok(content) = std::str::from_utf8(&parse_result.source) else {
+                    return file_refs;
                 };
 
                 let rel_path = path
@@ -431,13 +527,6 @@ impl Analyzer {
                     .to_string_lossy()
                     .to_string();
 
-                // Parse the file once with thread-local parser
-                let parser = Parser::new();
-                let parse_result = match parser.parse(content.as_bytes(), language, path) {
-                    Ok(result) => result,
-                    Err(_) => return file_refs,
-                };
-
                 // Apply built-in providers using pre-compiled queries
                 for builtin in builtin_providers.iter() {
                     if !providers.contains(&builtin.name.to_string()) {
@@ -596,6 +685,7 @@ impl Analyzer {
                 stale,
                 file_spread,
                 priority: FlagPriority::default(),
+                prioritization: None,
             });
         }
 
@@ -611,9 +701,9 @@ impl Analyzer {
         let file_complexity: HashMap<String, u32> = unique_flag_files
             .iter()
             .filter_map(|rel_path| {
-                let full_path = ctx.root.join(rel_path);
-                complexity_analyzer
-                    .analyze_file(&full_path)
+                let path = Path::new(rel_path);
+                ctx.read_file(path)
+                    .and_then(|content| complexity_analyzer.analyze_content(path, content))
                     .ok()
                     .map(|r| (rel_path.clone(), r.total_cyclomatic))
             })
@@ -659,6 +749,7 @@ impl Analyzer {
                     _ => b.age_days.cmp(&a.age_days),
                 })
         });
+        prioritize(ctx, &mut flags)?;
 
         let summary = calculate_summary(&flags);
 
@@ -674,19 +765,34 @@ impl Analyzer {
 /// Calculate summary statistics.
 fn calculate_summary(flags: &[FeatureFlag]) -> AnalysisSummary {
     let mut by_provider: HashMap<String, usize> = HashMap::new();
+    let mut providers: BTreeMap<String, ProviderSummary> = BTreeMap::new();
+    let mut provider_files: HashMap<&str, HashSet<&str>> = HashMap::new();
     let mut stale_flags = 0;
 
     for flag in flags {
         *by_provider.entry(flag.provider.clone()).or_insert(0) += 1;
+        let provider = providers.entry(flag.provider.clone()).or_default();
+        provider.flags += 1;
+        provider.references += flag.references.len();
+        provider_files
+            .entry(flag.provider.as_str())
+            .or_default()
+            .extend(flag.references.iter().map(|r| r.file.as_str()));
         if flag.stale {
+            provider.stale_flags += 1;
             stale_flags += 1;
         }
     }
+    for (name, provider) in &mut providers {
+        provider.files = provider_files.get(name.as_str()).map_or(0, HashSet::len);
+    }
 
     AnalysisSummary {
         total_flags: flags.len(),
         stale_flags,
         by_provider,
+        providers,
+        detected_providers: Vec::new(),
     }
 }
 
@@ -774,6 +880,23 @@ pub struct FeatureFlag {
     pub stale: bool,
     pub file_spread: usize,
     pub priority: FlagPriority,
+    /// Rank annotation when `prioritize_by` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prioritization: Option<Prioritization>,
+}
+
+impl Prioritized for FeatureFlag {
+    fn files(&self) -> Vec<&str> {
+        self.references.iter().map(|r| r.file.as_str()).collect()
+    }
+
+    fn prioritization(&self) -> Option<&Prioritization> {
+        self.prioritization.as_ref()
+    }
+
+    fn set_prioritization(&mut self, prioritization: Prioritization) {
+        self.prioritization = Some(prioritization);
+    }
 }
 
 /// Risk-based priority for a feature flag.
@@ -798,6 +921,23 @@ pub struct AnalysisSummary {
     pub total_flags: usize,
     pub stale_flags: usize,
     pub by_provider: HashMap<String, usize>,
+    /// Breakdown per provider, by name.
+    #[serde(default)]
+    pub providers: BTreeMap<String, ProviderSummary>,
+    /// Built-in providers enabled because their SDK is a dependency.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub detected_providers: Vec<String>,
+}
+
+/// Flags found for one provider.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProviderSummary {
+    pub flags: usize,
+    pub stale_flags: usize,
+    /// References to the provider's flags.
+    pub references: usize,
+    /// Distinct files referencing the provider's flags.
+    pub files: usize,
 }
 
 #[cfg(test)]
@@ -967,6 +1107,7 @@ mod tests {
                 stale: false,
                 file_spread: 1,
                 priority: FlagPriority::default(),
+                prioritization: None,
             },
             FeatureFlag {
                 key: "flag2".to_string(),
@@ -978,6 +1119,7 @@ mod tests {
                 stale: true,
                 file_spread: 2,
                 priority: FlagPriority::default(),
+                prioritization: None,
             },
             FeatureFlag {
                 key: "flag3".to_string(),
@@ -989,6 +1131,7 @@ mod tests {
                 stale: false,
                 file_spread: 1,
                 priority: FlagPriority::default(),
+                prioritization: None,
             },
         ];
 
@@ -997,6 +1140,92 @@ mod tests {
         assert_eq!(summary.stale_flags, 1);
         assert_eq!(summary.by_provider.get("launchdarkly"), Some(&2));
         assert_eq!(summary.by_provider.get("flipper"), Some(&1));
+        let launchdarkly = &summary.providers["launchdarkly"];
+        assert_eq!((launchdarkly.flags, launchdarkly.stale_flags), (2, 1));
+        assert_eq!(summary.providers["flipper"].stale_flags, 0);
+    }
+
+    #[test]
+    fn test_detect_providers_from_dependencies() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "app", "dependencies": {"@launchdarkly/node-server-sdk": "^9"},
+                "devDependencies": {"@splitsoftware/splitio": "^10", "react": "^18"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Gemfile"),
+            "source 'https://rubygems.org'\ngem 'flipper-active_record', '~> 1.0'\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("requirements.txt"),
+            "requests>=2\nUnleashClient==5.0 # flags\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            detect_providers(root),
+            vec!["flipper", "launchdarkly", "split", "unleash"]
+        );
+    }
+
+    #[test]
+    fn test_analyze_detects_multiple_providers() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "app", "dependencies": {"launchdarkly-node-server-sdk": "^7"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("app.js"),
+            "client.variation(\"new-checkout\", user, false);\n\
+             if (process.env.FEATURE_BETA) {}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("flags.rb"), "Flipper[:dark_mode]\n").unwrap();
+
+        let (file_set, mut config) = create_test_context(root);
+        config.feature_flags.providers = vec!["env".to_string(), "flipper".to_string()];
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        let analysis = Analyzer::new()
+            .with_git_history(false)
+            .analyze(&ctx)
+            .unwrap();
+
+        let mut found: Vec<(&str, &str)> = analysis
+            .flags
+            .iter()
+            .map(|f| (f.provider.as_str(), f.key.as_str()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("env", "FEATURE_BETA"),
+                ("flipper", "dark_mode"),
+                ("launchdarkly", "new-checkout"),
+            ]
+        );
+        assert_eq!(analysis.summary.detected_providers, vec!["launchdarkly"]);
+        assert_eq!(analysis.summary.providers.len(), 3);
+
+        config.feature_flags.auto_detect = false;
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        let analysis = Analyzer::new()
+            .with_git_history(false)
+            .analyze(&ctx)
+            .unwrap();
+        assert_eq!(analysis.flags.len(), 2);
+        assert!(analysis.summary.detected_providers.is_empty());
     }
 
     #[test]
@@ -1018,6 +1247,7 @@ mod tests {
                 score: 25.0,
                 max_complexity: 25,
             },
+            prioritization: None,
         };
 
         let json = serde_json::to_string(&flag).unwrap();
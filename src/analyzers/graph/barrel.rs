@@ -0,0 +1,647 @@
+//! Barrel file detection for the dependency graph.
+//!
+//! A barrel is a file that only re-exports other modules: an `index.ts` made
+//! of `export ... from` statements, or a Rust `mod.rs`/`lib.rs` holding
+//! nothing but `mod` declarations and `pub use` items. Every consumer imports
+//! the barrel instead of the defining file, so it shows up as a hub with a
+//! huge in-degree while contributing no code of its own.
+//!
+//! With `collapse_barrels` enabled the graph drops barrel nodes and redirects
+//! each import of a barrel to the files that define the imported names,
+//! following chains of barrels. When the imported names cannot be matched
+//! (namespace imports, unparsed syntax) the import is attributed to every
+//! file the barrel re-exports.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::Language;
+use crate::parser::{extract_imports, ImportNode, ParseResult};
+
+/// `(source, exposed)` name pairs of an import or re-export. A source of `*`
+/// stands for the whole module exposed under one name (`export * as ns`,
+/// `pub mod ns`).
+pub(super) type Bindings = Vec<(String, String)>;
+
+/// One re-export statement of a barrel file.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Reexport {
+    /// Import specifiers to resolve, in order of preference.
+    pub paths: Vec<String>,
+    /// Re-exported names; `None` re-exports everything (`export *`, `pub use a::*`).
+    pub names: Option<Bindings>,
+}
+
+fn is_js(lang: Language) -> bool {
+    matches!(
+        lang,
+        Language::TypeScript | Language::JavaScript | Language::Tsx | Language::Jsx
+    )
+}
+
+fn text<'a>(node: &tree_sitter::Node<'_>, source: &'a [u8]) -> Option<&'a str> {
+    node.utf8_text(source).ok()
+}
+
+fn unquote(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<String> {
+    Some(
+        text(node, source)?
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string(),
+    )
+}
+
+/// Re-exports of `result` if the file is a barrel, `None` otherwise.
+///
+/// Only TypeScript/JavaScript and Rust files are recognized.
+pub(super) fn reexports(result: &ParseResult) -> Option<Vec<Reexport>> {
+    let reexports = match result.language {
+        lang if is_js(lang) => js_reexports(result)?,
+        Language::Rust => rust_reexports(result)?,
+        _ => return None,
+    };
+    (!reexports.is_empty()).then_some(reexports)
+}
+
+fn js_reexports(result: &ParseResult) -> Option<Vec<Reexport>> {
+    let source = &result.source[..];
+    let root = result.root_node();
+    // Local binding -> (source name, module specifier) for `import` then `export { x }`
+    let mut imported: HashMap<String, (String, String)> = HashMap::new();
+    let mut reexports = Vec::new();
+
+    for node in root.children(&mut root.walk()) {
+        match node.kind() {
+            "comment" | "hash_bang_line" | "empty_statement" => {}
+            // "use strict" and other directive prologues
+            "expression_statement" if node.named_child(0).is_some_and(|c| c.kind() == "string") => {
+            }
+            "import_statement" => {
+                let path = unquote(&node.child_by_field_name("source")?, source)?;
+                for (name, local) in js_import_bindings(text(&node, source)?) {
+                    imported.insert(local, (name, path.clone()));
+                }
+            }
+            "export_statement" => {
+                if node.child_by_field_name("declaration").is_some()
+                    || node.child_by_field_name("value").is_some()
+                {
+                    return None;
+                }
+                let statement = text(&node, source)?;
+                match node.child_by_field_name("source") {
+                    Some(from) => reexports.push(Reexport {
+                        paths: vec![unquote(&from, source)?],
+                        names: js_export_bindings(statement),
+                    }),
+                    None => {
+                        for (local, exposed) in js_export_bindings(statement)? {
+                            let (name, path) = imported.get(&local)?;
+                            reexports.push(Reexport {
+                                paths: vec![path.clone()],
+                                names: Some(vec![(name.clone(), exposed)]),
+                            });
+                        }
+                    }
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(reexports)
+}
+
+fn rust_reexports(result: &ParseResult) -> Option<Vec<Reexport>> {
+    let source = &result.source[..];
+    let root = result.root_node();
+    let imports = extract_imports(result);
+    let mut modules: Vec<(String, bool)> = Vec::new();
+    let mut uses: Vec<(String, Option<Bindings>)> = Vec::new();
+
+    for node in root.children(&mut root.walk()) {
+        match node.kind() {
+            "line_comment"
+            | "block_comment"
+            | "attribute_item"
+            | "inner_attribute_item"
+            | "extern_crate_declaration"
+            | "empty_statement" => {}
+            "mod_item" => {
+                let mut name = None;
+                for child in node.children(&mut node.walk()) {
+                    match child.kind() {
+                        // Inline modules carry code of their own
+                        "declaration_list" => return None,
+                        "identifier" => name = text(&child, source),
+                        _ => {}
+                    }
+                }
+                let (public, _) = strip_visibility(text(&node, source)?);
+                modules.push((name?.to_string(), public));
+            }
+            "use_declaration" => {
+                let statement = text(&node, source)?;
+                if !strip_visibility(statement).0 {
+                    continue;
+                }
+                let import = imports
+                    .iter()
+                    .find(|i| i.span.start_byte == node.start_byte())?;
+                uses.push((import.path.clone(), rust_use_bindings(statement)));
+            }
+            _ => return None,
+        }
+    }
+
+    // Declared modules live next to `mod.rs`/`lib.rs`/`main.rs`, or in a
+    // directory named after any other file.
+    let stem = result
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let module_paths = |name: &str| -> Vec<String> {
+        let dir = if matches!(stem.as_str(), "mod" | "lib" | "main") {
+            ".".to_string()
+        } else {
+            format!("./{stem}")
+        };
+        vec![format!("{dir}/{name}"), format!("{dir}/{name}/mod")]
+    };
+
+    let mut reexports: Vec<Reexport> = modules
+        .iter()
+        .filter(|(_, public)| *public)
+        .map(|(name, _)| Reexport {
+            paths: module_paths(name),
+            names: Some(vec![("*".to_string(), name.clone())]),
+        })
+        .collect();
+    for (path, names) in uses {
+        let local = path.strip_prefix("self::").unwrap_or(&path);
+        let first = local.split("::").next().unwrap_or(local);
+        let paths = if modules.iter().any(|(name, _)| name == first) {
+            module_paths(first)
+        } else {
+            vec![path.clone()]
+        };
+        reexports.push(Reexport { paths, names });
+    }
+    Some(reexports)
+}
+
+/// Names exported by a TypeScript/JavaScript file, or `None` when they cannot
+/// be known (other languages, `export *` from another module).
+pub(super) fn exported_names(result: &ParseResult) -> Option<HashSet<String>> {
+    if !is_js(result.language) {
+        return None;
+    }
+    let source = &result.source[..];
+    let root = result.root_node();
+    let mut names = HashSet::new();
+
+    for node in root.children(&mut root.walk()) {
+        if node.kind() != "export_statement" {
+            continue;
+        }
+        if node
+            .children(&mut node.walk())
+            .any(|c| c.kind() == "default")
+        {
+            names.insert("default".to_string());
+        } else if let Some(decl) = node.child_by_field_name("declaration") {
+            if let Some(name) = decl.child_by_field_name("name") {
+                names.insert(text(&name, source)?.to_string());
+            } else {
+                // `export const a = 1, b = 2`
+                for declarator in decl.named_children(&mut decl.walk()) {
+                    if let Some(name) = declarator.child_by_field_name("name") {
+                        names.insert(text(&name, source)?.to_string());
+                    }
+                }
+            }
+        } else {
+            let bindings = js_export_bindings(text(&node, source)?)?;
+            names.extend(bindings.into_iter().map(|(_, exposed)| exposed));
+        }
+    }
+    Some(names)
+}
+
+/// Names `import` takes from its target, or `None` when it takes the whole
+/// module (namespace and wildcard imports, side-effect imports).
+pub(super) fn imported_names(result: &ParseResult, import: &ImportNode) -> Option<Vec<String>> {
+    let statement = result
+        .source
+        .get(import.span.start_byte..import.span.end_byte)
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())?;
+    let bindings = match result.language {
+        lang if is_js(lang) => js_import_bindings(statement),
+        Language::Rust => rust_use_bindings(statement)?,
+        _ => return None,
+    };
+    if bindings.is_empty() || bindings.iter().any(|(name, _)| name == "*") {
+        return None;
+    }
+    Some(bindings.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Split `a, b as c` into `(source, exposed)` pairs, dropping `type` modifiers.
+fn list_bindings(list: &str) -> Bindings {
+    list.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let part = part.strip_prefix("type ").unwrap_or(part).trim();
+            match part.split_once(" as ") {
+                Some((name, alias)) => (name.trim().to_string(), alias.trim().to_string()),
+                None => (part.to_string(), part.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// `(imported, local)` pairs of a JS `import` statement; default imports are
+/// named `default`.
+fn js_import_bindings(statement: &str) -> Bindings {
+    let body = statement.trim_start();
+    let body = body.strip_prefix("import").unwrap_or(body).trim_start();
+    let body = body.strip_prefix("type ").unwrap_or(body);
+    // Only look before the module specifier so `from` inside it is ignored
+    let body = &body[..body.find(['\'', '"']).unwrap_or(body.len())];
+    let Some(from) = body.rfind("from") else {
+        return Vec::new();
+    };
+    let clause = body[..from].trim();
+    let (default, named) = match clause.find('{') {
+        Some(open) => (
+            &clause[..open],
+            clause[open + 1..].split('}').next().unwrap_or(""),
+        ),
+        None => (clause, ""),
+    };
+
+    let mut bindings = Vec::new();
+    for part in default.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.strip_prefix('*') {
+            Some(namespace) => {
+                let local = namespace.trim().trim_start_matches("as").trim();
+                bindings.push(("*".to_string(), local.to_string()));
+            }
+            None => bindings.push(("default".to_string(), part.to_string())),
+        }
+    }
+    bindings.extend(list_bindings(named));
+    bindings
+}
+
+/// `(source, exposed)` pairs of a JS `export` clause; `None` for `export *`
+/// and anything that is not a clause.
+fn js_export_bindings(statement: &str) -> Option<Bindings> {
+    let body = statement.trim_start().strip_prefix("export")?.trim_start();
+    let body = body.strip_prefix("type ").unwrap_or(body).trim_start();
+    if let Some(rest) = body.strip_prefix('*') {
+        let namespace = rest.trim_start().strip_prefix("as ")?;
+        let name = namespace.split_whitespace().next()?;
+        return Some(vec![("*".to_string(), name.to_string())]);
+    }
+    let inner = body.strip_prefix('{')?.split('}').next()?;
+    Some(list_bindings(inner))
+}
+
+/// Strip a leading `pub`/`pub(...)`, reporting whether it was present.
+fn strip_visibility(statement: &str) -> (bool, &str) {
+    let statement = statement.trim_start();
+    let Some(rest) = statement.strip_prefix("pub") else {
+        return (false, statement);
+    };
+    let rest = match rest.trim_start().strip_prefix('(') {
+        Some(scoped) => scoped.split_once(')').map_or("", |(_, after)| after),
+        None => rest,
+    };
+    (true, rest.trim_start())
+}
+
+/// `(source, exposed)` pairs of a Rust `use` declaration; `None` for glob
+/// imports, nested lists, and anything that is not a `use`.
+fn rust_use_bindings(statement: &str) -> Option<Bindings> {
+    let (_, body) = strip_visibility(statement);
+    let body = body
+        .strip_prefix("use ")?
+        .trim()
+        .trim_end_matches(';')
+        .trim();
+    let last = |path: &str| path.rsplit("::").next().unwrap_or(path).trim().to_string();
+
+    if let Some(open) = body.find('{') {
+        let module = last(body[..open].trim_end_matches("::"));
+        let inner = body[open + 1..].trim_end().strip_suffix('}')?;
+        if inner.contains('{') || inner.contains('*') {
+            return None;
+        }
+        let bindings = list_bindings(inner)
+            .into_iter()
+            .map(|(name, exposed)| {
+                if name == "self" {
+                    let exposed = if exposed == "self" {
+                        module.clone()
+                    } else {
+                        exposed
+                    };
+                    ("*".to_string(), exposed)
+                } else {
+                    let exposed = if exposed == name {
+                        last(&name)
+                    } else {
+                        exposed
+                    };
+                    (last(&name), exposed)
+                }
+            })
+            .collect();
+        return Some(bindings);
+    }
+
+    if body.ends_with('*') {
+        return None;
+    }
+    let (path, alias) = match body.split_once(" as ") {
+        Some((path, alias)) => (path, Some(alias.trim())),
+        None => (body, None),
+    };
+    let name = last(path);
+    let exposed = alias.map_or_else(|| name.clone(), str::to_string);
+    Some(vec![(name, exposed)])
+}
+
+/// Resolved barrels of a project.
+#[derive(Debug, Default)]
+pub(super) struct Barrels {
+    /// Barrel file -> `(target file, re-exported names)` per resolved re-export.
+    reexports: HashMap<String, Vec<(String, Option<Bindings>)>>,
+    /// Names exported by non-barrel files, where known.
+    exports: HashMap<String, HashSet<String>>,
+}
+
+impl Barrels {
+    /// Register `path` as a barrel; ignored when none of its re-exports resolved.
+    pub fn insert(&mut self, path: String, targets: Vec<(String, Option<Bindings>)>) {
+        if !targets.is_empty() {
+            self.reexports.insert(path, targets);
+        }
+    }
+
+    /// Record the names a file exports, used to narrow `export *` chains.
+    pub fn insert_exports(&mut self, path: String, names: HashSet<String>) {
+        self.exports.insert(path, names);
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.reexports.contains_key(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.reexports.len()
+    }
+
+    /// Barrel paths, sorted.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.reexports.keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Files defining `names` imported through `barrel`, following nested
+    /// barrels. Falls back to every re-exported file when no name matches.
+    pub fn collapse(&self, barrel: &str, names: Option<&[String]>) -> Vec<String> {
+        let mut targets = Vec::new();
+        self.walk(barrel, names, &mut HashSet::new(), &mut targets);
+        if targets.is_empty() && names.is_some() {
+            self.walk(barrel, None, &mut HashSet::new(), &mut targets);
+        }
+        targets
+    }
+
+    fn walk(
+        &self,
+        barrel: &str,
+        names: Option<&[String]>,
+        visited: &mut HashSet<String>,
+        targets: &mut Vec<String>,
+    ) {
+        if !visited.insert(barrel.to_string()) {
+            return;
+        }
+        let Some(reexports) = self.reexports.get(barrel) else {
+            return;
+        };
+        for (target, bindings) in reexports {
+            let next: Option<Vec<String>> = match (names, bindings) {
+                (None, _) => None,
+                // `export *`: pass the names through, skipping files known
+                // not to export any of them
+                (Some(names), None) => {
+                    let known_missing = self
+                        .exports
+                        .get(target)
+                        .is_some_and(|exports| !names.iter().any(|n| exports.contains(n)));
+                    if known_missing && !self.contains(target) {
+                        continue;
+                    }
+                    Some(names.to_vec())
+                }
+                (Some(names), Some(bindings)) => {
+                    let sources: Vec<String> = bindings
+                        .iter()
+                        .filter(|(_, exposed)| names.contains(exposed))
+                        .map(|(source, _)| source.clone())
+                        .collect();
+                    if sources.is_empty() {
+                        continue;
+                    }
+                    (!sources.iter().any(|s| s == "*")).then_some(sources)
+                }
+            };
+            if self.contains(target) {
+                self.walk(target, next.as_deref(), visited, targets);
+            } else if !targets.contains(target) {
+                targets.push(target.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use std::path::Path;
+
+    fn parse(source: &str, lang: Language, file: &str) -> ParseResult {
+        Parser::new()
+            .parse(source.as_bytes(), lang, Path::new(file))
+            .unwrap()
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_typescript_barrel_reexports() {
+        let result = parse(
+            "// Public API\n\
+             export * from './user';\n\
+             export { Order as PurchaseOrder, type Line } from './order';\n\
+             import { helper } from './util';\n\
+             export { helper };\n",
+            Language::TypeScript,
+            "src/models/index.ts",
+        );
+        let reexports = reexports(&result).unwrap();
+
+        assert_eq!(reexports.len(), 3);
+        assert_eq!(reexports[0].paths, names(&["./user"]));
+        assert_eq!(reexports[0].names, None);
+        assert_eq!(
+            reexports[1].names,
+            Some(vec![
+                ("Order".to_string(), "PurchaseOrder".to_string()),
+                ("Line".to_string(), "Line".to_string()),
+            ])
+        );
+        assert_eq!(reexports[2].paths, names(&["./util"]));
+    }
+
+    #[test]
+    fn test_file_with_code_is_not_a_barrel() {
+        let ts = parse(
+            "export * from './user';\nexport const VERSION = 1;\n",
+            Language::TypeScript,
+            "src/index.ts",
+        );
+        assert_eq!(reexports(&ts), None);
+
+        let rs = parse(
+            "pub mod a;\npub fn run() {}\n",
+            Language::Rust,
+            "src/lib.rs",
+        );
+        assert_eq!(reexports(&rs), None);
+
+        let only_imports = parse("import { a } from './a';\n", Language::TypeScript, "x.ts");
+        assert_eq!(reexports(&only_imports), None);
+    }
+
+    #[test]
+    fn test_rust_mod_rs_barrel() {
+        let result = parse(
+            "//! Models.\n\
+             mod order;\n\
+             pub mod user;\n\
+             pub use self::order::{Order, Line as OrderLine};\n\
+             pub use crate::util::*;\n",
+            Language::Rust,
+            "src/models/mod.rs",
+        );
+        let reexports = reexports(&result).unwrap();
+
+        assert_eq!(reexports.len(), 3);
+        assert_eq!(reexports[0].paths, names(&["./user", "./user/mod"]));
+        assert_eq!(
+            reexports[0].names,
+            Some(vec![("*".to_string(), "user".to_string())])
+        );
+        assert_eq!(reexports[1].paths, names(&["./order", "./order/mod"]));
+        assert_eq!(
+            reexports[1].names,
+            Some(vec![
+                ("Order".to_string(), "Order".to_string()),
+                ("Line".to_string(), "OrderLine".to_string()),
+            ])
+        );
+        assert_eq!(reexports[2].paths, names(&["crate::util::*"]));
+        assert_eq!(reexports[2].names, None);
+    }
+
+    #[test]
+    fn test_imported_names() {
+        let ts = parse(
+            "import Default, { a, b as c } from './barrel';\nimport * as all from './barrel';\n",
+            Language::TypeScript,
+            "app.ts",
+        );
+        let imports = extract_imports(&ts);
+        assert_eq!(
+            imported_names(&ts, &imports[0]),
+            Some(names(&["default", "a", "b"]))
+        );
+        assert_eq!(imported_names(&ts, &imports[1]), None);
+
+        let rs = parse(
+            "use crate::models::{Order, User as Account};\nuse crate::models::*;\n",
+            Language::Rust,
+            "src/app.rs",
+        );
+        let imports = extract_imports(&rs);
+        assert_eq!(
+            imported_names(&rs, &imports[0]),
+            Some(names(&["Order", "User"]))
+        );
+        assert_eq!(imported_names(&rs, &imports[1]), None);
+    }
+
+    #[test]
+    fn test_exported_names() {
+        let result = parse(
+            "export class User {}\nexport const a = 1, b = 2;\nexport default function main() {}\n",
+            Language::TypeScript,
+            "user.ts",
+        );
+        let exported = exported_names(&result).unwrap();
+        for name in ["User", "a", "b", "default"] {
+            assert!(exported.contains(name), "missing {name}");
+        }
+    }
+
+    #[test]
+    fn test_collapse_follows_names_through_nested_barrels() {
+        let mut barrels = Barrels::default();
+        barrels.insert(
+            "index.ts".to_string(),
+            vec![
+                ("models/index.ts".to_string(), None),
+                (
+                    "util.ts".to_string(),
+                    Some(vec![("helper".to_string(), "help".to_string())]),
+                ),
+            ],
+        );
+        barrels.insert(
+            "models/index.ts".to_string(),
+            vec![
+                ("models/user.ts".to_string(), None),
+                ("models/order.ts".to_string(), None),
+            ],
+        );
+        barrels.insert_exports("models/user.ts".to_string(), HashSet::from(["User".into()]));
+        barrels.insert_exports(
+            "models/order.ts".to_string(),
+            HashSet::from(["Order".into()]),
+        );
+
+        assert_eq!(
+            barrels.collapse("index.ts", Some(&names(&["User", "help"]))),
+            names(&["models/user.ts", "util.ts"])
+        );
+        assert_eq!(
+            barrels.collapse("index.ts", None),
+            names(&["models/user.ts", "models/order.ts", "util.ts"])
+        );
+        // Unknown names fall back to every re-exported file
+        assert_eq!(
+            barrels.collapse("index.ts", Some(&names(&["Nope"]))).len(),
+            3
+        );
+        assert_eq!(barrels.paths(), names(&["index.ts", "models/index.ts"]));
+    }
+}
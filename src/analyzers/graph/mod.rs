@@ -0,0 +1,3038 @@
+//! Dependency graph analyzer.
+//!
+//! Builds a directed graph of file dependencies, calculates graph metrics,
+//! detects cycles, and renders the graph as Graphviz DOT, GraphML, or
+//! Mermaid with cycles highlighted.
+//!
+//! # Key Metrics
+//!
+//! - **PageRank**: Importance based on incoming edges
+//!   Reference: Page, Brin, Motwani, Winograd (1999) "The PageRank Citation Ranking"
+//!   Damping factor 0.85 is the canonical value.
+//!
+//! - **Betweenness Centrality**: How often a node appears on shortest paths
+//!   Reference: Brandes, U. (2001) "A Faster Algorithm for Betweenness Centrality"
+//!
+//! - **Instability**: out_degree / (in_degree + out_degree)
+//!   Reference: Martin, R.C. (2003) "Agile Software Development"
+//!   Measures tendency to change (1.0 = maximally unstable, 0.0 = maximally stable)
+//!
+//! - **Cycle Detection**: Uses Tarjan's SCC algorithm
+//!   Reference: Tarjan, R. (1972) "Depth-first search and linear graph algorithms"
+//!
+//! - **Cycle Breaks**: Per cycle, an approximate minimum feedback arc set --
+//!   imports whose removal makes the cycle acyclic -- reported with the
+//!   importing file and line.
+//!   Reference: Eades, P., Lin, X., Smyth, W.F. (1993) "A fast and effective
+//!   heuristic for the feedback arc set problem"
+//!
+//! # Edge Classification
+//!
+//! Every edge is tagged as `internal`, `workspace_external` (another package in
+//! the same workspace, or a relative path outside the analyzed tree), `stdlib`,
+//! or `third_party`. With `internal_only` set, only internal edges enter the
+//! graph so vendored or registry imports cannot distort cycle and hub metrics.
+//!
+//! # Dynamic Imports
+//!
+//! Runtime loads -- `import()` in JavaScript/TypeScript, `importlib` and
+//! `__import__` in Python, `Class.forName` and `loadClass` in Java -- become
+//! weak edges flagged `dynamic`. They count towards centrality and show up in
+//! the edge list for impact analysis, but are left out of cycle detection
+//! unless `dynamic_cycles` is set: a lazy import is the usual way to break a
+//! cycle, so reporting it as one would be noise.
+//!
+//! # Rust Use Paths
+//!
+//! Rust `use` and `mod` declarations resolve through the crate's module tree
+//! and its `pub use` re-exports to the file defining each item (see
+//! [`rust_modules`]), so `use crate::config::Config` points at the file
+//! declaring `Config` rather than the module re-exporting it. The edge lists
+//! the items taken in `symbols`.
+//!
+//! # Barrel Files
+//!
+//! With `collapse_barrels` set, re-export-only files (`index.ts` hubs, Rust
+//! `mod.rs` files of `mod` and `pub use` items) are removed and imports of
+//! them are attributed to the files defining the imported names, so hub and
+//! coupling metrics reflect the real architecture. See [`barrel`].
+//!
+//! # Packages
+//!
+//! With `packages` set, files are also grouped by directory into a package
+//! graph with afferent/efferent coupling, instability, abstractness, and
+//! distance from the main sequence per package. See [`packages`].
+//!
+//! # Known Limitation
+//!
+//! PageRank implementation does not redistribute dangling node mass uniformly,
+//! which may slightly affect scores in sparse graphs.
+
+mod barrel;
+mod packages;
+pub(crate) mod rust_modules;
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::GraphFormat;
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
+use crate::output::escape_xml;
+use crate::parser::{extract_imports, ImportNode, Parser};
+
+use barrel::{Barrels, Bindings};
+use packages::TypeCounts;
+pub use packages::{Package, PackageEdge, PackageGraph};
+use rust_modules::{RustFile, RustModules};
+
+/// Graph analyzer configuration.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// PageRank damping factor (default: 0.85).
+    pub damping: f64,
+    /// PageRank max iterations (default: 100).
+    pub max_iterations: usize,
+    /// PageRank convergence tolerance (default: 1e-6).
+    pub tolerance: f64,
+    /// Resolve relative imports to absolute paths.
+    pub resolve_imports: bool,
+    /// Include external dependencies.
+    pub include_external: bool,
+    /// Keep only edges between files in the same workspace package.
+    pub internal_only: bool,
+    /// Collapse re-export-only barrel files into their definition sites.
+    pub collapse_barrels: bool,
+    /// Count dynamic (runtime) imports when detecting cycles.
+    pub dynamic_cycles: bool,
+    /// Aggregate files into directory-level packages.
+    pub packages: bool,
+}
+
+/// A resolved import of a parsed file.
+struct ResolvedImport {
+    target: String,
+    kind: EdgeKind,
+    line: u32,
+    /// Imported names, when collapsing barrels.
+    names: Option<Vec<String>>,
+    /// Loaded at runtime rather than declared.
+    dynamic: bool,
+    /// Items taken from the target, for Rust `use` paths resolved to the
+    /// defining file.
+    symbols: Vec<String>,
+}
+
+/// Imports and barrel information read from one parsed file, before
+/// resolution.
+struct ExtractedFile {
+    path: String,
+    rel_path: std::path::PathBuf,
+    lang: Language,
+    /// Imports with their imported names, when collapsing barrels.
+    imports: Vec<(ImportNode, Option<Vec<String>>)>,
+    reexports: Option<Vec<(String, Option<Bindings>)>>,
+    exports: Option<HashSet<String>>,
+    /// Items and `use` declarations of a Rust file.
+    rust: Option<RustFile>,
+    /// Type declarations, when aggregating packages.
+    types: Option<TypeCounts>,
+}
+
+/// Imports and barrel information extracted from one file.
+struct ParsedFile {
+    path: String,
+    imports: Vec<ResolvedImport>,
+    /// Resolved re-exports when the file is a barrel.
+    reexports: Option<Vec<(String, Option<Bindings>)>>,
+    /// Names the file exports, where known.
+    exports: Option<HashSet<String>>,
+    /// Type declarations, when aggregating packages.
+    types: Option<TypeCounts>,
+}
+
+/// Pre-built index for O(1) file path lookups during import resolution.
+#[allow(dead_code)]
+struct FilePathIndex {
+    /// Full relative path -> relative path (identity mapping for exact matches).
+    by_full_path: HashMap<String, String>,
+    /// File stem (without extension) -> list of relative paths.
+    by_stem: HashMap<String, Vec<String>>,
+    /// File name (with extension) -> list of relative paths.
+    by_name: HashMap<String, Vec<String>>,
+    /// Normalized path segments for partial matching.
+    by_segments: HashMap<String, Vec<String>>,
+}
+
+impl FilePathIndex {
+    fn new(files: &[std::path::PathBuf], root: &Path) -> Self {
+        let mut by_full_path = HashMap::with_capacity(files.len());
+        let mut by_stem: HashMap<String, Vec<String>> = HashMap::new();
+        let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+        let mut by_segments: HashMap<String, Vec<String>> = HashMap::new();
+
+        for file in files {
+            let rel = file.strip_prefix(root).unwrap_or(file);
+            let rel_str = rel.to_string_lossy().to_string();
+
+            // Full path lookup
+            by_full_path.insert(rel_str.clone(), rel_str.clone());
+
+            // File stem lookup (e.g., "mod" from "mod.rs")
+            if let Some(stem) = rel.file_stem() {
+                let stem_str = stem.to_string_lossy().to_string();
+                by_stem.entry(stem_str).or_default().push(rel_str.clone());
+            }
+
+            // File name lookup (e.g., "mod.rs")
+            if let Some(name) = rel.file_name() {
+                let name_str = name.to_string_lossy().to_string();
+                by_name.entry(name_str).or_default().push(rel_str.clone());
+            }
+
+            // Segment-based lookup for partial path matching
+            // e.g., "src/utils/helpers" can match "utils/helpers.rs"
+            for component in rel.iter() {
+                let comp_str = component.to_string_lossy().to_string();
+                if !comp_str.is_empty() && comp_str != "." {
+                    by_segments
+                        .entry(comp_str)
+                        .or_default()
+                        .push(rel_str.clone());
+                }
+            }
+        }
+
+        Self {
+            by_full_path,
+            by_stem,
+            by_name,
+            by_segments,
+        }
+    }
+
+    /// Try to find a file matching the import path.
+    fn find_match(&self, import_path: &str, from_file: &Path) -> Option<String> {
+        // 0. Handle Rust crate-relative imports (crate::, super::, self::)
+        if import_path.starts_with("crate::")
+            || import_path.starts_with("super::")
+            || import_path.starts_with("self::")
+        {
+            let stripped = import_path
+                .strip_prefix("crate::")
+                .or_else(|| import_path.strip_prefix("super::"))
+                .or_else(|| import_path.strip_prefix("self::"))
+                .unwrap_or(import_path);
+
+            // Take the module path part (strip trailing type names which are CamelCase)
+            // e.g., crate::config::Config -> config, crate::analyzers::graph -> analyzers/graph
+            let segments: Vec<&str> = stripped.split("::").collect();
+
+            // Find the last segment that looks like a module (lowercase/snake_case)
+            let module_segments: Vec<&str> = segments
+                .iter()
+                .take_while(|s| {
+                    s.chars()
+                        .next()
+                        .map(|c| c.is_lowercase() || c == '_')
+                        .unwrap_or(false)
+                })
+                .copied()
+                .collect();
+
+            let module_path = if module_segments.is_empty() {
+                // All segments start with uppercase - use all as path
+                segments.join("/")
+            } else {
+                module_segments.join("/")
+            };
+
+            // Handle super:: relative to current file
+            if import_path.starts_with("super::") {
+                if let Some(parent) = from_file.parent().and_then(|p| p.parent()) {
+                    let resolved = parent.join(&module_path);
+                    let normalized = normalize_path(&resolved);
+                    for ext in &["", ".rs"] {
+                        let candidate = if ext.is_empty() {
+                            normalized.clone()
+                        } else {
+                            format!("{}{}", normalized, ext)
+                        };
+                        if self.by_full_path.contains_key(&candidate) {
+                            return Some(candidate);
+                        }
+                    }
+                    let mod_rs = format!("{}/mod.rs", normalized);
+                    if self.by_full_path.contains_key(&mod_rs) {
+                        return Some(mod_rs);
+                    }
+                }
+            }
+
+            // Try common Rust source roots with the module path
+            let source_roots = ["src", "lib", ""];
+            for root in &source_roots {
+                let base = if root.is_empty() {
+                    module_path.clone()
+                } else {
+                    format!("{}/{}", root, module_path)
+                };
+
+                // Try direct file match (e.g., src/config.rs)
+                let rs_path = format!("{}.rs", base);
+                if self.by_full_path.contains_key(&rs_path) {
+                    return Some(rs_path);
+                }
+
+                // Try mod.rs (e.g., src/config/mod.rs)
+                let mod_path = format!("{}/mod.rs", base);
+                if self.by_full_path.contains_key(&mod_path) {
+                    return Some(mod_path);
+                }
+            }
+
+            // Fall through to other matching strategies
+        }
+
+        // 1. Handle relative imports (./foo, ../foo)
+        if import_path.starts_with("./") || import_path.starts_with("../") {
+            if let Some(parent) = from_file.parent() {
+                let resolved = parent.join(import_path);
+                let normalized = normalize_path(&resolved);
+
+                // Try exact match with common extensions
+                for ext in &[
+                    "", ".rs", ".go", ".py", ".ts", ".tsx", ".js", ".jsx", ".java",
+                ] {
+                    let with_ext = if ext.is_empty() {
+                        normalized.clone()
+                    } else {
+                        format!(
+                            "{}.{}",
+                            normalized.trim_end_matches(&format!(".{}", &ext[1..])),
+                            &ext[1..]
+                        )
+                    };
+
+                    if self.by_full_path.contains_key(&with_ext) {
+                        return Some(with_ext);
+                    }
+
+                    // Try index files (e.g., ./utils -> ./utils/index.ts)
+                    let index_path = format!("{}/index{}", normalized, ext);
+                    if self.by_full_path.contains_key(&index_path) {
+                        return Some(index_path);
+                    }
+                }
+            }
+        }
+
+        // 2. Try exact stem match
+        if let Some(matches) = self.by_stem.get(import_path) {
+            if matches.len() == 1 {
+                return Some(matches[0].clone());
+            }
+            // Multiple matches - prefer shortest path (most specific)
+            if !matches.is_empty() {
+                let mut sorted = matches.clone();
+                sorted.sort_by_key(|s| s.len());
+                return Some(sorted[0].clone());
+            }
+        }
+
+        // 3. Try snake_case conversion for Ruby CamelCase constants
+        //    e.g., "OrderSearcher" -> "order_searcher", "ActiveModel::Validations" -> "active_model/validations"
+        if import_path.contains("::") {
+            // Scoped constant: split on ::, convert each segment, join with /
+            // e.g., ActiveModel::Validations -> active_model/validations
+            let snake_path: String = import_path
+                .split("::")
+                .map(camel_to_snake)
+                .collect::<Vec<_>>()
+                .join("/");
+            // Match on path segment boundaries (must be preceded by / or start of string)
+            let with_slash = format!("/{}", snake_path);
+            if let Some(matched) = self
+                .by_full_path
+                .keys()
+                .find(|k| k.starts_with(&snake_path) || k.contains(&with_slash))
+            {
+                return Some(matched.clone());
+            }
+            // Also try the last segment as a stem match
+            if let Some(last) = snake_path.rsplit('/').next() {
+                if let Some(matches) = self.by_stem.get(last) {
+                    for candidate in matches {
+                        if candidate.contains(&snake_path) {
+                            return Some(candidate.clone());
+                        }
+                    }
+                }
+            }
+        } else {
+            let snake = camel_to_snake(import_path);
+            if snake != import_path {
+                if let Some(matches) = self.by_stem.get(&snake) {
+                    if matches.len() == 1 {
+                        return Some(matches[0].clone());
+                    }
+                    if !matches.is_empty() {
+                        let mut sorted = matches.clone();
+                        sorted.sort_by_key(|s| s.len());
+                        return Some(sorted[0].clone());
+                    }
+                }
+            }
+        }
+
+        // 4. Try segment-based matching for module paths like "utils/helpers"
+        let segments: Vec<&str> = import_path.split('/').filter(|s| !s.is_empty()).collect();
+        if let Some(last_segment) = segments.last() {
+            if let Some(candidates) = self.by_stem.get(*last_segment) {
+                // Find candidates that match the full path pattern
+                for candidate in candidates {
+                    if candidate.contains(import_path) {
+                        return Some(candidate.clone());
+                    }
+                }
+                // Fall back to first match with the stem
+                if !candidates.is_empty() {
+                    return Some(candidates[0].clone());
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Convert CamelCase to snake_case for Ruby constant-to-filename resolution.
+/// Handles consecutive uppercase (e.g., HTTPClient -> http_client).
+fn camel_to_snake(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                let prev = chars[i - 1];
+                let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+                // Insert underscore before: uppercase after lowercase, or
+                // start of new word in consecutive uppercase (e.g., the P in HTTPParser)
+                if prev.is_lowercase() || (prev.is_uppercase() && next_is_lower) {
+                    result.push('_');
+                }
+            }
+            for lc in c.to_lowercase() {
+                result.push(lc);
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Normalize a path by removing . and resolving ..
+fn normalize_path(path: &Path) -> String {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::Normal(c) => {
+                components.push(c.to_string_lossy().to_string());
+            }
+            std::path::Component::ParentDir => {
+                components.pop();
+            }
+            std::path::Component::CurDir => {}
+            _ => {}
+        }
+    }
+    components.join("/")
+}
+
+/// Manifest files that mark the root of a workspace package.
+const PACKAGE_MANIFESTS: &[&str] = &[
+    "package.json",
+    "Cargo.toml",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "composer.json",
+    "Gemfile",
+];
+
+/// Directory names whose contents are vendored third-party code.
+const VENDOR_DIRS: &[&str] = &[
+    "node_modules",
+    "bower_components",
+    "vendor",
+    "third_party",
+    "site-packages",
+    ".venv",
+];
+
+/// Node.js built-in modules (importable without the `node:` prefix).
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "buffer",
+    "child_process",
+    "cluster",
+    "crypto",
+    "dgram",
+    "dns",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "querystring",
+    "readline",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "worker_threads",
+    "zlib",
+];
+
+/// Commonly imported Python standard library modules.
+const PYTHON_STDLIB: &[&str] = &[
+    "abc",
+    "argparse",
+    "array",
+    "ast",
+    "asyncio",
+    "base64",
+    "bisect",
+    "builtins",
+    "collections",
+    "concurrent",
+    "contextlib",
+    "copy",
+    "csv",
+    "ctypes",
+    "dataclasses",
+    "datetime",
+    "decimal",
+    "difflib",
+    "enum",
+    "errno",
+    "functools",
+    "gc",
+    "getpass",
+    "glob",
+    "gzip",
+    "hashlib",
+    "heapq",
+    "hmac",
+    "html",
+    "http",
+    "importlib",
+    "inspect",
+    "io",
+    "ipaddress",
+    "itertools",
+    "json",
+    "logging",
+    "math",
+    "mimetypes",
+    "multiprocessing",
+    "operator",
+    "os",
+    "pathlib",
+    "pickle",
+    "platform",
+    "pprint",
+    "queue",
+    "random",
+    "re",
+    "secrets",
+    "select",
+    "shlex",
+    "shutil",
+    "signal",
+    "socket",
+    "sqlite3",
+    "ssl",
+    "stat",
+    "statistics",
+    "string",
+    "struct",
+    "subprocess",
+    "sys",
+    "tempfile",
+    "textwrap",
+    "threading",
+    "time",
+    "timeit",
+    "traceback",
+    "types",
+    "typing",
+    "unittest",
+    "urllib",
+    "uuid",
+    "warnings",
+    "weakref",
+    "xml",
+    "zipfile",
+    "zlib",
+];
+
+/// Commonly required Ruby standard library features.
+const RUBY_STDLIB: &[&str] = &[
+    "base64",
+    "benchmark",
+    "bigdecimal",
+    "csv",
+    "date",
+    "digest",
+    "erb",
+    "fileutils",
+    "find",
+    "forwardable",
+    "json",
+    "logger",
+    "net/http",
+    "open3",
+    "openssl",
+    "optparse",
+    "ostruct",
+    "pathname",
+    "pp",
+    "securerandom",
+    "set",
+    "singleton",
+    "socket",
+    "stringio",
+    "strscan",
+    "tempfile",
+    "time",
+    "timeout",
+    "tmpdir",
+    "uri",
+    "yaml",
+    "zlib",
+];
+
+/// Classification of a dependency edge by where its target lives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// Target is an analyzed file in the same workspace package.
+    #[default]
+    Internal,
+    /// Target is another workspace package or a relative path outside the analyzed tree.
+    WorkspaceExternal,
+    /// Target is part of the language's standard library.
+    Stdlib,
+    /// Target is a third-party dependency (registry package or vendored code).
+    ThirdParty,
+}
+
+impl EdgeKind {
+    /// Name as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeKind::Internal => "internal",
+            EdgeKind::WorkspaceExternal => "workspace_external",
+            EdgeKind::Stdlib => "stdlib",
+            EdgeKind::ThirdParty => "third_party",
+        }
+    }
+}
+
+/// Maps analyzed files to the workspace package that contains them.
+struct WorkspaceIndex {
+    /// Relative file path -> package root directory ("" for the repository root).
+    package_of: HashMap<String, String>,
+    /// Go module paths declared by go.mod files at package roots.
+    go_modules: Vec<String>,
+}
+
+impl WorkspaceIndex {
+    fn new(files: &[std::path::PathBuf], root: &Path) -> Self {
+        let mut dir_cache: HashMap<std::path::PathBuf, String> = HashMap::new();
+        let mut package_of = HashMap::with_capacity(files.len());
+
+        for file in files {
+            let rel = file.strip_prefix(root).unwrap_or(file);
+            let dir = rel.parent().unwrap_or_else(|| Path::new(""));
+            let package = package_root(dir, root, &mut dir_cache);
+            package_of.insert(rel.to_string_lossy().to_string(), package);
+        }
+
+        let mut package_roots: Vec<&String> = dir_cache.values().collect();
+        package_roots.sort();
+        package_roots.dedup();
+        let go_modules = package_roots
+            .into_iter()
+            .filter_map(|pkg| std::fs::read_to_string(root.join(pkg).join("go.mod")).ok())
+            .filter_map(|content| {
+                content.lines().find_map(|line| {
+                    line.trim()
+                        .strip_prefix("module ")
+                        .map(|m| m.trim().to_string())
+                })
+            })
+            .collect();
+
+        Self {
+            package_of,
+            go_modules,
+        }
+    }
+
+    fn same_package(&self, a: &str, b: &str) -> bool {
+        self.package_of.get(a) == self.package_of.get(b)
+    }
+}
+
+/// Find the nearest ancestor directory (relative to root) containing a package manifest.
+fn package_root(
+    dir: &Path,
+    root: &Path,
+    cache: &mut HashMap<std::path::PathBuf, String>,
+) -> String {
+    if let Some(package) = cache.get(dir) {
+        return package.clone();
+    }
+    let package = if dir.as_os_str().is_empty() {
+        String::new()
+    } else if PACKAGE_MANIFESTS
+        .iter()
+        .any(|manifest| root.join(dir).join(manifest).is_file())
+    {
+        dir.to_string_lossy().to_string()
+    } else {
+        package_root(dir.parent().unwrap_or_else(|| Path::new("")), root, cache)
+    };
+    cache.insert(dir.to_path_buf(), package.clone());
+    package
+}
+
+/// Group files by the workspace package that contains them.
+///
+/// Keys are package root directories relative to `root`, with `""` for
+/// files outside any nested package.
+pub(crate) fn group_by_package(
+    files: &[std::path::PathBuf],
+    root: &Path,
+) -> BTreeMap<String, Vec<std::path::PathBuf>> {
+    let mut cache = HashMap::new();
+    let mut packages: BTreeMap<String, Vec<std::path::PathBuf>> = BTreeMap::new();
+    for file in files {
+        let rel = file.strip_prefix(root).unwrap_or(file);
+        let dir = rel.parent().unwrap_or_else(|| Path::new(""));
+        packages
+            .entry(package_root(dir, root, &mut cache))
+            .or_default()
+            .push(file.clone());
+    }
+    packages
+}
+
+/// Check whether an import path names a standard library module.
+fn is_stdlib_import(import_path: &str, lang: Language, go_modules: &[String]) -> bool {
+    match lang {
+        Language::Go => {
+            let first = import_path.split('/').next().unwrap_or(import_path);
+            !first.contains('.')
+                && !go_modules
+                    .iter()
+                    .any(|m| import_path == m || import_path.starts_with(&format!("{m}/")))
+        }
+        Language::Rust => import_path
+            .split_once("::")
+            .is_some_and(|(first, _)| matches!(first, "std" | "core" | "alloc" | "proc_macro")),
+        Language::Python => {
+            let first = import_path.split('.').next().unwrap_or(import_path);
+            PYTHON_STDLIB.contains(&first)
+        }
+        Language::TypeScript | Language::JavaScript | Language::Tsx | Language::Jsx => {
+            import_path.starts_with("node:") || {
+                let first = import_path.split('/').next().unwrap_or(import_path);
+                NODE_BUILTINS.contains(&first)
+            }
+        }
+        Language::Java => {
+            // Java import nodes carry the full declaration text
+            let name = import_path
+                .trim_start_matches("import")
+                .trim_start()
+                .trim_start_matches("static ")
+                .trim_start();
+            name.starts_with("java.") || name.starts_with("javax.") || name.starts_with("jdk.")
+        }
+        Language::Ruby => RUBY_STDLIB.contains(&import_path),
+        _ => false,
+    }
+}
+
+/// Check whether an import path is written relative to the importing file.
+fn is_relative_import(import_path: &str) -> bool {
+    import_path.starts_with('.')
+        || import_path.starts_with('/')
+        || import_path.starts_with("crate::")
+        || import_path.starts_with("super::")
+        || import_path.starts_with("self::")
+}
+
+/// Check whether a resolved path lies inside a vendored dependency directory.
+fn is_vendored_path(path: &str) -> bool {
+    path.split('/')
+        .any(|segment| VENDOR_DIRS.contains(&segment))
+}
+
+/// Classify an import from `from` given the file it resolved to, if any.
+fn classify_import(
+    import_path: &str,
+    lang: Language,
+    from: &str,
+    resolved: Option<&str>,
+    workspace: &WorkspaceIndex,
+) -> EdgeKind {
+    if is_stdlib_import(import_path, lang, &workspace.go_modules) {
+        return EdgeKind::Stdlib;
+    }
+    match resolved {
+        Some(target) if is_vendored_path(target) => EdgeKind::ThirdParty,
+        Some(target) if !workspace.same_package(from, target) => EdgeKind::WorkspaceExternal,
+        Some(_) => EdgeKind::Internal,
+        None if is_relative_import(import_path) => EdgeKind::WorkspaceExternal,
+        None => EdgeKind::ThirdParty,
+    }
+}
+
+/// Path to resolve a dynamic import by. Python and Java load modules and
+/// classes by dotted name (`pkg.plugins`, `com.example.Plugin`), which
+/// resolves like the matching file path.
+fn dynamic_lookup_path(import: &ImportNode, lang: Language) -> String {
+    match lang {
+        Language::Python | Language::Java if import.dynamic => {
+            import.path.trim_start_matches('.').replace('.', "/")
+        }
+        _ => import.path.clone(),
+    }
+}
+
+/// Resolve a Rust `use` or `mod` declaration through the module tree, one
+/// import per defining file with the items taken from it. Empty when nothing
+/// resolves, leaving the import to file-level matching.
+fn resolve_rust_import(
+    modules: &RustModules,
+    from: &str,
+    import: &ImportNode,
+    names: &Option<Vec<String>>,
+) -> Vec<ResolvedImport> {
+    let uses: Vec<_> = modules
+        .uses(from)
+        .iter()
+        .filter(|u| u.start_byte == import.span.start_byte)
+        .collect();
+    let mut targets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if uses.is_empty() {
+        // `mod name;`
+        if let Some(file) = modules.child_module(from, &import.path) {
+            targets.entry(file.clone()).or_default();
+        }
+    }
+    for item in uses {
+        let Some(resolved) = modules.resolve(from, item) else {
+            continue;
+        };
+        let symbols = targets.entry(resolved.file).or_default();
+        if let Some(symbol) = resolved.symbol {
+            if !symbols.contains(&symbol) {
+                symbols.push(symbol);
+            }
+        }
+    }
+    targets
+        .into_iter()
+        .map(|(target, symbols)| ResolvedImport {
+            target,
+            kind: EdgeKind::Internal,
+            line: import.line,
+            names: names.clone(),
+            dynamic: false,
+            symbols,
+        })
+        .collect()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            max_iterations: 100,
+            tolerance: 1e-6,
+            resolve_imports: true,
+            include_external: false,
+            internal_only: false,
+            collapse_barrels: false,
+            dynamic_cycles: false,
+            packages: false,
+        }
+    }
+}
+
+/// Graph analyzer.
+pub struct Analyzer {
+    #[allow(dead_code)]
+    parser: Parser,
+    config: Config,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self {
+            parser: Parser::new(),
+            config: Config::default(),
+        }
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            parser: Parser::new(),
+            config,
+        }
+    }
+
+    /// Analyze a directory and build dependency graph.
+    pub fn analyze_project(&self, root: &Path) -> Result<Analysis> {
+        use crate::config::Config as AppConfig;
+        use crate::core::FileSet;
+
+        let config = AppConfig::default();
+        let file_set = FileSet::from_path(root, &config)?;
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        self.analyze_files(&ctx)
+    }
+
+    /// Analyze a set of files and build dependency graph.
+    /// Uses ctx.read_file() to support both filesystem and git tree sources.
+    pub fn analyze_files(&self, ctx: &AnalysisContext<'_>) -> Result<Analysis> {
+        let files: Vec<_> = ctx.files.iter().collect();
+        let owned_files: Vec<std::path::PathBuf> = files.iter().map(|p| (*p).clone()).collect();
+
+        // Repository-level [graph] settings extend the analyzer's own config
+        let internal_only = self.config.internal_only || ctx.config.graph.internal_only;
+        let include_external =
+            !internal_only && (self.config.include_external || ctx.config.graph.include_external);
+        let collapse_barrels = self.config.collapse_barrels || ctx.config.graph.collapse_barrels;
+        let dynamic_cycles = self.config.dynamic_cycles || ctx.config.graph.dynamic_cycles;
+        let aggregate_packages = self.config.packages || ctx.config.graph.packages;
+
+        // Build file path index for O(1) lookups during import resolution
+        let file_index = FilePathIndex::new(&owned_files, ctx.root);
+        let workspace = WorkspaceIndex::new(&owned_files, ctx.root);
+
+        // Parallel parsing: extract imports from all files concurrently
+        let mut extracted: Vec<ExtractedFile> = files
+            .par_iter()
+            .filter_map(|file| {
+                let rel_path = file.strip_prefix(ctx.root).unwrap_or(file);
+                let path_str = rel_path.to_string_lossy().to_string();
+
+                // Parse via context (shared parse cache, filesystem or git tree)
+                let lang = ctx.files.language(file)?;
+                let result = ctx.parsed(file).ok()?;
+                let imports = extract_imports(&result)
+                    .into_iter()
+                    .map(|imp| {
+                        let names = if collapse_barrels && !imp.dynamic {
+                            barrel::imported_names(&result, &imp)
+                        } else {
+                            None
+                        };
+                        (imp, names)
+                    })
+                    .collect();
+
+                // Barrel re-exports resolve like imports; unresolved ones are dropped
+                let (reexports, exports) = if collapse_barrels {
+                    let reexports = barrel::reexports(&result).map(|reexports| {
+                        reexports
+                            .into_iter()
+                            .filter_map(|r| {
+                                let target = r
+                                    .paths
+                                    .iter()
+                                    .find_map(|p| file_index.find_match(p, rel_path))?;
+                                (target != path_str).then_some((target, r.names))
+                            })
+                            .collect()
+                    });
+                    (reexports, barrel::exported_names(&result))
+                } else {
+                    (None, None)
+                };
+
+                let rust = (lang == Language::Rust).then(|| rust_modules::parse(&result));
+                let types = aggregate_packages.then(|| packages::type_counts(&result));
+                Some(ExtractedFile {
+                    path: path_str,
+                    rel_path: rel_path.to_path_buf(),
+                    lang,
+                    imports,
+                    reexports,
+                    exports,
+                    rust,
+                    types,
+                })
+            })
+            .collect();
+
+        // Rust `use` paths resolve through the module tree to the defining file
+        let rust_files: Vec<(String, RustFile)> = extracted
+            .iter_mut()
+            .filter_map(|file| Some((file.path.clone(), file.rust.take()?)))
+            .collect();
+        let rust_modules = RustModules::new(ctx.root, rust_files);
+
+        // Resolve imports using the pre-built index, then classify
+        let file_imports: Vec<ParsedFile> = extracted
+            .into_par_iter()
+            .map(|file| {
+                let path_str = &file.path;
+                let lang = file.lang;
+                let mut resolved = Vec::new();
+                for (imp, names) in &file.imports {
+                    if lang == Language::Rust && self.config.resolve_imports {
+                        let rust_imports = resolve_rust_import(&rust_modules, path_str, imp, names);
+                        if !rust_imports.is_empty() {
+                            resolved.extend(rust_imports.into_iter().filter_map(|mut import| {
+                                import.kind = classify_import(
+                                    &imp.path,
+                                    lang,
+                                    path_str,
+                                    Some(import.target.as_str()),
+                                    &workspace,
+                                );
+                                (!internal_only || import.kind == EdgeKind::Internal)
+                                    .then_some(import)
+                            }));
+                            continue;
+                        }
+                    }
+
+                    let target = if self.config.resolve_imports
+                        && !is_stdlib_import(&imp.path, lang, &workspace.go_modules)
+                    {
+                        file_index.find_match(&dynamic_lookup_path(imp, lang), &file.rel_path)
+                    } else {
+                        None
+                    };
+                    let kind =
+                        classify_import(&imp.path, lang, path_str, target.as_deref(), &workspace);
+                    if internal_only && kind != EdgeKind::Internal {
+                        continue;
+                    }
+                    let target = match target {
+                        Some(target) => target,
+                        None if include_external || !self.config.resolve_imports => {
+                            imp.path.clone()
+                        }
+                        None => continue,
+                    };
+                    resolved.push(ResolvedImport {
+                        target,
+                        kind,
+                        line: imp.line,
+                        names: names.clone(),
+                        dynamic: imp.dynamic,
+                        symbols: Vec::new(),
+                    });
+                }
+
+                ParsedFile {
+                    path: file.path,
+                    imports: resolved,
+                    reexports: file.reexports,
+                    exports: file.exports,
+                    types: file.types,
+                }
+            })
+            .collect();
+
+        let mut barrels = Barrels::default();
+        for file in &file_imports {
+            if let Some(reexports) = &file.reexports {
+                barrels.insert(file.path.clone(), reexports.clone());
+            }
+            if let Some(exports) = &file.exports {
+                barrels.insert_exports(file.path.clone(), exports.clone());
+            }
+        }
+
+        // Build graph (sequential, but fast since parsing is done)
+        let mut graph: DiGraph<String, EdgeKind> =
+            DiGraph::with_capacity(files.len(), files.len() * 4);
+        let mut node_indices: HashMap<String, NodeIndex> = HashMap::with_capacity(files.len());
+        let mut import_lines: HashMap<(NodeIndex, NodeIndex), u32> = HashMap::new();
+        let mut dynamic_edges: HashSet<(NodeIndex, NodeIndex)> = HashSet::new();
+        let mut edge_symbols: HashMap<(NodeIndex, NodeIndex), Vec<String>> = HashMap::new();
+
+        // First pass: create all nodes (collapsed barrels get none)
+        for file in &file_imports {
+            if !node_indices.contains_key(&file.path) && !barrels.contains(&file.path) {
+                let idx = graph.add_node(file.path.clone());
+                node_indices.insert(file.path.clone(), idx);
+            }
+        }
+
+        // Second pass: create edges
+        for file in &file_imports {
+            let Some(&from_idx) = node_indices.get(&file.path) else {
+                continue;
+            };
+
+            for import in &file.imports {
+                // Imports of a barrel point at the files defining the imported names
+                let targets = if barrels.contains(&import.target) {
+                    barrels.collapse(&import.target, import.names.as_deref())
+                } else {
+                    vec![import.target.clone()]
+                };
+
+                for target in &targets {
+                    // Add target node if not exists (external dependency)
+                    let to_idx = if let Some(&idx) = node_indices.get(target) {
+                        idx
+                    } else if include_external {
+                        let idx = graph.add_node(target.clone());
+                        node_indices.insert(target.clone(), idx);
+                        idx
+                    } else {
+                        continue;
+                    };
+
+                    // Add edge (avoid self-loops)
+                    if from_idx == to_idx {
+                        continue;
+                    }
+                    let pair = (from_idx, to_idx);
+                    if !import.symbols.is_empty() {
+                        let symbols = edge_symbols.entry(pair).or_default();
+                        for symbol in &import.symbols {
+                            if !symbols.contains(symbol) {
+                                symbols.push(symbol.clone());
+                            }
+                        }
+                    }
+                    if !graph.contains_edge(from_idx, to_idx) {
+                        graph.add_edge(from_idx, to_idx, import.kind);
+                        import_lines.insert(pair, import.line);
+                        if import.dynamic {
+                            dynamic_edges.insert(pair);
+                        }
+                    } else if !import.dynamic && dynamic_edges.remove(&pair) {
+                        // A static import of the same file makes the edge a hard one
+                        import_lines.insert(pair, import.line);
+                    }
+                }
+            }
+        }
+
+        // Calculate metrics
+        let pagerank = self.calculate_pagerank(&graph);
+        let betweenness = self.calculate_betweenness(&graph);
+        // Cycles only run through static imports unless asked otherwise; keeping
+        // every node leaves node indices unchanged.
+        let cycle_graph = graph.filter_map(
+            |_, path| Some(path.clone()),
+            |edge, kind| {
+                let pair = graph.edge_endpoints(edge)?;
+                (dynamic_cycles || !dynamic_edges.contains(&pair)).then_some(*kind)
+            },
+        );
+        let cycles = self.detect_cycles(&cycle_graph);
+        let cycle_breaks: Vec<CycleBreak> = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, cycle)| {
+                let members: Vec<NodeIndex> = cycle.iter().map(|p| node_indices[p]).collect();
+                feedback_arcs(&cycle_graph, &members)
+                    .into_iter()
+                    .map(|(from, to)| CycleBreak {
+                        cycle: i,
+                        from: graph[from].clone(),
+                        to: graph[to].clone(),
+                        line: import_lines.get(&(from, to)).copied().unwrap_or(0),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        // Build nodes with metrics
+        let mut nodes: Vec<Node> = Vec::new();
+        for (path, &idx) in &node_indices {
+            let in_deg = graph.edges_directed(idx, Direction::Incoming).count();
+            let out_deg = graph.edges_directed(idx, Direction::Outgoing).count();
+            let total_deg = in_deg + out_deg;
+            let instability = if total_deg > 0 {
+                out_deg as f64 / total_deg as f64
+            } else {
+                0.0
+            };
+
+            nodes.push(Node {
+                path: path.clone(),
+                pagerank: *pagerank.get(&idx).unwrap_or(&0.0),
+                betweenness: *betweenness.get(&idx).unwrap_or(&0.0),
+                in_degree: in_deg,
+                out_degree: out_deg,
+                instability,
+            });
+        }
+
+        // Sort by PageRank descending
+        nodes.sort_by(|a, b| {
+            b.pagerank
+                .partial_cmp(&a.pagerank)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // Build edges list
+        let edges: Vec<Edge> = graph
+            .edge_references()
+            .map(|e| {
+                let from = &graph[e.source()];
+                let to = &graph[e.target()];
+                Edge {
+                    from: from.clone(),
+                    to: to.clone(),
+                    kind: *e.weight(),
+                    dynamic: dynamic_edges.contains(&(e.source(), e.target())),
+                    symbols: edge_symbols
+                        .remove(&(e.source(), e.target()))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        // Package graph over the analyzed files left in the graph
+        let packages = aggregate_packages.then(|| {
+            let files: HashMap<String, TypeCounts> = file_imports
+                .iter()
+                .filter(|file| node_indices.contains_key(&file.path))
+                .map(|file| (file.path.clone(), file.types.unwrap_or_default()))
+                .collect();
+            packages::aggregate(
+                &files,
+                edges.iter().map(|e| (e.from.as_str(), e.to.as_str())),
+            )
+        });
+
+        let count_kind = |kind: EdgeKind| edges.iter().filter(|e| e.kind == kind).count();
+
+        // Calculate summary
+        let total_nodes = nodes.len();
+        let total_edges = edges.len();
+        let avg_degree = if total_nodes > 0 {
+            (2.0 * total_edges as f64) / total_nodes as f64
+        } else {
+            0.0
+        };
+
+        Ok(Analysis {
+            nodes,
+            edges,
+            cycles,
+            cycle_breaks,
+            barrels: barrels.paths(),
+            packages,
+            diagram: None,
+            summary: AnalysisSummary {
+                total_nodes,
+                total_edges,
+                avg_degree,
+                cycle_count: 0, // Will be set from cycles.len()
+                internal_edges: count_kind(EdgeKind::Internal),
+                workspace_external_edges: count_kind(EdgeKind::WorkspaceExternal),
+                stdlib_edges: count_kind(EdgeKind::Stdlib),
+                third_party_edges: count_kind(EdgeKind::ThirdParty),
+                dynamic_edges: edges.iter().filter(|e| e.dynamic).count(),
+                collapsed_barrels: barrels.len(),
+            },
+        })
+    }
+
+    /// Calculate PageRank scores using power iteration.
+    fn calculate_pagerank<E>(&self, graph: &DiGraph<String, E>) -> HashMap<NodeIndex, f64> {
+        let n = graph.node_count();
+        if n == 0 {
+            return HashMap::new();
+        }
+
+        let d = self.config.damping;
+        let mut rank: HashMap<NodeIndex, f64> = graph
+            .node_indices()
+            .map(|idx| (idx, 1.0 / n as f64))
+            .collect();
+
+        for _ in 0..self.config.max_iterations {
+            let mut new_rank: HashMap<NodeIndex, f64> = HashMap::with_capacity(n);
+            let mut diff = 0.0;
+
+            for node in graph.node_indices() {
+                let incoming: f64 = graph
+                    .edges_directed(node, Direction::Incoming)
+                    .map(|e| {
+                        let source = e.source();
+                        let out_deg = graph.edges_directed(source, Direction::Outgoing).count();
+                        if out_deg > 0 {
+                            rank[&source] / out_deg as f64
+                        } else {
+                            0.0
+                        }
+                    })
+                    .sum();
+
+                let new_score = (1.0 - d) / n as f64 + d * incoming;
+                diff += (new_score - rank[&node]).abs();
+                new_rank.insert(node, new_score);
+            }
+
+            rank = new_rank;
+
+            if diff < self.config.tolerance {
+                break;
+            }
+        }
+
+        rank
+    }
+
+    /// Calculate betweenness centrality using Brandes' algorithm with parallel BFS.
+    fn calculate_betweenness<E: Sync>(
+        &self,
+        graph: &DiGraph<String, E>,
+    ) -> HashMap<NodeIndex, f64> {
+        let n = graph.node_count();
+        if n <= 2 {
+            return graph.node_indices().map(|idx| (idx, 0.0)).collect();
+        }
+
+        // Use all nodes as sources (no sampling - per project requirements)
+        let sources: Vec<NodeIndex> = graph.node_indices().collect();
+
+        // Parallel betweenness calculation
+        let partial_betweenness: Vec<HashMap<NodeIndex, f64>> = sources
+            .par_iter()
+            .map(|&source| {
+                let mut local_betweenness: HashMap<NodeIndex, f64> = HashMap::new();
+                let mut dist: HashMap<NodeIndex, i32> = HashMap::with_capacity(n);
+                let mut paths: HashMap<NodeIndex, f64> = HashMap::with_capacity(n);
+                let mut predecessors: HashMap<NodeIndex, Vec<NodeIndex>> =
+                    HashMap::with_capacity(n);
+                let mut stack: Vec<NodeIndex> = Vec::with_capacity(n);
+                let mut queue: VecDeque<NodeIndex> = VecDeque::with_capacity(n);
+
+                dist.insert(source, 0);
+                paths.insert(source, 1.0);
+                queue.push_back(source);
+
+                // BFS
+                while let Some(v) = queue.pop_front() {
+                    stack.push(v);
+                    let v_dist = dist[&v];
+
+                    for edge in graph.edges_directed(v, Direction::Outgoing) {
+                        let w = edge.target();
+
+                        // First visit
+                        if let std::collections::hash_map::Entry::Vacant(e) = dist.entry(w) {
+                            e.insert(v_dist + 1);
+                            queue.push_back(w);
+                        }
+
+                        // Shortest path via v
+                        if dist[&w] == v_dist + 1 {
+                            *paths.entry(w).or_insert(0.0) += *paths.get(&v).unwrap_or(&0.0);
+                            predecessors.entry(w).or_default().push(v);
+                        }
+                    }
+                }
+
+                // Accumulate dependencies
+                let mut delta: HashMap<NodeIndex, f64> = HashMap::with_capacity(n);
+                while let Some(w) = stack.pop() {
+                    if let Some(preds) = predecessors.get(&w) {
+                        for &v in preds {
+                            let coeff = (paths.get(&v).unwrap_or(&0.0)
+                                / paths.get(&w).unwrap_or(&1.0))
+                                * (1.0 + delta.get(&w).unwrap_or(&0.0));
+                            *delta.entry(v).or_insert(0.0) += coeff;
+                        }
+                    }
+                    if w != source {
+                        *local_betweenness.entry(w).or_insert(0.0) += delta.get(&w).unwrap_or(&0.0);
+                    }
+                }
+
+                local_betweenness
+            })
+            .collect();
+
+        // Merge partial results
+        let mut betweenness: HashMap<NodeIndex, f64> =
+            graph.node_indices().map(|idx| (idx, 0.0)).collect();
+
+        for partial in partial_betweenness {
+            for (idx, value) in partial {
+                *betweenness.entry(idx).or_insert(0.0) += value;
+            }
+        }
+
+        // Normalize betweenness scores
+        let norm = if n > 2 {
+            1.0 / ((n - 1) * (n - 2)) as f64
+        } else {
+            1.0
+        };
+
+        for value in betweenness.values_mut() {
+            *value *= norm;
+        }
+
+        betweenness
+    }
+
+    /// Detect cycles using Tarjan's strongly connected components.
+    fn detect_cycles<E>(&self, graph: &DiGraph<String, E>) -> Vec<Vec<String>> {
+        let sccs = tarjan_scc(graph);
+
+        sccs.into_iter()
+            .filter(|scc| {
+                // Only include SCCs with multiple nodes or self-loops
+                scc.len() > 1 || (scc.len() == 1 && graph.contains_edge(scc[0], scc[0]))
+            })
+            .map(|scc| scc.into_iter().map(|idx| graph[idx].clone()).collect())
+            .collect()
+    }
+
+    /// Render the graph as a diagram in `format`.
+    pub fn render(&self, analysis: &Analysis, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(analysis),
+            GraphFormat::Graphml => self.to_graphml(analysis),
+            GraphFormat::Mermaid => self.to_mermaid(analysis),
+        }
+    }
+
+    /// Generate Mermaid diagram.
+    ///
+    /// Cycle nodes are filled, edges inside a cycle drawn thick and red, and
+    /// suggested cycle breaks dotted and labelled `break`.
+    pub fn to_mermaid(&self, analysis: &Analysis) -> String {
+        let mut output = String::from("graph TD\n");
+        let marks = CycleMarks::new(analysis);
+
+        // Create node definitions with sanitized IDs
+        let mut node_ids: HashMap<&str, String> = HashMap::new();
+        for (i, node) in analysis.nodes.iter().enumerate() {
+            let id = format!("n{i}");
+            node_ids.insert(&node.path, id.clone());
+
+            // Format label with metrics
+            let label = format!(
+                "{}\\nPR:{:.3} In:{} Out:{}",
+                sanitize_mermaid_label(&node.path),
+                node.pagerank,
+                node.in_degree,
+                node.out_degree
+            );
+            output.push_str(&format!("    {id}[\"{label}\"]\n"));
+        }
+
+        // Add edges, remembering which links close a cycle
+        let mut cycle_links = Vec::new();
+        let mut link = 0;
+        for edge in &analysis.edges {
+            if let (Some(from_id), Some(to_id)) = (
+                node_ids.get(edge.from.as_str()),
+                node_ids.get(edge.to.as_str()),
+            ) {
+                let arrow = if marks.is_break(edge) {
+                    "-.->|break|"
+                } else if marks.in_cycle(edge) {
+                    "==>"
+                } else {
+                    "-->"
+                };
+                output.push_str(&format!("    {} {} {}\n", from_id, arrow, to_id));
+                if marks.in_cycle(edge) {
+                    cycle_links.push(link);
+                }
+                link += 1;
+            }
+        }
+
+        // Style cycle nodes
+        if !analysis.cycles.is_empty() {
+            output.push_str("\n    %% Cycle nodes\n");
+            let cycle_nodes: HashSet<&str> = analysis
+                .cycles
+                .iter()
+                .flatten()
+                .map(|s| s.as_str())
+                .collect();
+
+            for node in &cycle_nodes {
+                if let Some(id) = node_ids.get(node) {
+                    output.push_str(&format!("    style {id} fill:#f96\n"));
+                }
+            }
+        }
+        if !cycle_links.is_empty() {
+            let links: Vec<String> = cycle_links.iter().map(|l| l.to_string()).collect();
+            output.push_str(&format!(
+                "    linkStyle {} stroke:#d33,stroke-width:2px\n",
+                links.join(",")
+            ));
+        }
+
+        output
+    }
+
+    /// Generate DOT format (Graphviz).
+    ///
+    /// Cycle nodes are filled, edges inside a cycle drawn red, and suggested
+    /// cycle breaks dashed and labelled `break`.
+    pub fn to_dot(&self, analysis: &Analysis) -> String {
+        let mut output = String::from("digraph G {\n");
+        output.push_str("    rankdir=LR;\n");
+        output.push_str("    node [shape=box];\n\n");
+        let marks = CycleMarks::new(analysis);
+
+        // Create node definitions
+        let mut node_ids: HashMap<&str, String> = HashMap::new();
+        for (i, node) in analysis.nodes.iter().enumerate() {
+            let id = format!("n{i}");
+            node_ids.insert(&node.path, id.clone());
+
+            let label = format!(
+                "{}\\nPageRank: {:.3}\\nIn: {} Out: {}",
+                node.path.replace('"', "\\\""),
+                node.pagerank,
+                node.in_degree,
+                node.out_degree
+            );
+            let style = if marks.cycle_of(&node.path).is_some() {
+                ", style=filled, fillcolor=\"#ff9966\""
+            } else {
+                ""
+            };
+            output.push_str(&format!("    {id} [label=\"{label}\"{style}];\n"));
+        }
+
+        output.push('\n');
+
+        // Add edges
+        for edge in &analysis.edges {
+            if let (Some(from_id), Some(to_id)) = (
+                node_ids.get(edge.from.as_str()),
+                node_ids.get(edge.to.as_str()),
+            ) {
+                let style = match (marks.in_cycle(edge), marks.is_break(edge)) {
+                    (_, true) => " [color=\"#dd3333\", style=dashed, label=\"break\"]",
+                    (true, false) => " [color=\"#dd3333\", penwidth=2]",
+                    (false, false) => "",
+                };
+                output.push_str(&format!("    {} -> {}{};\n", from_id, to_id, style));
+            }
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Generate GraphML, keeping node metrics and edge classification as
+    /// data attributes. Nodes in a cycle carry its index in `cycles`;
+    /// edges are flagged `in_cycle` and `cycle_break`.
+    pub fn to_graphml(&self, analysis: &Analysis) -> String {
+        const KEYS: &[(&str, &str, &str)] = &[
+            ("path", "node", "string"),
+            ("pagerank", "node", "double"),
+            ("betweenness", "node", "double"),
+            ("in_degree", "node", "int"),
+            ("out_degree", "node", "int"),
+            ("instability", "node", "double"),
+            ("kind", "edge", "string"),
+            ("dynamic", "edge", "boolean"),
+            ("in_cycle", "edge", "boolean"),
+            ("cycle_break", "edge", "boolean"),
+        ];
+
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (name, domain, kind) in KEYS {
+            output.push_str(&format!(
+                "  <key id=\"{name}\" for=\"{domain}\" \
+                 attr.name=\"{name}\" attr.type=\"{kind}\"/>\n"
+            ));
+        }
+        output.push_str(
+            "  <key id=\"cycle\" for=\"node\" attr.name=\"cycle\" attr.type=\"int\">\
+             <default>-1</default></key>\n",
+        );
+        output.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+        let marks = CycleMarks::new(analysis);
+
+        let mut node_ids: HashMap<&str, String> = HashMap::new();
+        for (i, node) in analysis.nodes.iter().enumerate() {
+            let id = format!("n{i}");
+            node_ids.insert(&node.path, id.clone());
+
+            output.push_str(&format!("    <node id=\"{id}\">\n"));
+            output.push_str(&format!(
+                "      <data key=\"path\">{}</data>\n",
+                escape_xml(&node.path)
+            ));
+            output.push_str(&format!(
+                "      <data key=\"pagerank\">{}</data>\n",
+                node.pagerank
+            ));
+            output.push_str(&format!(
+                "      <data key=\"betweenness\">{}</data>\n",
+                node.betweenness
+            ));
+            output.push_str(&format!(
+                "      <data key=\"in_degree\">{}</data>\n",
+                node.in_degree
+            ));
+            output.push_str(&format!(
+                "      <data key=\"out_degree\">{}</data>\n",
+                node.out_degree
+            ));
+            output.push_str(&format!(
+                "      <data key=\"instability\">{}</data>\n",
+                node.instability
+            ));
+            if let Some(cycle) = marks.cycle_of(&node.path) {
+                output.push_str(&format!("      <data key=\"cycle\">{cycle}</data>\n"));
+            }
+            output.push_str("    </node>\n");
+        }
+
+        for edge in &analysis.edges {
+            if let (Some(from_id), Some(to_id)) = (
+                node_ids.get(edge.from.as_str()),
+                node_ids.get(edge.to.as_str()),
+            ) {
+                output.push_str(&format!(
+                    "    <edge source=\"{from_id}\" target=\"{to_id}\">\n"
+                ));
+                output.push_str(&format!(
+                    "      <data key=\"kind\">{}</data>\n",
+                    edge.kind.as_str()
+                ));
+                output.push_str(&format!(
+                    "      <data key=\"dynamic\">{}</data>\n",
+                    edge.dynamic
+                ));
+                output.push_str(&format!(
+                    "      <data key=\"in_cycle\">{}</data>\n",
+                    marks.in_cycle(edge)
+                ));
+                output.push_str(&format!(
+                    "      <data key=\"cycle_break\">{}</data>\n",
+                    marks.is_break(edge)
+                ));
+                output.push_str("    </edge>\n");
+            }
+        }
+
+        output.push_str("  </graph>\n</graphml>\n");
+        output
+    }
+}
+
+/// Cycle membership, for highlighting cycles in diagrams.
+struct CycleMarks<'a> {
+    /// Index in `cycles` of each node on a cycle.
+    nodes: HashMap<&'a str, usize>,
+    /// Imports suggested to break a cycle, as (from, to).
+    breaks: HashSet<(&'a str, &'a str)>,
+}
+
+impl<'a> CycleMarks<'a> {
+    fn new(analysis: &'a Analysis) -> Self {
+        let mut nodes = HashMap::new();
+        for (i, cycle) in analysis.cycles.iter().enumerate() {
+            for path in cycle {
+                nodes.insert(path.as_str(), i);
+            }
+        }
+        let breaks = analysis
+            .cycle_breaks
+            .iter()
+            .map(|b| (b.from.as_str(), b.to.as_str()))
+            .collect();
+        Self { nodes, breaks }
+    }
+
+    fn cycle_of(&self, path: &str) -> Option<usize> {
+        self.nodes.get(path).copied()
+    }
+
+    /// Whether both ends of `edge` are on the same cycle.
+    fn in_cycle(&self, edge: &Edge) -> bool {
+        match (self.cycle_of(&edge.from), self.cycle_of(&edge.to)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    fn is_break(&self, edge: &Edge) -> bool {
+        self.breaks
+            .contains(&(edge.from.as_str(), edge.to.as_str()))
+    }
+}
+
+fn sanitize_mermaid_label(s: &str) -> String {
+    s.replace(['/', '.', '-'], "_").replace('"', "'")
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "graph"
+    }
+
+    fn description(&self) -> &'static str {
+        "Map module dependencies, calculate PageRank/centrality"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let mut analysis = self.analyze_files(ctx)?;
+        analysis.summary.cycle_count = analysis.cycles.len();
+        analysis.diagram = ctx.config.graph.format.map(|format| Diagram {
+            format,
+            content: self.render(&analysis, format),
+        });
+        Ok(analysis)
+    }
+}
+
+/// Edges within one strongly connected component whose removal leaves it
+/// acyclic.
+///
+/// Orders the component with the Eades-Lin-Smyth greedy heuristic (peel
+/// sinks to the back, sources to the front, otherwise the node with the
+/// largest out-in degree surplus) and takes the edges pointing backwards.
+/// Each of those is then restored if doing so doesn't close a cycle, so no
+/// suggested edge is redundant. Ties are broken by path for stable output.
+fn feedback_arcs<E>(
+    graph: &DiGraph<String, E>,
+    members: &[NodeIndex],
+) -> Vec<(NodeIndex, NodeIndex)> {
+    let in_scc: HashSet<NodeIndex> = members.iter().copied().collect();
+    let mut nodes = members.to_vec();
+    nodes.sort_by(|a, b| graph[*a].cmp(&graph[*b]));
+    let mut edges: Vec<(NodeIndex, NodeIndex)> = nodes
+        .iter()
+        .flat_map(|&n| {
+            graph
+                .edges_directed(n, Direction::Outgoing)
+                .map(move |e| (n, e.target()))
+        })
+        .filter(|(_, to)| in_scc.contains(to))
+        .collect();
+    edges.sort_by(|a, b| (&graph[a.0], &graph[a.1]).cmp(&(&graph[b.0], &graph[b.1])));
+
+    let mut out_deg: HashMap<NodeIndex, usize> = HashMap::new();
+    let mut in_deg: HashMap<NodeIndex, usize> = HashMap::new();
+    for &(from, to) in &edges {
+        *out_deg.entry(from).or_default() += 1;
+        *in_deg.entry(to).or_default() += 1;
+    }
+
+    let mut remaining = in_scc;
+    let mut head = Vec::with_capacity(nodes.len());
+    let mut tail = Vec::new();
+    while !remaining.is_empty() {
+        let live_set = &remaining;
+        let live = || nodes.iter().copied().filter(move |n| live_set.contains(n));
+        let out = |n: &NodeIndex| out_deg.get(n).copied().unwrap_or(0);
+        let inn = |n: &NodeIndex| in_deg.get(n).copied().unwrap_or(0);
+        let next = if let Some(sink) = live().find(|n| out(n) == 0) {
+            tail.push(sink);
+            sink
+        } else {
+            // `max_by_key` keeps the last maximum; reversing keeps the first by path.
+            let pick = live().find(|n| inn(n) == 0).unwrap_or_else(|| {
+                live()
+                    .rev()
+                    .max_by_key(|n| out(n) as isize - inn(n) as isize)
+                    .expect("remaining is non-empty")
+            });
+            head.push(pick);
+            pick
+        };
+        remaining.remove(&next);
+        for &(from, to) in &edges {
+            if from == next && remaining.contains(&to) {
+                *in_deg.entry(to).or_default() -= 1;
+            } else if to == next && remaining.contains(&from) {
+                *out_deg.entry(from).or_default() -= 1;
+            }
+        }
+    }
+    head.extend(tail.into_iter().rev());
+    let position: HashMap<NodeIndex, usize> =
+        head.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+    let (backward, mut kept): (Vec<_>, Vec<_>) = edges
+        .into_iter()
+        .partition(|(from, to)| position[from] >= position[to]);
+
+    let mut arcs = Vec::new();
+    for (from, to) in backward {
+        if reachable(&kept, to, from) {
+            arcs.push((from, to));
+        } else {
+            kept.push((from, to));
+        }
+    }
+    arcs
+}
+
+/// Whether `target` can be reached from `start` over `edges`.
+fn reachable(edges: &[(NodeIndex, NodeIndex)], start: NodeIndex, target: NodeIndex) -> bool {
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if node == target {
+            return true;
+        }
+        for &(from, to) in edges {
+            if from == node && seen.insert(to) {
+                queue.push_back(to);
+            }
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub cycles: Vec<Vec<String>>,
+    /// Imports to remove to break each cycle; see [`feedback_arcs`].
+    #[serde(default)]
+    pub cycle_breaks: Vec<CycleBreak>,
+    /// Barrel files collapsed out of the graph (with `collapse_barrels`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub barrels: Vec<String>,
+    /// Files grouped into directory-level packages (with `packages`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packages: Option<PackageGraph>,
+    /// The graph rendered in the configured `graph.format`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagram: Option<Diagram>,
+    pub summary: AnalysisSummary,
+}
+
+/// The dependency graph rendered for another tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagram {
+    pub format: GraphFormat,
+    pub content: String,
+}
+
+/// An import whose removal breaks a dependency cycle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CycleBreak {
+    /// Index into `cycles`.
+    pub cycle: usize,
+    /// File containing the import.
+    pub from: String,
+    /// Imported file.
+    pub to: String,
+    /// Line of the import statement in `from`.
+    pub line: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Node {
+    pub path: String,
+    pub pagerank: f64,
+    pub betweenness: f64,
+    pub in_degree: usize,
+    pub out_degree: usize,
+    pub instability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub kind: EdgeKind,
+    /// Runtime import (`import()`, `importlib`, `Class.forName`); left out
+    /// of cycle detection unless `dynamic_cycles` is set.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dynamic: bool,
+    /// Items the importing file takes from the target, where known (Rust
+    /// `use` paths resolved to their definitions).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisSummary {
+    pub total_nodes: usize,
+    pub total_edges: usize,
+    pub avg_degree: f64,
+    pub cycle_count: usize,
+    #[serde(default)]
+    pub internal_edges: usize,
+    #[serde(default)]
+    pub workspace_external_edges: usize,
+    #[serde(default)]
+    pub stdlib_edges: usize,
+    #[serde(default)]
+    pub third_party_edges: usize,
+    #[serde(default)]
+    pub dynamic_edges: usize,
+    #[serde(default)]
+    pub collapsed_barrels: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyzer_creation() {
+        let analyzer = Analyzer::new();
+        assert_eq!(analyzer.name(), "graph");
+    }
+
+    #[test]
+    fn test_config_default() {
+        let config = Config::default();
+        assert!((config.damping - 0.85).abs() < 0.001);
+        assert_eq!(config.max_iterations, 100);
+        assert!((config.tolerance - 1e-6).abs() < 1e-10);
+        assert!(!config.collapse_barrels);
+    }
+
+    #[test]
+    fn test_pagerank_empty_graph() {
+        let analyzer = Analyzer::new();
+        let graph: DiGraph<String, ()> = DiGraph::new();
+        let ranks = analyzer.calculate_pagerank(&graph);
+        assert!(ranks.is_empty());
+    }
+
+    #[test]
+    fn test_pagerank_single_node() {
+        let analyzer = Analyzer::new();
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        graph.add_node("a.rs".to_string());
+        let ranks = analyzer.calculate_pagerank(&graph);
+        assert_eq!(ranks.len(), 1);
+        // Single node with no incoming edges converges to (1-d)/n = 0.15
+        for &rank in ranks.values() {
+            assert!((rank - 0.15).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_pagerank_two_nodes_with_edge() {
+        let analyzer = Analyzer::new();
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        let a = graph.add_node("a.rs".to_string());
+        let b = graph.add_node("b.rs".to_string());
+        graph.add_edge(a, b, ());
+
+        let ranks = analyzer.calculate_pagerank(&graph);
+        assert_eq!(ranks.len(), 2);
+
+        // Node b should have higher PageRank (receives link from a)
+        let rank_a = ranks[&a];
+        let rank_b = ranks[&b];
+        assert!(rank_b > rank_a, "Node b should have higher PageRank");
+    }
+
+    #[test]
+    fn test_betweenness_empty() {
+        let analyzer = Analyzer::new();
+        let graph: DiGraph<String, ()> = DiGraph::new();
+        let betweenness = analyzer.calculate_betweenness(&graph);
+        assert!(betweenness.is_empty());
+    }
+
+    #[test]
+    fn test_betweenness_linear_graph() {
+        let analyzer = Analyzer::new();
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        let a = graph.add_node("a.rs".to_string());
+        let b = graph.add_node("b.rs".to_string());
+        let c = graph.add_node("c.rs".to_string());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+
+        let betweenness = analyzer.calculate_betweenness(&graph);
+        // Node b is on all shortest paths from a to c
+        assert!(
+            betweenness[&b] > 0.0,
+            "Central node should have positive betweenness"
+        );
+    }
+
+    #[test]
+    fn test_cycle_detection_no_cycle() {
+        let analyzer = Analyzer::new();
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        let a = graph.add_node("a.rs".to_string());
+        let b = graph.add_node("b.rs".to_string());
+        graph.add_edge(a, b, ());
+
+        let cycles = analyzer.detect_cycles(&graph);
+        assert!(cycles.is_empty());
+    }
+
+    #[test]
+    fn test_cycle_detection_with_cycle() {
+        let analyzer = Analyzer::new();
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        let a = graph.add_node("a.rs".to_string());
+        let b = graph.add_node("b.rs".to_string());
+        let c = graph.add_node("c.rs".to_string());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(c, a, ());
+
+        let cycles = analyzer.detect_cycles(&graph);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 3);
+    }
+
+    #[test]
+    fn test_cycle_detection_self_loop() {
+        let analyzer = Analyzer::new();
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        let a = graph.add_node("a.rs".to_string());
+        graph.add_edge(a, a, ());
+
+        let cycles = analyzer.detect_cycles(&graph);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 1);
+    }
+
+    fn graph_of(edges: &[(&str, &str)]) -> (DiGraph<String, ()>, HashMap<String, NodeIndex>) {
+        let mut graph = DiGraph::new();
+        let mut index = HashMap::new();
+        for (from, to) in edges {
+            let a = *index
+                .entry(from.to_string())
+                .or_insert_with(|| graph.add_node(from.to_string()));
+            let b = *index
+                .entry(to.to_string())
+                .or_insert_with(|| graph.add_node(to.to_string()));
+            graph.add_edge(a, b, ());
+        }
+        (graph, index)
+    }
+
+    fn arc_names(graph: &DiGraph<String, ()>, arcs: &[(NodeIndex, NodeIndex)]) -> Vec<String> {
+        arcs.iter()
+            .map(|(a, b)| format!("{}->{}", graph[*a], graph[*b]))
+            .collect()
+    }
+
+    #[test]
+    fn test_feedback_arcs_simple_cycle() {
+        let (graph, _) = graph_of(&[("a.rs", "b.rs"), ("b.rs", "c.rs"), ("c.rs", "a.rs")]);
+        let members: Vec<NodeIndex> = graph.node_indices().collect();
+        let arcs = feedback_arcs(&graph, &members);
+        assert_eq!(arcs.len(), 1);
+    }
+
+    #[test]
+    fn test_feedback_arcs_breaks_every_cycle_minimally() {
+        // Two cycles sharing b.rs: a <-> b and b -> c -> d -> b
+        let (graph, _) = graph_of(&[
+            ("a.rs", "b.rs"),
+            ("b.rs", "a.rs"),
+            ("b.rs", "c.rs"),
+            ("c.rs", "d.rs"),
+            ("d.rs", "b.rs"),
+        ]);
+        let members: Vec<NodeIndex> = graph.node_indices().collect();
+        let arcs = feedback_arcs(&graph, &members);
+        assert_eq!(arcs.len(), 2, "{:?}", arc_names(&graph, &arcs));
+
+        let kept: Vec<(NodeIndex, NodeIndex)> = graph
+            .edge_references()
+            .map(|e| (e.source(), e.target()))
+            .filter(|e| !arcs.contains(e))
+            .collect();
+        for &(from, to) in &kept {
+            assert!(!reachable(&kept, to, from), "remaining edges still cyclic");
+        }
+        // Minimal: restoring any suggested edge brings a cycle back.
+        for &(from, to) in &arcs {
+            assert!(reachable(&kept, to, from));
+        }
+    }
+
+    #[test]
+    fn test_feedback_arcs_deterministic() {
+        let edges = [("x.rs", "y.rs"), ("y.rs", "x.rs")];
+        let (graph, _) = graph_of(&edges);
+        let members: Vec<NodeIndex> = graph.node_indices().collect();
+        let first = arc_names(&graph, &feedback_arcs(&graph, &members));
+        let mut reversed = members.clone();
+        reversed.reverse();
+        assert_eq!(first, arc_names(&graph, &feedback_arcs(&graph, &reversed)));
+        assert_eq!(first, vec!["y.rs->x.rs"]);
+    }
+
+    #[test]
+    fn test_cycle_breaks_report_import_line() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("a.js"), "import { b } from './b';\n").unwrap();
+        std::fs::write(
+            root.join("b.js"),
+            "import fs from 'fs';\nimport { a } from './a';\n",
+        )
+        .unwrap();
+
+        let analysis = Analyzer::new().analyze_project(root).unwrap();
+
+        assert_eq!(analysis.cycles.len(), 1);
+        assert_eq!(
+            analysis.cycle_breaks,
+            vec![CycleBreak {
+                cycle: 0,
+                from: "b.js".to_string(),
+                to: "a.js".to_string(),
+                line: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_imports_are_weak_edges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("a.js"), "import { b } from './b';\n").unwrap();
+        std::fs::write(
+            root.join("b.js"),
+            "export async function load() {\n  return import('./a');\n}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("plugins.py"), "def run(): pass\n").unwrap();
+        std::fs::write(
+            root.join("main.py"),
+            "import importlib\nplugins = importlib.import_module('plugins')\n",
+        )
+        .unwrap();
+
+        let analysis = Analyzer::new().analyze_project(root).unwrap();
+        let dynamic_of = |from: &str, to: &str| {
+            analysis
+                .edges
+                .iter()
+                .find(|e| e.from == from && e.to == to)
+                .map(|e| e.dynamic)
+        };
+        assert_eq!(dynamic_of("a.js", "b.js"), Some(false));
+        assert_eq!(dynamic_of("b.js", "a.js"), Some(true));
+        assert_eq!(dynamic_of("main.py", "plugins.py"), Some(true));
+        assert_eq!(analysis.summary.dynamic_edges, 2);
+        assert!(analysis.cycles.is_empty(), "lazy import breaks the cycle");
+
+        let analyzer = Analyzer::with_config(Config {
+            dynamic_cycles: true,
+            ..Config::default()
+        });
+        let analysis = analyzer.analyze_project(root).unwrap();
+        assert_eq!(analysis.cycles.len(), 1);
+        assert_eq!(analysis.cycle_breaks.len(), 1);
+    }
+
+    fn edge_pairs(analysis: &Analysis) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = analysis
+            .edges
+            .iter()
+            .map(|e| (e.from.clone(), e.to.clone()))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    fn pair(from: &str, to: &str) -> (String, String) {
+        (from.to_string(), to.to_string())
+    }
+
+    #[test]
+    fn test_collapse_typescript_barrel() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("models")).unwrap();
+        std::fs::write(root.join("models/user.ts"), "export class User {}\n").unwrap();
+        std::fs::write(root.join("models/order.ts"), "export class Order {}\n").unwrap();
+        std::fs::write(
+            root.join("models/index.ts"),
+            "export * from './user';\nexport * from './order';\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("a.ts"), "import { User } from './models';\n").unwrap();
+        std::fs::write(root.join("b.ts"), "import { Order } from './models';\n").unwrap();
+        std::fs::write(
+            root.join("c.ts"),
+            "import { User, Order } from './models';\n",
+        )
+        .unwrap();
+
+        let hub = Analyzer::new().analyze_project(root).unwrap();
+        let index = hub.nodes.iter().find(|n| n.path == "models/index.ts");
+        assert_eq!(index.map(|n| n.in_degree), Some(3));
+        assert!(hub.barrels.is_empty());
+
+        let analyzer = Analyzer::with_config(Config {
+            collapse_barrels: true,
+            ..Default::default()
+        });
+        let analysis = analyzer.analyze_project(root).unwrap();
+
+        assert_eq!(analysis.barrels, vec!["models/index.ts".to_string()]);
+        assert_eq!(analysis.summary.collapsed_barrels, 1);
+        assert!(analysis.nodes.iter().all(|n| n.path != "models/index.ts"));
+        assert_eq!(
+            edge_pairs(&analysis),
+            vec![
+                pair("a.ts", "models/user.ts"),
+                pair("b.ts", "models/order.ts"),
+                pair("c.ts", "models/order.ts"),
+                pair("c.ts", "models/user.ts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collapse_rust_mod_rs_barrel() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/models")).unwrap();
+        std::fs::write(
+            root.join("src/models/mod.rs"),
+            "mod order;\nmod user;\npub use self::order::Order;\npub use self::user::User;\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/models/order.rs"), "pub struct Order;\n").unwrap();
+        std::fs::write(root.join("src/models/user.rs"), "pub struct User;\n").unwrap();
+        std::fs::write(
+            root.join("src/app.rs"),
+            "use crate::models::Order;\n\npub fn run() {}\n",
+        )
+        .unwrap();
+
+        let analyzer = Analyzer::with_config(Config {
+            collapse_barrels: true,
+            ..Default::default()
+        });
+        let analysis = analyzer.analyze_project(root).unwrap();
+
+        assert_eq!(analysis.barrels, vec!["src/models/mod.rs".to_string()]);
+        assert_eq!(
+            edge_pairs(&analysis),
+            vec![pair("src/app.rs", "src/models/order.rs")]
+        );
+    }
+
+    #[test]
+    fn test_rust_use_paths_resolve_to_defining_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/config")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub mod app;\npub mod config;\n").unwrap();
+        std::fs::write(
+            root.join("src/config/mod.rs"),
+            "mod load;\nmod types;\npub use load::*;\npub use types::Config;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/config/types.rs"),
+            "pub struct Config;\npub struct Options;\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/config/load.rs"), "pub fn read_config() {}\n").unwrap();
+        std::fs::write(
+            root.join("src/app.rs"),
+            "use std::fmt;\nuse crate::config::{Config, read_config};\n\
+             use super::config::types::Options;\n",
+        )
+        .unwrap();
+
+        let analysis = Analyzer::new().analyze_project(root).unwrap();
+
+        assert_eq!(
+            edge_pairs(&analysis),
+            vec![
+                pair("src/app.rs", "src/config/load.rs"),
+                pair("src/app.rs", "src/config/types.rs"),
+                pair("src/config/mod.rs", "src/config/load.rs"),
+                pair("src/config/mod.rs", "src/config/types.rs"),
+                pair("src/lib.rs", "src/app.rs"),
+                pair("src/lib.rs", "src/config/mod.rs"),
+            ]
+        );
+        let symbols_of = |from: &str, to: &str| {
+            analysis
+                .edges
+                .iter()
+                .find(|e| e.from == from && e.to == to)
+                .map(|e| e.symbols.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            symbols_of("src/app.rs", "src/config/types.rs"),
+            vec!["Config".to_string(), "Options".to_string()]
+        );
+        assert_eq!(
+            symbols_of("src/app.rs", "src/config/load.rs"),
+            vec!["read_config".to_string()]
+        );
+        assert_eq!(
+            symbols_of("src/config/mod.rs", "src/config/types.rs"),
+            vec!["Config".to_string()]
+        );
+        assert!(symbols_of("src/lib.rs", "src/app.rs").is_empty());
+    }
+
+    #[test]
+    fn test_packages_aggregate_directories() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/store")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub mod app;\npub mod store;\n").unwrap();
+        std::fs::write(
+            root.join("src/store/mod.rs"),
+            "mod memory;\npub use memory::Memory;\npub trait Store {}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/store/memory.rs"), "pub struct Memory;\n").unwrap();
+        std::fs::write(
+            root.join("src/app.rs"),
+            "use crate::store::{Memory, Store};\npub struct App;\n",
+        )
+        .unwrap();
+
+        let analyzer = Analyzer::with_config(Config {
+            packages: true,
+            ..Config::default()
+        });
+        let analysis = analyzer.analyze_project(root).unwrap();
+        assert!(Analyzer::new()
+            .analyze_project(root)
+            .unwrap()
+            .packages
+            .is_none());
+
+        let packages = analysis.packages.unwrap();
+        let paths: Vec<_> = packages.nodes.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, ["src/store", "src"]);
+        let store = &packages.nodes[0];
+        assert_eq!((store.files, store.afferent, store.efferent), (2, 2, 0));
+        assert_eq!((store.abstract_types, store.types), (1, 2));
+        assert_eq!(store.instability, 0.0);
+        assert_eq!(store.distance, 0.5);
+        let src = &packages.nodes[1];
+        assert_eq!((src.afferent, src.efferent), (0, 2));
+        assert_eq!(
+            (src.instability, src.abstractness, src.distance),
+            (1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            packages.edges,
+            vec![PackageEdge {
+                from: "src".to_string(),
+                to: "src/store".to_string(),
+                imports: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_instability_calculation() {
+        // A node with only outgoing edges has instability = 1.0 (most unstable)
+        // A node with only incoming edges has instability = 0.0 (most stable)
+        let _analyzer = Analyzer::new(); // Ensure Analyzer compiles
+        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        let a = graph.add_node("a.rs".to_string());
+        let b = graph.add_node("b.rs".to_string());
+        let c = graph.add_node("c.rs".to_string());
+        graph.add_edge(a, b, ());
+        graph.add_edge(a, c, ());
+
+        // a: out=2, in=0 -> instability = 2/2 = 1.0
+        // b: out=0, in=1 -> instability = 0/1 = 0.0
+        // c: out=0, in=1 -> instability = 0/1 = 0.0
+        let in_a = graph.edges_directed(a, Direction::Incoming).count();
+        let out_a = graph.edges_directed(a, Direction::Outgoing).count();
+        let instability_a = out_a as f64 / (in_a + out_a) as f64;
+        assert!((instability_a - 1.0).abs() < 0.001);
+
+        let in_b = graph.edges_directed(b, Direction::Incoming).count();
+        let out_b = graph.edges_directed(b, Direction::Outgoing).count();
+        let instability_b = out_b as f64 / (in_b + out_b) as f64;
+        assert!((instability_b - 0.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_mermaid_generation() {
+        let analyzer = Analyzer::new();
+        let analysis = Analysis {
+            nodes: vec![
+                Node {
+                    path: "src/main.rs".to_string(),
+                    pagerank: 0.5,
+                    betweenness: 0.2,
+                    in_degree: 2,
+                    out_degree: 1,
+                    instability: 0.333,
+                },
+                Node {
+                    path: "src/lib.rs".to_string(),
+                    pagerank: 0.5,
+                    betweenness: 0.0,
+                    in_degree: 1,
+                    out_degree: 2,
+                    instability: 0.666,
+                },
+            ],
+            edges: vec![Edge {
+                from: "src/main.rs".to_string(),
+                to: "src/lib.rs".to_string(),
+                kind: EdgeKind::Internal,
+                dynamic: false,
+                symbols: vec![],
+            }],
+            cycles: vec![],
+            cycle_breaks: vec![],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
+            summary: AnalysisSummary::default(),
+        };
+
+        let mermaid = analyzer.to_mermaid(&analysis);
+        assert!(mermaid.starts_with("graph TD"));
+        assert!(mermaid.contains("n0"));
+        assert!(mermaid.contains("n1"));
+        assert!(mermaid.contains("-->"));
+    }
+
+    #[test]
+    fn test_mermaid_with_cycles() {
+        let analyzer = Analyzer::new();
+        let analysis = Analysis {
+            nodes: vec![
+                Node {
+                    path: "a.rs".to_string(),
+                    pagerank: 0.33,
+                    betweenness: 0.0,
+                    in_degree: 1,
+                    out_degree: 1,
+                    instability: 0.5,
+                },
+                Node {
+                    path: "b.rs".to_string(),
+                    pagerank: 0.33,
+                    betweenness: 0.0,
+                    in_degree: 1,
+                    out_degree: 1,
+                    instability: 0.5,
+                },
+            ],
+            edges: vec![
+                Edge {
+                    from: "a.rs".to_string(),
+                    to: "b.rs".to_string(),
+                    kind: EdgeKind::Internal,
+                    dynamic: false,
+                    symbols: vec![],
+                },
+                Edge {
+                    from: "b.rs".to_string(),
+                    to: "a.rs".to_string(),
+                    kind: EdgeKind::Internal,
+                    dynamic: false,
+                    symbols: vec![],
+                },
+            ],
+            cycles: vec![vec!["a.rs".to_string(), "b.rs".to_string()]],
+            cycle_breaks: vec![],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
+            summary: AnalysisSummary::default(),
+        };
+
+        let mermaid = analyzer.to_mermaid(&analysis);
+        assert!(mermaid.contains("Cycle nodes"));
+        assert!(mermaid.contains("style"));
+        assert!(mermaid.contains("fill:#f96"));
+    }
+
+    #[test]
+    fn test_dot_generation() {
+        let analyzer = Analyzer::new();
+        let analysis = Analysis {
+            nodes: vec![Node {
+                path: "main.rs".to_string(),
+                pagerank: 1.0,
+                betweenness: 0.0,
+                in_degree: 0,
+                out_degree: 0,
+                instability: 0.0,
+            }],
+            edges: vec![],
+            cycles: vec![],
+            cycle_breaks: vec![],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
+            summary: AnalysisSummary::default(),
+        };
+
+        let dot = analyzer.to_dot(&analysis);
+        assert!(dot.starts_with("digraph G"));
+        assert!(dot.contains("rankdir=LR"));
+        assert!(dot.contains("node [shape=box]"));
+        assert!(dot.contains("PageRank"));
+    }
+
+    /// `a.rs` and `b.rs` import each other, breaking at `b.rs -> a.rs`;
+    /// `c.rs` imports `a.rs` from outside the cycle.
+    fn cycle_analysis() -> Analysis {
+        let node = |path: &str| Node {
+            path: path.to_string(),
+            pagerank: 0.33,
+            betweenness: 0.0,
+            in_degree: 1,
+            out_degree: 1,
+            instability: 0.5,
+        };
+        let edge = |from: &str, to: &str| Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind: EdgeKind::Internal,
+            dynamic: false,
+            symbols: vec![],
+        };
+        Analysis {
+            nodes: vec![node("a.rs"), node("b.rs"), node("c&d.rs")],
+            edges: vec![
+                edge("a.rs", "b.rs"),
+                edge("b.rs", "a.rs"),
+                edge("c&d.rs", "a.rs"),
+            ],
+            cycles: vec![vec!["a.rs".to_string(), "b.rs".to_string()]],
+            cycle_breaks: vec![CycleBreak {
+                cycle: 0,
+                from: "b.rs".to_string(),
+                to: "a.rs".to_string(),
+                line: 1,
+            }],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
+            summary: AnalysisSummary::default(),
+        }
+    }
+
+    #[test]
+    fn test_mermaid_highlights_cycle_edges() {
+        let mermaid = Analyzer::new().to_mermaid(&cycle_analysis());
+
+        assert!(mermaid.contains("n0 ==> n1"));
+        assert!(mermaid.contains("n1 -.->|break| n0"));
+        assert!(mermaid.contains("n2 --> n0"));
+        assert!(mermaid.contains("linkStyle 0,1 stroke:#d33"));
+    }
+
+    #[test]
+    fn test_dot_highlights_cycles() {
+        let dot = Analyzer::new().to_dot(&cycle_analysis());
+
+        assert!(dot.contains("n0 [label=\"a.rs"));
+        assert!(dot.contains("fillcolor=\"#ff9966\""));
+        assert!(dot.contains("n0 -> n1 [color=\"#dd3333\", penwidth=2];"));
+        assert!(dot.contains("n1 -> n0 [color=\"#dd3333\", style=dashed, label=\"break\"];"));
+        assert!(dot.contains("n2 -> n0;"));
+        assert_eq!(dot.matches("fillcolor").count(), 2);
+    }
+
+    #[test]
+    fn test_graphml_export() {
+        let graphml = Analyzer::new().to_graphml(&cycle_analysis());
+
+        assert!(graphml.starts_with("<?xml"));
+        assert!(graphml.contains("<graph id=\"G\" edgedefault=\"directed\">"));
+        assert!(graphml.contains("<data key=\"path\">c&amp;d.rs</data>"));
+        assert_eq!(graphml.matches("<node ").count(), 3);
+        assert_eq!(graphml.matches("<edge ").count(), 3);
+        assert_eq!(graphml.matches("<data key=\"cycle\">0</data>").count(), 2);
+        assert_eq!(
+            graphml
+                .matches("<data key=\"in_cycle\">true</data>")
+                .count(),
+            2
+        );
+        assert_eq!(
+            graphml
+                .matches("<data key=\"cycle_break\">true</data>")
+                .count(),
+            1
+        );
+        assert!(graphml.contains("<data key=\"kind\">internal</data>"));
+        assert!(graphml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[test]
+    fn test_analyze_renders_configured_format() {
+        use crate::config::Config as AppConfig;
+        use crate::core::FileSet;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("a.js"), "import { b } from './b';\n").unwrap();
+        std::fs::write(root.join("b.js"), "import { a } from './a';\n").unwrap();
+
+        let mut config = AppConfig::default();
+        let file_set = FileSet::from_path(root, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        assert!(Analyzer::new().analyze(&ctx).unwrap().diagram.is_none());
+
+        config.graph.format = Some(GraphFormat::Dot);
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+        let diagram = analysis.diagram.as_ref().unwrap();
+        assert_eq!(diagram.format, GraphFormat::Dot);
+        assert_eq!(diagram.content, Analyzer::new().to_dot(&analysis));
+        assert!(diagram.content.contains("#ff9966"));
+    }
+
+    #[test]
+    fn test_sanitize_mermaid_label() {
+        assert_eq!(sanitize_mermaid_label("src/main.rs"), "src_main_rs");
+        assert_eq!(sanitize_mermaid_label("my-file.ts"), "my_file_ts");
+        assert_eq!(sanitize_mermaid_label("path/to/file"), "path_to_file");
+    }
+
+    #[test]
+    fn test_analysis_summary() {
+        let summary = AnalysisSummary {
+            total_nodes: 10,
+            total_edges: 15,
+            avg_degree: 3.0,
+            cycle_count: 2,
+            ..Default::default()
+        };
+        assert_eq!(summary.total_nodes, 10);
+        assert_eq!(summary.total_edges, 15);
+        assert!((summary.avg_degree - 3.0).abs() < 0.001);
+        assert_eq!(summary.cycle_count, 2);
+    }
+
+    #[test]
+    fn test_node_fields() {
+        let node = Node {
+            path: "test.rs".to_string(),
+            pagerank: 0.42,
+            betweenness: 0.15,
+            in_degree: 3,
+            out_degree: 2,
+            instability: 0.4,
+        };
+        assert_eq!(node.path, "test.rs");
+        assert!((node.pagerank - 0.42).abs() < 0.001);
+        assert!((node.betweenness - 0.15).abs() < 0.001);
+        assert_eq!(node.in_degree, 3);
+        assert_eq!(node.out_degree, 2);
+        assert!((node.instability - 0.4).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_edge_fields() {
+        let edge = Edge {
+            from: "a.rs".to_string(),
+            to: "b.rs".to_string(),
+            kind: EdgeKind::Internal,
+            dynamic: false,
+            symbols: vec![],
+        };
+        assert_eq!(edge.from, "a.rs");
+        assert_eq!(edge.to, "b.rs");
+    }
+
+    #[test]
+    fn test_is_stdlib_import() {
+        assert!(is_stdlib_import("fmt", Language::Go, &[]));
+        assert!(is_stdlib_import("net/http", Language::Go, &[]));
+        assert!(!is_stdlib_import("github.com/foo/bar", Language::Go, &[]));
+        assert!(!is_stdlib_import(
+            "example/internal/db",
+            Language::Go,
+            &["example".to_string()]
+        ));
+        assert!(is_stdlib_import(
+            "std::collections::HashMap",
+            Language::Rust,
+            &[]
+        ));
+        assert!(!is_stdlib_import("crate::config", Language::Rust, &[]));
+        assert!(!is_stdlib_import("core", Language::Rust, &[]));
+        assert!(is_stdlib_import("os.path", Language::Python, &[]));
+        assert!(!is_stdlib_import("requests", Language::Python, &[]));
+        assert!(is_stdlib_import("node:fs", Language::TypeScript, &[]));
+        assert!(is_stdlib_import("fs/promises", Language::JavaScript, &[]));
+        assert!(!is_stdlib_import("react", Language::Tsx, &[]));
+        assert!(is_stdlib_import("java.util.List", Language::Java, &[]));
+        assert!(is_stdlib_import(
+            "import static java.util.Objects.requireNonNull;",
+            Language::Java,
+            &[]
+        ));
+        assert!(!is_stdlib_import(
+            "import com.acme.Widget;",
+            Language::Java,
+            &[]
+        ));
+        assert!(is_stdlib_import("json", Language::Ruby, &[]));
+        assert!(!is_stdlib_import("rails", Language::Ruby, &[]));
+    }
+
+    #[test]
+    fn test_classify_import() {
+        let workspace = WorkspaceIndex {
+            package_of: HashMap::from([
+                (
+                    "packages/app/index.ts".to_string(),
+                    "packages/app".to_string(),
+                ),
+                (
+                    "packages/app/util.ts".to_string(),
+                    "packages/app".to_string(),
+                ),
+                (
+                    "packages/lib/index.ts".to_string(),
+                    "packages/lib".to_string(),
+                ),
+            ]),
+            go_modules: Vec::new(),
+        };
+        let from = "packages/app/index.ts";
+        let ts = Language::TypeScript;
+
+        assert_eq!(
+            classify_import("./util", ts, from, Some("packages/app/util.ts"), &workspace),
+            EdgeKind::Internal
+        );
+        assert_eq!(
+            classify_import(
+                "../lib",
+                ts,
+                from,
+                Some("packages/lib/index.ts"),
+                &workspace
+            ),
+            EdgeKind::WorkspaceExternal
+        );
+        assert_eq!(
+            classify_import("../../outside", ts, from, None, &workspace),
+            EdgeKind::WorkspaceExternal
+        );
+        assert_eq!(
+            classify_import(
+                "lodash",
+                ts,
+                from,
+                Some("node_modules/lodash/lodash.js"),
+                &workspace
+            ),
+            EdgeKind::ThirdParty
+        );
+        assert_eq!(
+            classify_import("react", ts, from, None, &workspace),
+            EdgeKind::ThirdParty
+        );
+        assert_eq!(
+            classify_import("path", ts, from, None, &workspace),
+            EdgeKind::Stdlib
+        );
+    }
+
+    #[test]
+    fn test_workspace_index_detects_packages() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("packages/app/src")).unwrap();
+        std::fs::create_dir_all(root.join("packages/lib")).unwrap();
+        std::fs::write(root.join("packages/app/package.json"), "{}").unwrap();
+        std::fs::write(root.join("packages/lib/go.mod"), "module example.com/lib\n").unwrap();
+
+        let files = vec![
+            std::path::PathBuf::from("packages/app/src/index.ts"),
+            std::path::PathBuf::from("packages/app/src/util.ts"),
+            std::path::PathBuf::from("packages/lib/lib.go"),
+            std::path::PathBuf::from("scripts/build.sh"),
+        ];
+        let workspace = WorkspaceIndex::new(&files, root);
+
+        assert!(workspace.same_package("packages/app/src/index.ts", "packages/app/src/util.ts"));
+        assert!(!workspace.same_package("packages/app/src/index.ts", "packages/lib/lib.go"));
+        assert_eq!(workspace.package_of["scripts/build.sh"], "");
+        assert_eq!(workspace.go_modules, vec!["example.com/lib".to_string()]);
+
+        let packages = group_by_package(&files, root);
+        assert_eq!(
+            packages.keys().collect::<Vec<_>>(),
+            vec!["", "packages/app", "packages/lib"]
+        );
+        assert_eq!(packages["packages/app"].len(), 2);
+    }
+
+    fn write_two_package_workspace(root: &Path) {
+        std::fs::create_dir_all(root.join("packages/app")).unwrap();
+        std::fs::create_dir_all(root.join("packages/lib")).unwrap();
+        std::fs::write(root.join("packages/app/package.json"), "{}").unwrap();
+        std::fs::write(root.join("packages/lib/package.json"), "{}").unwrap();
+        std::fs::write(
+            root.join("packages/app/index.js"),
+            "import { helper } from './helper';\nimport { util } from '../lib/util';\nimport fs from 'fs';\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("packages/app/helper.js"),
+            "export function helper() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("packages/lib/util.js"),
+            "export function util() {}\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_analyze_classifies_edges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_two_package_workspace(temp_dir.path());
+
+        let analysis = Analyzer::new().analyze_project(temp_dir.path()).unwrap();
+
+        let kind_of = |to: &str| {
+            analysis
+                .edges
+                .iter()
+                .find(|e| e.from == "packages/app/index.js" && e.to == to)
+                .map(|e| e.kind)
+        };
+        assert_eq!(kind_of("packages/app/helper.js"), Some(EdgeKind::Internal));
+        assert_eq!(
+            kind_of("packages/lib/util.js"),
+            Some(EdgeKind::WorkspaceExternal)
+        );
+        assert_eq!(analysis.summary.internal_edges, 1);
+        assert_eq!(analysis.summary.workspace_external_edges, 1);
+    }
+
+    #[test]
+    fn test_analyze_internal_only_drops_external_edges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_two_package_workspace(temp_dir.path());
+
+        let analyzer = Analyzer::with_config(Config {
+            internal_only: true,
+            include_external: true,
+            ..Config::default()
+        });
+        let analysis = analyzer.analyze_project(temp_dir.path()).unwrap();
+
+        assert_eq!(analysis.edges.len(), 1);
+        assert!(analysis.edges.iter().all(|e| e.kind == EdgeKind::Internal));
+        assert!(analysis.nodes.iter().all(|n| n.path != "fs"));
+        assert_eq!(analysis.summary.workspace_external_edges, 0);
+    }
+
+    #[test]
+    fn test_analyze_include_external_adds_classified_nodes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_two_package_workspace(temp_dir.path());
+
+        let analyzer = Analyzer::with_config(Config {
+            include_external: true,
+            ..Config::default()
+        });
+        let analysis = analyzer.analyze_project(temp_dir.path()).unwrap();
+
+        let fs_edge = analysis.edges.iter().find(|e| e.to == "fs").unwrap();
+        assert_eq!(fs_edge.kind, EdgeKind::Stdlib);
+        assert_eq!(analysis.summary.stdlib_edges, 1);
+    }
+
+    #[test]
+    fn test_camel_to_snake() {
+        assert_eq!(camel_to_snake("OrderSearcher"), "order_searcher");
+        assert_eq!(camel_to_snake("ApplicationRecord"), "application_record");
+        assert_eq!(camel_to_snake("HTTPClient"), "http_client");
+        assert_eq!(camel_to_snake("Foo"), "foo");
+        assert_eq!(camel_to_snake("FooBar"), "foo_bar");
+        assert_eq!(camel_to_snake("already_snake"), "already_snake");
+        assert_eq!(camel_to_snake("JSON"), "json");
+        assert_eq!(camel_to_snake("HTMLParser"), "html_parser");
+    }
+
+    #[test]
+    fn test_find_match_ruby_constant_to_snake_case() {
+        let root = Path::new("/project");
+        let files = vec![
+            std::path::PathBuf::from("/project/app/models/concerns/order_searcher.rb"),
+            std::path::PathBuf::from("/project/app/models/concerns/feature_gate.rb"),
+            std::path::PathBuf::from("/project/app/models/application_record.rb"),
+        ];
+        let index = FilePathIndex::new(&files, root);
+
+        // CamelCase constant should resolve to snake_case file
+        assert_eq!(
+            index.find_match("OrderSearcher", Path::new("app/models/order.rb")),
+            Some("app/models/concerns/order_searcher.rb".to_string())
+        );
+        assert_eq!(
+            index.find_match("FeatureGate", Path::new("app/models/order.rb")),
+            Some("app/models/concerns/feature_gate.rb".to_string())
+        );
+        assert_eq!(
+            index.find_match("ApplicationRecord", Path::new("app/models/order.rb")),
+            Some("app/models/application_record.rb".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_match_ruby_scoped_constant() {
+        let root = Path::new("/project");
+        let files = vec![
+            std::path::PathBuf::from("/project/app/models/concerns/active_model/validations.rb"),
+            std::path::PathBuf::from("/project/lib/active_record/base.rb"),
+        ];
+        let index = FilePathIndex::new(&files, root);
+
+        // Scoped constant ActiveModel::Validations -> active_model/validations
+        assert_eq!(
+            index.find_match("ActiveModel::Validations", Path::new("app/models/user.rb")),
+            Some("app/models/concerns/active_model/validations.rb".to_string())
+        );
+        assert_eq!(
+            index.find_match("ActiveRecord::Base", Path::new("app/models/user.rb")),
+            Some("lib/active_record/base.rb".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_match_scoped_constant_no_substring_collision() {
+        let root = Path::new("/project");
+        let files = vec![std::path::PathBuf::from(
+            "/project/packages/connect/app/controllers/connect/sign_up_controller.rb",
+        )];
+        let index = FilePathIndex::new(&files, root);
+
+        // T::Sig -> t/sig should NOT match connect/sign_up_controller.rb
+        // (substring "t/sig" appears in "connect/sign_up" but not on segment boundaries)
+        assert_eq!(
+            index.find_match("T::Sig", Path::new("app/models/user.rb")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_match_rust_crate_import() {
+        let root = Path::new("/project");
+        let files = vec![
+            std::path::PathBuf::from("/project/src/config/mod.rs"),
+            std::path::PathBuf::from("/project/src/utils.rs"),
+            std::path::PathBuf::from("/project/src/analyzers/graph.rs"),
+        ];
+        let index = FilePathIndex::new(&files, root);
+
+        // crate::config::Config -> should match src/config/mod.rs
+        assert_eq!(
+            index.find_match("crate::config", Path::new("src/main.rs")),
+            Some("src/config/mod.rs".to_string())
+        );
+
+        // crate::utils -> should match src/utils.rs
+        assert_eq!(
+            index.find_match("crate::utils", Path::new("src/main.rs")),
+            Some("src/utils.rs".to_string())
+        );
+
+        // crate::analyzers::graph -> should match src/analyzers/graph.rs
+        assert_eq!(
+            index.find_match("crate::analyzers::graph", Path::new("src/main.rs")),
+            Some("src/analyzers/graph.rs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_match_rust_mod_declaration() {
+        let root = Path::new("/project");
+        let files = vec![
+            std::path::PathBuf::from("/project/src/config.rs"),
+            std::path::PathBuf::from("/project/src/config/mod.rs"),
+            std::path::PathBuf::from("/project/src/utils.rs"),
+        ];
+        let index = FilePathIndex::new(&files, root);
+
+        // mod config -> should match src/config.rs or src/config/mod.rs
+        let result = index.find_match("config", Path::new("src/lib.rs"));
+        assert!(
+            result == Some("src/config.rs".to_string())
+                || result == Some("src/config/mod.rs".to_string()),
+            "Expected config.rs or config/mod.rs, got {:?}",
+            result
+        );
+
+        // mod utils -> should match src/utils.rs
+        assert_eq!(
+            index.find_match("utils", Path::new("src/lib.rs")),
+            Some("src/utils.rs".to_string())
+        );
+    }
+}
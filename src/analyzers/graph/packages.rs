@@ -0,0 +1,382 @@
+//! Package-level aggregation of the file dependency graph.
+//!
+//! Files are grouped into packages by directory, and file-level imports
+//! between packages become weighted package edges. Each package gets
+//! Martin's package metrics:
+//!
+//! - **Afferent coupling (Ca)**: files outside the package importing a file
+//!   in it
+//! - **Efferent coupling (Ce)**: files in the package importing a file
+//!   outside it
+//! - **Instability**: Ce / (Ca + Ce)
+//! - **Abstractness**: abstract types (traits, interfaces, abstract classes)
+//!   / all type declarations
+//! - **Distance from the main sequence**: |A + I - 1|; 0 is balanced, 1 is
+//!   either the zone of pain (stable and concrete) or the zone of
+//!   uselessness (unstable and abstract)
+//!
+//! Reference: Martin, R.C. (1994) "OO Design Quality Metrics: An Analysis of
+//! Dependencies"
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::Language;
+use crate::parser::ParseResult;
+
+/// Python base classes that make a class abstract.
+const PYTHON_ABSTRACT_BASES: &[&str] = &["ABC", "ABCMeta", "Protocol"];
+
+/// Type declarations in one file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct TypeCounts {
+    /// Traits, interfaces, and abstract classes.
+    pub abstract_types: usize,
+    /// All type declarations, abstract or not.
+    pub types: usize,
+}
+
+/// Count the abstract and concrete type declarations of a parsed file.
+///
+/// Languages without abstract types (C, Ruby, Bash, Lua, Zig) count every
+/// declared type as concrete.
+pub(super) fn type_counts(result: &ParseResult) -> TypeCounts {
+    let mut counts = TypeCounts::default();
+    let mut stack = vec![result.tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if let Some(is_abstract) = classify(&node, &result.source, result.language) {
+            counts.types += 1;
+            if is_abstract {
+                counts.abstract_types += 1;
+            }
+        }
+        stack.extend(node.named_children(&mut node.walk()));
+    }
+    counts
+}
+
+/// `Some(abstract)` when `node` declares a type, `None` otherwise.
+fn classify(node: &tree_sitter::Node<'_>, source: &[u8], lang: Language) -> Option<bool> {
+    let kind = node.kind();
+    match lang {
+        Language::Rust => match kind {
+            "trait_item" => Some(true),
+            "struct_item" | "enum_item" | "union_item" => Some(false),
+            _ => None,
+        },
+        Language::Go if kind == "type_spec" => match node.child_by_field_name("type")?.kind() {
+            "interface_type" => Some(true),
+            "struct_type" => Some(false),
+            _ => None,
+        },
+        Language::Go => None,
+        Language::Java | Language::CSharp | Language::Php => match kind {
+            "interface_declaration" => Some(true),
+            "class_declaration" => Some(has_abstract_modifier(node, source)),
+            "enum_declaration" | "record_declaration" | "struct_declaration" => Some(false),
+            _ => None,
+        },
+        Language::TypeScript | Language::JavaScript | Language::Tsx | Language::Jsx => match kind {
+            "interface_declaration" | "abstract_class_declaration" => Some(true),
+            "class_declaration" | "class" | "enum_declaration" => Some(false),
+            _ => None,
+        },
+        Language::Python => (kind == "class_definition").then(|| {
+            node.child_by_field_name("superclasses")
+                .and_then(|bases| bases.utf8_text(source).ok())
+                .is_some_and(|bases| {
+                    bases
+                        .split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .any(|name| PYTHON_ABSTRACT_BASES.contains(&name))
+                })
+        }),
+        Language::Cpp => match kind {
+            "class_specifier" | "struct_specifier" => {
+                let body = node.child_by_field_name("body")?;
+                Some(has_pure_virtual(&body, source))
+            }
+            _ => None,
+        },
+        Language::C => match kind {
+            "struct_specifier" | "union_specifier" => {
+                node.child_by_field_name("body").map(|_| false)
+            }
+            _ => None,
+        },
+        Language::Ruby => (kind == "class").then_some(false),
+        Language::Bash | Language::Lua | Language::Zig => None,
+    }
+}
+
+/// Whether a Java, C#, or PHP class declaration is marked `abstract`.
+fn has_abstract_modifier(node: &tree_sitter::Node<'_>, source: &[u8]) -> bool {
+    node.children(&mut node.walk())
+        .filter(|child| matches!(child.kind(), "modifiers" | "modifier" | "abstract_modifier"))
+        .filter_map(|child| child.utf8_text(source).ok())
+        .any(|text| text.split_whitespace().any(|word| word == "abstract"))
+}
+
+/// Whether a C++ class body declares a pure virtual method (`= 0`).
+fn has_pure_virtual(body: &tree_sitter::Node<'_>, source: &[u8]) -> bool {
+    body.named_children(&mut body.walk())
+        .filter(|child| child.kind() == "field_declaration")
+        .filter_map(|child| child.utf8_text(source).ok())
+        .any(|text| {
+            let text: String = text.split_whitespace().collect();
+            text.starts_with("virtual") && text.ends_with("=0;")
+        })
+}
+
+/// Package (directory) of a file path; `.` for files at the root.
+pub(super) fn package_of(path: &str) -> String {
+    match Path::new(path).parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_string_lossy().to_string(),
+        _ => ".".to_string(),
+    }
+}
+
+/// The dependency graph collapsed to packages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackageGraph {
+    /// Packages, furthest from the main sequence first.
+    pub nodes: Vec<Package>,
+    /// Imports between packages.
+    pub edges: Vec<PackageEdge>,
+    /// Mean distance from the main sequence over all packages.
+    pub mean_distance: f64,
+}
+
+/// Coupling and stability metrics of one package.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Package {
+    /// Directory of the package's files.
+    pub path: String,
+    pub files: usize,
+    /// Afferent coupling: files outside the package depending on it.
+    pub afferent: usize,
+    /// Efferent coupling: files in the package depending on other packages.
+    pub efferent: usize,
+    /// Ce / (Ca + Ce); 0 when the package is not coupled at all.
+    pub instability: f64,
+    pub types: usize,
+    pub abstract_types: usize,
+    /// Abstract types / types; 0 when the package declares no types.
+    pub abstractness: f64,
+    /// |A + I - 1|.
+    pub distance: f64,
+}
+
+/// File-level imports from one package into another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageEdge {
+    pub from: String,
+    pub to: String,
+    /// File-level import edges collapsed into this one.
+    pub imports: usize,
+}
+
+/// Collapse file-level edges into a package graph. Only edges between files
+/// in `files` are counted.
+pub(super) fn aggregate<'a>(
+    files: &HashMap<String, TypeCounts>,
+    edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> PackageGraph {
+    #[derive(Default)]
+    struct Totals<'a> {
+        files: usize,
+        types: TypeCounts,
+        afferent: HashSet<&'a str>,
+        efferent: HashSet<&'a str>,
+    }
+
+    let mut totals: BTreeMap<String, Totals<'a>> = BTreeMap::new();
+    for (path, counts) in files {
+        let entry = totals.entry(package_of(path)).or_default();
+        entry.files += 1;
+        entry.types.types += counts.types;
+        entry.types.abstract_types += counts.abstract_types;
+    }
+
+    let mut package_edges: BTreeMap<(String, String), usize> = BTreeMap::new();
+    for (from, to) in edges {
+        if !files.contains_key(from) || !files.contains_key(to) {
+            continue;
+        }
+        let (from_package, to_package) = (package_of(from), package_of(to));
+        if from_package == to_package {
+            continue;
+        }
+        if let Some(entry) = totals.get_mut(&from_package) {
+            entry.efferent.insert(from);
+        }
+        if let Some(entry) = totals.get_mut(&to_package) {
+            entry.afferent.insert(from);
+        }
+        *package_edges.entry((from_package, to_package)).or_default() += 1;
+    }
+
+    let mut nodes: Vec<Package> = totals
+        .into_iter()
+        .map(|(path, totals)| {
+            let (afferent, efferent) = (totals.afferent.len(), totals.efferent.len());
+            let instability = if afferent + efferent > 0 {
+                efferent as f64 / (afferent + efferent) as f64
+            } else {
+                0.0
+            };
+            let abstractness = if totals.types.types > 0 {
+                totals.types.abstract_types as f64 / totals.types.types as f64
+            } else {
+                0.0
+            };
+            Package {
+                path,
+                files: totals.files,
+                afferent,
+                efferent,
+                instability,
+                types: totals.types.types,
+                abstract_types: totals.types.abstract_types,
+                abstractness,
+                distance: (abstractness + instability - 1.0).abs(),
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| b.distance.total_cmp(&a.distance).then(a.path.cmp(&b.path)));
+
+    let mean_distance = if nodes.is_empty() {
+        0.0
+    } else {
+        nodes.iter().map(|p| p.distance).sum::<f64>() / nodes.len() as f64
+    };
+    let edges = package_edges
+        .into_iter()
+        .map(|((from, to), imports)| PackageEdge { from, to, imports })
+        .collect();
+
+    PackageGraph {
+        nodes,
+        edges,
+        mean_distance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn counts(source: &str, lang: Language, file: &str) -> TypeCounts {
+        let result = Parser::new()
+            .parse(source.as_bytes(), lang, Path::new(file))
+            .unwrap();
+        type_counts(&result)
+    }
+
+    fn types(abstract_types: usize, types: usize) -> TypeCounts {
+        TypeCounts {
+            abstract_types,
+            types,
+        }
+    }
+
+    #[test]
+    fn test_type_counts_per_language() {
+        assert_eq!(
+            counts(
+                "pub trait Store {}\npub struct Memory;\nenum Mode { A }\nfn f() {}\n",
+                Language::Rust,
+                "src/store.rs",
+            ),
+            types(1, 3)
+        );
+        assert_eq!(
+            counts(
+                "package s\ntype Store interface{}\ntype Memory struct{}\ntype ID int\n",
+                Language::Go,
+                "s/store.go",
+            ),
+            types(1, 2)
+        );
+        assert_eq!(
+            counts(
+                "interface Store {}\nabstract class Base {}\nclass Memory {}\n",
+                Language::Java,
+                "Store.java",
+            ),
+            types(2, 3)
+        );
+        assert_eq!(
+            counts(
+                "interface Store {}\nabstract class Base {}\nclass Memory {}\n",
+                Language::TypeScript,
+                "store.ts",
+            ),
+            types(2, 3)
+        );
+        assert_eq!(
+            counts(
+                "class Store(ABC):\n    pass\n\nclass Memory(Store):\n    pass\n",
+                Language::Python,
+                "store.py",
+            ),
+            types(1, 2)
+        );
+    }
+
+    #[test]
+    fn test_package_of() {
+        assert_eq!(package_of("src/graph/mod.rs"), "src/graph");
+        assert_eq!(package_of("main.go"), ".");
+    }
+
+    #[test]
+    fn test_aggregate_martin_metrics() {
+        // api (concrete) -> store (one trait, one struct); store -> util
+        let files: HashMap<String, TypeCounts> = [
+            ("api/handler.rs", types(0, 1)),
+            ("api/routes.rs", types(0, 0)),
+            ("store/mod.rs", types(1, 1)),
+            ("store/memory.rs", types(0, 1)),
+            ("util/ids.rs", types(0, 0)),
+        ]
+        .into_iter()
+        .map(|(path, counts)| (path.to_string(), counts))
+        .collect();
+        let edges = [
+            ("api/handler.rs", "store/mod.rs"),
+            ("api/routes.rs", "store/mod.rs"),
+            ("api/routes.rs", "api/handler.rs"),
+            ("store/memory.rs", "store/mod.rs"),
+            ("store/memory.rs", "util/ids.rs"),
+            ("api/handler.rs", "vendor/lib.rs"),
+        ];
+
+        let graph = aggregate(&files, edges);
+
+        let store = graph.nodes.iter().find(|p| p.path == "store").unwrap();
+        assert_eq!((store.files, store.afferent, store.efferent), (2, 2, 1));
+        assert!((store.instability - 1.0 / 3.0).abs() < 1e-9);
+        assert!((store.abstractness - 0.5).abs() < 1e-9);
+        assert!((store.distance - 1.0 / 6.0).abs() < 1e-9);
+
+        let api = graph.nodes.iter().find(|p| p.path == "api").unwrap();
+        assert_eq!((api.afferent, api.efferent), (0, 2));
+        assert_eq!(api.instability, 1.0);
+        assert_eq!(api.distance, 0.0);
+
+        // util: stable and concrete, the zone of pain
+        assert_eq!(graph.nodes[0].path, "util");
+        assert_eq!(graph.nodes[0].distance, 1.0);
+
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str(), e.imports))
+            .collect();
+        assert_eq!(edges, [("api", "store", 2), ("store", "util", 1)]);
+        assert!((graph.mean_distance - (1.0 + 1.0 / 6.0) / 3.0).abs() < 1e-9);
+    }
+}
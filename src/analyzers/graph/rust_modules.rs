@@ -0,0 +1,615 @@
+//! Rust module tree and `use` path resolution.
+//!
+//! File-level import resolution sends `use crate::config::Config` to the
+//! file of module `config`, even when `Config` is defined in a submodule and
+//! only re-exported there. This index maps each crate's files to module
+//! paths from the source layout (`src/lib.rs` is the crate root, `src/a.rs`
+//! and `src/a/mod.rs` are `crate::a`) and follows `use` paths through
+//! `pub use` re-exports, named or glob, to the file defining the item.
+//!
+//! Paths may start with `crate`, `self`, `super`, a child module, or the
+//! crate's own name from `Cargo.toml`, which is how binaries and integration
+//! tests reach their library. Items generated by macros or defined inside
+//! inline modules are not indexed; a path to one resolves to the module file
+//! holding it.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path};
+
+use crate::parser::ParseResult;
+
+/// Node kinds of named module-level items.
+const ITEM_KINDS: &[&str] = &[
+    "function_item",
+    "function_signature_item",
+    "struct_item",
+    "enum_item",
+    "union_item",
+    "trait_item",
+    "type_item",
+    "const_item",
+    "static_item",
+    "macro_definition",
+];
+
+/// One name brought into scope by a `use` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UseItem {
+    /// Path segments, e.g. `["crate", "config", "Config"]`; a glob import
+    /// names the module.
+    pub path: Vec<String>,
+    /// Name bound in the importing module; `*` for glob imports.
+    pub local: String,
+    /// Declared `pub` at module level, making it a re-export.
+    pub public: bool,
+    /// Inline modules (`mod tests { ... }`) enclosing the declaration.
+    pub depth: usize,
+    /// Start byte of the `use` declaration.
+    pub start_byte: usize,
+}
+
+/// Items and `use` declarations of one Rust file.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RustFile {
+    /// Names of items defined at module level.
+    pub items: HashSet<String>,
+    pub uses: Vec<UseItem>,
+}
+
+/// Where a `use` path leads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Resolved {
+    /// File defining the item, or the module's file for a module path.
+    pub file: String,
+    /// Item name; `None` when the path names a module.
+    pub symbol: Option<String>,
+}
+
+/// Read the module-level items and all `use` declarations of a Rust file.
+pub(crate) fn parse(result: &ParseResult) -> RustFile {
+    let source = &result.source[..];
+    let root = result.root_node();
+    let mut file = RustFile::default();
+    for node in root.named_children(&mut root.walk()) {
+        let inline_mod = node.kind() == "mod_item" && node.child_by_field_name("body").is_some();
+        if !ITEM_KINDS.contains(&node.kind()) && !inline_mod {
+            continue;
+        }
+        if let Some(name) = node
+            .child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+        {
+            file.items.insert(name.to_string());
+        }
+    }
+    collect_uses(root, source, 0, &mut file.uses);
+    file
+}
+
+fn collect_uses(node: tree_sitter::Node<'_>, source: &[u8], depth: usize, uses: &mut Vec<UseItem>) {
+    for child in node.named_children(&mut node.walk()) {
+        match child.kind() {
+            "use_declaration" => {
+                let Some(tree) = child
+                    .child_by_field_name("argument")
+                    .and_then(|a| a.utf8_text(source).ok())
+                else {
+                    continue;
+                };
+                let public = node.kind() == "source_file"
+                    && child
+                        .named_children(&mut child.walk())
+                        .any(|c| c.kind() == "visibility_modifier");
+                let mut expanded = Vec::new();
+                expand(&strip_comments(tree), &[], &mut expanded);
+                uses.extend(expanded.into_iter().map(|(path, local)| UseItem {
+                    path,
+                    local,
+                    public,
+                    depth,
+                    start_byte: child.start_byte(),
+                }));
+            }
+            "mod_item" => collect_uses(child, source, depth + 1, uses),
+            _ => collect_uses(child, source, depth, uses),
+        }
+    }
+}
+
+fn strip_comments(tree: &str) -> String {
+    tree.lines()
+        .map(|line| line.split("//").next().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Expand a use tree such as `a::{self, b::C as D, e::*}` into
+/// `(path, local name)` pairs.
+fn expand(tree: &str, prefix: &[String], out: &mut Vec<(Vec<String>, String)>) {
+    for part in split_top_level(tree) {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        if let Some(open) = part.find('{') {
+            if let Some(inner) = part[open + 1..].trim_end().strip_suffix('}') {
+                let mut path = prefix.to_vec();
+                path.extend(segments(&part[..open]));
+                expand(inner, &path, out);
+            }
+            continue;
+        }
+        let (path_text, alias) = match part.split_once(" as ") {
+            Some((path, alias)) => (path, Some(alias.trim())),
+            None => (part, None),
+        };
+        let mut path = prefix.to_vec();
+        path.extend(segments(path_text));
+        let local = match path.last().map(String::as_str) {
+            // `{self}` binds the module itself
+            Some("self") if path.len() > 1 => {
+                path.pop();
+                path.last().cloned()
+            }
+            Some("*") => {
+                path.pop();
+                Some("*".to_string())
+            }
+            last => last.map(str::to_string),
+        };
+        if let Some(local) = local {
+            out.push((path, alias.map_or(local, str::to_string)));
+        }
+    }
+}
+
+/// Split on commas outside braces.
+fn split_top_level(tree: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in tree.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&tree[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&tree[start..]);
+    parts
+}
+
+fn segments(path: &str) -> impl Iterator<Item = String> + '_ {
+    path.split("::")
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.trim_start_matches("r#").to_string())
+}
+
+/// A module: crate source directory and module path within the crate.
+type ModuleKey = (String, Vec<String>);
+
+/// Module tree of the crates in a project.
+#[derive(Debug, Default)]
+pub(crate) struct RustModules {
+    /// Module -> the file defining it.
+    modules: HashMap<ModuleKey, String>,
+    /// File -> the module it defines.
+    module_of: HashMap<String, ModuleKey>,
+    /// Crate name -> crate source directory.
+    crates: HashMap<String, String>,
+    files: HashMap<String, RustFile>,
+}
+
+impl RustModules {
+    /// Index `files`, named as the caller names them, of a project at
+    /// `root`. Crate names are read from the `Cargo.toml` next to each
+    /// crate's `src` directory.
+    pub fn new(root: &Path, files: impl IntoIterator<Item = (String, RustFile)>) -> Self {
+        let mut index = Self::default();
+        for (path, file) in files {
+            if let Some(key) = module_path(root, &path) {
+                // With both, `lib.rs` is the root the rest of the crate sees
+                let keep_existing = index
+                    .modules
+                    .get(&key)
+                    .is_some_and(|existing| existing.ends_with("lib.rs"));
+                if !keep_existing {
+                    index.modules.insert(key.clone(), path.clone());
+                }
+                index.module_of.insert(path.clone(), key);
+            }
+            index.files.insert(path, file);
+        }
+
+        let sources: HashSet<&String> = index.module_of.values().map(|(src, _)| src).collect();
+        for src in sources {
+            let manifest = Path::new(src)
+                .parent()
+                .map(|dir| root.join(dir).join("Cargo.toml"));
+            if let Some(name) = manifest
+                .and_then(|m| std::fs::read_to_string(m).ok())
+                .and_then(|m| crate_name(&m))
+            {
+                index.crates.insert(name, src.clone());
+            }
+        }
+        index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// `use` declarations of `from`, expanded.
+    pub fn uses(&self, from: &str) -> &[UseItem] {
+        self.files.get(from).map_or(&[], |f| &f.uses)
+    }
+
+    /// Resolve one `use` of `from`. `None` for paths outside the indexed
+    /// crates (`std`, dependencies).
+    pub fn resolve(&self, from: &str, item: &UseItem) -> Option<Resolved> {
+        self.resolve_path(from, &item.path, item.depth, &mut HashSet::new())
+    }
+
+    /// File of the module `from` declares with `mod name;`.
+    pub fn child_module(&self, from: &str, name: &str) -> Option<&String> {
+        let (src, module) = self.module_of.get(from)?;
+        let mut child = module.clone();
+        child.push(name.to_string());
+        self.modules.get(&(src.clone(), child))
+    }
+
+    /// Items `from` imports by name, keyed by the name bound, resolved to
+    /// their definitions.
+    pub fn bindings(&self, from: &str) -> HashMap<String, Resolved> {
+        self.uses(from)
+            .iter()
+            .filter(|u| u.local != "*")
+            .filter_map(|u| Some((u.local.clone(), self.resolve(from, u)?)))
+            .filter(|(_, resolved)| resolved.symbol.is_some())
+            .collect()
+    }
+
+    fn resolve_path(
+        &self,
+        from: &str,
+        path: &[String],
+        depth: usize,
+        visited: &mut HashSet<(String, String)>,
+    ) -> Option<Resolved> {
+        let (first, rest) = path.split_first()?;
+        let own = self.module_of.get(from);
+        let (src, base, rest) = match first.as_str() {
+            "crate" => (own?.0.clone(), Vec::new(), rest),
+            "self" => (own?.0.clone(), own?.1.clone(), rest),
+            "super" => {
+                let (src, mut module) = own?.clone();
+                let supers = path.iter().take_while(|s| *s == "super").count();
+                // The innermost `super`s only leave inline modules of this file
+                for _ in 0..supers.saturating_sub(depth) {
+                    module.pop()?;
+                }
+                (src, module, &path[supers..])
+            }
+            name => match self.crates.get(name) {
+                Some(src) => (src.clone(), Vec::new(), rest),
+                // Otherwise only a child module of the importing module
+                None => {
+                    self.child_module(from, name)?;
+                    (own?.0.clone(), own?.1.clone(), path)
+                }
+            },
+        };
+        self.walk(src, base, rest, visited)
+    }
+
+    /// Descend through modules along `rest`; the first segment that is not a
+    /// module is an item of the module reached.
+    fn walk(
+        &self,
+        src: String,
+        module: Vec<String>,
+        rest: &[String],
+        visited: &mut HashSet<(String, String)>,
+    ) -> Option<Resolved> {
+        let mut key = (src, module);
+        for segment in rest {
+            key.1.push(segment.clone());
+            if self.modules.contains_key(&key) {
+                continue;
+            }
+            key.1.pop();
+            let file = self.modules.get(&key)?;
+            return Some(
+                self.find(file, segment, visited)
+                    .unwrap_or_else(|| Resolved {
+                        file: file.clone(),
+                        symbol: Some(segment.clone()),
+                    }),
+            );
+        }
+        Some(Resolved {
+            file: self.modules.get(&key)?.clone(),
+            symbol: None,
+        })
+    }
+
+    /// Follow `name` from the module in `file` through re-exports to its
+    /// definition.
+    fn find(
+        &self,
+        file: &str,
+        name: &str,
+        visited: &mut HashSet<(String, String)>,
+    ) -> Option<Resolved> {
+        if !visited.insert((file.to_string(), name.to_string())) {
+            return None;
+        }
+        let rust = self.files.get(file)?;
+        if rust.items.contains(name) {
+            return Some(Resolved {
+                file: file.to_string(),
+                symbol: Some(name.to_string()),
+            });
+        }
+        let reexports = || rust.uses.iter().filter(|u| u.public);
+        for reexport in reexports().filter(|u| u.local == name) {
+            if let Some(found) = self.resolve_path(file, &reexport.path, reexport.depth, visited) {
+                return Some(found);
+            }
+        }
+        for glob in reexports().filter(|u| u.local == "*") {
+            if let Some(Resolved {
+                file: target,
+                symbol: None,
+            }) = self.resolve_path(file, &glob.path, glob.depth, visited)
+            {
+                if let Some(found) = self.find(&target, name, visited) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Crate source directory and module path of a file, from the layout
+/// under `src/`. Files outside `src/` and binaries under `src/bin/` are
+/// crate roots of their own and are left out.
+fn module_path(root: &Path, path: &str) -> Option<ModuleKey> {
+    let path = Path::new(path);
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    let parts: Vec<String> = rel
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    let src = parts.iter().position(|p| p == "src")?;
+    let mut module = parts[src + 1..].to_vec();
+    if module.first().is_some_and(|m| m == "bin") {
+        return None;
+    }
+    let file = module.pop()?;
+    match file.strip_suffix(".rs")? {
+        "mod" => {}
+        "lib" | "main" if module.is_empty() => {}
+        stem => module.push(stem.to_string()),
+    }
+    Some((parts[..=src].join("/"), module))
+}
+
+/// Library name declared in a `Cargo.toml`, as code refers to it.
+fn crate_name(manifest: &str) -> Option<String> {
+    let mut section = "";
+    let mut package = None;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        let Some(value) = line
+            .strip_prefix("name")
+            .and_then(|rest| rest.trim_start().strip_prefix('='))
+        else {
+            continue;
+        };
+        let name = value.trim().trim_matches('"').replace('-', "_");
+        match section {
+            "[lib]" => return Some(name),
+            "[package]" => package = Some(name),
+            _ => {}
+        }
+    }
+    package
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Language;
+    use crate::parser::Parser;
+
+    fn parse_rust(source: &str) -> RustFile {
+        let result = Parser::new()
+            .parse(source.as_bytes(), Language::Rust, Path::new("x.rs"))
+            .unwrap();
+        parse(&result)
+    }
+
+    fn index(files: &[(&str, &str)]) -> RustModules {
+        RustModules::new(
+            Path::new("/nonexistent"),
+            files
+                .iter()
+                .map(|(path, source)| (path.to_string(), parse_rust(source))),
+        )
+    }
+
+    fn resolve(index: &RustModules, from: &str, local: &str) -> Option<Resolved> {
+        let item = index.uses(from).iter().find(|u| u.local == local)?;
+        index.resolve(from, item)
+    }
+
+    fn item(file: &str, symbol: &str) -> Option<Resolved> {
+        Some(Resolved {
+            file: file.to_string(),
+            symbol: Some(symbol.to_string()),
+        })
+    }
+
+    #[test]
+    fn test_expands_use_trees() {
+        let file = parse_rust(
+            "pub use crate::a::{self, b::{C, D as E}, f::*};\n\
+             fn run() { use super::g; }\n\
+             struct S;\n\
+             mod tests { use super::*; }\n",
+        );
+        let uses: Vec<(String, &str, bool, usize)> = file
+            .uses
+            .iter()
+            .map(|u| (u.path.join("::"), u.local.as_str(), u.public, u.depth))
+            .collect();
+
+        assert_eq!(
+            uses,
+            vec![
+                ("crate::a".to_string(), "a", true, 0),
+                ("crate::a::b::C".to_string(), "C", true, 0),
+                ("crate::a::b::D".to_string(), "E", true, 0),
+                ("crate::a::f".to_string(), "*", true, 0),
+                ("super::g".to_string(), "g", false, 0),
+                ("super".to_string(), "*", false, 1),
+            ]
+        );
+        assert!(file.items.contains("S"));
+        assert!(file.items.contains("run"));
+        assert!(file.items.contains("tests"));
+    }
+
+    #[test]
+    fn test_follows_reexports_to_definition() {
+        let index = index(&[
+            ("src/lib.rs", "pub mod config;\npub use config::Config;\n"),
+            (
+                "src/config/mod.rs",
+                "mod types;\nmod load;\npub use self::types::Config;\npub use load::*;\n",
+            ),
+            ("src/config/types.rs", "pub struct Config;\n"),
+            ("src/config/load.rs", "pub fn read_config() {}\n"),
+            (
+                "src/main.rs",
+                "use crate::Config;\nuse crate::config::{self, read_config};\nuse std::fs;\n",
+            ),
+        ]);
+
+        assert_eq!(
+            resolve(&index, "src/main.rs", "Config"),
+            item("src/config/types.rs", "Config")
+        );
+        assert_eq!(
+            resolve(&index, "src/main.rs", "read_config"),
+            item("src/config/load.rs", "read_config")
+        );
+        assert_eq!(
+            resolve(&index, "src/main.rs", "config"),
+            Some(Resolved {
+                file: "src/config/mod.rs".to_string(),
+                symbol: None,
+            })
+        );
+        assert_eq!(resolve(&index, "src/main.rs", "fs"), None);
+    }
+
+    #[test]
+    fn test_relative_paths_and_inline_modules() {
+        let index = index(&[
+            ("src/lib.rs", "mod a;\npub fn top() {}\n"),
+            (
+                "src/a/mod.rs",
+                "mod b;\nuse b::helper;\nfn own() {}\n\
+                 mod tests { use super::own; use super::super::top; }\n",
+            ),
+            ("src/a/b.rs", "use super::super::top;\npub fn helper() {}\n"),
+        ]);
+
+        assert_eq!(
+            resolve(&index, "src/a/mod.rs", "helper"),
+            item("src/a/b.rs", "helper")
+        );
+        assert_eq!(
+            resolve(&index, "src/a/mod.rs", "own"),
+            item("src/a/mod.rs", "own")
+        );
+        assert_eq!(
+            resolve(&index, "src/a/mod.rs", "top"),
+            item("src/lib.rs", "top")
+        );
+        assert_eq!(
+            resolve(&index, "src/a/b.rs", "top"),
+            item("src/lib.rs", "top")
+        );
+        assert_eq!(
+            index.child_module("src/a/mod.rs", "b"),
+            Some(&"src/a/b.rs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_crate_name_paths_from_tests_and_binaries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"my-tool\"\n\n[dependencies]\nname = \"ignored\"\n",
+        )
+        .unwrap();
+        let files = [
+            ("src/lib.rs", "pub mod report;\n"),
+            ("src/report.rs", "pub fn render() {}\n"),
+            ("tests/cli.rs", "use my_tool::report::render;\n"),
+        ];
+        let index = RustModules::new(
+            root,
+            files
+                .iter()
+                .map(|(path, source)| (path.to_string(), parse_rust(source))),
+        );
+
+        assert_eq!(
+            resolve(&index, "tests/cli.rs", "render"),
+            item("src/report.rs", "render")
+        );
+        assert_eq!(
+            index.bindings("tests/cli.rs").get("render"),
+            item("src/report.rs", "render").as_ref()
+        );
+    }
+
+    #[test]
+    fn test_reexport_cycles_terminate() {
+        let index = index(&[
+            ("src/lib.rs", "mod a;\nmod b;\nuse a::X;\n"),
+            ("src/a.rs", "pub use crate::b::X;\n"),
+            ("src/b.rs", "pub use crate::a::X;\n"),
+        ]);
+
+        assert_eq!(resolve(&index, "src/lib.rs", "X"), item("src/a.rs", "X"));
+    }
+
+    #[test]
+    fn test_crate_name_prefers_lib_section() {
+        assert_eq!(
+            crate_name("[lib]\nname = \"core_lib\"\n[package]\nname = \"app\"\n"),
+            Some("core_lib".to_string())
+        );
+        assert_eq!(crate_name("[workspace]\nmembers = []\n"), None);
+    }
+}
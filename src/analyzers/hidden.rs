@@ -0,0 +1,468 @@
+//! Hidden dependency detection (temporal coupling x dependency graph).
+//!
+//! Files that keep changing together but have no import path between them,
+//! in either direction, are coupled through something the static graph
+//! cannot see: a shared schema, a wire format, duplicated logic, a config
+//! key. These are the links that break during refactors. Each pair is
+//! reported with the commits that changed both files.
+//!
+//! Only pairs of files the graph analyzer parses are considered; for docs,
+//! config or deleted files there is no import path to miss.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::graph;
+use crate::analyzers::temporal::{self, FileCoupling};
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
+use crate::git::{Commit, PathFilter};
+
+/// Default number of evidencing commits listed per pair.
+pub const DEFAULT_MAX_EVIDENCE: usize = 5;
+
+/// Hidden dependency analyzer configuration.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Number of days of history to analyze.
+    pub days: u32,
+    /// Minimum co-change count for a pair to be considered.
+    pub min_cochanges: u32,
+    /// Minimum coupling strength for a pair to be considered.
+    pub min_strength: f64,
+    /// Most evidencing commits listed per pair.
+    pub max_evidence: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            days: temporal::DEFAULT_DAYS,
+            min_cochanges: temporal::DEFAULT_MIN_COCHANGES,
+            min_strength: temporal::STRONG_COUPLING_THRESHOLD,
+            max_evidence: DEFAULT_MAX_EVIDENCE,
+        }
+    }
+}
+
+/// Hidden dependency analyzer.
+#[derive(Default)]
+pub struct Analyzer {
+    config: Config,
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Sets the number of days of history to analyze.
+    pub fn with_days(mut self, days: u32) -> Self {
+        self.config.days = days;
+        self
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "hidden"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find files that change together with no import path between them"
+    }
+
+    fn requires_git(&self) -> bool {
+        true
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let git_repo = ctx
+            .open_git()?
+            .ok_or_else(|| Error::git("Hidden dependency analysis requires git history"))?;
+        let path_filter = PathFilter::from_context(ctx, git_repo.root());
+        let since = format!("{} days", self.config.days);
+        let commits = ctx.log_with_stats(&git_repo, Some(&since), &path_filter)?;
+        let commits = temporal::group_commits(commits, &ctx.config.temporal)?;
+
+        let coupling = temporal::Analyzer::with_config(temporal::Config {
+            days: self.config.days,
+            min_cochanges: self.config.min_cochanges,
+        })
+        .analyze_commits(&commits, &ctx.config.temporal);
+        let graph = graph::Analyzer::default().analyze_files(ctx)?;
+
+        Ok(find_hidden(
+            &self.config,
+            &coupling.couplings,
+            &graph,
+            &commits,
+            &path_filter,
+            ctx.config.temporal.max_commit_files,
+        ))
+    }
+}
+
+/// Coupled pairs in `couplings` with no import path between them in `graph`.
+///
+/// Coupling and commit paths are repository-relative; `path_filter` maps
+/// them onto the analysis root the graph paths are relative to.
+fn find_hidden(
+    config: &Config,
+    couplings: &[FileCoupling],
+    graph: &graph::Analysis,
+    commits: &[Commit],
+    path_filter: &PathFilter,
+    max_commit_files: usize,
+) -> Analysis {
+    let mut imports = Imports::new(graph);
+    let mut coupled_pairs = 0;
+    let mut dependencies = Vec::new();
+
+    for coupling in couplings {
+        if coupling.coupling_strength < config.min_strength {
+            continue;
+        }
+        let (Some(a), Some(b)) = (
+            path_filter.relative_path(&coupling.file_a),
+            path_filter.relative_path(&coupling.file_b),
+        ) else {
+            continue;
+        };
+        if !imports.contains(a) || !imports.contains(b) {
+            continue;
+        }
+        coupled_pairs += 1;
+        if imports.reaches(a, b) || imports.reaches(b, a) {
+            continue;
+        }
+
+        dependencies.push(HiddenDependency {
+            file_a: a.to_string(),
+            file_b: b.to_string(),
+            cochange_count: coupling.cochange_count,
+            coupling_strength: coupling.coupling_strength,
+            commits: evidence(
+                commits,
+                &coupling.file_a,
+                &coupling.file_b,
+                max_commit_files,
+            )
+            .take(config.max_evidence)
+            .map(EvidenceCommit::from)
+            .collect(),
+        });
+    }
+
+    let files_involved = dependencies
+        .iter()
+        .flat_map(|d| [d.file_a.as_str(), d.file_b.as_str()])
+        .collect::<HashSet<_>>()
+        .len();
+
+    Analysis {
+        generated_at: Utc::now().to_rfc3339(),
+        period_days: config.days,
+        summary: Summary {
+            coupled_pairs,
+            hidden_dependencies: dependencies.len(),
+            files_involved,
+        },
+        dependencies,
+    }
+}
+
+/// Commits that changed both files, newest first. Mega-commits are
+/// skipped, as they are when temporal coupling counts co-changes.
+fn evidence<'a>(
+    commits: &'a [Commit],
+    a: &'a str,
+    b: &'a str,
+    max_commit_files: usize,
+) -> impl Iterator<Item = &'a Commit> {
+    let touches =
+        |commit: &Commit, file: &str| commit.files.iter().any(|f| f.path == Path::new(file));
+    commits.iter().filter(move |c| {
+        temporal::counts_toward_coupling(c, max_commit_files) && touches(c, a) && touches(c, b)
+    })
+}
+
+/// Import reachability over a dependency graph, memoized per source file.
+struct Imports<'a> {
+    files: HashSet<&'a str>,
+    edges: HashMap<&'a str, Vec<&'a str>>,
+    reachable: HashMap<&'a str, HashSet<&'a str>>,
+}
+
+impl<'a> Imports<'a> {
+    fn new(graph: &'a graph::Analysis) -> Self {
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &graph.edges {
+            edges
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(edge.to.as_str());
+        }
+        Self {
+            files: graph.nodes.iter().map(|n| n.path.as_str()).collect(),
+            edges,
+            reachable: HashMap::new(),
+        }
+    }
+
+    fn contains(&self, file: &str) -> bool {
+        self.files.contains(file)
+    }
+
+    /// Whether `from` imports `to`, directly or transitively.
+    fn reaches(&mut self, from: &'a str, to: &str) -> bool {
+        let edges = &self.edges;
+        self.reachable
+            .entry(from)
+            .or_insert_with(|| {
+                let mut seen = HashSet::new();
+                let mut queue = VecDeque::from([from]);
+                while let Some(file) = queue.pop_front() {
+                    for &next in edges.get(file).into_iter().flatten() {
+                        if seen.insert(next) {
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                seen
+            })
+            .contains(to)
+    }
+}
+
+/// Hidden dependency analysis result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    /// When the analysis was generated.
+    pub generated_at: String,
+    /// Number of days of history analyzed.
+    pub period_days: u32,
+    /// Hidden dependencies, sorted by coupling strength descending.
+    pub dependencies: Vec<HiddenDependency>,
+    /// Summary statistics.
+    pub summary: Summary,
+}
+
+/// Two files that change together with no import path between them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiddenDependency {
+    /// First file in the pair.
+    pub file_a: String,
+    /// Second file in the pair.
+    pub file_b: String,
+    /// Number of times the files changed together.
+    pub cochange_count: u32,
+    /// Coupling strength (0.0 - 1.0).
+    pub coupling_strength: f64,
+    /// Commits that changed both files, newest first.
+    pub commits: Vec<EvidenceCommit>,
+}
+
+/// A commit evidencing a hidden dependency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceCommit {
+    /// Commit SHA.
+    pub sha: String,
+    /// Author name.
+    pub author: String,
+    /// Commit timestamp.
+    pub timestamp: i64,
+    /// Commit message (first line).
+    pub message: String,
+}
+
+impl From<&Commit> for EvidenceCommit {
+    fn from(commit: &Commit) -> Self {
+        Self {
+            sha: commit.sha.clone(),
+            author: commit.author.clone(),
+            timestamp: commit.timestamp,
+            message: commit.message.clone(),
+        }
+    }
+}
+
+/// Aggregate statistics for hidden dependency analysis.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    /// Strongly coupled pairs where both files are in the dependency graph.
+    pub coupled_pairs: usize,
+    /// Coupled pairs with no import path between them.
+    pub hidden_dependencies: usize,
+    /// Distinct files in hidden dependencies.
+    pub files_involved: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{ChangeType, FileChange};
+    use std::path::PathBuf;
+
+    fn graph(nodes: &[&str], edges: &[(&str, &str)]) -> graph::Analysis {
+        graph::Analysis {
+            nodes: nodes
+                .iter()
+                .map(|path| graph::Node {
+                    path: path.to_string(),
+                    pagerank: 0.0,
+                    betweenness: 0.0,
+                    in_degree: 0,
+                    out_degree: 0,
+                    instability: 0.0,
+                })
+                .collect(),
+            edges: edges
+                .iter()
+                .map(|(from, to)| graph::Edge {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    kind: graph::EdgeKind::Internal,
+                    dynamic: false,
+                    symbols: Vec::new(),
+                })
+                .collect(),
+            cycles: Vec::new(),
+            cycle_breaks: Vec::new(),
+            barrels: Vec::new(),
+            packages: None,
+            diagram: None,
+            summary: graph::AnalysisSummary::default(),
+        }
+    }
+
+    fn coupling(a: &str, b: &str, strength: f64) -> FileCoupling {
+        FileCoupling {
+            file_a: a.to_string(),
+            file_b: b.to_string(),
+            cochange_count: 4,
+            coupling_strength: strength,
+            commits_a: 4,
+            commits_b: 4,
+        }
+    }
+
+    fn commit(sha: &str, files: &[&str]) -> Commit {
+        Commit {
+            sha: sha.to_string(),
+            author: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            timestamp: 1_700_000_000,
+            message: format!("change {sha}"),
+            files: files
+                .iter()
+                .map(|f| FileChange {
+                    path: PathBuf::from(f),
+                    additions: 1,
+                    deletions: 0,
+                    change_type: ChangeType::Modified,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_flags_coupled_pairs_without_import_path() {
+        // api -> service -> db; schema and client share no imports.
+        let graph = graph(
+            &["api.py", "service.py", "db.py", "schema.py", "client.py"],
+            &[("api.py", "service.py"), ("service.py", "db.py")],
+        );
+        let couplings = [
+            coupling("client.py", "schema.py", 1.0),
+            coupling("api.py", "db.py", 0.9),
+            coupling("README.md", "schema.py", 0.8),
+            coupling("client.py", "db.py", 0.2),
+        ];
+        let commits = [
+            commit("c3", &["client.py", "schema.py"]),
+            commit("c2", &["schema.py"]),
+            commit("c1", &["client.py", "schema.py", "api.py"]),
+        ];
+
+        let analysis = find_hidden(
+            &Config::default(),
+            &couplings,
+            &graph,
+            &commits,
+            &PathFilter::default(),
+            100,
+        );
+
+        // Transitive imports count; README.md is not in the graph and
+        // client/db is below the strength threshold.
+        assert_eq!(analysis.summary.coupled_pairs, 2);
+        assert_eq!(analysis.dependencies.len(), 1);
+        let hidden = &analysis.dependencies[0];
+        assert_eq!(
+            (hidden.file_a.as_str(), hidden.file_b.as_str()),
+            ("client.py", "schema.py")
+        );
+        let shas: Vec<_> = hidden.commits.iter().map(|c| c.sha.as_str()).collect();
+        assert_eq!(shas, vec!["c3", "c1"]);
+        assert_eq!(analysis.summary.files_involved, 2);
+    }
+
+    #[test]
+    fn test_import_in_either_direction_is_a_path() {
+        let graph = graph(&["a.rs", "b.rs"], &[("b.rs", "a.rs")]);
+        let analysis = find_hidden(
+            &Config::default(),
+            &[coupling("a.rs", "b.rs", 1.0)],
+            &graph,
+            &[],
+            &PathFilter::default(),
+            100,
+        );
+        assert_eq!(analysis.summary.coupled_pairs, 1);
+        assert!(analysis.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_maps_repository_paths_to_analysis_root() {
+        let graph = graph(&["a.go", "b.go"], &[]);
+        let commits: Vec<_> = (0..8)
+            .map(|i| commit(&format!("c{i}"), &["svc/a.go", "svc/b.go"]))
+            .collect();
+        let config = Config {
+            max_evidence: 3,
+            ..Config::default()
+        };
+        let analysis = find_hidden(
+            &config,
+            &[
+                coupling("svc/a.go", "svc/b.go", 0.75),
+                coupling("other/a.go", "svc/b.go", 0.75),
+            ],
+            &graph,
+            &commits,
+            &PathFilter::new(Some("svc".to_string()), vec![], vec![]),
+            100,
+        );
+
+        assert_eq!(analysis.dependencies.len(), 1);
+        assert_eq!(analysis.dependencies[0].file_a, "a.go");
+        assert_eq!(analysis.dependencies[0].commits.len(), 3);
+    }
+
+    #[test]
+    fn test_analyzer_trait_implementation() {
+        let analyzer = Analyzer::new();
+        assert_eq!(analyzer.name(), "hidden");
+        assert!(analyzer.requires_git());
+        assert_eq!(analyzer.with_days(90).config.days, 90);
+    }
+}
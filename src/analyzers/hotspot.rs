@@ -15,8 +15,30 @@
 //! - Critical = 0.81 (90th percentile in both dimensions: 0.9 * 0.9)
 //! - High = 0.64 (80th percentile: 0.8 * 0.8)
 //! - Moderate = 0.36 (60th percentile: 0.6 * 0.6)
+//!
+//! # Forecast
+//!
+//! Churn is also bucketed into 30-day windows. Files that are not yet
+//! hotspots but have rising churn and above-median complexity are
+//! projected one window ahead by a least-squares trend; those whose
+//! projected score reaches the moderate threshold are listed under
+//! `forecast`, separate from current hotspots.
+//!
+//! # Off-Hours Changes
+//!
+//! With `[churn.off_hours]` enabled, each hotspot reports the share of its
+//! commits made after hours or at weekends, and the summary correlates that
+//! share with the hotspot score across all files.
+//!
+//! # Scoring
+//!
+//! `[hotspot]` tunes the score: `churn_weight` shifts the balance between
+//! churn and complexity (the score is a weighted geometric mean, squared so
+//! the default 0.5 gives the plain product), `normalization` ranks files by
+//! percentile or by z-score, and `churn_half_life_days` decays older
+//! commits' churn. The parameters used are echoed under `parameters`.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use chrono::Utc;
@@ -24,9 +46,11 @@ use ignore::WalkBuilder;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::analyzers::churn::{CommitTime, WorkHours};
 use crate::analyzers::complexity;
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
-use crate::git::GitRepo;
+use crate::config::{HotspotConfig, HotspotNormalization};
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result, SeverityCounts};
+use crate::git::{Commit, GitRepo, PathFilter};
 
 /// Hotspot analyzer configuration.
 #[derive(Debug, Clone)]
@@ -43,8 +67,36 @@ pub struct Config {
     pub high_threshold: f64,
     /// Moderate severity threshold (default: 0.36).
     pub moderate_threshold: f64,
+    /// Weight of churn against complexity, 0.0-1.0 (default: 0.5).
+    pub churn_weight: f64,
+    /// How churn and complexity are normalized (default: rank).
+    pub normalization: HotspotNormalization,
+    /// Half-life in days for decaying older commits' churn (default: none).
+    pub churn_half_life_days: Option<f64>,
+    /// Number of hotspots to report, 0 for all (default: 0).
+    pub top: usize,
+}
+
+impl Config {
+    /// Take the scoring settings from `[hotspot]`.
+    pub fn with_scoring(mut self, config: &HotspotConfig) -> Self {
+        self.churn_weight = config.churn_weight.clamp(0.0, 1.0);
+        self.normalization = config.normalization;
+        self.churn_half_life_days = config.churn_half_life_days.filter(|days| *days > 0.0);
+        self
+    }
+
+    /// Combine normalized churn and complexity (0-100) into a 0-1 score.
+    fn score(&self, churn_pct: f64, complexity_pct: f64) -> f64 {
+        let churn = (churn_pct / 100.0).powf(2.0 * self.churn_weight);
+        let complexity = (complexity_pct / 100.0).powf(2.0 * (1.0 - self.churn_weight));
+        churn * complexity
+    }
 }
 
+/// Length of one churn bucket used for trend fitting.
+const TREND_BUCKET_DAYS: u32 = 30;
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -54,6 +106,10 @@ impl Default for Config {
             critical_threshold: 0.81, // 90th percentile in both
             high_threshold: 0.64,     // 80th percentile in both
             moderate_threshold: 0.36, // 60th percentile in both
+            churn_weight: 0.5,
+            normalization: HotspotNormalization::Rank,
+            churn_half_life_days: None,
+            top: 0,
         }
     }
 }
@@ -90,6 +146,12 @@ impl Analyzer {
         self
     }
 
+    /// Report only the `top` highest-scoring hotspots (0 for all).
+    pub fn with_top(mut self, top: usize) -> Self {
+        self.config.top = top;
+        self
+    }
+
     /// Analyze a project directory for hotspots.
     pub fn analyze_project(&self, root: &Path) -> Result<Analysis> {
         // Get files
@@ -104,7 +166,7 @@ impl Analyzer {
 
         // Run churn analysis using GitRepo
         let git_repo = GitRepo::open(root)?;
-        let churn_data = self.collect_churn_data(&git_repo, &files, root)?;
+        let churn_data = self.collect_churn_data(&git_repo, &files, root, None)?;
 
         // Run complexity analysis
         let complexity_data = self.collect_complexity_data(&files, root)?;
@@ -117,6 +179,7 @@ impl Analyzer {
         git_repo: &GitRepo,
         files: &[std::path::PathBuf],
         root: &Path,
+        work_hours: Option<&WorkHours>,
     ) -> Result<Vec<FileChurn>> {
         // Get cutoff timestamp
         let now = Utc::now();
@@ -125,11 +188,30 @@ impl Analyzer {
 
         // Get all commits in the time range
         let commits = git_repo.log_with_stats(Some(&since), None)?;
+        Ok(self.churn_from_commits(&commits, files, root, work_hours))
+    }
+
+    /// Per-file churn of `commits` for the files in `files`.
+    fn churn_from_commits(
+        &self,
+        commits: &[Commit],
+        files: &[std::path::PathBuf],
+        root: &Path,
+        work_hours: Option<&WorkHours>,
+    ) -> Vec<FileChurn> {
+        let now = Utc::now();
+        let buckets = self.config.days.div_ceil(TREND_BUCKET_DAYS).max(1) as usize;
+        let bucket_secs = i64::from(TREND_BUCKET_DAYS) * 86_400;
 
         // Build file -> churn map
         let mut file_churn: HashMap<String, FileChurn> = HashMap::new();
 
-        for commit in &commits {
+        for commit in commits {
+            let age_secs = (now.timestamp() - commit.timestamp).max(0);
+            let bucket = buckets - 1 - ((age_secs / bucket_secs) as usize).min(buckets - 1);
+            let decay = self.config.churn_half_life_days.map_or(1.0, |half_life| {
+                0.5_f64.powf(age_secs as f64 / 86_400.0 / half_life)
+            });
             for file_change in &commit.files {
                 let path_str = file_change.path.to_string_lossy().to_string();
                 let entry = file_churn
@@ -138,10 +220,19 @@ impl Analyzer {
                         path: path_str.clone(),
                         commits: 0,
                         churn_score: 0.0,
+                        series: vec![0.0; buckets],
+                        off_hours_commits: work_hours.map(|_| 0),
                     });
+                let churn = 1.0 + (file_change.additions + file_change.deletions) as f64 / 100.0;
                 entry.commits += 1;
-                entry.churn_score +=
-                    1.0 + (file_change.additions + file_change.deletions) as f64 / 100.0;
+                if let (Some(count), Some(hours)) = (entry.off_hours_commits.as_mut(), work_hours) {
+                    let time = hours.classify(&commit.author, &commit.email, commit.timestamp);
+                    if time != CommitTime::WorkHours {
+                        *count += 1;
+                    }
+                }
+                entry.churn_score += churn * decay;
+                entry.series[bucket] += churn;
             }
         }
 
@@ -154,11 +245,11 @@ impl Analyzer {
             })
             .collect();
 
-        Ok(file_churn
+        file_churn
             .into_iter()
             .filter(|(path, _)| file_set.contains(path))
             .map(|(_, fc)| fc)
-            .collect())
+            .collect()
     }
 
     fn collect_complexity_data(
@@ -196,30 +287,37 @@ impl Analyzer {
         let complexity_map: HashMap<&str, &FileComplexity> =
             complexity.iter().map(|f| (f.path.as_str(), f)).collect();
 
-        // Sort scores once so percentile lookups are O(log n) via binary search
-        let mut churn_scores: Vec<f64> = churn.iter().map(|f| f.churn_score).collect();
-        churn_scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let mut complexity_scores: Vec<f64> = complexity
-            .iter()
-            .map(|f| f.total_cyclomatic as f64)
-            .collect();
-        complexity_scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let normalization = self.config.normalization;
+        let churn_scale = Scale::new(churn.iter().map(|f| f.churn_score), normalization);
+        let complexity_scale = Scale::new(
+            complexity.iter().map(|f| f.total_cyclomatic as f64),
+            normalization,
+        );
+        // Forecasts project undecayed churn, so rank them against undecayed totals
+        let raw_churn_scale = Scale::new(
+            churn.iter().map(|f| f.series.iter().sum::<f64>()),
+            normalization,
+        );
 
         let mut hotspots = Vec::new();
+        // (off-hours ratio, score) of every file with both, hotspot or not.
+        let mut off_hours_points = Vec::new();
 
         for file in churn {
             // Find matching complexity data
             if let Some(cx) = complexity_map.get(file.path.as_str()) {
-                let churn_pct = percentile_rank_sorted(&churn_scores, file.churn_score);
-                let complexity_pct =
-                    percentile_rank_sorted(&complexity_scores, cx.total_cyclomatic as f64);
+                let churn_pct = churn_scale.percentile(file.churn_score);
+                let complexity_pct = complexity_scale.percentile(cx.total_cyclomatic as f64);
+                let score = self.config.score(churn_pct, complexity_pct);
+                let off_hours_ratio = file.off_hours_ratio();
+                if let Some(ratio) = off_hours_ratio {
+                    off_hours_points.push((ratio, score));
+                }
 
                 // Only include files above minimum thresholds
                 if churn_pct >= self.config.min_churn_percentile
                     && complexity_pct >= self.config.min_complexity_percentile
                 {
-                    // Score is product of normalized percentiles
-                    let score = (churn_pct / 100.0) * (complexity_pct / 100.0);
                     let severity = self.classify_severity(score);
 
                     hotspots.push(Hotspot {
@@ -230,6 +328,7 @@ impl Analyzer {
                         complexity_percentile: complexity_pct,
                         commits: file.commits,
                         avg_complexity: cx.avg_cyclomatic,
+                        off_hours_ratio,
                     });
                 }
             }
@@ -242,6 +341,15 @@ impl Analyzer {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
+        let forecast = self.forecast(
+            churn,
+            &complexity_map,
+            &churn_scale,
+            &raw_churn_scale,
+            &complexity_scale,
+            &hotspots,
+        );
+
         // Calculate summary
         let summary = AnalysisSummary {
             total_hotspots: hotspots.len(),
@@ -253,9 +361,72 @@ impl Analyzer {
                 .iter()
                 .filter(|h| matches!(h.severity, Severity::High))
                 .count(),
+            severity_counts: hotspots.iter().map(|h| h.severity).collect(),
+            forecast_count: forecast.len(),
+            off_hours_correlation: pearson(&off_hours_points),
         };
+        if self.config.top > 0 {
+            hotspots.truncate(self.config.top);
+        }
 
-        Ok(Analysis { hotspots, summary })
+        Ok(Analysis {
+            hotspots,
+            forecast,
+            summary,
+            parameters: Parameters::from(&self.config),
+        })
+    }
+
+    /// Project rising-churn, above-median-complexity files one window ahead
+    /// and keep those that would become hotspots.
+    fn forecast(
+        &self,
+        churn: &[FileChurn],
+        complexity_map: &HashMap<&str, &FileComplexity>,
+        churn_scale: &Scale,
+        raw_churn_scale: &Scale,
+        complexity_scale: &Scale,
+        hotspots: &[Hotspot],
+    ) -> Vec<ForecastHotspot> {
+        let current: HashSet<&str> = hotspots.iter().map(|h| h.file.as_str()).collect();
+
+        let mut forecast: Vec<ForecastHotspot> = churn
+            .iter()
+            .filter(|file| !current.contains(file.path.as_str()))
+            .filter_map(|file| {
+                let cx = complexity_map.get(file.path.as_str())?;
+                let complexity_pct = complexity_scale.percentile(cx.total_cyclomatic as f64);
+                let (intercept, slope) = fit_trend(&file.series);
+                if complexity_pct <= 50.0 || slope <= 0.0 {
+                    return None;
+                }
+
+                let n = file.series.len();
+                let projected: f64 = (n..2 * n)
+                    .map(|x| (intercept + slope * x as f64).max(0.0))
+                    .sum();
+                let projected_pct = raw_churn_scale.percentile(projected);
+                let score = self.config.score(projected_pct, complexity_pct);
+                if projected_pct < self.config.min_churn_percentile
+                    || score < self.config.moderate_threshold
+                {
+                    return None;
+                }
+
+                Some(ForecastHotspot {
+                    file: file.path.clone(),
+                    projected_score: score,
+                    projected_severity: self.classify_severity(score),
+                    churn_trend: slope,
+                    churn_percentile: churn_scale.percentile(file.churn_score),
+                    projected_churn_percentile: projected_pct,
+                    complexity_percentile: complexity_pct,
+                })
+            })
+            .collect();
+
+        forecast.sort_by(|a, b| b.projected_score.total_cmp(&a.projected_score));
+        forecast
     }
 
     fn classify_severity(&self, score: f64) -> Severity {
@@ -276,7 +447,39 @@ impl Analyzer {
 pub struct FileChurn {
     pub path: String,
     pub commits: u32,
+    /// Churn over the window, decayed by age when a half-life is set.
     pub churn_score: f64,
+    /// Undecayed churn per 30-day bucket, oldest first; sums to
+    /// `churn_score` without decay.
+    pub series: Vec<f64>,
+    /// Commits made after hours or at weekends, when tracked.
+    pub off_hours_commits: Option<u32>,
+}
+
+impl FileChurn {
+    /// Share of commits made after hours or at weekends, when tracked.
+    pub fn off_hours_ratio(&self) -> Option<f64> {
+        let count = self.off_hours_commits?;
+        (self.commits > 0).then(|| f64::from(count) / f64::from(self.commits))
+    }
+}
+
+/// Pearson correlation of `(x, y)` points; `None` for fewer than three
+/// points or when either side is constant.
+fn pearson(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 3 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in points {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    (var_x > 0.0 && var_y > 0.0).then(|| cov / (var_x * var_y).sqrt())
 }
 
 /// Internal struct for complexity data.
@@ -287,6 +490,81 @@ pub struct FileComplexity {
     pub avg_cyclomatic: f64,
 }
 
+/// Least-squares line through `series` with x = bucket index.
+///
+/// Returns `(intercept, slope)`; the slope is zero for fewer than two points.
+fn fit_trend(series: &[f64]) -> (f64, f64) {
+    let n = series.len() as f64;
+    if series.len() < 2 {
+        return (series.first().copied().unwrap_or(0.0), 0.0);
+    }
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = series.iter().sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (x, y) in series.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        cov += dx * (y - mean_y);
+        var += dx * dx;
+    }
+    let slope = cov / var;
+    (mean_y - slope * mean_x, slope)
+}
+
+/// Maps raw churn or complexity values onto 0-100.
+enum Scale {
+    /// Percentile rank among the values, sorted ascending.
+    Rank(Vec<f64>),
+    /// Normal CDF of the value's z-score.
+    ZScore { mean: f64, std_dev: f64 },
+}
+
+impl Scale {
+    fn new(values: impl Iterator<Item = f64>, normalization: HotspotNormalization) -> Self {
+        let mut values: Vec<f64> = values.collect();
+        match normalization {
+            HotspotNormalization::Rank => {
+                // Sort once so percentile lookups are O(log n) via binary search
+                values.sort_by(|a, b| a.total_cmp(b));
+                Self::Rank(values)
+            }
+            HotspotNormalization::Zscore => {
+                let n = values.len().max(1) as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                Self::ZScore {
+                    mean,
+                    std_dev: variance.sqrt(),
+                }
+            }
+        }
+    }
+
+    fn percentile(&self, value: f64) -> f64 {
+        match self {
+            Self::Rank(sorted) => percentile_rank_sorted(sorted, value),
+            Self::ZScore { std_dev, .. } if *std_dev == 0.0 => 50.0,
+            Self::ZScore { mean, std_dev } => 100.0 * normal_cdf((value - mean) / std_dev),
+        }
+    }
+}
+
+/// Standard normal CDF, via the Abramowitz-Stegun erf approximation
+/// (7.1.26, absolute error below 1.5e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
 /// Calculate percentile rank of a value in a pre-sorted slice using binary search.
 /// The slice must be sorted in ascending order.
 fn percentile_rank_sorted(sorted: &[f64], value: f64) -> f64 {
@@ -327,18 +605,66 @@ impl AnalyzerTrait for Analyzer {
         // Build absolute paths from the pre-filtered file set
         let files: Vec<std::path::PathBuf> = ctx.files.iter().map(|p| ctx.root.join(p)).collect();
 
+        let analyzer = Self {
+            config: self.config.clone().with_scoring(&ctx.config.hotspot),
+            complexity_analyzer: complexity::Analyzer::new(),
+        };
         let git_repo = GitRepo::open(ctx.root)?;
-        let churn_data = self.collect_churn_data(&git_repo, &files, ctx.root)?;
-        let complexity_data = self.collect_complexity_data(&files, ctx.root)?;
-
-        self.combine_analyses(&churn_data, &complexity_data)
+        let work_hours = WorkHours::from_context(ctx)?;
+        let since = format!("{} days", analyzer.config.days);
+        let commits = ctx.log_with_stats(&git_repo, Some(&since), &PathFilter::default())?;
+        let churn_data =
+            analyzer.churn_from_commits(&commits, &files, ctx.root, work_hours.as_ref());
+        let complexity_data = analyzer.collect_complexity_data(&files, ctx.root)?;
+
+        analyzer.combine_analyses(&churn_data, &complexity_data)
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Analysis {
     pub hotspots: Vec<Hotspot>,
+    /// Files predicted to become hotspots in the next window.
+    #[serde(default)]
+    pub forecast: Vec<ForecastHotspot>,
     pub summary: AnalysisSummary,
+    /// Scoring parameters the analysis ran with.
+    #[serde(default)]
+    pub parameters: Parameters,
+}
+
+/// Scoring parameters, echoed so a result can be reproduced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Parameters {
+    /// Days of history the churn covers.
+    pub days: u32,
+    /// Weight of churn against complexity, 0.0-1.0.
+    pub churn_weight: f64,
+    pub normalization: HotspotNormalization,
+    /// Half-life of the churn decay, when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub churn_half_life_days: Option<f64>,
+    /// Number of hotspots the list was cut to; 0 for all.
+    #[serde(default)]
+    pub top: usize,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::from(&Config::default())
+    }
+}
+
+impl From<&Config> for Parameters {
+    fn from(config: &Config) -> Self {
+        Self {
+            days: config.days,
+            churn_weight: config.churn_weight,
+            normalization: config.normalization,
+            churn_half_life_days: config.churn_half_life_days,
+            top: config.top,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -350,6 +676,23 @@ pub struct Hotspot {
     pub complexity_percentile: f64,
     pub commits: u32,
     pub avg_complexity: f64,
+    /// Share of commits made after hours or at weekends, when enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_hours_ratio: Option<f64>,
+}
+
+/// A file that is not a hotspot yet but is trending towards one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastHotspot {
+    pub file: String,
+    /// Score the file would have with its projected churn.
+    pub projected_score: f64,
+    pub projected_severity: Severity,
+    /// Fitted change in churn per 30-day bucket.
+    pub churn_trend: f64,
+    pub churn_percentile: f64,
+    pub projected_churn_percentile: f64,
+    pub complexity_percentile: f64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -371,11 +714,30 @@ impl std::fmt::Display for Severity {
     }
 }
 
+impl From<Severity> for crate::core::Severity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Critical => Self::Critical,
+            Severity::High => Self::High,
+            Severity::Moderate => Self::Medium,
+            Severity::Low => Self::Low,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnalysisSummary {
     pub total_hotspots: usize,
     pub critical_count: usize,
     pub high_count: usize,
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
+    #[serde(default)]
+    pub forecast_count: usize,
+    /// Correlation between files' off-hours commit share and hotspot score,
+    /// when off-hours metrics are enabled and there is enough data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_hours_correlation: Option<f64>,
 }
 
 #[cfg(test)]
@@ -387,6 +749,18 @@ mod tests {
             path: path.to_string(),
             commits,
             churn_score,
+            series: vec![churn_score],
+            off_hours_commits: None,
+        }
+    }
+
+    fn make_trending_file(path: &str, series: Vec<f64>) -> FileChurn {
+        FileChurn {
+            path: path.to_string(),
+            commits: series.len() as u32,
+            churn_score: series.iter().sum(),
+            series,
+            off_hours_commits: None,
         }
     }
 
@@ -547,6 +921,102 @@ mod tests {
         assert!(result.hotspots[1].score >= result.hotspots[2].score);
     }
 
+    #[test]
+    fn test_churn_weight_shifts_ranking() {
+        let churn = vec![
+            make_churn_file("churny.rs", 30, 300.0),
+            make_churn_file("complex.rs", 10, 100.0),
+            make_churn_file("quiet.rs", 5, 50.0),
+        ];
+        let complexity = vec![
+            make_complexity_file("churny.rs", 10),
+            make_complexity_file("complex.rs", 90),
+            make_complexity_file("quiet.rs", 5),
+        ];
+        let ranked = |churn_weight| {
+            let mut analyzer = Analyzer::new();
+            analyzer.config.min_churn_percentile = 0.0;
+            analyzer.config.min_complexity_percentile = 0.0;
+            analyzer.config.churn_weight = churn_weight;
+            let result = analyzer.combine_analyses(&churn, &complexity).unwrap();
+            result.hotspots[0].file.clone()
+        };
+
+        assert_eq!(ranked(0.9), "churny.rs");
+        assert_eq!(ranked(0.1), "complex.rs");
+        // The default weight is the plain product of the percentiles
+        let config = Config::default();
+        assert!((config.score(90.0, 50.0) - 0.45).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zscore_normalization() {
+        let mut analyzer = Analyzer::new();
+        analyzer.config.min_churn_percentile = 0.0;
+        analyzer.config.min_complexity_percentile = 0.0;
+        analyzer.config.normalization = HotspotNormalization::Zscore;
+        let churn: Vec<_> = (0..5)
+            .map(|i| make_churn_file(&format!("{i}.rs"), 1, 10.0))
+            .chain([make_churn_file("outlier.rs", 1, 10.0)])
+            .collect();
+        let complexity: Vec<_> = (0..5)
+            .map(|i| make_complexity_file(&format!("{i}.rs"), 10 + i))
+            .chain([make_complexity_file("outlier.rs", 500)])
+            .collect();
+
+        let result = analyzer.combine_analyses(&churn, &complexity).unwrap();
+        let outlier = &result.hotspots[0];
+        assert_eq!(outlier.file, "outlier.rs");
+        // Identical churn has no spread and sits at the middle
+        assert!((outlier.churn_percentile - 50.0).abs() < 1e-9);
+        assert!(outlier.complexity_percentile > 97.0);
+        assert!(result.hotspots[1].complexity_percentile < 50.0);
+        assert_eq!(
+            result.parameters.normalization,
+            HotspotNormalization::Zscore
+        );
+    }
+
+    #[test]
+    fn test_top_limits_hotspots_but_not_summary() {
+        let mut analyzer = Analyzer::new().with_top(2);
+        analyzer.config.min_churn_percentile = 0.0;
+        analyzer.config.min_complexity_percentile = 0.0;
+        let churn: Vec<_> = (0..5)
+            .map(|i| make_churn_file(&format!("{i}.rs"), i, f64::from(i)))
+            .collect();
+        let complexity: Vec<_> = (0..5)
+            .map(|i| make_complexity_file(&format!("{i}.rs"), i))
+            .collect();
+
+        let result = analyzer.combine_analyses(&churn, &complexity).unwrap();
+        assert_eq!(result.hotspots.len(), 2);
+        assert_eq!(result.hotspots[0].file, "4.rs");
+        assert_eq!(result.summary.total_hotspots, 5);
+        assert_eq!(result.parameters.top, 2);
+    }
+
+    #[test]
+    fn test_with_scoring_reads_hotspot_config() {
+        let config = Config::default().with_scoring(&HotspotConfig {
+            churn_weight: 1.5,
+            normalization: HotspotNormalization::Zscore,
+            churn_half_life_days: Some(0.0),
+            ..Default::default()
+        });
+        assert!((config.churn_weight - 1.0).abs() < f64::EPSILON);
+        assert_eq!(config.normalization, HotspotNormalization::Zscore);
+        // A zero half-life would divide by zero, so it means no decay
+        assert_eq!(config.churn_half_life_days, None);
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.0) - 0.158_655).abs() < 1e-5);
+    }
+
     #[test]
     fn test_summary_counts() {
         let mut analyzer = Analyzer::new();
@@ -584,6 +1054,7 @@ mod tests {
             complexity_percentile: 90.0,
             commits: 50,
             avg_complexity: 15.5,
+            off_hours_ratio: None,
         };
 
         assert_eq!(hotspot.file, "test.rs");
@@ -606,6 +1077,7 @@ mod tests {
             total_hotspots: 10,
             critical_count: 2,
             high_count: 3,
+            ..Default::default()
         };
         assert_eq!(summary.total_hotspots, 10);
         assert_eq!(summary.critical_count, 2);
@@ -776,6 +1248,48 @@ mod tests {
         assert!((percentile_rank_sorted(&values, 40.0) - 100.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_fit_trend() {
+        let (intercept, slope) = fit_trend(&[1.0, 3.0, 5.0]);
+        assert!((intercept - 1.0).abs() < 1e-9);
+        assert!((slope - 2.0).abs() < 1e-9);
+        assert_eq!(fit_trend(&[4.0]), (4.0, 0.0));
+        assert_eq!(fit_trend(&[]), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_forecast_flags_rising_complex_files() {
+        let analyzer = Analyzer::new();
+
+        let churn = vec![
+            make_trending_file("hot.rs", vec![30.0, 30.0, 30.0]),
+            make_trending_file("rising.rs", vec![0.0, 1.0, 4.0]),
+            make_trending_file("falling.rs", vec![4.0, 1.0, 0.0]),
+            make_trending_file("simple.rs", vec![0.0, 1.0, 4.0]),
+            make_trending_file("quiet.rs", vec![10.0, 10.0, 10.0]),
+            make_trending_file("stable.rs", vec![3.0, 3.0, 3.0]),
+        ];
+        let complexity = vec![
+            make_complexity_file("hot.rs", 50),
+            make_complexity_file("rising.rs", 45),
+            make_complexity_file("falling.rs", 46),
+            make_complexity_file("simple.rs", 1),
+            make_complexity_file("quiet.rs", 2),
+            make_complexity_file("stable.rs", 3),
+        ];
+
+        let result = analyzer.combine_analyses(&churn, &complexity).unwrap();
+        assert!(result.hotspots.iter().any(|h| h.file == "hot.rs"));
+
+        let files: Vec<&str> = result.forecast.iter().map(|f| f.file.as_str()).collect();
+        assert_eq!(files, vec!["rising.rs"]);
+        assert!(result.forecast[0].churn_trend > 0.0);
+        assert!(
+            result.forecast[0].projected_churn_percentile > result.forecast[0].churn_percentile
+        );
+        assert_eq!(result.summary.forecast_count, 1);
+    }
+
     #[test]
     fn test_combine_analyses_large_input() {
         let mut analyzer = Analyzer::new();
@@ -825,7 +1339,7 @@ mod tests {
             .collect();
 
         let churn_data = analyzer
-            .collect_churn_data(&git_repo, &files, &repo_root)
+            .collect_churn_data(&git_repo, &files, &repo_root, None)
             .unwrap();
 
         // The omen repo has git history with file changes.
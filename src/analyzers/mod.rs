@@ -1,20 +1,29 @@
 //! Code analyzers for various metrics and issues.
 
+pub mod api;
+pub mod callgraph;
 pub mod changes;
 pub mod churn;
 pub mod cohesion;
+pub mod commits;
 pub mod complexity;
 pub mod deadcode;
 pub mod defect;
+pub mod docdrift;
+pub mod docker;
 pub mod duplicates;
 pub mod flags;
 pub mod graph;
+pub mod hidden;
 pub mod hotspot;
 pub mod impact;
 pub mod mutation;
 pub mod outline;
 pub mod ownership;
+pub mod pipeline;
+pub mod prioritize;
 pub mod repomap;
+pub mod risk;
 pub mod satd;
 pub mod smells;
 pub mod tdg;
@@ -23,3 +32,37 @@ pub mod temporal;
 // Re-export analyzer types for convenience
 pub use complexity::Analyzer as ComplexityAnalyzer;
 pub use satd::Analyzer as SatdAnalyzer;
+
+/// Output version of each analyzer, recorded in the analysis manifest.
+///
+/// Bump an analyzer's version when the same input would produce different
+/// results, so results from before and after the change are not mistaken
+/// for the same analysis.
+pub const ANALYZER_VERSIONS: &[(&str, u32)] = &[
+    ("api", 1),
+    ("callgraph", 1),
+    ("changes", 1),
+    ("churn", 2),
+    ("cohesion", 1),
+    ("commits", 1),
+    ("complexity", 2),
+    ("deadcode", 2),
+    ("defect", 1),
+    ("docdrift", 1),
+    ("docker", 1),
+    ("duplicates", 1),
+    ("flags", 2),
+    ("graph", 2),
+    ("hidden", 3),
+    ("hotspot", 2),
+    ("mutation", 1),
+    ("outline", 1),
+    ("ownership", 3),
+    ("pipeline", 1),
+    ("repomap", 3),
+    ("risk", 1),
+    ("satd", 1),
+    ("smells", 1),
+    ("tdg", 1),
+    ("temporal", 3),
+];
@@ -0,0 +1,307 @@
+//! Baseline test run health check.
+//!
+//! Mutation scores only mean something when the unmutated suite passes
+//! reliably: a red suite kills every mutant, and a flaky one kills them at
+//! random. Before mutating, the suite is run a few times; a red or flaky
+//! baseline aborts the run with the tests to blame. The slowest passing run
+//! also sets the per-mutant timeout when none is given.
+
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Error, Result};
+
+use super::executor::{shell_command, ExecutionResult};
+use super::ExecutorConfig;
+
+/// Per-mutant timeout as a multiple of the slowest baseline run.
+pub const TIMEOUT_MULTIPLIER: u64 = 3;
+
+/// Lower bound for a scaled per-mutant timeout, so a suite that runs in
+/// milliseconds does not time out on scheduling jitter.
+pub const MIN_TIMEOUT_SECS: u64 = 5;
+
+/// Outcome of running the unmutated test suite several times.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Number of test runs.
+    pub runs: usize,
+    /// Runs that passed.
+    pub passed: usize,
+    /// Duration of the slowest passing run in milliseconds.
+    pub duration_ms: u64,
+    /// Tests that failed in every run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failing_tests: Vec<String>,
+    /// Tests that failed in some runs but not others.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flaky_tests: Vec<String>,
+    /// Runs that hit the timeout.
+    #[serde(default)]
+    pub timed_out: usize,
+}
+
+impl Baseline {
+    /// Run `config`'s test command `runs` times, capturing failing tests.
+    pub fn run(config: &ExecutorConfig, runs: usize) -> Self {
+        let timeout = Duration::from_secs(config.timeout_secs);
+        let outcomes = (0..runs)
+            .map(|_| {
+                let start = Instant::now();
+                let (result, output) = run_captured(config, timeout);
+                (result, start.elapsed(), failed_tests(&output))
+            })
+            .collect();
+        Self::from_runs(outcomes)
+    }
+
+    fn from_runs(outcomes: Vec<(ExecutionResult, Duration, BTreeSet<String>)>) -> Self {
+        let mut baseline = Self {
+            runs: outcomes.len(),
+            ..Default::default()
+        };
+        let mut always: Option<BTreeSet<String>> = None;
+        let mut ever = BTreeSet::new();
+        for (result, duration, failed) in outcomes {
+            match result {
+                ExecutionResult::Success => {
+                    baseline.passed += 1;
+                    baseline.duration_ms = baseline.duration_ms.max(duration.as_millis() as u64);
+                }
+                ExecutionResult::Timeout => baseline.timed_out += 1,
+                ExecutionResult::Failed | ExecutionResult::Error => {}
+            }
+            always = Some(match always {
+                Some(always) => always.intersection(&failed).cloned().collect(),
+                None => failed.clone(),
+            });
+            ever.extend(failed);
+        }
+        let always = always.unwrap_or_default();
+        baseline.flaky_tests = ever.difference(&always).cloned().collect();
+        baseline.failing_tests = always.into_iter().collect();
+        baseline
+    }
+
+    /// Whether every run passed.
+    pub fn is_healthy(&self) -> bool {
+        self.passed == self.runs
+    }
+
+    /// Whether some runs passed and others did not.
+    pub fn is_flaky(&self) -> bool {
+        self.passed > 0 && !self.is_healthy()
+    }
+
+    /// Per-mutant timeout scaled from the slowest passing run.
+    pub fn mutant_timeout_secs(&self) -> u64 {
+        (self.duration_ms * TIMEOUT_MULTIPLIER)
+            .div_ceil(1000)
+            .max(MIN_TIMEOUT_SECS)
+    }
+
+    /// Fail with a report of the red or flaky tests unless every run passed.
+    pub fn check(&self) -> Result<()> {
+        if self.is_healthy() {
+            return Ok(());
+        }
+        let mut report = if self.is_flaky() {
+            format!(
+                "Baseline test run is flaky: {}/{} runs passed",
+                self.passed, self.runs
+            )
+        } else {
+            format!("Baseline test run failed in all {} runs", self.runs)
+        };
+        if self.timed_out > 0 {
+            report.push_str(&format!(" ({} timed out)", self.timed_out));
+        }
+        for (label, tests) in [
+            ("Failing tests", &self.failing_tests),
+            ("Flaky tests", &self.flaky_tests),
+        ] {
+            if !tests.is_empty() {
+                report.push_str(&format!("\n{label}:"));
+                for test in tests {
+                    report.push_str(&format!("\n  {test}"));
+                }
+            }
+        }
+        report
+            .push_str("\nFix the test suite before mutation testing; scores would be meaningless.");
+        Err(Error::analysis(report))
+    }
+}
+
+/// Run the test command with a timeout, returning its combined output.
+fn run_captured(config: &ExecutorConfig, timeout: Duration) -> (ExecutionResult, String) {
+    let mut cmd = shell_command(&config.test_command, config.working_dir.as_deref());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let Ok(mut child) = cmd.spawn() else {
+        return (ExecutionResult::Error, String::new());
+    };
+    // Drain both pipes on threads so a chatty suite can't block on a full pipe.
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = pipe.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).into_owned()
+        })
+    })
+    .collect();
+
+    let start = Instant::now();
+    let result = loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => break ExecutionResult::Success,
+            Ok(Some(_)) => break ExecutionResult::Failed,
+            Ok(None) if start.elapsed() > timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break ExecutionResult::Timeout;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+            Err(_) => break ExecutionResult::Error,
+        }
+    };
+    // A killed shell can leave children holding the pipes open; don't wait
+    // on them after a timeout.
+    if result == ExecutionResult::Timeout {
+        return (result, String::new());
+    }
+    let output = readers
+        .into_iter()
+        .filter_map(|r| r.join().ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (result, output)
+}
+
+/// Names of the tests reported as failing in a test runner's output, from
+/// the failure lines of cargo, go, pytest, jest/vitest and rspec.
+fn failed_tests(output: &str) -> BTreeSet<String> {
+    static FAILED_TEST: OnceLock<Regex> = OnceLock::new();
+    let failed_test = FAILED_TEST.get_or_init(|| {
+        Regex::new(concat!(
+            r"(?m)^(?:test (\S+) \.\.\. FAILED",
+            r"|\s*--- FAIL: (\S+)(?: \(.*\))?",
+            r"|FAILED (\S+)(?: - .*)?",
+            r"|\s*[✕×] (.+?)(?: \(\d+ ?m?s\))?",
+            r"|rspec (\S+)(?: #.*)?) *$",
+        ))
+        .expect("valid regex")
+    });
+    failed_test
+        .captures_iter(output)
+        .filter_map(|c| c.iter().skip(1).flatten().next())
+        .map(|m| m.as_str().trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(
+        result: ExecutionResult,
+        ms: u64,
+        failed: &[&str],
+    ) -> (ExecutionResult, Duration, BTreeSet<String>) {
+        let failed = failed.iter().map(|s| s.to_string()).collect();
+        (result, Duration::from_millis(ms), failed)
+    }
+
+    #[test]
+    fn test_failed_tests_from_runner_output() {
+        let output = "\
+test parser::ok ... ok
+test parser::broken ... FAILED
+--- FAIL: TestServe (0.01s)
+FAILED tests/test_api.py::test_login - AssertionError
+  ✕ renders header (12 ms)
+rspec ./spec/user_spec.rb:12 # User saves
+";
+        let names: Vec<String> = failed_tests(output).into_iter().collect();
+        assert_eq!(
+            names,
+            vec![
+                "./spec/user_spec.rb:12",
+                "TestServe",
+                "parser::broken",
+                "renders header",
+                "tests/test_api.py::test_login",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_baseline_separates_flaky_from_failing() {
+        let baseline = Baseline::from_runs(vec![
+            run(ExecutionResult::Failed, 900, &["a", "b"]),
+            run(ExecutionResult::Success, 1200, &[]),
+            run(ExecutionResult::Failed, 800, &["b"]),
+        ]);
+        assert!(baseline.is_flaky());
+        assert_eq!(baseline.passed, 1);
+        assert_eq!(baseline.duration_ms, 1200);
+        assert!(baseline.failing_tests.is_empty());
+        assert_eq!(baseline.flaky_tests, vec!["a", "b"]);
+        let report = baseline.check().unwrap_err().to_string();
+        assert!(report.contains("flaky: 1/3 runs passed"), "{report}");
+        assert!(report.contains("Flaky tests:\n  a\n  b"), "{report}");
+
+        let red = Baseline::from_runs(vec![
+            run(ExecutionResult::Failed, 10, &["a", "b"]),
+            run(ExecutionResult::Timeout, 10, &["b"]),
+        ]);
+        assert!(!red.is_flaky());
+        assert_eq!(red.failing_tests, vec!["b"]);
+        assert_eq!(red.flaky_tests, vec!["a"]);
+        assert_eq!(red.timed_out, 1);
+        assert!(red.check().is_err());
+    }
+
+    #[test]
+    fn test_baseline_scales_mutant_timeout() {
+        let healthy = Baseline::from_runs(vec![
+            run(ExecutionResult::Success, 4000, &[]),
+            run(ExecutionResult::Success, 4100, &[]),
+        ]);
+        assert!(healthy.check().is_ok());
+        assert_eq!(healthy.mutant_timeout_secs(), 13);
+
+        let fast = Baseline::from_runs(vec![run(ExecutionResult::Success, 20, &[])]);
+        assert_eq!(fast.mutant_timeout_secs(), MIN_TIMEOUT_SECS);
+    }
+
+    #[test]
+    fn test_baseline_run_executes_command() {
+        let config = ExecutorConfig::with_command("echo 'test a::b ... FAILED'; exit 1").timeout(5);
+        let baseline = Baseline::run(&config, 2);
+        assert_eq!(baseline.runs, 2);
+        assert_eq!(baseline.passed, 0);
+        assert_eq!(baseline.failing_tests, vec!["a::b"]);
+
+        assert!(Baseline::run(&ExecutorConfig::with_command("true").timeout(5), 2).is_healthy());
+    }
+}
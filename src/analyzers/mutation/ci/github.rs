@@ -327,6 +327,7 @@ mod tests {
                 total: 10,
                 killed: 7,
                 survived: 3,
+                ..Default::default()
             },
         );
         by_operator.insert(
@@ -335,6 +336,7 @@ mod tests {
                 total: 5,
                 killed: 5,
                 survived: 0,
+                ..Default::default()
             },
         );
 
@@ -417,7 +419,9 @@ mod tests {
                 mutation_score: 0.8,
                 duration_ms: 5000,
                 by_operator,
+                suggested_operators: Vec::new(),
             },
+            baseline: None,
         }
     }
 
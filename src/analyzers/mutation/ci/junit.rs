@@ -0,0 +1,195 @@
+//! JUnit XML output for mutation testing.
+//!
+//! Each mutant is a test case and each file a test suite, so CI dashboards
+//! (Jenkins, Buildkite, GitLab) show survivors without a custom plugin:
+//! killed mutants pass, survived mutants fail, and mutants that don't count
+//! toward the mutation score (timeouts, build errors, equivalent, pending,
+//! ML-skipped) are reported as skipped.
+
+use std::fmt::Write;
+
+use super::super::{Analysis, MutantStatus, MutationResult};
+use crate::output::escape_xml;
+
+/// Format mutation results as a JUnit XML document.
+pub fn format_junit(analysis: &Analysis) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let (tests, failures, skipped) = counts(analysis.files.iter().flat_map(|f| &f.mutants));
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"omen mutation\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        tests,
+        failures,
+        skipped,
+        seconds(analysis.summary.duration_ms)
+    );
+
+    for file in &analysis.files {
+        let (tests, failures, skipped) = counts(file.mutants.iter());
+        let time: u64 = file.mutants.iter().map(|m| m.duration_ms).sum();
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">",
+            escape_xml(&file.path),
+            tests,
+            failures,
+            skipped,
+            seconds(time)
+        );
+        for result in &file.mutants {
+            write_testcase(&mut out, &file.path, result);
+        }
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+fn write_testcase(out: &mut String, file: &str, result: &MutationResult) {
+    let mutant = &result.mutant;
+    let name = format!(
+        "{} {}:{} {}",
+        mutant.operator, mutant.line, mutant.column, mutant.description
+    );
+    let _ = write!(
+        out,
+        "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+        escape_xml(file),
+        escape_xml(&name),
+        seconds(result.duration_ms)
+    );
+    match result.status {
+        MutantStatus::Killed => out.push_str("/>\n"),
+        MutantStatus::Survived => {
+            let _ = writeln!(
+                out,
+                ">\n      <failure message=\"mutant survived\" type=\"survived\">{}:{}:{}: `{}` -> `{}` was not detected by the test suite</failure>\n    </testcase>",
+                escape_xml(file),
+                mutant.line,
+                mutant.column,
+                escape_xml(&mutant.original),
+                escape_xml(&mutant.replacement)
+            );
+        }
+        status => {
+            let _ = writeln!(
+                out,
+                ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                skip_reason(status)
+            );
+        }
+    }
+}
+
+/// (tests, failures, skipped) over `results`.
+fn counts<'a>(results: impl Iterator<Item = &'a MutationResult>) -> (usize, usize, usize) {
+    results.fold((0, 0, 0), |(tests, failures, skipped), r| {
+        (
+            tests + 1,
+            failures + usize::from(r.status.is_survived()),
+            skipped + usize::from(!r.status.counts_for_score()),
+        )
+    })
+}
+
+/// Why a mutant that doesn't count toward the score was skipped.
+pub(super) fn skip_reason(status: MutantStatus) -> &'static str {
+    match status {
+        MutantStatus::Timeout => "timeout",
+        MutantStatus::BuildError => "build error",
+        MutantStatus::Equivalent => "equivalent mutant",
+        MutantStatus::Pending => "not executed",
+        MutantStatus::Skipped => "predicted killed",
+        MutantStatus::Killed | MutantStatus::Survived => "",
+    }
+}
+
+fn seconds(ms: u64) -> f64 {
+    ms as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::mutation::{FileResult, Mutant, Summary};
+    use std::collections::HashMap;
+
+    fn result(id: &str, line: u32, status: MutantStatus) -> MutationResult {
+        MutationResult::new(
+            Mutant::new(
+                id,
+                "src/cmp.rs",
+                "ROR",
+                line,
+                7,
+                "<",
+                ">=",
+                "Replace < with >=",
+                (0, 1),
+            ),
+            status,
+            1500,
+        )
+    }
+
+    fn analysis(mutants: Vec<MutationResult>) -> Analysis {
+        Analysis {
+            files: vec![FileResult {
+                path: "src/cmp.rs".to_string(),
+                mutants,
+                killed: 0,
+                survived: 0,
+                timeout: 0,
+                error: 0,
+                skipped: 0,
+                score: 0.0,
+            }],
+            summary: Summary {
+                total_files: 1,
+                total_mutants: 0,
+                killed: 0,
+                survived: 0,
+                timeout: 0,
+                error: 0,
+                skipped: 0,
+                mutation_score: 0.0,
+                duration_ms: 4500,
+                by_operator: HashMap::new(),
+                suggested_operators: Vec::new(),
+            },
+            baseline: None,
+        }
+    }
+
+    #[test]
+    fn test_format_junit_maps_statuses() {
+        let xml = format_junit(&analysis(vec![
+            result("1", 3, MutantStatus::Killed),
+            result("2", 9, MutantStatus::Survived),
+            result("3", 12, MutantStatus::Timeout),
+        ]));
+
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains(
+            "<testsuites name=\"omen mutation\" tests=\"3\" failures=\"1\" skipped=\"1\" time=\"4.500\">"
+        ));
+        assert!(xml.contains(
+            "<testcase classname=\"src/cmp.rs\" name=\"ROR 3:7 Replace &lt; with &gt;=\" time=\"1.500\"/>"
+        ));
+        assert!(xml.contains("<failure message=\"mutant survived\" type=\"survived\">src/cmp.rs:9:7: `&lt;` -&gt; `&gt;=`"));
+        assert!(xml.contains("<skipped message=\"timeout\"/>"));
+        assert_eq!(xml.matches("<testcase").count(), 3);
+        assert_eq!(xml.matches("</testsuite>").count(), 1);
+    }
+
+    #[test]
+    fn test_format_junit_empty() {
+        let xml = format_junit(&Analysis {
+            files: Vec::new(),
+            ..analysis(Vec::new())
+        });
+        assert!(xml.contains("tests=\"0\" failures=\"0\""));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! - **Incremental mode**: Only test mutants in changed files/lines
 //! - **GitHub integration**: Post results as PR comments and check runs
+//! - **JUnit XML / TAP**: Report each mutant as a test case for CI dashboards
 //!
 //! # Example
 //!
@@ -24,6 +25,10 @@
 
 mod github;
 mod incremental;
+mod junit;
+mod tap;
 
 pub use github::{get_pr_number_from_event, GitHubReporter};
 pub use incremental::IncrementalMutation;
+pub use junit::format_junit;
+pub use tap::format_tap;
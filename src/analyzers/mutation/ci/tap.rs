@@ -0,0 +1,131 @@
+//! TAP (Test Anything Protocol) output for mutation testing.
+//!
+//! Emits TAP version 13 with one test point per mutant: killed is `ok`,
+//! survived is `not ok` with a YAML diagnostic block, and mutants that don't
+//! count toward the score are `ok ... # SKIP <reason>`.
+
+use std::fmt::Write;
+
+use super::super::{Analysis, MutantStatus};
+use super::junit::skip_reason;
+
+/// Format mutation results as a TAP stream.
+pub fn format_tap(analysis: &Analysis) -> String {
+    let total: usize = analysis.files.iter().map(|f| f.mutants.len()).sum();
+    let mut out = format!("TAP version 13\n1..{total}\n");
+
+    let results = analysis
+        .files
+        .iter()
+        .flat_map(|file| file.mutants.iter().map(move |r| (file, r)));
+    for (n, (file, result)) in results.enumerate() {
+        let mutant = &result.mutant;
+        let description = format!(
+            "{}:{}:{} {} {}",
+            file.path, mutant.line, mutant.column, mutant.operator, mutant.description
+        )
+        .replace('#', "\\#");
+        match result.status {
+            MutantStatus::Killed => {
+                let _ = writeln!(out, "ok {} - {}", n + 1, description);
+            }
+            MutantStatus::Survived => {
+                let _ = writeln!(out, "not ok {} - {}", n + 1, description);
+                let _ = writeln!(out, "  ---");
+                let _ = writeln!(out, "  message: mutant survived");
+                let _ = writeln!(out, "  original: {:?}", mutant.original);
+                let _ = writeln!(out, "  replacement: {:?}", mutant.replacement);
+                let _ = writeln!(out, "  ...");
+            }
+            status => {
+                let _ = writeln!(
+                    out,
+                    "ok {} - {} # SKIP {}",
+                    n + 1,
+                    description,
+                    skip_reason(status)
+                );
+            }
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "# mutation score {:.1}% ({} killed, {} survived)",
+        analysis.summary.mutation_score * 100.0,
+        analysis.summary.killed,
+        analysis.summary.survived
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::mutation::{FileResult, Mutant, MutationResult, Summary};
+    use std::collections::HashMap;
+
+    fn result(line: u32, status: MutantStatus) -> MutationResult {
+        MutationResult::new(
+            Mutant::new(
+                line.to_string(),
+                "src/a.rs",
+                "CRR",
+                line,
+                1,
+                "1",
+                "0",
+                "Replace 1 with 0",
+                (0, 1),
+            ),
+            status,
+            10,
+        )
+    }
+
+    #[test]
+    fn test_format_tap() {
+        let analysis = Analysis {
+            files: vec![FileResult {
+                path: "src/a.rs".to_string(),
+                mutants: vec![
+                    result(1, MutantStatus::Killed),
+                    result(2, MutantStatus::Survived),
+                    result(3, MutantStatus::Equivalent),
+                ],
+                killed: 1,
+                survived: 1,
+                timeout: 0,
+                error: 0,
+                skipped: 0,
+                score: 0.5,
+            }],
+            summary: Summary {
+                total_files: 1,
+                total_mutants: 3,
+                killed: 1,
+                survived: 1,
+                timeout: 0,
+                error: 0,
+                skipped: 0,
+                mutation_score: 0.5,
+                duration_ms: 30,
+                by_operator: HashMap::new(),
+                suggested_operators: Vec::new(),
+            },
+            baseline: None,
+        };
+
+        let tap = format_tap(&analysis);
+        let lines: Vec<&str> = tap.lines().collect();
+
+        assert_eq!(lines[0], "TAP version 13");
+        assert_eq!(lines[1], "1..3");
+        assert_eq!(lines[2], "ok 1 - src/a.rs:1:1 CRR Replace 1 with 0");
+        assert_eq!(lines[3], "not ok 2 - src/a.rs:2:1 CRR Replace 1 with 0");
+        assert_eq!(lines[4], "  ---");
+        assert!(tap.contains("  original: \"1\"\n  replacement: \"0\"\n  ...\n"));
+        assert!(tap.contains("ok 3 - src/a.rs:3:1 CRR Replace 1 with 0 # SKIP equivalent mutant"));
+        assert!(tap.ends_with("# mutation score 50.0% (1 killed, 1 survived)\n"));
+    }
+}
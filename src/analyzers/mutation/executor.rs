@@ -165,20 +165,10 @@ impl MutantExecutor {
 
     /// Execute tests and return the mutant status.
     fn run_tests(&self) -> MutantStatus {
-        let mut cmd = if cfg!(windows) {
-            let mut c = Command::new("cmd");
-            c.args(["/C", &self.config.test_command]);
-            c
-        } else {
-            let mut c = Command::new("sh");
-            c.args(["-c", &self.config.test_command]);
-            c
-        };
-
-        if let Some(dir) = &self.config.working_dir {
-            cmd.current_dir(dir);
-        }
-
+        let mut cmd = shell_command(
+            &self.config.test_command,
+            self.config.working_dir.as_deref(),
+        );
         cmd.stdout(Stdio::null());
         cmd.stderr(Stdio::null());
 
@@ -430,6 +420,23 @@ async fn run_tests_async(config: &ExecutorConfig) -> MutantStatus {
     }
 }
 
+/// Run `command` through the platform shell, in `working_dir` when given.
+pub(super) fn shell_command(command: &str, working_dir: Option<&Path>) -> Command {
+    let mut cmd = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", command]);
+        c
+    };
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    cmd
+}
+
 /// Execute a command with a timeout.
 fn execute_with_timeout(cmd: &mut Command, timeout: Duration) -> ExecutionResult {
     let start = Instant::now();
@@ -19,6 +19,7 @@
 //! This module uses RAII guards to ensure source files are always restored
 //! to their original state, even if a panic occurs during testing.
 
+mod baseline;
 pub mod ci;
 pub mod coverage;
 pub mod equivalent;
@@ -29,8 +30,11 @@ mod mutant;
 mod operator;
 pub mod operators;
 mod safety;
+mod scope;
+mod tuning;
 pub mod worker;
 
+pub use baseline::{Baseline, MIN_TIMEOUT_SECS, TIMEOUT_MULTIPLIER};
 pub use executor::{
     detect_test_command, AsyncMutantExecutor, ExecutionResult, ExecutorConfig, MutantExecutor,
     ProgressCallback,
@@ -44,6 +48,8 @@ pub use operators::{
     ReturnValueOperator, StatementOperator, UnaryOperator,
 };
 pub use safety::{atomic_write, has_uncommitted_changes, MutationGuard};
+pub use scope::{Package, PackageKind, Workspace};
+pub use tuning::suggest_operators;
 pub use worker::{
     FileLockManager, ProgressUpdate, WorkItem, WorkQueue, WorkerPoolConfig, WorkerPoolHandle,
 };
@@ -61,6 +67,15 @@ use serde::{Deserialize, Serialize};
 use crate::config::Config;
 use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
 
+/// Per-mutant timeout when no baseline run measured the suite.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Cap on each baseline test run when no timeout is given.
+const BASELINE_TIMEOUT_SECS: u64 = 600;
+
+/// Unmutated test runs before mutation testing starts, unless configured.
+pub const DEFAULT_BASELINE_RUNS: usize = 3;
+
 /// Mutation testing mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MutationMode {
@@ -79,8 +94,13 @@ pub struct Analyzer {
     operators: Vec<String>,
     /// Test command to run.
     test_command: Option<String>,
-    /// Timeout in seconds.
+    /// Also run the tests of packages depending on the mutated package.
+    include_dependents: bool,
+    /// Timeout in seconds (0 = scale from the baseline test run).
     timeout_secs: u64,
+    /// Unmutated test runs checked for red or flaky tests before mutating
+    /// (0 = skip the check).
+    baseline_runs: usize,
     /// Whether to only generate mutants (no execution).
     dry_run: bool,
     /// Minimum mutation score threshold for check mode.
@@ -115,7 +135,9 @@ impl Analyzer {
         Self {
             operators: vec!["CRR".to_string(), "ROR".to_string(), "AOR".to_string()],
             test_command: None,
-            timeout_secs: 30,
+            include_dependents: false,
+            timeout_secs: 0,
+            baseline_runs: DEFAULT_BASELINE_RUNS,
             dry_run: false,
             min_score: None,
             jobs: 0,
@@ -141,12 +163,25 @@ impl Analyzer {
         self
     }
 
-    /// Set the timeout.
+    /// Include dependent packages when scoping tests to a mutant's package.
+    pub fn include_dependents(mut self, include: bool) -> Self {
+        self.include_dependents = include;
+        self
+    }
+
+    /// Set the timeout (0 = scale from the baseline test run).
     pub fn timeout(mut self, secs: u64) -> Self {
         self.timeout_secs = secs;
         self
     }
 
+    /// Set how many times the unmutated suite runs before mutating
+    /// (0 = skip the health check).
+    pub fn baseline_runs(mut self, runs: usize) -> Self {
+        self.baseline_runs = runs;
+        self
+    }
+
     /// Enable dry-run mode (generate only, no execution).
     pub fn dry_run(mut self, dry_run: bool) -> Self {
         self.dry_run = dry_run;
@@ -209,7 +244,6 @@ impl Analyzer {
     }
 
     /// Get the appropriate registry based on mutation mode.
-    #[allow(dead_code)]
     fn get_registry(&self) -> OperatorRegistry {
         match self.mode {
             MutationMode::All => default_registry(),
@@ -218,6 +252,24 @@ impl Analyzer {
         }
     }
 
+    /// Operators to generate mutants with: every operator in thorough mode,
+    /// language-specific ones included, otherwise the mode's operators that
+    /// are enabled by name.
+    fn selected_operators(&self) -> Vec<Box<dyn MutationOperator>> {
+        let operators = self.get_registry().into_operators();
+        if self.mode == MutationMode::Thorough {
+            return operators;
+        }
+        operators
+            .into_iter()
+            .filter(|op| {
+                self.operators
+                    .iter()
+                    .any(|n| n.eq_ignore_ascii_case(op.name()))
+            })
+            .collect()
+    }
+
     /// Get effective number of workers.
     #[allow(dead_code)]
     fn effective_jobs(&self) -> usize {
@@ -234,24 +286,7 @@ impl Analyzer {
     pub fn generate_only(&self, ctx: &AnalysisContext<'_>) -> Result<Analysis> {
         let start = Instant::now();
 
-        // Build operator list
-        let registry = default_registry();
-        let operator_names: Vec<&str> = self.operators.iter().map(|s| s.as_str()).collect();
-        let operators = registry.get_by_names(&operator_names);
-
-        let generator = MutantGenerator::new(
-            operators
-                .into_iter()
-                .map(|op| -> Box<dyn MutationOperator> {
-                    match op.name() {
-                        "CRR" => Box::new(LiteralOperator),
-                        "ROR" => Box::new(RelationalOperator),
-                        "AOR" => Box::new(ArithmeticOperator),
-                        _ => Box::new(LiteralOperator), // Fallback
-                    }
-                })
-                .collect(),
-        );
+        let generator = MutantGenerator::new(self.selected_operators());
 
         let total_files = ctx.files.len();
         let counter = Arc::new(AtomicUsize::new(0));
@@ -298,7 +333,11 @@ impl Analyzer {
         let files: Vec<FileResult> = file_results.into_values().collect();
         let summary = build_summary(&files, duration.as_millis() as u64);
 
-        Ok(Analysis { files, summary })
+        Ok(Analysis {
+            files,
+            summary,
+            baseline: None,
+        })
     }
 }
 
@@ -331,29 +370,42 @@ impl AnalyzerTrait for Analyzer {
                 Error::analysis("Could not detect test command. Please provide --test-command")
             })?;
 
-        // Build operator list
-        let registry = default_registry();
-        let operator_names: Vec<&str> = self.operators.iter().map(|s| s.as_str()).collect();
-        let operators = registry.get_by_names(&operator_names);
-
-        let generator = MutantGenerator::new(
-            operators
-                .into_iter()
-                .map(|op| -> Box<dyn MutationOperator> {
-                    match op.name() {
-                        "CRR" => Box::new(LiteralOperator),
-                        "ROR" => Box::new(RelationalOperator),
-                        "AOR" => Box::new(ArithmeticOperator),
-                        _ => Box::new(LiteralOperator),
-                    }
-                })
-                .collect(),
-        );
+        let operators = self.selected_operators();
+        let operator_names: Vec<String> =
+            operators.iter().map(|op| op.name().to_string()).collect();
+        let generator = MutantGenerator::new(operators);
+
+        // Run the unmutated suite first: a red or flaky baseline would make
+        // every score meaningless. Its duration scales the mutant timeout.
+        let baseline = (self.baseline_runs > 0).then(|| {
+            let cap = match self.timeout_secs {
+                0 => BASELINE_TIMEOUT_SECS,
+                secs => secs,
+            };
+            let config = ExecutorConfig::with_command(&test_cmd)
+                .timeout(cap)
+                .working_dir(project_root);
+            Baseline::run(&config, self.baseline_runs)
+        });
+        if let Some(baseline) = &baseline {
+            baseline.check()?;
+        }
+        let timeout_secs = match (self.timeout_secs, &baseline) {
+            (0, Some(baseline)) => baseline.mutant_timeout_secs(),
+            (0, None) => DEFAULT_TIMEOUT_SECS,
+            (secs, _) => secs,
+        };
 
         let executor_config = ExecutorConfig::with_command(&test_cmd)
-            .timeout(self.timeout_secs)
+            .timeout(timeout_secs)
             .working_dir(project_root);
-        let executor = MutantExecutor::new(executor_config);
+
+        // In a mono-repo, an auto-detected command is narrowed to the package
+        // owning each file; an explicit --test-command is always used as is.
+        let workspace = self
+            .test_command
+            .is_none()
+            .then(|| Workspace::discover(project_root));
 
         let total_files = ctx.files.len();
         let counter = Arc::new(AtomicUsize::new(0));
@@ -388,6 +440,15 @@ impl AnalyzerTrait for Analyzer {
                 score: 0.0,
             };
 
+            let mut file_config = executor_config.clone();
+            if let Some(cmd) = workspace
+                .as_ref()
+                .and_then(|ws| ws.test_command(path, self.include_dependents))
+            {
+                file_config.test_command = cmd;
+            }
+            let executor = MutantExecutor::new(file_config);
+
             // Get source as string for predictor context
             let source_str = String::from_utf8_lossy(&source);
 
@@ -446,7 +507,8 @@ impl AnalyzerTrait for Analyzer {
         }
 
         let duration = start.elapsed();
-        let summary = build_summary(&file_results, duration.as_millis() as u64);
+        let mut summary = build_summary(&file_results, duration.as_millis() as u64);
+        summary.suggested_operators = suggest_operators(&file_results, &operator_names);
 
         // Check threshold if in check mode
         if let Some(min_score) = self.min_score {
@@ -465,11 +527,12 @@ impl AnalyzerTrait for Analyzer {
         Ok(Analysis {
             files: file_results,
             summary,
+            baseline,
         })
     }
 
-    fn configure(&mut self, _config: &Config) -> Result<()> {
-        // Could read from config file in the future
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.operators = config.mutation.operators.clone();
         Ok(())
     }
 }
@@ -481,6 +544,9 @@ pub struct Analysis {
     pub files: Vec<FileResult>,
     /// Summary statistics.
     pub summary: Summary,
+    /// Unmutated test runs made before mutating.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<Baseline>,
 }
 
 /// Per-file mutation testing result.
@@ -529,10 +595,14 @@ pub struct Summary {
     pub duration_ms: u64,
     /// Mutants by operator.
     pub by_operator: HashMap<String, OperatorStats>,
+    /// Operators that would have found what this run found at the least
+    /// cost, for `[mutation] operators`. Empty when no mutant was run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggested_operators: Vec<String>,
 }
 
 /// Statistics for a single operator.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct OperatorStats {
     /// Total mutants from this operator.
     pub total: usize,
@@ -540,6 +610,15 @@ pub struct OperatorStats {
     pub killed: usize,
     /// Survived mutants.
     pub survived: usize,
+    /// Killed share of killed and survived mutants.
+    #[serde(default)]
+    pub kill_rate: f64,
+    /// Mean test run per executed mutant in milliseconds.
+    #[serde(default)]
+    pub mean_duration_ms: u64,
+    /// Killed mutants on lines where no other operator's mutant was killed.
+    #[serde(default)]
+    pub unique_kills: usize,
 }
 
 /// Build summary from file results.
@@ -555,6 +634,7 @@ fn build_summary(files: &[FileResult], duration_ms: u64) -> Summary {
         mutation_score: 0.0,
         duration_ms,
         by_operator: HashMap::new(),
+        suggested_operators: Vec::new(),
     };
 
     for file in files {
@@ -568,14 +648,7 @@ fn build_summary(files: &[FileResult], duration_ms: u64) -> Summary {
         // Aggregate by operator
         for result in &file.mutants {
             let op = &result.mutant.operator;
-            let entry = summary
-                .by_operator
-                .entry(op.clone())
-                .or_insert(OperatorStats {
-                    total: 0,
-                    killed: 0,
-                    survived: 0,
-                });
+            let entry = summary.by_operator.entry(op.clone()).or_default();
             entry.total += 1;
             if result.status == MutantStatus::Killed {
                 entry.killed += 1;
@@ -589,6 +662,7 @@ fn build_summary(files: &[FileResult], duration_ms: u64) -> Summary {
     if total_scored > 0 {
         summary.mutation_score = summary.killed as f64 / total_scored as f64;
     }
+    tuning::annotate(files, &mut summary.by_operator);
 
     summary
 }
@@ -602,6 +676,8 @@ mod tests {
         let analyzer = Analyzer::new();
         assert_eq!(analyzer.name(), "mutation");
         assert!(!analyzer.dry_run);
+        assert_eq!(analyzer.timeout_secs, 0);
+        assert_eq!(analyzer.baseline_runs, 3);
     }
 
     #[test]
@@ -609,12 +685,14 @@ mod tests {
         let analyzer = Analyzer::new()
             .operators(vec!["CRR".to_string()])
             .test_command(Some("cargo test".to_string()))
+            .include_dependents(true)
             .timeout(60)
             .dry_run(true)
             .min_score(Some(0.8));
 
         assert_eq!(analyzer.operators, vec!["CRR"]);
         assert_eq!(analyzer.test_command, Some("cargo test".to_string()));
+        assert!(analyzer.include_dependents);
         assert_eq!(analyzer.timeout_secs, 60);
         assert!(analyzer.dry_run);
         assert_eq!(analyzer.min_score, Some(0.8));
@@ -756,6 +834,30 @@ mod tests {
         assert!(registry.operators().len() >= 3);
     }
 
+    #[test]
+    fn test_selected_operators_by_mode() {
+        let names = |analyzer: Analyzer| -> Vec<&'static str> {
+            analyzer
+                .selected_operators()
+                .iter()
+                .map(|op| op.name())
+                .collect()
+        };
+        assert_eq!(names(Analyzer::new()), vec!["CRR", "ROR", "AOR"]);
+        assert_eq!(
+            names(Analyzer::new().operators(vec!["ror".to_string()])),
+            vec!["ROR"]
+        );
+        assert_eq!(
+            names(Analyzer::new().mode(MutationMode::Fast)),
+            vec!["ROR", "AOR"]
+        );
+        let thorough = names(Analyzer::new().mode(MutationMode::Thorough));
+        for name in ["COR", "RustTry", "RustLetChain", "TOR", "TAR"] {
+            assert!(thorough.contains(&name), "{name} missing from {thorough:?}");
+        }
+    }
+
     #[test]
     fn test_effective_jobs() {
         let analyzer = Analyzer::new().jobs(4);
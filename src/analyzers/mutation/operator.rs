@@ -55,6 +55,11 @@ impl OperatorRegistry {
         &self.operators
     }
 
+    /// Take the registered operators, e.g. to build a generator.
+    pub fn into_operators(self) -> Vec<Box<dyn MutationOperator>> {
+        self.operators
+    }
+
     /// Get operators filtered by name.
     pub fn get_by_names(&self, names: &[&str]) -> Vec<&dyn MutationOperator> {
         self.operators
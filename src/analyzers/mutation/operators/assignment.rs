@@ -108,6 +108,9 @@ fn get_assignment_expression_types(lang: Language) -> &'static [&'static str] {
         Language::Ruby => &["operator_assignment"],
         Language::Php => &["augmented_assignment_expression"],
         Language::Bash => &["assignment"],
+        // Lua has no compound assignment
+        Language::Lua => &[],
+        Language::Zig => &["assignment_expression"],
     }
 }
 
@@ -79,6 +79,8 @@ fn is_subscript_expression(kind: &str, lang: Language) -> bool {
         Language::Ruby => kind == "element_reference",
         Language::Php => kind == "subscript_expression",
         Language::Bash => kind == "subscript",
+        Language::Lua => kind == "bracket_index_expression",
+        Language::Zig => kind == "index_expression",
     }
 }
 
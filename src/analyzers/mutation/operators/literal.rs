@@ -105,6 +105,8 @@ fn get_literal_node_types(lang: Language) -> &'static [&'static str] {
         Language::Ruby => &["integer", "float"],
         Language::Php => &["integer", "float"],
         Language::Bash => &[], // Bash doesn't have typed literals
+        Language::Lua => &["number"],
+        Language::Zig => &["integer", "float"],
     }
 }
 
@@ -122,6 +124,7 @@ fn get_boolean_node_types(lang: Language) -> &'static [&'static str] {
         Language::Ruby => &["true", "false"],
         Language::Php => &["boolean"],
         Language::Bash => &[],
+        Language::Lua | Language::Zig => &["true", "false"],
     }
 }
 
@@ -43,9 +43,11 @@ pub use python::{PythonComprehensionOperator, PythonIdentityOperator};
 pub use relational::RelationalOperator;
 pub use return_value::ReturnValueOperator;
 pub use ruby::{RubyNilOperator, RubySymbolOperator};
-pub use rust::{BorrowOperator, OptionOperator, ResultOperator};
+pub use rust::{BorrowOperator, LetChainOperator, OptionOperator, ResultOperator, TryOperator};
 pub use statement::StatementOperator;
-pub use typescript::{TypeScriptEqualityOperator, TypeScriptOptionalOperator};
+pub use typescript::{
+    TypeScriptAsyncOperator, TypeScriptEqualityOperator, TypeScriptOptionalOperator,
+};
 pub use unary::UnaryOperator;
 
 use super::operator::OperatorRegistry;
@@ -252,6 +254,7 @@ pub fn register_go_operators(registry: &mut OperatorRegistry) {
 pub fn register_typescript_operators(registry: &mut OperatorRegistry) {
     registry.register(Box::new(TypeScriptEqualityOperator));
     registry.register(Box::new(TypeScriptOptionalOperator));
+    registry.register(Box::new(TypeScriptAsyncOperator));
 }
 
 /// Register Python-specific operators.
@@ -426,6 +429,26 @@ mod tests {
         assert!(full.operators().len() >= default.operators().len());
     }
 
+    #[test]
+    fn test_full_registry_has_language_operators() {
+        let registry = full_registry();
+        let rust: Vec<&str> = registry
+            .for_language(Language::Rust)
+            .iter()
+            .map(|op| op.name())
+            .collect();
+        assert!(rust.contains(&"RustTry"));
+        assert!(rust.contains(&"RustLetChain"));
+        let ts: Vec<&str> = registry
+            .for_language(Language::TypeScript)
+            .iter()
+            .map(|op| op.name())
+            .collect();
+        assert!(ts.contains(&"TOR"));
+        assert!(ts.contains(&"TAR"));
+        assert!(!ts.contains(&"RustTry"));
+    }
+
     #[test]
     fn test_fast_registry_is_subset() {
         let fast = fast_registry();
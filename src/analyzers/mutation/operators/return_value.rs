@@ -85,6 +85,8 @@ fn get_return_node_types(lang: Language) -> &'static [&'static str] {
         Language::Ruby => &["return"],
         Language::Php => &["return_statement"],
         Language::Bash => &["return_statement"],
+        Language::Lua => &["return_statement"],
+        Language::Zig => &["return_expression"],
     }
 }
 
@@ -375,6 +377,14 @@ fn generate_default_replacements(original: &str, lang: Language) -> Vec<String>
             replacements.push("return 0".to_string());
             replacements.push("return 1".to_string());
         }
+        Language::Lua => {
+            replacements.push("return nil".to_string());
+            replacements.push("return 0".to_string());
+        }
+        Language::Zig => {
+            replacements.push("return 0".to_string());
+            replacements.push("return null".to_string());
+        }
     }
 
     replacements
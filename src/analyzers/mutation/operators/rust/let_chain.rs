@@ -0,0 +1,181 @@
+//! Rust `if let` / `while let` condition mutation operator.
+//!
+//! This operator mutates the boolean logic of conditions that bind a
+//! pattern:
+//! - `&&` -> `||` and `||` -> `&&` inside the condition
+//! - a guard in a let chain (`let Some(x) = a && guard`) -> `true`
+//!
+//! The `&&` joining a let chain can't become `||` (`let` is not allowed
+//! there), so dropping the guard is the weakening that still compiles.
+
+use crate::core::Language;
+use crate::parser::ParseResult;
+
+use super::super::super::operator::MutationOperator;
+use super::super::super::Mutant;
+use super::super::{mutant_from_node, walk_and_collect_mutants};
+
+/// Rust let-condition mutation operator.
+///
+/// Swaps logical operators and drops guards in `if let` and `while let`
+/// conditions.
+pub struct LetChainOperator;
+
+impl MutationOperator for LetChainOperator {
+    fn name(&self) -> &'static str {
+        "RustLetChain"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rust Let Chain Mutation - swaps && and || and drops guards in if let/while let conditions"
+    }
+
+    fn generate_mutants(&self, result: &ParseResult, mutant_id_prefix: &str) -> Vec<Mutant> {
+        let mut counter = 0;
+        walk_and_collect_mutants(result, |node| match node.kind() {
+            "if_expression" | "while_expression" => {
+                let mut mutants = Vec::new();
+                if let Some(condition) = node
+                    .child_by_field_name("condition")
+                    .filter(|c| matches!(c.kind(), "let_condition" | "let_chain"))
+                {
+                    self.collect(
+                        &condition,
+                        result,
+                        mutant_id_prefix,
+                        &mut counter,
+                        &mut mutants,
+                    );
+                }
+                mutants
+            }
+            _ => Vec::new(),
+        })
+    }
+
+    fn supports_language(&self, lang: Language) -> bool {
+        matches!(lang, Language::Rust)
+    }
+}
+
+impl LetChainOperator {
+    /// Collect mutants from a condition subtree, stopping at closures and
+    /// blocks, whose logic is not part of the condition.
+    fn collect(
+        &self,
+        node: &tree_sitter::Node<'_>,
+        result: &ParseResult,
+        prefix: &str,
+        counter: &mut usize,
+        mutants: &mut Vec<Mutant>,
+    ) {
+        match node.kind() {
+            "closure_expression" | "block" => return,
+            "binary_expression" => {
+                if let Some(mutant) = self.swap_operator(node, result, prefix, counter) {
+                    mutants.push(mutant);
+                }
+            }
+            "let_chain" => {
+                for guard in node
+                    .named_children(&mut node.walk())
+                    .filter(|c| !matches!(c.kind(), "let_condition" | "let_chain"))
+                {
+                    let Ok(original) = guard.utf8_text(&result.source) else {
+                        continue;
+                    };
+                    *counter += 1;
+                    let id = format!("{}-{}", prefix, counter);
+                    mutants.push(mutant_from_node(
+                        id,
+                        result.path.clone(),
+                        self.name(),
+                        &guard,
+                        original,
+                        "true",
+                        format!("Drop guard `{}` from let chain", original),
+                    ));
+                }
+            }
+            _ => {}
+        }
+        for child in node.named_children(&mut node.walk()) {
+            self.collect(&child, result, prefix, counter, mutants);
+        }
+    }
+
+    /// Swap `&&` and `||` in a binary expression.
+    fn swap_operator(
+        &self,
+        node: &tree_sitter::Node<'_>,
+        result: &ParseResult,
+        prefix: &str,
+        counter: &mut usize,
+    ) -> Option<Mutant> {
+        let operator = node.child_by_field_name("operator")?;
+        let replacement = match operator.kind() {
+            "&&" => "||",
+            "||" => "&&",
+            _ => return None,
+        };
+
+        *counter += 1;
+        let id = format!("{}-{}", prefix, counter);
+        Some(mutant_from_node(
+            id,
+            result.path.clone(),
+            self.name(),
+            &operator,
+            operator.kind(),
+            replacement,
+            format!(
+                "Replace {} with {} in let condition",
+                operator.kind(),
+                replacement
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::super::super::test_utils::parse_rust;
+
+    fn mutations(code: &[u8]) -> Vec<String> {
+        let result = parse_rust(code);
+        LetChainOperator
+            .generate_mutants(&result, "test")
+            .into_iter()
+            .map(|m| format!("{} -> {}", m.original, m.replacement))
+            .collect()
+    }
+
+    #[test]
+    fn test_swaps_operators_in_let_value() {
+        let code = b"fn f() { if let Some(x) = (a && b).then_some(1) { g(x); } }";
+        assert_eq!(mutations(code), vec!["&& -> ||"]);
+    }
+
+    #[test]
+    fn test_drops_let_chain_guards() {
+        let code = b"fn f() { if let Some(x) = a && (ready || forced) { g(x); } }";
+        assert_eq!(
+            mutations(code),
+            vec!["(ready || forced) -> true", "|| -> &&"]
+        );
+    }
+
+    #[test]
+    fn test_while_let_chain() {
+        let code = b"fn f() { while let Some(x) = it.next() && x > 0 { g(x); } }";
+        assert_eq!(mutations(code), vec!["x > 0 -> true"]);
+    }
+
+    #[test]
+    fn test_ignores_plain_conditions_and_bodies() {
+        let code = b"fn f() { if a && b { c || d; } if let Some(x) = y { p && q; } }";
+        assert!(mutations(code).is_empty());
+    }
+}
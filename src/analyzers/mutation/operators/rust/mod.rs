@@ -1,23 +1,75 @@
 //! Rust-specific mutation operators.
 //!
 //! This module provides mutation operators that target Rust-specific language
-//! features like Option, Result, and borrowing.
+//! features like Option, Result, borrowing, `?` and let chains.
 
 mod borrow;
+mod let_chain;
 mod option;
 mod result;
+mod try_expr;
 
 pub use borrow::BorrowOperator;
+pub use let_chain::LetChainOperator;
 pub use option::OptionOperator;
 pub use result::ResultOperator;
+pub use try_expr::TryOperator;
+
+use crate::parser::ParseResult;
 
 use super::super::operator::OperatorRegistry;
+use super::super::Mutant;
+use super::mutant_from_node;
 
 /// Register all Rust-specific operators with a registry.
 pub fn register_rust_operators(registry: &mut OperatorRegistry) {
     registry.register(Box::new(OptionOperator));
     registry.register(Box::new(ResultOperator));
     registry.register(Box::new(BorrowOperator));
+    registry.register(Box::new(TryOperator));
+    registry.register(Box::new(LetChainOperator));
+}
+
+/// Mutate a call to one of a pair of opposite predicate methods (such as
+/// `is_some`/`is_none`) into the other, keeping receiver and arguments.
+fn swap_predicate_method(
+    node: &tree_sitter::Node<'_>,
+    result: &ParseResult,
+    operator_name: &'static str,
+    swaps: &[(&str, &str)],
+    prefix: &str,
+    counter: &mut usize,
+) -> Option<Mutant> {
+    let function = node.child_by_field_name("function")?;
+    if function.kind() != "field_expression" {
+        return None;
+    }
+    let method = function
+        .child_by_field_name("field")?
+        .utf8_text(&result.source)
+        .ok()?;
+    let &(_, swapped) = swaps.iter().find(|(from, _)| *from == method)?;
+    let receiver = function
+        .child_by_field_name("value")?
+        .utf8_text(&result.source)
+        .ok()?;
+    let arguments = node
+        .child_by_field_name("arguments")?
+        .utf8_text(&result.source)
+        .ok()?;
+    let original = node.utf8_text(&result.source).ok()?;
+
+    *counter += 1;
+    let id = format!("{}-{}", prefix, counter);
+    Some(mutant_from_node(
+        id,
+        result.path.clone(),
+        operator_name,
+        node,
+        original,
+        format!("{}.{}{}", receiver, swapped, arguments),
+        format!("Replace .{}() with .{}()", method, swapped),
+    ))
 }
 
 #[cfg(test)]
@@ -30,7 +82,7 @@ mod tests {
         let mut registry = OperatorRegistry::new();
         register_rust_operators(&mut registry);
 
-        assert_eq!(registry.operators().len(), 3);
+        assert_eq!(registry.operators().len(), 5);
     }
 
     #[test]
@@ -39,7 +91,7 @@ mod tests {
         register_rust_operators(&mut registry);
 
         let rust_ops = registry.for_language(Language::Rust);
-        assert_eq!(rust_ops.len(), 3);
+        assert_eq!(rust_ops.len(), 5);
     }
 
     #[test]
@@ -66,6 +118,8 @@ mod tests {
         assert!(names.contains(&"RustOption"));
         assert!(names.contains(&"RustResult"));
         assert!(names.contains(&"RustBorrow"));
+        assert!(names.contains(&"RustTry"));
+        assert!(names.contains(&"RustLetChain"));
     }
 
     #[test]
@@ -5,6 +5,7 @@
 //! - `Some(x)` -> `None`
 //! - `.unwrap()` -> `.expect("...")`
 //! - `.unwrap_or(default)` -> `.unwrap()`
+//! - `.is_some()` <-> `.is_none()`, `.is_some_and(f)` <-> `.is_none_or(f)`
 
 use crate::core::Language;
 use crate::parser::ParseResult;
@@ -12,6 +13,15 @@ use crate::parser::ParseResult;
 use super::super::super::operator::MutationOperator;
 use super::super::super::Mutant;
 use super::super::{mutant_from_node, walk_and_collect_mutants};
+use super::swap_predicate_method;
+
+/// Opposite Option predicates, swapped into each other.
+const PREDICATE_SWAPS: &[(&str, &str)] = &[
+    ("is_some", "is_none"),
+    ("is_none", "is_some"),
+    ("is_some_and", "is_none_or"),
+    ("is_none_or", "is_some_and"),
+];
 
 /// Rust Option mutation operator.
 ///
@@ -117,6 +127,15 @@ impl OptionOperator {
             }
         }
 
+        mutants.extend(swap_predicate_method(
+            node,
+            result,
+            self.name(),
+            PREDICATE_SWAPS,
+            prefix,
+            counter,
+        ));
+
         if mutants.is_empty() {
             None
         } else {
@@ -330,6 +349,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_predicate_swaps() {
+        let code = b"fn f() { if cfg.port.is_some() && x.is_some_and(|v| v > 1) {} }";
+        let result = parse_rust(code);
+        let op = OptionOperator;
+
+        let replacements: Vec<_> = op
+            .generate_mutants(&result, "test")
+            .into_iter()
+            .map(|m| m.replacement)
+            .collect();
+        assert_eq!(
+            replacements,
+            vec!["cfg.port.is_none()", "x.is_none_or(|v| v > 1)"]
+        );
+    }
+
     #[test]
     fn test_no_mutations_for_non_option_code() {
         let code = b"fn main() { let x = 42; }";
@@ -5,6 +5,7 @@
 //! - `Ok(x)` -> `Err(Default::default())`
 //! - `Err(e)` -> `Ok(Default::default())`
 //! - `.unwrap()` -> `.expect("...")`
+//! - `.is_ok()` <-> `.is_err()`
 
 use crate::core::Language;
 use crate::parser::ParseResult;
@@ -12,6 +13,10 @@ use crate::parser::ParseResult;
 use super::super::super::operator::MutationOperator;
 use super::super::super::Mutant;
 use super::super::{mutant_from_node, walk_and_collect_mutants};
+use super::swap_predicate_method;
+
+/// Opposite Result predicates, swapped into each other.
+const PREDICATE_SWAPS: &[(&str, &str)] = &[("is_ok", "is_err"), ("is_err", "is_ok")];
 
 /// Rust Result mutation operator.
 ///
@@ -137,6 +142,15 @@ impl ResultOperator {
             }
         }
 
+        mutants.extend(swap_predicate_method(
+            node,
+            result,
+            self.name(),
+            PREDICATE_SWAPS,
+            prefix,
+            counter,
+        ));
+
         if mutants.is_empty() {
             None
         } else {
@@ -398,6 +412,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_predicate_swaps() {
+        let code = b"fn f() { let ok = parse(s).is_ok(); let bad = r.is_err(); }";
+        let result = parse_rust(code);
+        let op = ResultOperator;
+
+        let swaps: Vec<_> = op
+            .generate_mutants(&result, "test")
+            .into_iter()
+            .map(|m| (m.original, m.replacement))
+            .collect();
+        assert_eq!(
+            swaps,
+            vec![
+                (
+                    "parse(s).is_ok()".to_string(),
+                    "parse(s).is_err()".to_string()
+                ),
+                ("r.is_err()".to_string(), "r.is_ok()".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_multiple_mutations_in_function() {
         let code = b"fn main() { let a = Ok(1); let b = Err(\"e\"); let c = res.unwrap(); }";
@@ -0,0 +1,114 @@
+//! Rust `?` mutation operator.
+//!
+//! This operator mutates error propagation:
+//! - `x?` -> `x.unwrap()`
+//!
+//! Dropping the `?` outright almost never type-checks, so the mutant keeps
+//! the value but panics instead of returning the error. Tests that exercise
+//! the error path kill it.
+
+use crate::core::Language;
+use crate::parser::ParseResult;
+
+use super::super::super::operator::MutationOperator;
+use super::super::super::Mutant;
+use super::super::{mutant_from_node, walk_and_collect_mutants};
+
+/// Rust `?` mutation operator.
+///
+/// Replaces error propagation with a panic to test error paths.
+pub struct TryOperator;
+
+impl MutationOperator for TryOperator {
+    fn name(&self) -> &'static str {
+        "RustTry"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rust Try Mutation - removes ? error propagation in favor of unwrap"
+    }
+
+    fn generate_mutants(&self, result: &ParseResult, mutant_id_prefix: &str) -> Vec<Mutant> {
+        let mut counter = 0;
+        walk_and_collect_mutants(result, |node| match node.kind() {
+            "try_expression" => self
+                .handle_try_expression(&node, result, mutant_id_prefix, &mut counter)
+                .map(|m| vec![m])
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        })
+    }
+
+    fn supports_language(&self, lang: Language) -> bool {
+        matches!(lang, Language::Rust)
+    }
+}
+
+impl TryOperator {
+    /// Replace `expr?` with `expr.unwrap()`.
+    fn handle_try_expression(
+        &self,
+        node: &tree_sitter::Node<'_>,
+        result: &ParseResult,
+        prefix: &str,
+        counter: &mut usize,
+    ) -> Option<Mutant> {
+        let original = node.utf8_text(&result.source).ok()?;
+        let inner = node.named_child(0)?.utf8_text(&result.source).ok()?;
+        let replacement = format!("{}.unwrap()", inner);
+
+        *counter += 1;
+        let id = format!("{}-{}", prefix, counter);
+        Some(mutant_from_node(
+            id,
+            result.path.clone(),
+            self.name(),
+            node,
+            original,
+            replacement.clone(),
+            format!("Replace {} with {}", original, replacement),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::super::super::test_utils::parse_rust;
+
+    #[test]
+    fn test_try_to_unwrap() {
+        let code = b"fn load() -> Result<u32, E> { let x = read(path)?; Ok(x) }";
+        let result = parse_rust(code);
+        let op = TryOperator;
+
+        let mutants = op.generate_mutants(&result, "test");
+
+        assert_eq!(mutants.len(), 1);
+        assert_eq!(mutants[0].original, "read(path)?");
+        assert_eq!(mutants[0].replacement, "read(path).unwrap()");
+        let (start, end) = mutants[0].byte_range;
+        assert_eq!(&code[start..end], b"read(path)?");
+    }
+
+    #[test]
+    fn test_chained_try_mutates_each() {
+        let code = b"fn f() -> Option<u32> { Some(a()?.b()?) }";
+        let result = parse_rust(code);
+        let op = TryOperator;
+
+        let replacements: Vec<_> = op
+            .generate_mutants(&result, "test")
+            .into_iter()
+            .map(|m| m.replacement)
+            .collect();
+        assert_eq!(replacements, vec!["a()?.b().unwrap()", "a().unwrap()"]);
+    }
+
+    #[test]
+    fn test_no_try_no_mutants() {
+        let result = parse_rust(b"fn f() -> u32 { 1 }");
+        assert!(TryOperator.generate_mutants(&result, "test").is_empty());
+    }
+}
@@ -130,6 +130,16 @@ fn get_statement_node_types(lang: Language) -> &'static [&'static str] {
         Language::Ruby => &["expression_statement", "assignment"],
         Language::Php => &["expression_statement", "assignment_expression"],
         Language::Bash => &["command", "variable_assignment"],
+        Language::Lua => &[
+            "function_call",
+            "assignment_statement",
+            "variable_declaration",
+        ],
+        Language::Zig => &[
+            "expression_statement",
+            "variable_declaration",
+            "assignment_expression",
+        ],
     }
 }
 
@@ -165,6 +175,13 @@ fn get_control_flow_node_types(lang: Language) -> &'static [&'static str] {
             "foreach_statement",
         ],
         Language::Bash => &["if_statement", "while_statement", "for_statement"],
+        Language::Lua => &[
+            "if_statement",
+            "while_statement",
+            "for_statement",
+            "repeat_statement",
+        ],
+        Language::Zig => &["if_statement", "while_statement", "for_statement"],
     }
 }
 
@@ -185,6 +202,7 @@ fn find_control_flow_body<'a>(
         Language::Ruby => &["then", "do"] as &[&str],
         Language::Php => &["compound_statement"] as &[&str],
         Language::Bash => &["compound_statement"] as &[&str],
+        Language::Lua | Language::Zig => &["block"] as &[&str],
     };
 
     node.children(&mut node.walk())
@@ -232,6 +250,7 @@ fn get_empty_replacement(lang: Language) -> String {
         Language::Go => "{}".to_string(),
         Language::Ruby => "nil".to_string(),
         Language::Bash => ":".to_string(),
+        Language::Zig => "{}".to_string(),
         _ => ";".to_string(),
     }
 }
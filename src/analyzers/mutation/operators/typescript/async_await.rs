@@ -0,0 +1,213 @@
+//! TypeScript-specific async/await mutation operator.
+//!
+//! This operator mutates TypeScript/JavaScript asynchronous code:
+//! - `await x` -> `x` (remove await)
+//! - `async function f()` -> `function f()` (remove async), when the
+//!   function awaits nothing itself
+//!
+//! Removing `async` from a function that still awaits would not parse, so
+//! only functions that merely return a promise are mutated.
+
+use crate::core::Language;
+use crate::parser::ParseResult;
+
+use crate::analyzers::mutation::operator::MutationOperator;
+use crate::analyzers::mutation::operators::walk_and_collect_mutants;
+use crate::analyzers::mutation::Mutant;
+
+/// Function node kinds that can be `async`.
+const FUNCTION_KINDS: &[&str] = &[
+    "function_declaration",
+    "function_expression",
+    "function",
+    "arrow_function",
+    "method_definition",
+    "generator_function_declaration",
+    "generator_function",
+];
+
+/// TAR (TypeScript Async Removal) operator.
+///
+/// Removes `await` from expressions and `async` from functions.
+pub struct TypeScriptAsyncOperator;
+
+impl MutationOperator for TypeScriptAsyncOperator {
+    fn name(&self) -> &'static str {
+        "TAR"
+    }
+
+    fn description(&self) -> &'static str {
+        "TypeScript Async Removal - removes await from expressions and async from functions"
+    }
+
+    fn generate_mutants(&self, result: &ParseResult, mutant_id_prefix: &str) -> Vec<Mutant> {
+        let mut counter = 0;
+        walk_and_collect_mutants(result, |node| {
+            let mutant = if node.kind() == "await_expression" {
+                self.try_remove_await(&node, result, mutant_id_prefix, &mut counter)
+            } else if FUNCTION_KINDS.contains(&node.kind()) {
+                self.try_remove_async(&node, result, mutant_id_prefix, &mut counter)
+            } else {
+                None
+            };
+            mutant.map(|m| vec![m]).unwrap_or_default()
+        })
+    }
+
+    fn supports_language(&self, lang: Language) -> bool {
+        matches!(
+            lang,
+            Language::TypeScript | Language::JavaScript | Language::Tsx | Language::Jsx
+        )
+    }
+}
+
+impl TypeScriptAsyncOperator {
+    /// Try to remove await: `await x` -> `x`
+    fn try_remove_await(
+        &self,
+        node: &tree_sitter::Node<'_>,
+        result: &ParseResult,
+        prefix: &str,
+        counter: &mut u32,
+    ) -> Option<Mutant> {
+        let node_text = node.utf8_text(&result.source).ok()?;
+        let inner_text = node.named_child(0)?.utf8_text(&result.source).ok()?;
+
+        *counter += 1;
+        let id = format!("{}-{}", prefix, counter);
+        let start = node.start_position();
+
+        Some(Mutant::new(
+            id,
+            result.path.clone(),
+            self.name(),
+            (start.row + 1) as u32,
+            (start.column + 1) as u32,
+            node_text,
+            inner_text,
+            "Remove await: await x -> x",
+            (node.start_byte(), node.end_byte()),
+        ))
+    }
+
+    /// Try to remove async from a function that does not await.
+    fn try_remove_async(
+        &self,
+        node: &tree_sitter::Node<'_>,
+        result: &ParseResult,
+        prefix: &str,
+        counter: &mut u32,
+    ) -> Option<Mutant> {
+        let async_token = node
+            .children(&mut node.walk())
+            .find(|child| child.kind() == "async")?;
+        let body = node.child_by_field_name("body")?;
+        if awaits(&body) {
+            return None;
+        }
+
+        // Remove the keyword and the whitespace after it.
+        let end = async_token.next_sibling()?.start_byte();
+        let original = std::str::from_utf8(&result.source[async_token.start_byte()..end]).ok()?;
+
+        *counter += 1;
+        let id = format!("{}-{}", prefix, counter);
+        let start = async_token.start_position();
+
+        Some(Mutant::new(
+            id,
+            result.path.clone(),
+            self.name(),
+            (start.row + 1) as u32,
+            (start.column + 1) as u32,
+            original,
+            "",
+            "Remove async: async function -> function",
+            (async_token.start_byte(), end),
+        ))
+    }
+}
+
+/// Whether `node` awaits, not counting nested functions.
+fn awaits(node: &tree_sitter::Node<'_>) -> bool {
+    if node.kind() == "await" {
+        return true;
+    }
+    if FUNCTION_KINDS.contains(&node.kind()) {
+        return false;
+    }
+    node.children(&mut node.walk()).any(|child| awaits(&child))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::super::super::test_utils::{parse_js, parse_ts};
+
+    fn mutations(result: &ParseResult) -> Vec<String> {
+        TypeScriptAsyncOperator
+            .generate_mutants(result, "test")
+            .into_iter()
+            .map(|m| format!("{:?} -> {:?}", m.original, m.replacement))
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_await() {
+        let code = b"async function load() { const user = await fetchUser(id); return user; }";
+        let result = parse_ts(code);
+
+        assert_eq!(
+            mutations(&result),
+            vec![r#""await fetchUser(id)" -> "fetchUser(id)""#]
+        );
+    }
+
+    #[test]
+    fn test_remove_async_without_await() {
+        let code = b"async function load(id) { return fetchUser(id); }";
+        let result = parse_js(code);
+
+        let mutants = TypeScriptAsyncOperator.generate_mutants(&result, "test");
+
+        assert_eq!(mutants.len(), 1);
+        let (start, end) = mutants[0].byte_range;
+        assert_eq!(&code[start..end], b"async ");
+        assert_eq!(mutants[0].replacement, "");
+    }
+
+    #[test]
+    fn test_remove_async_arrow_and_method() {
+        let code = b"class A { async get() { return 1; } }\nconst f = async () => g(async () => await h());";
+        let result = parse_ts(code);
+
+        assert_eq!(
+            mutations(&result),
+            vec![
+                r#""async " -> """#,
+                r#""async " -> """#,
+                r#""await h()" -> "h()""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_for_await_keeps_async() {
+        let code = b"async function drain(s) { for await (const x of s) { use(x); } }";
+        let result = parse_ts(code);
+
+        assert!(mutations(&result).is_empty());
+    }
+
+    #[test]
+    fn test_supports_typescript_variants_only() {
+        let op = TypeScriptAsyncOperator;
+        assert_eq!(op.name(), "TAR");
+        assert!(op.supports_language(Language::TypeScript));
+        assert!(op.supports_language(Language::Jsx));
+        assert!(!op.supports_language(Language::Rust));
+        assert!(!op.supports_language(Language::Python));
+    }
+}
@@ -3,10 +3,13 @@
 //! These operators target TypeScript/JavaScript language idioms:
 //! - TER (TypeScript Equality Replacement): Strict vs loose equality mutations
 //! - TOR (TypeScript Optional Replacement): Optional chaining and nullish coalescing mutations
+//! - TAR (TypeScript Async Removal): await and async removal
 
+mod async_await;
 mod equality;
 mod optional;
 
+pub use async_await::TypeScriptAsyncOperator;
 pub use equality::TypeScriptEqualityOperator;
 pub use optional::TypeScriptOptionalOperator;
 
@@ -68,8 +71,11 @@ mod tests {
         let equality_op = TypeScriptEqualityOperator;
         let optional_op = TypeScriptOptionalOperator;
 
+        let async_op = TypeScriptAsyncOperator;
+
         assert_ne!(equality_op.name(), optional_op.name());
         assert_eq!(equality_op.name(), "TER");
         assert_eq!(optional_op.name(), "TOR");
+        assert_eq!(async_op.name(), "TAR");
     }
 }
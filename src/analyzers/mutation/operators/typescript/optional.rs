@@ -3,6 +3,7 @@
 //! This operator mutates TypeScript/JavaScript optional operators:
 //! - `?.` -> `.` (remove optional chaining)
 //! - `??` -> `||` (nullish coalescing to logical or)
+//! - `||` -> `??` (logical or to nullish coalescing)
 //! - `x!` -> `x` (remove non-null assertion)
 
 use crate::core::Language;
@@ -97,7 +98,7 @@ impl TypeScriptOptionalOperator {
         ))
     }
 
-    /// Try to mutate nullish coalescing: `??` -> `||`
+    /// Try to swap nullish coalescing and logical or: `??` <-> `||`
     fn try_mutate_nullish_coalescing(
         &self,
         node: &tree_sitter::Node<'_>,
@@ -105,29 +106,39 @@ impl TypeScriptOptionalOperator {
         prefix: &str,
         counter: &mut u32,
     ) -> Option<Mutant> {
-        // Look for ?? operator child
+        // Look for ?? or || operator child
         for child in node.children(&mut node.walk()) {
-            if child.kind() == "??" {
-                if let Ok(op_text) = child.utf8_text(&result.source) {
-                    if op_text == "??" {
-                        *counter += 1;
-                        let id = format!("{}-{}", prefix, counter);
-                        let start = child.start_position();
-
-                        return Some(Mutant::new(
-                            id,
-                            result.path.clone(),
-                            self.name(),
-                            (start.row + 1) as u32,
-                            (start.column + 1) as u32,
-                            "??",
-                            "||",
-                            "Replace nullish coalescing with logical or: ?? -> ||",
-                            (child.start_byte(), child.end_byte()),
-                        ));
-                    }
-                }
+            let (original, replacement, description) = match child.kind() {
+                "??" => (
+                    "??",
+                    "||",
+                    "Replace nullish coalescing with logical or: ?? -> ||",
+                ),
+                "||" => (
+                    "||",
+                    "??",
+                    "Replace logical or with nullish coalescing: || -> ??",
+                ),
+                _ => continue,
+            };
+            if child.utf8_text(&result.source).ok()? != original {
+                continue;
             }
+            *counter += 1;
+            let id = format!("{}-{}", prefix, counter);
+            let start = child.start_position();
+
+            return Some(Mutant::new(
+                id,
+                result.path.clone(),
+                self.name(),
+                (start.row + 1) as u32,
+                (start.column + 1) as u32,
+                original,
+                replacement,
+                description,
+                (child.start_byte(), child.end_byte()),
+            ));
         }
 
         None
@@ -321,6 +332,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_logical_or_to_nullish_coalescing() {
+        let code = b"const port = config.port || 8080;";
+        let result = parse_ts(code);
+        let op = TypeScriptOptionalOperator;
+
+        let mutants = op.generate_mutants(&result, "test");
+
+        assert_eq!(mutants.len(), 1);
+        assert_eq!(mutants[0].original, "||");
+        assert_eq!(mutants[0].replacement, "??");
+        let (start, end) = mutants[0].byte_range;
+        assert_eq!(&code[start..end], b"||");
+    }
+
     #[test]
     fn test_nullish_coalescing_vs_logical_or() {
         let code = b"const x = a || b;";
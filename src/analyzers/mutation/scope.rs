@@ -0,0 +1,419 @@
+//! Per-package test scoping for mono-repos.
+//!
+//! In a workspace, a mutant in one package can only be caught by that
+//! package's tests (and by the tests of packages that depend on it), so
+//! running the whole repo's suite per mutant wastes most of the time.
+//! [`Workspace`] discovers Cargo members, npm workspaces and Go modules
+//! under the project root and narrows the test command to the package that
+//! owns each mutated file.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use figment::providers::{Format, Toml};
+use figment::Figment;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+
+/// Build system a package belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    /// Cargo crate (`Cargo.toml` with a `[package]` table).
+    Cargo,
+    /// npm package (`package.json` with a `name`).
+    Npm,
+    /// Go module (`go.mod`).
+    Go,
+}
+
+impl PackageKind {
+    fn manifest(self) -> &'static str {
+        match self {
+            PackageKind::Cargo => "Cargo.toml",
+            PackageKind::Npm => "package.json",
+            PackageKind::Go => "go.mod",
+        }
+    }
+}
+
+/// A package discovered in the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    /// Build system.
+    pub kind: PackageKind,
+    /// Package name (crate name, npm name, or Go module path).
+    pub name: String,
+    /// Directory containing the manifest, relative to the workspace root.
+    pub dir: PathBuf,
+    /// Names of packages this one depends on.
+    pub dependencies: Vec<String>,
+}
+
+impl Package {
+    /// Path of the package manifest relative to the workspace root.
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join(self.kind.manifest())
+    }
+}
+
+/// Packages found under a project root.
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    root: PathBuf,
+    packages: Vec<Package>,
+}
+
+impl Workspace {
+    /// Discover packages under `root`, honoring `.gitignore`.
+    pub fn discover(root: &Path) -> Self {
+        let mut packages = Vec::new();
+        for entry in WalkBuilder::new(root).build().flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+            let path = entry.path();
+            let Some(dir) = path.parent().and_then(|d| d.strip_prefix(root).ok()) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let package = match path.file_name().and_then(|n| n.to_str()) {
+                Some("Cargo.toml") => parse_cargo(&content),
+                Some("package.json") => parse_npm(&content),
+                Some("go.mod") => parse_go(&content),
+                _ => None,
+            };
+            if let Some((kind, name, dependencies)) = package {
+                packages.push(Package {
+                    kind,
+                    name,
+                    dir: dir.to_path_buf(),
+                    dependencies,
+                });
+            }
+        }
+        packages.sort_by(|a, b| a.dir.cmp(&b.dir).then(a.name.cmp(&b.name)));
+        Self {
+            root: root.to_path_buf(),
+            packages,
+        }
+    }
+
+    /// All discovered packages, ordered by directory.
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    /// The innermost package whose directory contains `file`.
+    pub fn owner(&self, file: &Path) -> Option<&Package> {
+        let rel = file.strip_prefix(&self.root).unwrap_or(file);
+        self.packages
+            .iter()
+            .filter(|p| rel.starts_with(&p.dir))
+            .max_by_key(|p| p.dir.components().count())
+    }
+
+    /// Packages of the same kind that depend on `package`, directly or
+    /// transitively, ordered by directory.
+    pub fn dependents(&self, package: &Package) -> Vec<&Package> {
+        let mut seen: HashSet<&str> = HashSet::from([package.name.as_str()]);
+        let mut frontier = vec![package.name.as_str()];
+        let mut found = Vec::new();
+        while let Some(name) = frontier.pop() {
+            for candidate in self.packages.iter().filter(|p| p.kind == package.kind) {
+                if candidate.dependencies.iter().any(|d| d == name)
+                    && seen.insert(candidate.name.as_str())
+                {
+                    frontier.push(candidate.name.as_str());
+                    found.push(candidate);
+                }
+            }
+        }
+        found.sort_by(|a, b| a.dir.cmp(&b.dir));
+        found
+    }
+
+    /// Test command scoped to the package owning `file`.
+    ///
+    /// Returns `None` when the file isn't owned by a package or when the
+    /// workspace has only one package of that kind, in which case the
+    /// repo-wide command already is the package command.
+    pub fn test_command(&self, file: &Path, include_dependents: bool) -> Option<String> {
+        let owner = self.owner(file)?;
+        if self
+            .packages
+            .iter()
+            .filter(|p| p.kind == owner.kind)
+            .count()
+            < 2
+        {
+            return None;
+        }
+        let mut targets = vec![owner];
+        if include_dependents {
+            targets.extend(self.dependents(owner));
+        }
+        Some(scoped_command(owner.kind, &targets))
+    }
+}
+
+fn scoped_command(kind: PackageKind, targets: &[&Package]) -> String {
+    match kind {
+        PackageKind::Cargo => {
+            let args: Vec<String> = targets.iter().map(|p| format!("-p {}", p.name)).collect();
+            format!("cargo test {}", args.join(" "))
+        }
+        PackageKind::Npm => {
+            let args: Vec<String> = targets
+                .iter()
+                .map(|p| format!("--workspace={}", p.name))
+                .collect();
+            format!("npm test {}", args.join(" "))
+        }
+        // Each module is its own build root, so run them one after another.
+        PackageKind::Go => targets
+            .iter()
+            .map(|p| {
+                if p.dir.as_os_str().is_empty() {
+                    "go test ./...".to_string()
+                } else {
+                    format!("go test -C {} ./...", p.dir.display())
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" && "),
+    }
+}
+
+type Parsed = (PackageKind, String, Vec<String>);
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    package: Option<CargoPackage>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, CargoDependency>,
+    #[serde(default, rename = "dev-dependencies")]
+    dev_dependencies: BTreeMap<String, CargoDependency>,
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: BTreeMap<String, CargoDependency>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CargoDependency {
+    Version(#[allow(dead_code)] String),
+    Detailed { package: Option<String> },
+}
+
+fn parse_cargo(content: &str) -> Option<Parsed> {
+    let manifest: CargoManifest = Figment::from(Toml::string(content)).extract().ok()?;
+    let name = manifest.package?.name;
+    let dependencies = [
+        manifest.dependencies,
+        manifest.dev_dependencies,
+        manifest.build_dependencies,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|(key, dep)| match dep {
+        // `foo = { package = "bar" }` depends on the crate named bar
+        CargoDependency::Detailed {
+            package: Some(real),
+        } => real,
+        _ => key,
+    })
+    .collect();
+    Some((PackageKind::Cargo, name, dependencies))
+}
+
+#[derive(Deserialize)]
+struct NpmManifest {
+    name: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default, rename = "devDependencies")]
+    dev_dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default, rename = "peerDependencies")]
+    peer_dependencies: BTreeMap<String, serde_json::Value>,
+}
+
+fn parse_npm(content: &str) -> Option<Parsed> {
+    let manifest: NpmManifest = serde_json::from_str(content).ok()?;
+    let name = manifest.name?;
+    let dependencies = [
+        manifest.dependencies,
+        manifest.dev_dependencies,
+        manifest.peer_dependencies,
+    ]
+    .into_iter()
+    .flat_map(|deps| deps.into_keys())
+    .collect();
+    Some((PackageKind::Npm, name, dependencies))
+}
+
+fn parse_go(content: &str) -> Option<Parsed> {
+    let mut name = None;
+    let mut dependencies = Vec::new();
+    let mut in_require = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if let Some(module) = line.strip_prefix("module ") {
+            name = Some(module.trim().trim_matches('"').to_string());
+        } else if line == "require (" {
+            in_require = true;
+        } else if in_require && line == ")" {
+            in_require = false;
+        } else if let Some(dep) = line.strip_prefix("require ") {
+            dependencies.extend(dep.split_whitespace().next().map(str::to_string));
+        } else if in_require {
+            dependencies.extend(line.split_whitespace().next().map(str::to_string));
+        }
+    }
+    Some((PackageKind::Go, name?, dependencies))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn cargo_workspace() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\nversion = \"0.1.0\"\n",
+        );
+        write(root, "crates/core/src/lib.rs", "pub fn f() {}\n");
+        write(
+            root,
+            "crates/api/Cargo.toml",
+            "[package]\nname = \"api\"\n\n[dependencies]\ncore = { path = \"../core\" }\nserde = \"1\"\n",
+        );
+        write(
+            root,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"cli\"\n\n[dev-dependencies]\nmy-api = { package = \"api\", path = \"../api\" }\n",
+        );
+        write(
+            root,
+            "crates/other/Cargo.toml",
+            "[package]\nname = \"other\"\n",
+        );
+        temp
+    }
+
+    #[test]
+    fn test_discover_skips_virtual_manifests() {
+        let temp = cargo_workspace();
+        let ws = Workspace::discover(temp.path());
+        let names: Vec<_> = ws.packages().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "cli", "core", "other"]);
+        assert_eq!(
+            ws.packages()[0].manifest_path(),
+            PathBuf::from("crates/api/Cargo.toml")
+        );
+    }
+
+    #[test]
+    fn test_owner_is_innermost_package() {
+        let temp = cargo_workspace();
+        let ws = Workspace::discover(temp.path());
+        let file = temp.path().join("crates/core/src/lib.rs");
+        assert_eq!(ws.owner(&file).unwrap().name, "core");
+        assert!(ws.owner(&temp.path().join("README.md")).is_none());
+    }
+
+    #[test]
+    fn test_cargo_command_with_transitive_dependents() {
+        let temp = cargo_workspace();
+        let ws = Workspace::discover(temp.path());
+        let file = temp.path().join("crates/core/src/lib.rs");
+
+        assert_eq!(
+            ws.test_command(&file, false).as_deref(),
+            Some("cargo test -p core")
+        );
+        // api depends on core, cli depends on api through a renamed dependency
+        assert_eq!(
+            ws.test_command(&file, true).as_deref(),
+            Some("cargo test -p core -p api -p cli")
+        );
+    }
+
+    #[test]
+    fn test_npm_workspace_command() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "package.json",
+            r#"{"private": true, "workspaces": ["packages/*"]}"#,
+        );
+        write(root, "packages/ui/package.json", r#"{"name": "@acme/ui"}"#);
+        write(
+            root,
+            "packages/web/package.json",
+            r#"{"name": "@acme/web", "dependencies": {"@acme/ui": "*"}}"#,
+        );
+
+        let ws = Workspace::discover(root);
+        let file = root.join("packages/ui/src/button.ts");
+        assert_eq!(
+            ws.test_command(&file, true).as_deref(),
+            Some("npm test --workspace=@acme/ui --workspace=@acme/web")
+        );
+    }
+
+    #[test]
+    fn test_go_module_command() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "lib/go.mod", "module example.com/lib\n\ngo 1.22\n");
+        write(
+            root,
+            "svc/go.mod",
+            "module example.com/svc\n\nrequire (\n\texample.com/lib v0.0.0 // local\n)\n",
+        );
+
+        let ws = Workspace::discover(root);
+        let file = root.join("lib/util.go");
+        assert_eq!(
+            ws.test_command(&file, false).as_deref(),
+            Some("go test -C lib ./...")
+        );
+        assert_eq!(
+            ws.test_command(&file, true).as_deref(),
+            Some("go test -C lib ./... && go test -C svc ./...")
+        );
+    }
+
+    #[test]
+    fn test_single_package_is_not_scoped() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "Cargo.toml", "[package]\nname = \"solo\"\n");
+
+        let ws = Workspace::discover(root);
+        assert!(ws.test_command(&root.join("src/lib.rs"), true).is_none());
+    }
+}
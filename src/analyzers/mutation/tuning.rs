@@ -0,0 +1,232 @@
+//! Operator effectiveness and per-project operator tuning.
+//!
+//! A run shows what each operator's mutants cost and what they found: how
+//! often the tests killed them, how long the suite took on them, and which
+//! lines only that operator's mutants were killed on. An operator whose
+//! mutants never survive, on lines other operators already cover, adds test
+//! runs without adding information; [`suggest_operators`] leaves it out.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::{FileResult, MutantStatus, OperatorStats};
+
+/// A line a mutant was killed on: file path and line number.
+type Location<'a> = (&'a str, u32);
+
+/// What one operator's executed mutants showed.
+#[derive(Default)]
+struct Evidence<'a> {
+    executed: usize,
+    killed: usize,
+    survived: usize,
+    duration_ms: u64,
+    /// Where each killed mutant was, one entry per mutant.
+    kills: Vec<Location<'a>>,
+}
+
+impl Evidence<'_> {
+    fn mean_duration_ms(&self) -> u64 {
+        match self.executed {
+            0 => 0,
+            n => self.duration_ms / n as u64,
+        }
+    }
+}
+
+/// Evidence by operator name. Mutants that were not run (pending, skipped
+/// by the predictor, or equivalent) count for nothing.
+fn evidence(files: &[FileResult]) -> BTreeMap<&str, Evidence<'_>> {
+    let mut by_operator: BTreeMap<&str, Evidence<'_>> = BTreeMap::new();
+    for file in files {
+        for result in &file.mutants {
+            let entry = by_operator
+                .entry(result.mutant.operator.as_str())
+                .or_default();
+            match result.status {
+                MutantStatus::Pending | MutantStatus::Skipped | MutantStatus::Equivalent => {
+                    continue
+                }
+                MutantStatus::Killed => {
+                    entry.killed += 1;
+                    entry.kills.push((file.path.as_str(), result.mutant.line));
+                }
+                MutantStatus::Survived => entry.survived += 1,
+                MutantStatus::Timeout | MutantStatus::BuildError => {}
+            }
+            entry.executed += 1;
+            entry.duration_ms += result.duration_ms;
+        }
+    }
+    by_operator
+}
+
+/// Fill in each operator's kill rate, mean runtime and unique kills: its
+/// killed mutants on lines where no other operator's mutant was killed.
+pub(super) fn annotate(files: &[FileResult], by_operator: &mut HashMap<String, OperatorStats>) {
+    let evidence = evidence(files);
+    let mut killers: HashMap<Location<'_>, HashSet<&str>> = HashMap::new();
+    for (name, e) in &evidence {
+        for &location in &e.kills {
+            killers.entry(location).or_default().insert(*name);
+        }
+    }
+    for (name, stats) in by_operator.iter_mut() {
+        let Some(e) = evidence.get(name.as_str()) else {
+            continue;
+        };
+        let scored = e.killed + e.survived;
+        if scored > 0 {
+            stats.kill_rate = e.killed as f64 / scored as f64;
+        }
+        stats.mean_duration_ms = e.mean_duration_ms();
+        stats.unique_kills = e
+            .kills
+            .iter()
+            .filter(|location| killers[*location].len() == 1)
+            .count();
+    }
+}
+
+/// The smallest, cheapest set of `operators` that still finds what this
+/// run found, in `operators`' order. Empty when no mutant was run.
+///
+/// Operators that let a mutant survive expose untested behavior, and those
+/// with no mutant run have shown nothing either way: both stay. The rest are
+/// added greedily, most newly covered kill lines per millisecond of test run
+/// first, until every line some operator's mutant was killed on is covered.
+pub fn suggest_operators(files: &[FileResult], operators: &[String]) -> Vec<String> {
+    let evidence = evidence(files);
+    if evidence.values().all(|e| e.executed == 0) {
+        return Vec::new();
+    }
+
+    let mut keep: HashSet<&str> = operators
+        .iter()
+        .map(String::as_str)
+        .filter(|name| {
+            evidence
+                .get(name)
+                .is_none_or(|e| e.executed == 0 || e.survived > 0)
+        })
+        .collect();
+    let mut covered: HashSet<Location<'_>> = keep
+        .iter()
+        .filter_map(|name| evidence.get(name))
+        .flat_map(|e| e.kills.iter().copied())
+        .collect();
+
+    loop {
+        let best = evidence
+            .iter()
+            .filter(|(name, _)| !keep.contains(*name))
+            .map(|(name, e)| {
+                let new: HashSet<_> = e.kills.iter().filter(|l| !covered.contains(*l)).collect();
+                let value = new.len() as f64 / e.mean_duration_ms().max(1) as f64;
+                (*name, new.len(), value)
+            })
+            .filter(|&(_, new, _)| new > 0)
+            .max_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((name, _, _)) = best else {
+            break;
+        };
+        keep.insert(name);
+        covered.extend(evidence[name].kills.iter().copied());
+    }
+
+    operators
+        .iter()
+        .filter(|name| keep.contains(name.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::mutation::{build_summary, Mutant, MutationResult};
+
+    fn result(
+        id: &str,
+        operator: &str,
+        line: u32,
+        status: MutantStatus,
+        ms: u64,
+    ) -> MutationResult {
+        MutationResult::new(
+            Mutant::new(id, "a.rs", operator, line, 1, "a", "b", "desc", (0, 1)),
+            status,
+            ms,
+        )
+    }
+
+    fn file(mutants: Vec<MutationResult>) -> Vec<FileResult> {
+        vec![FileResult {
+            path: "a.rs".to_string(),
+            mutants,
+            killed: 0,
+            survived: 0,
+            timeout: 0,
+            error: 0,
+            skipped: 0,
+            score: 0.0,
+        }]
+    }
+
+    fn names(operators: &[&str]) -> Vec<String> {
+        operators.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_annotate_rates_costs_and_unique_kills() {
+        let files = file(vec![
+            result("1", "CRR", 1, MutantStatus::Killed, 100),
+            result("2", "CRR", 2, MutantStatus::Survived, 300),
+            result("3", "ROR", 1, MutantStatus::Killed, 50),
+            result("4", "ROR", 3, MutantStatus::Killed, 50),
+            result("5", "AOR", 4, MutantStatus::Skipped, 0),
+        ]);
+        let summary = build_summary(&files, 0);
+        let crr = &summary.by_operator["CRR"];
+        assert!((crr.kill_rate - 0.5).abs() < f64::EPSILON);
+        assert_eq!(crr.mean_duration_ms, 200);
+        assert_eq!(crr.unique_kills, 0);
+        let ror = &summary.by_operator["ROR"];
+        assert!((ror.kill_rate - 1.0).abs() < f64::EPSILON);
+        assert_eq!(ror.mean_duration_ms, 50);
+        assert_eq!(ror.unique_kills, 1);
+        assert_eq!(summary.by_operator["AOR"].mean_duration_ms, 0);
+    }
+
+    #[test]
+    fn test_suggest_drops_redundant_operators() {
+        // AOR's kills are all on lines ROR also kills on, and none survived.
+        let files = file(vec![
+            result("1", "CRR", 1, MutantStatus::Survived, 100),
+            result("2", "ROR", 2, MutantStatus::Killed, 100),
+            result("3", "ROR", 3, MutantStatus::Killed, 100),
+            result("4", "AOR", 2, MutantStatus::Killed, 100),
+        ]);
+        assert_eq!(
+            suggest_operators(&files, &names(&["CRR", "ROR", "AOR", "COR"])),
+            names(&["CRR", "ROR", "COR"])
+        );
+    }
+
+    #[test]
+    fn test_suggest_prefers_cheaper_cover() {
+        let files = file(vec![
+            result("1", "ROR", 1, MutantStatus::Killed, 900),
+            result("2", "AOR", 1, MutantStatus::Killed, 100),
+        ]);
+        assert_eq!(
+            suggest_operators(&files, &names(&["ROR", "AOR"])),
+            names(&["AOR"])
+        );
+    }
+
+    #[test]
+    fn test_suggest_needs_executed_mutants() {
+        let files = file(vec![result("1", "CRR", 1, MutantStatus::Pending, 0)]);
+        assert!(suggest_operators(&files, &names(&["CRR"])).is_empty());
+    }
+}
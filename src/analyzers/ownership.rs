@@ -1,8 +1,17 @@
 //! Code ownership and bus factor analysis.
 //!
-//! Analyzes git blame data to determine code ownership concentration
-//! and calculate bus factor (minimum contributors needed to cover 50%
-//! of the codebase).
+//! Determines code ownership concentration and calculates bus factor
+//! (minimum contributors needed to cover 50% of the codebase).
+//!
+//! # Modes
+//!
+//! By default ownership is estimated from a single `git log --numstat` pass:
+//! commits are replayed oldest first, each commit's deleted lines are taken
+//! from the file's owners in proportion to their share, and its added lines
+//! are credited to its author. With `[ownership] precise` (or `--precise`)
+//! every file is blamed instead, in parallel, which is exact but far slower
+//! on large repositories. History before a rename is not followed in the
+//! estimate.
 //!
 //! # References
 //!
@@ -16,6 +25,12 @@
 //!
 //! Concentration thresholds (0.8 high, 0.6 medium) are heuristics commonly
 //! used in practice. The 50% threshold for bus factor is standard.
+//!
+//! # Warm Start
+//!
+//! With a [`crate::core::WarmStart`] in the context, files no commit touched
+//! since the previous run keep their previous ownership; only the rest are
+//! analyzed again.
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -26,8 +41,8 @@ use ignore::WalkBuilder;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
-use crate::git::GitRepo;
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result, SeverityCounts};
+use crate::git::{ChangeType, Commit, GitRepo, PathFilter};
 
 /// Default threshold for considering a contributor "significant" (5%).
 pub const SIGNIFICANT_CONTRIBUTOR_THRESHOLD: f64 = 5.0;
@@ -158,41 +173,128 @@ impl Analyzer {
             })
             .collect();
 
-        // Handle empty contributors (no blame data)
-        if contributors.is_empty() {
-            return Ok(None);
-        }
-
-        // Sort by lines owned (descending)
-        let mut sorted_contributors = contributors.clone();
-        sorted_contributors.sort_by(|a, b| b.lines_owned.cmp(&a.lines_owned));
+        Ok(file_ownership(file, contributors, total_lines as u32))
+    }
 
-        let primary_owner = sorted_contributors
-            .first()
-            .map(|c| c.name.clone())
-            .unwrap_or_default();
+    /// Estimates ownership for each file from `lines`, the surviving
+    /// lines per author replayed from the log (see [`estimate_lines`]).
+    fn estimate_file(
+        &self,
+        file: &Path,
+        lines: Option<&HashMap<String, AuthorLines>>,
+    ) -> Option<FileOwnership> {
+        let mut contributors: Vec<Contributor> = lines?
+            .iter()
+            .map(|(name, owned)| Contributor {
+                name: name.clone(),
+                email: owned.email.clone(),
+                lines_owned: owned.lines.round() as u32,
+                percentage: 0.0,
+            })
+            .filter(|c| c.lines_owned > 0)
+            .collect();
+        let total_lines: u32 = contributors.iter().map(|c| c.lines_owned).sum();
+        if total_lines == 0 || (total_lines as usize) < self.config.min_lines {
+            return None;
+        }
+        for contributor in &mut contributors {
+            contributor.percentage = contributor.lines_owned as f64 / total_lines as f64 * 100.0;
+        }
+        file_ownership(file, contributors, total_lines)
+    }
+}
 
-        let ownership_percent = sorted_contributors
-            .first()
-            .map(|c| c.percentage)
-            .unwrap_or(0.0);
+/// Ownership of `file` from its contributors, or `None` without any.
+fn file_ownership(
+    file: &Path,
+    mut contributors: Vec<Contributor>,
+    total_lines: u32,
+) -> Option<FileOwnership> {
+    if contributors.is_empty() {
+        return None;
+    }
 
-        let concentration = calculate_concentration(&sorted_contributors);
-        let is_silo = sorted_contributors.len() == 1;
+    // Sort by lines owned (descending), ties by name for stable output
+    contributors.sort_by(|a, b| {
+        b.lines_owned
+            .cmp(&a.lines_owned)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let primary_owner = contributors[0].name.clone();
+    let ownership_percent = contributors[0].percentage;
+    let concentration = calculate_concentration(&contributors);
+    let is_silo = contributors.len() == 1;
+    let risk_level = classify_risk(concentration, contributors.len());
+
+    Some(FileOwnership {
+        path: file.to_string_lossy().to_string(),
+        primary_owner,
+        ownership_percent,
+        concentration,
+        total_lines,
+        contributors,
+        is_silo,
+        risk_level,
+    })
+}
 
-        let risk_level = classify_risk(concentration, sorted_contributors.len());
+/// Lines of a file an author is estimated to still own.
+#[derive(Debug, Clone, Default)]
+struct AuthorLines {
+    email: String,
+    lines: f64,
+}
 
-        Ok(Some(FileOwnership {
-            path: file.to_string_lossy().to_string(),
-            primary_owner,
-            ownership_percent,
-            concentration,
-            total_lines: total_lines as u32,
-            contributors: sorted_contributors,
-            is_silo,
-            risk_level,
-        }))
+/// Surviving lines per author for each repository-relative path, replayed
+/// from `commits` (newest first, as the log returns them).
+///
+/// A commit's deletions are taken from the file's current owners in
+/// proportion to their lines, then its additions are credited to its author.
+/// Deleting a file drops its owners, so a path added again starts over.
+fn estimate_lines(commits: &[Commit]) -> HashMap<String, HashMap<String, AuthorLines>> {
+    let mut files: HashMap<String, HashMap<String, AuthorLines>> = HashMap::new();
+    for commit in commits.iter().rev() {
+        for change in &commit.files {
+            let path = change.path.to_string_lossy().replace('\\', "/");
+            if change.change_type == ChangeType::Deleted {
+                files.remove(&path);
+                continue;
+            }
+            let owners = files.entry(path).or_default();
+            let total: f64 = owners.values().map(|owner| owner.lines).sum();
+            if total > 0.0 && change.deletions > 0 {
+                let kept = (1.0 - change.deletions as f64 / total).max(0.0);
+                for owner in owners.values_mut() {
+                    owner.lines *= kept;
+                }
+            }
+            if change.additions > 0 {
+                let owner = owners.entry(commit.author.clone()).or_default();
+                owner.lines += change.additions as f64;
+                if owner.email.is_empty() {
+                    owner.email.clone_from(&commit.email);
+                }
+            }
+        }
     }
+    files
+}
+
+/// `file` relative to the repository `root` with forward slashes, matching
+/// the paths in the log.
+fn repo_relative(file: &Path, root: &Path) -> String {
+    let relative = file.strip_prefix(root).map(Path::to_path_buf).or_else(|_| {
+        let file = file.canonicalize()?;
+        let root = root.canonicalize()?;
+        file.strip_prefix(&root)
+            .map(Path::to_path_buf)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::NotFound))
+    });
+    relative
+        .unwrap_or_else(|_| file.to_path_buf())
+        .to_string_lossy()
+        .replace('\\', "/")
 }
 
 impl AnalyzerTrait for Analyzer {
@@ -219,6 +321,91 @@ impl AnalyzerTrait for Analyzer {
         let files: Vec<_> = ctx.files.iter().collect();
         let total_files = files.len();
 
+        // Ownership of files untouched since a previous run, keyed by path.
+        // Estimates and blame differ, so only a run in the same mode counts.
+        let precise = ctx.config.ownership.precise;
+        let previous: HashMap<String, FileOwnership> = ctx
+            .warm_start
+            .as_ref()
+            .and_then(|warm| warm.previous::<Analysis>("ownership"))
+            .filter(|analysis| analysis.summary.precise == precise)
+            .map(|analysis| {
+                analysis
+                    .files
+                    .into_iter()
+                    .map(|f| (f.path.clone(), f))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let reusable = |file: &Path| -> Option<FileOwnership> {
+            let warm = ctx.warm_start.as_ref()?;
+            if warm.changed(file) {
+                return None;
+            }
+            previous.get(file.to_string_lossy().as_ref()).cloned()
+        };
+
+        let file_ownerships = if precise {
+            self.blame_files(ctx, git_path, &files, &reusable)
+        } else {
+            let repo = GitRepo::open(git_path)?
+                .at_ref(ctx.config.git.at_ref.as_deref())
+                .with_identities(&ctx.config.git);
+            let filter = PathFilter::from_context(ctx, repo.root());
+            let lines = estimate_lines(&ctx.log_with_stats(&repo, None, &filter)?);
+            let root = repo.root().to_path_buf();
+            ctx.report_progress(total_files, total_files);
+            files
+                .par_iter()
+                .filter_map(|file| {
+                    reusable(file).or_else(|| {
+                        let key = repo_relative(file, &root);
+                        self.estimate_file(file, lines.get(&key))
+                    })
+                })
+                .collect()
+        };
+
+        // Aggregate contributor lines across files
+        let mut all_contributors: HashMap<String, u32> = HashMap::new();
+        for ownership in &file_ownerships {
+            for contributor in &ownership.contributors {
+                *all_contributors
+                    .entry(contributor.name.clone())
+                    .or_insert(0) += contributor.lines_owned;
+            }
+        }
+
+        // Sort by concentration (highest first - most risky)
+        let mut sorted_ownerships = file_ownerships;
+        sorted_ownerships.sort_by(|a, b| {
+            b.concentration
+                .partial_cmp(&a.concentration)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut summary = calculate_summary(&sorted_ownerships, &all_contributors);
+        summary.precise = precise;
+
+        Ok(Analysis {
+            generated_at: Utc::now().to_rfc3339(),
+            files: sorted_ownerships,
+            summary,
+        })
+    }
+}
+
+impl Analyzer {
+    /// Blames each file in parallel, reusing `reusable` files' ownership.
+    fn blame_files(
+        &self,
+        ctx: &AnalysisContext<'_>,
+        git_path: &Path,
+        files: &[&std::path::PathBuf],
+        reusable: &(dyn Fn(&Path) -> Option<FileOwnership> + Sync),
+    ) -> Vec<FileOwnership> {
+        let total_files = files.len();
+
         // Progress tracking
         let progress_counter = AtomicUsize::new(0);
 
@@ -231,14 +418,17 @@ impl AnalyzerTrait for Analyzer {
         // Phase 1: Parallel blame operations (the expensive part)
         // Each thread reuses its own GitRepo for all files it processes
         let git_path_owned = git_path.to_path_buf();
-        let file_ownerships: Vec<FileOwnership> = files
+        let at_ref = ctx.config.git.at_ref.as_deref();
+        files
             .par_iter()
             .filter_map(|file| {
                 // Get or create thread-local git repo
                 THREAD_REPO.with(|cell| {
                     let mut repo_opt = cell.borrow_mut();
                     if repo_opt.is_none() {
-                        *repo_opt = GitRepo::open(&git_path_owned).ok();
+                        *repo_opt = GitRepo::open(&git_path_owned)
+                            .ok()
+                            .map(|repo| repo.at_ref(at_ref).with_identities(&ctx.config.git));
                     }
                     let repo = repo_opt.as_ref()?;
 
@@ -248,38 +438,15 @@ impl AnalyzerTrait for Analyzer {
                         ctx.report_progress(current, total_files);
                     }
 
+                    if let Some(ownership) = reusable(file) {
+                        return Some(ownership);
+                    }
                     self.analyze_file(repo, file, &git_path_owned)
                         .ok()
                         .flatten()
                 })
             })
-            .collect();
-
-        // Phase 2: Sequential aggregation (fast, O(n))
-        let mut all_contributors: HashMap<String, u32> = HashMap::new();
-        for ownership in &file_ownerships {
-            for contributor in &ownership.contributors {
-                *all_contributors
-                    .entry(contributor.name.clone())
-                    .or_insert(0) += contributor.lines_owned;
-            }
-        }
-
-        // Sort by concentration (highest first - most risky)
-        let mut sorted_ownerships = file_ownerships;
-        sorted_ownerships.sort_by(|a, b| {
-            b.concentration
-                .partial_cmp(&a.concentration)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        let summary = calculate_summary(&sorted_ownerships, &all_contributors);
-
-        Ok(Analysis {
-            generated_at: Utc::now().to_rfc3339(),
-            files: sorted_ownerships,
-            summary,
-        })
+            .collect()
     }
 }
 
@@ -390,6 +557,8 @@ fn calculate_summary(files: &[FileOwnership], all_contributors: &HashMap<String,
         avg_contributors,
         max_concentration,
         top_contributors,
+        severity_counts: files.iter().map(|f| f.risk_level).collect(),
+        precise: false,
     }
 }
 
@@ -449,6 +618,16 @@ pub enum RiskLevel {
     Low,
 }
 
+impl From<RiskLevel> for crate::core::Severity {
+    fn from(level: RiskLevel) -> Self {
+        match level {
+            RiskLevel::High => Self::High,
+            RiskLevel::Medium => Self::Medium,
+            RiskLevel::Low => Self::Low,
+        }
+    }
+}
+
 impl std::fmt::Display for RiskLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -483,11 +662,19 @@ pub struct Summary {
     pub max_concentration: f64,
     /// Top contributors by files owned.
     pub top_contributors: Vec<TopContributor>,
+    /// Files per ownership risk level.
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
+    /// Whether ownership came from blame rather than estimated from the log.
+    #[serde(default)]
+    pub precise: bool,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::manifest::Manifest;
+    use crate::test_support::{git, init_repo};
 
     #[test]
     fn test_config_default() {
@@ -790,4 +977,193 @@ mod tests {
         assert_eq!(contributor.lines_owned, 150);
         assert!((contributor.percentage - 60.0).abs() < 0.001);
     }
+
+    fn change(path: &str, additions: u32, deletions: u32, change_type: ChangeType) -> Commit {
+        Commit {
+            sha: String::new(),
+            author: String::new(),
+            email: String::new(),
+            timestamp: 0,
+            message: String::new(),
+            files: vec![crate::git::FileChange {
+                path: path.into(),
+                additions,
+                deletions,
+                change_type,
+            }],
+        }
+    }
+
+    fn by(author: &str, mut commit: Commit) -> Commit {
+        commit.author = author.to_string();
+        commit.email = format!("{}@example.com", author.to_lowercase());
+        commit
+    }
+
+    #[test]
+    fn test_estimate_lines_replays_oldest_first() {
+        // Newest first, as the log returns them
+        let commits = vec![
+            by("Carol", change("b.rs", 5, 0, ChangeType::Added)),
+            by("Bob", change("a.rs", 40, 50, ChangeType::Modified)),
+            by("Alice", change("a.rs", 100, 0, ChangeType::Added)),
+            by("Alice", change("b.rs", 0, 10, ChangeType::Deleted)),
+            by("Alice", change("b.rs", 10, 0, ChangeType::Added)),
+        ];
+        let files = estimate_lines(&commits);
+
+        let a = &files["a.rs"];
+        assert!((a["Alice"].lines - 50.0).abs() < 1e-9);
+        assert!((a["Bob"].lines - 40.0).abs() < 1e-9);
+        assert_eq!(a["Bob"].email, "bob@example.com");
+        // Deleting b.rs dropped Alice; the re-added file is Carol's
+        assert_eq!(files["b.rs"].len(), 1);
+        assert!((files["b.rs"]["Carol"].lines - 5.0).abs() < 1e-9);
+
+        let analyzer = Analyzer::new();
+        let ownership = analyzer
+            .estimate_file(Path::new("a.rs"), files.get("a.rs"))
+            .unwrap();
+        assert_eq!(ownership.primary_owner, "Alice");
+        assert_eq!(ownership.total_lines, 90);
+        assert!((ownership.ownership_percent - 50.0 / 90.0 * 100.0).abs() < 1e-9);
+        assert_eq!(ownership.contributors[0].email, "alice@example.com");
+        assert!(analyzer.estimate_file(Path::new("c.rs"), None).is_none());
+    }
+
+    #[test]
+    fn test_precise_mode_blames_files() {
+        use crate::config::Config;
+        use crate::core::FileSet;
+
+        let repo = tempfile::TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::write(repo.path().join("a.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-q", "-m", "init"]);
+
+        let mut config = Config::default();
+        let files = FileSet::from_path(repo.path(), &config).unwrap();
+        let estimated = {
+            let ctx =
+                AnalysisContext::new(&files, &config, Some(repo.path())).with_git_path(repo.path());
+            Analyzer::new().analyze(&ctx).unwrap()
+        };
+        config.ownership.precise = true;
+        let ctx =
+            AnalysisContext::new(&files, &config, Some(repo.path())).with_git_path(repo.path());
+        let blamed = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert!(!estimated.summary.precise);
+        assert!(blamed.summary.precise);
+        for analysis in [&estimated, &blamed] {
+            assert_eq!(analysis.files.len(), 1);
+            assert_eq!(analysis.files[0].primary_owner, "Test");
+            assert_eq!(analysis.files[0].total_lines, 2);
+        }
+        assert_eq!(estimated.files[0].contributors[0].email, "test@example.com");
+    }
+
+    #[test]
+    fn test_warm_start_reuses_untouched_files() {
+        use crate::config::Config;
+        use crate::core::{FileSet, WarmStart};
+        use std::sync::Arc;
+
+        let repo = tempfile::TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::write(repo.path().join("a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(repo.path().join("b.rs"), "fn b() {}\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-q", "-m", "init"]);
+        let mut config = Config::default();
+
+        // Save a previous run with a recognizable owner everywhere
+        let files = FileSet::from_path(repo.path(), &config).unwrap();
+        let ctx =
+            AnalysisContext::new(&files, &config, Some(repo.path())).with_git_path(repo.path());
+        let mut previous = Analyzer::new().analyze(&ctx).unwrap();
+        assert_eq!(previous.files.len(), 2);
+        for file in &mut previous.files {
+            file.primary_owner = "Earlier".to_string();
+        }
+        let data = tempfile::TempDir::new().unwrap();
+        let manifest = Manifest::build(repo.path(), &files, &config).unwrap();
+        let metadata = serde_json::json!({ "since": "1y", "manifest": manifest });
+        std::fs::write(data.path().join("metadata.json"), metadata.to_string()).unwrap();
+        let previous = serde_json::to_string(&previous).unwrap();
+        std::fs::write(data.path().join("ownership.json"), previous).unwrap();
+
+        std::fs::write(repo.path().join("b.rs"), "fn b() { a() }\n").unwrap();
+        git(repo.path(), &["commit", "-q", "-am", "change b"]);
+        let current = Manifest::build(repo.path(), &files, &config).unwrap();
+        let warm = WarmStart::load(
+            data.path(),
+            &GitRepo::open(repo.path()).unwrap(),
+            &current,
+            "1y",
+        )
+        .unwrap();
+        let warm = Arc::new(warm);
+
+        let run = |config: &Config| {
+            let ctx = AnalysisContext::new(&files, config, Some(repo.path()))
+                .with_git_path(repo.path())
+                .with_warm_start(Arc::clone(&warm));
+            Analyzer::new().analyze(&ctx).unwrap()
+        };
+        let owner = |analysis: &Analysis, name: &str| {
+            let file = analysis.files.iter().find(|f| f.path.ends_with(name));
+            file.unwrap().primary_owner.clone()
+        };
+        let analysis = run(&config);
+        assert_eq!(owner(&analysis, "a.rs"), "Earlier");
+        assert_eq!(owner(&analysis, "b.rs"), "Test");
+
+        // Estimates are not reused for a precise run
+        config.ownership.precise = true;
+        let analysis = run(&config);
+        assert_eq!(owner(&analysis, "a.rs"), "Test");
+    }
+
+    #[test]
+    fn test_identity_aliases_merge_contributors() {
+        use crate::config::Config;
+        use crate::core::FileSet;
+
+        let repo = tempfile::TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::write(repo.path().join("a.rs"), "fn a() {}\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-q", "-m", "init"]);
+        std::fs::write(repo.path().join("a.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        git(
+            repo.path(),
+            &[
+                "-c",
+                "user.name=T. Ester",
+                "-c",
+                "user.email=tester@laptop.local",
+                "commit",
+                "-q",
+                "-am",
+                "add b",
+            ],
+        );
+        let mut config = Config::default();
+        config.git.identities.insert(
+            "Test <test@example.com>".to_string(),
+            vec!["tester@laptop.local".to_string()],
+        );
+
+        let files = FileSet::from_path(repo.path(), &config).unwrap();
+        let ctx =
+            AnalysisContext::new(&files, &config, Some(repo.path())).with_git_path(repo.path());
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let file = &analysis.files[0];
+        assert_eq!(file.contributors.len(), 1);
+        assert_eq!(file.primary_owner, "Test");
+        assert!(file.is_silo);
+    }
 }
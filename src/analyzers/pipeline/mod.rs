@@ -0,0 +1,919 @@
+//! Build and CI configuration complexity audit.
+//!
+//! Parses GitHub Actions workflows (`.github/workflows/*.yml`), GitLab CI
+//! configs (`.gitlab-ci.yml`, `.gitlab/ci/*.yml`) and build scripts
+//! (`Makefile`, `*.mk`, `justfile`) and reports, per file:
+//! - Job count (Make/just targets count as jobs, recipe lines as steps)
+//! - Step count, conditionals (`if:`, `rules:`, `ifeq`), `needs:` edges and
+//!   matrix axes
+//! - External references (actions, reusable workflows, container images,
+//!   GitLab components) classified as pinned to a commit SHA or digest,
+//!   pinned to an exact version, or floating (branch, major tag, `latest`)
+//!
+//! Across files, steps with identical bodies in different jobs are reported
+//! as duplicates. A file's complexity is the sum of its jobs, steps,
+//! conditionals, `needs:` edges and matrix axes.
+
+mod yaml;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, ContentSource, Result, TreeSource};
+use yaml::{Entry, Node};
+
+/// Build/CI configuration analyzer.
+#[derive(Default)]
+pub struct Analyzer;
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "pipeline"
+    }
+
+    fn description(&self) -> &'static str {
+        "Audit CI configs and build scripts for sprawl, duplication and floating versions"
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        if let Some(repo) = ctx.open_git()? {
+            if repo.reads_from_objects() {
+                return analyze_tree(&repo.tree_source()?);
+            }
+        }
+
+        let mut sources = Vec::new();
+        let walker = WalkBuilder::new(ctx.root)
+            .hidden(false)
+            .filter_entry(|e| e.file_name() != ".git")
+            .build();
+        for entry in walker.flatten() {
+            let Ok(relative) = entry.path().strip_prefix(ctx.root) else {
+                continue;
+            };
+            if PipelineKind::detect(relative).is_none() {
+                continue;
+            }
+            if let Ok(bytes) = ctx.read_file(relative) {
+                sources.push((
+                    relative.to_path_buf(),
+                    String::from_utf8_lossy(&bytes).into_owned(),
+                ));
+            }
+        }
+        Ok(analyze_sources(sources))
+    }
+}
+
+/// Audit the pipeline files in a git tree, for trend sampling.
+pub fn analyze_tree(tree: &TreeSource) -> Result<Analysis> {
+    let sources = tree
+        .list_files()?
+        .into_iter()
+        .filter(|path| PipelineKind::detect(path).is_some())
+        .filter_map(|path| {
+            let bytes = tree.read(&path).ok()?;
+            Some((path, String::from_utf8_lossy(&bytes).into_owned()))
+        })
+        .collect();
+    Ok(analyze_sources(sources))
+}
+
+/// Audit already-loaded pipeline files given as `(relative path, contents)`.
+pub fn analyze_sources(mut sources: Vec<(PathBuf, String)>) -> Analysis {
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut files = Vec::new();
+    let mut steps_by_body: BTreeMap<String, Vec<StepLocation>> = BTreeMap::new();
+    for (path, content) in &sources {
+        let Some(kind) = PipelineKind::detect(path) else {
+            continue;
+        };
+        let path = path.to_string_lossy().to_string();
+        let mut audit = FileAudit::default();
+        match kind {
+            PipelineKind::GithubActions => audit.github(&yaml::parse(content)),
+            PipelineKind::GitlabCi => audit.gitlab(&yaml::parse(content)),
+            PipelineKind::Make | PipelineKind::Just => audit.recipes(content, kind),
+        }
+        for step in audit.steps {
+            steps_by_body
+                .entry(step.body)
+                .or_default()
+                .push(StepLocation {
+                    file: path.clone(),
+                    job: step.job,
+                    line: step.line,
+                });
+        }
+        files.push(audit.file.finish(path, kind));
+    }
+
+    let mut duplicates: Vec<DuplicateStep> = steps_by_body
+        .into_iter()
+        .filter(|(_, locations)| {
+            locations.len() > 1
+                && locations
+                    .iter()
+                    .any(|l| (&l.file, &l.job) != (&locations[0].file, &locations[0].job))
+        })
+        .map(|(body, occurrences)| DuplicateStep {
+            step: body.lines().next().unwrap_or_default().to_string(),
+            occurrences,
+        })
+        .collect();
+    duplicates.sort_by(|a, b| {
+        b.occurrences
+            .len()
+            .cmp(&a.occurrences.len())
+            .then_with(|| a.step.cmp(&b.step))
+    });
+    files.sort_by(|a, b| b.complexity.cmp(&a.complexity).then(a.path.cmp(&b.path)));
+
+    let summary = Summary::from_results(&files, &duplicates);
+    Analysis {
+        generated_at: Utc::now(),
+        files,
+        duplicates,
+        summary,
+    }
+}
+
+/// Kind of pipeline or build file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineKind {
+    GithubActions,
+    GitlabCi,
+    Make,
+    Just,
+}
+
+impl PipelineKind {
+    /// Classify a root-relative path.
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let parent = path.parent().and_then(|p| p.to_str()).unwrap_or("");
+        let is_yaml = name.ends_with(".yml") || name.ends_with(".yaml");
+        if is_yaml && parent == ".github/workflows" {
+            return Some(Self::GithubActions);
+        }
+        if name == ".gitlab-ci.yml" || (is_yaml && parent.starts_with(".gitlab/ci")) {
+            return Some(Self::GitlabCi);
+        }
+        match name {
+            "Makefile" | "makefile" | "GNUmakefile" => Some(Self::Make),
+            _ if name.ends_with(".mk") => Some(Self::Make),
+            "justfile" | "Justfile" | ".justfile" => Some(Self::Just),
+            _ => None,
+        }
+    }
+}
+
+/// How tightly an external reference is pinned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pinning {
+    /// Full commit SHA or image digest; immutable.
+    Sha,
+    /// Exact version tag such as `v4.1.2`; tags can still be moved.
+    Version,
+    /// Branch, major-version tag, `latest` or no version at all.
+    Floating,
+}
+
+impl Pinning {
+    /// Classify a version string (`None` when the reference has none).
+    pub fn classify(version: Option<&str>) -> Self {
+        let Some(version) = version.filter(|v| !v.is_empty()) else {
+            return Self::Floating;
+        };
+        if let Some(digest) = version.strip_prefix("sha256:") {
+            if digest.len() == 64 && digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Self::Sha;
+            }
+        }
+        if version.len() == 40 && version.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Self::Sha;
+        }
+        let numeric = version.trim_start_matches('v');
+        let parts: Vec<&str> = numeric.split(['.', '-', '+']).collect();
+        if parts.len() >= 3 && parts[..3].iter().all(|p| p.parse::<u64>().is_ok()) {
+            Self::Version
+        } else {
+            Self::Floating
+        }
+    }
+}
+
+/// An action, reusable workflow, image or component used by a pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalRef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub pinning: Pinning,
+    pub line: u32,
+}
+
+impl ExternalRef {
+    /// Parse `owner/repo@ref` (actions, components).
+    fn at_ref(value: &str, line: u32) -> Self {
+        let (name, version) = match value.rsplit_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (value, None),
+        };
+        Self::new(name, version, line)
+    }
+
+    /// Parse `image:tag` or `image@sha256:digest`.
+    pub fn image(value: &str, line: u32) -> Self {
+        if let Some((name, digest)) = value.split_once('@') {
+            return Self::new(name, Some(digest), line);
+        }
+        // The tag separator is the last `:` after any registry port.
+        let last_segment = value.rfind('/').map_or(0, |i| i + 1);
+        match value[last_segment..].rfind(':') {
+            Some(i) => Self::new(
+                &value[..last_segment + i],
+                Some(&value[last_segment + i + 1..]),
+                line,
+            ),
+            None => Self::new(value, None, line),
+        }
+    }
+
+    fn new(name: &str, version: Option<&str>, line: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+            pinning: Pinning::classify(version),
+            line,
+        }
+    }
+}
+
+/// Metrics for one pipeline or build file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineFile {
+    pub path: String,
+    pub kind: PipelineKind,
+    pub jobs: usize,
+    pub steps: usize,
+    pub conditionals: usize,
+    pub needs: usize,
+    pub matrix_axes: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refs: Vec<ExternalRef>,
+    pub complexity: usize,
+}
+
+/// The same step body repeated in more than one job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateStep {
+    /// First line of the repeated step.
+    pub step: String,
+    pub occurrences: Vec<StepLocation>,
+}
+
+/// Where a step appears.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepLocation {
+    pub file: String,
+    pub job: String,
+    pub line: u32,
+}
+
+/// Pipeline audit result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    pub generated_at: DateTime<Utc>,
+    /// Files ordered by complexity, most complex first.
+    pub files: Vec<PipelineFile>,
+    /// Duplicated steps, most repeated first.
+    pub duplicates: Vec<DuplicateStep>,
+    pub summary: Summary,
+}
+
+/// Aggregate pipeline statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_files: usize,
+    pub total_jobs: usize,
+    pub total_steps: usize,
+    /// Step occurrences beyond the first of each duplicated step.
+    pub duplicated_steps: usize,
+    pub external_refs: usize,
+    pub sha_pinned_refs: usize,
+    pub version_pinned_refs: usize,
+    pub floating_refs: usize,
+    /// Share of external references pinned to a SHA or digest (1.0 when none).
+    pub sha_pinned_ratio: f64,
+    pub total_complexity: usize,
+    pub max_complexity: usize,
+}
+
+impl Summary {
+    fn from_results(files: &[PipelineFile], duplicates: &[DuplicateStep]) -> Self {
+        let mut summary = Self {
+            total_files: files.len(),
+            duplicated_steps: duplicates.iter().map(|d| d.occurrences.len() - 1).sum(),
+            ..Default::default()
+        };
+        for file in files {
+            summary.total_jobs += file.jobs;
+            summary.total_steps += file.steps;
+            summary.total_complexity += file.complexity;
+            summary.max_complexity = summary.max_complexity.max(file.complexity);
+            for r in &file.refs {
+                summary.external_refs += 1;
+                match r.pinning {
+                    Pinning::Sha => summary.sha_pinned_refs += 1,
+                    Pinning::Version => summary.version_pinned_refs += 1,
+                    Pinning::Floating => summary.floating_refs += 1,
+                }
+            }
+        }
+        summary.sha_pinned_ratio = if summary.external_refs == 0 {
+            1.0
+        } else {
+            summary.sha_pinned_refs as f64 / summary.external_refs as f64
+        };
+        summary
+    }
+}
+
+#[derive(Default)]
+struct Counts {
+    jobs: usize,
+    steps: usize,
+    conditionals: usize,
+    needs: usize,
+    matrix_axes: usize,
+    refs: Vec<ExternalRef>,
+}
+
+impl Counts {
+    fn finish(self, path: String, kind: PipelineKind) -> PipelineFile {
+        let complexity = self.jobs + self.steps + self.conditionals + self.needs + self.matrix_axes;
+        PipelineFile {
+            path,
+            kind,
+            jobs: self.jobs,
+            steps: self.steps,
+            conditionals: self.conditionals,
+            needs: self.needs,
+            matrix_axes: self.matrix_axes,
+            refs: self.refs,
+            complexity,
+        }
+    }
+}
+
+/// A step body considered for duplicate detection.
+struct Step {
+    job: String,
+    line: u32,
+    body: String,
+}
+
+#[derive(Default)]
+struct FileAudit {
+    file: Counts,
+    steps: Vec<Step>,
+}
+
+/// Top-level GitLab CI keys that configure the pipeline rather than name a job.
+const GITLAB_RESERVED: &[&str] = &[
+    "stages",
+    "variables",
+    "default",
+    "include",
+    "image",
+    "services",
+    "workflow",
+    "before_script",
+    "after_script",
+    "cache",
+    "types",
+];
+
+impl FileAudit {
+    fn github(&mut self, doc: &Node) {
+        let Some(jobs) = doc.get("jobs") else {
+            return;
+        };
+        for (name, job) in jobs.node.entries() {
+            let job_node = &job.node;
+            self.file.jobs += 1;
+            self.conditions(job_node, &["if"]);
+            self.file.needs += job_node.get("needs").map_or(0, |n| n.node.count());
+            if let Some(matrix) = job_node.get("strategy").and_then(|s| s.node.get("matrix")) {
+                self.file.matrix_axes += matrix
+                    .node
+                    .entries()
+                    .iter()
+                    .filter(|(k, _)| k != "include" && k != "exclude")
+                    .count();
+            }
+            // Reusable workflow call
+            if let Some(uses) = scalar(job_node.get("uses")) {
+                self.file.refs.push(action_ref(uses.0, uses.1));
+            }
+            if let Some(image) = container_image(job_node.get("container")) {
+                self.file.refs.push(ExternalRef::image(image.0, image.1));
+            }
+            let Some(steps) = job_node.get("steps") else {
+                continue;
+            };
+            for step in steps.node.items() {
+                self.file.steps += 1;
+                self.conditions(&step.node, &["if"]);
+                if let Some((uses, line)) = scalar(step.node.get("uses")) {
+                    if !uses.starts_with("./") {
+                        self.file.refs.push(action_ref(uses, line));
+                    }
+                }
+                if let Some(body) = github_step_body(&step.node) {
+                    self.steps.push(Step {
+                        job: name.clone(),
+                        line: step.line,
+                        body,
+                    });
+                }
+            }
+        }
+    }
+
+    fn gitlab(&mut self, doc: &Node) {
+        if let Some(image) = container_image(doc.get("image")) {
+            self.file.refs.push(ExternalRef::image(image.0, image.1));
+        }
+        if let Some(default) = doc.get("default") {
+            self.gitlab_job_refs(&default.node);
+        }
+        if let Some(include) = doc.get("include") {
+            for item in include.node.items() {
+                if let Some((component, line)) = scalar(item.node.get("component")) {
+                    self.file.refs.push(ExternalRef::at_ref(component, line));
+                } else if let Some((project, line)) = scalar(item.node.get("project")) {
+                    let version = scalar(item.node.get("ref")).map(|(v, _)| v);
+                    self.file
+                        .refs
+                        .push(ExternalRef::new(project, version, line));
+                }
+            }
+        }
+        for (name, job) in doc.entries() {
+            if GITLAB_RESERVED.contains(&name.as_str()) || !matches!(job.node, Node::Map(_)) {
+                continue;
+            }
+            let job_node = &job.node;
+            // Hidden `.template` keys are not jobs but their steps still run.
+            if !name.starts_with('.') {
+                self.file.jobs += 1;
+            }
+            self.conditions(job_node, &["rules", "only", "except"]);
+            self.file.needs += ["needs", "dependencies"]
+                .iter()
+                .filter_map(|k| job_node.get(k))
+                .map(|n| n.node.count())
+                .sum::<usize>();
+            if let Some(matrix) = job_node.get("parallel").and_then(|p| p.node.get("matrix")) {
+                let mut axes: Vec<&str> = matrix
+                    .node
+                    .items()
+                    .iter()
+                    .flat_map(|m| m.node.entries().iter().map(|(k, _)| k.as_str()))
+                    .collect();
+                axes.sort_unstable();
+                axes.dedup();
+                self.file.matrix_axes += axes.len();
+            }
+            self.gitlab_job_refs(job_node);
+            for key in ["before_script", "script", "after_script"] {
+                let Some(script) = job_node.get(key) else {
+                    continue;
+                };
+                self.file.steps += script.node.count();
+                let lines = script_lines(&script.node);
+                if !lines.is_empty() {
+                    self.steps.push(Step {
+                        job: name.clone(),
+                        line: script.line,
+                        body: lines.join("\n"),
+                    });
+                }
+            }
+        }
+    }
+
+    fn gitlab_job_refs(&mut self, job: &Node) {
+        if let Some(image) = container_image(job.get("image")) {
+            self.file.refs.push(ExternalRef::image(image.0, image.1));
+        }
+        if let Some(services) = job.get("services") {
+            for service in services.node.items() {
+                if let Some((image, line)) = container_image(Some(service)) {
+                    self.file.refs.push(ExternalRef::image(image, line));
+                }
+            }
+        }
+    }
+
+    /// Count conditional keys on a job or step; `rules:` counts each rule.
+    fn conditions(&mut self, node: &Node, keys: &[&str]) {
+        for key in keys {
+            if let Some(entry) = node.get(key) {
+                self.file.conditionals += match &entry.node {
+                    Node::Seq(rules) if *key == "rules" => rules.len(),
+                    _ => 1,
+                };
+            }
+        }
+    }
+
+    /// Make and just: unindented `target:` lines open a recipe, indented
+    /// lines below it are its steps.
+    fn recipes(&mut self, content: &str, kind: PipelineKind) {
+        let mut current: Option<(String, u32, Vec<String>)> = None;
+        let mut in_define = false;
+        for (i, line) in content.lines().enumerate() {
+            let no = i as u32 + 1;
+            let trimmed = line.trim();
+            if in_define {
+                in_define = trimmed != "endef";
+                continue;
+            }
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indented = line.starts_with('\t') || line.starts_with(' ');
+            if let Some((_, _, body)) = current.as_mut().filter(|_| indented) {
+                body.push(trimmed.to_string());
+                self.file.steps += 1;
+                continue;
+            }
+            self.flush_recipe(current.take());
+
+            let word = trimmed.split_whitespace().next().unwrap_or("");
+            if kind == PipelineKind::Make {
+                if matches!(word, "ifeq" | "ifneq" | "ifdef" | "ifndef") {
+                    self.file.conditionals += 1;
+                    continue;
+                }
+                if word == "define" {
+                    in_define = true;
+                    continue;
+                }
+            }
+            if let Some((targets, prerequisites)) = recipe_header(trimmed, kind) {
+                // `.PHONY:` and friends are directives, not targets.
+                if targets.starts_with('.') {
+                    continue;
+                }
+                self.file.jobs += 1;
+                self.file.needs += prerequisites;
+                current = Some((targets.to_string(), no, Vec::new()));
+            }
+        }
+        self.flush_recipe(current);
+    }
+
+    fn flush_recipe(&mut self, recipe: Option<(String, u32, Vec<String>)>) {
+        // Single-line recipes are usually aliases; only flag real blocks.
+        if let Some((job, line, body)) = recipe.filter(|(_, _, body)| body.len() > 1) {
+            self.steps.push(Step {
+                job,
+                line,
+                body: body.join("\n"),
+            });
+        }
+    }
+}
+
+fn scalar(entry: Option<&Entry>) -> Option<(&str, u32)> {
+    let entry = entry?;
+    entry
+        .node
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(|s| (s, entry.line))
+}
+
+/// `image: name` or `image: { name: ... }` (GitLab) / `container: name` or
+/// `container: { image: ... }` (GitHub).
+fn container_image(entry: Option<&Entry>) -> Option<(&str, u32)> {
+    let entry = entry?;
+    scalar(Some(entry))
+        .or_else(|| scalar(entry.node.get("name")))
+        .or_else(|| scalar(entry.node.get("image")))
+}
+
+fn action_ref(uses: &str, line: u32) -> ExternalRef {
+    match uses.strip_prefix("docker://") {
+        Some(image) => ExternalRef::image(image, line),
+        None => ExternalRef::at_ref(uses, line),
+    }
+}
+
+/// Body used to spot duplicated GitHub steps: `run` scripts and configured
+/// `uses` steps. A bare `uses: actions/checkout@v4` is boilerplate, not
+/// duplication worth extracting.
+fn github_step_body(step: &Node) -> Option<String> {
+    if let Some(run) = step.get("run").and_then(|r| r.node.as_str()) {
+        let lines: Vec<&str> = run
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        return (!lines.is_empty()).then(|| lines.join("\n"));
+    }
+    let uses = step.get("uses")?.node.as_str()?;
+    let with = step.get("with")?;
+    let mut body = format!("uses: {uses}");
+    for (key, value) in with.node.entries() {
+        body.push_str(&format!("\n{key}: {}", value.node.as_str().unwrap_or("")));
+    }
+    Some(body)
+}
+
+fn script_lines(node: &Node) -> Vec<String> {
+    match node {
+        Node::Scalar(s) => s
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Node::Seq(items) => items.iter().flat_map(|i| script_lines(&i.node)).collect(),
+        Node::Map(_) => Vec::new(),
+    }
+}
+
+/// Parse `targets: prerequisites`, rejecting variable assignments
+/// (`X := y`, `X = y`, `X ?= y`). just recipe parameters may carry
+/// defaults (`test filter='':`), so only make rejects `=` before the colon.
+fn recipe_header(line: &str, kind: PipelineKind) -> Option<(&str, usize)> {
+    let colon = line.find(':')?;
+    let (targets, rest) = (&line[..colon], &line[colon + 1..]);
+    if (kind == PipelineKind::Make && targets.contains('='))
+        || rest.starts_with('=')
+        || rest.starts_with(":=")
+    {
+        return None;
+    }
+    let targets = targets.trim().trim_start_matches('@');
+    if targets.is_empty() {
+        return None;
+    }
+    // just recipes list parameters before the colon; make lists targets.
+    let name = targets.split_whitespace().next().unwrap_or(targets);
+    let rest = rest.trim_start_matches(':');
+    let prerequisites = rest
+        .split(['#', ';'])
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .count();
+    Some((name, prerequisites))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit(files: &[(&str, &str)]) -> Analysis {
+        analyze_sources(
+            files
+                .iter()
+                .map(|(p, c)| (PathBuf::from(p), c.to_string()))
+                .collect(),
+        )
+    }
+
+    const WORKFLOW: &str = r#"name: CI
+on: [push]
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [ubuntu-latest, macos-latest]
+        rust: [stable, beta]
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@0b1efabc08b657293548b77fb76cc02d26091c7e
+      - run: |
+          cargo build
+          cargo test
+  lint:
+    needs: test
+    if: github.event_name == 'push'
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4.1.2
+      - uses: ./.github/actions/setup
+      - run: |
+          cargo build
+          cargo test
+"#;
+
+    #[test]
+    fn test_detect_kinds() {
+        let detect = |p: &str| PipelineKind::detect(Path::new(p));
+        assert_eq!(
+            detect(".github/workflows/ci.yml"),
+            Some(PipelineKind::GithubActions)
+        );
+        assert_eq!(detect(".gitlab-ci.yml"), Some(PipelineKind::GitlabCi));
+        assert_eq!(
+            detect(".gitlab/ci/build.yaml"),
+            Some(PipelineKind::GitlabCi)
+        );
+        assert_eq!(detect("Makefile"), Some(PipelineKind::Make));
+        assert_eq!(detect("tools/rules.mk"), Some(PipelineKind::Make));
+        assert_eq!(detect("justfile"), Some(PipelineKind::Just));
+        assert_eq!(detect("config/ci.yml"), None);
+        assert_eq!(detect(".github/dependabot.yml"), None);
+    }
+
+    #[test]
+    fn test_pinning_classify() {
+        assert_eq!(
+            Pinning::classify(Some("0b1efabc08b657293548b77fb76cc02d26091c7e")),
+            Pinning::Sha
+        );
+        assert_eq!(Pinning::classify(Some("v4.1.2")), Pinning::Version);
+        assert_eq!(Pinning::classify(Some("20.11.1-alpine")), Pinning::Version);
+        assert_eq!(Pinning::classify(Some("v4")), Pinning::Floating);
+        assert_eq!(Pinning::classify(Some("main")), Pinning::Floating);
+        assert_eq!(Pinning::classify(Some("latest")), Pinning::Floating);
+        assert_eq!(Pinning::classify(None), Pinning::Floating);
+    }
+
+    #[test]
+    fn test_image_refs() {
+        let r = ExternalRef::image("registry.local:5000/team/app", 1);
+        assert_eq!(r.name, "registry.local:5000/team/app");
+        assert_eq!(r.pinning, Pinning::Floating);
+
+        let r = ExternalRef::image("node:20.11.1", 1);
+        assert_eq!((r.name.as_str(), r.pinning), ("node", Pinning::Version));
+
+        let digest = format!("alpine@sha256:{}", "a".repeat(64));
+        assert_eq!(ExternalRef::image(&digest, 1).pinning, Pinning::Sha);
+    }
+
+    #[test]
+    fn test_github_workflow_metrics() {
+        let result = audit(&[(".github/workflows/ci.yml", WORKFLOW)]);
+        let file = &result.files[0];
+        assert_eq!(file.kind, PipelineKind::GithubActions);
+        assert_eq!(file.jobs, 2);
+        assert_eq!(file.steps, 6);
+        assert_eq!(file.conditionals, 1);
+        assert_eq!(file.needs, 1);
+        assert_eq!(file.matrix_axes, 2);
+        assert_eq!(file.complexity, 2 + 6 + 1 + 1 + 2);
+
+        // Local actions are not external references.
+        let pins: Vec<_> = file.refs.iter().map(|r| r.pinning).collect();
+        assert_eq!(
+            pins,
+            vec![Pinning::Floating, Pinning::Sha, Pinning::Version]
+        );
+        assert_eq!(file.refs[0].line, 11);
+
+        assert_eq!(result.summary.floating_refs, 1);
+        assert_eq!(result.summary.sha_pinned_refs, 1);
+        assert!((result.summary.sha_pinned_ratio - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_duplicate_steps_across_jobs() {
+        let result = audit(&[(".github/workflows/ci.yml", WORKFLOW)]);
+        assert_eq!(result.duplicates.len(), 1);
+        let dup = &result.duplicates[0];
+        assert_eq!(dup.step, "cargo build");
+        let jobs: Vec<_> = dup.occurrences.iter().map(|o| o.job.as_str()).collect();
+        assert_eq!(jobs, vec!["test", "lint"]);
+        assert_eq!(result.summary.duplicated_steps, 1);
+    }
+
+    #[test]
+    fn test_gitlab_metrics() {
+        let config = r#"
+stages: [build, test]
+image: node:latest
+include:
+  - component: gitlab.com/acme/ci/lint@1.2.0
+.cache: &cache
+  cache:
+    paths: [node_modules]
+build:
+  stage: build
+  script:
+    - npm ci
+    - npm run build
+test:
+  stage: test
+  needs: [build]
+  rules:
+    - if: $CI_COMMIT_BRANCH == "main"
+    - when: manual
+  parallel:
+    matrix:
+      - NODE: ["18", "20"]
+  services:
+    - postgres:16.2.0
+  script:
+    - npm ci
+    - npm run build
+"#;
+        let result = audit(&[(".gitlab-ci.yml", config)]);
+        let file = &result.files[0];
+        assert_eq!(file.kind, PipelineKind::GitlabCi);
+        assert_eq!(file.jobs, 2);
+        assert_eq!(file.steps, 4);
+        assert_eq!(file.conditionals, 2);
+        assert_eq!(file.needs, 1);
+        assert_eq!(file.matrix_axes, 1);
+
+        let refs: Vec<_> = file
+            .refs
+            .iter()
+            .map(|r| (r.name.as_str(), r.pinning))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                ("node", Pinning::Floating),
+                ("gitlab.com/acme/ci/lint", Pinning::Version),
+                ("postgres", Pinning::Version),
+            ]
+        );
+        assert_eq!(result.duplicates.len(), 1);
+        assert_eq!(result.duplicates[0].step, "npm ci");
+    }
+
+    #[test]
+    fn test_makefile_recipes() {
+        let makefile = "CC := gcc\n.PHONY: all test\n\nall: build test\n\nbuild:\n\t$(CC) -o app main.c\n\t strip app\n\ntest: build\n\t./app --test\n\nifeq ($(OS),Windows_NT)\nEXT = .exe\nendif\n\nrelease:\n\t$(CC) -o app main.c\n\t strip app\n";
+        let result = audit(&[("Makefile", makefile)]);
+        let file = &result.files[0];
+        assert_eq!(file.jobs, 4);
+        assert_eq!(file.steps, 5);
+        assert_eq!(file.needs, 3);
+        assert_eq!(file.conditionals, 1);
+
+        assert_eq!(result.duplicates.len(), 1);
+        let jobs: Vec<_> = result.duplicates[0]
+            .occurrences
+            .iter()
+            .map(|o| o.job.as_str())
+            .collect();
+        assert_eq!(jobs, vec!["build", "release"]);
+    }
+
+    #[test]
+    fn test_justfile_recipes() {
+        let justfile = "set shell := [\"bash\", \"-c\"]\n\ntest filter='': build\n    cargo test {{filter}}\n\nbuild:\n    cargo build\n";
+        let file = &audit(&[("justfile", justfile)]).files[0];
+        assert_eq!(file.kind, PipelineKind::Just);
+        assert_eq!(file.jobs, 2);
+        assert_eq!(file.steps, 2);
+        assert_eq!(file.needs, 1);
+    }
+
+    #[test]
+    fn test_analyze_walks_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join(".github/workflows")).unwrap();
+        std::fs::write(root.join(".github/workflows/ci.yml"), WORKFLOW).unwrap();
+        std::fs::write(root.join("Makefile"), "all:\n\techo hi\n").unwrap();
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(root, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(root));
+        let result = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert_eq!(result.summary.total_files, 2);
+        assert_eq!(result.files[0].path, ".github/workflows/ci.yml");
+        assert_eq!(result.files[1].path, "Makefile");
+    }
+}
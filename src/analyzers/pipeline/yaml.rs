@@ -0,0 +1,383 @@
+//! Minimal block-YAML reader for CI configs.
+//!
+//! CI files use a small, regular subset of YAML: block mappings, block
+//! sequences (including `- key: value` items), `|`/`>` block scalars and
+//! short flow sequences. This reader handles exactly that subset and keeps
+//! the line number of every entry so findings can point into the file.
+//! Anchors and tags are dropped; aliases and merge keys stay plain scalars.
+
+/// A parsed YAML value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Scalar(String),
+    Seq(Vec<Entry>),
+    Map(Vec<(String, Entry)>),
+}
+
+/// A value and the 1-indexed line it starts on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub line: u32,
+    pub node: Node,
+}
+
+impl Node {
+    /// Value of `key` when this is a mapping.
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        match self {
+            Node::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, e)| e),
+            _ => None,
+        }
+    }
+
+    /// Scalar text, if this is a scalar.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Mapping entries, empty for other nodes.
+    pub fn entries(&self) -> &[(String, Entry)] {
+        match self {
+            Node::Map(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Sequence items, empty for other nodes.
+    pub fn items(&self) -> Vec<&Entry> {
+        match self {
+            Node::Seq(items) => items.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Number of values: sequence length, 1 for a non-empty scalar.
+    pub fn count(&self) -> usize {
+        match self {
+            Node::Seq(items) => items.len(),
+            Node::Map(entries) => entries.len(),
+            Node::Scalar(s) if s.is_empty() => 0,
+            Node::Scalar(_) => 1,
+        }
+    }
+}
+
+struct Line<'a> {
+    no: u32,
+    indent: usize,
+    text: &'a str,
+}
+
+/// Parse a YAML document into its top-level node.
+pub fn parse(source: &str) -> Node {
+    let lines: Vec<Line<'_>> = source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, raw)| {
+            let indent = raw.len() - raw.trim_start().len();
+            let text = strip_comment(raw.trim()).trim_end();
+            if text.is_empty() || text == "---" || text.starts_with('%') {
+                return None;
+            }
+            Some(Line {
+                no: i as u32 + 1,
+                indent,
+                text,
+            })
+        })
+        .collect();
+    let mut parser = Parser { lines, pos: 0 };
+    parser.block(0)
+}
+
+struct Parser<'a> {
+    lines: Vec<Line<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Line<'a>> {
+        self.lines.get(self.pos)
+    }
+
+    fn block(&mut self, min_indent: usize) -> Node {
+        match self.peek() {
+            Some(line) if line.indent >= min_indent => {
+                let indent = line.indent;
+                if is_seq_item(line.text) {
+                    self.seq(indent)
+                } else {
+                    self.map(indent)
+                }
+            }
+            _ => Node::Scalar(String::new()),
+        }
+    }
+
+    fn map(&mut self, indent: usize) -> Node {
+        let mut entries = Vec::new();
+        while let Some(line) = self.peek() {
+            if line.indent > indent {
+                // Stray deeper line (e.g. after an unsupported construct).
+                self.pos += 1;
+                continue;
+            }
+            if line.indent < indent || is_seq_item(line.text) {
+                break;
+            }
+            let no = line.no;
+            let Some((key, value)) = split_key(line.text) else {
+                // A plain scalar where a key was expected: keep it as the
+                // value of a one-line document, skip it inside a mapping.
+                if entries.is_empty() {
+                    let text = unquote(line.text);
+                    self.pos += 1;
+                    return Node::Scalar(text);
+                }
+                self.pos += 1;
+                continue;
+            };
+            let key = unquote(key);
+            self.pos += 1;
+            let node = self.value(indent, value);
+            entries.push((key, Entry { line: no, node }));
+        }
+        Node::Map(entries)
+    }
+
+    fn seq(&mut self, indent: usize) -> Node {
+        let mut items = Vec::new();
+        while let Some(line) = self.peek() {
+            if line.indent > indent {
+                self.pos += 1;
+                continue;
+            }
+            if line.indent < indent || !is_seq_item(line.text) {
+                break;
+            }
+            let no = line.no;
+            let rest = line.text[1..].trim_start();
+            let node = if rest.is_empty() {
+                self.pos += 1;
+                self.nested(indent)
+            } else if split_key(rest).is_some() || is_seq_item(rest) {
+                // `- key: value` opens a mapping at the column of `key`.
+                let offset = line.text.len() - rest.len();
+                let current = &mut self.lines[self.pos];
+                current.indent = indent + offset;
+                current.text = rest;
+                self.block(indent + offset)
+            } else {
+                self.pos += 1;
+                self.inline(indent, rest)
+            };
+            items.push(Entry { line: no, node });
+        }
+        Node::Seq(items)
+    }
+
+    /// Value following `key:` on a line at `indent`.
+    fn value(&mut self, indent: usize, value: &'a str) -> Node {
+        let value = strip_properties(value);
+        if value.is_empty() {
+            return match self.peek() {
+                // YAML allows a sequence at the same indent as its key.
+                Some(next) if next.indent == indent && is_seq_item(next.text) => self.seq(indent),
+                _ => self.nested(indent),
+            };
+        }
+        if value.starts_with('|') || value.starts_with('>') {
+            let mut text = Vec::new();
+            while let Some(next) = self.peek() {
+                if next.indent <= indent {
+                    break;
+                }
+                text.push(next.text);
+                self.pos += 1;
+            }
+            return Node::Scalar(text.join("\n"));
+        }
+        self.inline(indent, value)
+    }
+
+    fn nested(&mut self, indent: usize) -> Node {
+        match self.peek() {
+            Some(next) if next.indent > indent => self.block(next.indent),
+            _ => Node::Scalar(String::new()),
+        }
+    }
+
+    /// An inline value, with flow sequences and multi-line plain scalars.
+    fn inline(&mut self, indent: usize, value: &'a str) -> Node {
+        if value.starts_with('[') {
+            let mut text = value.to_string();
+            while !text.contains(']') {
+                let Some(next) = self.peek() else { break };
+                text.push(' ');
+                text.push_str(next.text);
+                self.pos += 1;
+            }
+            let inner = text.trim_start_matches('[');
+            let inner = inner.split(']').next().unwrap_or("");
+            let line = self
+                .lines
+                .get(self.pos.saturating_sub(1))
+                .map_or(0, |l| l.no);
+            let items = inner
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Entry {
+                    line,
+                    node: Node::Scalar(unquote(s)),
+                })
+                .collect();
+            return Node::Seq(items);
+        }
+        let mut text = unquote(value);
+        while let Some(next) = self.peek() {
+            if next.indent <= indent || split_key(next.text).is_some() || is_seq_item(next.text) {
+                break;
+            }
+            text.push(' ');
+            text.push_str(next.text);
+            self.pos += 1;
+        }
+        Node::Scalar(text)
+    }
+}
+
+fn is_seq_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Split `key: value` at the first unquoted `:` followed by a space or the
+/// end of the line.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with('[') || text.starts_with('{') {
+        return None;
+    }
+    let bytes = text.as_bytes();
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        match (quote, b) {
+            (None, b'"' | b'\'') if opens_quote(bytes, i) => quote = Some(b),
+            (Some(q), _) if b == q => quote = None,
+            (None, b':') if i + 1 == bytes.len() || bytes[i + 1] == b' ' => {
+                return Some((text[..i].trim(), text[i + 1..].trim()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop a trailing ` # comment` that isn't inside quotes.
+fn strip_comment(text: &str) -> &str {
+    if text.starts_with('#') {
+        return "";
+    }
+    let mut quote = None;
+    let bytes = text.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match (quote, b) {
+            (None, b'"' | b'\'') if opens_quote(bytes, i) => quote = Some(b),
+            (Some(q), _) if b == q => quote = None,
+            (None, b'#') if i > 0 && bytes[i - 1] == b' ' => return &text[..i],
+            _ => {}
+        }
+    }
+    text
+}
+
+/// A quote only opens a quoted span at the start of a token, so the
+/// apostrophe in `don't` is plain text.
+fn opens_quote(bytes: &[u8], i: usize) -> bool {
+    i == 0 || matches!(bytes[i - 1], b' ' | b'[' | b',')
+}
+
+/// Drop leading `&anchor` and `!tag` properties from a value.
+fn strip_properties(value: &str) -> &str {
+    let mut value = value;
+    while value.starts_with('&') || value.starts_with('!') {
+        value = value
+            .split_once(' ')
+            .map_or("", |(_, rest)| rest.trim_start());
+    }
+    value
+}
+
+fn unquote(text: &str) -> String {
+    let text = text.trim();
+    for q in ['"', '\''] {
+        if text.len() >= 2 && text.starts_with(q) && text.ends_with(q) {
+            return text[1..text.len() - 1].to_string();
+        }
+    }
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workflow_shape() {
+        let doc = parse(
+            "name: CI\non:\n  push:\n    branches: [main, 'release/*']\njobs:\n  build:\n    runs-on: ubuntu-latest # comment\n    steps:\n      - uses: actions/checkout@v4\n      - name: Test\n        run: |\n          cargo build\n          cargo test\n",
+        );
+        let on = &doc.get("on").unwrap().node;
+        let branches = &on.get("push").unwrap().node.get("branches").unwrap().node;
+        assert_eq!(branches.count(), 2);
+
+        let build = &doc.get("jobs").unwrap().node.get("build").unwrap();
+        assert_eq!(build.line, 6);
+        assert_eq!(
+            build.node.get("runs-on").unwrap().node.as_str(),
+            Some("ubuntu-latest")
+        );
+        let steps = build.node.get("steps").unwrap().node.items();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].line, 10);
+        assert_eq!(
+            steps[1].node.get("run").unwrap().node.as_str(),
+            Some("cargo build\ncargo test")
+        );
+    }
+
+    #[test]
+    fn test_parse_sequence_at_key_indent_and_scalars() {
+        let doc = parse("test:\n  script:\n  - echo \"a: b\"\n  - make test\n  only: [main]\n");
+        let test = &doc.get("test").unwrap().node;
+        let script: Vec<_> = test
+            .get("script")
+            .unwrap()
+            .node
+            .items()
+            .iter()
+            .filter_map(|e| e.node.as_str())
+            .collect();
+        assert_eq!(script, vec!["echo \"a: b\"", "make test"]);
+        assert_eq!(test.get("only").unwrap().node.count(), 1);
+    }
+
+    #[test]
+    fn test_parse_anchors_keep_structure() {
+        let doc = parse(
+            ".base: &base
+  image: node:20
+lint:
+  <<: *base
+  script: npm run lint
+",
+        );
+        let base = &doc.get(".base").unwrap().node;
+        assert_eq!(base.get("image").unwrap().node.as_str(), Some("node:20"));
+        let lint = &doc.get("lint").unwrap().node;
+        assert_eq!(lint.get("<<").unwrap().node.as_str(), Some("*base"));
+        assert_eq!(lint.get("script").unwrap().node.count(), 1);
+    }
+}
@@ -0,0 +1,296 @@
+//! Finding prioritization by file risk.
+//!
+//! Flags, SATD, and smell findings can be re-ranked by the risk of the files
+//! they touch instead of only their own severity:
+//! - `hotspot`: churn x complexity score from the hotspot analyzer
+//! - `ownership`: ownership concentration, so knowledge silos come first
+//!
+//! Each finding is annotated with the factors behind its rank so the order of
+//! the queue can be explained.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::{hotspot, ownership};
+use crate::config::PrioritizeBy;
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result};
+
+/// Why a finding was ranked where it was.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prioritization {
+    /// Strategy that produced the rank.
+    pub by: PrioritizeBy,
+    /// Ranking score (0-1, higher ranks first).
+    pub score: f64,
+    /// Riskiest file the finding touches, if any file was scored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Human-readable factors, e.g. `hotspot: critical (0.86)`.
+    pub factors: Vec<String>,
+}
+
+/// A finding that can be re-ranked by file risk.
+pub trait Prioritized {
+    /// Files the finding touches.
+    fn files(&self) -> Vec<&str>;
+
+    /// The attached annotation, if any.
+    fn prioritization(&self) -> Option<&Prioritization>;
+
+    /// Attach the ranking annotation.
+    fn set_prioritization(&mut self, prioritization: Prioritization);
+}
+
+/// Per-file risk scores used to rank findings.
+#[derive(Debug, Clone)]
+pub struct FileRisk {
+    by: PrioritizeBy,
+    files: HashMap<String, (f64, Vec<String>)>,
+}
+
+impl FileRisk {
+    /// Compute file risk for `by` over the context's files.
+    ///
+    /// Returns `None` for [`PrioritizeBy::None`]. Both strategies need git
+    /// history and fail without it.
+    pub fn compute(by: PrioritizeBy, ctx: &AnalysisContext<'_>) -> Result<Option<Self>> {
+        match by {
+            PrioritizeBy::None => Ok(None),
+            PrioritizeBy::Hotspot => {
+                let analysis = hotspot::Analyzer::default().analyze(ctx)?;
+                Ok(Some(Self::from_hotspots(&analysis)))
+            }
+            PrioritizeBy::Ownership => {
+                let analysis = ownership::Analyzer::default().analyze(ctx)?;
+                Ok(Some(Self::from_ownership(&analysis)))
+            }
+        }
+    }
+
+    /// File risk from hotspot scores.
+    pub fn from_hotspots(analysis: &hotspot::Analysis) -> Self {
+        let files = analysis
+            .hotspots
+            .iter()
+            .map(|h| {
+                let factors = vec![
+                    format!("hotspot: {} ({:.2})", h.severity, h.score),
+                    format!(
+                        "{} commits, avg complexity {:.1}",
+                        h.commits, h.avg_complexity
+                    ),
+                ];
+                (normalize_path(&h.file), (h.score, factors))
+            })
+            .collect();
+        Self {
+            by: PrioritizeBy::Hotspot,
+            files,
+        }
+    }
+
+    /// File risk from ownership concentration.
+    pub fn from_ownership(analysis: &ownership::Analysis) -> Self {
+        let files = analysis
+            .files
+            .iter()
+            .map(|f| {
+                let mut factors = vec![format!(
+                    "ownership: {} risk, {} owns {:.0}%",
+                    f.risk_level, f.primary_owner, f.ownership_percent
+                )];
+                if f.is_silo {
+                    factors.push("single contributor (knowledge silo)".to_string());
+                }
+                (normalize_path(&f.path), (f.concentration, factors))
+            })
+            .collect();
+        Self {
+            by: PrioritizeBy::Ownership,
+            files,
+        }
+    }
+
+    /// Score and factors for `file`, relative to the analysis root.
+    pub fn get(&self, file: &str) -> Option<(f64, &[String])> {
+        self.files
+            .get(&normalize_path(file))
+            .map(|(score, factors)| (*score, factors.as_slice()))
+    }
+
+    /// Annotate `items` and stably re-rank them by the riskiest file each
+    /// touches. Findings touching no scored file get score 0 and keep their
+    /// original relative order.
+    pub fn apply<T: Prioritized>(&self, root: &Path, items: &mut [T]) {
+        for item in items.iter_mut() {
+            let best = item
+                .files()
+                .into_iter()
+                .map(|file| relative_to(root, file))
+                .filter_map(|file| {
+                    self.get(&file)
+                        .map(|(score, factors)| (file, score, factors))
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            let prioritization = match best {
+                Some((file, score, factors)) => Prioritization {
+                    by: self.by,
+                    score,
+                    file: Some(file),
+                    factors: factors.to_vec(),
+                },
+                None => Prioritization {
+                    by: self.by,
+                    score: 0.0,
+                    file: None,
+                    factors: Vec::new(),
+                },
+            };
+            item.set_prioritization(prioritization);
+        }
+        items.sort_by(|a, b| score_of(b).total_cmp(&score_of(a)));
+    }
+}
+
+/// Re-rank `items` by `ctx.config.prioritize_by`; a no-op when it is `none`.
+pub fn prioritize<T: Prioritized>(ctx: &AnalysisContext<'_>, items: &mut [T]) -> Result<()> {
+    if let Some(risk) = FileRisk::compute(ctx.config.prioritize_by, ctx)? {
+        risk.apply(ctx.root, items);
+    }
+    Ok(())
+}
+
+fn score_of<T: Prioritized>(item: &T) -> f64 {
+    item.prioritization().map_or(0.0, |p| p.score)
+}
+
+fn relative_to(root: &Path, file: &str) -> String {
+    let path = Path::new(file);
+    normalize_path(&path.strip_prefix(root).unwrap_or(path).to_string_lossy())
+}
+
+fn normalize_path(file: &str) -> String {
+    let file = file.replace('\\', "/");
+    file.trim_start_matches("./").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Finding {
+        name: &'static str,
+        files: Vec<&'static str>,
+        prioritization: Option<Prioritization>,
+    }
+
+    impl Prioritized for Finding {
+        fn files(&self) -> Vec<&str> {
+            self.files.clone()
+        }
+
+        fn prioritization(&self) -> Option<&Prioritization> {
+            self.prioritization.as_ref()
+        }
+
+        fn set_prioritization(&mut self, prioritization: Prioritization) {
+            self.prioritization = Some(prioritization);
+        }
+    }
+
+    fn finding(name: &'static str, files: Vec<&'static str>) -> Finding {
+        Finding {
+            name,
+            files,
+            prioritization: None,
+        }
+    }
+
+    fn make_hotspot(file: &str, score: f64) -> hotspot::Hotspot {
+        hotspot::Hotspot {
+            file: file.to_string(),
+            score,
+            severity: hotspot::Severity::High,
+            churn_percentile: 90.0,
+            complexity_percentile: 80.0,
+            commits: 12,
+            avg_complexity: 7.5,
+            off_hours_ratio: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_ranks_by_riskiest_file() {
+        let risk = FileRisk::from_hotspots(&hotspot::Analysis {
+            hotspots: vec![
+                make_hotspot("src/hot.rs", 0.9),
+                make_hotspot("src/warm.rs", 0.4),
+            ],
+            forecast: vec![],
+            summary: hotspot::AnalysisSummary {
+                total_hotspots: 2,
+                critical_count: 0,
+                high_count: 2,
+                ..Default::default()
+            },
+            parameters: Default::default(),
+        });
+        let mut items = vec![
+            finding("cold", vec!["src/cold.rs"]),
+            finding("warm", vec!["./src/warm.rs"]),
+            finding("both", vec!["src/warm.rs", "/repo/src/hot.rs"]),
+            finding("also-cold", vec!["src/other.rs"]),
+        ];
+
+        risk.apply(Path::new("/repo"), &mut items);
+
+        let order: Vec<&str> = items.iter().map(|i| i.name).collect();
+        assert_eq!(order, vec!["both", "warm", "cold", "also-cold"]);
+        let top = items[0].prioritization.as_ref().unwrap();
+        assert_eq!(top.by, PrioritizeBy::Hotspot);
+        assert_eq!(top.file.as_deref(), Some("src/hot.rs"));
+        assert!((top.score - 0.9).abs() < f64::EPSILON);
+        assert!(top.factors[0].starts_with("hotspot: high"));
+        let cold = items[2].prioritization.as_ref().unwrap();
+        assert_eq!(cold.score, 0.0);
+        assert!(cold.file.is_none());
+    }
+
+    #[test]
+    fn test_from_ownership_flags_silos() {
+        let analysis = ownership::Analysis {
+            generated_at: String::new(),
+            files: vec![ownership::FileOwnership {
+                path: "lib.rs".to_string(),
+                primary_owner: "alice".to_string(),
+                ownership_percent: 100.0,
+                concentration: 1.0,
+                total_lines: 40,
+                contributors: Vec::new(),
+                is_silo: true,
+                risk_level: ownership::RiskLevel::High,
+            }],
+            summary: Default::default(),
+        };
+        let risk = FileRisk::from_ownership(&analysis);
+        let (score, factors) = risk.get("lib.rs").unwrap();
+        assert_eq!(score, 1.0);
+        assert_eq!(factors[0], "ownership: high risk, alice owns 100%");
+        assert!(factors[1].contains("silo"));
+    }
+
+    #[test]
+    fn test_prioritize_none_is_noop() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+        let mut items = vec![finding("a", vec!["a.rs"])];
+
+        prioritize(&ctx, &mut items).unwrap();
+
+        assert!(items[0].prioritization.is_none());
+    }
+}
@@ -15,7 +15,8 @@ use petgraph::Direction;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
+use crate::analyzers::graph::rust_modules::{self, RustFile, RustModules};
+use crate::core::{is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
 use crate::parser::{extract_functions, Parser};
 
 /// Repomap analyzer configuration.
@@ -51,6 +52,7 @@ pub struct SymbolInfo {
     pub qualified_name: String,
     pub kind: SymbolKind,
     pub file: String,
+    pub language: Language,
     pub line: u32,
     pub end_line: u32,
     pub signature: String,
@@ -175,7 +177,7 @@ impl CallGraphIndex {
 /// This contains the parse/symbol-collection/graph-build phases (no PageRank).
 pub fn build_index(repo_path: &Path, files: &[PathBuf]) -> Result<CallGraphIndex> {
     // Phase 1: Parallel parsing - extract symbols from all files
-    let file_symbols: Vec<Vec<SymbolInfo>> = files
+    let parsed: Vec<(Vec<SymbolInfo>, Option<(String, RustFile)>)> = files
         .par_iter()
         .filter_map(|path| {
             let lang = Language::detect(path)?;
@@ -210,6 +212,7 @@ pub fn build_index(repo_path: &Path, files: &[PathBuf]) -> Result<CallGraphIndex
                         qualified_name,
                         kind: SymbolKind::Function,
                         file: rel_path.clone(),
+                        language: lang,
                         line: func.start_line,
                         end_line: func.end_line,
                         signature: func.signature.clone(),
@@ -219,13 +222,34 @@ pub fn build_index(repo_path: &Path, files: &[PathBuf]) -> Result<CallGraphIndex
                 })
                 .collect();
 
-            Some(symbols)
+            let rust = (lang == Language::Rust)
+                .then(|| (rel_path.clone(), rust_modules::parse(&parse_result)));
+            Some((symbols, rust))
         })
         .collect();
 
     // Flatten
+    let (file_symbols, rust_files): (Vec<_>, Vec<_>) = parsed.into_iter().unzip();
     let symbols: Vec<SymbolInfo> = file_symbols.into_iter().flatten().collect();
 
+    // Rust `use` declarations name the file each imported function comes from
+    let rust_files: Vec<(String, RustFile)> = rust_files.into_iter().flatten().collect();
+    let rust_paths: Vec<String> = rust_files.iter().map(|(path, _)| path.clone()).collect();
+    let modules = RustModules::new(repo_path, rust_files);
+    let imports: HashMap<String, HashMap<String, String>> = rust_paths
+        .into_iter()
+        .map(|path| {
+            let names = modules
+                .bindings(&path)
+                .into_iter()
+                .filter_map(|(local, resolved)| {
+                    Some((local, format!("{}:{}", resolved.file, resolved.symbol?)))
+                })
+                .collect();
+            (path, names)
+        })
+        .collect();
+
     // Build lookup indices
     let mut by_qualified: HashMap<String, usize> = HashMap::with_capacity(symbols.len());
     let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
@@ -269,12 +293,26 @@ pub fn build_index(repo_path: &Path, files: &[PathBuf]) -> Result<CallGraphIndex
                 continue;
             }
 
-            // 3. Use name index for O(1) lookup (already sorted for determinism)
-            if let Some(indices) = by_name.get(call) {
-                if let Some(&callee_idx) = indices.first() {
-                    let callee_node = node_indices[&callee_idx];
-                    graph.add_edge(caller_node, callee_node, ());
-                }
+            // 3. Follow the file's Rust `use` declarations
+            if let Some(&callee_idx) = imports
+                .get(&symbol.file)
+                .and_then(|names| names.get(call))
+                .and_then(|qualified| by_qualified.get(qualified))
+            {
+                let callee_node = node_indices[&callee_idx];
+                graph.add_edge(caller_node, callee_node, ());
+                continue;
+            }
+
+            // 4. Use name index for O(1) lookup (already sorted for determinism),
+            // staying within the caller's language family
+            if let Some(&callee_idx) = by_name.get(call).and_then(|indices| {
+                indices
+                    .iter()
+                    .find(|&&i| symbols[i].language.family() == symbol.language.family())
+            }) {
+                let callee_node = node_indices[&callee_idx];
+                graph.add_edge(caller_node, callee_node, ());
             }
         }
     }
@@ -353,7 +391,7 @@ impl Analyzer {
                 if self.config.skip_test_files && is_test_file(path) {
                     return false;
                 }
-                Language::detect(path).is_some()
+                file_set.language(path).is_some()
             })
             .map(|p| repo_path.join(p))
             .collect();
@@ -421,53 +459,68 @@ impl Analyzer {
     }
 
     /// Calculate PageRank for all nodes in the graph.
-    ///
-    /// Note: Dangling nodes (no outgoing edges) are effectively treated as
-    /// having self-loops rather than redistributing rank uniformly.
     fn calculate_pagerank(&self, graph: &DiGraph<usize, ()>) -> HashMap<NodeIndex, f64> {
-        let n = graph.node_count();
-        if n == 0 {
-            return HashMap::new();
-        }
+        pagerank(
+            graph,
+            self.config.damping,
+            self.config.max_iterations,
+            self.config.tolerance,
+        )
+    }
+}
 
-        let d = self.config.damping;
-        let mut rank: HashMap<NodeIndex, f64> = graph
-            .node_indices()
-            .map(|idx| (idx, 1.0 / n as f64))
-            .collect();
+/// PageRank of every node in a call graph, by power iteration.
+///
+/// Note: Dangling nodes (no outgoing edges) are effectively treated as
+/// having self-loops rather than redistributing rank uniformly.
+pub(crate) fn pagerank(
+    graph: &DiGraph<usize, ()>,
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> HashMap<NodeIndex, f64> {
+    let n = graph.node_count();
+    if n == 0 {
+        return HashMap::new();
+    }
 
-        for _ in 0..self.config.max_iterations {
-            let mut new_rank: HashMap<NodeIndex, f64> = HashMap::new();
-            let mut diff = 0.0;
+    let d = damping;
+    let mut rank: HashMap<NodeIndex, f64> = graph
+        .node_indices()
+        .map(|idx| (idx, 1.0 / n as f64))
+        .collect();
 
-            for node in graph.node_indices() {
-                let incoming: f64 = graph
-                    .edges_directed(node, Direction::Incoming)
-                    .map(|e| {
-                        let source = e.source();
-                        let out_deg = graph.edges_directed(source, Direction::Outgoing).count();
-                        if out_deg > 0 {
-                            rank[&source] / out_deg as f64
-                        } else {
-                            0.0
-                        }
-                    })
-                    .sum();
+    for _ in 0..max_iterations {
+        let mut new_rank: HashMap<NodeIndex, f64> = HashMap::new();
+        let mut diff = 0.0;
+
+        for node in graph.node_indices() {
+            let incoming: f64 = graph
+                .edges_directed(node, Direction::Incoming)
+                .map(|e| {
+                    let source = e.source();
+                    let out_deg = graph.edges_directed(source, Direction::Outgoing).count();
+                    if out_deg > 0 {
+                        rank[&source] / out_deg as f64
+                    } else {
+                        0.0
+                    }
+                })
+                .sum();
 
-                let new_score = (1.0 - d) / n as f64 + d * incoming;
-                diff += (new_score - rank[&node]).abs();
-                new_rank.insert(node, new_score);
-            }
+            let new_score = (1.0 - d) / n as f64 + d * incoming;
+            diff += (new_score - rank[&node]).abs();
+            new_rank.insert(node, new_score);
+        }
 
-            rank = new_rank;
+        rank = new_rank;
 
-            if diff < self.config.tolerance {
-                break;
-            }
+        if diff < tolerance {
+            break;
         }
-
-        rank
     }
+
+    rank
 }
 
 impl AnalyzerTrait for Analyzer {
@@ -621,6 +674,8 @@ fn get_call_node_kinds(lang: Language) -> Vec<&'static str> {
         Language::Ruby => vec!["call", "method_call"],
         Language::Php => vec!["function_call_expression", "method_call_expression"],
         Language::Bash => vec!["command"],
+        Language::Lua => vec!["function_call"],
+        Language::Zig => vec!["call_expression"],
     }
 }
 
@@ -694,24 +749,6 @@ fn extract_call_name(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Stri
     None
 }
 
-fn is_test_file(path: &Path) -> bool {
-    // Normalise to forward slashes so Windows paths (using `\`) are handled.
-    let path_str = path.to_string_lossy().replace('\\', "/");
-    path_str.contains("/test")
-        || path_str.contains("/tests/")
-        || path_str.contains("_test.")
-        || path_str.contains("test_")
-        || path_str.ends_with("_test.go")
-        || path_str.ends_with(".test.ts")
-        || path_str.ends_with(".spec.ts")
-        || path_str.ends_with(".test.js")
-        || path_str.ends_with(".spec.js")
-        || path_str.ends_with(".test.tsx")
-        || path_str.ends_with(".spec.tsx")
-        || path_str.ends_with(".test.jsx")
-        || path_str.ends_with(".spec.jsx")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -834,6 +871,56 @@ fn bar() {}
         assert!(names.contains(&"bar"));
     }
 
+    #[test]
+    fn test_build_index_follows_rust_use_paths() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "mod a;\nmod b;\nmod c;\n").unwrap();
+        fs::write(dir.path().join("src/a.rs"), "pub fn helper() {}\n").unwrap();
+        fs::write(dir.path().join("src/b.rs"), "pub fn helper() {}\n").unwrap();
+        fs::write(
+            dir.path().join("src/c.rs"),
+            "use crate::b::helper;\nfn run() { helper(); }\n",
+        )
+        .unwrap();
+
+        let files: Vec<PathBuf> = ["src/lib.rs", "src/a.rs", "src/b.rs", "src/c.rs"]
+            .iter()
+            .map(|f| dir.path().join(f))
+            .collect();
+        let index = build_index(dir.path(), &files).unwrap();
+
+        let run = index.resolve("src/c.rs:run")[0];
+        let callees: Vec<&str> = index
+            .graph
+            .neighbors(index.node_indices[&run])
+            .map(|n| index.symbols[index.graph[n]].qualified_name.as_str())
+            .collect();
+        assert_eq!(callees, vec!["src/b.rs:helper"]);
+    }
+
+    #[test]
+    fn test_build_index_stays_within_language_family() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.py"), "def helper():\n    pass\n").unwrap();
+        fs::write(dir.path().join("b.ts"), "export function helper() {}\n").unwrap();
+        fs::write(dir.path().join("c.js"), "function run() { helper(); }\n").unwrap();
+
+        let files: Vec<PathBuf> = ["a.py", "b.ts", "c.js"]
+            .iter()
+            .map(|f| dir.path().join(f))
+            .collect();
+        let index = build_index(dir.path(), &files).unwrap();
+
+        let run = index.resolve("c.js:run")[0];
+        let callees: Vec<&str> = index
+            .graph
+            .neighbors(index.node_indices[&run])
+            .map(|n| index.symbols[index.graph[n]].qualified_name.as_str())
+            .collect();
+        assert_eq!(callees, vec!["b.ts:helper"]);
+    }
+
     #[test]
     fn test_resolve_exact_qualified() {
         let dir = create_rust_fixture();
@@ -0,0 +1,385 @@
+//! Risk matrix: defect probability crossed with business criticality.
+//!
+//! Defect prediction ranks files by how likely they are to contain bugs, but
+//! a likely bug in a docs generator matters less than one in payments code.
+//! Paths are tagged with a criticality in `[criticality]` config and each file
+//! lands in a cell of a criticality x defect-risk matrix:
+//!
+//! | criticality \ defect | high   | medium | low    |
+//! |----------------------|--------|--------|--------|
+//! | critical             | urgent | urgent | high   |
+//! | high                 | urgent | high   | medium |
+//! | normal               | high   | medium | low    |
+//! | low                  | medium | low    | low    |
+//!
+//! Within the matrix, files are ranked by `probability x impact`, where impact
+//! is 1.0, 0.75, 0.5, and 0.25 for critical, high, normal, and low.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher};
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::defect::{self, RiskLevel};
+use crate::config::Criticality;
+use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result, SeverityCounts};
+
+/// Risk matrix analyzer.
+#[derive(Default)]
+pub struct Analyzer;
+
+impl AnalyzerTrait for Analyzer {
+    type Output = Analysis;
+
+    fn name(&self) -> &'static str {
+        "risk"
+    }
+
+    fn description(&self) -> &'static str {
+        "Cross defect probability with business criticality"
+    }
+
+    fn requires_git(&self) -> bool {
+        true
+    }
+
+    fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        let defects = defect::Analyzer::default().analyze(ctx)?;
+        Analysis::from_defect(&defects, &ctx.config.criticality)
+    }
+}
+
+/// Criticality globs from config.
+pub struct CriticalityMap {
+    patterns: Vec<(String, GlobMatcher, Criticality)>,
+}
+
+impl CriticalityMap {
+    /// Compile the `[criticality]` globs.
+    pub fn new(config: &HashMap<String, Criticality>) -> Result<Self> {
+        let mut patterns = config
+            .iter()
+            .map(|(pattern, level)| {
+                let glob = Glob::new(pattern).map_err(|e| {
+                    Error::config(format!("invalid criticality glob {pattern:?}: {e}"))
+                })?;
+                Ok((pattern.clone(), glob.compile_matcher(), *level))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Deterministic tie-breaking between equally critical patterns.
+        patterns.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Self { patterns })
+    }
+
+    /// Highest criticality among matching globs, with the glob that set it.
+    /// Untagged paths are [`Criticality::Normal`].
+    pub fn get(&self, path: &str) -> (Criticality, Option<&str>) {
+        let path = path.replace('\\', "/");
+        let path = path.trim_start_matches("./");
+        self.patterns
+            .iter()
+            .filter(|(_, matcher, _)| matcher.is_match(path))
+            .max_by_key(|(_, _, level)| *level)
+            .map_or((Criticality::Normal, None), |(pattern, _, level)| {
+                (*level, Some(pattern.as_str()))
+            })
+    }
+}
+
+/// Action priority of a matrix cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl From<Priority> for crate::core::Severity {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Urgent => Self::Critical,
+            Priority::High => Self::High,
+            Priority::Medium => Self::Medium,
+            Priority::Low => Self::Low,
+        }
+    }
+}
+
+impl Priority {
+    /// Priority of the cell at `criticality` x `defect_risk`.
+    pub fn of(criticality: Criticality, defect_risk: RiskLevel) -> Self {
+        let rank = criticality_rank(criticality) + defect_rank(defect_risk);
+        match rank {
+            4.. => Self::Urgent,
+            3 => Self::High,
+            2 => Self::Medium,
+            _ => Self::Low,
+        }
+    }
+}
+
+fn criticality_rank(criticality: Criticality) -> u8 {
+    match criticality {
+        Criticality::Low => 0,
+        Criticality::Normal => 1,
+        Criticality::High => 2,
+        Criticality::Critical => 3,
+    }
+}
+
+fn defect_rank(risk: RiskLevel) -> u8 {
+    match risk {
+        RiskLevel::Low => 0,
+        RiskLevel::Medium => 1,
+        RiskLevel::High => 2,
+    }
+}
+
+fn impact(criticality: Criticality) -> f64 {
+    match criticality {
+        Criticality::Low => 0.25,
+        Criticality::Normal => 0.5,
+        Criticality::High => 0.75,
+        Criticality::Critical => 1.0,
+    }
+}
+
+/// Risk matrix result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    /// Files ranked by priority, then `risk_score`, highest first.
+    pub files: Vec<RiskEntry>,
+    /// All 12 cells, criticality descending then defect risk descending.
+    pub matrix: Vec<MatrixCell>,
+    pub summary: Summary,
+}
+
+/// A file placed in the matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskEntry {
+    pub file_path: String,
+    pub probability: f32,
+    pub defect_risk: RiskLevel,
+    pub criticality: Criticality,
+    /// Glob that set the criticality, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_pattern: Option<String>,
+    /// `probability x impact` (0-1).
+    pub risk_score: f64,
+    pub priority: Priority,
+}
+
+/// One cell of the criticality x defect-risk matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixCell {
+    pub criticality: Criticality,
+    pub defect_risk: RiskLevel,
+    pub priority: Priority,
+    pub count: usize,
+}
+
+/// Summary statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub total_files: usize,
+    /// Files matched by a `[criticality]` glob.
+    pub tagged_files: usize,
+    pub urgent_count: usize,
+    pub high_count: usize,
+    pub medium_count: usize,
+    pub low_count: usize,
+    pub by_criticality: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
+}
+
+impl Analysis {
+    /// Build the matrix from defect predictions and `[criticality]` config.
+    pub fn from_defect(
+        defects: &defect::Analysis,
+        criticality: &HashMap<String, Criticality>,
+    ) -> Result<Self> {
+        let map = CriticalityMap::new(criticality)?;
+
+        let mut files: Vec<RiskEntry> = defects
+            .files
+            .iter()
+            .map(|file| {
+                let (level, pattern) = map.get(&file.file_path);
+                RiskEntry {
+                    file_path: file.file_path.clone(),
+                    probability: file.probability,
+                    defect_risk: file.risk_level,
+                    criticality: level,
+                    matched_pattern: pattern.map(str::to_string),
+                    risk_score: f64::from(file.probability) * impact(level),
+                    priority: Priority::of(level, file.risk_level),
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| b.risk_score.total_cmp(&a.risk_score))
+        });
+
+        let mut matrix = Vec::with_capacity(12);
+        for level in [
+            Criticality::Critical,
+            Criticality::High,
+            Criticality::Normal,
+            Criticality::Low,
+        ] {
+            for risk in [RiskLevel::High, RiskLevel::Medium, RiskLevel::Low] {
+                matrix.push(MatrixCell {
+                    criticality: level,
+                    defect_risk: risk,
+                    priority: Priority::of(level, risk),
+                    count: files
+                        .iter()
+                        .filter(|f| f.criticality == level && f.defect_risk == risk)
+                        .count(),
+                });
+            }
+        }
+
+        let mut summary = Summary {
+            total_files: files.len(),
+            ..Default::default()
+        };
+        for file in &files {
+            if file.matched_pattern.is_some() {
+                summary.tagged_files += 1;
+            }
+            summary.severity_counts.record(file.priority.into());
+            match file.priority {
+                Priority::Urgent => summary.urgent_count += 1,
+                Priority::High => summary.high_count += 1,
+                Priority::Medium => summary.medium_count += 1,
+                Priority::Low => summary.low_count += 1,
+            }
+            *summary
+                .by_criticality
+                .entry(file.criticality.to_string())
+                .or_default() += 1;
+        }
+
+        Ok(Self {
+            files,
+            matrix,
+            summary,
+        })
+    }
+}
+
+/// Build the matrix from a pre-generated `defect.json` (avoids re-running
+/// defect prediction). Used by `report generate`.
+pub fn compute_from_data_dir(
+    data_dir: &Path,
+    criticality: &HashMap<String, Criticality>,
+) -> Result<Analysis> {
+    let content = std::fs::read_to_string(data_dir.join("defect.json"))?;
+    let defects: defect::Analysis = serde_json::from_str(&content)?;
+    Analysis::from_defect(&defects, criticality)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(path: &str, probability: f32, risk_level: RiskLevel) -> defect::FileScore {
+        defect::FileScore {
+            file_path: path.to_string(),
+            probability,
+            confidence: 1.0,
+            risk_level,
+            contributing_factors: HashMap::new(),
+            recommendations: Vec::new(),
+        }
+    }
+
+    fn defects(files: Vec<defect::FileScore>) -> defect::Analysis {
+        defect::Analysis {
+            files,
+            summary: defect::Summary::default(),
+            weights: defect::Weights::default(),
+        }
+    }
+
+    #[test]
+    fn test_criticality_map_highest_match_wins() {
+        let config = HashMap::from([
+            ("src/**".to_string(), Criticality::High),
+            ("src/payments/**".to_string(), Criticality::Critical),
+            ("docs/**".to_string(), Criticality::Low),
+        ]);
+        let map = CriticalityMap::new(&config).unwrap();
+
+        assert_eq!(
+            map.get("src/payments/charge.rs"),
+            (Criticality::Critical, Some("src/payments/**"))
+        );
+        assert_eq!(map.get("./src/lib.rs"), (Criticality::High, Some("src/**")));
+        assert_eq!(map.get("docs/gen.py").0, Criticality::Low);
+        assert_eq!(map.get("scripts/x.sh"), (Criticality::Normal, None));
+    }
+
+    #[test]
+    fn test_criticality_map_rejects_invalid_glob() {
+        let config = HashMap::from([("src/[".to_string(), Criticality::High)]);
+        assert!(CriticalityMap::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_priority_of_cells() {
+        use Criticality::*;
+        assert_eq!(Priority::of(Critical, RiskLevel::Medium), Priority::Urgent);
+        assert_eq!(Priority::of(High, RiskLevel::High), Priority::Urgent);
+        assert_eq!(Priority::of(Normal, RiskLevel::High), Priority::High);
+        assert_eq!(Priority::of(Low, RiskLevel::High), Priority::Medium);
+        assert_eq!(Priority::of(Normal, RiskLevel::Low), Priority::Low);
+    }
+
+    #[test]
+    fn test_from_defect_ranks_by_business_impact() {
+        let config = HashMap::from([
+            ("payments/**".to_string(), Criticality::Critical),
+            ("tools/**".to_string(), Criticality::Low),
+        ]);
+        let analysis = Analysis::from_defect(
+            &defects(vec![
+                score("tools/gen.rs", 0.9, RiskLevel::High),
+                score("payments/charge.rs", 0.5, RiskLevel::Medium),
+                score("src/util.rs", 0.8, RiskLevel::High),
+            ]),
+            &config,
+        )
+        .unwrap();
+
+        let order: Vec<&str> = analysis
+            .files
+            .iter()
+            .map(|f| f.file_path.as_str())
+            .collect();
+        assert_eq!(
+            order,
+            vec!["payments/charge.rs", "src/util.rs", "tools/gen.rs"]
+        );
+        assert_eq!(analysis.files[0].priority, Priority::Urgent);
+        assert!((analysis.files[2].risk_score - 0.225).abs() < 1e-6);
+
+        assert_eq!(analysis.matrix.len(), 12);
+        let cell = &analysis.matrix[1];
+        assert_eq!(cell.criticality, Criticality::Critical);
+        assert_eq!(cell.defect_risk, RiskLevel::Medium);
+        assert_eq!(cell.count, 1);
+
+        assert_eq!(analysis.summary.total_files, 3);
+        assert_eq!(analysis.summary.tagged_files, 2);
+        assert_eq!(analysis.summary.urgent_count, 1);
+        assert_eq!(analysis.summary.by_criticality.get("normal"), Some(&1));
+    }
+}
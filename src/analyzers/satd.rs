@@ -1,20 +1,62 @@
 //! SATD (Self-Admitted Technical Debt) analyzer.
 //!
 //! Finds TODO, FIXME, HACK, and other debt markers in comments. omen:ignore
+//!
+//! Items whose comment text normalizes to the same string (the same TODO
+//! pasted into several files) are grouped under `duplicates`, and
+//! `summary.unique_items` counts each such group once.
+//!
+//! With `[satd] compare_ref` set (`--compare <REF>` on the CLI), the files at
+//! that revision are scanned as well and items are matched by file and
+//! normalized text, so moved lines still match. Unmatched current items are
+//! reported as `added`, unmatched base items as `resolved`.
+//!
+//! `[satd.markers]` adds markers or overrides built-in ones, with a severity,
+//! a category and optionally a regex to match instead of the marker word.
+//! Custom markers are checked before the built-in categories, which can be
+//! limited with `categories` and reweighted with `category_weights`.
+//! `comment_prefixes` adds comment syntaxes per language.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+use chrono::Utc;
 use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result, SourceFile};
+use crate::analyzers::prioritize::{prioritize, Prioritization, Prioritized};
+use crate::config::{SatdConfig, SatdMarker, SatdMarkerRule};
+use crate::core::{
+    AnalysisContext, Analyzer as AnalyzerTrait, ContentSource, Error, FileSet, Language, Result,
+    SeverityCounts, SourceFile, Span,
+};
+use crate::git::{GitRepo, LineOrigin};
 use crate::parser::queries::satd;
+use crate::parser::{extract_functions, Parser};
+
+/// Category of markers given only in `custom_markers`, and the default for
+/// `[satd.markers]` entries that aren't built-in markers.
+const CUSTOM_CATEGORY: &str = "custom";
 
 /// SATD analyzer.
 pub struct Analyzer {
-    /// Compiled regex patterns for each category.
-    patterns: Vec<(String, Regex, f64)>,
+    /// Rules in match order; the first match on a line wins.
+    rules: Vec<Rule>,
+    /// Comment prefixes recognized for each language, beyond the common ones.
+    comment_prefixes: HashMap<Language, Vec<String>>,
+    /// Built from a [`SatdConfig`] rather than the defaults; otherwise
+    /// `analyze` uses the context's `[satd]` settings.
+    configured: bool,
+}
+
+/// A marker pattern and what a match is reported as.
+struct Rule {
+    category: String,
+    regex: Regex,
+    weight: f64,
 }
 
 impl Default for Analyzer {
@@ -24,28 +66,170 @@ impl Default for Analyzer {
 }
 
 impl Analyzer {
-    /// Create a new SATD analyzer.
+    /// Create a new SATD analyzer with the built-in markers.
     pub fn new() -> Self {
-        let patterns = satd::all_categories()
+        let rules = satd::all_categories()
             .iter()
             .map(|(category, markers, weight)| {
                 let pattern = format!(r"(?i)\b({})\b", markers.join("|"));
                 let regex = Regex::new(&pattern).expect("Invalid SATD pattern");
-                (category.to_string(), regex, *weight)
+                Rule {
+                    category: category.to_string(),
+                    regex,
+                    weight: *weight,
+                }
             })
             .collect();
 
-        Self { patterns }
+        Self {
+            rules,
+            comment_prefixes: HashMap::new(),
+            configured: false,
+        }
+    }
+
+    /// Create an analyzer from `[satd]` settings, merging custom markers
+    /// with the built-in ones. Fails on an invalid marker pattern or an
+    /// unknown language in `comment_prefixes`.
+    pub fn with_config(config: &SatdConfig) -> Result<Self> {
+        let category_weight = |category: &str| {
+            config.category_weights.get(category).copied().or_else(|| {
+                satd::all_categories()
+                    .iter()
+                    .find(|(name, _, _)| *name == category)
+                    .map(|(_, _, weight)| *weight)
+            })
+        };
+        let builtin_category = |marker: &str| {
+            satd::all_categories()
+                .iter()
+                .find(|(_, markers, _)| markers.contains(&marker))
+                .map(|(category, _, _)| category.to_string())
+        };
+
+        // Sorted so overlapping custom markers match in a stable order.
+        let mut custom: Vec<(String, SatdMarkerRule)> = config
+            .custom_markers
+            .iter()
+            .map(|marker| (marker.to_uppercase(), SatdMarkerRule::default()))
+            .collect();
+        let mut markers: Vec<_> = config.markers.iter().collect();
+        markers.sort_by(|a, b| a.0.cmp(b.0));
+        for (marker, entry) in markers {
+            let rule = match entry {
+                SatdMarker::Severity(severity) => SatdMarkerRule {
+                    severity: Some(*severity),
+                    ..SatdMarkerRule::default()
+                },
+                SatdMarker::Rule(rule) => rule.clone(),
+            };
+            let marker = marker.to_uppercase();
+            custom.retain(|(existing, _)| *existing != marker);
+            custom.push((marker, rule));
+        }
+
+        let mut rules = Vec::new();
+        for (marker, rule) in &custom {
+            let category = rule
+                .category
+                .clone()
+                .or_else(|| builtin_category(marker))
+                .unwrap_or_else(|| CUSTOM_CATEGORY.to_string());
+            let weight = match rule.severity {
+                Some(severity) => weight_from_severity(severity),
+                None => category_weight(&category).unwrap_or(1.0),
+            };
+            let pattern = match &rule.pattern {
+                Some(pattern) => pattern.clone(),
+                None => marker_pattern(marker),
+            };
+            let regex = Regex::new(&pattern).map_err(|e| {
+                Error::config(format!("invalid pattern for SATD marker {marker}: {e}"))
+            })?;
+            rules.push(Rule {
+                category,
+                regex,
+                weight,
+            });
+        }
+
+        for (category, markers, weight) in satd::all_categories() {
+            if !config.categories.iter().any(|c| c == category) {
+                continue;
+            }
+            let markers: Vec<&str> = markers
+                .iter()
+                .copied()
+                .filter(|m| !custom.iter().any(|(marker, _)| marker == m))
+                .collect();
+            if markers.is_empty() {
+                continue;
+            }
+            let pattern = format!(r"(?i)\b({})\b", markers.join("|"));
+            rules.push(Rule {
+                category: category.to_string(),
+                regex: Regex::new(&pattern).expect("Invalid SATD pattern"),
+                weight: category_weight(category).unwrap_or(*weight),
+            });
+        }
+
+        let mut comment_prefixes = HashMap::new();
+        for (name, prefixes) in &config.comment_prefixes {
+            let language: Language = serde_json::from_value(serde_json::Value::String(
+                name.to_lowercase(),
+            ))
+            .map_err(|_| {
+                Error::config(format!(
+                    "unknown language in [satd.comment_prefixes]: {name}"
+                ))
+            })?;
+            comment_prefixes.insert(language, prefixes.clone());
+        }
+
+        Ok(Self {
+            rules,
+            comment_prefixes,
+            configured: true,
+        })
+    }
+
+    /// Whether `line` is a comment in `language`.
+    fn is_comment(&self, line: &str, language: Language) -> bool {
+        is_comment_line(line)
+            || self
+                .comment_prefixes
+                .get(&language)
+                .is_some_and(|prefixes| {
+                    let trimmed = line.trim();
+                    prefixes
+                        .iter()
+                        .any(|prefix| trimmed.starts_with(prefix.as_str()))
+                })
     }
 
     /// Analyze a single file for SATD.
     pub fn analyze_file(&self, file: &SourceFile) -> Vec<SatdItem> {
         let content = file.content_str();
         let mut items = Vec::new();
+        let decoded;
+        let lines = match &content {
+            Cow::Borrowed(_) => file.line_index(),
+            // Invalid UTF-8 was replaced, shifting offsets: index the text read.
+            Cow::Owned(text) => {
+                decoded = LineIndex::new(text.as_bytes());
+                &decoded
+            }
+        };
+
+        let mut line_start = 0;
+        for (line_num, raw_line) in content.split_inclusive('\n').enumerate() {
+            let offset = line_start;
+            line_start += raw_line.len();
+            let line = raw_line.strip_suffix('\n').unwrap_or(raw_line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
 
-        for (line_num, line) in content.lines().enumerate() {
             // Check if line is a comment
-            if !is_comment_line(line) {
+            if !self.is_comment(line, file.language) {
                 continue;
             }
 
@@ -54,7 +238,12 @@ impl Analyzer {
                 continue;
             }
 
-            for (category, regex, weight) in &self.patterns {
+            for Rule {
+                category,
+                regex,
+                weight,
+            } in &self.rules
+            {
                 if let Some(mat) = regex.find(line) {
                     let marker = mat.as_str().to_uppercase();
 
@@ -66,19 +255,178 @@ impl Analyzer {
                     items.push(SatdItem {
                         file: file.path.to_string_lossy().to_string(),
                         line: line_num as u32 + 1,
+                        span: lines.span(
+                            content.as_bytes(),
+                            offset + mat.start(),
+                            offset + mat.end(),
+                        ),
                         category: category.clone(),
                         severity: severity_from_weight(*weight),
                         marker,
                         text: line.trim().chars().take(200).collect(),
                         weight: *weight,
+                        function: None,
+                        prioritization: None,
+                        introduced: None,
+                        changed: None,
+                        age_days: None,
                     });
                     break; // One category per line
                 }
             }
         }
 
+        if !items.is_empty() {
+            attach_functions(file, &mut items);
+        }
         items
     }
+
+    /// Scan the analyzed files as they were at `base_ref`.
+    ///
+    /// Lists the tree at that revision, keeps the files under the analysis
+    /// root that pass the same glob filters as the current file set, and
+    /// reports them under the same paths as current items.
+    fn analyze_ref(&self, ctx: &AnalysisContext<'_>, base_ref: &str) -> Result<Vec<SatdItem>> {
+        let git_path = ctx.git_path.ok_or_else(|| {
+            crate::core::Error::InvalidArgument(
+                "comparing SATD against a ref requires a git repository".to_string(),
+            )
+        })?;
+        let repo = GitRepo::open(git_path)?.at_ref(Some(base_ref));
+        let tree = repo.tree_source()?;
+        let prefix = ctx
+            .root
+            .strip_prefix(repo.root())
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+
+        let tree_files = FileSet::from_tree_source(&tree, ctx.config)?;
+        let relative: Vec<PathBuf> = tree_files
+            .iter()
+            .filter_map(|path| path.strip_prefix(&prefix).ok().map(Path::to_path_buf))
+            .collect();
+        let mut base_files = FileSet::from_files(ctx.root.to_path_buf(), relative);
+        for pattern in ctx.files.include_patterns() {
+            base_files = base_files.filter_by_glob(pattern);
+        }
+        for pattern in ctx.files.exclude_patterns() {
+            base_files = base_files.exclude_by_glob(pattern);
+        }
+
+        let paths: Vec<_> = base_files.iter().collect();
+        Ok(paths
+            .par_iter()
+            .filter_map(|path| {
+                let language = tree_files.language(&prefix.join(path))?;
+                let content = tree.read(&prefix.join(path)).ok()?;
+                let file = SourceFile::from_content(ctx.root.join(path), language, content);
+                Some(self.analyze_file(&file))
+            })
+            .flatten()
+            .collect())
+    }
+}
+
+/// Attribute each item to the innermost function whose lines contain it.
+///
+/// Only files with items are parsed; files that fail to parse keep
+/// `function` unset.
+fn attach_functions(file: &SourceFile, items: &mut [SatdItem]) {
+    let Ok(result) = Parser::new().parse_source(file) else {
+        return;
+    };
+    let functions = extract_functions(&result);
+    for item in items {
+        item.function = functions
+            .iter()
+            .filter(|f| f.start_line <= item.line && item.line <= f.end_line)
+            .min_by_key(|f| f.end_line - f.start_line)
+            .map(|f| f.name.clone());
+    }
+}
+
+/// Normalize comment text so copies differing only in comment syntax,
+/// case or spacing compare equal.
+fn normalize_text(text: &str) -> String {
+    let body = text
+        .trim_start_matches(|c: char| {
+            c.is_whitespace() || matches!(c, '/' | '*' | '#' | '-' | ';' | '!' | '\'' | '"')
+        })
+        .trim_end_matches(|c: char| c.is_whitespace() || matches!(c, '/' | '*'));
+    body.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Group items that share normalized text, keeping groups seen more than once.
+///
+/// Groups are ordered by occurrence count (descending), then text.
+fn group_duplicates(items: &[SatdItem]) -> Vec<DuplicateGroup> {
+    let mut groups: HashMap<String, DuplicateGroup> = HashMap::new();
+    for item in items {
+        groups
+            .entry(normalize_text(&item.text))
+            .or_insert_with_key(|text| DuplicateGroup {
+                text: text.clone(),
+                marker: item.marker.clone(),
+                category: item.category.clone(),
+                occurrences: Vec::new(),
+            })
+            .occurrences
+            .push(Occurrence {
+                file: item.file.clone(),
+                line: item.line,
+            });
+    }
+
+    let mut duplicates: Vec<DuplicateGroup> = groups
+        .into_values()
+        .filter(|g| g.occurrences.len() > 1)
+        .collect();
+    duplicates.sort_by(|a, b| {
+        b.occurrences
+            .len()
+            .cmp(&a.occurrences.len())
+            .then_with(|| a.text.cmp(&b.text))
+    });
+    duplicates
+}
+
+/// Match base items against current ones by file and normalized text.
+///
+/// Line numbers are ignored so edits above a comment don't count as
+/// resolving and re-adding it. Repeated text is matched one-for-one.
+fn compare(base_ref: &str, base: Vec<SatdItem>, current: &[SatdItem]) -> Comparison {
+    let base_total = base.len();
+    let mut unmatched: HashMap<(String, String), Vec<SatdItem>> = HashMap::new();
+    for item in base {
+        unmatched
+            .entry((item.file.clone(), normalize_text(&item.text)))
+            .or_default()
+            .push(item);
+    }
+
+    let mut added = Vec::new();
+    for item in current {
+        let key = (item.file.clone(), normalize_text(&item.text));
+        let matched = unmatched.get_mut(&key).and_then(Vec::pop).is_some();
+        if !matched {
+            added.push(item.clone());
+        }
+    }
+
+    let mut resolved: Vec<SatdItem> = unmatched.into_values().flatten().collect();
+    resolved.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
+    Comparison {
+        base_ref: base_ref.to_string(),
+        base_total,
+        current_total: current.len(),
+        added,
+        resolved,
+    }
 }
 
 /// Markers that are commonly false positives when not at the start of a comment.
@@ -146,19 +494,23 @@ impl AnalyzerTrait for Analyzer {
 
     fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
         let start = Instant::now();
+        let from_config;
+        let analyzer = if self.configured {
+            self
+        } else {
+            from_config = Self::with_config(&ctx.config.satd)?;
+            &from_config
+        };
 
         // Single pass: collect SATD items and LOC simultaneously to avoid double file loading
         // Collect into Vec first for efficient parallel iteration
         let files: Vec<_> = ctx.files.iter().collect();
-        let (items, total_loc): (Vec<SatdItem>, usize) = files
+        let (mut items, total_loc): (Vec<SatdItem>, usize) = files
             .par_iter()
-            .filter_map(|path| {
-                let full_path = ctx.root.join(path);
-                SourceFile::load(&full_path).ok()
-            })
+            .filter_map(|path| ctx.load_source(path).ok())
             .map(|file| {
                 let loc = file.lines_of_code();
-                let file_items = self.analyze_file(&file);
+                let file_items = analyzer.analyze_file(&file);
                 (file_items, loc)
             })
             .reduce(
@@ -169,6 +521,30 @@ impl AnalyzerTrait for Analyzer {
                 },
             );
 
+        if let Some(blame) = &ctx.blame {
+            let now = Utc::now();
+            items.par_iter_mut().for_each(|item| {
+                item.introduced = blame.origin(Path::new(&item.file), item.line);
+                item.age_days = item.introduced.as_ref().map(|o| (now - o.date).num_days());
+            });
+        }
+        if let Some(changed) = &ctx.changed_lines {
+            for item in &mut items {
+                item.changed = Some(changed.touches(Path::new(&item.file), item.line, item.line));
+            }
+        }
+
+        prioritize(ctx, &mut items)?;
+
+        let comparison = match ctx.config.satd.compare_ref.as_deref() {
+            Some(base_ref) => {
+                let base_items = analyzer.analyze_ref(ctx, base_ref)?;
+                Some(compare(base_ref, base_items, &items))
+            }
+            None => None,
+        };
+        let duplicates = group_duplicates(&items);
+
         // Group by category
         let mut by_category = std::collections::HashMap::new();
         for item in &items {
@@ -185,12 +561,18 @@ impl AnalyzerTrait for Analyzer {
         };
 
         let total_items: usize = by_category.values().sum();
+        let repeated: usize = duplicates.iter().map(|g| g.occurrences.len() - 1).sum();
+        let severity_counts: SeverityCounts = items.iter().map(|i| i.severity).collect();
         let analysis = Analysis {
             items,
             by_category,
             density,
+            duplicates,
+            comparison,
             summary: AnalysisSummary {
                 total_items,
+                unique_items: total_items - repeated,
+                severity_counts,
                 weighted_count: total_weight,
                 density,
             },
@@ -216,10 +598,53 @@ pub struct Analysis {
     pub by_category: std::collections::HashMap<String, usize>,
     /// Weighted density per 1K LOC.
     pub density: f64,
+    /// Comment texts found at more than one location.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicateGroup>,
+    /// Debt added and resolved since `compare_ref`, when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<Comparison>,
     /// Summary statistics.
     pub summary: AnalysisSummary,
 }
 
+/// SATD text repeated across locations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Normalized comment text shared by every occurrence.
+    pub text: String,
+    /// Marker of the first occurrence.
+    pub marker: String,
+    /// Category of the first occurrence.
+    pub category: String,
+    /// Where the text appears.
+    pub occurrences: Vec<Occurrence>,
+}
+
+/// One location of a repeated SATD comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Occurrence {
+    /// File path.
+    pub file: String,
+    /// Line number (1-indexed).
+    pub line: u32,
+}
+
+/// SATD changes between a base revision and the analyzed tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comparison {
+    /// Revision the current items were compared against.
+    pub base_ref: String,
+    /// Item count at the base revision.
+    pub base_total: usize,
+    /// Item count in the analyzed tree.
+    pub current_total: usize,
+    /// Items with no counterpart at the base revision.
+    pub added: Vec<SatdItem>,
+    /// Base items with no counterpart in the analyzed tree.
+    pub resolved: Vec<SatdItem>,
+}
+
 /// A single SATD item.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SatdItem {
@@ -227,6 +652,9 @@ pub struct SatdItem {
     pub file: String,
     /// Line number (1-indexed).
     pub line: u32,
+    /// Range of the matched marker.
+    #[serde(default)]
+    pub span: Span,
     /// Category (design, defect, requirement, etc.).
     pub category: String,
     /// Severity level.
@@ -235,9 +663,38 @@ pub struct SatdItem {
     pub marker: String,
     /// Comment text (truncated).
     pub text: String,
+    /// Innermost function containing the comment, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
     /// Severity weight.
     #[serde(skip)]
     pub weight: f64,
+    /// Rank annotation when `prioritize_by` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prioritization: Option<Prioritization>,
+    /// Commit that last changed the line, when `[git] blame` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<LineOrigin>,
+    /// Days since `introduced`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<i64>,
+    /// Whether the comment is on a line changed since `--base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
+}
+
+impl Prioritized for SatdItem {
+    fn files(&self) -> Vec<&str> {
+        vec![self.file.as_str()]
+    }
+
+    fn prioritization(&self) -> Option<&Prioritization> {
+        self.prioritization.as_ref()
+    }
+
+    fn set_prioritization(&mut self, prioritization: Prioritization) {
+        self.prioritization = Some(prioritization);
+    }
 }
 
 /// SATD severity level.
@@ -250,11 +707,28 @@ pub enum Severity {
     Low,
 }
 
+impl From<Severity> for crate::core::Severity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Critical => Self::Critical,
+            Severity::High => Self::High,
+            Severity::Medium => Self::Medium,
+            Severity::Low => Self::Low,
+        }
+    }
+}
+
 /// Analysis summary.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnalysisSummary {
     /// Total SATD items.
     pub total_items: usize,
+    /// Items counting each group of repeated text once.
+    #[serde(default)]
+    pub unique_items: usize,
+    /// Items per severity.
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
     /// Weighted count.
     pub weighted_count: f64,
     /// Density per 1K LOC.
@@ -274,6 +748,34 @@ fn is_comment_line(line: &str) -> bool {
         || trimmed.starts_with(';')
 }
 
+/// Regex matching `marker` case-insensitively as a whole word.
+fn marker_pattern(marker: &str) -> String {
+    let escaped = regex::escape(marker);
+    let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if word(marker.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if word(marker.chars().last()) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!("(?i){start}{escaped}{end}")
+}
+
+/// Weight for a configured severity, the lowest weight of that severity.
+fn weight_from_severity(severity: crate::core::Severity) -> f64 {
+    use crate::core::Severity as Level;
+    match severity {
+        Level::Critical => 4.0,
+        Level::High => 2.0,
+        Level::Medium => 1.0,
+        Level::Low => 0.25,
+    }
+}
+
 /// Convert weight to severity level.
 fn severity_from_weight(weight: f64) -> Severity {
     if weight >= 4.0 {
@@ -304,6 +806,22 @@ mod tests {
         assert_eq!(items[1].marker, "FIXME");
     }
 
+    #[test]
+    fn test_satd_item_span_covers_marker() {
+        let analyzer = Analyzer::new();
+        let content = "fn main() {}\r\n/* café: TODO fix */\r\n"
+            .as_bytes()
+            .to_vec();
+        let file = SourceFile::from_content("test.rs", Language::Rust, content.clone());
+
+        let items = analyzer.analyze_file(&file);
+        assert_eq!(items.len(), 1);
+        let span = items[0].span;
+        assert_eq!(&content[span.start_byte..span.end_byte], b"TODO");
+        assert_eq!((span.start_line, span.start_column), (2, 10));
+        assert_eq!((span.end_line, span.end_column), (2, 14));
+    }
+
     #[test]
     fn test_satd_categories() {
         let analyzer = Analyzer::new();
@@ -486,4 +1004,350 @@ fn main() {}\n\
         assert!(!has_ignore_directive("// TODO: real debt"));
         assert!(!has_ignore_directive("// normal comment"));
     }
+
+    #[test]
+    fn test_analyze_in_memory_files() {
+        use crate::core::{FileSet, MemorySource};
+        use std::sync::Arc;
+
+        let source = MemorySource::new()
+            .with_file("src/main.rs", "// TODO: wire up config\nfn main() {}\n")
+            .with_file("src/clean.rs", "fn clean() {}\n");
+        let config = crate::config::Config::default();
+        let file_set = FileSet::from_memory_source(&source, &config).unwrap();
+        let ctx =
+            AnalysisContext::new(&file_set, &config, None).with_content_source(Arc::new(source));
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert_eq!(analysis.items.len(), 1);
+        assert!(analysis.items[0].file.ends_with("src/main.rs"));
+        assert_eq!(analysis.summary.total_items, 1);
+    }
+
+    #[test]
+    fn test_analyze_tags_items_with_blame() {
+        use crate::core::FileSet;
+        use crate::git::BlameCache;
+        use std::process::Command;
+        use std::sync::Arc;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| {
+            assert!(Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .unwrap()
+                .success());
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "alice@example.com"]);
+        git(&["config", "user.name", "Alice"]);
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n// FIXME: leaks handles\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+
+        let config = crate::config::Config::default();
+        let file_set = FileSet::from_path(dir, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir))
+            .with_git_path(dir)
+            .with_blame(Arc::new(BlameCache::new(dir, None)));
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert_eq!(analysis.items.len(), 1);
+        let origin = analysis.items[0].introduced.as_ref().unwrap();
+        assert_eq!(origin.author, "Alice");
+        assert_eq!(origin.commit.len(), 40);
+        assert_eq!(analysis.items[0].age_days, Some(0));
+    }
+
+    #[test]
+    fn test_analyze_marks_items_on_changed_lines() {
+        use crate::core::FileSet;
+        use crate::git::ChangedLines;
+        use std::process::Command;
+        use std::sync::Arc;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| {
+            assert!(Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .unwrap()
+                .success());
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "alice@example.com"]);
+        git(&["config", "user.name", "Alice"]);
+        std::fs::write(dir.join("lib.rs"), "// TODO: old debt\nfn a() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+        std::fs::write(
+            dir.join("lib.rs"),
+            "// TODO: old debt\nfn a() {}\n// HACK: new debt\n",
+        )
+        .unwrap();
+
+        let config = crate::config::Config::default();
+        let file_set = FileSet::from_path(dir, &config).unwrap();
+        let changed = ChangedLines::since(dir, "HEAD", None).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir))
+            .with_changed_lines(Arc::new(changed));
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let mut changed: Vec<_> = analysis.items.iter().map(|i| (i.line, i.changed)).collect();
+        changed.sort();
+        assert_eq!(changed, vec![(1, Some(false)), (3, Some(true))]);
+    }
+
+    #[test]
+    fn test_analyze_file_attaches_enclosing_function() {
+        let content = b"// TODO: module-level\nfn outer() {\n    // FIXME: outer\n    fn inner() {\n        // HACK: inner\n    }\n}\n";
+        let file = SourceFile::from_content("lib.rs", Language::Rust, content.to_vec());
+        let items = Analyzer::new().analyze_file(&file);
+
+        let functions: Vec<_> = items.iter().map(|i| i.function.as_deref()).collect();
+        assert_eq!(functions, vec![None, Some("outer"), Some("inner")]);
+    }
+
+    fn item(file: &str, line: u32, text: &str) -> SatdItem {
+        SatdItem {
+            file: file.to_string(),
+            line,
+            span: Span::default(),
+            category: "design".to_string(),
+            severity: Severity::High,
+            marker: "TODO".to_string(),
+            text: text.to_string(),
+            weight: 2.0,
+            function: None,
+            prioritization: None,
+            introduced: None,
+            changed: None,
+            age_days: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_text_ignores_comment_syntax_and_spacing() {
+        assert_eq!(
+            normalize_text("// TODO:  Retry   on 503"),
+            "todo: retry on 503"
+        );
+        assert_eq!(normalize_text("# todo: retry on 503"), "todo: retry on 503");
+        assert_eq!(
+            normalize_text("/* TODO: retry on 503 */"),
+            "todo: retry on 503"
+        );
+    }
+
+    #[test]
+    fn test_group_duplicates_lists_every_occurrence() {
+        let items = vec![
+            item("a.rs", 3, "// TODO: retry on 503"),
+            item("b.py", 9, "# TODO: retry on 503"),
+            item("c.rs", 1, "// TODO: unique"),
+        ];
+
+        let groups = group_duplicates(&items);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].text, "todo: retry on 503");
+        let files: Vec<_> = groups[0]
+            .occurrences
+            .iter()
+            .map(|o| o.file.as_str())
+            .collect();
+        assert_eq!(files, ["a.rs", "b.py"]);
+    }
+
+    #[test]
+    fn test_compare_matches_by_file_and_text_not_line() {
+        let base = vec![
+            item("a.rs", 3, "// TODO: keep"),
+            item("a.rs", 8, "// FIXME: gone"),
+        ];
+        let current = vec![
+            item("a.rs", 5, "// TODO: keep"),
+            item("b.rs", 1, "// TODO: keep"),
+        ];
+
+        let cmp = compare("main", base, &current);
+
+        assert_eq!((cmp.base_total, cmp.current_total), (2, 2));
+        assert_eq!(cmp.added.len(), 1);
+        assert_eq!(cmp.added[0].file, "b.rs");
+        assert_eq!(cmp.resolved.len(), 1);
+        assert_eq!(cmp.resolved[0].text, "// FIXME: gone");
+    }
+
+    #[test]
+    fn test_analyze_deduplicates_repeated_text() {
+        use crate::core::{FileSet, MemorySource};
+        use std::sync::Arc;
+
+        let source = MemorySource::new()
+            .with_file("a.rs", "// TODO: handle timeouts\nfn a() {}\n")
+            .with_file("b.rs", "// TODO: handle timeouts\nfn b() {}\n");
+        let config = crate::config::Config::default();
+        let file_set = FileSet::from_memory_source(&source, &config).unwrap();
+        let ctx =
+            AnalysisContext::new(&file_set, &config, None).with_content_source(Arc::new(source));
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        assert_eq!(analysis.summary.total_items, 2);
+        assert_eq!(analysis.summary.unique_items, 1);
+        assert_eq!(analysis.duplicates[0].occurrences.len(), 2);
+    }
+
+    #[test]
+    fn test_analyze_compares_against_ref() {
+        use crate::core::FileSet;
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        let git = |args: &[&str]| {
+            assert!(Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .status()
+                .unwrap()
+                .success());
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "alice@example.com"]);
+        git(&["config", "user.name", "Alice"]);
+        std::fs::write(
+            dir.join("lib.rs"),
+            "// TODO: cache lookups\n// FIXME: leaks handles\nfn a() {}\n",
+        )
+        .unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+        git(&["tag", "base"]);
+        std::fs::write(
+            dir.join("lib.rs"),
+            "fn a() {}\n// TODO: cache lookups\n// HACK: skip validation\n",
+        )
+        .unwrap();
+
+        let mut config = crate::config::Config::default();
+        config.satd.compare_ref = Some("base".to_string());
+        let file_set = FileSet::from_path(dir, &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir)).with_git_path(dir);
+
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let cmp = analysis.comparison.unwrap();
+        assert_eq!(cmp.base_ref, "base");
+        assert_eq!(cmp.base_total, 2);
+        assert_eq!(cmp.added.len(), 1);
+        assert_eq!(cmp.added[0].marker, "HACK");
+        assert_eq!(cmp.resolved.len(), 1);
+        assert_eq!(cmp.resolved[0].marker, "FIXME");
+    }
+
+    fn satd_config(toml: &str) -> SatdConfig {
+        use figment::providers::{Format, Toml};
+        figment::Figment::from(Toml::string(toml))
+            .extract()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_custom_markers_merge_with_builtins() {
+        let config = satd_config(
+            r#"
+            custom_markers = ["REVISIT"]
+            category_weights = { design = 2.0 }
+
+            [markers]
+            SECURITY = "low"
+            PERF-REGRESSION = { category = "performance", severity = "high" }
+            TICKET = { pattern = 'TICKET-\d+', category = "requirement" }
+            "#,
+        );
+        let analyzer = Analyzer::with_config(&config).unwrap();
+        let content = b"// REVISIT: later
+\
+            // SECURITY: check input
+\
+            // PERF-REGRESSION since 2.0
+\
+            // see TICKET-42
+\
+            // HACK: workaround
+\
+            // TODO: still detected
+"
+        .to_vec();
+        let file = SourceFile::from_content("test.rs", Language::Rust, content);
+
+        let items = analyzer.analyze_file(&file);
+        let found: Vec<(&str, &str, Severity)> = items
+            .iter()
+            .map(|i| (i.marker.as_str(), i.category.as_str(), i.severity))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("REVISIT", "custom", Severity::Medium),
+                ("SECURITY", "security", Severity::Low),
+                ("PERF-REGRESSION", "performance", Severity::High),
+                ("TICKET-42", "requirement", Severity::Low),
+                ("HACK", "design", Severity::High),
+                ("TODO", "requirement", Severity::Low),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_categories_limit_builtin_markers() {
+        let config = satd_config(r#"categories = ["defect"]"#);
+        let analyzer = Analyzer::with_config(&config).unwrap();
+        let content = b"// FIXME: broken
+// TODO: later
+"
+        .to_vec();
+        let file = SourceFile::from_content("test.rs", Language::Rust, content);
+
+        let items = analyzer.analyze_file(&file);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].marker, "FIXME");
+    }
+
+    #[test]
+    fn test_language_comment_prefixes() {
+        let config = satd_config(
+            r#"
+            categories = ["requirement"]
+            comment_prefixes = { ruby = ["=begin"] }
+            "#,
+        );
+        let analyzer = Analyzer::with_config(&config).unwrap();
+        let ruby = SourceFile::from_content("a.rb", Language::Ruby, b"=begin TODO\n".to_vec());
+        let python = SourceFile::from_content("a.py", Language::Python, b"=begin TODO\n".to_vec());
+
+        assert_eq!(analyzer.analyze_file(&ruby).len(), 1);
+        assert!(analyzer.analyze_file(&python).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_marker_config_errors() {
+        let config = satd_config("[markers]\nBAD = { pattern = \"(\" }\n");
+        let err = Analyzer::with_config(&config).err().unwrap();
+        assert!(err.to_string().contains("SATD marker BAD"), "got: {err}");
+
+        let config = satd_config("comment_prefixes = { cobol = [\"*>\"] }\n");
+        let err = Analyzer::with_config(&config).err().unwrap();
+        assert!(err.to_string().contains("cobol"), "got: {err}");
+    }
 }
@@ -0,0 +1,558 @@
+//! Constructor over-injection detection for DI frameworks.
+//!
+//! Fan-in/fan-out only sees imports, so a Spring service or NestJS provider
+//! that receives a dozen collaborators through its constructor looks no more
+//! coupled than any other file. This module lists the dependencies each class
+//! receives from the container:
+//!
+//! - Java: constructors of Spring/Jakarta components (`@Service`,
+//!   `@Component`, ...) or constructors marked `@Inject`/`@Autowired`, plus
+//!   `@Autowired`/`@Inject`/`@Resource` fields.
+//! - C#: the widest constructor (or primary constructor) of each class, as
+//!   ASP.NET Core resolves constructors by convention, plus `[Inject]`
+//!   properties.
+//! - TypeScript: constructor parameter properties (`private readonly
+//!   users: UsersService`) and parameters of `@Injectable`/`@Component`/
+//!   `@Controller` classes, plus `inject(...)` and `@Inject...` fields.
+//!
+//! Primitive and value types (strings, numbers, dates, ids) are not counted,
+//! since they are configuration rather than collaborators.
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+
+use crate::core::{Language, Span};
+use crate::parser::ParseResult;
+
+/// How a dependency reaches the class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Injection {
+    Constructor,
+    Field,
+}
+
+/// One dependency a class receives from the container. omen:ignore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InjectedDependency {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub via: Injection,
+}
+
+/// A class and the dependencies injected into it.
+#[derive(Debug, Clone)]
+pub struct InjectedClass {
+    pub class_name: String,
+    /// The injecting constructor, or the class when only fields are injected.
+    pub span: Span,
+    pub dependencies: Vec<InjectedDependency>,
+}
+
+/// Java annotations marking a container-managed class.
+const JAVA_COMPONENTS: &[&str] = &[
+    "Service",
+    "Component",
+    "Controller",
+    "RestController",
+    "Repository",
+    "Configuration",
+    "Named",
+    "Singleton",
+    "ApplicationScoped",
+    "RequestScoped",
+    "SessionScoped",
+    "Dependent",
+    "Stateless",
+    "Stateful",
+];
+
+/// Java annotations marking an injection point.
+const JAVA_INJECT: &[&str] = &["Inject", "Autowired", "Resource"];
+
+const JAVA_VALUE_TYPES: &[&str] = &[
+    "String",
+    "Integer",
+    "Long",
+    "Short",
+    "Byte",
+    "Double",
+    "Float",
+    "Boolean",
+    "Character",
+    "BigDecimal",
+    "BigInteger",
+    "UUID",
+    "Instant",
+    "Duration",
+    "LocalDate",
+    "LocalDateTime",
+];
+
+const CSHARP_VALUE_TYPES: &[&str] = &[
+    "String",
+    "DateTime",
+    "DateTimeOffset",
+    "TimeSpan",
+    "Guid",
+    "CancellationToken",
+];
+
+/// TypeScript decorators marking a container-managed class.
+const TS_COMPONENTS: &[&str] = &[
+    "Injectable",
+    "Component",
+    "Controller",
+    "Directive",
+    "Pipe",
+    "Resolver",
+    "Gateway",
+    "WebSocketGateway",
+];
+
+/// Classes in `parse` that receive at least one injected dependency.
+pub fn injected_classes(parse: &ParseResult) -> Vec<InjectedClass> {
+    let class_kinds: &[&str] = match parse.language {
+        Language::Java | Language::CSharp => &["class_declaration"],
+        Language::TypeScript | Language::Tsx => {
+            &["class_declaration", "abstract_class_declaration", "class"]
+        }
+        _ => return Vec::new(),
+    };
+
+    let mut classes = Vec::new();
+    let mut stack = vec![parse.root_node()];
+    while let Some(node) = stack.pop() {
+        if class_kinds.contains(&node.kind()) {
+            if let Some(class) = inspect_class(parse, node) {
+                classes.push(class);
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    classes.sort_by_key(|c| c.span.start_byte);
+    classes
+}
+
+fn inspect_class(parse: &ParseResult, class: Node<'_>) -> Option<InjectedClass> {
+    let class_name = parse
+        .node_text(&class.child_by_field_name("name")?)
+        .to_string();
+    let body = class.child_by_field_name("body")?;
+    let (constructor, mut dependencies) = match parse.language {
+        Language::Java => java_constructor(parse, class, body),
+        Language::CSharp => csharp_constructor(parse, class, body),
+        _ => ts_constructor(parse, class, body),
+    };
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        let field = match parse.language {
+            Language::Java => java_field(parse, member),
+            Language::CSharp => csharp_field(parse, member),
+            _ => ts_field(parse, member),
+        };
+        dependencies.extend(field);
+    }
+    if dependencies.is_empty() {
+        return None;
+    }
+    Some(InjectedClass {
+        class_name,
+        span: Span::from_node(&constructor.unwrap_or(class), &parse.source),
+        dependencies,
+    })
+}
+
+fn named_children_of_kind<'a>(node: Node<'a>, kinds: &[&str]) -> Vec<Node<'a>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|c| kinds.contains(&c.kind()))
+        .collect()
+}
+
+/// Last path segment of an annotation, attribute or decorator name.
+fn simple_name(text: &str) -> &str {
+    let text = text.split(['(', '<']).next().unwrap_or(text);
+    text.rsplit('.').next().unwrap_or(text).trim()
+}
+
+/// Strip generic arguments and array/nullable suffixes from a type name.
+fn base_type(text: &str) -> &str {
+    simple_name(text.trim_end_matches(['?', '[', ']']))
+}
+
+fn constructor_dependency(name: &str, type_name: &str) -> InjectedDependency {
+    InjectedDependency {
+        name: name.to_string(),
+        type_name: type_name.to_string(),
+        via: Injection::Constructor,
+    }
+}
+
+fn field_dependency(name: &str, type_name: &str) -> InjectedDependency {
+    InjectedDependency {
+        name: name.to_string(),
+        type_name: type_name.to_string(),
+        via: Injection::Field,
+    }
+}
+
+// ---------------------------------------------------------------- Java
+
+/// Annotation names in a Java declaration's `modifiers`.
+fn java_annotations(parse: &ParseResult, node: Node<'_>) -> Vec<String> {
+    named_children_of_kind(node, &["modifiers"])
+        .into_iter()
+        .flat_map(|m| named_children_of_kind(m, &["marker_annotation", "annotation"]))
+        .filter_map(|a| a.child_by_field_name("name"))
+        .map(|n| simple_name(parse.node_text(&n)).to_string())
+        .collect()
+}
+
+fn java_constructor<'a>(
+    parse: &ParseResult,
+    class: Node<'a>,
+    body: Node<'a>,
+) -> (Option<Node<'a>>, Vec<InjectedDependency>) {
+    let managed = java_annotations(parse, class)
+        .iter()
+        .any(|a| JAVA_COMPONENTS.contains(&a.as_str()));
+    let constructors = named_children_of_kind(body, &["constructor_declaration"]);
+    let marked = constructors.iter().copied().find(|c| {
+        java_annotations(parse, *c)
+            .iter()
+            .any(|a| JAVA_INJECT.contains(&a.as_str()))
+    });
+    let chosen = match marked {
+        Some(constructor) => Some(constructor),
+        None if managed => constructors
+            .iter()
+            .copied()
+            .max_by_key(|c| java_parameters(*c).len()),
+        None => None,
+    };
+    let Some(constructor) = chosen else {
+        return (None, Vec::new());
+    };
+    let dependencies = java_parameters(constructor)
+        .into_iter()
+        .filter(|p| !java_annotations(parse, *p).iter().any(|a| a == "Value"))
+        .filter_map(|p| {
+            let ty = p.child_by_field_name("type")?;
+            let name = p.child_by_field_name("name")?;
+            java_injectable(parse, ty)
+                .then(|| constructor_dependency(parse.node_text(&name), parse.node_text(&ty)))
+        })
+        .collect();
+    (Some(constructor), dependencies)
+}
+
+fn java_parameters(constructor: Node<'_>) -> Vec<Node<'_>> {
+    constructor
+        .child_by_field_name("parameters")
+        .map(|p| named_children_of_kind(p, &["formal_parameter"]))
+        .unwrap_or_default()
+}
+
+fn java_injectable(parse: &ParseResult, ty: Node<'_>) -> bool {
+    !matches!(
+        ty.kind(),
+        "integral_type" | "floating_point_type" | "boolean_type" | "array_type"
+    ) && !JAVA_VALUE_TYPES.contains(&base_type(parse.node_text(&ty)))
+}
+
+fn java_field(parse: &ParseResult, member: Node<'_>) -> Vec<InjectedDependency> {
+    if member.kind() != "field_declaration"
+        || !java_annotations(parse, member)
+            .iter()
+            .any(|a| JAVA_INJECT.contains(&a.as_str()))
+    {
+        return Vec::new();
+    }
+    let Some(ty) = member.child_by_field_name("type") else {
+        return Vec::new();
+    };
+    let type_name = parse.node_text(&ty);
+    named_children_of_kind(member, &["variable_declarator"])
+        .into_iter()
+        .filter_map(|d| d.child_by_field_name("name"))
+        .map(|n| field_dependency(parse.node_text(&n), type_name))
+        .collect()
+}
+
+// ---------------------------------------------------------------- C#
+
+fn csharp_attributes(parse: &ParseResult, node: Node<'_>) -> Vec<String> {
+    named_children_of_kind(node, &["attribute_list"])
+        .into_iter()
+        .flat_map(|l| named_children_of_kind(l, &["attribute"]))
+        .filter_map(|a| a.child_by_field_name("name"))
+        .map(|n| simple_name(parse.node_text(&n)).to_string())
+        .collect()
+}
+
+fn csharp_constructor<'a>(
+    parse: &ParseResult,
+    class: Node<'a>,
+    body: Node<'a>,
+) -> (Option<Node<'a>>, Vec<InjectedDependency>) {
+    // Primary constructors put the parameter list on the class itself.
+    let primary = named_children_of_kind(class, &["parameter_list"])
+        .into_iter()
+        .next()
+        .map(|params| (class, params));
+    let widest = named_children_of_kind(body, &["constructor_declaration"])
+        .into_iter()
+        .filter_map(|c| Some((c, c.child_by_field_name("parameters")?)))
+        .max_by_key(|(_, params)| params.named_child_count());
+    let Some((constructor, params)) = primary.or(widest) else {
+        return (None, Vec::new());
+    };
+    let dependencies = named_children_of_kind(params, &["parameter"])
+        .into_iter()
+        .filter_map(|p| {
+            let ty = p.child_by_field_name("type")?;
+            let name = p.child_by_field_name("name")?;
+            csharp_injectable(parse, ty)
+                .then(|| constructor_dependency(parse.node_text(&name), parse.node_text(&ty)))
+        })
+        .collect();
+    let constructor = (constructor != class).then_some(constructor);
+    (constructor, dependencies)
+}
+
+fn csharp_injectable(parse: &ParseResult, ty: Node<'_>) -> bool {
+    let mut ty = ty;
+    if ty.kind() == "nullable_type" {
+        match ty.named_child(0) {
+            Some(inner) => ty = inner,
+            None => return false,
+        }
+    }
+    !matches!(ty.kind(), "predefined_type" | "array_type")
+        && !CSHARP_VALUE_TYPES.contains(&base_type(parse.node_text(&ty)))
+}
+
+fn csharp_field(parse: &ParseResult, member: Node<'_>) -> Vec<InjectedDependency> {
+    if member.kind() != "property_declaration"
+        || !csharp_attributes(parse, member)
+            .iter()
+            .any(|a| a == "Inject")
+    {
+        return Vec::new();
+    }
+    match (
+        member.child_by_field_name("name"),
+        member.child_by_field_name("type"),
+    ) {
+        (Some(name), Some(ty)) => vec![field_dependency(
+            parse.node_text(&name),
+            parse.node_text(&ty),
+        )],
+        _ => Vec::new(),
+    }
+}
+
+// ---------------------------------------------------------------- TypeScript
+
+/// Decorator names on `node`, including those attached to an enclosing
+/// `export` statement.
+fn ts_decorators(parse: &ParseResult, node: Node<'_>) -> Vec<String> {
+    let mut decorators = named_children_of_kind(node, &["decorator"]);
+    if let Some(parent) = node.parent().filter(|p| p.kind() == "export_statement") {
+        decorators.extend(named_children_of_kind(parent, &["decorator"]));
+    }
+    decorators
+        .into_iter()
+        .filter_map(|d| d.named_child(0))
+        .map(|expr| match expr.child_by_field_name("function") {
+            Some(function) => parse.node_text(&function),
+            None => parse.node_text(&expr),
+        })
+        .map(|name| simple_name(name).to_string())
+        .collect()
+}
+
+fn ts_constructor<'a>(
+    parse: &ParseResult,
+    class: Node<'a>,
+    body: Node<'a>,
+) -> (Option<Node<'a>>, Vec<InjectedDependency>) {
+    let managed = ts_decorators(parse, class)
+        .iter()
+        .any(|d| TS_COMPONENTS.contains(&d.as_str()));
+    let constructor = named_children_of_kind(body, &["method_definition"])
+        .into_iter()
+        .find(|m| {
+            m.child_by_field_name("name")
+                .is_some_and(|n| parse.node_text(&n) == "constructor")
+        });
+    let Some(params) = constructor.and_then(|c| c.child_by_field_name("parameters")) else {
+        return (None, Vec::new());
+    };
+    let dependencies: Vec<InjectedDependency> =
+        named_children_of_kind(params, &["required_parameter", "optional_parameter"])
+            .into_iter()
+            .filter(|p| managed || ts_parameter_property(parse, *p))
+            .filter_map(|p| {
+                let name = p.child_by_field_name("pattern")?;
+                let annotation = p.child_by_field_name("type");
+                let ty = annotation.and_then(|a| a.named_child(0));
+                if ty.is_some_and(|t| matches!(t.kind(), "predefined_type" | "literal_type")) {
+                    return None;
+                }
+                let type_name = ty.map(|t| parse.node_text(&t)).unwrap_or_default();
+                Some(constructor_dependency(parse.node_text(&name), type_name))
+            })
+            .collect();
+    (constructor, dependencies)
+}
+
+/// A parameter the container fills: `private readonly x: X` or `@Inject(T) x`.
+fn ts_parameter_property(parse: &ParseResult, param: Node<'_>) -> bool {
+    let mut cursor = param.walk();
+    let marked = param
+        .children(&mut cursor)
+        .any(|c| matches!(c.kind(), "accessibility_modifier" | "readonly"));
+    marked || !ts_decorators(parse, param).is_empty()
+}
+
+fn ts_field(parse: &ParseResult, member: Node<'_>) -> Vec<InjectedDependency> {
+    if member.kind() != "public_field_definition" {
+        return Vec::new();
+    }
+    let Some(name) = member.child_by_field_name("name") else {
+        return Vec::new();
+    };
+    let name = parse.node_text(&name);
+
+    // Angular: `private http = inject(HttpClient);`
+    if let Some(value) = member
+        .child_by_field_name("value")
+        .filter(|v| v.kind() == "call_expression")
+    {
+        let is_inject = value
+            .child_by_field_name("function")
+            .is_some_and(|f| parse.node_text(&f) == "inject");
+        if is_inject {
+            let type_name = value
+                .child_by_field_name("arguments")
+                .and_then(|a| a.named_child(0))
+                .map(|t| parse.node_text(&t))
+                .unwrap_or_default();
+            return vec![field_dependency(name, type_name)];
+        }
+    }
+
+    // NestJS/TypeORM: `@InjectRepository(User) private users: Repository<User>;`
+    if ts_decorators(parse, member)
+        .iter()
+        .any(|d| d.starts_with("Inject"))
+    {
+        let type_name = member
+            .child_by_field_name("type")
+            .and_then(|a| a.named_child(0))
+            .map(|t| parse.node_text(&t))
+            .unwrap_or_default();
+        return vec![field_dependency(name, type_name)];
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use std::path::Path;
+
+    fn parse(source: &str, lang: Language, file: &str) -> Vec<InjectedClass> {
+        let result = Parser::new()
+            .parse(source.as_bytes(), lang, Path::new(file))
+            .unwrap();
+        injected_classes(&result)
+    }
+
+    fn names(class: &InjectedClass) -> Vec<&str> {
+        class.dependencies.iter().map(|d| d.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_java_spring_constructor_and_fields() {
+        let source = r#"
+@Service
+public class OrderService {
+    @Autowired
+    private AuditLog audit;
+
+    public OrderService(OrderRepository orders, PaymentGateway payments,
+                        @Value("${retries}") int retries, String region) {
+    }
+}
+
+public class Money {
+    public Money(Currency currency, BigDecimal amount) {}
+}
+"#;
+        let classes = parse(source, Language::Java, "OrderService.java");
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].class_name, "OrderService");
+        assert_eq!(names(&classes[0]), vec!["orders", "payments", "audit"]);
+        assert_eq!(classes[0].dependencies[0].type_name, "OrderRepository");
+        assert_eq!(classes[0].dependencies[2].via, Injection::Field);
+    }
+
+    #[test]
+    fn test_csharp_widest_constructor() {
+        let source = r#"
+public class OrdersController : ControllerBase
+{
+    public OrdersController(IOrderService orders) {}
+    public OrdersController(IOrderService orders, ILogger<OrdersController> logger, string name, Guid id) {}
+}
+"#;
+        let classes = parse(source, Language::CSharp, "OrdersController.cs");
+        assert_eq!(classes.len(), 1);
+        assert_eq!(names(&classes[0]), vec!["orders", "logger"]);
+        assert_eq!(
+            classes[0].dependencies[1].type_name,
+            "ILogger<OrdersController>"
+        );
+    }
+
+    #[test]
+    fn test_typescript_parameter_properties_and_inject() {
+        let source = r#"
+@Injectable()
+export class UsersService {
+  private readonly http = inject(HttpClient);
+
+  constructor(
+    private readonly users: UsersRepository,
+    @Inject(CONFIG) config: AppConfig,
+    name: string,
+  ) {}
+}
+
+export class Point {
+  constructor(x: number, y: number, origin: Point) {}
+}
+"#;
+        let classes = parse(source, Language::TypeScript, "users.service.ts");
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].class_name, "UsersService");
+        assert_eq!(names(&classes[0]), vec!["users", "config", "http"]);
+        assert_eq!(classes[0].dependencies[2].type_name, "HttpClient");
+    }
+
+    #[test]
+    fn test_other_languages_are_ignored() {
+        let classes = parse(
+            "class A:\n    def __init__(self, b, c):\n        pass\n",
+            Language::Python,
+            "a.py",
+        );
+        assert!(classes.is_empty());
+    }
+}
@@ -5,6 +5,10 @@
 //! - Hub-like dependencies (excessive fan-in + fan-out)
 //! - Central connectors (high fan-in AND high fan-out coupling)
 //! - Unstable dependencies (stable components depending on unstable ones)
+//! - Scattered modules (files whose free functions form unrelated groups,
+//!   using the module-level LCOM from the cohesion analyzer)
+//! - Constructor over-injection (DI-managed classes in Java, C# and
+//!   TypeScript receiving too many dependencies, see [`injection`])
 //!
 //! Based on detection algorithms from Fontana et al. (2017) "Arcan".
 //!
@@ -13,21 +17,40 @@
 //! (LOC) metrics, whereas our Central Connector detection uses bidirectional coupling
 //! metrics (fan-in + fan-out). Both indicate components that may need decomposition,
 //! but they measure different aspects of over-centralization.
+//!
+//! Given the analysis of an earlier run (see [`Analyzer::with_previous`]),
+//! each smell is marked new or persisting, smells that no longer occur are
+//! listed as resolved, and the summary counts all three. A smell is the same
+//! across runs when its type and set of components match; severity and
+//! metrics may differ.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
 
 use chrono::Utc;
 use petgraph::algo::tarjan_scc;
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Language, Result};
-use crate::parser::{extract_imports, Parser};
+use crate::analyzers::cohesion::{module_metrics, ModuleMetrics};
+use crate::analyzers::prioritize::{prioritize, Prioritization, Prioritized};
+use crate::core::{
+    is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Result, SeverityCounts, Span,
+};
+use crate::git::LineOrigin;
+use crate::parser::extract_imports;
+
+mod injection;
+
+use injection::{injected_classes, InjectedClass};
+pub use injection::{InjectedDependency, Injection};
 
 /// Detection thresholds.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Thresholds {
     /// Fan-in + Fan-out threshold for hub detection.
     pub hub_threshold: usize,
@@ -41,6 +64,12 @@ pub struct Thresholds {
     pub stable_threshold: f64,
     /// I > this is considered unstable.
     pub unstable_threshold: f64,
+    /// Module LCOM at or above which a file is a scattered module.
+    pub scattered_module_lcom: u32,
+    /// Minimum free functions for scattered module detection.
+    pub scattered_module_min_functions: u32,
+    /// Injected dependencies above which a class is over-injected.
+    pub max_injected_dependencies: usize,
 }
 
 impl Default for Thresholds {
@@ -52,6 +81,9 @@ impl Default for Thresholds {
             instability_difference: 0.4,
             stable_threshold: 0.3,
             unstable_threshold: 0.7,
+            scattered_module_lcom: 4,
+            scattered_module_min_functions: 6,
+            max_injected_dependencies: 7,
         }
     }
 }
@@ -60,11 +92,14 @@ impl Default for Thresholds {
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     pub thresholds: Thresholds,
+    /// Overrides `[smells] max_injected_dependencies` from the project config.
+    pub max_injected_dependencies: Option<usize>,
 }
 
 /// Smells analyzer.
 pub struct Analyzer {
     config: Config,
+    previous: Option<Analysis>,
 }
 
 impl Default for Analyzer {
@@ -77,11 +112,15 @@ impl Analyzer {
     pub fn new() -> Self {
         Self {
             config: Config::default(),
+            previous: None,
         }
     }
 
     pub fn with_config(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            previous: None,
+        }
     }
 
     pub fn with_hub_threshold(mut self, threshold: usize) -> Self {
@@ -100,14 +139,38 @@ impl Analyzer {
         self
     }
 
+    pub fn with_max_injected_dependencies(mut self, max: usize) -> Self {
+        self.config.max_injected_dependencies = Some(max);
+        self
+    }
+
+    /// Compare against the analysis of an earlier run, marking each smell
+    /// new or persisting and listing the ones since resolved.
+    pub fn with_previous(mut self, previous: Analysis) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
     /// Analyze a repository for architectural smells.
     /// Uses ctx.read_file() to support both filesystem and git tree sources.
     pub fn analyze_repo(&self, ctx: &AnalysisContext<'_>) -> Result<Analysis> {
+        let mut thresholds = self.config.thresholds.clone();
+        thresholds.max_injected_dependencies = self
+            .config
+            .max_injected_dependencies
+            .unwrap_or(ctx.config.smells.max_injected_dependencies);
+
         // Phase 1: Get files from context (already filtered by language)
         let files: Vec<_> = ctx.files.iter().collect();
 
         // Phase 2: Parallel parsing - extract imports using content_source
-        let file_imports: Vec<(String, Vec<String>)> = files
+        #[allow(clippy::type_complexity)]
+        let parsed: Vec<(
+            String,
+            Vec<(String, Span, bool)>,
+            Option<ModuleMetrics>,
+            Vec<InjectedClass>,
+        )> = files
             .par_iter()
             .filter_map(|path| {
                 let rel_path = path
@@ -116,22 +179,45 @@ impl Analyzer {
                     .to_string_lossy()
                     .to_string();
 
-                // Read file via context (supports both filesystem and git tree)
-                let content = ctx.read_file(path).ok()?;
-                let lang = Language::detect(path)?;
-
-                // Parse with the content
-                let parser = Parser::new();
-                let parse_result = parser.parse(&content, lang, path).ok()?;
+                // Parse via context (shared parse cache, filesystem or git tree)
+                let parse_result = ctx.parsed(path).ok()?;
                 let imports = extract_imports(&parse_result);
-                let import_paths: Vec<String> = imports.into_iter().map(|imp| imp.path).collect();
+                let import_paths: Vec<(String, Span, bool)> = imports
+                    .into_iter()
+                    .map(|imp| (imp.path, imp.span, imp.dynamic))
+                    .collect();
+                // Test files group independent test functions by design,
+                // and wire up fixtures by hand rather than through a container.
+                let (module, injected) = if is_test_file(path) {
+                    (None, Vec::new())
+                } else {
+                    (
+                        module_metrics(&parse_result),
+                        injected_classes(&parse_result),
+                    )
+                };
 
-                Some((rel_path, import_paths))
+                Some((rel_path, import_paths, module, injected))
             })
             .collect();
 
+        #[allow(clippy::type_complexity)]
+        let mut file_imports: Vec<(String, Vec<(String, Span, bool)>)> =
+            Vec::with_capacity(parsed.len());
+        let mut modules: Vec<(String, ModuleMetrics)> = Vec::new();
+        let mut injected: Vec<(String, InjectedClass)> = Vec::new();
+        for (rel_path, import_paths, module, classes) in parsed {
+            if let Some(module) = module {
+                modules.push((rel_path.clone(), module));
+            }
+            injected.extend(classes.into_iter().map(|c| (rel_path.clone(), c)));
+            file_imports.push((rel_path, import_paths));
+        }
+
         // Phase 3: Build graph and lookup index
-        let mut graph: DiGraph<String, ()> = DiGraph::new();
+        // Edge weights are the spans of the import statements and whether
+        // they load the module at runtime (`import()`, `importlib`, ...).
+        let mut graph: DiGraph<String, (Span, bool)> = DiGraph::new();
         let mut node_indices: HashMap<String, NodeIndex> = HashMap::new();
 
         // Build index for O(1) lookups: stem -> list of full paths
@@ -162,10 +248,11 @@ impl Analyzer {
         for (from_file, imports) in &file_imports {
             let from_idx = node_indices[from_file];
 
-            for import in imports {
+            for (import, span, dynamic) in imports {
+                let weight = (*span, *dynamic);
                 // 1. Try exact path match
                 if let Some(&to_idx) = node_indices.get(import) {
-                    graph.add_edge(from_idx, to_idx, ());
+                    graph.add_edge(from_idx, to_idx, weight);
                     continue;
                 }
 
@@ -178,7 +265,7 @@ impl Analyzer {
                 if let Some(matches) = by_stem.get(&import_stem) {
                     if let Some(first_match) = matches.first() {
                         if let Some(&to_idx) = node_indices.get(first_match) {
-                            graph.add_edge(from_idx, to_idx, ());
+                            graph.add_edge(from_idx, to_idx, weight);
                             continue;
                         }
                     }
@@ -189,7 +276,7 @@ impl Analyzer {
                     if let Some(matches) = by_stem.get(last_segment) {
                         if let Some(first_match) = matches.first() {
                             if let Some(&to_idx) = node_indices.get(first_match) {
-                                graph.add_edge(from_idx, to_idx, ());
+                                graph.add_edge(from_idx, to_idx, weight);
                             }
                         }
                     }
@@ -231,17 +318,46 @@ impl Analyzer {
 
         // 1. Detect cyclic dependencies using Tarjan's SCC
         // Also detect self-loops (files importing themselves)
-        let sccs = tarjan_scc(&graph);
+        // Dynamic imports are how cycles get broken, so they only count when
+        // `[graph] dynamic_cycles` asks for it.
+        let dynamic_cycles = ctx.config.graph.dynamic_cycles;
+        let cycle_graph = graph.filter_map(
+            |_, path| Some(path.clone()),
+            |_, &(span, dynamic)| (dynamic_cycles || !dynamic).then_some(span),
+        );
+        let sccs = tarjan_scc(&cycle_graph);
         for scc in sccs {
-            let is_cycle = scc.len() > 1 || (scc.len() == 1 && graph.contains_edge(scc[0], scc[0]));
+            let is_cycle =
+                scc.len() > 1 || (scc.len() == 1 && cycle_graph.contains_edge(scc[0], scc[0]));
             if is_cycle {
                 let component_names: Vec<String> =
-                    scc.iter().map(|&idx| graph[idx].clone()).collect();
+                    scc.iter().map(|&idx| cycle_graph[idx].clone()).collect();
+                // The imports that keep the cycle closed.
+                let mut locations: Vec<SmellLocation> = scc
+                    .iter()
+                    .flat_map(|&idx| cycle_graph.edges_directed(idx, Direction::Outgoing))
+                    .filter(|edge| scc.contains(&edge.target()))
+                    .map(|edge| SmellLocation {
+                        file: cycle_graph[edge.source()].clone(),
+                        span: *edge.weight(),
+                    })
+                    .collect();
+                locations.sort_by(|a, b| {
+                    a.file
+                        .cmp(&b.file)
+                        .then(a.span.start_byte.cmp(&b.span.start_byte))
+                });
+                locations.dedup();
 
                 smells.push(Smell {
                     smell_type: SmellType::CyclicDependency,
                     severity: Severity::Critical,
                     components: component_names.clone(),
+                    locations,
+                    prioritization: None,
+                    introduced: None,
+                    changed: None,
+                    status: None,
                     description: format!(
                         "Cyclic dependency detected between {} components: {}",
                         scc.len(),
@@ -253,7 +369,10 @@ impl Analyzer {
                         fan_out: None,
                         instability: None,
                         cycle_length: Some(scc.len()),
+                        lcom: None,
+                        injected_dependencies: None,
                     },
+                    dependencies: Vec::new(),
                 });
             }
         }
@@ -265,6 +384,11 @@ impl Analyzer {
                     smell_type: SmellType::HubLikeDependency,
                     severity: Severity::High,
                     components: vec![cm.id.clone()],
+                    locations: Vec::new(),
+                    prioritization: None,
+                    introduced: None,
+                    changed: None,
+                    status: None,
                     description: format!(
                         "Hub-like component \"{}\" has {} connections (fan-in={}, fan-out={}, threshold={})",
                         cm.name,
@@ -279,7 +403,10 @@ impl Analyzer {
                         fan_out: Some(cm.fan_out),
                         instability: Some(cm.instability),
                         cycle_length: None,
+                        lcom: None,
+                        injected_dependencies: None,
                     },
+                    dependencies: Vec::new(),
                 });
             }
         }
@@ -294,6 +421,11 @@ impl Analyzer {
                     smell_type: SmellType::CentralConnector,
                     severity: Severity::Critical,
                     components: vec![cm.id.clone()],
+                    locations: Vec::new(),
+                    prioritization: None,
+                    introduced: None,
+                    changed: None,
+                    status: None,
                     description: format!(
                         "Central connector \"{}\" has excessive bidirectional coupling (fan-in={}, fan-out={})",
                         cm.name, cm.fan_in, cm.fan_out
@@ -304,7 +436,10 @@ impl Analyzer {
                         fan_out: Some(cm.fan_out),
                         instability: Some(cm.instability),
                         cycle_length: None,
+                        lcom: None,
+                        injected_dependencies: None,
                     },
+                    dependencies: Vec::new(),
                 });
             }
         }
@@ -362,6 +497,11 @@ impl Analyzer {
                             smell_type: SmellType::UnstableDependency,
                             severity: Severity::Medium,
                             components: vec![from_cm.id.clone(), to_cm.id.clone()],
+                            locations: Vec::new(),
+                            prioritization: None,
+                            introduced: None,
+                            changed: None,
+                            status: None,
                             description: format!(
                                 "Stable component \"{}\" (I={:.2}) depends on unstable component \"{}\" (I={:.2})",
                                 from_cm.name, from_cm.instability, to_cm.name, to_cm.instability
@@ -372,29 +512,196 @@ impl Analyzer {
                                 fan_out: None,
                                 instability: Some(diff),
                                 cycle_length: None,
+                                lcom: None,
+                                injected_dependencies: None,
                             },
+                            dependencies: Vec::new(),
                         });
                     }
                 }
             }
         }
 
+        // 5. Detect scattered modules (unrelated groups of free functions)
+        let lcom_threshold = self.config.thresholds.scattered_module_lcom;
+        for (rel_path, module) in &modules {
+            if module.functions < self.config.thresholds.scattered_module_min_functions
+                || module.lcom < lcom_threshold
+            {
+                continue;
+            }
+            let severity = if module.lcom >= lcom_threshold * 2 {
+                Severity::Medium
+            } else {
+                Severity::Low
+            };
+            let largest = module
+                .groups
+                .first()
+                .map(|g| g.iter().take(3).cloned().collect::<Vec<_>>().join(", "))
+                .unwrap_or_default();
+            smells.push(Smell {
+                smell_type: SmellType::ScatteredModule,
+                severity,
+                components: vec![rel_path.clone()],
+                locations: Vec::new(),
+                prioritization: None,
+                introduced: None,
+                changed: None,
+                status: None,
+                description: format!(
+                    "Module \"{}\" has {} unrelated function groups across {} functions (shared identifier ratio {:.2})",
+                    rel_path, module.lcom, module.functions, module.shared_identifier_ratio
+                ),
+                suggestion: format!(
+                    "Split unrelated functions into focused modules, starting with the largest group ({})",
+                    largest
+                ),
+                metrics: SmellMetrics {
+                    lcom: Some(module.lcom),
+                    ..Default::default()
+                },
+                dependencies: Vec::new(),
+            });
+        }
+
+        // 6. Detect constructor over-injection (framework-mediated coupling)
+        let max_injected = thresholds.max_injected_dependencies;
+        for (rel_path, class) in injected {
+            let count = class.dependencies.len();
+            if count <= max_injected {
+                continue;
+            }
+            let severity = if count > max_injected * 2 {
+                Severity::High
+            } else {
+                Severity::Medium
+            };
+            smells.push(Smell {
+                smell_type: SmellType::ConstructorOverInjection,
+                severity,
+                components: vec![rel_path.clone()],
+                locations: vec![SmellLocation {
+                    file: rel_path.clone(),
+                    span: class.span,
+                }],
+                prioritization: None,
+                introduced: None,
+                changed: None,
+                status: None,
+                description: format!(
+                    "Class \"{}\" in \"{}\" receives {} injected dependencies (threshold={})",
+                    class.class_name, rel_path, count, max_injected
+                ),
+                suggestion: "Split the class by responsibility or group related collaborators behind a facade service".to_string(),
+                metrics: SmellMetrics {
+                    injected_dependencies: Some(count),
+                    ..Default::default()
+                },
+                dependencies: class.dependencies,
+            });
+        }
+
+        if let Some(blame) = &ctx.blame {
+            smells.par_iter_mut().for_each(|smell| {
+                smell.introduced = smell
+                    .locations
+                    .iter()
+                    .filter_map(|loc| {
+                        blame.latest_origin(
+                            &ctx.root.join(&loc.file),
+                            loc.span.start_line,
+                            loc.span.end_line,
+                        )
+                    })
+                    .max_by_key(|origin| origin.date);
+            });
+        }
+        if let Some(changed) = &ctx.changed_lines {
+            for smell in &mut smells {
+                let touched = if smell.locations.is_empty() {
+                    smell
+                        .components
+                        .iter()
+                        .any(|file| changed.contains(Path::new(file)))
+                } else {
+                    smell.locations.iter().any(|loc| {
+                        changed.touches(
+                            Path::new(&loc.file),
+                            loc.span.start_line,
+                            loc.span.end_line,
+                        )
+                    })
+                };
+                smell.changed = Some(touched);
+            }
+        }
+
         // Sort smells by severity (critical first)
         smells.sort_by(|a, b| b.severity.weight().cmp(&a.severity.weight()));
+        prioritize(ctx, &mut smells)?;
+
+        let resolved = match &self.previous {
+            Some(previous) => compare_with_previous(&mut smells, &previous.smells),
+            None => Vec::new(),
+        };
 
         // Calculate summary
-        let summary = calculate_summary(&smells, &components);
+        let mut summary = calculate_summary(&smells, &components);
+        if self.previous.is_some() {
+            summary.trend = Some(Trend::count(&smells, &resolved));
+        }
 
         Ok(Analysis {
             generated_at: Utc::now().to_rfc3339(),
             smells,
+            resolved,
             components,
             summary,
-            thresholds: self.config.thresholds.clone(),
+            thresholds,
         })
     }
 }
 
+/// Identity of a smell across runs: its type and set of components, so a
+/// cycle found from a different starting file is still the same cycle.
+fn smell_key(smell: &Smell) -> (SmellType, BTreeSet<&str>) {
+    // Older names count as the current ones, as in the summary omen:ignore
+    let smell_type = match smell.smell_type {
+        SmellType::GodComponent | SmellType::GodClass => SmellType::CentralConnector,
+        SmellType::HubLikeDependency => SmellType::Hub,
+        other => other,
+    };
+    (
+        smell_type,
+        smell.components.iter().map(String::as_str).collect(),
+    )
+}
+
+/// Mark `smells` new or persisting against `previous`, returning the
+/// previous smells no longer found.
+fn compare_with_previous(smells: &mut [Smell], previous: &[Smell]) -> Vec<Smell> {
+    let before: HashSet<_> = previous.iter().map(smell_key).collect();
+    let now: HashSet<_> = smells.iter().map(smell_key).collect();
+    let resolved = previous
+        .iter()
+        .filter(|smell| !now.contains(&smell_key(smell)))
+        .map(|smell| Smell {
+            status: Some(SmellStatus::Resolved),
+            ..smell.clone()
+        })
+        .collect();
+
+    for smell in smells.iter_mut() {
+        smell.status = Some(if before.contains(&smell_key(smell)) {
+            SmellStatus::Persisting
+        } else {
+            SmellStatus::New
+        });
+    }
+    resolved
+}
+
 impl AnalyzerTrait for Analyzer {
     type Output = Analysis;
 
@@ -442,6 +749,8 @@ fn calculate_summary(smells: &[Smell], components: &[ComponentMetrics]) -> Summa
             SmellType::CentralConnector => summary.central_connector_count += 1,
             // Backward compatibility with old smell types omen:ignore
             SmellType::GodComponent | SmellType::GodClass => summary.central_connector_count += 1,
+            SmellType::ScatteredModule => summary.scattered_module_count += 1,
+            SmellType::ConstructorOverInjection => summary.over_injection_count += 1,
             SmellType::FeatureEnvy => {}
         }
 
@@ -451,6 +760,7 @@ fn calculate_summary(smells: &[Smell], components: &[ComponentMetrics]) -> Summa
             Severity::Medium => summary.medium_count += 1,
             Severity::Low => {}
         }
+        summary.severity_counts.record(smell.severity.into());
     }
 
     if !components.is_empty() {
@@ -466,6 +776,9 @@ fn calculate_summary(smells: &[Smell], components: &[ComponentMetrics]) -> Summa
 pub struct Analysis {
     pub generated_at: String,
     pub smells: Vec<Smell>,
+    /// Smells of the previous run no longer found.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolved: Vec<Smell>,
     pub components: Vec<ComponentMetrics>,
     pub summary: Summary,
     pub thresholds: Thresholds,
@@ -477,9 +790,50 @@ pub struct Smell {
     pub smell_type: SmellType,
     pub severity: Severity,
     pub components: Vec<String>,
+    /// Source ranges behind the smell, e.g. the imports that form a cycle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<SmellLocation>,
+    /// Rank annotation when `prioritize_by` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prioritization: Option<Prioritization>,
+    /// Most recent commit touching `locations`, when `[git] blame` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<LineOrigin>,
+    /// Whether `locations` (or, without them, the component files) overlap
+    /// lines changed since `--base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed: Option<bool>,
+    /// New, persisting, or resolved since the previous run, when compared
+    /// against one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<SmellStatus>,
     pub description: String,
     pub suggestion: String,
     pub metrics: SmellMetrics,
+    /// Injected dependencies of the class (constructor over-injection only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<InjectedDependency>,
+}
+
+impl Prioritized for Smell {
+    fn files(&self) -> Vec<&str> {
+        self.components.iter().map(String::as_str).collect()
+    }
+
+    fn prioritization(&self) -> Option<&Prioritization> {
+        self.prioritization.as_ref()
+    }
+
+    fn set_prioritization(&mut self, prioritization: Prioritization) {
+        self.prioritization = Some(prioritization);
+    }
+}
+
+/// A source range within a component. omen:ignore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmellLocation {
+    pub file: String,
+    pub span: Span,
 }
 
 /// Quantitative metrics about a smell. omen:ignore
@@ -493,6 +847,21 @@ pub struct SmellMetrics {
     pub instability: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cycle_length: Option<usize>,
+    /// Module LCOM (scattered modules only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lcom: Option<u32>,
+    /// Injected dependency count (constructor over-injection only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub injected_dependencies: Option<usize>,
+}
+
+/// How a smell compares to the previous run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmellStatus {
+    New,
+    Persisting,
+    Resolved,
 }
 
 /// Type of architectural smell. omen:ignore
@@ -506,6 +875,10 @@ pub enum SmellType {
     /// High bidirectional coupling (high fan-in AND fan-out).
     /// Note: This differs from Arcan's "God Component" which uses LOC metrics.
     CentralConnector,
+    /// File whose free functions form unrelated groups (module-level LCOM).
+    ScatteredModule,
+    /// DI-managed class receiving more dependencies than the threshold.
+    ConstructorOverInjection,
     // Backward compatibility aliases
     #[serde(alias = "GodComponent")]
     GodComponent,
@@ -522,6 +895,17 @@ pub enum Severity {
     Low,
 }
 
+impl From<Severity> for crate::core::Severity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Critical => Self::Critical,
+            Severity::High => Self::High,
+            Severity::Medium => Self::Medium,
+            Severity::Low => Self::Low,
+        }
+    }
+}
+
 impl Severity {
     pub fn weight(&self) -> u32 {
         match self {
@@ -554,11 +938,42 @@ pub struct Summary {
     pub hub_count: usize,
     pub unstable_count: usize,
     pub central_connector_count: usize,
+    #[serde(default)]
+    pub scattered_module_count: usize,
+    #[serde(default)]
+    pub over_injection_count: usize,
     pub critical_count: usize,
     pub high_count: usize,
     pub medium_count: usize,
     pub total_components: usize,
     pub average_instability: f64,
+    #[serde(default)]
+    pub severity_counts: SeverityCounts,
+    /// Smell churn against the previous run, when compared against one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+}
+
+/// Smells new, persisting, and resolved since the previous run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trend {
+    pub new: usize,
+    pub persisting: usize,
+    pub resolved: usize,
+}
+
+impl Trend {
+    fn count(smells: &[Smell], resolved: &[Smell]) -> Self {
+        let new = smells
+            .iter()
+            .filter(|s| s.status == Some(SmellStatus::New))
+            .count();
+        Self {
+            new,
+            persisting: smells.len() - new,
+            resolved: resolved.len(),
+        }
+    }
 }
 
 // Keep backward compatibility with old struct name
@@ -590,6 +1005,9 @@ mod tests {
         assert!((thresholds.instability_difference - 0.4).abs() < 0.001);
         assert!((thresholds.stable_threshold - 0.3).abs() < 0.001);
         assert!((thresholds.unstable_threshold - 0.7).abs() < 0.001);
+        assert_eq!(thresholds.scattered_module_lcom, 4);
+        assert_eq!(thresholds.scattered_module_min_functions, 6);
+        assert_eq!(thresholds.max_injected_dependencies, 7);
     }
 
     #[test]
@@ -674,17 +1092,29 @@ mod tests {
                 smell_type: SmellType::CyclicDependency,
                 severity: Severity::Critical,
                 components: vec!["a".to_string(), "b".to_string()],
+                locations: Vec::new(),
+                prioritization: None,
+                introduced: None,
+                changed: None,
+                status: None,
                 description: String::new(),
                 suggestion: String::new(),
                 metrics: SmellMetrics::default(),
+                dependencies: Vec::new(),
             },
             Smell {
                 smell_type: SmellType::HubLikeDependency,
                 severity: Severity::High,
                 components: vec!["c".to_string()],
+                locations: Vec::new(),
+                prioritization: None,
+                introduced: None,
+                changed: None,
+                status: None,
                 description: String::new(),
                 suggestion: String::new(),
                 metrics: SmellMetrics::default(),
+                dependencies: Vec::new(),
             },
         ];
 
@@ -715,6 +1145,12 @@ mod tests {
         assert_eq!(summary.hub_count, 1);
         assert_eq!(summary.critical_count, 1);
         assert_eq!(summary.high_count, 1);
+        assert_eq!(
+            summary
+                .severity_counts
+                .at_least(crate::core::Severity::High),
+            2
+        );
         assert_eq!(summary.total_components, 2);
         assert!((summary.average_instability - 0.65).abs() < 0.01);
     }
@@ -742,12 +1178,18 @@ mod tests {
             smell_type: SmellType::CyclicDependency,
             severity: Severity::Critical,
             components: vec!["a.rs".to_string(), "b.rs".to_string()],
+            locations: Vec::new(),
+            prioritization: None,
+            introduced: None,
+            changed: None,
+            status: None,
             description: "Test cycle".to_string(),
             suggestion: "Break it".to_string(),
             metrics: SmellMetrics {
                 cycle_length: Some(2),
                 ..Default::default()
             },
+            dependencies: Vec::new(),
         };
 
         let json = serde_json::to_string(&smell).unwrap();
@@ -764,6 +1206,7 @@ mod tests {
         let analysis = Analysis {
             generated_at: "2024-01-01T00:00:00Z".to_string(),
             smells: vec![],
+            resolved: vec![],
             components: vec![],
             summary: Summary::default(),
             thresholds: Thresholds::default(),
@@ -851,6 +1294,190 @@ mod tests {
         assert!(found_cycle, "Multi-node cycle should be detected");
     }
 
+    #[test]
+    fn test_scattered_module_detection() {
+        use crate::config::Config;
+        use crate::core::{AnalysisContext, FileSet};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source: String = (0..6)
+            .map(|i| format!("export function helper{i}(x: number) {{ return x + {i}; }}\n"))
+            .collect();
+        std::fs::write(temp_dir.path().join("misc.ts"), source).unwrap();
+        std::fs::write(
+            temp_dir.path().join("small.ts"),
+            "export function a() { return 1; }\nexport function b() { return 2; }\n",
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let file_set = FileSet::from_path(temp_dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(temp_dir.path()));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let scattered: Vec<_> = analysis
+            .smells
+            .iter()
+            .filter(|s| s.smell_type == SmellType::ScatteredModule)
+            .collect();
+        assert_eq!(scattered.len(), 1, "only files above the function floor");
+        assert_eq!(scattered[0].components, vec!["misc.ts".to_string()]);
+        assert_eq!(scattered[0].metrics.lcom, Some(6));
+        assert_eq!(scattered[0].severity, Severity::Low);
+        assert_eq!(analysis.summary.scattered_module_count, 1);
+    }
+
+    #[test]
+    fn test_constructor_over_injection_detection() {
+        use crate::config::Config;
+        use crate::core::{AnalysisContext, FileSet};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let params: Vec<String> = (0..5).map(|i| format!("Dep{i} dep{i}")).collect();
+        std::fs::write(
+            temp_dir.path().join("Big.java"),
+            format!(
+                "@Service\npublic class Big {{\n    public Big({}, String name) {{}}\n}}\n",
+                params.join(", ")
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("Small.java"),
+            "@Service\npublic class Small {\n    public Small(Dep0 dep0) {}\n}\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.smells.max_injected_dependencies = 4;
+        let file_set = FileSet::from_path(temp_dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(temp_dir.path()));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let over: Vec<_> = analysis
+            .smells
+            .iter()
+            .filter(|s| s.smell_type == SmellType::ConstructorOverInjection)
+            .collect();
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].components, vec!["Big.java".to_string()]);
+        assert_eq!(over[0].metrics.injected_dependencies, Some(5));
+        assert_eq!(over[0].dependencies.len(), 5);
+        assert_eq!(over[0].dependencies[0].type_name, "Dep0");
+        assert_eq!(over[0].severity, Severity::Medium);
+        assert_eq!(over[0].locations[0].span.start_line, 3);
+        assert_eq!(analysis.summary.over_injection_count, 1);
+        assert_eq!(analysis.thresholds.max_injected_dependencies, 4);
+
+        // The builder override wins over the project config.
+        let analysis = Analyzer::new()
+            .with_max_injected_dependencies(5)
+            .analyze(&ctx)
+            .unwrap();
+        assert_eq!(analysis.summary.over_injection_count, 0);
+    }
+
+    #[test]
+    fn test_previous_analysis_marks_smell_status() {
+        use crate::config::Config;
+        use crate::core::{AnalysisContext, FileSet};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, other: &str| {
+            std::fs::write(
+                temp_dir.path().join(format!("{name}.ts")),
+                format!("import {{ {other} }} from './{other}';\nexport const {name} = 1;\n"),
+            )
+            .unwrap();
+        };
+        write("a", "b");
+        write("b", "a");
+        let config = Config::default();
+        let analyze = |analyzer: Analyzer| {
+            let file_set = FileSet::from_path(temp_dir.path(), &config).unwrap();
+            let ctx = AnalysisContext::new(&file_set, &config, Some(temp_dir.path()));
+            analyzer.analyze(&ctx).unwrap()
+        };
+
+        let mut previous = analyze(Analyzer::new());
+        assert!(previous.smells.iter().all(|s| s.status.is_none()));
+        assert_eq!(previous.summary.trend, None);
+        let mut gone = previous.smells[0].clone();
+        gone.components = vec!["x.ts".to_string(), "y.ts".to_string()];
+        previous.smells.push(gone);
+
+        write("c", "d");
+        write("d", "c");
+        let analysis = analyze(Analyzer::new().with_previous(previous));
+
+        let status = |file: &str| {
+            analysis
+                .smells
+                .iter()
+                .find(|s| s.components.iter().any(|c| c == file))
+                .and_then(|s| s.status)
+        };
+        assert_eq!(status("a.ts"), Some(SmellStatus::Persisting));
+        assert_eq!(status("c.ts"), Some(SmellStatus::New));
+        assert_eq!(analysis.resolved.len(), 1);
+        assert_eq!(analysis.resolved[0].components, ["x.ts", "y.ts"]);
+        assert_eq!(analysis.resolved[0].status, Some(SmellStatus::Resolved));
+        assert_eq!(
+            analysis.summary.trend,
+            Some(Trend {
+                new: 1,
+                persisting: 1,
+                resolved: 1,
+            })
+        );
+
+        let json = serde_json::to_value(&analysis).unwrap();
+        assert_eq!(json["resolved"][0]["status"], "resolved");
+        assert_eq!(json["summary"]["trend"]["new"], 1);
+    }
+
+    #[test]
+    fn test_cyclic_dependency_locations_point_at_imports() {
+        use crate::config::Config;
+        use crate::core::{AnalysisContext, FileSet};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("a.ts"),
+            "import { b } from './b';\nexport const a = b + 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("b.ts"),
+            "// ß\nimport { a } from './a';\nexport const b = a + 1;\n",
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let file_set = FileSet::from_path(temp_dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(temp_dir.path()));
+        let analysis = Analyzer::new().analyze(&ctx).unwrap();
+
+        let cycle = analysis
+            .smells
+            .iter()
+            .find(|s| s.smell_type == SmellType::CyclicDependency)
+            .expect("cycle detected");
+        let locations: Vec<(&str, u32, u32, u32)> = cycle
+            .locations
+            .iter()
+            .map(|l| {
+                (
+                    l.file.as_str(),
+                    l.span.start_line,
+                    l.span.start_column,
+                    l.span.end_column,
+                )
+            })
+            .collect();
+        assert_eq!(locations, vec![("a.ts", 1, 1, 25), ("b.ts", 2, 1, 25)]);
+    }
+
     #[test]
     fn test_analyzer_uses_content_source_for_historical_commits() {
         use crate::config::Config;
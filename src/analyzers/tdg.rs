@@ -1520,12 +1520,16 @@ fn risky() {
                 complexity_percentile: 85.0,
                 commits: 42,
                 avg_complexity: 12.0,
+                off_hours_ratio: None,
             }],
+            forecast: vec![],
             summary: hs::AnalysisSummary {
                 total_hotspots: 1,
                 critical_count: 0,
                 high_count: 1,
+                ..Default::default()
             },
+            parameters: Default::default(),
         };
 
         let temporal_analysis = tp::Analysis {
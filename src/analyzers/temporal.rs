@@ -14,15 +14,33 @@
 //! Uses a symmetric formula: `cochanges / max(commits_a, commits_b)`
 //! - 0.5 threshold for "strong" coupling is a heuristic
 //! - Min cochanges (default 3) filters statistical noise
+//!
+//! # Noise Filtering
+//!
+//! Commits touching more than `max_commit_files` files (bulk renames,
+//! formatter runs) are ignored. With `weight_by_commit_size`, a co-change
+//! from a commit touching N files counts `1 / (N - 1)` toward strength, so
+//! each file's co-changes in one commit add up to one. `exclude_renames`
+//! leaves renamed files out of co-changes.
+//!
+//! # Logical Changes
+//!
+//! One change is often split across commits ("fix review comments", a
+//! follow-up for the same ticket). With `group_by = "ticket"`, commits whose
+//! messages name the same ticket ID count as one change; with `"author"`, so
+//! do one author's commits within `group_window_minutes` of each other.
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::path::Path;
 
 use chrono::Utc;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
-use crate::git::GitRepo;
+use crate::config::{CommitGrouping, TemporalConfig};
+use crate::core::{is_test_file, AnalysisContext, Analyzer as AnalyzerTrait, Error, Result};
+use crate::git::{ChangeType, Commit, GitRepo, PathFilter};
 
 /// Default minimum number of co-changes to consider files coupled.
 pub const DEFAULT_MIN_COCHANGES: u32 = 3;
@@ -95,56 +113,50 @@ impl Analyzer {
 
     /// Analyzes temporal coupling using an existing git repo.
     fn analyze_with_git(&self, git_repo: &GitRepo, _root: &Path) -> Result<Analysis> {
-        self.analyze_with_git_filtered(git_repo, _root, false, &[])
+        let filters = TemporalConfig {
+            exclude_tests: false,
+            ..TemporalConfig::default()
+        };
+        self.analyze_with_git_filtered(git_repo, _root, &filters, &PathFilter::default())
     }
 
-    /// Analyzes temporal coupling with optional test file exclusion.
+    /// Analyzes temporal coupling with the given noise filters.
+    ///
+    /// `path_filter` is pushed down into `git log` so history outside the
+    /// analysis root and glob filters is never diffed.
     fn analyze_with_git_filtered(
         &self,
         git_repo: &GitRepo,
         _root: &Path,
-        exclude_tests: bool,
-        exclude_patterns: &[String],
+        filters: &TemporalConfig,
+        path_filter: &PathFilter,
     ) -> Result<Analysis> {
-        let exclude_globs = if !exclude_patterns.is_empty() {
-            let mut builder = globset::GlobSetBuilder::new();
-            for pat in exclude_patterns {
-                if let Ok(g) = globset::Glob::new(pat) {
-                    builder.add(g);
-                }
-            }
-            builder.build().ok()
-        } else {
-            None
-        };
-
         // Format since for git log (git accepts "N days" format)
         let since_str = format!("{} days", self.config.days);
 
         // Get commit log with file changes
-        let commits = git_repo.log_with_stats(Some(&since_str), None)?;
+        let commits = git_repo.log_with_stats_filtered(Some(&since_str), None, path_filter)?;
+        let commits = group_commits(commits, filters)?;
+        Ok(self.analyze_commits(&commits, filters))
+    }
 
-        // Track co-changes: normalized pair -> count
-        let mut cochanges: HashMap<FilePair, u32> = HashMap::new();
+    /// Computes couplings from an already-read commit log.
+    pub(crate) fn analyze_commits(&self, commits: &[Commit], filters: &TemporalConfig) -> Analysis {
+        // Track co-changes: normalized pair -> (count, weighted count)
+        let mut cochanges: HashMap<FilePair, (u32, f64)> = HashMap::new();
         // Track individual file commits: file -> count
         let mut file_commits: HashMap<String, u32> = HashMap::new();
 
-        for commit in &commits {
+        for commit in commits {
+            if !counts_toward_coupling(commit, filters.max_commit_files) {
+                continue;
+            }
             let changed_files: Vec<String> = commit
                 .files
                 .iter()
+                .filter(|f| !(filters.exclude_renames && f.change_type == ChangeType::Renamed))
                 .map(|f| f.path.to_string_lossy().to_string())
-                .filter(|f| {
-                    if exclude_tests && is_test_file(f) {
-                        return false;
-                    }
-                    if let Some(ref gs) = exclude_globs {
-                        if gs.is_match(f) {
-                            return false;
-                        }
-                    }
-                    true
-                })
+                .filter(|f| !(filters.exclude_tests && is_test_file(f)))
                 .collect();
 
             // Update individual file commit counts
@@ -152,18 +164,19 @@ impl Analyzer {
                 *file_commits.entry(file.clone()).or_insert(0) += 1;
             }
 
-            // Skip mega-commits (e.g. bulk renames, formatter runs).
-            // Pairing all N files is O(N^2) and these commits rarely
-            // indicate meaningful coupling.
-            if changed_files.len() > 100 {
-                continue;
-            }
+            let weight = if filters.weight_by_commit_size && changed_files.len() > 1 {
+                1.0 / (changed_files.len() - 1) as f64
+            } else {
+                1.0
+            };
 
             // Record co-changes for all pairs
             for i in 0..changed_files.len() {
                 for j in (i + 1)..changed_files.len() {
                     let pair = FilePair::new(&changed_files[i], &changed_files[j]);
-                    *cochanges.entry(pair).or_insert(0) += 1;
+                    let entry = cochanges.entry(pair).or_insert((0, 0.0));
+                    entry.0 += 1;
+                    entry.1 += weight;
                 }
             }
         }
@@ -171,12 +184,11 @@ impl Analyzer {
         // Build coupling results, filtering by minimum threshold
         let mut couplings: Vec<FileCoupling> = cochanges
             .into_iter()
-            .filter(|(_, count)| *count >= self.config.min_cochanges)
-            .map(|(pair, cochange_count)| {
+            .filter(|(_, (count, _))| *count >= self.config.min_cochanges)
+            .map(|(pair, (cochange_count, weighted))| {
                 let commits_a = file_commits.get(&pair.a).copied().unwrap_or(0);
                 let commits_b = file_commits.get(&pair.b).copied().unwrap_or(0);
-                let coupling_strength =
-                    calculate_coupling_strength(cochange_count, commits_a, commits_b);
+                let coupling_strength = weighted_coupling_strength(weighted, commits_a, commits_b);
 
                 FileCoupling {
                     file_a: pair.a,
@@ -200,13 +212,14 @@ impl Analyzer {
         let total_files = file_commits.len();
         let summary = calculate_summary(&couplings, total_files);
 
-        Ok(Analysis {
+        Analysis {
             generated_at: generated_at.to_rfc3339(),
             period_days: self.config.days,
             min_cochanges: self.config.min_cochanges,
+            grouping: filters.group_by,
             couplings,
             summary,
-        })
+        }
     }
 }
 
@@ -231,10 +244,14 @@ impl AnalyzerTrait for Analyzer {
             .as_ref()
             .ok_or_else(|| Error::git("Temporal coupling analysis requires git history"))?;
 
-        let git_repo = GitRepo::open(git_path)?;
-        let exclude_tests = ctx.config.temporal.exclude_tests;
-        let exclude_patterns = &ctx.config.exclude_patterns;
-        self.analyze_with_git_filtered(&git_repo, ctx.root, exclude_tests, exclude_patterns)
+        let git_repo = GitRepo::open(git_path)?
+            .at_ref(ctx.config.git.at_ref.as_deref())
+            .with_identities(&ctx.config.git);
+        let path_filter = PathFilter::from_context(ctx, git_repo.root());
+        let since = format!("{} days", self.config.days);
+        let commits = ctx.log_with_stats(&git_repo, Some(&since), &path_filter)?;
+        let commits = group_commits(commits, &ctx.config.temporal)?;
+        Ok(self.analyze_commits(&commits, &ctx.config.temporal))
     }
 }
 
@@ -262,14 +279,94 @@ impl FilePair {
     }
 }
 
+/// Merge the commits in `commits` (newest first) that make up one logical
+/// change under `config.group_by`.
+///
+/// A merged change keeps its newest commit's metadata and lists each file
+/// once, with the additions and deletions of all its commits. Commits
+/// without a ticket ID stay on their own under ticket grouping.
+pub(crate) fn group_commits(commits: Vec<Commit>, config: &TemporalConfig) -> Result<Vec<Commit>> {
+    let mut groups: Vec<Commit> = Vec::new();
+    match config.group_by {
+        CommitGrouping::Commit => return Ok(commits),
+        CommitGrouping::Ticket => {
+            let pattern = Regex::new(&config.ticket_pattern)
+                .map_err(|e| Error::config(format!("invalid temporal ticket_pattern: {e}")))?;
+            let mut by_ticket: HashMap<String, usize> = HashMap::new();
+            for commit in commits {
+                let Some(ticket) = pattern.find(&commit.message) else {
+                    groups.push(commit);
+                    continue;
+                };
+                match by_ticket.entry(ticket.as_str().to_string()) {
+                    Entry::Occupied(group) => merge_commit(&mut groups[*group.get()], commit),
+                    Entry::Vacant(group) => {
+                        group.insert(groups.len());
+                        groups.push(commit);
+                    }
+                }
+            }
+        }
+        CommitGrouping::Author => {
+            let window = config.group_window_minutes.saturating_mul(60) as i64;
+            // Author -> (their latest group, its oldest commit's timestamp)
+            let mut open: HashMap<String, (usize, i64)> = HashMap::new();
+            for commit in commits {
+                let author = if commit.email.is_empty() {
+                    commit.author.clone()
+                } else {
+                    commit.email.to_lowercase()
+                };
+                let timestamp = commit.timestamp;
+                match open.get_mut(&author) {
+                    Some((group, oldest)) if *oldest - timestamp <= window => {
+                        *oldest = timestamp;
+                        merge_commit(&mut groups[*group], commit);
+                    }
+                    _ => {
+                        open.insert(author, (groups.len(), timestamp));
+                        groups.push(commit);
+                    }
+                }
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// Add `commit`'s file changes to `group`.
+fn merge_commit(group: &mut Commit, commit: Commit) {
+    for change in commit.files {
+        match group.files.iter_mut().find(|f| f.path == change.path) {
+            Some(existing) => {
+                existing.additions += change.additions;
+                existing.deletions += change.deletions;
+            }
+            None => group.files.push(change),
+        }
+    }
+}
+
+/// Whether `commit` is small enough to count toward coupling. Pairing all N
+/// files of a mega-commit is O(N^2), and such commits rarely indicate
+/// meaningful coupling.
+pub(crate) fn counts_toward_coupling(commit: &Commit, max_commit_files: usize) -> bool {
+    commit.files.len() <= max_commit_files
+}
+
 /// Calculates the coupling strength between two files.
 /// Strength = cochanges / max(commits_a, commits_b), capped at 1.0.
 pub fn calculate_coupling_strength(cochanges: u32, commits_a: u32, commits_b: u32) -> f64 {
+    weighted_coupling_strength(f64::from(cochanges), commits_a, commits_b)
+}
+
+/// [`calculate_coupling_strength`] for co-changes weighted by commit size.
+fn weighted_coupling_strength(cochanges: f64, commits_a: u32, commits_b: u32) -> f64 {
     let max_commits = commits_a.max(commits_b);
     if max_commits == 0 {
         return 0.0;
     }
-    let strength = f64::from(cochanges) / f64::from(max_commits);
+    let strength = cochanges / f64::from(max_commits);
     strength.min(1.0)
 }
 
@@ -317,6 +414,9 @@ pub struct Analysis {
     pub period_days: u32,
     /// Minimum co-change threshold used.
     pub min_cochanges: u32,
+    /// How commits were grouped into logical changes.
+    #[serde(default)]
+    pub grouping: CommitGrouping,
     /// File couplings found, sorted by strength descending.
     pub couplings: Vec<FileCoupling>,
     /// Summary statistics.
@@ -355,54 +455,134 @@ pub struct Summary {
     pub total_files_analyzed: usize,
 }
 
-/// Returns true if the path looks like a test file.
-fn is_test_file(path: &str) -> bool {
-    let lower = path.to_lowercase();
-    let parts: Vec<&str> = lower.split('/').collect();
-
-    // Directory-based patterns
-    for part in &parts {
-        match *part {
-            "test" | "tests" | "spec" | "specs" | "__tests__" | "__mocks__" | "test_helpers"
-            | "testdata" | "fixtures" => return true,
-            _ => {}
-        }
-        // Java/Maven convention: src/test/...
-        if *part == "src" {
-            if let Some(next_idx) = parts.iter().position(|p| *p == *part) {
-                if parts.get(next_idx + 1) == Some(&"test") {
-                    return true;
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::FileChange;
+
+    fn commit(files: &[(&str, ChangeType)]) -> Commit {
+        Commit {
+            sha: "abc".to_string(),
+            author: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            timestamp: 1_700_000_000,
+            message: "change".to_string(),
+            files: files
+                .iter()
+                .map(|(path, change_type)| FileChange {
+                    path: path.into(),
+                    additions: 1,
+                    deletions: 0,
+                    change_type: *change_type,
+                })
+                .collect(),
         }
     }
 
-    // Filename-based patterns
-    if let Some(filename) = parts.last() {
-        // _test.go, _test.rb, etc.
-        if filename.contains("_test.") || filename.contains("_spec.") {
-            return true;
-        }
-        // test_*.py
-        if filename.starts_with("test_") {
-            return true;
-        }
-        // *.test.ts, *.spec.js, etc.
-        let dot_parts: Vec<&str> = filename.split('.').collect();
-        if dot_parts.len() >= 3 {
-            let second_last = dot_parts[dot_parts.len() - 2];
-            if second_last == "test" || second_last == "spec" {
-                return true;
-            }
-        }
+    fn strength(analysis: &Analysis, a: &str, b: &str) -> Option<f64> {
+        analysis
+            .couplings
+            .iter()
+            .find(|c| c.file_a == a && c.file_b == b)
+            .map(|c| c.coupling_strength)
     }
 
-    false
-}
+    #[test]
+    fn test_noise_filters() {
+        use ChangeType::{Modified, Renamed};
+        let pair = commit(&[("a.rs", Modified), ("b.rs", Modified)]);
+        let triple = commit(&[("a.rs", Modified), ("b.rs", Modified), ("c.rs", Modified)]);
+        let analyzer = Analyzer::new();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // The three-file commit is over the limit, so it doesn't dilute a/b.
+        let commits = [pair.clone(), pair.clone(), pair.clone(), triple.clone()];
+        let filters = TemporalConfig {
+            max_commit_files: 2,
+            ..TemporalConfig::default()
+        };
+        let analysis = analyzer.analyze_commits(&commits, &filters);
+        assert_eq!(strength(&analysis, "a.rs", "b.rs"), Some(1.0));
+        assert_eq!(analysis.summary.total_files_analyzed, 2);
+
+        // Each co-change from a three-file commit counts 1/2.
+        let commits = [triple.clone(), triple.clone(), triple];
+        let unweighted = analyzer.analyze_commits(&commits, &TemporalConfig::default());
+        assert_eq!(strength(&unweighted, "a.rs", "b.rs"), Some(1.0));
+        let filters = TemporalConfig {
+            weight_by_commit_size: true,
+            ..TemporalConfig::default()
+        };
+        let weighted = analyzer.analyze_commits(&commits, &filters);
+        assert_eq!(strength(&weighted, "a.rs", "b.rs"), Some(0.5));
+        assert_eq!(weighted.couplings[0].cochange_count, 3);
+
+        // Renamed files are left out of co-changes.
+        let renamed = commit(&[("a.rs", Modified), ("b.rs", Renamed)]);
+        let commits = [renamed.clone(), renamed.clone(), renamed];
+        let filters = TemporalConfig {
+            exclude_renames: true,
+            ..TemporalConfig::default()
+        };
+        assert!(analyzer
+            .analyze_commits(&commits, &filters)
+            .couplings
+            .is_empty());
+        let kept = analyzer.analyze_commits(&commits, &TemporalConfig::default());
+        assert_eq!(kept.couplings.len(), 1);
+    }
+
+    #[test]
+    fn test_group_commits() {
+        use ChangeType::Modified;
+        let at = |message: &str, author: &str, minutes: i64, file: &str| Commit {
+            sha: format!("{message}-{minutes}"),
+            author: author.to_string(),
+            email: format!("{}@example.com", author.to_lowercase()),
+            timestamp: 1_700_000_000 + minutes * 60,
+            message: message.to_string(),
+            ..commit(&[(file, Modified)])
+        };
+        // Newest first, as the log returns them
+        let commits = vec![
+            at("PAY-12 fix review comments", "Bob", 300, "b.rs"),
+            at("Tidy docs", "Ada", 50, "docs.md"),
+            at("PAY-12 charge cards", "Ada", 40, "a.rs"),
+            at("PAY-7 refunds", "Ada", 0, "a.rs"),
+        ];
+
+        let by_ticket = TemporalConfig {
+            group_by: CommitGrouping::Ticket,
+            ..TemporalConfig::default()
+        };
+        let groups = group_commits(commits.clone(), &by_ticket).unwrap();
+        let files: Vec<Vec<&str>> = groups
+            .iter()
+            .map(|g| g.files.iter().map(|f| f.path.to_str().unwrap()).collect())
+            .collect();
+        assert_eq!(files, [vec!["b.rs", "a.rs"], vec!["docs.md"], vec!["a.rs"]]);
+        assert_eq!(groups[0].sha, "PAY-12 fix review comments-300");
+
+        let by_author = TemporalConfig {
+            group_by: CommitGrouping::Author,
+            group_window_minutes: 45,
+            ..TemporalConfig::default()
+        };
+        let groups = group_commits(commits.clone(), &by_author).unwrap();
+        let sizes: Vec<usize> = groups.iter().map(|g| g.files.len()).collect();
+        // Ada's three commits chain within 45 minutes; docs.md and a.rs merge,
+        // and the earlier a.rs change adds to the same entry
+        assert_eq!(sizes, [1, 2]);
+        assert_eq!(groups[1].files[1].additions, 2);
+
+        let by_commit = group_commits(commits.clone(), &TemporalConfig::default()).unwrap();
+        assert_eq!(by_commit.len(), 4);
+
+        let invalid = TemporalConfig {
+            ticket_pattern: "(".to_string(),
+            ..by_ticket
+        };
+        assert!(group_commits(commits, &invalid).is_err());
+    }
 
     #[test]
     fn test_config_default() {
@@ -1,8 +1,11 @@
 //! CLI implementation using clap.
 
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 
 /// Omen - Code analysis CLI for technical debt and complexity metrics.
 #[derive(Parser)]
@@ -17,14 +20,42 @@ pub struct Cli {
     #[arg(short, long, value_enum, default_value = "markdown")]
     pub format: OutputFormat,
 
-    /// Compact JSON output (single line, no indentation; ignored for non-JSON formats)
-    #[arg(long)]
-    pub compact: bool,
+    /// Compact JSON output; `--compact=strings` also interns repeated long strings
+    /// (ignored for non-JSON formats)
+    #[arg(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "lines"
+    )]
+    pub compact: Option<CompactMode>,
+
+    /// Write output to this file instead of stdout
+    #[arg(long = "output", value_name = "FILE", conflicts_with = "output_dir")]
+    pub output_file: Option<PathBuf>,
+
+    /// Write output into this directory, one file per analyzer
+    #[arg(long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Filename template for --output-dir ({analyzer} and {format} are expanded)
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        requires = "output_dir",
+        default_value = "{analyzer}.{format}"
+    )]
+    pub template: String,
 
     /// Configuration file path
     #[arg(short, long)]
     pub config: Option<PathBuf>,
 
+    /// Config profile to apply, from a `[profile.<name>]` table
+    #[arg(long, global = true, env = "OMEN_PROFILE")]
+    pub profile: Option<String>,
+
     /// Enable verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -45,6 +76,35 @@ pub struct Cli {
     #[arg(long)]
     pub shallow: bool,
 
+    /// Git directory to analyze, e.g. a bare mirror (overrides --path)
+    #[arg(long)]
+    pub git_dir: Option<PathBuf>,
+
+    /// Analyze history and file contents at this revision instead of HEAD and the worktree
+    #[arg(long, global = true, visible_alias = "at", alias = "since-ref")]
+    pub at_ref: Option<String>,
+
+    /// Tag SATD items, complexity violations and smells with the commit that introduced them
+    #[arg(long, global = true)]
+    pub blame: bool,
+
+    /// Analyze only files changed since the merge base with this ref, marking findings on
+    /// changed lines
+    #[arg(long, global = true, value_name = "REF")]
+    pub base: Option<String>,
+
+    /// Analyze only files changed since HEAD (shorthand for `--base HEAD`)
+    #[arg(long, global = true, conflicts_with = "base")]
+    pub changed_only: bool,
+
+    /// Exit non-zero if any finding is at or above this severity
+    #[arg(long, global = true, value_enum, value_name = "SEVERITY")]
+    pub fail_on: Option<FailOn>,
+
+    /// Print file walk and analyzer timings to stderr
+    #[arg(long, global = true)]
+    pub timings: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -57,11 +117,11 @@ pub enum Command {
 
     /// Detect Self-Admitted Technical Debt
     #[command(alias = "debt")]
-    Satd(AnalyzerArgs),
+    Satd(SatdArgs),
 
     /// Find dead/unreachable code
     #[command(alias = "dc")]
-    Deadcode(AnalyzerArgs),
+    Deadcode(DeadcodeArgs),
 
     /// Analyze code churn from git history
     Churn(ChurnArgs),
@@ -72,12 +132,20 @@ pub enum Command {
 
     /// Predict defect-prone files using PMAT
     #[command(alias = "predict")]
-    Defect(AnalyzerArgs),
+    Defect(DefectArgs),
+
+    /// Risk matrix of defect probability against business criticality
+    #[command(alias = "matrix")]
+    Risk(AnalyzerArgs),
 
     /// Analyze recent changes (JIT risk)
     #[command(alias = "jit")]
     Changes(AnalyzerArgs),
 
+    /// Check commit messages for conventional-commit compliance and hygiene
+    #[command(alias = "cm")]
+    Commits(CommitsArgs),
+
     /// Analyze a specific diff (PR review)
     #[command(alias = "pr")]
     Diff(DiffArgs),
@@ -87,34 +155,58 @@ pub enum Command {
 
     /// Analyze dependency graph structure
     #[command(alias = "dag")]
-    Graph(AnalyzerArgs),
+    Graph(GraphArgs),
 
     /// Find complexity/churn hotspots
     #[command(alias = "hs")]
-    Hotspot(AnalyzerArgs),
+    Hotspot(HotspotArgs),
 
     /// Detect temporally coupled files
     #[command(alias = "tc", visible_alias = "temporal-coupling")]
     Temporal(AnalyzerArgs),
 
+    /// Find co-changing files with no import path between them
+    #[command(alias = "hidden-deps")]
+    Hidden(AnalyzerArgs),
+
     /// Analyze code ownership and bus factor
     #[command(alias = "own", alias = "bus-factor")]
-    Ownership(AnalyzerArgs),
+    Ownership(OwnershipArgs),
 
     /// Calculate CK cohesion metrics
     #[command(alias = "ck")]
-    Cohesion(AnalyzerArgs),
+    Cohesion(CohesionArgs),
 
     /// Generate PageRank-ranked symbol map
     Repomap(AnalyzerArgs),
 
+    /// Build the function call graph with fan-in/fan-out and recursion cycles
+    #[command(alias = "calls")]
+    Callgraph(AnalyzerArgs),
+
     /// Detect architectural smells
-    Smells(AnalyzerArgs),
+    Smells(PrioritizedArgs),
 
     /// Find and assess feature flags
     #[command(alias = "ff")]
     Flags(FlagsArgs),
 
+    /// Audit CI configs and build scripts for sprawl and unpinned versions
+    #[command(alias = "ci")]
+    Pipeline(AnalyzerArgs),
+
+    /// Check Dockerfiles for unpinned images, package caches, secrets and layer bloat
+    #[command(alias = "dockerfile")]
+    Docker(AnalyzerArgs),
+
+    /// Find doc comments whose parameters no longer match the signature
+    #[command(alias = "drift")]
+    Docdrift(AnalyzerArgs),
+
+    /// List the public API surface and what changed in it since a baseline
+    #[command(alias = "surface")]
+    Api(ApiArgs),
+
     /// Calculate composite health score
     Score(ScoreCommand),
 
@@ -122,12 +214,25 @@ pub enum Command {
     Mcp(McpCommand),
 
     /// Run all analyzers
-    All(AnalyzerArgs),
+    All(AllArgs),
+
+    /// Run several named analyzers over one shared file set
+    Run(RunArgs),
+
+    /// Evaluate a JMESPath expression over analyzer results
+    #[command(alias = "q")]
+    Query(QueryArgs),
 
     /// Generate deep context for LLM consumption
     #[command(alias = "ctx")]
     Context(ContextArgs),
 
+    /// Show config, cache, search index and git state
+    Status,
+
+    /// Check that result files all came from the same snapshot, config and omen build
+    VerifyManifest(VerifyManifestArgs),
+
     /// Generate and manage HTML health reports
     Report(ReportCommand),
 
@@ -150,6 +255,18 @@ pub enum Command {
     /// One-call symbol report: source, signature, location, callers/callees, complexity
     #[command(alias = "sym")]
     Symbol(SymbolArgs),
+
+    /// Apply safe automatic fixes: dead private functions, rolled-out flags, resolved SATD
+    Fix(FixArgs),
+
+    /// Interactive dashboard of score, hotspots, complexity and SATD
+    Tui(AnalyzerArgs),
+
+    /// Generate shell completion scripts
+    Completions(CompletionsArgs),
+
+    /// Generate man pages for omen and all subcommands
+    Manpages(ManpagesArgs),
 }
 
 #[derive(Args)]
@@ -175,11 +292,93 @@ pub struct AnalyzerArgs {
     pub offset: Option<usize>,
 }
 
+/// Arguments for `omen all`.
+#[derive(Args)]
+pub struct AllArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Write each analyzer's result as one JSON line (NDJSON) as soon as it finishes
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Start no further analyzers after one fails, and exit with an error
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Report an analyzer as failed if it runs longer than this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Finish within this time (e.g. 60s, 2m): run the fastest analyzers first
+    /// and skip those expected to overrun, based on earlier runs
+    #[arg(long, value_name = "DURATION", value_parser = crate::core::timings::parse_deadline)]
+    pub deadline: Option<Duration>,
+
+    /// Predict run time from repo size and earlier runs, without running anything
+    #[arg(long)]
+    pub estimate: bool,
+}
+
+/// Arguments for analyzers whose findings can be re-ranked by file risk.
+#[derive(Args)]
+pub struct PrioritizedArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Re-rank findings by the risk of the files they touch
+    #[arg(long, value_enum)]
+    pub prioritize_by: Option<PrioritizeBy>,
+}
+
+/// Severity threshold for `--fail-on`.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum FailOn {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Finding prioritization strategy.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum PrioritizeBy {
+    /// Files with concentrated ownership (knowledge silos) first
+    Ownership,
+    /// Files with high churn and complexity first
+    Hotspot,
+    /// Keep the analyzer's own ordering
+    None,
+}
+
 #[derive(Args)]
 pub struct DiffArgs {
     /// Target branch to diff against (default: auto-detect main/master)
     #[arg(short, long)]
     pub target: Option<String>,
+
+    /// Join the diff with this pull request's metadata from GitHub or GitLab
+    /// (needs GITHUB_TOKEN or GITLAB_TOKEN)
+    #[arg(long, value_name = "NUMBER")]
+    pub pr: Option<u64>,
+
+    /// Analyze only staged changes (index vs HEAD), e.g. from a pre-commit hook
+    #[arg(long, conflicts_with_all = ["worktree", "pr", "target"])]
+    pub staged: bool,
+
+    /// Analyze only unstaged changes (working tree vs index)
+    #[arg(long, conflicts_with_all = ["pr", "target"])]
+    pub worktree: bool,
+
+    /// With --staged or --worktree, exit non-zero when any hunk's risk score
+    /// is above this (0.0-1.0; default: changes.fail_above from config)
+    #[arg(long, value_name = "SCORE")]
+    pub fail_above: Option<f64>,
+
+    /// Exit non-zero when the branch adds more net cyclomatic complexity than
+    /// this (default: changes.complexity_budget from config)
+    #[arg(long, value_name = "N", conflicts_with_all = ["staged", "worktree"])]
+    pub complexity_budget: Option<u32>,
 }
 
 #[derive(Args)]
@@ -198,6 +397,22 @@ pub struct ComplexityArgs {
     /// Maximum cognitive complexity (default: from config or 30)
     #[arg(long)]
     pub max_cognitive: Option<u32>,
+
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+}
+
+/// Baseline options shared by check modes.
+#[derive(Args)]
+pub struct BaselineArgs {
+    /// With --check, fail only on violations missing from this baseline
+    /// file; it is written from the current violations if it does not exist
+    #[arg(long, value_name = "FILE", requires = "check")]
+    pub baseline: Option<PathBuf>,
+
+    /// Rewrite the --baseline file from the current violations
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
 }
 
 #[derive(Args)]
@@ -208,6 +423,131 @@ pub struct ChurnArgs {
     /// Number of days to analyze
     #[arg(long, default_value = "30")]
     pub days: u32,
+
+    /// Report after-hours and weekend commit ratios (see [churn.off_hours])
+    #[arg(long)]
+    pub off_hours: bool,
+}
+
+#[derive(Args)]
+pub struct HotspotArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Report off-hours commit ratios and their correlation with hotspot score
+    #[arg(long)]
+    pub off_hours: bool,
+
+    /// Weight of churn against complexity, 0.0-1.0 (see [hotspot] churn_weight)
+    #[arg(long, value_name = "WEIGHT")]
+    pub churn_weight: Option<f64>,
+
+    /// Normalize churn and complexity by percentile rank or z-score
+    #[arg(long, value_enum)]
+    pub normalization: Option<HotspotNormalization>,
+
+    /// Halve a commit's churn for every DAYS of age
+    #[arg(long, value_name = "DAYS")]
+    pub half_life: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct OwnershipArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Blame every file instead of estimating ownership from the log
+    #[arg(long)]
+    pub precise: bool,
+}
+
+#[derive(Args)]
+pub struct ApiArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Compare with this revision, e.g. the last release tag (see [api] base)
+    #[arg(long, value_name = "REF")]
+    pub base: Option<String>,
+
+    /// Compare with the start of this history window, e.g. 6m (see [api] since)
+    #[arg(long, conflicts_with = "base")]
+    pub since: Option<String>,
+}
+
+/// Hotspot normalization strategy.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HotspotNormalization {
+    /// Percentile rank among the analyzed files
+    Rank,
+    /// Normal CDF of the z-score
+    Zscore,
+}
+
+#[derive(Args)]
+pub struct DefectArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Calibrate predictions against past incidents (CSV `path,date` or JSON)
+    #[arg(long)]
+    pub incidents: Option<PathBuf>,
+
+    /// Number of top-ranked files used for precision@k and recall@k
+    #[arg(long, default_value = "10")]
+    pub top_k: usize,
+}
+
+#[derive(Args)]
+pub struct CommitsArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Number of days to analyze (default: from config or 30)
+    #[arg(long)]
+    pub days: Option<u32>,
+
+    /// Check mode: fail if compliance is below threshold or fixup commits remain
+    #[arg(long)]
+    pub check: bool,
+
+    /// Minimum conventional-commit compliance, 0-1 (default: from config or 0.8)
+    #[arg(long)]
+    pub min_compliance: Option<f64>,
+}
+
+#[derive(Args)]
+pub struct DeadcodeArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Write a removal patch per file into this directory for dead private functions at or
+    /// above --patch-confidence, including imports only they used
+    #[arg(long, value_name = "DIR")]
+    pub emit_patches: Option<PathBuf>,
+
+    /// Minimum confidence (0.0-1.0) of items removed by --emit-patches
+    #[arg(
+        long,
+        value_name = "CONFIDENCE",
+        default_value = "0.95",
+        requires = "emit_patches"
+    )]
+    pub patch_confidence: f64,
+}
+
+#[derive(Args)]
+pub struct SatdArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Re-rank findings by the risk of the files they touch
+    #[arg(long, value_enum)]
+    pub prioritize_by: Option<PrioritizeBy>,
+
+    /// Report debt added and resolved since this git ref (branch, tag, SHA)
+    #[arg(long, value_name = "REF")]
+    pub compare: Option<String>,
 }
 
 #[derive(Args)]
@@ -215,13 +555,76 @@ pub struct FlagsArgs {
     #[command(flatten)]
     pub common: AnalyzerArgs,
 
-    /// Feature flag provider (launchdarkly, split, etc.)
+    /// Feature flag providers to detect (launchdarkly, split, etc.);
+    /// repeat or separate with commas
+    #[arg(long, value_delimiter = ',')]
+    pub provider: Vec<String>,
+
+    /// Only detect the listed providers, without auto-detection from
+    /// dependencies
     #[arg(long)]
-    pub provider: Option<String>,
+    pub no_auto_detect: bool,
 
     /// Days threshold for staleness
     #[arg(long, default_value = "90")]
     pub stale_days: u32,
+
+    /// Re-rank flags by the risk of the files they touch
+    #[arg(long, value_enum)]
+    pub prioritize_by: Option<PrioritizeBy>,
+}
+
+#[derive(Args)]
+pub struct CohesionArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Write the inheritance hierarchy to this file in DOT format
+    #[arg(long, value_name = "FILE")]
+    pub hierarchy_dot: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct GraphArgs {
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+
+    /// Keep only internal edges (drop stdlib, third-party, and cross-package imports)
+    #[arg(long)]
+    pub internal_only: bool,
+
+    /// Add nodes for imports that do not resolve to an analyzed file
+    #[arg(long, conflicts_with = "internal_only")]
+    pub include_external: bool,
+
+    /// Collapse re-export-only barrel files into the files that define their exports
+    #[arg(long)]
+    pub collapse_barrels: bool,
+
+    /// Count dynamic imports (import(), importlib, Class.forName) when detecting cycles
+    #[arg(long)]
+    pub dynamic_cycles: bool,
+
+    /// Aggregate files into directory-level packages with coupling, instability,
+    /// abstractness, and distance from the main sequence
+    #[arg(long)]
+    pub packages: bool,
+
+    /// Export the graph as a diagram, with cycles highlighted (printed as-is,
+    /// or in the `diagram` field of JSON output)
+    #[arg(long, value_enum)]
+    pub graph_format: Option<GraphFormat>,
+}
+
+/// Dependency graph export format.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// GraphML, for Gephi, yEd, and networkx
+    Graphml,
+    /// Mermaid flowchart
+    Mermaid,
 }
 
 /// Score command with subcommands.
@@ -239,6 +642,9 @@ pub enum ScoreSubcommand {
     /// Analyze score trends over git history
     #[command(alias = "tr")]
     Trend(ScoreTrendArgs),
+
+    /// Write shields.io endpoint badges for the grade
+    Badge(ScoreBadgeArgs),
 }
 
 #[derive(Args)]
@@ -250,6 +656,33 @@ pub struct ScoreArgs {
     /// Minimum score to pass (default: from config)
     #[arg(long)]
     pub fail_under: Option<f64>,
+
+    /// Start markdown output with grade, duplication, complexity, and SATD badges
+    /// linking to their sections, for pasting at the top of a README
+    #[arg(long)]
+    pub with_badges: bool,
+
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+}
+
+#[derive(Args)]
+pub struct ScoreBadgeArgs {
+    /// Badge JSON file to write (default: print the overall badge as output)
+    #[arg(long, value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// Also write one badge per score component (e.g. badge-complexity.json)
+    #[arg(long, requires = "file")]
+    pub components: bool,
+
+    /// Also write an SVG next to each JSON badge
+    #[arg(long, requires = "file")]
+    pub svg: bool,
+
+    /// Badge label
+    #[arg(long, default_value = "omen")]
+    pub label: String,
 }
 
 #[derive(Args)]
@@ -323,6 +756,40 @@ pub struct ContextArgs {
     /// Depth for dependency traversal
     #[arg(long, default_value = "2")]
     pub depth: usize,
+
+    /// How to count tokens against --max-tokens (default: from config)
+    #[arg(long, value_enum)]
+    pub tokenizer: Option<Tokenizer>,
+
+    /// Write the repo map and findings in a format an AI coding tool reads
+    #[arg(long, value_enum)]
+    pub export: Option<ContextExport>,
+
+    /// File to write --export to (default: where the tool looks for it)
+    #[arg(long, value_name = "FILE", requires = "export")]
+    pub export_path: Option<PathBuf>,
+}
+
+/// AI coding tool formats for `omen context --export`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ContextExport {
+    /// Aider-style repo map in .aider.omen.md
+    Aider,
+    /// A section of CLAUDE.md that omen keeps up to date
+    ClaudeProject,
+    /// An always-applied Cursor rule in .cursor/rules/omen.mdc
+    CursorRules,
+}
+
+/// Token counting for `omen context`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Tokenizer {
+    /// One token per four characters
+    Chars,
+    /// Identifier pieces and punctuation, close to code tokenizers
+    Words,
+    /// One token per byte, the most conservative
+    Bytes,
 }
 
 /// Report command with subcommands.
@@ -343,8 +810,11 @@ pub enum ReportSubcommand {
     /// Combine data + insights into self-contained HTML
     Render(ReportRenderArgs),
 
-    /// Serve HTML with live re-render on request
+    /// Serve the report and its data files over HTTP, re-rendering on request
     Serve(ReportServeArgs),
+
+    /// Summarize the difference between two data directories as a PR comment
+    Comment(ReportCommentArgs),
 }
 
 #[derive(Args)]
@@ -368,6 +838,21 @@ pub struct ReportGenerateArgs {
     /// Number of samples for trend analysis (evenly spaced over the time range)
     #[arg(long)]
     pub samples: Option<usize>,
+
+    /// Finish within this time (e.g. 60s, 2m): run the fastest analyzers first
+    /// and skip those expected to overrun, based on earlier runs
+    #[arg(long, value_name = "DURATION", value_parser = crate::core::timings::parse_deadline)]
+    pub deadline: Option<Duration>,
+
+    /// Predict run time from repo size and earlier runs, without running anything
+    #[arg(long)]
+    pub estimate: bool,
+
+    /// Data directory of a previous run: reuse its ownership, churn and
+    /// duplicates state for files unchanged since, and mark smells new or
+    /// resolved (e.g. .omen/data-old)
+    #[arg(long, value_name = "DIR")]
+    pub previous: Option<PathBuf>,
 }
 
 #[derive(Args)]
@@ -376,7 +861,7 @@ pub struct ReportValidateArgs {
     #[arg(short, long, default_value = ".omen/data")]
     pub data: PathBuf,
 
-    /// Schema directory (embedded by default)
+    /// Directory of <name>.schema.json files replacing the embedded schemas
     #[arg(long)]
     pub schema: Option<PathBuf>,
 }
@@ -411,6 +896,32 @@ pub struct ReportServeArgs {
     pub host: String,
 }
 
+/// Output format for report comments.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CommentFormat {
+    /// GitHub-flavored Markdown
+    GithubMarkdown,
+}
+
+#[derive(Args)]
+pub struct ReportCommentArgs {
+    /// Data directory for the base of the change
+    #[arg(long)]
+    pub before: PathBuf,
+
+    /// Data directory for the head of the change
+    #[arg(long)]
+    pub after: PathBuf,
+
+    /// Comment format
+    #[arg(long, value_enum, default_value = "github-markdown")]
+    pub format: CommentFormat,
+
+    /// Maximum comment length in characters
+    #[arg(long, default_value_t = crate::report::comment::DEFAULT_MAX_CHARS)]
+    pub max_chars: usize,
+}
+
 /// Search command with subcommands.
 #[derive(Args)]
 pub struct SearchCommand {
@@ -425,6 +936,12 @@ pub enum SearchSubcommand {
 
     /// Search for code symbols
     Query(SearchQueryArgs),
+
+    /// Show index size, symbols per language, and stale entries
+    Stats,
+
+    /// Remove deleted and excluded files from the index
+    Prune,
 }
 
 #[derive(Clone, Args)]
@@ -454,6 +971,10 @@ pub struct SearchQueryArgs {
     /// Include additional project paths for cross-repo search (comma-separated)
     #[arg(long)]
     pub include_project: Option<String>,
+
+    /// Rerank results by PageRank, hotspot, and complexity ([search.rerank] weights)
+    #[arg(long)]
+    pub rerank: bool,
 }
 
 #[derive(Args)]
@@ -465,13 +986,23 @@ pub struct MutationArgs {
     #[arg(long)]
     pub test_command: Option<String>,
 
-    /// Timeout per mutant in seconds
-    #[arg(long, default_value = "30")]
+    /// With an auto-detected command, also run tests of packages that depend on the mutated one
+    #[arg(long)]
+    pub include_dependents: bool,
+
+    /// Timeout per mutant in seconds (0 = 3x the slowest baseline test run)
+    #[arg(long, default_value = "0")]
     pub timeout: u64,
 
-    /// Mutation operators to use (comma-separated: CRR,ROR,AOR)
-    #[arg(long, default_value = "CRR,ROR,AOR")]
-    pub operators: String,
+    /// Run the unmutated test suite this many times first and abort if it is
+    /// red or flaky (0 = skip the check)
+    #[arg(long, default_value = "3", value_name = "N")]
+    pub baseline_runs: usize,
+
+    /// Mutation operators to use, comma-separated (default: `[mutation] operators`
+    /// in config, else CRR,ROR,AOR)
+    #[arg(long)]
+    pub operators: Option<String>,
 
     /// Check mode: fail if mutation score below threshold
     #[arg(long)]
@@ -485,6 +1016,10 @@ pub struct MutationArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Count mutants and predict run time from earlier runs, without running tests
+    #[arg(long)]
+    pub estimate: bool,
+
     /// Number of parallel workers (0 = num_cpus)
     #[arg(long, default_value = "0")]
     pub jobs: usize,
@@ -501,7 +1036,8 @@ pub struct MutationArgs {
     #[arg(long)]
     pub skip_equivalent: bool,
 
-    /// Mutation mode: all, fast, thorough
+    /// Mutation mode: all, fast, thorough (every operator, including the
+    /// Rust and TypeScript-specific ones, regardless of --operators)
     #[arg(long, value_enum, default_value = "all")]
     pub mode: MutationMode,
 
@@ -509,6 +1045,14 @@ pub struct MutationArgs {
     #[arg(long)]
     pub output_survivors: Option<PathBuf>,
 
+    /// Write results as JUnit XML (one test case per mutant)
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<PathBuf>,
+
+    /// Print results as TAP instead of the selected format
+    #[arg(long)]
+    pub tap: bool,
+
     /// Record results to history file for model training
     #[arg(long)]
     pub record: bool,
@@ -520,6 +1064,11 @@ pub struct MutationArgs {
     /// Skip mutants predicted to be killed above this threshold (0.0-1.0)
     #[arg(long, value_name = "THRESHOLD")]
     pub skip_predicted: Option<f64>,
+
+    /// Save the suggested operator set, which finds what this run found in
+    /// less test time, as `[mutation] operators` in the config file
+    #[arg(long, conflicts_with_all = ["dry_run", "estimate"])]
+    pub apply_suggestions: bool,
 }
 
 /// Mutation testing mode.
@@ -606,6 +1155,83 @@ pub struct SymbolArgs {
     pub common: AnalyzerArgs,
 }
 
+/// Kinds of fix applied by `omen fix`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FixKind {
+    /// Private functions nothing reaches
+    DeadCode,
+    /// Stale flags fully rolled out (per the provider, `--rolled-out` or config)
+    StaleFlags,
+    /// SATD comments whose referenced issues are closed
+    ResolvedSatd,
+}
+
+/// Arguments for the fix command.
+#[derive(Args)]
+pub struct FixArgs {
+    /// Print the fixes as a unified diff instead of applying them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Apply only these kinds of fix (default: all)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub only: Vec<FixKind>,
+
+    /// Flag key to treat as fully rolled out without asking its provider (adds to config)
+    #[arg(long, value_name = "KEY")]
+    pub rolled_out: Vec<String>,
+
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+}
+
+/// Arguments for the query command.
+#[derive(Args)]
+pub struct QueryArgs {
+    /// JMESPath expression over results keyed by analyzer name
+    /// (e.g. "complexity.files[?avg_cyclomatic > `10`].path")
+    #[arg()]
+    pub expr: String,
+
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+}
+
+/// Arguments for the run command.
+#[derive(Args)]
+pub struct RunArgs {
+    /// Comma-separated analyzer names (e.g. complexity,satd,smells)
+    #[arg(value_name = "ANALYZERS", value_delimiter = ',', required = true)]
+    pub analyzers: Vec<String>,
+
+    #[command(flatten)]
+    pub common: AnalyzerArgs,
+}
+
+/// Arguments for the verify-manifest command.
+#[derive(Args)]
+pub struct VerifyManifestArgs {
+    /// JSON or SARIF result files, or directories of them (e.g. .omen/data)
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+/// Arguments for the completions command.
+#[derive(Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+/// Arguments for the manpages command.
+#[derive(Args)]
+pub struct ManpagesArgs {
+    /// Directory to write man pages into (created if missing)
+    #[arg()]
+    pub dir: PathBuf,
+}
+
 /// Arguments for mutation train command.
 #[derive(Args)]
 pub struct MutationTrainArgs {
@@ -622,12 +1248,28 @@ pub struct MutationTrainArgs {
     pub model: Option<PathBuf>,
 }
 
+/// How `--compact` shrinks JSON output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CompactMode {
+    /// Single line, no indentation
+    Lines,
+    /// Single line, with repeated long strings moved to a `strings` table
+    Strings,
+}
+
 #[derive(Clone, Copy, ValueEnum)]
 pub enum OutputFormat {
     Json,
     Markdown,
     Text,
     Sarif,
+    /// Comma-separated rows (complexity, churn, hotspot, ownership, defect)
+    Csv,
+    /// Tab-separated rows (complexity, churn, hotspot, ownership, defect)
+    Tsv,
+    /// GitHub Actions annotations (`::error file=...,line=...::...`) for SATD, smells and
+    /// complexity violations
+    Github,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -642,10 +1284,47 @@ impl Cli {
     }
 }
 
+/// Write a completion script for `shell` derived from the clap definitions.
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, out);
+}
+
+/// Write `omen.1` and one `omen-<subcommand>.1` page per (nested) subcommand
+/// into `dir`. Returns the written paths in generation order.
+pub fn write_manpages(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    let mut written = Vec::new();
+    write_manpage(&cmd, &name, dir, &mut written)?;
+    Ok(written)
+}
+
+fn write_manpage(
+    cmd: &clap::Command,
+    name: &str,
+    dir: &Path,
+    written: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    clap_mangen::Man::new(cmd.clone())
+        .title(name)
+        .render(&mut buffer)?;
+    let path = dir.join(format!("{name}.1"));
+    std::fs::write(&path, buffer)?;
+    written.push(path);
+
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        write_manpage(sub, &format!("{name}-{}", sub.get_name()), dir, written)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use clap::CommandFactory;
 
     /// Parse CLI args and return the parsed Cli, panicking on failure.
     fn parse(args: &[&str]) -> Cli {
@@ -716,6 +1395,46 @@ mod tests {
         assert_eq!(cli.path, std::path::PathBuf::from("."));
     }
 
+    #[test]
+    fn test_cli_output_file() {
+        let cli = parse(&["omen", "--output", "out.json", "complexity"]);
+        assert_eq!(cli.output_file, Some(std::path::PathBuf::from("out.json")));
+        assert!(cli.output_dir.is_none());
+    }
+
+    #[test]
+    fn test_cli_output_dir_with_template() {
+        let cli = parse(&["omen", "--output-dir", "reports", "all"]);
+        assert_eq!(cli.output_dir, Some(std::path::PathBuf::from("reports")));
+        assert_eq!(cli.template, "{analyzer}.{format}");
+
+        let cli = parse(&[
+            "omen",
+            "--output-dir",
+            "reports",
+            "--template",
+            "omen-{analyzer}.{format}",
+            "satd",
+        ]);
+        assert_eq!(cli.template, "omen-{analyzer}.{format}");
+    }
+
+    #[test]
+    fn test_cli_output_conflicts() {
+        assert!(Cli::try_parse_from([
+            "omen",
+            "--output",
+            "out.json",
+            "--output-dir",
+            "reports",
+            "complexity"
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["omen", "--template", "{analyzer}.md", "complexity"]).is_err()
+        );
+    }
+
     #[test]
     fn test_cli_custom_path() {
         let cli = parse(&["omen", "-p", "/tmp", "complexity"]);
@@ -754,6 +1473,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_cli_format_csv_tsv() {
+        assert!(matches!(
+            parse(&["omen", "-f", "csv", "hotspot"]).format,
+            OutputFormat::Csv
+        ));
+        assert!(matches!(
+            parse(&["omen", "-f", "tsv", "churn"]).format,
+            OutputFormat::Tsv
+        ));
+    }
+
+    #[test]
+    fn test_cli_format_github() {
+        assert!(matches!(
+            parse(&["omen", "-f", "github", "complexity", "--check"]).format,
+            OutputFormat::Github
+        ));
+    }
+
     #[test]
     fn test_cli_config_flag() {
         let cli = parse(&["omen", "-c", "config.toml", "complexity"]);
@@ -786,6 +1525,18 @@ mod tests {
         assert_parses_to!(&["omen", "deadcode"], Command::Deadcode(_));
     }
 
+    #[test]
+    fn test_deadcode_emit_patches() {
+        let cli = parse(&["omen", "deadcode", "--emit-patches", "patches/"]);
+        if let Command::Deadcode(args) = cli.command {
+            assert_eq!(args.emit_patches, Some(PathBuf::from("patches/")));
+            assert_eq!(args.patch_confidence, 0.95);
+        } else {
+            panic!("Expected Deadcode command");
+        }
+        assert!(Cli::try_parse_from(["omen", "deadcode", "--patch-confidence", "0.9"]).is_err());
+    }
+
     #[test]
     fn test_command_churn() {
         assert_parses_to!(&["omen", "churn"], Command::Churn(_));
@@ -801,11 +1552,52 @@ mod tests {
         assert_parses_to!(&["omen", "defect"], Command::Defect(_));
     }
 
+    #[test]
+    fn test_command_defect_incidents() {
+        let cli = Cli::try_parse_from([
+            "omen",
+            "defect",
+            "--incidents",
+            "incidents.csv",
+            "--top-k",
+            "5",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Defect(args) => {
+                assert_eq!(args.incidents, Some(PathBuf::from("incidents.csv")));
+                assert_eq!(args.top_k, 5);
+            }
+            _ => panic!("expected Defect command"),
+        }
+
+        let cli = Cli::try_parse_from(["omen", "defect"]).unwrap();
+        match cli.command {
+            Command::Defect(args) => {
+                assert!(args.incidents.is_none());
+                assert_eq!(args.top_k, 10);
+            }
+            _ => panic!("expected Defect command"),
+        }
+    }
+
+    #[test]
+    fn test_command_risk() {
+        assert_parses_to!(&["omen", "risk"], Command::Risk(_));
+        assert_parses_to!(&["omen", "matrix"], Command::Risk(_));
+    }
+
     #[test]
     fn test_command_changes() {
         assert_parses_to!(&["omen", "changes"], Command::Changes(_));
     }
 
+    #[test]
+    fn test_command_commits() {
+        assert_parses_to!(&["omen", "commits"], Command::Commits(_));
+        assert_parses_to!(&["omen", "cm"], Command::Commits(_));
+    }
+
     #[test]
     fn test_command_diff() {
         assert_parses_to!(&["omen", "diff"], Command::Diff(_));
@@ -829,6 +1621,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_diff_complexity_budget() {
+        match parse(&["omen", "diff", "--complexity-budget", "10"]).command {
+            Command::Diff(args) => assert_eq!(args.complexity_budget, Some(10)),
+            _ => panic!("expected Diff command"),
+        }
+        assert!(
+            Cli::try_parse_from(["omen", "diff", "--staged", "--complexity-budget", "1"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_complexity_check_baseline() {
+        match parse(&["omen", "complexity", "--check", "--baseline", "b.json"]).command {
+            Command::Complexity(args) => {
+                assert_eq!(args.baseline.baseline, Some(PathBuf::from("b.json")));
+                assert!(!args.baseline.update_baseline);
+            }
+            _ => panic!("expected Complexity command"),
+        }
+        // A baseline only applies to check mode.
+        assert!(Cli::try_parse_from(["omen", "score", "--baseline", "b.json"]).is_err());
+        assert!(Cli::try_parse_from(["omen", "complexity", "--update-baseline"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_diff_pr() {
+        match parse(&["omen", "diff", "--pr", "42"]).command {
+            Command::Diff(args) => {
+                assert_eq!(args.pr, Some(42));
+                assert!(args.target.is_none());
+            }
+            _ => panic!("expected Diff command"),
+        }
+    }
+
     #[test]
     fn test_command_tdg() {
         assert_parses_to!(&["omen", "tdg"], Command::Tdg(_));
@@ -839,6 +1667,69 @@ mod tests {
         assert_parses_to!(&["omen", "graph"], Command::Graph(_));
     }
 
+    #[test]
+    fn test_graph_internal_only_flag() {
+        let cli = parse(&["omen", "graph", "--internal-only"]);
+        match cli.command {
+            Command::Graph(args) => {
+                assert!(args.internal_only);
+                assert!(!args.include_external);
+            }
+            _ => panic!("expected Graph command"),
+        }
+    }
+
+    #[test]
+    fn test_graph_internal_only_conflicts_with_include_external() {
+        assert!(
+            Cli::try_parse_from(["omen", "graph", "--internal-only", "--include-external"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_graph_collapse_barrels_flag() {
+        let cli = parse(&["omen", "graph", "--collapse-barrels", "--internal-only"]);
+        match cli.command {
+            Command::Graph(args) => {
+                assert!(args.collapse_barrels);
+                assert!(args.internal_only);
+                assert!(!args.dynamic_cycles);
+            }
+            _ => panic!("expected Graph command"),
+        }
+    }
+
+    #[test]
+    fn test_graph_dynamic_cycles_flag() {
+        let cli = parse(&["omen", "graph", "--dynamic-cycles"]);
+        match cli.command {
+            Command::Graph(args) => assert!(args.dynamic_cycles),
+            _ => panic!("expected Graph command"),
+        }
+    }
+
+    #[test]
+    fn test_graph_packages_flag() {
+        let cli = parse(&["omen", "graph", "--packages"]);
+        match cli.command {
+            Command::Graph(args) => assert!(args.packages),
+            _ => panic!("expected Graph command"),
+        }
+    }
+
+    #[test]
+    fn test_graph_format_flag() {
+        let cli = parse(&["omen", "graph", "--graph-format", "graphml"]);
+        match cli.command {
+            Command::Graph(args) => {
+                assert!(matches!(args.graph_format, Some(GraphFormat::Graphml)))
+            }
+            _ => panic!("expected Graph command"),
+        }
+        assert!(Cli::try_parse_from(["omen", "graph", "--graph-format", "png"]).is_err());
+    }
+
     #[test]
     fn test_command_hotspot() {
         assert_parses_to!(&["omen", "hotspot"], Command::Hotspot(_));
@@ -849,6 +1740,40 @@ mod tests {
         assert_parses_to!(&["omen", "temporal"], Command::Temporal(_));
     }
 
+    #[test]
+    fn test_command_hidden() {
+        assert_parses_to!(&["omen", "hidden"], Command::Hidden(_));
+        assert_parses_to!(&["omen", "hidden-deps"], Command::Hidden(_));
+    }
+
+    #[test]
+    fn test_command_pipeline() {
+        assert_parses_to!(&["omen", "pipeline"], Command::Pipeline(_));
+        assert_parses_to!(&["omen", "ci"], Command::Pipeline(_));
+    }
+
+    #[test]
+    fn test_command_docker() {
+        assert_parses_to!(&["omen", "docker"], Command::Docker(_));
+        assert_parses_to!(&["omen", "dockerfile"], Command::Docker(_));
+    }
+
+    #[test]
+    fn test_command_docdrift() {
+        assert_parses_to!(&["omen", "docdrift"], Command::Docdrift(_));
+        assert_parses_to!(&["omen", "drift"], Command::Docdrift(_));
+    }
+
+    #[test]
+    fn test_command_api() {
+        assert_parses_to!(&["omen", "surface"], Command::Api(_));
+        let Command::Api(args) = parse(&["omen", "api", "--base", "v1.2.0"]).command else {
+            panic!("expected api command");
+        };
+        assert_eq!(args.base.as_deref(), Some("v1.2.0"));
+        assert!(Cli::try_parse_from(["omen", "api", "--base", "v1", "--since", "1y"]).is_err());
+    }
+
     #[test]
     fn test_command_ownership() {
         assert_parses_to!(&["omen", "ownership"], Command::Ownership(_));
@@ -857,6 +1782,12 @@ mod tests {
     #[test]
     fn test_command_cohesion() {
         assert_parses_to!(&["omen", "cohesion"], Command::Cohesion(_));
+        match parse(&["omen", "cohesion", "--hierarchy-dot", "classes.dot"]).command {
+            Command::Cohesion(args) => {
+                assert_eq!(args.hierarchy_dot, Some(PathBuf::from("classes.dot")))
+            }
+            _ => panic!("expected Cohesion command"),
+        }
     }
 
     #[test]
@@ -864,6 +1795,12 @@ mod tests {
         assert_parses_to!(&["omen", "repomap"], Command::Repomap(_));
     }
 
+    #[test]
+    fn test_command_callgraph() {
+        assert_parses_to!(&["omen", "callgraph"], Command::Callgraph(_));
+        assert_parses_to!(&["omen", "calls"], Command::Callgraph(_));
+    }
+
     #[test]
     fn test_command_smells() {
         assert_parses_to!(&["omen", "smells"], Command::Smells(_));
@@ -889,11 +1826,55 @@ mod tests {
         assert_parses_to!(&["omen", "all"], Command::All(_));
     }
 
+    #[test]
+    fn test_all_streaming_flags() {
+        let cli = parse(&[
+            "omen",
+            "all",
+            "--stream",
+            "--fail-fast",
+            "--timeout",
+            "30",
+            "--top",
+            "5",
+        ]);
+        match cli.command {
+            Command::All(args) => {
+                assert!(args.stream);
+                assert!(args.fail_fast);
+                assert_eq!(args.timeout, Some(30));
+                assert_eq!(args.common.top, Some(5));
+            }
+            _ => panic!("expected All command"),
+        }
+        assert!(Cli::try_parse_from(["omen", "all", "--timeout", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_deadline_flag() {
+        match parse(&["omen", "all", "--deadline", "60s"]).command {
+            Command::All(args) => assert_eq!(args.deadline, Some(Duration::from_secs(60))),
+            _ => panic!("expected All command"),
+        }
+        match parse_report_subcommand(&["omen", "report", "generate", "--deadline", "2m"]) {
+            ReportSubcommand::Generate(args) => {
+                assert_eq!(args.deadline, Some(Duration::from_secs(120)))
+            }
+            _ => panic!("expected report generate"),
+        }
+        assert!(Cli::try_parse_from(["omen", "all", "--deadline", "soon"]).is_err());
+    }
+
     #[test]
     fn test_command_context() {
         assert_parses_to!(&["omen", "context"], Command::Context(_));
     }
 
+    #[test]
+    fn test_command_status() {
+        assert_parses_to!(&["omen", "status"], Command::Status);
+    }
+
     #[test]
     fn test_command_mutation() {
         assert_parses_to!(&["omen", "mutation"], Command::Mutation(_));
@@ -1013,11 +1994,109 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_off_hours_flags() {
+        let Command::Churn(args) = parse(&["omen", "churn", "--off-hours"]).command else {
+            panic!("expected churn command");
+        };
+        assert!(args.off_hours);
+        let Command::Hotspot(args) = parse(&["omen", "hotspot"]).command else {
+            panic!("expected hotspot command");
+        };
+        assert!(!args.off_hours);
+        let Command::Hotspot(args) = parse(&["omen", "hs", "--off-hours"]).command else {
+            panic!("expected hotspot command");
+        };
+        assert!(args.off_hours);
+    }
+
+    #[test]
+    fn test_ownership_precise_flag() {
+        let Command::Ownership(args) = parse(&["omen", "ownership"]).command else {
+            panic!("expected ownership command");
+        };
+        assert!(!args.precise);
+        let Command::Ownership(args) = parse(&["omen", "own", "--precise"]).command else {
+            panic!("expected ownership command");
+        };
+        assert!(args.precise);
+    }
+
+    #[test]
+    fn test_hotspot_scoring_flags() {
+        let Command::Hotspot(args) = parse(&[
+            "omen",
+            "hotspot",
+            "--churn-weight",
+            "0.7",
+            "--normalization",
+            "zscore",
+            "--half-life",
+            "30",
+            "--top",
+            "5",
+        ])
+        .command
+        else {
+            panic!("expected hotspot command");
+        };
+        assert_eq!(args.churn_weight, Some(0.7));
+        assert!(matches!(
+            args.normalization,
+            Some(HotspotNormalization::Zscore)
+        ));
+        assert_eq!(args.half_life, Some(30.0));
+        assert_eq!(args.common.top, Some(5));
+    }
+
+    #[test]
+    fn test_commits_check_flags() {
+        let cli = parse(&["omen", "commits"]);
+        let Command::Commits(args) = cli.command else {
+            panic!("expected commits command");
+        };
+        assert!(!args.check);
+        assert!(args.days.is_none());
+
+        let cli = parse(&[
+            "omen",
+            "commits",
+            "--check",
+            "--days",
+            "90",
+            "--min-compliance",
+            "0.9",
+        ]);
+        let Command::Commits(args) = cli.command else {
+            panic!("expected commits command");
+        };
+        assert!(args.check);
+        assert_eq!(args.days, Some(90));
+        assert_eq!(args.min_compliance, Some(0.9));
+    }
+
     #[test]
     fn test_flags_provider() {
         let cli = parse(&["omen", "flags", "--provider", "launchdarkly"]);
         if let Command::Flags(args) = cli.command {
-            assert_eq!(args.provider, Some("launchdarkly".to_string()));
+            assert_eq!(args.provider, vec!["launchdarkly".to_string()]);
+        }
+    }
+
+    #[test]
+    fn test_flags_multiple_providers() {
+        let cli = parse(&[
+            "omen",
+            "flags",
+            "--provider",
+            "launchdarkly,split",
+            "--provider",
+            "env",
+            "--no-auto-detect",
+        ]);
+        if let Command::Flags(args) = cli.command {
+            assert_eq!(args.provider, vec!["launchdarkly", "split", "env"]);
+            assert!(args.no_auto_detect);
         }
     }
 
@@ -1123,6 +2202,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_context_tokenizer() {
+        let cli = parse(&["omen", "context", "--tokenizer", "words"]);
+        if let Command::Context(args) = cli.command {
+            assert_eq!(args.tokenizer, Some(Tokenizer::Words));
+        }
+        let cli = parse(&["omen", "context"]);
+        if let Command::Context(args) = cli.command {
+            assert_eq!(args.tokenizer, None);
+        }
+    }
+
+    #[test]
+    fn test_context_export() {
+        let cli = parse(&["omen", "context", "--export", "claude-project"]);
+        if let Command::Context(args) = cli.command {
+            assert_eq!(args.export, Some(ContextExport::ClaudeProject));
+            assert_eq!(args.export_path, None);
+        }
+        let cli = parse(&[
+            "omen",
+            "context",
+            "--export",
+            "cursor-rules",
+            "--export-path",
+            "rules.mdc",
+        ]);
+        if let Command::Context(args) = cli.command {
+            assert_eq!(args.export, Some(ContextExport::CursorRules));
+            assert_eq!(args.export_path, Some(PathBuf::from("rules.mdc")));
+        }
+        assert!(Cli::try_parse_from(["omen", "context", "--export-path", "x.md"]).is_err());
+    }
+
     #[test]
     fn test_context_depth() {
         let cli = parse(&["omen", "context", "--depth", "3"]);
@@ -1255,6 +2368,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_report_generate_previous() {
+        match parse_report_subcommand(&["omen", "report", "generate", "--previous", "old"]) {
+            ReportSubcommand::Generate(args) => {
+                assert_eq!(args.previous, Some(PathBuf::from("old")))
+            }
+            _ => panic!("expected report generate"),
+        }
+    }
+
     #[test]
     fn test_report_generate_samples_default_is_none() {
         if let ReportSubcommand::Generate(args) =
@@ -1264,6 +2387,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_report_comment() {
+        let sub = parse_report_subcommand(&[
+            "omen",
+            "report",
+            "comment",
+            "--before",
+            "base/.omen/data",
+            "--after",
+            ".omen/data",
+            "--format",
+            "github-markdown",
+            "--max-chars",
+            "4000",
+        ]);
+        if let ReportSubcommand::Comment(args) = sub {
+            assert_eq!(args.before, PathBuf::from("base/.omen/data"));
+            assert_eq!(args.after, PathBuf::from(".omen/data"));
+            assert!(matches!(args.format, CommentFormat::GithubMarkdown));
+            assert_eq!(args.max_chars, 4000);
+        } else {
+            panic!("Expected Comment subcommand");
+        }
+    }
+
+    #[test]
+    fn test_report_comment_requires_both_dirs() {
+        assert!(Cli::try_parse_from(["omen", "report", "comment", "--after", "x"]).is_err());
+    }
+
     #[test]
     fn test_score_trend_samples() {
         let cli = parse(&["omen", "score", "trend", "--samples", "12"]);
@@ -1312,6 +2465,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_score_badge() {
+        let cli = parse(&[
+            "omen",
+            "score",
+            "badge",
+            "--file",
+            "badge.json",
+            "--components",
+            "--svg",
+        ]);
+        if let Command::Score(cmd) = cli.command {
+            if let Some(ScoreSubcommand::Badge(args)) = cmd.subcommand {
+                assert_eq!(args.file, Some(PathBuf::from("badge.json")));
+                assert!(args.components);
+                assert!(args.svg);
+                assert_eq!(args.label, "omen");
+            } else {
+                panic!("Expected Badge subcommand");
+            }
+        } else {
+            panic!("Expected Score command");
+        }
+    }
+
+    #[test]
+    fn test_score_with_badges() {
+        let cli = parse(&["omen", "score", "--with-badges"]);
+        match cli.command {
+            Command::Score(cmd) => {
+                assert!(cmd.args.with_badges);
+                assert!(cmd.subcommand.is_none());
+            }
+            _ => panic!("Expected Score command"),
+        }
+    }
+
+    #[test]
+    fn test_score_badge_svg_requires_file() {
+        assert!(Cli::try_parse_from(["omen", "score", "badge", "--svg"]).is_err());
+        let cli = parse(&["omen", "--output", "badge.json", "score", "badge"]);
+        assert_eq!(cli.output_file, Some(PathBuf::from("badge.json")));
+    }
+
     #[test]
     fn test_score_check_flag() {
         let cli = parse(&["omen", "score", "--check"]);
@@ -1367,6 +2564,107 @@ mod tests {
         assert!(parse(&["omen", "--shallow", "complexity"]).shallow);
     }
 
+    #[test]
+    fn test_timings_flag_is_global() {
+        assert!(parse(&["omen", "complexity", "--timings"]).timings);
+        assert!(!parse(&["omen", "complexity"]).timings);
+    }
+
+    #[test]
+    fn test_fail_on_flag_is_global() {
+        let cli = parse(&["omen", "smells", "--fail-on", "high"]);
+        assert!(matches!(cli.fail_on, Some(FailOn::High)));
+        assert!(parse(&["omen", "smells"]).fail_on.is_none());
+    }
+
+    #[test]
+    fn test_prioritize_by_flag() {
+        let cli = parse(&["omen", "satd", "--prioritize-by", "hotspot"]);
+        match cli.command {
+            Command::Satd(args) => {
+                assert!(matches!(args.prioritize_by, Some(PrioritizeBy::Hotspot)))
+            }
+            _ => panic!("expected satd"),
+        }
+        let cli = parse(&["omen", "flags", "--prioritize-by", "ownership"]);
+        match cli.command {
+            Command::Flags(args) => {
+                assert!(matches!(args.prioritize_by, Some(PrioritizeBy::Ownership)))
+            }
+            _ => panic!("expected flags"),
+        }
+        let cli = parse(&["omen", "smells"]);
+        match cli.command {
+            Command::Smells(args) => assert!(args.prioritize_by.is_none()),
+            _ => panic!("expected smells"),
+        }
+    }
+
+    #[test]
+    fn test_satd_compare_flag() {
+        match parse(&["omen", "satd", "--compare", "v1.0.0"]).command {
+            Command::Satd(args) => assert_eq!(args.compare, Some("v1.0.0".to_string())),
+            _ => panic!("expected satd"),
+        }
+        match parse(&["omen", "satd"]).command {
+            Command::Satd(args) => assert!(args.compare.is_none()),
+            _ => panic!("expected satd"),
+        }
+    }
+
+    #[test]
+    fn test_git_dir_and_at_ref_flags() {
+        let cli = parse(&[
+            "omen",
+            "--git-dir",
+            "/srv/mirror.git",
+            "--at-ref",
+            "release",
+            "churn",
+        ]);
+        assert_eq!(cli.git_dir, Some(PathBuf::from("/srv/mirror.git")));
+        assert_eq!(cli.at_ref, Some("release".to_string()));
+    }
+
+    #[test]
+    fn test_profile_flag() {
+        assert!(parse(&["omen", "satd"]).profile.is_none());
+        assert_eq!(
+            parse(&["omen", "all", "--profile", "ci"]).profile,
+            Some("ci".to_string())
+        );
+    }
+
+    #[test]
+    fn test_blame_flag() {
+        assert!(!parse(&["omen", "satd"]).blame);
+        assert!(parse(&["omen", "satd", "--blame"]).blame);
+        assert!(parse(&["omen", "--blame", "smells"]).blame);
+    }
+
+    #[test]
+    fn test_base_and_changed_only_flags() {
+        let cli = parse(&["omen", "--base", "origin/main", "complexity"]);
+        assert_eq!(cli.base, Some("origin/main".to_string()));
+        assert!(!cli.changed_only);
+
+        let cli = parse(&["omen", "smells", "--changed-only"]);
+        assert!(cli.changed_only);
+        assert!(cli.base.is_none());
+
+        assert!(Cli::try_parse_from(["omen", "--base", "main", "--changed-only", "satd"]).is_err());
+    }
+
+    #[test]
+    fn test_at_ref_after_subcommand() {
+        let cli = parse(&["omen", "complexity", "--at", "v1.0.0"]);
+        assert_eq!(cli.at_ref, Some("v1.0.0".to_string()));
+        assert!(matches!(cli.command, Command::Complexity(_)));
+
+        let cli = parse(&["omen", "hotspot", "--since-ref", "v0.9.0"]);
+        assert_eq!(cli.at_ref, Some("v0.9.0".to_string()));
+    }
+
     // Search command tests
 
     #[test]
@@ -1386,6 +2684,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_search_stats_and_prune() {
+        assert!(matches!(
+            parse_search_subcommand(&["omen", "search", "stats"]),
+            SearchSubcommand::Stats
+        ));
+        assert!(matches!(
+            parse_search_subcommand(&["omen", "search", "prune"]),
+            SearchSubcommand::Prune
+        ));
+    }
+
     #[test]
     fn test_command_search_query() {
         if let SearchSubcommand::Query(args) =
@@ -1444,6 +2754,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_query_rerank() {
+        if let SearchSubcommand::Query(args) =
+            parse_search_subcommand(&["omen", "search", "query", "test"])
+        {
+            assert!(!args.rerank);
+        }
+        if let SearchSubcommand::Query(args) =
+            parse_search_subcommand(&["omen", "search", "query", "test", "--rerank"])
+        {
+            assert!(args.rerank);
+        }
+    }
+
     // Complexity command tests
 
     #[test]
@@ -1493,8 +2817,9 @@ mod tests {
     #[test]
     fn test_mutation_defaults() {
         let args = parse_mutation_args(&["omen", "mutation"]);
-        assert_eq!(args.timeout, 30);
-        assert_eq!(args.operators, "CRR,ROR,AOR");
+        assert_eq!(args.timeout, 0);
+        assert_eq!(args.baseline_runs, 3);
+        assert!(args.operators.is_none());
         assert!(!args.check);
         assert!((args.min_score - 0.8).abs() < 0.001);
         assert!(!args.dry_run);
@@ -1504,6 +2829,17 @@ mod tests {
         assert!(!args.skip_equivalent);
         assert!(matches!(args.mode, MutationMode::All));
         assert!(args.output_survivors.is_none());
+        assert!(args.junit.is_none());
+        assert!(!args.tap);
+        assert!(!args.include_dependents);
+        assert!(!args.apply_suggestions);
+    }
+
+    #[test]
+    fn test_mutation_include_dependents() {
+        assert!(
+            parse_mutation_args(&["omen", "mutation", "--include-dependents"]).include_dependents
+        );
     }
 
     #[test]
@@ -1520,11 +2856,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mutation_baseline_runs() {
+        assert_eq!(
+            parse_mutation_args(&["omen", "mutation", "--baseline-runs", "0"]).baseline_runs,
+            0
+        );
+    }
+
     #[test]
     fn test_mutation_operators() {
         assert_eq!(
             parse_mutation_args(&["omen", "mutation", "--operators", "CRR,ROR"]).operators,
-            "CRR,ROR"
+            Some("CRR,ROR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mutation_apply_suggestions() {
+        assert!(
+            parse_mutation_args(&["omen", "mutation", "--apply-suggestions"]).apply_suggestions
+        );
+        assert!(
+            Cli::try_parse_from(["omen", "mutation", "--apply-suggestions", "--dry-run"]).is_err()
         );
     }
 
@@ -1544,6 +2898,20 @@ mod tests {
         assert!(parse_mutation_args(&["omen", "mutation", "--dry-run"]).dry_run);
     }
 
+    #[test]
+    fn test_estimate_flag() {
+        match parse(&["omen", "all", "--estimate"]).command {
+            Command::All(args) => assert!(args.estimate),
+            _ => panic!("expected All command"),
+        }
+        match parse_report_subcommand(&["omen", "report", "generate", "--estimate"]) {
+            ReportSubcommand::Generate(args) => assert!(args.estimate),
+            _ => panic!("expected report generate"),
+        }
+        assert!(parse_mutation_args(&["omen", "mutation", "--estimate"]).estimate);
+        assert!(!parse_mutation_args(&["omen", "mutation"]).estimate);
+    }
+
     #[test]
     fn test_mutation_jobs() {
         assert_eq!(
@@ -1593,6 +2961,13 @@ mod tests {
         assert_eq!(args.output_survivors, Some(PathBuf::from("survivors.json")));
     }
 
+    #[test]
+    fn test_mutation_junit_and_tap() {
+        let args = parse_mutation_args(&["omen", "mutation", "--junit", "out.xml", "--tap"]);
+        assert_eq!(args.junit, Some(PathBuf::from("out.xml")));
+        assert!(args.tap);
+    }
+
     #[test]
     fn test_mutation_combined_options() {
         let args = parse_mutation_args(&[
@@ -1651,12 +3026,19 @@ mod tests {
 
     #[test]
     fn test_cli_compact_flag() {
-        assert!(parse(&["omen", "--compact", "complexity"]).compact);
+        assert_eq!(
+            parse(&["omen", "--compact", "complexity"]).compact,
+            Some(CompactMode::Lines)
+        );
+        assert_eq!(
+            parse(&["omen", "--compact=strings", "complexity"]).compact,
+            Some(CompactMode::Strings)
+        );
     }
 
     #[test]
     fn test_cli_compact_default_false() {
-        assert!(!parse(&["omen", "complexity"]).compact);
+        assert!(parse(&["omen", "complexity"]).compact.is_none());
     }
 
     #[test]
@@ -1791,6 +3173,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_command_verify_manifest() {
+        let cli = parse(&["omen", "verify-manifest", "complexity.json", ".omen/data"]);
+        if let Command::VerifyManifest(args) = cli.command {
+            assert_eq!(
+                args.paths,
+                vec![
+                    PathBuf::from("complexity.json"),
+                    PathBuf::from(".omen/data")
+                ]
+            );
+        } else {
+            panic!("Expected VerifyManifest command");
+        }
+        assert!(Cli::try_parse_from(["omen", "verify-manifest"]).is_err());
+    }
+
+    // Query command tests
+
+    #[test]
+    fn test_command_query() {
+        assert_parses_to!(&["omen", "query", "score.grade"], Command::Query(_));
+        assert_parses_to!(&["omen", "q", "score.grade"], Command::Query(_));
+    }
+
+    #[test]
+    fn test_query_expr_and_common_args() {
+        let cli = parse(&[
+            "omen",
+            "query",
+            "complexity.files[?avg_cyclomatic > `10`].path",
+            "--glob",
+            "*.rs",
+        ]);
+        if let Command::Query(args) = cli.command {
+            assert_eq!(args.expr, "complexity.files[?avg_cyclomatic > `10`].path");
+            assert_eq!(args.common.glob.as_deref(), Some("*.rs"));
+        } else {
+            panic!("Expected Query command");
+        }
+    }
+
+    #[test]
+    fn test_run_analyzer_list() {
+        let cli = parse(&["omen", "run", "complexity,satd", "smells", "--top", "5"]);
+        if let Command::Run(args) = cli.command {
+            assert_eq!(args.analyzers, ["complexity", "satd", "smells"]);
+            assert_eq!(args.common.top, Some(5));
+        } else {
+            panic!("Expected Run command");
+        }
+        assert!(Cli::try_parse_from(["omen", "run"]).is_err());
+    }
+
     // Symbol command tests
 
     #[test]
@@ -1852,4 +3288,93 @@ mod tests {
             panic!("Expected Symbol command");
         }
     }
+
+    // Fix command tests
+
+    #[test]
+    fn test_fix_args() {
+        let cli = parse(&[
+            "omen",
+            "fix",
+            "--dry-run",
+            "--only",
+            "dead-code,resolved-satd",
+            "--rolled-out",
+            "new-checkout",
+        ]);
+        if let Command::Fix(args) = cli.command {
+            assert!(args.dry_run);
+            assert_eq!(args.only, vec![FixKind::DeadCode, FixKind::ResolvedSatd]);
+            assert_eq!(args.rolled_out, vec!["new-checkout"]);
+        } else {
+            panic!("Expected Fix command");
+        }
+    }
+
+    #[test]
+    fn test_tui_command() {
+        let cli = parse(&["omen", "tui", "--glob", "src/**"]);
+        if let Command::Tui(args) = cli.command {
+            assert_eq!(args.glob.as_deref(), Some("src/**"));
+        } else {
+            panic!("Expected Tui command");
+        }
+    }
+
+    #[test]
+    fn test_completions_command() {
+        let cli = parse(&["omen", "completions", "zsh"]);
+        if let Command::Completions(args) = cli.command {
+            assert_eq!(args.shell, Shell::Zsh);
+        } else {
+            panic!("Expected Completions command");
+        }
+    }
+
+    #[test]
+    fn test_completions_rejects_unknown_shell() {
+        assert!(Cli::try_parse_from(["omen", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn test_write_completions_covers_subcommands() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut out = Vec::new();
+            write_completions(shell, &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(
+                script.contains("omen"),
+                "{shell} script missing binary name"
+            );
+            assert!(
+                script.contains("complexity"),
+                "{shell} script missing subcommands"
+            );
+        }
+    }
+
+    #[test]
+    fn test_manpages_command() {
+        let cli = parse(&["omen", "manpages", "target/man"]);
+        if let Command::Manpages(args) = cli.command {
+            assert_eq!(args.dir, PathBuf::from("target/man"));
+        } else {
+            panic!("Expected Manpages command");
+        }
+    }
+
+    #[test]
+    fn test_write_manpages_includes_subcommands() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("man1");
+        let written = write_manpages(&dir).unwrap();
+
+        assert_eq!(written[0], dir.join("omen.1"));
+        assert!(dir.join("omen-complexity.1").exists());
+        assert!(dir.join("omen-mutation-train.1").exists());
+
+        let root = std::fs::read_to_string(dir.join("omen.1")).unwrap();
+        assert!(root.contains(".TH"));
+        assert!(root.contains("complexity"));
+    }
 }
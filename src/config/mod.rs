@@ -1,5 +1,6 @@
 //! Configuration loading and management.
 
+use std::collections::HashMap;
 use std::path::Path;
 
 use figment::{
@@ -8,7 +9,7 @@ use figment::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::core::Result;
+use crate::core::{Result, Severity};
 
 /// Main configuration structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +28,8 @@ pub struct Config {
     pub duplicates: DuplicatesConfig,
     /// Hotspot configuration.
     pub hotspot: HotspotConfig,
+    /// Ownership configuration.
+    pub ownership: OwnershipConfig,
     /// Score thresholds.
     pub score: ScoreConfig,
     /// Feature flag configuration.
@@ -39,6 +42,36 @@ pub struct Config {
     pub exclude_built_assets: bool,
     /// Changes/JIT analyzer configuration.
     pub changes: ChangesConfig,
+    /// Dependency graph configuration.
+    pub graph: GraphConfig,
+    /// Architectural smells configuration.
+    pub smells: SmellsConfig,
+    /// Dockerfile hygiene configuration.
+    pub docker: DockerConfig,
+    /// Documentation drift configuration.
+    pub docdrift: DocDriftConfig,
+    /// Public API surface configuration.
+    pub api: ApiConfig,
+    /// Mutation testing configuration.
+    pub mutation: MutationConfig,
+    /// Git repository configuration.
+    pub git: GitConfig,
+    /// Commit message analyzer configuration.
+    pub commits: CommitsConfig,
+    /// How flag, SATD, and smell findings are ranked.
+    pub prioritize_by: PrioritizeBy,
+    /// Business criticality of paths, keyed by glob (e.g. `"payments/**"`).
+    pub criticality: HashMap<String, Criticality>,
+    /// Handling of generated and macro-expanded code.
+    pub generated: GeneratedConfig,
+    /// Semantic search configuration.
+    pub search: SearchConfig,
+    /// `omen context` configuration.
+    pub context: ContextConfig,
+    /// Analyzers left out of `omen all` and `omen report generate`.
+    pub skip: Vec<String>,
+    /// Exit non-zero when any finding is at or above this severity.
+    pub fail_on: Option<Severity>,
 }
 
 impl Default for Config {
@@ -50,12 +83,28 @@ impl Default for Config {
             churn: ChurnConfig::default(),
             duplicates: DuplicatesConfig::default(),
             hotspot: HotspotConfig::default(),
+            ownership: OwnershipConfig::default(),
             score: ScoreConfig::default(),
             feature_flags: FeatureFlagsConfig::default(),
             temporal: TemporalConfig::default(),
             output: OutputConfig::default(),
             exclude_built_assets: true,
             changes: ChangesConfig::default(),
+            graph: GraphConfig::default(),
+            smells: SmellsConfig::default(),
+            docker: DockerConfig::default(),
+            docdrift: DocDriftConfig::default(),
+            api: ApiConfig::default(),
+            mutation: MutationConfig::default(),
+            git: GitConfig::default(),
+            commits: CommitsConfig::default(),
+            prioritize_by: PrioritizeBy::default(),
+            criticality: HashMap::new(),
+            generated: GeneratedConfig::default(),
+            search: SearchConfig::default(),
+            context: ContextConfig::default(),
+            skip: Vec::new(),
+            fail_on: None,
         }
     }
 }
@@ -66,6 +115,14 @@ impl Config {
     /// Errors if the file does not exist. Use this for explicit `--config` flags.
     /// Env vars with `OMEN_` prefix override file values.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_file_with_profile(path, None)
+    }
+
+    /// Load configuration from an explicit file path, applying `[profile.<name>]`.
+    ///
+    /// The profile's tables are merged over the rest of the file; env vars
+    /// still override both. Errors if the profile is not defined.
+    pub fn from_file_with_profile(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self> {
         let path = path.as_ref();
         if !path.exists() {
             return Err(crate::core::Error::Config(format!(
@@ -73,12 +130,9 @@ impl Config {
                 path.display()
             )));
         }
-        let config: Self = Figment::from(Serialized::defaults(Self::default()))
-            .merge(Toml::file_exact(path))
-            .merge(Env::prefixed("OMEN_").split("__"))
-            .extract()
-            .map_err(|e| crate::core::Error::Config(e.to_string()))?;
-        Ok(config)
+        let figment =
+            Figment::from(Serialized::defaults(Self::default())).merge(Toml::file_exact(path));
+        Self::extract(figment, profile)
     }
 
     /// Alias for from_file.
@@ -91,14 +145,18 @@ impl Config {
     /// Missing files are silently skipped (defaults are used).
     /// Env vars with `OMEN_` prefix override file/default values.
     pub fn load_default(dir: impl AsRef<Path>) -> Result<Self> {
+        Self::load_default_with_profile(dir, None)
+    }
+
+    /// Load configuration from directory, applying `[profile.<name>]`.
+    ///
+    /// See [`Config::from_file_with_profile`] for how the profile is merged.
+    pub fn load_default_with_profile(dir: impl AsRef<Path>, profile: Option<&str>) -> Result<Self> {
         let dir = dir.as_ref();
-        let config: Self = Figment::from(Serialized::defaults(Self::default()))
+        let figment = Figment::from(Serialized::defaults(Self::default()))
             .merge(Toml::file(dir.join("omen.toml")))
-            .merge(Toml::file(dir.join(".omen/omen.toml")))
-            .merge(Env::prefixed("OMEN_").split("__"))
-            .extract()
-            .map_err(|e| crate::core::Error::Config(e.to_string()))?;
-        Ok(config)
+            .merge(Toml::file(dir.join(".omen/omen.toml")));
+        Self::extract(figment, profile)
     }
 
     /// Alias for load_default.
@@ -106,12 +164,95 @@ impl Config {
         Self::load_default(dir)
     }
 
+    /// Merge the selected profile and `OMEN_` env vars over the loaded files.
+    fn extract(figment: Figment, profile: Option<&str>) -> Result<Self> {
+        let figment = match profile {
+            Some(name) => {
+                let key = format!("profile.{name}");
+                if !figment.contains(&key) {
+                    return Err(crate::core::Error::Config(format!(
+                        "profile not found: {name}"
+                    )));
+                }
+                let overrides = figment.focus(&key);
+                figment.merge(overrides)
+            }
+            None => figment,
+        };
+        let config: Self = figment
+            // OMEN_PROFILE selects a profile; it is not a config key.
+            .merge(Env::prefixed("OMEN_").split("__").ignore(&["profile"]))
+            .extract()
+            .map_err(|e| crate::core::Error::Config(e.to_string()))?;
+        config.score.weights.validate()?;
+        Ok(config)
+    }
+
     /// Create default config file content.
     pub fn default_toml() -> &'static str {
         include_str!("default_config.toml")
     }
 }
 
+/// Set `key` in `[table]` of the config file at `path` to `value`, a TOML
+/// literal, creating the file or table as needed.
+///
+/// The file is edited as a TOML document, so its comments and layout
+/// survive, whether the table has a header, is set with dotted keys
+/// (`table.key = ...`) or is written inline.
+pub fn set_value(path: &Path, table: &str, key: &str, value: &str) -> Result<()> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let updated = with_value(&content, table, key, value).map_err(|reason| {
+        crate::core::Error::Config(format!(
+            "can't set {table}.{key} in {}: {reason}; edit it by hand",
+            path.display()
+        ))
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, updated)?;
+    Ok(())
+}
+
+/// `content` with `table.key` set to `value`, keeping the decoration
+/// (spacing and trailing comment) of a value it replaces.
+fn with_value(
+    content: &str,
+    table: &str,
+    key: &str,
+    value: &str,
+) -> std::result::Result<String, String> {
+    let mut doc: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|e| format!("it does not parse: {e}"))?;
+    let mut parsed: toml_edit::Value = value
+        .parse()
+        .map_err(|e| format!("invalid value {value}: {e}"))?;
+
+    let item = doc.entry(table).or_insert(toml_edit::table());
+    if let Some(entries) = item.as_table_mut() {
+        // A table only named by its subtables' headers gets its own header.
+        if !entries.is_dotted() {
+            entries.set_implicit(false);
+        }
+    }
+    let entries = item
+        .as_table_like_mut()
+        .ok_or_else(|| format!("`{table}` is not a table"))?;
+    match entries.get(key) {
+        Some(toml_edit::Item::Value(old)) => *parsed.decor_mut() = old.decor().clone(),
+        Some(old) if !old.is_none() => return Err(format!("`{table}.{key}` is a table")),
+        _ => {}
+    }
+    entries.insert(key, toml_edit::Item::Value(parsed));
+    Ok(doc.to_string())
+}
+
 /// Complexity analyzer configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -126,6 +267,9 @@ pub struct ComplexityConfig {
     pub cognitive_error: u32,
     /// Maximum nesting depth.
     pub max_nesting: u32,
+    /// Leave trivial functions (accessors, one-call delegations) out of the
+    /// summary percentiles. They are still listed and counted.
+    pub exclude_trivial_from_percentiles: bool,
 }
 
 impl Default for ComplexityConfig {
@@ -136,6 +280,7 @@ impl Default for ComplexityConfig {
             cognitive_warn: 15,
             cognitive_error: 30,
             max_nesting: 5,
+            exclude_trivial_from_percentiles: false,
         }
     }
 }
@@ -144,10 +289,40 @@ impl Default for ComplexityConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SatdConfig {
-    /// Categories to detect.
+    /// Built-in categories to detect.
     pub categories: Vec<String>,
-    /// Custom markers to detect.
+    /// Custom markers to detect, in the `custom` category.
     pub custom_markers: Vec<String>,
+    /// Custom markers and overrides for built-in ones, keyed by marker.
+    pub markers: HashMap<String, SatdMarker>,
+    /// Weight of each category, overriding the built-in weight. The weight
+    /// sets the severity: >= 4 critical, >= 2 high, >= 1 medium, else low.
+    pub category_weights: HashMap<String, f64>,
+    /// Extra comment prefixes per language (e.g. `ruby = ["=begin"]`).
+    pub comment_prefixes: HashMap<String, Vec<String>>,
+    /// Revision (branch, tag, SHA) to report added and resolved debt against.
+    pub compare_ref: Option<String>,
+}
+
+/// A `[satd.markers]` entry: a severity (`SECURITY = "critical"`) or a
+/// table with `category`, `severity` and `pattern`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum SatdMarker {
+    Severity(Severity),
+    Rule(SatdMarkerRule),
+}
+
+/// A custom SATD marker, or an override for a built-in one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SatdMarkerRule {
+    /// Category; built-in markers keep theirs, custom ones default to `custom`.
+    pub category: Option<String>,
+    /// Severity; defaults to the category's.
+    pub severity: Option<Severity>,
+    /// Regex to match instead of the marker as a whole word (e.g. `SEC-\d+`).
+    pub pattern: Option<String>,
 }
 
 impl Default for SatdConfig {
@@ -160,8 +335,13 @@ impl Default for SatdConfig {
                 "test".to_string(),
                 "performance".to_string(),
                 "security".to_string(),
+                "documentation".to_string(),
             ],
             custom_markers: Vec::new(),
+            markers: HashMap::new(),
+            category_weights: HashMap::new(),
+            comment_prefixes: HashMap::new(),
+            compare_ref: None,
         }
     }
 }
@@ -174,6 +354,8 @@ pub struct ChurnConfig {
     pub since: String,
     /// Number of top files to report.
     pub top: usize,
+    /// When commits count as after-hours or weekend work.
+    pub off_hours: OffHoursConfig,
 }
 
 impl Default for ChurnConfig {
@@ -181,10 +363,56 @@ impl Default for ChurnConfig {
         Self {
             since: "6m".to_string(),
             top: 20,
+            off_hours: OffHoursConfig::default(),
+        }
+    }
+}
+
+/// After-hours and weekend commit metrics for churn and hotspot analysis.
+///
+/// Commit times are UTC; each is read in a fixed UTC offset, its author's
+/// team's or the default. Offsets don't follow daylight saving time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OffHoursConfig {
+    /// Report after-hours and weekend commit ratios.
+    pub enabled: bool,
+    /// UTC offset commit times are read in, e.g. "-05:00".
+    pub timezone: String,
+    /// Hour the working day starts, local time (0-23).
+    pub workday_start: u32,
+    /// Hour the working day ends, local time (1-24).
+    pub workday_end: u32,
+    /// Days that are weekend: "mon" through "sun".
+    pub weekend: Vec<String>,
+    /// Teams reported separately, each in its own time zone.
+    pub teams: Vec<OffHoursTeam>,
+}
+
+impl Default for OffHoursConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timezone: "+00:00".to_string(),
+            workday_start: 9,
+            workday_end: 18,
+            weekend: vec!["sat".to_string(), "sun".to_string()],
+            teams: Vec::new(),
         }
     }
 }
 
+/// A team for off-hours metrics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OffHoursTeam {
+    pub name: String,
+    /// Author names or emails.
+    pub members: Vec<String>,
+    /// UTC offset of the team's commits; the default offset when unset.
+    pub timezone: Option<String>,
+}
+
 /// Clone detection configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -193,40 +421,158 @@ pub struct DuplicatesConfig {
     pub min_tokens: usize,
     /// Minimum similarity threshold (0.0-1.0).
     pub min_similarity: f64,
+    /// Minimum number of non-blank, non-comment lines in a clone.
+    pub min_lines: usize,
+    /// Detect clones between different ranges of the same file.
+    pub intra_file: bool,
+    /// Also match fragments whose syntax trees have the same shape, so
+    /// reformatted or renamed copies are found as Type-2 clones.
+    pub structural: bool,
+    /// Per-language overrides keyed by language name (e.g. `python`, `go`).
+    pub languages: HashMap<String, DuplicatesLanguageConfig>,
 }
 
 impl Default for DuplicatesConfig {
     fn default() -> Self {
         Self {
             min_tokens: 50,
-            min_similarity: 0.9,
+            min_similarity: 0.70,
+            min_lines: 6,
+            intra_file: true,
+            structural: true,
+            languages: HashMap::new(),
         }
     }
 }
 
+/// Per-language clone detection thresholds; unset values fall back to
+/// the `[duplicates]` defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuplicatesLanguageConfig {
+    pub min_tokens: Option<usize>,
+    pub min_similarity: Option<f64>,
+    pub min_lines: Option<usize>,
+}
+
 /// Hotspot analyzer configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HotspotConfig {
-    /// Number of top hotspots to report.
+    /// Number of top hotspots `omen hotspot` reports (0 for all).
     pub top: usize,
+    /// Weight of churn against complexity in the score, 0.0-1.0.
+    pub churn_weight: f64,
+    /// How churn and complexity are normalized before scoring.
+    pub normalization: HotspotNormalization,
+    /// Halve a commit's churn for every this many days of age.
+    pub churn_half_life_days: Option<f64>,
 }
 
 impl Default for HotspotConfig {
     fn default() -> Self {
-        Self { top: 20 }
+        Self {
+            top: 20,
+            churn_weight: 0.5,
+            normalization: HotspotNormalization::Rank,
+            churn_half_life_days: None,
+        }
     }
 }
 
+/// Normalization of hotspot churn and complexity.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HotspotNormalization {
+    /// Percentile rank among the analyzed files.
+    #[default]
+    Rank,
+    /// Normal CDF of the z-score; keeps the size of outliers' lead.
+    Zscore,
+}
+
 /// Score configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
-#[derive(Default)]
 pub struct ScoreConfig {
     /// Minimum overall score to pass.
     pub fail_under: Option<f64>,
     /// Component thresholds.
     pub thresholds: ScoreThresholds,
+    /// Score each workspace package separately and weight packages by size.
+    /// Unset scores the repository as one unit.
+    pub monorepo: Option<PackageWeight>,
+    /// Metric budgets, reported with their trend and projected date.
+    pub budgets: Vec<Budget>,
+    /// History `omen score` samples to project budgets (default "3m").
+    pub budget_since: Option<String>,
+    /// Weight of each component in the overall score.
+    pub weights: ScoreWeights,
+    /// Persist `omen score trend` results per commit under `.omen/trend-cache`.
+    pub trend_cache: bool,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            fail_under: None,
+            thresholds: ScoreThresholds::default(),
+            monorepo: None,
+            budgets: Vec::new(),
+            budget_since: None,
+            weights: ScoreWeights::default(),
+            trend_cache: true,
+        }
+    }
+}
+
+/// Package size measure for monorepo score aggregation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageWeight {
+    /// Non-blank, non-comment lines of code.
+    Loc,
+    /// Number of analyzed files.
+    Files,
+}
+
+/// A target for one metric, optionally with a deadline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Budget {
+    pub metric: BudgetMetric,
+    /// Value to reach: a ceiling, or a floor for `score`.
+    pub target: f64,
+    /// Deadline as `YYYY-MM-DD` or a quarter such as `2026-Q3`.
+    #[serde(default)]
+    pub by: Option<String>,
+}
+
+/// Metric a budget applies to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetMetric {
+    /// Overall health score.
+    Score,
+    /// Number of SATD items.
+    SatdItems,
+    /// Duplicated share of the code, in percent.
+    Duplication,
+    /// 90th percentile cyclomatic complexity.
+    ComplexityP90,
+    /// Number of architectural smells.
+    Smells,
+}
+
+impl BudgetMetric {
+    /// Whether the metric improves upwards, making its budget a floor.
+    pub fn higher_is_better(self) -> bool {
+        matches!(self, Self::Score)
+    }
+
+    /// Whether per-package values add up to the repository's value.
+    pub fn is_count(self) -> bool {
+        matches!(self, Self::SatdItems | Self::Smells)
+    }
 }
 
 /// Score component thresholds.
@@ -243,17 +589,117 @@ pub struct ScoreThresholds {
     pub cohesion: Option<f64>,
 }
 
+/// Weight of each component in the overall score. Components weighted 0
+/// are not run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreWeights {
+    pub complexity: f64,
+    pub satd: f64,
+    pub deadcode: f64,
+    pub churn: f64,
+    pub duplicates: f64,
+    pub defect: f64,
+    pub ownership: f64,
+    pub cohesion: f64,
+    pub tdg: f64,
+    pub coupling: f64,
+    pub smells: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        // Weights based on 3.x report component importance:
+        // Complexity 25%, Duplication 20%, Cohesion 15%, TDG 15%,
+        // Known Debt 10%, Coupling 10%, Smells 5%
+        Self {
+            complexity: 1.0, // 25% - highest priority
+            duplicates: 0.8, // 20%
+            cohesion: 0.6,   // 15%
+            tdg: 0.6,        // 15%
+            satd: 0.4,       // 10%
+            coupling: 0.4,   // 10%
+            smells: 0.2,     // 5%
+            deadcode: 0.0,   // Not in 3.x display
+            churn: 0.0,      // Not in 3.x display
+            defect: 0.0,     // Not in 3.x display
+            ownership: 0.0,  // Not in 3.x display
+        }
+    }
+}
+
+impl ScoreWeights {
+    fn entries(&self) -> [(&'static str, f64); 11] {
+        [
+            ("complexity", self.complexity),
+            ("satd", self.satd),
+            ("deadcode", self.deadcode),
+            ("churn", self.churn),
+            ("duplicates", self.duplicates),
+            ("defect", self.defect),
+            ("ownership", self.ownership),
+            ("cohesion", self.cohesion),
+            ("tdg", self.tdg),
+            ("coupling", self.coupling),
+            ("smells", self.smells),
+        ]
+    }
+
+    /// Reject negative weights, and weights that would leave nothing to score.
+    pub fn validate(&self) -> Result<()> {
+        for (name, weight) in self.entries() {
+            // Written this way round so NaN fails too.
+            if !(weight >= 0.0 && weight.is_finite()) {
+                return Err(crate::core::Error::Config(format!(
+                    "score.weights.{name} must be a number >= 0, got {weight}"
+                )));
+            }
+        }
+        if self.entries().iter().all(|&(_, weight)| weight == 0.0) {
+            return Err(crate::core::Error::Config(
+                "score.weights: at least one weight must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Feature flag detection configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct FeatureFlagsConfig {
     /// Days before a flag is considered stale.
     pub stale_days: u32,
     /// Built-in providers to enable (e.g., "launchdarkly", "flipper", "split", "unleash", "generic", "env").
-    /// If empty, no built-in providers are used; you must explicitly list providers to enable them.
+    /// If empty, only auto-detected built-in providers are used.
     pub providers: Vec<String>,
+    /// Also enable built-in providers whose SDK is a dependency in
+    /// `package.json`, `Cargo.toml`, `go.mod`, `Gemfile` or `requirements.txt`.
+    pub auto_detect: bool,
     /// Custom providers defined via tree-sitter queries.
     pub custom_providers: Vec<CustomProvider>,
+    /// Flag keys known to be fully rolled out, in addition to those
+    /// LaunchDarkly or Unleash report. `omen fix` inlines the checks of
+    /// those that are also stale.
+    pub rolled_out: Vec<String>,
+    /// Provider project whose rollouts `omen fix` looks up.
+    pub project: String,
+    /// Provider environment whose rollouts `omen fix` looks up.
+    pub environment: String,
+}
+
+impl Default for FeatureFlagsConfig {
+    fn default() -> Self {
+        Self {
+            stale_days: 0,
+            providers: Vec::new(),
+            auto_detect: true,
+            custom_providers: Vec::new(),
+            rolled_out: Vec::new(),
+            project: "default".to_string(),
+            environment: "production".to_string(),
+        }
+    }
 }
 
 /// Custom feature flag provider.
@@ -274,16 +720,52 @@ pub struct CustomProvider {
 pub struct TemporalConfig {
     /// Exclude test files from coupling analysis.
     pub exclude_tests: bool,
+    /// Ignore commits touching more files than this (bulk renames,
+    /// formatter runs).
+    pub max_commit_files: usize,
+    /// Weight each co-change by `1 / (files in the commit - 1)`, so large
+    /// commits contribute less to coupling strength than small ones.
+    pub weight_by_commit_size: bool,
+    /// Leave renamed files out of co-changes.
+    pub exclude_renames: bool,
+    /// How commits are grouped into logical changes before counting
+    /// co-changes.
+    pub group_by: CommitGrouping,
+    /// Regex for the ticket ID in a commit message, for `group_by = "ticket"`.
+    pub ticket_pattern: String,
+    /// Largest gap between one author's commits in a group, for
+    /// `group_by = "author"`.
+    pub group_window_minutes: u64,
 }
 
 impl Default for TemporalConfig {
     fn default() -> Self {
         Self {
             exclude_tests: true,
+            max_commit_files: 100,
+            weight_by_commit_size: false,
+            exclude_renames: false,
+            group_by: CommitGrouping::Commit,
+            ticket_pattern: r"\b[A-Z][A-Z0-9]+-\d+\b".to_string(),
+            group_window_minutes: 30,
         }
     }
 }
 
+/// How temporal coupling groups commits into logical changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitGrouping {
+    /// Each commit is its own change.
+    #[default]
+    Commit,
+    /// Commits naming the same ticket ID are one change.
+    Ticket,
+    /// One author's commits within `group_window_minutes` of each other
+    /// are one change.
+    Author,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct OutputConfig {
@@ -308,14 +790,339 @@ impl Default for OutputConfig {
 pub struct ChangesConfig {
     /// Number of days of history to analyze.
     pub days: u32,
+    /// `omen diff` flags branches touching more than this many hotspots.
+    pub max_hotspots: usize,
+    /// Net lines removed from a test file that `omen diff` treats as
+    /// deleting tests.
+    pub test_deletion_lines: u32,
+    /// `omen diff --staged`/`--worktree` exit non-zero when any hunk's risk
+    /// score (0.0-1.0) is above this.
+    pub fail_above: Option<f64>,
+    /// `omen diff` exits non-zero when a branch adds more net cyclomatic
+    /// complexity than this.
+    pub complexity_budget: Option<u32>,
 }
 
 impl Default for ChangesConfig {
     fn default() -> Self {
-        Self { days: 30 }
+        Self {
+            days: 30,
+            max_hotspots: 2,
+            test_deletion_lines: 20,
+            fail_above: None,
+            complexity_budget: None,
+        }
     }
 }
 
+/// Dependency graph configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphConfig {
+    /// Add nodes for imports that do not resolve to an analyzed file.
+    pub include_external: bool,
+    /// Drop stdlib, third-party, and cross-package edges before computing metrics.
+    pub internal_only: bool,
+    /// Remove re-export-only files (barrels) from the graph, attributing their
+    /// incoming edges to the files that define the re-exported items.
+    pub collapse_barrels: bool,
+    /// Count dynamic imports (`import()`, `importlib`, `Class.forName`) as
+    /// cycle edges. Off by default: lazy loading is how cycles get broken.
+    pub dynamic_cycles: bool,
+    /// Also group files by directory into packages with Martin's coupling,
+    /// instability, abstractness, and main-sequence distance metrics.
+    pub packages: bool,
+    /// Render the graph as a diagram in this format alongside the analysis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<GraphFormat>,
+}
+
+/// Diagram format for dependency graph export.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// GraphML (XML), for yEd, Gephi, and graph libraries.
+    Graphml,
+    /// Mermaid flowchart, for embedding in Markdown docs.
+    Mermaid,
+}
+
+/// Architectural smells configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmellsConfig {
+    /// Injected constructor/field dependencies above which a DI-managed
+    /// class is reported as constructor over-injection.
+    pub max_injected_dependencies: usize,
+}
+
+impl Default for SmellsConfig {
+    fn default() -> Self {
+        Self {
+            max_injected_dependencies: 7,
+        }
+    }
+}
+
+/// Dockerfile hygiene configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DockerConfig {
+    /// Layer-creating instructions (`RUN`, `COPY`, `ADD`) per build stage
+    /// above which the stage is reported.
+    pub max_layers: usize,
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        Self { max_layers: 15 }
+    }
+}
+
+/// Ownership configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OwnershipConfig {
+    /// Blame every file instead of estimating ownership from one log pass.
+    pub precise: bool,
+}
+
+/// Documentation drift configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DocDriftConfig {
+    /// Also check private functions, not just the public API.
+    pub include_private: bool,
+}
+
+/// Public API surface configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// History window (e.g., "3m", "1y") whose start is the baseline the
+    /// surface is compared with.
+    pub since: String,
+    /// Revision to compare with instead (branch, tag, SHA), such as the
+    /// last release.
+    pub base: Option<String>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            since: "3m".to_string(),
+            base: None,
+        }
+    }
+}
+
+/// Mutation testing configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutationConfig {
+    /// Operators to generate mutants with when `--operators` is not given.
+    /// `omen mutation --apply-suggestions` rewrites this list.
+    pub operators: Vec<String>,
+}
+
+impl Default for MutationConfig {
+    fn default() -> Self {
+        Self {
+            operators: vec!["CRR".to_string(), "ROR".to_string(), "AOR".to_string()],
+        }
+    }
+}
+
+/// Git repository configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitConfig {
+    /// Revision (branch, tag, SHA) to analyze instead of HEAD and the worktree.
+    /// File contents are read from the object database, so this also works
+    /// for bare repositories.
+    pub at_ref: Option<String>,
+    /// Annotate SATD items, complexity violations and smells with the commit
+    /// that last changed their lines.
+    pub blame: bool,
+    /// Persist blame results under `.omen/cache/blame`, keyed by file contents
+    /// and commit.
+    pub blame_cache: bool,
+    /// Analyze only files changed since the merge base with this revision,
+    /// and mark findings on changed lines.
+    pub base: Option<String>,
+    /// Merge author identities listed in the repository's `.mailmap`.
+    pub mailmap: bool,
+    /// Author aliases: each canonical `Name` or `Name <email>` with the
+    /// other names and emails its author commits under.
+    pub identities: HashMap<String, Vec<String>>,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            at_ref: None,
+            blame: false,
+            blame_cache: true,
+            base: None,
+            mailmap: true,
+            identities: HashMap::new(),
+        }
+    }
+}
+
+/// Commit message analyzer configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitsConfig {
+    /// Number of days of history to analyze.
+    pub days: u32,
+    /// Longest subject line (in characters) before it is flagged.
+    pub max_subject_length: usize,
+    /// Accepted conventional-commit types.
+    pub types: Vec<String>,
+    /// Minimum conventional-commit compliance (0-1) for check mode.
+    pub min_compliance: f64,
+    /// Fail check mode when `fixup!`/`squash!` commits are present.
+    pub fail_on_fixups: bool,
+}
+
+impl Default for CommitsConfig {
+    fn default() -> Self {
+        Self {
+            days: 30,
+            max_subject_length: 72,
+            types: [
+                "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore",
+                "revert",
+            ]
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+            min_compliance: 0.8,
+            fail_on_fixups: true,
+        }
+    }
+}
+
+/// Generated and macro-expanded code configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneratedConfig {
+    /// Handling of generated files in complexity analysis.
+    pub complexity: GeneratedHandling,
+    /// Handling of generated files in cohesion (CK) analysis.
+    pub cohesion: GeneratedHandling,
+    /// Multiplier (0-1) applied to the metrics of down-weighted files.
+    pub weight: f64,
+    /// Share of lines inside macro invocations above which a Rust file
+    /// counts as macro-expanded.
+    pub macro_ratio: f64,
+    /// Extra header markers that identify generated files.
+    pub markers: Vec<String>,
+}
+
+impl Default for GeneratedConfig {
+    fn default() -> Self {
+        Self {
+            complexity: GeneratedHandling::Downweight,
+            cohesion: GeneratedHandling::Downweight,
+            weight: 0.25,
+            macro_ratio: 0.5,
+            markers: Vec::new(),
+        }
+    }
+}
+
+/// What an analyzer does with generated or macro-expanded files.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratedHandling {
+    /// Analyze them like hand-written code.
+    Include,
+    /// Leave them out of the results.
+    Exclude,
+    /// Keep them, scaling their metrics by `weight`.
+    #[default]
+    Downweight,
+}
+
+/// Semantic search configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Query-time reranking of results by analyzer signals.
+    pub rerank: RerankConfig,
+}
+
+/// Weights blending a search result's similarity score with what omen knows
+/// about the symbol, so central implementations outrank look-alike helpers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerankConfig {
+    /// Rerank every query, not only those run with `--rerank`.
+    pub enabled: bool,
+    /// Results fetched and reranked per query; the best `top_k` are kept.
+    pub candidates: usize,
+    /// Weight of the similarity score.
+    pub semantic: f64,
+    /// Weight of the symbol's PageRank percentile in the repo map.
+    pub pagerank: f64,
+    /// Weight of the file's hotspot score.
+    pub hotspot: f64,
+    /// Weight of the symbol's cyclomatic complexity.
+    pub complexity: f64,
+}
+
+impl Default for RerankConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            candidates: 50,
+            semantic: 0.6,
+            pagerank: 0.25,
+            hotspot: 0.1,
+            complexity: 0.05,
+        }
+    }
+}
+
+/// `omen context` configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextConfig {
+    /// How text is counted against `--max-tokens`.
+    pub tokenizer: Tokenizer,
+}
+
+/// Token counting for context budgets.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Tokenizer {
+    /// One token per four characters, the usual rule of thumb.
+    #[default]
+    Chars,
+    /// One token per word or punctuation mark, plus one per further eight
+    /// characters of a long word. Closer to BPE tokenizers on code.
+    Words,
+    /// One token per byte, an upper bound for any tokenizer.
+    Bytes,
+}
+
+/// Re-ranking strategy for flag, SATD, and smell findings.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrioritizeBy {
+    /// Keep each analyzer's own ordering.
+    #[default]
+    None,
+    /// Rank by ownership concentration of the touched files.
+    Ownership,
+    /// Rank by the hotspot (churn x complexity) score of the touched files.
+    Hotspot,
+}
+
 /// Output format.
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -342,6 +1149,35 @@ impl std::str::FromStr for OutputFormat {
     }
 }
 
+/// Business criticality of a path, used by the risk matrix.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Criticality {
+    /// Tooling, docs, and other code whose failure has little impact.
+    Low,
+    /// Paths without a criticality tag.
+    #[default]
+    Normal,
+    /// Important user-facing functionality.
+    High,
+    /// Revenue, security, or data-integrity paths.
+    Critical,
+}
+
+impl std::fmt::Display for Criticality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+            Self::Critical => "critical",
+        };
+        f.write_str(name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,6 +1324,123 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_profile_overrides_base_values() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "omen.toml",
+                "skip = [\"churn\"]\n\
+                 [complexity]\ncyclomatic_warn = 15\ncyclomatic_error = 25\n\
+                 [profile.ci]\nskip = [\"churn\", \"temporal\"]\n\
+                 [profile.ci.complexity]\ncyclomatic_warn = 8\n",
+            )?;
+            let config = Config::load_default_with_profile(".", Some("ci")).unwrap();
+            assert_eq!(config.complexity.cyclomatic_warn, 8);
+            assert_eq!(config.complexity.cyclomatic_error, 25);
+            assert_eq!(config.skip, ["churn", "temporal"]);
+
+            let config = Config::load_default(".").unwrap();
+            assert_eq!(config.complexity.cyclomatic_warn, 15);
+            assert_eq!(config.skip, ["churn"]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_env_var_overrides_profile_value() {
+        Jail::expect_with(|jail| {
+            jail.create_file("omen.toml", "[profile.nightly.churn]\ntop = 100")?;
+            jail.set_env("OMEN_CHURN__TOP", "7");
+            jail.set_env("OMEN_PROFILE", "nightly");
+            let config = Config::from_file_with_profile("omen.toml", Some("nightly")).unwrap();
+            assert_eq!(config.churn.top, 7);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_fail_on_from_profile() {
+        Jail::expect_with(|jail| {
+            jail.create_file("omen.toml", "[profile.ci]\nfail_on = \"high\"")?;
+            assert_eq!(Config::load_default(".").unwrap().fail_on, None);
+            let config = Config::load_default_with_profile(".", Some("ci")).unwrap();
+            assert_eq!(config.fail_on, Some(Severity::High));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_score_budgets_from_file() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "omen.toml",
+                "[[score.budgets]]\nmetric = \"satd_items\"\ntarget = 150\nby = \"2026-Q3\"\n\n\
+                 [[score.budgets]]\nmetric = \"score\"\ntarget = 80\n",
+            )?;
+            let config = Config::load_default(".").unwrap();
+            assert_eq!(config.score.budgets.len(), 2);
+            assert_eq!(config.score.budgets[0].metric, BudgetMetric::SatdItems);
+            assert_eq!(config.score.budgets[0].by.as_deref(), Some("2026-Q3"));
+            assert!(config.score.budgets[1].metric.higher_is_better());
+            assert_eq!(config.score.budgets[1].by, None);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_score_weights_from_file() {
+        Jail::expect_with(|jail| {
+            jail.create_file("omen.toml", "[score.weights]\nchurn = 0.5\nsmells = 0\n")?;
+            let config = Config::load_default(".").unwrap();
+            assert_eq!(config.score.weights.churn, 0.5);
+            assert_eq!(config.score.weights.smells, 0.0);
+            // Unset weights keep their defaults.
+            assert_eq!(config.score.weights.complexity, 1.0);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_score_weights_validation() {
+        Jail::expect_with(|jail| {
+            jail.create_file("omen.toml", "[score.weights]\nsatd = -1\n")?;
+            let err = Config::load_default(".").unwrap_err().to_string();
+            assert!(
+                err.contains("score.weights.satd must be a number >= 0"),
+                "got: {err}"
+            );
+            Ok(())
+        });
+
+        let zero = ScoreWeights {
+            complexity: 0.0,
+            satd: 0.0,
+            deadcode: 0.0,
+            churn: 0.0,
+            duplicates: 0.0,
+            defect: 0.0,
+            ownership: 0.0,
+            cohesion: 0.0,
+            tdg: 0.0,
+            coupling: 0.0,
+            smells: 0.0,
+        };
+        assert!(zero.validate().is_err());
+        assert!(ScoreWeights::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_unknown_profile_errors() {
+        Jail::expect_with(|jail| {
+            jail.create_file("omen.toml", "[profile.ci]\nskip = []")?;
+            let err = Config::load_default_with_profile(".", Some("local"))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("profile not found: local"), "got: {err}");
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_config_default_toml() {
         let content = Config::default_toml();
@@ -510,6 +1463,36 @@ mod tests {
         assert!(config.categories.contains(&"design".to_string()));
         assert!(config.categories.contains(&"defect".to_string()));
         assert!(config.custom_markers.is_empty());
+        assert!(config.markers.is_empty());
+        assert!(config.compare_ref.is_none());
+    }
+
+    #[test]
+    fn test_satd_markers() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "omen.toml",
+                "[satd.markers]
+SECURITY = \"critical\"
+TICKET = { pattern = \"TICKET-\\\\d+\", category = \"requirement\" }
+",
+            )?;
+            let config = Config::load_default(".").unwrap();
+            let markers = &config.satd.markers;
+            assert_eq!(
+                markers["SECURITY"],
+                SatdMarker::Severity(Severity::Critical)
+            );
+            assert_eq!(
+                markers["TICKET"],
+                SatdMarker::Rule(SatdMarkerRule {
+                    category: Some("requirement".to_string()),
+                    severity: None,
+                    pattern: Some("TICKET-\\d+".to_string()),
+                })
+            );
+            Ok(())
+        });
     }
 
     #[test]
@@ -523,7 +1506,27 @@ mod tests {
     fn test_duplicates_config_default() {
         let config = DuplicatesConfig::default();
         assert_eq!(config.min_tokens, 50);
-        assert_eq!(config.min_similarity, 0.9);
+        assert_eq!(config.min_similarity, 0.70);
+        assert_eq!(config.min_lines, 6);
+        assert!(config.intra_file);
+        assert!(config.languages.is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_language_overrides() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "omen.toml",
+                "[duplicates]\nmin_lines = 8\n\n[duplicates.languages.python]\nmin_tokens = 30\nmin_similarity = 0.8\n",
+            )?;
+            let config = Config::load_default(".").unwrap();
+            assert_eq!(config.duplicates.min_lines, 8);
+            let python = &config.duplicates.languages["python"];
+            assert_eq!(python.min_tokens, Some(30));
+            assert_eq!(python.min_similarity, Some(0.8));
+            assert_eq!(python.min_lines, None);
+            Ok(())
+        });
     }
 
     #[test]
@@ -536,6 +1539,17 @@ mod tests {
     fn test_score_config_default() {
         let config = ScoreConfig::default();
         assert!(config.fail_under.is_none());
+        assert!(config.monorepo.is_none());
+    }
+
+    #[test]
+    fn test_score_monorepo_parses() {
+        Jail::expect_with(|jail| {
+            jail.create_file("omen.toml", "[score]\nmonorepo = \"files\"")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.score.monorepo, Some(PackageWeight::Files));
+            Ok(())
+        });
     }
 
     #[test]
@@ -572,11 +1586,265 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_graph_config_default() {
+        let config = GraphConfig::default();
+        assert!(!config.include_external);
+        assert!(!config.internal_only);
+        assert!(!config.collapse_barrels);
+        assert!(!config.packages);
+        assert_eq!(config.format, None);
+    }
+
+    #[test]
+    fn test_graph_config_from_file() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "omen.toml",
+                "[graph]\ninternal_only = true\ncollapse_barrels = true\npackages = true\n\
+                 format = \"graphml\"",
+            )?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert!(config.graph.internal_only);
+            assert!(config.graph.collapse_barrels);
+            assert!(config.graph.packages);
+            assert!(!config.graph.include_external);
+            assert_eq!(config.graph.format, Some(GraphFormat::Graphml));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_git_config_from_file() {
+        Jail::expect_with(|jail| {
+            assert!(Config::default().git.at_ref.is_none());
+            assert!(!Config::default().git.blame);
+            jail.create_file("omen.toml", "[git]\nat_ref = \"v1.2.0\"\nblame = true")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.git.at_ref.as_deref(), Some("v1.2.0"));
+            assert!(config.git.blame);
+            assert!(config.git.blame_cache);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_commits_config_from_file() {
+        Jail::expect_with(|jail| {
+            let defaults = CommitsConfig::default();
+            assert_eq!(defaults.max_subject_length, 72);
+            assert!(defaults.types.iter().any(|t| t == "feat"));
+            jail.create_file(
+                "omen.toml",
+                "[commits]\ndays = 90\ntypes = [\"feat\", \"fix\"]\nmin_compliance = 0.5",
+            )?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.commits.days, 90);
+            assert_eq!(config.commits.types, vec!["feat", "fix"]);
+            assert!((config.commits.min_compliance - 0.5).abs() < f64::EPSILON);
+            assert!(config.commits.fail_on_fixups);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_changes_config_from_file() {
+        Jail::expect_with(|jail| {
+            let defaults = ChangesConfig::default();
+            assert_eq!(defaults.max_hotspots, 2);
+            assert_eq!(defaults.test_deletion_lines, 20);
+            jail.create_file("omen.toml", "[changes]\nmax_hotspots = 5")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.changes.days, 30);
+            assert_eq!(config.changes.max_hotspots, 5);
+            assert_eq!(config.changes.test_deletion_lines, 20);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_criticality_from_file() {
+        Jail::expect_with(|jail| {
+            assert!(Config::default().criticality.is_empty());
+            jail.create_file(
+                "omen.toml",
+                "[criticality]\n\"payments/**\" = \"critical\"\n\"docs/**\" = \"low\"",
+            )?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(
+                config.criticality.get("payments/**"),
+                Some(&Criticality::Critical)
+            );
+            assert_eq!(config.criticality.get("docs/**"), Some(&Criticality::Low));
+            assert!(Criticality::Critical > Criticality::High);
+            assert!(Criticality::Normal > Criticality::Low);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_prioritize_by_from_file() {
+        Jail::expect_with(|jail| {
+            assert_eq!(Config::default().prioritize_by, PrioritizeBy::None);
+            jail.create_file("omen.toml", "prioritize_by = \"hotspot\"")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.prioritize_by, PrioritizeBy::Hotspot);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_generated_config_from_file() {
+        Jail::expect_with(|jail| {
+            let defaults = GeneratedConfig::default();
+            assert_eq!(defaults.complexity, GeneratedHandling::Downweight);
+            assert!((defaults.weight - 0.25).abs() < f64::EPSILON);
+            jail.create_file(
+                "omen.toml",
+                "[generated]\ncohesion = \"exclude\"\nmarkers = [\"@autogen\"]",
+            )?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.generated.complexity, GeneratedHandling::Downweight);
+            assert_eq!(config.generated.cohesion, GeneratedHandling::Exclude);
+            assert_eq!(config.generated.markers, vec!["@autogen"]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_search_rerank_config_from_file() {
+        Jail::expect_with(|jail| {
+            let defaults = RerankConfig::default();
+            assert!(!defaults.enabled);
+            assert_eq!(defaults.candidates, 50);
+            jail.create_file(
+                "omen.toml",
+                "[search.rerank]\nenabled = true\npagerank = 0.5\nhotspot = 0.0",
+            )?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert!(config.search.rerank.enabled);
+            assert_eq!(config.search.rerank.pagerank, 0.5);
+            assert_eq!(config.search.rerank.hotspot, 0.0);
+            assert_eq!(config.search.rerank.semantic, defaults.semantic);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_context_tokenizer_from_file() {
+        Jail::expect_with(|jail| {
+            assert_eq!(Config::default().context.tokenizer, Tokenizer::Chars);
+            jail.create_file("omen.toml", "[context]\ntokenizer = \"words\"")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.context.tokenizer, Tokenizer::Words);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_smells_config_from_file() {
+        Jail::expect_with(|jail| {
+            assert_eq!(Config::default().smells.max_injected_dependencies, 7);
+            jail.create_file("omen.toml", "[smells]\nmax_injected_dependencies = 4")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.smells.max_injected_dependencies, 4);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_docker_config_from_file() {
+        Jail::expect_with(|jail| {
+            assert_eq!(Config::default().docker.max_layers, 15);
+            jail.create_file("omen.toml", "[docker]\nmax_layers = 8")?;
+            let config = Config::from_file("omen.toml").unwrap();
+            assert_eq!(config.docker.max_layers, 8);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_set_value_keeps_the_rest_of_the_file() {
+        Jail::expect_with(|jail| {
+            assert_eq!(Config::default().mutation.operators, ["CRR", "ROR", "AOR"]);
+            jail.create_file(
+                "omen.toml",
+                "# project config\n[mutation]\noperators = [\n  \"CRR\",\n  \"AOR\",\n]\n\n\
+                 [churn]\ntop = 5\n",
+            )?;
+            let path = Path::new("omen.toml");
+            set_value(path, "mutation", "operators", "[\"ROR\"]").unwrap();
+            set_value(path, "api", "base", "\"v1.0.0\"").unwrap();
+            set_value(path, "churn", "since", "\"1y\"").unwrap();
+
+            let content = std::fs::read_to_string(path).unwrap();
+            assert!(content.starts_with("# project config\n[mutation]\noperators = [\"ROR\"]\n\n"));
+            let config = Config::from_file(path).unwrap();
+            assert_eq!(config.mutation.operators, ["ROR"]);
+            assert_eq!(config.api.base.as_deref(), Some("v1.0.0"));
+            assert_eq!(config.churn.top, 5);
+            assert_eq!(config.churn.since, "1y");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_set_value_finds_headers_with_comments_and_spaces() {
+        let content =
+            "[ mutation ] # tuned\noperators = [\n  \"CRR\", # \"]\"\n] # kept\n[churn]\ntop = 5\n";
+        let updated = with_value(content, "mutation", "operators", "[\"ROR\"]").unwrap();
+        assert!(updated.starts_with("[ mutation ] # tuned\n"), "{updated}");
+        assert!(
+            updated.contains("operators = [\"ROR\"] # kept\n"),
+            "{updated}"
+        );
+        assert!(updated.ends_with("[churn]\ntop = 5\n"), "{updated}");
+    }
+
+    #[test]
+    fn test_set_value_edits_dotted_and_inline_tables_in_place() {
+        Jail::expect_with(|jail| {
+            let path = Path::new("omen.toml");
+            jail.create_file("omen.toml", "mutation.timeout = 30\n\n[churn]\ntop = 5\n")?;
+            set_value(path, "mutation", "operators", "[\"ROR\"]").unwrap();
+            let content = std::fs::read_to_string(path).unwrap();
+            assert!(!content.contains("[mutation]"), "{content}");
+            let config = Config::from_file(path).unwrap();
+            assert_eq!(config.mutation.operators, ["ROR"]);
+            assert_eq!(config.churn.top, 5);
+
+            jail.create_file("omen.toml", "mutation = { operators = [\"CRR\"] }\n")?;
+            set_value(path, "mutation", "operators", "[\"AOR\"]").unwrap();
+            assert_eq!(
+                std::fs::read_to_string(path).unwrap(),
+                "mutation = { operators = [\"AOR\"] }\n"
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_set_value_refuses_edits_it_cannot_make() {
+        Jail::expect_with(|jail| {
+            let content = "[[mutation]]\noperators = [\"CRR\"]\n";
+            jail.create_file("omen.toml", content)?;
+            let path = Path::new("omen.toml");
+            let err = set_value(path, "mutation", "operators", "[\"ROR\"]").unwrap_err();
+            assert!(err.to_string().contains("edit it by hand"), "{err}");
+            assert_eq!(std::fs::read_to_string(path).unwrap(), content);
+            assert!(set_value(path, "churn", "top", "[unclosed").is_err());
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_feature_flags_config_default() {
         let config = FeatureFlagsConfig::default();
         assert_eq!(config.stale_days, 0);
         assert!(config.providers.is_empty());
+        assert!(config.auto_detect);
         assert!(config.custom_providers.is_empty());
+        assert_eq!(config.project, "default");
+        assert_eq!(config.environment, "production");
     }
 }
@@ -1,11 +1,12 @@
-use std::collections::{BTreeMap, BTreeSet};
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::analyzers::repomap::CallGraphIndex;
 use crate::analyzers::{complexity, repomap, satd};
-use crate::config::Config;
-use crate::core::{AnalysisContext, Analyzer, FileSet, Language, Result};
+use crate::config::{Config, Tokenizer};
+use crate::core::{is_test_file, AnalysisContext, Analyzer, Error, FileSet, Language, Result};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Context {
@@ -17,6 +18,12 @@ pub struct Context {
     pub top_symbols: Vec<SymbolSummary>,
     pub risks: Vec<RiskSummary>,
     pub hints: Vec<String>,
+    /// Source of the focused or top-ranked symbols and their callees.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceSlice>,
+    /// Tokens in the serialized context, counted by the configured tokenizer.
+    #[serde(default)]
+    pub tokens: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +52,8 @@ pub struct SymbolSummary {
     pub file: String,
     pub line: u32,
     pub score: f64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub signature: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +65,61 @@ pub struct RiskSummary {
     pub severity: String,
 }
 
+/// Source of one symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceSlice {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Calls away from the focus: 0 for the focused symbols, 1 for their
+    /// direct callees, and so on.
+    pub depth: usize,
+    pub source: String,
+    /// Cut short to fit the token budget.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Which symbols a context includes source for. With neither `symbol` nor
+/// `target`, symbols are taken by PageRank.
+#[derive(Debug, Clone, Default)]
+pub struct Focus {
+    /// Symbol name, or `file:name`.
+    pub symbol: Option<String>,
+    /// File or directory whose symbols to include, or to pick `symbol` from.
+    pub target: Option<PathBuf>,
+    /// Levels of callees to follow from the focused symbols.
+    pub depth: usize,
+}
+
+/// A file format an AI coding tool reads project context from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Export {
+    /// Aider-style repo map, for `read:` in `.aider.conf.yml`.
+    Aider,
+    /// A section of `CLAUDE.md`, which Claude reads as project memory.
+    ClaudeProject,
+    /// A Cursor project rule that is always applied.
+    CursorRules,
+}
+
+impl Export {
+    /// Where the tool looks for the file, relative to the repository root.
+    pub fn default_path(self) -> &'static str {
+        match self {
+            Self::Aider => ".aider.omen.md",
+            Self::ClaudeProject => "CLAUDE.md",
+            Self::CursorRules => ".cursor/rules/omen.mdc",
+        }
+    }
+}
+
+/// Markers around the section omen owns in a file it shares with people.
+const MANAGED_BEGIN: &str = "<!-- omen:begin -->";
+const MANAGED_END: &str = "<!-- omen:end -->";
+
 /// Well-known entry-point filenames with human-readable reasons.
 const ENTRY_POINT_TABLE: &[(&str, &str)] = &[
     ("main.rs", "well-known entry filename"),
@@ -110,7 +174,7 @@ fn build_tree(root: &Path, files: &FileSet) -> Vec<DirSummary> {
         let entry = dir_map.entry(dir_key).or_insert((0, BTreeSet::new()));
         entry.0 += 1;
 
-        if let Some(lang) = Language::detect(file_path) {
+        if let Some(lang) = files.language(file_path) {
             entry.1.insert(lang.display_name().to_string());
         }
     }
@@ -185,6 +249,7 @@ pub fn build_context(
             file: symbol.file.clone(),
             line: symbol.line,
             score: symbol.pagerank,
+            signature: symbol.signature.clone(),
         })
         .collect();
 
@@ -200,66 +265,252 @@ pub fn build_context(
         top_symbols,
         risks,
         hints,
+        sources: Vec::new(),
+        tokens: 0,
     })
 }
 
+/// Tokens in `text` by `tokenizer`.
+pub fn count_tokens(text: &str, tokenizer: Tokenizer) -> usize {
+    match tokenizer {
+        Tokenizer::Chars => text.chars().count().div_ceil(4),
+        Tokenizer::Bytes => text.len(),
+        Tokenizer::Words => {
+            let mut tokens = 0;
+            let mut word = 0usize;
+            for c in text.chars() {
+                if c.is_alphanumeric() || c == '_' {
+                    word += 1;
+                    continue;
+                }
+                tokens += word.div_ceil(8);
+                word = 0;
+                if !c.is_whitespace() {
+                    tokens += 1;
+                }
+            }
+            tokens + word.div_ceil(8)
+        }
+    }
+}
+
+fn json_tokens<T: Serialize>(value: &T, tokenizer: Tokenizer) -> usize {
+    serde_json::to_string(value)
+        .map(|json| count_tokens(&json, tokenizer))
+        .unwrap_or(usize::MAX)
+}
+
+/// Fit `context` into `max_tokens`, filling the room left with source.
+///
+/// The summary sections are trimmed to half the budget first. Source slices
+/// then take what remains in priority order: the focused symbols by
+/// PageRank, then their callees level by level. A slice that doesn't fit is
+/// left out, except the first, which is cut short instead.
+pub fn assemble(
+    context: &mut Context,
+    root: &Path,
+    files: &FileSet,
+    focus: &Focus,
+    max_tokens: usize,
+    tokenizer: Tokenizer,
+) -> Result<()> {
+    apply_token_budget(context, max_tokens / 2, tokenizer);
+    let mut used = json_tokens(context, tokenizer);
+
+    let source_files: Vec<PathBuf> = files
+        .iter()
+        .filter(|path| !is_test_file(path) && Language::detect(path).is_some())
+        .map(|path| files.root().join(path))
+        .collect();
+    let index = repomap::build_index(files.root(), &source_files)?;
+    let target = focus
+        .target
+        .as_ref()
+        .map(|t| relative_target(root, files.root(), t));
+
+    let mut contents: HashMap<String, Option<String>> = HashMap::new();
+    for (idx, depth) in candidate_symbols(
+        &index,
+        focus.symbol.as_deref(),
+        target.as_deref(),
+        focus.depth,
+    )? {
+        if used >= max_tokens {
+            break;
+        }
+        let symbol = &index.symbols[idx];
+        let content = contents
+            .entry(symbol.file.clone())
+            .or_insert_with(|| std::fs::read_to_string(files.root().join(&symbol.file)).ok());
+        let Some(content) = content else {
+            continue;
+        };
+        let lines: Vec<&str> = content
+            .lines()
+            .skip(symbol.line.saturating_sub(1) as usize)
+            .take((symbol.end_line + 1).saturating_sub(symbol.line) as usize)
+            .collect();
+        let mut slice = SourceSlice {
+            name: symbol.name.clone(),
+            kind: format!("{:?}", symbol.kind).to_lowercase(),
+            file: symbol.file.clone(),
+            start_line: symbol.line,
+            end_line: symbol.end_line,
+            depth,
+            source: lines.join("\n"),
+            truncated: false,
+        };
+        let mut tokens = json_tokens(&slice, tokenizer);
+        if used + tokens > max_tokens {
+            if !context.sources.is_empty() {
+                continue;
+            }
+            truncate_slice(&mut slice, &lines, max_tokens - used, tokenizer);
+            tokens = json_tokens(&slice, tokenizer);
+        }
+        used += tokens;
+        context.sources.push(slice);
+    }
+
+    context.tokens = json_tokens(context, tokenizer);
+    Ok(())
+}
+
+/// `target` relative to the file set root, as symbol files are.
+fn relative_target(root: &Path, files_root: &Path, target: &Path) -> PathBuf {
+    root.join(target)
+        .canonicalize()
+        .ok()
+        .and_then(|path| path.strip_prefix(files_root).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| {
+            target
+                .components()
+                .filter(|c| !matches!(c, Component::CurDir))
+                .collect()
+        })
+}
+
+/// Symbols to include source for, with their depth, in priority order.
+fn candidate_symbols(
+    index: &CallGraphIndex,
+    symbol: Option<&str>,
+    target: Option<&Path>,
+    depth: usize,
+) -> Result<Vec<(usize, usize)>> {
+    let rank = repomap::pagerank(&index.graph, 0.85, 100, 1e-6);
+    let score = |idx: &usize| {
+        index
+            .node_indices
+            .get(idx)
+            .and_then(|node| rank.get(node))
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let by_rank = |mut level: Vec<usize>| {
+        level.sort_by(|a, b| score(b).total_cmp(&score(a)).then(a.cmp(b)));
+        level
+    };
+    let in_target =
+        |idx: &usize| target.is_none_or(|t| Path::new(&index.symbols[*idx].file).starts_with(t));
+
+    let roots: Vec<usize> = match symbol {
+        Some(symbol) => index
+            .resolve(symbol)
+            .into_iter()
+            .filter(in_target)
+            .collect(),
+        None if target.is_some() => (0..index.symbols.len()).filter(in_target).collect(),
+        None => {
+            let all = by_rank((0..index.symbols.len()).collect());
+            return Ok(all.into_iter().map(|idx| (idx, 0)).collect());
+        }
+    };
+    if roots.is_empty() {
+        let what = symbol.map_or_else(|| "no symbols".to_string(), |s| format!("no symbol `{s}`"));
+        let place = target.map_or_else(String::new, |t| format!(" in {}", t.display()));
+        return Err(Error::InvalidArgument(format!("{what}{place}")));
+    }
+
+    let mut candidates: Vec<(usize, usize)> = by_rank(roots.clone())
+        .into_iter()
+        .map(|idx| (idx, 0))
+        .collect();
+    for (level, callees) in index.callees(&roots, depth).into_iter().enumerate() {
+        candidates.extend(by_rank(callees).into_iter().map(|idx| (idx, level + 1)));
+    }
+    Ok(candidates)
+}
+
+/// Keep the leading lines of `slice` that fit in `budget` tokens.
+fn truncate_slice(slice: &mut SourceSlice, lines: &[&str], budget: usize, tokenizer: Tokenizer) {
+    slice.source.clear();
+    slice.truncated = true;
+    let mut used = json_tokens(slice, tokenizer);
+    let mut kept = 0;
+    for line in lines {
+        // The escaped line, quotes standing in for the joining newline
+        let tokens = json_tokens(line, tokenizer);
+        if used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        kept += 1;
+    }
+    slice.source = lines[..kept].join("\n");
+}
+
 /// Apply a token budget to a context, trimming in priority order:
 /// tree → risks → top_symbols → entry_points → hints, until the serialized
-/// JSON fits within `max_tokens * 4` bytes, keeping each list at minimum
-/// `MIN_ITEMS` entries.
-pub fn apply_token_budget(ctx: &mut Context, max_tokens: usize) {
-    let byte_budget = max_tokens * 4;
+/// JSON fits within `max_tokens` as counted by `tokenizer`, keeping each
+/// list at minimum `MIN_ITEMS` entries.
+pub fn apply_token_budget(ctx: &mut Context, max_tokens: usize, tokenizer: Tokenizer) {
     const MIN_ITEMS: usize = 5;
 
     // Helper: estimate size
-    let estimate_size = |c: &Context| -> usize {
-        serde_json::to_string(c)
-            .map(|s| s.len())
-            .unwrap_or(usize::MAX)
-    };
+    let estimate_size = |c: &Context| -> usize { json_tokens(c, tokenizer) };
 
-    if estimate_size(ctx) <= byte_budget {
+    if estimate_size(ctx) <= max_tokens {
         return;
     }
 
     // Trim tree
-    while ctx.tree.len() > MIN_ITEMS && estimate_size(ctx) > byte_budget {
+    while ctx.tree.len() > MIN_ITEMS && estimate_size(ctx) > max_tokens {
         ctx.tree.pop();
     }
 
-    if estimate_size(ctx) <= byte_budget {
+    if estimate_size(ctx) <= max_tokens {
         return;
     }
 
     // Trim risks
-    while ctx.risks.len() > MIN_ITEMS && estimate_size(ctx) > byte_budget {
+    while ctx.risks.len() > MIN_ITEMS && estimate_size(ctx) > max_tokens {
         ctx.risks.pop();
     }
 
-    if estimate_size(ctx) <= byte_budget {
+    if estimate_size(ctx) <= max_tokens {
         return;
     }
 
     // Trim top_symbols
-    while ctx.top_symbols.len() > MIN_ITEMS && estimate_size(ctx) > byte_budget {
+    while ctx.top_symbols.len() > MIN_ITEMS && estimate_size(ctx) > max_tokens {
         ctx.top_symbols.pop();
     }
 
-    if estimate_size(ctx) <= byte_budget {
+    if estimate_size(ctx) <= max_tokens {
         return;
     }
 
     // Trim entry_points
-    while ctx.entry_points.len() > MIN_ITEMS && estimate_size(ctx) > byte_budget {
+    while ctx.entry_points.len() > MIN_ITEMS && estimate_size(ctx) > max_tokens {
         ctx.entry_points.pop();
     }
 
-    if estimate_size(ctx) <= byte_budget {
+    if estimate_size(ctx) <= max_tokens {
         return;
     }
 
     // Trim hints (last resort)
-    while ctx.hints.len() > MIN_ITEMS && estimate_size(ctx) > byte_budget {
+    while ctx.hints.len() > MIN_ITEMS && estimate_size(ctx) > max_tokens {
         ctx.hints.pop();
     }
 }
@@ -271,6 +522,9 @@ impl Context {
 
         out.push_str(&format!("# Repository: {}\n\n", self.repository));
         out.push_str(&format!("**Files**: {}\n\n", self.file_count));
+        if self.tokens > 0 {
+            out.push_str(&format!("**Tokens**: {}\n\n", self.tokens));
+        }
 
         // Languages
         let lang_line: Vec<String> = self
@@ -336,14 +590,202 @@ impl Context {
             out.push('\n');
         }
 
+        // Source
+        if !self.sources.is_empty() {
+            out.push_str("## Source\n\n");
+            for slice in &self.sources {
+                let relation = match slice.depth {
+                    0 => String::new(),
+                    1 => " — direct dependency".to_string(),
+                    n => format!(" — dependency, {n} calls away"),
+                };
+                out.push_str(&format!(
+                    "### `{}` ({}) {}:{}-{}{}\n\n",
+                    slice.name, slice.kind, slice.file, slice.start_line, slice.end_line, relation
+                ));
+                let fence = Path::new(&slice.file)
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                out.push_str(&format!("```{fence}\n{}\n```\n\n", slice.source));
+                if slice.truncated {
+                    out.push_str("_Truncated to fit the token budget._\n\n");
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Render the repo map and findings in `export`'s format.
+    pub fn render_export(&self, export: Export) -> String {
+        match export {
+            Export::Aider => format!(
+                "# Repository map: {}\n\n\
+                 Generated by `omen context --export aider`. Symbols are ranked by \
+                 PageRank over the call graph, most central first.\n\n\
+                 ```text\n{}```\n\n{}",
+                self.repository,
+                self.render_repo_map(),
+                self.render_findings("##")
+            ),
+            Export::ClaudeProject => format!(
+                "{MANAGED_BEGIN}\n## Codebase map\n\n\
+                 _Generated by `omen context --export claude-project`; edits inside \
+                 this section are overwritten._\n\n{}{}{MANAGED_END}\n",
+                self.render_overview("###"),
+                self.render_findings("###")
+            ),
+            Export::CursorRules => format!(
+                "---\n\
+                 description: Repository map and known risks for {}, generated by omen\n\
+                 globs:\n\
+                 alwaysApply: true\n\
+                 ---\n\n\
+                 # {}\n\n{}{}",
+                self.repository,
+                self.repository,
+                self.render_overview("##"),
+                self.render_findings("##")
+            ),
+        }
+    }
+
+    /// Top symbols grouped by file in rank order, in Aider's repo map
+    /// layout: the path, then each signature on a `│` line between `⋮...`
+    /// elisions.
+    fn render_repo_map(&self) -> String {
+        let mut files: Vec<(&str, Vec<&SymbolSummary>)> = Vec::new();
+        for symbol in &self.top_symbols {
+            match files.iter_mut().find(|(file, _)| *file == symbol.file) {
+                Some((_, symbols)) => symbols.push(symbol),
+                None => files.push((symbol.file.as_str(), vec![symbol])),
+            }
+        }
+
+        let mut out = String::new();
+        for (file, mut symbols) in files {
+            symbols.sort_by_key(|symbol| symbol.line);
+            out.push_str(&format!("{file}:\n"));
+            for symbol in symbols {
+                let signature = symbol.signature.lines().next().unwrap_or_default();
+                let line = if signature.trim().is_empty() {
+                    symbol.name.as_str()
+                } else {
+                    signature.trim_end()
+                };
+                out.push_str(&format!("⋮...\n│{line}\n"));
+            }
+            out.push_str("⋮...\n\n");
+        }
+        out
+    }
+
+    /// Languages, entry points and top symbols, with `heading` level
+    /// section headings.
+    fn render_overview(&self, heading: &str) -> String {
+        let mut out = String::new();
+        let languages: Vec<String> = self
+            .languages
+            .iter()
+            .map(|l| format!("{} ({})", l.language, l.files))
+            .collect();
+        out.push_str(&format!(
+            "**Files**: {}. **Languages**: {}\n\n",
+            self.file_count,
+            languages.join(", ")
+        ));
+        if !self.entry_points.is_empty() {
+            out.push_str(&format!("{heading} Entry points\n\n"));
+            for ep in &self.entry_points {
+                out.push_str(&format!("- `{}`\n", ep.file));
+            }
+            out.push('\n');
+        }
+        if !self.top_symbols.is_empty() {
+            out.push_str(&format!("{heading} Key symbols\n\n"));
+            out.push_str("Ranked by PageRank over the call graph, most central first.\n\n");
+            for sym in &self.top_symbols {
+                out.push_str(&format!(
+                    "- `{}` ({}) {}:{}\n",
+                    sym.name, sym.kind, sym.file, sym.line
+                ));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Risks and hints, with `heading` level section headings.
+    fn render_findings(&self, heading: &str) -> String {
+        let mut out = String::new();
+        if !self.risks.is_empty() {
+            out.push_str(&format!("{heading} Known risks\n\n"));
+            for risk in &self.risks {
+                out.push_str(&format!(
+                    "- [{}] {} {}:{} — {}\n",
+                    risk.severity, risk.kind, risk.file, risk.line, risk.message
+                ));
+            }
+            out.push('\n');
+        }
+        if !self.hints.is_empty() {
+            out.push_str(&format!("{heading} Hints\n\n"));
+            for hint in &self.hints {
+                out.push_str(&format!("- {}\n", hint));
+            }
+            out.push('\n');
+        }
         out
     }
 }
 
-fn summarize_languages(files: &FileSet) -> Vec<LanguageSummary> {
+/// Write `ctx` to `path` in `export`'s format, creating parent directories.
+///
+/// `CLAUDE.md` is usually written by hand, so only the section between
+/// omen's markers is replaced (or appended); the other formats are files
+/// omen owns and are overwritten.
+pub fn write_export(ctx: &Context, export: Export, path: &Path) -> Result<()> {
+    let rendered = ctx.render_export(export);
+    let content = match export {
+        Export::ClaudeProject => match std::fs::read_to_string(path) {
+            Ok(existing) => replace_managed_section(&existing, &rendered),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => rendered,
+            Err(e) => return Err(e.into()),
+        },
+        Export::Aider | Export::CursorRules => rendered,
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// `existing` with its omen section replaced by `section`, or with
+/// `section` appended when it has none.
+fn replace_managed_section(existing: &str, section: &str) -> String {
+    let bounds = existing.find(MANAGED_BEGIN).and_then(|start| {
+        existing[start..]
+            .find(MANAGED_END)
+            .map(|end| (start, start + end + MANAGED_END.len()))
+    });
+    match bounds {
+        Some((start, end)) => {
+            let rest = existing[end..]
+                .strip_prefix('\n')
+                .unwrap_or(&existing[end..]);
+            format!("{}{section}{rest}", &existing[..start])
+        }
+        None if existing.trim().is_empty() => section.to_string(),
+        None => format!("{}\n\n{section}", existing.trim_end()),
+    }
+}
+
+pub(crate) fn summarize_languages(files: &FileSet) -> Vec<LanguageSummary> {
     let mut counts = std::collections::BTreeMap::<String, usize>::new();
     for file in files.files() {
-        if let Some(language) = Language::detect(file) {
+        if let Some(language) = files.language(file) {
             *counts
                 .entry(language.display_name().to_string())
                 .or_default() += 1;
@@ -568,6 +1010,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_render_export_formats() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::write(
+            temp.path().join("src/main.rs"),
+            "pub fn my_symbol(x: i32) -> i32 { x }\n// TODO: fix this\n",
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let files = FileSet::from_path(temp.path(), &config).unwrap();
+        let context = build_context(temp.path(), &files, &config, None, None).unwrap();
+
+        let aider = context.render_export(Export::Aider);
+        assert!(aider.contains("src/main.rs:\n⋮...\n│pub fn my_symbol"));
+        assert!(aider.contains("## Known risks"));
+
+        let claude = context.render_export(Export::ClaudeProject);
+        assert!(claude.starts_with(MANAGED_BEGIN));
+        assert!(claude.trim_end().ends_with(MANAGED_END));
+        assert!(claude.contains("### Key symbols"));
+        assert!(claude.contains("`my_symbol`"));
+
+        let cursor = context.render_export(Export::CursorRules);
+        assert!(cursor.starts_with("---\ndescription: "));
+        assert!(cursor.contains("alwaysApply: true\n---\n"));
+        assert!(cursor.contains("## Key symbols"));
+    }
+
+    #[test]
+    fn test_write_export_keeps_hand_written_claude_md() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("lib.rs"), "pub fn f() {}\n").unwrap();
+        let config = Config::default();
+        let files = FileSet::from_path(temp.path(), &config).unwrap();
+        let context = build_context(temp.path(), &files, &config, None, None).unwrap();
+
+        let path = temp.path().join(Export::ClaudeProject.default_path());
+        std::fs::write(&path, "# Notes\n\nRun `make test`.\n").unwrap();
+        write_export(&context, Export::ClaudeProject, &path).unwrap();
+        write_export(&context, Export::ClaudeProject, &path).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# Notes\n\nRun `make test`.\n\n<!-- omen:begin -->"));
+        assert_eq!(written.matches(MANAGED_BEGIN).count(), 1);
+
+        let rules = temp.path().join(Export::CursorRules.default_path());
+        write_export(&context, Export::CursorRules, &rules).unwrap();
+        assert!(std::fs::read_to_string(rules).unwrap().starts_with("---\n"));
+    }
+
+    #[test]
+    fn test_replace_managed_section() {
+        let existing = "intro\n<!-- omen:begin -->\nold\n<!-- omen:end -->\noutro\n";
+        let section = "<!-- omen:begin -->\nnew\n<!-- omen:end -->\n";
+        assert_eq!(
+            replace_managed_section(existing, section),
+            "intro\n<!-- omen:begin -->\nnew\n<!-- omen:end -->\noutro\n"
+        );
+        assert_eq!(replace_managed_section("", section), section);
+        assert_eq!(
+            replace_managed_section("intro\n", section),
+            format!("intro\n\n{section}")
+        );
+    }
+
     #[test]
     fn test_apply_token_budget_trims_in_order() {
         let temp = tempfile::tempdir().unwrap();
@@ -598,6 +1106,7 @@ mod tests {
                 file: format!("src/file{i}.rs"),
                 line: i as u32,
                 score: 0.1,
+                signature: String::new(),
             });
         }
 
@@ -617,7 +1126,7 @@ mod tests {
         // Use a large-enough budget so trimming works
         // Original is ~21k bytes; use 4000 tokens = 16000 bytes to force
         // some trimming of tree/risks while keeping minimums.
-        apply_token_budget(&mut context, 4000);
+        apply_token_budget(&mut context, 4000, Tokenizer::Chars);
 
         let after_size = serde_json::to_string(&context)
             .map(|s| s.len())
@@ -705,7 +1214,7 @@ mod tests {
         // Tight budget: forces entry_points and hints to be trimmed.
         // Use 500 tokens; even if the budget floor (MIN_ITEMS entries) cannot
         // fit within 2000 bytes, the lists must be trimmed toward the minimum.
-        apply_token_budget(&mut context, 500);
+        apply_token_budget(&mut context, 500, Tokenizer::Chars);
 
         let after_size = serde_json::to_string(&context)
             .map(|s| s.len())
@@ -747,10 +1256,116 @@ mod tests {
         let symbols_before = context.top_symbols.len();
 
         // Very large budget: nothing should be trimmed
-        apply_token_budget(&mut context, 1_000_000);
+        apply_token_budget(&mut context, 1_000_000, Tokenizer::Chars);
 
         assert_eq!(context.tree.len(), tree_before);
         assert_eq!(context.risks.len(), risks_before);
         assert_eq!(context.top_symbols.len(), symbols_before);
     }
+
+    fn assembled(source: &str, focus: &Focus, max_tokens: usize) -> Result<Context> {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::write(temp.path().join("src/lib.rs"), source).unwrap();
+
+        let config = Config::default();
+        let files = FileSet::from_path(temp.path(), &config).unwrap();
+        let mut context = build_context(temp.path(), &files, &config, None, None)?;
+        assemble(
+            &mut context,
+            temp.path(),
+            &files,
+            focus,
+            max_tokens,
+            Tokenizer::Chars,
+        )?;
+        Ok(context)
+    }
+
+    const CALLS: &str = "fn main() { parse(); }\n\
+                         fn parse() {\n    lex();\n}\n\
+                         fn lex() { read(); }\n\
+                         fn read() {}\n\
+                         fn unrelated() {}\n";
+
+    #[test]
+    fn test_count_tokens() {
+        assert_eq!(count_tokens("fn main() {}", Tokenizer::Chars), 3);
+        assert_eq!(count_tokens("fn main() {}", Tokenizer::Bytes), 12);
+        // fn, main, (, ), {, }
+        assert_eq!(count_tokens("fn main() {}", Tokenizer::Words), 6);
+        // A 20-character identifier counts as three
+        assert_eq!(count_tokens("abcdefghijklmnopqrst", Tokenizer::Words), 3);
+        assert_eq!(count_tokens("", Tokenizer::Chars), 0);
+    }
+
+    #[test]
+    fn test_assemble_slices_symbol_and_callees() {
+        let focus = Focus {
+            symbol: Some("parse".to_string()),
+            target: None,
+            depth: 1,
+        };
+        let context = assembled(CALLS, &focus, 8000).unwrap();
+
+        let slices: Vec<(&str, usize)> = context
+            .sources
+            .iter()
+            .map(|s| (s.name.as_str(), s.depth))
+            .collect();
+        assert_eq!(slices, [("parse", 0), ("lex", 1)]);
+        assert_eq!(context.sources[0].source, "fn parse() {\n    lex();\n}");
+        assert_eq!(
+            (context.sources[0].start_line, context.sources[0].end_line),
+            (2, 4)
+        );
+        assert!(context.tokens > 0 && context.tokens <= 8000);
+
+        let md = context.render_markdown();
+        assert!(md.contains("## Source"));
+        assert!(md.contains("### `lex` (function) src/lib.rs:5-5 — direct dependency"));
+        assert!(md.contains("```rs\nfn parse() {"));
+
+        let deeper = Focus { depth: 2, ..focus };
+        let context = assembled(CALLS, &deeper, 8000).unwrap();
+        assert_eq!(context.sources.len(), 3);
+        assert_eq!(context.sources[2].name, "read");
+        assert_eq!(context.sources[2].depth, 2);
+    }
+
+    #[test]
+    fn test_assemble_truncates_first_slice_to_budget() {
+        let body: String = (0..200).map(|i| format!("    step_{i}();\n")).collect();
+        let source = format!("fn long() {{\n{body}}}\nfn other() {{}}\n");
+        let focus = Focus {
+            symbol: Some("long".to_string()),
+            ..Focus::default()
+        };
+
+        let context = assembled(&source, &focus, 1000).unwrap();
+
+        assert_eq!(context.sources.len(), 1);
+        let slice = &context.sources[0];
+        assert!(slice.truncated);
+        assert!(slice.source.starts_with("fn long() {\n    step_0();"));
+        assert!(!slice.source.contains("step_199"));
+        assert!(context.tokens <= 1000, "{} tokens", context.tokens);
+    }
+
+    #[test]
+    fn test_assemble_target_and_unknown_symbol() {
+        let focus = Focus {
+            target: Some(PathBuf::from("./src/lib.rs")),
+            ..Focus::default()
+        };
+        let context = assembled(CALLS, &focus, 8000).unwrap();
+        assert_eq!(context.sources.len(), 5);
+
+        let focus = Focus {
+            symbol: Some("missing".to_string()),
+            ..Focus::default()
+        };
+        let err = assembled(CALLS, &focus, 8000).unwrap_err();
+        assert!(err.to_string().contains("no symbol `missing`"));
+    }
 }
@@ -6,9 +6,10 @@ use std::time::Duration;
 
 use serde::Serialize;
 
-use super::{ContentSource, FileSet, Result};
+use super::{ContentSource, FileContent, FileSet, Result, SourceFile, WarmStart};
 use crate::config::Config;
-use crate::git::GitRepo;
+use crate::git::{BlameCache, ChangedLines, Commit, CommitCache, GitRepo, PathFilter};
+use crate::parser::{ParseCache, ParseResult, Parser};
 
 /// Trait implemented by all analyzers.
 pub trait Analyzer: Send + Sync {
@@ -49,6 +50,19 @@ pub struct AnalysisContext<'a> {
     pub on_progress: Option<Box<dyn Fn(usize, usize) + Send + Sync + 'a>>,
     /// Optional content source for reading files (e.g., from git tree).
     pub content_source: Option<Arc<dyn ContentSource>>,
+    /// Shared blame lookups, set when findings should carry their origin commit.
+    pub blame: Option<Arc<BlameCache>>,
+    /// Lines changed since `--base`, set when findings should say whether
+    /// they are on them.
+    pub changed_lines: Option<Arc<ChangedLines>>,
+    /// Files parsed up front, set when several analyzers share one run.
+    pub parse_cache: Option<Arc<ParseCache>>,
+    /// A previous run's results, set when analyzers may reuse them for
+    /// unchanged files.
+    pub warm_start: Option<Arc<WarmStart>>,
+    /// Commit history read once, set when several history-based analyzers
+    /// share one run.
+    pub commits: Option<Arc<CommitCache>>,
 }
 
 impl<'a> AnalysisContext<'a> {
@@ -61,6 +75,11 @@ impl<'a> AnalysisContext<'a> {
             config,
             on_progress: None,
             content_source: None,
+            blame: None,
+            changed_lines: None,
+            parse_cache: None,
+            warm_start: None,
+            commits: None,
         }
     }
 
@@ -70,6 +89,37 @@ impl<'a> AnalysisContext<'a> {
         self
     }
 
+    /// Share a blame cache so analyzers can annotate findings with their origin.
+    pub fn with_blame(mut self, blame: Arc<BlameCache>) -> Self {
+        self.blame = Some(blame);
+        self
+    }
+
+    /// Share the lines changed since a base revision so analyzers can mark
+    /// findings on them.
+    pub fn with_changed_lines(mut self, changed_lines: Arc<ChangedLines>) -> Self {
+        self.changed_lines = Some(changed_lines);
+        self
+    }
+
+    /// Share files parsed up front so analyzers don't parse them again.
+    pub fn with_parse_cache(mut self, cache: Arc<ParseCache>) -> Self {
+        self.parse_cache = Some(cache);
+        self
+    }
+
+    /// Share a previous run's results so analyzers can skip unchanged files.
+    pub fn with_warm_start(mut self, warm_start: Arc<WarmStart>) -> Self {
+        self.warm_start = Some(warm_start);
+        self
+    }
+
+    /// Share one read of the commit history between analyzers.
+    pub fn with_commit_cache(mut self, commits: Arc<CommitCache>) -> Self {
+        self.commits = Some(commits);
+        self
+    }
+
     /// Add a content source for reading files.
     pub fn with_content_source(mut self, source: Arc<dyn ContentSource>) -> Self {
         self.content_source = Some(source);
@@ -77,14 +127,57 @@ impl<'a> AnalysisContext<'a> {
     }
 
     /// Open a thread-local git repository (for parallel operations).
+    ///
+    /// The repository reads history at `config.git.at_ref` when set, and
+    /// reports authors under their canonical identities.
     pub fn open_git(&self) -> Result<Option<GitRepo>> {
         if let Some(path) = self.git_path {
-            Ok(Some(GitRepo::open(path)?))
+            Ok(Some(
+                GitRepo::open(path)?
+                    .at_ref(self.config.git.at_ref.as_deref())
+                    .with_identities(&self.config.git),
+            ))
         } else {
             Ok(None)
         }
     }
 
+    /// Commits since `since` with file stats, restricted to `filter`, from
+    /// the shared commit cache when there is one and from `repo` otherwise.
+    pub fn log_with_stats(
+        &self,
+        repo: &GitRepo,
+        since: Option<&str>,
+        filter: &PathFilter,
+    ) -> Result<Vec<Commit>> {
+        if let Some(commits) = self
+            .commits
+            .as_ref()
+            .and_then(|cache| cache.log_with_stats(since, filter))
+        {
+            return commits;
+        }
+        repo.log_with_stats_filtered(since, None, filter)
+    }
+
+    /// A context over `files` sharing this context's root, config, git
+    /// access and content source, without the progress callback.
+    pub fn for_files<'b>(&'b self, files: &'b FileSet) -> AnalysisContext<'b> {
+        AnalysisContext {
+            root: self.root,
+            files,
+            git_path: self.git_path,
+            config: self.config,
+            on_progress: None,
+            content_source: self.content_source.clone(),
+            blame: self.blame.clone(),
+            changed_lines: self.changed_lines.clone(),
+            parse_cache: self.parse_cache.clone(),
+            warm_start: self.warm_start.clone(),
+            commits: self.commits.clone(),
+        }
+    }
+
     /// Read file contents using the content source if available, otherwise from filesystem.
     pub fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
         if let Some(ref source) = self.content_source {
@@ -95,6 +188,45 @@ impl<'a> AnalysisContext<'a> {
         }
     }
 
+    /// Load a file from the set as a [`SourceFile`] via [`Self::read_file`].
+    ///
+    /// The returned path is joined onto `root` so findings report the same
+    /// paths whether contents came from disk or a content source.
+    pub fn load_source(&self, path: &Path) -> Result<SourceFile> {
+        let language =
+            self.files
+                .language(path)
+                .ok_or_else(|| super::Error::UnsupportedLanguage {
+                    path: path.to_path_buf(),
+                })?;
+        // Without a content source, read through `FileContent` so large
+        // files can be mapped instead of copied.
+        let content = match self.content_source {
+            Some(ref source) => source.read(path)?.into(),
+            None => FileContent::read(&self.root.join(path))?,
+        };
+        Ok(SourceFile::from_content(
+            self.root.join(path),
+            language,
+            content,
+        ))
+    }
+
+    /// Parse a file from the set, taking the tree from the parse cache when
+    /// one is attached. Like [`Self::load_source`], the result's path is
+    /// joined onto `root`.
+    pub fn parsed(&self, path: &Path) -> Result<Arc<ParseResult>> {
+        if let Some(parsed) = self
+            .parse_cache
+            .as_ref()
+            .and_then(|cache| cache.get(path, self.root))
+        {
+            return Ok(parsed);
+        }
+        let source = self.load_source(path)?;
+        Ok(Arc::new(Parser::new().parse_source(&source)?))
+    }
+
     /// Add progress callback.
     pub fn with_progress<F>(mut self, f: F) -> Self
     where
@@ -253,6 +385,20 @@ mod tests {
         ctx.report_progress(5, 10);
     }
 
+    #[test]
+    fn test_analysis_context_load_source_from_memory() {
+        let source = crate::core::MemorySource::new().with_file("lib.go", "package lib");
+        let config = Config::default();
+        let files = FileSet::from_memory_source(&source, &config).unwrap();
+        let ctx = AnalysisContext::new(&files, &config, None).with_content_source(Arc::new(source));
+
+        let file = ctx.load_source(Path::new("lib.go")).unwrap();
+        assert_eq!(file.path, Path::new(".").join("lib.go"));
+        assert_eq!(file.language, super::super::Language::Go);
+        assert_eq!(&file.content[..], b"package lib");
+        assert!(ctx.load_source(Path::new("missing.go")).is_err());
+    }
+
     #[test]
     fn test_analysis_context_with_progress() {
         use std::sync::atomic::{AtomicUsize, Ordering};
@@ -0,0 +1,202 @@
+//! Baselines of known violations for check modes.
+//!
+//! A baseline records the violations a check found at one point in time so
+//! CI can fail only on new ones. Entries are matched by file and name, not
+//! line, so edits elsewhere in a file do not invalidate them. Each entry
+//! also keeps the measured value (higher is worse): a baselined violation
+//! that gets worse counts as new again.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Error, Result};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// Violations recorded for one check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    /// Check the violations came from, e.g. `complexity` or `score`.
+    pub check: String,
+    pub violations: Vec<BaselineEntry>,
+}
+
+/// One recorded violation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// File relative to the repository root; empty for repository-wide checks.
+    pub file: String,
+    /// What violated the check within the file, e.g. a function name.
+    pub name: String,
+    /// Measured value; higher is worse.
+    pub value: f64,
+}
+
+/// Current violations split against a baseline.
+#[derive(Debug, Clone, Default)]
+pub struct BaselineDiff {
+    /// Not in the baseline, or worse than recorded. These fail the check.
+    pub new: Vec<BaselineEntry>,
+    /// In the baseline and no worse than recorded.
+    pub existing: Vec<BaselineEntry>,
+    /// In the baseline but no longer violating.
+    pub fixed: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn new(check: &str, mut violations: Vec<BaselineEntry>) -> Self {
+        // Stable order keeps committed baselines diff-friendly.
+        violations.sort_by(|a, b| {
+            (&a.file, &a.name)
+                .cmp(&(&b.file, &b.name))
+                .then(a.value.total_cmp(&b.value))
+        });
+        Self {
+            version: BASELINE_VERSION,
+            check: check.to_string(),
+            violations,
+        }
+    }
+
+    /// Read a baseline written for `check`.
+    pub fn load(path: &Path, check: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let baseline: Self = serde_json::from_str(&content).map_err(|e| {
+            Error::InvalidArgument(format!("invalid baseline {}: {e}", path.display()))
+        })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(Error::InvalidArgument(format!(
+                "baseline {} has version {}, expected {BASELINE_VERSION}",
+                path.display(),
+                baseline.version
+            )));
+        }
+        if baseline.check != check {
+            return Err(Error::InvalidArgument(format!(
+                "baseline {} is for `{}`, not `{check}`",
+                path.display(),
+                baseline.check
+            )));
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Split `current` into new, existing and fixed violations.
+    ///
+    /// Entries with the same file and name (overloads, same-named methods)
+    /// are paired worst-first so one worsened entry does not hide behind
+    /// an untouched one.
+    pub fn diff(&self, current: Vec<BaselineEntry>) -> BaselineDiff {
+        let mut recorded: HashMap<(&str, &str), Vec<&BaselineEntry>> = HashMap::new();
+        for entry in &self.violations {
+            recorded
+                .entry((entry.file.as_str(), entry.name.as_str()))
+                .or_default()
+                .push(entry);
+        }
+        for entries in recorded.values_mut() {
+            entries.sort_by(|a, b| a.value.total_cmp(&b.value));
+        }
+
+        let mut current = current;
+        current.sort_by(|a, b| b.value.total_cmp(&a.value));
+        let mut diff = BaselineDiff::default();
+        for entry in current {
+            let matched = recorded
+                .get_mut(&(entry.file.as_str(), entry.name.as_str()))
+                .filter(|entries| entries.last().is_some_and(|e| entry.value <= e.value))
+                .and_then(|entries| entries.pop());
+            match matched {
+                Some(_) => diff.existing.push(entry),
+                None => diff.new.push(entry),
+            }
+        }
+        diff.fixed = recorded.into_values().flatten().cloned().collect();
+        diff.fixed
+            .sort_by(|a, b| (&a.file, &a.name).cmp(&(&b.file, &b.name)));
+        diff
+    }
+}
+
+impl BaselineEntry {
+    /// Entry for `file`, stored relative to `root` so baselines are portable.
+    pub fn new(root: &Path, file: &str, name: &str, value: f64) -> Self {
+        let file = Path::new(file)
+            .strip_prefix(root)
+            .map_or_else(|_| file.to_string(), |p| p.to_string_lossy().into_owned());
+        Self {
+            file,
+            name: name.to_string(),
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(file: &str, name: &str, value: f64) -> BaselineEntry {
+        BaselineEntry::new(Path::new("/repo"), file, name, value)
+    }
+
+    #[test]
+    fn test_diff_splits_new_existing_fixed() {
+        let baseline = Baseline::new(
+            "complexity",
+            vec![
+                entry("/repo/src/a.rs", "parse", 30.0),
+                entry("/repo/src/a.rs", "render", 25.0),
+                entry("/repo/src/b.rs", "gone", 40.0),
+            ],
+        );
+        let diff = baseline.diff(vec![
+            entry("/repo/src/a.rs", "parse", 28.0),
+            entry("/repo/src/a.rs", "render", 31.0),
+            entry("/repo/src/c.rs", "fresh", 22.0),
+        ]);
+
+        let names =
+            |entries: &[BaselineEntry]| entries.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.existing), vec!["parse"]);
+        // Worse than recorded counts as new.
+        assert_eq!(names(&diff.new), vec!["render", "fresh"]);
+        assert_eq!(names(&diff.fixed), vec!["gone"]);
+        assert_eq!(diff.existing[0].file, "src/a.rs");
+    }
+
+    #[test]
+    fn test_diff_pairs_same_named_entries() {
+        let baseline = Baseline::new(
+            "complexity",
+            vec![entry("a.rs", "new", 20.0), entry("a.rs", "new", 30.0)],
+        );
+        let diff = baseline.diff(vec![entry("a.rs", "new", 30.0), entry("a.rs", "new", 25.0)]);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].value, 25.0);
+        assert_eq!(diff.existing.len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(".omen/baseline.json");
+        let baseline = Baseline::new("score", vec![entry("", "overall", 4.5)]);
+        baseline.save(&path).unwrap();
+
+        assert_eq!(Baseline::load(&path, "score").unwrap(), baseline);
+        let err = Baseline::load(&path, "complexity").unwrap_err().to_string();
+        assert!(err.contains("not `complexity`"), "got: {err}");
+    }
+}
@@ -1,8 +1,10 @@
 //! Content source abstraction for reading file contents.
 //!
 //! This module provides a trait for abstracting how file contents are read,
-//! allowing analysis to work with both filesystem files and git tree objects.
+//! allowing analysis to work with filesystem files, git tree objects, and
+//! in-memory contents such as unsaved editor buffers or generated code.
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use super::Result;
@@ -12,6 +14,7 @@ use super::Result;
 /// Implementations can read from:
 /// - Filesystem (current working directory)
 /// - Git tree objects (historical commits without checkout)
+/// - Memory (editor buffers, generated code, archive entries, tests)
 pub trait ContentSource: Send + Sync {
     /// Read the contents of a file at the given path.
     fn read(&self, path: &Path) -> Result<Vec<u8>>;
@@ -166,6 +169,54 @@ impl ContentSource for TreeSource {
     }
 }
 
+/// Serves file contents from memory.
+///
+/// Paths are relative to the analysis root, like [`TreeSource`] entries.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemorySource {
+    /// Create an empty memory source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, replacing any previous contents at `path`.
+    pub fn with_file(mut self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> Self {
+        self.insert(path, contents);
+        self
+    }
+
+    /// Insert or replace the contents of a file.
+    pub fn insert(&mut self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) {
+        self.files.insert(path.into(), contents.into());
+    }
+
+    /// Remove a file, returning its contents.
+    pub fn remove(&mut self, path: &Path) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    /// List all files, sorted by path.
+    pub fn list_files(&self) -> Vec<PathBuf> {
+        self.files.keys().cloned().collect()
+    }
+}
+
+impl ContentSource for MemorySource {
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        self.files.get(path).cloned().ok_or_else(|| {
+            super::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("File not found in memory: {}", path.display()),
+            ))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,6 +303,40 @@ mod tests {
         assert_eq!(content, b"fn main() {}");
     }
 
+    #[test]
+    fn test_memory_source_reads_and_lists_files() {
+        let mut source = MemorySource::new()
+            .with_file("src/lib.rs", "pub fn a() {}")
+            .with_file("main.rs", b"fn main() {}".to_vec());
+        source.insert("src/lib.rs", "pub fn b() {}");
+
+        assert_eq!(
+            source.read(Path::new("src/lib.rs")).unwrap(),
+            b"pub fn b() {}"
+        );
+        assert_eq!(
+            source.read(Path::new("./main.rs")).unwrap(),
+            b"fn main() {}"
+        );
+        assert_eq!(
+            source.list_files(),
+            vec![PathBuf::from("main.rs"), PathBuf::from("src/lib.rs")]
+        );
+    }
+
+    #[test]
+    fn test_memory_source_returns_not_found_for_missing_file() {
+        let mut source = MemorySource::new().with_file("a.rs", "");
+        assert!(source.remove(Path::new("a.rs")).is_some());
+
+        match source.read(Path::new("a.rs")) {
+            Err(super::super::Error::Io(e)) => {
+                assert_eq!(e.kind(), std::io::ErrorKind::NotFound)
+            }
+            other => panic!("expected not found, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_tree_source_reads_file_at_commit() {
         let temp_dir = tempfile::tempdir().unwrap();
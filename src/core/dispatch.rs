@@ -0,0 +1,177 @@
+//! Running analyzers by name.
+//!
+//! [`run_by_name`] runs any analyzer with a default configuration and
+//! returns its typed result wrapped in [`AnalyzerResult`], for callers that
+//! pick analyzers at runtime (`omen all`, `omen query`, the MCP server)
+//! without matching on every analyzer type themselves.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{AnalysisContext, Analyzer, Error, Result};
+use crate::analyzers::{
+    api, callgraph, changes, churn, cohesion, commits, complexity, deadcode, defect, docdrift,
+    docker, duplicates, flags, graph, hidden, hotspot, ownership, pipeline, repomap, risk, satd,
+    smells, tdg, temporal,
+};
+use crate::score;
+
+macro_rules! analyzer_results {
+    ($($variant:ident($module:ident) = $name:literal),* $(,)?) => {
+        /// The result of one analyzer. Serializes as the wrapped analysis.
+        #[derive(Debug, Clone, Serialize)]
+        #[serde(untagged)]
+        pub enum AnalyzerResult {
+            $($variant($module::Analysis),)*
+        }
+
+        impl AnalyzerResult {
+            /// Names accepted by [`run_by_name`], as returned by each
+            /// analyzer's [`Analyzer::name`].
+            pub const NAMES: &'static [&'static str] = &[$($name),*];
+
+            /// Other names [`run_by_name`] accepts, with the analyzer each
+            /// stands for.
+            pub const ALIASES: &'static [(&'static str, &'static str)] =
+                &[("clones", "duplicates")];
+
+            /// The analyzer `name` refers to, resolving [`Self::ALIASES`].
+            pub fn resolve(name: &str) -> Option<&'static str> {
+                Self::NAMES.iter().copied().find(|known| *known == name).or_else(|| {
+                    Self::ALIASES
+                        .iter()
+                        .find(|(alias, _)| *alias == name)
+                        .map(|(_, target)| *target)
+                })
+            }
+
+            /// Every name [`run_by_name`] accepts: [`Self::NAMES`] followed by
+            /// the aliases.
+            pub fn accepted_names() -> impl Iterator<Item = &'static str> {
+                Self::NAMES
+                    .iter()
+                    .copied()
+                    .chain(Self::ALIASES.iter().map(|(alias, _)| *alias))
+            }
+
+            /// Name of the analyzer that produced this result.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => $name,)*
+                }
+            }
+        }
+
+        /// Run the analyzer called `name` with its default configuration.
+        /// [`AnalyzerResult::ALIASES`] are accepted too, as on the command line.
+        pub fn run_by_name(name: &str, ctx: &AnalysisContext<'_>) -> Result<AnalyzerResult> {
+            match AnalyzerResult::resolve(name) {
+                $(Some($name) => Ok(AnalyzerResult::$variant(
+                    $module::Analyzer::default().analyze(ctx)?,
+                )),)*
+                _ => Err(Error::InvalidArgument(format!("unknown analyzer: {name}"))),
+            }
+        }
+    };
+}
+
+analyzer_results! {
+    Complexity(complexity) = "complexity",
+    Satd(satd) = "satd",
+    Deadcode(deadcode) = "deadcode",
+    Churn(churn) = "churn",
+    Duplicates(duplicates) = "duplicates",
+    Defect(defect) = "defect",
+    Risk(risk) = "risk",
+    Changes(changes) = "changes",
+    Commits(commits) = "commits",
+    Tdg(tdg) = "tdg",
+    Graph(graph) = "graph",
+    Hotspot(hotspot) = "hotspot",
+    Temporal(temporal) = "temporal",
+    Hidden(hidden) = "hidden",
+    Ownership(ownership) = "ownership",
+    Cohesion(cohesion) = "cohesion",
+    Repomap(repomap) = "repomap",
+    Callgraph(callgraph) = "callgraph",
+    Smells(smells) = "smells",
+    Flags(flags) = "flags",
+    Pipeline(pipeline) = "pipeline",
+    Docker(docker) = "docker",
+    Docdrift(docdrift) = "docdrift",
+    Api(api) = "api",
+    Score(score) = "score",
+}
+
+impl AnalyzerResult {
+    /// The result as JSON, as the analyzer's own output serializes.
+    pub fn to_value(&self) -> Result<Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::config::Config;
+    use crate::core::FileSet;
+
+    #[test]
+    fn test_run_by_name_returns_typed_result() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("lib.rs"),
+            "fn f(x: i32) -> i32 { if x > 0 { 1 } else { 2 } }\n",
+        )
+        .unwrap();
+        let config = Config::default();
+        let files = FileSet::from_path(temp.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&files, &config, Some(temp.path()));
+
+        let result = run_by_name("complexity", &ctx).unwrap();
+
+        assert_eq!(result.name(), "complexity");
+        let AnalyzerResult::Complexity(analysis) = &result else {
+            panic!("expected complexity, got {}", result.name());
+        };
+        assert_eq!(analysis.files.len(), 1);
+        let value = result.to_value().unwrap();
+        assert!(
+            value.get("files").is_some(),
+            "serializes unwrapped: {value}"
+        );
+
+        let clones = run_by_name("clones", &ctx).unwrap();
+        assert_eq!(clones.name(), "duplicates");
+    }
+
+    #[test]
+    fn test_run_by_name_rejects_unknown() {
+        let config = Config::default();
+        let files = FileSet::from_files(PathBuf::from("."), Vec::new());
+        let ctx = AnalysisContext::new(&files, &config, None);
+
+        let err = run_by_name("nope", &ctx).unwrap_err();
+        assert!(err.to_string().contains("unknown analyzer: nope"));
+    }
+
+    #[test]
+    fn test_names_match_analyzers() {
+        assert_eq!(AnalyzerResult::NAMES.len(), 25);
+        assert!(AnalyzerResult::NAMES.contains(&"callgraph"));
+        assert!(!AnalyzerResult::NAMES.contains(&"clones"));
+    }
+
+    #[test]
+    fn test_resolve_accepts_names_and_aliases() {
+        assert_eq!(AnalyzerResult::resolve("duplicates"), Some("duplicates"));
+        assert_eq!(AnalyzerResult::resolve("clones"), Some("duplicates"));
+        assert_eq!(AnalyzerResult::resolve("nope"), None);
+        assert_eq!(
+            AnalyzerResult::accepted_names().count(),
+            AnalyzerResult::NAMES.len() + AnalyzerResult::ALIASES.len()
+        );
+    }
+}
@@ -1,13 +1,18 @@
 //! File set for collecting files to analyze.
 
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
-use ignore::{WalkBuilder, WalkState};
+use ignore::{DirEntry, ParallelVisitor, ParallelVisitorBuilder, WalkBuilder, WalkState};
+use indicatif::ProgressBar;
 
 use super::progress::{create_spinner, is_tty};
-use super::{Language, Result};
+use super::{ContentSource, Language, Result};
 use crate::config::Config;
 
 /// A set of files to analyze, respecting .gitignore.
@@ -17,9 +22,23 @@ pub struct FileSet {
     root: PathBuf,
     /// All files in the set.
     files: Vec<PathBuf>,
-    /// Excluded patterns.
-    #[allow(dead_code)]
+    /// Excluded patterns (config excludes plus any `exclude_by_glob` filters).
     exclude_patterns: Vec<String>,
+    /// Include patterns applied via `filter_by_glob`.
+    include_patterns: Vec<String>,
+    /// Walk timing, when the set was built from the filesystem.
+    walk: Option<WalkStats>,
+    /// Languages of the extensionless scripts in the set, by relative path.
+    scripts: HashMap<PathBuf, Language>,
+}
+
+/// Cost of the directory walk behind a [`FileSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkStats {
+    /// Wall-clock time spent walking and filtering.
+    pub elapsed: Duration,
+    /// Directory entries visited, including directories and skipped files.
+    pub entries: usize,
 }
 
 impl FileSet {
@@ -46,23 +65,49 @@ impl FileSet {
             root,
             files,
             exclude_patterns: Vec::new(),
+            include_patterns: Vec::new(),
+            walk: None,
+            scripts: HashMap::new(),
         }
     }
 
     /// Create a file set from a TreeSource (git tree at a specific commit).
     /// Only includes files with recognized source code languages.
     pub fn from_tree_source(tree_source: &super::TreeSource, config: &Config) -> Result<Self> {
-        let all_files = tree_source.list_files()?;
+        Ok(Self::from_listed_files(
+            tree_source.list_files()?,
+            tree_source,
+            config,
+        ))
+    }
+
+    /// Create a file set from the files held by a memory source.
+    ///
+    /// Applies the same language, built-asset and exclude filtering as
+    /// [`FileSet::from_tree_source`]. Pair it with
+    /// `AnalysisContext::with_content_source` so analyzers read the same
+    /// in-memory contents.
+    pub fn from_memory_source(source: &super::MemorySource, config: &Config) -> Result<Self> {
+        Ok(Self::from_listed_files(source.list_files(), source, config))
+    }
 
+    /// Filter root-relative paths listed by a non-filesystem source.
+    fn from_listed_files(
+        all_files: Vec<PathBuf>,
+        source: &dyn ContentSource,
+        config: &Config,
+    ) -> Self {
         // Pre-compile glob patterns for exclusion
         let exclude_globs = build_glob_set(&config.exclude_patterns);
 
         // Filter to supported languages and apply exclusions
+        let mut scripts = HashMap::new();
         let files: Vec<PathBuf> = all_files
             .into_iter()
             .filter(|path| {
+                let extensionless = path.extension().is_none();
                 // Only include files with recognized languages
-                if Language::detect(path).is_none() {
+                if !extensionless && Language::detect(path).is_none() {
                     return false;
                 }
                 // Exclude built/minified assets
@@ -71,13 +116,30 @@ impl FileSet {
                 }
                 // Check exclude patterns
                 let path_str = path.to_string_lossy();
-                !exclude_globs.is_match(&*path_str)
+                if exclude_globs.is_match(&*path_str) {
+                    return false;
+                }
+                // Extensionless files are kept only as scripts
+                if extensionless {
+                    let script = source
+                        .read(path)
+                        .ok()
+                        .and_then(|c| Language::from_script(&c));
+                    let Some(language) = script else {
+                        return false;
+                    };
+                    scripts.insert(path.clone(), language);
+                }
+                true
             })
             .collect();
 
-        // Use a placeholder root since files are relative paths from tree
+        // Use a placeholder root since files are relative paths from the source
         let root = PathBuf::from(".");
-        Ok(Self::from_files(root, files))
+        Self {
+            scripts,
+            ..Self::from_files(root, files)
+        }
     }
 
     /// Create a file set with custom exclude patterns.
@@ -86,6 +148,7 @@ impl FileSet {
         exclude_patterns: Vec<String>,
         exclude_built_assets: bool,
     ) -> Result<Self> {
+        let start = Instant::now();
         let root = path.as_ref().canonicalize()?;
 
         let spinner = if is_tty() {
@@ -95,73 +158,43 @@ impl FileSet {
             None
         };
 
-        let walker = WalkBuilder::new(&root)
+        let mut builder = WalkBuilder::new(&root);
+        builder
             .hidden(true)
             .git_ignore(true)
             .git_global(true)
-            .git_exclude(true)
-            .build_parallel();
-
-        // Pre-compile glob patterns once for efficient matching
-        let exclude_globs = build_glob_set(&exclude_patterns);
-
-        let files_mutex = Mutex::new(Vec::new());
-        walker.run(|| {
-            let files_mutex = &files_mutex;
-            let exclude_globs = &exclude_globs;
-            let spinner = &spinner;
-            let root = &root;
-            Box::new(move |entry| {
-                let entry = match entry {
-                    Ok(e) => e,
-                    Err(_) => return WalkState::Continue,
-                };
-
-                if !entry.file_type().is_some_and(|ft| ft.is_file()) {
-                    return WalkState::Continue;
-                }
-
-                let path = entry.path();
-
-                if Language::detect(path).is_none() {
-                    return WalkState::Continue;
-                }
-
-                if exclude_built_assets && is_built_asset(path) {
-                    return WalkState::Continue;
-                }
-
-                // Match exclude patterns against relative path so that
-                // patterns like "tests/**" work regardless of absolute root.
-                let rel_path = path.strip_prefix(root).unwrap_or(path);
-                if exclude_built_assets && is_default_ignored_path(rel_path) {
-                    return WalkState::Continue;
-                }
-
-                let rel_str = rel_path.to_string_lossy();
-                if exclude_globs.is_match(&*rel_str) {
-                    return WalkState::Continue;
-                }
+            .git_exclude(true);
+        if exclude_built_assets {
+            // Prune generated directories instead of walking and discarding
+            // every file below them.
+            builder.filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_some_and(|ft| ft.is_dir())
+                    || !is_default_ignored_path(Path::new(entry.file_name()))
+            });
+        }
 
-                let owned = entry.into_path();
-                let owned = owned
-                    .strip_prefix(root)
-                    .map(|p| p.to_path_buf())
-                    .unwrap_or(owned);
-                let mut locked = files_mutex.lock().expect("file_set mutex poisoned");
-                locked.push(owned);
-
-                if let Some(ref s) = spinner {
-                    let count = locked.len();
-                    if count % 100 == 0 {
-                        s.set_message(format!("Scanning files... {count} found"));
-                    }
-                }
+        let shared = WalkShared {
+            root: &root,
+            // Pre-compile glob patterns once for efficient matching
+            exclude_globs: build_glob_set(&exclude_patterns),
+            exclude_built_assets,
+            spinner: spinner.as_ref(),
+            found: Mutex::new(Vec::new()),
+            scripts: Mutex::new(HashMap::new()),
+            matched: AtomicUsize::new(0),
+            entries: AtomicUsize::new(0),
+        };
+        builder
+            .build_parallel()
+            .visit(&mut WalkCollectorBuilder(&shared));
 
-                WalkState::Continue
-            })
-        });
-        let mut files = files_mutex.into_inner().expect("file_set mutex poisoned");
+        let entries = shared.entries.into_inner();
+        let mut files = shared.found.into_inner().expect("file_set mutex poisoned");
+        let scripts = shared
+            .scripts
+            .into_inner()
+            .expect("file_set mutex poisoned");
 
         // Sort for deterministic ordering
         files.sort();
@@ -174,9 +207,22 @@ impl FileSet {
             root,
             files,
             exclude_patterns,
+            include_patterns: Vec::new(),
+            walk: Some(WalkStats {
+                elapsed: start.elapsed(),
+                entries,
+            }),
+            scripts,
         })
     }
 
+    /// Timing of the directory walk that built this set.
+    ///
+    /// `None` for sets listed from a git tree, memory source or file list.
+    pub fn walk_stats(&self) -> Option<WalkStats> {
+        self.walk
+    }
+
     /// Get the root directory.
     pub fn root(&self) -> &Path {
         &self.root
@@ -187,6 +233,16 @@ impl FileSet {
         &self.files
     }
 
+    /// Glob patterns this set was narrowed to, relative to the root.
+    pub fn include_patterns(&self) -> &[String] {
+        &self.include_patterns
+    }
+
+    /// Glob patterns excluded from this set, relative to the root.
+    pub fn exclude_patterns(&self) -> &[String] {
+        &self.exclude_patterns
+    }
+
     /// Get the number of files.
     pub fn len(&self) -> usize {
         self.files.len()
@@ -202,11 +258,22 @@ impl FileSet {
         self.files.iter()
     }
 
+    /// Language of a file in the set, by extension or, for an extensionless
+    /// script, as recognized when the set was built. `path` may be relative
+    /// to the root or joined onto it.
+    pub fn language(&self, path: &Path) -> Option<Language> {
+        if path.extension().is_some() {
+            return Language::detect(path);
+        }
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        self.scripts.get(relative).copied()
+    }
+
     /// Filter files by language.
     pub fn filter_by_language(&self, lang: Language) -> Vec<&PathBuf> {
         self.files
             .iter()
-            .filter(|f| Language::detect(f) == Some(lang))
+            .filter(|f| self.language(f) == Some(lang))
             .collect()
     }
 
@@ -230,10 +297,17 @@ impl FileSet {
             .cloned()
             .collect();
 
+        let mut include_patterns = self.include_patterns.clone();
+        include_patterns.push(pattern.to_string());
+
         Self {
             root: self.root.clone(),
             files,
             exclude_patterns: self.exclude_patterns.clone(),
+            include_patterns,
+
+            walk: self.walk,
+            scripts: self.scripts.clone(),
         }
     }
 
@@ -251,6 +325,10 @@ impl FileSet {
             root: self.root.clone(),
             files,
             exclude_patterns: self.exclude_patterns.clone(),
+            include_patterns: self.include_patterns.clone(),
+
+            walk: self.walk,
+            scripts: self.scripts.clone(),
         }
     }
 
@@ -275,10 +353,17 @@ impl FileSet {
             .cloned()
             .collect();
 
+        let mut exclude_patterns = self.exclude_patterns.clone();
+        exclude_patterns.push(pattern.to_string());
+
         Self {
             root: self.root.clone(),
             files,
-            exclude_patterns: self.exclude_patterns.clone(),
+            exclude_patterns,
+            include_patterns: self.include_patterns.clone(),
+
+            walk: self.walk,
+            scripts: self.scripts.clone(),
         }
     }
 
@@ -286,7 +371,7 @@ impl FileSet {
     pub fn group_by_language(&self) -> std::collections::HashMap<Language, Vec<&PathBuf>> {
         let mut groups = std::collections::HashMap::new();
         for file in &self.files {
-            if let Some(lang) = Language::detect(file) {
+            if let Some(lang) = self.language(file) {
                 groups.entry(lang).or_insert_with(Vec::new).push(file);
             }
         }
@@ -319,6 +404,139 @@ impl<'a> IntoIterator for &'a FileSet {
     }
 }
 
+/// State shared by the walker threads of [`FileSet::from_path_with_patterns`].
+struct WalkShared<'a> {
+    root: &'a Path,
+    exclude_globs: GlobSet,
+    exclude_built_assets: bool,
+    spinner: Option<&'a ProgressBar>,
+    found: Mutex<Vec<PathBuf>>,
+    scripts: Mutex<HashMap<PathBuf, Language>>,
+    matched: AtomicUsize,
+    entries: AtomicUsize,
+}
+
+struct WalkCollectorBuilder<'a>(&'a WalkShared<'a>);
+
+impl<'s> ParallelVisitorBuilder<'s> for WalkCollectorBuilder<'s> {
+    fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
+        Box::new(WalkCollector {
+            shared: self.0,
+            files: Vec::new(),
+            scripts: Vec::new(),
+            entries: 0,
+            extensions: HashMap::new(),
+        })
+    }
+}
+
+/// One walker thread's view of the walk.
+///
+/// Matches are buffered per thread and merged once when the thread
+/// finishes, so workers never contend on a lock per file. Language support
+/// is cached by extension, since a tree holds thousands of files but only
+/// a handful of distinct extensions.
+struct WalkCollector<'a> {
+    shared: &'a WalkShared<'a>,
+    files: Vec<PathBuf>,
+    scripts: Vec<(PathBuf, Language)>,
+    entries: usize,
+    extensions: HashMap<OsString, bool>,
+}
+
+impl WalkCollector<'_> {
+    fn is_source(&mut self, path: &Path) -> bool {
+        let Some(ext) = path.extension() else {
+            return false;
+        };
+        if let Some(&known) = self.extensions.get(ext) {
+            return known;
+        }
+        let known = ext.to_str().and_then(Language::from_extension).is_some();
+        self.extensions.insert(ext.to_os_string(), known);
+        known
+    }
+
+    fn accept(&mut self, entry: DirEntry) {
+        // The file type comes from the directory listing, so skipping
+        // directories and specials costs no extra stat call.
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            return;
+        }
+
+        let path = entry.path();
+        let extensionless = path.extension().is_none();
+        if !extensionless && !self.is_source(path) {
+            return;
+        }
+
+        let shared = self.shared;
+        if shared.exclude_built_assets && is_built_asset(path) {
+            return;
+        }
+
+        // Match exclude patterns against relative path so that
+        // patterns like "tests/**" work regardless of absolute root.
+        let rel_path = path.strip_prefix(shared.root).unwrap_or(path);
+        let rel_str = rel_path.to_string_lossy();
+        if shared.exclude_globs.is_match(&*rel_str) {
+            return;
+        }
+
+        // Extensionless files are kept only as scripts, which takes reading
+        // them, so that is left until the cheap checks have passed.
+        let script = if extensionless {
+            let Some(language) = Language::detect_script(path) else {
+                return;
+            };
+            Some(language)
+        } else {
+            None
+        };
+
+        let owned = entry.into_path();
+        let owned = owned
+            .strip_prefix(shared.root)
+            .map(|p| p.to_path_buf())
+            .unwrap_or(owned);
+        if let Some(language) = script {
+            self.scripts.push((owned.clone(), language));
+        }
+        self.files.push(owned);
+
+        if let Some(s) = shared.spinner {
+            let count = shared.matched.fetch_add(1, Ordering::Relaxed) + 1;
+            if count % 100 == 0 {
+                s.set_message(format!("Scanning files... {count} found"));
+            }
+        }
+    }
+}
+
+impl ParallelVisitor for WalkCollector<'_> {
+    fn visit(&mut self, entry: std::result::Result<DirEntry, ignore::Error>) -> WalkState {
+        self.entries += 1;
+        if let Ok(entry) = entry {
+            self.accept(entry);
+        }
+        WalkState::Continue
+    }
+}
+
+impl Drop for WalkCollector<'_> {
+    fn drop(&mut self) {
+        self.shared
+            .entries
+            .fetch_add(self.entries, Ordering::Relaxed);
+        if let Ok(mut found) = self.shared.found.lock() {
+            found.append(&mut self.files);
+        }
+        if let Ok(mut scripts) = self.shared.scripts.lock() {
+            scripts.extend(self.scripts.drain(..));
+        }
+    }
+}
+
 /// Check if a file path is a built/minified asset.
 ///
 /// Detects JS/TS family files with `.min.`, `.bundle.`, or `.chunk.` segments
@@ -346,20 +564,21 @@ fn is_built_asset(path: &Path) -> bool {
     BUILT_SUFFIXES.iter().any(|suffix| stem.ends_with(suffix))
 }
 
-fn is_default_ignored_path(path: &Path) -> bool {
-    const DEFAULT_IGNORED_DIRS: &[&str] = &[
-        "node_modules",
-        "target",
-        ".next",
-        "dist",
-        "build",
-        "coverage",
-        ".coverage",
-        ".turbo",
-        ".cache",
-        "vendor",
-    ];
+/// Directories skipped when `exclude_built_assets` is enabled.
+pub(crate) const DEFAULT_IGNORED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    ".next",
+    "dist",
+    "build",
+    "coverage",
+    ".coverage",
+    ".turbo",
+    ".cache",
+    "vendor",
+];
 
+fn is_default_ignored_path(path: &Path) -> bool {
     path.components().any(|component| {
         component
             .as_os_str()
@@ -466,6 +685,73 @@ mod tests {
         assert_eq!(groups.get(&Language::Python).map(|v| v.len()), Some(1));
     }
 
+    #[test]
+    fn test_file_set_detects_extensionless_scripts() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("bin")).unwrap();
+        std::fs::write(
+            temp.path().join("bin/deploy"),
+            "#!/usr/bin/env python3\nprint('hi')\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("bin/setup"), "set -e\n# vim: ft=sh\n").unwrap();
+        std::fs::write(temp.path().join("LICENSE"), "MIT License\n").unwrap();
+
+        let file_set = FileSet::from_path_default(temp.path()).unwrap();
+        assert_eq!(
+            file_set.files(),
+            &[PathBuf::from("bin/deploy"), PathBuf::from("bin/setup")]
+        );
+        assert_eq!(
+            file_set.language(&file_set.root().join("bin/deploy")),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            file_set.language(Path::new("bin/setup")),
+            Some(Language::Bash)
+        );
+        assert_eq!(Language::detect(Path::new("bin/deploy")), None);
+        let narrowed = file_set.filter_by_glob("bin/*");
+        assert_eq!(
+            narrowed.filter_by_language(Language::Python),
+            vec![&PathBuf::from("bin/deploy")]
+        );
+        let groups = file_set.group_by_language();
+        assert_eq!(groups.get(&Language::Bash).map(|v| v.len()), Some(1));
+
+        let source = crate::core::MemorySource::new()
+            .with_file("scripts/release", "#!/bin/bash\necho release\n")
+            .with_file("NOTICE", "notice\n");
+        let file_set = FileSet::from_memory_source(&source, &Config::default()).unwrap();
+        assert_eq!(file_set.files(), &[PathBuf::from("scripts/release")]);
+        assert_eq!(
+            file_set.language(Path::new("scripts/release")),
+            Some(Language::Bash)
+        );
+    }
+
+    #[test]
+    fn test_file_set_walk_stats() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("node_modules/pkg")).unwrap();
+        for i in 0..20 {
+            std::fs::write(temp.path().join(format!("node_modules/pkg/{i}.js")), "").unwrap();
+        }
+        std::fs::write(temp.path().join("a.rs"), "").unwrap();
+        std::fs::write(temp.path().join("README"), "").unwrap();
+
+        let file_set = FileSet::from_path_default(temp.path()).unwrap();
+        let walk = file_set.walk_stats().unwrap();
+        assert_eq!(file_set.len(), 1);
+        // Root, a.rs and README; node_modules is pruned without being visited.
+        assert_eq!(walk.entries, 3);
+        assert_eq!(file_set.filter_by_glob("*.rs").walk_stats(), Some(walk));
+
+        assert!(FileSet::from_files(PathBuf::from("."), vec![])
+            .walk_stats()
+            .is_none());
+    }
+
     #[test]
     fn test_file_set_relative_path() {
         let temp = tempfile::tempdir().unwrap();
@@ -617,6 +903,26 @@ mod tests {
         assert_eq!(sorted_files[2], PathBuf::from("z.rs"));
     }
 
+    #[test]
+    fn test_file_set_from_memory_source() {
+        use crate::core::MemorySource;
+
+        let source = MemorySource::new()
+            .with_file("src/main.rs", "fn main() {}")
+            .with_file("README.md", "# readme")
+            .with_file("dist/app.min.js", "var a=1;")
+            .with_file("vendor/lib.go", "package lib");
+        let config = Config {
+            exclude_patterns: vec!["vendor/**".to_string()],
+            ..Config::default()
+        };
+
+        let file_set = FileSet::from_memory_source(&source, &config).unwrap();
+
+        assert_eq!(file_set.root(), Path::new("."));
+        assert_eq!(file_set.files(), &[PathBuf::from("src/main.rs")]);
+    }
+
     #[test]
     fn test_file_set_from_tree_source() {
         use crate::core::TreeSource;
@@ -811,6 +1117,20 @@ mod tests {
         assert!(filtered.files()[0].to_string_lossy().contains("lib.rs"));
     }
 
+    #[test]
+    fn test_glob_filters_are_recorded() {
+        let root = PathBuf::from("/project");
+        let files = vec![PathBuf::from("src/main.rs"), PathBuf::from("src/lib.rs")];
+
+        let filtered = FileSet::from_files(root, files)
+            .filter_by_glob("*.rs")
+            .exclude_by_glob("main.rs")
+            .filter_by_glob("[invalid");
+
+        assert_eq!(filtered.include_patterns(), ["*.rs"]);
+        assert_eq!(filtered.exclude_patterns(), ["main.rs"]);
+    }
+
     #[test]
     fn test_file_set_stores_relative_paths() {
         let temp = tempfile::tempdir().unwrap();
@@ -0,0 +1,457 @@
+//! Generated and macro-expanded code detection.
+//!
+//! Generated clients, protobuf stubs and macro-heavy Rust files produce
+//! metric values that say nothing about the code people maintain. The
+//! complexity and cohesion analyzers use this classification to exclude or
+//! down-weight them according to `[generated]` in the config, and dead code
+//! detection uses the path conventions to skip generated definitions.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{GeneratedConfig, GeneratedHandling};
+
+/// Why a file is treated as non hand-written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratedKind {
+    /// Generated by a tool: naming convention or a header marker.
+    Generated,
+    /// Rust file dominated by macro invocations and definitions.
+    MacroExpanded,
+}
+
+/// How much of an analysis was generated code, and what was done with it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedSummary {
+    pub handling: GeneratedHandling,
+    /// Files classified as generated or macro-expanded.
+    pub files: usize,
+    /// Share of analyzed files they make up (0-1).
+    pub ratio: f64,
+}
+
+impl GeneratedSummary {
+    pub fn new(handling: GeneratedHandling, files: usize, total_files: usize) -> Self {
+        Self {
+            handling,
+            files,
+            ratio: if total_files > 0 {
+                files as f64 / total_files as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Header markers that tools write at the top of generated files.
+const MARKERS: &[&str] = &[
+    "@generated",
+    "do not edit",
+    "code generated",
+    "auto-generated",
+    "autogenerated",
+    "automatically generated",
+    "this file was generated",
+    "this file is generated",
+];
+
+/// Lines from the top of a file searched for header markers.
+const HEADER_LINES: usize = 10;
+
+/// Standard library and logging macros that don't hide generated code.
+const COMMON_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "format",
+    "format_args",
+    "write",
+    "writeln",
+    "vec",
+    "panic",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "matches",
+    "dbg",
+    "concat",
+    "include_str",
+    "include_bytes",
+    "env",
+    "trace",
+    "debug",
+    "info",
+    "warn",
+    "error",
+];
+
+/// Classify a file from its path and content.
+pub fn classify(path: &Path, content: &[u8], config: &GeneratedConfig) -> Option<GeneratedKind> {
+    if is_generated_path(path) || has_marker(content, &config.markers) {
+        return Some(GeneratedKind::Generated);
+    }
+    if path.extension().is_some_and(|ext| ext == "rs")
+        && macro_line_ratio(&String::from_utf8_lossy(content)) >= config.macro_ratio
+    {
+        return Some(GeneratedKind::MacroExpanded);
+    }
+    None
+}
+
+/// Scale a metric for a down-weighted file, rounding up so non-zero values
+/// stay non-zero.
+pub fn downweight(value: u32, weight: f64) -> u32 {
+    (value as f64 * weight.clamp(0.0, 1.0)).ceil() as u32
+}
+
+/// Returns true if the file name follows a code generator's convention.
+pub fn is_generated_path(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    if path.components().any(|c| c.as_os_str() == "__generated__") {
+        return true;
+    }
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let lower = filename.to_lowercase();
+
+    // Protobuf / gRPC generated files
+    if lower.ends_with(".pb.go")
+        || lower.ends_with(".pb.cc")
+        || lower.ends_with(".pb.h")
+        || lower.ends_with(".pb2.py")
+        || lower.ends_with("_pb2.py")
+        || lower.ends_with("_pb2_grpc.py")
+        || lower.ends_with("_pb.js")
+        || lower.ends_with("_pb.ts")
+        || lower.ends_with("_grpc_pb.js")
+    {
+        return true;
+    }
+
+    // Common generated file patterns
+    if lower.ends_with(".gen.go")
+        || lower.ends_with(".generated.go")
+        || lower.ends_with(".generated.ts")
+        || lower.ends_with(".generated.js")
+        || lower.ends_with(".g.cs")
+        || lower.ends_with(".designer.cs")
+    {
+        return true;
+    }
+
+    // Go-specific generated file conventions
+    if lower.ends_with("_gen.go")
+        || lower.ends_with("_generated.go")
+        || lower == "bindata.go"
+        || lower.ends_with("wire_gen.go")
+    {
+        return true;
+    }
+
+    // Mock generated files
+    if lower.starts_with("mock_") && lower.ends_with(".go") {
+        return true;
+    }
+
+    // Kubernetes code-gen (zz_generated.*.go)
+    lower.starts_with("zz_generated.")
+}
+
+/// Returns true if a generator marker appears in the file header.
+fn has_marker(content: &[u8], extra: &[String]) -> bool {
+    let head: String = String::from_utf8_lossy(&content[..content.len().min(4096)])
+        .lines()
+        .take(HEADER_LINES)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    MARKERS.iter().any(|m| head.contains(m))
+        || extra
+            .iter()
+            .any(|m| !m.is_empty() && head.contains(&m.to_lowercase()))
+}
+
+/// Share of non-blank lines inside macro invocations or `macro_rules!`
+/// definitions, ignoring common std and logging macros.
+pub fn macro_line_ratio(source: &str) -> f64 {
+    let bytes = source.as_bytes();
+    let lines: Vec<&str> = source.lines().collect();
+    let mut covered = vec![false; lines.len() + 1];
+    let mut line = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        if let Some(next) = skip_trivia(bytes, i, &mut line) {
+            i = next;
+            continue;
+        }
+        match bytes[i] {
+            b'\n' => line += 1,
+            b'!' if bytes.get(i + 1) != Some(&b'=') => {
+                let name = ident_before(bytes, i);
+                if let Some(open) = macro_body(bytes, i + 1, name) {
+                    let start = line;
+                    let end = skip_group(bytes, open, &mut line);
+                    if !name.is_empty() && !COMMON_MACROS.contains(&name) {
+                        for flag in covered.iter_mut().take(line + 1).skip(start) {
+                            *flag = true;
+                        }
+                    }
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let non_blank: Vec<usize> = (0..lines.len())
+        .filter(|&n| !lines[n].trim().is_empty())
+        .collect();
+    if non_blank.is_empty() {
+        return 0.0;
+    }
+    let in_macros = non_blank.iter().filter(|&&n| covered[n]).count();
+    in_macros as f64 / non_blank.len() as f64
+}
+
+/// Identifier ending just before `end`.
+fn ident_before(bytes: &[u8], end: usize) -> &str {
+    let start = bytes[..end]
+        .iter()
+        .rposition(|&b| !is_ident_byte(b))
+        .map_or(0, |p| p + 1);
+    std::str::from_utf8(&bytes[start..end]).unwrap_or_default()
+}
+
+/// Position of the opening delimiter of a macro body starting at `i`
+/// (after the `!`), skipping the rule name of `macro_rules! name`.
+fn macro_body(bytes: &[u8], mut i: usize, name: &str) -> Option<usize> {
+    let skip_ws = |mut i: usize| {
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t') {
+            i += 1;
+        }
+        i
+    };
+    i = skip_ws(i);
+    if name == "macro_rules" {
+        while i < bytes.len() && is_ident_byte(bytes[i]) {
+            i += 1;
+        }
+        i = skip_ws(i);
+    }
+    matches!(bytes.get(i), Some(b'(' | b'[' | b'{')).then_some(i)
+}
+
+/// Skip a bracketed group starting at `open`; returns the index after the
+/// matching close and advances `line` past any newlines.
+fn skip_group(bytes: &[u8], open: usize, line: &mut usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        if let Some(next) = skip_trivia(bytes, i, line) {
+            i = next;
+            continue;
+        }
+        match bytes[i] {
+            b'\n' => *line += 1,
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// If a comment, string or char literal starts at `i`, return the index
+/// after it, counting newlines inside it.
+fn skip_trivia(bytes: &[u8], i: usize, line: &mut usize) -> Option<usize> {
+    let count_lines = |from: usize, to: usize, line: &mut usize| {
+        *line += bytes[from..to].iter().filter(|&&b| b == b'\n').count();
+    };
+    match (bytes[i], bytes.get(i + 1)) {
+        (b'/', Some(b'/')) => Some(
+            bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |p| i + p),
+        ),
+        (b'/', Some(b'*')) => {
+            let end = find(bytes, i + 2, b"*/").map_or(bytes.len(), |p| p + 2);
+            count_lines(i, end, line);
+            Some(end)
+        }
+        (b'"', _) => {
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j] != b'"' {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            let end = (j + 1).min(bytes.len());
+            count_lines(i, end, line);
+            Some(end)
+        }
+        (b'r', Some(b'"' | b'#')) if i == 0 || !is_ident_byte(bytes[i - 1]) => {
+            let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+            if bytes.get(i + 1 + hashes) != Some(&b'"') {
+                return None;
+            }
+            let mut close = vec![b'"'];
+            close.extend(std::iter::repeat_n(b'#', hashes));
+            let end = find(bytes, i + 2 + hashes, &close).map_or(bytes.len(), |p| p + close.len());
+            count_lines(i, end, line);
+            Some(end)
+        }
+        (b'\'', Some(b'\\')) => {
+            // Escaped char: the closing quote comes after the escape.
+            let end = bytes
+                .get(i + 3..)?
+                .iter()
+                .position(|&b| b == b'\'')
+                .map_or(bytes.len(), |p| i + 4 + p);
+            Some(end)
+        }
+        (b'\'', Some(_)) if bytes.get(i + 2) == Some(&b'\'') => Some(i + 3),
+        _ => None,
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|p| from + p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_generated_path() {
+        assert!(is_generated_path("api/service.pb.go"));
+        assert!(is_generated_path("client/api_pb.ts"));
+        assert!(is_generated_path("src/__generated__/graphql.ts"));
+        assert!(is_generated_path("Forms/Main.Designer.cs"));
+        assert!(!is_generated_path("src/generator.rs"));
+        assert!(!is_generated_path("src/client.ts"));
+    }
+
+    #[test]
+    fn test_classify_header_markers() {
+        let config = GeneratedConfig::default();
+        let ts = b"/* eslint-disable */\n// This file was auto-generated by openapi-generator.\nexport class Api {}\n";
+        assert_eq!(
+            classify(Path::new("src/api.ts"), ts, &config),
+            Some(GeneratedKind::Generated)
+        );
+        let go = b"// Code generated by sqlc. DO NOT EDIT.\npackage db\n";
+        assert_eq!(
+            classify(Path::new("db/query.go"), go, &config),
+            Some(GeneratedKind::Generated)
+        );
+        let late = format!("{}// @generated\n", "fn f() {}\n".repeat(20));
+        assert_eq!(
+            classify(Path::new("src/lib.rs"), late.as_bytes(), &config),
+            None
+        );
+
+        let custom = GeneratedConfig {
+            markers: vec!["Produced by Acme".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            classify(Path::new("x.py"), b"# produced by acme\nx = 1\n", &custom),
+            Some(GeneratedKind::Generated)
+        );
+    }
+
+    #[test]
+    fn test_macro_line_ratio() {
+        let heavy = r#"use crate::ops;
+
+impl_ops! {
+    Add, add, "+";
+    Sub, sub, "-";
+    Mul, mul, "}";
+}
+
+macro_rules! square {
+    ($x:expr) => {
+        $x * $x
+    };
+}
+"#;
+        assert!(macro_line_ratio(heavy) > 0.8);
+        assert_eq!(
+            classify(
+                Path::new("src/ops.rs"),
+                heavy.as_bytes(),
+                &GeneratedConfig::default()
+            ),
+            Some(GeneratedKind::MacroExpanded)
+        );
+
+        let plain = r#"fn main() {
+    let v = vec![
+        1,
+        2,
+    ];
+    println!(
+        "{}",
+        v.len()
+    );
+    if a != b {
+        run();
+    }
+}
+"#;
+        assert_eq!(macro_line_ratio(plain), 0.0);
+        assert_eq!(
+            classify(
+                Path::new("src/main.rs"),
+                plain.as_bytes(),
+                &GeneratedConfig::default()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_downweight_and_summary() {
+        assert_eq!(downweight(40, 0.25), 10);
+        assert_eq!(downweight(1, 0.25), 1);
+        assert_eq!(downweight(0, 0.25), 0);
+        assert_eq!(downweight(7, 2.0), 7);
+
+        let summary = GeneratedSummary::new(GeneratedHandling::Exclude, 1, 4);
+        assert!((summary.ratio - 0.25).abs() < f64::EPSILON);
+        assert_eq!(
+            GeneratedSummary::new(GeneratedHandling::Include, 0, 0).ratio,
+            0.0
+        );
+    }
+}
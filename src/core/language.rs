@@ -1,9 +1,21 @@
 //! Language detection and enumeration.
+//!
+//! Files are recognized by extension. Extensionless scripts such as
+//! `bin/deploy` are recognized by their shebang or an editor modeline when
+//! a file set is built; the file set keeps their languages, so ask
+//! `FileSet::language` about files in a set.
 
+use std::io::Read;
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+/// Bytes read from an extensionless file to look for a shebang or modeline.
+const SCRIPT_SNIFF_BYTES: u64 = 16 * 1024;
+
+/// Lines searched for a modeline at either end of a file, as in Vim.
+const MODELINE_LINES: usize = 5;
+
 /// Supported programming languages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -22,6 +34,8 @@ pub enum Language {
     Ruby,
     Php,
     Bash,
+    Lua,
+    Zig,
 }
 
 impl Language {
@@ -31,6 +45,107 @@ impl Language {
         Self::from_extension(extension)
     }
 
+    /// Detect the language of the script at `path` from its content.
+    pub fn detect_script(path: &Path) -> Option<Self> {
+        let mut head = Vec::new();
+        std::fs::File::open(path)
+            .ok()?
+            .take(SCRIPT_SNIFF_BYTES)
+            .read_to_end(&mut head)
+            .ok()?;
+        Self::from_script(&head)
+    }
+
+    /// Get language from a script's shebang line, or failing that, a Vim or
+    /// Emacs modeline in its first or last lines.
+    pub fn from_script(content: &[u8]) -> Option<Self> {
+        if content.contains(&0) {
+            return None;
+        }
+        let text = String::from_utf8_lossy(content);
+        let lines: Vec<&str> = text.lines().collect();
+        if let Some(shebang) = lines.first().and_then(|line| line.strip_prefix("#!")) {
+            return Self::from_shebang(shebang);
+        }
+        // Skip at least the head so short files are not searched twice.
+        let tail = lines
+            .len()
+            .saturating_sub(MODELINE_LINES)
+            .max(MODELINE_LINES);
+        lines
+            .iter()
+            .take(MODELINE_LINES)
+            .chain(lines.iter().skip(tail))
+            .find_map(|line| Self::from_modeline(line))
+    }
+
+    /// `/usr/bin/python3`, `/usr/bin/env -S node --flag`, ...
+    fn from_shebang(shebang: &str) -> Option<Self> {
+        let mut words = shebang.split_whitespace();
+        let mut program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            // Skip env's options and variable assignments.
+            program = words.find(|w| !w.starts_with('-') && !w.contains('='))?;
+        }
+        Self::from_interpreter(program)
+    }
+
+    /// Vim `vim: set ft=python:` or Emacs `-*- mode: ruby -*-` / `-*- sh -*-`.
+    fn from_modeline(line: &str) -> Option<Self> {
+        if let Some((_, rest)) = line.split_once("-*-") {
+            let (vars, _) = rest.split_once("-*-")?;
+            if !vars.contains(':') {
+                return Self::from_name(vars.trim());
+            }
+            return vars.split(';').find_map(|var| {
+                let (key, value) = var.split_once(':')?;
+                key.trim()
+                    .eq_ignore_ascii_case("mode")
+                    .then(|| Self::from_name(value.trim()))?
+            });
+        }
+
+        let options = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+            line.match_indices(marker)
+                .find(|(i, _)| *i == 0 || line[..*i].ends_with(char::is_whitespace))
+                .map(|(i, _)| &line[i + marker.len()..])
+        })?;
+        options
+            .split(|c: char| c.is_whitespace() || c == ':')
+            .find_map(|option| {
+                let (key, value) = option.split_once('=')?;
+                matches!(key, "ft" | "filetype" | "syntax").then(|| Self::from_name(value))?
+            })
+    }
+
+    /// Get language from an interpreter name, ignoring its version suffix.
+    fn from_interpreter(program: &str) -> Option<Self> {
+        match program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+            "sh" | "bash" | "dash" | "ksh" | "zsh" => Some(Self::Bash),
+            "python" | "pypy" => Some(Self::Python),
+            "node" | "nodejs" | "bun" => Some(Self::JavaScript),
+            "ts-node" | "tsx" => Some(Self::TypeScript),
+            "ruby" => Some(Self::Ruby),
+            "php" => Some(Self::Php),
+            "lua" | "luajit" => Some(Self::Lua),
+            _ => None,
+        }
+    }
+
+    /// Get language from an editor filetype or mode name.
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let name = name.strip_suffix("-mode").unwrap_or(&name);
+        match name {
+            "javascript" => Some(Self::JavaScript),
+            "typescript" => Some(Self::TypeScript),
+            "shell-script" | "shell" => Some(Self::Bash),
+            "rust" => Some(Self::Rust),
+            "csharp" => Some(Self::CSharp),
+            _ => Self::from_interpreter(name).or_else(|| Self::from_extension(name)),
+        }
+    }
+
     /// Get language from file extension.
     pub fn from_extension(ext: &str) -> Option<Self> {
         match ext.to_lowercase().as_str() {
@@ -48,6 +163,8 @@ impl Language {
             "rb" | "rake" | "gemspec" => Some(Self::Ruby),
             "php" => Some(Self::Php),
             "sh" | "bash" => Some(Self::Bash),
+            "lua" => Some(Self::Lua),
+            "zig" => Some(Self::Zig),
             _ => None,
         }
     }
@@ -69,6 +186,8 @@ impl Language {
             Self::Ruby => "Ruby",
             Self::Php => "PHP",
             Self::Bash => "Bash",
+            Self::Lua => "Lua",
+            Self::Zig => "Zig",
         }
     }
 
@@ -89,6 +208,16 @@ impl Language {
         )
     }
 
+    /// Representative of the languages whose files can call each other's
+    /// functions: TypeScript, TSX, and JSX join JavaScript, C++ joins C.
+    pub fn family(self) -> Self {
+        match self {
+            Self::TypeScript | Self::Tsx | Self::Jsx => Self::JavaScript,
+            Self::Cpp => Self::C,
+            other => other,
+        }
+    }
+
     /// Check if the language uses explicit imports.
     pub fn has_imports(&self) -> bool {
         !matches!(self, Self::C | Self::Cpp | Self::Bash)
@@ -113,6 +242,8 @@ impl Language {
             Self::Ruby => &["**/*.rb", "**/*.rake", "**/*.gemspec"],
             Self::Php => &["**/*.php"],
             Self::Bash => &["**/*.sh", "**/*.bash"],
+            Self::Lua => &["**/*.lua"],
+            Self::Zig => &["**/*.zig"],
         }
     }
 }
@@ -162,9 +293,63 @@ mod tests {
             Language::detect(Path::new("script.sh")),
             Some(Language::Bash)
         );
+        assert_eq!(Language::detect(Path::new("init.lua")), Some(Language::Lua));
+        assert_eq!(
+            Language::detect(Path::new("build.zig")),
+            Some(Language::Zig)
+        );
         assert_eq!(Language::detect(Path::new("README.md")), None);
     }
 
+    #[test]
+    fn test_from_script_shebang() {
+        let detect = |content: &str| Language::from_script(content.as_bytes());
+        assert_eq!(detect("#!/bin/sh\necho hi\n"), Some(Language::Bash));
+        assert_eq!(detect("#! /usr/bin/env bash\n"), Some(Language::Bash));
+        assert_eq!(detect("#!/usr/bin/env python3\n"), Some(Language::Python));
+        assert_eq!(
+            detect("#!/usr/local/bin/python3.12 -u\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            detect("#!/usr/bin/env -S NODE_ENV=prod node --flag\n"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(detect("#!/usr/bin/env ruby\n"), Some(Language::Ruby));
+        assert_eq!(detect("#!/usr/bin/perl\n"), None);
+        // A shebang wins over a modeline.
+        assert_eq!(detect("#!/usr/bin/perl\n# vim: ft=python\n"), None);
+    }
+
+    #[test]
+    fn test_from_script_modeline() {
+        let detect = |content: &str| Language::from_script(content.as_bytes());
+        assert_eq!(
+            detect("# vim: set ft=python :\nx = 1\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(detect("x=1\n# vi: filetype=sh\n"), Some(Language::Bash));
+        assert_eq!(
+            detect("# -*- mode: ruby; coding: utf-8 -*-\n"),
+            Some(Language::Ruby)
+        );
+        assert_eq!(detect("# -*- shell-script -*-\n"), Some(Language::Bash));
+        assert_eq!(detect("# -*- coding: utf-8 -*-\n"), None);
+        // `vi:` must start a word.
+        assert_eq!(detect("# navi: ft=python\n"), None);
+
+        let mut long = "# vim: ft=lua\n".to_string();
+        long.push_str(&"x = 1\n".repeat(20));
+        assert_eq!(detect(&long), Some(Language::Lua));
+        long.push_str("-- vim: ft=python\n");
+        assert_eq!(detect(&long), Some(Language::Lua), "head is searched first");
+        let middle = format!("{}# vim: ft=python\n{}", "x\n".repeat(6), "x\n".repeat(6));
+        assert_eq!(detect(&middle), None, "only the first and last lines count");
+
+        assert_eq!(detect("\0#!/bin/sh"), None);
+        assert_eq!(detect(""), None);
+    }
+
     #[test]
     fn test_from_extension() {
         assert_eq!(Language::from_extension("go"), Some(Language::Go));
@@ -1,17 +1,34 @@
 //! Core types and traits for code analysis.
 
 mod analyzer;
+pub mod baseline;
 mod content_source;
+mod dispatch;
 mod error;
 mod file_set;
+pub mod generated;
 mod language;
 pub mod progress;
+mod severity;
 mod source_file;
+mod span;
+mod test_file;
+pub mod timings;
+pub mod warm_start;
 
 pub use analyzer::{AnalysisContext, AnalysisResult, Analyzer, Summary};
-pub use content_source::{ContentSource, FilesystemSource, TreeSource};
+pub use baseline::{Baseline, BaselineDiff, BaselineEntry};
+pub use content_source::{ContentSource, FilesystemSource, MemorySource, TreeSource};
+pub use dispatch::{run_by_name, AnalyzerResult};
 pub use error::{Error, Result};
-pub use file_set::FileSet;
+pub(crate) use file_set::DEFAULT_IGNORED_DIRS;
+pub use file_set::{FileSet, WalkStats};
+pub use generated::{is_generated_path, GeneratedKind, GeneratedSummary};
 pub use language::Language;
 pub use progress::{create_progress, create_spinner, is_tty, ProgressBuilder, ProgressTracker};
-pub use source_file::SourceFile;
+pub use severity::{Severity, SeverityCounts};
+pub use source_file::{FileContent, SourceFile};
+pub use span::{LineIndex, Span};
+pub use test_file::is_test_file;
+pub use timings::{Deadline, Timings};
+pub use warm_start::WarmStart;
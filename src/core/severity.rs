@@ -0,0 +1,140 @@
+//! Severity levels shared across analyzers.
+//!
+//! Analyzers keep their own severity or risk enums, which differ in naming
+//! (`moderate` vs `medium`) and range (some stop at `high`). Each maps onto
+//! [`Severity`] so summaries can carry a common `severity_counts` histogram
+//! that dashboards and `--fail-on` read without knowing the analyzer.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Normalized severity, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Low => write!(f, "low"),
+            Severity::Medium => write!(f, "medium"),
+            Severity::High => write!(f, "high"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(Severity::Low),
+            "medium" | "moderate" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            _ => Err(format!("unknown severity: {s}")),
+        }
+    }
+}
+
+/// Number of findings at each [`Severity`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeverityCounts {
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+impl SeverityCounts {
+    /// Count one finding at `severity`.
+    pub fn record(&mut self, severity: Severity) {
+        match severity {
+            Severity::Critical => self.critical += 1,
+            Severity::High => self.high += 1,
+            Severity::Medium => self.medium += 1,
+            Severity::Low => self.low += 1,
+        }
+    }
+
+    /// Findings at `severity` or worse.
+    pub fn at_least(&self, severity: Severity) -> usize {
+        [
+            (Severity::Critical, self.critical),
+            (Severity::High, self.high),
+            (Severity::Medium, self.medium),
+            (Severity::Low, self.low),
+        ]
+        .iter()
+        .filter(|(level, _)| *level >= severity)
+        .map(|(_, count)| count)
+        .sum()
+    }
+
+    /// Read the `summary.severity_counts` of a serialized analyzer result.
+    ///
+    /// Returns `None` for analyzers whose summary has no histogram.
+    pub fn from_result(result: &serde_json::Value) -> Option<Self> {
+        let counts = result.get("summary")?.get("severity_counts")?;
+        serde_json::from_value(counts.clone()).ok()
+    }
+}
+
+impl<S: Into<Severity>> FromIterator<S> for SeverityCounts {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut counts = Self::default();
+        for severity in iter {
+            counts.record(severity.into());
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_least_includes_worse_levels() {
+        let counts: SeverityCounts = [
+            Severity::Critical,
+            Severity::High,
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(counts.at_least(Severity::Critical), 1);
+        assert_eq!(counts.at_least(Severity::High), 3);
+        assert_eq!(counts.at_least(Severity::Low), 5);
+    }
+
+    #[test]
+    fn test_from_result_reads_summary() {
+        let result = serde_json::json!({
+            "summary": {"severity_counts": {"critical": 2, "high": 1}}
+        });
+        let counts = SeverityCounts::from_result(&result).unwrap();
+        assert_eq!((counts.critical, counts.high, counts.low), (2, 1, 0));
+
+        assert!(SeverityCounts::from_result(&serde_json::json!({"summary": {}})).is_none());
+    }
+
+    #[test]
+    fn test_parse_accepts_moderate() {
+        assert_eq!("moderate".parse::<Severity>(), Ok(Severity::Medium));
+        assert_eq!("CRITICAL".parse::<Severity>(), Ok(Severity::Critical));
+        assert!("severe".parse::<Severity>().is_err());
+    }
+}
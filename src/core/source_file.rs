@@ -1,8 +1,15 @@
 //! Source file representation.
+//!
+//! File contents are held in a [`FileContent`], a shared buffer that the
+//! parser and analyzers borrow from instead of copying. With the `mmap`
+//! feature, large files are memory-mapped rather than read.
 
+use std::fmt;
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
-use super::{Language, Result};
+use super::{ContentSource, Language, LineIndex, Result, Span};
 
 /// A source file with its content loaded.
 #[derive(Debug, Clone)]
@@ -12,34 +19,154 @@ pub struct SourceFile {
     /// Detected language.
     pub language: Language,
     /// File content as bytes.
-    pub content: Vec<u8>,
+    pub content: FileContent,
+    /// Line starts of `content`, built on first use by [`Self::span`].
+    lines: OnceLock<LineIndex>,
+}
+
+/// Immutable file contents, cheap to clone.
+///
+/// Clones share one buffer, so a [`SourceFile`] and the parse results built
+/// from it never duplicate the bytes.
+#[derive(Clone)]
+pub struct FileContent(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Owned(Arc<[u8]>),
+    #[cfg(feature = "mmap")]
+    Mapped(Arc<memmap2::Mmap>),
+}
+
+impl FileContent {
+    /// Files at least this large are mapped; smaller ones are cheaper to read.
+    #[cfg(feature = "mmap")]
+    const MMAP_THRESHOLD: u64 = 64 * 1024;
+
+    /// Read the file at `path`.
+    ///
+    /// With the `mmap` feature, files of 64 KiB or more are memory-mapped.
+    /// A mapped file that is truncated while mapped can fault, so only map
+    /// files that are not being rewritten during analysis.
+    pub fn read(path: &Path) -> Result<Self> {
+        #[cfg(feature = "mmap")]
+        {
+            let file = std::fs::File::open(path)?;
+            if file.metadata()?.len() >= Self::MMAP_THRESHOLD {
+                // SAFETY: the map is read-only; the file changing underneath
+                // it is the caveat documented above.
+                let map = unsafe { memmap2::Mmap::map(&file)? };
+                return Ok(Self(Repr::Mapped(Arc::new(map))));
+            }
+        }
+        Ok(std::fs::read(path)?.into())
+    }
+
+    /// Whether the contents are memory-mapped rather than held in memory.
+    pub fn is_mapped(&self) -> bool {
+        match self.0 {
+            Repr::Owned(_) => false,
+            #[cfg(feature = "mmap")]
+            Repr::Mapped(_) => true,
+        }
+    }
+}
+
+impl Deref for FileContent {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Owned(bytes) => &bytes[..],
+            #[cfg(feature = "mmap")]
+            Repr::Mapped(map) => &map[..],
+        }
+    }
+}
+
+impl AsRef<[u8]> for FileContent {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl From<Vec<u8>> for FileContent {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(Repr::Owned(bytes.into()))
+    }
+}
+
+impl From<&[u8]> for FileContent {
+    fn from(bytes: &[u8]) -> Self {
+        Self(Repr::Owned(bytes.into()))
+    }
+}
+
+impl fmt::Debug for FileContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileContent")
+            .field("len", &self.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
 }
 
 impl SourceFile {
-    /// Load a source file from disk.
+    /// Load a source file from disk, memory-mapping it if large and the
+    /// `mmap` feature is enabled. An extensionless file is loaded as the
+    /// script its shebang or modeline names.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
-        let language = Language::detect(path).ok_or_else(|| super::Error::UnsupportedLanguage {
+        let (language, content) = read_detected(path, || FileContent::read(path))?;
+
+        Ok(Self {
             path: path.to_path_buf(),
-        })?;
-        let content = std::fs::read(path)?;
+            language,
+            content,
+            lines: OnceLock::new(),
+        })
+    }
+
+    /// Load a source file through a content source.
+    ///
+    /// `path` is resolved by the source, so it is usually relative to the
+    /// source's root.
+    pub fn from_source(source: &dyn ContentSource, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let (language, content) = read_detected(path, || Ok(source.read(path)?.into()))?;
 
         Ok(Self {
             path: path.to_path_buf(),
             language,
             content,
+            lines: OnceLock::new(),
         })
     }
 
     /// Create from existing content.
-    pub fn from_content(path: impl Into<PathBuf>, language: Language, content: Vec<u8>) -> Self {
+    pub fn from_content(
+        path: impl Into<PathBuf>,
+        language: Language,
+        content: impl Into<FileContent>,
+    ) -> Self {
         Self {
             path: path.into(),
             language,
-            content,
+            content: content.into(),
+            lines: OnceLock::new(),
         }
     }
 
+    /// Line starts of the content, indexed once per file.
+    pub fn line_index(&self) -> &LineIndex {
+        self.lines.get_or_init(|| LineIndex::new(&self.content))
+    }
+
+    /// Span of the byte range `start..end` of the content.
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        self.line_index().span(&self.content, start, end)
+    }
+
     /// Get content as string (lossy conversion).
     pub fn content_str(&self) -> std::borrow::Cow<'_, str> {
         String::from_utf8_lossy(&self.content)
@@ -63,6 +190,24 @@ impl SourceFile {
     }
 }
 
+/// Read `path` with `read` and detect its language: by extension, checked
+/// before reading, or for an extensionless file, by its shebang or modeline.
+fn read_detected(
+    path: &Path,
+    read: impl FnOnce() -> Result<FileContent>,
+) -> Result<(Language, FileContent)> {
+    let unsupported = || super::Error::UnsupportedLanguage {
+        path: path.to_path_buf(),
+    };
+    if path.extension().is_some() {
+        let language = Language::detect(path).ok_or_else(unsupported)?;
+        return Ok((language, read()?));
+    }
+    let content = read()?;
+    let language = Language::from_script(&content).ok_or_else(unsupported)?;
+    Ok((language, content))
+}
+
 /// Check if a line is a comment (simple heuristic).
 fn is_comment_line(line: &str, lang: Language) -> bool {
     match lang {
@@ -79,6 +224,9 @@ fn is_comment_line(line: &str, lang: Language) -> bool {
         | Language::Php => {
             line.starts_with("//") || line.starts_with("/*") || line.starts_with('*')
         }
+        // Zig only has line comments (`//`, `///`, `//!`)
+        Language::Zig => line.starts_with("//"),
+        Language::Lua => line.starts_with("--"),
         Language::Python | Language::Ruby | Language::Bash => {
             line.starts_with('#') || line.starts_with("'''") || line.starts_with("\"\"\"")
         }
@@ -89,6 +237,45 @@ fn is_comment_line(line: &str, lang: Language) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_source_file_span_uses_its_line_index() {
+        let file = SourceFile::from_content("a.py", Language::Python, &b"x = 1\n# TODO\n"[..]);
+        let span = file.span(8, 12);
+        assert_eq!((span.start_line, span.start_column), (2, 3));
+        assert_eq!(file.line_index(), &LineIndex::new(b"x = 1\n# TODO\n"));
+    }
+
+    #[test]
+    fn test_source_file_from_source() {
+        let source = crate::core::MemorySource::new().with_file("src/app.py", "x = 1\n");
+        let file = SourceFile::from_source(&source, "src/app.py").unwrap();
+
+        assert_eq!(file.path, PathBuf::from("src/app.py"));
+        assert_eq!(file.language, Language::Python);
+        assert_eq!(&file.content[..], b"x = 1\n");
+        assert!(SourceFile::from_source(&source, "notes.txt").is_err());
+    }
+
+    #[test]
+    fn test_source_file_detects_extensionless_script() {
+        let source = crate::core::MemorySource::new()
+            .with_file(
+                "bin/deploy",
+                "#!/usr/bin/env ruby
+puts 1
+",
+            )
+            .with_file(
+                "LICENSE",
+                "MIT License
+",
+            );
+        let file = SourceFile::from_source(&source, "bin/deploy").unwrap();
+
+        assert_eq!(file.language, Language::Ruby);
+        assert!(SourceFile::from_source(&source, "LICENSE").is_err());
+    }
+
     #[test]
     fn test_source_file_from_content() {
         let content = b"fn main() {\n    println!(\"Hello\");\n}\n".to_vec();
@@ -107,4 +294,29 @@ mod tests {
         assert_eq!(file.total_lines(), 3);
         assert_eq!(file.lines_of_code(), 1);
     }
+
+    #[test]
+    fn test_file_content_clones_share_buffer() {
+        let content = FileContent::from(b"let x = 1;\n".to_vec());
+        let clone = content.clone();
+
+        assert_eq!(&clone[..], b"let x = 1;\n");
+        assert_eq!(content.as_ptr(), clone.as_ptr());
+        assert!(!content.is_mapped());
+    }
+
+    #[test]
+    fn test_file_content_read_large_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("big.py");
+        let body = "x = 1\n".repeat(20_000);
+        std::fs::write(&path, &body).unwrap();
+
+        let content = FileContent::read(&path).unwrap();
+        assert_eq!(&content[..], body.as_bytes());
+        assert_eq!(content.is_mapped(), cfg!(feature = "mmap"));
+
+        let file = SourceFile::load(&path).unwrap();
+        assert_eq!(file.total_lines(), 20_000);
+    }
 }
@@ -0,0 +1,181 @@
+//! Source ranges for finding locations.
+//!
+//! Lines and columns are 1-indexed and columns count UTF-16 code units, which
+//! is SARIF's default `columnKind`. LSP positions are the same values minus
+//! one. Byte offsets are kept alongside for tools that slice the source.
+
+use serde::{Deserialize, Serialize};
+
+/// A range in a source file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Span {
+    /// Start line (1-indexed).
+    pub start_line: u32,
+    /// Start column (1-indexed, UTF-16 code units).
+    pub start_column: u32,
+    /// End line (1-indexed).
+    pub end_line: u32,
+    /// End column (1-indexed, UTF-16 code units, exclusive).
+    pub end_column: u32,
+    /// Start byte offset (inclusive).
+    pub start_byte: usize,
+    /// End byte offset (exclusive).
+    pub end_byte: usize,
+}
+
+impl Span {
+    /// Span of a tree-sitter node in `source`.
+    pub fn from_node(node: &tree_sitter::Node<'_>, source: &[u8]) -> Self {
+        let start = node.start_position();
+        let end = node.end_position();
+        Self {
+            start_line: start.row as u32 + 1,
+            start_column: utf16_column(source, node.start_byte(), start.column),
+            end_line: end.row as u32 + 1,
+            end_column: utf16_column(source, node.end_byte(), end.column),
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+        }
+    }
+
+    /// Span of the byte range `start..end` in `source`.
+    ///
+    /// Offsets past the end of `source` are clamped. This indexes `source`
+    /// for the one span; use a [`LineIndex`] (or [`SourceFile::span`]) to
+    /// place many spans in the same file.
+    ///
+    /// [`SourceFile::span`]: super::SourceFile::span
+    pub fn from_bytes(source: &[u8], start: usize, end: usize) -> Self {
+        LineIndex::new(source).span(source, start, end)
+    }
+}
+
+/// Byte offsets at which each line of a source starts, for placing spans by
+/// binary search instead of rescanning the source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineIndex {
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    /// Index the line starts of `source`.
+    pub fn new(source: &[u8]) -> Self {
+        let starts = std::iter::once(0)
+            .chain(
+                source
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| b == b'\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        Self { starts }
+    }
+
+    /// Span of the byte range `start..end` in `source`, which must be the
+    /// bytes this index was built from. Offsets past the end are clamped.
+    pub fn span(&self, source: &[u8], start: usize, end: usize) -> Span {
+        let start = start.min(source.len());
+        let end = end.clamp(start, source.len());
+        let (start_line, start_column) = self.line_and_column(source, start);
+        let (end_line, end_column) = self.line_and_column(source, end);
+        Span {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+            start_byte: start,
+            end_byte: end,
+        }
+    }
+
+    /// 1-indexed line and UTF-16 column of byte `offset`.
+    fn line_and_column(&self, source: &[u8], offset: usize) -> (u32, u32) {
+        let line = self.starts.partition_point(|&start| start <= offset);
+        let line_start = self.starts[line - 1];
+        (
+            line as u32,
+            utf16_column(source, offset, offset - line_start),
+        )
+    }
+}
+
+/// 1-indexed UTF-16 column of byte `offset`, which is `byte_column` bytes
+/// past the start of its line.
+fn utf16_column(source: &[u8], offset: usize, byte_column: usize) -> u32 {
+    let line_prefix = &source[offset - byte_column..offset];
+    if line_prefix.is_ascii() {
+        return byte_column as u32 + 1;
+    }
+    let units: usize = String::from_utf8_lossy(line_prefix)
+        .chars()
+        .map(char::len_utf16)
+        .sum();
+    units as u32 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_ascii() {
+        let source = b"fn a() {}\n// TODO: fix\n";
+        let span = Span::from_bytes(source, 13, 17);
+        assert_eq!(span.start_line, 2);
+        assert_eq!(span.start_column, 4);
+        assert_eq!(span.end_line, 2);
+        assert_eq!(span.end_column, 8);
+        assert_eq!(&source[span.start_byte..span.end_byte], b"TODO");
+    }
+
+    #[test]
+    fn test_from_bytes_counts_utf16_units() {
+        // "é" is 2 bytes / 1 unit; "😀" is 4 bytes / 2 units.
+        let source = "let s = \"é😀\"; // TODO".as_bytes();
+        let start = source.len() - 4;
+        let span = Span::from_bytes(source, start, source.len());
+        assert_eq!(span.start_line, 1);
+        assert_eq!(span.start_column, 19);
+        assert_eq!(span.end_column, 23);
+    }
+
+    #[test]
+    fn test_from_bytes_clamps_out_of_range() {
+        let span = Span::from_bytes(b"abc", 2, 10);
+        assert_eq!(span.end_byte, 3);
+        assert_eq!(span.end_column, 4);
+    }
+
+    #[test]
+    fn test_line_index_places_spans_on_later_lines() {
+        let source = "a\n\nbé = 1\n".as_bytes();
+        let index = LineIndex::new(source);
+        let span = index.span(source, 4, 8);
+        assert_eq!((span.start_line, span.start_column), (3, 2));
+        assert_eq!((span.end_line, span.end_column), (3, 5));
+        assert_eq!(index.span(source, 2, 2).start_line, 2);
+        assert_eq!(index.span(source, source.len(), 99).start_line, 4);
+        assert_eq!(span, Span::from_bytes(source, 4, 8));
+    }
+
+    #[test]
+    fn test_from_node_matches_from_bytes() {
+        let source = "// ünïcode\nfunction greet() { return 1; }\n".as_bytes();
+        let result = crate::parser::Parser::new()
+            .parse(
+                source,
+                crate::core::Language::JavaScript,
+                std::path::Path::new("a.js"),
+            )
+            .unwrap();
+        let func = result.root_node().named_child(1).unwrap();
+        let span = Span::from_node(&func, source);
+        assert_eq!(
+            span,
+            Span::from_bytes(source, func.start_byte(), func.end_byte())
+        );
+        assert_eq!((span.start_line, span.start_column), (2, 1));
+        assert_eq!((span.end_line, span.end_column), (2, 31));
+    }
+}
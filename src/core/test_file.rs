@@ -0,0 +1,142 @@
+//! Test vs production file classification.
+//!
+//! Analyzers that treat test code differently (dead code, temporal coupling,
+//! cohesion, repo maps) share this classification so they agree on what
+//! counts as a test file.
+
+use std::path::Path;
+
+/// Directory names whose contents are tests, fixtures, or mocks.
+const TEST_DIRS: &[&str] = &[
+    "test",
+    "tests",
+    "spec",
+    "specs",
+    "__tests__",
+    "__mocks__",
+    "test_helpers",
+    "testdata",
+    "fixtures",
+];
+
+/// Extensions whose test classes follow the `FooTest` / `FooTests` naming
+/// convention (JUnit, xUnit, PHPUnit, Spock).
+const CLASS_SUFFIX_EXTENSIONS: &[&str] = &["java", "kt", "scala", "groovy", "cs", "php"];
+
+/// Returns true if `path` is a test file according to its language's
+/// conventions.
+///
+/// Directory conventions: `test/`, `tests/`, `spec/`, `__tests__/`,
+/// `testdata/`, `fixtures/`, and Maven's `src/test/`.
+///
+/// Filename conventions:
+/// - Go: `*_test.go`
+/// - Python: `test_*.py`, `*_test.py`, `conftest.py`
+/// - TypeScript/JavaScript: `*.test.ts`, `*.spec.js`, ...
+/// - Ruby: `*_spec.rb`, `*_test.rb`
+/// - Java/Kotlin/C#/PHP: `FooTest.java`, `FooTests.cs`, `FooSpec.groovy`
+/// - C/C++: `test_*.c`, `*_test.cpp`
+/// - Bash: `*.bats`, `*_test.sh`
+///
+/// Rust unit tests live in `#[cfg(test)]` modules inside production files, so
+/// only integration tests under `tests/` are classified here.
+pub fn is_test_file(path: impl AsRef<Path>) -> bool {
+    // Normalise to forward slashes so Windows paths are handled.
+    let normalized = path.as_ref().to_string_lossy().replace('\\', "/");
+    let lower = normalized.to_lowercase();
+
+    let parts: Vec<&str> = lower.split('/').collect();
+    let Some((&filename, dirs)) = parts.split_last() else {
+        return false;
+    };
+
+    if dirs.iter().any(|dir| TEST_DIRS.contains(dir)) {
+        return true;
+    }
+
+    // _test.go, _test.py, _spec.rb, etc.
+    if filename.contains("_test.") || filename.contains("_spec.") {
+        return true;
+    }
+    // test_*.py, test_*.c
+    if filename.starts_with("test_") || filename == "conftest.py" {
+        return true;
+    }
+    if filename.ends_with(".bats") {
+        return true;
+    }
+
+    // *.test.ts, *.spec.js, etc.
+    let dot_parts: Vec<&str> = filename.split('.').collect();
+    if dot_parts.len() >= 3 {
+        let second_last = dot_parts[dot_parts.len() - 2];
+        if second_last == "test" || second_last == "spec" {
+            return true;
+        }
+    }
+
+    // FooTest.java, FooTests.cs: case-sensitive so `Contest.java` is not a test.
+    let original_name = normalized.rsplit('/').next().unwrap_or_default();
+    if let Some((stem, ext)) = original_name.rsplit_once('.') {
+        if CLASS_SUFFIX_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+            && stem.len() > 4
+            && (stem.ends_with("Test") || stem.ends_with("Tests") || stem.ends_with("Spec"))
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_conventions() {
+        assert!(is_test_file("tests/integration.rs"));
+        assert!(is_test_file("src/test/java/com/example/Foo.java"));
+        assert!(is_test_file("spec/models/user.rb"));
+        assert!(is_test_file("src/__tests__/App.jsx"));
+        assert!(is_test_file("pkg/parser/testdata/input.go"));
+        assert!(is_test_file("fixtures/data.json"));
+    }
+
+    #[test]
+    fn test_filename_conventions_per_language() {
+        assert!(is_test_file("pkg/auth/handler_test.go"));
+        assert!(is_test_file("app/test_models.py"));
+        assert!(is_test_file("app/models_test.py"));
+        assert!(is_test_file("conftest.py"));
+        assert!(is_test_file("src/Button.test.tsx"));
+        assert!(is_test_file("lib/api.spec.js"));
+        assert!(is_test_file("lib/user_spec.rb"));
+        assert!(is_test_file("src/main/java/FooTest.java"));
+        assert!(is_test_file("Services/AuthTests.cs"));
+        assert!(is_test_file("src/ServiceTest.php"));
+        assert!(is_test_file("src/UserSpec.groovy"));
+        assert!(is_test_file("src/test_parser.c"));
+        assert!(is_test_file("scripts/install.bats"));
+    }
+
+    #[test]
+    fn test_production_files() {
+        assert!(!is_test_file("src/main.rs"));
+        assert!(!is_test_file("src/analyzers/temporal.rs"));
+        assert!(!is_test_file("cmd/server/main.go"));
+        assert!(!is_test_file("lib/auth.py"));
+        assert!(!is_test_file("src/components/Button.tsx"));
+        assert!(!is_test_file("src/main/java/com/example/Contest.java"));
+        assert!(!is_test_file("src/Test.java"));
+        assert!(!is_test_file("src/attestation.rs"));
+        assert!(!is_test_file("scripts/deploy.sh"));
+    }
+
+    #[test]
+    fn test_windows_separators() {
+        assert!(is_test_file("src\\tests\\foo.rs"));
+        assert!(is_test_file("src\\foo.test.tsx"));
+        assert!(!is_test_file("src\\foo.tsx"));
+    }
+}
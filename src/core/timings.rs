@@ -0,0 +1,300 @@
+//! Recorded analyzer run times and deadline scheduling.
+//!
+//! `omen all` and `omen report generate` record how long each analyzer took
+//! under `.omen/cache/timings.json`. With `--deadline`, the next run orders
+//! analyzers cheapest first and skips those expected to overrun.
+//! `--estimate` predicts a run from the same timings.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Where timings are kept, relative to the analyzed root.
+pub const TIMINGS_PATH: &str = ".omen/cache/timings.json";
+
+/// Expected run time per analyzer, in seconds.
+pub struct Timings {
+    path: Option<PathBuf>,
+    recorded: Mutex<Recorded>,
+}
+
+/// What is kept on disk.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Recorded {
+    /// Files analyzed by the latest run, to scale timings to another scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    files: Option<usize>,
+    seconds: BTreeMap<String, f64>,
+}
+
+impl Recorded {
+    /// Parse timings, including the bare analyzer-to-seconds map written
+    /// before file counts were kept.
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok().or_else(|| {
+            serde_json::from_slice(bytes).ok().map(|seconds| Self {
+                files: None,
+                seconds,
+            })
+        })
+    }
+}
+
+impl Timings {
+    /// Timings that are never persisted.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            recorded: Mutex::new(Recorded::default()),
+        }
+    }
+
+    /// Timings persisted at `path`. A missing or unreadable file starts empty.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let recorded = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| Recorded::parse(&bytes))
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            recorded: Mutex::new(recorded),
+        }
+    }
+
+    /// Expected run time of `analyzer`, if it has run before.
+    pub fn expected(&self, analyzer: &str) -> Option<Duration> {
+        self.lock()
+            .seconds
+            .get(analyzer)
+            .map(|&secs| Duration::from_secs_f64(secs))
+    }
+
+    /// Expected run time of `analyzer` over `files` files: its recorded
+    /// time, scaled by file count when the latest run's count is known.
+    pub fn expected_for(&self, analyzer: &str, files: usize) -> Option<Duration> {
+        let expected = self.expected(analyzer)?;
+        match self.files() {
+            Some(recorded) if recorded > 0 => {
+                Some(expected.mul_f64(files as f64 / recorded as f64))
+            }
+            _ => Some(expected),
+        }
+    }
+
+    /// Files analyzed by the latest recorded run.
+    pub fn files(&self) -> Option<usize> {
+        self.lock().files
+    }
+
+    /// Note how many files the run being recorded analyzes.
+    pub fn set_files(&self, files: usize) {
+        self.lock().files = Some(files);
+    }
+
+    /// Fold a run of `analyzer` into its expected time. Older runs fade out
+    /// so the estimate follows the repository as it grows.
+    pub fn record(&self, analyzer: &str, elapsed: Duration) {
+        let sample = elapsed.as_secs_f64();
+        self.lock()
+            .seconds
+            .entry(analyzer.to_string())
+            .and_modify(|secs| *secs = (*secs + sample) / 2.0)
+            .or_insert(sample);
+    }
+
+    /// Sort `jobs` cheapest first by the analyzer `name` runs. Analyzers
+    /// without a recorded time go last, in their original order.
+    pub fn order<T>(&self, jobs: &mut [T], name: impl Fn(&T) -> &str) {
+        // `None` sorts before `Some`, hence the flag first.
+        jobs.sort_by_cached_key(|job| {
+            let expected = self.expected(name(job));
+            (expected.is_none(), expected)
+        });
+    }
+
+    /// Best effort: timings that can't be written are just lost.
+    pub fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            if std::fs::create_dir_all(dir).is_err() {
+                return;
+            }
+        }
+        if let Ok(json) = serde_json::to_vec_pretty(&*self.lock()) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Recorded> {
+        self.recorded.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Wall-clock budget for a run.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    end: Instant,
+}
+
+impl Deadline {
+    /// A deadline `limit` from now.
+    pub fn after(limit: Duration) -> Self {
+        Self {
+            end: Instant::now() + limit,
+        }
+    }
+
+    /// Time left before the deadline.
+    pub fn remaining(&self) -> Duration {
+        self.end.saturating_duration_since(Instant::now())
+    }
+
+    /// Whether an analyzer expected to take `expected` should start now.
+    /// Returns the time it may use, or why it is skipped. Analyzers that
+    /// have never run start while any time is left.
+    pub fn admit(&self, expected: Option<Duration>) -> Result<Duration, String> {
+        let remaining = self.remaining();
+        if remaining.is_zero() {
+            return Err("deadline reached".to_string());
+        }
+        match expected {
+            Some(expected) if expected > remaining => Err(format!(
+                "expected to take {:.1}s, {:.1}s left before the deadline",
+                expected.as_secs_f64(),
+                remaining.as_secs_f64()
+            )),
+            _ => Ok(remaining),
+        }
+    }
+}
+
+/// Parse a deadline such as `60s`, `2m`, `1h` or a bare number of seconds.
+pub fn parse_deadline(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration `{text}`: expected e.g. 60s, 2m or 1h"))?;
+    let scale = match unit.trim() {
+        "" | "s" | "sec" | "secs" => 1.0,
+        "m" | "min" | "mins" => 60.0,
+        "h" | "hr" | "hrs" => 3600.0,
+        other => return Err(format!("invalid duration unit `{other}`: use s, m or h")),
+    };
+    let secs = value * scale;
+    if !(secs > 0.0 && secs.is_finite()) {
+        return Err(format!("invalid duration `{text}`: must be greater than 0"));
+    }
+    Ok(Duration::from_secs_f64(secs))
+}
+
+/// Timings file for the analysis of `root`.
+pub fn timings_path(root: &Path) -> PathBuf {
+    root.join(TIMINGS_PATH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_cheapest_first_unknown_last() {
+        let timings = Timings::in_memory();
+        timings.record("churn", Duration::from_secs(30));
+        timings.record("satd", Duration::from_millis(200));
+
+        let mut names = ["complexity", "churn", "docker", "satd"];
+        timings.order(&mut names, |&name| name);
+        assert_eq!(names, ["satd", "churn", "complexity", "docker"]);
+    }
+
+    #[test]
+    fn test_record_averages_runs() {
+        let timings = Timings::in_memory();
+        timings.record("churn", Duration::from_secs(10));
+        timings.record("churn", Duration::from_secs(20));
+        assert_eq!(timings.expected("churn"), Some(Duration::from_secs(15)));
+        assert_eq!(timings.expected("satd"), None);
+    }
+
+    #[test]
+    fn test_timings_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = timings_path(temp.path());
+
+        let timings = Timings::load(&path);
+        timings.record("satd", Duration::from_secs(2));
+        timings.save();
+
+        let reloaded = Timings::load(&path);
+        assert_eq!(reloaded.expected("satd"), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_expected_for_scales_by_file_count() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = timings_path(temp.path());
+
+        let timings = Timings::load(&path);
+        timings.record("satd", Duration::from_secs(4));
+        assert_eq!(
+            timings.expected_for("satd", 50),
+            Some(Duration::from_secs(4)),
+            "unscaled without a recorded file count"
+        );
+        timings.set_files(100);
+        timings.save();
+
+        let reloaded = Timings::load(&path);
+        assert_eq!(reloaded.files(), Some(100));
+        assert_eq!(
+            reloaded.expected_for("satd", 50),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(reloaded.expected_for("churn", 50), None);
+    }
+
+    #[test]
+    fn test_load_reads_timings_without_file_count() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("timings.json");
+        std::fs::write(&path, r#"{"churn": 3.0, "satd": 0.5}"#).unwrap();
+
+        let timings = Timings::load(&path);
+        assert_eq!(timings.expected("churn"), Some(Duration::from_secs(3)));
+        assert_eq!(timings.files(), None);
+    }
+
+    #[test]
+    fn test_deadline_admits_what_fits() {
+        let deadline = Deadline::after(Duration::from_secs(60));
+        assert!(deadline.admit(Some(Duration::from_secs(1))).is_ok());
+        assert!(deadline.admit(None).is_ok(), "unknown cost gets a chance");
+        let err = deadline.admit(Some(Duration::from_secs(600))).unwrap_err();
+        assert!(err.contains("expected to take 600.0s"), "got: {err}");
+
+        let passed = Deadline::after(Duration::ZERO);
+        assert_eq!(passed.admit(None).unwrap_err(), "deadline reached");
+    }
+
+    #[test]
+    fn test_parse_deadline() {
+        assert_eq!(parse_deadline("60s"), Ok(Duration::from_secs(60)));
+        assert_eq!(parse_deadline("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_deadline("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_deadline("45"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_deadline("1.5m"), Ok(Duration::from_secs(90)));
+        assert!(parse_deadline("0s").is_err());
+        assert!(parse_deadline("soon").is_err());
+        assert!(parse_deadline("5d").is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Warm starts from a previous report run.
+//!
+//! `omen report generate --previous <dir>` points at the data directory of an
+//! earlier run. When that run analyzed an ancestor of the current commit with
+//! the same config and time window, analyzers that work file by file reuse
+//! its results for files no commit has touched since, and only recompute the
+//! rest. Ownership reuses its previous result directly. Churn and duplicates
+//! reuse per-file state saved next to their results (see [`state_path`]):
+//! churn the numstat of each commit in its window, dropping commits that
+//! have aged out of it, and duplicates the fragment hashes of each file.
+//! Every other analyzer runs in full.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{Error, Result};
+use crate::git::GitRepo;
+use crate::manifest::Manifest;
+
+/// A previous run's results and the files changed since.
+#[derive(Debug)]
+pub struct WarmStart {
+    dir: PathBuf,
+    root: PathBuf,
+    /// Analyzers whose version is unchanged since the previous run.
+    analyzers: HashSet<String>,
+    /// Paths touched since the previous run, relative to the repository root.
+    changed: HashSet<PathBuf>,
+}
+
+impl WarmStart {
+    /// Load the run saved in `dir` for the run of `repo` described by
+    /// `current` over `since`. Fails when the previous results can't be
+    /// trusted for this run: a different config or window, a dirty or
+    /// unknown commit, or a commit that isn't an ancestor of the current one.
+    pub fn load(dir: &Path, repo: &GitRepo, current: &Manifest, since: &str) -> Result<Self> {
+        let metadata_path = dir.join("metadata.json");
+        let metadata: Value =
+            serde_json::from_slice(&std::fs::read(&metadata_path).map_err(|e| {
+                Error::config(format!("cannot read {}: {e}", metadata_path.display()))
+            })?)?;
+        let previous: Manifest = metadata
+            .get("manifest")
+            .and_then(|m| serde_json::from_value(m.clone()).ok())
+            .ok_or_else(|| Error::config("previous run has no manifest"))?;
+
+        if metadata.get("since").and_then(Value::as_str) != Some(since) {
+            return Err(Error::config(
+                "previous run covered a different time window",
+            ));
+        }
+        if previous.config_hash != current.config_hash {
+            return Err(Error::config("config changed since the previous run"));
+        }
+        let (Some(previous_sha), Some(current_sha)) = (&previous.git_sha, &current.git_sha) else {
+            return Err(Error::git("warm start needs both runs to be at a commit"));
+        };
+        if previous.dirty {
+            return Err(Error::git("previous run had uncommitted changes"));
+        }
+        if repo.merge_base(previous_sha, current_sha)? != *previous_sha {
+            return Err(Error::git(format!(
+                "previous commit {previous_sha} is not an ancestor of {current_sha}"
+            )));
+        }
+
+        let mut changed = repo.touched_paths(previous_sha)?;
+        if current.dirty {
+            changed.extend(repo.dirty_paths()?);
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            root: repo
+                .root()
+                .canonicalize()
+                .unwrap_or_else(|_| repo.root().to_path_buf()),
+            analyzers: unchanged_versions(&previous.analyzer_versions, &current.analyzer_versions),
+            changed,
+        })
+    }
+
+    /// Whether `path` may differ from the previous run. `path` may be
+    /// relative to the repository root or absolute.
+    pub fn changed(&self, path: &Path) -> bool {
+        self.changed
+            .contains(path.strip_prefix(&self.root).unwrap_or(path))
+    }
+
+    /// Paths touched since the previous run, relative to the repository root.
+    pub fn changed_paths(&self) -> impl Iterator<Item = &Path> {
+        self.changed.iter().map(PathBuf::as_path)
+    }
+
+    /// The previous result of `analyzer`, unless it was skipped, failed, or
+    /// came from a different analyzer version.
+    pub fn previous<T: DeserializeOwned>(&self, analyzer: &str) -> Option<T> {
+        self.read(analyzer, &self.dir.join(format!("{analyzer}.json")))
+    }
+
+    /// The state `analyzer` saved with [`save_state`] in the previous run,
+    /// under the same conditions as [`WarmStart::previous`].
+    pub fn previous_state<T: DeserializeOwned>(&self, analyzer: &str) -> Option<T> {
+        self.read(analyzer, &state_path(&self.dir, analyzer))
+    }
+
+    fn read<T: DeserializeOwned>(&self, analyzer: &str, path: &Path) -> Option<T> {
+        if !self.analyzers.contains(analyzer) {
+            return None;
+        }
+        let bytes = std::fs::read(path).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// Where `analyzer` keeps the per-file state a later run warm-starts from,
+/// next to its result in the data directory `dir`. The state belongs to the
+/// run that wrote the directory's `metadata.json`, so it is removed whenever
+/// that run doesn't produce a result for the analyzer.
+pub fn state_path(dir: &Path, analyzer: &str) -> PathBuf {
+    dir.join(format!("{analyzer}.state.json"))
+}
+
+/// Save `analyzer`'s per-file state to the data directory `dir`.
+pub fn save_state<T: Serialize>(dir: &Path, analyzer: &str, state: &T) -> Result<()> {
+    std::fs::write(state_path(dir, analyzer), serde_json::to_vec(state)?)?;
+    Ok(())
+}
+
+fn unchanged_versions(
+    previous: &BTreeMap<String, u32>,
+    current: &BTreeMap<String, u32>,
+) -> HashSet<String> {
+    current
+        .iter()
+        .filter(|(name, version)| previous.get(*name) == Some(*version))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::FileSet;
+    use crate::test_support::{git, init_repo};
+    use tempfile::TempDir;
+
+    fn commit(dir: &Path, file: &str, content: &str) {
+        std::fs::write(dir.join(file), content).unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", file]);
+    }
+
+    fn manifest(root: &Path, config: &Config) -> Manifest {
+        let files = FileSet::from_path(root, config).unwrap();
+        Manifest::build(root, &files, config).unwrap()
+    }
+
+    /// A repository with `a.rs` and `b.rs` and a saved run at its first
+    /// commit, for `config`.
+    fn previous_run(config: &Config) -> (TempDir, TempDir) {
+        let repo = TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::write(repo.path().join("a.rs"), "fn a() {}\n").unwrap();
+        commit(repo.path(), "b.rs", "fn b() {}\n");
+
+        let data = TempDir::new().unwrap();
+        let manifest = manifest(repo.path(), config);
+        let metadata = serde_json::json!({ "since": "1y", "manifest": manifest });
+        std::fs::write(data.path().join("metadata.json"), metadata.to_string()).unwrap();
+        std::fs::write(data.path().join("churn.json"), r#"{"files": 3}"#).unwrap();
+        (repo, data)
+    }
+
+    #[test]
+    fn test_changed_files_since_previous_run() {
+        let config = Config::default();
+        let (repo, data) = previous_run(&config);
+        commit(repo.path(), "b.rs", "fn b() { a() }\n");
+        let git_repo = GitRepo::open(repo.path()).unwrap();
+
+        let current = manifest(repo.path(), &config);
+
+        let warm = WarmStart::load(data.path(), &git_repo, &current, "1y").unwrap();
+
+        assert!(warm.changed(Path::new("b.rs")));
+        assert!(!warm.changed(Path::new("a.rs")));
+        let absolute = repo.path().canonicalize().unwrap().join("a.rs");
+        assert!(!warm.changed(&absolute));
+        assert_eq!(
+            warm.previous::<Value>("churn"),
+            Some(serde_json::json!({"files": 3}))
+        );
+        assert_eq!(warm.previous::<Value>("ownership"), None);
+        assert_eq!(
+            warm.changed_paths().collect::<Vec<_>>(),
+            vec![Path::new("b.rs")]
+        );
+    }
+
+    #[test]
+    fn test_state_saved_next_to_results() {
+        let config = Config::default();
+        let (repo, data) = previous_run(&config);
+        save_state(data.path(), "churn", &vec![1, 2]).unwrap();
+        assert!(data.path().join("churn.state.json").is_file());
+        let git_repo = GitRepo::open(repo.path()).unwrap();
+        let current = manifest(repo.path(), &config);
+
+        let warm = WarmStart::load(data.path(), &git_repo, &current, "1y").unwrap();
+
+        assert_eq!(warm.previous_state::<Vec<u32>>("churn"), Some(vec![1, 2]));
+        assert_eq!(warm.previous_state::<Vec<u32>>("duplicates"), None);
+    }
+
+    #[test]
+    fn test_rejects_different_config_or_window() {
+        let config = Config::default();
+        let (repo, data) = previous_run(&config);
+        let git_repo = GitRepo::open(repo.path()).unwrap();
+
+        let current = manifest(repo.path(), &config);
+        let err = WarmStart::load(data.path(), &git_repo, &current, "6m").unwrap_err();
+        assert!(err.to_string().contains("time window"));
+
+        let mut changed = Config::default();
+        changed.git.blame = !changed.git.blame;
+        let current = manifest(repo.path(), &changed);
+        let err = WarmStart::load(data.path(), &git_repo, &current, "1y").unwrap_err();
+        assert!(err.to_string().contains("config changed"));
+    }
+}
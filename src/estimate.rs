@@ -0,0 +1,312 @@
+//! Dry-run cost estimates for the expensive commands.
+//!
+//! `--estimate` on `omen all`, `omen report generate`, and `omen mutation`
+//! predicts the run without running an analyzer or a test. Analyzers that
+//! ran before are estimated from their recorded time (see
+//! [`crate::core::timings`]), scaled to the current file count; the rest
+//! are guessed from the file count alone.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::timings::Timings;
+
+/// Seconds per file guessed for an analyzer that has never run.
+const GUESS_SECS_PER_FILE: f64 = 0.002;
+
+/// Seconds per test run guessed for mutation testing that has never run.
+const GUESS_SECS_PER_MUTANT: f64 = 5.0;
+
+/// Timing key for one mutant's test run, recorded by `omen mutation`.
+pub const MUTANT_TIMING: &str = "mutant";
+
+/// Analyzer names in phases. Phases run one after another; the groups in a
+/// phase run concurrently, and the analyzers in a group one at a time.
+type Plan = &'static [&'static [&'static [&'static str]]];
+
+/// `omen all` group A: file-based analyzers (no git dependency).
+pub const ALL_FILE_JOBS: &[&str] = &[
+    "complexity",
+    "satd",
+    "deadcode",
+    "cohesion",
+    "graph",
+    "repomap",
+    "smells",
+    "flags",
+    "duplicates",
+    "pipeline",
+    "docker",
+    "docdrift",
+];
+
+/// `omen all` group B: git-based analyzers.
+pub const ALL_GIT_JOBS: &[&str] = &["churn", "temporal", "ownership", "commits"];
+
+/// `omen all` group C: analyzers that internally depend on both file and
+/// git data.
+pub const ALL_COMBINED_JOBS: &[&str] = &[
+    "hotspot", "tdg", "hidden", "defect", "changes", "api", "score",
+];
+
+/// `omen all`: groups A and B side by side, then group C.
+pub const ALL_PLAN: Plan = &[&[ALL_FILE_JOBS, ALL_GIT_JOBS], &[ALL_COMBINED_JOBS]];
+
+/// `omen report generate` group A: file-based analyzers. Each is saved as
+/// `<name>.json`.
+pub const REPORT_FILE_JOBS: &[&str] = &[
+    "complexity",
+    "satd",
+    "deadcode",
+    "duplicates",
+    "cohesion",
+    "repomap",
+    "pipeline",
+    "docker",
+];
+
+/// `omen report generate` group B: git-heavy analyzers.
+pub const REPORT_GIT_JOBS: &[&str] = &["ownership", "churn", "temporal", "changes"];
+
+/// `omen report generate` group C: mixed file and git analyzers. The
+/// hotspot analyzer is saved as `hotspots`.
+pub const REPORT_MIXED_JOBS: &[&str] = &[
+    "graph", "smells", "flags", "defect", "hotspots", "tdg", "hidden",
+];
+
+/// `omen report generate`: the three groups side by side, then the score
+/// trend. Risk and score are read back from the data files and cost next
+/// to nothing.
+pub const REPORT_PLAN: Plan = &[
+    &[REPORT_FILE_JOBS, REPORT_GIT_JOBS, REPORT_MIXED_JOBS],
+    &[&["trend"]],
+];
+
+/// Command being estimated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Command {
+    All,
+    Report,
+    Mutation,
+}
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::All => write!(f, "all"),
+            Command::Report => write!(f, "report generate"),
+            Command::Mutation => write!(f, "mutation"),
+        }
+    }
+}
+
+/// Where an estimate comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Basis {
+    /// Recorded by earlier runs.
+    History,
+    /// Guessed from the file or mutant count.
+    Size,
+}
+
+/// Predicted cost of one command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Estimate {
+    pub command: Command,
+    /// Files the command would analyze.
+    pub files: usize,
+    /// Mutants that would be tested (mutation only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mutants: Option<usize>,
+    /// Predicted wall-clock time.
+    pub seconds: f64,
+    pub analyzers: Vec<AnalyzerEstimate>,
+}
+
+/// Predicted cost of one analyzer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzerEstimate {
+    pub name: String,
+    pub seconds: f64,
+    pub basis: Basis,
+}
+
+/// Estimate `omen all` over `files` files, leaving out `skip`.
+pub fn all(files: usize, skip: &[&str], timings: &Timings) -> Estimate {
+    from_plan(Command::All, ALL_PLAN, files, skip, timings)
+}
+
+/// Estimate `omen report generate` over `files` files, leaving out `skip`.
+pub fn report(files: usize, skip: &[&str], timings: &Timings) -> Estimate {
+    from_plan(Command::Report, REPORT_PLAN, files, skip, timings)
+}
+
+/// Estimate `omen mutation`: the test suite runs `baseline_runs` times
+/// unmutated, then once per mutant.
+pub fn mutation(files: usize, mutants: usize, baseline_runs: usize, timings: &Timings) -> Estimate {
+    let (per_run, basis) = match timings.expected(MUTANT_TIMING) {
+        Some(expected) => (expected.as_secs_f64(), Basis::History),
+        None => (GUESS_SECS_PER_MUTANT, Basis::Size),
+    };
+    let seconds = per_run * (mutants + baseline_runs) as f64;
+    Estimate {
+        command: Command::Mutation,
+        files,
+        mutants: Some(mutants),
+        seconds,
+        analyzers: vec![AnalyzerEstimate {
+            name: "mutation".to_string(),
+            seconds,
+            basis,
+        }],
+    }
+}
+
+fn from_plan(
+    command: Command,
+    plan: Plan,
+    files: usize,
+    skip: &[&str],
+    timings: &Timings,
+) -> Estimate {
+    let mut analyzers = Vec::new();
+    let mut seconds = 0.0;
+    for phase in plan {
+        let mut slowest_group: f64 = 0.0;
+        for group in *phase {
+            let mut group_seconds = 0.0;
+            for &name in group.iter().filter(|name| !skip.contains(*name)) {
+                let estimate = analyzer(name, files, timings);
+                group_seconds += estimate.seconds;
+                analyzers.push(estimate);
+            }
+            slowest_group = slowest_group.max(group_seconds);
+        }
+        seconds += slowest_group;
+    }
+    Estimate {
+        command,
+        files,
+        mutants: None,
+        seconds,
+        analyzers,
+    }
+}
+
+fn analyzer(name: &str, files: usize, timings: &Timings) -> AnalyzerEstimate {
+    let (seconds, basis) = match timings.expected_for(name, files) {
+        Some(expected) => (expected.as_secs_f64(), Basis::History),
+        None => (files as f64 * GUESS_SECS_PER_FILE, Basis::Size),
+    };
+    AnalyzerEstimate {
+        name: name.to_string(),
+        seconds,
+        basis,
+    }
+}
+
+impl Estimate {
+    /// Render the estimate as markdown.
+    pub fn render_markdown(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str(&format!("# Estimate: omen {}\n\n", self.command));
+        out.push_str(&format!("**Files**: {}\n\n", self.files));
+        if let Some(mutants) = self.mutants {
+            out.push_str(&format!("**Mutants**: {mutants}\n\n"));
+        }
+        out.push_str(&format!("**Predicted time**: {:.1}s\n\n", self.seconds));
+
+        out.push_str("| Analyzer | Seconds | Basis |\n");
+        out.push_str("|----------|---------|-------|\n");
+        for analyzer in &self.analyzers {
+            let basis = match analyzer.basis {
+                Basis::History => "earlier runs",
+                Basis::Size => "size guess",
+            };
+            out.push_str(&format!(
+                "| {} | {:.1} | {} |\n",
+                analyzer.name, analyzer.seconds, basis
+            ));
+        }
+
+        if self.analyzers.iter().any(|a| a.basis == Basis::Size) {
+            out.push_str(
+                "\nSize guesses are rough; estimates sharpen after one full run \
+                 records real timings.\n",
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_all_overlaps_file_and_git_groups() {
+        let timings = Timings::in_memory();
+        timings.set_files(100);
+        timings.record("complexity", Duration::from_secs(4));
+        timings.record("churn", Duration::from_secs(10));
+        timings.record("score", Duration::from_secs(1));
+        let skip: Vec<&str> = ALL_PLAN
+            .iter()
+            .flat_map(|phase| phase.iter().copied().flatten().copied())
+            .filter(|name| !["complexity", "churn", "score"].contains(name))
+            .collect();
+
+        let estimate = all(100, &skip, &timings);
+
+        let names: Vec<_> = estimate.analyzers.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["complexity", "churn", "score"]);
+        assert!(estimate.analyzers.iter().all(|a| a.basis == Basis::History));
+        // complexity and churn run side by side, score after both.
+        assert!((estimate.seconds - 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_plans_name_runnable_analyzers() {
+        let names = [ALL_PLAN, REPORT_PLAN]
+            .into_iter()
+            .flat_map(|plan| plan.iter().copied().flatten().copied().flatten());
+        for name in names {
+            assert!(
+                crate::core::AnalyzerResult::NAMES.contains(name)
+                    || ["hotspots", "trend"].contains(name),
+                "unknown analyzer {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unrecorded_analyzers_are_guessed_from_size() {
+        let timings = Timings::in_memory();
+
+        let estimate = report(1000, &["trend"], &timings);
+
+        assert_eq!(estimate.command, Command::Report);
+        assert!(estimate.analyzers.iter().all(|a| a.basis == Basis::Size));
+        assert!(!estimate.analyzers.iter().any(|a| a.name == "trend"));
+        assert!(estimate.seconds > 0.0);
+        assert!(estimate.render_markdown().contains("size guess"));
+    }
+
+    #[test]
+    fn test_mutation_counts_baseline_and_mutants() {
+        let timings = Timings::in_memory();
+        timings.record(MUTANT_TIMING, Duration::from_secs(2));
+
+        let estimate = mutation(3, 10, 2, &timings);
+
+        assert_eq!(estimate.mutants, Some(10));
+        assert!((estimate.seconds - 24.0).abs() < 1e-9);
+        assert_eq!(estimate.analyzers[0].basis, Basis::History);
+        let markdown = estimate.render_markdown();
+        assert!(markdown.contains("# Estimate: omen mutation"));
+        assert!(markdown.contains("**Mutants**: 10"));
+    }
+}
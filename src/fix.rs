@@ -0,0 +1,1270 @@
+//! Automatic fixes for simple findings.
+//!
+//! `omen fix` turns findings that need no judgement into edits:
+//!
+//! - private functions the dead code analyzer is certain about (unreachable
+//!   and unreferenced, or reported by rustc), together with the doc
+//!   comments, attributes and decorators directly above them and the
+//!   imports only they used;
+//! - stale feature flags that are fully rolled out, as LaunchDarkly or
+//!   Unleash report (see [`crate::rollout`]) or as listed under
+//!   `[feature_flags] rolled_out`, where the flag check is the whole
+//!   condition of an `if`: the statement is replaced by the code it guards
+//!   and any `else` branch is dropped;
+//! - SATD comments whose referenced issues (`#123`) are all closed.
+//!
+//! Every edit replaces whole lines, so a plan previews as a unified diff.
+//! Findings that do not fit one of these shapes are reported as skipped
+//! rather than guessed at.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+
+use crate::analyzers::deadcode::{self, DeadCodeItem};
+use crate::analyzers::{flags, satd};
+use crate::core::{Language, Result, Span};
+use crate::parser::{extract_functions, extract_imports, ParseResult, Parser};
+
+/// Lines of context around each hunk of the diff.
+const CONTEXT: usize = 3;
+
+/// Node kinds of `if` statements and expressions across grammars.
+const IF_KINDS: &[&str] = &["if_statement", "if_expression", "if"];
+
+/// Node kinds that only wrap a condition.
+const CONDITION_WRAPPERS: &[&str] = &["parenthesized_expression", "condition_clause"];
+
+/// Node kinds of function and method calls across grammars.
+const CALL_KINDS: &[&str] = &[
+    "call_expression",
+    "call",
+    "method_invocation",
+    "invocation_expression",
+    "function_call_expression",
+    "member_call_expression",
+    "scoped_call_expression",
+];
+
+/// What a fix does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// Delete a private function nothing reaches.
+    DeadCode,
+    /// Replace a rolled-out flag check with the code it guards.
+    StaleFlag,
+    /// Delete a SATD comment whose issues are closed.
+    ResolvedSatd,
+}
+
+/// One fix in a plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fix {
+    pub kind: Kind,
+    pub file: String,
+    /// First line replaced (1-indexed).
+    pub line: u32,
+    /// Last line replaced (1-indexed).
+    pub end_line: u32,
+    pub description: String,
+}
+
+/// A finding that was left alone, and why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skipped {
+    pub kind: Kind,
+    pub file: String,
+    pub line: u32,
+    pub reason: String,
+}
+
+/// Fixes ready to preview or apply.
+#[derive(Debug, Clone, Serialize)]
+pub struct Plan {
+    pub fixes: Vec<Fix>,
+    pub skipped: Vec<Skipped>,
+    pub summary: Summary,
+    #[serde(skip)]
+    patches: Vec<FilePatch>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Summary {
+    pub fixes: usize,
+    pub files: usize,
+    pub skipped: usize,
+    pub by_kind: BTreeMap<Kind, usize>,
+}
+
+impl Plan {
+    /// The plan as a unified diff against the current files.
+    pub fn diff(&self) -> String {
+        let mut out = String::new();
+        for patch in &self.patches {
+            patch.diff(&mut out);
+        }
+        out
+    }
+
+    /// Write the fixed files.
+    pub fn apply(&self) -> Result<()> {
+        for patch in &self.patches {
+            std::fs::write(&patch.path, patch.patched())?;
+        }
+        Ok(())
+    }
+
+    /// Write one unified diff per fixed file into `dir`, mirroring the
+    /// file's path with a `.patch` suffix, and return the written paths.
+    pub fn write_patches(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        for patch in &self.patches {
+            let path = dir.join(format!("{}.patch", patch.file));
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut diff = String::new();
+            patch.diff(&mut diff);
+            std::fs::write(&path, diff)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+/// Collects fixes from analyzer results.
+pub struct Planner {
+    root: PathBuf,
+    /// Root-relative files fixes may touch; `None` allows any.
+    files: Option<HashSet<PathBuf>>,
+    /// Lowest dead code confidence that is removed.
+    min_confidence: f64,
+    parser: Parser,
+    sources: HashMap<String, Option<Arc<Source>>>,
+    edits: BTreeMap<String, Vec<(Fix, Edit)>>,
+    skipped: Vec<Skipped>,
+}
+
+impl Planner {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            files: None,
+            min_confidence: 1.0,
+            parser: Parser::new(),
+            sources: HashMap::new(),
+            edits: BTreeMap::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Only fix findings in these root-relative files. Analyzers still see
+    /// the whole project, so reachability is not judged from a subset.
+    pub fn with_files(mut self, files: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.files = Some(files.into_iter().collect());
+        self
+    }
+
+    /// Remove dead code down to this confidence instead of only what the
+    /// analyzer is certain about.
+    pub fn with_min_confidence(mut self, confidence: f64) -> Self {
+        self.min_confidence = confidence;
+        self
+    }
+
+    /// Delete dead private functions at or above the minimum confidence,
+    /// and the imports left unused without them.
+    pub fn dead_code(&mut self, analysis: &deadcode::Analysis) {
+        let mut touched = BTreeSet::new();
+        let items = analysis
+            .items
+            .iter()
+            .filter(|item| is_removable(item, self.min_confidence));
+        for item in items {
+            let file = self.relative(&item.file);
+            let Some(source) = self.source(Kind::DeadCode, &file, item.line) else {
+                continue;
+            };
+            let Some(result) = self.parse(&file, &source) else {
+                self.skip(Kind::DeadCode, &file, item.line, "file could not be parsed");
+                continue;
+            };
+            match function_removal(&source, &result, item) {
+                Ok(edit) => {
+                    self.add(
+                        Kind::DeadCode,
+                        &file,
+                        edit,
+                        format!("remove unused function `{}`", item.name),
+                    );
+                    touched.insert(file);
+                }
+                Err(reason) => self.skip(Kind::DeadCode, &file, item.line, reason),
+            }
+        }
+        for file in touched {
+            self.unused_imports(&file);
+        }
+    }
+
+    /// Delete the imports of `file` that only removed functions used.
+    fn unused_imports(&mut self, file: &str) {
+        let Some(Some(source)) = self.sources.get(file).cloned() else {
+            return;
+        };
+        let Some(result) = self.parse(file, &source) else {
+            return;
+        };
+        let removed: Vec<(usize, usize)> = self.edits[file]
+            .iter()
+            .filter(|(fix, _)| fix.kind == Kind::DeadCode)
+            .map(|(_, edit)| (edit.start, edit.end))
+            .collect();
+        for (edit, names) in import_removals(&source, &result, &removed) {
+            let names = names.join("`, `");
+            self.add(
+                Kind::DeadCode,
+                file,
+                edit,
+                format!("remove import of `{names}` left unused"),
+            );
+        }
+    }
+
+    /// Inline the checks of stale flags listed in `rolled_out`.
+    pub fn stale_flags(&mut self, analysis: &flags::Analysis, rolled_out: &[String]) {
+        let flags = analysis
+            .flags
+            .iter()
+            .filter(|flag| flag.stale && rolled_out.contains(&flag.key));
+        for flag in flags {
+            for reference in &flag.references {
+                let file = self.relative(&reference.file);
+                let Some(source) = self.source(Kind::StaleFlag, &file, reference.line) else {
+                    continue;
+                };
+                let Some(result) = self.parse(&file, &source) else {
+                    self.skip(
+                        Kind::StaleFlag,
+                        &file,
+                        reference.line,
+                        "file could not be parsed",
+                    );
+                    continue;
+                };
+                match flag_removal(&source, &result, reference.line, &flag.key) {
+                    Ok(edit) => self.add(
+                        Kind::StaleFlag,
+                        &file,
+                        edit,
+                        format!("inline fully rolled-out flag `{}`", flag.key),
+                    ),
+                    Err(reason) => self.skip(Kind::StaleFlag, &file, reference.line, reason),
+                }
+            }
+        }
+    }
+
+    /// Delete SATD comments whose referenced issues are all in `closed`.
+    pub fn resolved_satd(&mut self, analysis: &satd::Analysis, closed: &HashSet<u64>) {
+        for item in &analysis.items {
+            let issues = issue_refs(&item.text);
+            if issues.is_empty() || !issues.iter().all(|n| closed.contains(n)) {
+                continue;
+            }
+            let file = self.relative(&item.file);
+            let Some(source) = self.source(Kind::ResolvedSatd, &file, item.line) else {
+                continue;
+            };
+            let row = item.line as usize - 1;
+            if !source
+                .lines
+                .get(row)
+                .is_some_and(|l| is_standalone_comment(l))
+            {
+                let reason = "comment shares its line with code or spans several lines";
+                self.skip(Kind::ResolvedSatd, &file, item.line, reason);
+                continue;
+            }
+            let issues: Vec<String> = issues.iter().map(|n| format!("#{n}")).collect();
+            self.add(
+                Kind::ResolvedSatd,
+                &file,
+                Edit {
+                    start: row,
+                    end: row + 1,
+                    lines: Vec::new(),
+                },
+                format!("remove {} for closed {}", item.marker, issues.join(", ")),
+            );
+        }
+    }
+
+    /// Resolve overlapping edits and build the plan.
+    pub fn finish(mut self) -> Plan {
+        let mut fixes = Vec::new();
+        let mut patches = Vec::new();
+        let mut by_kind = BTreeMap::new();
+        for (file, mut edits) in std::mem::take(&mut self.edits) {
+            edits.sort_by_key(|(_, edit)| (edit.start, edit.end));
+            let mut kept: Vec<Edit> = Vec::new();
+            for (fix, edit) in edits {
+                match kept.last() {
+                    // Two references inside one `if` produce the same edit.
+                    Some(last) if (last.start, last.end) == (edit.start, edit.end) => continue,
+                    Some(last) if edit.start < last.end => {
+                        self.skip(fix.kind, &fix.file, fix.line, "overlaps another fix");
+                        continue;
+                    }
+                    _ => {}
+                }
+                *by_kind.entry(fix.kind).or_insert(0) += 1;
+                fixes.push(fix);
+                kept.push(edit);
+            }
+            let Some(Some(source)) = self.sources.get(&file) else {
+                continue;
+            };
+            patches.push(FilePatch {
+                path: self.root.join(&file),
+                file,
+                source: source.clone(),
+                edits: kept,
+            });
+        }
+        self.skipped
+            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
+        Plan {
+            summary: Summary {
+                fixes: fixes.len(),
+                files: patches.len(),
+                skipped: self.skipped.len(),
+                by_kind,
+            },
+            fixes,
+            skipped: self.skipped,
+            patches,
+        }
+    }
+
+    fn add(&mut self, kind: Kind, file: &str, edit: Edit, description: String) {
+        let fix = Fix {
+            kind,
+            file: file.to_string(),
+            line: edit.start as u32 + 1,
+            end_line: edit.end as u32,
+            description,
+        };
+        self.edits
+            .entry(file.to_string())
+            .or_default()
+            .push((fix, edit));
+    }
+
+    fn skip(&mut self, kind: Kind, file: &str, line: u32, reason: &str) {
+        self.skipped.push(Skipped {
+            kind,
+            file: file.to_string(),
+            line,
+            reason: reason.to_string(),
+        });
+    }
+
+    /// `file` relative to the root, as analyzers report paths under it.
+    fn relative(&self, file: &str) -> String {
+        let path = Path::new(file);
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Contents of root-relative `file`, if fixes may touch it. Unreadable
+    /// files are recorded as skipped.
+    fn source(&mut self, kind: Kind, file: &str, line: u32) -> Option<Arc<Source>> {
+        if self
+            .files
+            .as_ref()
+            .is_some_and(|files| !files.contains(Path::new(file)))
+        {
+            return None;
+        }
+        let root = &self.root;
+        let source = self
+            .sources
+            .entry(file.to_string())
+            .or_insert_with(|| {
+                let text = std::fs::read_to_string(root.join(file)).ok()?;
+                Some(Arc::new(Source::new(text)))
+            })
+            .clone();
+        if source.is_none() {
+            self.skip(kind, file, line, "file could not be read as UTF-8");
+        }
+        source
+    }
+
+    fn parse(&self, file: &str, source: &Source) -> Option<ParseResult> {
+        let path = Path::new(file);
+        let language = Language::detect(path)?;
+        self.parser
+            .parse(source.text.as_bytes(), language, path)
+            .ok()
+    }
+}
+
+/// Issue numbers referenced as `#123` or by an issue URL.
+pub fn issue_refs(text: &str) -> Vec<u64> {
+    static ISSUE: OnceLock<Regex> = OnceLock::new();
+    let issue = ISSUE
+        .get_or_init(|| Regex::new(r"(?:^|[\s(\[])#(\d+)\b|/issues/(\d+)\b").expect("valid regex"));
+    let mut numbers: Vec<u64> = issue
+        .captures_iter(text)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2))?.as_str().parse().ok())
+        .collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// Private functions that are both unreachable and unreferenced score full
+/// confidence. rustc only reports items no export reaches; their visibility
+/// is `unknown`.
+fn is_removable(item: &DeadCodeItem, min_confidence: f64) -> bool {
+    item.kind == "function"
+        && item.confidence >= min_confidence
+        && matches!(item.visibility.as_str(), "private" | "unknown")
+}
+
+/// Import statements that bind only names the `removed` line ranges used
+/// and the rest of the file does not. Comments and strings count as uses,
+/// so an import is kept whenever its name might still be needed.
+fn import_removals(
+    source: &Source,
+    result: &ParseResult,
+    removed: &[(usize, usize)],
+) -> Vec<(Edit, Vec<String>)> {
+    let imports: Vec<Span> = extract_imports(result)
+        .into_iter()
+        .filter(|import| !import.dynamic)
+        .map(|import| import.span)
+        .collect();
+    // Go wraps each `import_spec` in an `import_declaration`: a declaration
+    // with one spec is removed whole, one with several spec by spec.
+    let statements: Vec<(Span, Span)> = imports
+        .iter()
+        .filter_map(|outer| {
+            let inner: Vec<&Span> = imports
+                .iter()
+                .filter(|span| {
+                    *span != outer
+                        && span.start_byte >= outer.start_byte
+                        && span.end_byte <= outer.end_byte
+                })
+                .collect();
+            match inner.as_slice() {
+                [] => Some((*outer, *outer)),
+                [only] => Some((*outer, **only)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    let in_removed = |row: usize| {
+        removed
+            .iter()
+            .any(|&(start, end)| (start..end).contains(&row))
+    };
+    let import_rows: HashSet<usize> = imports
+        .iter()
+        .flat_map(|span| span.start_line as usize - 1..span.end_line as usize)
+        .collect();
+    let mut kept = HashSet::new();
+    let mut gone = HashSet::new();
+    for (row, line) in source.lines.iter().enumerate() {
+        if import_rows.contains(&row) {
+            continue;
+        }
+        let names = if in_removed(row) {
+            &mut gone
+        } else {
+            &mut kept
+        };
+        names.extend(identifiers(line));
+    }
+
+    statements
+        .into_iter()
+        .filter_map(|(span, binding)| {
+            let (start, end) = (span.start_line as usize - 1, span.end_line as usize);
+            if !source.whole_lines(span.start_byte, span.end_byte) || (start..end).any(in_removed) {
+                return None;
+            }
+            let text = &source.text[binding.start_byte..binding.end_byte];
+            let names = import_bindings(result.language, text)?;
+            let unused = names.iter().all(|name| !kept.contains(name.as_str()))
+                && names.iter().any(|name| gone.contains(name.as_str()));
+            unused.then(|| {
+                let edit = Edit {
+                    start,
+                    end,
+                    lines: Vec::new(),
+                };
+                (edit, names)
+            })
+        })
+        .collect()
+}
+
+/// Identifier-like words of `line`.
+fn identifiers(line: &str) -> impl Iterator<Item = &str> {
+    static IDENTIFIER: OnceLock<Regex> = OnceLock::new();
+    IDENTIFIER
+        .get_or_init(|| Regex::new(r"[A-Za-z_$][A-Za-z0-9_$]*").expect("valid regex"))
+        .find_iter(line)
+        .map(|m| m.as_str())
+}
+
+/// Names an import statement binds in the file, or `None` for wildcard,
+/// side-effect and re-exporting imports, whose uses can't be told from the
+/// file alone.
+fn import_bindings(language: Language, text: &str) -> Option<Vec<String>> {
+    let text = text.trim().trim_end_matches(';').trim();
+    // (comma-separated items, separator of a path's segments, whether a bare
+    // path binds its first segment rather than its last)
+    let (items, separator, binds_first) = match language {
+        Language::Python => match text.strip_prefix("from ") {
+            Some(rest) => (rest.split_once(" import ")?.1.to_string(), ".", false),
+            None => (text.strip_prefix("import ")?.to_string(), ".", true),
+        },
+        Language::Rust => {
+            let path = text.strip_prefix("use ")?;
+            // `a::{b, c::{d, e}}` binds the last segment of each leaf.
+            let items = match path.split_once('{') {
+                Some((prefix, list)) => list
+                    .replace(['{', '}'], "")
+                    .split(',')
+                    .map(|item| match item.trim() {
+                        "self" => prefix.trim_end_matches("::").to_string(),
+                        item => item.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+                None => path.to_string(),
+            };
+            (items, "::", false)
+        }
+        Language::Java => {
+            let path = text.strip_prefix("import ")?;
+            let path = path.strip_prefix("static ").unwrap_or(path);
+            (path.to_string(), ".", false)
+        }
+        Language::Go => {
+            let (alias, path) = text.rsplit_once(char::is_whitespace).unwrap_or(("", text));
+            match alias.trim() {
+                "" => (path.trim_matches('"').to_string(), "/", false),
+                "_" | "." => return None,
+                alias => (alias.to_string(), "/", false),
+            }
+        }
+        Language::TypeScript | Language::JavaScript | Language::Tsx | Language::Jsx => {
+            let clause = text.strip_prefix("import ")?;
+            let clause = clause.strip_prefix("type ").unwrap_or(clause);
+            let (clause, _) = clause.rsplit_once(" from ")?;
+            let items = clause
+                .replace(['{', '}'], ",")
+                .split(',')
+                .map(|item| {
+                    let item = item.trim();
+                    let item = item.strip_prefix("type ").unwrap_or(item);
+                    item.strip_prefix("* as ").unwrap_or(item).to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            (items, ".", false)
+        }
+        _ => return None,
+    };
+
+    let names: Vec<String> = items
+        .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.rsplit_once(" as ") {
+            Some((_, alias)) => alias.trim().to_string(),
+            None if binds_first => item.split(separator).next().unwrap_or(item).to_string(),
+            None => item.rsplit(separator).next().unwrap_or(item).to_string(),
+        })
+        .collect();
+    let bindable = |name: &String| identifiers(name).next() == Some(name.as_str());
+    (!names.is_empty() && names.iter().all(bindable)).then_some(names)
+}
+
+/// Remove the function `item` points at, with its annotations and the
+/// blank lines after it.
+fn function_removal(
+    source: &Source,
+    result: &ParseResult,
+    item: &DeadCodeItem,
+) -> std::result::Result<Edit, &'static str> {
+    // rustc reports the line of the name, the parser the first line.
+    let function = extract_functions(result)
+        .into_iter()
+        .filter(|f| f.name == item.name && (f.start_line..=f.end_line).contains(&item.line))
+        .min_by_key(|f| f.end_line - f.start_line)
+        .ok_or("function not found at the reported line")?;
+    if !source.whole_lines(function.span.start_byte, function.span.end_byte) {
+        return Err("function shares its lines with other code");
+    }
+
+    let mut start = annotated_start(&source.lines, function.start_line as usize - 1);
+    let mut end = function.end_line as usize;
+    let blank = |row: usize| source.lines[row].trim().is_empty();
+    // Keep the blank lines before the function as its separator.
+    if start == 0 || blank(start - 1) {
+        while end < source.lines.len() && blank(end) {
+            end += 1;
+        }
+        if end == source.lines.len() {
+            while start > 0 && blank(start - 1) {
+                start -= 1;
+            }
+        }
+    }
+    Ok(Edit {
+        start,
+        end,
+        lines: Vec::new(),
+    })
+}
+
+/// First line of the doc comments, attributes and decorators directly
+/// above line `start`.
+fn annotated_start(lines: &[String], mut start: usize) -> usize {
+    while start > 0 {
+        let line = lines[start - 1].trim();
+        if line.ends_with("*/") {
+            // A block comment is taken whole or not at all.
+            match (0..start)
+                .rev()
+                .find(|&i| lines[i].trim_start().starts_with("/*"))
+            {
+                Some(open) => start = open,
+                None => break,
+            }
+        } else if line.starts_with("//")
+            || line.starts_with("#[")
+            || line.starts_with("# ")
+            || line == "#"
+            || line.starts_with('@')
+        {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// Replace the `if` whose condition is a check of flag `key` on `line`
+/// with the body of its first branch.
+fn flag_removal(
+    source: &Source,
+    result: &ParseResult,
+    line: u32,
+    key: &str,
+) -> std::result::Result<Edit, &'static str> {
+    let row = line as usize - 1;
+    let mut found = None;
+    enclosing_if(result.root_node(), row, &mut found);
+    let node = found.ok_or("flag is not checked by an `if` condition")?;
+
+    let mut condition = node
+        .child_by_field_name("condition")
+        .ok_or("flag is not checked by an `if` condition")?;
+    while CONDITION_WRAPPERS.contains(&condition.kind()) && condition.named_child_count() == 1 {
+        condition = condition.named_child(0).unwrap_or(condition);
+    }
+    if !CALL_KINDS.contains(&condition.kind()) || !result.node_text(&condition).contains(key) {
+        return Err("condition does more than check the flag");
+    }
+    if !source.whole_lines(node.start_byte(), node.end_byte()) {
+        return Err("`if` shares its lines with other code");
+    }
+
+    let body = node
+        .child_by_field_name("consequence")
+        .or_else(|| node.child_by_field_name("body"))
+        .ok_or("`if` has no body")?;
+    let text = result.node_text(&body);
+    let (first, last) = if text.starts_with('{') && text.ends_with('}') {
+        if !source.blank_after(body.start_byte() + 1) || !source.blank_before(body.end_byte() - 1) {
+            return Err("braces share lines with the guarded code");
+        }
+        (body.start_position().row + 1, body.end_position().row)
+    } else {
+        if body.start_position().row == node.start_position().row {
+            return Err("guarded code is on the same line as the condition");
+        }
+        (body.start_position().row, body.end_position().row + 1)
+    };
+
+    let start = node.start_position().row;
+    let indent = leading_whitespace(&source.lines[start]);
+    Ok(Edit {
+        start,
+        end: node.end_position().row + 1,
+        lines: reindent(&source.lines[first..last], indent),
+    })
+}
+
+/// Innermost `if` whose condition spans `row`.
+fn enclosing_if<'t>(node: Node<'t>, row: usize, found: &mut Option<Node<'t>>) {
+    if IF_KINDS.contains(&node.kind()) {
+        let spans_row = node
+            .child_by_field_name("condition")
+            .is_some_and(|c| c.start_position().row <= row && row <= c.end_position().row);
+        if spans_row {
+            *found = Some(node);
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.start_position().row <= row && row <= child.end_position().row {
+            enclosing_if(child, row, found);
+        }
+    }
+}
+
+/// Move `lines` to `indent`, keeping their indentation relative to each other.
+fn reindent(lines: &[String], indent: &str) -> Vec<String> {
+    let common = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| leading_whitespace(line).len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{}", &line[common..])
+            }
+        })
+        .collect()
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// A comment that fills its line and does not open or close a block.
+fn is_standalone_comment(line: &str) -> bool {
+    let line = line.trim();
+    if line.starts_with("/*") {
+        return line.ends_with("*/");
+    }
+    !line.starts_with("*/")
+        && ["//", "#", "--", ";", "*"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+}
+
+/// File contents split into lines.
+#[derive(Debug)]
+struct Source {
+    text: String,
+    /// Lines without their terminators.
+    lines: Vec<String>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+impl Source {
+    fn new(text: String) -> Self {
+        Self {
+            lines: text.lines().map(str::to_string).collect(),
+            newline: if text.contains("\r\n") { "\r\n" } else { "\n" },
+            trailing_newline: text.ends_with('\n'),
+            text,
+        }
+    }
+
+    /// Whether only whitespace precedes `byte` on its line.
+    fn blank_before(&self, byte: usize) -> bool {
+        let start = self.text[..byte].rfind('\n').map_or(0, |i| i + 1);
+        self.text[start..byte].trim().is_empty()
+    }
+
+    /// Whether only whitespace follows `byte` on its line.
+    fn blank_after(&self, byte: usize) -> bool {
+        let end = self.text[byte..]
+            .find('\n')
+            .map_or(self.text.len(), |i| byte + i);
+        self.text[byte..end].trim().is_empty()
+    }
+
+    /// Whether `start..end` covers its lines from indentation to line end.
+    fn whole_lines(&self, start: usize, end: usize) -> bool {
+        self.blank_before(start) && self.blank_after(end)
+    }
+
+    fn join(&self, lines: &[String]) -> String {
+        let mut text = lines.join(self.newline);
+        if self.trailing_newline && !lines.is_empty() {
+            text.push_str(self.newline);
+        }
+        text
+    }
+}
+
+/// Replace lines `start..end` (0-indexed) with `lines`.
+#[derive(Debug, Clone)]
+struct Edit {
+    start: usize,
+    end: usize,
+    lines: Vec<String>,
+}
+
+/// Non-overlapping edits to one file, sorted by line.
+#[derive(Debug, Clone)]
+struct FilePatch {
+    path: PathBuf,
+    file: String,
+    source: Arc<Source>,
+    edits: Vec<Edit>,
+}
+
+impl FilePatch {
+    fn patched(&self) -> String {
+        let old = &self.source.lines;
+        let mut lines = Vec::with_capacity(old.len());
+        let mut next = 0;
+        for edit in &self.edits {
+            lines.extend_from_slice(&old[next..edit.start]);
+            lines.extend_from_slice(&edit.lines);
+            next = edit.end;
+        }
+        lines.extend_from_slice(&old[next..]);
+        self.source.join(&lines)
+    }
+
+    fn diff(&self, out: &mut String) {
+        let old = &self.source.lines;
+        let _ = writeln!(out, "--- a/{}\n+++ b/{}", self.file, self.file);
+
+        // Lines added minus lines removed by earlier hunks.
+        let mut shift = 0isize;
+        let mut i = 0;
+        while i < self.edits.len() {
+            // Edits whose context would meet share a hunk.
+            let mut j = i + 1;
+            while j < self.edits.len() && self.edits[j].start <= self.edits[j - 1].end + 2 * CONTEXT
+            {
+                j += 1;
+            }
+            let group = &self.edits[i..j];
+            let old_start = group[0].start.saturating_sub(CONTEXT);
+            let old_end = (group[group.len() - 1].end + CONTEXT).min(old.len());
+
+            let mut body = String::new();
+            let mut delta = 0isize;
+            let mut next = old_start;
+            for edit in group {
+                for line in &old[next..edit.start] {
+                    let _ = writeln!(body, " {line}");
+                }
+                for line in &old[edit.start..edit.end] {
+                    let _ = writeln!(body, "-{line}");
+                }
+                for line in &edit.lines {
+                    let _ = writeln!(body, "+{line}");
+                }
+                delta += edit.lines.len() as isize - (edit.end - edit.start) as isize;
+                next = edit.end;
+            }
+            for line in &old[next..old_end] {
+                let _ = writeln!(body, " {line}");
+            }
+
+            let old_len = old_end - old_start;
+            let new_start = (old_start as isize + shift) as usize;
+            let new_len = (old_len as isize + delta) as usize;
+            let _ = writeln!(
+                out,
+                "@@ -{} +{} @@",
+                hunk_range(old_start, old_len),
+                hunk_range(new_start, new_len)
+            );
+            out.push_str(&body);
+            shift += delta;
+            i = j;
+        }
+    }
+}
+
+/// `start,len` of a hunk, 1-indexed; an empty range names the line before.
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{len}", start + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::flags::{FeatureFlag, FlagReferenceOutput};
+    use crate::analyzers::satd::{SatdItem, Severity};
+    use crate::core::Span;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, file: &str, content: &str) {
+        std::fs::write(dir.path().join(file), content).unwrap();
+    }
+
+    fn read(dir: &TempDir, file: &str) -> String {
+        std::fs::read_to_string(dir.path().join(file)).unwrap()
+    }
+
+    fn dead(name: &str, file: &str, line: u32, end_line: u32) -> DeadCodeItem {
+        DeadCodeItem {
+            name: name.to_string(),
+            kind: "function".to_string(),
+            file: file.to_string(),
+            line,
+            end_line,
+            span: None,
+            visibility: "private".to_string(),
+            confidence: 1.0,
+            reason: "Not reachable from entry points and no references found".to_string(),
+        }
+    }
+
+    fn flag(key: &str, stale: bool, references: &[(&str, u32)]) -> FeatureFlag {
+        FeatureFlag {
+            key: key.to_string(),
+            provider: "launchdarkly".to_string(),
+            references: references
+                .iter()
+                .map(|(file, line)| FlagReferenceOutput {
+                    file: file.to_string(),
+                    line: *line,
+                })
+                .collect(),
+            first_seen: None,
+            last_seen: None,
+            age_days: 200,
+            stale,
+            file_spread: 1,
+            priority: Default::default(),
+            prioritization: None,
+        }
+    }
+
+    fn satd_item(file: &str, line: u32, text: &str) -> SatdItem {
+        SatdItem {
+            file: file.to_string(),
+            line,
+            span: Span::default(),
+            category: "design".to_string(),
+            severity: Severity::Medium,
+            marker: "TODO".to_string(),
+            text: text.to_string(),
+            function: None,
+            weight: 1.0,
+            prioritization: None,
+            introduced: None,
+            changed: None,
+            age_days: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_refs() {
+        assert_eq!(issue_refs("// TODO(#12): drop after #7"), vec![7, 12]);
+        assert_eq!(
+            issue_refs("# FIXME see https://github.com/o/r/issues/40"),
+            vec![40]
+        );
+        assert!(issue_refs("// TODO: colour #fff and a&#39;b").is_empty());
+    }
+
+    #[test]
+    fn test_removes_dead_function_with_its_docs() {
+        let dir = TempDir::new().unwrap();
+        let source = "\
+def main():
+    run()
+
+
+# Old entry point.
+@cached
+def legacy(x):
+    return x + 1
+
+
+def run():
+    pass
+";
+        write(&dir, "app.py", source);
+        let analysis = deadcode::Analysis {
+            items: vec![
+                dead("legacy", "app.py", 7, 8),
+                DeadCodeItem {
+                    confidence: 0.9,
+                    ..dead("run", "app.py", 11, 12)
+                },
+            ],
+            test_only: vec![],
+            summary: Default::default(),
+        };
+
+        let mut planner = Planner::new(dir.path());
+        planner.dead_code(&analysis);
+        let plan = planner.finish();
+
+        assert_eq!(plan.fixes.len(), 1, "only the confirmed item is fixed");
+        assert_eq!((plan.fixes[0].line, plan.fixes[0].end_line), (5, 10));
+        let expected = [
+            "--- a/app.py",
+            "+++ b/app.py",
+            "@@ -2,11 +2,5 @@",
+            "     run()",
+            " ",
+            " ",
+            "-# Old entry point.",
+            "-@cached",
+            "-def legacy(x):",
+            "-    return x + 1",
+            "-",
+            "-",
+            " def run():",
+            "     pass",
+            "",
+        ];
+        assert_eq!(plan.diff(), expected.join("\n"));
+
+        plan.apply().unwrap();
+        assert_eq!(
+            read(&dir, "app.py"),
+            "def main():\n    run()\n\n\ndef run():\n    pass\n"
+        );
+    }
+
+    #[test]
+    fn test_patches_remove_imports_left_unused() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("pkg")).unwrap();
+        let source = "\
+import os
+import sys
+from typing import List, Optional
+
+
+def main():
+    print(sys.argv)
+
+
+def _legacy(paths: List[str]) -> Optional[str]:
+    return os.path.join(*paths)
+";
+        write(&dir, "pkg/app.py", source);
+        let analysis = deadcode::Analysis {
+            items: vec![DeadCodeItem {
+                confidence: 0.95,
+                ..dead("_legacy", "pkg/app.py", 10, 11)
+            }],
+            test_only: vec![],
+            summary: Default::default(),
+        };
+
+        let mut planner = Planner::new(dir.path());
+        planner.dead_code(&analysis);
+        assert!(planner.finish().fixes.is_empty(), "below full confidence");
+
+        let mut planner = Planner::new(dir.path()).with_min_confidence(0.9);
+        planner.dead_code(&analysis);
+        let plan = planner.finish();
+        let descriptions: Vec<&str> = plan.fixes.iter().map(|f| f.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "remove import of `os` left unused",
+                "remove import of `List`, `Optional` left unused",
+                "remove unused function `_legacy`",
+            ]
+        );
+
+        let patches = dir.path().join("patches");
+        let written = plan.write_patches(&patches).unwrap();
+        assert_eq!(written, vec![patches.join("pkg/app.py.patch")]);
+        let patch = std::fs::read_to_string(&written[0]).unwrap();
+        assert!(patch.starts_with("--- a/pkg/app.py\n+++ b/pkg/app.py\n"));
+        assert!(
+            patch.contains("-import os\n import sys\n-from typing"),
+            "{patch}"
+        );
+
+        plan.apply().unwrap();
+        assert_eq!(
+            read(&dir, "pkg/app.py"),
+            "import sys\n\n\ndef main():\n    print(sys.argv)\n"
+        );
+    }
+
+    #[test]
+    fn test_import_bindings() {
+        let list = |items: &[&str]| Some(items.iter().map(|s| s.to_string()).collect());
+        assert_eq!(
+            import_bindings(Language::Python, "import os.path"),
+            list(&["os"])
+        );
+        assert_eq!(
+            import_bindings(Language::Python, "from x import (\n    a,\n    b as c,\n)"),
+            list(&["a", "c"])
+        );
+        assert_eq!(import_bindings(Language::Python, "from x import *"), None);
+        assert_eq!(
+            import_bindings(Language::Rust, "use crate::a::{self, b::{c, d as e}};"),
+            list(&["a", "c", "e"])
+        );
+        assert_eq!(import_bindings(Language::Rust, "pub use a::b;"), None);
+        assert_eq!(import_bindings(Language::Rust, "use a::*;"), None);
+        assert_eq!(
+            import_bindings(Language::Go, "\"net/http\""),
+            list(&["http"])
+        );
+        assert_eq!(
+            import_bindings(Language::Go, "f \"github.com/x/fmtx\""),
+            list(&["f"])
+        );
+        assert_eq!(import_bindings(Language::Go, "_ \"embed\""), None);
+        assert_eq!(
+            import_bindings(Language::TypeScript, "import X, { a, b as c } from \"m\";"),
+            list(&["X", "a", "c"])
+        );
+        assert_eq!(
+            import_bindings(Language::JavaScript, "import * as ns from 'm'"),
+            list(&["ns"])
+        );
+        assert_eq!(
+            import_bindings(Language::JavaScript, "import './side';"),
+            None
+        );
+        assert_eq!(
+            import_bindings(
+                Language::Java,
+                "import static org.junit.Assert.assertEquals;"
+            ),
+            list(&["assertEquals"])
+        );
+    }
+
+    #[test]
+    fn test_inlines_rolled_out_flag_checks() {
+        let dir = TempDir::new().unwrap();
+        let source = r#"function checkout(user) {
+  if (client.variation("new-checkout", user, false)) {
+    newCheckout();
+    track("new");
+  } else {
+    oldCheckout();
+  }
+  if (!client.variation("new-checkout", user, false)) {
+    warn();
+  }
+  if (client.variation("beta", user, false)) {
+    beta();
+  }
+}
+"#;
+        write(&dir, "checkout.js", source);
+        let analysis = flags::Analysis {
+            generated_at: String::new(),
+            flags: vec![
+                flag(
+                    "new-checkout",
+                    true,
+                    &[("checkout.js", 2), ("checkout.js", 8)],
+                ),
+                flag("beta", true, &[("checkout.js", 11)]),
+            ],
+            stale_count: 2,
+            summary: Default::default(),
+        };
+
+        let mut planner = Planner::new(dir.path());
+        planner.stale_flags(&analysis, &["new-checkout".to_string()]);
+        let plan = planner.finish();
+        plan.apply().unwrap();
+
+        assert_eq!(plan.summary.by_kind[&Kind::StaleFlag], 1);
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.skipped[0].line, 8);
+        assert_eq!(
+            plan.skipped[0].reason,
+            "condition does more than check the flag"
+        );
+        assert_eq!(
+            read(&dir, "checkout.js"),
+            r#"function checkout(user) {
+  newCheckout();
+  track("new");
+  if (!client.variation("new-checkout", user, false)) {
+    warn();
+  }
+  if (client.variation("beta", user, false)) {
+    beta();
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_strips_satd_for_closed_issues_only() {
+        let dir = TempDir::new().unwrap();
+        write(
+            &dir,
+            "lib.go",
+            "package lib\n\n// TODO(#3): retry\n// TODO(#3, #4): batch\nfunc F() {} // TODO #3\n",
+        );
+        let analysis = satd::Analysis {
+            items: vec![
+                satd_item("lib.go", 3, "// TODO(#3): retry"),
+                satd_item("lib.go", 4, "// TODO(#3, #4): batch"),
+                satd_item("lib.go", 5, "func F() {} // TODO #3"),
+            ],
+            by_category: Default::default(),
+            density: 0.0,
+            duplicates: vec![],
+            comparison: None,
+            summary: Default::default(),
+        };
+
+        let mut planner = Planner::new(dir.path()).with_files([PathBuf::from("lib.go")]);
+        planner.resolved_satd(&analysis, &HashSet::from([3]));
+        let plan = planner.finish();
+
+        assert_eq!(plan.fixes.len(), 1);
+        assert_eq!(plan.fixes[0].description, "remove TODO for closed #3");
+        assert_eq!(plan.skipped.len(), 1, "the trailing comment is left alone");
+        assert_eq!(
+            plan.diff(),
+            "--- a/lib.go\n+++ b/lib.go\n@@ -1,5 +1,4 @@\n package lib\n \n\
+             -// TODO(#3): retry\n // TODO(#3, #4): batch\n func F() {} // TODO #3\n"
+        );
+    }
+}
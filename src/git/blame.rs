@@ -3,9 +3,11 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use chrono::{DateTime, TimeZone, Utc};
 use gix::Repository;
 use serde::{Deserialize, Serialize};
 
+use super::Identities;
 use crate::core::{Error, Result};
 
 /// Blame information for a file.
@@ -32,6 +34,17 @@ pub struct BlameLine {
     pub timestamp: i64,
 }
 
+/// When and by whom a line was last changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineOrigin {
+    /// Commit SHA.
+    pub commit: String,
+    /// Author name.
+    pub author: String,
+    /// Author date.
+    pub date: DateTime<Utc>,
+}
+
 /// Statistics for an author.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AuthorStats {
@@ -49,16 +62,30 @@ pub struct AuthorStats {
 ///
 /// Uses `git blame --line-porcelain` which is much faster than gix's pure-Rust
 /// blame implementation, especially on large repositories with deep history.
-pub fn get_blame(_repo: &Repository, root: &Path, path: &Path) -> Result<BlameInfo> {
+/// Blames the file as of `rev` when given, which is required for bare
+/// repositories since there is no worktree copy to blame. Authors are
+/// reported under their canonical `identities`.
+pub fn get_blame(
+    repo: &Repository,
+    root: &Path,
+    rev: Option<&str>,
+    path: &Path,
+    identities: &Identities,
+) -> Result<BlameInfo> {
     let relative_path = path
         .strip_prefix(root)
         .unwrap_or(path)
         .to_string_lossy()
         .to_string();
 
-    let output = std::process::Command::new("git")
-        .current_dir(root)
-        .args(["blame", "--line-porcelain", "--", &relative_path])
+    let mut cmd = std::process::Command::new("git");
+    cmd.current_dir(super::log::cli_dir(repo))
+        .args(["blame", "--line-porcelain"]);
+    if let Some(rev) = rev {
+        cmd.arg(rev);
+    }
+    let output = cmd
+        .args(["--", &relative_path])
         .output()
         .map_err(|e| Error::git(format!("Failed to run git blame: {e}")))?;
 
@@ -67,11 +94,11 @@ pub fn get_blame(_repo: &Repository, root: &Path, path: &Path) -> Result<BlameIn
         return Err(Error::git(format!("git blame failed: {}", stderr)));
     }
 
-    parse_line_porcelain(&output.stdout, path)
+    parse_line_porcelain(&output.stdout, path, identities)
 }
 
 /// Parse `git blame --line-porcelain` output into BlameInfo.
-fn parse_line_porcelain(output: &[u8], path: &Path) -> Result<BlameInfo> {
+fn parse_line_porcelain(output: &[u8], path: &Path, identities: &Identities) -> Result<BlameInfo> {
     let text = String::from_utf8_lossy(output);
 
     let mut lines = Vec::new();
@@ -79,12 +106,16 @@ fn parse_line_porcelain(output: &[u8], path: &Path) -> Result<BlameInfo> {
 
     let mut current_sha = String::new();
     let mut current_author = String::new();
+    let mut current_email = String::new();
     let mut current_timestamp: i64 = 0;
     let mut current_line_num: u32 = 0;
 
     for line in text.lines() {
         if line.starts_with('\t') {
             // Content line - marks end of a blame entry
+            if !identities.is_empty() {
+                current_author = identities.resolve(&current_author, &current_email).0;
+            }
             lines.push(BlameLine {
                 line: current_line_num,
                 author: current_author.clone(),
@@ -97,6 +128,8 @@ fn parse_line_porcelain(output: &[u8], path: &Path) -> Result<BlameInfo> {
                 .push(current_timestamp);
         } else if let Some(rest) = line.strip_prefix("author ") {
             current_author = rest.to_string();
+        } else if let Some(rest) = line.strip_prefix("author-mail ") {
+            current_email = rest.trim_matches(['<', '>']).to_string();
         } else if let Some(rest) = line.strip_prefix("author-time ") {
             current_timestamp = rest.parse().unwrap_or(0);
         } else if line.len() >= 40 && line.as_bytes()[0].is_ascii_hexdigit() {
@@ -164,6 +197,22 @@ impl BlameInfo {
             .map(|(_, pct)| pct / 100.0)
             .unwrap_or(0.0)
     }
+
+    /// The most recent commit among lines `start..=end` (1-indexed).
+    pub fn latest_origin(&self, start: u32, end: u32) -> Option<LineOrigin> {
+        self.lines
+            .iter()
+            .filter(|l| (start..=end).contains(&l.line))
+            .max_by_key(|l| l.timestamp)
+            .map(|l| LineOrigin {
+                commit: l.sha.clone(),
+                author: l.author.clone(),
+                date: Utc
+                    .timestamp_opt(l.timestamp, 0)
+                    .single()
+                    .unwrap_or_default(),
+            })
+    }
 }
 
 #[cfg(test)]
@@ -171,6 +220,34 @@ mod tests {
     use super::*;
     use std::process::Command;
 
+    fn blame_line(line: u32, author: &str, timestamp: i64) -> BlameLine {
+        BlameLine {
+            line,
+            author: author.to_string(),
+            sha: format!("{line:040}"),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_latest_origin() {
+        let info = BlameInfo {
+            path: "test.rs".to_string(),
+            lines: vec![
+                blame_line(1, "Alice", 100),
+                blame_line(2, "Bob", 300),
+                blame_line(3, "Alice", 200),
+            ],
+            authors: HashMap::new(),
+        };
+
+        let origin = info.latest_origin(1, 3).unwrap();
+        assert_eq!(origin.author, "Bob");
+        assert_eq!(origin.date.timestamp(), 300);
+        assert_eq!(info.latest_origin(3, 3).unwrap().author, "Alice");
+        assert!(info.latest_origin(4, 9).is_none());
+    }
+
     #[test]
     fn test_bus_factor_empty() {
         let info = BlameInfo {
@@ -349,7 +426,7 @@ mod tests {
 
         // Open repo and get blame
         let repo = gix::open(temp.path()).unwrap();
-        let result = get_blame(&repo, temp.path(), &file_path);
+        let result = get_blame(&repo, temp.path(), None, &file_path, &Identities::default());
 
         assert!(result.is_ok());
         let blame = result.unwrap();
@@ -407,7 +484,7 @@ mod tests {
 
         // Open repo and get blame
         let repo = gix::open(temp.path()).unwrap();
-        let result = get_blame(&repo, temp.path(), &file_path);
+        let result = get_blame(&repo, temp.path(), None, &file_path, &Identities::default());
 
         assert!(result.is_ok());
         let blame = result.unwrap();
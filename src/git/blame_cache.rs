@@ -0,0 +1,197 @@
+//! Shared, cached blame lookups.
+//!
+//! Analyzers that want to say when and by whom a finding was introduced
+//! (SATD, complexity, smells) share one [`BlameCache`] through the analysis
+//! context. Blame results are keyed by file path, content hash and the
+//! commit blamed at: a file blamed by one analyzer is free for the next, and
+//! with a cache directory set, unchanged files skip `git blame` on later runs
+//! at the same commit too. A new commit, even one that leaves the file as it
+//! is, changes which commit uncommitted or reverted lines belong to.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::blame::{BlameInfo, LineOrigin};
+use super::GitRepo;
+use crate::core::Result;
+
+/// Cached `git blame` results for one repository and revision.
+pub struct BlameCache {
+    root: PathBuf,
+    rev: Option<String>,
+    dir: Option<PathBuf>,
+    /// Commit blamed at, resolved on first use; `None` (an unborn branch)
+    /// keeps results out of the cache directory.
+    commit: OnceLock<Option<String>>,
+    entries: Mutex<HashMap<String, Arc<BlameInfo>>>,
+}
+
+impl BlameCache {
+    /// Blame files in the repository at `git_path`, at `rev` when set.
+    pub fn new(git_path: impl Into<PathBuf>, rev: Option<&str>) -> Self {
+        Self {
+            root: git_path.into(),
+            rev: rev.map(str::to_string),
+            dir: None,
+            commit: OnceLock::new(),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Also persist results as JSON files under `dir`.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Blame `path`, reusing an earlier result for the same contents.
+    pub fn blame(&self, path: &Path) -> Result<Arc<BlameInfo>> {
+        let repo = GitRepo::open(&self.root)?.at_ref(self.rev.as_deref());
+        let content = repo.read_file(path)?;
+        let commit = self.commit.get_or_init(|| repo.rev_sha().ok()).as_deref();
+        let key = cache_key(path, commit.unwrap_or_default(), &content);
+
+        if let Some(info) = self.lock().get(&key) {
+            return Ok(Arc::clone(info));
+        }
+
+        let persisted = commit.and_then(|_| self.read_persisted(&key));
+        let info = match persisted {
+            Some(info) => info,
+            None => {
+                let info = repo.blame(path)?;
+                if commit.is_some() {
+                    self.persist(&key, &info);
+                }
+                info
+            }
+        };
+        let info = Arc::new(info);
+        self.lock().insert(key, Arc::clone(&info));
+        Ok(info)
+    }
+
+    /// Commit that last changed `line` (1-indexed) of `path`.
+    pub fn origin(&self, path: &Path, line: u32) -> Option<LineOrigin> {
+        self.latest_origin(path, line, line)
+    }
+
+    /// Most recent commit touching lines `start..=end` of `path`.
+    pub fn latest_origin(&self, path: &Path, start: u32, end: u32) -> Option<LineOrigin> {
+        self.blame(path).ok()?.latest_origin(start, end)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<BlameInfo>>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn read_persisted(&self, key: &str) -> Option<BlameInfo> {
+        let file = self.dir.as_ref()?.join(format!("{key}.json"));
+        let bytes = std::fs::read(file).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Best effort: a cache that can't be written just means blaming again.
+    fn persist(&self, key: &str, info: &BlameInfo) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+        if std::fs::create_dir_all(dir).is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec(info) {
+            let _ = std::fs::write(dir.join(format!("{key}.json")), json);
+        }
+    }
+}
+
+fn cache_key(path: &Path, commit: &str, content: &[u8]) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(&[0]);
+    hasher.update(commit.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(content);
+    hasher.finalize().to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::git;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn repo_with_history() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        git(dir, &["init", "-q"]);
+        git(dir, &["config", "user.email", "a@example.com"]);
+        git(dir, &["config", "user.name", "Alice"]);
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-qm", "first"]);
+        git(dir, &["config", "user.name", "Bob"]);
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n// TODO: b\n").unwrap();
+        git(dir, &["commit", "-qam", "second"]);
+        temp
+    }
+
+    #[test]
+    fn test_origin_and_latest_origin() {
+        let temp = repo_with_history();
+        let cache = BlameCache::new(temp.path(), None);
+        let path = temp.path().join("lib.rs");
+
+        assert_eq!(cache.origin(&path, 1).unwrap().author, "Alice");
+        assert_eq!(cache.origin(&path, 2).unwrap().author, "Bob");
+        assert_eq!(cache.latest_origin(&path, 1, 2).unwrap().author, "Bob");
+        assert!(cache.origin(&path, 99).is_none());
+    }
+
+    #[test]
+    fn test_blame_is_cached_by_content() {
+        let temp = repo_with_history();
+        let cache_dir = TempDir::new().unwrap();
+        let path = temp.path().join("lib.rs");
+
+        let cache = BlameCache::new(temp.path(), None).with_dir(cache_dir.path());
+        let first = cache.blame(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &cache.blame(&path).unwrap()));
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 1);
+
+        // A fresh cache picks the result up from disk.
+        let reloaded = BlameCache::new(temp.path(), None).with_dir(cache_dir.path());
+        assert_eq!(reloaded.blame(&path).unwrap().lines.len(), 2);
+
+        // Changed contents get a new entry.
+        std::fs::write(&path, "fn a() {}\n// TODO: b\nfn c() {}\n").unwrap();
+        assert_eq!(cache.blame(&path).unwrap().lines.len(), 3);
+        assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_cache_entries_are_per_commit() {
+        let temp = repo_with_history();
+        let cache_dir = TempDir::new().unwrap();
+        let path = temp.path().join("lib.rs");
+        std::fs::write(&path, "fn a() {}\n// TODO: b\nfn c() {}\n").unwrap();
+        let _ = BlameCache::new(temp.path(), None)
+            .with_dir(cache_dir.path())
+            .blame(&path);
+
+        // Committing the file unchanged must not serve the worktree blame,
+        // where the new line was not committed yet.
+        git(temp.path(), &["commit", "-qam", "third"]);
+        let head = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        let head = String::from_utf8(head.stdout).unwrap();
+        let cache = BlameCache::new(temp.path(), None).with_dir(cache_dir.path());
+        assert_eq!(cache.origin(&path, 3).unwrap().commit, head.trim());
+        assert_eq!(cache.origin(&path, 1).unwrap().author, "Alice");
+    }
+}
@@ -0,0 +1,196 @@
+//! Lines changed relative to a base revision, for diff-aware analysis.
+//!
+//! `--base <ref>` restricts analysis to files changed since the merge base
+//! with `<ref>`, and analyzers mark findings on the changed lines so a PR gate
+//! can look at what the branch touched.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::hunks::parse_unified_diff;
+use super::ChangeType;
+use crate::core::{Error, Result};
+
+/// Changed line ranges per file, relative to a base revision.
+#[derive(Debug, Clone)]
+pub struct ChangedLines {
+    base: String,
+    root: PathBuf,
+    /// Inclusive 1-indexed line ranges in the current version of each file.
+    files: HashMap<PathBuf, Vec<(u32, u32)>>,
+}
+
+impl ChangedLines {
+    /// Lines changed in the repository at `root` since its merge base with
+    /// `base`: up to `head` when given, otherwise in the working tree,
+    /// including untracked files. Paths are relative to `root`.
+    pub fn since(root: &Path, base: &str, head: Option<&str>) -> Result<Self> {
+        let mut args = vec![
+            "-c",
+            "core.quotepath=off",
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--unified=0",
+            "-M",
+            "--relative",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            "--merge-base",
+            base,
+        ];
+        args.extend(head);
+        args.push("--");
+        let diff = git_output(root, &args)?;
+
+        let mut files = HashMap::new();
+        for file in parse_unified_diff(&diff) {
+            if file.change.change_type == ChangeType::Deleted {
+                continue;
+            }
+            let ranges = file
+                .hunks
+                .iter()
+                .map(|hunk| match hunk.new_lines {
+                    // A pure deletion sits between `new_start` and the next line.
+                    0 => (hunk.new_start.max(1), hunk.new_start + 1),
+                    n => (hunk.new_start, hunk.new_start + n - 1),
+                })
+                .collect();
+            files.insert(file.change.path, ranges);
+        }
+
+        if head.is_none() {
+            let untracked = git_output(root, &["ls-files", "--others", "--exclude-standard"])?;
+            for path in untracked.lines().filter(|line| !line.is_empty()) {
+                files.insert(PathBuf::from(path), vec![(1, u32::MAX)]);
+            }
+        }
+
+        Ok(Self {
+            base: base.to_string(),
+            root: root.to_path_buf(),
+            files,
+        })
+    }
+
+    /// The revision changes are measured against.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Changed files, relative to the root.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.files.keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Whether `path` changed at all.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.contains_key(self.relative(path))
+    }
+
+    /// Whether any of lines `start..=end` of `path` changed. `path` may be
+    /// relative to the root or absolute.
+    pub fn touches(&self, path: &Path, start: u32, end: u32) -> bool {
+        self.files
+            .get(self.relative(path))
+            .is_some_and(|ranges| ranges.iter().any(|&(s, e)| s <= end && start <= e))
+    }
+
+    fn relative<'p>(&self, path: &'p Path) -> &'p Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+}
+
+fn git_output(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| Error::git(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::git(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::git;
+    use tempfile::TempDir;
+
+    fn numbered(lines: std::ops::RangeInclusive<u32>) -> String {
+        lines.map(|i| format!("line {i}\n")).collect()
+    }
+
+    fn repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        git(dir, &["init", "-q"]);
+        git(dir, &["config", "user.email", "a@example.com"]);
+        git(dir, &["config", "user.name", "Alice"]);
+        std::fs::write(dir.join("a.rs"), numbered(1..=10)).unwrap();
+        std::fs::write(dir.join("b.rs"), numbered(1..=10)).unwrap();
+        std::fs::write(dir.join("gone.rs"), "x\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-qm", "base"]);
+        git(dir, &["tag", "base"]);
+        temp
+    }
+
+    #[test]
+    fn test_worktree_changes_since_base() {
+        let temp = repo();
+        let dir = temp.path();
+        let edited = numbered(1..=10).replace("line 4\n", "line four\nline 4b\n");
+        std::fs::write(dir.join("a.rs"), edited).unwrap();
+        std::fs::remove_file(dir.join("gone.rs")).unwrap();
+        std::fs::write(dir.join("new.rs"), "fn new() {}\n").unwrap();
+
+        let changed = ChangedLines::since(dir, "base", None).unwrap();
+        assert_eq!(changed.base(), "base");
+        assert_eq!(
+            changed.paths(),
+            vec![PathBuf::from("a.rs"), PathBuf::from("new.rs")],
+            "deleted files are left out, untracked ones included"
+        );
+        assert!(changed.touches(Path::new("a.rs"), 4, 4));
+        assert!(
+            changed.touches(&dir.join("a.rs"), 1, 5),
+            "absolute paths work"
+        );
+        assert!(!changed.touches(Path::new("a.rs"), 6, 11));
+        assert!(!changed.touches(Path::new("b.rs"), 1, 10));
+        assert!(changed.touches(Path::new("new.rs"), 1, 1));
+        assert!(!changed.contains(Path::new("b.rs")));
+    }
+
+    #[test]
+    fn test_committed_changes_up_to_head() {
+        let temp = repo();
+        let dir = temp.path();
+        // Drop lines 6 and 7: the lines on either side count as touched.
+        let edited = numbered(1..=10).replace("line 6\nline 7\n", "");
+        std::fs::write(dir.join("b.rs"), edited).unwrap();
+        git(dir, &["commit", "-qam", "trim"]);
+        std::fs::write(dir.join("untracked.rs"), "x\n").unwrap();
+
+        let changed = ChangedLines::since(dir, "base", Some("HEAD")).unwrap();
+        assert_eq!(changed.paths(), vec![PathBuf::from("b.rs")]);
+        assert!(changed.touches(Path::new("b.rs"), 5, 5));
+        assert!(changed.touches(Path::new("b.rs"), 6, 6));
+        assert!(!changed.touches(Path::new("b.rs"), 1, 4));
+        assert!(!changed.touches(Path::new("b.rs"), 7, 8));
+
+        assert!(ChangedLines::since(dir, "no-such-ref", None).is_err());
+    }
+}
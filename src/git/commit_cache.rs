@@ -0,0 +1,116 @@
+//! Commit history shared by the history-based analyzers.
+//!
+//! Churn, temporal and hidden coupling, hotspots and ownership each read
+//! `git log --numstat`. When several run together (`omen all`, `omen report
+//! generate`), a [`CommitCache`] in the analysis context reads the whole
+//! history once, on first use, and serves each analyzer its time window and
+//! path filter from memory.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use super::log::{cutoff_timestamp, is_since_all};
+use super::{Commit, GitRepo, PathFilter};
+use crate::config::GitConfig;
+use crate::core::{Error, Result};
+
+/// Every commit with file stats for one repository and revision.
+pub struct CommitCache {
+    root: PathBuf,
+    git: GitConfig,
+    commits: OnceLock<std::result::Result<Vec<Commit>, String>>,
+}
+
+impl CommitCache {
+    /// Read history from the repository at `git_path`, at `config.at_ref`
+    /// when set, with authors under their canonical identities.
+    pub fn new(git_path: impl Into<PathBuf>, config: &GitConfig) -> Self {
+        Self {
+            root: git_path.into(),
+            git: config.clone(),
+            commits: OnceLock::new(),
+        }
+    }
+
+    /// Commits since `since` with their changes filtered by `filter`, newest
+    /// first, as [`GitRepo::log_with_stats_filtered`] returns them.
+    ///
+    /// `None` when `since` is not a relative duration (such as an absolute
+    /// date), which only git can apply; callers then ask git directly.
+    pub fn log_with_stats(
+        &self,
+        since: Option<&str>,
+        filter: &PathFilter,
+    ) -> Option<Result<Vec<Commit>>> {
+        let cutoff = match since {
+            Some(since) if !is_since_all(since) => Some(cutoff_timestamp(Some(since))?),
+            _ => None,
+        };
+        let commits = match self
+            .commits
+            .get_or_init(|| self.load().map_err(|e| e.to_string()))
+        {
+            Ok(commits) => commits,
+            Err(e) => return Some(Err(Error::git(e.clone()))),
+        };
+        let mut selected: Vec<Commit> = commits
+            .iter()
+            .filter(|commit| cutoff.is_none_or(|cutoff| commit.timestamp >= cutoff))
+            .cloned()
+            .collect();
+        filter.retain_commits(&mut selected);
+        Some(Ok(selected))
+    }
+
+    fn load(&self) -> Result<Vec<Commit>> {
+        GitRepo::open(&self.root)?
+            .at_ref(self.git.at_ref.as_deref())
+            .with_identities(&self.git)
+            .log_with_stats(None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{git, init_repo};
+
+    #[test]
+    fn test_serves_windows_and_filters_from_one_read() {
+        let repo = tempfile::TempDir::new().unwrap();
+        init_repo(repo.path());
+        std::fs::create_dir_all(repo.path().join("src")).unwrap();
+        std::fs::write(repo.path().join("src/a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(repo.path().join("README.md"), "# a\n").unwrap();
+        git(repo.path(), &["add", "."]);
+        git(
+            repo.path(),
+            &[
+                "commit",
+                "-q",
+                "-m",
+                "old",
+                "--date",
+                "2001-01-01T00:00:00Z",
+            ],
+        );
+        std::fs::write(repo.path().join("src/a.rs"), "fn a() { b() }\n").unwrap();
+        git(repo.path(), &["commit", "-q", "-am", "new"]);
+
+        let cache = CommitCache::new(repo.path(), &GitConfig::default());
+        let all = cache.log_with_stats(None, &PathFilter::default());
+        assert_eq!(all.unwrap().unwrap().len(), 2);
+
+        let recent = cache.log_with_stats(Some("30 days"), &PathFilter::default());
+        let recent = recent.unwrap().unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].message, "new");
+
+        let filter = PathFilter::new(None, Vec::new(), vec!["src/**".to_string()]);
+        let docs = cache.log_with_stats(Some("all"), &filter).unwrap().unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].files[0].path, PathBuf::from("README.md"));
+
+        assert!(cache.log_with_stats(Some("2024-01-01"), &filter).is_none());
+    }
+}
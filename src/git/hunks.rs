@@ -0,0 +1,252 @@
+//! Uncommitted changes split into hunks.
+//!
+//! gix has no index or worktree diff that yields hunks, so this reads
+//! `git diff --unified=0`, the same way clones shell out to `git`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChangeType, FileChange};
+use crate::core::{Error, Result};
+
+/// Which uncommitted changes to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffScope {
+    /// The index against HEAD: what the next commit would contain.
+    Staged,
+    /// The working tree against the index: edits not yet staged.
+    Worktree,
+}
+
+/// A contiguous block of changed lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    /// Added lines, without the leading `+`.
+    pub added: Vec<String>,
+}
+
+/// A changed file and its hunks.
+#[derive(Debug, Clone)]
+pub struct FileHunks {
+    pub change: FileChange,
+    pub hunks: Vec<Hunk>,
+}
+
+/// Read the uncommitted changes of the repository at `root`.
+pub fn uncommitted_changes(root: &Path, scope: DiffScope) -> Result<Vec<FileHunks>> {
+    let mut args = vec![
+        "-c",
+        "core.quotepath=off",
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--unified=0",
+        "-M",
+    ];
+    if scope == DiffScope::Staged {
+        args.push("--cached");
+    }
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| Error::git(format!("Failed to run git diff: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::git(format!("git diff failed: {}", stderr.trim())));
+    }
+    Ok(parse_unified_diff(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Paths with uncommitted changes in the repository at `root`, including
+/// untracked files, relative to the root and sorted.
+pub fn dirty_paths(root: &Path) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain=v1", "-z", "--untracked-files=all"])
+        .current_dir(root)
+        .output()
+        .map_err(|e| Error::git(format!("Failed to run git status: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::git(format!("git status failed: {}", stderr.trim())));
+    }
+    Ok(parse_porcelain_status(&output.stdout))
+}
+
+/// Parse `git status --porcelain=v1 -z` output into the changed paths.
+fn parse_porcelain_status(output: &[u8]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut entries = output.split(|&b| b == 0).filter(|e| e.len() > 3);
+    while let Some(entry) = entries.next() {
+        let (status, path) = entry.split_at(3);
+        paths.push(PathBuf::from(String::from_utf8_lossy(path).into_owned()));
+        // Renames and copies are followed by their original path.
+        if status.contains(&b'R') || status.contains(&b'C') {
+            entries.next();
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Parse `git diff --unified=0` output into per-file hunks.
+pub(crate) fn parse_unified_diff(text: &str) -> Vec<FileHunks> {
+    let mut files: Vec<FileHunks> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with("diff --git ") {
+            files.push(FileHunks {
+                change: FileChange {
+                    path: PathBuf::new(),
+                    additions: 0,
+                    deletions: 0,
+                    change_type: ChangeType::Modified,
+                },
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if let Some(header) = line.strip_prefix("@@ ") {
+            if let Some(hunk) = parse_hunk_header(header) {
+                file.hunks.push(hunk);
+            }
+        } else if let Some(hunk) = file.hunks.last_mut() {
+            // Inside a hunk: with zero context every line is `+`, `-` or `\`.
+            if let Some(added) = line.strip_prefix('+') {
+                hunk.added.push(added.to_string());
+                file.change.additions += 1;
+            } else if line.starts_with('-') {
+                file.change.deletions += 1;
+            }
+        } else if line.starts_with("new file mode") {
+            file.change.change_type = ChangeType::Added;
+        } else if line.starts_with("deleted file mode") {
+            file.change.change_type = ChangeType::Deleted;
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.change.change_type = ChangeType::Renamed;
+            file.change.path = PathBuf::from(path);
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            file.change.path = PathBuf::from(path.trim_end_matches('\t'));
+        } else if let Some(path) = line.strip_prefix("--- a/") {
+            // Deleted files have `+++ /dev/null`; keep the old path.
+            if file.change.path.as_os_str().is_empty() {
+                file.change.path = PathBuf::from(path.trim_end_matches('\t'));
+            }
+        }
+    }
+    files.retain(|f| !f.change.path.as_os_str().is_empty());
+    files
+}
+
+/// Parse `-a,b +c,d @@ ...`; a missing count means one line.
+fn parse_hunk_header(header: &str) -> Option<Hunk> {
+    let mut ranges = header.split_whitespace();
+    let (old_start, old_lines) = parse_range(ranges.next()?.strip_prefix('-')?)?;
+    let (new_start, new_lines) = parse_range(ranges.next()?.strip_prefix('+')?)?;
+    Some(Hunk {
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
+        added: Vec::new(),
+    })
+}
+
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3 +3,2 @@ fn a() {
+-    old();
++    new();
++    // TODO: tidy
+@@ -10,2 +11,0 @@ fn b() {
+-    gone();
+-    gone();
+diff --git a/new.py b/new.py
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/new.py
+@@ -0,0 +1 @@
++print('hi')
+diff --git a/old.go b/old.go
+deleted file mode 100644
+index 4444444..0000000
+--- a/old.go
++++ /dev/null
+@@ -1 +0,0 @@
+-package old
+";
+
+    #[test]
+    fn test_parse_porcelain_status() {
+        let output = b"M  src/lib.rs\0R  new.rs\0old.rs\0?? notes/todo.md\0 D gone.py\0";
+        assert_eq!(
+            parse_porcelain_status(output),
+            vec![
+                PathBuf::from("gone.py"),
+                PathBuf::from("new.rs"),
+                PathBuf::from("notes/todo.md"),
+                PathBuf::from("src/lib.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let files = parse_unified_diff(DIFF);
+        assert_eq!(files.len(), 3);
+
+        let lib = &files[0];
+        assert_eq!(lib.change.path, PathBuf::from("src/lib.rs"));
+        assert_eq!(lib.change.change_type, ChangeType::Modified);
+        assert_eq!((lib.change.additions, lib.change.deletions), (2, 3));
+        assert_eq!(lib.hunks.len(), 2);
+        assert_eq!(
+            (lib.hunks[0].old_start, lib.hunks[0].old_lines),
+            (3, 1),
+            "missing count means one line"
+        );
+        assert_eq!((lib.hunks[0].new_start, lib.hunks[0].new_lines), (3, 2));
+        assert_eq!(lib.hunks[0].added, vec!["    new();", "    // TODO: tidy"]);
+        assert_eq!((lib.hunks[1].new_start, lib.hunks[1].new_lines), (11, 0));
+
+        assert_eq!(files[1].change.change_type, ChangeType::Added);
+        assert_eq!(files[1].change.path, PathBuf::from("new.py"));
+        assert_eq!(files[2].change.change_type, ChangeType::Deleted);
+        assert_eq!(files[2].change.path, PathBuf::from("old.go"));
+        assert_eq!(files[2].change.deletions, 1);
+    }
+
+    #[test]
+    fn test_parse_hunk_header() {
+        let hunk = parse_hunk_header("-0,0 +1,12 @@").unwrap();
+        assert_eq!((hunk.old_start, hunk.old_lines), (0, 0));
+        assert_eq!((hunk.new_start, hunk.new_lines), (1, 12));
+        assert!(parse_hunk_header("garbage").is_none());
+    }
+}
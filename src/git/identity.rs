@@ -0,0 +1,236 @@
+//! Author identity normalization.
+//!
+//! Contributors often commit under several names and emails. [`Identities`]
+//! maps each of them to one canonical identity, from the repository's
+//! `.mailmap` (in git's format) and the `[git.identities]` config table, so
+//! analyzers that count authors count people rather than addresses.
+
+use std::collections::HashMap;
+
+use super::GitRepo;
+use crate::config::GitConfig;
+
+/// Canonical author identities.
+#[derive(Debug, Clone, Default)]
+pub struct Identities {
+    /// Mailmap entries for a commit name and email, both lowercased.
+    by_name_and_email: HashMap<(String, String), Replacement>,
+    /// Mailmap entries for a commit email, lowercased.
+    by_email: HashMap<String, Replacement>,
+    /// Config aliases, lowercased names or emails.
+    aliases: HashMap<String, Replacement>,
+}
+
+/// The canonical name and email an identity maps to; `None` keeps the
+/// commit's own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Replacement {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Replacement {
+    /// Fill the fields `other` sets, as later mailmap lines do in git.
+    fn merge(&mut self, other: Replacement) {
+        self.name = other.name.or(self.name.take());
+        self.email = other.email.or(self.email.take());
+    }
+
+    fn apply(&self, name: &mut String, email: &mut String) {
+        if let Some(canonical) = &self.name {
+            name.clone_from(canonical);
+        }
+        if let Some(canonical) = &self.email {
+            email.clone_from(canonical);
+        }
+    }
+}
+
+impl Identities {
+    /// The `.mailmap` at `repo`'s root when `config.mailmap` is set, plus
+    /// the `config.identities` aliases. A missing `.mailmap` is not an error.
+    pub fn load(repo: &GitRepo, config: &GitConfig) -> Self {
+        let mailmap = if config.mailmap {
+            repo.read_file(std::path::Path::new(".mailmap"))
+                .map(|bytes| Self::from_mailmap(&String::from_utf8_lossy(&bytes)))
+                .unwrap_or_default()
+        } else {
+            Self::default()
+        };
+        mailmap.with_aliases(&config.identities)
+    }
+
+    /// Parse a `.mailmap` file. Lines that don't parse are skipped.
+    ///
+    /// Supports git's four forms: `Name <commit>`, `<email> <commit>`,
+    /// `Name <email> <commit>` and `Name <email> Commit Name <commit>`.
+    /// Emails and names are matched case-insensitively.
+    pub fn from_mailmap(content: &str) -> Self {
+        let mut identities = Self::default();
+        for line in content.lines() {
+            let line = line.trim_start();
+            if line.starts_with('#') {
+                continue;
+            }
+            let entries = parse_mailmap_line(line);
+            let (replacement, commit_name, commit_email) = match entries.as_slice() {
+                [(name, email)] => (
+                    Replacement {
+                        name: non_empty(name),
+                        email: None,
+                    },
+                    "",
+                    email,
+                ),
+                [(name, email), (commit_name, commit_email), ..] => (
+                    Replacement {
+                        name: non_empty(name),
+                        email: non_empty(email),
+                    },
+                    commit_name.as_str(),
+                    commit_email,
+                ),
+                [] => continue,
+            };
+            if replacement == Replacement::default() {
+                continue;
+            }
+            let email = commit_email.to_lowercase();
+            let entry = if commit_name.is_empty() {
+                identities.by_email.entry(email).or_default()
+            } else {
+                identities
+                    .by_name_and_email
+                    .entry((commit_name.to_lowercase(), email))
+                    .or_default()
+            };
+            entry.merge(replacement);
+        }
+        identities
+    }
+
+    /// Add aliases keyed by canonical identity, `Name` or `Name <email>`,
+    /// each listing the names and emails that map to it.
+    pub fn with_aliases(mut self, aliases: &HashMap<String, Vec<String>>) -> Self {
+        for (canonical, names) in aliases {
+            let replacement = match parse_mailmap_line(canonical).first() {
+                Some((name, email)) => Replacement {
+                    name: non_empty(name),
+                    email: non_empty(email),
+                },
+                None => Replacement {
+                    name: non_empty(canonical.trim()),
+                    email: None,
+                },
+            };
+            for alias in names {
+                self.aliases
+                    .insert(alias.trim().to_lowercase(), replacement.clone());
+            }
+        }
+        self
+    }
+
+    /// Whether no identity is remapped.
+    pub fn is_empty(&self) -> bool {
+        self.by_name_and_email.is_empty() && self.by_email.is_empty() && self.aliases.is_empty()
+    }
+
+    /// Canonical name and email for an author. The mailmap is applied
+    /// first, then the aliases, matched on the mapped email or name.
+    pub fn resolve(&self, name: &str, email: &str) -> (String, String) {
+        let (mut name, mut email) = (name.to_string(), email.to_string());
+        let email_key = email.to_lowercase();
+        if let Some(replacement) = self
+            .by_name_and_email
+            .get(&(name.to_lowercase(), email_key.clone()))
+            .or_else(|| self.by_email.get(&email_key))
+        {
+            replacement.apply(&mut name, &mut email);
+        }
+        if let Some(replacement) = self
+            .aliases
+            .get(&email.to_lowercase())
+            .or_else(|| self.aliases.get(&name.to_lowercase()))
+        {
+            replacement.apply(&mut name, &mut email);
+        }
+        (name, email)
+    }
+}
+
+/// The `Name <email>` pairs on a mailmap line, with trimmed names. Text
+/// after the last `>` (such as a comment) is ignored.
+fn parse_mailmap_line(line: &str) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut rest = line;
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        entries.push((
+            rest[..open].trim().to_string(),
+            rest[open + 1..open + close].trim().to_string(),
+        ));
+        rest = &rest[open + close + 1..];
+    }
+    entries
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mailmap_forms() {
+        let identities = Identities::from_mailmap(
+            "# comment\n\
+             Ada Lovelace <ada@old.example.com>\n\
+             <ada@example.com> <ada@old.example.com>\n\
+             Grace Hopper <grace@example.com> <GRACE@laptop.local>\n\
+             Bob <bob@example.com> bobby <shared@example.com>\n",
+        );
+
+        assert_eq!(
+            identities.resolve("ada", "ada@old.example.com"),
+            ("Ada Lovelace".into(), "ada@example.com".into())
+        );
+        assert_eq!(
+            identities.resolve("grace", "grace@LAPTOP.local"),
+            ("Grace Hopper".into(), "grace@example.com".into())
+        );
+        assert_eq!(
+            identities.resolve("Bobby", "shared@example.com"),
+            ("Bob".into(), "bob@example.com".into())
+        );
+        assert_eq!(
+            identities.resolve("carol", "shared@example.com"),
+            ("carol".into(), "shared@example.com".into())
+        );
+    }
+
+    #[test]
+    fn test_aliases_apply_after_mailmap() {
+        let aliases = HashMap::from([(
+            "Ada Lovelace <ada@example.com>".to_string(),
+            vec!["ada@home.example.com".to_string(), "alovelace".to_string()],
+        )]);
+        let identities = Identities::from_mailmap("<ada@home.example.com> <ada@old.example.com>\n")
+            .with_aliases(&aliases);
+
+        assert_eq!(
+            identities.resolve("ada", "ada@old.example.com"),
+            ("Ada Lovelace".into(), "ada@example.com".into())
+        );
+        assert_eq!(
+            identities.resolve("ALovelace", "laptop@local"),
+            ("Ada Lovelace".into(), "ada@example.com".into())
+        );
+        assert!(!identities.is_empty());
+        assert!(Identities::default().is_empty());
+    }
+}
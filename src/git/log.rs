@@ -1,7 +1,7 @@
 //! Git log operations.
 
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use gix::Repository;
 use serde::{Deserialize, Serialize};
@@ -61,36 +61,43 @@ pub struct CommitStats {
     pub files_changed: usize,
 }
 
+/// A commit with its full message, for message-level analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitMessage {
+    /// Commit SHA.
+    pub sha: String,
+    /// Author name.
+    pub author: String,
+    /// Commit timestamp.
+    pub timestamp: i64,
+    /// First line of the message.
+    pub subject: String,
+    /// Message body after the subject, trimmed (empty when absent).
+    pub body: String,
+    /// Number of parent commits (2+ for merges).
+    pub parents: usize,
+}
+
+/// Directory to run git CLI commands in: the worktree, or the git directory
+/// itself for bare repositories.
+pub(crate) fn cli_dir(repo: &Repository) -> &Path {
+    repo.workdir().unwrap_or_else(|| repo.git_dir())
+}
+
 /// Get commit log from repository.
 ///
 /// This is a fast gix-based implementation that avoids spawning git CLI.
 /// If `paths` is provided, only commits that touch those paths are returned.
+/// History is walked from `rev`, or HEAD when `None`.
 pub fn get_log(
     repo: &Repository,
+    rev: Option<&str>,
     since: Option<&str>,
     paths: Option<&[PathBuf]>,
     limit: Option<usize>,
 ) -> Result<Vec<Commit>> {
-    let head = repo
-        .head_id()
-        .map_err(|e| Error::git(format!("Failed to get HEAD: {e}")))?;
-
-    let cutoff_time = since.and_then(parse_since_duration).map(|duration| {
-        let now = std::time::SystemTime::now();
-        now.checked_sub(duration)
-            .unwrap_or(std::time::UNIX_EPOCH)
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_secs() as i64)
-            .unwrap_or(0)
-    });
-
-    let walk = repo
-        .rev_walk([head])
-        .sorting(gix::revision::walk::Sorting::ByCommitTime(
-            gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
-        ))
-        .all()
-        .map_err(|e| Error::git(format!("Failed to walk commits: {e}")))?;
+    let cutoff_time = cutoff_timestamp(since);
+    let walk = walk_newest_first(repo, rev)?;
 
     let mut commits = Vec::new();
 
@@ -152,6 +159,88 @@ pub fn get_log(
     Ok(commits)
 }
 
+/// Get commits with their full messages, newest first.
+///
+/// Walks history from `rev`, or HEAD when `None`, stopping at the `since`
+/// cutoff or after `limit` commits.
+pub fn get_messages(
+    repo: &Repository,
+    rev: Option<&str>,
+    since: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<CommitMessage>> {
+    let cutoff_time = cutoff_timestamp(since);
+    let walk = walk_newest_first(repo, rev)?;
+
+    let mut messages = Vec::new();
+    for info in walk {
+        if limit.is_some_and(|max| messages.len() >= max) {
+            break;
+        }
+        let info = info.map_err(|e| Error::git(format!("Failed to read commit: {e}")))?;
+        let commit = info
+            .object()
+            .map_err(|e| Error::git(format!("Failed to get commit object: {e}")))?;
+
+        let author = commit.author().map_err(|e| Error::git(format!("{e}")))?;
+        let timestamp = author.seconds();
+        if cutoff_time.is_some_and(|cutoff| timestamp < cutoff) {
+            break;
+        }
+
+        let message = commit.message().map_err(|e| Error::git(format!("{e}")))?;
+        messages.push(CommitMessage {
+            sha: commit.id.to_string(),
+            author: author.name.to_string(),
+            timestamp,
+            subject: message.title.to_string().trim().to_string(),
+            body: message
+                .body
+                .map(|body| body.to_string().trim().to_string())
+                .unwrap_or_default(),
+            parents: commit.parent_ids().count(),
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Walk commits reachable from `rev` (or HEAD), newest first.
+fn walk_newest_first<'repo>(
+    repo: &'repo Repository,
+    rev: Option<&str>,
+) -> Result<gix::revision::Walk<'repo>> {
+    let head = match rev {
+        Some(rev) => repo
+            .rev_parse_single(rev.as_bytes())
+            .map_err(|e| Error::git(format!("Failed to resolve {rev}: {e}")))?
+            .detach(),
+        None => repo
+            .head_id()
+            .map_err(|e| Error::git(format!("Failed to get HEAD: {e}")))?
+            .detach(),
+    };
+
+    repo.rev_walk([head])
+        .sorting(gix::revision::walk::Sorting::ByCommitTime(
+            gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
+        ))
+        .all()
+        .map_err(|e| Error::git(format!("Failed to walk commits: {e}")))
+}
+
+/// Unix timestamp of the `since` cutoff, if one applies.
+pub(super) fn cutoff_timestamp(since: Option<&str>) -> Option<i64> {
+    since.and_then(parse_since_duration).map(|duration| {
+        let now = std::time::SystemTime::now();
+        now.checked_sub(duration)
+            .unwrap_or(std::time::UNIX_EPOCH)
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0)
+    })
+}
+
 /// Returns true if the since string means "all history" (no time limit).
 pub fn is_since_all(since: &str) -> bool {
     matches!(since.trim().to_lowercase().as_str(), "all" | "forever")
@@ -201,18 +290,19 @@ fn parse_since_duration(since: &str) -> Option<std::time::Duration> {
 /// Get commit log with file change statistics (numstat equivalent).
 ///
 /// Uses git CLI for performance - gix tree diff is ~160x slower.
+/// Non-empty `pathspecs` (relative to the repository root) are passed after
+/// `--` so git only diffs the matching paths. History is walked from `rev`,
+/// or HEAD when `None`.
 pub fn get_log_with_stats(
     repo: &Repository,
+    rev: Option<&str>,
     since: Option<&str>,
     limit: Option<usize>,
+    pathspecs: &[String],
 ) -> Result<Vec<Commit>> {
-    let repo_path = repo
-        .workdir()
-        .ok_or_else(|| Error::git("Not a work tree"))?;
-
     // Build git log command with numstat
     let mut cmd = std::process::Command::new("git");
-    cmd.current_dir(repo_path);
+    cmd.current_dir(cli_dir(repo));
     cmd.args(["log", "--format=%H|%an|%ae|%at|%s", "--numstat"]);
 
     if let Some(since_str) = since {
@@ -223,6 +313,15 @@ pub fn get_log_with_stats(
         cmd.arg(format!("-n{}", max));
     }
 
+    if let Some(rev) = rev {
+        cmd.arg(rev);
+    }
+
+    if !pathspecs.is_empty() {
+        cmd.arg("--");
+        cmd.args(pathspecs);
+    }
+
     let output = cmd
         .output()
         .map_err(|e| Error::git(format!("Failed to run git log: {e}")))?;
@@ -254,19 +353,20 @@ pub struct FileChurnEntry {
 /// avoiding the cost of scanning the entire history.
 pub fn get_file_churn(
     repo: &Repository,
+    rev: Option<&str>,
     paths: &[String],
 ) -> Result<HashMap<String, FileChurnEntry>> {
     if paths.is_empty() {
         return Ok(HashMap::new());
     }
 
-    let repo_path = repo
-        .workdir()
-        .ok_or_else(|| Error::git("Not a work tree"))?;
-
     let mut cmd = std::process::Command::new("git");
-    cmd.current_dir(repo_path);
-    cmd.args(["log", "--format=%H|%an", "--name-only", "--"]);
+    cmd.current_dir(cli_dir(repo));
+    cmd.args(["log", "--format=%H|%an", "--name-only"]);
+    if let Some(rev) = rev {
+        cmd.arg(rev);
+    }
+    cmd.arg("--");
     for p in paths {
         cmd.arg(p);
     }
@@ -322,6 +422,36 @@ fn parse_file_churn(output: &[u8], paths: &[String]) -> Result<HashMap<String, F
     Ok(churn)
 }
 
+/// Paths touched by any commit reachable from `to` but not from `from`.
+///
+/// Merge commits list their changes against every parent and renames both
+/// paths, so a file counts as touched whenever its history differs.
+pub fn get_touched_paths(repo: &Repository, from: &str, to: &str) -> Result<HashSet<PathBuf>> {
+    let output = std::process::Command::new("git")
+        .current_dir(cli_dir(repo))
+        .args([
+            "-c",
+            "core.quotepath=off",
+            "log",
+            "--format=",
+            "--name-only",
+        ])
+        .args(["-m", "--no-renames", &format!("{from}..{to}")])
+        .output()
+        .map_err(|e| Error::git(format!("Failed to run git log: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::git(format!("git log failed: {}", stderr)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
 /// Parse git log --numstat output into Commit structs.
 fn parse_git_log_numstat(output: &[u8]) -> Result<Vec<Commit>> {
     use std::io::{BufRead, BufReader};
@@ -994,7 +1124,7 @@ mod tests {
         // Get log for file1.rs only - should return 2 commits (Add file1, Modify file1)
         let file1_path = PathBuf::from("file1.rs");
         let paths = [file1_path];
-        let commits = get_log(&repo, None, Some(&paths), None).expect("failed to get log");
+        let commits = get_log(&repo, None, None, Some(&paths), None).expect("failed to get log");
 
         // After fix: Should return only 2 commits that touch file1.rs
         assert_eq!(
@@ -1110,11 +1240,12 @@ mod tests {
         let repo = gix::open(repo_path).expect("failed to open repo");
 
         // Without limit: all 5 commits
-        let all = get_log_with_stats(&repo, None, None).expect("failed to get log");
+        let all = get_log_with_stats(&repo, None, None, None, &[]).expect("failed to get log");
         assert_eq!(all.len(), 5);
 
         // With limit of 2: only 2 commits
-        let limited = get_log_with_stats(&repo, None, Some(2)).expect("failed to get limited log");
+        let limited =
+            get_log_with_stats(&repo, None, None, Some(2), &[]).expect("failed to get limited log");
         assert_eq!(
             limited.len(),
             2,
@@ -1164,11 +1295,11 @@ mod tests {
         let repo = gix::open(repo_path).expect("failed to open repo");
 
         // Without limit: all 5 commits
-        let all = get_log(&repo, None, None, None).expect("failed to get log");
+        let all = get_log(&repo, None, None, None, None).expect("failed to get log");
         assert_eq!(all.len(), 5);
 
         // With limit of 3: only 3 commits
-        let limited = get_log(&repo, None, None, Some(3)).expect("failed to get limited log");
+        let limited = get_log(&repo, None, None, None, Some(3)).expect("failed to get limited log");
         assert_eq!(
             limited.len(),
             3,
@@ -1176,4 +1307,46 @@ mod tests {
             limited.len()
         );
     }
+
+    #[test]
+    fn test_get_messages_returns_subject_and_body() {
+        use std::process::Command;
+
+        let temp = tempfile::tempdir().unwrap();
+        let repo_path = temp.path();
+        for args in [
+            vec!["init"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test User"],
+            vec!["commit", "--allow-empty", "-m", "chore: init"],
+            vec![
+                "commit",
+                "--allow-empty",
+                "-m",
+                "feat(cli): add flag",
+                "-m",
+                "Closes #12",
+            ],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(repo_path)
+                .output()
+                .expect("failed to run git");
+        }
+
+        let repo = gix::open(repo_path).expect("failed to open repo");
+        let messages = get_messages(&repo, None, None, None).expect("failed to get messages");
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].subject, "feat(cli): add flag");
+        assert_eq!(messages[0].body, "Closes #12");
+        assert_eq!(messages[0].parents, 1);
+        assert_eq!(messages[1].subject, "chore: init");
+        assert!(messages[1].body.is_empty());
+        assert_eq!(messages[1].parents, 0);
+
+        let limited = get_messages(&repo, None, None, Some(1)).unwrap();
+        assert_eq!(limited.len(), 1);
+    }
 }
@@ -1,41 +1,93 @@
 //! Git operations for repository analysis.
 
 mod blame;
+mod blame_cache;
+mod changed_lines;
+mod commit_cache;
+mod forge;
+mod hunks;
+mod identity;
 mod log;
+mod pathspec;
 mod remote;
 
 use std::path::{Path, PathBuf};
 
 use gix::Repository;
 
-use crate::core::{Error, Result};
+use crate::config::GitConfig;
+use crate::core::{ContentSource, Error, Result, TreeSource};
 
-pub use blame::BlameInfo;
+pub use blame::{BlameInfo, LineOrigin};
+pub use blame_cache::BlameCache;
+pub use changed_lines::ChangedLines;
+pub use commit_cache::CommitCache;
+pub use forge::{parse_remote, Forge, ForgeClient, PullRequest, ReviewState};
+pub use hunks::{DiffScope, FileHunks, Hunk};
+pub use identity::Identities;
 pub use log::{
-    is_since_all, parse_since_to_days, ChangeType, Commit, CommitStats, FileChange, FileChurnEntry,
+    is_since_all, parse_since_to_days, ChangeType, Commit, CommitMessage, CommitStats, FileChange,
+    FileChurnEntry,
 };
+pub use pathspec::PathFilter;
 pub use remote::{clone_remote, is_remote_repo, CloneOptions};
 
 /// Git repository wrapper for analysis operations.
 pub struct GitRepo {
     /// The gix repository handle.
     repo: Repository,
-    /// Repository root path.
+    /// Repository root path (the git directory for bare repositories).
     root: PathBuf,
+    /// Revision history and file contents are read at, instead of HEAD and
+    /// the worktree.
+    rev: Option<String>,
+    /// Canonical author identities applied to commits and blame.
+    identities: Identities,
 }
 
 impl GitRepo {
     /// Open a git repository at the given path.
+    ///
+    /// Bare repositories are supported; their root is the git directory and
+    /// file contents are read from the object database at HEAD (or the
+    /// revision set with [`GitRepo::at_ref`]).
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let repo =
             gix::open(path).map_err(|e| Error::git(format!("Failed to open repository: {e}")))?;
-        let root = repo
-            .workdir()
-            .ok_or_else(|| Error::git("Not a work tree"))?
-            .to_path_buf();
+        let root = repo.workdir().unwrap_or(repo.git_dir()).to_path_buf();
+
+        Ok(Self {
+            repo,
+            root,
+            rev: None,
+            identities: Identities::default(),
+        })
+    }
+
+    /// Read history and file contents at `rev` instead of HEAD and the worktree.
+    pub fn at_ref(mut self, rev: Option<&str>) -> Self {
+        self.rev = rev.map(str::to_string);
+        self
+    }
 
-        Ok(Self { repo, root })
+    /// Report commit and blame authors under their canonical identities,
+    /// from `.mailmap` and `[git] identities`. Call after
+    /// [`GitRepo::at_ref`]: the `.mailmap` is read at the analyzed revision.
+    pub fn with_identities(mut self, config: &GitConfig) -> Self {
+        self.identities = Identities::load(&self, config);
+        self
+    }
+
+    /// Replace commit authors with their canonical identities.
+    fn resolve_authors(&self, mut commits: Vec<Commit>) -> Vec<Commit> {
+        if !self.identities.is_empty() {
+            for commit in &mut commits {
+                (commit.author, commit.email) =
+                    self.identities.resolve(&commit.author, &commit.email);
+            }
+        }
+        commits
     }
 
     /// Get the repository root path.
@@ -43,6 +95,45 @@ impl GitRepo {
         &self.root
     }
 
+    /// Whether the repository has no worktree.
+    pub fn is_bare(&self) -> bool {
+        self.repo.workdir().is_none()
+    }
+
+    /// Whether the repository is a shallow clone with truncated history.
+    pub fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
+
+    /// The revision set with [`GitRepo::at_ref`], if any.
+    pub fn rev(&self) -> Option<&str> {
+        self.rev.as_deref()
+    }
+
+    /// Whether file contents must come from the object database rather than
+    /// the filesystem.
+    pub fn reads_from_objects(&self) -> bool {
+        self.is_bare() || self.rev.is_some()
+    }
+
+    /// A content source for the tree at the configured revision (HEAD by default).
+    pub fn tree_source(&self) -> Result<TreeSource> {
+        TreeSource::new(self.repo.git_dir(), self.rev.as_deref().unwrap_or("HEAD"))
+    }
+
+    /// Read a file relative to the repository root.
+    ///
+    /// Reads from the worktree unless the repository is bare or a revision
+    /// was set, in which case the blob is read from the object database.
+    pub fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if self.reads_from_objects() {
+            self.tree_source()?.read(relative)
+        } else {
+            std::fs::read(self.root.join(relative)).map_err(Error::Io)
+        }
+    }
+
     /// Check if path is inside this repository.
     pub fn contains(&self, path: &Path) -> bool {
         path.starts_with(&self.root)
@@ -66,6 +157,13 @@ impl GitRepo {
         }
     }
 
+    /// Fetch URL of the remote called `name`, if configured.
+    pub fn remote_url(&self, name: &str) -> Option<String> {
+        let remote = self.repo.find_remote(name).ok()?;
+        let url = remote.url(gix::remote::Direction::Fetch)?;
+        Some(url.to_bstring().to_string())
+    }
+
     /// Get the HEAD commit SHA.
     pub fn head_sha(&self) -> Result<String> {
         let head = self
@@ -75,6 +173,16 @@ impl GitRepo {
         Ok(head.to_string())
     }
 
+    /// Get the commit SHA of the configured revision (HEAD by default).
+    pub fn rev_sha(&self) -> Result<String> {
+        let rev = self.rev.as_deref().unwrap_or("HEAD");
+        let id = self
+            .repo
+            .rev_parse_single(format!("{rev}^{{commit}}").as_bytes())
+            .map_err(|e| Error::git(format!("Invalid ref '{rev}': {e}")))?;
+        Ok(id.to_string())
+    }
+
     /// Get commit log with optional path filter.
     pub fn log(
         &self,
@@ -82,12 +190,56 @@ impl GitRepo {
         paths: Option<&[PathBuf]>,
         limit: Option<usize>,
     ) -> Result<Vec<Commit>> {
-        log::get_log(&self.repo, since, paths, limit)
+        log::get_log(&self.repo, self.rev(), since, paths, limit)
+            .map(|commits| self.resolve_authors(commits))
+    }
+
+    /// Get commits with their full messages, newest first.
+    pub fn messages(
+        &self,
+        since: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<CommitMessage>> {
+        log::get_messages(&self.repo, self.rev(), since, limit)
     }
 
     /// Get commit log with file change statistics (equivalent to git log --numstat).
     pub fn log_with_stats(&self, since: Option<&str>, limit: Option<usize>) -> Result<Vec<Commit>> {
-        log::get_log_with_stats(&self.repo, since, limit)
+        log::get_log_with_stats(&self.repo, self.rev(), since, limit, &[])
+            .map(|commits| self.resolve_authors(commits))
+    }
+
+    /// Like [`GitRepo::log_with_stats`], restricted to a path filter.
+    ///
+    /// The filter's pathspecs are applied by git while walking history, then
+    /// the exact filter is applied to the returned file changes.
+    pub fn log_with_stats_filtered(
+        &self,
+        since: Option<&str>,
+        limit: Option<usize>,
+        filter: &PathFilter,
+    ) -> Result<Vec<Commit>> {
+        let mut commits =
+            log::get_log_with_stats(&self.repo, self.rev(), since, limit, &filter.pathspecs())?;
+        filter.retain_commits(&mut commits);
+        Ok(self.resolve_authors(commits))
+    }
+
+    /// [`Self::log_with_stats_filtered`] limited to `paths`, relative to the
+    /// repository root, so git only diffs those files.
+    pub fn log_with_stats_for_paths(
+        &self,
+        since: Option<&str>,
+        paths: &[&Path],
+        filter: &PathFilter,
+    ) -> Result<Vec<Commit>> {
+        let pathspecs: Vec<String> = paths
+            .iter()
+            .map(|path| format!(":(literal){}", path.display()))
+            .collect();
+        let mut commits = log::get_log_with_stats(&self.repo, self.rev(), since, None, &pathspecs)?;
+        filter.retain_commits(&mut commits);
+        Ok(self.resolve_authors(commits))
     }
 
     /// Get per-file churn (commit count + authors) for specific paths.
@@ -98,12 +250,17 @@ impl GitRepo {
         &self,
         paths: &[String],
     ) -> Result<std::collections::HashMap<String, FileChurnEntry>> {
-        log::get_file_churn(&self.repo, paths)
+        log::get_file_churn(&self.repo, self.rev(), paths)
+    }
+
+    /// Paths touched by commits since `from`, up to the analyzed revision.
+    pub fn touched_paths(&self, from: &str) -> Result<std::collections::HashSet<PathBuf>> {
+        log::get_touched_paths(&self.repo, from, self.rev().unwrap_or("HEAD"))
     }
 
     /// Get blame information for a file.
     pub fn blame(&self, path: &Path) -> Result<BlameInfo> {
-        blame::get_blame(&self.repo, &self.root, path)
+        blame::get_blame(&self.repo, &self.root, self.rev(), path, &self.identities)
     }
 
     /// Get commit statistics for a specific commit.
@@ -130,6 +287,16 @@ impl GitRepo {
     pub fn commit_count(&self, from: &str, to: &str) -> Result<i32> {
         log::get_commit_count(&self.repo, from, to)
     }
+
+    /// Paths with uncommitted changes, including untracked files.
+    pub fn dirty_paths(&self) -> Result<Vec<PathBuf>> {
+        hunks::dirty_paths(&self.root)
+    }
+
+    /// Get the staged or unstaged changes, split into hunks.
+    pub fn uncommitted_changes(&self, scope: DiffScope) -> Result<Vec<FileHunks>> {
+        hunks::uncommitted_changes(&self.root, scope)
+    }
 }
 
 #[cfg(test)]
@@ -163,6 +330,166 @@ mod tests {
             .expect("failed to commit");
     }
 
+    #[test]
+    fn test_log_with_stats_filtered_pushes_down_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        init_git_repo(temp.path());
+        for (dir, file) in [("app", "a.rs"), ("vendor", "b.rs"), ("app", "c.rs")] {
+            std::fs::create_dir_all(temp.path().join(dir)).unwrap();
+            std::fs::write(temp.path().join(dir).join(file), "fn f() {}").unwrap();
+            Command::new("git")
+                .args(["add", "."])
+                .current_dir(temp.path())
+                .output()
+                .expect("failed to add");
+            make_commit(temp.path(), &format!("add {dir}/{file}"));
+        }
+        let repo = GitRepo::open(temp.path()).unwrap();
+
+        let all = repo.log_with_stats(None, None).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let no_vendor = PathFilter::new(None, vec![], vec!["vendor/**".to_string()]);
+        let commits = repo
+            .log_with_stats_filtered(None, None, &no_vendor)
+            .unwrap();
+        assert_eq!(commits.len(), 2);
+        assert!(commits
+            .iter()
+            .flat_map(|c| &c.files)
+            .all(|f| f.path.starts_with("app")));
+
+        let scoped = PathFilter::new(Some("vendor".to_string()), vec![], vec![]);
+        let commits = repo.log_with_stats_filtered(None, None, &scoped).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].files[0].path, PathBuf::from("vendor/b.rs"));
+    }
+
+    #[test]
+    fn test_log_resolves_mailmap_identities() {
+        let temp = tempfile::tempdir().unwrap();
+        init_git_repo(temp.path());
+        std::fs::write(
+            temp.path().join(".mailmap"),
+            "Test User <test@example.com> <old@laptop.local>\n",
+        )
+        .unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(temp.path())
+            .output()
+            .expect("failed to add");
+        make_commit(temp.path(), "add mailmap");
+        Command::new("git")
+            .args(["-c", "user.name=tuser", "-c", "user.email=old@laptop.local"])
+            .args(["commit", "--allow-empty", "-m", "from laptop"])
+            .current_dir(temp.path())
+            .output()
+            .expect("failed to commit");
+
+        let raw = GitRepo::open(temp.path()).unwrap();
+        assert_eq!(raw.log(None, None, None).unwrap()[0].author, "tuser");
+
+        let mut config = GitConfig::default();
+        let repo = GitRepo::open(temp.path()).unwrap().with_identities(&config);
+        let authors: Vec<(String, String)> = repo
+            .log(None, None, None)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.author, c.email))
+            .collect();
+        let canonical = ("Test User".to_string(), "test@example.com".to_string());
+        assert_eq!(authors, vec![canonical.clone(), canonical]);
+
+        config.mailmap = false;
+        let repo = GitRepo::open(temp.path()).unwrap().with_identities(&config);
+        assert_eq!(repo.log(None, None, None).unwrap()[0].author, "tuser");
+    }
+
+    /// Commit `lib.rs` twice and return a bare clone of the repository.
+    fn bare_clone_with_history(temp: &Path) -> PathBuf {
+        let work = temp.join("work");
+        std::fs::create_dir_all(&work).unwrap();
+        init_git_repo(&work);
+        for (content, message) in [("fn one() {}\n", "first"), ("fn two() {}\n", "second")] {
+            std::fs::write(work.join("lib.rs"), content).unwrap();
+            Command::new("git")
+                .args(["add", "."])
+                .current_dir(&work)
+                .output()
+                .expect("failed to add");
+            make_commit(&work, message);
+        }
+        Command::new("git")
+            .args(["tag", "v1", "HEAD~1"])
+            .current_dir(&work)
+            .output()
+            .expect("failed to tag");
+        let bare = temp.join("mirror.git");
+        Command::new("git")
+            .args(["clone", "--bare", "--quiet"])
+            .arg(&work)
+            .arg(&bare)
+            .output()
+            .expect("failed to clone");
+        bare
+    }
+
+    #[test]
+    fn test_git_repo_open_bare() {
+        let temp = tempfile::tempdir().unwrap();
+        let bare = bare_clone_with_history(temp.path());
+
+        let repo = GitRepo::open(&bare).unwrap();
+        assert!(repo.is_bare());
+        assert!(repo.reads_from_objects());
+        assert_eq!(
+            repo.root().canonicalize().unwrap(),
+            bare.canonicalize().unwrap()
+        );
+
+        assert_eq!(repo.log_with_stats(None, None).unwrap().len(), 2);
+        assert_eq!(
+            repo.read_file(Path::new("lib.rs")).unwrap(),
+            b"fn two() {}\n"
+        );
+        let blame = repo.blame(Path::new("lib.rs")).unwrap();
+        assert_eq!(blame.lines.len(), 1);
+        assert!(blame.authors.contains_key("Test User"));
+    }
+
+    #[test]
+    fn test_git_repo_at_ref() {
+        let temp = tempfile::tempdir().unwrap();
+        let bare = bare_clone_with_history(temp.path());
+
+        let repo = GitRepo::open(&bare).unwrap().at_ref(Some("v1"));
+        assert_eq!(repo.rev(), Some("v1"));
+
+        let commits = repo.log_with_stats(None, None).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "first");
+        assert_eq!(repo.log(None, None, None).unwrap().len(), 1);
+        assert_eq!(
+            repo.read_file(Path::new("lib.rs")).unwrap(),
+            b"fn one() {}\n"
+        );
+        let churn = repo.file_churn(&["lib.rs".to_string()]).unwrap();
+        assert_eq!(churn["lib.rs"].commit_count, 1);
+    }
+
+    #[test]
+    fn test_git_repo_worktree_reads_filesystem() {
+        let temp = tempfile::tempdir().unwrap();
+        init_git_repo(temp.path());
+        std::fs::write(temp.path().join("a.rs"), "uncommitted").unwrap();
+
+        let repo = GitRepo::open(temp.path()).unwrap();
+        assert!(!repo.is_bare());
+        assert!(!repo.reads_from_objects());
+        assert_eq!(repo.read_file(Path::new("a.rs")).unwrap(), b"uncommitted");
+    }
+
     #[test]
     fn test_git_repo_open_not_a_repo() {
         let temp = tempfile::tempdir().unwrap();
@@ -300,6 +627,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_git_repo_touched_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        init_git_repo(temp.path());
+        for (file, content) in [("a.rs", "fn a() {}"), ("b.rs", "fn b() {}"), ("a.rs", "")] {
+            std::fs::write(temp.path().join(file), content).unwrap();
+            Command::new("git")
+                .args(["add", "."])
+                .current_dir(temp.path())
+                .output()
+                .expect("failed to add");
+            make_commit(temp.path(), &format!("write {file}"));
+        }
+        let repo = GitRepo::open(temp.path()).unwrap();
+
+        let touched = repo.touched_paths("HEAD~2").unwrap();
+        assert_eq!(touched.len(), 2);
+        assert!(touched.contains(Path::new("a.rs")));
+        assert!(touched.contains(Path::new("b.rs")));
+        assert!(repo.touched_paths("HEAD").unwrap().is_empty());
+    }
+
     #[test]
     fn test_git_repo_merge_base() {
         let temp = tempfile::tempdir().unwrap();
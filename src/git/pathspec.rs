@@ -0,0 +1,287 @@
+//! Path filters pushed down into `git log`.
+//!
+//! History-based analyzers (churn, temporal coupling) walk `git log` for the
+//! whole repository. When the analysis is scoped to a subdirectory or
+//! narrowed with `--glob`/`--exclude`/`exclude_patterns`, the same filters are
+//! passed to git as pathspecs so it never diffs trees for unrelated paths.
+//!
+//! Pathspecs are conservative: git glob semantics differ slightly from
+//! globset (`*` doesn't cross `/` in git), so only patterns whose git meaning
+//! is at least as broad as the file set's are pushed down, and
+//! [`PathFilter::matches`] re-applies the exact filters to the result.
+
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use super::Commit;
+use crate::core::{AnalysisContext, DEFAULT_IGNORED_DIRS};
+
+/// Path scope and glob filters for history-based analysis.
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    /// Analysis root relative to the repository root (`None` at the top).
+    prefix: Option<String>,
+    include_patterns: Vec<String>,
+    exclude_patterns: Vec<String>,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathFilter {
+    /// Create a filter from a scope prefix and glob patterns relative to it.
+    pub fn new(
+        prefix: Option<String>,
+        include_patterns: Vec<String>,
+        exclude_patterns: Vec<String>,
+    ) -> Self {
+        let prefix = prefix
+            .map(|p| p.trim_matches('/').to_string())
+            .filter(|p| !p.is_empty() && p != ".");
+        Self {
+            prefix,
+            include: build_glob_set(&include_patterns),
+            exclude: build_glob_set(&exclude_patterns),
+            include_patterns,
+            exclude_patterns,
+        }
+    }
+
+    /// Build the filter for an analysis context: its root relative to the
+    /// repository, the file set's glob filters, config excludes, and the
+    /// default ignored directories when built assets are excluded.
+    pub fn from_context(ctx: &AnalysisContext<'_>, repo_root: &Path) -> Self {
+        let prefix = relative_prefix(ctx.root, repo_root);
+
+        let mut exclude_patterns: Vec<String> = ctx.files.exclude_patterns().to_vec();
+        for pattern in &ctx.config.exclude_patterns {
+            if !exclude_patterns.contains(pattern) {
+                exclude_patterns.push(pattern.clone());
+            }
+        }
+        if ctx.config.exclude_built_assets {
+            exclude_patterns.extend(DEFAULT_IGNORED_DIRS.iter().map(|d| format!("**/{d}/**")));
+        }
+
+        Self::new(
+            prefix,
+            ctx.files.include_patterns().to_vec(),
+            exclude_patterns,
+        )
+    }
+
+    /// Returns true if no filtering applies.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_none()
+            && self.include_patterns.is_empty()
+            && self.exclude_patterns.is_empty()
+    }
+
+    /// Pathspecs (relative to the repository root) for `git log -- <specs>`.
+    pub fn pathspecs(&self) -> Vec<String> {
+        let base = self
+            .prefix
+            .as_ref()
+            .map(|p| format!("{p}/"))
+            .unwrap_or_default();
+        let mut specs = Vec::new();
+
+        // Slash-free includes match file names at any depth in both globset
+        // (via the file-name match) and git (via `**/`).
+        let includes_pushable = !self.include_patterns.is_empty()
+            && self.include_patterns.iter().all(|p| !p.contains('/'));
+        if includes_pushable {
+            specs.extend(
+                self.include_patterns
+                    .iter()
+                    .map(|p| format!(":(glob){base}**/{p}")),
+            );
+        } else if let Some(prefix) = &self.prefix {
+            specs.push(format!(":(literal){prefix}"));
+        }
+
+        // A git pathspec that names a directory also excludes its contents,
+        // which globset doesn't, so only push directory globs (`dir/**`) and
+        // file-name wildcards.
+        for pattern in &self.exclude_patterns {
+            let last = pattern.rsplit('/').next().unwrap_or(pattern);
+            if pattern.ends_with("/**") || (last != "**" && last.contains('*')) {
+                let anchored = if pattern.contains('/') {
+                    pattern.clone()
+                } else {
+                    format!("**/{pattern}")
+                };
+                specs.push(format!(":(exclude,glob){base}{anchored}"));
+            }
+        }
+
+        specs
+    }
+
+    /// A repository-relative path made relative to the analysis root, or
+    /// `None` if it lies outside it.
+    pub fn relative_path<'a>(&self, path: &'a str) -> Option<&'a str> {
+        match &self.prefix {
+            Some(prefix) => path
+                .strip_prefix(prefix.as_str())
+                .and_then(|rest| rest.strip_prefix('/')),
+            None => Some(path),
+        }
+    }
+
+    /// Returns true if a repository-relative path passes the filter.
+    pub fn matches(&self, path: &str) -> bool {
+        let Some(rel) = self.relative_path(path) else {
+            return false;
+        };
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+
+        if let Some(include) = &self.include {
+            if !include.is_match(rel) && !include.is_match(name) {
+                return false;
+            }
+        }
+        if let Some(exclude) = &self.exclude {
+            if exclude.is_match(rel) || exclude.is_match(name) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Drop file changes outside the filter, and commits left with none.
+    pub fn retain_commits(&self, commits: &mut Vec<Commit>) {
+        if self.is_empty() {
+            return;
+        }
+        for commit in commits.iter_mut() {
+            commit
+                .files
+                .retain(|f| self.matches(&f.path.to_string_lossy()));
+        }
+        commits.retain(|c| !c.files.is_empty());
+    }
+}
+
+/// `root` relative to `repo_root` with forward slashes, if it is inside it.
+fn relative_prefix(root: &Path, repo_root: &Path) -> Option<String> {
+    let root = root.canonicalize().ok()?;
+    let repo_root = repo_root.canonicalize().ok()?;
+    let rel = root.strip_prefix(&repo_root).ok()?;
+    let rel = rel.to_string_lossy().replace('\\', "/");
+    (!rel.is_empty()).then_some(rel)
+}
+
+fn build_glob_set(patterns: &[String]) -> Option<GlobSet> {
+    if patterns.is_empty() {
+        return None;
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder.build().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = PathFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.pathspecs().is_empty());
+        assert!(filter.matches("vendor/lib.go"));
+    }
+
+    #[test]
+    fn test_prefix_scopes_paths() {
+        let filter = PathFilter::new(Some("services/api/".to_string()), vec![], vec![]);
+        assert_eq!(filter.pathspecs(), vec![":(literal)services/api"]);
+        assert!(filter.matches("services/api/main.go"));
+        assert!(!filter.matches("services/apiary/main.go"));
+        assert!(!filter.matches("web/app.ts"));
+        assert_eq!(
+            filter.relative_path("services/api/main.go"),
+            Some("main.go")
+        );
+        assert_eq!(filter.relative_path("web/app.ts"), None);
+    }
+
+    #[test]
+    fn test_dot_prefix_is_top_level() {
+        let filter = PathFilter::new(Some(".".to_string()), vec![], vec![]);
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn test_slash_free_includes_are_pushed_down() {
+        let filter = PathFilter::new(Some("app".to_string()), strings(&["*.rs"]), vec![]);
+        assert_eq!(filter.pathspecs(), vec![":(glob)app/**/*.rs"]);
+        assert!(filter.matches("app/src/lib.rs"));
+        assert!(!filter.matches("app/src/lib.go"));
+    }
+
+    #[test]
+    fn test_includes_with_slashes_fall_back_to_prefix() {
+        let filter = PathFilter::new(Some("app".to_string()), strings(&["src/*.rs"]), vec![]);
+        assert_eq!(filter.pathspecs(), vec![":(literal)app"]);
+        assert!(filter.matches("app/src/lib.rs"));
+        assert!(!filter.matches("app/tests/it.rs"));
+    }
+
+    #[test]
+    fn test_excludes_pushed_down_only_when_safe() {
+        let filter = PathFilter::new(
+            None,
+            vec![],
+            strings(&["vendor/**", "*.pb.go", "generated"]),
+        );
+        assert_eq!(
+            filter.pathspecs(),
+            vec![":(exclude,glob)vendor/**", ":(exclude,glob)**/*.pb.go"]
+        );
+        assert!(!filter.matches("vendor/x/y.go"));
+        assert!(!filter.matches("api/types.pb.go"));
+        assert!(filter.matches("generated/keep.go"));
+        assert!(!filter.matches("generated"));
+    }
+
+    #[test]
+    fn test_retain_commits_drops_filtered_files() {
+        use crate::git::{ChangeType, FileChange};
+        use std::path::PathBuf;
+
+        let change = |path: &str| FileChange {
+            path: PathBuf::from(path),
+            additions: 1,
+            deletions: 0,
+            change_type: ChangeType::Modified,
+        };
+        let commit = |files: Vec<FileChange>| Commit {
+            sha: "abc".to_string(),
+            author: "a".to_string(),
+            email: "a@example.com".to_string(),
+            timestamp: 0,
+            message: String::new(),
+            files,
+        };
+        let mut commits = vec![
+            commit(vec![change("app/a.rs"), change("vendor/b.rs")]),
+            commit(vec![change("vendor/c.rs")]),
+        ];
+
+        PathFilter::new(None, vec![], strings(&["vendor/**"])).retain_commits(&mut commits);
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].files.len(), 1);
+        assert_eq!(commits[0].files[0].path, PathBuf::from("app/a.rs"));
+    }
+}
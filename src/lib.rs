@@ -22,19 +22,32 @@
 //! let result = analyzer.analyze(&ctx).unwrap();
 //! println!("Analyzed {} functions", result.summary.total_functions);
 //! ```
+//!
+//! Analyzers chosen at runtime can be run by name with
+//! [`core::run_by_name`], which returns a typed [`core::AnalyzerResult`].
 
 pub mod analyzers;
 pub mod cli;
 pub mod config;
 pub mod context;
 pub mod core;
+pub mod estimate;
+pub mod fix;
 pub mod git;
+pub mod manifest;
 pub mod mcp;
 pub mod output;
 pub mod parser;
+pub mod query;
 pub mod report;
+pub mod rollout;
 pub mod score;
 pub mod semantic;
+pub mod status;
 pub mod symbol;
+pub mod tui;
+
+#[cfg(test)]
+mod test_support;
 
 pub use core::{AnalysisContext, AnalysisResult, Analyzer};
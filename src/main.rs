@@ -1,27 +1,43 @@
 //! Omen CLI - Multi-language code analysis for AI assistants.
 
-use std::io::stdout;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::ThreadPoolBuilder;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 use omen::cli::{
-    AnalyzerArgs, Cli, Command, ComplexityArgs, ImpactArgs, McpSubcommand, MutationArgs,
-    MutationSubcommand, MutationTrainArgs, OutlineArgs, OutputFormat, ReportSubcommand, ScoreArgs,
-    ScoreSubcommand, SearchSubcommand, SymbolArgs,
+    AllArgs, AnalyzerArgs, BaselineArgs, Cli, CohesionArgs, Command, CommitsArgs, CompactMode,
+    ComplexityArgs, ContextExport, DeadcodeArgs, DefectArgs, DiffArgs, FailOn, FixArgs, GraphArgs,
+    GraphFormat, HotspotArgs, HotspotNormalization, ImpactArgs, McpSubcommand, MutationArgs,
+    MutationSubcommand, MutationTrainArgs, OutlineArgs, OutputFormat, PrioritizeBy, QueryArgs,
+    ReportSubcommand, RunArgs, ScoreArgs, ScoreBadgeArgs, ScoreSubcommand, SearchSubcommand,
+    SymbolArgs, Tokenizer,
 };
 use omen::config::Config;
 use omen::core::progress::is_tty;
-use omen::core::{AnalysisContext, Analyzer, FileSet};
-use omen::git::{clone_remote, is_remote_repo, CloneOptions};
+use omen::core::timings::timings_path;
+use omen::core::{
+    AnalysisContext, Analyzer, AnalyzerResult, Baseline, BaselineEntry, Deadline, FileSet,
+    Severity, SeverityCounts, Timings, WarmStart,
+};
+use omen::estimate::{
+    ALL_COMBINED_JOBS, ALL_FILE_JOBS, ALL_GIT_JOBS, REPORT_FILE_JOBS, REPORT_GIT_JOBS,
+    REPORT_MIXED_JOBS,
+};
+use omen::git::{clone_remote, is_remote_repo, BlameCache, CloneOptions, CommitCache};
+use omen::manifest::Manifest;
 use omen::mcp::McpServer;
+use omen::output::sink::{self, Destination, Output};
 use omen::output::{format_with_limits, Format};
+use omen::parser::ParseCache;
 
 fn main() -> ExitCode {
     // Initialize tracing
@@ -30,9 +46,12 @@ fn main() -> ExitCode {
         .with(EnvFilter::from_default_env())
         .init();
 
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let command_name = matches.subcommand_name().unwrap_or("omen").to_string();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
-    match run(cli) {
+    let result = run_clap_only(&cli).unwrap_or_else(|| run(cli, &command_name));
+    match result {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
             eprintln!("Error: {e:#}");
@@ -41,9 +60,38 @@ fn main() -> ExitCode {
     }
 }
 
+/// Run `completions` and `manpages`, which depend only on the clap
+/// definition, before any config or repository is read: a broken
+/// `omen.toml` must not stop them. `None` for every other command.
+fn run_clap_only(cli: &Cli) -> Option<omen::core::Result<()>> {
+    let result = match &cli.command {
+        Command::Completions(args) => match cli.output_file {
+            Some(ref file) => std::fs::File::create(file)
+                .map(|mut file| omen::cli::write_completions(args.shell, &mut file)),
+            None => {
+                omen::cli::write_completions(args.shell, &mut std::io::stdout().lock());
+                Ok(())
+            }
+        },
+        Command::Manpages(args) => omen::cli::write_manpages(&args.dir).map(|written| {
+            eprintln!(
+                "Wrote {} man pages to {}",
+                written.len(),
+                args.dir.display()
+            );
+        }),
+        _ => return None,
+    };
+    Some(result.map_err(Into::into))
+}
+
 /// Resolve the repository path, cloning if it's a remote reference.
 /// Returns (resolved_path, cleanup_path) where cleanup_path is Some if we cloned a temp repo.
 fn resolve_repo_path(cli: &Cli) -> omen::core::Result<(PathBuf, Option<PathBuf>)> {
+    if let Some(ref git_dir) = cli.git_dir {
+        return Ok((git_dir.clone(), None));
+    }
+
     let path_str = cli.path.to_string_lossy();
 
     if is_remote_repo(&path_str) {
@@ -77,7 +125,19 @@ fn cleanup_repo(path: &PathBuf) {
     }
 }
 
-fn run(cli: Cli) -> omen::core::Result<()> {
+/// Set from `--timings`; read wherever a phase worth timing finishes.
+static TIMINGS: AtomicBool = AtomicBool::new(false);
+
+/// Print one `--timings` line to stderr.
+fn report_timing(phase: &str, elapsed: Duration) {
+    if TIMINGS.load(Ordering::Relaxed) {
+        eprintln!("[timings] {phase}: {elapsed:.1?}");
+    }
+}
+
+fn run(cli: Cli, command_name: &str) -> omen::core::Result<()> {
+    TIMINGS.store(cli.timings, Ordering::Relaxed);
+
     // Configure rayon thread pool if -j/--jobs flag is specified
     if let Some(jobs) = cli.jobs {
         ThreadPoolBuilder::new()
@@ -90,7 +150,7 @@ fn run(cli: Cli) -> omen::core::Result<()> {
     let (path, cleanup_path) = resolve_repo_path(&cli)?;
 
     // Use a closure to ensure cleanup happens even on error
-    let result = run_with_path(&cli, &path);
+    let result = run_with_path(&cli, &path, command_name);
 
     // Clean up cloned repository if we created one
     if let Some(ref cleanup) = cleanup_path {
@@ -100,20 +160,57 @@ fn run(cli: Cli) -> omen::core::Result<()> {
     result
 }
 
-fn run_with_path(cli: &Cli, path: &PathBuf) -> omen::core::Result<()> {
-    let config = match &cli.config {
-        Some(config_path) => Config::from_file(config_path)?,
-        None => Config::load_default(path)?,
+fn run_with_path(cli: &Cli, path: &PathBuf, command_name: &str) -> omen::core::Result<()> {
+    let mut config = match &cli.config {
+        Some(config_path) => Config::from_file_with_profile(config_path, cli.profile.as_deref())?,
+        None => Config::load_default_with_profile(path, cli.profile.as_deref())?,
     };
+    if let Some(ref at_ref) = cli.at_ref {
+        config.git.at_ref = Some(at_ref.clone());
+    }
+    if cli.blame {
+        config.git.blame = true;
+    }
+    if cli.no_cache {
+        config.git.blame_cache = false;
+        config.score.trend_cache = false;
+    }
+    if let Some(ref base) = cli.base {
+        config.git.base = Some(base.clone());
+    } else if cli.changed_only {
+        config.git.base = Some("HEAD".to_string());
+    }
+    if let Some(level) = cli.fail_on {
+        config.fail_on = Some(match level {
+            FailOn::Low => Severity::Low,
+            FailOn::Medium => Severity::Medium,
+            FailOn::High => Severity::High,
+            FailOn::Critical => Severity::Critical,
+        });
+    }
 
     let format = match (cli.format, cli.compact) {
-        (OutputFormat::Json, true) => Format::JsonCompact,
-        (OutputFormat::Json, false) => Format::Json,
+        (OutputFormat::Json, Some(CompactMode::Strings)) => Format::JsonInterned,
+        (OutputFormat::Json, Some(CompactMode::Lines)) => Format::JsonCompact,
+        (OutputFormat::Json, None) => Format::Json,
         (OutputFormat::Markdown, _) => Format::Markdown,
         (OutputFormat::Text, _) => Format::Text,
         (OutputFormat::Sarif, _) => Format::Sarif,
+        (OutputFormat::Csv, _) => Format::Csv,
+        (OutputFormat::Tsv, _) => Format::Tsv,
+        (OutputFormat::Github, _) => Format::Github,
     };
 
+    let destination = output_destination(cli)?;
+    let mut output = Output::stdout();
+    if !(destination.is_per_analyzer() && matches!(cli.command, Command::All(_))) {
+        if let Some(target) = destination.path_for(command_name, format) {
+            output = Output::create(&target)?;
+            eprintln!("Writing output to {}", target.display());
+        }
+    }
+    let out = &mut output;
+
     match &cli.command {
         Command::Mcp(cmd) => {
             match cmd.subcommand {
@@ -128,63 +225,113 @@ fn run_with_path(cli: &Cli, path: &PathBuf) -> omen::core::Result<()> {
                         "description": "Code analysis tools for AI assistants",
                         "tools": omen::mcp::McpServer::tool_names()
                     });
-                    println!("{}", serde_json::to_string_pretty(&manifest)?);
+                    writeln!(out, "{}", serde_json::to_string_pretty(&manifest)?)?;
                 }
                 None => {
-                    let server = McpServer::new(path.clone(), config);
+                    let server = McpServer::new(path.clone(), config)
+                        .with_config_source(cli.config.clone(), cli.profile.clone());
                     server.run_stdio()?;
                 }
             }
         }
         Command::Complexity(args) => {
             if args.check {
-                run_complexity_check(path, &config, args)?;
+                run_complexity_check(path, &config, args, format, out)?;
             } else {
                 run_analyzer::<omen::analyzers::complexity::Analyzer>(
                     path,
                     &config,
                     format,
                     Some(&args.common),
+                    out,
                 )?;
             }
         }
         Command::Diff(args) => {
-            run_diff_analyzer(path, args.target.as_deref(), format)?;
+            run_diff_analyzer(path, &config, args, format, out)?;
         }
         Command::Changes(args) => {
-            run_changes_analyzer(path, &config, format, args)?;
+            run_changes_analyzer(path, &config, format, args, out)?;
+        }
+        Command::Commits(args) => {
+            run_commits_analyzer(path, &config, format, args, out)?;
+        }
+        Command::Defect(args) if args.incidents.is_some() => {
+            run_defect_calibration(path, &config, format, args, out)?;
         }
         Command::Satd(_)
         | Command::Deadcode(_)
         | Command::Clones(_)
         | Command::Defect(_)
+        | Command::Risk(_)
         | Command::Tdg(_)
-        | Command::Graph(_)
         | Command::Hotspot(_)
         | Command::Temporal(_)
+        | Command::Hidden(_)
         | Command::Ownership(_)
         | Command::Cohesion(_)
         | Command::Repomap(_)
+        | Command::Callgraph(_)
+        | Command::Pipeline(_)
+        | Command::Docker(_)
+        | Command::Docdrift(_)
+        | Command::Api(_)
         | Command::Smells(_) => {
-            dispatch_analyzer(&cli.command, path, &config, format)?;
+            dispatch_analyzer(&cli.command, path, &config, format, out)?;
         }
         Command::Churn(args) => {
-            run_churn_analyzer(path, &config, format, args.days, &args.common)?;
+            let mut config = config.clone();
+            if args.off_hours {
+                config.churn.off_hours.enabled = true;
+            }
+            run_churn_analyzer(path, &config, format, args.days, &args.common, out)?;
+        }
+        Command::Graph(args) => {
+            // Merge CLI edge filtering options into config
+            let mut config = config.clone();
+            if args.internal_only {
+                config.graph.internal_only = true;
+            }
+            if args.include_external {
+                config.graph.include_external = true;
+            }
+            if args.collapse_barrels {
+                config.graph.collapse_barrels = true;
+            }
+            if args.dynamic_cycles {
+                config.graph.dynamic_cycles = true;
+            }
+            if args.packages {
+                config.graph.packages = true;
+            }
+            if let Some(graph_format) = args.graph_format {
+                config.graph.format = Some(match graph_format {
+                    GraphFormat::Dot => omen::config::GraphFormat::Dot,
+                    GraphFormat::Graphml => omen::config::GraphFormat::Graphml,
+                    GraphFormat::Mermaid => omen::config::GraphFormat::Mermaid,
+                });
+            }
+            run_graph_analyzer(path, &config, format, args, out)?;
         }
         Command::Flags(args) => {
             // Merge CLI --provider option into config
             let mut config = config.clone();
-            if let Some(ref provider) = args.provider {
-                config.feature_flags.providers = vec![provider.clone()];
+            if !args.provider.is_empty() {
+                config.feature_flags.providers = args.provider.clone();
+            }
+            if args.no_auto_detect {
+                config.feature_flags.auto_detect = false;
             }
             if args.stale_days > 0 {
                 config.feature_flags.stale_days = args.stale_days;
             }
+            let config = prioritized_config(&config, args.prioritize_by);
             run_analyzer::<omen::analyzers::flags::Analyzer>(
                 path,
                 &config,
                 format,
                 Some(&args.common),
+                out,
             )?;
         }
         Command::Score(cmd) => {
@@ -201,226 +348,672 @@ fn run_with_path(cli: &Cli, path: &PathBuf) -> omen::core::Result<()> {
                             args.period,
                             args.samples,
                         )?;
+                        let manifest = walked_manifest(path, &config, format)?;
                         match format {
-                            Format::Json | Format::JsonCompact => {
-                                format.format(&trend_data, &mut stdout())?;
+                            Format::Json | Format::JsonCompact | Format::JsonInterned => {
+                                format.format(&trend_data, manifest.as_ref(), out)?;
                             }
                             Format::Markdown => {
-                                println!("# Score Trend Analysis\n");
-                                println!(
+                                writeln!(out, "# Score Trend Analysis\n")?;
+                                writeln!(
+                                    out,
                                     "**Period**: {} ({})",
                                     args.since,
                                     format!("{:?}", args.period).to_lowercase()
-                                );
-                                println!("**Data Points**: {}\n", trend_data.points.len());
+                                )?;
+                                writeln!(out, "**Data Points**: {}\n", trend_data.points.len())?;
 
                                 if !trend_data.points.is_empty() {
-                                    println!("## Overall Trend\n");
-                                    println!("- **Start Score**: {}", trend_data.start_score);
-                                    println!("- **End Score**: {}", trend_data.end_score);
-                                    println!(
+                                    writeln!(out, "## Overall Trend\n")?;
+                                    writeln!(out, "- **Start Score**: {}", trend_data.start_score)?;
+                                    writeln!(out, "- **End Score**: {}", trend_data.end_score)?;
+                                    writeln!(
+                                        out,
                                         "- **Change**: {:+}",
                                         trend_data.end_score - trend_data.start_score
-                                    );
-                                    println!("- **Slope**: {:.2} points/period", trend_data.slope);
-                                    println!("- **R-squared**: {:.3}\n", trend_data.r_squared);
+                                    )?;
+                                    writeln!(
+                                        out,
+                                        "- **Slope**: {:.2} points/period",
+                                        trend_data.slope
+                                    )?;
+                                    writeln!(
+                                        out,
+                                        "- **R-squared**: {:.3}\n",
+                                        trend_data.r_squared
+                                    )?;
 
                                     if !trend_data.component_trends.is_empty() {
-                                        println!("## Component Trends\n");
-                                        println!("| Component | Slope | Correlation |");
-                                        println!("|-----------|-------|-------------|");
+                                        writeln!(out, "## Component Trends\n")?;
+                                        writeln!(out, "| Component | Slope | Correlation |")?;
+                                        writeln!(out, "|-----------|-------|-------------|")?;
                                         for (name, stats) in &trend_data.component_trends {
-                                            println!(
+                                            writeln!(
+                                                out,
                                                 "| {} | {:.2} | {:.3} |",
                                                 name, stats.slope, stats.correlation
-                                            );
+                                            )?;
                                         }
-                                        println!();
+                                        writeln!(out)?;
                                     }
 
-                                    println!("## History\n");
-                                    println!("| Date | Score |");
-                                    println!("|------|-------|");
+                                    writeln!(out, "## History\n")?;
+                                    writeln!(out, "| Date | Score |")?;
+                                    writeln!(out, "|------|-------|")?;
                                     for point in &trend_data.points {
-                                        println!("| {} | {} |", point.date, point.score);
+                                        writeln!(out, "| {} | {} |", point.date, point.score)?;
                                     }
                                 } else {
-                                    println!(
+                                    writeln!(
+                                        out,
                                         "No historical data available for the specified period."
-                                    );
+                                    )?;
                                 }
                             }
                             Format::Text => {
-                                println!(
+                                writeln!(
+                                    out,
                                     "Score Trend: {} - {}",
                                     trend_data.start_score, trend_data.end_score
-                                );
-                                println!(
+                                )?;
+                                writeln!(
+                                    out,
                                     "Change: {:+}",
                                     trend_data.end_score - trend_data.start_score
-                                );
-                                println!("Slope: {:.2}", trend_data.slope);
+                                )?;
+                                writeln!(out, "Slope: {:.2}", trend_data.slope)?;
+                            }
+                            Format::Sarif | Format::Csv | Format::Tsv | Format::Github => {
+                                format.format(&trend_data, manifest.as_ref(), out)?
                             }
-                            Format::Sarif => format.format(&trend_data, &mut stdout())?,
                         }
                     }
+                    Some(ScoreSubcommand::Badge(args)) => {
+                        run_score_badge(path, &config, args, out)?;
+                    }
+                    None if !config.score.budgets.is_empty() || cmd.args.with_badges => {
+                        run_score_report(path, &config, format, cmd.args.with_badges, out)?;
+                    }
                     None => {
-                        run_analyzer::<omen::score::Analyzer>(path, &config, format, None)?;
+                        run_analyzer::<omen::score::Analyzer>(path, &config, format, None, out)?;
                     }
                 }
             }
         }
         Command::All(args) => {
-            use serde_json::{json, Value};
-            let file_set = filtered_file_set(path, &config, Some(args))?;
-            let git_root = omen::git::GitRepo::open(path)
-                .ok()
-                .map(|r| r.root().to_path_buf());
-            let ctx = AnalysisContext::new(&file_set, &config, Some(path));
-            let ctx = if let Some(ref git_path) = git_root {
-                ctx.with_git_path(git_path)
-            } else {
-                ctx
-            };
-
-            macro_rules! run_and_collect {
-                ($ctx:expr, $analyzer:ty, $name:expr) => {{
-                    let a = <$analyzer>::default();
-                    match a.analyze($ctx) {
-                        Ok(result) => match serde_json::to_value(&result) {
-                            Ok(v) => json!({ "analyzer": $name, "result": v }),
-                            Err(e) => json!({ "analyzer": $name, "error": format!("serialization failed: {e}") }),
-                        },
-                        Err(e) => {
-                            json!({ "analyzer": $name, "error": e.to_string() })
-                        }
-                    }
-                }};
-            }
-
-            // Run analyzers in parallel using std::thread::scope.
-            //
-            // Group A: file-based analyzers (no git dependency)
-            // Group B: git-based analyzers
-            // These two groups run concurrently. After both complete,
-            // Group C (analyzers that internally depend on git + file data)
-            // and score run sequentially.
-            let (group_a, group_b) = std::thread::scope(|s| {
-                let handle_a = s.spawn(|| -> Vec<Value> {
-                    vec![
-                        run_and_collect!(&ctx, omen::analyzers::complexity::Analyzer, "complexity"),
-                        run_and_collect!(&ctx, omen::analyzers::satd::Analyzer, "satd"),
-                        run_and_collect!(&ctx, omen::analyzers::deadcode::Analyzer, "deadcode"),
-                        run_and_collect!(&ctx, omen::analyzers::cohesion::Analyzer, "cohesion"),
-                        run_and_collect!(&ctx, omen::analyzers::graph::Analyzer, "graph"),
-                        run_and_collect!(&ctx, omen::analyzers::repomap::Analyzer, "repomap"),
-                        run_and_collect!(&ctx, omen::analyzers::smells::Analyzer, "smells"),
-                        run_and_collect!(&ctx, omen::analyzers::flags::Analyzer, "flags"),
-                        run_and_collect!(&ctx, omen::analyzers::duplicates::Analyzer, "duplicates"),
-                    ]
-                });
-
-                let handle_b = s.spawn(|| -> Vec<Value> {
-                    vec![
-                        run_and_collect!(&ctx, omen::analyzers::churn::Analyzer, "churn"),
-                        run_and_collect!(&ctx, omen::analyzers::temporal::Analyzer, "temporal"),
-                        run_and_collect!(&ctx, omen::analyzers::ownership::Analyzer, "ownership"),
-                    ]
-                });
-
-                (
-                    handle_a.join().unwrap_or_default(),
-                    handle_b.join().unwrap_or_default(),
-                )
-            });
-
-            let mut results: Vec<Value> = Vec::with_capacity(17);
-            results.extend(group_a);
-            results.extend(group_b);
-
-            // Group C: analyzers that internally depend on both file and git data.
-            // Run after groups A and B to benefit from warm OS page cache.
-            results.push(run_and_collect!(
-                &ctx,
-                omen::analyzers::hotspot::Analyzer,
-                "hotspot"
-            ));
-            results.push(run_and_collect!(
-                &ctx,
-                omen::analyzers::tdg::Analyzer,
-                "tdg"
-            ));
-            results.push(run_and_collect!(
-                &ctx,
-                omen::analyzers::defect::Analyzer,
-                "defect"
-            ));
-            results.push(run_and_collect!(
-                &ctx,
-                omen::analyzers::changes::Analyzer,
-                "changes"
-            ));
-            results.push(run_and_collect!(&ctx, omen::score::Analyzer, "score"));
-
-            let combined = json!({ "analyzers": results });
-            // `all` is machine-first: always emit JSON unless the caller
-            // explicitly requested compact JSON, in which case honour that.
-            // Markdown/Text/Sarif are not meaningful for the combined payload.
-            // This matches the existing integration test expectation that
-            // `omen all` (no -f flag) emits valid JSON.
-            let all_format = match format {
-                Format::JsonCompact => Format::JsonCompact,
-                _ => Format::Json,
-            };
-            format_with_limits(combined, all_format, args.top, args.offset, &mut stdout())?;
+            run_all(path, &config, args, format, &destination, out)?;
+        }
+        Command::Run(args) => {
+            run_named(path, &config, args, format, out)?;
         }
         Command::Context(args) => {
-            run_context(path, &config, args, format)?;
+            run_context(path, &config, args, format, out)?;
+        }
+        Command::Status => {
+            run_status(path, &config, cli, format, out)?;
+        }
+        Command::VerifyManifest(args) => {
+            let verification = omen::manifest::verify(omen::manifest::collect(&args.paths)?);
+            format.format(&verification, None, out)?;
+            verification.check()?;
         }
         Command::Report(cmd) => {
-            run_report(path, &config, &cmd.subcommand)?;
+            run_report(path, &config, &cmd.subcommand, format, out)?;
         }
         Command::Search(ref cmd) => {
-            run_search(path, &config, cmd.subcommand.clone(), format)?;
+            run_search(path, &config, cmd.subcommand.clone(), format, out)?;
         }
         Command::Mutation(ref cmd) => match &cmd.subcommand {
             Some(MutationSubcommand::Train(args)) => {
-                run_mutation_train(&args.path, args)?;
+                run_mutation_train(&args.path, args, out)?;
             }
             None => {
-                run_mutation(path, &config, &cmd.args, format)?;
+                run_mutation(path, &config, cli.config.as_deref(), &cmd.args, format, out)?;
             }
         },
         Command::Outline(args) => {
-            run_outline(path, &config, args, format)?;
+            run_outline(path, &config, args, format, out)?;
         }
         Command::Impact(args) => {
-            run_impact(path, &config, args, format)?;
+            run_impact(path, &config, args, format, out)?;
         }
         Command::Symbol(args) => {
-            run_symbol(path, &config, args, format)?;
+            run_symbol(path, &config, args, format, out)?;
+        }
+        Command::Query(args) => {
+            run_query(path, &config, args, format, out)?;
+        }
+        Command::Fix(args) => {
+            run_fix(path, &config, args, format, out)?;
+        }
+        Command::Tui(args) => {
+            run_tui(path, &config, args)?;
+        }
+        // Run by `run_clap_only` before the config is loaded.
+        Command::Completions(_) | Command::Manpages(_) => {}
+    }
+
+    Ok(())
+}
+
+/// Resolve `--output` / `--output-dir` into where command output should go.
+fn output_destination(cli: &Cli) -> omen::core::Result<Destination> {
+    if let Some(ref file) = cli.output_file {
+        return Ok(Destination::File(file.clone()));
+    }
+    if let Some(ref dir) = cli.output_dir {
+        sink::validate_template(&cli.template)?;
+        return Ok(Destination::Dir {
+            dir: dir.clone(),
+            template: cli.template.clone(),
+        });
+    }
+    Ok(Destination::Stdout)
+}
+
+/// An analyzer run by `omen report generate`: its name (and data file) and
+/// a function producing its result.
+type ReportJob<'a> = (
+    &'static str,
+    Box<dyn FnOnce() -> omen::core::Result<serde_json::Value> + 'a>,
+);
+
+/// Run every analyzer, emitting each `{analyzer, result}` (or
+/// `{analyzer, error}`) entry as soon as it completes.
+///
+/// Groups A and B run concurrently; group C runs after both, to benefit
+/// from a warm OS page cache. By default the entries are collected and
+/// printed as one JSON document in group order. `--stream` writes each
+/// entry as an NDJSON line instead, and `--output-dir` writes each to its
+/// own file as it arrives.
+///
+/// Call once per process: the analyzers' state is leaked (see [`AllState`]).
+fn run_all(
+    path: &Path,
+    config: &Config,
+    args: &AllArgs,
+    format: Format,
+    destination: &Destination,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    use serde_json::{json, Value};
+
+    if let Destination::Dir { template, .. } = destination {
+        if !template.contains("{analyzer}") {
+            return Err(omen::core::Error::InvalidArgument(
+                "--template must contain {analyzer} when writing `all` to --output-dir".to_string(),
+            ));
+        }
+    }
+
+    if args.estimate {
+        let file_set = filtered_file_set(path, config, Some(&args.common))?;
+        let skip: Vec<&str> = config.skip.iter().map(String::as_str).collect();
+        let timings = Timings::load(timings_path(path));
+        return print_estimate(
+            &omen::estimate::all(file_set.len(), &skip, &timings),
+            format,
+            out,
+        );
+    }
+
+    let (state, ctx) = AllState::leak(path, config, args)?;
+    let (file_set, config, timings) = (&state.file_set, &state.config, &state.timings);
+
+    // `all` is machine-first: always emit JSON unless the caller
+    // explicitly requested compact or interned JSON, in which case honour that.
+    // Markdown/Text/Sarif are not meaningful for the combined payload.
+    // This matches the existing integration test expectation that
+    // `omen all` (no -f flag) emits valid JSON.
+    let all_format = match format {
+        Format::JsonCompact => Format::JsonCompact,
+        Format::JsonInterned => Format::JsonInterned,
+        Format::Github => Format::Github,
+        _ => Format::Json,
+    };
+    let manifest = manifest_for(path, file_set, config, all_format);
+    let limits = AllLimits {
+        timeout: args.timeout.map(Duration::from_secs),
+        deadline: args.deadline.map(Deadline::after),
+        fail_fast: args.fail_fast,
+        timings,
+    };
+    let (tx, rx) = mpsc::channel::<(usize, Value)>();
+    std::thread::spawn(move || {
+        let stop = AtomicBool::new(false);
+        let git_offset = ALL_FILE_JOBS.len();
+        std::thread::scope(|s| {
+            let tx_a = tx.clone();
+            let stop = &stop;
+            s.spawn(move || run_all_group(ctx, ALL_FILE_JOBS, 0, limits, stop, &tx_a));
+            run_all_group(ctx, ALL_GIT_JOBS, git_offset, limits, stop, &tx);
+        });
+        run_all_group(
+            ctx,
+            ALL_COMBINED_JOBS,
+            git_offset + ALL_GIT_JOBS.len(),
+            limits,
+            &stop,
+            &tx,
+        );
+    });
+
+    let mut entries: Vec<(usize, Value)> = Vec::new();
+    let mut failure: Option<String> = None;
+    let mut breach: Option<omen::core::Error> = None;
+    let mut skipped: Vec<String> = Vec::new();
+    for (index, mut entry) in rx {
+        let name = entry["analyzer"].as_str().unwrap_or("unknown");
+        if entry.get("skipped").is_some() {
+            skipped.push(name.to_string());
+        }
+        if let Some(error) = entry.get("error").and_then(Value::as_str) {
+            failure.get_or_insert_with(|| format!("{name} failed: {error}"));
+        }
+        if let Some(result) = entry.get("result") {
+            if let Err(e) = check_fail_on(config, name, result) {
+                breach.get_or_insert(e);
+            }
+        }
+        if destination.is_per_analyzer() {
+            write_all_entry(
+                entry,
+                destination,
+                all_format,
+                manifest.as_ref(),
+                &args.common,
+            )?;
+        } else if args.stream {
+            if let Some(result) = entry.get_mut("result") {
+                omen::output::truncate_lists(
+                    result,
+                    args.common.top.unwrap_or(0),
+                    args.common.offset.unwrap_or(0),
+                );
+            }
+            Format::JsonCompact.format_value(&entry, manifest.as_ref(), out)?;
+            out.flush()?;
+        } else {
+            entries.push((index, entry));
+        }
+    }
+
+    if !destination.is_per_analyzer() && !args.stream {
+        entries.sort_by_key(|(index, _)| *index);
+        let results: Vec<Value> = entries.into_iter().map(|(_, entry)| entry).collect();
+        let combined = json!({ "analyzers": results });
+        format_with_limits(
+            combined,
+            all_format,
+            args.common.top,
+            args.common.offset,
+            manifest.as_ref(),
+            out,
+        )?;
+    }
+
+    timings.set_files(file_set.len());
+    timings.save();
+    if !skipped.is_empty() {
+        eprintln!(
+            "Warning: skipped {} analyzer(s) to meet the deadline: {}",
+            skipped.len(),
+            skipped.join(", ")
+        );
+    }
+
+    match (failure, breach) {
+        (Some(message), _) if args.fail_fast => Err(omen::core::Error::analysis(message)),
+        (_, Some(e)) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Run the analyzers named in `args` in parallel over one file set, parse
+/// and commit history, and print their results keyed by analyzer name.
+fn run_named(
+    path: &PathBuf,
+    config: &Config,
+    args: &RunArgs,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    use rayon::prelude::*;
+    use serde_json::{json, Value};
+
+    let mut names: Vec<&'static str> = Vec::new();
+    for name in args
+        .analyzers
+        .iter()
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+    {
+        let known = AnalyzerResult::resolve(name).ok_or_else(|| {
+            omen::core::Error::InvalidArgument(format!(
+                "unknown analyzer `{name}` (expected one of: {})",
+                AnalyzerResult::accepted_names()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        if !names.contains(&known) {
+            names.push(known);
+        }
+    }
+    if names.is_empty() {
+        return Err(omen::core::Error::InvalidArgument(
+            "name at least one analyzer to run".to_string(),
+        ));
+    }
+
+    let file_set = filtered_file_set(path, config, Some(&args.common))?;
+    let mut ctx = with_commit_cache(build_context(path, &file_set, config));
+    if names.iter().any(|name| ALL_FILE_JOBS.contains(name)) {
+        ctx = with_parse_cache(ctx);
+    }
+
+    let results: Vec<(&str, omen::core::Result<Value>)> = names
+        .par_iter()
+        .map(|name| {
+            let start = Instant::now();
+            let result = omen::core::run_by_name(name, &ctx).and_then(|result| result.to_value());
+            report_timing(name, start.elapsed());
+            (*name, result)
+        })
+        .collect();
+
+    let mut combined = serde_json::Map::new();
+    let mut failure: Option<omen::core::Error> = None;
+    for (name, result) in results {
+        let value = match result {
+            Ok(value) => {
+                if let Err(e) = check_fail_on(config, name, &value) {
+                    failure.get_or_insert(e);
+                }
+                value
+            }
+            Err(e) => {
+                let message = format!("{name} failed: {e}");
+                failure.get_or_insert(omen::core::Error::analysis(message));
+                json!({ "error": e.to_string() })
+            }
+        };
+        combined.insert(name.to_string(), value);
+    }
+
+    // Like `all`, the combined document is JSON unless compact, interned or
+    // GitHub output was asked for.
+    let run_format = match format {
+        Format::JsonCompact | Format::JsonInterned | Format::Github => format,
+        _ => Format::Json,
+    };
+    format_with_limits(
+        Value::Object(combined),
+        run_format,
+        args.common.top,
+        args.common.offset,
+        manifest_for(path, &file_set, config, run_format).as_ref(),
+        out,
+    )?;
+    failure.map_or(Ok(()), Err)
+}
+
+/// Fail when `result` reports findings at or above the `fail_on` severity.
+///
+/// Reads the shared `summary.severity_counts` histogram, so analyzers
+/// without one never trip the check.
+fn check_fail_on(
+    config: &Config,
+    name: &str,
+    result: &serde_json::Value,
+) -> omen::core::Result<()> {
+    let Some(level) = config.fail_on else {
+        return Ok(());
+    };
+    let count = SeverityCounts::from_result(result).map_or(0, |c| c.at_least(level));
+    if count > 0 {
+        return Err(omen::core::Error::analysis(format!(
+            "{name}: {count} finding(s) at or above {level} severity"
+        )));
+    }
+    Ok(())
+}
+
+/// Everything `omen all` analyzers borrow.
+///
+/// An analyzer abandoned at `--timeout` or `--deadline` keeps running on a
+/// detached thread that nothing can stop, so this state and the context
+/// built on it are leaked to live for the rest of the process. `run_all` is
+/// therefore a once-per-process CLI path: long-running hosts such as the MCP
+/// server must not call it.
+struct AllState {
+    path: PathBuf,
+    file_set: FileSet,
+    config: Config,
+    timings: Timings,
+}
+
+impl AllState {
+    /// Walk the files, then leak the state and an analysis context over it
+    /// with shared parse trees and commit history. The only leaks in `all`.
+    fn leak(
+        path: &Path,
+        config: &Config,
+        args: &AllArgs,
+    ) -> omen::core::Result<(&'static Self, &'static AnalysisContext<'static>)> {
+        let state: &'static Self = Box::leak(Box::new(Self {
+            path: path.to_path_buf(),
+            file_set: filtered_file_set(path, config, Some(&args.common))?,
+            config: config.clone(),
+            timings: Timings::load(timings_path(path)),
+        }));
+        let ctx = build_context(&state.path, &state.file_set, &state.config);
+        let ctx: &'static AnalysisContext<'static> =
+            Box::leak(Box::new(with_commit_cache(with_parse_cache(ctx))));
+        Ok((state, ctx))
+    }
+}
+
+/// How `omen all` bounds its analyzers.
+#[derive(Clone, Copy)]
+struct AllLimits {
+    timeout: Option<Duration>,
+    deadline: Option<Deadline>,
+    fail_fast: bool,
+    timings: &'static Timings,
+}
+
+/// How long `omen all` waits for one analyzer.
+#[derive(Clone, Copy)]
+enum Limit {
+    /// `--timeout`: running longer is a failure.
+    Timeout(Duration),
+    /// Time left before `--deadline`: running longer skips the analyzer.
+    Deadline(Duration),
+}
+
+/// Run `jobs` in order, cheapest first under a deadline, sending each entry
+/// tagged with its position among all `omen all` analyzers. Stops early
+/// once `stop` is set.
+fn run_all_group(
+    ctx: &'static AnalysisContext<'static>,
+    jobs: &[&'static str],
+    first_index: usize,
+    limits: AllLimits,
+    stop: &AtomicBool,
+    tx: &mpsc::Sender<(usize, serde_json::Value)>,
+) {
+    let mut jobs: Vec<(usize, &'static str)> = (first_index..).zip(jobs.iter().copied()).collect();
+    if limits.deadline.is_some() {
+        limits.timings.order(&mut jobs, |(_, name)| *name);
+    }
+    for (index, name) in jobs {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        if ctx.config.skip.iter().any(|skip| skip == name) {
+            continue;
+        }
+        let admitted = limits
+            .deadline
+            .map(|deadline| deadline.admit(limits.timings.expected_for(name, ctx.files.len())));
+        let entry = match admitted {
+            Some(Err(reason)) => serde_json::json!({ "analyzer": name, "skipped": reason }),
+            Some(Ok(remaining)) if limits.timeout.is_none_or(|timeout| remaining < timeout) => {
+                run_all_job(ctx, name, Some(Limit::Deadline(remaining)), limits.timings)
+            }
+            _ => run_all_job(
+                ctx,
+                name,
+                limits.timeout.map(Limit::Timeout),
+                limits.timings,
+            ),
+        };
+        if limits.fail_fast && entry.get("error").is_some() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        if tx.send((index, entry)).is_err() {
+            break;
         }
     }
+}
+
+/// Run one analyzer on its own thread so it can be abandoned after `limit`.
+fn run_all_job(
+    ctx: &'static AnalysisContext<'static>,
+    name: &'static str,
+    limit: Option<Limit>,
+    timings: &'static Timings,
+) -> serde_json::Value {
+    use mpsc::RecvTimeoutError;
+    use serde_json::json;
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let start = Instant::now();
+        let result = omen::core::run_by_name(name, ctx).and_then(|result| result.to_value());
+        report_timing(name, start.elapsed());
+        timings.record(name, start.elapsed());
+        let _ = tx.send(result);
+    });
+    let received = match limit {
+        Some(Limit::Timeout(wait) | Limit::Deadline(wait)) => rx.recv_timeout(wait),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match (received, limit) {
+        (Ok(Ok(result)), _) => json!({ "analyzer": name, "result": result }),
+        (Ok(Err(e)), _) => json!({ "analyzer": name, "error": e.to_string() }),
+        // An abandoned analyzer took at least this long; remember that in
+        // case the process exits before it finishes.
+        (Err(RecvTimeoutError::Timeout), Some(Limit::Deadline(wait))) => {
+            timings.record(name, wait);
+            json!({ "analyzer": name, "skipped": "deadline reached while running" })
+        }
+        (Err(RecvTimeoutError::Timeout), Some(Limit::Timeout(wait))) => {
+            timings.record(name, wait);
+            json!({ "analyzer": name, "error": format!("timed out after {}s", wait.as_secs()) })
+        }
+        (Err(_), _) => json!({ "analyzer": name, "error": "analyzer panicked" }),
+    }
+}
 
+/// Write one `all` entry to its own templated file under `--output-dir`.
+fn write_all_entry(
+    mut entry: serde_json::Value,
+    destination: &Destination,
+    format: Format,
+    manifest: Option<&Manifest>,
+    args: &AnalyzerArgs,
+) -> omen::core::Result<()> {
+    let name = entry["analyzer"].as_str().unwrap_or("unknown").to_string();
+    let Some(target) = destination.path_for(&name, format) else {
+        return Ok(());
+    };
+    // Successful analyzers get their bare result; failures keep the
+    // `{analyzer, error}` object so the file explains what went wrong.
+    let value = match entry.get_mut("result") {
+        Some(result) => result.take(),
+        None => entry,
+    };
+    let mut out = Output::create(&target)?;
+    format_with_limits(value, format, args.top, args.offset, manifest, &mut out)?;
+    eprintln!("Wrote {}", target.display());
     Ok(())
 }
 
 /// Build a `FileSet` and `AnalysisContext` for the given path, including git
 /// root discovery. This eliminates the repeated file-set + context + git-root
 /// boilerplate that appears in every command handler.
+///
+/// For bare repositories and `--at-ref`, file contents are read from the git
+/// object database instead of the filesystem.
 fn build_context<'a>(
     path: &'a PathBuf,
     file_set: &'a FileSet,
     config: &'a Config,
 ) -> AnalysisContext<'a> {
     let mut ctx = AnalysisContext::new(file_set, config, Some(path));
-    if let Ok(repo) = omen::git::GitRepo::open(path) {
+    if let Some(repo) = open_git_repo(path, config) {
+        if repo.reads_from_objects() {
+            if let Ok(source) = repo.tree_source() {
+                ctx = ctx.with_content_source(Arc::new(source));
+            }
+        }
+        if config.git.blame {
+            let mut blame = BlameCache::new(repo.root(), config.git.at_ref.as_deref());
+            if config.git.blame_cache && !repo.is_bare() {
+                blame = blame.with_dir(repo.root().join(".omen/cache/blame"));
+            }
+            ctx = ctx.with_blame(Arc::new(blame));
+        }
         let git_root = repo.root().to_path_buf();
         ctx = ctx.with_git_path(Box::leak(Box::new(git_root)));
     }
+    if let Some(Ok(changed)) = changed_lines(path, config) {
+        ctx = ctx.with_changed_lines(Arc::new(changed));
+    }
     ctx
 }
 
+/// Parse every file once, in parallel, for a run of several analyzers.
+fn with_parse_cache(ctx: AnalysisContext<'_>) -> AnalysisContext<'_> {
+    let start = Instant::now();
+    let cache = ParseCache::build(&ctx);
+    report_timing("parse", start.elapsed());
+    ctx.with_parse_cache(Arc::new(cache))
+}
+
+/// Read the commit history once for a run of several history-based analyzers.
+fn with_commit_cache(ctx: AnalysisContext<'_>) -> AnalysisContext<'_> {
+    match ctx.git_path {
+        Some(git_path) => {
+            let cache = CommitCache::new(git_path, &ctx.config.git);
+            ctx.with_commit_cache(Arc::new(cache))
+        }
+        None => ctx,
+    }
+}
+
+/// Lines changed since `config.git.base`, up to `--at-ref` when set.
+fn changed_lines(
+    path: &Path,
+    config: &Config,
+) -> Option<omen::core::Result<omen::git::ChangedLines>> {
+    let base = config.git.base.as_deref()?;
+    Some(omen::git::ChangedLines::since(
+        path,
+        base,
+        config.git.at_ref.as_deref(),
+    ))
+}
+
+/// Open the repository at `path`, pinned to `config.git.at_ref` when set.
+fn open_git_repo(path: &Path, config: &Config) -> Option<omen::git::GitRepo> {
+    omen::git::GitRepo::open(path)
+        .ok()
+        .map(|repo| repo.at_ref(config.git.at_ref.as_deref()))
+}
+
 /// Dispatch a command variant to its corresponding analyzer. This consolidates
 /// the 15 command arms that all follow the same `run_analyzer::<T>` pattern.
 fn dispatch_analyzer(
@@ -428,54 +1021,184 @@ fn dispatch_analyzer(
     path: &PathBuf,
     config: &Config,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     match command {
         Command::Satd(args) => {
-            run_analyzer::<omen::analyzers::satd::Analyzer>(path, config, format, Some(args))
-        }
-        Command::Deadcode(args) => {
-            run_analyzer::<omen::analyzers::deadcode::Analyzer>(path, config, format, Some(args))
-        }
-        Command::Clones(args) => {
-            run_analyzer::<omen::analyzers::duplicates::Analyzer>(path, config, format, Some(args))
+            let mut config = prioritized_config(config, args.prioritize_by);
+            if let Some(ref base_ref) = args.compare {
+                config.satd.compare_ref = Some(base_ref.clone());
+            }
+            run_analyzer::<omen::analyzers::satd::Analyzer>(
+                path,
+                &config,
+                format,
+                Some(&args.common),
+                out,
+            )
         }
-        Command::Defect(args) => {
-            run_analyzer::<omen::analyzers::defect::Analyzer>(path, config, format, Some(args))
+        Command::Deadcode(args) => run_deadcode(path, config, args, format, out),
+        Command::Clones(args) => run_analyzer::<omen::analyzers::duplicates::Analyzer>(
+            path,
+            config,
+            format,
+            Some(args),
+            out,
+        ),
+        Command::Defect(args) => run_analyzer::<omen::analyzers::defect::Analyzer>(
+            path,
+            config,
+            format,
+            Some(&args.common),
+            out,
+        ),
+        Command::Risk(args) => {
+            run_analyzer::<omen::analyzers::risk::Analyzer>(path, config, format, Some(args), out)
         }
         Command::Tdg(args) => {
-            run_analyzer::<omen::analyzers::tdg::Analyzer>(path, config, format, Some(args))
-        }
-        Command::Graph(args) => {
-            run_analyzer::<omen::analyzers::graph::Analyzer>(path, config, format, Some(args))
+            run_analyzer::<omen::analyzers::tdg::Analyzer>(path, config, format, Some(args), out)
         }
-        Command::Hotspot(args) => {
-            run_analyzer::<omen::analyzers::hotspot::Analyzer>(path, config, format, Some(args))
-        }
-        Command::Temporal(args) => {
-            run_analyzer::<omen::analyzers::temporal::Analyzer>(path, config, format, Some(args))
+        Command::Hotspot(args) => run_hotspot_analyzer(path, config, format, args, out),
+        Command::Temporal(args) => run_analyzer::<omen::analyzers::temporal::Analyzer>(
+            path,
+            config,
+            format,
+            Some(args),
+            out,
+        ),
+        Command::Hidden(args) => {
+            run_analyzer::<omen::analyzers::hidden::Analyzer>(path, config, format, Some(args), out)
         }
         Command::Ownership(args) => {
-            run_analyzer::<omen::analyzers::ownership::Analyzer>(path, config, format, Some(args))
+            let mut config = config.clone();
+            config.ownership.precise |= args.precise;
+            run_analyzer::<omen::analyzers::ownership::Analyzer>(
+                path,
+                &config,
+                format,
+                Some(&args.common),
+                out,
+            )
         }
-        Command::Cohesion(args) => {
-            run_analyzer::<omen::analyzers::cohesion::Analyzer>(path, config, format, Some(args))
+        Command::Cohesion(args) => run_cohesion_analyzer(path, config, format, args, out),
+        Command::Repomap(args) => run_analyzer::<omen::analyzers::repomap::Analyzer>(
+            path,
+            config,
+            format,
+            Some(args),
+            out,
+        ),
+        Command::Callgraph(args) => run_analyzer::<omen::analyzers::callgraph::Analyzer>(
+            path,
+            config,
+            format,
+            Some(args),
+            out,
+        ),
+        Command::Pipeline(args) => run_analyzer::<omen::analyzers::pipeline::Analyzer>(
+            path,
+            config,
+            format,
+            Some(args),
+            out,
+        ),
+        Command::Docker(args) => {
+            run_analyzer::<omen::analyzers::docker::Analyzer>(path, config, format, Some(args), out)
         }
-        Command::Repomap(args) => {
-            run_analyzer::<omen::analyzers::repomap::Analyzer>(path, config, format, Some(args))
+        Command::Docdrift(args) => run_analyzer::<omen::analyzers::docdrift::Analyzer>(
+            path,
+            config,
+            format,
+            Some(args),
+            out,
+        ),
+        Command::Api(args) => {
+            let mut config = config.clone();
+            if let Some(ref base) = args.base {
+                config.api.base = Some(base.clone());
+            }
+            if let Some(ref since) = args.since {
+                config.api.since = since.clone();
+                config.api.base = None;
+            }
+            run_analyzer::<omen::analyzers::api::Analyzer>(
+                path,
+                &config,
+                format,
+                Some(&args.common),
+                out,
+            )
         }
         Command::Smells(args) => {
-            run_analyzer::<omen::analyzers::smells::Analyzer>(path, config, format, Some(args))
+            let config = prioritized_config(config, args.prioritize_by);
+            run_analyzer::<omen::analyzers::smells::Analyzer>(
+                path,
+                &config,
+                format,
+                Some(&args.common),
+                out,
+            )
         }
         _ => unreachable!("dispatch_analyzer called with non-dispatched command"),
     }
 }
 
+/// Merge a `--prioritize-by` override into config.
+fn prioritized_config(config: &Config, prioritize_by: Option<PrioritizeBy>) -> Config {
+    let mut config = config.clone();
+    if let Some(by) = prioritize_by {
+        config.prioritize_by = match by {
+            PrioritizeBy::Ownership => omen::config::PrioritizeBy::Ownership,
+            PrioritizeBy::Hotspot => omen::config::PrioritizeBy::Hotspot,
+            PrioritizeBy::None => omen::config::PrioritizeBy::None,
+        };
+    }
+    config
+}
+
+/// The manifest to stamp on `format` output of analyzing `files`, if the
+/// format carries one.
+fn manifest_for(path: &Path, files: &FileSet, config: &Config, format: Format) -> Option<Manifest> {
+    if !format.is_stamped() {
+        return None;
+    }
+    Manifest::build(path, files, config).ok()
+}
+
+/// [`manifest_for`] for commands that read history rather than walk the
+/// tree: the files are walked only when the format carries a manifest.
+fn walked_manifest(
+    path: &Path,
+    config: &Config,
+    format: Format,
+) -> omen::core::Result<Option<Manifest>> {
+    if !format.is_stamped() {
+        return Ok(None);
+    }
+    let files = filtered_file_set(path, config, None)?;
+    Ok(manifest_for(path, &files, config, format))
+}
+
 fn filtered_file_set(
-    path: &PathBuf,
+    path: &Path,
     config: &Config,
     args: Option<&AnalyzerArgs>,
 ) -> omen::core::Result<FileSet> {
-    let mut file_set = FileSet::from_path(path, config)?;
+    let mut file_set = match open_git_repo(path, config) {
+        Some(repo) if repo.reads_from_objects() => {
+            FileSet::from_tree_source(&repo.tree_source()?, config)?
+        }
+        _ => FileSet::from_path(path, config)?,
+    };
+    if let Some(walk) = file_set.walk_stats() {
+        report_timing(
+            &format!("walk ({} entries, {} files)", walk.entries, file_set.len()),
+            walk.elapsed,
+        );
+    }
+    if let Some(changed) = changed_lines(path, config) {
+        file_set = file_set.filter_by_paths(&changed?.paths());
+    }
     if let Some(args) = args {
         if let Some(ref changed_since) = args.changed_since {
             let changed_files = changed_files_since(path, changed_since)?;
@@ -519,6 +1242,7 @@ fn run_analyzer<A: Analyzer + Default>(
     config: &Config,
     format: Format,
     args: Option<&AnalyzerArgs>,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     let file_set = filtered_file_set(path, config, args)?;
 
@@ -555,7 +1279,9 @@ fn run_analyzer<A: Analyzer + Default>(
         }
     });
 
+    let start = Instant::now();
     let result = analyzer.analyze(&ctx)?;
+    report_timing(analyzer.name(), start.elapsed());
 
     if let Some(s) = spinner {
         s.finish_and_clear();
@@ -564,15 +1290,162 @@ fn run_analyzer<A: Analyzer + Default>(
     let top = args.and_then(|a| a.top);
     let offset = args.and_then(|a| a.offset);
     let value = serde_json::to_value(&result)?;
-    format_with_limits(value, format, top, offset, &mut stdout())?;
-    Ok(())
+    let verdict = check_fail_on(config, analyzer.name(), &value);
+    format_with_limits(
+        value,
+        format,
+        top,
+        offset,
+        manifest_for(path, &file_set, config, format).as_ref(),
+        out,
+    )?;
+    verdict
 }
 
-fn run_diff_analyzer(path: &Path, target: Option<&str>, format: Format) -> omen::core::Result<()> {
-    let analyzer = omen::analyzers::changes::Analyzer::default();
-    let result = analyzer.analyze_diff(path, target)?;
-    format.format(&result, &mut stdout())?;
-    Ok(())
+fn run_diff_analyzer(
+    path: &Path,
+    config: &Config,
+    args: &DiffArgs,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let analyzer = omen::analyzers::changes::Analyzer::default().with_diff_rules(
+        omen::analyzers::changes::DiffRules {
+            max_hotspots: config.changes.max_hotspots,
+            test_deletion_lines: config.changes.test_deletion_lines,
+            complexity_budget: args.complexity_budget.or(config.changes.complexity_budget),
+        },
+    );
+    let manifest = walked_manifest(path, config, format)?;
+    let scope = if args.staged {
+        Some(omen::git::DiffScope::Staged)
+    } else if args.worktree {
+        Some(omen::git::DiffScope::Worktree)
+    } else {
+        None
+    };
+    if let Some(scope) = scope {
+        let result = analyzer.analyze_uncommitted(path, scope)?;
+        let value = serde_json::to_value(&result)?;
+        let verdict = check_fail_on(config, "diff", &value);
+        format.format_value(&value, manifest.as_ref(), out)?;
+        verdict?;
+        let Some(max_score) = args.fail_above.or(config.changes.fail_above) else {
+            return Ok(());
+        };
+        let risky = result.hunks_above(max_score).count();
+        if risky > 0 {
+            return Err(omen::core::Error::analysis(format!(
+                "diff: {risky} hunk(s) with risk above {max_score:.2}"
+            )));
+        }
+        return Ok(());
+    }
+
+    let target = args.target.as_deref();
+    let Some(number) = args.pr else {
+        let result = analyzer.analyze_diff(path, target)?;
+        format.format(&result, manifest.as_ref(), out)?;
+        return check_complexity_budget(&result);
+    };
+
+    let remote = omen::git::GitRepo::open(path)?
+        .remote_url("origin")
+        .ok_or_else(|| {
+            omen::core::Error::InvalidArgument(
+                "--pr needs an `origin` remote to locate the project".to_string(),
+            )
+        })?;
+    let pull_request = omen::git::ForgeClient::from_remote(&remote)?.pull_request(number)?;
+    let result = analyzer.analyze_pull_request(path, pull_request, target)?;
+    format.format(&result, manifest.as_ref(), out)?;
+    check_complexity_budget(&result.risk)
+}
+
+fn check_complexity_budget(
+    result: &omen::analyzers::changes::DiffResult,
+) -> omen::core::Result<()> {
+    let complexity = &result.complexity;
+    match complexity.budget {
+        Some(budget) if complexity.over_budget => Err(omen::core::Error::analysis(format!(
+            "diff: net cyclomatic complexity +{} exceeds budget {budget}",
+            complexity.net_cyclomatic
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn run_cohesion_analyzer(
+    path: &PathBuf,
+    config: &Config,
+    format: Format,
+    args: &CohesionArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let Some(ref dot_path) = args.hierarchy_dot else {
+        return run_analyzer::<omen::analyzers::cohesion::Analyzer>(
+            path,
+            config,
+            format,
+            Some(&args.common),
+            out,
+        );
+    };
+
+    let file_set = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let analyzer = omen::analyzers::cohesion::Analyzer::default();
+    let result = analyzer.analyze(&ctx)?;
+    std::fs::write(dot_path, analyzer.to_dot(&result))?;
+    eprintln!("Inheritance hierarchy written to {}", dot_path.display());
+
+    let value = serde_json::to_value(&result)?;
+    let verdict = check_fail_on(config, analyzer.name(), &value);
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(
+        value,
+        format,
+        args.common.top,
+        args.common.offset,
+        manifest.as_ref(),
+        out,
+    )?;
+    verdict
+}
+
+/// Run the graph analyzer. With a graph format set, JSON output carries the
+/// diagram in its `diagram` field; other formats print the diagram alone.
+fn run_graph_analyzer(
+    path: &PathBuf,
+    config: &Config,
+    format: Format,
+    args: &GraphArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let json = matches!(
+        format,
+        Format::Json | Format::JsonCompact | Format::JsonInterned
+    );
+    if config.graph.format.is_none() || json {
+        return run_analyzer::<omen::analyzers::graph::Analyzer>(
+            path,
+            config,
+            format,
+            Some(&args.common),
+            out,
+        );
+    }
+
+    let file_set = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let analyzer = omen::analyzers::graph::Analyzer::new();
+    let result = analyzer.analyze(&ctx)?;
+    if let Some(diagram) = &result.diagram {
+        write!(out, "{}", diagram.content)?;
+    }
+
+    let value = serde_json::to_value(&result)?;
+    check_fail_on(config, analyzer.name(), &value)
 }
 
 fn run_changes_analyzer(
@@ -580,6 +1453,7 @@ fn run_changes_analyzer(
     config: &Config,
     format: Format,
     args: &AnalyzerArgs,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     let path_buf = path.to_path_buf();
     let file_set = filtered_file_set(&path_buf, config, Some(args))?;
@@ -587,7 +1461,118 @@ fn run_changes_analyzer(
     let analyzer = omen::analyzers::changes::Analyzer::new().with_days(config.changes.days);
     let result = analyzer.analyze(&ctx)?;
     let value = serde_json::to_value(&result)?;
-    format_with_limits(value, format, args.top, args.offset, &mut stdout())?;
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(value, format, args.top, args.offset, manifest.as_ref(), out)?;
+    Ok(())
+}
+
+fn run_commits_analyzer(
+    path: &PathBuf,
+    config: &Config,
+    format: Format,
+    args: &CommitsArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let file_set = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let mut analyzer = omen::analyzers::commits::Analyzer::new();
+    if let Some(days) = args.days {
+        analyzer = analyzer.with_days(days);
+    }
+    let result = analyzer.analyze(&ctx)?;
+
+    if args.check {
+        let min_compliance = args.min_compliance.unwrap_or(config.commits.min_compliance);
+        let summary = &result.summary;
+        eprintln!(
+            "{}/{} commits follow conventional commits ({:.0}%, minimum {:.0}%); {} fixup, {} revert",
+            summary.compliant_commits,
+            summary.scored_commits,
+            summary.compliance_rate * 100.0,
+            min_compliance * 100.0,
+            summary.fixup_commits,
+            summary.revert_commits
+        );
+        if let Err(e) = result.check(min_compliance, config.commits.fail_on_fixups) {
+            use omen::analyzers::commits::CommitKind;
+            let offending = result
+                .commits
+                .iter()
+                .filter(|c| (c.scored && !c.compliant) || c.kind == CommitKind::Fixup);
+            for commit in offending {
+                let reason = if commit.kind == CommitKind::Fixup {
+                    "fixup".to_string()
+                } else {
+                    let problems: Vec<String> =
+                        commit.problems.iter().map(|p| p.to_string()).collect();
+                    problems.join(", ")
+                };
+                eprintln!(
+                    "  {} {} ({})",
+                    &commit.commit_hash[..commit.commit_hash.len().min(8)],
+                    commit.subject,
+                    reason
+                );
+            }
+            return Err(e);
+        }
+        return Ok(());
+    }
+
+    let value = serde_json::to_value(&result)?;
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(
+        value,
+        format,
+        args.common.top,
+        args.common.offset,
+        manifest.as_ref(),
+        out,
+    )?;
+    Ok(())
+}
+
+fn run_defect_calibration(
+    path: &PathBuf,
+    config: &Config,
+    format: Format,
+    args: &DefectArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let Some(incidents_path) = &args.incidents else {
+        return Ok(());
+    };
+    let incidents = omen::analyzers::defect::load_incidents(incidents_path)?;
+    let file_set = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let result =
+        omen::analyzers::defect::Analyzer::new().calibrate(&ctx, &incidents, args.top_k)?;
+
+    let summary = &result.summary;
+    eprintln!(
+        "{}/{} incidents matched over {} replays; AUC {}, precision@{} {:.2}, recall@{} {:.2}",
+        summary.matched_incidents,
+        summary.total_incidents,
+        summary.replays,
+        summary
+            .auc
+            .map_or_else(|| "n/a".to_string(), |auc| format!("{auc:.2}")),
+        result.top_k,
+        summary.precision_at_k,
+        result.top_k,
+        summary.recall_at_k
+    );
+
+    let value = serde_json::to_value(&result)?;
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(
+        value,
+        format,
+        args.common.top,
+        args.common.offset,
+        manifest.as_ref(),
+        out,
+    )?;
     Ok(())
 }
 
@@ -595,6 +1580,8 @@ fn run_complexity_check(
     path: &PathBuf,
     config: &Config,
     args: &ComplexityArgs,
+    format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     let file_set = filtered_file_set(path, config, Some(&args.common))?;
     let ctx = build_context(path, &file_set, config);
@@ -609,42 +1596,70 @@ fn run_complexity_check(
         .max_cognitive
         .unwrap_or(config.complexity.cognitive_error);
 
-    match result.check_thresholds(max_cyclomatic, max_cognitive) {
-        Ok(()) => {
-            eprintln!(
-                "All {} functions within thresholds (cyclomatic <= {}, cognitive <= {})",
-                result.summary.total_functions, max_cyclomatic, max_cognitive
-            );
-            Ok(())
-        }
-        Err(violations) => {
+    let violations = result
+        .check_thresholds(max_cyclomatic, max_cognitive)
+        .err()
+        .unwrap_or_default();
+    let total = violations.len();
+    let entries: Vec<BaselineEntry> = violations
+        .iter()
+        .map(|v| BaselineEntry::new(path, &v.file, &v.name, (v.cyclomatic + v.cognitive) as f64))
+        .collect();
+    let failing = apply_baseline("complexity", entries.clone(), &args.baseline)?;
+    let violations: Vec<_> = violations
+        .into_iter()
+        .zip(entries)
+        .filter(|(_, entry)| failing.contains(entry))
+        .map(|(v, _)| v)
+        .collect();
+    if matches!(format, Format::Github) {
+        format.format(&violations, None, out)?;
+    }
+
+    if violations.is_empty() && total > 0 {
+        eprintln!("No new complexity violations ({total} in baseline)");
+        Ok(())
+    } else if violations.is_empty() {
+        eprintln!(
+            "All {} functions within thresholds (cyclomatic <= {}, cognitive <= {})",
+            result.summary.total_functions, max_cyclomatic, max_cognitive
+        );
+        Ok(())
+    } else {
+        eprintln!(
+            "Complexity threshold exceeded in {} function(s):\n",
+            violations.len()
+        );
+        for v in &violations {
             eprintln!(
-                "Complexity threshold exceeded in {} function(s):\n",
-                violations.len()
+                "  {}:{} - {}: cyclomatic={}, cognitive={}",
+                v.file, v.line, v.name, v.cyclomatic, v.cognitive
             );
-            for v in &violations {
+            if let Some(ref origin) = v.introduced {
                 eprintln!(
-                    "  {}:{} - {}: cyclomatic={}, cognitive={}",
-                    v.file, v.line, v.name, v.cyclomatic, v.cognitive
+                    "      last changed in {} by {} on {}",
+                    &origin.commit[..origin.commit.len().min(8)],
+                    origin.author,
+                    origin.date.format("%Y-%m-%d")
                 );
             }
-            eprintln!(
-                "\nThresholds: cyclomatic <= {}, cognitive <= {}",
-                max_cyclomatic, max_cognitive
-            );
-            Err(omen::core::Error::threshold_violation(
-                format!(
-                    "{} function(s) exceed complexity thresholds",
-                    violations.len()
-                ),
-                violations.len() as f64,
-            ))
         }
+        eprintln!(
+            "\nThresholds: cyclomatic <= {}, cognitive <= {}",
+            max_cyclomatic, max_cognitive
+        );
+        Err(omen::core::Error::threshold_violation(
+            format!(
+                "{} function(s) exceed complexity thresholds",
+                violations.len()
+            ),
+            violations.len() as f64,
+        ))
     }
 }
 
 fn run_score_check(path: &PathBuf, config: &Config, args: &ScoreArgs) -> omen::core::Result<()> {
-    let file_set = FileSet::from_path(path, config)?;
+    let file_set = filtered_file_set(path, config, None)?;
     let ctx = build_context(path, &file_set, config);
 
     let analyzer = omen::score::Analyzer::default();
@@ -654,6 +1669,20 @@ fn run_score_check(path: &PathBuf, config: &Config, args: &ScoreArgs) -> omen::c
         .fail_under
         .unwrap_or_else(|| config.score.fail_under.unwrap_or(80.0));
 
+    let shortfall = min_score - result.overall_score;
+    let entries = if shortfall > 0.0 {
+        vec![BaselineEntry::new(path, "", "overall", shortfall)]
+    } else {
+        Vec::new()
+    };
+    if apply_baseline("score", entries, &args.baseline)?.is_empty() && shortfall > 0.0 {
+        eprintln!(
+            "Score {:.1} ({}) is below minimum {:.1}, within baseline",
+            result.overall_score, result.grade, min_score
+        );
+        return Ok(());
+    }
+
     match result.check_threshold(min_score) {
         Ok(()) => {
             eprintln!(
@@ -666,19 +1695,194 @@ fn run_score_check(path: &PathBuf, config: &Config, args: &ScoreArgs) -> omen::c
     }
 }
 
+/// Apply `--baseline` to a check's violations, returning the ones that
+/// should fail it: all without a baseline, none while writing one,
+/// otherwise those missing from the baseline or worse than recorded.
+fn apply_baseline(
+    check: &str,
+    violations: Vec<BaselineEntry>,
+    args: &BaselineArgs,
+) -> omen::core::Result<Vec<BaselineEntry>> {
+    let Some(ref path) = args.baseline else {
+        return Ok(violations);
+    };
+    if args.update_baseline || !path.exists() {
+        let count = violations.len();
+        Baseline::new(check, violations).save(path)?;
+        eprintln!(
+            "Baseline with {count} violation(s) written to {}",
+            path.display()
+        );
+        return Ok(Vec::new());
+    }
+
+    let diff = Baseline::load(path, check)?.diff(violations);
+    eprintln!(
+        "Baseline {}: {} known, {} fixed, {} new violation(s)",
+        path.display(),
+        diff.existing.len(),
+        diff.fixed.len(),
+        diff.new.len()
+    );
+    Ok(diff.new)
+}
+
+/// Score the repository and project each of `score.budgets` from the
+/// trend over `score.budget_since`.
+/// Anchor prefix for the score document under `--with-badges`.
+const SCORE_ANCHOR: &str = "omen";
+
+/// Run the score with budget progress and, for markdown with `with_badges`,
+/// a badges block linking into the document.
+fn run_score_report(
+    path: &PathBuf,
+    config: &Config,
+    format: Format,
+    with_badges: bool,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let file_set = filtered_file_set(path, config, None)?;
+    let ctx = build_context(path, &file_set, config);
+    let mut result = omen::score::Analyzer::default().analyze(&ctx)?;
+    if !config.score.budgets.is_empty() {
+        evaluate_budgets(path, config, &mut result)?;
+    }
+
+    let value = serde_json::to_value(&result)?;
+    let verdict = check_fail_on(config, "score", &value);
+    if with_badges && matches!(format, Format::Markdown) {
+        write!(
+            out,
+            "{}",
+            omen::score::badge::markdown_block(&result, SCORE_ANCHOR)
+        )?;
+        omen::output::format_markdown_anchored(&value, SCORE_ANCHOR, out)?;
+    } else {
+        let manifest = manifest_for(path, &file_set, config, format);
+        format_with_limits(value, format, None, None, manifest.as_ref(), out)?;
+    }
+    verdict
+}
+
+/// Fill in `result.budgets` from the configured budgets and the score trend.
+fn evaluate_budgets(
+    path: &PathBuf,
+    config: &Config,
+    result: &mut omen::score::Analysis,
+) -> omen::core::Result<()> {
+    let since = config.score.budget_since.as_deref().unwrap_or("3m");
+    let days = omen::git::parse_since_to_days(since).unwrap_or(90) as f64;
+    let history = match omen::score::analyze_trend(
+        path,
+        config,
+        since,
+        omen::cli::TrendPeriod::Monthly,
+        Some(omen::score::default_sample_count(days)),
+    ) {
+        Ok(trend) => trend.points,
+        Err(e) => {
+            eprintln!("Warning: budget trend unavailable: {}", e);
+            Vec::new()
+        }
+    };
+    result.budgets = omen::score::budget::evaluate(
+        &config.score.budgets,
+        &result.metrics,
+        &history,
+        omen::score::budget::today(),
+    )?;
+    Ok(())
+}
+
+fn run_score_badge(
+    path: &PathBuf,
+    config: &Config,
+    args: &ScoreBadgeArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let file_set = filtered_file_set(path, config, None)?;
+    let ctx = build_context(path, &file_set, config);
+    let result = omen::score::Analyzer::default().analyze(&ctx)?;
+
+    match &args.file {
+        Some(file) => {
+            let written = omen::score::badge::write_badges(
+                &result,
+                file,
+                &args.label,
+                args.components,
+                args.svg,
+            )?;
+            for path in written {
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+        None => {
+            let badge = omen::score::badge::overall_badge(&result, &args.label);
+            writeln!(out, "{}", serde_json::to_string_pretty(&badge)?)?;
+        }
+    }
+    Ok(())
+}
+
+fn run_hotspot_analyzer(
+    path: &PathBuf,
+    config: &Config,
+    format: Format,
+    args: &HotspotArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let mut config = config.clone();
+    if args.off_hours {
+        config.churn.off_hours.enabled = true;
+    }
+    if let Some(weight) = args.churn_weight {
+        config.hotspot.churn_weight = weight;
+    }
+    if let Some(normalization) = args.normalization {
+        config.hotspot.normalization = match normalization {
+            HotspotNormalization::Rank => omen::config::HotspotNormalization::Rank,
+            HotspotNormalization::Zscore => omen::config::HotspotNormalization::Zscore,
+        };
+    }
+    if let Some(days) = args.half_life {
+        config.hotspot.churn_half_life_days = Some(days);
+    }
+
+    let file_set = filtered_file_set(path, &config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, &config);
+    let analyzer = omen::analyzers::hotspot::Analyzer::default()
+        .with_top(args.common.top.unwrap_or(config.hotspot.top));
+    let result = analyzer.analyze(&ctx)?;
+    let value = serde_json::to_value(&result)?;
+    let verdict = check_fail_on(&config, analyzer.name(), &value);
+    let manifest = manifest_for(path, &file_set, &config, format);
+    format_with_limits(
+        value,
+        format,
+        args.common.top,
+        args.common.offset,
+        manifest.as_ref(),
+        out,
+    )?;
+    verdict
+}
+
 fn run_churn_analyzer(
     path: &PathBuf,
     config: &Config,
     format: Format,
     days: u32,
     args: &AnalyzerArgs,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     let file_set = filtered_file_set(path, config, Some(args))?;
     let ctx = build_context(path, &file_set, config);
     let analyzer = omen::analyzers::churn::Analyzer::new().with_days(days);
     let result = analyzer.analyze(&ctx)?;
     let value = serde_json::to_value(&result)?;
-    format_with_limits(value, format, args.top, args.offset, &mut stdout())?;
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(value, format, args.top, args.offset, manifest.as_ref(), out)?;
     Ok(())
 }
 
@@ -687,6 +1891,7 @@ fn run_context(
     config: &Config,
     args: &omen::cli::ContextArgs,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     let file_set = FileSet::from_path(path, config)?;
     let mut context = omen::context::build_context(
@@ -697,15 +1902,97 @@ fn run_context(
         Some(25),
     )?;
 
-    // Apply token budget for non-JSON formats or when explicitly requested
-    omen::context::apply_token_budget(&mut context, args.max_tokens);
+    let tokenizer = match args.tokenizer {
+        Some(Tokenizer::Chars) => omen::config::Tokenizer::Chars,
+        Some(Tokenizer::Words) => omen::config::Tokenizer::Words,
+        Some(Tokenizer::Bytes) => omen::config::Tokenizer::Bytes,
+        None => config.context.tokenizer,
+    };
+    let focus = omen::context::Focus {
+        symbol: args.symbol.clone(),
+        target: args.target.clone(),
+        depth: args.depth,
+    };
+    omen::context::assemble(
+        &mut context,
+        path,
+        &file_set,
+        &focus,
+        args.max_tokens,
+        tokenizer,
+    )?;
+
+    if let Some(export) = args.export {
+        let export = match export {
+            ContextExport::Aider => omen::context::Export::Aider,
+            ContextExport::ClaudeProject => omen::context::Export::ClaudeProject,
+            ContextExport::CursorRules => omen::context::Export::CursorRules,
+        };
+        let target = args
+            .export_path
+            .clone()
+            .unwrap_or_else(|| path.join(export.default_path()));
+        omen::context::write_export(&context, export, &target)?;
+        eprintln!("Wrote {}", target.display());
+        return Ok(());
+    }
 
+    let manifest = manifest_for(path, &file_set, config, format);
     match format {
-        Format::Json | Format::JsonCompact => format.format(&context, &mut stdout())?,
+        Format::Json | Format::JsonCompact | Format::JsonInterned => {
+            format.format(&context, manifest.as_ref(), out)?
+        }
         Format::Markdown | Format::Text => {
-            print!("{}", context.render_markdown());
+            write!(out, "{}", context.render_markdown())?;
+        }
+        Format::Sarif | Format::Csv | Format::Tsv | Format::Github => {
+            format.format(&context, manifest.as_ref(), out)?
         }
-        Format::Sarif => format.format(&context, &mut stdout())?,
+    }
+
+    Ok(())
+}
+
+/// Print a `--estimate` prediction.
+fn print_estimate(
+    estimate: &omen::estimate::Estimate,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    match format {
+        Format::Markdown | Format::Text => {
+            write!(out, "{}", estimate.render_markdown())?;
+        }
+        _ => format.format(estimate, None, out)?,
+    }
+    Ok(())
+}
+
+fn run_status(
+    path: &Path,
+    config: &Config,
+    cli: &Cli,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let file_set = FileSet::from_path(path, config)?;
+    let status = omen::status::build_status(
+        path,
+        &file_set,
+        config,
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+    )?;
+
+    match format {
+        Format::Markdown | Format::Text => {
+            write!(out, "{}", status.render_markdown())?;
+        }
+        _ => format.format(
+            &status,
+            manifest_for(path, &file_set, config, format).as_ref(),
+            out,
+        )?,
     }
 
     Ok(())
@@ -715,22 +2002,35 @@ fn run_report(
     path: &PathBuf,
     config: &Config,
     subcommand: &ReportSubcommand,
+    format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     use serde_json::{json, Value};
 
     match subcommand {
         ReportSubcommand::Generate(args) => {
+            let mut skip_list: Vec<&str> = args
+                .skip
+                .as_deref()
+                .map(|s| s.split(',').collect())
+                .unwrap_or_default();
+            skip_list.extend(config.skip.iter().map(String::as_str));
+
+            let file_set = filtered_file_set(path, config, None)?;
+            if args.estimate {
+                let timings = Timings::load(timings_path(path));
+                return print_estimate(
+                    &omen::estimate::report(file_set.len(), &skip_list, &timings),
+                    format,
+                    out,
+                );
+            }
+
             // Create output directory
             std::fs::create_dir_all(&args.output)?;
 
-            let file_set = FileSet::from_path(path, config)?;
-            let git_root = omen::git::GitRepo::open(path)
-                .ok()
-                .map(|r| r.root().to_path_buf());
-            let mut ctx = AnalysisContext::new(&file_set, config, Some(path));
-            if let Some(ref git_path) = git_root {
-                ctx = ctx.with_git_path(git_path);
-            }
+            let mut ctx =
+                with_commit_cache(with_parse_cache(build_context(path, &file_set, config)));
 
             // Generate metadata.json (matches Go structure)
             // Canonicalize path to handle "." and get actual directory name
@@ -744,46 +2044,54 @@ fn run_report(
             } else {
                 args.since.clone()
             };
+
+            let manifest = Manifest::build(path, &file_set, config)?;
+
+            // Read the previous run before this one overwrites it
+            if let Some(previous) = &args.previous {
+                let warm_start = open_git_repo(path, config)
+                    .ok_or_else(|| omen::core::Error::git("not a git repository"))
+                    .and_then(|repo| WarmStart::load(previous, &repo, &manifest, &since_str));
+                match warm_start {
+                    Ok(warm_start) => ctx = ctx.with_warm_start(Arc::new(warm_start)),
+                    Err(e) => eprintln!(
+                        "Warning: not reusing {}: {}; running in full",
+                        previous.display(),
+                        e
+                    ),
+                }
+            }
+            // Smell churn only needs the previous smells, reusable or not
+            let previous_smells: Option<omen::analyzers::smells::Analysis> = args
+                .previous
+                .as_ref()
+                .and_then(|dir| std::fs::read(dir.join("smells.json")).ok())
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+            let smells = match previous_smells {
+                Some(previous) => {
+                    omen::analyzers::smells::Analyzer::default().with_previous(previous)
+                }
+                None => omen::analyzers::smells::Analyzer::default(),
+            };
+
             let metadata = json!({
                 "repository": repo_name,
                 "generated_at": chrono::Utc::now().to_rfc3339(),
                 "since": since_str,
                 "omen_version": env!("CARGO_PKG_VERSION"),
-                "paths": [path.display().to_string()]
+                "paths": [path.display().to_string()],
+                "manifest": manifest
             });
             let metadata_path = args.output.join("metadata.json");
             std::fs::write(&metadata_path, serde_json::to_string_pretty(&metadata)?)?;
 
-            let skip_list: Vec<&str> = args
-                .skip
-                .as_deref()
-                .map(|s| s.split(',').collect())
-                .unwrap_or_default();
-
-            // Count total analyzers to run
-            let analyzer_names = [
-                "complexity",
-                "satd",
-                "deadcode",
-                "churn",
-                "duplicates",
-                "defect",
-                "changes",
-                "tdg",
-                "graph",
-                "hotspots",
-                "temporal",
-                "ownership",
-                "cohesion",
-                "repomap",
-                "smells",
-                "flags",
-                "score",
-                "trend",
-            ];
-            let total_analyzers = analyzer_names
-                .iter()
-                .filter(|n| !skip_list.contains(*n))
+            // Count total analyzers to run: the job groups, then risk, score
+            // and trend
+            let total_analyzers = [REPORT_FILE_JOBS, REPORT_GIT_JOBS, REPORT_MIXED_JOBS]
+                .concat()
+                .into_iter()
+                .chain(["risk", "score", "trend"])
+                .filter(|n| !skip_list.contains(n))
                 .count();
 
             // Set up progress bar
@@ -804,29 +2112,67 @@ fn run_report(
             let completed = std::sync::atomic::AtomicU64::new(0);
             let output_dir = &args.output;
 
-            // Helper: run an analyzer and save its JSON output
-            macro_rules! run_analyzer {
-                ($analyzer:expr, $name:expr, $filename:expr) => {{
-                    if !skip_list.contains(&$name) {
-                        let result: Value = match $analyzer.analyze(&ctx) {
-                            Ok(r) => serde_json::to_value(&r)
-                                .unwrap_or(json!({"error": "serialization failed"})),
-                            Err(e) => json!({"error": e.to_string()}),
-                        };
-                        let output_path = output_dir.join(format!("{}.json", $filename));
-                        let _ = std::fs::write(
-                            &output_path,
-                            serde_json::to_string_pretty(&result).unwrap_or_default(),
-                        );
-                        let done = completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                        if let Some(ref bar) = progress {
-                            bar.set_position(done);
-                        } else {
-                            eprintln!("Generated: {}", output_path.display());
+            let timings = Timings::load(timings_path(path));
+            let deadline = args.deadline.map(Deadline::after);
+            let skipped: std::sync::Mutex<Vec<&str>> = std::sync::Mutex::new(Vec::new());
+
+            // An analyzer saved as `<name>.json`.
+            macro_rules! job {
+                ($analyzer:expr, $name:expr) => {
+                    (
+                        $name,
+                        Box::new(|| -> omen::core::Result<Value> {
+                            Ok(serde_json::to_value($analyzer.analyze(&ctx)?)?)
+                        }) as Box<dyn FnOnce() -> omen::core::Result<Value> + '_>,
+                    )
+                };
+            }
+
+            // Run analyzers in order, cheapest first under a deadline, and
+            // save each result. Analyzers that would overrun the deadline
+            // are saved as `{"skipped": reason}`.
+            let run_jobs = |mut jobs: Vec<ReportJob>| {
+                if deadline.is_some() {
+                    timings.order(&mut jobs, |(name, _)| *name);
+                }
+                for (name, analyze) in jobs {
+                    // Saved state must come from the run that wrote this
+                    // directory, so drop it unless the analyzer succeeds.
+                    let state_path = omen::core::warm_start::state_path(output_dir, name);
+                    if skip_list.contains(&name) {
+                        let _ = std::fs::remove_file(&state_path);
+                        continue;
+                    }
+                    let result: Value = match deadline
+                        .map(|d| d.admit(timings.expected_for(name, file_set.len())))
+                    {
+                        Some(Err(reason)) => {
+                            skipped.lock().unwrap_or_else(|e| e.into_inner()).push(name);
+                            json!({ "skipped": reason })
+                        }
+                        _ => {
+                            let start = Instant::now();
+                            let result = analyze();
+                            timings.record(name, start.elapsed());
+                            result.unwrap_or_else(|e| json!({"error": e.to_string()}))
                         }
+                    };
+                    if result.get("skipped").is_some() || result.get("error").is_some() {
+                        let _ = std::fs::remove_file(&state_path);
                     }
-                }};
-            }
+                    let output_path = output_dir.join(format!("{}.json", name));
+                    let _ = std::fs::write(
+                        &output_path,
+                        serde_json::to_string_pretty(&result).unwrap_or_default(),
+                    );
+                    let done = completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+                    if let Some(ref bar) = progress {
+                        bar.set_position(done);
+                    } else {
+                        eprintln!("Generated: {}", output_path.display());
+                    }
+                }
+            };
 
             // Phase 1: Run all analyzers in parallel groups.
             // Group A: File-parsing analyzers (CPU-bound, share tree-sitter work)
@@ -836,103 +2182,79 @@ fn run_report(
                 .days
                 .unwrap_or_else(|| omen::git::parse_since_to_days(&args.since).unwrap_or(u32::MAX));
 
-            std::thread::scope(|s| {
-                // Group A: file-based analyzers
-                s.spawn(|| {
-                    run_analyzer!(
-                        omen::analyzers::complexity::Analyzer::default(),
-                        "complexity",
-                        "complexity"
-                    );
-                    run_analyzer!(omen::analyzers::satd::Analyzer::default(), "satd", "satd");
-                    run_analyzer!(
-                        omen::analyzers::deadcode::Analyzer::default(),
-                        "deadcode",
-                        "deadcode"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::duplicates::Analyzer::default(),
-                        "duplicates",
-                        "duplicates"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::cohesion::Analyzer::default(),
-                        "cohesion",
-                        "cohesion"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::repomap::Analyzer::default(),
-                        "repomap",
-                        "repomap"
-                    );
-                });
-
-                // Group B: git-heavy analyzers (ownership is the longest at ~57s)
-                s.spawn(|| {
-                    run_analyzer!(
-                        omen::analyzers::ownership::Analyzer::default(),
-                        "ownership",
-                        "ownership"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::churn::Analyzer::new().with_days(churn_days),
-                        "churn",
-                        "churn"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::temporal::Analyzer::default(),
-                        "temporal",
-                        "temporal"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::changes::Analyzer::default(),
-                        "changes",
-                        "changes"
-                    );
-                });
+            // The analyzer behind each job. Most run with their defaults;
+            // churn honors `--days`/`--since`, churn and duplicates save
+            // state for the next warm start, smells tracks the previous run,
+            // and the hotspot analyzer is saved as `hotspots`.
+            let report_job = |name: &'static str| match name {
+                "churn" => job!(
+                    omen::analyzers::churn::Analyzer::new()
+                        .with_days(churn_days)
+                        .with_state_dir(output_dir),
+                    "churn"
+                ),
+                "duplicates" => job!(
+                    omen::analyzers::duplicates::Analyzer::default().with_state_dir(output_dir),
+                    "duplicates"
+                ),
+                "smells" => job!(smells, "smells"),
+                "hotspots" => job!(omen::analyzers::hotspot::Analyzer::default(), "hotspots"),
+                name => {
+                    let ctx = &ctx;
+                    (
+                        name,
+                        Box::new(move || omen::core::run_by_name(name, ctx)?.to_value())
+                            as Box<dyn FnOnce() -> omen::core::Result<Value> + '_>,
+                    )
+                }
+            };
+            let run_group = |names: &[&'static str]| {
+                run_jobs(names.iter().map(|&name| report_job(name)).collect())
+            };
 
-                // Group C: mixed file+git analyzers
-                s.spawn(|| {
-                    run_analyzer!(
-                        omen::analyzers::graph::Analyzer::default(),
-                        "graph",
-                        "graph"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::smells::Analyzer::default(),
-                        "smells",
-                        "smells"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::flags::Analyzer::default(),
-                        "flags",
-                        "flags"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::defect::Analyzer::default(),
-                        "defect",
-                        "defect"
-                    );
-                    run_analyzer!(
-                        omen::analyzers::hotspot::Analyzer::default(),
-                        "hotspots",
-                        "hotspots"
-                    );
-                    run_analyzer!(omen::analyzers::tdg::Analyzer::default(), "tdg", "tdg");
-                });
+            std::thread::scope(|s| {
+                s.spawn(|| run_group(REPORT_FILE_JOBS));
+                s.spawn(|| run_group(REPORT_GIT_JOBS));
+                s.spawn(|| run_group(REPORT_MIXED_JOBS));
             });
 
-            // Phase 2: Score (reads pre-generated JSON files, nearly instant)
+            // Phase 2: Risk matrix and score (read pre-generated JSON files, nearly instant)
+            if !skip_list.contains(&"risk") {
+                let result: Value = match omen::analyzers::risk::compute_from_data_dir(
+                    output_dir,
+                    &config.criticality,
+                ) {
+                    Ok(r) => {
+                        serde_json::to_value(&r).unwrap_or(json!({"error": "serialization failed"}))
+                    }
+                    Err(e) => json!({"error": e.to_string()}),
+                };
+                let output_path = output_dir.join("risk.json");
+                std::fs::write(&output_path, serde_json::to_string_pretty(&result)?)?;
+                let done = completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+                if let Some(ref bar) = progress {
+                    bar.set_position(done);
+                } else {
+                    eprintln!("Generated: {}", output_path.display());
+                }
+            }
+
+            let mut score_metrics = BTreeMap::new();
             if !skip_list.contains(&"score") {
                 if let Some(ref bar) = progress {
                     bar.set_message("score...");
                 }
-                let result: Value =
-                    match omen::score::compute_from_data_dir(output_dir, ctx.files.files().len()) {
-                        Ok(r) => serde_json::to_value(&r)
-                            .unwrap_or(json!({"error": "serialization failed"})),
-                        Err(e) => json!({"error": e.to_string()}),
-                    };
+                let result: Value = match omen::score::compute_from_data_dir(
+                    output_dir,
+                    ctx.files.files().len(),
+                    &config.score.weights,
+                ) {
+                    Ok(r) => {
+                        score_metrics = r.metrics.clone();
+                        serde_json::to_value(&r).unwrap_or(json!({"error": "serialization failed"}))
+                    }
+                    Err(e) => json!({"error": e.to_string()}),
+                };
                 let output_path = output_dir.join("score.json");
                 std::fs::write(&output_path, serde_json::to_string_pretty(&result)?)?;
                 let done = completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
@@ -944,92 +2266,115 @@ fn run_report(
             }
 
             // Phase 3: Trend data (analyzes historical commits)
+            let mut trend_points = Vec::new();
             if !skip_list.contains(&"trend") {
-                if let Some(ref bar) = progress {
-                    bar.set_message("trend...");
-                }
-                let samples = args.samples.or_else(|| {
-                    let days =
-                        omen::git::parse_since_to_days(&args.since).unwrap_or(365 * 50) as f64;
-                    Some(omen::score::default_sample_count(days))
-                });
-                match omen::score::analyze_trend(
-                    path,
-                    config,
-                    &args.since,
-                    omen::cli::TrendPeriod::Monthly,
-                    samples,
-                ) {
-                    Ok(trend_data) => {
-                        let output_path = output_dir.join("trend.json");
-                        if let Err(e) =
-                            std::fs::write(&output_path, serde_json::to_string_pretty(&trend_data)?)
-                        {
-                            eprintln!("Warning: failed to write trend.json: {}", e);
-                        } else {
-                            let done =
-                                completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                            if let Some(ref bar) = progress {
-                                bar.set_position(done);
+                if let Some(Err(reason)) =
+                    deadline.map(|d| d.admit(timings.expected_for("trend", file_set.len())))
+                {
+                    let output_path = output_dir.join("trend.json");
+                    std::fs::write(
+                        &output_path,
+                        serde_json::to_string_pretty(&json!({ "skipped": reason }))?,
+                    )?;
+                    skipped
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push("trend");
+                } else {
+                    if let Some(ref bar) = progress {
+                        bar.set_message("trend...");
+                    }
+                    let start = Instant::now();
+                    let samples = args.samples.or_else(|| {
+                        let days =
+                            omen::git::parse_since_to_days(&args.since).unwrap_or(365 * 50) as f64;
+                        Some(omen::score::default_sample_count(days))
+                    });
+                    match omen::score::analyze_trend(
+                        path,
+                        config,
+                        &args.since,
+                        omen::cli::TrendPeriod::Monthly,
+                        samples,
+                    ) {
+                        Ok(trend_data) => {
+                            trend_points = trend_data.points.clone();
+                            let output_path = output_dir.join("trend.json");
+                            if let Err(e) = std::fs::write(
+                                &output_path,
+                                serde_json::to_string_pretty(&trend_data)?,
+                            ) {
+                                eprintln!("Warning: failed to write trend.json: {}", e);
                             } else {
-                                eprintln!("Generated: {}", output_path.display());
+                                let done = completed
+                                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                                    + 1;
+                                if let Some(ref bar) = progress {
+                                    bar.set_position(done);
+                                } else {
+                                    eprintln!("Generated: {}", output_path.display());
+                                }
                             }
                         }
+                        Err(e) => {
+                            eprintln!("Warning: trend analysis failed: {}", e);
+                        }
                     }
-                    Err(e) => {
-                        eprintln!("Warning: trend analysis failed: {}", e);
-                    }
+                    timings.record("trend", start.elapsed());
+                }
+            }
+            timings.set_files(file_set.len());
+            timings.save();
+
+            // Budgets: current metric values against the trend
+            if !config.score.budgets.is_empty() && !score_metrics.is_empty() {
+                let budgets = omen::score::budget::evaluate(
+                    &config.score.budgets,
+                    &score_metrics,
+                    &trend_points,
+                    omen::score::budget::today(),
+                )?;
+                let output_path = output_dir.join("budgets.json");
+                std::fs::write(&output_path, serde_json::to_string_pretty(&budgets)?)?;
+                if progress.is_none() {
+                    eprintln!("Generated: {}", output_path.display());
                 }
             }
 
             if let Some(bar) = progress {
                 bar.finish_with_message("done");
             }
+            let skipped = skipped.into_inner().unwrap_or_else(|e| e.into_inner());
+            if !skipped.is_empty() {
+                eprintln!(
+                    "Warning: skipped {} analyzer(s) to meet the deadline: {}",
+                    skipped.len(),
+                    skipped.join(", ")
+                );
+            }
             eprintln!("Report data generated in: {}", output_dir.display());
         }
         ReportSubcommand::Validate(args) => {
-            // Basic validation: check that expected JSON files exist and are valid JSON
-            // File list matches Go version
-            let expected_files = [
-                "metadata",
-                "complexity",
-                "satd",
-                "deadcode",
-                "churn",
-                "duplicates",
-                "defect",
-                "changes",
-                "tdg",
-                "graph",
-                "hotspots", // Go uses plural
-                "temporal",
-                "ownership",
-                "cohesion",
-                "repomap",
-                "smells",
-                "flags",
-                "score",
-                "trend",
-            ];
+            use omen::report::schema::{validate_dir, FileStatus};
 
+            let results = validate_dir(&args.data, args.schema.as_deref())?;
             let mut errors = Vec::new();
             let mut valid_count = 0;
-
-            for name in expected_files {
-                let file_path = args.data.join(format!("{}.json", name));
-                if file_path.exists() {
-                    match std::fs::read_to_string(&file_path) {
-                        Ok(contents) => match serde_json::from_str::<Value>(&contents) {
-                            Ok(_) => {
-                                valid_count += 1;
-                                eprintln!("Valid: {}.json", name);
-                            }
-                            Err(e) => errors.push(format!("{}.json: invalid JSON - {}", name, e)),
-                        },
-                        Err(e) => errors.push(format!("{}.json: read error - {}", name, e)),
+            for result in &results {
+                match &result.status {
+                    FileStatus::Valid => {
+                        valid_count += 1;
+                        eprintln!("Valid: {}.json", result.name);
                     }
-                } else {
-                    errors.push(format!("{}.json: missing", name));
+                    FileStatus::Skipped { reason } => {
+                        eprintln!("Skipped: {}.json ({})", result.name, reason);
+                    }
+                    FileStatus::Missing => errors.push(format!("{}.json: missing", result.name)),
+                    FileStatus::Invalid { errors: found } => errors.extend(
+                        found
+                            .iter()
+                            .map(|error| format!("{}.json: {}", result.name, error)),
+                    ),
                 }
             }
 
@@ -1057,47 +2402,22 @@ fn run_report(
                 Renderer::gz_path(&args.output).display()
             );
         }
+        ReportSubcommand::Comment(args) => {
+            let comparison = omen::report::comment::Comparison::load(&args.before, &args.after);
+            let comment = match args.format {
+                omen::cli::CommentFormat::GithubMarkdown => {
+                    comparison.to_github_markdown(args.max_chars)
+                }
+            };
+            write!(out, "{comment}")?;
+        }
         ReportSubcommand::Serve(args) => {
-            eprintln!("Starting server at http://{}:{}/", args.host, args.port);
+            let server =
+                omen::report::serve::Server::bind((args.host.as_str(), args.port), &args.data)?;
+            eprintln!("Serving report at http://{}/", server.local_addr()?);
             eprintln!("Serving data from: {}", args.data.display());
-            eprintln!("Press Ctrl+C to stop.");
-
-            // Simple HTTP server using std::net
-            use std::io::{Read, Write};
-            use std::net::TcpListener;
-
-            let addr = format!("{}:{}", args.host, args.port);
-            let listener = TcpListener::bind(&addr)?;
-
-            for mut stream in listener.incoming().flatten() {
-                let mut buffer = [0; 1024];
-                if stream.read(&mut buffer).is_ok() {
-                    let request = String::from_utf8_lossy(&buffer);
-
-                    let response = if request.starts_with("GET / ")
-                        || request.starts_with("GET /index.html ")
-                    {
-                        // Serve rendered report
-                        let report_path = args.data.parent().unwrap_or(path).join("report.html");
-                        if report_path.exists() {
-                            match std::fs::read_to_string(&report_path) {
-                                Ok(html) => format!(
-                                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\n\r\n{}",
-                                    html.len(),
-                                    html
-                                ),
-                                Err(_) => "HTTP/1.1 500 Internal Server Error\r\n\r\nFailed to read report".to_string(),
-                            }
-                        } else {
-                            "HTTP/1.1 404 Not Found\r\n\r\nReport not found. Run 'omen report render' first.".to_string()
-                        }
-                    } else {
-                        "HTTP/1.1 404 Not Found\r\n\r\nNot Found".to_string()
-                    };
-
-                    let _ = stream.write_all(response.as_bytes());
-                }
-            }
+            eprintln!("POST /api/rerender after regenerating the data. Press Ctrl+C to stop.");
+            server.run()?;
         }
     }
 
@@ -1109,6 +2429,7 @@ fn run_search(
     config: &Config,
     subcommand: SearchSubcommand,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     use omen::semantic::{SearchConfig, SemanticSearch};
 
@@ -1143,6 +2464,16 @@ fn run_search(
             let file_filter: Option<Vec<&str>> =
                 args.files.as_ref().map(|f| f.split(',').collect());
 
+            // Reranking pulls a wider candidate pool, then keeps the best top_k.
+            // Signals are per repository, so cross-repo results are left as is.
+            let rerank =
+                (args.rerank || config.search.rerank.enabled) && args.include_project.is_none();
+            let fetch_k = if rerank {
+                args.top_k.max(config.search.rerank.candidates)
+            } else {
+                args.top_k
+            };
+
             let output = if let Some(ref include) = args.include_project {
                 // Multi-repo search: combine current project with additional projects
                 let extra_paths: Vec<std::path::PathBuf> = include
@@ -1162,17 +2493,21 @@ fn run_search(
                 )?;
                 omen::semantic::SearchOutput::new(args.query.clone(), mr.total_symbols, mr.results)
             } else if let Some(files) = file_filter {
-                search.search_in_files(&args.query, &files, Some(args.top_k))?
+                search.search_in_files(&args.query, &files, Some(fetch_k))?
             } else {
-                search.search(&args.query, Some(args.top_k))?
+                search.search(&args.query, Some(fetch_k))?
             };
 
             // Filter by min_score
-            let filtered_results: Vec<_> = output
+            let mut filtered_results: Vec<_> = output
                 .results
                 .into_iter()
                 .filter(|r| r.score >= args.min_score)
                 .collect();
+            if rerank {
+                filtered_results = search.rerank(filtered_results, config)?;
+                filtered_results.truncate(args.top_k);
+            }
 
             let output = omen::semantic::SearchOutput::new(
                 output.query,
@@ -1181,31 +2516,91 @@ fn run_search(
             );
 
             match format {
-                Format::Json | Format::JsonCompact => format.format(&output, &mut stdout())?,
+                Format::Json | Format::JsonCompact | Format::JsonInterned => {
+                    format.format(&output, None, out)?
+                }
                 Format::Markdown | Format::Text => {
-                    println!("Query: {}", output.query);
-                    println!("Total symbols indexed: {}", output.total_symbols);
-                    println!("Results: {}\n", output.results.len());
+                    writeln!(out, "Query: {}", output.query)?;
+                    writeln!(out, "Total symbols indexed: {}", output.total_symbols)?;
+                    writeln!(out, "Results: {}\n", output.results.len())?;
 
                     for (i, result) in output.results.iter().enumerate() {
-                        println!(
-                            "{}. {} ({}) - score: {:.3}",
-                            i + 1,
-                            result.symbol_name,
-                            result.symbol_type,
-                            result.score
-                        );
-                        println!(
+                        match &result.rerank {
+                            Some(rerank) => writeln!(
+                                out,
+                                "{}. {} ({}) - score: {:.3} (similarity {:.3}, pagerank {:.2}, \
+                                 hotspot {:.2}, complexity {:.2})",
+                                i + 1,
+                                result.symbol_name,
+                                result.symbol_type,
+                                rerank.score,
+                                result.score,
+                                rerank.pagerank,
+                                rerank.hotspot,
+                                rerank.complexity
+                            )?,
+                            None => writeln!(
+                                out,
+                                "{}. {} ({}) - score: {:.3}",
+                                i + 1,
+                                result.symbol_name,
+                                result.symbol_type,
+                                result.score
+                            )?,
+                        }
+                        writeln!(
+                            out,
                             "   {}:{}-{}",
                             result.file_path, result.start_line, result.end_line
-                        );
-                        println!("   {}", result.signature);
-                        println!();
+                        )?;
+                        writeln!(out, "   {}", result.signature)?;
+                        writeln!(out)?;
+                    }
+                }
+                Format::Sarif | Format::Csv | Format::Tsv | Format::Github => {
+                    format.format(&output, None, out)?
+                }
+            }
+        }
+        SearchSubcommand::Stats => {
+            let stats = search.stats(config)?;
+            match format {
+                Format::Json
+                | Format::JsonCompact
+                | Format::JsonInterned
+                | Format::Sarif
+                | Format::Csv
+                | Format::Tsv
+                | Format::Github => format.format(&stats, None, out)?,
+                Format::Markdown | Format::Text => {
+                    writeln!(out, "Index: {}", stats.cache_path.display())?;
+                    writeln!(out, "Size: {:.1} KiB", stats.size_bytes as f64 / 1024.0)?;
+                    writeln!(out, "Files: {}", stats.files)?;
+                    writeln!(out, "Symbols: {}\n", stats.symbols)?;
+                    for (language, counts) in &stats.by_language {
+                        writeln!(
+                            out,
+                            "  {:<12} {:>6} files {:>8} symbols",
+                            language, counts.files, counts.symbols
+                        )?;
+                    }
+                    writeln!(out, "\nStale entries: {}", stats.stale.len())?;
+                    for file in &stats.stale {
+                        writeln!(out, "  {} ({})", file.file_path, file.reason)?;
+                    }
+                    if !stats.stale.is_empty() {
+                        writeln!(
+                            out,
+                            "\nRun 'omen search prune' to drop deleted/excluded entries, or 'omen search index' to refresh."
+                        )?;
                     }
                 }
-                Format::Sarif => format.format(&output, &mut stdout())?,
             }
         }
+        SearchSubcommand::Prune => {
+            let removed = search.prune(config)?;
+            eprintln!("Pruned {} files from the index", removed);
+        }
     }
 
     Ok(())
@@ -1214,13 +2609,22 @@ fn run_search(
 fn run_mutation(
     path: &PathBuf,
     config: &Config,
+    config_file: Option<&Path>,
     args: &MutationArgs,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     use omen::analyzers::mutation;
     use omen::analyzers::mutation::ml_predictor::{SurvivabilityPredictor, TrainingData};
     use omen::analyzers::mutation::MutantStatus;
 
+    if config.git.at_ref.is_some() {
+        return Err(omen::core::Error::InvalidArgument(
+            "mutation testing runs the test suite in the worktree and cannot use --at-ref"
+                .to_string(),
+        ));
+    }
+
     let mut file_set = FileSet::from_path(path, config)?;
 
     // Load predictor model if --skip-predicted is specified omen:ignore
@@ -1269,19 +2673,28 @@ fn run_mutation(
         s.set_message(format!("Analyzing {} files...", file_set.len()));
     }
 
-    // Parse operators
-    let operators: Vec<String> = args
-        .operators
-        .split(',')
-        .map(|s| s.trim().to_uppercase())
-        .collect();
+    // Parse operators, falling back to the configured set
+    let operators: Vec<String> = match args.operators {
+        Some(ref operators) => operators
+            .split(',')
+            .map(|s| s.trim().to_uppercase())
+            .collect(),
+        None => config.mutation.operators.clone(),
+    };
 
     // Build analyzer
     let mut analyzer = mutation::Analyzer::new()
         .operators(operators)
         .test_command(args.test_command.clone())
+        .include_dependents(args.include_dependents)
         .timeout(args.timeout)
-        .dry_run(args.dry_run);
+        .baseline_runs(args.baseline_runs)
+        .dry_run(args.dry_run)
+        .mode(match args.mode {
+            omen::cli::MutationMode::All => mutation::MutationMode::All,
+            omen::cli::MutationMode::Fast => mutation::MutationMode::Fast,
+            omen::cli::MutationMode::Thorough => mutation::MutationMode::Thorough,
+        });
 
     if args.check {
         analyzer = analyzer.min_score(Some(args.min_score));
@@ -1308,103 +2721,251 @@ fn run_mutation(
         }
     });
 
+    if args.estimate {
+        let generated = analyzer.generate_only(&ctx)?;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        let timings = Timings::load(timings_path(path));
+        let estimate = omen::estimate::mutation(
+            file_set.len(),
+            generated.summary.total_mutants,
+            args.baseline_runs,
+            &timings,
+        );
+        return print_estimate(&estimate, format, out);
+    }
+
     let result = analyzer.analyze(&ctx)?;
 
     if let Some(s) = spinner {
         s.finish_and_clear();
     }
 
+    // The average test run per mutant feeds the next `--estimate`.
+    let tested: Vec<u64> = result
+        .files
+        .iter()
+        .flat_map(|file| &file.mutants)
+        .filter(|m| {
+            !matches!(
+                m.status,
+                MutantStatus::Pending | MutantStatus::Skipped | MutantStatus::Equivalent
+            )
+        })
+        .map(|m| m.duration_ms)
+        .collect();
+    if !tested.is_empty() {
+        let timings = Timings::load(timings_path(path));
+        let average = tested.iter().sum::<u64>() / tested.len() as u64;
+        timings.record(
+            omen::estimate::MUTANT_TIMING,
+            Duration::from_millis(average),
+        );
+        timings.save();
+    }
+
+    if let Some(ref junit_path) = args.junit {
+        std::fs::write(junit_path, mutation::ci::format_junit(&result))?;
+        eprintln!("JUnit report written to {}", junit_path.display());
+    }
+
     // Output results
+    let manifest = manifest_for(path, &file_set, config, format);
     match format {
-        Format::Json | Format::JsonCompact => {
+        _ if args.tap => write!(out, "{}", mutation::ci::format_tap(&result))?,
+        Format::Json | Format::JsonCompact | Format::JsonInterned => {
             let value = serde_json::to_value(&result)?;
             format_with_limits(
                 value,
                 format,
                 args.common.top,
                 args.common.offset,
-                &mut stdout(),
+                manifest.as_ref(),
+                out,
             )?;
         }
         Format::Markdown => {
-            println!("# Mutation Testing Report\n");
-            println!("## Summary\n");
-            println!("- **Total Files**: {}", result.summary.total_files);
-            println!("- **Total Mutants**: {}", result.summary.total_mutants);
-            println!("- **Killed**: {}", result.summary.killed);
-            println!("- **Survived**: {}", result.summary.survived);
-            println!("- **Timeout**: {}", result.summary.timeout);
-            println!("- **Error**: {}", result.summary.error);
+            writeln!(out, "# Mutation Testing Report\n")?;
+            writeln!(out, "## Summary\n")?;
+            writeln!(out, "- **Total Files**: {}", result.summary.total_files)?;
+            writeln!(out, "- **Total Mutants**: {}", result.summary.total_mutants)?;
+            writeln!(out, "- **Killed**: {}", result.summary.killed)?;
+            writeln!(out, "- **Survived**: {}", result.summary.survived)?;
+            writeln!(out, "- **Timeout**: {}", result.summary.timeout)?;
+            writeln!(out, "- **Error**: {}", result.summary.error)?;
             if result.summary.skipped > 0 {
-                println!("- **Skipped**: {} (ML predicted)", result.summary.skipped);
+                writeln!(
+                    out,
+                    "- **Skipped**: {} (ML predicted)",
+                    result.summary.skipped
+                )?;
             }
-            println!(
+            writeln!(
+                out,
                 "- **Mutation Score**: {:.1}%",
                 result.summary.mutation_score * 100.0
-            );
-            println!("- **Duration**: {}ms\n", result.summary.duration_ms);
+            )?;
+            writeln!(out, "- **Duration**: {}ms", result.summary.duration_ms)?;
+            if let Some(ref baseline) = result.baseline {
+                writeln!(
+                    out,
+                    "- **Baseline**: {}/{} test runs passed, slowest {}ms",
+                    baseline.passed, baseline.runs, baseline.duration_ms
+                )?;
+            }
+            writeln!(out)?;
 
             if !result.summary.by_operator.is_empty() {
-                println!("## By Operator\n");
-                println!("| Operator | Total | Killed | Survived |");
-                println!("|----------|-------|--------|----------|");
-                for (op, stats) in &result.summary.by_operator {
-                    println!(
-                        "| {} | {} | {} | {} |",
-                        op, stats.total, stats.killed, stats.survived
-                    );
+                writeln!(out, "## By Operator\n")?;
+                writeln!(
+                    out,
+                    "| Operator | Total | Killed | Survived | Kill Rate | Mean Run | Unique |"
+                )?;
+                writeln!(
+                    out,
+                    "|----------|-------|--------|----------|-----------|----------|--------|"
+                )?;
+                let mut operators: Vec<_> = result.summary.by_operator.iter().collect();
+                operators.sort_by_key(|(name, _)| *name);
+                for (op, stats) in operators {
+                    writeln!(
+                        out,
+                        "| {} | {} | {} | {} | {:.1}% | {}ms | {} |",
+                        op,
+                        stats.total,
+                        stats.killed,
+                        stats.survived,
+                        stats.kill_rate * 100.0,
+                        stats.mean_duration_ms,
+                        stats.unique_kills
+                    )?;
+                }
+                writeln!(out)?;
+                if !result.summary.suggested_operators.is_empty() {
+                    writeln!(
+                        out,
+                        "Suggested operators: {}\n",
+                        result.summary.suggested_operators.join(",")
+                    )?;
                 }
-                println!();
             }
 
             if !result.files.is_empty() {
-                println!("## Files\n");
+                writeln!(out, "## Files\n")?;
                 for file in &result.files {
-                    println!("### {} (score: {:.1}%)\n", file.path, file.score * 100.0);
+                    writeln!(
+                        out,
+                        "### {} (score: {:.1}%)\n",
+                        file.path,
+                        file.score * 100.0
+                    )?;
                     if file.skipped > 0 {
-                        println!(
+                        writeln!(
+                            out,
                             "- Killed: {}, Survived: {}, Skipped: {}, Timeout: {}, Error: {}\n",
                             file.killed, file.survived, file.skipped, file.timeout, file.error
-                        );
+                        )?;
                     } else {
-                        println!(
+                        writeln!(
+                            out,
                             "- Killed: {}, Survived: {}, Timeout: {}, Error: {}\n",
                             file.killed, file.survived, file.timeout, file.error
-                        );
+                        )?;
                     }
                 }
             }
         }
         Format::Text => {
-            println!("Mutation Testing Report");
-            println!("=======================");
-            println!("Files: {}", result.summary.total_files);
-            println!("Mutants: {}", result.summary.total_mutants);
+            writeln!(out, "Mutation Testing Report")?;
+            writeln!(out, "=======================")?;
+            writeln!(out, "Files: {}", result.summary.total_files)?;
+            writeln!(out, "Mutants: {}", result.summary.total_mutants)?;
             if result.summary.skipped > 0 {
-                println!(
+                writeln!(
+                    out,
                     "Killed: {} | Survived: {} | Skipped: {} | Timeout: {} | Error: {}",
                     result.summary.killed,
                     result.summary.survived,
                     result.summary.skipped,
                     result.summary.timeout,
                     result.summary.error
-                );
+                )?;
             } else {
-                println!(
+                writeln!(
+                    out,
                     "Killed: {} | Survived: {} | Timeout: {} | Error: {}",
                     result.summary.killed,
                     result.summary.survived,
                     result.summary.timeout,
                     result.summary.error
-                );
+                )?;
             }
-            println!(
+            writeln!(
+                out,
                 "Mutation Score: {:.1}%",
                 result.summary.mutation_score * 100.0
+            )?;
+            writeln!(out, "Duration: {}ms", result.summary.duration_ms)?;
+            if let Some(ref baseline) = result.baseline {
+                writeln!(
+                    out,
+                    "Baseline: {}/{} test runs passed, slowest {}ms",
+                    baseline.passed, baseline.runs, baseline.duration_ms
+                )?;
+            }
+            if !result.summary.by_operator.is_empty() {
+                writeln!(out, "By operator:")?;
+            }
+            let mut operators: Vec<_> = result.summary.by_operator.iter().collect();
+            operators.sort_by_key(|(name, _)| *name);
+            for (op, stats) in operators {
+                writeln!(
+                    out,
+                    "  {}: kill rate {:.1}% | mean run {}ms | unique kills {}",
+                    op,
+                    stats.kill_rate * 100.0,
+                    stats.mean_duration_ms,
+                    stats.unique_kills
+                )?;
+            }
+            if !result.summary.suggested_operators.is_empty() {
+                writeln!(
+                    out,
+                    "Suggested operators: {}",
+                    result.summary.suggested_operators.join(",")
+                )?;
+            }
+        }
+        Format::Sarif | Format::Csv | Format::Tsv | Format::Github => {
+            format.format(&result, manifest.as_ref(), out)?
+        }
+    }
+
+    if args.apply_suggestions {
+        let suggested = &result.summary.suggested_operators;
+        if suggested.is_empty() {
+            eprintln!("No operator suggestions: no mutant was run");
+        } else {
+            // The last file in merge order wins, so the setting takes effect.
+            let config_path = match config_file {
+                Some(file) => file.to_path_buf(),
+                None => omen::status::discover_config_files(path)
+                    .pop()
+                    .unwrap_or_else(|| path.join("omen.toml")),
+            };
+            let value = suggested
+                .iter()
+                .map(|op| format!("\"{op}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            omen::config::set_value(&config_path, "mutation", "operators", &format!("[{value}]"))?;
+            eprintln!(
+                "Saved operators {} to {}",
+                suggested.join(","),
+                config_path.display()
             );
-            println!("Duration: {}ms", result.summary.duration_ms);
         }
-        Format::Sarif => format.format(&result, &mut stdout())?,
     }
 
     // Check mode: fail if score below threshold
@@ -1418,8 +2979,6 @@ fn run_mutation(
 
     // Save results to history if --record flag is set
     if args.record && !args.dry_run {
-        use std::io::Write;
-
         let history_path = path.join(".omen/mutation-history.jsonl");
 
         // Ensure .omen directory exists
@@ -1481,10 +3040,11 @@ fn run_outline(
     config: &Config,
     args: &OutlineArgs,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     use omen::analyzers::outline::{outline_file, Analyzer as OutlineAnalyzer, OutlineResult};
 
-    let result = if let Some(ref file_path) = args.file {
+    let (result, manifest) = if let Some(ref file_path) = args.file {
         // Single-file mode: resolve relative paths against the repo root (-p),
         // not the shell cwd, so `omen outline --file src/main.rs -p /some/repo`
         // works regardless of where the user ran the command from.
@@ -1494,20 +3054,23 @@ fn run_outline(
             file_path.clone()
         };
         let file_outline = outline_file(&resolved)?;
-        OutlineResult {
+        let file_set = FileSet::from_files(path.to_path_buf(), vec![resolved]);
+        let result = OutlineResult {
             files: vec![file_outline],
-        }
+        };
+        (result, manifest_for(path, &file_set, config, format))
     } else {
         // Repo mode
         let file_set = filtered_file_set(path, config, Some(&args.common))?;
         let ctx = build_context(path, &file_set, config);
         let analyzer = OutlineAnalyzer;
-        analyzer.analyze(&ctx)?
+        let result = analyzer.analyze(&ctx)?;
+        (result, manifest_for(path, &file_set, config, format))
     };
 
     match format {
         Format::Markdown | Format::Text => {
-            print!("{}", result.to_markdown());
+            write!(out, "{}", result.to_markdown())?;
         }
         _ => {
             let value = serde_json::to_value(&result)?;
@@ -1516,7 +3079,8 @@ fn run_outline(
                 format,
                 args.common.top,
                 args.common.offset,
-                &mut std::io::stdout(),
+                manifest.as_ref(),
+                out,
             )?;
         }
     }
@@ -1528,6 +3092,7 @@ fn run_impact(
     config: &Config,
     args: &ImpactArgs,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     use omen::analyzers::impact::{analyze, Direction};
     use omen::cli::ImpactDirection;
@@ -1543,12 +3108,14 @@ fn run_impact(
 
     let report = analyze(path, &files, &args.symbol, args.depth, direction)?;
     let value = serde_json::to_value(&report)?;
+    let manifest = manifest_for(path, &file_set, config, format);
     format_with_limits(
         value,
         format,
         args.common.top,
         args.common.offset,
-        &mut std::io::stdout(),
+        manifest.as_ref(),
+        out,
     )?;
     Ok(())
 }
@@ -1558,6 +3125,7 @@ fn run_symbol(
     config: &Config,
     args: &SymbolArgs,
     format: Format,
+    out: &mut Output,
 ) -> omen::core::Result<()> {
     use omen::symbol::{get_symbol, SymbolOptions};
 
@@ -1571,17 +3139,253 @@ fn run_symbol(
 
     let report = get_symbol(path, &files, &args.name, &opts)?;
     let value = serde_json::to_value(&report)?;
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(
+        value,
+        format,
+        args.common.top,
+        args.common.offset,
+        manifest.as_ref(),
+        out,
+    )?;
+    Ok(())
+}
+
+fn run_fix(
+    path: &PathBuf,
+    config: &Config,
+    args: &FixArgs,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    use omen::cli::FixKind;
+    use omen::fix::Planner;
+
+    if config.git.at_ref.is_some() {
+        return Err(omen::core::Error::InvalidArgument(
+            "fix edits the working tree and cannot run with --at-ref".to_string(),
+        ));
+    }
+    let wants = |kind: FixKind| args.only.is_empty() || args.only.contains(&kind);
+
+    // Reachability needs the whole project; the filters only pick the files
+    // that get fixed.
+    let file_set = filtered_file_set(path, config, None)?;
+    let selected = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let mut planner = Planner::new(path).with_files(selected.iter().map(|p| p.to_path_buf()));
+
+    if wants(FixKind::DeadCode) {
+        let analysis = omen::analyzers::deadcode::Analyzer::default().analyze(&ctx)?;
+        planner.dead_code(&analysis);
+    }
+    if wants(FixKind::StaleFlags) {
+        let analysis = omen::analyzers::flags::Analyzer::default().analyze(&ctx)?;
+        let mut rolled_out = config.feature_flags.rolled_out.clone();
+        rolled_out.extend(args.rolled_out.iter().cloned());
+        let reported = provider_rolled_out(config, &analysis, &rolled_out);
+        rolled_out.extend(reported);
+        planner.stale_flags(&analysis, &rolled_out);
+    }
+    if wants(FixKind::ResolvedSatd) {
+        let analysis = omen::analyzers::satd::Analyzer::default().analyze(&ctx)?;
+        planner.resolved_satd(&analysis, &closed_issues(path, &analysis));
+    }
+
+    let plan = planner.finish();
+    if args.dry_run {
+        write!(out, "{}", plan.diff())?;
+    } else {
+        plan.apply()?;
+        format.format(
+            &plan,
+            manifest_for(path, &file_set, config, format).as_ref(),
+            out,
+        )?;
+    }
+    eprintln!(
+        "{} fix(es) in {} file(s){}, {} finding(s) skipped",
+        plan.summary.fixes,
+        plan.summary.files,
+        if args.dry_run { " (dry run)" } else { "" },
+        plan.summary.skipped
+    );
+    Ok(())
+}
+
+/// `omen deadcode`, writing removal patches with `--emit-patches`.
+fn run_deadcode(
+    path: &PathBuf,
+    config: &Config,
+    args: &DeadcodeArgs,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let Some(ref dir) = args.emit_patches else {
+        return run_analyzer::<omen::analyzers::deadcode::Analyzer>(
+            path,
+            config,
+            format,
+            Some(&args.common),
+            out,
+        );
+    };
+    if config.git.at_ref.is_some() {
+        return Err(omen::core::Error::InvalidArgument(
+            "--emit-patches diffs the working tree and cannot run with --at-ref".to_string(),
+        ));
+    }
+
+    // As with `omen fix`, reachability needs the whole project; the filters
+    // only pick the files that get patches.
+    let file_set = filtered_file_set(path, config, None)?;
+    let selected = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let start = Instant::now();
+    let analysis = omen::analyzers::deadcode::Analyzer::default().analyze(&ctx)?;
+    report_timing("deadcode", start.elapsed());
+
+    let mut planner = omen::fix::Planner::new(path)
+        .with_files(selected.iter().map(|p| p.to_path_buf()))
+        .with_min_confidence(args.patch_confidence);
+    planner.dead_code(&analysis);
+    let plan = planner.finish();
+    let written = plan.write_patches(dir)?;
+    eprintln!(
+        "Wrote {} patch(es) removing {} item(s) to {}, {} finding(s) skipped",
+        written.len(),
+        plan.summary.fixes,
+        dir.display(),
+        plan.summary.skipped
+    );
+
+    let value = serde_json::to_value(&analysis)?;
+    let verdict = check_fail_on(config, "deadcode", &value);
+    let manifest = manifest_for(path, &file_set, config, format);
+    format_with_limits(
+        value,
+        format,
+        args.common.top,
+        args.common.offset,
+        manifest.as_ref(),
+        out,
+    )?;
+    verdict
+}
+
+/// Stale flags not in `listed` that their provider reports fully rolled out.
+/// A provider that cannot be reached is skipped with a warning, leaving its
+/// flags to the configured list.
+fn provider_rolled_out(
+    config: &Config,
+    analysis: &omen::analyzers::flags::Analysis,
+    listed: &[String],
+) -> Vec<String> {
+    use omen::rollout::{Provider, RolloutClient};
+
+    let mut clients: HashMap<Provider, Option<RolloutClient>> = HashMap::new();
+    let mut reported = Vec::new();
+    for flag in analysis.flags.iter().filter(|f| f.stale) {
+        let Some(provider) = Provider::from_name(&flag.provider) else {
+            continue;
+        };
+        if listed.contains(&flag.key) || reported.contains(&flag.key) {
+            continue;
+        }
+        let client = clients.entry(provider).or_insert_with(|| {
+            RolloutClient::new(provider, &config.feature_flags)
+                .map_err(|e| eprintln!("Warning: not looking up {provider:?} rollouts: {e}"))
+                .ok()
+        });
+        let Some(client) = client else {
+            continue;
+        };
+        match client.fully_rolled_out(&flag.key) {
+            Ok(true) => reported.push(flag.key.clone()),
+            Ok(false) => {}
+            Err(e) => eprintln!("Warning: could not look up flag `{}`: {}", flag.key, e),
+        }
+    }
+    reported
+}
+
+/// Issues referenced by SATD comments that the forge reports closed. Without
+/// a reachable forge the set is empty, so no comment is stripped.
+fn closed_issues(path: &Path, analysis: &omen::analyzers::satd::Analysis) -> HashSet<u64> {
+    let issues: BTreeSet<u64> = analysis
+        .items
+        .iter()
+        .flat_map(|item| omen::fix::issue_refs(&item.text))
+        .collect();
+    if issues.is_empty() {
+        return HashSet::new();
+    }
+    let remote = omen::git::GitRepo::open(path)
+        .ok()
+        .and_then(|repo| repo.remote_url("origin"));
+    let client = match remote.map(|url| omen::git::ForgeClient::from_remote(&url)) {
+        Some(Ok(client)) => client,
+        Some(Err(e)) => {
+            eprintln!("Warning: skipping resolved SATD: {}", e);
+            return HashSet::new();
+        }
+        None => {
+            eprintln!("Warning: skipping resolved SATD: no `origin` remote to look up issues");
+            return HashSet::new();
+        }
+    };
+    issues
+        .into_iter()
+        .filter(|&number| match client.issue_closed(number) {
+            Ok(closed) => closed,
+            Err(e) => {
+                eprintln!("Warning: could not look up issue #{}: {}", number, e);
+                false
+            }
+        })
+        .collect()
+}
+
+fn run_tui(path: &PathBuf, config: &Config, args: &AnalyzerArgs) -> omen::core::Result<()> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        return Err(omen::core::Error::InvalidArgument(
+            "tui needs an interactive terminal".to_string(),
+        ));
+    }
+    let file_set = filtered_file_set(path, config, Some(args))?;
+    let ctx = build_context(path, &file_set, config);
+    omen::tui::run(&ctx)
+}
+
+fn run_query(
+    path: &PathBuf,
+    config: &Config,
+    args: &QueryArgs,
+    format: Format,
+    out: &mut Output,
+) -> omen::core::Result<()> {
+    let file_set = filtered_file_set(path, config, Some(&args.common))?;
+    let ctx = build_context(path, &file_set, config);
+    let value = omen::query::run(&args.expr, &ctx)?;
+    let manifest = manifest_for(path, &file_set, config, format);
     format_with_limits(
         value,
         format,
         args.common.top,
         args.common.offset,
-        &mut std::io::stdout(),
+        manifest.as_ref(),
+        out,
     )?;
     Ok(())
 }
 
-fn run_mutation_train(path: &std::path::Path, args: &MutationTrainArgs) -> omen::core::Result<()> {
+fn run_mutation_train(
+    path: &std::path::Path,
+    args: &MutationTrainArgs,
+    out: &mut Output,
+) -> omen::core::Result<()> {
     use omen::analyzers::mutation::ml_predictor::{SurvivabilityPredictor, TrainingData};
     use std::io::BufRead;
 
@@ -1630,10 +3434,11 @@ fn run_mutation_train(path: &std::path::Path, args: &MutationTrainArgs) -> omen:
         ));
     }
 
-    println!(
+    writeln!(
+        out,
         "Training model from {} historical results...",
         training_data.len()
-    );
+    )?;
 
     // Train the predictor
     let mut predictor = SurvivabilityPredictor::new();
@@ -1653,10 +3458,10 @@ fn run_mutation_train(path: &std::path::Path, args: &MutationTrainArgs) -> omen:
         .save(&model_path)
         .map_err(|e| omen::core::Error::analysis(format!("Failed to save model: {}", e)))?;
 
-    println!("Model saved to {}", model_path.display());
-    println!("\nOperator kill rates learned:");
+    writeln!(out, "Model saved to {}", model_path.display())?;
+    writeln!(out, "\nOperator kill rates learned:")?;
     for (op, rate) in predictor.operator_kill_rates() {
-        println!("  {}: {:.1}%", op, rate * 100.0);
+        writeln!(out, "  {}: {:.1}%", op, rate * 100.0)?;
     }
 
     Ok(())
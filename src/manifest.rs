@@ -0,0 +1,451 @@
+//! Analysis manifest: the snapshot, config and omen build behind a result.
+//!
+//! Commands build one from the files they analyzed and hand it to the
+//! formatter, so JSON and SARIF analysis output, and the report's
+//! `metadata.json`, carry it. When results feed an audit trail, [`verify`] confirms that a
+//! set of them came from the same snapshot of the code, analyzed the same
+//! way.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::analyzers::ANALYZER_VERSIONS;
+use crate::config::Config;
+use crate::core::{Error, FileSet, Result};
+use crate::git::GitRepo;
+
+/// Where a result came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub omen_version: String,
+    /// Hash of the effective config, after profiles and CLI overrides.
+    pub config_hash: String,
+    /// Commit analyzed; absent outside a git repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    /// Whether the worktree had uncommitted or untracked changes.
+    pub dirty: bool,
+    pub file_count: usize,
+    pub analyzer_versions: BTreeMap<String, u32>,
+    /// Hash of the analyzed snapshot: the commit plus the contents of dirty
+    /// files, or every file's path and contents outside git.
+    pub fingerprint: String,
+}
+
+impl Manifest {
+    /// Build the manifest for analyzing `files` under `root` with `config`.
+    pub fn build(root: &Path, files: &FileSet, config: &Config) -> Result<Self> {
+        let git = GitRepo::open(root)
+            .ok()
+            .map(|repo| repo.at_ref(config.git.at_ref.as_deref()));
+        let git_sha = git.as_ref().and_then(|repo| repo.rev_sha().ok());
+
+        let mut fingerprint = Xxh3::new();
+        let dirty = match (&git, &git_sha) {
+            (Some(repo), Some(sha)) => {
+                fingerprint.update(sha.as_bytes());
+                // A revision is read from the object database, so the
+                // worktree can't make it dirty.
+                let dirty_paths = if repo.reads_from_objects() {
+                    Vec::new()
+                } else {
+                    repo.dirty_paths()?
+                };
+                for path in &dirty_paths {
+                    hash_file(&mut fingerprint, repo.root(), path);
+                }
+                !dirty_paths.is_empty()
+            }
+            _ => {
+                let mut paths: Vec<PathBuf> = files
+                    .files()
+                    .iter()
+                    .map(|p| p.strip_prefix(root).unwrap_or(p).to_path_buf())
+                    .collect();
+                paths.sort();
+                for path in &paths {
+                    hash_file(&mut fingerprint, root, path);
+                }
+                false
+            }
+        };
+
+        Ok(Self {
+            omen_version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: config_hash(config)?,
+            git_sha,
+            dirty,
+            file_count: files.len(),
+            analyzer_versions: ANALYZER_VERSIONS
+                .iter()
+                .map(|(name, version)| (name.to_string(), *version))
+                .collect(),
+            fingerprint: format!("{:016x}", fingerprint.digest()),
+        })
+    }
+
+    /// Add this manifest to a command's JSON output. Objects get a top-level
+    /// `manifest` key; SARIF logs get it in each run's `properties`. Other
+    /// values are returned unchanged.
+    pub fn attach(&self, mut value: Value) -> Value {
+        let manifest = serde_json::to_value(self).unwrap_or_default();
+        if let Some(runs) = value.get_mut("runs").and_then(Value::as_array_mut) {
+            for run in runs.iter_mut().filter(|run| run.is_object()) {
+                run["properties"]["manifest"] = manifest.clone();
+            }
+        } else if let Some(map) = value.as_object_mut() {
+            map.insert("manifest".to_string(), manifest);
+        }
+        value
+    }
+
+    /// The manifests attached to a result with [`Manifest::attach`].
+    pub fn extract(value: &Value) -> Vec<Self> {
+        let attached = match value.get("runs").and_then(Value::as_array) {
+            Some(runs) => runs
+                .iter()
+                .filter_map(|run| run.pointer("/properties/manifest"))
+                .collect(),
+            None => value.get("manifest").into_iter().collect::<Vec<_>>(),
+        };
+        attached
+            .into_iter()
+            .filter_map(|m| serde_json::from_value(m.clone()).ok())
+            .collect()
+    }
+}
+
+/// Hash of the effective config, independent of map iteration order.
+pub(crate) fn config_hash(config: &Config) -> Result<String> {
+    let config_value = sorted_keys(serde_json::to_value(config)?);
+    Ok(format!(
+        "{:016x}",
+        xxhash_rust::xxh3::xxh3_64(config_value.to_string().as_bytes())
+    ))
+}
+
+/// `value` with object keys sorted, so config maps hash the same regardless
+/// of iteration order.
+fn sorted_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sorted_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sorted_keys).collect()),
+        value => value,
+    }
+}
+
+fn hash_file(hasher: &mut Xxh3, root: &Path, path: &Path) {
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(&[0]);
+    // Deleted files hash as their path alone.
+    if let Ok(contents) = std::fs::read(root.join(path)) {
+        hasher.update(&contents);
+    }
+    hasher.update(&[0]);
+}
+
+/// Manifests read from result files, and the files that had none.
+#[derive(Debug, Default)]
+pub struct Collected {
+    pub manifests: Vec<(String, Manifest)>,
+    pub unstamped: Vec<String>,
+}
+
+/// Read the manifests of result files. A JSON file may also hold one result
+/// per line, as `omen all --stream` writes. A directory contributes its
+/// stamped `.json` and `.sarif` files, such as `metadata.json` in a report
+/// data directory.
+pub fn collect(paths: &[PathBuf]) -> Result<Collected> {
+    let mut collected = Collected::default();
+    for path in paths {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = std::fs::read_dir(path)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    p.extension()
+                        .is_some_and(|ext| ext == "json" || ext == "sarif")
+                })
+                .collect();
+            entries.sort();
+            for entry in entries {
+                let source = entry.display().to_string();
+                for manifest in read_manifests(&entry)? {
+                    collected.manifests.push((source.clone(), manifest));
+                }
+            }
+            continue;
+        }
+        let source = path.display().to_string();
+        let manifests = read_manifests(path)?;
+        if manifests.is_empty() {
+            collected.unstamped.push(source);
+        }
+        for manifest in manifests {
+            collected.manifests.push((source.clone(), manifest));
+        }
+    }
+    Ok(collected)
+}
+
+fn read_manifests(path: &Path) -> Result<Vec<Manifest>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::InvalidArgument(format!("Cannot read {}: {e}", path.display())))?;
+    if let Ok(value) = serde_json::from_str::<Value>(&content) {
+        return Ok(Manifest::extract(&value));
+    }
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .flat_map(|value| Manifest::extract(&value))
+        .collect())
+}
+
+/// Whether a set of results came from the same snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Verification {
+    pub consistent: bool,
+    /// Number of manifests compared.
+    pub results: usize,
+    /// The shared manifest, when consistent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<Manifest>,
+    /// Manifest fields that differ between results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mismatches: Vec<Mismatch>,
+    /// Result files without a manifest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unstamped: Vec<String>,
+}
+
+/// A manifest field with different values across results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mismatch {
+    pub field: String,
+    /// Each value seen, with the results that had it.
+    pub values: BTreeMap<String, Vec<String>>,
+}
+
+/// Compare the manifests of a set of results field by field.
+pub fn verify(collected: Collected) -> Verification {
+    let mut fields: BTreeMap<&str, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for (source, manifest) in &collected.manifests {
+        let Ok(Value::Object(map)) = serde_json::to_value(manifest) else {
+            continue;
+        };
+        for field in [
+            "fingerprint",
+            "git_sha",
+            "dirty",
+            "config_hash",
+            "omen_version",
+            "file_count",
+            "analyzer_versions",
+        ] {
+            let value = map.get(field).cloned().unwrap_or(Value::Null).to_string();
+            let sources = fields.entry(field).or_default().entry(value).or_default();
+            if !sources.contains(source) {
+                sources.push(source.clone());
+            }
+        }
+    }
+    let mismatches: Vec<Mismatch> = fields
+        .into_iter()
+        .filter(|(_, values)| values.len() > 1)
+        .map(|(field, values)| Mismatch {
+            field: field.to_string(),
+            values,
+        })
+        .collect();
+    let consistent =
+        !collected.manifests.is_empty() && mismatches.is_empty() && collected.unstamped.is_empty();
+    Verification {
+        consistent,
+        results: collected.manifests.len(),
+        manifest: collected
+            .manifests
+            .first()
+            .filter(|_| consistent)
+            .map(|(_, m)| m.clone()),
+        mismatches,
+        unstamped: collected.unstamped,
+    }
+}
+
+impl Verification {
+    /// Fail with the differing fields unless every result came from the same
+    /// snapshot.
+    pub fn check(&self) -> Result<()> {
+        if self.consistent {
+            return Ok(());
+        }
+        if self.results == 0 {
+            return Err(Error::analysis("No analysis manifests found"));
+        }
+        let mut report = "Results do not come from the same snapshot".to_string();
+        for mismatch in &self.mismatches {
+            report.push_str(&format!("\n{}:", mismatch.field));
+            for (value, sources) in &mismatch.values {
+                report.push_str(&format!("\n  {value}: {}", sources.join(", ")));
+            }
+        }
+        if !self.unstamped.is_empty() {
+            report.push_str(&format!("\nNo manifest: {}", self.unstamped.join(", ")));
+        }
+        Err(Error::analysis(report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{git, init_repo};
+    use tempfile::TempDir;
+
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        init_repo(dir.path());
+        std::fs::write(dir.path().join("lib.py"), "def f():\n    return 1\n").unwrap();
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "-m", "init"]);
+        dir
+    }
+
+    fn build(root: &Path, config: &Config) -> Manifest {
+        let files = FileSet::from_path(root, config).unwrap();
+        Manifest::build(root, &files, config).unwrap()
+    }
+
+    #[test]
+    fn test_manifest_tracks_commit_and_dirty_state() {
+        let dir = repo();
+        let config = Config::default();
+        let clean = build(dir.path(), &config);
+        assert_eq!(clean.git_sha.as_ref().map(String::len), Some(40));
+        assert!(!clean.dirty);
+        assert_eq!(clean.file_count, 1);
+        assert_eq!(clean.analyzer_versions["complexity"], 1);
+        assert_eq!(build(dir.path(), &config), clean);
+
+        std::fs::write(dir.path().join("lib.py"), "def f():\n    return 2\n").unwrap();
+        let dirty = build(dir.path(), &config);
+        assert!(dirty.dirty);
+        assert_eq!(dirty.git_sha, clean.git_sha);
+        assert_ne!(dirty.fingerprint, clean.fingerprint);
+
+        let mut strict = Config::default();
+        strict.fail_on = Some(crate::core::Severity::High);
+        assert_ne!(build(dir.path(), &strict).config_hash, dirty.config_hash);
+    }
+
+    #[test]
+    fn test_manifest_counts_the_files_analyzed() {
+        let dir = repo();
+        std::fs::write(dir.path().join("util.py"), "def g():\n    pass\n").unwrap();
+        let config = Config::default();
+        let files = FileSet::from_path(dir.path(), &config).unwrap();
+        let lib_only = files.filter_by_glob("lib.py");
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            Manifest::build(dir.path(), &lib_only, &config)
+                .unwrap()
+                .file_count,
+            1
+        );
+    }
+
+    #[test]
+    fn test_manifest_outside_git_hashes_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.go"), "package main\n").unwrap();
+        let config = Config::default();
+        let before = build(dir.path(), &config);
+        assert_eq!(before.git_sha, None);
+        std::fs::write(dir.path().join("main.go"), "package other\n").unwrap();
+        let after = build(dir.path(), &config);
+        assert_ne!(before.fingerprint, after.fingerprint);
+    }
+
+    #[test]
+    fn test_attach_and_extract_round_trip() {
+        let dir = repo();
+        let manifest = build(dir.path(), &Config::default());
+
+        let object = manifest.attach(serde_json::json!({"files": []}));
+        assert_eq!(Manifest::extract(&object), vec![manifest.clone()]);
+
+        let sarif = manifest.attach(serde_json::json!({"version": "2.1.0", "runs": [{}]}));
+        assert!(sarif.get("manifest").is_none());
+        assert_eq!(Manifest::extract(&sarif), vec![manifest.clone()]);
+
+        let array = manifest.attach(serde_json::json!([1, 2]));
+        assert!(Manifest::extract(&array).is_empty());
+    }
+
+    #[test]
+    fn test_verify_reports_mismatched_fields() {
+        let dir = repo();
+        let manifest = build(dir.path(), &Config::default());
+        std::fs::write(
+            dir.path().join("complexity.json"),
+            manifest
+                .attach(serde_json::json!({"files": []}))
+                .to_string(),
+        )
+        .unwrap();
+        let stream = [1, 2]
+            .map(|n| {
+                manifest
+                    .attach(serde_json::json!({"analyzer": n}))
+                    .to_string()
+            })
+            .join("\n");
+        std::fs::write(dir.path().join("all.json"), stream).unwrap();
+
+        let paths = [
+            dir.path().join("complexity.json"),
+            dir.path().join("all.json"),
+        ];
+        let verification = verify(collect(&paths).unwrap());
+        assert!(verification.consistent, "{verification:?}");
+        assert_eq!(verification.results, 3);
+        assert!(verification.check().is_ok());
+
+        let mut other = manifest.clone();
+        other.dirty = true;
+        other.fingerprint = "0".repeat(16);
+        std::fs::write(
+            dir.path().join("satd.json"),
+            other.attach(serde_json::json!({"items": []})).to_string(),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("plain.json"), "{}").unwrap();
+        let verification = verify(collect(&[dir.path().to_path_buf()]).unwrap());
+        assert!(!verification.consistent);
+        let fields: Vec<&str> = verification
+            .mismatches
+            .iter()
+            .map(|m| m.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["dirty", "fingerprint"]);
+        assert!(verification.unstamped.is_empty(), "directories skip them");
+        let report = verification.check().unwrap_err().to_string();
+        assert!(report.contains("satd.json"), "{report}");
+
+        let verification = verify(collect(&[dir.path().join("plain.json")]).unwrap());
+        assert!(!verification.consistent);
+        assert_eq!(verification.unstamped.len(), 1);
+    }
+}
@@ -0,0 +1,246 @@
+//! Pass/fail verdicts for the check tools.
+//!
+//! `check_complexity`, `check_score` and `check_mutation` apply the same
+//! thresholds as the CLI `--check` modes but return the outcome as data
+//! instead of an exit code, so an agent gating CI reads `passed` rather
+//! than parsing stderr. Baselines are only read: the server never writes
+//! into the repository.
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::analyzers::{complexity, mutation};
+use crate::core::{Baseline, BaselineEntry};
+
+/// Outcome of one check.
+#[derive(Debug, Serialize)]
+pub(super) struct Verdict {
+    pub check: &'static str,
+    pub passed: bool,
+    /// One-line outcome, worded like the CLI's.
+    pub message: String,
+    /// Thresholds applied, after config defaults.
+    pub thresholds: Value,
+    /// What fails the check; empty when it passes. Baselined violations
+    /// are left out.
+    pub violations: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineCounts>,
+}
+
+/// How the current violations compare to the baseline.
+#[derive(Debug, Serialize)]
+pub(super) struct BaselineCounts {
+    pub known: usize,
+    pub fixed: usize,
+    pub new: usize,
+}
+
+/// Read the baseline named by the `baseline` argument, relative to `root`.
+pub(super) fn load_baseline(
+    root: &Path,
+    check: &str,
+    arguments: &Value,
+) -> Result<Option<Baseline>, String> {
+    let Some(file) = arguments.get("baseline").and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    Baseline::load(&root.join(file), check)
+        .map(Some)
+        .map_err(|e| format!("Failed to load baseline: {}", e))
+}
+
+/// Functions over either complexity limit fail the check.
+pub(super) fn complexity(
+    root: &Path,
+    analysis: &complexity::Analysis,
+    max_cyclomatic: u32,
+    max_cognitive: u32,
+    baseline: Option<&Baseline>,
+) -> Verdict {
+    let found = analysis
+        .check_thresholds(max_cyclomatic, max_cognitive)
+        .err()
+        .unwrap_or_default();
+    let total = found.len();
+    let entries = found
+        .iter()
+        .map(|v| BaselineEntry::new(root, &v.file, &v.name, (v.cyclomatic + v.cognitive) as f64))
+        .collect();
+    let violations = found.iter().map(|v| json!(v)).collect();
+    let (violations, baseline) = against_baseline(violations, entries, baseline);
+
+    let message = if !violations.is_empty() {
+        format!(
+            "{} function(s) exceed complexity thresholds",
+            violations.len()
+        )
+    } else if total > 0 {
+        format!("No new complexity violations ({total} in baseline)")
+    } else {
+        format!(
+            "All {} functions within thresholds (cyclomatic <= {max_cyclomatic}, \
+             cognitive <= {max_cognitive})",
+            analysis.summary.total_functions
+        )
+    };
+    Verdict {
+        check: "complexity",
+        passed: violations.is_empty(),
+        message,
+        thresholds: json!({"max_cyclomatic": max_cyclomatic, "max_cognitive": max_cognitive}),
+        violations,
+        baseline,
+    }
+}
+
+/// An overall score below `min_score` fails the check.
+pub(super) fn score(
+    root: &Path,
+    analysis: &crate::score::Analysis,
+    min_score: f64,
+    baseline: Option<&Baseline>,
+) -> Verdict {
+    let shortfall = min_score - analysis.overall_score;
+    let (violations, entries) = if shortfall > 0.0 {
+        (
+            vec![json!({
+                "name": "overall",
+                "score": analysis.overall_score,
+                "grade": analysis.grade,
+                "shortfall": shortfall,
+            })],
+            vec![BaselineEntry::new(root, "", "overall", shortfall)],
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let (violations, baseline) = against_baseline(violations, entries, baseline);
+
+    let relation = if shortfall <= 0.0 {
+        "meets minimum"
+    } else if violations.is_empty() {
+        "is below minimum, within baseline,"
+    } else {
+        "is below minimum"
+    };
+    Verdict {
+        check: "score",
+        passed: violations.is_empty(),
+        message: format!(
+            "Score {:.1} ({}) {relation} {min_score:.1}",
+            analysis.overall_score, analysis.grade
+        ),
+        thresholds: json!({"min_score": min_score}),
+        violations,
+        baseline,
+    }
+}
+
+/// A mutation score below `min_score` fails the check; the surviving
+/// mutants are its violations.
+pub(super) fn mutation(analysis: &mutation::Analysis, min_score: f64) -> Verdict {
+    let score = analysis.summary.mutation_score;
+    let passed = score >= min_score;
+    let violations = if passed {
+        Vec::new()
+    } else {
+        analysis
+            .files
+            .iter()
+            .flat_map(|file| &file.mutants)
+            .filter(|result| result.status.is_survived())
+            .map(|result| {
+                json!({
+                    "file": result.mutant.file_path,
+                    "line": result.mutant.line,
+                    "operator": result.mutant.operator,
+                    "description": result.mutant.description,
+                })
+            })
+            .collect()
+    };
+    let relation = if passed { "meets" } else { "is below" };
+    Verdict {
+        check: "mutation",
+        passed,
+        message: format!(
+            "Mutation score {:.1}% {relation} minimum threshold {:.1}%",
+            score * 100.0,
+            min_score * 100.0
+        ),
+        thresholds: json!({"min_score": min_score}),
+        violations,
+        baseline: None,
+    }
+}
+
+/// Keep the violations whose baseline entry is new or worse than recorded.
+fn against_baseline(
+    violations: Vec<Value>,
+    entries: Vec<BaselineEntry>,
+    baseline: Option<&Baseline>,
+) -> (Vec<Value>, Option<BaselineCounts>) {
+    let Some(baseline) = baseline else {
+        return (violations, None);
+    };
+    let diff = baseline.diff(entries.clone());
+    let counts = BaselineCounts {
+        known: diff.existing.len(),
+        fixed: diff.fixed.len(),
+        new: diff.new.len(),
+    };
+    let failing = violations
+        .into_iter()
+        .zip(entries)
+        .filter(|(_, entry)| diff.new.contains(entry))
+        .map(|(v, _)| v)
+        .collect();
+    (failing, Some(counts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn score_analysis(overall_score: f64) -> crate::score::Analysis {
+        crate::score::Analysis {
+            overall_score,
+            grade: "C".to_string(),
+            components: HashMap::new(),
+            summary: crate::score::AnalysisSummary {
+                files_analyzed: 1,
+                analyzers_run: 1,
+                critical_issues: 0,
+            },
+            weights: crate::score::ScoreWeights::default(),
+            packages: Vec::new(),
+            metrics: BTreeMap::new(),
+            budgets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_score_verdict_respects_baseline() {
+        let root = Path::new("/repo");
+        let analysis = score_analysis(72.0);
+
+        let verdict = score(root, &analysis, 80.0, None);
+        assert!(!verdict.passed);
+        assert_eq!(verdict.violations[0]["shortfall"], 8.0);
+        assert!(verdict.baseline.is_none());
+
+        let recorded = Baseline::new("score", vec![BaselineEntry::new(root, "", "overall", 10.0)]);
+        let verdict = score(root, &analysis, 80.0, Some(&recorded));
+        assert!(verdict.passed, "{}", verdict.message);
+        assert!(verdict.violations.is_empty());
+        assert_eq!(verdict.baseline.as_ref().unwrap().known, 1);
+
+        let verdict = score(root, &score_analysis(85.0), 80.0, Some(&recorded));
+        assert!(verdict.passed);
+        assert_eq!(verdict.baseline.unwrap().fixed, 1);
+    }
+}
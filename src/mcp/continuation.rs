@@ -0,0 +1,219 @@
+//! Summary-first paging of large tool results.
+//!
+//! Tool results keep every scalar field (summaries, totals) and only the
+//! top-level arrays are paged. When items remain, the full result is kept in
+//! a small in-memory store and the response carries a `continuation_token`
+//! that the `fetch_more` tool resolves to the next page. Pages are also shrunk
+//! until the serialized result fits in [`MAX_RESULT_CHARS`], so a client never
+//! receives JSON cut off mid-array.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::output::truncate_lists;
+
+/// Upper bound on the serialized size of one page of results.
+pub(super) const MAX_RESULT_CHARS: usize = 48_000;
+
+/// Number of full results kept for continuation; older tokens expire.
+const MAX_STORED: usize = 16;
+
+/// One page of a tool result.
+pub(super) struct Page {
+    pub value: Value,
+    pub total_items: Option<usize>,
+    pub returned: Option<usize>,
+    /// Items per array actually used for this page (0 = unlimited).
+    pub limit: usize,
+    /// Items left after this page across all paged arrays.
+    pub remaining: usize,
+}
+
+/// Cut one page out of `value`.
+///
+/// With `nested`, each top-level object field is paged on its own (the shape
+/// `analyze_many` returns); otherwise the top-level arrays are paged.
+pub(super) fn page(value: &Value, limit: usize, offset: usize, nested: bool) -> Page {
+    let total_items = count(value, nested);
+    let mut limit = limit;
+    if limit == 0 {
+        // Unlimited requests are still bounded by the response budget.
+        limit = longest_array(value, nested);
+    }
+    loop {
+        let mut paged = value.clone();
+        let remaining = truncate(&mut paged, limit, offset, nested);
+        let fits = serde_json::to_string(&paged).map_or(0, |s| s.len()) <= MAX_RESULT_CHARS;
+        if fits || limit <= 1 {
+            return Page {
+                returned: count(&paged, nested),
+                value: paged,
+                total_items,
+                limit,
+                remaining,
+            };
+        }
+        limit /= 2;
+    }
+}
+
+fn truncate(value: &mut Value, limit: usize, offset: usize, nested: bool) -> usize {
+    match value {
+        Value::Object(map) if nested => map
+            .values_mut()
+            .map(|v| truncate_lists(v, limit, offset))
+            .sum(),
+        _ => truncate_lists(value, limit, offset),
+    }
+}
+
+fn count(value: &Value, nested: bool) -> Option<usize> {
+    match value {
+        Value::Object(map) if nested => {
+            let counts: Vec<usize> = map.values().filter_map(super::count_items).collect();
+            (!counts.is_empty()).then(|| counts.iter().sum())
+        }
+        _ => super::count_items(value),
+    }
+}
+
+fn longest_array(value: &Value, nested: bool) -> usize {
+    let arrays = |v: &Value| -> usize {
+        match v {
+            Value::Array(arr) => arr.len(),
+            Value::Object(map) => map
+                .values()
+                .filter_map(Value::as_array)
+                .map(Vec::len)
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        }
+    };
+    match value {
+        Value::Object(map) if nested => map.values().map(arrays).max().unwrap_or(0),
+        _ => arrays(value),
+    }
+}
+
+/// A full result held for later pages.
+pub(super) struct Stored {
+    pub tool: String,
+    pub nested: bool,
+    pub value: Arc<Value>,
+}
+
+/// Bounded store of results awaiting `fetch_more`.
+#[derive(Default)]
+pub(super) struct ContinuationStore {
+    next_id: u64,
+    entries: VecDeque<(u64, Stored)>,
+}
+
+impl ContinuationStore {
+    /// Keep `stored` and return its id, evicting the oldest entry when full.
+    pub fn insert(&mut self, stored: Stored) -> u64 {
+        if self.entries.len() >= MAX_STORED {
+            self.entries.pop_front();
+        }
+        self.next_id += 1;
+        self.entries.push_back((self.next_id, stored));
+        self.next_id
+    }
+
+    /// Look up the result and offset a token refers to.
+    pub fn resolve(&self, token: &str) -> std::result::Result<(u64, usize, &Stored), String> {
+        let (id, offset) = token
+            .split_once('.')
+            .and_then(|(id, offset)| Some((id.parse().ok()?, offset.parse().ok()?)))
+            .ok_or_else(|| format!("Invalid continuation token: {token}"))?;
+        self.entries
+            .iter()
+            .find(|(stored_id, _)| *stored_id == id)
+            .map(|(_, stored)| (id, offset, stored))
+            .ok_or_else(|| {
+                format!("Continuation token expired: {token}; re-run the original tool call")
+            })
+    }
+}
+
+/// Encode the token that resumes result `id` at `offset`.
+pub(super) fn token(id: u64, offset: usize) -> String {
+    format!("{id}.{offset}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_page_keeps_summary_and_reports_remaining() {
+        let items: Vec<i64> = (0..10).collect();
+        let value = json!({"summary": {"total": 10}, "items": items});
+
+        let first = page(&value, 4, 0, false);
+        assert_eq!(first.value["summary"]["total"], 10);
+        assert_eq!(first.value["items"], json!([0, 1, 2, 3]));
+        assert_eq!(first.remaining, 6);
+        assert_eq!(first.total_items, Some(10));
+        assert_eq!(first.returned, Some(4));
+
+        let last = page(&value, 4, 8, false);
+        assert_eq!(last.value["items"], json!([8, 9]));
+        assert_eq!(last.remaining, 0);
+    }
+
+    #[test]
+    fn test_page_shrinks_to_fit_budget() {
+        let big = "x".repeat(1_000);
+        let items: Vec<String> = (0..200).map(|_| big.clone()).collect();
+        let value = json!({"items": items});
+
+        let page = page(&value, 0, 0, false);
+        assert!(serde_json::to_string(&page.value).unwrap().len() <= MAX_RESULT_CHARS);
+        assert!(page.limit < 200);
+        assert_eq!(page.remaining, 200 - page.limit);
+    }
+
+    #[test]
+    fn test_page_nested_pages_each_result() {
+        let value = json!({
+            "complexity": {"files": [1, 2, 3]},
+            "satd": {"items": [1, 2, 3, 4, 5]}
+        });
+        let page = page(&value, 2, 0, true);
+        assert_eq!(page.value["complexity"]["files"], json!([1, 2]));
+        assert_eq!(page.value["satd"]["items"], json!([1, 2]));
+        assert_eq!(page.total_items, Some(8));
+        assert_eq!(page.returned, Some(4));
+        assert_eq!(page.remaining, 4);
+    }
+
+    #[test]
+    fn test_store_resolves_and_expires_tokens() {
+        let mut store = ContinuationStore::default();
+        let first = store.insert(Stored {
+            tool: "satd".to_string(),
+            nested: false,
+            value: Arc::new(json!({"items": [1]})),
+        });
+        let (id, offset, stored) = store.resolve(&token(first, 50)).unwrap();
+        assert_eq!((id, offset, stored.tool.as_str()), (first, 50, "satd"));
+
+        for _ in 0..MAX_STORED {
+            store.insert(Stored {
+                tool: "churn".to_string(),
+                nested: false,
+                value: Arc::new(json!({})),
+            });
+        }
+        assert!(store
+            .resolve(&token(first, 50))
+            .unwrap_err()
+            .contains("expired"));
+        assert!(store.resolve("garbage").unwrap_err().contains("Invalid"));
+    }
+}
@@ -1,15 +1,21 @@
 //! MCP (Model Context Protocol) server implementation.
 
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::config::Config;
-use crate::core::{AnalysisContext, Analyzer, FileSet, Result};
+use crate::core::{run_by_name, AnalysisContext, Analyzer, AnalyzerResult, FileSet, Result};
 use crate::git::GitRepo;
 
+mod check;
+mod continuation;
+
+use continuation::{ContinuationStore, Stored};
+
 struct ToolDef {
     name: &'static str,
     description: &'static str,
@@ -23,21 +29,19 @@ impl ToolDef {
         for (k, v) in &self.properties {
             props.insert(k.to_string(), v.clone());
         }
-        // Shared pagination params on every tool
-        props.insert(
-            "limit".to_string(),
+        // Shared pagination params on every tool, unless it declares its own
+        props.entry("limit").or_insert_with(|| {
             json!({
                 "type": "integer",
                 "description": "Max items to return (default: 50, 0 = unlimited)"
-            }),
-        );
-        props.insert(
-            "offset".to_string(),
+            })
+        });
+        props.entry("offset").or_insert_with(|| {
             json!({
                 "type": "integer",
                 "description": "Item offset for pagination (default: 0)"
-            }),
-        );
+            })
+        });
         let mut schema = json!({
             "type": "object",
             "properties": props
@@ -77,15 +81,77 @@ fn count_items(value: &serde_json::Value) -> Option<usize> {
     }
 }
 
+/// Tools that accept `target` and `glob` to analyze part of the repository.
+const SCOPED_TOOLS: &[&str] = &[
+    "tdg",
+    "hotspot",
+    "ownership",
+    "cohesion",
+    "smells",
+    "estimate",
+];
+
+/// Narrow `file_set` to the `target` file or directory and the `glob`
+/// pattern in `arguments`. Git history is still read from the whole
+/// repository, so churn-based scores stay comparable.
+fn scope_file_set(
+    file_set: &FileSet,
+    root: &Path,
+    arguments: &Value,
+) -> std::result::Result<FileSet, String> {
+    let target = arguments.get("target").and_then(Value::as_str);
+    let glob = arguments.get("glob").and_then(Value::as_str);
+    let mut scoped = file_set.clone();
+    if let Some(target) = target {
+        let target = Path::new(target);
+        let target = target.strip_prefix(root).unwrap_or(target);
+        let target = target.strip_prefix(".").unwrap_or(target);
+        let paths: Vec<PathBuf> = scoped
+            .iter()
+            .filter(|file| file.starts_with(target))
+            .cloned()
+            .collect();
+        scoped = scoped.filter_by_paths(&paths);
+    }
+    if let Some(glob) = glob {
+        globset::Glob::new(glob).map_err(|e| format!("Invalid glob {glob:?}: {e}"))?;
+        scoped = scoped.filter_by_glob(glob);
+    }
+    if (target.is_some() || glob.is_some()) && scoped.is_empty() {
+        return Err(format!(
+            "No files match target {:?} and glob {:?}",
+            target.unwrap_or("."),
+            glob.unwrap_or("*")
+        ));
+    }
+    Ok(scoped)
+}
+
 /// MCP Server for LLM tool integration.
 pub struct McpServer {
     config: Config,
     root_path: PathBuf,
+    config_path: Option<PathBuf>,
+    profile: Option<String>,
+    continuations: Mutex<ContinuationStore>,
 }
 
 impl McpServer {
     pub fn new(root_path: PathBuf, config: Config) -> Self {
-        Self { config, root_path }
+        Self {
+            config,
+            root_path,
+            config_path: None,
+            profile: None,
+            continuations: Mutex::default(),
+        }
+    }
+
+    /// Record where `config` was loaded from, for the `status` tool.
+    pub fn with_config_source(mut self, path: Option<PathBuf>, profile: Option<String>) -> Self {
+        self.config_path = path;
+        self.profile = profile;
+        self
     }
 
     /// Run the MCP server with stdio transport.
@@ -178,23 +244,54 @@ impl McpServer {
     fn tool_response(
         &self,
         tool_name: &str,
-        mut value: serde_json::Value,
+        value: serde_json::Value,
         args: &serde_json::Value,
     ) -> std::result::Result<serde_json::Value, String> {
         let limit = args["limit"].as_u64().unwrap_or(50) as usize;
         let offset = args["offset"].as_u64().unwrap_or(0) as usize;
+        self.paged_response(tool_name, Arc::new(value), limit, offset, false, None)
+    }
 
-        let total_items = count_items(&value);
-        crate::output::truncate_lists(&mut value, limit, offset);
-        // Count returned items by summing array lengths AFTER truncation.
-        let returned = count_items(&value);
+    /// Wrap one page of `value` in the response envelope.
+    ///
+    /// Scalar fields (summaries, totals) are always returned; arrays are cut
+    /// to `limit` items from `offset`. If items remain, the full result is
+    /// kept and the envelope carries a `continuation_token` for `fetch_more`.
+    /// `resume` reuses an already stored result instead of storing it again.
+    fn paged_response(
+        &self,
+        tool_name: &str,
+        value: Arc<Value>,
+        limit: usize,
+        offset: usize,
+        nested: bool,
+        resume: Option<u64>,
+    ) -> std::result::Result<serde_json::Value, String> {
+        let page = continuation::page(&value, limit, offset, nested);
+
+        let continuation_token = (page.remaining > 0).then(|| {
+            let id = resume.unwrap_or_else(|| {
+                self.continuations
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(Stored {
+                        tool: tool_name.to_string(),
+                        nested,
+                        value,
+                    })
+            });
+            continuation::token(id, offset + page.limit)
+        });
 
         let envelope = json!({
             "tool": tool_name,
-            "total_items": total_items,
-            "returned": returned,
+            "total_items": page.total_items,
+            "returned": page.returned,
             "offset": offset,
-            "result": value
+            "limit": page.limit,
+            "remaining": page.remaining,
+            "continuation_token": continuation_token,
+            "result": page.value
         });
 
         Ok(json!({
@@ -205,8 +302,40 @@ impl McpServer {
         }))
     }
 
+    /// Return the next page of a result left unfinished by an earlier call.
+    fn handle_fetch_more(&self, arguments: &Value) -> std::result::Result<Value, String> {
+        let token = arguments
+            .get("continuation_token")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing required argument: continuation_token")?;
+        let (id, offset, tool, nested, value) = {
+            let store = self
+                .continuations
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let (id, offset, stored) = store.resolve(token)?;
+            (
+                id,
+                offset,
+                stored.tool.clone(),
+                stored.nested,
+                Arc::clone(&stored.value),
+            )
+        };
+        let limit = arguments["limit"].as_u64().unwrap_or(50) as usize;
+        self.paged_response(&tool, value, limit, offset, nested, Some(id))
+    }
+
     fn handle_tools_list(&self) -> std::result::Result<Value, String> {
         let tools: Vec<serde_json::Value> = vec![
+            ToolDef {
+                name: "status",
+                description: "Check first. Reports config files in effect, cache and search index state, git availability (shallow/bare), and languages detected. Tells you which tools will work. Very cheap.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "Repository path"})),
+                ],
+                required: &[],
+            },
             ToolDef {
                 name: "context",
                 description: "Use first. Returns top-N PageRank-ranked symbols, risks, language breakdown, directory tree, entry points, and navigation hints. Cheap.",
@@ -214,7 +343,10 @@ impl McpServer {
                     ("path", json!({"type": "string", "description": "File or directory path"})),
                     ("max_symbols", json!({"type": "integer", "description": "Maximum symbols to include"})),
                     ("max_risks", json!({"type": "integer", "description": "Maximum risks to include"})),
-                    ("max_tokens", json!({"type": "integer", "description": "Token budget; response is trimmed to fit (default: 8000)"})),
+                    ("max_tokens", json!({"type": "integer", "description": "Token budget; summary is trimmed and the remainder filled with source (default: 8000)"})),
+                    ("symbol", json!({"type": "string", "description": "Include source for this symbol (name or file:name) and its callees"})),
+                    ("target", json!({"type": "string", "description": "File or directory to take source from, relative to path"})),
+                    ("depth", json!({"type": "integer", "description": "Levels of callees to include with symbol (default: 2)"})),
                     ("format", json!({"type": "string", "enum": ["json", "markdown"], "description": "Output format: 'markdown' returns compact agent-facing text directly (default: json)"})),
                 ],
                 required: &[],
@@ -281,6 +413,14 @@ impl McpServer {
                 ],
                 required: &[],
             },
+            ToolDef {
+                name: "risk",
+                description: "Use to prioritize by business impact. Crosses defect probability with path criticality from [criticality] config into a risk matrix.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                ],
+                required: &[],
+            },
             ToolDef {
                 name: "changes",
                 description: "Use to assess recent commit risk. Analyzes recent changes with JIT risk analysis.",
@@ -290,6 +430,14 @@ impl McpServer {
                 ],
                 required: &[],
             },
+            ToolDef {
+                name: "commits",
+                description: "Use to review commit hygiene. Checks recent commit messages for conventional-commit compliance, subject length, issue links, and revert/fixup density.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "Repository path"})),
+                ],
+                required: &[],
+            },
             ToolDef {
                 name: "diff",
                 description: "Use to review branch or PR risk. Analyzes diff between commits; auto-detects target branch.",
@@ -304,6 +452,8 @@ impl McpServer {
                 description: "Use to understand technical debt accumulation. Generates Technical Debt Gradient with critical defect detection.",
                 properties: vec![
                     ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("target", json!({"type": "string", "description": "File or directory to restrict analysis to, relative to path"})),
+                    ("glob", json!({"type": "string", "description": "Only analyze files matching this glob, e.g. src/**/*.rs"})),
                 ],
                 required: &[],
             },
@@ -320,6 +470,8 @@ impl McpServer {
                 description: "Use to find the riskiest files. Combines high churn + high complexity.",
                 properties: vec![
                     ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("target", json!({"type": "string", "description": "File or directory to restrict analysis to, relative to path"})),
+                    ("glob", json!({"type": "string", "description": "Only analyze files matching this glob, e.g. src/**/*.rs"})),
                     ("days", json!({"type": "integer", "description": "Number of days for churn"})),
                 ],
                 required: &[],
@@ -334,11 +486,21 @@ impl McpServer {
                 ],
                 required: &[],
             },
+            ToolDef {
+                name: "hidden",
+                description: "Use before refactoring to find links the import graph misses. Lists file pairs that change together in git history but have no import path between them, with the commits that changed both.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                ],
+                required: &[],
+            },
             ToolDef {
                 name: "ownership",
-                description: "Use to assess bus factor and knowledge silos. Analyzes code ownership from git blame.",
+                description: "Use to assess bus factor and knowledge silos. Estimates code ownership from the git log, or from blame with [ownership] precise.",
                 properties: vec![
                     ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("target", json!({"type": "string", "description": "File or directory to restrict analysis to, relative to path"})),
+                    ("glob", json!({"type": "string", "description": "Only analyze files matching this glob, e.g. src/**/*.rs"})),
                 ],
                 required: &[],
             },
@@ -347,6 +509,8 @@ impl McpServer {
                 description: "Use for OO design quality assessment. Calculates CK metrics: WMC, CBO, RFC, LCOM4, DIT, NOC.",
                 properties: vec![
                     ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("target", json!({"type": "string", "description": "File or directory to restrict analysis to, relative to path"})),
+                    ("glob", json!({"type": "string", "description": "Only analyze files matching this glob, e.g. src/**/*.rs"})),
                 ],
                 required: &[],
             },
@@ -359,11 +523,21 @@ impl McpServer {
                 ],
                 required: &[],
             },
+            ToolDef {
+                name: "callgraph",
+                description: "Use to find central functions and recursion. Returns the function-level call graph with PageRank, fan-in/fan-out per symbol and recursive call cycles.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                ],
+                required: &[],
+            },
             ToolDef {
                 name: "smells",
                 description: "Use to find architectural anti-patterns. Detects cycles and smells via Tarjan SCC.",
                 properties: vec![
                     ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("target", json!({"type": "string", "description": "File or directory to restrict analysis to, relative to path"})),
+                    ("glob", json!({"type": "string", "description": "Only analyze files matching this glob, e.g. src/**/*.rs"})),
                 ],
                 required: &[],
             },
@@ -377,6 +551,39 @@ impl McpServer {
                 ],
                 required: &[],
             },
+            ToolDef {
+                name: "pipeline",
+                description: "Use to assess CI/build sprawl. Audits GitHub Actions, GitLab CI and Makefile/justfile configs for job counts, duplicated steps and unpinned action or image versions.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "Repository path"})),
+                ],
+                required: &[],
+            },
+            ToolDef {
+                name: "docker",
+                description: "Use to review container builds. Checks Dockerfiles for unpinned base images, apt/apk caches left in layers, secrets in ENV or ARG, and stages with too many layers.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "Repository path"})),
+                ],
+                required: &[],
+            },
+            ToolDef {
+                name: "docdrift",
+                description: "Use to find misleading API docs. Reports doc comments (rustdoc, JSDoc, Javadoc, PHPDoc, Python docstrings) whose parameters were renamed, removed or never documented.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                ],
+                required: &[],
+            },
+            ToolDef {
+                name: "api",
+                description: "Use before changing exported code or cutting a release. Lists public functions, methods and types with signatures, and which were added, removed or changed since a baseline (removed and changed may break callers).",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("base", json!({"type": "string", "description": "Revision to compare with, e.g. the last release tag (default: start of the [api] since window)"})),
+                ],
+                required: &[],
+            },
             ToolDef {
                 name: "score",
                 description: "Use for an overall health summary. Calculates composite repository health score.",
@@ -386,6 +593,80 @@ impl McpServer {
                 ],
                 required: &[],
             },
+            ToolDef {
+                name: "check_complexity",
+                description: "Use to gate work in CI. Same check as `omen complexity --check`: returns passed, the thresholds applied, and the functions over them. Thresholds default to the config error levels.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("max_cyclomatic", json!({"type": "integer", "description": "Maximum cyclomatic complexity (default: complexity.cyclomatic_error)"})),
+                    ("max_cognitive", json!({"type": "integer", "description": "Maximum cognitive complexity (default: complexity.cognitive_error)"})),
+                    ("baseline", json!({"type": "string", "description": "Baseline file, relative to path; only violations missing from it or worse than recorded fail"})),
+                ],
+                required: &[],
+            },
+            ToolDef {
+                name: "check_score",
+                description: "Use to gate work in CI. Same check as `omen score --check`: returns passed, the minimum applied, and the shortfall when the health score is below it.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("min_score", json!({"type": "number", "description": "Minimum score 0-100 (default: score.fail_under, else 80)"})),
+                    ("baseline", json!({"type": "string", "description": "Baseline file, relative to path; a shortfall no larger than recorded passes"})),
+                ],
+                required: &[],
+            },
+            ToolDef {
+                name: "check_mutation",
+                description: "Use to gate work in CI. Same check as `omen mutation --check`: runs mutation testing and returns passed, the minimum applied, and the surviving mutants when the score is below it. Slow: runs the test suite once per mutant.",
+                properties: vec![
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("min_score", json!({"type": "number", "description": "Minimum mutation score 0-1 (default: 0.8)"})),
+                    ("operators", json!({"type": "string", "description": "Mutation operators, comma-separated (default: [mutation] operators in config, else CRR,ROR,AOR)"})),
+                    ("test_command", json!({"type": "string", "description": "Test command to run (default: detected from the project)"})),
+                    ("timeout", json!({"type": "integer", "description": "Timeout per mutant in seconds (default: 3x the slowest of three baseline test runs, which must all pass)"})),
+                ],
+                required: &[],
+            },
+            ToolDef {
+                name: "estimate",
+                description: "Use before an expensive run. Predicts run time and file (and mutant) counts for `omen all`, `omen report generate`, or mutation testing from repo size and earlier runs, without running anything, so you can decide whether to proceed or narrow scope.",
+                properties: vec![
+                    ("command", json!({"type": "string", "enum": ["all", "report", "mutation"], "description": "Command to estimate"})),
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("target", json!({"type": "string", "description": "File or directory to restrict analysis to, relative to path"})),
+                    ("glob", json!({"type": "string", "description": "Only count files matching this glob"})),
+                    ("operators", json!({"type": "string", "description": "Mutation operators, comma-separated (default: [mutation] operators in config, else CRR,ROR,AOR)"})),
+                ],
+                required: &["command"],
+            },
+            ToolDef {
+                name: "query",
+                description: "Precise cross-analyzer lookup. Evaluates a JMESPath expression over results keyed by analyzer name, running only the analyzers it names. Example: complexity.files[?avg_cyclomatic > `10`].path",
+                properties: vec![
+                    ("expr", json!({"type": "string", "description": "JMESPath expression; top-level keys are analyzer names (complexity, churn, hotspot, score, ...)"})),
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                ],
+                required: &["expr"],
+            },
+            ToolDef {
+                name: "analyze_many",
+                description: "Use for a full assessment in one call. Runs several analyzers over one shared file set and returns results keyed by analyzer name; limit/offset apply to each result.",
+                properties: vec![
+                    ("analyzers", json!({"type": "array", "items": {"type": "string", "enum": AnalyzerResult::accepted_names().collect::<Vec<_>>()}, "description": "Analyzers to run (e.g. [\"complexity\", \"satd\", \"hotspot\"])"})),
+                    ("path", json!({"type": "string", "description": "File or directory path"})),
+                    ("glob", json!({"type": "string", "description": "Only analyze files matching this glob"})),
+                    ("exclude", json!({"type": "string", "description": "Skip files matching this glob"})),
+                ],
+                required: &["analyzers"],
+            },
+            ToolDef {
+                name: "fetch_more",
+                description: "Page through a truncated result. Pass the continuation_token from a previous response to get the next items; the token in each response resumes after it.",
+                properties: vec![
+                    ("continuation_token", json!({"type": "string", "description": "Token from the previous response's continuation_token field"})),
+                    ("limit", json!({"type": "integer", "description": "Items in the next page (default: 50, 0 = all remaining)"})),
+                ],
+                required: &["continuation_token"],
+            },
             ToolDef {
                 name: "semantic_search",
                 description: "Semantic symbol search via TF-IDF. Use when you know what you're looking for conceptually.",
@@ -396,6 +677,7 @@ impl McpServer {
                     ("files", json!({"type": "string", "description": "Comma-separated file paths to search within"})),
                     ("max_complexity", json!({"type": "integer", "description": "Exclude symbols with cyclomatic complexity above this value"})),
                     ("include_projects", json!({"type": "string", "description": "Comma-separated paths to additional project roots for cross-repo search"})),
+                    ("rerank", json!({"type": "boolean", "description": "Rerank by PageRank, hotspot, and complexity so central implementations outrank helpers (default: [search.rerank] enabled)"})),
                 ],
                 required: &["query"],
             },
@@ -450,6 +732,7 @@ impl McpServer {
     /// A test asserts that these names match the actual `handle_tools_list` output.
     pub fn tool_names() -> &'static [&'static str] {
         &[
+            "status",
             "context",
             "outline",
             "complexity",
@@ -458,18 +741,33 @@ impl McpServer {
             "churn",
             "clones",
             "defect",
+            "risk",
             "changes",
+            "commits",
             "diff",
             "tdg",
             "graph",
             "hotspot",
             "temporal",
+            "hidden",
             "ownership",
             "cohesion",
             "repomap",
+            "callgraph",
             "smells",
             "flags",
+            "pipeline",
+            "docker",
+            "docdrift",
+            "api",
             "score",
+            "check_complexity",
+            "check_score",
+            "check_mutation",
+            "estimate",
+            "query",
+            "analyze_many",
+            "fetch_more",
             "semantic_search",
             "get_symbol",
             "impact",
@@ -485,14 +783,21 @@ impl McpServer {
             .ok_or("Missing tool name")?;
         let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
 
+        if tool_name == "fetch_more" {
+            return self.handle_fetch_more(&arguments);
+        }
+
         let path = arguments
             .get("path")
             .and_then(|v| v.as_str())
             .map(PathBuf::from)
             .unwrap_or_else(|| self.root_path.clone());
 
-        let file_set = FileSet::from_path(&path, &self.config)
+        let mut file_set = FileSet::from_path(&path, &self.config)
             .map_err(|e| format!("Failed to create file set: {}", e))?;
+        if SCOPED_TOOLS.contains(&tool_name) {
+            file_set = scope_file_set(&file_set, &path, &arguments)?;
+        }
 
         // Try to open a git repository at the path
         let git_root = GitRepo::open(&path).ok().map(|r| r.root().to_path_buf());
@@ -503,26 +808,47 @@ impl McpServer {
         }
 
         let result = match tool_name {
-            "complexity" => self.run_analyzer::<crate::analyzers::complexity::Analyzer>(&ctx),
-            "satd" => self.run_analyzer::<crate::analyzers::satd::Analyzer>(&ctx),
-            "deadcode" => self.run_analyzer::<crate::analyzers::deadcode::Analyzer>(&ctx),
-            "churn" => self.run_analyzer::<crate::analyzers::churn::Analyzer>(&ctx),
-            "clones" => self.run_analyzer::<crate::analyzers::duplicates::Analyzer>(&ctx),
-            "defect" => self.run_analyzer::<crate::analyzers::defect::Analyzer>(&ctx),
-            "changes" => self.run_analyzer::<crate::analyzers::changes::Analyzer>(&ctx),
-            "tdg" => self.run_analyzer::<crate::analyzers::tdg::Analyzer>(&ctx),
-            "graph" => self.run_analyzer::<crate::analyzers::graph::Analyzer>(&ctx),
-            "hotspot" => self.run_analyzer::<crate::analyzers::hotspot::Analyzer>(&ctx),
-            "temporal" => self.run_analyzer::<crate::analyzers::temporal::Analyzer>(&ctx),
-            "ownership" => self.run_analyzer::<crate::analyzers::ownership::Analyzer>(&ctx),
-            "cohesion" => self.run_analyzer::<crate::analyzers::cohesion::Analyzer>(&ctx),
-            "repomap" => self.run_analyzer::<crate::analyzers::repomap::Analyzer>(&ctx),
-            "smells" => self.run_analyzer::<crate::analyzers::smells::Analyzer>(&ctx),
-            "flags" => self.run_analyzer::<crate::analyzers::flags::Analyzer>(&ctx),
-            "score" => self.run_analyzer::<crate::score::Analyzer>(&ctx),
+            "api" if arguments.get("base").is_some() => {
+                let mut config = self.config.clone();
+                config.api.base = arguments
+                    .get("base")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+                let mut ctx = AnalysisContext::new(&file_set, &config, Some(&path));
+                if let Some(ref git_path) = git_root {
+                    ctx = ctx.with_git_path(git_path);
+                }
+                self.run_analyzer(tool_name, &ctx)
+            }
+            name if AnalyzerResult::resolve(name).is_some() => self.run_analyzer(name, &ctx),
+            "check_complexity" => self.handle_check_complexity(&path, &ctx, &arguments),
+            "check_score" => self.handle_check_score(&path, &ctx, &arguments),
+            "check_mutation" => self.handle_check_mutation(&ctx, &arguments),
+            "estimate" => self.handle_estimate(&path, &ctx, &arguments),
+            "query" => {
+                let expr = arguments
+                    .get("expr")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing required argument: expr")?;
+                crate::query::run(expr, &ctx).map_err(|e| e.to_string())
+            }
+            "analyze_many" => {
+                return self.handle_analyze_many(&path, &file_set, &arguments);
+            }
             "context" => {
                 return self.handle_context(&path, &file_set, &arguments);
             }
+            "status" => {
+                let status = crate::status::build_status(
+                    &path,
+                    &file_set,
+                    &self.config,
+                    self.config_path.as_deref(),
+                    self.profile.as_deref(),
+                )
+                .map_err(|e| format!("Status failed: {}", e))?;
+                serde_json::to_value(status).map_err(|e| format!("Serialization failed: {}", e))
+            }
             "diff" => {
                 return self.handle_diff(&path, &arguments);
             }
@@ -547,15 +873,225 @@ impl McpServer {
         self.tool_response(tool_name, result, &arguments)
     }
 
-    fn run_analyzer<A: Analyzer + Default>(
+    fn run_analyzer(
         &self,
+        name: &str,
         ctx: &AnalysisContext<'_>,
     ) -> std::result::Result<Value, String> {
-        let analyzer = A::default();
-        let result = analyzer
+        let result = run_by_name(name, ctx).map_err(|e| format!("Analysis failed: {}", e))?;
+        result
+            .to_value()
+            .map_err(|e| format!("Serialization failed: {}", e))
+    }
+
+    fn handle_check_complexity(
+        &self,
+        path: &std::path::Path,
+        ctx: &AnalysisContext<'_>,
+        arguments: &Value,
+    ) -> std::result::Result<Value, String> {
+        let limit = |key: &str, default: u32| {
+            arguments
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .map_or(default, |v| v as u32)
+        };
+        let max_cyclomatic = limit("max_cyclomatic", self.config.complexity.cyclomatic_error);
+        let max_cognitive = limit("max_cognitive", self.config.complexity.cognitive_error);
+        let baseline = check::load_baseline(path, "complexity", arguments)?;
+
+        let result = crate::analyzers::complexity::Analyzer::default()
+            .analyze(ctx)
+            .map_err(|e| format!("Analysis failed: {}", e))?;
+        let verdict = check::complexity(
+            path,
+            &result,
+            max_cyclomatic,
+            max_cognitive,
+            baseline.as_ref(),
+        );
+        serde_json::to_value(verdict).map_err(|e| format!("Serialization failed: {}", e))
+    }
+
+    fn handle_check_score(
+        &self,
+        path: &std::path::Path,
+        ctx: &AnalysisContext<'_>,
+        arguments: &Value,
+    ) -> std::result::Result<Value, String> {
+        let min_score = arguments
+            .get("min_score")
+            .and_then(|v| v.as_f64())
+            .unwrap_or_else(|| self.config.score.fail_under.unwrap_or(80.0));
+        let baseline = check::load_baseline(path, "score", arguments)?;
+
+        let result = crate::score::Analyzer::default()
+            .analyze(ctx)
+            .map_err(|e| format!("Analysis failed: {}", e))?;
+        let verdict = check::score(path, &result, min_score, baseline.as_ref());
+        serde_json::to_value(verdict).map_err(|e| format!("Serialization failed: {}", e))
+    }
+
+    fn handle_check_mutation(
+        &self,
+        ctx: &AnalysisContext<'_>,
+        arguments: &Value,
+    ) -> std::result::Result<Value, String> {
+        let min_score = arguments
+            .get("min_score")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.8);
+        let operators: Vec<String> = match arguments.get("operators").and_then(|v| v.as_str()) {
+            Some(operators) => operators
+                .split(',')
+                .map(|s| s.trim().to_uppercase())
+                .collect(),
+            None => self.config.mutation.operators.clone(),
+        };
+        let test_command = arguments
+            .get("test_command")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let timeout = arguments
+            .get("timeout")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+
+        let result = crate::analyzers::mutation::Analyzer::new()
+            .operators(operators)
+            .test_command(test_command)
+            .timeout(timeout)
+            .min_score(Some(min_score))
             .analyze(ctx)
             .map_err(|e| format!("Analysis failed: {}", e))?;
-        serde_json::to_value(result).map_err(|e| format!("Serialization failed: {}", e))
+        let verdict = check::mutation(&result, min_score);
+        serde_json::to_value(verdict).map_err(|e| format!("Serialization failed: {}", e))
+    }
+
+    /// Predict the cost of an expensive command without running it.
+    fn handle_estimate(
+        &self,
+        path: &Path,
+        ctx: &AnalysisContext<'_>,
+        arguments: &Value,
+    ) -> std::result::Result<Value, String> {
+        use crate::core::timings::{timings_path, Timings};
+
+        let command = arguments
+            .get("command")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing required argument: command")?;
+        let timings = Timings::load(timings_path(path));
+        let files = ctx.files.len();
+        let skip: Vec<&str> = self.config.skip.iter().map(String::as_str).collect();
+
+        let estimate = match command {
+            "all" => crate::estimate::all(files, &skip, &timings),
+            "report" => crate::estimate::report(files, &skip, &timings),
+            "mutation" => {
+                let operators: Vec<String> =
+                    match arguments.get("operators").and_then(|v| v.as_str()) {
+                        Some(operators) => operators
+                            .split(',')
+                            .map(|s| s.trim().to_uppercase())
+                            .collect(),
+                        None => self.config.mutation.operators.clone(),
+                    };
+                let generated = crate::analyzers::mutation::Analyzer::new()
+                    .operators(operators)
+                    .generate_only(ctx)
+                    .map_err(|e| format!("Mutant generation failed: {}", e))?;
+                crate::estimate::mutation(
+                    files,
+                    generated.summary.total_mutants,
+                    crate::analyzers::mutation::DEFAULT_BASELINE_RUNS,
+                    &timings,
+                )
+            }
+            other => {
+                return Err(format!(
+                    "Unknown command '{}': expected all, report, or mutation",
+                    other
+                ))
+            }
+        };
+        serde_json::to_value(estimate).map_err(|e| format!("Serialization failed: {}", e))
+    }
+
+    /// Run several analyzers against one shared file set.
+    ///
+    /// Analyzers run in parallel. A failing analyzer is reported as
+    /// `{"error": "..."}` under its name, like `omen all`, so one git-only
+    /// analyzer outside a repository does not sink the whole batch.
+    fn handle_analyze_many(
+        &self,
+        path: &std::path::Path,
+        file_set: &FileSet,
+        arguments: &Value,
+    ) -> std::result::Result<Value, String> {
+        use rayon::prelude::*;
+
+        let requested: Vec<String> = match arguments.get("analyzers") {
+            Some(Value::Array(names)) => names
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .collect(),
+            Some(Value::String(names)) => names
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            _ => return Err("Missing required argument: analyzers".to_string()),
+        };
+        if requested.is_empty() {
+            return Err("analyzers must name at least one analyzer".to_string());
+        }
+        let mut names: Vec<&'static str> = Vec::new();
+        for name in &requested {
+            let known =
+                AnalyzerResult::resolve(name).ok_or_else(|| format!("Unknown analyzer: {name}"))?;
+            if !names.contains(&known) {
+                names.push(known);
+            }
+        }
+
+        let mut file_set = file_set.clone();
+        if let Some(glob) = arguments.get("glob").and_then(|v| v.as_str()) {
+            file_set = file_set.filter_by_glob(glob);
+        }
+        if let Some(exclude) = arguments.get("exclude").and_then(|v| v.as_str()) {
+            file_set = file_set.exclude_by_glob(exclude);
+        }
+        let git_root = GitRepo::open(path).ok().map(|r| r.root().to_path_buf());
+        let mut ctx = AnalysisContext::new(&file_set, &self.config, Some(path));
+        if let Some(ref git_path) = git_root {
+            ctx = ctx.with_git_path(git_path);
+        }
+
+        let limit = arguments["limit"].as_u64().unwrap_or(50) as usize;
+        let offset = arguments["offset"].as_u64().unwrap_or(0) as usize;
+        let results: Vec<(&str, Value)> = names
+            .par_iter()
+            .map(|name| {
+                let value = crate::query::run_analyzer(name, &ctx)
+                    .unwrap_or_else(|e| json!({ "error": e.to_string() }));
+                (*name, value)
+            })
+            .collect();
+
+        let mut map = serde_json::Map::new();
+        for (name, value) in results {
+            map.insert(name.to_string(), value);
+        }
+        self.paged_response(
+            "analyze_many",
+            Arc::new(Value::Object(map)),
+            limit,
+            offset,
+            true,
+            None,
+        )
     }
 
     fn handle_context(
@@ -586,8 +1122,30 @@ impl McpServer {
             crate::context::build_context(path, file_set, &self.config, max_symbols, max_risks)
                 .map_err(|e| format!("Context failed: {}", e))?;
 
-        // Apply token budget
-        crate::context::apply_token_budget(&mut context, max_tokens);
+        let focus = crate::context::Focus {
+            symbol: arguments
+                .get("symbol")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            target: arguments
+                .get("target")
+                .and_then(|v| v.as_str())
+                .map(std::path::PathBuf::from),
+            depth: arguments
+                .get("depth")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize)
+                .unwrap_or(2),
+        };
+        crate::context::assemble(
+            &mut context,
+            path,
+            file_set,
+            &focus,
+            max_tokens,
+            self.config.context.tokenizer,
+        )
+        .map_err(|e| format!("Context failed: {}", e))?;
 
         if format == "markdown" {
             // Return markdown text directly (not wrapped in JSON envelope)
@@ -671,6 +1229,18 @@ impl McpServer {
                     .collect()
             });
 
+        // Reranking pulls a wider candidate pool, then keeps the best top_k.
+        let rerank = arguments
+            .get("rerank")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(self.config.search.rerank.enabled)
+            && include_projects.is_none();
+        let fetch_k = if rerank {
+            top_k.max(self.config.search.rerank.candidates)
+        } else {
+            top_k
+        };
+
         let search_config = SearchConfig {
             min_score,
             ..SearchConfig::default()
@@ -697,7 +1267,7 @@ impl McpServer {
             crate::semantic::SearchOutput::new(query.to_string(), mr.total_symbols, mr.results)
         } else if let Some(file_paths) = files {
             search
-                .search_in_files(query, &file_paths, Some(top_k))
+                .search_in_files(query, &file_paths, Some(fetch_k))
                 .map_err(|e| format!("Search failed: {}", e))?
         } else if max_complexity.is_some() {
             let filters = SearchFilters {
@@ -705,11 +1275,11 @@ impl McpServer {
                 max_complexity,
             };
             search
-                .search_filtered(query, Some(top_k), &filters)
+                .search_filtered(query, Some(fetch_k), &filters)
                 .map_err(|e| format!("Search failed: {}", e))?
         } else {
             search
-                .search(query, Some(top_k))
+                .search(query, Some(fetch_k))
                 .map_err(|e| format!("Search failed: {}", e))?
         };
 
@@ -720,6 +1290,13 @@ impl McpServer {
                 .retain(|r| r.cyclomatic_complexity.is_none_or(|c| c <= max));
         }
 
+        if rerank {
+            output.results = search
+                .rerank(output.results, &self.config)
+                .map_err(|e| format!("Rerank failed: {}", e))?;
+            output.results.truncate(top_k);
+        }
+
         let result =
             serde_json::to_value(&output).map_err(|e| format!("Serialization failed: {}", e))?;
 
@@ -990,6 +1567,7 @@ struct JsonRpcError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{Baseline, BaselineEntry};
     use tempfile::TempDir;
 
     fn create_test_server() -> (McpServer, TempDir) {
@@ -1164,6 +1742,146 @@ mod tests {
         assert!(response.get("content").is_some());
     }
 
+    #[test]
+    fn test_handle_tool_call_query() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn queried() {}\n").unwrap();
+
+        let params = json!({
+            "name": "query",
+            "arguments": {
+                "path": temp_dir.path().to_str().unwrap(),
+                "expr": "complexity.files[].functions[].name"
+            }
+        });
+        let response = server.handle_tool_call(Some(params)).unwrap();
+        let text = response["content"][0]["text"].as_str().unwrap();
+        let envelope: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(envelope["tool"], "query");
+        assert_eq!(envelope["result"], json!(["queried"]));
+    }
+
+    #[test]
+    fn test_handle_tool_call_estimate() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(
+            temp_dir.path().join("test.rs"),
+            "fn positive(a: i32, b: i32) -> bool { a + b > 0 }\n",
+        )
+        .unwrap();
+
+        let estimate = |command: &str| {
+            let params = json!({
+                "name": "estimate",
+                "arguments": {
+                    "path": temp_dir.path().to_str().unwrap(),
+                    "command": command
+                }
+            });
+            let response = server.handle_tool_call(Some(params)).unwrap();
+            let text = response["content"][0]["text"].as_str().unwrap();
+            serde_json::from_str::<Value>(text).unwrap()["result"].clone()
+        };
+
+        let all = estimate("all");
+        assert_eq!(all["command"], "all");
+        assert_eq!(all["files"], 1);
+        assert!(all["analyzers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|a| a["basis"] == "size"));
+        assert!(all.get("mutants").is_none());
+
+        let mutation = estimate("mutation");
+        assert!(mutation["mutants"].as_u64().unwrap() > 0);
+        // Nothing ran, so no timings were recorded.
+        assert!(!temp_dir.path().join(".omen/cache/timings.json").exists());
+
+        let params = json!({"name": "estimate", "arguments": {"command": "lint"}});
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("expected all, report, or mutation"));
+    }
+
+    #[test]
+    fn test_handle_tool_call_query_requires_expr() {
+        let (server, _temp_dir) = create_test_server();
+        let params = json!({"name": "query", "arguments": {}});
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("expr"));
+    }
+
+    #[test]
+    fn test_handle_tool_call_analyze_many() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(
+            temp_dir.path().join("test.rs"),
+            "// TODO: batch me\nfn batched() {}\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("skip.rs"), "fn skipped() {}\n").unwrap();
+
+        let params = json!({
+            "name": "analyze_many",
+            "arguments": {
+                "path": temp_dir.path().to_str().unwrap(),
+                "analyzers": ["complexity", "satd", "churn", "complexity"],
+                "exclude": "skip.rs"
+            }
+        });
+        let response = server.handle_tool_call(Some(params)).unwrap();
+        let text = response["content"][0]["text"].as_str().unwrap();
+        let envelope: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(envelope["tool"], "analyze_many");
+
+        let result = envelope["result"].as_object().unwrap();
+        let keys: Vec<&str> = result.keys().map(String::as_str).collect();
+        assert_eq!(keys.len(), 3);
+        let functions: Vec<&str> = result["complexity"]["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|f| f["functions"].as_array().unwrap())
+            .map(|f| f["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(functions, vec!["batched"]);
+        assert_eq!(result["satd"]["items"].as_array().unwrap().len(), 1);
+        // No git repository: churn fails on its own without failing the batch.
+        assert!(result["churn"]["error"].is_string());
+    }
+
+    #[test]
+    fn test_handle_tool_call_analyze_many_accepts_comma_list() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {}\n").unwrap();
+
+        let params = json!({
+            "name": "analyze_many",
+            "arguments": {
+                "path": temp_dir.path().to_str().unwrap(),
+                "analyzers": "complexity, cohesion"
+            }
+        });
+        let response = server.handle_tool_call(Some(params)).unwrap();
+        let text = response["content"][0]["text"].as_str().unwrap();
+        let envelope: Value = serde_json::from_str(text).unwrap();
+        assert!(envelope["result"]["complexity"].is_object());
+        assert!(envelope["result"]["cohesion"].is_object());
+    }
+
+    #[test]
+    fn test_handle_tool_call_analyze_many_rejects_unknown_analyzer() {
+        let (server, _temp_dir) = create_test_server();
+        let params =
+            json!({"name": "analyze_many", "arguments": {"analyzers": ["complexity", "nope"]}});
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("Unknown analyzer: nope"));
+
+        let params = json!({"name": "analyze_many", "arguments": {}});
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("analyzers"));
+    }
+
     #[test]
     fn test_handle_tool_call_uses_requested_path_as_analysis_root() {
         let (server, _server_root) = create_test_server();
@@ -1209,6 +1927,22 @@ mod tests {
         assert!(text.contains("mcp_entrypoint"));
     }
 
+    #[test]
+    fn test_handle_tool_call_status() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(temp_dir.path().join("lib.rs"), "pub fn f() {}\n").unwrap();
+
+        let params = json!({
+            "name": "status",
+            "arguments": {"path": temp_dir.path().to_str().unwrap()}
+        });
+        let response = server.handle_tool_call(Some(params)).unwrap();
+        let text = response["content"][0]["text"].as_str().unwrap();
+
+        assert!(text.contains("search_index"));
+        assert!(text.contains("Rust"));
+    }
+
     #[test]
     fn test_handle_tool_call_context_markdown_returns_text_directly() {
         let (server, temp_dir) = create_test_server();
@@ -1458,6 +2192,48 @@ mod tests {
         (server, temp_dir)
     }
 
+    #[test]
+    fn test_scoped_tools_honor_target_and_glob() {
+        let (server, temp_dir) = create_test_server();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/api")).unwrap();
+        std::fs::create_dir_all(root.join("tools")).unwrap();
+        std::fs::write(root.join("src/api/handler.rs"), "fn handle() {}\n").unwrap();
+        std::fs::write(root.join("src/api/handler.py"), "def handle():\n    pass\n").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "fn lib() {}\n").unwrap();
+        std::fs::write(root.join("tools/gen.rs"), "fn gen() {}\n").unwrap();
+
+        let tdg_files = |arguments: Value| -> Vec<String> {
+            let params = json!({"name": "tdg", "arguments": arguments});
+            let response = server.handle_tool_call(Some(params)).unwrap();
+            let text = response["content"][0]["text"].as_str().unwrap();
+            let envelope: Value = serde_json::from_str(text).unwrap();
+            let mut files: Vec<String> = envelope["result"]["files"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|f| f["file_path"].as_str().unwrap().to_string())
+                .collect();
+            files.sort();
+            files
+        };
+        let path = root.to_str().unwrap();
+
+        assert_eq!(tdg_files(json!({"path": path})).len(), 4);
+        assert_eq!(
+            tdg_files(json!({"path": path, "target": "src/api"})),
+            vec!["src/api/handler.py", "src/api/handler.rs"]
+        );
+        assert_eq!(
+            tdg_files(json!({"path": path, "target": "src", "glob": "*.rs"})),
+            vec!["src/api/handler.rs", "src/lib.rs"]
+        );
+
+        let params = json!({"name": "smells", "arguments": {"path": path, "target": "docs"}});
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("No files match"), "got: {err}");
+    }
+
     #[test]
     fn test_handle_tool_call_ownership_with_git() {
         let (server, temp_dir) = create_git_test_server();
@@ -1587,6 +2363,86 @@ mod tests {
         assert!(response.get("content").is_some());
     }
 
+    fn check_verdict(server: &McpServer, params: Value) -> Value {
+        envelope_of(&server.handle_tool_call(Some(params)).unwrap())["result"].clone()
+    }
+
+    #[test]
+    fn test_handle_tool_call_check_complexity() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(
+            temp_dir.path().join("test.rs"),
+            "fn branchy(x: i32) -> i32 {\n    if x > 0 { 1 } else { 0 }\n}\n",
+        )
+        .unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+
+        let params = json!({"name": "check_complexity", "arguments": {"path": path}});
+        let verdict = check_verdict(&server, params);
+        assert_eq!(verdict["check"], "complexity");
+        assert_eq!(verdict["passed"], true);
+        assert_eq!(verdict["thresholds"]["max_cyclomatic"], 20);
+        assert!(verdict["violations"].as_array().unwrap().is_empty());
+
+        let params = json!({
+            "name": "check_complexity",
+            "arguments": {"path": path, "max_cyclomatic": 1}
+        });
+        let verdict = check_verdict(&server, params);
+        assert_eq!(verdict["passed"], false);
+        assert_eq!(verdict["violations"][0]["name"], "branchy");
+    }
+
+    #[test]
+    fn test_handle_tool_call_check_complexity_with_baseline() {
+        let (server, temp_dir) = create_test_server();
+        let file = temp_dir.path().join("test.rs");
+        std::fs::write(
+            &file,
+            "fn branchy(x: i32) -> i32 {\n    if x > 0 { 1 } else { 0 }\n}\n",
+        )
+        .unwrap();
+        let entry = BaselineEntry::new(temp_dir.path(), file.to_str().unwrap(), "branchy", 99.0);
+        Baseline::new("complexity", vec![entry])
+            .save(&temp_dir.path().join("baseline.json"))
+            .unwrap();
+
+        let params = json!({
+            "name": "check_complexity",
+            "arguments": {
+                "path": temp_dir.path().to_str().unwrap(),
+                "max_cyclomatic": 1,
+                "baseline": "baseline.json"
+            }
+        });
+        let verdict = check_verdict(&server, params);
+        assert_eq!(verdict["passed"], true, "{verdict}");
+        assert_eq!(verdict["baseline"]["known"], 1);
+
+        let params = json!({
+            "name": "check_score",
+            "arguments": {"path": temp_dir.path().to_str().unwrap(), "baseline": "baseline.json"}
+        });
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("not `score`"), "got: {err}");
+    }
+
+    #[test]
+    fn test_handle_tool_call_check_score() {
+        let (server, temp_dir) = create_test_server();
+        std::fs::write(temp_dir.path().join("test.rs"), "fn main() {}").unwrap();
+
+        let params = json!({
+            "name": "check_score",
+            "arguments": {"path": temp_dir.path().to_str().unwrap(), "min_score": 101.0}
+        });
+        let verdict = check_verdict(&server, params);
+        assert_eq!(verdict["check"], "score");
+        assert_eq!(verdict["passed"], false);
+        assert_eq!(verdict["thresholds"]["min_score"], 101.0);
+        assert_eq!(verdict["violations"][0]["name"], "overall");
+    }
+
     #[test]
     fn test_handle_tool_call_diff() {
         let (server, temp_dir) = create_git_test_server();
@@ -1669,6 +2525,26 @@ mod tests {
         assert!(response.get("content").is_some());
     }
 
+    #[test]
+    fn test_semantic_search_with_rerank() {
+        let (server, temp_dir) = create_test_server();
+
+        std::fs::write(temp_dir.path().join("test.rs"), "fn simple() { return; }\n").unwrap();
+
+        let params = json!({
+            "name": "semantic_search",
+            "arguments": {
+                "query": "simple",
+                "rerank": true
+            }
+        });
+        let result = server.handle_tool_call(Some(params));
+        assert!(
+            result.is_ok(),
+            "semantic_search with rerank should succeed: {result:?}"
+        );
+    }
+
     #[test]
     fn test_semantic_search_hyde_with_max_complexity() {
         let (server, temp_dir) = create_test_server();
@@ -1827,6 +2703,65 @@ mod tests {
         assert_eq!(envelope["returned"].as_u64().unwrap(), 50);
     }
 
+    fn envelope_of(response: &Value) -> Value {
+        serde_json::from_str(response["content"][0]["text"].as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_tool_response_continuation_token_pages_through_result() {
+        let (server, _temp_dir) = create_test_server();
+        let items: Vec<i64> = (0..25).collect();
+        let value = json!({"summary": {"total": 25}, "items": items});
+
+        let first = envelope_of(
+            &server
+                .tool_response("satd", value, &json!({"limit": 10}))
+                .unwrap(),
+        );
+        assert_eq!(first["result"]["summary"]["total"], 25);
+        assert_eq!(first["remaining"], 15);
+        let token = first["continuation_token"].as_str().unwrap().to_string();
+
+        let params =
+            json!({"name": "fetch_more", "arguments": {"continuation_token": token, "limit": 10}});
+        let second = envelope_of(&server.handle_tool_call(Some(params)).unwrap());
+        assert_eq!(second["tool"], "satd");
+        assert_eq!(second["offset"], 10);
+        assert_eq!(second["result"]["items"][0], 10);
+        assert_eq!(second["result"]["summary"]["total"], 25);
+        let token = second["continuation_token"].as_str().unwrap().to_string();
+
+        let params = json!({"name": "fetch_more", "arguments": {"continuation_token": token}});
+        let last = envelope_of(&server.handle_tool_call(Some(params)).unwrap());
+        assert_eq!(last["returned"], 5);
+        assert_eq!(last["remaining"], 0);
+        assert!(last["continuation_token"].is_null());
+    }
+
+    #[test]
+    fn test_tool_response_without_more_items_has_no_token() {
+        let (server, _temp_dir) = create_test_server();
+        let envelope = envelope_of(
+            &server
+                .tool_response("test", json!({"items": [1, 2, 3]}), &json!({}))
+                .unwrap(),
+        );
+        assert!(envelope["continuation_token"].is_null());
+    }
+
+    #[test]
+    fn test_fetch_more_rejects_missing_or_unknown_token() {
+        let (server, _temp_dir) = create_test_server();
+        let err = server
+            .handle_tool_call(Some(json!({"name": "fetch_more", "arguments": {}})))
+            .unwrap_err();
+        assert!(err.contains("continuation_token"));
+
+        let params = json!({"name": "fetch_more", "arguments": {"continuation_token": "99.10"}});
+        let err = server.handle_tool_call(Some(params)).unwrap_err();
+        assert!(err.contains("expired"));
+    }
+
     #[test]
     fn test_all_tools_have_limit_and_offset_params() {
         let (server, _temp_dir) = create_test_server();
@@ -1854,6 +2789,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fetch_more_declares_page_size() {
+        let (server, _temp_dir) = create_test_server();
+        let result = server.handle_tools_list().unwrap();
+        let tools = result["tools"].as_array().unwrap();
+        let fetch_more = tools.iter().find(|t| t["name"] == "fetch_more").unwrap();
+        let limit = &fetch_more["inputSchema"]["properties"]["limit"];
+        assert!(limit["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Items in the next page"));
+    }
+
     #[test]
     fn test_handle_tools_list_has_outline() {
         let (server, _temp_dir) = create_test_server();
@@ -0,0 +1,224 @@
+//! GitHub Actions workflow commands (`::error file=...,line=...::message`).
+//!
+//! Printed from a workflow step, each line becomes an annotation on the
+//! pull request diff. Findings are picked out of any analyzer's JSON by
+//! shape: objects with a file and a severity (SATD items and most
+//! analyzers), smells with source `locations`, and complexity violations.
+//! Findings marked `"changed": false` by `--base` are left out, so a PR
+//! only sees annotations on lines it touched.
+
+use std::io::Write;
+
+use serde_json::{Map, Value};
+
+use crate::core::Result;
+
+#[derive(Debug, PartialEq)]
+struct Annotation {
+    level: &'static str,
+    file: String,
+    line: u64,
+    end_line: Option<u64>,
+    title: String,
+    message: String,
+}
+
+pub(super) fn write_annotations<W: Write>(value: &Value, writer: &mut W) -> Result<()> {
+    let mut annotations = Vec::new();
+    collect(value, &mut annotations);
+    for a in annotations {
+        write!(
+            writer,
+            "::{} file={},line={}",
+            a.level,
+            escape_property(&a.file),
+            a.line
+        )?;
+        if let Some(end) = a.end_line.filter(|&end| end > a.line) {
+            write!(writer, ",endLine={end}")?;
+        }
+        writeln!(
+            writer,
+            ",title={}::{}",
+            escape_property(&a.title),
+            escape_data(&a.message)
+        )?;
+    }
+    Ok(())
+}
+
+fn collect(value: &Value, annotations: &mut Vec<Annotation>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(annotation) = annotation(map) {
+                annotations.push(annotation);
+                // A finding's nested objects (spans, locations) are part of it.
+                return;
+            }
+            for child in map.values() {
+                collect(child, annotations);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect(item, annotations);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn annotation(map: &Map<String, Value>) -> Option<Annotation> {
+    if map.get("changed") == Some(&Value::Bool(false)) {
+        return None;
+    }
+    let str_field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| map.get(*key).and_then(Value::as_str))
+    };
+    let num_field = |keys: &[&str]| keys.iter().find_map(|key| map.get(*key)?.as_u64());
+
+    // Complexity violations (`omen complexity --check`).
+    if let (Some(cyclomatic), Some(cognitive)) =
+        (num_field(&["cyclomatic"]), num_field(&["cognitive"]))
+    {
+        let name = str_field(&["name"]).unwrap_or("function");
+        return Some(Annotation {
+            level: "error",
+            file: str_field(&["file"])?.to_string(),
+            line: num_field(&["line"]).unwrap_or(1).max(1),
+            end_line: None,
+            title: format!("Complexity: {name}"),
+            message: format!(
+                "{name} has cyclomatic complexity {cyclomatic} \
+                 and cognitive complexity {cognitive}"
+            ),
+        });
+    }
+
+    let level = level(str_field(&["severity"])?);
+    let title = str_field(&["smell_type", "marker", "category", "kind"]).unwrap_or("omen");
+    let message = str_field(&["description", "text", "reason", "message", "name"])
+        .unwrap_or("Omen finding")
+        .to_string();
+
+    // Smells point at the first of their source locations.
+    let location = map
+        .get("locations")
+        .and_then(Value::as_array)
+        .and_then(|locations| locations.first())
+        .and_then(Value::as_object);
+    let (file, span) = match location {
+        Some(location) => (location.get("file")?.as_str()?, location.get("span")),
+        None => (str_field(&["file", "path", "file_path"])?, map.get("span")),
+    };
+    let span_line = |key: &str| span?.get(key)?.as_u64();
+    let line = span_line("start_line")
+        .or_else(|| num_field(&["line", "start_line", "line_start"]))
+        .unwrap_or(1)
+        .max(1);
+    let end_line = span_line("end_line").or_else(|| num_field(&["end_line", "line_end"]));
+
+    Some(Annotation {
+        level,
+        file: file.to_string(),
+        line,
+        end_line,
+        title: format!("omen: {title}"),
+        message,
+    })
+}
+
+fn level(severity: &str) -> &'static str {
+    match severity.to_ascii_lowercase().as_str() {
+        "critical" | "high" | "error" => "error",
+        "low" | "info" | "note" => "notice",
+        _ => "warning",
+    }
+}
+
+/// Escape a message as the runner expects.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property; `:` and `,` delimit properties.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render(value: Value) -> String {
+        let mut out = Vec::new();
+        write_annotations(&value, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_satd_items_become_annotations() {
+        let out = render(json!({
+            "items": [
+                {"file": "src/a.rs", "line": 3, "severity": "high", "marker": "FIXME",
+                 "text": "leaks 100% of handles,\nsometimes"},
+                {"file": "src/b.rs", "line": 9, "severity": "low", "marker": "TODO",
+                 "text": "tidy", "changed": false},
+                {"file": "src/c.rs", "line": 1, "severity": "low", "marker": "TODO",
+                 "text": "rename", "changed": true}
+            ],
+            "summary": {"total_items": 3}
+        }));
+        assert_eq!(
+            out,
+            "::error file=src/a.rs,line=3,title=omen%3A FIXME::\
+             leaks 100%25 of handles,%0Asometimes\n\
+             ::notice file=src/c.rs,line=1,title=omen%3A TODO::rename\n"
+        );
+    }
+
+    #[test]
+    fn test_smells_point_at_first_location() {
+        let out = render(json!({
+            "smells": [{
+                "smell_type": "cyclic_dependency",
+                "severity": "critical",
+                "components": ["a.py", "b.py"],
+                "locations": [
+                    {"file": "a.py", "span": {"start_line": 2, "end_line": 4}},
+                    {"file": "b.py", "span": {"start_line": 1, "end_line": 1}}
+                ],
+                "description": "Cycle between a.py and b.py"
+            }]
+        }));
+        assert_eq!(
+            out,
+            "::error file=a.py,line=2,endLine=4,title=omen%3A cyclic_dependency::\
+             Cycle between a.py and b.py\n"
+        );
+    }
+
+    #[test]
+    fn test_complexity_violations() {
+        let out = render(json!([
+            {"name": "parse", "file": "src/p.rs", "line": 12, "cyclomatic": 31, "cognitive": 40}
+        ]));
+        assert_eq!(
+            out,
+            "::error file=src/p.rs,line=12,title=Complexity%3A parse::parse has cyclomatic \
+             complexity 31 and cognitive complexity 40\n"
+        );
+    }
+
+    #[test]
+    fn test_objects_without_severity_are_skipped() {
+        let out = render(json!({
+            "files": [{"path": "src/a.rs", "functions": [{"name": "f", "file": "src/a.rs"}]}]
+        }));
+        assert!(out.is_empty(), "got: {out}");
+    }
+}
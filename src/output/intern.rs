@@ -0,0 +1,177 @@
+//! String interning for compact JSON output.
+//!
+//! Long strings that repeat across a result (signatures, snippets, file
+//! paths in large repos) are stored once in a top-level `strings` table.
+//! Each occurrence becomes `{"$ref": "#/strings/N"}`, a JSON pointer into
+//! that table, so the document stays valid JSON and every value remains
+//! addressable. [`resolve`] restores the original value.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::core::{Error, Result};
+
+/// Strings shorter than this cost more as a reference than inline.
+const MIN_INTERN_LEN: usize = 32;
+
+const REF_KEY: &str = "$ref";
+const REF_PREFIX: &str = "#/strings/";
+
+/// Intern repeated long strings in `value`.
+///
+/// Returns `{"strings": [...], "data": ...}`. Object keys are left inline.
+pub fn intern(value: &Value) -> Value {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    count_strings(value, &mut counts);
+
+    let mut strings: Vec<&str> = counts
+        .into_iter()
+        .filter(|(s, n)| *n > 1 && s.len() >= MIN_INTERN_LEN)
+        .map(|(s, _)| s)
+        .collect();
+    // Stable output: the same input always produces the same table.
+    strings.sort_unstable();
+    let index: HashMap<&str, usize> = strings.iter().enumerate().map(|(i, s)| (*s, i)).collect();
+
+    json!({
+        "strings": strings,
+        "data": replace_strings(value, &index),
+    })
+}
+
+/// Undo [`intern`], returning the original value.
+///
+/// Values that are not interned documents are returned unchanged, so
+/// callers can resolve output without knowing how it was written.
+pub fn resolve(document: &Value) -> Result<Value> {
+    let (Some(Value::Array(strings)), Some(data)) = (document.get("strings"), document.get("data"))
+    else {
+        return Ok(document.clone());
+    };
+    if document.as_object().is_some_and(|map| map.len() != 2) {
+        return Ok(document.clone());
+    }
+    let strings: Vec<&str> = strings
+        .iter()
+        .map(|s| {
+            s.as_str()
+                .ok_or_else(|| Error::InvalidArgument("string table holds a non-string".into()))
+        })
+        .collect::<Result<_>>()?;
+    resolve_refs(data, &strings)
+}
+
+fn count_strings<'a>(value: &'a Value, counts: &mut HashMap<&'a str, usize>) {
+    match value {
+        Value::String(s) => *counts.entry(s.as_str()).or_default() += 1,
+        Value::Array(items) => items.iter().for_each(|v| count_strings(v, counts)),
+        Value::Object(map) => map.values().for_each(|v| count_strings(v, counts)),
+        _ => {}
+    }
+}
+
+fn replace_strings(value: &Value, index: &HashMap<&str, usize>) -> Value {
+    match value {
+        Value::String(s) => match index.get(s.as_str()) {
+            Some(i) => json!({ REF_KEY: format!("{REF_PREFIX}{i}") }),
+            None => value.clone(),
+        },
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| replace_strings(v, index)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), replace_strings(v, index)))
+                .collect::<Map<_, _>>(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+fn resolve_refs(value: &Value, strings: &[&str]) -> Result<Value> {
+    match value {
+        Value::Object(map) => {
+            if let Some(pointer) = string_ref(map) {
+                let i: usize = pointer.parse().map_err(|_| {
+                    Error::InvalidArgument(format!("bad string reference: {pointer}"))
+                })?;
+                let s = strings.get(i).ok_or_else(|| {
+                    Error::InvalidArgument(format!("dangling string reference: {REF_PREFIX}{i}"))
+                })?;
+                return Ok(Value::String((*s).to_string()));
+            }
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), resolve_refs(v, strings)?)))
+                .collect::<Result<Map<_, _>>>()
+                .map(Value::Object)
+        }
+        Value::Array(items) => items
+            .iter()
+            .map(|v| resolve_refs(v, strings))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        _ => Ok(value.clone()),
+    }
+}
+
+/// The table index of a `{"$ref": "#/strings/N"}` object, if `map` is one.
+fn string_ref(map: &Map<String, Value>) -> Option<&str> {
+    if map.len() != 1 {
+        return None;
+    }
+    map.get(REF_KEY)?.as_str()?.strip_prefix(REF_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str = "pub fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Analysis>";
+
+    #[test]
+    fn test_intern_round_trips() {
+        let value = json!({
+            "files": [
+                {"path": "src/lib.rs", "signature": SIGNATURE, "line": 1},
+                {"path": "src/lib.rs", "signature": SIGNATURE, "line": 2},
+            ],
+            "summary": {"total": 2, "note": null}
+        });
+
+        let interned = intern(&value);
+        assert_eq!(interned["strings"], json!([SIGNATURE]));
+        assert_eq!(
+            interned.pointer("/data/files/1/signature"),
+            Some(&json!({"$ref": "#/strings/0"}))
+        );
+        // Short strings stay inline even when repeated.
+        assert_eq!(
+            interned.pointer("/data/files/0/path"),
+            Some(&json!("src/lib.rs"))
+        );
+
+        assert_eq!(resolve(&interned).unwrap(), value);
+    }
+
+    #[test]
+    fn test_intern_skips_unique_strings() {
+        let value = json!({"a": SIGNATURE, "b": "x".repeat(40)});
+        let interned = intern(&value);
+        assert_eq!(interned["strings"], json!([]));
+        assert_eq!(interned["data"], value);
+    }
+
+    #[test]
+    fn test_resolve_passes_through_plain_values() {
+        let value = json!({"strings": ["kept"], "other": 1});
+        assert_eq!(resolve(&value).unwrap(), value);
+    }
+
+    #[test]
+    fn test_resolve_rejects_dangling_reference() {
+        let doc = json!({"strings": [], "data": {"$ref": "#/strings/3"}});
+        let err = resolve(&doc).unwrap_err().to_string();
+        assert!(err.contains("dangling"), "got: {err}");
+    }
+}
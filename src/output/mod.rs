@@ -1,11 +1,18 @@
 //! Output formatters for analysis results.
 
+use std::borrow::Cow;
 use std::io::Write;
 
 use serde::Serialize;
 use serde_json::Value;
 
 use crate::core::Result;
+use crate::manifest::Manifest;
+
+mod github;
+pub mod intern;
+pub mod sink;
+mod table;
 
 /// Output format enum.
 #[derive(Clone, Copy, Debug, Default)]
@@ -13,25 +20,78 @@ pub enum Format {
     #[default]
     Json,
     JsonCompact,
+    /// Single-line JSON with repeated long strings interned (see [`intern`]).
+    JsonInterned,
     Markdown,
     Text,
     Sarif,
+    Csv,
+    Tsv,
+    /// GitHub Actions workflow commands that annotate pull requests.
+    Github,
 }
 
 impl Format {
-    pub fn format_value<W: Write>(&self, value: &Value, writer: &mut W) -> Result<()> {
+    /// File extension conventionally used for this format.
+    pub fn extension(&self) -> &'static str {
         match self {
-            Format::Json => format_json(value, writer),
-            Format::JsonCompact => format_json_compact(value, writer),
+            Format::Json | Format::JsonCompact | Format::JsonInterned => "json",
+            Format::Markdown => "md",
+            Format::Text => "txt",
+            Format::Sarif => "sarif",
+            Format::Csv => "csv",
+            Format::Tsv => "tsv",
+            Format::Github => "txt",
+        }
+    }
+
+    /// Whether output in this format carries the analysis manifest.
+    pub fn is_stamped(&self) -> bool {
+        matches!(
+            self,
+            Format::Json | Format::JsonCompact | Format::JsonInterned | Format::Sarif
+        )
+    }
+
+    /// Write `value` in this format. JSON and SARIF output gets `manifest`
+    /// attached when one is given.
+    pub fn format_value<W: Write>(
+        &self,
+        value: &Value,
+        manifest: Option<&Manifest>,
+        writer: &mut W,
+    ) -> Result<()> {
+        match self {
+            Format::Json => format_json(&stamped(value, manifest), writer),
+            Format::JsonCompact => format_json_compact(&stamped(value, manifest), writer),
+            Format::JsonInterned => {
+                format_json_compact(&intern::intern(&stamped(value, manifest)), writer)
+            }
             Format::Markdown => format_markdown(value, writer),
             Format::Text => format_text(value, writer),
-            Format::Sarif => format_sarif(value, writer),
+            Format::Sarif => format_sarif(value, manifest, writer),
+            Format::Csv => table::write_table(value, ',', writer),
+            Format::Tsv => table::write_table(value, '\t', writer),
+            Format::Github => github::write_annotations(value, writer),
         }
     }
 
-    pub fn format<T: Serialize, W: Write>(&self, data: &T, writer: &mut W) -> Result<()> {
+    pub fn format<T: Serialize, W: Write>(
+        &self,
+        data: &T,
+        manifest: Option<&Manifest>,
+        writer: &mut W,
+    ) -> Result<()> {
         let value = serde_json::to_value(data)?;
-        self.format_value(&value, writer)
+        self.format_value(&value, manifest, writer)
+    }
+}
+
+/// `value` with `manifest` attached, if there is one.
+fn stamped<'a>(value: &'a Value, manifest: Option<&Manifest>) -> Cow<'a, Value> {
+    match manifest {
+        Some(manifest) => Cow::Owned(manifest.attach(value.clone())),
+        None => Cow::Borrowed(value),
     }
 }
 
@@ -117,8 +177,8 @@ pub fn truncate_lists(value: &mut Value, top: usize, offset: usize) -> usize {
     total_omitted
 }
 
-/// Format a JSON value with optional truncation applied for JSON formats.
-/// For non-JSON formats, truncation is not applied.
+/// Format a JSON value with optional truncation applied for JSON and
+/// tabular formats. For other formats, truncation is not applied.
 /// `top`: max items per array (None = unlimited). `offset`: skip first N items (None = 0).
 /// When only `offset` is set, truncation is applied with top=0 (unlimited after offset).
 pub fn format_with_limits<W: Write>(
@@ -126,27 +186,65 @@ pub fn format_with_limits<W: Write>(
     format: Format,
     top: Option<usize>,
     offset: Option<usize>,
+    manifest: Option<&Manifest>,
     writer: &mut W,
 ) -> Result<()> {
-    if matches!(format, Format::Json | Format::JsonCompact) && (top.is_some() || offset.is_some()) {
+    if matches!(
+        format,
+        Format::Json | Format::JsonCompact | Format::JsonInterned | Format::Csv | Format::Tsv
+    ) && (top.is_some() || offset.is_some())
+    {
         let limit = top.unwrap_or(0); // 0 means unlimited
         let off = offset.unwrap_or(0);
         truncate_lists(&mut value, limit, off);
     }
-    format.format_value(&value, writer)
+    format.format_value(&value, manifest, writer)
 }
 
 fn format_markdown<W: Write>(value: &Value, writer: &mut W) -> Result<()> {
-    format_value_as_markdown(value, writer, 0)?;
+    format_value_as_markdown(value, writer, 0, None)?;
     Ok(())
 }
 
+/// Format a JSON value as markdown with an HTML anchor in every heading.
+///
+/// Anchor ids are built from the JSON keys rather than the heading text, so
+/// links into the document survive changes to how keys are displayed:
+/// `components.satd` under prefix `omen` becomes `omen-components-satd`.
+/// Headings inside list items get no anchor.
+pub fn format_markdown_anchored<W: Write>(
+    value: &Value,
+    prefix: &str,
+    writer: &mut W,
+) -> Result<()> {
+    format_value_as_markdown(value, writer, 0, Some(prefix))?;
+    Ok(())
+}
+
+/// Anchor id for `key` below the `prefix` anchor.
+pub fn markdown_anchor(prefix: &str, key: &str) -> String {
+    format!("{}-{}", prefix, key.replace('_', "-"))
+}
+
+/// Escape `text` for an XML element body or double-quoted attribute, as in
+/// SVG badges, GraphML and JUnit reports.
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 fn format_text<W: Write>(value: &Value, writer: &mut W) -> Result<()> {
     format_value_as_text(value, writer, 0)?;
     Ok(())
 }
 
-fn format_sarif<W: Write>(value: &Value, writer: &mut W) -> Result<()> {
+fn format_sarif<W: Write>(
+    value: &Value,
+    manifest: Option<&Manifest>,
+    writer: &mut W,
+) -> Result<()> {
     let mut findings = Vec::new();
     collect_sarif_findings(value, &mut findings);
 
@@ -167,7 +265,7 @@ fn format_sarif<W: Write>(value: &Value, writer: &mut W) -> Result<()> {
                 "locations": [{
                     "physicalLocation": {
                         "artifactLocation": {"uri": finding.file},
-                        "region": {"startLine": finding.line}
+                        "region": finding.region
                     }
                 }]
             })
@@ -189,13 +287,13 @@ fn format_sarif<W: Write>(value: &Value, writer: &mut W) -> Result<()> {
         }]
     });
 
-    format_json(&sarif, writer)
+    format_json(&stamped(&sarif, manifest), writer)
 }
 
 #[derive(Debug)]
 struct SarifFinding {
     file: String,
-    line: u64,
+    region: Value,
     level: &'static str,
     message: String,
 }
@@ -239,9 +337,25 @@ fn sarif_finding_from_object(map: &serde_json::Map<String, Value>) -> Option<Sar
         .map(sarif_level)
         .unwrap_or("warning");
 
+    // Spans use SARIF's default 1-indexed UTF-16 columns, so they map directly.
+    let region = match map.get("span").and_then(Value::as_object) {
+        Some(span) => {
+            let field = |key: &str| span.get(key).and_then(Value::as_u64).unwrap_or(0);
+            serde_json::json!({
+                "startLine": field("start_line").max(1),
+                "startColumn": field("start_column").max(1),
+                "endLine": field("end_line").max(1),
+                "endColumn": field("end_column").max(1),
+                "byteOffset": field("start_byte"),
+                "byteLength": field("end_byte").saturating_sub(field("start_byte")),
+            })
+        }
+        None => serde_json::json!({ "startLine": line }),
+    };
+
     Some(SarifFinding {
         file: file.to_string(),
-        line,
+        region,
         level,
         message,
     })
@@ -256,15 +370,30 @@ fn sarif_level(severity: &str) -> &'static str {
     }
 }
 
-fn format_value_as_markdown<W: Write>(value: &Value, writer: &mut W, depth: usize) -> Result<()> {
+fn format_value_as_markdown<W: Write>(
+    value: &Value,
+    writer: &mut W,
+    depth: usize,
+    anchor: Option<&str>,
+) -> Result<()> {
     match value {
         Value::Object(map) => {
             for (key, val) in map {
                 let header_level = "#".repeat((depth + 1).min(6));
                 match val {
                     Value::Object(_) | Value::Array(_) => {
-                        writeln!(writer, "{} {}\n", header_level, format_key(key))?;
-                        format_value_as_markdown(val, writer, depth + 1)?;
+                        let id = anchor.map(|prefix| markdown_anchor(prefix, key));
+                        match &id {
+                            Some(id) => writeln!(
+                                writer,
+                                "{} <a id=\"{}\"></a>{}\n",
+                                header_level,
+                                id,
+                                format_key(key)
+                            )?,
+                            None => writeln!(writer, "{} {}\n", header_level, format_key(key))?,
+                        }
+                        format_value_as_markdown(val, writer, depth + 1, id.as_deref())?;
                     }
                     _ => {
                         writeln!(writer, "**{}**: {}\n", format_key(key), format_scalar(val))?;
@@ -280,7 +409,7 @@ fn format_value_as_markdown<W: Write>(value: &Value, writer: &mut W, depth: usiz
             } else {
                 for item in arr {
                     writeln!(writer, "---\n")?;
-                    format_value_as_markdown(item, writer, depth)?;
+                    format_value_as_markdown(item, writer, depth, None)?;
                 }
             }
         }
@@ -421,11 +550,30 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"a < b && c > "d""#),
+            "a &lt; b &amp;&amp; c &gt; &quot;d&quot;"
+        );
+    }
+
     #[test]
     fn test_format_default_is_json() {
         assert!(matches!(Format::default(), Format::Json));
     }
 
+    #[test]
+    fn test_format_extension() {
+        assert_eq!(Format::Json.extension(), "json");
+        assert_eq!(Format::JsonCompact.extension(), "json");
+        assert_eq!(Format::JsonInterned.extension(), "json");
+        assert_eq!(Format::Markdown.extension(), "md");
+        assert_eq!(Format::Sarif.extension(), "sarif");
+        assert_eq!(Format::Tsv.extension(), "tsv");
+        assert_eq!(Format::Github.extension(), "txt");
+    }
+
     #[test]
     fn test_format_sarif_emits_findings_from_items_array() {
         let value = json!({
@@ -438,7 +586,7 @@ mod tests {
             }]
         });
         let mut buf = Vec::new();
-        Format::Sarif.format_value(&value, &mut buf).unwrap();
+        Format::Sarif.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("\"version\": \"2.1.0\""));
         assert!(output.contains("\"artifactLocation\""));
@@ -447,11 +595,73 @@ mod tests {
         assert!(output.contains("TODO: remove shortcut"));
     }
 
+    #[test]
+    fn test_format_sarif_region_uses_span() {
+        let value = json!({
+            "items": [{
+                "file": "src/lib.rs",
+                "line": 2,
+                "marker": "TODO",
+                "span": {
+                    "start_line": 2,
+                    "start_column": 4,
+                    "end_line": 2,
+                    "end_column": 8,
+                    "start_byte": 13,
+                    "end_byte": 17
+                }
+            }]
+        });
+        let mut buf = Vec::new();
+        Format::Sarif.format_value(&value, None, &mut buf).unwrap();
+        let sarif: Value = serde_json::from_slice(&buf).unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"],
+            json!({
+                "startLine": 2,
+                "startColumn": 4,
+                "endLine": 2,
+                "endColumn": 8,
+                "byteOffset": 13,
+                "byteLength": 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_and_sarif_carry_the_given_manifest() {
+        let manifest = Manifest {
+            omen_version: "1.0.0".to_string(),
+            config_hash: "c".to_string(),
+            git_sha: None,
+            dirty: false,
+            file_count: 1,
+            analyzer_versions: Default::default(),
+            fingerprint: "f".to_string(),
+        };
+        let value = json!({"items": [{"file": "a.rs", "line": 1}]});
+        for format in [Format::Json, Format::JsonCompact, Format::Sarif] {
+            let mut buf = Vec::new();
+            format
+                .format_value(&value, Some(&manifest), &mut buf)
+                .unwrap();
+            let stamped: Value = serde_json::from_slice(&buf).unwrap();
+            assert_eq!(Manifest::extract(&stamped), vec![manifest.clone()]);
+
+            let mut buf = Vec::new();
+            format.format_value(&value, None, &mut buf).unwrap();
+            let bare: Value = serde_json::from_slice(&buf).unwrap();
+            assert!(Manifest::extract(&bare).is_empty());
+        }
+    }
+
     #[test]
     fn test_format_json_simple_object() {
         let value = json!({"name": "test", "count": 42});
         let mut buf = Vec::new();
-        Format::Json.format_value(&value, &mut buf).unwrap();
+        Format::Json.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("\"name\": \"test\""));
         assert!(output.contains("\"count\": 42"));
@@ -461,7 +671,7 @@ mod tests {
     fn test_format_json_nested() {
         let value = json!({"outer": {"inner": 123}});
         let mut buf = Vec::new();
-        Format::Json.format_value(&value, &mut buf).unwrap();
+        Format::Json.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("outer"));
         assert!(output.contains("inner"));
@@ -472,7 +682,9 @@ mod tests {
     fn test_format_markdown_simple_object() {
         let value = json!({"file_name": "test.rs", "score": 95});
         let mut buf = Vec::new();
-        Format::Markdown.format_value(&value, &mut buf).unwrap();
+        Format::Markdown
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("File Name"));
         assert!(output.contains("test.rs"));
@@ -483,11 +695,30 @@ mod tests {
     fn test_format_markdown_nested_object() {
         let value = json!({"summary": {"total": 10, "passed": 8}});
         let mut buf = Vec::new();
-        Format::Markdown.format_value(&value, &mut buf).unwrap();
+        Format::Markdown
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("# Summary"));
     }
 
+    #[test]
+    fn test_format_markdown_anchored() {
+        let value = json!({
+            "overall_score": 84,
+            "components": {"satd": {"score": 70}},
+            "packages": [{"path": "a", "files": {"count": 1}}]
+        });
+        let mut buf = Vec::new();
+        format_markdown_anchored(&value, "omen", &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("# <a id=\"omen-components\"></a>Components\n"));
+        assert!(output.contains("## <a id=\"omen-components-satd\"></a>Satd\n"));
+        assert!(output.contains("# <a id=\"omen-packages\"></a>Packages\n"));
+        assert!(output.contains("**Overall Score**: 84"));
+        assert!(!output.contains("omen-packages-files"));
+    }
+
     #[test]
     fn test_format_markdown_array_as_table() {
         let value = json!([
@@ -495,7 +726,9 @@ mod tests {
             {"name": "b", "value": 2}
         ]);
         let mut buf = Vec::new();
-        Format::Markdown.format_value(&value, &mut buf).unwrap();
+        Format::Markdown
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         // Should be formatted as markdown table
         assert!(output.contains("|"));
@@ -506,7 +739,9 @@ mod tests {
     fn test_format_markdown_empty_array() {
         let value = json!([]);
         let mut buf = Vec::new();
-        Format::Markdown.format_value(&value, &mut buf).unwrap();
+        Format::Markdown
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("_No items_"));
     }
@@ -518,7 +753,9 @@ mod tests {
             {"nested": {"deep": 2}}
         ]);
         let mut buf = Vec::new();
-        Format::Markdown.format_value(&value, &mut buf).unwrap();
+        Format::Markdown
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("---"));
     }
@@ -527,7 +764,7 @@ mod tests {
     fn test_format_text_simple_object() {
         let value = json!({"file_name": "test.rs", "score": 95});
         let mut buf = Vec::new();
-        Format::Text.format_value(&value, &mut buf).unwrap();
+        Format::Text.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("File Name: test.rs"));
         assert!(output.contains("Score: 95"));
@@ -537,7 +774,7 @@ mod tests {
     fn test_format_text_nested_object() {
         let value = json!({"summary": {"total": 10}});
         let mut buf = Vec::new();
-        Format::Text.format_value(&value, &mut buf).unwrap();
+        Format::Text.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("Summary:"));
         assert!(output.contains("Total: 10"));
@@ -547,7 +784,7 @@ mod tests {
     fn test_format_text_array() {
         let value = json!([{"name": "a"}, {"name": "b"}]);
         let mut buf = Vec::new();
-        Format::Text.format_value(&value, &mut buf).unwrap();
+        Format::Text.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("[0]"));
         assert!(output.contains("[1]"));
@@ -659,7 +896,7 @@ mod tests {
             count: 42,
         };
         let mut buf = Vec::new();
-        Format::Json.format(&data, &mut buf).unwrap();
+        Format::Json.format(&data, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("\"name\": \"test\""));
         assert!(output.contains("\"count\": 42"));
@@ -669,7 +906,7 @@ mod tests {
     fn test_format_text_scalar_at_root() {
         let value = json!("just a string");
         let mut buf = Vec::new();
-        Format::Text.format_value(&value, &mut buf).unwrap();
+        Format::Text.format_value(&value, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("just a string"));
     }
@@ -678,7 +915,9 @@ mod tests {
     fn test_format_markdown_scalar_at_root() {
         let value = json!("just a string");
         let mut buf = Vec::new();
-        Format::Markdown.format_value(&value, &mut buf).unwrap();
+        Format::Markdown
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         assert!(output.contains("just a string"));
     }
@@ -687,7 +926,9 @@ mod tests {
     fn test_format_json_compact_single_line() {
         let value = json!({"name": "test", "items": [1, 2, 3]});
         let mut buf = Vec::new();
-        Format::JsonCompact.format_value(&value, &mut buf).unwrap();
+        Format::JsonCompact
+            .format_value(&value, None, &mut buf)
+            .unwrap();
         let output = String::from_utf8(buf).unwrap();
         // Compact output must not contain newlines within the JSON (only trailing newline)
         assert_eq!(output.trim().lines().count(), 1);
@@ -698,9 +939,11 @@ mod tests {
         let value = json!({"name": "test", "count": 42, "items": [1, 2, 3]});
         let mut buf_pretty = Vec::new();
         let mut buf_compact = Vec::new();
-        Format::Json.format_value(&value, &mut buf_pretty).unwrap();
+        Format::Json
+            .format_value(&value, None, &mut buf_pretty)
+            .unwrap();
         Format::JsonCompact
-            .format_value(&value, &mut buf_compact)
+            .format_value(&value, None, &mut buf_compact)
             .unwrap();
         // Parse both back and compare
         let v1: Value = serde_json::from_slice(&buf_pretty).unwrap();
@@ -708,6 +951,22 @@ mod tests {
         assert_eq!(v1, v2);
     }
 
+    #[test]
+    fn test_format_json_interned_resolves_to_value() {
+        let path = "src/analyzers/some/deeply/nested/module/file.rs";
+        let value = json!({"files": [{"path": path}, {"path": path}]});
+        let mut buf = Vec::new();
+        Format::JsonInterned
+            .format_value(&value, None, &mut buf)
+            .unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert_eq!(output.trim().lines().count(), 1);
+        assert_eq!(output.matches(path).count(), 1);
+
+        let doc: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(intern::resolve(&doc).unwrap(), value);
+    }
+
     #[test]
     fn test_truncate_lists_limits_top_level_arrays() {
         let mut value = json!({"files": (0..100).collect::<Vec<_>>(), "summary": {"total": 100}});
@@ -763,7 +1022,7 @@ mod tests {
         // JsonCompact format with top=3: only 3 items should appear in output.
         let value = json!({"items": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]});
         let mut buf = Vec::new();
-        format_with_limits(value, Format::JsonCompact, Some(3), None, &mut buf).unwrap();
+        format_with_limits(value, Format::JsonCompact, Some(3), None, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
         assert_eq!(parsed["items"].as_array().unwrap().len(), 3);
@@ -774,7 +1033,7 @@ mod tests {
         // offset=2, top=None → unlimited after offset; items 2..9 (8 items).
         let value = json!({"items": [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]});
         let mut buf = Vec::new();
-        format_with_limits(value, Format::Json, None, Some(2), &mut buf).unwrap();
+        format_with_limits(value, Format::Json, None, Some(2), None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
         let items = parsed["items"].as_array().unwrap();
@@ -791,7 +1050,7 @@ mod tests {
         // Non-JSON format (Markdown) should not apply truncation.
         let value = json!({"items": [1, 2, 3, 4, 5]});
         let mut buf = Vec::new();
-        format_with_limits(value, Format::Markdown, Some(2), None, &mut buf).unwrap();
+        format_with_limits(value, Format::Markdown, Some(2), None, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         // Markdown output should contain all 5 items (no truncation).
         assert!(output.contains("1"), "markdown should contain item 1");
@@ -803,7 +1062,7 @@ mod tests {
         // No top and no offset: JSON format should pass through all items.
         let value = json!({"items": [1, 2, 3]});
         let mut buf = Vec::new();
-        format_with_limits(value, Format::Json, None, None, &mut buf).unwrap();
+        format_with_limits(value, Format::Json, None, None, None, &mut buf).unwrap();
         let output = String::from_utf8(buf).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
         assert_eq!(parsed["items"].as_array().unwrap().len(), 3);
@@ -0,0 +1,174 @@
+//! Destination for command output.
+//!
+//! The CLI hands each command an [`Output`] and the command writes
+//! everything it would normally print to it. By default that is the real
+//! standard output; `--output` and `--output-dir` redirect it into files so
+//! every command (including the per-analyzer results of `all`) can be
+//! captured the same way.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::Format;
+use crate::core::{Error, Result};
+
+/// Default filename template used with `--output-dir`.
+pub const DEFAULT_TEMPLATE: &str = "{analyzer}.{format}";
+
+/// Where command output should go.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Destination {
+    /// Standard output.
+    #[default]
+    Stdout,
+    /// A single file receiving all output of the command.
+    File(PathBuf),
+    /// A directory with one file per analyzer, named from `template`.
+    Dir { dir: PathBuf, template: String },
+}
+
+impl Destination {
+    /// Resolve the file that output for `analyzer` should be written to.
+    /// Returns `None` when output goes to standard output.
+    pub fn path_for(&self, analyzer: &str, format: Format) -> Option<PathBuf> {
+        match self {
+            Destination::Stdout => None,
+            Destination::File(path) => Some(path.clone()),
+            Destination::Dir { dir, template } => {
+                Some(dir.join(expand_template(template, analyzer, format)))
+            }
+        }
+    }
+
+    /// Whether each analyzer gets its own file.
+    pub fn is_per_analyzer(&self) -> bool {
+        matches!(self, Destination::Dir { .. })
+    }
+}
+
+/// Expand `{analyzer}` and `{format}` placeholders in a filename template.
+pub fn expand_template(template: &str, analyzer: &str, format: Format) -> String {
+    template
+        .replace("{analyzer}", analyzer)
+        .replace("{format}", format.extension())
+}
+
+/// Validate a `--template` value before any file is written.
+pub fn validate_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(Error::InvalidArgument(
+            "output template must not be empty".to_string(),
+        ));
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            return Err(Error::InvalidArgument(format!(
+                "unterminated placeholder in output template: {template}"
+            )));
+        };
+        let name = &rest[start + 1..start + len];
+        if name != "analyzer" && name != "format" {
+            return Err(Error::InvalidArgument(format!(
+                "unknown placeholder {{{name}}} in output template (expected {{analyzer}} or {{format}})"
+            )));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    Ok(())
+}
+
+/// Writer for command output: standard output or a file.
+pub struct Output(Option<File>);
+
+impl Output {
+    /// Write to standard output.
+    pub fn stdout() -> Self {
+        Self(None)
+    }
+
+    /// Write to `path`, truncating it and creating parent directories.
+    pub fn create(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(Self(Some(File::create(path)?)))
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            Some(file) => file.write(buf),
+            None => io::stdout().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.0 {
+            Some(file) => file.flush(),
+            None => io::stdout().flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_template() {
+        assert_eq!(
+            expand_template(DEFAULT_TEMPLATE, "complexity", Format::Json),
+            "complexity.json"
+        );
+        assert_eq!(
+            expand_template("omen-{analyzer}-report.{format}", "satd", Format::Markdown),
+            "omen-satd-report.md"
+        );
+        assert_eq!(
+            expand_template("{analyzer}.{format}", "tdg", Format::JsonCompact),
+            "tdg.json"
+        );
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template(DEFAULT_TEMPLATE).is_ok());
+        assert!(validate_template("reports/{analyzer}.txt").is_ok());
+        assert!(validate_template("").is_err());
+        assert!(validate_template("{tool}.{format}").is_err());
+        assert!(validate_template("{analyzer.json").is_err());
+    }
+
+    #[test]
+    fn test_destination_path_for() {
+        assert_eq!(Destination::Stdout.path_for("churn", Format::Json), None);
+        assert_eq!(
+            Destination::File(PathBuf::from("out.json")).path_for("churn", Format::Csv),
+            Some(PathBuf::from("out.json"))
+        );
+        let dir = Destination::Dir {
+            dir: PathBuf::from("reports"),
+            template: DEFAULT_TEMPLATE.to_string(),
+        };
+        assert!(dir.is_per_analyzer());
+        assert_eq!(
+            dir.path_for("churn", Format::Csv),
+            Some(PathBuf::from("reports").join("churn.csv"))
+        );
+    }
+
+    #[test]
+    fn test_output_creates_the_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("nested").join("out.txt");
+
+        let mut out = Output::create(&path).unwrap();
+        write!(out, "hello").unwrap();
+        drop(out);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+    }
+}
@@ -0,0 +1,276 @@
+//! CSV/TSV output for analyzers with naturally tabular results.
+//!
+//! Each supported analyzer has a fixed column list so spreadsheets and
+//! scripts can rely on header names and order across releases. New columns
+//! are only ever appended.
+//!
+//! | analyzer   | one row per | columns |
+//! |------------|-------------|---------|
+//! | complexity | function    | file, function, start_line, end_line, cyclomatic, cognitive, max_nesting, lines |
+//! | churn      | file        | file, commits, author_count, additions, deletions, churn_score, first_commit, last_commit |
+//! | hotspot    | file        | file, score, severity, churn_percentile, complexity_percentile, commits, avg_complexity |
+//! | ownership  | file        | file, primary_owner, ownership_percent, concentration, total_lines, contributor_count, is_silo, risk_level |
+//! | defect     | file        | file, probability, confidence, risk_level, churn, complexity, duplication, coupling, ownership |
+//!
+//! List-valued fields (`author_count`, `contributor_count`) are written as
+//! their length; missing values are empty cells.
+
+use std::io::Write;
+
+use serde_json::Value;
+
+use crate::core::{Error, Result};
+
+/// A column: header name and JSON pointer relative to the row.
+struct Column {
+    header: &'static str,
+    pointer: &'static str,
+}
+
+const fn col(header: &'static str, pointer: &'static str) -> Column {
+    Column { header, pointer }
+}
+
+/// Column layout for one analyzer's output.
+struct TableSpec {
+    analyzer: &'static str,
+    /// Path from the root to the rows; `*` expands every array element.
+    rows: &'static [&'static str],
+    /// JSON pointer only this analyzer's output has, used to recognize it.
+    marker: &'static str,
+    columns: &'static [Column],
+}
+
+const SPECS: &[TableSpec] = &[
+    TableSpec {
+        analyzer: "complexity",
+        rows: &["files", "*", "functions", "*"],
+        marker: "/summary/max_cognitive",
+        columns: &[
+            col("file", "/file"),
+            col("function", "/name"),
+            col("start_line", "/start_line"),
+            col("end_line", "/end_line"),
+            col("cyclomatic", "/metrics/cyclomatic"),
+            col("cognitive", "/metrics/cognitive"),
+            col("max_nesting", "/metrics/max_nesting"),
+            col("lines", "/metrics/lines"),
+        ],
+    },
+    TableSpec {
+        analyzer: "churn",
+        rows: &["files", "*"],
+        marker: "/repository_root",
+        columns: &[
+            col("file", "/relative_path"),
+            col("commits", "/commits"),
+            col("author_count", "/unique_authors"),
+            col("additions", "/additions"),
+            col("deletions", "/deletions"),
+            col("churn_score", "/churn_score"),
+            col("first_commit", "/first_commit"),
+            col("last_commit", "/last_commit"),
+        ],
+    },
+    TableSpec {
+        analyzer: "hotspot",
+        rows: &["hotspots", "*"],
+        marker: "/hotspots",
+        columns: &[
+            col("file", "/file"),
+            col("score", "/score"),
+            col("severity", "/severity"),
+            col("churn_percentile", "/churn_percentile"),
+            col("complexity_percentile", "/complexity_percentile"),
+            col("commits", "/commits"),
+            col("avg_complexity", "/avg_complexity"),
+        ],
+    },
+    TableSpec {
+        analyzer: "ownership",
+        rows: &["files", "*"],
+        marker: "/summary/bus_factor",
+        columns: &[
+            col("file", "/path"),
+            col("primary_owner", "/primary_owner"),
+            col("ownership_percent", "/ownership_percent"),
+            col("concentration", "/concentration"),
+            col("total_lines", "/total_lines"),
+            col("contributor_count", "/contributors"),
+            col("is_silo", "/is_silo"),
+            col("risk_level", "/risk_level"),
+        ],
+    },
+    TableSpec {
+        analyzer: "defect",
+        rows: &["files", "*"],
+        marker: "/weights",
+        columns: &[
+            col("file", "/file_path"),
+            col("probability", "/probability"),
+            col("confidence", "/confidence"),
+            col("risk_level", "/risk_level"),
+            col("churn", "/contributing_factors/churn"),
+            col("complexity", "/contributing_factors/complexity"),
+            col("duplication", "/contributing_factors/duplication"),
+            col("coupling", "/contributing_factors/coupling"),
+            col("ownership", "/contributing_factors/ownership"),
+        ],
+    },
+];
+
+impl TableSpec {
+    /// The spec for the analyzer that produced `value`.
+    fn detect(value: &Value) -> Option<&'static TableSpec> {
+        SPECS
+            .iter()
+            .find(|spec| value.pointer(spec.marker).is_some())
+    }
+
+    fn rows<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+        for segment in self.rows {
+            current = current
+                .into_iter()
+                .flat_map(|v| match (*segment, v) {
+                    ("*", Value::Array(items)) => items.iter().collect::<Vec<_>>(),
+                    ("*", _) => Vec::new(),
+                    (key, v) => v.get(key).into_iter().collect(),
+                })
+                .collect();
+        }
+        current
+    }
+}
+
+/// Write `value` as a delimited table. Fails for outputs that have no
+/// documented column layout.
+pub(super) fn write_table<W: Write>(value: &Value, delimiter: char, writer: &mut W) -> Result<()> {
+    let spec = TableSpec::detect(value).ok_or_else(|| {
+        let supported: Vec<&str> = SPECS.iter().map(|s| s.analyzer).collect();
+        Error::InvalidArgument(format!(
+            "CSV/TSV output is only supported for: {}",
+            supported.join(", ")
+        ))
+    })?;
+
+    let headers: Vec<String> = spec
+        .columns
+        .iter()
+        .map(|c| escape_cell(c.header, delimiter))
+        .collect();
+    writeln!(writer, "{}", headers.join(&delimiter.to_string()))?;
+
+    for row in spec.rows(value) {
+        let cells: Vec<String> = spec
+            .columns
+            .iter()
+            .map(|c| escape_cell(&cell_text(row.pointer(c.pointer)), delimiter))
+            .collect();
+        writeln!(writer, "{}", cells.join(&delimiter.to_string()))?;
+    }
+    Ok(())
+}
+
+fn cell_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) => items.len().to_string(),
+        Some(Value::Object(_)) => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Quote per RFC 4180 for CSV; TSV has no quoting, so tabs and newlines
+/// become spaces.
+fn escape_cell(text: &str, delimiter: char) -> String {
+    if delimiter == '\t' {
+        return text.replace(['\t', '\n', '\r'], " ");
+    }
+    if text.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render(value: &Value, delimiter: char) -> String {
+        let mut out = Vec::new();
+        write_table(value, delimiter, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_complexity_one_row_per_function() {
+        let value = json!({
+            "files": [{
+                "path": "src/a.rs",
+                "functions": [
+                    {"name": "f", "file": "src/a.rs", "start_line": 1, "end_line": 4,
+                     "metrics": {"cyclomatic": 2, "cognitive": 1, "max_nesting": 1, "lines": 4}},
+                    {"name": "g, h", "file": "src/a.rs", "start_line": 6, "end_line": 9,
+                     "metrics": {"cyclomatic": 5, "cognitive": 7, "max_nesting": 2, "lines": 4}}
+                ]
+            }],
+            "summary": {"max_cognitive": 7}
+        });
+
+        assert_eq!(
+            render(&value, ','),
+            "file,function,start_line,end_line,cyclomatic,cognitive,max_nesting,lines\n\
+             src/a.rs,f,1,4,2,1,1,4\n\
+             src/a.rs,\"g, h\",6,9,5,7,2,4\n"
+        );
+    }
+
+    #[test]
+    fn test_detects_each_file_level_analyzer() {
+        let churn = json!({"repository_root": "/repo", "files": [{"relative_path": "a.rs", "commits": 3,
+            "unique_authors": ["x", "y"], "additions": 10, "deletions": 2,
+            "churn_score": 0.5, "first_commit": null, "last_commit": null}]});
+        assert_eq!(
+            render(&churn, '\t').lines().nth(1),
+            Some("a.rs\t3\t2\t10\t2\t0.5\t\t")
+        );
+
+        let ownership = json!({"files": [{"path": "a.rs", "primary_owner": "alice",
+            "ownership_percent": 80.0, "concentration": 0.8, "total_lines": 10,
+            "contributors": [{}, {}], "is_silo": false, "risk_level": "medium"}],
+            "summary": {"bus_factor": 1}});
+        assert!(render(&ownership, ',').starts_with("file,primary_owner,"));
+
+        let defect = json!({"files": [{"file_path": "a.rs", "probability": 0.7,
+            "confidence": 1.0, "risk_level": "high",
+            "contributing_factors": {"churn": 0.1}}], "weights": {}});
+        assert_eq!(
+            render(&defect, ',').lines().nth(1),
+            Some("a.rs,0.7,1.0,high,0.1,,,,")
+        );
+
+        let hotspot = json!({"hotspots": [], "summary": {}});
+        assert_eq!(
+            render(&hotspot, ','),
+            "file,score,severity,churn_percentile,complexity_percentile,commits,avg_complexity\n"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_output_is_an_error() {
+        let mut out = Vec::new();
+        let err = write_table(&json!({"items": [1, 2]}), ',', &mut out).unwrap_err();
+        assert!(err.to_string().contains("complexity, churn"));
+    }
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell("plain", ','), "plain");
+        assert_eq!(escape_cell("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_cell("a\tb\nc", '\t'), "a b c");
+    }
+}
@@ -0,0 +1,92 @@
+//! Parse results shared between analyzers.
+//!
+//! `omen all` and `omen report generate` run most analyzers over the same
+//! files. Parsing them once up front, in parallel, and handing every analyzer
+//! the same syntax trees through [`AnalysisContext::parsed`] saves a
+//! tree-sitter pass per analyzer.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use super::{ParseResult, Parser};
+use crate::core::AnalysisContext;
+
+/// Parsed files of a [`FileSet`](crate::core::FileSet), keyed by their path
+/// in the set.
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    parsed: HashMap<PathBuf, Arc<ParseResult>>,
+}
+
+impl ParseCache {
+    /// Parse every file of `ctx` in parallel. Files that can't be read or
+    /// parsed are left out and parsed on demand, failing the same way.
+    pub fn build(ctx: &AnalysisContext<'_>) -> Self {
+        let parsed = ctx
+            .files
+            .files()
+            .par_iter()
+            .filter_map(|path| {
+                let source = ctx.load_source(path).ok()?;
+                let result = Parser::new().parse_source(&source).ok()?;
+                Some((path.clone(), Arc::new(result)))
+            })
+            .collect();
+        Self { parsed }
+    }
+
+    /// The parse of `path`, relative to the root or under it.
+    pub fn get(&self, path: &Path, root: &Path) -> Option<Arc<ParseResult>> {
+        self.parsed
+            .get(path)
+            .or_else(|| self.parsed.get(path.strip_prefix(root).ok()?))
+            .cloned()
+    }
+
+    /// Number of parsed files.
+    pub fn len(&self) -> usize {
+        self.parsed.len()
+    }
+
+    /// Whether no file was parsed.
+    pub fn is_empty(&self) -> bool {
+        self.parsed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::FileSet;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_analyzers_share_cached_parses() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("lib.py"), "def f():\n    return 1\n").unwrap();
+        std::fs::write(dir.path().join("main.go"), "package main\n").unwrap();
+        let config = Config::default();
+        let files = FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&files, &config, Some(dir.path()));
+
+        let cache = Arc::new(ParseCache::build(&ctx));
+        assert_eq!(cache.len(), 2);
+        let ctx = ctx.with_parse_cache(cache.clone());
+
+        let first = ctx.parsed(Path::new("lib.py")).unwrap();
+        let again = ctx.parsed(&dir.path().join("lib.py")).unwrap();
+        assert!(Arc::ptr_eq(&first, &again), "both come from the cache");
+        assert_eq!(first.path, dir.path().join("lib.py"));
+
+        // Without a cache the file is parsed on demand, to the same result.
+        let uncached = AnalysisContext::new(&files, &config, Some(dir.path()));
+        let parsed = uncached.parsed(Path::new("lib.py")).unwrap();
+        assert_eq!(parsed.path, first.path);
+        assert_eq!(&parsed.source[..], &first.source[..]);
+        assert!(uncached.parsed(Path::new("missing.py")).is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! Tree-sitter based multi-language parser.
 
+mod cache;
 pub mod queries;
 
+pub use cache::ParseCache;
+
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::Path;
@@ -9,7 +12,7 @@ use std::sync::Arc;
 
 use tree_sitter::{Language as TsLanguage, Parser as TsParser, Tree};
 
-use crate::core::{Error, Language, Result, SourceFile};
+use crate::core::{Error, FileContent, Language, Result, SourceFile, Span};
 
 // Thread-local parser cache to avoid lock contention in parallel parsing.
 // Each rayon worker thread gets its own set of parsers.
@@ -40,12 +43,27 @@ impl Parser {
     }
 
     /// Parse source content.
+    ///
+    /// The result shares the file's buffer rather than copying it.
     pub fn parse_source(&self, file: &SourceFile) -> Result<ParseResult> {
-        self.parse(&file.content, file.language, &file.path)
+        self.parse_content(file.content.clone(), file.language, &file.path)
     }
 
     /// Parse content with explicit language.
+    ///
+    /// Copies `content` into the result; prefer [`Self::parse_content`] when
+    /// the bytes are already owned.
     pub fn parse(&self, content: &[u8], lang: Language, path: &Path) -> Result<ParseResult> {
+        self.parse_content(content.into(), lang, path)
+    }
+
+    /// Parse shared content with explicit language, without copying it.
+    pub fn parse_content(
+        &self,
+        content: FileContent,
+        lang: Language,
+        path: &Path,
+    ) -> Result<ParseResult> {
         let ts_lang = get_tree_sitter_language(lang)?;
 
         let tree = THREAD_PARSERS.with(|parsers| {
@@ -56,15 +74,17 @@ impl Parser {
                 p
             });
 
-            parser.parse(content, None).ok_or_else(|| Error::Parse {
-                path: path.to_path_buf(),
-                message: "Failed to parse file".to_string(),
-            })
+            parser
+                .parse(&content[..], None)
+                .ok_or_else(|| Error::Parse {
+                    path: path.to_path_buf(),
+                    message: "Failed to parse file".to_string(),
+                })
         })?;
 
         Ok(ParseResult {
             tree: Arc::new(tree),
-            source: content.to_vec(),
+            source: content,
             language: lang,
             path: path.to_path_buf(),
         })
@@ -76,8 +96,8 @@ impl Parser {
 pub struct ParseResult {
     /// The parsed syntax tree.
     pub tree: Arc<Tree>,
-    /// Original source content.
-    pub source: Vec<u8>,
+    /// Original source content, shared with the [`SourceFile`] it came from.
+    pub source: FileContent,
     /// Detected language.
     pub language: Language,
     /// File path.
@@ -111,6 +131,8 @@ pub fn get_tree_sitter_language(lang: Language) -> Result<TsLanguage> {
         Language::Ruby => tree_sitter_ruby::LANGUAGE,
         Language::Php => tree_sitter_php::LANGUAGE_PHP,
         Language::Bash => tree_sitter_bash::LANGUAGE,
+        Language::Lua => tree_sitter_lua::LANGUAGE,
+        Language::Zig => tree_sitter_zig::LANGUAGE,
     };
     Ok(ts_lang.into())
 }
@@ -124,6 +146,8 @@ pub struct FunctionNode {
     pub start_line: u32,
     /// End line (1-indexed).
     pub end_line: u32,
+    /// Range of the whole function.
+    pub span: Span,
     /// Byte range of the function body (start, end).
     pub body_byte_range: Option<(usize, usize)>,
     /// Whether function is exported/public.
@@ -154,7 +178,7 @@ pub fn extract_classes(result: &ParseResult) -> Vec<ClassNode> {
     match result.language {
         Language::Rust => extract_rust_classes(result),
         Language::Go => extract_go_classes(result),
-        Language::C | Language::Bash => Vec::new(),
+        Language::C | Language::Bash | Language::Lua | Language::Zig => Vec::new(),
         _ => extract_generic_classes(result),
     }
 }
@@ -913,8 +937,13 @@ pub struct ImportNode {
     pub path: String,
     /// Start line.
     pub line: u32,
+    /// Range of the import statement.
+    pub span: Span,
     /// Imported names (if any).
     pub names: Vec<String>,
+    /// Loaded at runtime (`import()`, `importlib`, `Class.forName`) rather
+    /// than declared; the module may never actually be loaded.
+    pub dynamic: bool,
 }
 
 /// Extract functions from a parse result.
@@ -999,6 +1028,23 @@ pub fn extract_imports(result: &ParseResult) -> Vec<ImportNode> {
                     imports.push(import);
                 }
             }
+            Language::TypeScript | Language::JavaScript | Language::Tsx | Language::Jsx
+                if node.kind() == "call_expression" =>
+            {
+                if let Some(import) = extract_js_dynamic_import(&node, source) {
+                    imports.push(import);
+                }
+            }
+            Language::Python if node.kind() == "call" => {
+                if let Some(import) = extract_python_dynamic_import(&node, source) {
+                    imports.push(import);
+                }
+            }
+            Language::Java if node.kind() == "method_invocation" => {
+                if let Some(import) = extract_java_dynamic_import(&node, source) {
+                    imports.push(import);
+                }
+            }
             Language::Ruby if node.kind() == "call" => {
                 if let Some(import) = extract_ruby_import(&node, source) {
                     imports.push(import);
@@ -1039,6 +1085,8 @@ fn get_function_node_types(lang: Language) -> Vec<&'static str> {
         Language::Ruby => vec!["method", "singleton_method"],
         Language::Php => vec!["function_definition", "method_declaration"],
         Language::Bash => vec!["function_definition"],
+        Language::Lua => vec!["function_declaration", "local_function_declaration"],
+        Language::Zig => vec!["function_declaration"],
     }
 }
 
@@ -1073,6 +1121,7 @@ fn extract_function_info(
         name,
         start_line: node.start_position().row as u32 + 1,
         end_line: node.end_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         body_byte_range: body.map(|b| (b.start_byte(), b.end_byte())),
         is_exported,
         signature,
@@ -1176,6 +1225,13 @@ fn check_is_exported(node: &tree_sitter::Node<'_>, source: &[u8], lang: Language
             }
             false
         }
+        Language::Lua => {
+            // `local function` is private to its chunk
+            !node.children(&mut node.walk()).any(|c| c.kind() == "local")
+        }
+        Language::Zig => node
+            .children(&mut node.walk())
+            .any(|c| matches!(c.kind(), "pub" | "export")),
         _ => true, // Default to exported for other languages
     }
 }
@@ -1202,7 +1258,9 @@ fn extract_go_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Impo
     Some(ImportNode {
         path,
         line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         names: Vec::new(),
+        dynamic: false,
     })
 }
 
@@ -1221,7 +1279,9 @@ fn extract_rust_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Im
                     return Some(ImportNode {
                         path,
                         line: node.start_position().row as u32 + 1,
+                        span: Span::from_node(node, source),
                         names: Vec::new(),
+                        dynamic: false,
                     });
                 }
             }
@@ -1241,7 +1301,9 @@ fn extract_rust_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Im
                 return Some(ImportNode {
                     path,
                     line: node.start_position().row as u32 + 1,
+                    span: Span::from_node(node, source),
                     names: Vec::new(),
+                    dynamic: false,
                 });
             }
         }
@@ -1273,7 +1335,9 @@ fn extract_rust_mod(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Impor
     Some(ImportNode {
         path: name,
         line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         names: Vec::new(),
+        dynamic: false,
     })
 }
 
@@ -1282,7 +1346,9 @@ fn extract_python_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<
     Some(ImportNode {
         path,
         line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         names: Vec::new(),
+        dynamic: false,
     })
 }
 
@@ -1291,7 +1357,9 @@ fn extract_js_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Impo
     Some(ImportNode {
         path: path.trim_matches(|c| c == '"' || c == '\'').to_string(),
         line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         names: Vec::new(),
+        dynamic: false,
     })
 }
 
@@ -1300,7 +1368,67 @@ fn extract_java_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Im
     Some(ImportNode {
         path,
         line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
+        names: Vec::new(),
+        dynamic: false,
+    })
+}
+
+/// `import("./module")`.
+fn extract_js_dynamic_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<ImportNode> {
+    if node.child_by_field_name("function")?.kind() != "import" {
+        return None;
+    }
+    dynamic_import(node, source)
+}
+
+/// `importlib.import_module("pkg.mod")` and `__import__("pkg.mod")`.
+fn extract_python_dynamic_import(
+    node: &tree_sitter::Node<'_>,
+    source: &[u8],
+) -> Option<ImportNode> {
+    let function = find_child_by_field(node, "function", source)?;
+    if !matches!(
+        function.as_str(),
+        "importlib.import_module" | "import_module" | "__import__"
+    ) {
+        return None;
+    }
+    dynamic_import(node, source)
+}
+
+/// `Class.forName("com.example.Plugin")` and `loader.loadClass(...)`.
+fn extract_java_dynamic_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<ImportNode> {
+    match find_child_by_field(node, "name", source)?.as_str() {
+        "forName" if find_child_by_field(node, "object", source)?.ends_with("Class") => {}
+        "loadClass" => {}
+        _ => return None,
+    }
+    dynamic_import(node, source)
+}
+
+/// A runtime import of the module named by the call's first argument. Only
+/// string literals count: a computed name can't be resolved statically.
+fn dynamic_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<ImportNode> {
+    let argument = node.child_by_field_name("arguments")?.named_child(0)?;
+    if !matches!(argument.kind(), "string" | "string_literal") {
+        return None;
+    }
+    let text = argument.utf8_text(source).ok()?;
+    // Python f-strings and other prefixed literals are computed too.
+    if !text.starts_with(['"', '\'']) {
+        return None;
+    }
+    let path = text.trim_matches(|c| c == '"' || c == '\'');
+    if path.is_empty() {
+        return None;
+    }
+    Some(ImportNode {
+        path: path.to_string(),
+        line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         names: Vec::new(),
+        dynamic: true,
     })
 }
 
@@ -1317,7 +1445,9 @@ fn extract_ruby_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Im
             Some(ImportNode {
                 path: content,
                 line,
+                span: Span::from_node(node, source),
                 names: Vec::new(),
+                dynamic: false,
             })
         }
         "include" | "extend" | "prepend" => {
@@ -1326,7 +1456,9 @@ fn extract_ruby_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Im
             Some(ImportNode {
                 path,
                 line,
+                span: Span::from_node(node, source),
                 names: Vec::new(),
+                dynamic: false,
             })
         }
         "autoload" => {
@@ -1335,7 +1467,9 @@ fn extract_ruby_import(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<Im
             Some(ImportNode {
                 path: content,
                 line,
+                span: Span::from_node(node, source),
                 names: Vec::new(),
+                dynamic: false,
             })
         }
         _ => None,
@@ -1349,7 +1483,9 @@ fn extract_ruby_superclass(node: &tree_sitter::Node<'_>, source: &[u8]) -> Optio
     Some(ImportNode {
         path,
         line: node.start_position().row as u32 + 1,
+        span: Span::from_node(node, source),
         names: Vec::new(),
+        dynamic: false,
     })
 }
 
@@ -1527,6 +1663,38 @@ mod tests {
         assert_eq!(functions.len(), 1);
     }
 
+    #[test]
+    fn test_parse_lua() {
+        let parser = Parser::new();
+        let content = b"local function helper() return 1 end\n\nfunction M.run(x)\n  return helper() + x\nend\n";
+        let result = parser
+            .parse(content, Language::Lua, Path::new("init.lua"))
+            .unwrap();
+
+        let functions = extract_functions(&result);
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name, "helper");
+        assert!(!functions[0].is_exported);
+        assert!(functions[1].is_exported);
+    }
+
+    #[test]
+    fn test_parse_zig() {
+        let parser = Parser::new();
+        let content =
+            b"fn helper() u32 {\n    return 1;\n}\n\npub fn main() void {\n    _ = helper();\n}\n";
+        let result = parser
+            .parse(content, Language::Zig, Path::new("main.zig"))
+            .unwrap();
+
+        let functions = extract_functions(&result);
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].name, "helper");
+        assert!(!functions[0].is_exported);
+        assert_eq!(functions[1].name, "main");
+        assert!(functions[1].is_exported);
+    }
+
     #[test]
     fn test_parse_csharp() {
         let parser = Parser::new();
@@ -1604,6 +1772,8 @@ mod tests {
         assert!(get_tree_sitter_language(Language::Ruby).is_ok());
         assert!(get_tree_sitter_language(Language::Php).is_ok());
         assert!(get_tree_sitter_language(Language::Bash).is_ok());
+        assert!(get_tree_sitter_language(Language::Lua).is_ok());
+        assert!(get_tree_sitter_language(Language::Zig).is_ok());
     }
 
     #[test]
@@ -1712,6 +1882,54 @@ mod tests {
         assert!(!imports.is_empty());
     }
 
+    #[test]
+    fn test_extract_dynamic_imports() {
+        let parser = Parser::new();
+        let dynamic = |content: &[u8], lang: Language, file: &str| {
+            let result = parser.parse(content, lang, Path::new(file)).unwrap();
+            extract_imports(&result)
+                .into_iter()
+                .map(|import| (import.path, import.dynamic))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dynamic(
+                b"import a from './a';\nconst b = import('./b');\nimport(`./${name}`);",
+                Language::JavaScript,
+                "main.js"
+            ),
+            vec![("./a".to_string(), false), ("./b".to_string(), true)]
+        );
+        assert_eq!(
+            dynamic(
+                b"import importlib\nm = importlib.import_module('pkg.plugins')\n\
+                  n = __import__(\"json\")\nimportlib.import_module(name)\n",
+                Language::Python,
+                "main.py"
+            ),
+            vec![
+                ("import importlib".to_string(), false),
+                ("pkg.plugins".to_string(), true),
+                ("json".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            dynamic(
+                b"class Main { void run(ClassLoader l) throws Exception {\n\
+                  Class.forName(\"com.example.Plugin\");\n\
+                  l.loadClass(\"com.example.Other\");\n\
+                  Integer.valueOf(\"1\"); } }",
+                Language::Java,
+                "Main.java"
+            ),
+            vec![
+                ("com.example.Plugin".to_string(), true),
+                ("com.example.Other".to_string(), true),
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_ruby_require_path() {
         let parser = Parser::new();
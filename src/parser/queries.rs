@@ -98,6 +98,24 @@ pub fn get_decision_node_types(lang: Language) -> &'static [&'static str] {
             "case_statement",
             "elif_clause",
         ],
+        Language::Lua => &[
+            "if_statement",
+            "elseif_statement",
+            "for_statement",
+            "while_statement",
+            "repeat_statement",
+        ],
+        Language::Zig => &[
+            "if_statement",
+            "if_expression",
+            "for_statement",
+            "for_expression",
+            "while_statement",
+            "while_expression",
+            "switch_expression",
+            // Each prong is an independent path, like Go's expression_case.
+            "switch_case",
+        ],
     }
 }
 
@@ -166,6 +184,21 @@ pub fn get_nesting_node_types(lang: Language) -> &'static [&'static str] {
             "while_statement",
             "case_statement",
         ],
+        Language::Lua => &[
+            "if_statement",
+            "for_statement",
+            "while_statement",
+            "repeat_statement",
+        ],
+        Language::Zig => &[
+            "if_statement",
+            "if_expression",
+            "for_statement",
+            "for_expression",
+            "while_statement",
+            "while_expression",
+            "switch_expression",
+        ],
     }
 }
 
@@ -224,6 +257,13 @@ pub fn get_flat_node_types(lang: Language) -> &'static [&'static str] {
             "continue_statement",
         ],
         Language::Bash => &["elif_clause", "else_clause"],
+        Language::Lua => &[
+            "elseif_statement",
+            "else_statement",
+            "break_statement",
+            "goto_statement",
+        ],
+        Language::Zig => &["else_clause", "break_expression", "continue_expression"],
     }
 }
 
@@ -245,7 +285,7 @@ pub fn get_class_node_types(lang: Language) -> &'static [&'static str] {
             "interface_declaration",
             "trait_declaration",
         ],
-        Language::Bash => &[],
+        Language::Bash | Language::Lua | Language::Zig => &[],
     }
 }
 
@@ -264,7 +304,7 @@ pub fn get_binary_expression_types(lang: Language) -> &'static [&'static str] {
         Language::C | Language::Cpp => &["binary_expression"],
         Language::Ruby => &["binary"],
         Language::Php => &["binary_expression"],
-        Language::Bash => &["binary_expression"],
+        Language::Bash | Language::Lua | Language::Zig => &["binary_expression"],
     }
 }
 
@@ -317,6 +357,8 @@ mod tests {
             Language::Ruby,
             Language::Php,
             Language::Bash,
+            Language::Lua,
+            Language::Zig,
         ];
         for lang in all_languages {
             let types = get_nesting_node_types(lang);
@@ -342,6 +384,8 @@ mod tests {
             Language::Ruby,
             Language::Php,
             Language::Bash,
+            Language::Lua,
+            Language::Zig,
         ];
         for lang in all_languages {
             let types = get_flat_node_types(lang);
@@ -404,6 +448,11 @@ mod tests {
         // Bash should have elif_clause
         let bash_types = get_flat_node_types(Language::Bash);
         assert!(bash_types.contains(&"elif_clause"));
+
+        // Lua spells its else branches as statements
+        let lua_types = get_flat_node_types(Language::Lua);
+        assert!(lua_types.contains(&"elseif_statement"));
+        assert!(lua_types.contains(&"else_statement"));
     }
 }
 
@@ -0,0 +1,189 @@
+//! Analyzer composition via JMESPath.
+//!
+//! `omen query '<expr>'` evaluates a JMESPath expression over an object keyed
+//! by analyzer name, e.g. `complexity.files[?avg_cyclomatic > \`10\`].path`.
+//! Only the analyzers named in the expression are run, each at most once.
+
+use serde_json::{Map, Value};
+
+use crate::core::{run_by_name, AnalysisContext, AnalyzerResult, Error, Result};
+
+/// Compile a JMESPath expression, reporting syntax errors as invalid arguments.
+pub fn compile(expr: &str) -> Result<jmespath::Expression<'static>> {
+    jmespath::compile(expr).map_err(|e| Error::InvalidArgument(format!("invalid query: {e}")))
+}
+
+/// Analyzers referenced by identifiers in `expr`, in
+/// [`AnalyzerResult::accepted_names`] order.
+///
+/// Identifiers inside raw string (`'...'`) and JSON literal (`` `...` ``)
+/// tokens are ignored; quoted identifiers (`"complexity"`) count. An alias
+/// such as `clones` is returned as written, since the expression addresses
+/// its result by that key. When no analyzer is named (e.g. `keys(@)`), every
+/// analyzer is needed.
+pub fn referenced_analyzers(expr: &str) -> Vec<&'static str> {
+    let mut identifiers = Vec::new();
+    let mut chars = expr.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\'' | '`' => {
+                let mut escaped = false;
+                for (_, next) in chars.by_ref() {
+                    if !escaped && next == c {
+                        break;
+                    }
+                    escaped = !escaped && next == '\\';
+                }
+            }
+            '"' => {
+                let mut ident = String::new();
+                let mut escaped = false;
+                for (_, next) in chars.by_ref() {
+                    if !escaped && next == '"' {
+                        break;
+                    }
+                    escaped = !escaped && next == '\\';
+                    ident.push(next);
+                }
+                identifiers.push(ident);
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, next)) = chars.peek() {
+                    if !(next.is_ascii_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    end = i + next.len_utf8();
+                    chars.next();
+                }
+                identifiers.push(expr[start..end].to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let referenced: Vec<&'static str> = AnalyzerResult::accepted_names()
+        .filter(|name| identifiers.iter().any(|ident| ident == name))
+        .collect();
+    if referenced.is_empty() {
+        AnalyzerResult::NAMES.to_vec()
+    } else {
+        referenced
+    }
+}
+
+/// Run one analyzer by query name and serialize its result.
+pub fn run_analyzer(name: &str, ctx: &AnalysisContext<'_>) -> Result<Value> {
+    run_by_name(name, ctx)?.to_value()
+}
+
+/// Evaluate a compiled expression against combined analyzer results.
+pub fn search(expr: &jmespath::Expression<'_>, data: &Value) -> Result<Value> {
+    let result = expr
+        .search(data.clone())
+        .map_err(|e| Error::analysis(format!("query failed: {e}")))?;
+    Ok(serde_json::to_value(&*result)?)
+}
+
+/// Run the analyzers `expr` references and evaluate it over their results.
+///
+/// An analyzer that fails (e.g. a git analyzer outside a repository) is
+/// surfaced as `{"error": "..."}` under its name rather than aborting the
+/// query, matching `omen all`.
+pub fn run(expr: &str, ctx: &AnalysisContext<'_>) -> Result<Value> {
+    let compiled = compile(expr)?;
+    let mut data = Map::new();
+    for name in referenced_analyzers(expr) {
+        let value = run_analyzer(name, ctx)
+            .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() }));
+        data.insert(name.to_string(), value);
+    }
+    search(&compiled, &Value::Object(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_referenced_analyzers_from_identifiers() {
+        assert_eq!(
+            referenced_analyzers("complexity.files[?avg_cyclomatic > `10`].path"),
+            vec!["complexity"]
+        );
+        assert_eq!(
+            referenced_analyzers("{s: score.overall_score, h: hotspot.files[0]}"),
+            vec!["hotspot", "score"]
+        );
+        assert_eq!(referenced_analyzers("\"churn\".files"), vec!["churn"]);
+        assert_eq!(
+            referenced_analyzers("duplicates.clones"),
+            vec!["duplicates", "clones"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_analyzers_ignores_literals() {
+        assert_eq!(
+            referenced_analyzers("complexity.files[?path == 'score.rs' || `\"churn\"` == x]"),
+            vec!["complexity"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_analyzers_defaults_to_all() {
+        assert_eq!(
+            referenced_analyzers("keys(@)"),
+            AnalyzerResult::NAMES.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_syntax() {
+        let err = compile("complexity.files[?").unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn test_search_filters_and_projects() {
+        let data = json!({
+            "complexity": {
+                "files": [
+                    {"path": "a.rs", "avg_cyclomatic": 12.5},
+                    {"path": "b.rs", "avg_cyclomatic": 3.0}
+                ]
+            }
+        });
+        let expr = compile("complexity.files[?avg_cyclomatic > `10`].path").unwrap();
+        assert_eq!(search(&expr, &data).unwrap(), json!(["a.rs"]));
+    }
+
+    #[test]
+    fn test_unknown_analyzer_is_invalid_argument() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+        assert!(matches!(
+            run_analyzer("nope", &ctx),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_evaluates_over_complexity() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.go"),
+            "package main\n\nfunc simple() int {\n\treturn 1\n}\n",
+        )
+        .unwrap();
+        let config = crate::config::Config::default();
+        let file_set = crate::core::FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&file_set, &config, Some(dir.path()));
+
+        let result = run("complexity.summary.total_functions", &ctx).unwrap();
+        assert_eq!(result, json!(1));
+    }
+}
@@ -0,0 +1,495 @@
+//! Pull request comments comparing two report data directories.
+//!
+//! `omen report comment --before <dir> --after <dir>` reads the JSON files
+//! written by `omen report generate` for the base and head of a pull request
+//! and renders a compact Markdown summary that a CI bot can post as-is: the
+//! score delta, critical findings introduced and resolved, and the files whose
+//! TDG score dropped the most.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::render::{load_json, rel_path_ref, smell_type_label};
+use super::types::{HotspotsData, Metadata, SATDData, ScoreRaw, SmellsData, TdgData};
+
+/// GitHub's maximum issue and pull request comment length.
+pub const DEFAULT_MAX_CHARS: usize = 65_536;
+
+/// Number of regressions listed in the comment.
+const TOP_REGRESSIONS: usize = 3;
+
+/// Smallest TDG drop reported as a regression.
+const MIN_REGRESSION: f32 = 0.5;
+
+/// A critical finding from one report run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Finding source, e.g. "SATD" or "Hotspot".
+    pub kind: &'static str,
+    /// Repository-relative file (or first component for smells).
+    pub file: String,
+    /// Line number, when the finding has one.
+    pub line: Option<i32>,
+    pub message: String,
+}
+
+impl Finding {
+    /// Identity across runs. Line numbers are excluded so findings that only
+    /// moved are not reported as new.
+    fn key(&self) -> (&'static str, &str, &str) {
+        (self.kind, &self.file, &self.message)
+    }
+
+    fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{}", self.file, line),
+            None => self.file.clone(),
+        }
+    }
+}
+
+/// A file whose TDG score dropped between runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub file: String,
+    pub before: f32,
+    pub after: f32,
+}
+
+/// Differences between a base and head report run.
+#[derive(Debug, Clone, Default)]
+pub struct Comparison {
+    pub score_before: Option<f64>,
+    pub score_after: Option<f64>,
+    /// Critical findings present after but not before.
+    pub new_critical: Vec<Finding>,
+    /// Critical findings present before but not after.
+    pub resolved: Vec<Finding>,
+    /// Largest TDG drops, worst first.
+    pub regressions: Vec<Regression>,
+}
+
+impl Comparison {
+    /// Compare the data directories of two `omen report generate` runs.
+    /// Missing or unreadable data files are treated as empty.
+    pub fn load(before: &Path, after: &Path) -> Self {
+        let before_roots = roots(before);
+        let after_roots = roots(after);
+        let before_findings = critical_findings(before, &before_roots);
+        let after_findings = critical_findings(after, &after_roots);
+
+        Self {
+            score_before: overall_score(before),
+            score_after: overall_score(after),
+            new_critical: difference(&after_findings, &before_findings),
+            resolved: difference(&before_findings, &after_findings),
+            regressions: tdg_regressions(before, &before_roots, after, &after_roots),
+        }
+    }
+
+    /// Render the comparison as GitHub-flavored Markdown of at most
+    /// `max_chars` characters.
+    pub fn to_github_markdown(&self, max_chars: usize) -> String {
+        let mut out = String::from("## Omen health report\n\n");
+        let _ = writeln!(out, "{}\n", self.score_line());
+
+        if self.new_critical.is_empty() {
+            out.push_str("No new critical findings.\n\n");
+        } else {
+            let _ = writeln!(
+                out,
+                "### :rotating_light: New critical findings ({})\n",
+                self.new_critical.len()
+            );
+            push_findings(&mut out, &self.new_critical);
+        }
+
+        if !self.resolved.is_empty() {
+            let _ = writeln!(
+                out,
+                "### :white_check_mark: Resolved findings ({})\n",
+                self.resolved.len()
+            );
+            push_findings(&mut out, &self.resolved);
+        }
+
+        if !self.regressions.is_empty() {
+            out.push_str(
+                "### Top regressions\n\n| File | TDG before | TDG after |\n|---|---|---|\n",
+            );
+            for r in &self.regressions {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | {:.1} | {:.1} (-{:.1}) |",
+                    r.file,
+                    r.before,
+                    r.after,
+                    r.before - r.after
+                );
+            }
+            out.push('\n');
+        }
+
+        truncate_markdown(out.trim_end().to_string() + "\n", max_chars)
+    }
+
+    fn score_line(&self) -> String {
+        match (self.score_before, self.score_after) {
+            (Some(before), Some(after)) => {
+                let delta = after.round() - before.round();
+                let icon = if delta > 0.0 {
+                    ":arrow_up:"
+                } else if delta < 0.0 {
+                    ":arrow_down:"
+                } else {
+                    ":heavy_minus_sign:"
+                };
+                format!(
+                    "**Score:** {:.0} → {:.0} ({:+.0}) {}",
+                    before, after, delta, icon
+                )
+            }
+            (None, Some(after)) => format!("**Score:** {:.0}", after),
+            _ => "**Score:** unavailable".to_string(),
+        }
+    }
+}
+
+fn push_findings(out: &mut String, findings: &[Finding]) {
+    for f in findings {
+        let _ = writeln!(
+            out,
+            "- **{}** `{}`: {}",
+            f.kind,
+            f.location(),
+            single_line(&f.message)
+        );
+    }
+    out.push('\n');
+}
+
+/// Cut `markdown` at a line boundary so the result, including a truncation
+/// note, fits in `max_chars` characters.
+fn truncate_markdown(markdown: String, max_chars: usize) -> String {
+    if markdown.chars().count() <= max_chars {
+        return markdown;
+    }
+    const NOTE: &str = "\n_Comment truncated; see the full report for details._\n";
+    let budget = max_chars.saturating_sub(NOTE.chars().count());
+
+    let mut out = String::new();
+    let mut used = 0;
+    for line in markdown.split_inclusive('\n') {
+        let len = line.chars().count();
+        if used + len > budget {
+            break;
+        }
+        out.push_str(line);
+        used += len;
+    }
+    if used + NOTE.chars().count() <= max_chars {
+        out.push_str(NOTE);
+    }
+    out
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn roots(dir: &Path) -> Vec<String> {
+    load_json::<Metadata>(&dir.join("metadata.json"))
+        .map(|m| m.paths)
+        .unwrap_or_default()
+}
+
+fn relative(path: &str, roots: &[String]) -> String {
+    let rel = rel_path_ref(path, roots);
+    rel.strip_prefix("./").unwrap_or(&rel).to_string()
+}
+
+fn overall_score(dir: &Path) -> Option<f64> {
+    load_json::<ScoreRaw>(&dir.join("score.json"))
+        .ok()
+        .map(|s| s.overall_score)
+}
+
+/// Critical findings from SATD, hotspots, smells, and TDG critical defects.
+fn critical_findings(dir: &Path, roots: &[String]) -> Vec<Finding> {
+    let is_critical = |severity: &str| severity.eq_ignore_ascii_case("critical");
+    let mut findings = Vec::new();
+
+    if let Ok(satd) = load_json::<SATDData>(&dir.join("satd.json")) {
+        findings.extend(
+            satd.items
+                .into_iter()
+                .filter(|i| is_critical(&i.severity))
+                .map(|i| Finding {
+                    kind: "SATD",
+                    file: relative(&i.file, roots),
+                    line: Some(i.line),
+                    message: i.content.trim().to_string(),
+                }),
+        );
+    }
+    if let Ok(hotspots) = load_json::<HotspotsData>(&dir.join("hotspots.json")) {
+        findings.extend(
+            hotspots
+                .files
+                .into_iter()
+                .filter(|h| is_critical(&h.severity))
+                .map(|h| Finding {
+                    kind: "Hotspot",
+                    file: relative(&h.path, roots),
+                    line: None,
+                    message: "Critical churn and complexity hotspot".to_string(),
+                }),
+        );
+    }
+    if let Ok(smells) = load_json::<SmellsData>(&dir.join("smells.json")) {
+        findings.extend(
+            smells
+                .smells
+                .into_iter()
+                .filter(|s| is_critical(&s.severity))
+                .map(|s| Finding {
+                    kind: "Smell",
+                    file: s
+                        .components
+                        .first()
+                        .map(|c| relative(c, roots))
+                        .unwrap_or_default(),
+                    line: None,
+                    message: if s.description.is_empty() {
+                        smell_type_label(&s.smell_type)
+                    } else {
+                        s.description
+                    },
+                }),
+        );
+    }
+    if let Ok(tdg) = load_json::<TdgData>(&dir.join("tdg.json")) {
+        findings.extend(
+            tdg.files
+                .into_iter()
+                .filter(|f| f.has_critical_defects)
+                .map(|f| Finding {
+                    kind: "Critical defect",
+                    file: relative(&f.file_path, roots),
+                    line: None,
+                    message: "Critical defect pattern detected".to_string(),
+                }),
+        );
+    }
+
+    findings
+}
+
+/// Findings in `a` whose identity is not in `b`, in `a`'s order.
+fn difference(a: &[Finding], b: &[Finding]) -> Vec<Finding> {
+    let keys: HashSet<_> = b.iter().map(Finding::key).collect();
+    a.iter()
+        .filter(|f| !keys.contains(&f.key()))
+        .cloned()
+        .collect()
+}
+
+fn tdg_regressions(
+    before: &Path,
+    before_roots: &[String],
+    after: &Path,
+    after_roots: &[String],
+) -> Vec<Regression> {
+    let (Ok(before), Ok(after)) = (
+        load_json::<TdgData>(&before.join("tdg.json")),
+        load_json::<TdgData>(&after.join("tdg.json")),
+    ) else {
+        return Vec::new();
+    };
+
+    let before_scores: HashMap<String, f32> = before
+        .files
+        .iter()
+        .map(|f| (relative(&f.file_path, before_roots), f.total))
+        .collect();
+
+    let mut regressions: Vec<Regression> = after
+        .files
+        .iter()
+        .filter_map(|f| {
+            let file = relative(&f.file_path, after_roots);
+            let before = *before_scores.get(&file)?;
+            (before - f.total >= MIN_REGRESSION).then_some(Regression {
+                file,
+                before,
+                after: f.total,
+            })
+        })
+        .collect();
+
+    regressions.sort_by(|a, b| {
+        (b.before - b.after)
+            .total_cmp(&(a.before - a.after))
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    regressions.truncate(TOP_REGRESSIONS);
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(dir: &Path, name: &str, value: serde_json::Value) {
+        std::fs::write(dir.join(name), value.to_string()).unwrap();
+    }
+
+    fn tdg(files: &[(&str, f32, bool)]) -> serde_json::Value {
+        json!({
+            "files": files
+                .iter()
+                .map(|(path, total, critical)| json!({
+                    "file_path": path,
+                    "total": total,
+                    "grade": "B",
+                    "has_critical_defects": critical
+                }))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    fn fixture() -> (tempfile::TempDir, tempfile::TempDir) {
+        let before = tempfile::tempdir().unwrap();
+        let after = tempfile::tempdir().unwrap();
+
+        write(before.path(), "score.json", json!({"overall_score": 82.0}));
+        write(after.path(), "score.json", json!({"overall_score": 78.4}));
+        write(
+            before.path(),
+            "satd.json",
+            json!({"items": [
+                {"file": "src/db.rs", "line": 10, "severity": "critical", "category": "security", "content": "SECURITY: unescaped input"},
+                {"file": "src/api.rs", "line": 3, "severity": "low", "category": "design", "content": "TODO: tidy"}
+            ]}),
+        );
+        write(
+            after.path(),
+            "satd.json",
+            json!({"items": [
+                {"file": "src/auth.rs", "line": 7, "severity": "critical", "category": "security", "content": "FIXME: token never expires"}
+            ]}),
+        );
+        write(
+            before.path(),
+            "tdg.json",
+            tdg(&[
+                ("src/a.rs", 90.0, false),
+                ("src/b.rs", 80.0, false),
+                ("src/c.rs", 70.0, false),
+                ("src/d.rs", 95.0, false),
+                ("src/e.rs", 60.0, false),
+            ]),
+        );
+        write(
+            after.path(),
+            "tdg.json",
+            tdg(&[
+                ("src/a.rs", 70.0, true),
+                ("src/b.rs", 75.0, false),
+                ("src/c.rs", 69.0, false),
+                ("src/d.rs", 95.0, false),
+                ("src/e.rs", 59.8, false),
+            ]),
+        );
+
+        (before, after)
+    }
+
+    #[test]
+    fn test_compare_findings_and_regressions() {
+        let (before, after) = fixture();
+        let comparison = Comparison::load(before.path(), after.path());
+
+        assert_eq!(comparison.score_before, Some(82.0));
+        assert_eq!(comparison.score_after, Some(78.4));
+
+        let new: Vec<(&str, &str)> = comparison
+            .new_critical
+            .iter()
+            .map(|f| (f.kind, f.file.as_str()))
+            .collect();
+        assert_eq!(
+            new,
+            vec![("SATD", "src/auth.rs"), ("Critical defect", "src/a.rs")]
+        );
+        assert_eq!(comparison.resolved.len(), 1);
+        assert_eq!(comparison.resolved[0].file, "src/db.rs");
+
+        let regressed: Vec<&str> = comparison
+            .regressions
+            .iter()
+            .map(|r| r.file.as_str())
+            .collect();
+        assert_eq!(regressed, vec!["src/a.rs", "src/b.rs", "src/c.rs"]);
+    }
+
+    #[test]
+    fn test_moved_finding_is_not_new() {
+        let before = vec![Finding {
+            kind: "SATD",
+            file: "src/a.rs".to_string(),
+            line: Some(1),
+            message: "HACK".to_string(),
+        }];
+        let mut after = before.clone();
+        after[0].line = Some(40);
+        assert!(difference(&after, &before).is_empty());
+    }
+
+    #[test]
+    fn test_github_markdown_sections() {
+        let (before, after) = fixture();
+        let markdown =
+            Comparison::load(before.path(), after.path()).to_github_markdown(DEFAULT_MAX_CHARS);
+
+        assert!(markdown.contains("**Score:** 82 → 78 (-4) :arrow_down:"));
+        assert!(markdown.contains("New critical findings (2)"));
+        assert!(markdown.contains("`src/auth.rs:7`: FIXME: token never expires"));
+        assert!(markdown.contains("Resolved findings (1)"));
+        assert!(markdown.contains("| `src/a.rs` | 90.0 | 70.0 (-20.0) |"));
+        assert!(!markdown.contains("src/e.rs"));
+    }
+
+    #[test]
+    fn test_missing_data_renders_placeholder() {
+        let before = tempfile::tempdir().unwrap();
+        let after = tempfile::tempdir().unwrap();
+        let markdown =
+            Comparison::load(before.path(), after.path()).to_github_markdown(DEFAULT_MAX_CHARS);
+        assert!(markdown.contains("**Score:** unavailable"));
+        assert!(markdown.contains("No new critical findings."));
+    }
+
+    #[test]
+    fn test_markdown_is_capped() {
+        let comparison = Comparison {
+            score_before: Some(80.0),
+            score_after: Some(80.0),
+            new_critical: (0..200)
+                .map(|i| Finding {
+                    kind: "SATD",
+                    file: format!("src/file_{i}.rs"),
+                    line: Some(i),
+                    message: "FIXME: something risky".to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let markdown = comparison.to_github_markdown(1000);
+        assert!(markdown.chars().count() <= 1000);
+        assert!(markdown.ends_with("see the full report for details._\n"));
+        assert!(markdown.contains("src/file_0.rs"));
+    }
+}
@@ -0,0 +1,189 @@
+//! Per-file index for the report's drill-down view.
+//!
+//! Each analyzer reports on files in its own JSON; this merges them into
+//! one entry per file so the report can show a file's complex functions,
+//! SATD, clones, smells and churn side by side.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::render::{load_json, rel_path_ref};
+use super::types::*;
+
+/// Functions kept per file, most cognitively complex first.
+const MAX_FUNCTIONS_PER_FILE: usize = 25;
+
+/// Merge the findings in `data` and the data directory into a per-file
+/// index keyed by path relative to the analyzed root.
+pub(super) fn build_file_index(
+    data_dir: &Path,
+    data: &RenderData,
+) -> BTreeMap<String, FileFindings> {
+    let roots = &data.metadata.paths;
+    let mut index: BTreeMap<String, FileFindings> = BTreeMap::new();
+
+    if let Ok(complexity) = load_json::<ComplexityRaw>(&data_dir.join("complexity.json")) {
+        for file in complexity.files {
+            let mut functions: Vec<FileFunction> = file
+                .functions
+                .into_iter()
+                .map(|f| FileFunction {
+                    name: f.name,
+                    line: f.start_line,
+                    cyclomatic: f.metrics.cyclomatic,
+                    cognitive: f.metrics.cognitive,
+                })
+                .collect();
+            if functions.is_empty() {
+                continue;
+            }
+            functions.sort_by(|a, b| {
+                (b.cognitive, b.cyclomatic)
+                    .cmp(&(a.cognitive, a.cyclomatic))
+                    .then(a.line.cmp(&b.line))
+            });
+            functions.truncate(MAX_FUNCTIONS_PER_FILE);
+            entry(&mut index, &file.path, roots).functions = functions;
+        }
+    }
+
+    if let Some(satd) = &data.satd {
+        for item in &satd.items {
+            entry(&mut index, &item.file, roots).satd.push(FileSatd {
+                line: item.line,
+                severity: item.severity.clone(),
+                category: item.category.clone(),
+                content: item.content.clone(),
+            });
+        }
+    }
+
+    if let Ok(duplicates) = load_json::<DuplicateClones>(&data_dir.join("duplicates.json")) {
+        for clone in duplicates.clones {
+            let a = rel_path_ref(&clone.file_a, roots);
+            let b = rel_path_ref(&clone.file_b, roots);
+            index.entry(a.clone()).or_default().clones.push(FileClone {
+                start_line: clone.start_line_a,
+                end_line: clone.end_line_a,
+                other_file: b.clone(),
+                other_start_line: clone.start_line_b,
+                other_end_line: clone.end_line_b,
+                similarity: clone.similarity,
+            });
+            index.entry(b).or_default().clones.push(FileClone {
+                start_line: clone.start_line_b,
+                end_line: clone.end_line_b,
+                other_file: a,
+                other_start_line: clone.start_line_a,
+                other_end_line: clone.end_line_a,
+                similarity: clone.similarity,
+            });
+        }
+    }
+
+    if let Some(smells) = &data.smells {
+        for smell in &smells.smells {
+            for component in &smell.components {
+                entry(&mut index, component, roots).smells.push(FileSmell {
+                    smell_type: smell.smell_type.clone(),
+                    severity: smell.severity.clone(),
+                    description: smell.description.clone(),
+                });
+            }
+        }
+    }
+
+    if let Some(churn) = &data.churn {
+        for file in &churn.files {
+            entry(&mut index, &file.file, roots).churn = Some(FileChurn {
+                commits: file.commits,
+                authors: file.authors.len(),
+                additions: file.additions,
+                deletions: file.deletions,
+                churn_score: file.churn_score,
+            });
+        }
+    }
+
+    index
+}
+
+fn entry<'a>(
+    index: &'a mut BTreeMap<String, FileFindings>,
+    path: &str,
+    roots: &[String],
+) -> &'a mut FileFindings {
+    index.entry(rel_path_ref(path, roots)).or_default()
+}
+
+/// Serialize `index` for a `<script>` block; `<` is escaped so no string
+/// in it can close the tag.
+pub(super) fn file_index_json(index: &BTreeMap<String, FileFindings>) -> String {
+    serde_json::to_string(index)
+        .unwrap_or_else(|_| "{}".to_string())
+        .replace('<', "\\u003c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_build_file_index_merges_analyzers() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("complexity.json"),
+            r#"{"summary": {"avg_cyclomatic": 3.0, "avg_cognitive": 4.0},
+                "files": [{"path": "/repo/src/lib.rs", "functions": [
+                    {"name": "simple", "start_line": 1,
+                     "metrics": {"cyclomatic": 1, "cognitive": 0}},
+                    {"name": "parse", "start_line": 10,
+                     "metrics": {"cyclomatic": 9, "cognitive": 14}}
+                ]}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("duplicates.json"),
+            r#"{"clones": [{"file_a": "/repo/src/lib.rs", "file_b": "/repo/src/util.rs",
+                "start_line_a": 10, "end_line_a": 30, "start_line_b": 5, "end_line_b": 25,
+                "similarity": 0.9}]}"#,
+        )
+        .unwrap();
+
+        let mut data = RenderData::default();
+        data.metadata.paths = vec!["/repo".to_string()];
+        data.satd = Some(
+            serde_json::from_str(
+                r#"{"items": [{"file": "/repo/src/lib.rs", "line": 12, "severity": "high",
+                    "category": "design", "content": "// TODO: </script>"}]}"#,
+            )
+            .unwrap(),
+        );
+        data.churn = Some(
+            serde_json::from_str(
+                r#"{"files": [{"file": "src/lib.rs", "commits": 7,
+                    "authors": ["a", "b"], "churn_score": 0.4}]}"#,
+            )
+            .unwrap(),
+        );
+
+        let index = build_file_index(dir.path(), &data);
+
+        assert_eq!(
+            index.keys().collect::<Vec<_>>(),
+            vec!["src/lib.rs", "src/util.rs"]
+        );
+        let lib = &index["src/lib.rs"];
+        assert_eq!(lib.functions[0].name, "parse", "most complex first");
+        assert_eq!(lib.satd[0].line, 12);
+        assert_eq!(lib.clones[0].other_file, "src/util.rs");
+        assert_eq!(lib.churn.as_ref().unwrap().authors, 2);
+        assert_eq!(index["src/util.rs"].clones[0].other_start_line, 10);
+
+        let json = file_index_json(&index);
+        assert!(!json.contains("</script>"));
+        let parsed: BTreeMap<String, FileFindings> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["src/lib.rs"].satd[0].content, "// TODO: </script>");
+    }
+}
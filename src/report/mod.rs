@@ -2,7 +2,11 @@
 //!
 //! This module generates interactive HTML reports matching the Go version exactly.
 
+pub mod comment;
+mod files;
 mod render;
+pub mod schema;
+pub mod serve;
 mod types;
 
 pub use render::Renderer;
@@ -12,7 +12,9 @@ use pulldown_cmark::{html, Options, Parser};
 
 use crate::core::Language;
 use crate::core::Result;
+use crate::report::files::{build_file_index, file_index_json};
 use crate::report::types::*;
+use crate::score::BudgetStatus;
 
 /// The embedded HTML template (matches Go version exactly).
 const TEMPLATE_HTML: &str = include_str!("template.html");
@@ -60,7 +62,7 @@ impl Renderer {
     }
 
     /// Render generates HTML from the data directory into a byte buffer.
-    fn render_to_bytes(&self, data_dir: &Path) -> Result<Vec<u8>> {
+    pub(crate) fn render_to_bytes(&self, data_dir: &Path) -> Result<Vec<u8>> {
         let data = self.load_data(data_dir)?;
         let roots = &data.metadata.paths;
 
@@ -83,6 +85,8 @@ impl Renderer {
             .temporal
             .as_ref()
             .map(|t| build_temporal_json(&t.couplings, roots));
+        let file_index = build_file_index(data_dir, &data);
+        let file_index_json = (!file_index.is_empty()).then(|| file_index_json(&file_index));
 
         let tmpl = self.env.get_template("report")?;
         let rendered = tmpl.render(context! {
@@ -109,12 +113,17 @@ impl Renderer {
             OwnershipInsight => data.ownership_insight,
             Temporal => data.temporal,
             TemporalInsight => data.temporal_insight,
+            Hidden => data.hidden,
             Smells => data.smells,
             SmellsInsight => data.smells_insight,
             Graph => data.graph,
             GraphInsight => data.graph_insight,
             Tdg => data.tdg,
             TdgInsight => data.tdg_insight,
+            Risk => data.risk,
+            Pipeline => data.pipeline,
+            Docker => data.docker,
+            Budgets => data.budgets,
             ComponentTrends => data.component_trends,
             SATDStats => data.satd_stats,
             HotspotsTableJson => hotspots_json,
@@ -124,6 +133,7 @@ impl Renderer {
             GraphTableJson => graph_json,
             TdgTableJson => tdg_json,
             TemporalTableJson => temporal_json,
+            FileIndexJson => file_index_json,
         })?;
 
         let minified = minify_html_output(rendered.as_bytes());
@@ -287,6 +297,11 @@ impl Renderer {
             data.temporal = Some(temporal);
         }
 
+        // Load hidden dependencies (coupled files with no import path)
+        if let Ok(hidden) = load_json::<HiddenData>(&data_dir.join("hidden.json")) {
+            data.hidden = Some(hidden);
+        }
+
         // Load architectural smells
         if let Ok(smells) = load_json::<SmellsData>(&data_dir.join("smells.json")) {
             data.smells = Some(smells);
@@ -297,6 +312,26 @@ impl Renderer {
             data.graph = Some(graph);
         }
 
+        // Load risk matrix (already ranked urgent first)
+        if let Ok(risk) = load_json::<RiskData>(&data_dir.join("risk.json")) {
+            data.risk = Some(risk);
+        }
+
+        // Load CI/build pipeline audit
+        if let Ok(pipeline) = load_json::<PipelineData>(&data_dir.join("pipeline.json")) {
+            data.pipeline = Some(pipeline);
+        }
+
+        // Load Dockerfile hygiene findings
+        if let Ok(docker) = load_json::<DockerData>(&data_dir.join("docker.json")) {
+            data.docker = Some(docker);
+        }
+
+        // Load budget progress
+        if let Ok(budgets) = load_json::<Vec<BudgetStatus>>(&data_dir.join("budgets.json")) {
+            data.budgets = budgets;
+        }
+
         // Load TDG (technical debt gradient) and sort by score ascending (worst first)
         if let Ok(mut tdg) = load_json::<TdgData>(&data_dir.join("tdg.json")) {
             tdg.files.sort_by(|a, b| {
@@ -374,7 +409,7 @@ impl Renderer {
     }
 }
 
-fn load_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+pub(super) fn load_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
     let content = fs::read_to_string(path)?;
     let value = serde_json::from_str(&content)?;
     Ok(value)
@@ -402,7 +437,7 @@ fn rel_path(path: &str, roots: Option<Vec<String>>) -> String {
 }
 
 /// Same as `rel_path` but takes a slice, avoiding per-call allocation.
-fn rel_path_ref(path: &str, roots: &[String]) -> String {
+pub(super) fn rel_path_ref(path: &str, roots: &[String]) -> String {
     if roots.is_empty() {
         return path.to_string();
     }
@@ -454,7 +489,7 @@ fn hotspot_badge(score: f64) -> &'static str {
 /// Get badge class based on priority string.
 fn priority_badge(priority: &str) -> &'static str {
     match priority.to_uppercase().as_str() {
-        "CRITICAL" => "critical",
+        "CRITICAL" | "URGENT" => "critical",
         "HIGH" => "high",
         "MEDIUM" => "medium",
         _ => "low",
@@ -700,13 +735,15 @@ fn grade_class(grade: &str) -> &'static str {
 }
 
 /// Convert SmellType enum name to human-readable label.
-fn smell_type_label(s: &str) -> String {
+pub(super) fn smell_type_label(s: &str) -> String {
     match s {
         "CyclicDependency" => "Cyclic Dependency".to_string(),
         "UnstableDependency" => "Unstable Dependency".to_string(),
         "Hub" | "HubLikeDependency" => "Hub Dependency".to_string(),
         "CentralConnector" | "GodComponent" | "GodClass" => "Central Connector".to_string(),
         "FeatureEnvy" => "Feature Envy".to_string(),
+        "ScatteredModule" => "Scattered Module".to_string(),
+        "ConstructorOverInjection" => "Constructor Over-Injection".to_string(),
         _ => s.to_string(),
     }
 }
@@ -753,7 +790,7 @@ fn build_hotspots_json(files: &[HotspotItem], roots: &[String]) -> String {
             let score = item.hotspot_score;
             let badge = hotspot_badge(score);
             vec![
-                format!("<code>{}</code>", html_escape(&truncate_path(&path, 60))),
+                file_link(&path, 60),
                 lang,
                 format!("<span class=\"badge {badge}\">{:.3}</span>", score),
                 item.commits.to_string(),
@@ -777,9 +814,13 @@ fn build_satd_json(items: &[SATDItem], roots: &[String]) -> String {
                     html_escape(&item.severity)
                 ),
                 html_escape(&item.category),
-                format!("<code>{}</code>", html_escape(&truncate_path(&path, 50))),
+                file_link(&path, 50),
                 lang,
                 item.line.to_string(),
+                item.function
+                    .as_deref()
+                    .map_or_else(String::new, |f| format!("<code>{}</code>", html_escape(f))),
+                satd_origin(item),
                 html_escape(&item.content),
             ]
         })
@@ -787,6 +828,17 @@ fn build_satd_json(items: &[SATDItem], roots: &[String]) -> String {
     rows_to_json(&rows)
 }
 
+/// "author, N days ago" for blamed SATD items, empty otherwise.
+fn satd_origin(item: &SATDItem) -> String {
+    let Some(origin) = &item.introduced else {
+        return String::new();
+    };
+    match item.age_days {
+        Some(days) => format!("{}, {days}d ago", html_escape(&origin.author)),
+        None => html_escape(&origin.author),
+    }
+}
+
 fn build_churn_json(files: &[ChurnFile]) -> String {
     let rows: Vec<Vec<String>> = files
         .iter()
@@ -794,7 +846,7 @@ fn build_churn_json(files: &[ChurnFile]) -> String {
             let lang = lang_from_path(&item.file);
             let badge = churn_badge(item.churn_score);
             vec![
-                format!("<code>{}</code>", html_escape(&item.file)),
+                file_link(&item.file, usize::MAX),
                 lang,
                 item.commits.to_string(),
                 item.authors.len().to_string(),
@@ -840,7 +892,7 @@ fn build_graph_json(nodes: &[GraphNode], roots: &[String]) -> String {
             let lang = lang_from_path(&node.path);
             let inst_class = instability_class(node.instability);
             vec![
-                format!("<code>{}</code>", html_escape(&truncate_path(&path, 50))),
+                file_link(&path, 50),
                 lang,
                 format!("{:.4}", node.pagerank),
                 format!("{:.4}", node.betweenness),
@@ -865,7 +917,7 @@ fn build_tdg_json(files: &[TdgFile], roots: &[String]) -> String {
             let score_class_val = score_class(item.total.round() as i32);
             let grade_class_val = grade_class(&item.grade);
             vec![
-                format!("<code>{}</code>", html_escape(&truncate_path(&path, 50))),
+                file_link(&path, 50),
                 lang,
                 format!("<span class=\"{score_class_val}\">{:.1}</span>", item.total),
                 format!(
@@ -903,6 +955,15 @@ fn build_temporal_json(couplings: &[TemporalCoupling], roots: &[String]) -> Stri
     rows_to_json(&rows)
 }
 
+/// A path cell that opens the file's drill-down view.
+fn file_link(path: &str, max_len: usize) -> String {
+    format!(
+        "<a class=\"file-link\" href=\"#file-detail\" data-file=\"{}\"><code>{}</code></a>",
+        html_escape(path),
+        html_escape(&truncate_path(path, max_len))
+    )
+}
+
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -934,6 +995,193 @@ mod tests {
         assert_eq!(hotspot_badge(0.2), "low");
     }
 
+    #[test]
+    fn test_priority_badge() {
+        assert_eq!(priority_badge("urgent"), "critical");
+        assert_eq!(priority_badge("CRITICAL"), "critical");
+        assert_eq!(priority_badge("High"), "high");
+        assert_eq!(priority_badge("medium"), "medium");
+        assert_eq!(priority_badge("other"), "low");
+    }
+
+    #[test]
+    fn test_render_includes_risk_matrix() {
+        let dir = tempfile::tempdir().unwrap();
+        let risk = crate::analyzers::risk::Analysis::from_defect(
+            &crate::analyzers::defect::Analysis {
+                files: vec![crate::analyzers::defect::FileScore {
+                    file_path: "src/payments/charge.rs".to_string(),
+                    probability: 0.8,
+                    confidence: 1.0,
+                    risk_level: crate::analyzers::defect::RiskLevel::High,
+                    contributing_factors: HashMap::new(),
+                    recommendations: Vec::new(),
+                }],
+                summary: Default::default(),
+                weights: Default::default(),
+            },
+            &HashMap::from([(
+                "src/payments/**".to_string(),
+                crate::config::Criticality::Critical,
+            )]),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("risk.json"),
+            serde_json::to_string(&risk).unwrap(),
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("Risk Matrix"));
+        assert!(html.contains("src/payments/charge.rs"));
+    }
+
+    #[test]
+    fn test_render_is_accessible_and_printable() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("Skip to main content"));
+        assert!(html.contains("Report sections"));
+        assert!(html.contains("@media print"));
+        assert!(html.contains("prefers-reduced-motion"));
+        assert!(!html.contains("<th>"), "every header cell has a scope");
+    }
+
+    #[test]
+    fn test_render_includes_pipeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let pipeline = crate::analyzers::pipeline::analyze_sources(vec![(
+            std::path::PathBuf::from(".github/workflows/ci.yml"),
+            "jobs:\n  test:\n    steps:\n      - uses: actions/checkout@main\n      - run: cargo test\n"
+                .to_string(),
+        )]);
+        fs::write(
+            dir.path().join("pipeline.json"),
+            serde_json::to_string(&pipeline).unwrap(),
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("CI &amp; Build Pipelines"));
+        assert!(html.contains("actions/checkout@main"));
+    }
+
+    #[test]
+    fn test_render_includes_docker() {
+        let dir = tempfile::tempdir().unwrap();
+        let docker = crate::analyzers::docker::analyze_sources(
+            vec![(
+                std::path::PathBuf::from("Dockerfile"),
+                "FROM node\nENV API_TOKEN=abc\n".to_string(),
+            )],
+            &crate::config::DockerConfig::default(),
+        );
+        fs::write(
+            dir.path().join("docker.json"),
+            serde_json::to_string(&docker).unwrap(),
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("Dockerfiles"));
+        assert!(html.contains("API_TOKEN"));
+    }
+
+    #[test]
+    fn test_render_includes_budgets() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("budgets.json"),
+            r#"[{"metric": "satd_items", "target": 150.0, "by": "2026-09-30",
+                "current": 190.0, "trend": -30.0, "projected": "2026-04-10",
+                "state": "on_track"}]"#,
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("budgets-table"));
+        assert!(html.contains("satd_items"));
+        assert!(html.contains("2026-04-10"));
+        assert!(html.contains("on track"));
+    }
+
+    #[test]
+    fn test_render_includes_file_details() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("satd.json"),
+            r#"{"items": [{"file": "src/lib.rs", "line": 3, "severity": "high",
+                "category": "design", "content": "// TODO: split"}]}"#,
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("File Details"));
+        assert!(html.contains(r#"data-file=\"src/lib.rs\""#));
+        assert!(html.contains(r#""src/lib.rs":{"satd""#));
+    }
+
+    #[test]
+    fn test_render_includes_hidden_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("hidden.json"),
+            r#"{"dependencies": [{"file_a": "src/client.py", "file_b": "src/schema.py",
+                "cochange_count": 4, "coupling_strength": 0.8,
+                "commits": [{"sha": "0123456789abcdef", "message": "Add order field"}]}],
+                "summary": {"coupled_pairs": 3, "hidden_dependencies": 1, "files_involved": 2}}"#,
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        Renderer::new()
+            .unwrap()
+            .render(dir.path(), &mut out)
+            .unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("Hidden Dependencies"));
+        assert!(html.contains("01234567"));
+        assert!(html.contains("Add order field"));
+    }
+
     #[test]
     fn test_truncate_path() {
         assert_eq!(truncate_path("short.rs", 20), "short.rs");
@@ -997,6 +1245,11 @@ mod tests {
         assert_eq!(smell_type_label("CyclicDependency"), "Cyclic Dependency");
         assert_eq!(smell_type_label("Hub"), "Hub Dependency");
         assert_eq!(smell_type_label("CentralConnector"), "Central Connector");
+        assert_eq!(smell_type_label("ScatteredModule"), "Scattered Module");
+        assert_eq!(
+            smell_type_label("ConstructorOverInjection"),
+            "Constructor Over-Injection"
+        );
         assert_eq!(smell_type_label("Unknown"), "Unknown");
     }
 
@@ -1112,12 +1365,14 @@ mod tests {
                 hotspot_score: 0.85,
                 commits: 42,
                 avg_cognitive: 12.3,
+                severity: "Critical".to_string(),
             },
             HotspotItem {
                 path: "/root/src/lib.rs".to_string(),
                 hotspot_score: 0.15,
                 commits: 5,
                 avg_cognitive: 2.0,
+                severity: "Low".to_string(),
             },
         ];
         let roots = vec!["/root".to_string()];
@@ -1132,6 +1387,26 @@ mod tests {
         assert_eq!(parsed[0][3], "42");
     }
 
+    #[test]
+    fn test_build_satd_json_shows_function_and_origin() {
+        let item: SATDItem = serde_json::from_value(serde_json::json!({
+            "file": "/root/src/lib.rs",
+            "line": 12,
+            "severity": "high",
+            "category": "design",
+            "text": "// TODO: split",
+            "function": "parse",
+            "introduced": {"commit": "abc", "author": "Alice", "date": "2026-01-01T00:00:00Z"},
+            "age_days": 40
+        }))
+        .unwrap();
+        let json = build_satd_json(&[item], &["/root".to_string()]);
+        let parsed: Vec<Vec<String>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0].len(), 8);
+        assert_eq!(parsed[0][5], "<code>parse</code>");
+        assert_eq!(parsed[0][6], "Alice, 40d ago");
+    }
+
     #[test]
     fn test_build_temporal_json_empty() {
         let json = build_temporal_json(&[], &[]);
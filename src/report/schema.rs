@@ -0,0 +1,412 @@
+//! JSON Schemas for report data files.
+//!
+//! `omen report validate` checks each file written by `omen report generate`
+//! against a JSON Schema embedded from `src/report/schemas/`, so consumers of
+//! the data directory have a contract for field names and types beyond "it
+//! parses". `--schema <dir>` replaces any of them with `<name>.schema.json`
+//! files from that directory.
+//!
+//! The validator covers the subset of JSON Schema (2020-12) the embedded
+//! schemas use: `type`, `enum`, `minimum`, `maximum`, `required`,
+//! `properties`, `additionalProperties`, `items`, `anyOf`, and local `$ref`s
+//! into `$defs`. Other keywords are ignored.
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::{Error, Result};
+
+/// Data files written by `omen report generate`, by name without `.json`.
+pub const DATA_FILES: &[&str] = &[
+    "metadata",
+    "complexity",
+    "satd",
+    "deadcode",
+    "churn",
+    "duplicates",
+    "defect",
+    "risk",
+    "changes",
+    "tdg",
+    "graph",
+    "hotspots",
+    "temporal",
+    "hidden",
+    "ownership",
+    "cohesion",
+    "repomap",
+    "smells",
+    "flags",
+    "pipeline",
+    "docker",
+    "score",
+    "trend",
+];
+
+const EMBEDDED: &[(&str, &str)] = &[
+    ("metadata", include_str!("schemas/metadata.schema.json")),
+    ("complexity", include_str!("schemas/complexity.schema.json")),
+    ("satd", include_str!("schemas/satd.schema.json")),
+    ("deadcode", include_str!("schemas/deadcode.schema.json")),
+    ("churn", include_str!("schemas/churn.schema.json")),
+    ("duplicates", include_str!("schemas/duplicates.schema.json")),
+    ("defect", include_str!("schemas/defect.schema.json")),
+    ("risk", include_str!("schemas/risk.schema.json")),
+    ("changes", include_str!("schemas/changes.schema.json")),
+    ("tdg", include_str!("schemas/tdg.schema.json")),
+    ("graph", include_str!("schemas/graph.schema.json")),
+    ("hotspots", include_str!("schemas/hotspots.schema.json")),
+    ("temporal", include_str!("schemas/temporal.schema.json")),
+    ("hidden", include_str!("schemas/hidden.schema.json")),
+    ("ownership", include_str!("schemas/ownership.schema.json")),
+    ("cohesion", include_str!("schemas/cohesion.schema.json")),
+    ("repomap", include_str!("schemas/repomap.schema.json")),
+    ("smells", include_str!("schemas/smells.schema.json")),
+    ("flags", include_str!("schemas/flags.schema.json")),
+    ("pipeline", include_str!("schemas/pipeline.schema.json")),
+    ("docker", include_str!("schemas/docker.schema.json")),
+    ("score", include_str!("schemas/score.schema.json")),
+    ("trend", include_str!("schemas/trend.schema.json")),
+];
+
+/// The embedded schema for data file `name`.
+pub fn embedded(name: &str) -> Option<Value> {
+    EMBEDDED
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, schema)| serde_json::from_str(schema).expect("embedded schema is valid JSON"))
+}
+
+/// Outcome of validating one data file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum FileStatus {
+    Valid,
+    /// Skipped under `--deadline`; carries the reason.
+    Skipped {
+        reason: String,
+    },
+    Missing,
+    /// Unreadable, not JSON, a failed analyzer, or not matching the schema.
+    Invalid {
+        errors: Vec<String>,
+    },
+}
+
+/// Validation result for one data file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileValidation {
+    pub name: String,
+    #[serde(flatten)]
+    pub status: FileStatus,
+}
+
+/// Validate every data file in `data_dir`, with schemas from `schema_dir`
+/// taking precedence over the embedded ones.
+pub fn validate_dir(data_dir: &Path, schema_dir: Option<&Path>) -> Result<Vec<FileValidation>> {
+    let mut results = Vec::with_capacity(DATA_FILES.len());
+    for &name in DATA_FILES {
+        let schema = load_schema(name, schema_dir)?;
+        let status = match std::fs::read_to_string(data_dir.join(format!("{name}.json"))) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => FileStatus::Missing,
+            Err(e) => FileStatus::Invalid {
+                errors: vec![format!("read error - {e}")],
+            },
+            Ok(contents) => match serde_json::from_str::<Value>(&contents) {
+                Err(e) => FileStatus::Invalid {
+                    errors: vec![format!("invalid JSON - {e}")],
+                },
+                Ok(value) => check_file(&schema, &value),
+            },
+        };
+        results.push(FileValidation {
+            name: name.to_string(),
+            status,
+        });
+    }
+    Ok(results)
+}
+
+fn load_schema(name: &str, schema_dir: Option<&Path>) -> Result<Value> {
+    if let Some(path) = schema_dir
+        .map(|dir| dir.join(format!("{name}.schema.json")))
+        .filter(|path| path.exists())
+    {
+        let contents = std::fs::read_to_string(&path)?;
+        return serde_json::from_str(&contents)
+            .map_err(|e| Error::config(format!("invalid schema {}: {e}", path.display())));
+    }
+    embedded(name).ok_or_else(|| Error::config(format!("no schema for {name}.json")))
+}
+
+/// Check a parsed data file, recognizing the placeholders `report generate`
+/// writes for skipped and failed analyzers.
+fn check_file(schema: &Value, value: &Value) -> FileStatus {
+    if let Some(object) = value.as_object().filter(|o| o.len() == 1) {
+        if let Some(reason) = object.get("skipped") {
+            return FileStatus::Skipped {
+                reason: reason.as_str().unwrap_or_default().to_string(),
+            };
+        }
+        if let Some(error) = object.get("error") {
+            return FileStatus::Invalid {
+                errors: vec![format!(
+                    "analyzer failed - {}",
+                    error.as_str().unwrap_or("unknown")
+                )],
+            };
+        }
+    }
+    let errors = validate(schema, value);
+    if errors.is_empty() {
+        FileStatus::Valid
+    } else {
+        FileStatus::Invalid { errors }
+    }
+}
+
+/// Validate `value` against `schema`, returning one message per violation,
+/// each prefixed with the JSON Pointer of the offending value.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    check(schema, schema, value, "", &mut errors);
+    errors
+}
+
+fn check(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let fail = |errors: &mut Vec<String>, message: String| {
+        let at = if path.is_empty() { "/" } else { path };
+        errors.push(format!("{at}: {message}"));
+    };
+
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => return fail(errors, "not allowed".to_string()),
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+            Some(target) => check(root, target, value, path, errors),
+            None => return fail(errors, format!("unresolvable $ref {reference}")),
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.iter().any(|t| has_type(value, t)) {
+            return fail(
+                errors,
+                format!(
+                    "expected {}, got {}",
+                    allowed.join(" or "),
+                    type_name(value)
+                ),
+            );
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            fail(
+                errors,
+                format!("{value} is not one of {}", options.join(", ")),
+            );
+        }
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+            if n < min {
+                fail(errors, format!("{n} is less than {min}"));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+            if n > max {
+                fail(errors, format!("{n} is greater than {max}"));
+            }
+        }
+    }
+
+    if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+        let matches = options.iter().any(|option| {
+            let mut option_errors = Vec::new();
+            check(root, option, value, path, &mut option_errors);
+            option_errors.is_empty()
+        });
+        if !matches {
+            fail(errors, "matches none of the allowed shapes".to_string());
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    fail(errors, format!("missing required field `{key}`"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, item) in object {
+            let item_path = format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"));
+            match properties.and_then(|p| p.get(key)) {
+                Some(property) => check(root, property, item, &item_path, errors),
+                None => {
+                    if let Some(additional) = schema.get("additionalProperties") {
+                        check(root, additional, item, &item_path, errors);
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in array.iter().enumerate() {
+            check(root, items, item, &format!("{path}/{i}"), errors);
+        }
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_embedded_schemas_cover_data_files() {
+        for name in DATA_FILES {
+            let schema = embedded(name).unwrap_or_else(|| panic!("no schema for {name}"));
+            assert_eq!(schema["type"], "object", "{name}");
+        }
+        assert_eq!(EMBEDDED.len(), DATA_FILES.len());
+    }
+
+    #[test]
+    fn test_validate_reports_paths_of_violations() {
+        let schema = embedded("smells").unwrap();
+        let data = json!({
+            "smells": [
+                {"smell_type": "Hub", "severity": "High", "components": ["a.rs"]},
+                {"smell_type": "Hub", "components": "b.rs", "status": "gone"}
+            ],
+            "summary": {"total_smells": -1}
+        });
+
+        let errors = validate(&schema, &data);
+
+        assert_eq!(
+            errors,
+            [
+                "/smells/1: missing required field `severity`",
+                "/smells/1/components: expected array, got string",
+                "/smells/1/status: \"gone\" is not one of \"new\", \"persisting\", \"resolved\"",
+                "/summary/total_smells: -1 is less than 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_types() {
+        let schema = json!({
+            "type": "object",
+            "required": ["count"],
+            "properties": {
+                "count": {"type": "integer"},
+                "ratio": {"type": "number"},
+                "note": {"type": ["string", "null"]}
+            },
+            "additionalProperties": {"type": "boolean"}
+        });
+
+        assert!(validate(&schema, &json!({"count": 2, "ratio": 1, "note": null})).is_empty());
+        assert_eq!(
+            validate(&schema, &json!({"count": 2.5, "extra": 1})),
+            [
+                "/count: expected integer, got number",
+                "/extra: expected boolean, got integer",
+            ]
+        );
+        assert_eq!(
+            validate(&schema, &json!([])),
+            ["/: expected object, got array"]
+        );
+    }
+
+    #[test]
+    fn test_validate_dir() {
+        let data = tempfile::tempdir().unwrap();
+        let write = |name: &str, value: Value| {
+            std::fs::write(data.path().join(format!("{name}.json")), value.to_string()).unwrap();
+        };
+        write(
+            "satd",
+            json!({"items": [], "by_category": {}, "density": 0.0, "summary": {"total_items": 0}}),
+        );
+        write("trend", json!({"skipped": "deadline"}));
+        write("churn", json!({"error": "not a git repository"}));
+        write("graph", json!({"nodes": []}));
+
+        let results = validate_dir(data.path(), None).unwrap();
+        let status = |name: &str| &results.iter().find(|r| r.name == name).unwrap().status;
+
+        assert_eq!(status("satd"), &FileStatus::Valid);
+        assert_eq!(
+            status("trend"),
+            &FileStatus::Skipped {
+                reason: "deadline".to_string()
+            }
+        );
+        assert_eq!(status("complexity"), &FileStatus::Missing);
+        assert_eq!(
+            status("churn"),
+            &FileStatus::Invalid {
+                errors: vec!["analyzer failed - not a git repository".to_string()]
+            }
+        );
+        let FileStatus::Invalid { errors } = status("graph") else {
+            panic!("graph.json should be invalid");
+        };
+        assert_eq!(errors.len(), 3);
+
+        // A schema directory replaces only the schemas it has
+        let schemas = tempfile::tempdir().unwrap();
+        std::fs::write(
+            schemas.path().join("graph.schema.json"),
+            r#"{"type": "object"}"#,
+        )
+        .unwrap();
+        let results = validate_dir(data.path(), Some(schemas.path())).unwrap();
+        let status = |name: &str| &results.iter().find(|r| r.name == name).unwrap().status;
+        assert_eq!(status("graph"), &FileStatus::Valid);
+        assert_eq!(status("satd"), &FileStatus::Valid);
+    }
+}
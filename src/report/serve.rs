@@ -0,0 +1,476 @@
+//! HTTP server behind `omen report serve`.
+//!
+//! Serves the report rendered from a data directory at `/`, the directory's
+//! data files at `/api/<name>.json`, and re-renders the report on
+//! `POST /api/rerender` after the data has been regenerated. Each connection
+//! is handled on its own thread and closed after one response.
+//!
+//! The server may be bound to a public address, so requests are bounded:
+//! header lines, header count and concurrent connections are capped, and
+//! only loopback clients may trigger a re-render.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use super::schema::DATA_FILES;
+use super::Renderer;
+use crate::core::{Error, Result};
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most request body bytes read and discarded; no route takes a body.
+const MAX_BODY: u64 = 64 * 1024;
+
+/// Longest request or header line accepted, in bytes.
+const MAX_LINE: u64 = 8 * 1024;
+
+/// Most headers accepted in one request.
+const MAX_HEADERS: usize = 100;
+
+/// Default cap on connections handled at once; more are turned away with 503.
+const MAX_CONNECTIONS: usize = 64;
+
+/// A report server bound to an address.
+pub struct Server {
+    listener: TcpListener,
+    state: Arc<State>,
+    max_connections: usize,
+}
+
+struct State {
+    data_dir: PathBuf,
+    renderer: Renderer,
+    /// The last successful render.
+    report: RwLock<Arc<Vec<u8>>>,
+    /// Connections being handled.
+    active: AtomicUsize,
+}
+
+impl Server {
+    /// Bind to `addr` and render the report from `data_dir`.
+    pub fn bind(addr: impl ToSocketAddrs, data_dir: &Path) -> Result<Self> {
+        if !data_dir.is_dir() {
+            return Err(Error::InvalidArgument(format!(
+                "data directory not found: {}",
+                data_dir.display()
+            )));
+        }
+        let renderer = Renderer::new()?;
+        let report = renderer.render_to_bytes(data_dir)?;
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            state: Arc::new(State {
+                data_dir: data_dir.to_path_buf(),
+                renderer,
+                report: RwLock::new(Arc::new(report)),
+                active: AtomicUsize::new(0),
+            }),
+            max_connections: MAX_CONNECTIONS,
+        })
+    }
+
+    /// Cap the connections handled at once (default 64).
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// The bound address, useful when binding port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accept connections until the listener fails.
+    pub fn run(self) -> Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::warn!("Failed to accept connection: {e}");
+                    continue;
+                }
+            };
+            if self.state.active.fetch_add(1, Ordering::AcqRel) >= self.max_connections {
+                self.state.active.fetch_sub(1, Ordering::AcqRel);
+                let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+                let _ = Response::error(503).write_to(&stream, false);
+                continue;
+            }
+            let state = Arc::clone(&self.state);
+            std::thread::spawn(move || {
+                if let Err(e) = state.handle(stream) {
+                    tracing::debug!("Connection failed: {e}");
+                }
+                state.active.fetch_sub(1, Ordering::AcqRel);
+            });
+        }
+        Ok(())
+    }
+}
+
+impl State {
+    fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let peer = stream.peer_addr()?.ip();
+        let mut reader = BufReader::new(&stream);
+        let (response, head) = match read_request(&mut reader)? {
+            Ok(request) => (
+                self.respond(&request.method, &request.path, peer),
+                request.method == "HEAD",
+            ),
+            Err(status) => (Response::error(status), false),
+        };
+        response.write_to(&stream, head)
+    }
+
+    fn respond(&self, method: &str, path: &str, peer: IpAddr) -> Response {
+        let get = matches!(method, "GET" | "HEAD");
+        match path {
+            "/" | "/index.html" if get => Response {
+                content_type: "text/html; charset=utf-8",
+                body: Arc::clone(&self.report.read().unwrap_or_else(PoisonError::into_inner)),
+                ..Response::text(200, "")
+            },
+            "/api" | "/api/" if get => Response::json(200, &self.index()),
+            // Rendering is expensive; don't let remote clients trigger it.
+            "/api/rerender" if method == "POST" && !peer.to_canonical().is_loopback() => {
+                Response::error(403)
+            }
+            "/api/rerender" if method == "POST" => self.rerender(),
+            "/" | "/index.html" | "/api" | "/api/" => Response::not_allowed("GET, HEAD"),
+            "/api/rerender" => Response::not_allowed("POST"),
+            _ => match data_file(path) {
+                Some(name) if get => self.data_file(name),
+                Some(_) => Response::not_allowed("GET, HEAD"),
+                None => Response::error(404),
+            },
+        }
+    }
+
+    /// The data files present, by name.
+    fn index(&self) -> Value {
+        let files: Vec<&str> = DATA_FILES
+            .iter()
+            .copied()
+            .filter(|name| self.data_dir.join(format!("{name}.json")).is_file())
+            .collect();
+        json!({ "files": files })
+    }
+
+    fn data_file(&self, name: &str) -> Response {
+        match std::fs::read(self.data_dir.join(format!("{name}.json"))) {
+            Ok(bytes) => Response {
+                content_type: "application/json",
+                body: Arc::new(bytes),
+                ..Response::text(200, "")
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Response::json(404, &json!({ "error": format!("{name}.json not found") }))
+            }
+            Err(e) => Response::json(500, &json!({ "error": e.to_string() })),
+        }
+    }
+
+    fn rerender(&self) -> Response {
+        match self.renderer.render_to_bytes(&self.data_dir) {
+            Ok(report) => {
+                let bytes = report.len();
+                *self.report.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(report);
+                Response::json(200, &json!({ "rendered": true, "bytes": bytes }))
+            }
+            Err(e) => Response::json(500, &json!({ "error": e.to_string() })),
+        }
+    }
+}
+
+/// The data file named by an `/api/<name>.json` path. Only known data files
+/// are served, so the path can't reach outside the data directory.
+fn data_file(path: &str) -> Option<&'static str> {
+    let name = path.strip_prefix("/api/")?.strip_suffix(".json")?;
+    DATA_FILES.iter().copied().find(|known| *known == name)
+}
+
+struct Request {
+    method: String,
+    /// The request target without its query string.
+    path: String,
+}
+
+/// Read the request line and headers, discarding any body. Fails with the
+/// status to answer for a malformed, truncated or oversized request.
+fn read_request(reader: &mut impl BufRead) -> io::Result<std::result::Result<Request, u16>> {
+    let mut line = String::new();
+    if let Err(status) = read_line(reader, &mut line)? {
+        return Ok(Err(status));
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Ok(Err(400));
+    };
+    let request = Request {
+        method: method.to_string(),
+        path: target
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    };
+
+    let mut content_length = 0;
+    let mut headers = 0;
+    loop {
+        line.clear();
+        if let Err(status) = read_line(reader, &mut line)? {
+            return Ok(Err(status));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        headers += 1;
+        if headers > MAX_HEADERS {
+            return Ok(Err(431));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    io::copy(
+        &mut reader.by_ref().take(content_length.min(MAX_BODY)),
+        &mut io::sink(),
+    )?;
+    Ok(Ok(request))
+}
+
+/// Read one line of at most [`MAX_LINE`] bytes into `line`. Fails with 400
+/// when the connection ends first and 431 when the line is too long.
+fn read_line(
+    reader: &mut impl BufRead,
+    line: &mut String,
+) -> io::Result<std::result::Result<(), u16>> {
+    let read = reader.by_ref().take(MAX_LINE + 1).read_line(line)?;
+    Ok(if line.ends_with('\n') {
+        Ok(())
+    } else if read as u64 > MAX_LINE {
+        Err(431)
+    } else {
+        Err(400)
+    })
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: Arc<Vec<u8>>,
+    /// Methods allowed, for 405 responses.
+    allow: Option<&'static str>,
+}
+
+impl Response {
+    fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: Arc::new(body.as_bytes().to_vec()),
+            allow: None,
+        }
+    }
+
+    fn json(status: u16, value: &Value) -> Self {
+        Self {
+            content_type: "application/json",
+            body: Arc::new(value.to_string().into_bytes()),
+            ..Self::text(status, "")
+        }
+    }
+
+    /// A plain-text response carrying the status's reason phrase.
+    fn error(status: u16) -> Self {
+        Self::text(status, reason(status))
+    }
+
+    fn not_allowed(allow: &'static str) -> Self {
+        Self {
+            allow: Some(allow),
+            ..Self::error(405)
+        }
+    }
+
+    /// Write the response, without the body when answering a HEAD request.
+    fn write_to(&self, mut writer: impl Write, head: bool) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+             Cache-Control: no-store\r\nConnection: close\r\n",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len()
+        )?;
+        if let Some(allow) = self.allow {
+            write!(writer, "Allow: {allow}\r\n")?;
+        }
+        writer.write_all(b"\r\n")?;
+        if !head {
+            writer.write_all(&self.body)?;
+        }
+        writer.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn start(data_dir: &Path) -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0", data_dir).unwrap();
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+        addr
+    }
+
+    /// Send a raw request line and return the status and body.
+    fn request(addr: SocketAddr, method: &str, path: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+        (status, body)
+    }
+
+    #[test]
+    fn test_serves_report_and_data_files() {
+        let data = TempDir::new().unwrap();
+        std::fs::write(data.path().join("churn.json"), r#"{"files":[]}"#).unwrap();
+        let addr = start(data.path());
+
+        let (status, body) = request(addr, "GET", "/");
+        assert_eq!(status, 200);
+        assert!(!body.is_empty());
+        assert_eq!(request(addr, "HEAD", "/index.html"), (200, String::new()));
+
+        assert_eq!(
+            request(addr, "GET", "/api/churn.json?x=1"),
+            (200, r#"{"files":[]}"#.to_string())
+        );
+        assert_eq!(request(addr, "GET", "/api/satd.json").0, 404);
+        assert_eq!(request(addr, "GET", "/api/../churn.json").0, 404);
+        assert_eq!(request(addr, "DELETE", "/api/churn.json").0, 405);
+        assert_eq!(request(addr, "GET", "/api/rerender").0, 405);
+
+        let (status, body) = request(addr, "GET", "/api");
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"files":["churn"]}"#);
+    }
+
+    #[test]
+    fn test_rerender_while_another_connection_is_open() {
+        let data = TempDir::new().unwrap();
+        let addr = start(data.path());
+
+        // An idle client must not block the next one.
+        let _idle = TcpStream::connect(addr).unwrap();
+        let (status, body) = request(addr, "POST", "/api/rerender");
+
+        assert_eq!(status, 200, "{body}");
+        let value: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(value["rendered"], true);
+        assert!(value["bytes"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_rerender_only_from_loopback() {
+        let data = TempDir::new().unwrap();
+        let server = Server::bind("127.0.0.1:0", data.path()).unwrap();
+        let rerender = |peer: &str| {
+            let peer = peer.parse().unwrap();
+            server.state.respond("POST", "/api/rerender", peer).status
+        };
+
+        assert_eq!(rerender("127.0.0.1"), 200);
+        assert_eq!(rerender("::1"), 200);
+        assert_eq!(rerender("::ffff:127.0.0.1"), 200);
+        assert_eq!(rerender("192.0.2.7"), 403);
+        assert_eq!(rerender("::ffff:192.0.2.7"), 403);
+        let peer = "192.0.2.7".parse().unwrap();
+        assert_eq!(server.state.respond("GET", "/", peer).status, 200);
+    }
+
+    #[test]
+    fn test_oversized_requests_are_refused() {
+        let read = |raw: String| match read_request(&mut raw.as_bytes()).unwrap() {
+            Ok(request) => Ok(request.path),
+            Err(status) => Err(status),
+        };
+        let long = "x".repeat(MAX_LINE as usize);
+
+        assert_eq!(read(format!("GET /{long} HTTP/1.1\r\n\r\n")), Err(431));
+        assert_eq!(
+            read(format!("GET / HTTP/1.1\r\nCookie: {long}\r\n\r\n")),
+            Err(431)
+        );
+        let headers = "X-A: 1\r\n".repeat(MAX_HEADERS + 1);
+        assert_eq!(read(format!("GET / HTTP/1.1\r\n{headers}\r\n")), Err(431));
+        let headers = "X-A: 1\r\n".repeat(MAX_HEADERS);
+        assert_eq!(
+            read(format!("GET / HTTP/1.1\r\n{headers}\r\n")),
+            Ok("/".to_string())
+        );
+        assert_eq!(read("GET / HTTP/1.1\r\nHost: x".to_string()), Err(400));
+    }
+
+    #[test]
+    fn test_connections_over_the_cap_get_503() {
+        let data = TempDir::new().unwrap();
+        let server = Server::bind("127.0.0.1:0", data.path())
+            .unwrap()
+            .with_max_connections(1);
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+
+        let _idle = TcpStream::connect(addr).unwrap();
+        let mut turned_away = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+        turned_away.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 "), "{response}");
+    }
+
+    #[test]
+    fn test_missing_data_dir_is_rejected() {
+        let data = TempDir::new().unwrap();
+        let err = Server::bind("127.0.0.1:0", &data.path().join("missing"))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("data directory not found"));
+    }
+}
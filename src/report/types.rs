@@ -2,7 +2,11 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::BudgetMetric;
+use crate::manifest::Manifest;
+use crate::score::BudgetStatus;
 
 /// Metadata contains report generation metadata.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -13,6 +17,8 @@ pub struct Metadata {
     pub omen_version: String,
     #[serde(default)]
     pub paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<Manifest>,
 }
 
 /// ScoreData represents the score.json structure.
@@ -67,6 +73,34 @@ pub struct ComplexityData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComplexityRaw {
     pub summary: ComplexitySummary,
+    #[serde(default)]
+    pub files: Vec<ComplexityFile>,
+}
+
+/// A file's functions from complexity.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityFile {
+    pub path: String,
+    #[serde(default)]
+    pub functions: Vec<ComplexityFunction>,
+}
+
+/// A function's complexity from complexity.json.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexityFunction {
+    pub name: String,
+    #[serde(default)]
+    pub start_line: u32,
+    #[serde(default)]
+    pub metrics: ComplexityMetrics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ComplexityMetrics {
+    #[serde(default)]
+    pub cyclomatic: u32,
+    #[serde(default)]
+    pub cognitive: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -111,6 +145,8 @@ pub struct HotspotItem {
     pub commits: i32,
     #[serde(default, alias = "avg_complexity")]
     pub avg_cognitive: f64,
+    #[serde(default)]
+    pub severity: String,
 }
 
 /// SATDData represents the satd.json structure.
@@ -118,6 +154,21 @@ pub struct HotspotItem {
 pub struct SATDData {
     #[serde(default)]
     pub items: Vec<SATDItem>,
+    /// Present when satd was run with a comparison ref.
+    #[serde(default)]
+    pub comparison: Option<SATDComparison>,
+}
+
+/// SATDComparison holds debt added/resolved since a base ref, for the burn-down chart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SATDComparison {
+    pub base_ref: String,
+    pub base_total: i32,
+    pub current_total: i32,
+    #[serde(default)]
+    pub added: Vec<SATDItem>,
+    #[serde(default)]
+    pub resolved: Vec<SATDItem>,
 }
 
 /// SATDItem represents a single SATD item.
@@ -129,6 +180,21 @@ pub struct SATDItem {
     pub category: String,
     #[serde(alias = "text")]
     pub content: String,
+    #[serde(default)]
+    pub function: Option<String>,
+    /// Commit that last changed the line, when satd ran with blame.
+    #[serde(default)]
+    pub introduced: Option<SATDOrigin>,
+    #[serde(default)]
+    pub age_days: Option<i64>,
+}
+
+/// Who last changed a SATD line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SATDOrigin {
+    pub author: String,
+    #[serde(default)]
+    pub date: String,
 }
 
 /// SATDStats contains SATD statistics for charts.
@@ -243,6 +309,26 @@ pub struct DuplicatesData {
     pub duplication_ratio: f64,
 }
 
+/// DuplicateClones for loading the clone pairs from duplicates.json.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DuplicateClones {
+    #[serde(default)]
+    pub clones: Vec<CloneItem>,
+}
+
+/// A pair of duplicated code ranges.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneItem {
+    pub file_a: String,
+    pub file_b: String,
+    pub start_line_a: u32,
+    pub end_line_a: u32,
+    pub start_line_b: u32,
+    pub end_line_b: u32,
+    #[serde(default)]
+    pub similarity: f64,
+}
+
 /// DuplicatesSummary contains aggregate duplication metrics.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DuplicatesSummary {
@@ -284,6 +370,12 @@ pub struct TrendPoint {
     pub components: HashMap<String, i32>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub notable_commits: Vec<String>,
+    /// Total CI/build pipeline complexity at this point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline_complexity: Option<usize>,
+    /// Raw values of the metrics budgets can target.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<BudgetMetric, f64>,
 }
 
 /// ComponentTrendStats contains trend statistics for a component.
@@ -435,6 +527,47 @@ pub struct TemporalSummary {
     pub total_files_analyzed: usize,
 }
 
+/// HiddenData represents the hidden.json structure.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HiddenData {
+    #[serde(default)]
+    pub dependencies: Vec<HiddenDependency>,
+    #[serde(default)]
+    pub summary: HiddenSummary,
+}
+
+/// Co-changing files with no import path between them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiddenDependency {
+    pub file_a: String,
+    pub file_b: String,
+    #[serde(default)]
+    pub cochange_count: u32,
+    #[serde(default)]
+    pub coupling_strength: f64,
+    #[serde(default)]
+    pub commits: Vec<HiddenCommit>,
+}
+
+/// A commit that changed both files of a hidden dependency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiddenCommit {
+    pub sha: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Summary statistics for hidden dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct HiddenSummary {
+    #[serde(default)]
+    pub coupled_pairs: usize,
+    #[serde(default)]
+    pub hidden_dependencies: usize,
+    #[serde(default)]
+    pub files_involved: usize,
+}
+
 // ============================================================================
 // Architectural Smells Types
 // ============================================================================
@@ -444,6 +577,9 @@ pub struct TemporalSummary {
 pub struct SmellsData {
     #[serde(default)]
     pub smells: Vec<SmellItem>,
+    /// Smells of the previous run no longer found.
+    #[serde(default)]
+    pub resolved: Vec<SmellItem>,
     #[serde(default)]
     pub components: Vec<SmellComponent>,
     #[serde(default)]
@@ -463,6 +599,9 @@ pub struct SmellItem {
     pub suggestion: String,
     #[serde(default)]
     pub metrics: SmellItemMetrics,
+    /// "new", "persisting", or "resolved" when compared to a previous run.
+    #[serde(default)]
+    pub status: Option<String>,
 }
 
 /// Quantitative metrics for a smell.
@@ -518,6 +657,19 @@ pub struct SmellsSummary {
     pub total_components: usize,
     #[serde(default)]
     pub average_instability: f64,
+    #[serde(default)]
+    pub trend: Option<SmellsTrend>,
+}
+
+/// Smells new, persisting, and resolved since the previous run.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SmellsTrend {
+    #[serde(default)]
+    pub new: usize,
+    #[serde(default)]
+    pub persisting: usize,
+    #[serde(default)]
+    pub resolved: usize,
 }
 
 // ============================================================================
@@ -534,6 +686,8 @@ pub struct GraphData {
     #[serde(default)]
     pub cycles: Vec<Vec<String>>,
     #[serde(default)]
+    pub cycle_breaks: Vec<GraphCycleBreak>,
+    #[serde(default)]
     pub summary: GraphSummary,
 }
 
@@ -560,6 +714,17 @@ pub struct GraphEdge {
     pub to: String,
 }
 
+/// An import suggested for removal to break a cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphCycleBreak {
+    /// Index into `cycles`.
+    pub cycle: usize,
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub line: u32,
+}
+
 /// Summary statistics for the dependency graph.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GraphSummary {
@@ -617,6 +782,271 @@ pub struct TdgFile {
     pub has_critical_defects: bool,
 }
 
+// ============================================================================
+// Risk Matrix Types
+// ============================================================================
+
+/// RiskData represents the risk.json structure.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RiskData {
+    #[serde(default)]
+    pub files: Vec<RiskFile>,
+    #[serde(default)]
+    pub matrix: Vec<RiskMatrixCell>,
+    #[serde(default)]
+    pub summary: RiskSummary,
+}
+
+/// A file placed in the risk matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFile {
+    pub file_path: String,
+    #[serde(default)]
+    pub probability: f32,
+    #[serde(default)]
+    pub defect_risk: String,
+    #[serde(default)]
+    pub criticality: String,
+    #[serde(default)]
+    pub matched_pattern: Option<String>,
+    #[serde(default)]
+    pub risk_score: f64,
+    #[serde(default)]
+    pub priority: String,
+}
+
+/// One cell of the criticality x defect-risk matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskMatrixCell {
+    pub criticality: String,
+    pub defect_risk: String,
+    pub priority: String,
+    #[serde(default)]
+    pub count: usize,
+}
+
+/// Aggregate risk matrix stats.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RiskSummary {
+    #[serde(default)]
+    pub total_files: usize,
+    #[serde(default)]
+    pub tagged_files: usize,
+    #[serde(default)]
+    pub urgent_count: usize,
+    #[serde(default)]
+    pub high_count: usize,
+    #[serde(default)]
+    pub medium_count: usize,
+    #[serde(default)]
+    pub low_count: usize,
+}
+
+/// PipelineData represents the pipeline.json structure.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PipelineData {
+    #[serde(default)]
+    pub files: Vec<PipelineFileData>,
+    #[serde(default)]
+    pub duplicates: Vec<PipelineDuplicate>,
+    #[serde(default)]
+    pub summary: PipelineSummary,
+}
+
+/// A CI config or build script in the pipeline audit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineFileData {
+    pub path: String,
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub jobs: usize,
+    #[serde(default)]
+    pub steps: usize,
+    #[serde(default)]
+    pub complexity: usize,
+    #[serde(default)]
+    pub refs: Vec<PipelineRef>,
+}
+
+/// An action, image or component reference and how it is pinned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineRef {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub pinning: String,
+    #[serde(default)]
+    pub line: u32,
+}
+
+/// A step repeated across jobs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineDuplicate {
+    pub step: String,
+    #[serde(default)]
+    pub occurrences: Vec<PipelineStepLocation>,
+}
+
+/// Where a duplicated step appears.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStepLocation {
+    pub file: String,
+    pub job: String,
+    #[serde(default)]
+    pub line: u32,
+}
+
+/// Aggregate pipeline audit stats.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PipelineSummary {
+    #[serde(default)]
+    pub total_files: usize,
+    #[serde(default)]
+    pub total_jobs: usize,
+    #[serde(default)]
+    pub total_steps: usize,
+    #[serde(default)]
+    pub duplicated_steps: usize,
+    #[serde(default)]
+    pub external_refs: usize,
+    #[serde(default)]
+    pub sha_pinned_refs: usize,
+    #[serde(default)]
+    pub floating_refs: usize,
+    #[serde(default)]
+    pub total_complexity: usize,
+}
+
+/// DockerData represents the docker.json structure.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DockerData {
+    #[serde(default)]
+    pub files: Vec<DockerFileData>,
+    #[serde(default)]
+    pub findings: Vec<DockerFinding>,
+    #[serde(default)]
+    pub summary: DockerSummary,
+}
+
+/// A Dockerfile in the hygiene check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerFileData {
+    pub path: String,
+    #[serde(default)]
+    pub stages: usize,
+    #[serde(default)]
+    pub layers: usize,
+    #[serde(default)]
+    pub findings: usize,
+}
+
+/// A Dockerfile hygiene problem.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerFinding {
+    pub file: String,
+    #[serde(default)]
+    pub line: u32,
+    #[serde(default)]
+    pub rule: String,
+    #[serde(default)]
+    pub severity: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub suggestion: String,
+}
+
+/// Aggregate Dockerfile hygiene stats.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DockerSummary {
+    #[serde(default)]
+    pub total_files: usize,
+    #[serde(default)]
+    pub total_stages: usize,
+    #[serde(default)]
+    pub total_findings: usize,
+    #[serde(default)]
+    pub severity_counts: DockerSeverityCounts,
+}
+
+/// Dockerfile findings per severity.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DockerSeverityCounts {
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+// ============================================================================
+// File Drill-down Types
+// ============================================================================
+
+/// Everything the analyzers found about one file, for the drill-down view.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FileFindings {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<FileFunction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub satd: Vec<FileSatd>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clones: Vec<FileClone>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub smells: Vec<FileSmell>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub churn: Option<FileChurn>,
+}
+
+/// A function's complexity within a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileFunction {
+    pub name: String,
+    pub line: u32,
+    pub cyclomatic: u32,
+    pub cognitive: u32,
+}
+
+/// A SATD comment within a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSatd {
+    pub line: i32,
+    pub severity: String,
+    pub category: String,
+    pub content: String,
+}
+
+/// A range of a file duplicated elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileClone {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub other_file: String,
+    pub other_start_line: u32,
+    pub other_end_line: u32,
+    pub similarity: f64,
+}
+
+/// An architectural smell the file takes part in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSmell {
+    pub smell_type: String,
+    pub severity: String,
+    pub description: String,
+}
+
+/// A file's change activity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChurn {
+    pub commits: i32,
+    pub authors: usize,
+    pub additions: i32,
+    pub deletions: i32,
+    pub churn_score: f64,
+}
+
 // ============================================================================
 // Insight Types (LLM-generated content)
 // ============================================================================
@@ -849,9 +1279,14 @@ pub struct RenderData {
     pub component_trends: HashMap<String, ComponentTrendStats>,
     pub satd_stats: Option<SATDStats>,
     pub temporal: Option<TemporalData>,
+    pub hidden: Option<HiddenData>,
     pub smells: Option<SmellsData>,
     pub graph: Option<GraphData>,
     pub tdg: Option<TdgData>,
+    pub risk: Option<RiskData>,
+    pub pipeline: Option<PipelineData>,
+    pub docker: Option<DockerData>,
+    pub budgets: Vec<BudgetStatus>,
 }
 
 impl RenderData {
@@ -914,6 +1349,28 @@ mod tests {
         assert_eq!(data.score_class, "danger");
     }
 
+    #[test]
+    fn test_satd_data_reads_comparison() {
+        let data: SATDData = serde_json::from_str(
+            r#"{
+                "items": [],
+                "comparison": {
+                    "base_ref": "v1.0.0",
+                    "base_total": 3,
+                    "current_total": 2,
+                    "added": [],
+                    "resolved": [{"file": "a.rs", "line": 4, "severity": "high",
+                                  "category": "design", "text": "TODO: split"}]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let comparison = data.comparison.unwrap();
+        assert_eq!(comparison.base_ref, "v1.0.0");
+        assert_eq!(comparison.resolved[0].content, "TODO: split");
+    }
+
     #[test]
     fn test_satd_stats_computation() {
         let mut data = RenderData {
@@ -925,6 +1382,9 @@ mod tests {
                         severity: "critical".to_string(),
                         category: "defect".to_string(),
                         content: "FIXME".to_string(),
+                        function: None,
+                        introduced: None,
+                        age_days: None,
                     },
                     SATDItem {
                         file: "test.rs".to_string(),
@@ -932,6 +1392,9 @@ mod tests {
                         severity: "high".to_string(),
                         category: "defect".to_string(),
                         content: "TODO".to_string(),
+                        function: None,
+                        introduced: None,
+                        age_days: None,
                     },
                     SATDItem {
                         file: "test.rs".to_string(),
@@ -939,8 +1402,12 @@ mod tests {
                         severity: "medium".to_string(),
                         category: "design".to_string(),
                         content: "HACK".to_string(),
+                        function: None,
+                        introduced: None,
+                        age_days: None,
                     },
                 ],
+                comparison: None,
             }),
             ..Default::default()
         };
@@ -1085,6 +1552,27 @@ mod tests {
         assert_eq!(data.smells.len(), 1);
         assert_eq!(data.smells[0].smell_type, "CyclicDependency");
         assert_eq!(data.summary.critical_count, 1);
+        assert!(data.summary.trend.is_none());
+        assert!(data.resolved.is_empty());
+    }
+
+    #[test]
+    fn test_smells_deserialize_trend() {
+        let json = r#"{
+            "smells": [
+                {"smell_type": "Hub", "severity": "High", "components": ["a.rs"], "status": "new"}
+            ],
+            "resolved": [
+                {"smell_type": "CyclicDependency", "severity": "Critical",
+                 "components": ["b.rs", "c.rs"], "status": "resolved"}
+            ],
+            "summary": {"total_smells": 1, "trend": {"new": 1, "persisting": 0, "resolved": 1}}
+        }"#;
+        let data: SmellsData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.smells[0].status.as_deref(), Some("new"));
+        assert_eq!(data.resolved[0].components, ["b.rs", "c.rs"]);
+        let trend = data.summary.trend.unwrap();
+        assert_eq!((trend.new, trend.persisting, trend.resolved), (1, 0, 1));
     }
 
     #[test]
@@ -1102,6 +1590,7 @@ mod tests {
             ],
             "edges": [{ "from": "src/main.rs", "to": "src/lib.rs" }],
             "cycles": [["src/a.rs", "src/b.rs"]],
+            "cycle_breaks": [{ "cycle": 0, "from": "src/b.rs", "to": "src/a.rs", "line": 3 }],
             "summary": {
                 "total_nodes": 1,
                 "total_edges": 1,
@@ -1112,6 +1601,7 @@ mod tests {
         let data: GraphData = serde_json::from_str(json).unwrap();
         assert_eq!(data.nodes.len(), 1);
         assert_eq!(data.cycles.len(), 1);
+        assert_eq!(data.cycle_breaks[0].line, 3);
         assert_eq!(data.summary.cycle_count, 1);
     }
 
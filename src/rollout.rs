@@ -0,0 +1,264 @@
+//! Rollout state of feature flags, read from their providers.
+//!
+//! `omen fix` asks LaunchDarkly and Unleash whether a stale flag is fully
+//! rolled out before inlining its checks. Tokens come from
+//! `LAUNCHDARKLY_API_TOKEN` or `UNLEASH_API_TOKEN`; `LAUNCHDARKLY_API_URL`
+//! overrides the LaunchDarkly API root and `UNLEASH_API_URL`, which
+//! self-hosted Unleash needs, is the server's `/api` root as the SDKs take
+//! it. The project and environment come from `[feature_flags]`.
+
+use std::env;
+
+use serde_json::Value;
+
+use crate::config::FeatureFlagsConfig;
+use crate::core::{Error, Result};
+
+/// A flag provider whose rollout state can be looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Provider {
+    LaunchDarkly,
+    Unleash,
+}
+
+impl Provider {
+    /// The provider behind a flags analyzer provider name, if it has a
+    /// rollout lookup.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "launchdarkly" => Some(Self::LaunchDarkly),
+            "unleash" => Some(Self::Unleash),
+            _ => None,
+        }
+    }
+}
+
+/// Authenticated client for one project and environment of a provider.
+#[derive(Debug, Clone)]
+pub struct RolloutClient {
+    provider: Provider,
+    api_url: String,
+    token: String,
+    project: String,
+    environment: String,
+}
+
+impl RolloutClient {
+    /// Build a client for `provider` from the environment and `config`.
+    pub fn new(provider: Provider, config: &FeatureFlagsConfig) -> Result<Self> {
+        let (token_var, api_var, default_api) = match provider {
+            Provider::LaunchDarkly => (
+                "LAUNCHDARKLY_API_TOKEN",
+                "LAUNCHDARKLY_API_URL",
+                Some("https://app.launchdarkly.com"),
+            ),
+            Provider::Unleash => ("UNLEASH_API_TOKEN", "UNLEASH_API_URL", None),
+        };
+        let token = env::var(token_var)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| {
+                Error::InvalidArgument(format!("{token_var} must be set to look up rollouts"))
+            })?;
+        let api_url = env::var(api_var)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .or_else(|| default_api.map(str::to_string))
+            .ok_or_else(|| {
+                Error::InvalidArgument(format!("{api_var} must be set to look up rollouts"))
+            })?;
+
+        Ok(Self {
+            provider,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+            project: config.project.clone(),
+            environment: config.environment.clone(),
+        })
+    }
+
+    /// Whether the provider serves `true` to everyone for flag `key` in the
+    /// configured environment.
+    pub fn fully_rolled_out(&self, key: &str) -> Result<bool> {
+        let flag = self.get(&self.flag_url(key))?;
+        Ok(match self.provider {
+            Provider::LaunchDarkly => launchdarkly_rolled_out(&flag, &self.environment),
+            Provider::Unleash => unleash_rolled_out(&flag, &self.environment),
+        })
+    }
+
+    fn flag_url(&self, key: &str) -> String {
+        match self.provider {
+            Provider::LaunchDarkly => format!(
+                "{}/api/v2/flags/{}/{key}?env={}",
+                self.api_url, self.project, self.environment
+            ),
+            Provider::Unleash => format!(
+                "{}/admin/projects/{}/features/{key}",
+                self.api_url, self.project
+            ),
+        }
+    }
+
+    fn get(&self, url: &str) -> Result<Value> {
+        let response = reqwest::blocking::Client::new()
+            .get(url)
+            .header("User-Agent", concat!("omen/", env!("CARGO_PKG_VERSION")))
+            .header("Authorization", &self.token)
+            .send()
+            .map_err(|e| Error::analysis(format!("Failed to fetch {url}: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().unwrap_or_default();
+            return Err(Error::analysis(format!(
+                "{:?} API error ({status}) for {url}: {}",
+                self.provider,
+                body.trim()
+            )));
+        }
+        response
+            .json()
+            .map_err(|e| Error::analysis(format!("Invalid response from {url}: {e}")))
+    }
+}
+
+/// LaunchDarkly serves everyone the same `true` variation: targeting is on,
+/// with no individual targets or rules, and the default rule serves that
+/// variation outright or with all of a rollout's weight.
+fn launchdarkly_rolled_out(flag: &Value, environment: &str) -> bool {
+    let env = &flag["environments"][environment];
+    let empty = |field: &str| env[field].as_array().is_none_or(Vec::is_empty);
+    if env["on"] != true || !empty("rules") || !empty("targets") || !empty("contextTargets") {
+        return false;
+    }
+    let fallthrough = &env["fallthrough"];
+    let served = fallthrough["variation"].as_u64().or_else(|| {
+        let weights = fallthrough["rollout"]["variations"].as_array()?;
+        weights
+            .iter()
+            .find(|v| v["weight"].as_u64() == Some(100_000))?["variation"]
+            .as_u64()
+    });
+    served.is_some_and(|index| flag["variations"][index as usize]["value"] == true)
+}
+
+/// Unleash enables the flag for everyone: the environment is enabled and
+/// one active strategy without constraints or segments is `default` or a
+/// 100% `flexibleRollout`.
+fn unleash_rolled_out(flag: &Value, environment: &str) -> bool {
+    let Some(env) = flag["environments"]
+        .as_array()
+        .and_then(|envs| envs.iter().find(|env| env["name"] == environment))
+    else {
+        return false;
+    };
+    if env["enabled"] != true || flag["archived"] == true {
+        return false;
+    }
+    let empty =
+        |strategy: &Value, field: &str| strategy[field].as_array().is_none_or(Vec::is_empty);
+    env["strategies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|strategy| {
+            let everyone = match strategy["name"].as_str() {
+                Some("default") => true,
+                Some("flexibleRollout") => {
+                    let rollout = &strategy["parameters"]["rollout"];
+                    rollout == "100" || rollout == 100
+                }
+                _ => false,
+            };
+            everyone
+                && strategy["disabled"] != true
+                && empty(strategy, "constraints")
+                && empty(strategy, "segments")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_launchdarkly_fallthrough_to_true() {
+        let flag = |production: Value| {
+            json!({
+                "variations": [{"value": true}, {"value": false}],
+                "environments": {"production": production}
+            })
+        };
+        let rolled_out = flag(json!({
+            "on": true, "rules": [], "targets": [], "fallthrough": {"variation": 0}
+        }));
+        assert!(launchdarkly_rolled_out(&rolled_out, "production"));
+        assert!(!launchdarkly_rolled_out(&rolled_out, "staging"));
+
+        let weighted = flag(json!({
+            "on": true,
+            "fallthrough": {"rollout": {"variations": [
+                {"variation": 1, "weight": 0},
+                {"variation": 0, "weight": 100000}
+            ]}}
+        }));
+        assert!(launchdarkly_rolled_out(&weighted, "production"));
+
+        for production in [
+            json!({"on": false, "fallthrough": {"variation": 0}}),
+            json!({"on": true, "fallthrough": {"variation": 1}}),
+            json!({"on": true, "rules": [{"variation": 1}], "fallthrough": {"variation": 0}}),
+            json!({"on": true, "fallthrough": {"rollout": {"variations": [
+                {"variation": 0, "weight": 50000},
+                {"variation": 1, "weight": 50000}
+            ]}}}),
+        ] {
+            assert!(!launchdarkly_rolled_out(&flag(production), "production"));
+        }
+    }
+
+    #[test]
+    fn test_unleash_strategy_for_everyone() {
+        let flag = |enabled: bool, strategy: Value| {
+            json!({
+                "name": "new-checkout",
+                "environments": [
+                    {"name": "development", "enabled": false, "strategies": []},
+                    {"name": "production", "enabled": enabled, "strategies": [strategy]}
+                ]
+            })
+        };
+        let default = json!({"name": "default", "constraints": [], "parameters": {}});
+        let full = json!({"name": "flexibleRollout", "parameters": {"rollout": "100"}});
+        let half = json!({"name": "flexibleRollout", "parameters": {"rollout": "50"}});
+        let constrained = json!({
+            "name": "default",
+            "constraints": [{"contextName": "userId", "operator": "IN", "values": ["1"]}]
+        });
+
+        assert!(unleash_rolled_out(
+            &flag(true, default.clone()),
+            "production"
+        ));
+        assert!(unleash_rolled_out(&flag(true, full), "production"));
+        assert!(!unleash_rolled_out(
+            &flag(true, default.clone()),
+            "development"
+        ));
+        assert!(!unleash_rolled_out(&flag(false, default), "production"));
+        assert!(!unleash_rolled_out(&flag(true, half), "production"));
+        assert!(!unleash_rolled_out(&flag(true, constrained), "production"));
+    }
+
+    #[test]
+    fn test_provider_names() {
+        assert_eq!(
+            Provider::from_name("launchdarkly"),
+            Some(Provider::LaunchDarkly)
+        );
+        assert_eq!(Provider::from_name("unleash"), Some(Provider::Unleash));
+        assert_eq!(Provider::from_name("flipper"), None);
+    }
+}
@@ -0,0 +1,358 @@
+//! Grade badges for READMEs and CI artifacts.
+//!
+//! Badges use the shields.io endpoint schema
+//! (<https://shields.io/badges/endpoint-badge>), so a committed or published
+//! `badge.json` can be rendered with
+//! `https://img.shields.io/endpoint?url=<raw badge.json url>`. A static SVG
+//! can be written alongside for hosts that can't reach shields.io.
+//!
+//! For pasting into a README or wiki page, [`markdown_block`] renders the
+//! headline numbers as static shields.io badges and a summary table, each
+//! linking to its section of the markdown score document.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{score_to_grade, Analysis};
+use crate::config::BudgetMetric;
+use crate::core::Result;
+use crate::output::{escape_xml, markdown_anchor};
+
+/// A shields.io endpoint badge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Badge {
+    /// Always 1.
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    /// Named shields.io color.
+    pub color: String,
+}
+
+impl Badge {
+    /// Build a badge for a 0-100 score, e.g. `B (84)`.
+    pub fn for_score(label: impl Into<String>, score: f64) -> Self {
+        let grade = score_to_grade(score);
+        Self {
+            schema_version: 1,
+            label: label.into(),
+            message: format!("{} ({:.0})", grade, score),
+            color: grade_color(&grade).to_string(),
+        }
+    }
+
+    /// Static shields.io image URL showing this badge.
+    pub fn static_url(&self) -> String {
+        format!(
+            "https://img.shields.io/badge/{}-{}-{}",
+            shields_escape(&self.label),
+            shields_escape(&self.message),
+            self.color
+        )
+    }
+
+    /// Render a flat-style SVG equivalent to the shields.io rendering.
+    pub fn to_svg(&self) -> String {
+        let label_width = text_width(&self.label);
+        let message_width = text_width(&self.message);
+        let width = label_width + message_width;
+        let label = escape_xml(&self.label);
+        let message = escape_xml(&self.message);
+        let fill = color_hex(&self.color);
+
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{fill}"/><rect width="{width}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="14">{label}</text><text x="{message_x}" y="14">{message}</text></g></svg>
+"##,
+            label_x = label_width / 2,
+            message_x = label_width + message_width / 2,
+        )
+    }
+}
+
+/// Badge for the overall grade.
+pub fn overall_badge(analysis: &Analysis, label: &str) -> Badge {
+    Badge::for_score(label, analysis.overall_score)
+}
+
+/// One badge per score component, sorted by component name.
+pub fn component_badges(analysis: &Analysis, label: &str) -> Vec<(String, Badge)> {
+    let mut names: Vec<&String> = analysis.components.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let component = &analysis.components[name];
+            (
+                name.clone(),
+                Badge::for_score(format!("{label} {name}"), component.score),
+            )
+        })
+        .collect()
+}
+
+/// Write the overall badge to `output` and, optionally, per-component badges
+/// next to it (`badge.json` -> `badge-complexity.json`) and SVG renderings
+/// (`badge.svg`). Returns the written paths.
+pub fn write_badges(
+    analysis: &Analysis,
+    output: &Path,
+    label: &str,
+    components: bool,
+    svg: bool,
+) -> Result<Vec<PathBuf>> {
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut badges = vec![(output.to_path_buf(), overall_badge(analysis, label))];
+    if components {
+        let stem = output
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "badge".to_string());
+        for (name, badge) in component_badges(analysis, label) {
+            badges.push((output.with_file_name(format!("{stem}-{name}.json")), badge));
+        }
+    }
+
+    let mut written = Vec::new();
+    for (path, badge) in badges {
+        std::fs::write(&path, serde_json::to_string_pretty(&badge)? + "\n")?;
+        written.push(path.clone());
+        if svg {
+            let svg_path = path.with_extension("svg");
+            std::fs::write(&svg_path, badge.to_svg())?;
+            written.push(svg_path);
+        }
+    }
+    Ok(written)
+}
+
+/// Badges and a summary table of the grade, duplication, p90 complexity,
+/// and SATD count, for the top of a README or wiki page.
+///
+/// Each entry links to its section of the score document rendered with
+/// [`crate::output::format_markdown_anchored`] under the same `anchor`
+/// prefix. Metrics whose analyzer did not run are left out. The block is
+/// wrapped in `omen:badges` comments so tooling can replace it in place.
+pub fn markdown_block(analysis: &Analysis, anchor: &str) -> String {
+    let components = markdown_anchor(anchor, "components");
+    let mut entries = vec![(
+        "Grade",
+        Badge::for_score("grade", analysis.overall_score),
+        components.clone(),
+    )];
+    let metrics = [
+        (BudgetMetric::Duplication, "Duplication", "duplication"),
+        (BudgetMetric::ComplexityP90, "Complexity p90", "complexity"),
+        (BudgetMetric::SatdItems, "SATD items", "satd"),
+    ];
+    for (metric, title, component) in metrics {
+        let Some(&value) = analysis.metrics.get(&metric) else {
+            continue;
+        };
+        let message = match metric {
+            BudgetMetric::Duplication => format!("{:.1}%", value),
+            _ => format!("{:.0}", value),
+        };
+        // Colored by the component's grade, like the component badges.
+        let color = analysis
+            .components
+            .get(component)
+            .map_or("lightgrey", |c| grade_color(&score_to_grade(c.score)));
+        let badge = Badge {
+            schema_version: 1,
+            label: title.to_lowercase(),
+            message,
+            color: color.to_string(),
+        };
+        entries.push((title, badge, markdown_anchor(&components, component)));
+    }
+
+    let mut out = String::from("<!-- omen:badges -->\n");
+    let images: Vec<String> = entries
+        .iter()
+        .map(|(title, badge, id)| format!("[![{}]({})](#{})", title, badge.static_url(), id))
+        .collect();
+    out.push_str(&images.join(" "));
+    out.push_str("\n\n");
+
+    let titles: Vec<&str> = entries.iter().map(|(title, _, _)| *title).collect();
+    out.push_str(&format!("| {} |\n", titles.join(" | ")));
+    out.push_str(&format!("|{}\n", "---|".repeat(entries.len())));
+    let cells: Vec<String> = entries
+        .iter()
+        .map(|(_, badge, id)| format!("[{}](#{})", badge.message, id))
+        .collect();
+    out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    out.push_str("<!-- /omen:badges -->\n\n");
+    out
+}
+
+/// Escape text for a static shields.io badge path segment.
+fn shields_escape(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '-' => out.push_str("--"),
+            '_' => out.push_str("__"),
+            ' ' => out.push_str("%20"),
+            '%' => out.push_str("%25"),
+            '(' => out.push_str("%28"),
+            ')' => out.push_str("%29"),
+            '/' => out.push_str("%2F"),
+            '#' => out.push_str("%23"),
+            '?' => out.push_str("%3F"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn grade_color(grade: &str) -> &'static str {
+    match grade {
+        "A" => "brightgreen",
+        "B" => "green",
+        "C" => "yellow",
+        "D" => "orange",
+        _ => "red",
+    }
+}
+
+fn color_hex(color: &str) -> &'static str {
+    match color {
+        "brightgreen" => "#4c1",
+        "green" => "#97ca00",
+        "yellow" => "#dfb317",
+        "orange" => "#fe7d37",
+        _ => "#e05d44",
+    }
+}
+
+/// Approximate rendered width of 11px Verdana text plus padding.
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score::{AnalysisSummary, ScoreComponent, ScoreWeights};
+    use std::collections::{BTreeMap, HashMap};
+
+    fn sample_analysis() -> Analysis {
+        let mut components = HashMap::new();
+        components.insert(
+            "complexity".to_string(),
+            ScoreComponent {
+                score: 92.4,
+                weight: 1.0,
+                details: String::new(),
+            },
+        );
+        components.insert(
+            "duplicates".to_string(),
+            ScoreComponent {
+                score: 55.0,
+                weight: 0.8,
+                details: String::new(),
+            },
+        );
+        Analysis {
+            overall_score: 84.2,
+            grade: "B".to_string(),
+            components,
+            summary: AnalysisSummary::default(),
+            weights: ScoreWeights::default(),
+            packages: Vec::new(),
+            metrics: BTreeMap::new(),
+            budgets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_overall_badge_is_shields_endpoint_json() {
+        let badge = overall_badge(&sample_analysis(), "omen");
+        let json = serde_json::to_value(&badge).unwrap();
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["label"], "omen");
+        assert_eq!(json["message"], "B (84)");
+        assert_eq!(json["color"], "green");
+    }
+
+    #[test]
+    fn test_grade_colors() {
+        assert_eq!(Badge::for_score("x", 95.0).color, "brightgreen");
+        assert_eq!(Badge::for_score("x", 75.0).color, "yellow");
+        assert_eq!(Badge::for_score("x", 65.0).color, "orange");
+        assert_eq!(Badge::for_score("x", 10.0).color, "red");
+    }
+
+    #[test]
+    fn test_component_badges_sorted() {
+        let badges = component_badges(&sample_analysis(), "omen");
+        let names: Vec<&str> = badges.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["complexity", "duplicates"]);
+        assert_eq!(badges[0].1.label, "omen complexity");
+        assert_eq!(badges[0].1.message, "A (92)");
+        assert_eq!(badges[1].1.message, "F (55)");
+    }
+
+    #[test]
+    fn test_svg_escapes_and_embeds_message() {
+        let badge = Badge::for_score("a<b", 84.0);
+        let svg = badge.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("a&lt;b"));
+        assert!(svg.contains("B (84)"));
+        assert!(svg.contains("#97ca00"));
+    }
+
+    #[test]
+    fn test_static_url_escapes_shields_syntax() {
+        let badge = Badge::for_score("my-repo grade", 84.0);
+        assert_eq!(
+            badge.static_url(),
+            "https://img.shields.io/badge/my--repo%20grade-B%20%2884%29-green"
+        );
+    }
+
+    #[test]
+    fn test_markdown_block_links_into_document() {
+        let mut analysis = sample_analysis();
+        analysis.metrics.insert(BudgetMetric::Duplication, 12.345);
+        analysis.metrics.insert(BudgetMetric::ComplexityP90, 9.0);
+
+        let block = markdown_block(&analysis, "omen");
+
+        assert!(block.starts_with("<!-- omen:badges -->\n[![Grade](https://img.shields.io/badge/"));
+        assert!(block.contains("](#omen-components)"));
+        assert!(block.contains("| Grade | Duplication | Complexity p90 |\n|---|---|---|\n"));
+        assert!(block.contains("[12.3%](#omen-components-duplication)"));
+        assert!(block.contains("[9](#omen-components-complexity)"));
+        // No SATD metric, no SATD entry
+        assert!(!block.contains("SATD"));
+        // Complexity is colored by its component grade; duplication has none
+        assert!(block.contains("complexity%20p90-9-brightgreen"));
+        assert!(block.contains("duplication-12.3%25-lightgrey"));
+        assert!(block.ends_with("<!-- /omen:badges -->\n\n"));
+    }
+
+    #[test]
+    fn test_write_badges_with_components_and_svg() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("badges/badge.json");
+        let written = write_badges(&sample_analysis(), &output, "omen", true, true).unwrap();
+
+        assert_eq!(written.len(), 6);
+        assert!(dir.path().join("badges/badge.svg").exists());
+        assert!(dir.path().join("badges/badge-complexity.json").exists());
+        assert!(dir.path().join("badges/badge-duplicates.svg").exists());
+
+        let overall: Badge =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(overall.message, "B (84)");
+    }
+}
@@ -0,0 +1,260 @@
+//! Metric budgets with burn-down projections.
+//!
+//! A budget caps a raw metric such as the SATD item count, or sets a floor
+//! for the overall score, optionally with a deadline. The score trend
+//! history gives each metric a slope, from which the date the budget will
+//! be reached is projected.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Budget, BudgetMetric};
+use crate::core::{Error, Result};
+use crate::report::TrendPoint;
+
+/// Progress of one budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    pub metric: BudgetMetric,
+    pub target: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<NaiveDate>,
+    /// Latest value; `None` when the analyzer behind the metric did not run.
+    pub current: Option<f64>,
+    /// Change per 30 days over the trend history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<f64>,
+    /// Date the trend reaches the target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub projected: Option<NaiveDate>,
+    pub state: BudgetState,
+}
+
+/// Where a budget stands.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetState {
+    /// The current value is within budget.
+    Met,
+    /// The trend reaches the target before the deadline, or there is none.
+    OnTrack,
+    /// The trend reaches the target only after the deadline.
+    Behind,
+    /// The trend is flat or moving away from the target.
+    Stalled,
+    /// The metric has no current value.
+    NoData,
+}
+
+/// Evaluate `budgets` against the `current` metric values and the trend
+/// `history`, as of `today`.
+pub fn evaluate(
+    budgets: &[Budget],
+    current: &BTreeMap<BudgetMetric, f64>,
+    history: &[TrendPoint],
+    today: NaiveDate,
+) -> Result<Vec<BudgetStatus>> {
+    budgets
+        .iter()
+        .map(|budget| {
+            let by = budget.by.as_deref().map(parse_deadline).transpose()?;
+            Ok(status(
+                budget,
+                by,
+                current.get(&budget.metric).copied(),
+                history,
+                today,
+            ))
+        })
+        .collect()
+}
+
+fn status(
+    budget: &Budget,
+    by: Option<NaiveDate>,
+    current: Option<f64>,
+    history: &[TrendPoint],
+    today: NaiveDate,
+) -> BudgetStatus {
+    let mut samples: Vec<(NaiveDate, f64)> = history
+        .iter()
+        .filter_map(|p| {
+            let date = NaiveDate::parse_from_str(&p.date, "%Y-%m-%d").ok()?;
+            Some((date, *p.metrics.get(&budget.metric)?))
+        })
+        .filter(|(date, _)| *date < today)
+        .collect();
+    if let Some(value) = current {
+        samples.push((today, value));
+    }
+    let per_day = slope_per_day(&samples);
+
+    let remaining = current.map(|value| budget.target - value);
+    let met = remaining.map(|gap| {
+        if budget.metric.higher_is_better() {
+            gap <= 0.0
+        } else {
+            gap >= 0.0
+        }
+    });
+    // Days until the trend line closes the gap; only when it is closing.
+    let projected = match (met, remaining, per_day) {
+        (Some(false), Some(gap), Some(slope)) if gap * slope > 0.0 => {
+            Some(today + Duration::days((gap / slope).ceil() as i64))
+        }
+        _ => None,
+    };
+
+    let state = match (met, projected) {
+        (None, _) => BudgetState::NoData,
+        (Some(true), _) => BudgetState::Met,
+        (Some(false), None) => BudgetState::Stalled,
+        (Some(false), Some(date)) if by.is_some_and(|by| date > by) => BudgetState::Behind,
+        (Some(false), Some(_)) => BudgetState::OnTrack,
+    };
+
+    BudgetStatus {
+        metric: budget.metric,
+        target: budget.target,
+        by,
+        current,
+        trend: per_day.map(|slope| slope * 30.0),
+        projected,
+        state,
+    }
+}
+
+/// Least-squares slope of the values over calendar days.
+fn slope_per_day(samples: &[(NaiveDate, f64)]) -> Option<f64> {
+    let first = samples.first()?.0;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|(date, value)| ((*date - first).num_days() as f64, *value))
+        .collect();
+    let n = points.len() as f64;
+    let x_mean = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let y_mean = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (x, y) in &points {
+        numerator += (x - x_mean) * (y - y_mean);
+        denominator += (x - x_mean) * (x - x_mean);
+    }
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+/// Parse a deadline: `YYYY-MM-DD`, or `YYYY-Qn` for the end of a quarter.
+fn parse_deadline(value: &str) -> Result<NaiveDate> {
+    let invalid = || {
+        Error::config(format!(
+            "Invalid budget deadline '{value}': expected YYYY-MM-DD or YYYY-Qn"
+        ))
+    };
+    if let Some((year, quarter)) = value.split_once("-Q") {
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let quarter: u32 = quarter.parse().map_err(|_| invalid())?;
+        if !(1..=4).contains(&quarter) {
+            return Err(invalid());
+        }
+        // The day before the next quarter starts.
+        let next = if quarter == 4 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, quarter * 3 + 1, 1)
+        };
+        return next.and_then(|d| d.pred_opt()).ok_or_else(invalid);
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| invalid())
+}
+
+/// Today's date, for evaluating budgets.
+pub fn today() -> NaiveDate {
+    chrono::Utc::now().date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(date: &str, metric: BudgetMetric, value: f64) -> TrendPoint {
+        TrendPoint {
+            date: date.to_string(),
+            score: 0,
+            components: Default::default(),
+            notable_commits: vec![],
+            pipeline_complexity: None,
+            metrics: BTreeMap::from([(metric, value)]),
+        }
+    }
+
+    fn budget(metric: BudgetMetric, target: f64, by: Option<&str>) -> Budget {
+        Budget {
+            metric,
+            target,
+            by: by.map(str::to_string),
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_deadline() {
+        assert_eq!(parse_deadline("2026-09-30").unwrap(), date("2026-09-30"));
+        assert_eq!(parse_deadline("2026-Q3").unwrap(), date("2026-09-30"));
+        assert_eq!(parse_deadline("2026-Q4").unwrap(), date("2026-12-31"));
+        assert!(parse_deadline("2026-Q5").is_err());
+        assert!(parse_deadline("next year").is_err());
+    }
+
+    #[test]
+    fn test_projects_burn_down_date() {
+        let today = date("2026-03-01");
+        // SATD items fall by one a day: 220 -> 190 over the last 30 days.
+        let history = vec![
+            point("2026-01-30", BudgetMetric::SatdItems, 220.0),
+            point("2026-02-14", BudgetMetric::SatdItems, 205.0),
+        ];
+        let current = BTreeMap::from([
+            (BudgetMetric::SatdItems, 190.0),
+            (BudgetMetric::Score, 85.0),
+        ]);
+        let budgets = vec![
+            budget(BudgetMetric::SatdItems, 150.0, Some("2026-Q2")),
+            budget(BudgetMetric::SatdItems, 150.0, Some("2026-03-15")),
+            budget(BudgetMetric::Score, 80.0, None),
+            budget(BudgetMetric::Duplication, 5.0, None),
+        ];
+
+        let statuses = evaluate(&budgets, &current, &history, today).unwrap();
+
+        let on_track = &statuses[0];
+        assert_eq!(on_track.state, BudgetState::OnTrack);
+        assert_eq!(on_track.trend, Some(-30.0));
+        assert_eq!(on_track.projected, Some(date("2026-04-10")));
+        assert_eq!(on_track.by, Some(date("2026-06-30")));
+        assert_eq!(statuses[1].state, BudgetState::Behind);
+        assert_eq!(statuses[2].state, BudgetState::Met, "score is a floor");
+        assert_eq!(statuses[2].projected, None);
+        assert_eq!(statuses[3].state, BudgetState::NoData);
+    }
+
+    #[test]
+    fn test_diverging_trend_is_stalled() {
+        let today = date("2026-03-01");
+        let history = vec![point("2026-02-01", BudgetMetric::Score, 78.0)];
+        let current = BTreeMap::from([(BudgetMetric::Score, 74.0)]);
+        let budgets = vec![budget(BudgetMetric::Score, 80.0, None)];
+
+        let statuses = evaluate(&budgets, &current, &history, today).unwrap();
+        assert_eq!(statuses[0].state, BudgetState::Stalled);
+        assert_eq!(statuses[0].projected, None);
+
+        let bad = vec![budget(BudgetMetric::Score, 80.0, Some("soon"))];
+        assert!(evaluate(&bad, &current, &history, today).is_err());
+    }
+}
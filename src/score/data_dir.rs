@@ -0,0 +1,162 @@
+//! Score computation from analyzer results already written to disk.
+
+use std::path::Path;
+
+use crate::config::BudgetMetric;
+use crate::core::Result;
+
+use super::{
+    calculate_cohesion_score, calculate_complexity_score, calculate_coupling_score,
+    calculate_duplicates_score, calculate_satd_score, calculate_smells_score, calculate_tdg_score,
+    Analysis, ScoreAccumulator, ScoreWeights,
+};
+
+/// Compute the health score from pre-generated JSON files (avoids re-running analyzers).
+///
+/// Reads analyzer results from the given directory and computes the composite score.
+/// Used by `report generate` to avoid redundantly re-running all sub-analyzers.
+pub fn compute_from_data_dir(
+    data_dir: &Path,
+    file_count: usize,
+    weights: &ScoreWeights,
+) -> Result<Analysis> {
+    let mut acc = ScoreAccumulator::default();
+
+    macro_rules! load_and_score {
+        ($file:expr, $name:expr, $weight:expr, $type:ty, $score_fn:expr, $details_fn:expr
+         $(, $metric:expr => $value:expr)?) => {
+            if $weight > 0.0 {
+                let path = data_dir.join($file);
+                if let Ok(content) = std::fs::read_to_string(&path) {
+                    if let Ok(result) = serde_json::from_str::<$type>(&content) {
+                        let score = $score_fn(&result);
+                        let details = $details_fn(&result);
+                        acc.add($name, $weight, score, details);
+                        $(acc.metric($metric, $value(&result));)?
+                    }
+                }
+            }
+        };
+    }
+
+    // Complexity: skip when no functions detected to avoid false 100 score.
+    if weights.complexity > 0.0 {
+        let path = data_dir.join("complexity.json");
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            if let Ok(result) =
+                serde_json::from_str::<crate::analyzers::complexity::Analysis>(&content)
+            {
+                if result.summary.total_functions > 0 {
+                    let score = calculate_complexity_score(&result);
+                    let details = format!(
+                        "Analyzed {} files, avg cyclomatic: {:.1}",
+                        result.files.len(),
+                        result.summary.avg_cyclomatic
+                    );
+                    acc.add("complexity", weights.complexity, score, details);
+                    acc.metric(
+                        BudgetMetric::ComplexityP90,
+                        result.summary.p90_cyclomatic as f64,
+                    );
+                }
+            }
+        }
+    }
+
+    load_and_score!(
+        "satd.json",
+        "satd",
+        weights.satd,
+        crate::analyzers::satd::Analysis,
+        |r: &crate::analyzers::satd::Analysis| calculate_satd_score(r, file_count),
+        |r: &crate::analyzers::satd::Analysis| {
+            let high_priority = r
+                .items
+                .iter()
+                .filter(|i| {
+                    matches!(
+                        i.severity,
+                        crate::analyzers::satd::Severity::Critical
+                            | crate::analyzers::satd::Severity::High
+                    )
+                })
+                .count();
+            format!(
+                "Found {} debt items ({} high priority)",
+                r.items.len(),
+                high_priority
+            )
+        },
+        BudgetMetric::SatdItems => |r: &crate::analyzers::satd::Analysis| r.items.len() as f64
+    );
+
+    load_and_score!(
+        "duplicates.json",
+        "duplication",
+        weights.duplicates,
+        crate::analyzers::duplicates::Analysis,
+        calculate_duplicates_score,
+        |r: &crate::analyzers::duplicates::Analysis| format!(
+            "Found {} clones, {:.1}% duplication",
+            r.summary.total_clones,
+            r.summary.duplication_ratio * 100.0
+        ),
+        BudgetMetric::Duplication =>
+            |r: &crate::analyzers::duplicates::Analysis| r.summary.duplication_ratio * 100.0
+    );
+
+    load_and_score!(
+        "cohesion.json",
+        "cohesion",
+        weights.cohesion,
+        crate::analyzers::cohesion::Analysis,
+        calculate_cohesion_score,
+        |r: &crate::analyzers::cohesion::Analysis| format!(
+            "Analyzed {} classes, avg LCOM: {:.1}; {} modules, avg module LCOM: {:.1}",
+            r.summary.total_classes,
+            r.summary.avg_lcom,
+            r.summary.total_modules,
+            r.summary.avg_module_lcom
+        )
+    );
+
+    load_and_score!(
+        "tdg.json",
+        "tdg",
+        weights.tdg,
+        crate::analyzers::tdg::Analysis,
+        calculate_tdg_score,
+        |r: &crate::analyzers::tdg::Analysis| format!(
+            "Analyzed {} files, avg grade: {:?}",
+            r.total_files, r.average_grade
+        )
+    );
+
+    load_and_score!(
+        "graph.json",
+        "coupling",
+        weights.coupling,
+        crate::analyzers::graph::Analysis,
+        calculate_coupling_score,
+        |r: &crate::analyzers::graph::Analysis| format!(
+            "{} nodes, {} cycles, avg degree: {:.1}",
+            r.summary.total_nodes, r.summary.cycle_count, r.summary.avg_degree
+        )
+    );
+
+    load_and_score!(
+        "smells.json",
+        "smells",
+        weights.smells,
+        crate::analyzers::smells::Analysis,
+        calculate_smells_score,
+        |r: &crate::analyzers::smells::Analysis| format!(
+            "{} smells ({} critical, {} high)",
+            r.summary.total_smells, r.summary.critical_count, r.summary.high_count
+        ),
+        BudgetMetric::Smells =>
+            |r: &crate::analyzers::smells::Analysis| r.summary.total_smells as f64
+    );
+
+    acc.into_analysis(file_count, weights)
+}
@@ -1,19 +1,29 @@
 //! Composite health score analyzer.
 
+pub mod badge;
+pub mod budget;
+mod data_dir;
+mod monorepo;
 pub mod trend;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::BudgetMetric;
 use crate::core::{AnalysisContext, Analyzer as AnalyzerTrait, Result};
 
+pub use crate::config::ScoreWeights;
+pub use budget::BudgetStatus;
+pub use data_dir::compute_from_data_dir;
+pub use monorepo::PackageScore;
 pub use trend::{analyze_trend, default_sample_count};
 
 /// Score analyzer - calculates composite health score.
 #[derive(Default)]
 pub struct Analyzer {
-    weights: ScoreWeights,
+    /// Overrides `score.weights` from the config.
+    weights: Option<ScoreWeights>,
 }
 
 impl Analyzer {
@@ -22,7 +32,14 @@ impl Analyzer {
     }
 
     pub fn with_weights(weights: ScoreWeights) -> Self {
-        Self { weights }
+        Self {
+            weights: Some(weights),
+        }
+    }
+
+    /// Weights in effect for `ctx`.
+    fn weights<'a>(&'a self, ctx: &'a AnalysisContext<'_>) -> &'a ScoreWeights {
+        self.weights.as_ref().unwrap_or(&ctx.config.score.weights)
     }
 }
 
@@ -38,15 +55,32 @@ impl AnalyzerTrait for Analyzer {
     }
 
     fn analyze(&self, ctx: &AnalysisContext<'_>) -> Result<Self::Output> {
+        if let Some(weight) = ctx.config.score.monorepo {
+            let packages = crate::analyzers::graph::group_by_package(ctx.files.files(), ctx.root);
+            if packages.len() > 1 {
+                return self.analyze_packages(ctx, packages, weight);
+            }
+        }
+        self.score_components(ctx)
+            .into_analysis(ctx.files.files().len(), self.weights(ctx))
+    }
+}
+
+impl Analyzer {
+    /// Run every weighted analyzer over `ctx` and collect component scores.
+    fn score_components(&self, ctx: &AnalysisContext<'_>) -> ScoreAccumulator {
+        let weights = self.weights(ctx);
         let mut acc = ScoreAccumulator::default();
 
         macro_rules! run_analyzer {
-            ($name:expr, $weight:expr, $analyzer:expr, $score_fn:expr, $details:expr) => {
+            ($name:expr, $weight:expr, $analyzer:expr, $score_fn:expr, $details:expr
+             $(, $metric:expr => $value:expr)?) => {
                 if $weight > 0.0 {
                     if let Ok(result) = $analyzer.analyze(ctx) {
                         let score = $score_fn(&result);
                         let details = $details(&result);
                         acc.add($name, $weight, score, details);
+                        $(acc.metric($metric, $value(&result));)?
                     }
                 }
             };
@@ -54,13 +88,17 @@ impl AnalyzerTrait for Analyzer {
 
         // Complexity needs inline handling: skip when no functions are detected,
         // otherwise p90_cyclomatic == 0 produces a false perfect score of 100.
-        if self.weights.complexity > 0.0 {
+        if weights.complexity > 0.0 {
             if let Ok(result) = crate::analyzers::complexity::Analyzer::new().analyze(ctx) {
                 if result.summary.total_functions > 0 {
                     let score = calculate_complexity_score(&result);
+                    acc.metric(
+                        BudgetMetric::ComplexityP90,
+                        result.summary.p90_cyclomatic as f64,
+                    );
                     acc.add(
                         "complexity",
-                        self.weights.complexity,
+                        weights.complexity,
                         score,
                         format!(
                             "Analyzed {} files, p90 cyclomatic: {}, avg: {:.1}",
@@ -74,7 +112,7 @@ impl AnalyzerTrait for Analyzer {
         }
 
         // SATD needs file_count from ctx, so handle inline
-        if self.weights.satd > 0.0 {
+        if weights.satd > 0.0 {
             if let Ok(result) = crate::analyzers::satd::Analyzer::new().analyze(ctx) {
                 let score = calculate_satd_score(&result, ctx.files.files().len());
                 let high_priority = result
@@ -88,9 +126,10 @@ impl AnalyzerTrait for Analyzer {
                         )
                     })
                     .count();
+                acc.metric(BudgetMetric::SatdItems, result.items.len() as f64);
                 acc.add(
                     "satd",
-                    self.weights.satd,
+                    weights.satd,
                     score,
                     format!(
                         "Found {} debt items ({} high priority)",
@@ -103,7 +142,7 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "deadcode",
-            self.weights.deadcode,
+            weights.deadcode,
             crate::analyzers::deadcode::Analyzer::new(),
             calculate_deadcode_score,
             |r: &crate::analyzers::deadcode::Analysis| format!(
@@ -114,7 +153,7 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "churn",
-            self.weights.churn,
+            weights.churn,
             crate::analyzers::churn::Analyzer::new(),
             calculate_churn_score,
             |r: &crate::analyzers::churn::Analysis| format!(
@@ -125,30 +164,35 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "duplication",
-            self.weights.duplicates,
+            weights.duplicates,
             crate::analyzers::duplicates::Analyzer::new(),
             calculate_duplicates_score,
             |r: &crate::analyzers::duplicates::Analysis| format!(
                 "Found {} clones, {:.1}% duplication",
                 r.summary.total_clones,
                 r.summary.duplication_ratio * 100.0
-            )
+            ),
+            BudgetMetric::Duplication =>
+                |r: &crate::analyzers::duplicates::Analysis| r.summary.duplication_ratio * 100.0
         );
 
         run_analyzer!(
             "cohesion",
-            self.weights.cohesion,
+            weights.cohesion,
             crate::analyzers::cohesion::Analyzer::new(),
             calculate_cohesion_score,
             |r: &crate::analyzers::cohesion::Analysis| format!(
-                "Analyzed {} classes, avg LCOM: {:.1}",
-                r.summary.total_classes, r.summary.avg_lcom
+                "Analyzed {} classes, avg LCOM: {:.1}; {} modules, avg module LCOM: {:.1}",
+                r.summary.total_classes,
+                r.summary.avg_lcom,
+                r.summary.total_modules,
+                r.summary.avg_module_lcom
             )
         );
 
         run_analyzer!(
             "ownership",
-            self.weights.ownership,
+            weights.ownership,
             crate::analyzers::ownership::Analyzer::new(),
             calculate_ownership_score,
             |r: &crate::analyzers::ownership::Analysis| format!(
@@ -159,7 +203,7 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "defect",
-            self.weights.defect,
+            weights.defect,
             crate::analyzers::defect::Analyzer::new(),
             calculate_defect_score,
             |r: &crate::analyzers::defect::Analysis| format!(
@@ -171,7 +215,7 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "tdg",
-            self.weights.tdg,
+            weights.tdg,
             crate::analyzers::tdg::Analyzer::new(),
             calculate_tdg_score,
             |r: &crate::analyzers::tdg::Analysis| format!(
@@ -182,7 +226,7 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "coupling",
-            self.weights.coupling,
+            weights.coupling,
             crate::analyzers::graph::Analyzer::new(),
             calculate_coupling_score,
             |r: &crate::analyzers::graph::Analysis| format!(
@@ -193,22 +237,25 @@ impl AnalyzerTrait for Analyzer {
 
         run_analyzer!(
             "smells",
-            self.weights.smells,
+            weights.smells,
             crate::analyzers::smells::Analyzer::new(),
             calculate_smells_score,
             |r: &crate::analyzers::smells::Analysis| format!(
                 "{} smells ({} critical, {} high)",
                 r.summary.total_smells, r.summary.critical_count, r.summary.high_count
-            )
+            ),
+            BudgetMetric::Smells =>
+                |r: &crate::analyzers::smells::Analysis| r.summary.total_smells as f64
         );
 
-        acc.into_analysis(ctx.files.files().len())
+        acc
     }
 }
 
 #[derive(Default)]
 struct ScoreAccumulator {
     components: HashMap<String, ScoreComponent>,
+    metrics: BTreeMap<BudgetMetric, f64>,
     weighted_sum: f64,
     total_weight: f64,
 }
@@ -227,15 +274,25 @@ impl ScoreAccumulator {
         self.total_weight += weight;
     }
 
-    fn into_analysis(self, files_analyzed: usize) -> Result<Analysis> {
-        let overall_score = if self.total_weight > 0.0 {
+    fn metric(&mut self, metric: BudgetMetric, value: f64) {
+        self.metrics.insert(metric, value);
+    }
+
+    fn overall_score(&self) -> f64 {
+        if self.total_weight > 0.0 {
             self.weighted_sum / self.total_weight
         } else {
             100.0
-        };
+        }
+    }
+
+    fn into_analysis(self, files_analyzed: usize, weights: &ScoreWeights) -> Result<Analysis> {
+        let overall_score = self.overall_score();
         let grade = score_to_grade(overall_score);
         let analyzers_run = self.components.len();
         let critical_issues = count_critical_issues(&self.components);
+        let mut metrics = self.metrics;
+        metrics.insert(BudgetMetric::Score, overall_score);
         Ok(Analysis {
             overall_score,
             grade,
@@ -245,143 +302,14 @@ impl ScoreAccumulator {
                 analyzers_run,
                 critical_issues,
             },
+            weights: weights.clone(),
+            packages: Vec::new(),
+            metrics,
+            budgets: Vec::new(),
         })
     }
 }
 
-/// Compute the health score from pre-generated JSON files (avoids re-running analyzers).
-///
-/// Reads analyzer results from the given directory and computes the composite score.
-/// Used by `report generate` to avoid redundantly re-running all sub-analyzers.
-pub fn compute_from_data_dir(data_dir: &std::path::Path, file_count: usize) -> Result<Analysis> {
-    let weights = ScoreWeights::default();
-    let mut acc = ScoreAccumulator::default();
-
-    macro_rules! load_and_score {
-        ($file:expr, $name:expr, $weight:expr, $type:ty, $score_fn:expr, $details_fn:expr) => {
-            if $weight > 0.0 {
-                let path = data_dir.join($file);
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(result) = serde_json::from_str::<$type>(&content) {
-                        let score = $score_fn(&result);
-                        let details = $details_fn(&result);
-                        acc.add($name, $weight, score, details);
-                    }
-                }
-            }
-        };
-    }
-
-    // Complexity: skip when no functions detected to avoid false 100 score.
-    if weights.complexity > 0.0 {
-        let path = data_dir.join("complexity.json");
-        if let Ok(content) = std::fs::read_to_string(&path) {
-            if let Ok(result) =
-                serde_json::from_str::<crate::analyzers::complexity::Analysis>(&content)
-            {
-                if result.summary.total_functions > 0 {
-                    let score = calculate_complexity_score(&result);
-                    let details = format!(
-                        "Analyzed {} files, avg cyclomatic: {:.1}",
-                        result.files.len(),
-                        result.summary.avg_cyclomatic
-                    );
-                    acc.add("complexity", weights.complexity, score, details);
-                }
-            }
-        }
-    }
-
-    load_and_score!(
-        "satd.json",
-        "satd",
-        weights.satd,
-        crate::analyzers::satd::Analysis,
-        |r: &crate::analyzers::satd::Analysis| calculate_satd_score(r, file_count),
-        |r: &crate::analyzers::satd::Analysis| {
-            let high_priority = r
-                .items
-                .iter()
-                .filter(|i| {
-                    matches!(
-                        i.severity,
-                        crate::analyzers::satd::Severity::Critical
-                            | crate::analyzers::satd::Severity::High
-                    )
-                })
-                .count();
-            format!(
-                "Found {} debt items ({} high priority)",
-                r.items.len(),
-                high_priority
-            )
-        }
-    );
-
-    load_and_score!(
-        "duplicates.json",
-        "duplication",
-        weights.duplicates,
-        crate::analyzers::duplicates::Analysis,
-        calculate_duplicates_score,
-        |r: &crate::analyzers::duplicates::Analysis| format!(
-            "Found {} clones, {:.1}% duplication",
-            r.summary.total_clones,
-            r.summary.duplication_ratio * 100.0
-        )
-    );
-
-    load_and_score!(
-        "cohesion.json",
-        "cohesion",
-        weights.cohesion,
-        crate::analyzers::cohesion::Analysis,
-        calculate_cohesion_score,
-        |r: &crate::analyzers::cohesion::Analysis| format!(
-            "Analyzed {} classes, avg LCOM: {:.1}",
-            r.summary.total_classes, r.summary.avg_lcom
-        )
-    );
-
-    load_and_score!(
-        "tdg.json",
-        "tdg",
-        weights.tdg,
-        crate::analyzers::tdg::Analysis,
-        calculate_tdg_score,
-        |r: &crate::analyzers::tdg::Analysis| format!(
-            "Analyzed {} files, avg grade: {:?}",
-            r.total_files, r.average_grade
-        )
-    );
-
-    load_and_score!(
-        "graph.json",
-        "coupling",
-        weights.coupling,
-        crate::analyzers::graph::Analysis,
-        calculate_coupling_score,
-        |r: &crate::analyzers::graph::Analysis| format!(
-            "{} nodes, {} cycles, avg degree: {:.1}",
-            r.summary.total_nodes, r.summary.cycle_count, r.summary.avg_degree
-        )
-    );
-
-    load_and_score!(
-        "smells.json",
-        "smells",
-        weights.smells,
-        crate::analyzers::smells::Analysis,
-        calculate_smells_score,
-        |r: &crate::analyzers::smells::Analysis| format!(
-            "{} smells ({} critical, {} high)",
-            r.summary.total_smells, r.summary.critical_count, r.summary.high_count
-        )
-    );
-
-    acc.into_analysis(file_count)
-}
-
 fn calculate_complexity_score(result: &crate::analyzers::complexity::Analysis) -> f64 {
     // Scores p90 cyclomatic complexity (not average, which is skewed by outliers).
     //
@@ -480,8 +408,13 @@ fn calculate_duplicates_score(result: &crate::analyzers::duplicates::Analysis) -
 
 fn calculate_cohesion_score(result: &crate::analyzers::cohesion::Analysis) -> f64 {
     // Lower LCOM (Lack of Cohesion of Methods) = better cohesion = higher score
-    // LCOM typically 0-10+
-    let lcom = result.summary.avg_lcom;
+    // LCOM typically 0-10+. Codebases without classes are scored on module
+    // (free function) cohesion instead.
+    let lcom = if result.summary.total_classes == 0 {
+        result.summary.avg_module_lcom
+    } else {
+        result.summary.avg_lcom
+    };
     if lcom <= 1.0 {
         100.0
     } else if lcom <= 2.0 {
@@ -656,6 +589,18 @@ pub struct Analysis {
     pub grade: String,
     pub components: HashMap<String, ScoreComponent>,
     pub summary: AnalysisSummary,
+    /// Weights in effect, including those of components that did not run.
+    #[serde(default)]
+    pub weights: ScoreWeights,
+    /// Per-package scores when `score.monorepo` is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<PackageScore>,
+    /// Raw values of the metrics budgets can target.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<BudgetMetric, f64>,
+    /// Progress of the budgets configured in `score.budgets`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budgets: Vec<BudgetStatus>,
 }
 
 impl Analysis {
@@ -689,42 +634,6 @@ pub struct AnalysisSummary {
     pub critical_issues: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ScoreWeights {
-    pub complexity: f64,
-    pub satd: f64,
-    pub deadcode: f64,
-    pub churn: f64,
-    pub duplicates: f64,
-    pub defect: f64,
-    pub ownership: f64,
-    pub cohesion: f64,
-    pub tdg: f64,
-    pub coupling: f64,
-    pub smells: f64,
-}
-
-impl Default for ScoreWeights {
-    fn default() -> Self {
-        // Weights based on 3.x report component importance:
-        // Complexity 25%, Duplication 20%, Cohesion 15%, TDG 15%,
-        // Known Debt 10%, Coupling 10%, Smells 5%
-        Self {
-            complexity: 1.0, // 25% - highest priority
-            duplicates: 0.8, // 20%
-            cohesion: 0.6,   // 15%
-            tdg: 0.6,        // 15%
-            satd: 0.4,       // 10%
-            coupling: 0.4,   // 10%
-            smells: 0.2,     // 5%
-            deadcode: 0.0,   // Not in 3.x display
-            churn: 0.0,      // Not in 3.x display
-            defect: 0.0,     // Not in 3.x display
-            ownership: 0.0,  // Not in 3.x display
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -738,7 +647,7 @@ mod tests {
     #[test]
     fn test_analyzer_default() {
         let analyzer = Analyzer::default();
-        assert_eq!(analyzer.weights.complexity, 1.0);
+        assert!(analyzer.weights.is_none(), "weights come from the config");
     }
 
     #[test]
@@ -757,7 +666,40 @@ mod tests {
             smells: 0.5,
         };
         let analyzer = Analyzer::with_weights(weights);
-        assert_eq!(analyzer.weights.complexity, 0.5);
+        assert_eq!(analyzer.weights.unwrap().complexity, 0.5);
+    }
+
+    #[test]
+    fn test_config_weights_flow_into_analysis() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("a.py"), "def f(x):\n    return x\n").unwrap();
+        let mut config = crate::config::Config::default();
+        config.score.weights = ScoreWeights {
+            complexity: 2.0,
+            satd: 0.0,
+            deadcode: 0.0,
+            churn: 0.0,
+            duplicates: 0.0,
+            defect: 0.0,
+            ownership: 0.0,
+            cohesion: 0.0,
+            tdg: 0.0,
+            coupling: 0.0,
+            smells: 0.0,
+        };
+        let files = crate::core::FileSet::from_path(temp.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&files, &config, Some(temp.path()));
+
+        let analysis = Analyzer::default().analyze(&ctx).unwrap();
+        assert_eq!(analysis.components.len(), 1);
+        assert_eq!(analysis.components["complexity"].weight, 2.0);
+        assert_eq!(analysis.weights, config.score.weights);
+
+        // Explicit weights win over the config.
+        let analysis = Analyzer::with_weights(ScoreWeights::default())
+            .analyze(&ctx)
+            .unwrap();
+        assert_eq!(analysis.weights, ScoreWeights::default());
     }
 
     #[test]
@@ -911,8 +853,12 @@ mod tests {
             items: vec![],
             by_category: std::collections::HashMap::new(),
             density: 0.0,
+            duplicates: vec![],
+            comparison: None,
             summary: crate::analyzers::satd::AnalysisSummary {
                 total_items: 0,
+                unique_items: 0,
+                severity_counts: Default::default(),
                 weighted_count: 0.0,
                 density: 0.0,
             },
@@ -926,8 +872,12 @@ mod tests {
             items: vec![],
             by_category: std::collections::HashMap::new(),
             density: 0.0,
+            duplicates: vec![],
+            comparison: None,
             summary: crate::analyzers::satd::AnalysisSummary {
                 total_items: 0,
+                unique_items: 0,
+                severity_counts: Default::default(),
                 weighted_count: 0.0,
                 density: 0.0,
             },
@@ -942,16 +892,26 @@ mod tests {
             items: vec![crate::analyzers::satd::SatdItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                span: Default::default(),
                 marker: "TODO".to_string(),
                 text: "test".to_string(),
                 category: "design".to_string(),
                 severity: crate::analyzers::satd::Severity::Low,
                 weight: 1.0,
+                function: None,
+                prioritization: None,
+                introduced: None,
+                changed: None,
+                age_days: None,
             }],
             by_category: std::collections::HashMap::new(),
             density: 0.01,
+            duplicates: vec![],
+            comparison: None,
             summary: crate::analyzers::satd::AnalysisSummary {
                 total_items: 1,
+                unique_items: 1,
+                severity_counts: Default::default(),
                 weighted_count: 1.0,
                 density: 0.01,
             },
@@ -967,19 +927,29 @@ mod tests {
             .map(|i| crate::analyzers::satd::SatdItem {
                 file: "test.rs".to_string(),
                 line: i,
+                span: Default::default(),
                 marker: "TODO".to_string(),
                 text: "test".to_string(),
                 category: "design".to_string(),
                 severity: crate::analyzers::satd::Severity::Low,
                 weight: 1.0,
+                function: None,
+                prioritization: None,
+                introduced: None,
+                changed: None,
+                age_days: None,
             })
             .collect();
         let result = crate::analyzers::satd::Analysis {
             items,
             by_category: std::collections::HashMap::new(),
             density: 4.0,
+            duplicates: vec![],
+            comparison: None,
             summary: crate::analyzers::satd::AnalysisSummary {
                 total_items: 20,
+                unique_items: 20,
+                severity_counts: Default::default(),
                 weighted_count: 20.0,
                 density: 4.0,
             },
@@ -992,11 +962,13 @@ mod tests {
     fn test_calculate_deadcode_score_none() {
         let result = crate::analyzers::deadcode::Analysis {
             items: vec![],
+            test_only: vec![],
             summary: crate::analyzers::deadcode::AnalysisSummary {
                 total_items: 0,
                 by_kind: std::collections::HashMap::new(),
                 total_definitions: 0,
                 reachable_count: 0,
+                test_only_items: 0,
             },
         };
         assert_eq!(calculate_deadcode_score(&result), 100.0);
@@ -1009,6 +981,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 1,
                 end_line: 5,
+                span: None,
                 name: "test".to_string(),
                 kind: "function".to_string(),
                 visibility: "private".to_string(),
@@ -1018,11 +991,13 @@ mod tests {
             .collect();
         let result = crate::analyzers::deadcode::Analysis {
             items,
+            test_only: vec![],
             summary: crate::analyzers::deadcode::AnalysisSummary {
                 total_items: 3,
                 by_kind: std::collections::HashMap::new(),
                 total_definitions: 10,
                 reachable_count: 7,
+                test_only_items: 0,
             },
         };
         let score = calculate_deadcode_score(&result);
@@ -1036,6 +1011,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 1,
                 end_line: 5,
+                span: None,
                 name: "test".to_string(),
                 kind: "function".to_string(),
                 visibility: "private".to_string(),
@@ -1045,11 +1021,13 @@ mod tests {
             .collect();
         let result = crate::analyzers::deadcode::Analysis {
             items,
+            test_only: vec![],
             summary: crate::analyzers::deadcode::AnalysisSummary {
                 total_items: 50,
                 by_kind: std::collections::HashMap::new(),
                 total_definitions: 100,
                 reachable_count: 50,
+                test_only_items: 0,
             },
         };
         let score = calculate_deadcode_score(&result);
@@ -1133,6 +1111,10 @@ mod tests {
                 analyzers_run: 3,
                 critical_issues: 0,
             },
+            weights: ScoreWeights::default(),
+            packages: Vec::new(),
+            metrics: BTreeMap::new(),
+            budgets: Vec::new(),
         };
         let json = serde_json::to_string(&analysis).unwrap();
         assert!(json.contains("\"overall_score\":85.0"));
@@ -1159,6 +1141,10 @@ mod tests {
             grade: "B".to_string(),
             components: HashMap::new(),
             summary: AnalysisSummary::default(),
+            weights: ScoreWeights::default(),
+            packages: Vec::new(),
+            metrics: BTreeMap::new(),
+            budgets: Vec::new(),
         };
         assert!(analysis.check_threshold(80.0).is_ok());
     }
@@ -1170,6 +1156,10 @@ mod tests {
             grade: "C".to_string(),
             components: HashMap::new(),
             summary: AnalysisSummary::default(),
+            weights: ScoreWeights::default(),
+            packages: Vec::new(),
+            metrics: BTreeMap::new(),
+            budgets: Vec::new(),
         };
         assert!(analysis.check_threshold(80.0).is_err());
     }
@@ -1181,6 +1171,10 @@ mod tests {
             grade: "B".to_string(),
             components: HashMap::new(),
             summary: AnalysisSummary::default(),
+            weights: ScoreWeights::default(),
+            packages: Vec::new(),
+            metrics: BTreeMap::new(),
+            budgets: Vec::new(),
         };
         assert!(analysis.check_threshold(80.0).is_ok());
     }
@@ -1219,6 +1213,7 @@ mod tests {
                 hotspot_files: vec![],
                 stable_files: vec![],
                 author_contributions: std::collections::HashMap::new(),
+                off_hours: None,
             },
         };
         let score = calculate_churn_score(&result);
@@ -1248,6 +1243,7 @@ mod tests {
                 hotspot_files: vec![],
                 stable_files: vec![],
                 author_contributions: std::collections::HashMap::new(),
+                off_hours: None,
             },
         };
         let score = calculate_churn_score(&result);
@@ -1277,6 +1273,7 @@ mod tests {
                 hotspot_files: vec![],
                 stable_files: vec![],
                 author_contributions: std::collections::HashMap::new(),
+                off_hours: None,
             },
         };
         let score = calculate_churn_score(&result);
@@ -1371,6 +1368,7 @@ mod tests {
         let result = crate::analyzers::cohesion::Analysis {
             generated_at: "2024-01-01T00:00:00Z".to_string(),
             classes: vec![],
+            modules: vec![],
             summary: crate::analyzers::cohesion::Summary {
                 total_classes: 10,
                 total_files: 5,
@@ -1385,6 +1383,7 @@ mod tests {
                 max_dit: 3,
                 low_cohesion_count: 1,
                 violation_count: 0,
+                ..Default::default()
             },
         };
         assert_eq!(calculate_cohesion_score(&result), 100.0);
@@ -1395,6 +1394,7 @@ mod tests {
         let result = crate::analyzers::cohesion::Analysis {
             generated_at: "2024-01-01T00:00:00Z".to_string(),
             classes: vec![],
+            modules: vec![],
             summary: crate::analyzers::cohesion::Summary {
                 total_classes: 10,
                 total_files: 5,
@@ -1409,12 +1409,28 @@ mod tests {
                 max_dit: 10,
                 low_cohesion_count: 8,
                 violation_count: 15,
+                ..Default::default()
             },
         };
         let score = calculate_cohesion_score(&result);
         assert!(score < 50.0);
     }
 
+    #[test]
+    fn test_calculate_cohesion_score_uses_modules_without_classes() {
+        let result = crate::analyzers::cohesion::Analysis {
+            generated_at: "2024-01-01T00:00:00Z".to_string(),
+            classes: vec![],
+            modules: vec![],
+            summary: crate::analyzers::cohesion::Summary {
+                total_modules: 12,
+                avg_module_lcom: 4.0,
+                ..Default::default()
+            },
+        };
+        assert_eq!(calculate_cohesion_score(&result), 60.0);
+    }
+
     #[test]
     fn test_calculate_ownership_score_good() {
         let result = crate::analyzers::ownership::Analysis {
@@ -1428,6 +1444,7 @@ mod tests {
                 avg_contributors: 4.0,
                 max_concentration: 0.5,
                 top_contributors: vec![],
+                ..Default::default()
             },
         };
         let score = calculate_ownership_score(&result);
@@ -1447,6 +1464,7 @@ mod tests {
                 avg_contributors: 1.2,
                 max_concentration: 0.95,
                 top_contributors: vec![],
+                ..Default::default()
             },
         };
         let score = calculate_ownership_score(&result);
@@ -1465,6 +1483,7 @@ mod tests {
                 avg_probability: 0.05,
                 p50_probability: 0.03,
                 p95_probability: 0.15,
+                ..Default::default()
             },
             weights: crate::analyzers::defect::Weights::default(),
         };
@@ -1477,6 +1496,7 @@ mod tests {
         let result = crate::analyzers::smells::Analysis {
             generated_at: String::new(),
             smells: vec![],
+            resolved: vec![],
             components: vec![],
             summary: crate::analyzers::smells::Summary {
                 total_smells: 0,
@@ -1494,6 +1514,7 @@ mod tests {
         let result = crate::analyzers::smells::Analysis {
             generated_at: String::new(),
             smells: vec![],
+            resolved: vec![],
             components: vec![],
             summary: crate::analyzers::smells::Summary {
                 total_smells: 305,
@@ -1523,6 +1544,7 @@ mod tests {
         let large = crate::analyzers::smells::Analysis {
             generated_at: String::new(),
             smells: vec![],
+            resolved: vec![],
             components: vec![],
             summary: crate::analyzers::smells::Summary {
                 total_smells: 20,
@@ -1537,6 +1559,7 @@ mod tests {
         let small = crate::analyzers::smells::Analysis {
             generated_at: String::new(),
             smells: vec![],
+            resolved: vec![],
             components: vec![],
             summary: crate::analyzers::smells::Summary {
                 total_smells: 20,
@@ -1562,6 +1585,7 @@ mod tests {
         let result = crate::analyzers::smells::Analysis {
             generated_at: String::new(),
             smells: vec![],
+            resolved: vec![],
             components: vec![],
             summary: crate::analyzers::smells::Summary {
                 total_smells: 5,
@@ -1597,11 +1621,16 @@ mod tests {
                 .collect(),
             edges: vec![],
             cycles: vec![],
+            cycle_breaks: vec![],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
             summary: crate::analyzers::graph::AnalysisSummary {
                 total_nodes: 100,
                 total_edges: 100,
                 avg_degree: 2.0,
                 cycle_count: 0,
+                ..Default::default()
             },
         };
         let hub_heavy = crate::analyzers::graph::Analysis {
@@ -1623,11 +1652,16 @@ mod tests {
             },
             edges: vec![],
             cycles: vec![],
+            cycle_breaks: vec![],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
             summary: crate::analyzers::graph::AnalysisSummary {
                 total_nodes: 100,
                 total_edges: 141,
                 avg_degree: 1.41, // low avg because most nodes are 0
                 cycle_count: 0,
+                ..Default::default()
             },
         };
         let uniform_score = calculate_coupling_score(&uniform);
@@ -1665,11 +1699,16 @@ mod tests {
             },
             edges: vec![],
             cycles: vec![vec!["a".into(), "b".into()]],
+            cycle_breaks: vec![],
+            barrels: vec![],
+            packages: None,
+            diagram: None,
             summary: crate::analyzers::graph::AnalysisSummary {
                 total_nodes: 4835,
                 total_edges: 2907,
                 avg_degree: 1.2,
                 cycle_count: 1,
+                ..Default::default()
             },
         };
         let score = calculate_coupling_score(&result);
@@ -1702,6 +1741,7 @@ mod tests {
                 avg_probability: 0.75,
                 p50_probability: 0.70,
                 p95_probability: 0.95,
+                ..Default::default()
             },
             weights: crate::analyzers::defect::Weights::default(),
         };
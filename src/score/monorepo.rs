@@ -0,0 +1,187 @@
+//! Monorepo scoring: per-package scores weighted by package size.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{BudgetMetric, PackageWeight};
+use crate::core::{AnalysisContext, Result};
+
+use super::{score_to_grade, Analysis, Analyzer, ScoreAccumulator};
+
+/// Score of one workspace package in monorepo mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageScore {
+    /// Package directory relative to the root; `.` for the root package.
+    pub package: String,
+    pub files: usize,
+    /// Weight of the package: lines of code or file count.
+    pub size: usize,
+    pub overall_score: f64,
+    pub grade: String,
+}
+
+impl Analyzer {
+    /// Score each package on its own, then combine every component as the
+    /// size-weighted mean over the packages that produced it, so a small
+    /// utility package moves the result in proportion to its size.
+    pub(super) fn analyze_packages(
+        &self,
+        ctx: &AnalysisContext<'_>,
+        packages: BTreeMap<String, Vec<PathBuf>>,
+        weight: PackageWeight,
+    ) -> Result<Analysis> {
+        let mut package_scores = Vec::new();
+        let mut totals: HashMap<String, ComponentTotal> = HashMap::new();
+        let mut metrics: HashMap<BudgetMetric, (f64, f64)> = HashMap::new();
+
+        for (package, files) in packages {
+            let size = match weight {
+                PackageWeight::Files => files.len(),
+                PackageWeight::Loc => files
+                    .iter()
+                    .filter_map(|f| ctx.load_source(f).ok())
+                    .map(|source| source.lines_of_code())
+                    .sum(),
+            };
+            if size == 0 {
+                continue;
+            }
+
+            let file_set = ctx.files.filter_by_paths(&files);
+            let acc = self.score_components(&ctx.for_files(&file_set));
+            for (name, component) in &acc.components {
+                let total = totals.entry(name.clone()).or_insert(ComponentTotal {
+                    weight: component.weight,
+                    ..Default::default()
+                });
+                total.weighted_score += component.score * size as f64;
+                total.size += size as f64;
+                total.packages += 1;
+            }
+            for (&metric, &value) in &acc.metrics {
+                let (sum, total_size) = metrics.entry(metric).or_default();
+                if metric.is_count() {
+                    *sum += value;
+                } else {
+                    *sum += value * size as f64;
+                    *total_size += size as f64;
+                }
+            }
+
+            let overall_score = acc.overall_score();
+            package_scores.push(PackageScore {
+                package: if package.is_empty() {
+                    ".".to_string()
+                } else {
+                    package
+                },
+                files: files.len(),
+                size,
+                overall_score,
+                grade: score_to_grade(overall_score),
+            });
+        }
+
+        let unit = match weight {
+            PackageWeight::Loc => "lines of code",
+            PackageWeight::Files => "file count",
+        };
+        let mut acc = ScoreAccumulator::default();
+        for (name, total) in totals {
+            acc.add(
+                &name,
+                total.weight,
+                total.weighted_score / total.size,
+                format!("Weighted by {unit} across {} packages", total.packages),
+            );
+        }
+
+        // Counts add up across packages; other metrics are size-weighted.
+        for (metric, (sum, size)) in metrics {
+            acc.metric(metric, if size > 0.0 { sum / size } else { sum });
+        }
+
+        let mut analysis = acc.into_analysis(ctx.files.files().len(), self.weights(ctx))?;
+        analysis.packages = package_scores;
+        Ok(analysis)
+    }
+}
+
+/// Running size-weighted sum of one component across packages.
+#[derive(Default)]
+struct ComponentTotal {
+    weight: f64,
+    weighted_score: f64,
+    size: f64,
+    packages: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, PackageWeight};
+    use crate::core::{AnalysisContext, Analyzer as _, FileSet};
+    use crate::score::Analyzer;
+
+    fn write_workspace(root: &std::path::Path) {
+        for package in ["packages/core", "packages/util"] {
+            std::fs::create_dir_all(root.join(package)).unwrap();
+            std::fs::write(root.join(package).join("package.json"), "{}").unwrap();
+        }
+        let body: String = (0..20)
+            .map(|i| format!("export function f{i}(x) {{\n  return x + {i};\n}}\n"))
+            .collect();
+        std::fs::write(root.join("packages/core/a.js"), &body).unwrap();
+        std::fs::write(root.join("packages/core/b.js"), &body).unwrap();
+        std::fs::write(
+            root.join("packages/util/index.js"),
+            "export function id(x) {\n  return x;\n}\n",
+        )
+        .unwrap();
+    }
+
+    fn score(root: &std::path::Path, weight: Option<PackageWeight>) -> crate::score::Analysis {
+        let mut config = Config::default();
+        config.score.monorepo = weight;
+        let files = FileSet::from_path(root, &config).unwrap();
+        let ctx = AnalysisContext::new(&files, &config, Some(root));
+        Analyzer::default().analyze(&ctx).unwrap()
+    }
+
+    #[test]
+    fn test_monorepo_scores_each_package_by_size() {
+        let temp = tempfile::tempdir().unwrap();
+        write_workspace(temp.path());
+
+        let by_files = score(temp.path(), Some(PackageWeight::Files));
+        let packages: Vec<_> = by_files
+            .packages
+            .iter()
+            .map(|p| (p.package.as_str(), p.files, p.size))
+            .collect();
+        assert_eq!(
+            packages,
+            vec![("packages/core", 2, 2), ("packages/util", 1, 1)]
+        );
+        assert_eq!(by_files.summary.files_analyzed, 3);
+        let complexity = &by_files.components["complexity"];
+        assert!(complexity.details.contains("file count across 2 packages"));
+
+        let by_loc = score(temp.path(), Some(PackageWeight::Loc));
+        assert_eq!(by_loc.packages[0].size, 120);
+        assert_eq!(by_loc.packages[1].size, 3);
+    }
+
+    #[test]
+    fn test_monorepo_unset_scores_repository_as_one_unit() {
+        let temp = tempfile::tempdir().unwrap();
+        write_workspace(temp.path());
+
+        let analysis = score(temp.path(), None);
+        assert!(analysis.packages.is_empty());
+        assert!(!analysis.components["complexity"]
+            .details
+            .contains("packages"));
+    }
+}
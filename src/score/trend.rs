@@ -1,15 +1,22 @@
 //! Score trend analysis over git history.
+//!
+//! Scores of sampled commits are cached under `.omen/trend-cache`, keyed by
+//! commit, config and omen version, so a later run only analyzes commits it
+//! hasn't seen before.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use chrono::{DateTime, Duration, Utc};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::analyzers::pipeline::Analyzer as PipelineAnalyzer;
+use crate::analyzers::ANALYZER_VERSIONS;
 use crate::cli::TrendPeriod;
-use crate::config::Config;
+use crate::config::{BudgetMetric, Config};
 use crate::core::{
     AnalysisContext, Analyzer as AnalyzerTrait, ContentSource, Error, FileSet, Result, TreeSource,
 };
@@ -58,9 +65,14 @@ pub fn analyze_trend(
         return Ok(TrendData::default());
     }
 
-    // Build list of commits to analyze at each sample point
+    // Build list of commits to analyze at each sample point. Period samples
+    // sit on a fixed grid (multiples of the interval since the epoch), so
+    // later runs sample the same commits and find them in the cache.
     let mut sample_commits: Vec<(DateTime<Utc>, String)> = Vec::new();
-    let mut current_time = start_time;
+    let mut current_time = match samples {
+        Some(_) => start_time,
+        None => align_up(start_time, interval),
+    };
 
     while current_time <= now {
         if let Some(commit) = find_commit_at_time(&commits, current_time) {
@@ -76,8 +88,16 @@ pub fn analyze_trend(
         current_time += interval;
     }
 
-    // Analyze commits in parallel using worktrees
-    let points = analyze_commits_parallel(path, config, &sample_commits, &commits)?;
+    // Analyze commits in parallel, reusing cached scores
+    let cache = if config.score.trend_cache {
+        Some(TrendCache::new(
+            repo.root().join(".omen/trend-cache"),
+            config,
+        )?)
+    } else {
+        None
+    };
+    let points = analyze_commits_parallel(path, config, &sample_commits, &commits, cache.as_ref())?;
 
     // Always include the current HEAD if not already included
     let mut final_points = points;
@@ -100,6 +120,8 @@ pub fn analyze_trend(
                     .map(|(k, v)| (k.clone(), v.score as i32))
                     .collect(),
                 notable_commits: head_commits,
+                pipeline_complexity: pipeline_complexity_current(path, config),
+                metrics: score_data.metrics,
             });
         }
     }
@@ -130,20 +152,27 @@ pub fn analyze_trend(
 
 /// Analyze multiple commits in parallel using TreeSource (no worktrees needed).
 /// Reads file contents directly from git's object store without filesystem checkout.
+/// Commits found in `cache` are not analyzed again; new results are added to it.
 fn analyze_commits_parallel(
     path: &Path,
     config: &Config,
     sample_commits: &[(DateTime<Utc>, String)],
     all_commits: &[Commit],
+    cache: Option<&TrendCache>,
 ) -> Result<Vec<TrendPoint>> {
     if sample_commits.is_empty() {
         return Ok(Vec::new());
     }
 
-    let total = sample_commits.len();
+    let cached: Vec<Option<CommitScore>> = sample_commits
+        .iter()
+        .map(|(_, sha)| cache.and_then(|cache| cache.get(sha)))
+        .collect();
+    let total = cached.iter().filter(|score| score.is_none()).count();
     eprintln!(
-        "Trend analysis: analyzing {} commits using tree-based analysis",
-        total
+        "Trend analysis: analyzing {} commits using tree-based analysis ({} cached)",
+        total,
+        sample_commits.len() - total
     );
 
     // Build time windows for commit message collection.
@@ -164,33 +193,34 @@ fn analyze_commits_parallel(
     let completed = Arc::new(AtomicUsize::new(0));
     let path_buf = path.to_path_buf();
 
-    // Analyze all commits in parallel using TreeSource
+    // Analyze uncached commits in parallel using TreeSource
     let all_points: Vec<TrendPoint> = sample_commits
         .par_iter()
         .zip(windows.par_iter())
-        .filter_map(|((time, sha), &(window_start, window_end))| {
-            // Create TreeSource for this commit
-            let tree_source = TreeSource::new(&path_buf, sha).ok()?;
-            let result = analyze_at_tree(&tree_source, config).ok()?;
-
-            // Update progress
-            let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
-            if done.is_multiple_of(10) || done == total {
-                eprintln!("Trend analysis: {}/{} commits analyzed", done, total);
-            }
+        .zip(cached.into_par_iter())
+        .filter_map(|(((time, sha), &(window_start, window_end)), cached)| {
+            let score = match cached {
+                Some(score) => score,
+                None => {
+                    // Create TreeSource for this commit
+                    let tree_source = TreeSource::new(&path_buf, sha).ok()?;
+                    let result = analyze_at_tree(&tree_source, config).ok()?;
+                    let score = CommitScore::new(result, pipeline_complexity_at_tree(&tree_source));
+                    if let Some(cache) = cache {
+                        cache.put(sha, &score);
+                    }
+
+                    // Update progress
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if done.is_multiple_of(10) || done == total {
+                        eprintln!("Trend analysis: {}/{} commits analyzed", done, total);
+                    }
+                    score
+                }
+            };
 
             let notable = collect_commits_in_range(all_commits, window_start, window_end);
-
-            Some(TrendPoint {
-                date: time.format("%Y-%m-%d").to_string(),
-                score: result.overall_score as i32,
-                components: result
-                    .components
-                    .iter()
-                    .map(|(k, v)| (k.clone(), v.score as i32))
-                    .collect(),
-                notable_commits: notable,
-            })
+            Some(score.into_point(time.format("%Y-%m-%d").to_string(), notable))
         })
         .collect();
 
@@ -201,6 +231,90 @@ fn analyze_commits_parallel(
     Ok(sorted_points)
 }
 
+/// The score of one commit, as stored in the trend cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommitScore {
+    score: i32,
+    components: HashMap<String, i32>,
+    pipeline_complexity: Option<usize>,
+    metrics: BTreeMap<BudgetMetric, f64>,
+}
+
+impl CommitScore {
+    fn new(analysis: super::Analysis, pipeline_complexity: Option<usize>) -> Self {
+        Self {
+            score: analysis.overall_score as i32,
+            components: analysis
+                .components
+                .iter()
+                .map(|(k, v)| (k.clone(), v.score as i32))
+                .collect(),
+            pipeline_complexity,
+            metrics: analysis.metrics,
+        }
+    }
+
+    fn into_point(self, date: String, notable_commits: Vec<String>) -> TrendPoint {
+        TrendPoint {
+            date,
+            score: self.score,
+            components: self.components,
+            notable_commits,
+            pipeline_complexity: self.pipeline_complexity,
+            metrics: self.metrics,
+        }
+    }
+}
+
+/// Commit scores persisted as JSON files, one per commit. Entries are keyed
+/// by the config hash, omen version and analyzer versions too, so a change to
+/// any of them starts afresh instead of reusing stale scores.
+struct TrendCache {
+    dir: PathBuf,
+    key: String,
+}
+
+impl TrendCache {
+    fn new(dir: PathBuf, config: &Config) -> Result<Self> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update(crate::manifest::config_hash(config)?.as_bytes());
+        for (name, version) in ANALYZER_VERSIONS {
+            hasher.update(format!("{name}={version};").as_bytes());
+        }
+        Ok(Self {
+            dir,
+            key: hasher.finalize().to_hex()[..16].to_string(),
+        })
+    }
+
+    fn file(&self, sha: &str) -> PathBuf {
+        self.dir.join(format!("{sha}-{}.json", self.key))
+    }
+
+    fn get(&self, sha: &str) -> Option<CommitScore> {
+        let bytes = std::fs::read(self.file(sha)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Best effort: a score that can't be cached is just computed again.
+    fn put(&self, sha: &str, score: &CommitScore) {
+        if std::fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec(score) {
+            let _ = std::fs::write(self.file(sha), json);
+        }
+    }
+}
+
+/// The first multiple of `interval` since the epoch at or after `time`.
+fn align_up(time: DateTime<Utc>, interval: Duration) -> DateTime<Utc> {
+    let step = interval.num_seconds().max(1);
+    let aligned = (time.timestamp() + step - 1).div_euclid(step) * step;
+    DateTime::from_timestamp(aligned, 0).unwrap_or(time)
+}
+
 /// Analyze commits sequentially using TreeSource (for debugging or when parallelism fails).
 #[allow(dead_code)]
 fn analyze_commits_sequential(
@@ -225,6 +339,8 @@ fn analyze_commits_sequential(
                         .map(|(k, v)| (k.clone(), v.score as i32))
                         .collect(),
                     notable_commits: notable,
+                    pipeline_complexity: pipeline_complexity_at_tree(&tree_source),
+                    metrics: score_data.metrics,
                 });
                 prev_ts = time.timestamp();
             }
@@ -315,6 +431,23 @@ pub fn analyze_at_tree(tree_source: &TreeSource, config: &Config) -> Result<supe
     analyzer.analyze(&ctx)
 }
 
+/// Total CI/build pipeline complexity of the working tree.
+fn pipeline_complexity_current(path: &Path, config: &Config) -> Option<usize> {
+    let file_set = FileSet::from_files(path.to_path_buf(), Vec::new());
+    let ctx = AnalysisContext::new(&file_set, config, Some(path));
+    PipelineAnalyzer::new()
+        .analyze(&ctx)
+        .ok()
+        .map(|a| a.summary.total_complexity)
+}
+
+/// Total CI/build pipeline complexity at a git tree.
+fn pipeline_complexity_at_tree(tree_source: &TreeSource) -> Option<usize> {
+    crate::analyzers::pipeline::analyze_tree(tree_source)
+        .ok()
+        .map(|a| a.summary.total_complexity)
+}
+
 /// Wrapper to create a new TreeSource for the content source.
 /// This is needed because TreeSource stores state that can't be easily cloned.
 struct TreeSourceWrapper {
@@ -449,6 +582,8 @@ fn calculate_component_trends(points: &[TrendPoint]) -> HashMap<String, Componen
                     score,
                     components: HashMap::new(),
                     notable_commits: Vec::new(),
+                    pipeline_complexity: None,
+                    metrics: BTreeMap::new(),
                 })
             })
             .collect();
@@ -471,6 +606,62 @@ fn calculate_component_trends(points: &[TrendPoint]) -> HashMap<String, Componen
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_trend_cache_reuses_commit_scores() {
+        let repo = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(repo.path())
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.path().join("lib.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "first"]);
+        let git_repo = GitRepo::open(repo.path()).unwrap();
+        let sha = git_repo.rev_sha().unwrap();
+        let commits = git_repo.log(None, None, None).unwrap();
+        let samples = vec![(Utc::now(), sha.clone())];
+
+        let config = Config::default();
+        let cache_dir = TempDir::new().unwrap();
+        let cache = TrendCache::new(cache_dir.path().to_path_buf(), &config).unwrap();
+        let first =
+            analyze_commits_parallel(repo.path(), &config, &samples, &commits, Some(&cache))
+                .unwrap();
+        assert_eq!(first.len(), 1);
+        let cached = cache.get(&sha).unwrap();
+        assert_eq!(cached.score, first[0].score);
+
+        // A cached score is used as-is, without analyzing the commit again.
+        cache.put(&sha, &CommitScore { score: 7, ..cached });
+        let second =
+            analyze_commits_parallel(repo.path(), &config, &samples, &commits, Some(&cache))
+                .unwrap();
+        assert_eq!(second[0].score, 7);
+        assert_eq!(second[0].notable_commits, vec!["first".to_string()]);
+
+        // Another config doesn't see it.
+        let mut other = Config::default();
+        other.git.blame = !other.git.blame;
+        let other = TrendCache::new(cache_dir.path().to_path_buf(), &other).unwrap();
+        assert!(other.get(&sha).is_none());
+    }
+
+    #[test]
+    fn test_align_up() {
+        let time = DateTime::from_timestamp(86_400 * 10 + 5, 0).unwrap();
+        assert_eq!(align_up(time, Duration::days(1)).timestamp(), 86_400 * 11);
+        let midnight = DateTime::from_timestamp(86_400 * 10, 0).unwrap();
+        assert_eq!(align_up(midnight, Duration::days(1)), midnight);
+    }
 
     #[test]
     fn test_default_sample_count() {
@@ -542,18 +733,24 @@ mod tests {
                 score: 50,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-08".to_string(),
                 score: 60,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-15".to_string(),
                 score: 70,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
         ];
 
@@ -570,18 +767,24 @@ mod tests {
                 score: 80,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-08".to_string(),
                 score: 70,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-15".to_string(),
                 score: 60,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
         ];
 
@@ -598,18 +801,24 @@ mod tests {
                 score: 75,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-08".to_string(),
                 score: 75,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-15".to_string(),
                 score: 75,
                 components: HashMap::new(),
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
         ];
 
@@ -627,6 +836,8 @@ mod tests {
             score: 75,
             components: HashMap::new(),
             notable_commits: vec![],
+            pipeline_complexity: None,
+            metrics: BTreeMap::new(),
         }];
 
         let (slope, intercept, r_squared) = calculate_linear_regression(&points);
@@ -664,18 +875,24 @@ mod tests {
                 score: 65,
                 components: components1,
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-08".to_string(),
                 score: 70,
                 components: components2,
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
             TrendPoint {
                 date: "2024-01-15".to_string(),
                 score: 75,
                 components: components3,
                 notable_commits: vec![],
+                pipeline_complexity: None,
+                metrics: BTreeMap::new(),
             },
         ];
 
@@ -307,6 +307,26 @@ impl EmbeddingCache {
         Ok(files)
     }
 
+    /// Get the number of cached symbols per indexed file.
+    pub fn symbol_counts_by_file(&self) -> Result<Vec<(String, usize)>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT f.file_path, COUNT(s.id) FROM files f \
+                 LEFT JOIN symbols s ON s.file_path = f.file_path \
+                 GROUP BY f.file_path ORDER BY f.file_path",
+            )
+            .map_err(|e| Error::analysis(format!("Failed to prepare query: {}", e)))?;
+
+        let counts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))
+            .map_err(|e| Error::analysis(format!("Failed to count symbols: {}", e)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(counts)
+    }
+
     /// Get the number of cached symbols.
     pub fn symbol_count(&self) -> Result<usize> {
         let count: i64 = self
@@ -535,6 +555,31 @@ mod tests {
         assert!(cache.get_file_hash(&symbol.file_path).unwrap().is_none());
     }
 
+    #[test]
+    fn test_symbol_counts_by_file() {
+        let cache = EmbeddingCache::in_memory().unwrap();
+        let symbol = create_test_symbol();
+        cache.upsert_symbol(&symbol).unwrap();
+        cache
+            .upsert_symbol(&CachedSymbol {
+                symbol_name: "other_func".to_string(),
+                ..symbol.clone()
+            })
+            .unwrap();
+        cache
+            .record_file_indexed(&symbol.file_path, "hash1")
+            .unwrap();
+        cache.record_file_indexed("src/empty.rs", "hash2").unwrap();
+
+        assert_eq!(
+            cache.symbol_counts_by_file().unwrap(),
+            vec![
+                ("src/empty.rs".to_string(), 0),
+                ("src/main.rs".to_string(), 2),
+            ]
+        );
+    }
+
     #[test]
     fn test_get_all_indexed_files() {
         let cache = EmbeddingCache::in_memory().unwrap();
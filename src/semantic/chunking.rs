@@ -162,7 +162,7 @@ fn get_type_node_kinds(lang: Language) -> Vec<&'static str> {
         Language::C => vec!["struct_specifier"],
         Language::Ruby => vec!["class", "module"],
         Language::Php => vec!["class_declaration", "interface_declaration"],
-        Language::Bash => vec![],
+        Language::Bash | Language::Lua | Language::Zig => vec![],
     }
 }
 
@@ -284,6 +284,7 @@ mod tests {
             name: "foo".to_string(),
             start_line: 1,
             end_line: 3,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "fn foo()".to_string(),
@@ -313,6 +314,7 @@ mod tests {
             name: "long_func".to_string(),
             start_line: 1,
             end_line: line_count,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "fn long_func()".to_string(),
@@ -344,6 +346,7 @@ mod tests {
             name: "bar".to_string(),
             start_line: 3,
             end_line: 5,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "fn bar()".to_string(),
@@ -363,6 +366,7 @@ mod tests {
             name: "free".to_string(),
             start_line: 1,
             end_line: 1,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "fn free()".to_string(),
@@ -475,6 +479,7 @@ mod tests {
             name: "Start".to_string(),
             start_line: 3,
             end_line: 5,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "func (s *Server) Start()".to_string(),
@@ -491,6 +496,7 @@ mod tests {
             name: "bar".to_string(),
             start_line: 2,
             end_line: 3,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "def bar(self)".to_string(),
@@ -507,6 +513,7 @@ mod tests {
             name: "render".to_string(),
             start_line: 2,
             end_line: 4,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "render()".to_string(),
@@ -523,6 +530,7 @@ mod tests {
             name: "run".to_string(),
             start_line: 2,
             end_line: 4,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "void run()".to_string(),
@@ -539,6 +547,7 @@ mod tests {
             name: "start".to_string(),
             start_line: 3,
             end_line: 5,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "void start()".to_string(),
@@ -555,6 +564,7 @@ mod tests {
             name: "draw".to_string(),
             start_line: 2,
             end_line: 2,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "void draw()".to_string(),
@@ -571,6 +581,7 @@ mod tests {
             name: "bark".to_string(),
             start_line: 2,
             end_line: 4,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "def bark".to_string(),
@@ -588,6 +599,7 @@ mod tests {
             name: "index".to_string(),
             start_line: 3,
             end_line: 5,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "function index()".to_string(),
@@ -604,6 +616,7 @@ mod tests {
             name: "Process".to_string(),
             start_line: 2,
             end_line: 4,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "void Process()".to_string(),
@@ -620,6 +633,7 @@ mod tests {
             name: "my_func".to_string(),
             start_line: 2,
             end_line: 4,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "my_func()".to_string(),
@@ -637,6 +651,7 @@ mod tests {
             name: "fmt".to_string(),
             start_line: 3,
             end_line: 5,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: false,
             signature: "fn fmt()".to_string(),
@@ -42,6 +42,7 @@ mod tests {
             name: "test_func".to_string(),
             start_line: 1,
             end_line: 3,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "fn test_func()".to_string(),
@@ -58,6 +59,7 @@ mod tests {
             name: "test_func".to_string(),
             start_line: 1,
             end_line: 1,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "fn test_func()".to_string(),
@@ -74,6 +76,7 @@ mod tests {
             name: "test_func".to_string(),
             start_line: 1,
             end_line: 1,
+            span: Default::default(),
             body_byte_range: None,
             is_exported: true,
             signature: "fn test_func()".to_string(),
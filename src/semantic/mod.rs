@@ -11,15 +11,18 @@
 //! - **cache**: SQLite storage for symbols and staleness tracking
 //! - **sync**: Incremental indexing and staleness detection
 //! - **search**: Query engine wrapping TF-IDF over cached symbols
+//! - **rerank**: Query-time reranking by PageRank, hotspot, and complexity
 
 pub mod cache;
 pub mod chunking;
 pub mod embed;
 pub mod multi_repo;
+pub mod rerank;
 pub mod search;
 pub mod sync;
 pub mod tfidf;
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
@@ -28,8 +31,9 @@ use crate::config::Config;
 use crate::core::{FileSet, Result};
 
 pub use cache::EmbeddingCache;
+pub use rerank::{Rerank, RerankSignals};
 pub use search::{SearchEngine, SearchFilters, SearchOutput, SearchResult};
-pub use sync::{SyncManager, SyncStats};
+pub use sync::{StaleFile, StaleReason, SyncManager, SyncStats};
 pub use tfidf::TfidfEngine;
 
 /// Configuration for semantic search.
@@ -53,9 +57,34 @@ impl Default for SearchConfig {
     }
 }
 
+/// Size and freshness of the search index.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    /// Path to the cache database.
+    pub cache_path: PathBuf,
+    /// Database size on disk in bytes.
+    pub size_bytes: u64,
+    /// Number of indexed files.
+    pub files: usize,
+    /// Number of indexed symbols (chunks).
+    pub symbols: usize,
+    /// File and symbol counts per language.
+    pub by_language: BTreeMap<String, LanguageStats>,
+    /// Indexed files that are deleted, excluded, or modified.
+    pub stale: Vec<StaleFile>,
+}
+
+/// Per-language index counts.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LanguageStats {
+    pub files: usize,
+    pub symbols: usize,
+}
+
 /// High-level semantic search interface.
 pub struct SemanticSearch {
     cache: EmbeddingCache,
+    cache_path: PathBuf,
     root_path: PathBuf,
     config: SearchConfig,
 }
@@ -78,6 +107,7 @@ impl SemanticSearch {
 
         Ok(Self {
             cache,
+            cache_path,
             root_path,
             config: config.clone(),
         })
@@ -90,6 +120,40 @@ impl SemanticSearch {
         sync_manager.sync(&file_set, &self.root_path)
     }
 
+    /// Report index size, per-language counts, and stale entries, using the
+    /// same FileSet rules as indexing.
+    pub fn stats(&self, file_config: &Config) -> Result<IndexStats> {
+        let file_set = FileSet::from_path(&self.root_path, file_config)?;
+        let stale = SyncManager::new(&self.cache).stale_files(&file_set, &self.root_path)?;
+
+        let counts = self.cache.symbol_counts_by_file()?;
+        let mut by_language: BTreeMap<String, LanguageStats> = BTreeMap::new();
+        for (file_path, symbols) in &counts {
+            let language = file_set
+                .language(Path::new(file_path))
+                .map_or_else(|| "unknown".to_string(), |l| l.to_string());
+            let entry = by_language.entry(language).or_default();
+            entry.files += 1;
+            entry.symbols += symbols;
+        }
+
+        Ok(IndexStats {
+            size_bytes: std::fs::metadata(&self.cache_path).map_or(0, |m| m.len()),
+            cache_path: self.cache_path.clone(),
+            files: counts.len(),
+            symbols: self.cache.symbol_count()?,
+            by_language,
+            stale,
+        })
+    }
+
+    /// Drop deleted and excluded files from the index without re-indexing.
+    /// Returns the number of files removed.
+    pub fn prune(&self, file_config: &Config) -> Result<usize> {
+        let file_set = FileSet::from_path(&self.root_path, file_config)?;
+        SyncManager::new(&self.cache).prune(&file_set, &self.root_path)
+    }
+
     /// Search for symbols matching the query.
     pub fn search(&self, query: &str, top_k: Option<usize>) -> Result<SearchOutput> {
         let top_k = top_k.unwrap_or(self.config.max_results);
@@ -133,6 +197,21 @@ impl SemanticSearch {
         Ok(SearchOutput::new(query.to_string(), total_symbols, results))
     }
 
+    /// Rerank results by analyzer signals using the `[search.rerank]`
+    /// weights in `file_config`.
+    pub fn rerank(
+        &self,
+        results: Vec<SearchResult>,
+        file_config: &Config,
+    ) -> Result<Vec<SearchResult>> {
+        let signals = RerankSignals::collect(&self.root_path, file_config)?;
+        Ok(rerank::rerank(
+            results,
+            &signals,
+            &file_config.search.rerank,
+        ))
+    }
+
     /// Get the number of indexed symbols.
     pub fn symbol_count(&self) -> Result<usize> {
         self.cache.symbol_count()
@@ -172,6 +251,44 @@ mod tests {
         assert_eq!(deserialized.max_results, 10);
     }
 
+    #[test]
+    fn test_semantic_search_stats_and_prune() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("vendor")).unwrap();
+        std::fs::write(temp.path().join("a.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(temp.path().join("b.py"), "def c():\n    pass\n").unwrap();
+        std::fs::write(temp.path().join("vendor/v.rs"), "fn v() {}\n").unwrap();
+
+        let search_config = SearchConfig {
+            cache_path: Some(temp.path().join("search.db")),
+            ..SearchConfig::default()
+        };
+        let search = SemanticSearch::new(&search_config, temp.path()).unwrap();
+        search.index(&Config::default()).unwrap();
+
+        let config = Config {
+            exclude_patterns: vec!["vendor/**".to_string()],
+            ..Default::default()
+        };
+        let stats = search.stats(&config).unwrap();
+        assert_eq!(stats.files, 3);
+        assert_eq!(
+            stats.symbols,
+            stats.by_language.values().map(|l| l.symbols).sum::<usize>()
+        );
+        assert!(stats.size_bytes > 0);
+        let rust = &stats.by_language["Rust"];
+        assert_eq!((rust.files, rust.symbols), (2, 3));
+        assert_eq!(stats.stale.len(), 1);
+        assert_eq!(stats.stale[0].file_path, "vendor/v.rs");
+        assert_eq!(stats.stale[0].reason, StaleReason::Excluded);
+
+        assert_eq!(search.prune(&config).unwrap(), 1);
+        let stats = search.stats(&config).unwrap();
+        assert_eq!(stats.files, 2);
+        assert!(stats.stale.is_empty());
+    }
+
     #[test]
     fn test_semantic_search_search_filtered() {
         use crate::semantic::cache::CachedSymbol;
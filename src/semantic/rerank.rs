@@ -0,0 +1,255 @@
+//! Query-time reranking of search results by analyzer signals.
+//!
+//! TF-IDF similarity can't tell a central implementation from a test helper
+//! that mentions the same words. Reranking blends the similarity score with
+//! what the other analyzers know about each result:
+//!
+//! - **pagerank**: the symbol's PageRank percentile in the repo map (test
+//!   files are left out of the map and score 0)
+//! - **hotspot**: the file's hotspot score from git history
+//! - **complexity**: the symbol's cyclomatic complexity, log-scaled against
+//!   the most complex candidate
+//!
+//! Each signal is in 0-1 and the blend is a weighted average, so the
+//! reranked score stays comparable to the similarity score.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyzers::{hotspot, repomap};
+use crate::config::{Config, RerankConfig};
+use crate::core::{AnalysisContext, Analyzer, FileSet, Result};
+
+use super::SearchResult;
+
+/// How a result's reranked score was derived.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rerank {
+    /// Blended score the results are ordered by (0-1).
+    pub score: f64,
+    /// PageRank percentile of the symbol (0-1).
+    pub pagerank: f64,
+    /// Hotspot score of the file (0-1).
+    pub hotspot: f64,
+    /// Normalized cyclomatic complexity (0-1).
+    pub complexity: f64,
+}
+
+/// Per-symbol and per-file signals gathered once per query.
+#[derive(Debug, Default)]
+pub struct RerankSignals {
+    /// PageRank percentile keyed by (file, line).
+    pagerank: HashMap<(String, u32), f64>,
+    /// PageRank percentile keyed by (file, name), for symbols whose start
+    /// line differs between the index and the repo map.
+    pagerank_by_name: HashMap<(String, String), f64>,
+    /// Hotspot score keyed by file.
+    hotspot: HashMap<String, f64>,
+}
+
+impl RerankSignals {
+    /// Collect signals for the repository at `root`. Signals with a zero
+    /// weight are skipped, and hotspot scores are left empty outside git.
+    pub fn collect(root: &Path, config: &Config) -> Result<Self> {
+        let weights = &config.search.rerank;
+        let files = FileSet::from_path(root, config)?;
+        let mut signals = Self::default();
+
+        if weights.pagerank > 0.0 {
+            let map = repomap::Analyzer::new().analyze_with_files(root, &files)?;
+            signals.set_pagerank(&map.symbols);
+        }
+
+        if weights.hotspot > 0.0 {
+            let ctx = AnalysisContext::new(&files, config, Some(root)).with_git_path(root);
+            if let Ok(analysis) = hotspot::Analyzer::new().analyze(&ctx) {
+                signals.hotspot = analysis
+                    .hotspots
+                    .into_iter()
+                    .map(|h| (h.file, h.score))
+                    .collect();
+            }
+        }
+
+        Ok(signals)
+    }
+
+    /// Record PageRank percentiles from repo map symbols, which are sorted
+    /// by PageRank descending.
+    fn set_pagerank(&mut self, symbols: &[repomap::SymbolEntry]) {
+        let n = symbols.len() as f64;
+        for (i, symbol) in symbols.iter().enumerate() {
+            let percentile = 1.0 - i as f64 / n;
+            self.pagerank
+                .entry((symbol.file.clone(), symbol.line))
+                .or_insert(percentile);
+            self.pagerank_by_name
+                .entry((symbol.file.clone(), symbol.name.clone()))
+                .or_insert(percentile);
+        }
+    }
+
+    fn pagerank_of(&self, result: &SearchResult) -> f64 {
+        self.pagerank
+            .get(&(result.file_path.clone(), result.start_line))
+            .or_else(|| {
+                self.pagerank_by_name
+                    .get(&(result.file_path.clone(), result.symbol_name.clone()))
+            })
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    fn hotspot_of(&self, result: &SearchResult) -> f64 {
+        self.hotspot.get(&result.file_path).copied().unwrap_or(0.0)
+    }
+}
+
+/// Rerank results by blending their similarity score with analyzer signals.
+///
+/// Each result gets a [`Rerank`] breakdown and results are sorted by the
+/// blended score, highest first. With all weights zero the order is kept.
+pub fn rerank(
+    mut results: Vec<SearchResult>,
+    signals: &RerankSignals,
+    weights: &RerankConfig,
+) -> Vec<SearchResult> {
+    let total = weights.semantic + weights.pagerank + weights.hotspot + weights.complexity;
+    if total <= 0.0 {
+        return results;
+    }
+
+    let max_complexity = results
+        .iter()
+        .filter_map(|r| r.cyclomatic_complexity)
+        .max()
+        .unwrap_or(0);
+    let complexity_of = |r: &SearchResult| match r.cyclomatic_complexity {
+        Some(c) if max_complexity > 0 => (1.0 + c as f64).ln() / (1.0 + max_complexity as f64).ln(),
+        _ => 0.0,
+    };
+
+    for result in &mut results {
+        let pagerank = signals.pagerank_of(result);
+        let hotspot = signals.hotspot_of(result);
+        let complexity = complexity_of(result);
+        let score = (weights.semantic * result.score as f64
+            + weights.pagerank * pagerank
+            + weights.hotspot * hotspot
+            + weights.complexity * complexity)
+            / total;
+        result.rerank = Some(Rerank {
+            score,
+            pagerank,
+            hotspot,
+            complexity,
+        });
+    }
+
+    let blended = |r: &SearchResult| r.rerank.as_ref().map_or(0.0, |x| x.score);
+    results.sort_by(|a, b| blended(b).total_cmp(&blended(a)));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(file: &str, name: &str, line: u32, score: f32, cc: Option<u32>) -> SearchResult {
+        SearchResult {
+            file_path: file.to_string(),
+            symbol_name: name.to_string(),
+            symbol_type: "function".to_string(),
+            signature: format!("fn {}()", name),
+            start_line: line,
+            end_line: line + 10,
+            score,
+            cyclomatic_complexity: cc,
+            cognitive_complexity: None,
+            rerank: None,
+        }
+    }
+
+    fn signals() -> RerankSignals {
+        let mut signals = RerankSignals::default();
+        signals
+            .pagerank
+            .insert(("src/config/mod.rs".to_string(), 40), 1.0);
+        signals
+            .pagerank_by_name
+            .insert(("src/main.rs".to_string(), "run".to_string()), 0.5);
+        signals.hotspot.insert("src/config/mod.rs".to_string(), 0.8);
+        signals
+    }
+
+    #[test]
+    fn test_rerank_promotes_central_symbol_over_test_helper() {
+        let results = vec![
+            result("tests/helpers.rs", "load_config", 3, 0.9, Some(1)),
+            result("src/config/mod.rs", "load", 40, 0.7, Some(8)),
+        ];
+
+        let reranked = rerank(results, &signals(), &RerankConfig::default());
+
+        assert_eq!(reranked[0].file_path, "src/config/mod.rs");
+        let top = reranked[0].rerank.as_ref().unwrap();
+        assert_eq!(top.pagerank, 1.0);
+        assert_eq!(top.hotspot, 0.8);
+        assert_eq!(top.complexity, 1.0);
+        let helper = reranked[1].rerank.as_ref().unwrap();
+        assert_eq!((helper.pagerank, helper.hotspot), (0.0, 0.0));
+        assert!(top.score > helper.score);
+    }
+
+    #[test]
+    fn test_rerank_matches_pagerank_by_name() {
+        let results = vec![result("src/main.rs", "run", 12, 0.5, None)];
+
+        let reranked = rerank(results, &signals(), &RerankConfig::default());
+
+        assert_eq!(reranked[0].rerank.as_ref().unwrap().pagerank, 0.5);
+    }
+
+    #[test]
+    fn test_semantic_only_weights_keep_similarity() {
+        let weights = RerankConfig {
+            semantic: 1.0,
+            pagerank: 0.0,
+            hotspot: 0.0,
+            complexity: 0.0,
+            ..RerankConfig::default()
+        };
+        let results = vec![
+            result("tests/helpers.rs", "load_config", 3, 0.9, None),
+            result("src/config/mod.rs", "load", 40, 0.7, None),
+        ];
+
+        let reranked = rerank(results, &signals(), &weights);
+
+        assert_eq!(reranked[0].file_path, "tests/helpers.rs");
+        let score = reranked[0].rerank.as_ref().unwrap().score;
+        assert!((score - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_zero_weights_keep_order() {
+        let weights = RerankConfig {
+            semantic: 0.0,
+            pagerank: 0.0,
+            hotspot: 0.0,
+            complexity: 0.0,
+            ..RerankConfig::default()
+        };
+        let results = vec![
+            result("a.rs", "a", 1, 0.1, None),
+            result("b.rs", "b", 1, 0.9, None),
+        ];
+
+        let reranked = rerank(results, &signals(), &weights);
+
+        assert_eq!(reranked[0].file_path, "a.rs");
+        assert!(reranked[0].rerank.is_none());
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use crate::core::Result;
 
 use super::cache::EmbeddingCache;
+use super::rerank::Rerank;
 use super::tfidf::{DocMeta, TfidfEngine};
 
 /// A search result with similarity score and optional quality metrics.
@@ -32,6 +33,9 @@ pub struct SearchResult {
     /// Cognitive complexity (if computed during indexing).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cognitive_complexity: Option<u32>,
+    /// Reranked score and its signals (if reranking was applied).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank: Option<Rerank>,
 }
 
 /// Search engine for semantic code search.
@@ -141,6 +145,7 @@ fn to_search_result((meta, score): (DocMeta, f32)) -> SearchResult {
         score,
         cyclomatic_complexity: meta.cyclomatic_complexity,
         cognitive_complexity: meta.cognitive_complexity,
+        rerank: None,
     }
 }
 
@@ -218,6 +223,7 @@ mod tests {
             score: 0.95,
             cyclomatic_complexity: Some(5),
             cognitive_complexity: Some(3),
+            rerank: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -243,6 +249,7 @@ mod tests {
                 score: 0.8,
                 cyclomatic_complexity: None,
                 cognitive_complexity: None,
+                rerank: None,
             }],
         };
 
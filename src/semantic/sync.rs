@@ -11,6 +11,7 @@ use std::sync::Arc;
 use blake3::Hasher;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use rayon::prelude::*;
+use serde::Serialize;
 
 use crate::analyzers::complexity::analyze_function_complexity;
 use crate::core::progress::is_tty;
@@ -65,18 +66,8 @@ impl<'a> SyncManager<'a> {
         // Get current files
         let current_files: HashSet<PathBuf> = file_set.files().iter().cloned().collect();
 
-        // Get indexed files
-        let indexed_files: HashSet<String> =
-            self.cache.get_all_indexed_files()?.into_iter().collect();
-
-        // Find files to remove (deleted or moved)
-        for indexed_path in &indexed_files {
-            let indexed_current_path = indexed_path_for_comparison(indexed_path, root_path);
-            if !current_files.contains(&indexed_current_path) {
-                self.cache.remove_file(indexed_path)?;
-                stats.removed += 1;
-            }
-        }
+        // Remove files that were deleted or are now excluded
+        stats.removed = self.prune(file_set, root_path)?;
 
         // Check each current file for changes
         let files_to_index: Vec<_> = current_files
@@ -220,6 +211,61 @@ impl<'a> SyncManager<'a> {
         Ok(stats)
     }
 
+    /// Find indexed files that are out of date with `file_set`: deleted,
+    /// excluded by the current FileSet rules, or modified since indexing.
+    pub fn stale_files(&self, file_set: &FileSet, root_path: &Path) -> Result<Vec<StaleFile>> {
+        let mut stale = self.missing_files(file_set, root_path)?;
+        for path in file_set.files() {
+            let rel_path = path.to_string_lossy().to_string();
+            let indexed = self.cache.get_file_hash(&rel_path)?.is_some();
+            if indexed
+                && self
+                    .check_file_changed(&root_path.join(path), &rel_path)
+                    .unwrap_or(true)
+            {
+                stale.push(StaleFile {
+                    file_path: rel_path,
+                    reason: StaleReason::Modified,
+                });
+            }
+        }
+        stale.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        Ok(stale)
+    }
+
+    /// Remove deleted and excluded files from the index without re-indexing
+    /// anything. Returns the number of files removed.
+    pub fn prune(&self, file_set: &FileSet, root_path: &Path) -> Result<usize> {
+        let missing = self.missing_files(file_set, root_path)?;
+        for file in &missing {
+            self.cache.remove_file(&file.file_path)?;
+        }
+        Ok(missing.len())
+    }
+
+    /// Indexed files that are no longer part of `file_set`.
+    fn missing_files(&self, file_set: &FileSet, root_path: &Path) -> Result<Vec<StaleFile>> {
+        let current_files: HashSet<&Path> = file_set.files().iter().map(PathBuf::as_path).collect();
+
+        let mut missing = Vec::new();
+        for indexed_path in self.cache.get_all_indexed_files()? {
+            let current_path = indexed_path_for_comparison(&indexed_path, root_path);
+            if current_files.contains(current_path.as_path()) {
+                continue;
+            }
+            let reason = if root_path.join(&current_path).exists() {
+                StaleReason::Excluded
+            } else {
+                StaleReason::Deleted
+            };
+            missing.push(StaleFile {
+                file_path: indexed_path,
+                reason,
+            });
+        }
+        Ok(missing)
+    }
+
     /// Check if a file has changed since last indexing.
     fn check_file_changed(&self, path: &Path, rel_path: &str) -> Result<bool> {
         let current_hash = hash_file(path)?;
@@ -318,6 +364,35 @@ fn indexed_path_for_comparison(indexed_path: &str, root_path: &Path) -> PathBuf
         .unwrap_or_else(|_| path.to_path_buf())
 }
 
+/// An index entry that no longer matches the working tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaleFile {
+    pub file_path: String,
+    pub reason: StaleReason,
+}
+
+/// Why an index entry is stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StaleReason {
+    /// The file no longer exists.
+    Deleted,
+    /// The file exists but is filtered out by .gitignore or config excludes.
+    Excluded,
+    /// The file changed since it was indexed.
+    Modified,
+}
+
+impl std::fmt::Display for StaleReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deleted => write!(f, "deleted"),
+            Self::Excluded => write!(f, "excluded"),
+            Self::Modified => write!(f, "modified"),
+        }
+    }
+}
+
 /// Statistics from a sync operation.
 #[derive(Debug, Default, Clone)]
 pub struct SyncStats {
@@ -471,6 +546,54 @@ mod tests {
         assert_eq!(cache.symbol_count().unwrap(), 1);
     }
 
+    #[test]
+    fn test_stale_files_and_prune() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("gen")).unwrap();
+        std::fs::write(temp.path().join("keep.rs"), "fn keep() {}\n").unwrap();
+        std::fs::write(temp.path().join("edit.rs"), "fn edit() {}\n").unwrap();
+        std::fs::write(temp.path().join("gone.rs"), "fn gone() {}\n").unwrap();
+        std::fs::write(temp.path().join("gen/out.rs"), "fn generated() {}\n").unwrap();
+
+        let cache = EmbeddingCache::in_memory().unwrap();
+        let sync = SyncManager::new(&cache);
+        let config = crate::config::Config::default();
+        sync.sync(
+            &FileSet::from_path(temp.path(), &config).unwrap(),
+            temp.path(),
+        )
+        .unwrap();
+        assert_eq!(cache.get_all_indexed_files().unwrap().len(), 4);
+
+        std::fs::remove_file(temp.path().join("gone.rs")).unwrap();
+        std::fs::write(temp.path().join("edit.rs"), "fn edited() {}\n").unwrap();
+        let config = crate::config::Config {
+            exclude_patterns: vec!["gen/**".to_string()],
+            ..Default::default()
+        };
+        let file_set = FileSet::from_path(temp.path(), &config).unwrap();
+
+        let stale = sync.stale_files(&file_set, temp.path()).unwrap();
+        let reasons: Vec<(&str, StaleReason)> = stale
+            .iter()
+            .map(|f| (f.file_path.as_str(), f.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("edit.rs", StaleReason::Modified),
+                ("gen/out.rs", StaleReason::Excluded),
+                ("gone.rs", StaleReason::Deleted),
+            ]
+        );
+
+        assert_eq!(sync.prune(&file_set, temp.path()).unwrap(), 2);
+        let mut indexed = cache.get_all_indexed_files().unwrap();
+        indexed.sort();
+        assert_eq!(indexed, vec!["edit.rs", "keep.rs"]);
+        assert!(cache.get_symbols_for_file("gen/out.rs").unwrap().is_empty());
+    }
+
     #[test]
     fn test_sync_matches_absolute_indexed_paths_to_relative_file_set() {
         let temp = tempfile::tempdir().unwrap();
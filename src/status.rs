@@ -0,0 +1,333 @@
+//! Repository status: config, caches, search index, and git state.
+//!
+//! Cheap to compute, so it is the first thing to check before picking
+//! tools: history analyzers need git, semantic search needs an index.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::context::{summarize_languages, LanguageSummary};
+use crate::core::{FileSet, Result};
+use crate::git::GitRepo;
+use crate::semantic::{SearchConfig, SemanticSearch};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Status {
+    pub repository: String,
+    pub version: String,
+    pub config: ConfigStatus,
+    pub cache: CacheStatus,
+    pub search_index: IndexStatus,
+    pub git: GitStatus,
+    pub file_count: usize,
+    pub languages: Vec<LanguageSummary>,
+    pub hints: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigStatus {
+    /// Config files in effect, in merge order; empty means built-in defaults.
+    pub files: Vec<String>,
+    pub profile: Option<String>,
+    pub skip: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStatus {
+    pub path: String,
+    pub exists: bool,
+    pub size_bytes: u64,
+    /// Persisted blame results under `blame/`.
+    pub blame_entries: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexStatus {
+    pub path: String,
+    pub exists: bool,
+    pub size_bytes: u64,
+    pub files: usize,
+    pub symbols: usize,
+    /// Indexed files that were deleted, excluded, or modified since indexing.
+    pub stale_files: usize,
+    pub fresh: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitStatus {
+    pub available: bool,
+    pub root: Option<String>,
+    pub branch: Option<String>,
+    pub head: Option<String>,
+    pub bare: bool,
+    pub shallow: bool,
+}
+
+/// Config files that [`Config::load_default`] reads from `dir`, in merge order.
+pub fn discover_config_files(dir: &Path) -> Vec<PathBuf> {
+    ["omen.toml", ".omen/omen.toml"]
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Build the status report for the repository at `root`.
+///
+/// `config_file` is the explicit `--config` path, if any; otherwise the
+/// files discovered in `root` are reported.
+pub fn build_status(
+    root: &Path,
+    files: &FileSet,
+    config: &Config,
+    config_file: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<Status> {
+    let config_files = match config_file {
+        Some(path) => vec![path.to_path_buf()],
+        None => discover_config_files(root),
+    };
+
+    let cache_dir = root.join(".omen/cache");
+    let cache = CacheStatus {
+        path: display(&cache_dir),
+        exists: cache_dir.is_dir(),
+        size_bytes: dir_size(&cache_dir),
+        blame_entries: std::fs::read_dir(cache_dir.join("blame")).map_or(0, |d| d.count()),
+    };
+
+    let search_index = index_status(root, config)?;
+    let git = git_status(root);
+    let languages = summarize_languages(files);
+
+    let mut status = Status {
+        repository: display(root),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        config: ConfigStatus {
+            files: config_files.iter().map(|p| display(p)).collect(),
+            profile: profile.map(str::to_string),
+            skip: config.skip.clone(),
+        },
+        cache,
+        search_index,
+        git,
+        file_count: files.len(),
+        languages,
+        hints: Vec::new(),
+    };
+    status.hints = build_hints(&status);
+    Ok(status)
+}
+
+fn index_status(root: &Path, config: &Config) -> Result<IndexStatus> {
+    let path = root.join(".omen").join("search.db");
+    if !path.is_file() {
+        return Ok(IndexStatus {
+            path: display(&path),
+            exists: false,
+            size_bytes: 0,
+            files: 0,
+            symbols: 0,
+            stale_files: 0,
+            fresh: false,
+        });
+    }
+
+    let stats = SemanticSearch::new(&SearchConfig::default(), root)?.stats(config)?;
+    Ok(IndexStatus {
+        path: display(&path),
+        exists: true,
+        size_bytes: stats.size_bytes,
+        files: stats.files,
+        symbols: stats.symbols,
+        stale_files: stats.stale.len(),
+        fresh: stats.stale.is_empty(),
+    })
+}
+
+fn git_status(root: &Path) -> GitStatus {
+    match GitRepo::open(root) {
+        Ok(repo) => GitStatus {
+            available: true,
+            root: Some(display(repo.root())),
+            branch: repo.current_branch().ok(),
+            head: repo.head_sha().ok(),
+            bare: repo.is_bare(),
+            shallow: repo.is_shallow(),
+        },
+        Err(_) => GitStatus {
+            available: false,
+            root: None,
+            branch: None,
+            head: None,
+            bare: false,
+            shallow: false,
+        },
+    }
+}
+
+fn build_hints(status: &Status) -> Vec<String> {
+    let mut hints = Vec::new();
+    if !status.git.available {
+        hints.push(
+            "No git repository: churn, hotspot, ownership, temporal, changes and defect will fail."
+                .to_string(),
+        );
+    } else if status.git.shallow {
+        hints.push(
+            "Shallow clone: history-based analyzers only see the fetched commits.".to_string(),
+        );
+    }
+    if !status.search_index.exists {
+        hints.push("No search index: run `omen search index` before semantic_search.".to_string());
+    } else if !status.search_index.fresh {
+        hints.push(format!(
+            "Search index has {} stale file(s): run `omen search index` to refresh.",
+            status.search_index.stale_files
+        ));
+    }
+    if status.file_count == 0 {
+        hints.push("No supported source files found; check exclude patterns.".to_string());
+    }
+    hints
+}
+
+/// Total size of regular files under `dir`, or 0 if it does not exist.
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(ft) if ft.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |m| m.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+impl Status {
+    /// Render a compact markdown summary.
+    pub fn render_markdown(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str(&format!("# Status: {}\n\n", self.repository));
+        out.push_str(&format!("**omen**: {}\n\n", self.version));
+
+        let config = if self.config.files.is_empty() {
+            "defaults".to_string()
+        } else {
+            self.config.files.join(", ")
+        };
+        out.push_str(&format!("**Config**: {config}"));
+        if let Some(ref profile) = self.config.profile {
+            out.push_str(&format!(" (profile `{profile}`)"));
+        }
+        out.push_str("\n\n");
+
+        let git = if !self.git.available {
+            "unavailable".to_string()
+        } else {
+            let mut parts = vec![self.git.branch.clone().unwrap_or_else(|| "detached".into())];
+            if let Some(ref head) = self.git.head {
+                parts.push(head.chars().take(8).collect());
+            }
+            if self.git.bare {
+                parts.push("bare".to_string());
+            }
+            if self.git.shallow {
+                parts.push("shallow".to_string());
+            }
+            parts.join(", ")
+        };
+        out.push_str(&format!("**Git**: {git}\n\n"));
+
+        out.push_str(&format!(
+            "**Cache**: {} ({} bytes, {} blame entries)\n\n",
+            if self.cache.exists {
+                "present"
+            } else {
+                "absent"
+            },
+            self.cache.size_bytes,
+            self.cache.blame_entries
+        ));
+
+        let index = if !self.search_index.exists {
+            "absent".to_string()
+        } else {
+            format!(
+                "{} files, {} symbols, {} stale",
+                self.search_index.files, self.search_index.symbols, self.search_index.stale_files
+            )
+        };
+        out.push_str(&format!("**Search index**: {index}\n\n"));
+
+        let langs: Vec<String> = self
+            .languages
+            .iter()
+            .map(|l| format!("{} ({})", l.language, l.files))
+            .collect();
+        out.push_str(&format!(
+            "**Files**: {} — {}\n\n",
+            self.file_count,
+            langs.join(", ")
+        ));
+
+        if !self.hints.is_empty() {
+            out.push_str("## Hints\n\n");
+            for hint in &self.hints {
+                out.push_str(&format!("- {hint}\n"));
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_outside_git_without_index() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("main.py"), "def main():\n    pass\n").unwrap();
+        std::fs::write(temp.path().join("omen.toml"), "skip = [\"churn\"]\n").unwrap();
+
+        let config = Config::load_default(temp.path()).unwrap();
+        let files = FileSet::from_path(temp.path(), &config).unwrap();
+        let status = build_status(temp.path(), &files, &config, None, None).unwrap();
+
+        assert_eq!(status.config.files.len(), 1);
+        assert!(status.config.files[0].ends_with("omen.toml"));
+        assert_eq!(status.config.skip, vec!["churn"]);
+        assert!(!status.git.available);
+        assert!(!status.search_index.exists);
+        assert!(!status.cache.exists);
+        assert_eq!(status.file_count, 1);
+        assert_eq!(status.languages[0].language, "Python");
+        assert!(status.hints.iter().any(|h| h.contains("No git repository")));
+        assert!(status.hints.iter().any(|h| h.contains("omen search index")));
+        // Reporting status must not create the index as a side effect.
+        assert!(!temp.path().join(".omen/search.db").exists());
+    }
+
+    #[test]
+    fn test_dir_size_sums_nested_files() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("blame")).unwrap();
+        std::fs::write(temp.path().join("a"), [0u8; 10]).unwrap();
+        std::fs::write(temp.path().join("blame/b.json"), [0u8; 5]).unwrap();
+
+        assert_eq!(dir_size(temp.path()), 15);
+        assert_eq!(dir_size(&temp.path().join("missing")), 0);
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers shared by the unit tests.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run `git` with `args` in `dir`, failing the test if it fails.
+pub(crate) fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Create a repository in `dir` that commits as `Test <test@example.com>`.
+pub(crate) fn init_repo(dir: &Path) {
+    git(dir, &["init", "-q"]);
+    git(dir, &["config", "user.name", "Test"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+}
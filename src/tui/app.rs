@@ -0,0 +1,316 @@
+//! Dashboard state and key handling, independent of the terminal.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Rows moved by page keys before the first frame reports the real height.
+const DEFAULT_PAGE: usize = 20;
+
+/// A dashboard tab, one per analyzer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Score,
+    Hotspots,
+    Complexity,
+    Satd,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 4] = [Tab::Score, Tab::Hotspots, Tab::Complexity, Tab::Satd];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Score => "Score",
+            Tab::Hotspots => "Hotspots",
+            Tab::Complexity => "Complexity",
+            Tab::Satd => "SATD",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// One row of a tab's table.
+#[derive(Debug, Clone, Default)]
+pub struct Entry {
+    pub cells: Vec<String>,
+    /// File the row points at, relative to the root.
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// Results of a tab's analyzer.
+#[derive(Debug, Clone, Default)]
+pub struct Listing {
+    /// One-line summary shown above the table.
+    pub summary: String,
+    pub header: Vec<&'static str>,
+    pub entries: Vec<Entry>,
+}
+
+/// A file opened from a row.
+#[derive(Debug, Clone)]
+pub struct FileView {
+    pub path: String,
+    pub lines: Vec<String>,
+    /// Line the row points at (0-indexed).
+    pub focus: Option<usize>,
+    /// First line on screen (0-indexed).
+    pub scroll: usize,
+}
+
+/// What the event loop should do after a key press.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Quit,
+    /// Read `file` and show it at `line`.
+    Open {
+        file: String,
+        line: Option<u32>,
+    },
+}
+
+/// Dashboard state.
+pub struct App {
+    pub tab: Tab,
+    /// Results per tab; `None` until analyzed, and again after `r`.
+    listings: [Option<Result<Listing, String>>; 4],
+    /// Selected row per tab.
+    selected: [usize; 4],
+    pub file: Option<FileView>,
+    /// Shown in the footer until the next key press.
+    pub message: Option<String>,
+    /// Rows that fit in the body, set by the renderer.
+    pub page: usize,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self {
+            tab: Tab::Score,
+            listings: Default::default(),
+            selected: [0; 4],
+            file: None,
+            message: None,
+            page: DEFAULT_PAGE,
+        }
+    }
+
+    /// Results of the current tab, if analyzed.
+    pub fn listing(&self) -> Option<&Result<Listing, String>> {
+        self.listings[self.tab.index()].as_ref()
+    }
+
+    /// Selected row of the current tab.
+    pub fn selected(&self) -> usize {
+        self.selected[self.tab.index()]
+    }
+
+    /// The current tab when it still needs analyzing.
+    pub fn pending(&self) -> Option<Tab> {
+        self.listing().is_none().then_some(self.tab)
+    }
+
+    pub fn set_listing(&mut self, tab: Tab, listing: Result<Listing, String>) {
+        let rows = listing.as_ref().map_or(0, |l| l.entries.len());
+        let selected = &mut self.selected[tab.index()];
+        *selected = (*selected).min(rows.saturating_sub(1));
+        self.listings[tab.index()] = Some(listing);
+    }
+
+    /// Show `text` as `path`, centred on `line` (1-indexed).
+    pub fn open(&mut self, path: String, text: &str, line: Option<u32>) {
+        let focus = line.map(|l| (l as usize).saturating_sub(1));
+        self.file = Some(FileView {
+            path,
+            lines: text.lines().map(str::to_string).collect(),
+            focus,
+            scroll: focus.map_or(0, |f| f.saturating_sub(self.page / 2)),
+        });
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        if key.code == KeyCode::Char('q')
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+        {
+            return Action::Quit;
+        }
+        if self.file.is_some() {
+            self.handle_file_key(key);
+            return Action::None;
+        }
+
+        match key.code {
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.switch(1),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                self.switch(Tab::ALL.len() - 1)
+            }
+            KeyCode::Char(c @ '1'..='4') => {
+                self.tab = Tab::ALL[c as usize - '1' as usize];
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.move_selection(self.page as isize),
+            KeyCode::PageUp => self.move_selection(-(self.page as isize)),
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX),
+            KeyCode::Char('r') => self.listings[self.tab.index()] = None,
+            KeyCode::Enter => return self.open_selected(),
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn handle_file_key(&mut self, key: KeyEvent) {
+        if matches!(
+            key.code,
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h')
+        ) {
+            self.file = None;
+            return;
+        }
+        let page = self.page;
+        let Some(view) = self.file.as_mut() else {
+            return;
+        };
+        let last = view.lines.len().saturating_sub(1);
+        view.scroll = match key.code {
+            KeyCode::Down | KeyCode::Char('j') => view.scroll + 1,
+            KeyCode::Up | KeyCode::Char('k') => view.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ') => view.scroll + page,
+            KeyCode::PageUp => view.scroll.saturating_sub(page),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            _ => view.scroll,
+        }
+        .min(last);
+    }
+
+    fn switch(&mut self, step: usize) {
+        self.tab = Tab::ALL[(self.tab.index() + step) % Tab::ALL.len()];
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let rows = match self.listing() {
+            Some(Ok(listing)) => listing.entries.len(),
+            _ => return,
+        };
+        let selected = &mut self.selected[self.tab.index()];
+        *selected = selected
+            .saturating_add_signed(delta)
+            .min(rows.saturating_sub(1));
+    }
+
+    fn open_selected(&self) -> Action {
+        let Some(Ok(listing)) = self.listing() else {
+            return Action::None;
+        };
+        match listing.entries.get(self.selected()) {
+            Some(Entry {
+                file: Some(file),
+                line,
+                ..
+            }) => Action::Open {
+                file: file.clone(),
+                line: *line,
+            },
+            _ => Action::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn listing(rows: usize) -> Listing {
+        Listing {
+            summary: String::new(),
+            header: vec!["Location"],
+            entries: (0..rows)
+                .map(|i| Entry {
+                    cells: vec![format!("src/f{i}.rs")],
+                    file: Some(format!("src/f{i}.rs")),
+                    line: Some(i as u32 + 1),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_tabs_load_on_first_visit_and_on_reload() {
+        let mut app = App::new();
+        assert_eq!(app.pending(), Some(Tab::Score));
+        app.set_listing(Tab::Score, Ok(listing(1)));
+        assert_eq!(app.pending(), None);
+
+        app.handle_key(key(KeyCode::BackTab));
+        assert_eq!(app.tab, Tab::Satd, "tabs wrap around");
+        app.handle_key(key(KeyCode::Char('1')));
+        assert_eq!(app.tab, Tab::Score);
+        app.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(app.pending(), Some(Tab::Score));
+    }
+
+    #[test]
+    fn test_selection_stays_in_range_and_opens_files() {
+        let mut app = App::new();
+        app.tab = Tab::Complexity;
+        app.set_listing(Tab::Complexity, Ok(listing(3)));
+
+        app.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(app.selected(), 1);
+        app.handle_key(key(KeyCode::PageDown));
+        assert_eq!(app.selected(), 2);
+        app.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(app.selected(), 0);
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.selected(), 0);
+
+        app.handle_key(key(KeyCode::End));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Action::Open {
+                file: "src/f2.rs".to_string(),
+                line: Some(3),
+            }
+        );
+
+        // A re-analysis with fewer rows pulls the selection back in.
+        app.set_listing(Tab::Complexity, Ok(listing(1)));
+        assert_eq!(app.selected(), 0);
+    }
+
+    #[test]
+    fn test_file_view_scrolls_and_closes() {
+        let mut app = App::new();
+        app.page = 4;
+        let text: String = (1..=30).map(|i| format!("line {i}\n")).collect();
+        app.open("src/main.rs".to_string(), &text, Some(20));
+
+        let view = app.file.as_ref().unwrap();
+        assert_eq!(view.focus, Some(19));
+        assert_eq!(view.scroll, 17, "the focused line is centred");
+
+        app.handle_key(key(KeyCode::Char('G')));
+        assert_eq!(app.file.as_ref().unwrap().scroll, 29);
+        app.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(app.file.as_ref().unwrap().scroll, 29);
+        app.handle_key(key(KeyCode::Esc));
+        assert!(app.file.is_none());
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+    }
+}
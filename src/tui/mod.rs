@@ -0,0 +1,232 @@
+//! Interactive terminal dashboard (`omen tui`).
+//!
+//! Tabs list the score components, hotspots, the most complex functions and
+//! SATD items. Each tab's analyzer runs the first time the tab is shown and
+//! again on `r`, so a fix can be checked without leaving the dashboard.
+//! Enter opens the selected row's file at its line.
+
+mod app;
+mod view;
+
+use std::path::Path;
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+use crate::analyzers::{complexity, hotspot, satd};
+use crate::core::{AnalysisContext, Analyzer, Result};
+
+pub use app::{Action, App, Entry, Listing, Tab};
+
+/// Run the dashboard until the user quits.
+pub fn run(ctx: &AnalysisContext<'_>) -> Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, ctx);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, ctx: &AnalysisContext<'_>) -> Result<()> {
+    let mut app = App::new();
+    loop {
+        if let Some(tab) = app.pending() {
+            // Show "Analyzing..." while the analyzer runs.
+            terminal.draw(|frame| view::draw(frame, &mut app))?;
+            app.set_listing(tab, load(tab, ctx).map_err(|e| e.to_string()));
+        }
+        terminal.draw(|frame| view::draw(frame, &mut app))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match app.handle_key(key) {
+            Action::Quit => return Ok(()),
+            Action::Open { file, line } => match ctx.read_file(Path::new(&file)) {
+                Ok(bytes) => app.open(file, &String::from_utf8_lossy(&bytes), line),
+                Err(e) => app.message = Some(format!("Cannot open {file}: {e}")),
+            },
+            Action::None => {}
+        }
+    }
+}
+
+/// Run the analyzer behind `tab` and tabulate its results.
+pub fn load(tab: Tab, ctx: &AnalysisContext<'_>) -> Result<Listing> {
+    let relative = |file: &str| {
+        let path = Path::new(file);
+        path.strip_prefix(ctx.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let listing = match tab {
+        Tab::Score => {
+            let analysis = crate::score::Analyzer::default().analyze(ctx)?;
+            let mut components: Vec<_> = analysis.components.into_iter().collect();
+            // Weakest first: that is where the points are.
+            components.sort_by(|a, b| a.1.score.total_cmp(&b.1.score).then(a.0.cmp(&b.0)));
+            Listing {
+                summary: format!(
+                    "Score {:.1} ({}) across {} files",
+                    analysis.overall_score, analysis.grade, analysis.summary.files_analyzed
+                ),
+                header: vec!["Component", "Score", "Weight", "Details"],
+                entries: components
+                    .into_iter()
+                    .map(|(name, component)| Entry {
+                        cells: vec![
+                            name,
+                            format!("{:.1}", component.score),
+                            format!("{:.2}", component.weight),
+                            component.details,
+                        ],
+                        ..Default::default()
+                    })
+                    .collect(),
+            }
+        }
+        Tab::Hotspots => {
+            let analysis = hotspot::Analyzer::default().analyze(ctx)?;
+            Listing {
+                summary: format!(
+                    "{} hotspots, {} critical, {} high",
+                    analysis.summary.total_hotspots,
+                    analysis.summary.critical_count,
+                    analysis.summary.high_count
+                ),
+                header: vec!["Score", "Severity", "Commits", "Avg complexity", "File"],
+                entries: analysis
+                    .hotspots
+                    .into_iter()
+                    .map(|hotspot| {
+                        let file = relative(&hotspot.file);
+                        Entry {
+                            cells: vec![
+                                format!("{:.2}", hotspot.score),
+                                hotspot.severity.to_string(),
+                                hotspot.commits.to_string(),
+                                format!("{:.1}", hotspot.avg_complexity),
+                                file.clone(),
+                            ],
+                            file: Some(file),
+                            line: None,
+                        }
+                    })
+                    .collect(),
+            }
+        }
+        Tab::Complexity => {
+            let analysis = complexity::Analyzer::default().analyze(ctx)?;
+            let mut functions: Vec<_> = analysis
+                .files
+                .into_iter()
+                .flat_map(|file| file.functions)
+                .collect();
+            functions.sort_by(|a, b| {
+                b.metrics
+                    .cyclomatic
+                    .cmp(&a.metrics.cyclomatic)
+                    .then(b.metrics.cognitive.cmp(&a.metrics.cognitive))
+            });
+            Listing {
+                summary: format!(
+                    "{} functions, average cyclomatic {:.1}, p90 {}",
+                    analysis.summary.total_functions,
+                    analysis.summary.avg_cyclomatic,
+                    analysis.summary.p90_cyclomatic
+                ),
+                header: vec!["Cyclomatic", "Cognitive", "Nesting", "Function", "Location"],
+                entries: functions
+                    .into_iter()
+                    .map(|function| {
+                        let file = relative(&function.file);
+                        Entry {
+                            cells: vec![
+                                function.metrics.cyclomatic.to_string(),
+                                function.metrics.cognitive.to_string(),
+                                function.metrics.max_nesting.to_string(),
+                                function.name,
+                                format!("{file}:{}", function.start_line),
+                            ],
+                            file: Some(file),
+                            line: Some(function.start_line),
+                        }
+                    })
+                    .collect(),
+            }
+        }
+        Tab::Satd => {
+            let analysis = satd::Analyzer::default().analyze(ctx)?;
+            Listing {
+                summary: format!(
+                    "{} items ({} unique), {:.1} per 1K lines",
+                    analysis.summary.total_items,
+                    analysis.summary.unique_items,
+                    analysis.summary.density
+                ),
+                header: vec!["Severity", "Marker", "Location", "Text"],
+                entries: analysis
+                    .items
+                    .into_iter()
+                    .map(|item| {
+                        let file = relative(&item.file);
+                        Entry {
+                            cells: vec![
+                                crate::core::Severity::from(item.severity).to_string(),
+                                item.marker,
+                                format!("{file}:{}", item.line),
+                                item.text,
+                            ],
+                            file: Some(file),
+                            line: Some(item.line),
+                        }
+                    })
+                    .collect(),
+            }
+        }
+    };
+    Ok(listing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::core::FileSet;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_points_rows_at_files() {
+        let dir = TempDir::new().unwrap();
+        let source = [
+            "def simple():",
+            "    return 1",
+            "",
+            "",
+            "def branchy(x):",
+            "    # TODO: split this up",
+            "    if x:",
+            "        return 1",
+            "    return 2",
+        ];
+        std::fs::write(dir.path().join("lib.py"), source.join("\n")).unwrap();
+        let config = Config::default();
+        let files = FileSet::from_path(dir.path(), &config).unwrap();
+        let ctx = AnalysisContext::new(&files, &config, Some(dir.path()));
+
+        let complexity = load(Tab::Complexity, &ctx).unwrap();
+        let top = &complexity.entries[0];
+        assert_eq!(top.cells[3], "branchy");
+        assert_eq!(top.cells[4], "lib.py:5");
+        assert_eq!((top.file.as_deref(), top.line), (Some("lib.py"), Some(5)));
+
+        let satd = load(Tab::Satd, &ctx).unwrap();
+        assert_eq!(satd.entries.len(), 1);
+        assert_eq!(satd.entries[0].line, Some(6));
+        assert_eq!(satd.header.len(), satd.entries[0].cells.len());
+    }
+}
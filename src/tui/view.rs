@@ -0,0 +1,107 @@
+//! Rendering of the dashboard.
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState, Tabs};
+use ratatui::Frame;
+
+use super::app::{App, FileView, Tab};
+
+const TABLE_KEYS: &str = "←/→ tab  ↑/↓ select  enter open  r re-analyze  q quit";
+const FILE_KEYS: &str = "↑/↓ scroll  pgup/pgdn page  esc back  q quit";
+
+pub fn draw(frame: &mut Frame<'_>, app: &mut App) {
+    let [tabs, summary, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let titles = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| format!("{} {}", i + 1, tab.title()));
+    frame.render_widget(
+        Tabs::new(titles)
+            .select(app.tab.index())
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+        tabs,
+    );
+
+    let status = match app.listing() {
+        None => format!("Analyzing {}...", app.tab.title().to_lowercase()),
+        Some(Ok(listing)) => listing.summary.clone(),
+        Some(Err(e)) => format!("{} failed: {e}", app.tab.title()),
+    };
+    frame.render_widget(Paragraph::new(status).bold(), summary);
+
+    // Borders take two rows, the table header one more.
+    app.page = usize::from(body.height.saturating_sub(3)).max(1);
+    match app.file {
+        Some(ref view) => draw_file(frame, view, body),
+        None => draw_table(frame, app, body),
+    }
+
+    let help = match (&app.message, &app.file) {
+        (Some(message), _) => message.as_str(),
+        (None, Some(_)) => FILE_KEYS,
+        (None, None) => TABLE_KEYS,
+    };
+    frame.render_widget(Paragraph::new(help).dim(), footer);
+}
+
+fn draw_table(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let block = Block::bordered().title(app.tab.title());
+    let Some(Ok(listing)) = app.listing() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let rows = listing
+        .entries
+        .iter()
+        .map(|entry| Row::new(entry.cells.iter().map(String::as_str)));
+    let table = Table::new(rows, widths(app.tab))
+        .header(Row::new(listing.header.iter().copied()).bold())
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(block);
+    let mut state = TableState::default().with_selected(Some(app.selected()));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_file(frame: &mut Frame<'_>, view: &FileView, area: Rect) {
+    let height = usize::from(area.height.saturating_sub(2));
+    let number_width = view.lines.len().to_string().len();
+    let lines: Vec<Line<'_>> = view
+        .lines
+        .iter()
+        .enumerate()
+        .skip(view.scroll)
+        .take(height)
+        .map(|(i, text)| {
+            let number = Span::raw(format!("{:>number_width$} ", i + 1)).dim();
+            let line = Line::from(vec![number, Span::raw(text.as_str())]);
+            if view.focus == Some(i) {
+                line.reversed()
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(view.path.as_str())),
+        area,
+    );
+}
+
+fn widths(tab: Tab) -> Vec<Constraint> {
+    use Constraint::{Fill, Length};
+    match tab {
+        Tab::Score => vec![Length(14), Length(7), Length(7), Fill(1)],
+        Tab::Hotspots => vec![Length(7), Length(9), Length(8), Length(15), Fill(1)],
+        Tab::Complexity => vec![Length(11), Length(10), Length(8), Fill(1), Fill(1)],
+        Tab::Satd => vec![Length(9), Length(8), Fill(1), Fill(2)],
+    }
+}
@@ -24,6 +24,18 @@ fn test_help_output() {
         .stdout(predicate::str::contains("code analysis"));
 }
 
+#[test]
+fn test_completions_ignore_a_broken_config() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("omen.toml"), "[complexity\n").unwrap();
+    omen()
+        .current_dir(dir.path())
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("omen"));
+}
+
 #[test]
 fn test_complexity_runs_successfully() {
     omen()
@@ -581,6 +593,70 @@ fn test_all_json_has_analyzers_array() {
     }
 }
 
+#[test]
+fn test_all_stream_emits_one_json_line_per_analyzer() {
+    let output = omen()
+        .args(["-p", fixtures_dir(), "all", "--stream", "--timeout", "300"])
+        .output()
+        .expect("command runs");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let names: Vec<String> = stdout
+        .lines()
+        .map(|line| {
+            let entry: serde_json::Value =
+                serde_json::from_str(line).expect("each line should be valid JSON");
+            entry["analyzer"]
+                .as_str()
+                .expect("each entry needs an analyzer name")
+                .to_string()
+        })
+        .collect();
+
+    assert!(names.contains(&"complexity".to_string()));
+    assert!(names.contains(&"score".to_string()));
+    // Score runs last, after the file and git analyzers it builds on
+    assert_eq!(names.last().map(String::as_str), Some("score"));
+}
+
+#[test]
+fn test_all_estimate_covers_every_analyzer() {
+    let output = omen()
+        .args(["-p", fixtures_dir(), "-f", "json", "all", "--estimate"])
+        .output()
+        .expect("command runs");
+    assert!(output.status.success());
+    let estimate: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("estimate should be valid JSON");
+    let mut estimated: Vec<String> = estimate["analyzers"]
+        .as_array()
+        .expect("analyzers should be an array")
+        .iter()
+        .map(|a| a["name"].as_str().expect("name").to_string())
+        .collect();
+    assert!(estimate["seconds"].as_f64().expect("seconds") > 0.0);
+
+    let output = omen()
+        .args(["-p", fixtures_dir(), "all", "--stream"])
+        .output()
+        .expect("command runs");
+    let mut ran: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            let entry: serde_json::Value = serde_json::from_str(line).expect("valid JSON");
+            entry["analyzer"].as_str().expect("analyzer").to_string()
+        })
+        .collect();
+
+    estimated.sort();
+    ran.sort();
+    assert_eq!(
+        estimated, ran,
+        "the estimate plan must match what `all` runs"
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Output format consistency
 // ---------------------------------------------------------------------------